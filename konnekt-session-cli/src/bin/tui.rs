@@ -1,12 +1,20 @@
 use clap::{Parser, Subcommand};
-use konnekt_session_cli::infrastructure::LogConfig;
-use konnekt_session_cli::presentation::tui::{self, App, AppEvent, UserAction};
+use konnekt_session_cli::infrastructure::{
+    BotBehavior, BotSwarm, JoinTarget, LocalConfig, LocalSession, LogConfig, tail_log,
+};
+use konnekt_session_cli::presentation::tui::app::{ConfirmKeys, LogEntry, Severity};
+use konnekt_session_cli::presentation::tui::{self, App, AppEvent, Theme, UserAction};
 use konnekt_session_cli::{CliError, Result};
-use konnekt_session_core::DomainCommand;
-use konnekt_session_core::domain::{ActivityConfig, ActivityResult};
-use konnekt_session_p2p::{IceServer, P2PLoopBuilder, SessionId, SessionLoop};
-use std::time::Duration;
+use konnekt_session_core::domain::{ActivityConfig, ActivityResult, ActivityRun, RunStatus};
+use konnekt_session_core::{DomainCommand, LobbySettings, PluginRegistry};
+use konnekt_session_p2p::{
+    HostTakeover, IceServer, NetworkConnection, P2PLoopBuilder, PeerStats, Presence, SessionEvent,
+    SessionId, SessionLoop,
+};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
 use tracing::{info, instrument};
 use uuid::Uuid;
 
@@ -19,6 +27,83 @@ use uuid::Uuid;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Key confirming a kick, a cancel or quitting as host
+    #[arg(long, global = true, default_value_t = 'y')]
+    confirm_yes_key: char,
+
+    /// Key backing out of a confirmation (Esc always works)
+    #[arg(long, global = true, default_value_t = 'n')]
+    confirm_no_key: char,
+
+    /// Heartbeat interval in milliseconds, for peer round trips in the
+    /// Session tab (0 disables; the host's setting applies to guests)
+    #[arg(long, global = true, default_value_t = 1000)]
+    heartbeat_ms: u64,
+
+    /// Open the Session tab on the join QR code (toggle with `r`)
+    #[arg(long, global = true)]
+    qr: bool,
+
+    /// Web app to join through; the QR code links here with the session ID
+    #[arg(long, global = true, value_name = "URL")]
+    join_url: Option<String>,
+
+    /// Colours: dark, light, high-contrast, colorblind, or a JSON theme file
+    #[arg(long, global = true, value_name = "THEME", default_value = "dark")]
+    theme: String,
+
+    /// Write structured JSON logs to this file (rotated at 10 MiB, 5 kept)
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+
+    /// Log lines F3 dumps for a bug report
+    #[arg(long, global = true, default_value_t = 200)]
+    log_dump_lines: usize,
+}
+
+impl Cli {
+    fn confirm_keys(&self) -> Result<ConfirmKeys> {
+        if self
+            .confirm_yes_key
+            .to_lowercase()
+            .eq(self.confirm_no_key.to_lowercase())
+        {
+            return Err(CliError::InvalidConfig(
+                "--confirm-yes-key and --confirm-no-key must differ".to_string(),
+            ));
+        }
+        Ok(ConfirmKeys::new(self.confirm_yes_key, self.confirm_no_key))
+    }
+
+    fn heartbeat_interval(&self) -> Option<Duration> {
+        (self.heartbeat_ms > 0).then(|| Duration::from_millis(self.heartbeat_ms))
+    }
+
+    fn log_dump(&self) -> Option<LogDump> {
+        self.log_file.clone().map(|log_file| LogDump {
+            log_file,
+            lines: self.log_dump_lines,
+        })
+    }
+}
+
+/// The log file F3 reads and how many of its last lines it dumps
+#[derive(Clone)]
+struct LogDump {
+    log_file: PathBuf,
+    lines: usize,
+}
+
+/// Presentation settings shared by both subcommands
+#[derive(Clone)]
+struct TuiOptions {
+    export_on_exit: Option<PathBuf>,
+    confirm_keys: ConfirmKeys,
+    join_url: Option<String>,
+    show_qr: bool,
+    theme: Theme,
+    log_dump: Option<LogDump>,
 }
 
 #[derive(Subcommand)]
@@ -34,6 +119,11 @@ enum Commands {
         turn_username: Option<String>,
         #[arg(long)]
         turn_credential: Option<String>,
+        #[arg(long)]
+        turn_secret: Option<String>,
+        /// Export results (CSV + JSON) to this path when quitting
+        #[arg(long, value_name = "PATH")]
+        export_on_exit: Option<PathBuf>,
     },
     Join {
         #[arg(short = 's', long, default_value = "wss://match.konnektoren.help")]
@@ -48,9 +138,53 @@ enum Commands {
         turn_username: Option<String>,
         #[arg(long)]
         turn_credential: Option<String>,
+        #[arg(long)]
+        turn_secret: Option<String>,
+        /// Export results (CSV + JSON) to this path when quitting
+        #[arg(long, value_name = "PATH")]
+        export_on_exit: Option<PathBuf>,
+    },
+    /// Host in-process with simulated guests (no signalling server needed)
+    Local {
+        /// Signalling server for sessions joined from inside the TUI
+        #[arg(short = 's', long, default_value = "wss://match.konnektoren.help")]
+        server: String,
+        #[arg(short = 'n', long, default_value = "Host")]
+        name: String,
+        /// Number of simulated guests
+        #[arg(short = 'g', long, default_value_t = 3)]
+        guests: usize,
+        /// What the simulated guests do
+        #[arg(long, value_enum, default_value_t = BotBehavior::Submit)]
+        behavior: BotBehavior,
+        /// Seed of the guests' decisions
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Milliseconds between guest actions
+        #[arg(long, default_value_t = 1000)]
+        action_interval_ms: u64,
+        /// Export results (CSV + JSON) to this path when quitting
+        #[arg(long, value_name = "PATH")]
+        export_on_exit: Option<PathBuf>,
     },
 }
 
+/// Simulated guests playing along in `local` mode
+struct LocalGuests<C: NetworkConnection> {
+    swarm: BotSwarm<C>,
+    /// Time between guest actions
+    interval: Duration,
+}
+
+/// Where the export key writes when `--export-on-exit` is not given
+const DEFAULT_EXPORT_PATH: &str = "konnekt-results";
+
+/// How often the Session tab's peer stats refresh
+const PEER_STATS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long an elected guest has to answer before taking over as host
+const TAKEOVER_ANSWER_TIMEOUT: Duration = Duration::from_secs(30);
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logging (TUI mode - silent)
@@ -65,20 +199,51 @@ async fn main() -> Result<()> {
     #[cfg(not(feature = "console"))]
     let log_config = LogConfig::tui();
 
+    let cli = Cli::parse();
+
+    // The TUI owns the terminal, so logs only ever go to the file
+    let log_config = match &cli.log_file {
+        Some(path) => log_config.with_file_output(path.display().to_string()),
+        None => log_config,
+    };
     log_config.init().map_err(|e| CliError::InvalidInput(e))?;
 
-    let cli = Cli::parse();
+    let log_dump = cli.log_dump();
+    let confirm_keys = cli.confirm_keys()?;
+    let heartbeat_interval = cli.heartbeat_interval();
+    let (join_url, show_qr) = (cli.join_url, cli.qr);
+    let theme = Theme::load(&cli.theme)?;
 
-    match cli.command {
+    // Either returns the session picked in the join prompt, if any
+    let (mut server, name, ice_servers, options, mut next) = match cli.command {
         Commands::CreateHost {
             server,
             name,
             turn_server,
             turn_username,
             turn_credential,
+            turn_secret,
+            export_on_exit,
         } => {
-            let ice_servers = build_ice_servers(turn_server, turn_username, turn_credential)?;
-            create_host(&server, &name, ice_servers).await?;
+            let ice_servers =
+                build_ice_servers(turn_server, turn_username, turn_credential, turn_secret)?;
+            let options = TuiOptions {
+                export_on_exit,
+                confirm_keys,
+                join_url,
+                show_qr,
+                theme,
+                log_dump,
+            };
+            let next = create_host(
+                &server,
+                &name,
+                ice_servers.clone(),
+                heartbeat_interval,
+                options.clone(),
+            )
+            .await?;
+            (server, name, ice_servers, options, next)
         }
         Commands::Join {
             server,
@@ -87,10 +252,76 @@ async fn main() -> Result<()> {
             turn_server,
             turn_username,
             turn_credential,
+            turn_secret,
+            export_on_exit,
         } => {
-            let ice_servers = build_ice_servers(turn_server, turn_username, turn_credential)?;
-            join_session(&server, &session_id, &name, ice_servers).await?;
+            let ice_servers =
+                build_ice_servers(turn_server, turn_username, turn_credential, turn_secret)?;
+            let options = TuiOptions {
+                export_on_exit,
+                confirm_keys,
+                join_url,
+                show_qr,
+                theme,
+                log_dump,
+            };
+            let next = join_session(
+                &server,
+                &session_id,
+                &name,
+                ice_servers.clone(),
+                heartbeat_interval,
+                options.clone(),
+            )
+            .await?;
+            (server, name, ice_servers, options, next)
         }
+        Commands::Local {
+            server,
+            name,
+            guests,
+            behavior,
+            seed,
+            action_interval_ms,
+            export_on_exit,
+        } => {
+            let config = LocalConfig::new()
+                .with_names("TUI Lobby", &name)
+                .with_guests(guests)
+                .with_behavior(behavior, seed)
+                .with_heartbeat_interval(heartbeat_interval);
+            let options = TuiOptions {
+                export_on_exit,
+                confirm_keys,
+                join_url,
+                show_qr,
+                theme,
+                log_dump,
+            };
+            let interval = Duration::from_millis(action_interval_ms.max(1));
+            let next = run_local(&config, interval, &server, options.clone()).await?;
+            (
+                server,
+                name,
+                IceServer::default_stun_servers(),
+                options,
+                next,
+            )
+        }
+    };
+
+    // Sessions joined from inside the TUI keep the name and settings
+    while let Some(target) = next {
+        server = target.server.unwrap_or(server);
+        next = join_session(
+            &server,
+            &target.session_id.to_string(),
+            &name,
+            ice_servers.clone(),
+            heartbeat_interval,
+            options.clone(),
+        )
+        .await?;
     }
 
     Ok(())
@@ -100,17 +331,22 @@ fn build_ice_servers(
     turn_server: Option<String>,
     turn_username: Option<String>,
     turn_credential: Option<String>,
+    turn_secret: Option<String>,
 ) -> Result<Vec<IceServer>> {
     let mut ice_servers = IceServer::default_stun_servers();
 
     if let Some(turn_url) = turn_server {
-        match (turn_username, turn_credential) {
-            (Some(username), Some(credential)) => {
+        match (turn_username, turn_credential, turn_secret) {
+            (_, _, Some(secret)) => {
+                ice_servers.push(IceServer::turn_from_rest_endpoint(turn_url, secret));
+            }
+            (Some(username), Some(credential), None) => {
                 ice_servers.push(IceServer::turn(turn_url, username, credential));
             }
             _ => {
                 return Err(CliError::InvalidConfig(
-                    "TURN server requires both username and credential".to_string(),
+                    "TURN server requires either --turn-secret or both username and credential"
+                        .to_string(),
                 ));
             }
         }
@@ -119,8 +355,15 @@ fn build_ice_servers(
     Ok(ice_servers)
 }
 
-async fn create_host(server: &str, name: &str, ice_servers: Vec<IceServer>) -> Result<()> {
+async fn create_host(
+    server: &str,
+    name: &str,
+    ice_servers: Vec<IceServer>,
+    heartbeat_interval: Option<Duration>,
+    options: TuiOptions,
+) -> Result<Option<JoinTarget>> {
     let (session_loop, session_id) = P2PLoopBuilder::new()
+        .heartbeat_interval(heartbeat_interval)
         .build_session_host(
             server,
             ice_servers,
@@ -129,7 +372,27 @@ async fn create_host(server: &str, name: &str, ice_servers: Vec<IceServer>) -> R
         )
         .await?;
 
-    run_tui(session_loop, session_id).await
+    run_tui(session_loop, None, session_id, server, options).await
+}
+
+/// Host an in-process session whose guests are bots
+async fn run_local(
+    config: &LocalConfig,
+    interval: Duration,
+    server: &str,
+    options: TuiOptions,
+) -> Result<Option<JoinTarget>> {
+    let LocalSession {
+        session_id,
+        host,
+        guests,
+    } = LocalSession::start(config)?;
+    let guests = LocalGuests {
+        swarm: guests,
+        interval,
+    };
+
+    run_tui(host, Some(guests), session_id, server, options).await
 }
 
 async fn join_session(
@@ -137,10 +400,14 @@ async fn join_session(
     session_id_str: &str,
     name: &str,
     ice_servers: Vec<IceServer>,
-) -> Result<()> {
+    heartbeat_interval: Option<Duration>,
+    options: TuiOptions,
+) -> Result<Option<JoinTarget>> {
     let session_id = SessionId::parse(session_id_str)?;
 
     let (mut session_loop, lobby_id) = P2PLoopBuilder::new()
+        .heartbeat_interval(heartbeat_interval)
+        .confirm_takeover(Some(TAKEOVER_ANSWER_TIMEOUT))
         .build_session_guest(server, session_id.clone(), ice_servers)
         .await?;
 
@@ -153,7 +420,7 @@ async fn join_session(
         guest_name: name.to_string(),
     })?;
 
-    run_tui(session_loop, session_id).await
+    run_tui(session_loop, None, session_id, server, options).await
 }
 
 /// Commands from TUI to SessionLoop
@@ -165,6 +432,21 @@ enum UserCommand {
     KickGuest {
         guest_id: Uuid,
     },
+    ForceSpectate {
+        participant_id: Uuid,
+    },
+    DelegateHost {
+        new_host_id: Uuid,
+    },
+    SetCoHost {
+        participant_id: Uuid,
+        co_host: bool,
+    },
+    UpdateLobbySettings {
+        settings: LobbySettings,
+    },
+    AcceptTakeover,
+    DeclineTakeover,
     LeaveSession {
         participant_id: Uuid,
     },
@@ -182,25 +464,46 @@ enum UserCommand {
         participant_id: Uuid,
         response: String,
     },
+    SetPresence {
+        presence: Option<Presence>,
+    },
+    SendChat {
+        text: String,
+    },
 }
 
 /// Updates sent from SessionLoop to TUI
 #[derive(Debug, Clone)]
 enum UiUpdate {
-    Lobby(konnekt_session_core::Lobby),
+    Lobby(Box<konnekt_session_core::Lobby>),
     PeerInfo {
         peer_id: String,
         peer_count: usize,
         is_host: bool,
     },
+    Presence(Vec<(Uuid, Presence)>),
+    PeerStats(Vec<PeerStats>),
+    Runs(Vec<ActivityRun>),
+    Events(Vec<SessionEvent>),
+    Takeover(Option<HostTakeover>),
 }
 
-#[instrument(skip(session_loop), fields(session_id = %session_id))]
-async fn run_tui(mut session_loop: SessionLoop, session_id: SessionId) -> Result<()> {
+/// Run the TUI until quitting; returns the session picked in the join prompt
+#[instrument(skip(session_loop, guests, options), fields(session_id = %session_id))]
+async fn run_tui<C: NetworkConnection + Send + 'static>(
+    mut session_loop: SessionLoop<C>,
+    mut guests: Option<LocalGuests<C>>,
+    session_id: SessionId,
+    server: &str,
+    options: TuiOptions,
+) -> Result<Option<JoinTarget>> {
     info!("Starting TUI");
 
     let mut terminal = tui::setup_terminal()?;
-    let mut app = App::new(session_id.to_string());
+    let mut app = App::new(session_id.to_string())
+        .with_confirm_keys(options.confirm_keys)
+        .with_server(server)
+        .with_join_qr(options.join_url, options.show_qr);
 
     let (ui_tx, mut ui_rx) = mpsc::channel(10);
     let (cmd_tx, mut cmd_rx) = mpsc::channel::<UserCommand>(10);
@@ -214,6 +517,11 @@ async fn run_tui(mut session_loop: SessionLoop, session_id: SessionId) -> Result
 
         let mut interval = tokio::time::interval(Duration::from_millis(10));
         interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut sent_runs = None;
+        let mut stats_sent_at: Option<Instant> = None;
+        let mut pending_events = Vec::new();
+        let mut guests_acted_at = Instant::now();
+        session_loop.record_events();
 
         loop {
             interval.tick().await;
@@ -227,10 +535,25 @@ async fn run_tui(mut session_loop: SessionLoop, session_id: SessionId) -> Result
 
             // 2. Poll SessionLoop (P2P + Domain)
             session_loop.poll();
+            if let Some(guests) = guests.as_mut() {
+                guests.swarm.poll();
+                if guests_acted_at.elapsed() >= guests.interval {
+                    guests.swarm.step();
+                    guests_acted_at = Instant::now();
+                }
+            }
+
+            // 3. Send UI updates (non-blocking); events are kept until sent
+            pending_events.extend(session_loop.take_events());
+            if !pending_events.is_empty()
+                && let Err(TrySendError::Full(UiUpdate::Events(events))) =
+                    ui_tx.try_send(UiUpdate::Events(std::mem::take(&mut pending_events)))
+            {
+                pending_events = events;
+            }
 
-            // 3. Send UI updates (non-blocking)
             if let Some(lobby) = session_loop.get_lobby() {
-                let _ = ui_tx.try_send(UiUpdate::Lobby(lobby.clone()));
+                let _ = ui_tx.try_send(UiUpdate::Lobby(Box::new(lobby.clone())));
             }
 
             if let Some(peer_id) = session_loop.local_peer_id() {
@@ -240,17 +563,88 @@ async fn run_tui(mut session_loop: SessionLoop, session_id: SessionId) -> Result
                     is_host: session_loop.is_host(),
                 });
             }
+
+            let _ = ui_tx.try_send(UiUpdate::Presence(session_loop.presence()));
+            let _ = ui_tx.try_send(UiUpdate::Takeover(session_loop.host_takeover()));
+
+            // Round trips only change with heartbeats: a few updates a second do
+            if stats_sent_at.is_none_or(|sent| sent.elapsed() >= PEER_STATS_INTERVAL)
+                && ui_tx
+                    .try_send(UiUpdate::PeerStats(session_loop.peer_stats()))
+                    .is_ok()
+            {
+                stats_sent_at = Some(Instant::now());
+            }
+
+            // Finished runs only change when one ends
+            let finished = || {
+                session_loop
+                    .domain()
+                    .event_loop()
+                    .runs_for_lobby(lobby_id)
+                    .filter(|run| run.status() != RunStatus::InProgress)
+            };
+            let count = finished().count();
+            if sent_runs != Some(count)
+                && ui_tx
+                    .try_send(UiUpdate::Runs(finished().cloned().collect()))
+                    .is_ok()
+            {
+                sent_runs = Some(count);
+            }
         }
     });
 
     // Run TUI in main task
-    let result = run_app_loop(&mut terminal, &mut app, &mut ui_rx, cmd_tx).await;
+    let export_path = options
+        .export_on_exit
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_EXPORT_PATH));
+    let result = run_app_loop(
+        &mut terminal,
+        &mut app,
+        &mut ui_rx,
+        cmd_tx,
+        &export_path,
+        options.log_dump.as_ref(),
+        &options.theme,
+    )
+    .await;
 
     // Cleanup
     tui::restore_terminal(terminal)?;
     session_handle.abort();
 
-    result
+    if let Some(path) = options.export_on_exit {
+        let files = export_results(&app, &path)?;
+        println!("📤 Results exported to {}", files.join(", "));
+    }
+
+    result.map(|()| app.next_session.take())
+}
+
+/// Write the results seen so far, returning the written files
+fn export_results(app: &App, path: &Path) -> Result<Vec<String>> {
+    let files = app.results_export().write(path)?;
+    info!("📤 Exported results to {}", path.display());
+    Ok(files.iter().map(|f| f.display().to_string()).collect())
+}
+
+/// Copy the last log lines next to the log file, returning the written file
+fn dump_logs(log_dump: &LogDump) -> Result<PathBuf> {
+    let lines = tail_log(&log_dump.log_file, log_dump.lines)?;
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let mut path = log_dump.log_file.clone().into_os_string();
+    path.push(format!(".dump-{stamp}"));
+    let path = PathBuf::from(path);
+
+    let mut dump = lines.join("\n");
+    dump.push('\n');
+    std::fs::write(&path, dump)?;
+    info!("🪵 Dumped {} log lines to {}", lines.len(), path.display());
+    Ok(path)
 }
 
 async fn run_app_loop(
@@ -258,10 +652,15 @@ async fn run_app_loop(
     app: &mut App,
     ui_rx: &mut mpsc::Receiver<UiUpdate>,
     cmd_tx: mpsc::Sender<UserCommand>,
+    export_path: &Path,
+    log_dump: Option<&LogDump>,
+    theme: &Theme,
 ) -> Result<()> {
+    let mut last_presence = None;
+
     loop {
         // Draw UI
-        terminal.draw(|f| tui::ui::render(f, app))?;
+        terminal.draw(|f| tui::ui::render(f, app, theme))?;
 
         tokio::select! {
             // Handle keyboard input
@@ -269,12 +668,24 @@ async fn run_app_loop(
                 match app_event? {
                     AppEvent::Key(key) => {
                         if let Some(action) = app.handle_key(key) {
-                            handle_user_action(app, action, &cmd_tx).await?;
+                            handle_user_action(app, action, &cmd_tx, export_path, log_dump)
+                                .await?;
+                        }
+                        // SessionLoop throttles repeats, so every keystroke can refresh it
+                        let presence = app.local_presence();
+                        if (presence.is_some() || presence != last_presence)
+                            && app.get_local_participant_id().is_some()
+                        {
+                            let _ = cmd_tx.send(UserCommand::SetPresence { presence }).await;
+                            last_presence = presence;
                         }
                         if app.should_quit {
                             break;
                         }
                     }
+                    AppEvent::Paste(text) => {
+                        app.handle_paste(&text);
+                    }
                     AppEvent::Tick => {
                         app.tick();
                     }
@@ -285,11 +696,28 @@ async fn run_app_loop(
             Some(update) = ui_rx.recv() => {
                 match update {
                     UiUpdate::Lobby(lobby) => {
-                        app.update_lobby(lobby);
+                        app.update_lobby(*lobby);
                     }
                     UiUpdate::PeerInfo { peer_id, peer_count, is_host } => {
                         app.update_peer_info(peer_id, peer_count, is_host);
                     }
+                    UiUpdate::Presence(presence) => {
+                        app.update_presence(presence);
+                    }
+                    UiUpdate::PeerStats(stats) => {
+                        app.update_peer_stats(stats);
+                    }
+                    UiUpdate::Runs(runs) => {
+                        app.update_runs(runs);
+                    }
+                    UiUpdate::Takeover(takeover) => {
+                        app.update_takeover(takeover);
+                    }
+                    UiUpdate::Events(events) => {
+                        for event in &events {
+                            app.record_event(event);
+                        }
+                    }
                 }
             }
         }
@@ -299,8 +727,8 @@ async fn run_app_loop(
 }
 
 /// Handle user commands (business logic)
-fn handle_user_command(
-    session_loop: &mut SessionLoop,
+fn handle_user_command<C: NetworkConnection>(
+    session_loop: &mut SessionLoop<C>,
     lobby_id: Uuid,
     command: UserCommand,
 ) -> Result<()> {
@@ -313,17 +741,80 @@ fn handle_user_command(
             })?;
         }
         UserCommand::KickGuest { guest_id } => {
+            // Co-hosts kick in their own name
             let host_id = session_loop
-                .get_lobby()
-                .map(|l| l.host_id())
+                .p2p()
+                .local_participant_id()
+                .or_else(|| session_loop.get_lobby().map(|l| l.host_id()))
                 .ok_or_else(|| CliError::InvalidConfig("No lobby".to_string()))?;
 
             session_loop.submit_command(DomainCommand::KickGuest {
                 lobby_id,
                 host_id,
                 guest_id,
+                ban: false,
             })?;
         }
+        UserCommand::ForceSpectate { participant_id } => {
+            // Moderators switch guests in their own name
+            let requester_id = session_loop
+                .p2p()
+                .local_participant_id()
+                .or_else(|| session_loop.get_lobby().map(|l| l.host_id()))
+                .ok_or_else(|| CliError::InvalidConfig("No lobby".to_string()))?;
+
+            session_loop.submit_command(DomainCommand::ToggleParticipationMode {
+                lobby_id,
+                participant_id,
+                requester_id,
+            })?;
+        }
+        UserCommand::DelegateHost { new_host_id } => {
+            let current_host_id = session_loop
+                .get_lobby()
+                .map(|l| l.host_id())
+                .ok_or_else(|| CliError::InvalidConfig("No lobby".to_string()))?;
+
+            session_loop.submit_command(DomainCommand::DelegateHost {
+                lobby_id,
+                current_host_id,
+                new_host_id,
+            })?;
+        }
+        UserCommand::SetCoHost {
+            participant_id,
+            co_host,
+        } => {
+            let host_id = session_loop
+                .get_lobby()
+                .map(|l| l.host_id())
+                .ok_or_else(|| CliError::InvalidConfig("No lobby".to_string()))?;
+
+            session_loop.submit_command(DomainCommand::SetCoHost {
+                lobby_id,
+                host_id,
+                participant_id,
+                co_host,
+            })?;
+        }
+        UserCommand::UpdateLobbySettings { settings } => {
+            let host_id = session_loop
+                .get_lobby()
+                .map(|l| l.host_id())
+                .ok_or_else(|| CliError::InvalidConfig("No lobby".to_string()))?;
+
+            session_loop.submit_command(DomainCommand::UpdateLobbySettings {
+                lobby_id,
+                host_id,
+                settings,
+            })?;
+        }
+        UserCommand::AcceptTakeover => {
+            session_loop.accept_takeover();
+        }
+        UserCommand::DeclineTakeover => {
+            session_loop.decline_takeover()?;
+        }
         UserCommand::LeaveSession { participant_id } => {
             session_loop.submit_command(DomainCommand::LeaveLobby {
                 lobby_id,
@@ -331,6 +822,7 @@ fn handle_user_command(
             })?;
         }
         UserCommand::PlanActivity { config } => {
+            PluginRegistry::global().validate(&config)?;
             session_loop.submit_command(DomainCommand::QueueActivity { lobby_id, config })?;
         }
         UserCommand::StartActivity { _activity_id: _ } => {
@@ -354,6 +846,12 @@ fn handle_user_command(
                 result,
             })?;
         }
+        UserCommand::SetPresence { presence } => {
+            session_loop.set_presence(presence)?;
+        }
+        UserCommand::SendChat { text } => {
+            session_loop.send_chat(text)?;
+        }
     }
     Ok(())
 }
@@ -363,6 +861,8 @@ async fn handle_user_action(
     app: &mut App,
     action: UserAction,
     cmd_tx: &mpsc::Sender<UserCommand>,
+    export_path: &Path,
+    log_dump: Option<&LogDump>,
 ) -> Result<()> {
     match action {
         UserAction::CopySessionId => {
@@ -371,6 +871,18 @@ async fn handle_user_action(
         UserAction::CopyJoinCommand => {
             let _ = app.copy_join_command();
         }
+        UserAction::JoinSession(target) => {
+            if !app.is_host
+                && let Some(participant_id) = app.get_local_participant_id()
+            {
+                let _ = cmd_tx
+                    .send(UserCommand::LeaveSession { participant_id })
+                    .await;
+            }
+            info!("🔀 Leaving for session {}", target.session_id);
+            app.next_session = Some(target);
+            app.should_quit = true;
+        }
         UserAction::ToggleParticipationMode => {
             if let Some(participant_id) = app.get_local_participant_id() {
                 cmd_tx
@@ -387,6 +899,48 @@ async fn handle_user_action(
                 .await
                 .map_err(|e| CliError::InvalidConfig(format!("Failed to send command: {}", e)))?;
         }
+        UserAction::ForceSpectate(participant_id) => {
+            cmd_tx
+                .send(UserCommand::ForceSpectate { participant_id })
+                .await
+                .map_err(|e| CliError::InvalidConfig(format!("Failed to send command: {}", e)))?;
+        }
+        UserAction::DelegateHost(new_host_id) => {
+            cmd_tx
+                .send(UserCommand::DelegateHost { new_host_id })
+                .await
+                .map_err(|e| CliError::InvalidConfig(format!("Failed to send command: {}", e)))?;
+        }
+        UserAction::SetCoHost {
+            participant_id,
+            co_host,
+        } => {
+            cmd_tx
+                .send(UserCommand::SetCoHost {
+                    participant_id,
+                    co_host,
+                })
+                .await
+                .map_err(|e| CliError::InvalidConfig(format!("Failed to send command: {}", e)))?;
+        }
+        UserAction::UpdateLobbySettings(settings) => {
+            cmd_tx
+                .send(UserCommand::UpdateLobbySettings { settings })
+                .await
+                .map_err(|e| CliError::InvalidConfig(format!("Failed to send command: {}", e)))?;
+        }
+        UserAction::AcceptTakeover => {
+            cmd_tx
+                .send(UserCommand::AcceptTakeover)
+                .await
+                .map_err(|e| CliError::InvalidConfig(format!("Failed to send command: {}", e)))?;
+        }
+        UserAction::DeclineTakeover => {
+            cmd_tx
+                .send(UserCommand::DeclineTakeover)
+                .await
+                .map_err(|e| CliError::InvalidConfig(format!("Failed to send command: {}", e)))?;
+        }
         UserAction::PlanActivity(config) => {
             cmd_tx
                 .send(UserCommand::PlanActivity { config })
@@ -424,6 +978,41 @@ async fn handle_user_action(
                     })?;
             }
         }
+        UserAction::SendChat(text) => {
+            cmd_tx
+                .send(UserCommand::SendChat { text })
+                .await
+                .map_err(|e| CliError::InvalidConfig(format!("Failed to send command: {}", e)))?;
+        }
+        UserAction::ExportResults => match export_results(app, export_path) {
+            Ok(files) => app.notify(LogEntry::new(
+                "ResultsExported",
+                Severity::Info,
+                format!("📤 Exported results to {}", files.join(", ")),
+            )),
+            Err(e) => app.notify(LogEntry::new(
+                "ResultsExported",
+                Severity::Error,
+                format!("❌ {}", e),
+            )),
+        },
+        UserAction::DumpLogs => match log_dump.map(dump_logs) {
+            Some(Ok(path)) => app.notify(LogEntry::new(
+                "LogsDumped",
+                Severity::Info,
+                format!("🪵 Dumped the last log lines to {}", path.display()),
+            )),
+            Some(Err(e)) => app.notify(LogEntry::new(
+                "LogsDumped",
+                Severity::Error,
+                format!("❌ {}", e),
+            )),
+            None => app.notify(LogEntry::new(
+                "LogsDumped",
+                Severity::Warning,
+                "Start with --log-file to record logs".to_string(),
+            )),
+        },
         UserAction::Quit => {
             if !app.is_host {
                 if let Some(participant_id) = app.get_local_participant_id() {
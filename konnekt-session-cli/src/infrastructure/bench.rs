@@ -0,0 +1,291 @@
+use konnekt_session_core::{
+    DomainCommand, DomainEvent as CoreDomainEvent, DomainLoop, Participant,
+};
+use konnekt_session_p2p::{DomainEvent, LobbyEvent, LobbySnapshot, SnapshotPart, SyncMessage};
+use serde::Serialize;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Parameters of a benchmark run
+#[derive(Debug, Clone)]
+pub struct BenchConfig {
+    /// Lobby sizes (guests) to measure
+    pub sizes: Vec<usize>,
+    /// Repetitions per measurement
+    pub iterations: usize,
+    /// Items per streamed snapshot part
+    pub page_size: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            sizes: vec![10, 100, 1000],
+            iterations: 1000,
+            page_size: konnekt_session_p2p::DEFAULT_SNAPSHOT_PAGE_SIZE,
+        }
+    }
+}
+
+impl BenchConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_sizes(mut self, sizes: Vec<usize>) -> Self {
+        self.sizes = sizes;
+        self
+    }
+
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations.max(1);
+        self
+    }
+}
+
+/// One measurement
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BenchResult {
+    pub name: &'static str,
+    /// Guests in the lobby (`None` for size-independent measurements)
+    pub lobby_size: Option<usize>,
+    pub ops: usize,
+    pub elapsed_us: u64,
+    /// Encoded size of one item, for codec measurements
+    pub bytes: Option<usize>,
+}
+
+impl BenchResult {
+    fn new(name: &'static str, lobby_size: Option<usize>, ops: usize, elapsed: Duration) -> Self {
+        Self {
+            name,
+            lobby_size,
+            ops,
+            elapsed_us: elapsed.as_micros() as u64,
+            bytes: None,
+        }
+    }
+
+    fn with_bytes(mut self, bytes: usize) -> Self {
+        self.bytes = Some(bytes);
+        self
+    }
+
+    pub fn ops_per_sec(&self) -> f64 {
+        self.ops as f64 / (self.elapsed_us.max(1) as f64 / 1_000_000.0)
+    }
+
+    pub fn us_per_op(&self) -> f64 {
+        self.elapsed_us as f64 / self.ops.max(1) as f64
+    }
+}
+
+/// Measure local throughput of command processing, the JSON wire codec and
+/// snapshot generation (no network involved)
+pub fn run_benchmarks(config: &BenchConfig) -> Vec<BenchResult> {
+    let mut results = bench_event_codec(config.iterations);
+
+    for &size in &config.sizes {
+        let (lobby_commands, domain, lobby_id) = bench_domain_loop(size, config.iterations);
+        results.push(lobby_commands);
+
+        let lobby = domain
+            .event_loop()
+            .get_lobby(&lobby_id)
+            .expect("Benchmark lobby was created");
+        results.extend(bench_snapshots(lobby, size, config));
+    }
+
+    results
+}
+
+/// Create a lobby, join `size` guests and let them toggle their mode
+fn bench_domain_loop(size: usize, iterations: usize) -> (BenchResult, DomainLoop, Uuid) {
+    let mut domain = DomainLoop::new(64, 1024);
+    let started = Instant::now();
+
+    domain
+        .submit(DomainCommand::CreateLobby {
+            lobby_id: None,
+            lobby_name: "Bench Lobby".to_string(),
+            host_name: "Host".to_string(),
+        })
+        .expect("Empty queue accepts a command");
+    domain.poll();
+    let lobby_id = match domain.drain_events().pop() {
+        Some(CoreDomainEvent::LobbyCreated { lobby }) => lobby.id(),
+        other => panic!("Expected LobbyCreated, got {:?}", other),
+    };
+
+    let mut guests = Vec::with_capacity(size);
+    let joins = (0..size).map(|i| DomainCommand::JoinLobby {
+        lobby_id,
+        guest_name: format!("Guest {i}"),
+    });
+    run_commands(&mut domain, joins, |event| {
+        if let CoreDomainEvent::GuestJoined { participant, .. } = event {
+            guests.push(participant.id());
+        }
+    });
+
+    let toggles = guests
+        .iter()
+        .cycle()
+        .take(if guests.is_empty() { 0 } else { iterations })
+        .map(|&participant_id| DomainCommand::ToggleParticipationMode {
+            lobby_id,
+            participant_id,
+            requester_id: participant_id,
+        });
+    let toggled = run_commands(&mut domain, toggles, |_| {});
+
+    let result = BenchResult::new(
+        "domain_commands",
+        Some(size),
+        1 + guests.len() + toggled,
+        started.elapsed(),
+    );
+    (result, domain, lobby_id)
+}
+
+/// Submit `commands`, polling whenever the queue is full; returns how many ran
+fn run_commands(
+    domain: &mut DomainLoop,
+    commands: impl Iterator<Item = DomainCommand>,
+    mut on_event: impl FnMut(CoreDomainEvent),
+) -> usize {
+    let mut processed = 0;
+    for command in commands {
+        if domain.is_full() {
+            processed += domain.poll();
+            domain.drain_events().into_iter().for_each(&mut on_event);
+        }
+        domain
+            .submit(command)
+            .expect("Queue has room after polling");
+    }
+    while domain.pending_commands() > 0 {
+        processed += domain.poll();
+    }
+    domain.drain_events().into_iter().for_each(&mut on_event);
+    processed
+}
+
+/// Encode and decode an event broadcast, the most frequent message
+fn bench_event_codec(iterations: usize) -> Vec<BenchResult> {
+    let participant = Participant::new_guest("Guest".to_string()).expect("Valid guest name");
+    let message = SyncMessage::EventBroadcast {
+        event: LobbyEvent::new(1, Uuid::new_v4(), DomainEvent::GuestJoined { participant }),
+    };
+    let encoded = serde_json::to_vec(&message).expect("Sync messages serialize");
+
+    let started = Instant::now();
+    for _ in 0..iterations {
+        black_box(serde_json::to_vec(black_box(&message)).expect("Sync messages serialize"));
+    }
+    let encode = BenchResult::new("event_encode", None, iterations, started.elapsed())
+        .with_bytes(encoded.len());
+
+    let started = Instant::now();
+    for _ in 0..iterations {
+        black_box(
+            serde_json::from_slice::<SyncMessage>(black_box(&encoded))
+                .expect("Encoded sync message decodes"),
+        );
+    }
+    let decode = BenchResult::new("event_decode", None, iterations, started.elapsed())
+        .with_bytes(encoded.len());
+
+    vec![encode, decode]
+}
+
+/// Build, stream, encode and decode full syncs of `lobby`
+fn bench_snapshots(
+    lobby: &konnekt_session_core::Lobby,
+    size: usize,
+    config: &BenchConfig,
+) -> Vec<BenchResult> {
+    let iterations = config.iterations;
+
+    let started = Instant::now();
+    for _ in 0..iterations {
+        black_box(LobbySnapshot::from_lobby(black_box(lobby), 0));
+    }
+    let build = BenchResult::new("snapshot_build", Some(size), iterations, started.elapsed());
+
+    let started = Instant::now();
+    for _ in 0..iterations {
+        black_box(SnapshotPart::split(
+            black_box(lobby),
+            None,
+            0,
+            config.page_size,
+        ));
+    }
+    let elapsed = started.elapsed();
+    let streamed_bytes = SnapshotPart::split(lobby, None, 0, config.page_size)
+        .unwrap_or_default()
+        .iter()
+        .map(|part| serde_json::to_vec(part).map_or(0, |bytes| bytes.len()))
+        .sum();
+    let stream = BenchResult::new("snapshot_stream", Some(size), iterations, elapsed)
+        .with_bytes(streamed_bytes);
+
+    let snapshot = LobbySnapshot::from_lobby(lobby, 0);
+    let encoded = serde_json::to_vec(&snapshot).expect("Snapshots serialize");
+
+    let started = Instant::now();
+    for _ in 0..iterations {
+        black_box(serde_json::to_vec(black_box(&snapshot)).expect("Snapshots serialize"));
+    }
+    let encode = BenchResult::new("snapshot_encode", Some(size), iterations, started.elapsed())
+        .with_bytes(encoded.len());
+
+    let started = Instant::now();
+    for _ in 0..iterations {
+        black_box(
+            serde_json::from_slice::<LobbySnapshot>(black_box(&encoded))
+                .expect("Encoded snapshot decodes"),
+        );
+    }
+    let decode = BenchResult::new("snapshot_decode", Some(size), iterations, started.elapsed())
+        .with_bytes(encoded.len());
+
+    vec![build, stream, encode, decode]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_benchmarks_cover_every_size() {
+        let config = BenchConfig::new()
+            .with_sizes(vec![0, 5, 60])
+            .with_iterations(10);
+
+        let results = run_benchmarks(&config);
+
+        // Two codec results, then five per size
+        assert_eq!(results.len(), 2 + 3 * 5);
+        for size in [0, 5, 60] {
+            let domain = results
+                .iter()
+                .find(|r| r.name == "domain_commands" && r.lobby_size == Some(size))
+                .unwrap();
+            let toggles = if size == 0 { 0 } else { 10 };
+            assert_eq!(domain.ops, 1 + size + toggles);
+        }
+
+        // The big snapshot is larger than the small one
+        let encoded = |size| {
+            results
+                .iter()
+                .find(|r| r.name == "snapshot_encode" && r.lobby_size == Some(size))
+                .and_then(|r| r.bytes)
+                .unwrap()
+        };
+        assert!(encoded(60) > encoded(5));
+    }
+}
@@ -0,0 +1,371 @@
+use konnekt_session_core::domain::{ActivityResult, ActivityRunId};
+use konnekt_session_core::{DomainCommand, ParticipationMode, PluginRegistry};
+use konnekt_session_p2p::infrastructure::SimRng;
+use konnekt_session_p2p::infrastructure::connection::MatchboxConnection;
+use konnekt_session_p2p::{NetworkConnection, SessionLoop};
+use std::collections::HashSet;
+use std::task::{Context, Poll};
+use uuid::Uuid;
+
+/// What simulated guests do once they are connected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum BotBehavior {
+    /// Join and stay quiet
+    Idle,
+    /// Keep leaving and re-joining the lobby
+    Churn,
+    /// Submit a result to every run
+    Submit,
+    /// Toggle between active and spectating
+    Spectate,
+    /// A random mix of all of the above
+    Random,
+}
+
+/// What the bots of a swarm have done so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SwarmStats {
+    pub joins: u64,
+    pub leaves: u64,
+    pub submissions: u64,
+    pub toggles: u64,
+    pub errors: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BotAction {
+    Join,
+    Leave(Uuid),
+    Submit(Uuid, ActivityRunId),
+    Toggle(Uuid),
+}
+
+struct Bot<C: NetworkConnection> {
+    name: String,
+    session_loop: SessionLoop<C>,
+    /// Wants to be in the lobby (the join or leave may still be in flight)
+    wants_in: bool,
+    /// Runs this bot already submitted to
+    submitted: HashSet<ActivityRunId>,
+}
+
+impl<C: NetworkConnection> Bot<C> {
+    /// Our participant, found by name (it changes whenever we re-join)
+    fn participant(&self) -> Option<(Uuid, ParticipationMode)> {
+        self.session_loop
+            .get_lobby()?
+            .participants()
+            .values()
+            .find(|p| p.name() == self.name)
+            .map(|p| (p.id(), p.participation_mode()))
+    }
+}
+
+/// Headless guest sessions driven by a scripted behavior (load testing)
+///
+/// Call `poll` often to keep the sessions moving and `step` whenever the
+/// bots should act; each step, every bot does at most one thing.
+pub struct BotSwarm<C: NetworkConnection = MatchboxConnection> {
+    bots: Vec<Bot<C>>,
+    behavior: BotBehavior,
+    rng: SimRng,
+    stats: SwarmStats,
+}
+
+impl<C: NetworkConnection> BotSwarm<C> {
+    pub fn new(behavior: BotBehavior, seed: u64) -> Self {
+        Self {
+            bots: Vec::new(),
+            behavior,
+            rng: SimRng::new(seed),
+            stats: SwarmStats::default(),
+        }
+    }
+
+    /// Add a guest session; the bot joins as `name` once the lobby synced
+    pub fn add_bot(&mut self, name: String, session_loop: SessionLoop<C>) {
+        self.bots.push(Bot {
+            name,
+            session_loop,
+            wants_in: false,
+            submitted: HashSet::new(),
+        });
+    }
+
+    /// Poll every bot's session, returning the number of processed events
+    pub fn poll(&mut self) -> usize {
+        self.bots
+            .iter_mut()
+            .map(|bot| bot.session_loop.poll())
+            .sum()
+    }
+
+    /// Wake `cx` once any bot's connection has new events
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        // Register with every connection, not just up to the first ready one
+        let mut ready = false;
+        for bot in &mut self.bots {
+            ready |= bot.session_loop.poll_ready(cx).is_ready();
+        }
+        if ready {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+
+    /// Let every bot act once according to the behavior
+    pub fn step(&mut self) {
+        for index in 0..self.bots.len() {
+            if let Some(action) = self.next_action(index) {
+                self.perform(index, action);
+            }
+        }
+    }
+
+    /// Leave the lobby with every bot that is in it
+    pub fn leave_all(&mut self) {
+        for index in 0..self.bots.len() {
+            if let Some((participant_id, _)) = self.bots[index].participant() {
+                self.perform(index, BotAction::Leave(participant_id));
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bots.is_empty()
+    }
+
+    /// Bots that are in the lobby (as far as they know)
+    pub fn joined(&self) -> usize {
+        self.bots
+            .iter()
+            .filter(|bot| bot.participant().is_some())
+            .count()
+    }
+
+    pub fn stats(&self) -> SwarmStats {
+        self.stats
+    }
+
+    fn next_action(&mut self, index: usize) -> Option<BotAction> {
+        let bot = &self.bots[index];
+        let lobby = bot.session_loop.get_lobby()?;
+
+        let Some((participant_id, mode)) = bot.participant() else {
+            // Not in yet: join (again), unless our join is still in flight
+            return (!bot.wants_in).then_some(BotAction::Join);
+        };
+        if !bot.wants_in {
+            return None; // Leaving
+        }
+
+        let pending_run = lobby
+            .active_run_id()
+            .filter(|run_id| mode == ParticipationMode::Active && !bot.submitted.contains(run_id));
+
+        match self.behavior {
+            BotBehavior::Idle => None,
+            BotBehavior::Churn => self
+                .rng
+                .chance(0.3)
+                .then_some(BotAction::Leave(participant_id)),
+            BotBehavior::Submit => {
+                pending_run.map(|run_id| BotAction::Submit(participant_id, run_id))
+            }
+            BotBehavior::Spectate => (lobby.active_run_id().is_none() && self.rng.chance(0.3))
+                .then_some(BotAction::Toggle(participant_id)),
+            BotBehavior::Random => match self.rng.below(10) {
+                0 => Some(BotAction::Leave(participant_id)),
+                1 if lobby.active_run_id().is_none() => Some(BotAction::Toggle(participant_id)),
+                2..=5 => pending_run.map(|run_id| BotAction::Submit(participant_id, run_id)),
+                _ => None,
+            },
+        }
+    }
+
+    fn perform(&mut self, index: usize, action: BotAction) {
+        let bot = &mut self.bots[index];
+        let lobby_id = bot.session_loop.lobby_id();
+
+        let command = match action {
+            BotAction::Join => {
+                bot.wants_in = true;
+                self.stats.joins += 1;
+                DomainCommand::JoinLobby {
+                    lobby_id,
+                    guest_name: bot.name.clone(),
+                }
+            }
+            BotAction::Leave(participant_id) => {
+                bot.wants_in = false;
+                self.stats.leaves += 1;
+                DomainCommand::LeaveLobby {
+                    lobby_id,
+                    participant_id,
+                }
+            }
+            BotAction::Submit(participant_id, run_id) => {
+                bot.submitted.insert(run_id);
+                self.stats.submissions += 1;
+
+                // Echo the prompt back, most of the time correctly
+                let prompt = bot
+                    .session_loop
+                    .domain()
+                    .event_loop()
+                    .get_run(&run_id)
+                    .and_then(|run| {
+                        let config = run.config();
+                        PluginRegistry::global()
+                            .render_hints(&config.activity_type, &config.config)
+                            .ok()?
+                            .prompt
+                    })
+                    .unwrap_or_default();
+                let response = if self.rng.chance(0.8) {
+                    prompt
+                } else {
+                    prompt.chars().rev().collect()
+                };
+
+                DomainCommand::SubmitResult {
+                    lobby_id,
+                    run_id,
+                    result: ActivityResult::new(run_id, participant_id)
+                        .with_data(serde_json::json!({ "response": response }))
+                        .with_time(500 + self.rng.below(4500) as u64),
+                }
+            }
+            BotAction::Toggle(participant_id) => {
+                self.stats.toggles += 1;
+                DomainCommand::ToggleParticipationMode {
+                    lobby_id,
+                    participant_id,
+                    requester_id: participant_id,
+                }
+            }
+        };
+
+        if let Err(e) = bot.session_loop.submit_command(command) {
+            tracing::warn!("🤖 {} failed to act: {}", bot.name, e);
+            self.stats.errors += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_core::EchoChallenge;
+    use konnekt_session_core::domain::ActivityConfig;
+    use konnekt_session_p2p::{LoopbackConnection, LoopbackNetwork, P2PLoopBuilder, SessionId};
+
+    fn swarm_with_host(
+        behavior: BotBehavior,
+        bots: usize,
+    ) -> (
+        SessionLoop<LoopbackConnection>,
+        BotSwarm<LoopbackConnection>,
+    ) {
+        let network = LoopbackNetwork::new();
+        let session_id = SessionId::new();
+
+        let (host, _) = P2PLoopBuilder::new()
+            .build_session_host_with_connection(
+                network.connect(),
+                session_id.clone(),
+                "Load Test".to_string(),
+                "Host".to_string(),
+            )
+            .unwrap();
+
+        let mut swarm = BotSwarm::new(behavior, 7);
+        for i in 0..bots {
+            let (guest, _) = P2PLoopBuilder::new()
+                .build_session_guest_with_connection(network.connect(), session_id.clone());
+            swarm.add_bot(format!("Bot {i}"), guest);
+        }
+        (host, swarm)
+    }
+
+    fn run(
+        host: &mut SessionLoop<LoopbackConnection>,
+        swarm: &mut BotSwarm<LoopbackConnection>,
+        steps: usize,
+    ) {
+        for _ in 0..steps {
+            for _ in 0..5 {
+                host.poll();
+                swarm.poll();
+            }
+            swarm.step();
+        }
+    }
+
+    #[test]
+    fn test_bots_join_and_submit_to_runs() {
+        let (mut host, mut swarm) = swarm_with_host(BotBehavior::Submit, 4);
+
+        run(&mut host, &mut swarm, 5);
+        assert_eq!(swarm.joined(), 4);
+        assert_eq!(host.get_lobby().unwrap().participants().len(), 5);
+
+        let lobby_id = host.lobby_id();
+        host.submit_command(DomainCommand::QueueActivity {
+            lobby_id,
+            config: ActivityConfig::new(
+                EchoChallenge::activity_type().to_string(),
+                "Echo".to_string(),
+                EchoChallenge::new("Hello".to_string()).to_config(),
+            ),
+        })
+        .unwrap();
+        host.submit_command(DomainCommand::StartNextRun { lobby_id })
+            .unwrap();
+        run(&mut host, &mut swarm, 5);
+        assert_eq!(swarm.stats().submissions, 4);
+        assert_eq!(swarm.stats().errors, 0);
+
+        // The host's own result ends the run, for the bots too
+        let run_id = host.get_lobby().unwrap().active_run_id().unwrap();
+        let host_id = host.get_lobby().unwrap().host_id();
+        host.submit_command(DomainCommand::SubmitResult {
+            lobby_id,
+            run_id,
+            result: ActivityResult::new(run_id, host_id),
+        })
+        .unwrap();
+        run(&mut host, &mut swarm, 2);
+
+        assert!(!host.get_lobby().unwrap().has_active_run());
+        assert_eq!(swarm.stats().submissions, 4);
+        assert!(swarm.bots.iter().all(|bot| {
+            let lobby = bot.session_loop.get_lobby().unwrap();
+            !lobby.has_active_run() && lobby.activity_queue().is_empty()
+        }));
+    }
+
+    #[test]
+    fn test_churning_bots_leave_and_rejoin() {
+        let (mut host, mut swarm) = swarm_with_host(BotBehavior::Churn, 3);
+
+        run(&mut host, &mut swarm, 30);
+
+        let stats = swarm.stats();
+        assert!(stats.leaves > 0);
+        assert!(stats.joins > 3);
+        assert_eq!(stats.errors, 0);
+
+        swarm.leave_all();
+        for _ in 0..5 {
+            host.poll();
+            swarm.poll();
+        }
+        assert_eq!(host.get_lobby().unwrap().participants().len(), 1);
+    }
+}
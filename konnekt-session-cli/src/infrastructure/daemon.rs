@@ -0,0 +1,535 @@
+use futures::StreamExt;
+use konnekt_session_core::domain::{ActivityRun, RunStatus};
+use konnekt_session_core::{DomainCommand, Lobby};
+use konnekt_session_p2p::{AsyncSessionLoop, NetworkConnection, SessionEvent, SessionId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, oneshot};
+use uuid::Uuid;
+
+#[cfg(unix)]
+use std::path::PathBuf;
+#[cfg(unix)]
+use tokio::net::UnixListener;
+
+use crate::infrastructure::error::{CliError, Result};
+use crate::infrastructure::host_script::ScriptFile;
+use crate::infrastructure::lesson_script::ScriptRunner;
+use crate::infrastructure::results_export::ResultsExport;
+use crate::infrastructure::webhook::{WebhookEvent, Webhooks};
+
+/// Requests a connected client may have in flight before it waits
+const REQUEST_QUEUE: usize = 32;
+
+/// How often a lesson script's timed actions are checked
+const LESSON_TICK: Duration = Duration::from_millis(100);
+
+/// One line a control client sends
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "request", rename_all = "snake_case")]
+pub enum ControlRequest {
+    /// Session, lobby and peers right now
+    Status,
+    /// Submit a domain command to the session
+    Command { command: Box<DomainCommand> },
+    /// Results and leaderboard of the finished activities
+    Export,
+}
+
+/// The line the daemon answers a request with
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlResponse {
+    Status(Box<DaemonStatus>),
+    /// The command was queued; its effects show up in the next `status`
+    Submitted {
+        lobby_id: Option<Uuid>,
+    },
+    Export(ResultsExport),
+    Error(String),
+}
+
+/// What `status` reports
+#[derive(Debug, Clone, Serialize)]
+pub struct DaemonStatus {
+    pub session_id: String,
+    pub lobby_id: Uuid,
+    pub is_host: bool,
+    pub local_peer_id: Option<String>,
+    pub connected_peers: usize,
+    pub finished_runs: usize,
+    pub lobby: Option<Lobby>,
+}
+
+/// Where the control API accepts clients
+///
+/// The API is unauthenticated: it only listens on localhost or on a Unix
+/// socket, guarded by its file permissions.
+pub enum ControlListener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix {
+        listener: UnixListener,
+        path: PathBuf,
+    },
+}
+
+impl ControlListener {
+    /// Listen on a localhost TCP address (port 0 picks a free one)
+    pub async fn bind_tcp(addr: SocketAddr) -> Result<Self> {
+        if !addr.ip().is_loopback() {
+            return Err(CliError::InvalidInput(format!(
+                "Control API only listens on localhost, not {}",
+                addr.ip()
+            )));
+        }
+        Ok(Self::Tcp(TcpListener::bind(addr).await?))
+    }
+
+    /// Listen on a Unix socket, replacing a stale one left by a crashed daemon
+    #[cfg(unix)]
+    pub fn bind_unix(path: impl Into<PathBuf>) -> Result<Self> {
+        use std::os::unix::fs::FileTypeExt;
+
+        let path = path.into();
+        if let Ok(metadata) = std::fs::symlink_metadata(&path) {
+            if !metadata.file_type().is_socket() {
+                return Err(CliError::InvalidInput(format!(
+                    "{} exists and is not a socket",
+                    path.display()
+                )));
+            }
+            std::fs::remove_file(&path)?;
+        }
+
+        let listener = UnixListener::bind(&path)?;
+        Ok(Self::Unix { listener, path })
+    }
+
+    /// The TCP address clients connect to (`None` for a Unix socket)
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        match self {
+            Self::Tcp(listener) => listener.local_addr().ok(),
+            #[cfg(unix)]
+            Self::Unix { .. } => None,
+        }
+    }
+
+    /// Accept one client and serve it in its own task
+    async fn accept(&self, requests: &mpsc::Sender<PendingRequest>) -> std::io::Result<()> {
+        match self {
+            Self::Tcp(listener) => {
+                let (stream, peer) = listener.accept().await?;
+                tracing::debug!("🔌 Control client connected from {}", peer);
+                tokio::spawn(serve_client(stream, requests.clone()));
+            }
+            #[cfg(unix)]
+            Self::Unix { listener, .. } => {
+                let (stream, _) = listener.accept().await?;
+                tracing::debug!("🔌 Control client connected");
+                tokio::spawn(serve_client(stream, requests.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ControlListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(listener) => match listener.local_addr() {
+                Ok(addr) => write!(f, "tcp://{}", addr),
+                Err(_) => write!(f, "tcp://(unbound)"),
+            },
+            #[cfg(unix)]
+            Self::Unix { path, .. } => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for ControlListener {
+    fn drop(&mut self) {
+        if let Self::Unix { path, .. } = self {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+type PendingRequest = (ControlRequest, oneshot::Sender<ControlResponse>);
+
+/// Read request lines from one client and write a response line for each
+async fn serve_client<S>(stream: S, requests: mpsc::Sender<PendingRequest>)
+where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<ControlRequest>(&line) {
+            Ok(request) => {
+                let (reply_tx, reply_rx) = oneshot::channel();
+                if requests.send((request, reply_tx)).await.is_err() {
+                    break;
+                }
+                let Ok(response) = reply_rx.await else { break };
+                response
+            }
+            Err(e) => ControlResponse::Error(format!("Invalid request: {e}")),
+        };
+
+        let mut line = serde_json::to_string(&response).unwrap_or_else(|e| {
+            serde_json::json!({ "error": format!("Failed to encode response: {e}") }).to_string()
+        });
+        line.push('\n');
+        if writer.write_all(line.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+
+    tracing::debug!("🔌 Control client disconnected");
+}
+
+/// The session a daemon drives, and what it remembers for exports
+struct DaemonState<C: NetworkConnection> {
+    session: AsyncSessionLoop<C>,
+    session_id: SessionId,
+    /// Everyone seen in the lobby, so results keep the names of those who left
+    names: HashMap<Uuid, String>,
+}
+
+impl<C: NetworkConnection> DaemonState<C> {
+    fn remember_names(&mut self) {
+        if let Some(lobby) = self.session.session().get_lobby() {
+            for participant in lobby.participants().values() {
+                self.names
+                    .insert(participant.id(), participant.name().to_string());
+            }
+        }
+    }
+
+    fn handle(&mut self, request: ControlRequest) -> ControlResponse {
+        match request {
+            ControlRequest::Status => ControlResponse::Status(Box::new(self.status())),
+            ControlRequest::Command { command } => {
+                let lobby_id = command.lobby_id();
+                match self.session.submit_command(*command) {
+                    Ok(()) => ControlResponse::Submitted { lobby_id },
+                    Err(e) => ControlResponse::Error(e.to_string()),
+                }
+            }
+            ControlRequest::Export => ControlResponse::Export(self.export()),
+        }
+    }
+
+    fn status(&self) -> DaemonStatus {
+        let session = self.session.session();
+        let lobby_id = session.lobby_id();
+        DaemonStatus {
+            session_id: self.session_id.to_string(),
+            lobby_id,
+            is_host: session.is_host(),
+            local_peer_id: session.local_peer_id().map(|p| p.to_string()),
+            connected_peers: session.connected_peers().len(),
+            finished_runs: self.export_runs().count(),
+            lobby: session.get_lobby().cloned(),
+        }
+    }
+
+    fn export(&self) -> ResultsExport {
+        ResultsExport::collect(self.export_runs(), &self.names)
+    }
+
+    /// Relay a session event to the webhooks if it is a lifecycle event
+    fn notify(&self, event: &SessionEvent, webhooks: &Webhooks) {
+        let SessionEvent::Domain(event) = event else {
+            return;
+        };
+        let session = self.session.session();
+        let runs = session
+            .domain()
+            .event_loop()
+            .runs_for_lobby(session.lobby_id());
+        if let Some(event) = WebhookEvent::from_domain(event, &self.names, runs) {
+            webhooks.send(event);
+        }
+    }
+
+    /// Run the lesson script's due actions; returns whether it closed the session
+    fn run_lesson(&mut self, lesson: &mut ScriptRunner, elapsed: Duration) -> bool {
+        lesson.run_due(elapsed, self.session.session_mut(), &self.names)
+    }
+
+    fn export_runs(&self) -> impl Iterator<Item = &ActivityRun> {
+        let session = self.session.session();
+        session
+            .domain()
+            .event_loop()
+            .runs_for_lobby(session.lobby_id())
+            .filter(|run| run.status() == RunStatus::Completed)
+    }
+}
+
+/// Drive a session headlessly behind a local control API
+///
+/// Clients connect to `listener` and send one JSON request per line
+/// (`{"request": "status"}`, `{"request": "command", "command": {...}}` or
+/// `{"request": "export"}`); each gets one JSON response line. Lobby
+/// lifecycle events go to `webhooks`, domain events to a Rhai `script`;
+/// a lesson `script` runs its timed actions. Runs until `shutdown` resolves
+/// or the lesson script closes the session.
+pub async fn run_daemon<C>(
+    session: AsyncSessionLoop<C>,
+    session_id: SessionId,
+    listener: ControlListener,
+    webhooks: Webhooks,
+    script: Option<ScriptFile>,
+    shutdown: impl Future<Output = ()>,
+) -> AsyncSessionLoop<C>
+where
+    C: NetworkConnection + Unpin,
+{
+    let mut state = DaemonState {
+        session,
+        session_id,
+        names: HashMap::new(),
+    };
+    state.remember_names();
+    if let Some(lobby) = state.session.session().get_lobby() {
+        webhooks.send(WebhookEvent::lobby_created(lobby));
+    }
+
+    let (requests_tx, mut requests_rx) = mpsc::channel::<PendingRequest>(REQUEST_QUEUE);
+    tokio::pin!(shutdown);
+
+    let (host_script, mut lesson) = match script {
+        Some(ScriptFile::Rhai(script)) => (Some(*script), None),
+        Some(ScriptFile::Lesson(script)) => (None, Some(ScriptRunner::new(script))),
+        None => (None, None),
+    };
+    let started = Instant::now();
+    let mut lesson_tick = tokio::time::interval(LESSON_TICK);
+
+    tracing::info!("🛰️  Control API listening on {}", listener);
+
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+
+            event = state.session.next() => {
+                let Some(event) = event else { break };
+                tracing::debug!("Session event: {:?}", event);
+                state.notify(&event, &webhooks);
+                state.remember_names();
+                if let Some(script) = &host_script {
+                    script.react(&event, state.session.session_mut());
+                }
+            }
+
+            _ = lesson_tick.tick(), if lesson.is_some() => {
+                if let Some(lesson) = lesson.as_mut()
+                    && state.run_lesson(lesson, started.elapsed())
+                {
+                    tracing::info!("📜 Host script closed the session");
+                    break;
+                }
+            }
+
+            accepted = listener.accept(&requests_tx) => {
+                if let Err(e) = accepted {
+                    tracing::warn!("Failed to accept control client: {}", e);
+                }
+            }
+
+            Some((request, reply)) = requests_rx.recv() => {
+                let _ = reply.send(state.handle(request));
+            }
+        }
+    }
+
+    webhooks.send(WebhookEvent::LobbyClosed {
+        lobby_id: state.session.session().lobby_id(),
+        export: state.export(),
+    });
+    webhooks.finish().await;
+
+    state.session
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::lesson_script::LessonScript;
+    use konnekt_session_p2p::{LoopbackConnection, LoopbackNetwork, P2PLoopBuilder};
+
+    fn host() -> (AsyncSessionLoop<LoopbackConnection>, Uuid, Uuid) {
+        let network = LoopbackNetwork::new();
+        let (host, _) = P2PLoopBuilder::new()
+            .build_session_host_with_connection(
+                network.connect(),
+                SessionId::new(),
+                "Daemon".to_string(),
+                "Host".to_string(),
+            )
+            .unwrap();
+        let lobby_id = host.lobby_id();
+        let host_id = host.get_lobby().unwrap().host_id();
+        (AsyncSessionLoop::new(host), lobby_id, host_id)
+    }
+
+    /// Send each line and read back one response per line
+    async fn ask<S>(stream: S, requests: &[String]) -> Vec<serde_json::Value>
+    where
+        S: AsyncRead + AsyncWrite,
+    {
+        let (reader, mut writer) = tokio::io::split(stream);
+        let mut lines = BufReader::new(reader).lines();
+        let mut responses = Vec::new();
+        for request in requests {
+            writer
+                .write_all(format!("{request}\n").as_bytes())
+                .await
+                .unwrap();
+            let line = lines.next_line().await.unwrap().unwrap();
+            responses.push(serde_json::from_str(&line).unwrap());
+        }
+        responses
+    }
+
+    #[tokio::test]
+    async fn test_status_command_and_export_over_tcp() {
+        let (session, lobby_id, host_id) = host();
+        let listener = ControlListener::bind_tcp("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let (stop_tx, stop_rx) = oneshot::channel::<()>();
+        let daemon = tokio::spawn(run_daemon(
+            session,
+            SessionId::new(),
+            listener,
+            Webhooks::default(),
+            None,
+            async {
+                let _ = stop_rx.await;
+            },
+        ));
+
+        let command = DomainCommand::SendChatMessage {
+            lobby_id,
+            author_id: host_id,
+            text: "hello".to_string(),
+        };
+        let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let responses = ask(
+            stream,
+            &[
+                serde_json::json!({ "request": "command", "command": command }).to_string(),
+                r#"{"request": "reboot"}"#.to_string(),
+                r#"{"request": "status"}"#.to_string(),
+                r#"{"request": "export"}"#.to_string(),
+            ],
+        )
+        .await;
+
+        assert_eq!(responses[0]["submitted"]["lobby_id"], lobby_id.to_string());
+        assert!(
+            responses[1]["error"]
+                .as_str()
+                .is_some_and(|e| e.starts_with("Invalid request"))
+        );
+        let status = &responses[2]["status"];
+        assert_eq!(status["lobby_id"], lobby_id.to_string());
+        assert_eq!(status["is_host"], true);
+        assert_eq!(status["lobby"]["name"], "Daemon");
+        assert_eq!(responses[3]["export"]["results"], serde_json::json!([]));
+
+        stop_tx.send(()).unwrap();
+        let session = daemon.await.unwrap();
+        assert_eq!(
+            session.session().get_lobby().unwrap().chat_messages().len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lesson_script_runs_and_closes_the_daemon() {
+        let (session, _, _) = host();
+        let listener = ControlListener::bind_tcp("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+        let script = LessonScript::from_json(
+            r#"{"steps": [
+                {"at": 0, "do": "start_activity", "name": "Warm-up"},
+                {"at": 0, "do": "close"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let session = tokio::time::timeout(
+            Duration::from_secs(5),
+            run_daemon(
+                session,
+                SessionId::new(),
+                listener,
+                Webhooks::default(),
+                Some(ScriptFile::Lesson(script)),
+                std::future::pending(),
+            ),
+        )
+        .await
+        .expect("the script closes the daemon");
+
+        let mut session = session.into_inner();
+        for _ in 0..5 {
+            session.poll();
+        }
+        assert!(session.get_lobby().unwrap().has_active_run());
+    }
+
+    #[tokio::test]
+    async fn test_refuses_to_listen_beyond_localhost() {
+        let result = ControlListener::bind_tcp("0.0.0.0:0".parse().unwrap()).await;
+        assert!(matches!(result, Err(CliError::InvalidInput(_))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix_socket_is_removed_on_shutdown() {
+        let (session, lobby_id, _) = host();
+        let path = std::env::temp_dir().join(format!("konnekt-daemon-{}.sock", Uuid::new_v4()));
+        let listener = ControlListener::bind_unix(&path).unwrap();
+
+        let (stop_tx, stop_rx) = oneshot::channel::<()>();
+        let daemon = tokio::spawn(run_daemon(
+            session,
+            SessionId::new(),
+            listener,
+            Webhooks::default(),
+            None,
+            async {
+                let _ = stop_rx.await;
+            },
+        ));
+
+        let stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        let responses = ask(stream, &[r#"{"request": "status"}"#.to_string()]).await;
+        assert_eq!(responses[0]["status"]["lobby_id"], lobby_id.to_string());
+
+        stop_tx.send(()).unwrap();
+        daemon.await.unwrap();
+        assert!(!path.exists());
+    }
+}
@@ -1,3 +1,4 @@
+use konnekt_session_core::{CodedError, ErrorCode};
 use std::path::PathBuf;
 
 #[derive(Debug, thiserror::Error)]
@@ -41,6 +42,25 @@ pub enum CliError {
     #[error("Session not initialized")] // 🆕 From error.rs
     NotInitialized,
 
+    #[error("QR code generation failed: {0}")]
+    QrCode(String),
+
+    #[error("Simulation diverged (seed {seed})")]
+    SimulationDiverged { seed: u64 },
+
+    #[error("{failed} diagnostic check(s) failed")]
+    DiagnosticsFailed { failed: usize },
+
+    #[error("Host script error: {0}")]
+    Script(String),
+
+    #[error("Timed out after {secs}s waiting for {condition} ({observed})")]
+    WaitTimedOut {
+        secs: u64,
+        condition: String,
+        observed: String,
+    },
+
     // Auto-conversions from dependencies
     #[error("P2P error: {0}")]
     P2P(#[from] konnekt_session_p2p::P2PError),
@@ -53,6 +73,9 @@ pub enum CliError {
 
     #[error("Lobby error: {0}")]
     Lobby(#[from] konnekt_session_core::LobbyError),
+
+    #[error("Activity error: {0}")]
+    Plugin(#[from] konnekt_session_core::PluginError),
 }
 
 impl CliError {
@@ -65,4 +88,54 @@ impl CliError {
     }
 }
 
+impl CodedError for CliError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            CliError::Io(_) => ErrorCode::Io,
+            CliError::Json(_) | CliError::Serialization(_) | CliError::SchemaGeneration(_) => {
+                ErrorCode::Serialization
+            }
+            CliError::SchemaFileNotFound { .. }
+            | CliError::InvalidSchemaDirectory { .. }
+            | CliError::InvalidConfig(_)
+            | CliError::InvalidInput(_)
+            | CliError::QrCode(_)
+            | CliError::Script(_) => ErrorCode::InvalidInput,
+            CliError::ParticipantCreation(_) => ErrorCode::InvalidName,
+            CliError::P2PConnection(_) => ErrorCode::ConnectionFailed,
+            CliError::InvalidSessionId(_) => ErrorCode::InvalidSessionId,
+            CliError::MessageSend(_) => ErrorCode::SendFailed,
+            CliError::NotInitialized => ErrorCode::Unknown,
+            CliError::SimulationDiverged { .. } | CliError::DiagnosticsFailed { .. } => {
+                ErrorCode::CheckFailed
+            }
+            CliError::WaitTimedOut { .. } => ErrorCode::Timeout,
+            CliError::P2P(e) => e.code(),
+            CliError::Participant(e) => e.code(),
+            CliError::Queue(e) => e.code(),
+            CliError::Lobby(e) => e.code(),
+            CliError::Plugin(e) => e.code(),
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, CliError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrapped_errors_keep_their_code() {
+        let full = CliError::from(konnekt_session_core::LobbyError::LobbyFull(30));
+        assert_eq!(full.code(), ErrorCode::LobbyFull);
+
+        let timed_out = CliError::WaitTimedOut {
+            secs: 30,
+            condition: "participants>=3".to_string(),
+            observed: "2 participants".to_string(),
+        };
+        assert!(timed_out.is_retryable());
+        assert!(!CliError::InvalidInput("bad".to_string()).is_retryable());
+    }
+}
@@ -0,0 +1,472 @@
+use konnekt_session_core::domain::ActivityRun;
+use konnekt_session_core::{DomainCommand, DomainEvent, Lobby};
+use konnekt_session_p2p::{NetworkConnection, SessionEvent, SessionLoop};
+use rhai::{AST, Dynamic, Engine, EvalAltResult, Map, Scope};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use uuid::Uuid;
+
+use crate::infrastructure::error::{CliError, Result};
+use crate::infrastructure::lesson_script::LessonScript;
+
+/// The function every host script defines
+const ENTRY_POINT: &str = "on_event";
+
+/// Operations one event may cost a script before it is stopped
+const MAX_OPERATIONS: u64 = 100_000;
+
+/// The event a script is handling, and the commands it queued so far
+#[derive(Default)]
+struct ScriptContext {
+    lobby_id: Uuid,
+    host_id: Uuid,
+    run_id: Option<Uuid>,
+    commands: Vec<DomainCommand>,
+}
+
+type SharedContext = Arc<Mutex<ScriptContext>>;
+
+fn lock(context: &SharedContext) -> MutexGuard<'_, ScriptContext> {
+    context.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// A [rhai](https://rhai.rs) script automating the host
+///
+/// The script defines `fn on_event(event, session)`, called for every
+/// domain event. `event` is the event as a map with its variant in `kind`
+/// (e.g. `event.kind == "ResultSubmitted"`); `session` summarizes the
+/// lobby after the event:
+///
+/// | Field | |
+/// |-------|-|
+/// | `lobby_id`, `host_id` | IDs as strings |
+/// | `participants` | Participants in the lobby |
+/// | `queued` | Activities waiting in the queue |
+/// | `run` | The run in progress, `()` without one: `run_id`, `name`, `required` and `submitted` (counts), `pending` (IDs still to answer) |
+///
+/// It acts through these functions, submitted as the host once
+/// `on_event` returns:
+///
+/// - `start_next_run()`, `cancel_run()`, `remove_submitter(participant_id)`
+/// - `send_chat(text)`
+/// - `submit(command)`: any [`DomainCommand`] as a map, in its JSON shape
+pub struct HostScript {
+    engine: Engine,
+    ast: AST,
+    context: SharedContext,
+}
+
+impl HostScript {
+    /// Compile the script at `path`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)?;
+        Self::from_source(&source)
+            .map_err(|e| CliError::Script(format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn from_source(source: &str) -> Result<Self> {
+        let context = SharedContext::default();
+        let engine = engine(&context);
+        let ast = engine
+            .compile(source)
+            .map_err(|e| CliError::Script(e.to_string()))?;
+        if !ast
+            .iter_functions()
+            .any(|f| f.name == ENTRY_POINT && f.params.len() == 2)
+        {
+            return Err(CliError::Script(format!(
+                "no `fn {ENTRY_POINT}(event, session)` defined"
+            )));
+        }
+        Ok(Self {
+            engine,
+            ast,
+            context,
+        })
+    }
+
+    /// Run the script on one event and return the commands it queued
+    ///
+    /// A script that fails keeps running on later events; the commands it
+    /// queued before failing are dropped.
+    pub fn on_event(
+        &self,
+        event: &DomainEvent,
+        lobby: &Lobby,
+        run: Option<&ActivityRun>,
+    ) -> Vec<DomainCommand> {
+        *lock(&self.context) = ScriptContext {
+            lobby_id: lobby.id(),
+            host_id: lobby.host_id(),
+            run_id: run.map(|run| run.id()),
+            commands: Vec::new(),
+        };
+
+        let result = event_map(event).and_then(|event| {
+            self.engine.call_fn::<Dynamic>(
+                &mut Scope::new(),
+                &self.ast,
+                ENTRY_POINT,
+                (event, session_map(lobby, run)),
+            )
+        });
+        let commands = std::mem::take(&mut lock(&self.context).commands);
+        match result {
+            Ok(_) => commands,
+            Err(e) => {
+                tracing::warn!("📜 Host script failed on {:?}: {}", event.kind(), e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Run the script on a session event and submit what it queued as the
+    /// host of `session`
+    pub fn react<C: NetworkConnection>(&self, event: &SessionEvent, session: &mut SessionLoop<C>) {
+        let SessionEvent::Domain(event) = event else {
+            return;
+        };
+        let Some(lobby) = session.get_lobby() else {
+            return;
+        };
+        let run = lobby
+            .active_run_id()
+            .and_then(|run_id| session.domain().event_loop().get_run(&run_id));
+        let commands = self.on_event(event, lobby, run);
+
+        for command in commands {
+            if let Err(e) = session.submit_command(command) {
+                tracing::warn!("📜 Host script command rejected: {}", e);
+            }
+        }
+    }
+}
+
+/// What `--script` loads, picked by the file extension
+pub enum ScriptFile {
+    /// `.rhai`: reacts to every domain event
+    Rhai(Box<HostScript>),
+    /// Anything else: timed actions from YAML/JSON
+    Lesson(LessonScript),
+}
+
+impl ScriptFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        if path.extension().is_some_and(|ext| ext == "rhai") {
+            HostScript::load(path).map(|script| Self::Rhai(Box::new(script)))
+        } else {
+            LessonScript::load(path).map(Self::Lesson)
+        }
+    }
+}
+
+/// An engine whose command functions queue into `context`
+fn engine(context: &SharedContext) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.on_print(|text| tracing::info!("📜 {}", text));
+    engine.on_debug(|text, _, _| tracing::debug!("📜 {}", text));
+
+    let ctx = context.clone();
+    engine.register_fn("start_next_run", move || {
+        let mut context = lock(&ctx);
+        let lobby_id = context.lobby_id;
+        context
+            .commands
+            .push(DomainCommand::StartNextRun { lobby_id });
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("cancel_run", move || -> ScriptResult {
+        let mut context = lock(&ctx);
+        let (lobby_id, run_id) = (context.lobby_id, context.run_in_progress()?);
+        context
+            .commands
+            .push(DomainCommand::CancelRun { lobby_id, run_id });
+        Ok(())
+    });
+
+    let ctx = context.clone();
+    engine.register_fn(
+        "remove_submitter",
+        move |participant_id: &str| -> ScriptResult {
+            let participant_id = Uuid::parse_str(participant_id)
+                .map_err(|e| format!("invalid participant ID {participant_id:?}: {e}"))?;
+            let mut context = lock(&ctx);
+            let (lobby_id, run_id) = (context.lobby_id, context.run_in_progress()?);
+            context.commands.push(DomainCommand::RemoveSubmitter {
+                lobby_id,
+                run_id,
+                participant_id,
+            });
+            Ok(())
+        },
+    );
+
+    let ctx = context.clone();
+    engine.register_fn("send_chat", move |text: &str| {
+        let mut context = lock(&ctx);
+        let (lobby_id, author_id) = (context.lobby_id, context.host_id);
+        context.commands.push(DomainCommand::SendChatMessage {
+            lobby_id,
+            author_id,
+            text: text.to_string(),
+        });
+    });
+
+    let ctx = context.clone();
+    engine.register_fn("submit", move |command: Dynamic| -> ScriptResult {
+        let command: DomainCommand = rhai::serde::from_dynamic(&command)?;
+        lock(&ctx).commands.push(command);
+        Ok(())
+    });
+
+    engine
+}
+
+type ScriptResult = std::result::Result<(), Box<EvalAltResult>>;
+
+impl ScriptContext {
+    fn run_in_progress(&self) -> std::result::Result<Uuid, Box<EvalAltResult>> {
+        self.run_id.ok_or_else(|| "no activity in progress".into())
+    }
+}
+
+/// The event as a map: `{"RunEnded": {...}}` becomes `#{kind: "RunEnded", ...}`
+fn event_map(event: &DomainEvent) -> std::result::Result<Dynamic, Box<EvalAltResult>> {
+    let mut map = Map::new();
+    match serde_json::to_value(event) {
+        Ok(serde_json::Value::Object(variant)) => {
+            for (kind, fields) in variant {
+                map.insert("kind".into(), kind.into());
+                if let Some(fields) = rhai::serde::to_dynamic(&fields)?.try_cast::<Map>() {
+                    map.extend(fields);
+                }
+            }
+        }
+        Ok(other) => return Err(format!("unexpected event shape: {other}").into()),
+        Err(e) => return Err(e.to_string().into()),
+    }
+    Ok(map.into())
+}
+
+fn session_map(lobby: &Lobby, run: Option<&ActivityRun>) -> Map {
+    let mut session = Map::new();
+    session.insert("lobby_id".into(), lobby.id().to_string().into());
+    session.insert("host_id".into(), lobby.host_id().to_string().into());
+    session.insert(
+        "participants".into(),
+        (lobby.participants().len() as i64).into(),
+    );
+    session.insert(
+        "queued".into(),
+        (lobby.activity_queue().len() as i64).into(),
+    );
+    session.insert(
+        "run".into(),
+        run.map_or(Dynamic::UNIT, |run| run_map(run).into()),
+    );
+    session
+}
+
+fn run_map(run: &ActivityRun) -> Map {
+    let required = run.required_submitters();
+    let results = run.results();
+    let mut pending: Vec<_> = required
+        .iter()
+        .filter(|id| !results.contains_key(id))
+        .map(|id| id.to_string())
+        .collect();
+    pending.sort();
+
+    let mut map = Map::new();
+    map.insert("run_id".into(), run.id().to_string().into());
+    map.insert("name".into(), run.config().name.clone().into());
+    map.insert("required".into(), (required.len() as i64).into());
+    map.insert(
+        "submitted".into(),
+        ((required.len() - pending.len()) as i64).into(),
+    );
+    map.insert("pending".into(), pending.into());
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_core::domain::{ActivityConfig, ActivityResult, RunStatus};
+    use konnekt_session_core::{DomainEventLoop, EventFilter, EventSubscription};
+
+    /// Submit `command` and feed every resulting event to the script,
+    /// submitting what it queues in turn
+    fn drive(
+        el: &mut DomainEventLoop,
+        events: &EventSubscription,
+        script: &HostScript,
+        command: DomainCommand,
+    ) -> Vec<DomainEvent> {
+        let mut seen = Vec::new();
+        let mut commands = vec![command];
+        while !commands.is_empty() {
+            for command in std::mem::take(&mut commands) {
+                el.handle_command(command);
+            }
+            for event in events.drain() {
+                let lobby_id = event.lobby_id().unwrap();
+                let lobby = el.get_lobby(&lobby_id).unwrap();
+                let run = lobby.active_run_id().and_then(|id| el.get_run(&id));
+                commands.extend(script.on_event(&event, lobby, run));
+                seen.push(event);
+            }
+        }
+        seen
+    }
+
+    #[test]
+    fn test_auto_advance_script_starts_the_next_activity_at_80_percent() {
+        let script =
+            HostScript::from_source(include_str!("../../scripts/auto-advance.rhai")).unwrap();
+        let mut el = DomainEventLoop::new();
+        let events = el.subscribe(EventFilter::all());
+
+        let DomainEvent::LobbyCreated { lobby } = el.handle_command(DomainCommand::CreateLobby {
+            lobby_id: None,
+            lobby_name: "Class".to_string(),
+            host_name: "Teacher".to_string(),
+        }) else {
+            panic!("lobby not created");
+        };
+        let (lobby_id, host_id) = (lobby.id(), lobby.host_id());
+        let mut players = vec![host_id];
+        for name in ["Alice", "Bob", "Carol", "Dave"] {
+            let DomainEvent::GuestJoined { participant, .. } =
+                el.handle_command(DomainCommand::JoinLobby {
+                    lobby_id,
+                    guest_name: name.to_string(),
+                })
+            else {
+                panic!("guest not joined");
+            };
+            players.push(participant.id());
+        }
+        for name in ["First", "Second"] {
+            el.handle_command(DomainCommand::QueueActivity {
+                lobby_id,
+                config: ActivityConfig::new(
+                    "quiz".to_string(),
+                    name.to_string(),
+                    serde_json::json!({}),
+                ),
+            });
+        }
+        let DomainEvent::RunStarted { run_id, .. } =
+            el.handle_command(DomainCommand::StartNextRun { lobby_id })
+        else {
+            panic!("run not started");
+        };
+        events.drain();
+
+        let submit = |participant_id| DomainCommand::SubmitResult {
+            lobby_id,
+            run_id,
+            result: ActivityResult::new(run_id, participant_id).with_score(1),
+        };
+        for &player in &players[..3] {
+            drive(&mut el, &events, &script, submit(player));
+        }
+        assert_eq!(
+            el.get_lobby(&lobby_id).unwrap().active_run_id(),
+            Some(run_id)
+        );
+
+        // The fourth of five answers ends the run and starts the next one
+        let seen = drive(&mut el, &events, &script, submit(players[3]));
+        assert!(seen.iter().any(|event| matches!(
+            event,
+            DomainEvent::RunEnded { run_id: ended, status: RunStatus::Completed, .. }
+                if *ended == run_id
+        )));
+        let next = el.get_lobby(&lobby_id).unwrap().active_run_id().unwrap();
+        assert_eq!(el.get_run(&next).unwrap().config().name, "Second");
+    }
+
+    #[test]
+    fn test_scripts_submit_commands_and_survive_their_errors() {
+        assert!(matches!(
+            HostScript::from_source("fn on_join(event) {}"),
+            Err(CliError::Script(_))
+        ));
+
+        let script = HostScript::from_source(
+            r#"
+            fn on_event(event, session) {
+                send_chat("Welcome!");
+                // No run in progress
+                cancel_run();
+            }
+            "#,
+        )
+        .unwrap();
+        let mut el = DomainEventLoop::new();
+        let DomainEvent::LobbyCreated { lobby } = el.handle_command(DomainCommand::CreateLobby {
+            lobby_id: None,
+            lobby_name: "Class".to_string(),
+            host_name: "Teacher".to_string(),
+        }) else {
+            panic!("lobby not created");
+        };
+        let joined = el.handle_command(DomainCommand::JoinLobby {
+            lobby_id: lobby.id(),
+            guest_name: "Alice".to_string(),
+        });
+        let lobby = el.get_lobby(&lobby.id()).unwrap();
+        assert!(script.on_event(&joined, lobby, None).is_empty());
+
+        let script = HostScript::from_source(
+            r#"
+            fn on_event(event, session) {
+                send_chat(event.kind + " in a lobby of " + session.participants);
+                submit(#{ SetReady: #{
+                    lobby_id: session.lobby_id,
+                    participant_id: event.participant.id,
+                    ready: true,
+                } });
+            }
+            "#,
+        )
+        .unwrap();
+        let commands = script.on_event(&joined, lobby, None);
+        assert_eq!(commands.len(), 2);
+        assert!(matches!(
+            &commands[0],
+            DomainCommand::SendChatMessage { text, author_id, .. }
+                if text == "GuestJoined in a lobby of 2" && *author_id == lobby.host_id()
+        ));
+        assert!(matches!(
+            commands[1],
+            DomainCommand::SetReady { ready: true, .. }
+        ));
+    }
+
+    #[test]
+    fn test_script_file_picks_the_format_by_extension() {
+        let dir = std::env::temp_dir().join(format!("konnekt-script-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let rhai = dir.join("advance.rhai");
+        std::fs::write(&rhai, "fn on_event(event, session) {}").unwrap();
+        let lesson = dir.join("lesson.json");
+        std::fs::write(&lesson, r#"{"steps": [{"at": 5, "do": "start_next"}]}"#).unwrap();
+
+        assert!(matches!(ScriptFile::load(&rhai), Ok(ScriptFile::Rhai(_))));
+        assert!(matches!(
+            ScriptFile::load(&lesson),
+            Ok(ScriptFile::Lesson(_))
+        ));
+        // A lesson under a Rhai name is compiled as Rhai, and fails as such
+        std::fs::copy(&lesson, &rhai).unwrap();
+        assert!(matches!(ScriptFile::load(&rhai), Err(CliError::Script(_))));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,205 @@
+use konnekt_session_p2p::SessionId;
+use qrcode::QrCode;
+use qrcode::render::unicode::Dense1x2;
+use std::fmt;
+
+use crate::infrastructure::error::{CliError, Result};
+
+/// What a participant scans to join: the web app URL with the session
+/// attached, or just the session ID when no web app is given
+pub fn join_link(session_id: impl fmt::Display, join_url: Option<&str>) -> String {
+    match join_url {
+        Some(url) => {
+            let separator = if url.contains('?') { '&' } else { '?' };
+            format!("{}{}session_id={}", url, separator, session_id)
+        }
+        None => session_id.to_string(),
+    }
+}
+
+/// Where a pasted session ID, join link or join command points
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinTarget {
+    pub session_id: SessionId,
+    /// Signalling server, when the input names one
+    pub server: Option<String>,
+}
+
+impl JoinTarget {
+    /// Parse a bare session ID, a join link (`https://…?session_id=…`,
+    /// optionally with `&server=wss://…`) or a join command
+    /// (`konnekt-tui join -s wss://… --session-id …`)
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut session_id = None;
+        let mut server = None;
+
+        let mut tokens = input
+            .split_whitespace()
+            .map(|token| token.trim_matches(|c| c == '"' || c == '\''));
+        while let Some(token) = tokens.next() {
+            let (flag, inline) = match token.split_once('=') {
+                Some((flag, value)) if flag.starts_with('-') => (flag, Some(value)),
+                _ => (token, None),
+            };
+            match flag {
+                "-i" | "--session-id" => {
+                    session_id = inline.or_else(|| tokens.next()).map(str::to_string)
+                }
+                "-s" | "--server" => server = inline.or_else(|| tokens.next()).map(str::to_string),
+                _ if token.starts_with("ws://") || token.starts_with("wss://") => {
+                    server = Some(token.to_string())
+                }
+                _ if token.contains("://") => {
+                    for (key, value) in query_pairs(token) {
+                        match key {
+                            "session_id" | "session" => session_id = Some(value),
+                            "server" => server = Some(value),
+                            _ => {}
+                        }
+                    }
+                }
+                _ if SessionId::parse(token).is_ok() => session_id = Some(token.to_string()),
+                _ => {}
+            }
+        }
+
+        let session_id = session_id.ok_or_else(|| {
+            CliError::InvalidSessionId(format!("no session ID in '{}'", input.trim()))
+        })?;
+        Ok(Self {
+            session_id: SessionId::parse(&session_id)
+                .map_err(|_| CliError::InvalidSessionId(session_id.clone()))?,
+            server,
+        })
+    }
+}
+
+/// Query parameters of `url`, percent-decoded
+fn query_pairs(url: &str) -> impl Iterator<Item = (&str, String)> {
+    let query = url.split_once('?').map_or("", |(_, query)| query);
+    let query = query.split_once('#').map_or(query, |(query, _)| query);
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (key, percent_decode(value)))
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (escaped, bytes[i]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (None, b'+') => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (None, byte) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Render `data` as a QR code of half-block characters, two modules per row.
+///
+/// Colours are swapped for light-on-dark terminals, and the quiet zone is
+/// kept so phones find the code next to other text.
+pub fn render_qr(data: &str) -> Result<Vec<String>> {
+    let code = QrCode::new(data.as_bytes()).map_err(|e| CliError::QrCode(e.to_string()))?;
+    let image = code
+        .render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .quiet_zone(true)
+        .build();
+    Ok(image.lines().map(str::to_string).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_link_appends_session() {
+        let session_id = SessionId::new();
+
+        assert_eq!(join_link(&session_id, None), session_id.to_string());
+        assert_eq!(
+            join_link(&session_id, Some("https://example.org/session")),
+            format!("https://example.org/session?session_id={}", session_id)
+        );
+        assert_eq!(
+            join_link(&session_id, Some("https://example.org/?lang=de")),
+            format!("https://example.org/?lang=de&session_id={}", session_id)
+        );
+    }
+
+    #[test]
+    fn test_join_target_from_pasted_text() {
+        let session_id = SessionId::new();
+        let target = |server: Option<&str>| JoinTarget {
+            session_id: session_id.clone(),
+            server: server.map(str::to_string),
+        };
+
+        assert_eq!(
+            JoinTarget::parse(&format!("  {}\n", session_id)).unwrap(),
+            target(None)
+        );
+        assert_eq!(
+            JoinTarget::parse(&join_link(
+                &session_id,
+                Some("https://example.org/?lang=de")
+            ))
+            .unwrap(),
+            target(None)
+        );
+        assert_eq!(
+            JoinTarget::parse(&format!(
+                "https://example.org/join?server=wss%3A%2F%2Fmatch.example.org&session_id={}#top",
+                session_id
+            ))
+            .unwrap(),
+            target(Some("wss://match.example.org"))
+        );
+        assert_eq!(
+            JoinTarget::parse(&format!(
+                "konnekt-tui join -s wss://match.example.org --session-id={} -n Alice",
+                session_id
+            ))
+            .unwrap(),
+            target(Some("wss://match.example.org"))
+        );
+        assert_eq!(
+            JoinTarget::parse(&format!("wss://match.example.org '{}'", session_id)).unwrap(),
+            target(Some("wss://match.example.org"))
+        );
+
+        assert!(matches!(
+            JoinTarget::parse("konnekt-tui join --session-id nope"),
+            Err(CliError::InvalidSessionId(_))
+        ));
+        assert!(JoinTarget::parse("https://example.org/").is_err());
+    }
+
+    #[test]
+    fn test_render_qr_is_square() {
+        let lines = render_qr("https://example.org/session?session_id=x").unwrap();
+
+        let width = lines[0].chars().count();
+        assert!(lines.iter().all(|line| line.chars().count() == width));
+        // Two modules per row, rounded up
+        assert_eq!(lines.len(), width.div_ceil(2));
+    }
+}
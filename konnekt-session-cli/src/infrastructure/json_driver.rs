@@ -0,0 +1,136 @@
+use futures::StreamExt;
+use konnekt_session_core::{CodedError, DomainCommand, ErrorCode};
+use konnekt_session_p2p::{AsyncSessionLoop, NetworkConnection, SessionEvent};
+use std::future::Future;
+use std::io::Write;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+/// One line of output: an event, or why an input line was rejected
+fn event_line(event: &SessionEvent) -> serde_json::Result<String> {
+    serde_json::to_string(event)
+}
+
+fn error_line(code: ErrorCode, message: String) -> String {
+    serde_json::json!({ "error": message, "code": code }).to_string()
+}
+
+/// Drive a session as a JSON-lines automation endpoint
+///
+/// Every session event is written to `output` as one JSON object per line
+/// (`{"domain": {...}}` or `{"connection": {...}}`). Each line of `input` is
+/// parsed as a `DomainCommand` and submitted; malformed or rejected lines get
+/// an `{"error": "...", "code": "..."}` line. Runs until `shutdown` resolves; the end of
+/// `input` only stops reading commands.
+pub async fn run_json_driver<C, R, W>(
+    mut session: AsyncSessionLoop<C>,
+    input: R,
+    mut output: W,
+    shutdown: impl Future<Output = ()>,
+) -> std::io::Result<AsyncSessionLoop<C>>
+where
+    C: NetworkConnection + Unpin,
+    R: AsyncBufRead + Unpin,
+    W: Write,
+{
+    let mut lines = input.lines();
+    let mut input_open = true;
+    tokio::pin!(shutdown);
+
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+
+            event = session.next() => {
+                let Some(event) = event else { break };
+                match event_line(&event) {
+                    Ok(line) => writeln!(output, "{line}")?,
+                    Err(e) => tracing::warn!("Failed to encode event {:?}: {}", event, e),
+                }
+                output.flush()?;
+            }
+
+            line = lines.next_line(), if input_open => {
+                match line? {
+                    Some(line) if line.trim().is_empty() => {}
+                    Some(line) => {
+                        let submitted = serde_json::from_str::<DomainCommand>(&line)
+                            .map_err(|e| (ErrorCode::InvalidInput, format!("Invalid command: {e}")))
+                            .and_then(|cmd| {
+                                session.submit_command(cmd).map_err(|e| (e.code(), e.to_string()))
+                            });
+                        if let Err((code, message)) = submitted {
+                            writeln!(output, "{}", error_line(code, message))?;
+                            output.flush()?;
+                        }
+                    }
+                    None => {
+                        tracing::info!("Input closed, no more commands");
+                        input_open = false;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_p2p::{LoopbackNetwork, P2PLoopBuilder, SessionId};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_commands_in_events_out() {
+        let network = LoopbackNetwork::new();
+        let (host, _) = P2PLoopBuilder::new()
+            .build_session_host_with_connection(
+                network.connect(),
+                SessionId::new(),
+                "Scripted".to_string(),
+                "Host".to_string(),
+            )
+            .unwrap();
+        let lobby_id = host.lobby_id();
+        let host_id = host.get_lobby().unwrap().host_id();
+
+        let command = DomainCommand::SendChatMessage {
+            lobby_id,
+            author_id: host_id,
+            text: "hello".to_string(),
+        };
+        let input = format!("{}\n\nnot json\n", serde_json::to_string(&command).unwrap());
+
+        let mut output = Vec::new();
+        let session = run_json_driver(
+            AsyncSessionLoop::new(host),
+            input.as_bytes(),
+            &mut output,
+            tokio::time::sleep(Duration::from_millis(200)),
+        )
+        .await
+        .unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert!(lines.iter().any(|line| {
+            line["error"]
+                .as_str()
+                .is_some_and(|e| e.starts_with("Invalid command"))
+                && line["code"] == "invalid_input"
+        }));
+        assert!(
+            lines
+                .iter()
+                .any(|line| line["domain"]["ChatMessageSent"]["message"]["text"] == "hello")
+        );
+        assert_eq!(
+            session.session().get_lobby().unwrap().chat_messages().len(),
+            1
+        );
+    }
+}
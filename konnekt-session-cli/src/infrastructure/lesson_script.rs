@@ -0,0 +1,276 @@
+use crate::infrastructure::error::{CliError, Result};
+use crate::infrastructure::results_export::ResultsExport;
+use konnekt_session_core::domain::{ActivityConfig, RunStatus};
+use konnekt_session_core::{DomainCommand, EchoChallenge};
+use konnekt_session_p2p::{NetworkConnection, SessionLoop};
+use serde::Deserialize;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Timed host actions, so a recurring lesson format can run unattended
+///
+/// Loaded from YAML (`.yaml`/`.yml`) or JSON:
+///
+/// ```yaml
+/// steps:
+///   - at: 60
+///     do: start_activity
+///     name: Warm-up
+///     prompt: Guten Morgen
+///   - at: 300
+///     do: export_results
+///     path: lesson-1
+///   - at: 300
+///     do: close
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LessonScript {
+    pub steps: Vec<ScriptStep>,
+}
+
+/// One action and when it runs
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ScriptStep {
+    /// Seconds after the script started
+    pub at: u64,
+    #[serde(flatten)]
+    pub action: ScriptAction,
+}
+
+/// What the host does when a step is due
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "do", rename_all = "snake_case")]
+pub enum ScriptAction {
+    /// Queue an echo activity and start it right away
+    StartActivity {
+        name: String,
+        /// Text to echo (default: the name)
+        #[serde(default)]
+        prompt: Option<String>,
+    },
+    /// Start the next queued activity
+    StartNext,
+    /// Cancel the activity in progress, if any
+    CancelActivity,
+    /// Post a chat message as the host
+    Chat { text: String },
+    /// Write results and leaderboard (CSV + JSON) next to `path`
+    ExportResults { path: PathBuf },
+    /// End the session
+    Close,
+}
+
+impl LessonScript {
+    /// Read a script, picking YAML or JSON by the file extension
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let is_yaml = path
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml");
+        let script = if is_yaml {
+            Self::from_yaml(&text)
+        } else {
+            Self::from_json(&text)
+        };
+        script.map_err(|e| CliError::Script(format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn from_json(text: &str) -> Result<Self> {
+        serde_json::from_str(text).map_err(|e| CliError::Script(e.to_string()))
+    }
+
+    pub fn from_yaml(text: &str) -> Result<Self> {
+        serde_yaml::from_str(text).map_err(|e| CliError::Script(e.to_string()))
+    }
+}
+
+/// Hands out a script's actions as their time comes
+#[derive(Debug, Clone)]
+pub struct ScriptRunner {
+    /// Steps not run yet, earliest first
+    pending: VecDeque<ScriptStep>,
+}
+
+impl ScriptRunner {
+    pub fn new(script: LessonScript) -> Self {
+        let mut steps = script.steps;
+        // Stable, so steps at the same time keep their order
+        steps.sort_by_key(|step| step.at);
+        Self {
+            pending: steps.into(),
+        }
+    }
+
+    /// Actions due `elapsed` after the start, in script order
+    pub fn due(&mut self, elapsed: Duration) -> Vec<ScriptAction> {
+        let mut due = Vec::new();
+        while self
+            .pending
+            .front()
+            .is_some_and(|step| Duration::from_secs(step.at) <= elapsed)
+        {
+            if let Some(step) = self.pending.pop_front() {
+                due.push(step.action);
+            }
+        }
+        due
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Carry out the actions due `elapsed` after the start as the host of
+    /// `session`; returns whether a `Close` came due
+    pub fn run_due<C: NetworkConnection>(
+        &mut self,
+        elapsed: Duration,
+        session: &mut SessionLoop<C>,
+        names: &HashMap<Uuid, String>,
+    ) -> bool {
+        let mut closed = false;
+        for action in self.due(elapsed) {
+            tracing::info!("📜 Host script: {:?}", action);
+            if action == ScriptAction::Close {
+                closed = true;
+                continue;
+            }
+            if let Err(e) = perform_action(&action, session, names) {
+                tracing::error!("Host script action failed: {}", e);
+            }
+        }
+        closed
+    }
+}
+
+/// Carry out `action` as the host of `session`
+///
+/// `names` maps participant ids to names for exports, including those who
+/// left. `Close` is left to the caller, which owns the session's lifetime.
+pub fn perform_action<C: NetworkConnection>(
+    action: &ScriptAction,
+    session: &mut SessionLoop<C>,
+    names: &HashMap<Uuid, String>,
+) -> Result<()> {
+    let lobby_id = session.lobby_id();
+    match action {
+        ScriptAction::StartActivity { name, prompt } => {
+            let prompt = prompt.clone().unwrap_or_else(|| name.clone());
+            session.submit_command(DomainCommand::QueueActivity {
+                lobby_id,
+                config: ActivityConfig::new(
+                    EchoChallenge::activity_type().to_string(),
+                    name.clone(),
+                    EchoChallenge::new(prompt).to_config(),
+                ),
+            })?;
+            session.submit_command(DomainCommand::StartNextRun { lobby_id })?;
+        }
+        ScriptAction::StartNext => {
+            session.submit_command(DomainCommand::StartNextRun { lobby_id })?;
+        }
+        ScriptAction::CancelActivity => {
+            let run_id = session.get_lobby().and_then(|lobby| lobby.active_run_id());
+            if let Some(run_id) = run_id {
+                session.submit_command(DomainCommand::CancelRun { lobby_id, run_id })?;
+            }
+        }
+        ScriptAction::Chat { text } => {
+            let host_id = session.get_lobby().map(|lobby| lobby.host_id());
+            if let Some(author_id) = host_id {
+                session.submit_command(DomainCommand::SendChatMessage {
+                    lobby_id,
+                    author_id,
+                    text: text.clone(),
+                })?;
+            }
+        }
+        ScriptAction::ExportResults { path } => {
+            let runs = session
+                .domain()
+                .event_loop()
+                .runs_for_lobby(lobby_id)
+                .filter(|run| run.status() == RunStatus::Completed);
+            let files = ResultsExport::collect(runs, names).write(path)?;
+            tracing::info!("📤 Script exported results to {} file(s)", files.len());
+        }
+        ScriptAction::Close => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_p2p::{LoopbackNetwork, P2PLoopBuilder, SessionId};
+
+    const SCRIPT: &str = r#"{
+        "steps": [
+            {"at": 300, "do": "close"},
+            {"at": 60, "do": "start_activity", "name": "Warm-up", "prompt": "Hallo"},
+            {"at": 300, "do": "export_results", "path": "lesson"}
+        ]
+    }"#;
+
+    #[test]
+    fn test_runner_hands_out_due_steps_in_order() {
+        let mut runner = ScriptRunner::new(LessonScript::from_json(SCRIPT).unwrap());
+
+        assert!(runner.due(Duration::from_secs(59)).is_empty());
+        assert_eq!(
+            runner.due(Duration::from_secs(60)),
+            vec![ScriptAction::StartActivity {
+                name: "Warm-up".to_string(),
+                prompt: Some("Hallo".to_string()),
+            }]
+        );
+        assert_eq!(
+            runner.due(Duration::from_secs(400)),
+            vec![
+                ScriptAction::Close,
+                ScriptAction::ExportResults {
+                    path: PathBuf::from("lesson")
+                },
+            ]
+        );
+        assert!(runner.is_done());
+    }
+
+    #[test]
+    fn test_yaml_and_unknown_actions() {
+        let script = LessonScript::from_yaml("steps:\n  - at: 5\n    do: start_next\n").unwrap();
+        assert_eq!(script.steps[0].action, ScriptAction::StartNext);
+
+        assert!(matches!(
+            LessonScript::from_json(r#"{"steps": [{"at": 1, "do": "dance"}]}"#),
+            Err(CliError::Script(_))
+        ));
+    }
+
+    #[test]
+    fn test_start_activity_starts_a_run() {
+        let network = LoopbackNetwork::new();
+        let (mut host, _) = P2PLoopBuilder::new()
+            .build_session_host_with_connection(
+                network.connect(),
+                SessionId::new(),
+                "Lesson".to_string(),
+                "Host".to_string(),
+            )
+            .unwrap();
+        let action = ScriptAction::StartActivity {
+            name: "Warm-up".to_string(),
+            prompt: None,
+        };
+
+        perform_action(&action, &mut host, &HashMap::new()).unwrap();
+        for _ in 0..5 {
+            host.poll();
+        }
+
+        assert!(host.get_lobby().unwrap().has_active_run());
+    }
+}
@@ -0,0 +1,138 @@
+use crate::infrastructure::bot_swarm::{BotBehavior, BotSwarm};
+use crate::infrastructure::error::Result;
+use konnekt_session_p2p::{
+    LoopbackConnection, LoopbackNetwork, P2PLoopBuilder, SessionId, SessionLoop,
+};
+use std::time::Duration;
+use tracing::info;
+
+/// Settings of a session that runs entirely in-process
+#[derive(Debug, Clone)]
+pub struct LocalConfig {
+    pub lobby_name: String,
+    pub host_name: String,
+    /// Simulated guests joining the host
+    pub guests: usize,
+    /// What the simulated guests do
+    pub behavior: BotBehavior,
+    /// Seed of the guests' decisions (the same seed replays the same choices)
+    pub seed: u64,
+    /// Heartbeat interval of every session (`None` disables heartbeats)
+    pub heartbeat_interval: Option<Duration>,
+}
+
+impl Default for LocalConfig {
+    fn default() -> Self {
+        Self {
+            lobby_name: "Local Lobby".to_string(),
+            host_name: "Host".to_string(),
+            guests: 3,
+            behavior: BotBehavior::Submit,
+            seed: 0,
+            heartbeat_interval: None,
+        }
+    }
+}
+
+impl LocalConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_names(mut self, lobby_name: &str, host_name: &str) -> Self {
+        self.lobby_name = lobby_name.to_string();
+        self.host_name = host_name.to_string();
+        self
+    }
+
+    pub fn with_guests(mut self, guests: usize) -> Self {
+        self.guests = guests;
+        self
+    }
+
+    pub fn with_behavior(mut self, behavior: BotBehavior, seed: u64) -> Self {
+        self.behavior = behavior;
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_heartbeat_interval(mut self, interval: Option<Duration>) -> Self {
+        self.heartbeat_interval = interval;
+        self
+    }
+}
+
+/// A host and simulated guests talking over an in-process network
+///
+/// No signalling server is involved, so the full session and activity flow
+/// works offline. Poll `host` and `guests` as usual; the guests join once
+/// the lobby synced to them.
+pub struct LocalSession {
+    pub session_id: SessionId,
+    pub host: SessionLoop<LoopbackConnection>,
+    pub guests: BotSwarm<LoopbackConnection>,
+}
+
+impl LocalSession {
+    pub fn start(config: &LocalConfig) -> Result<Self> {
+        let network = LoopbackNetwork::new();
+        let session_id = SessionId::new();
+        info!(
+            "🏠 Hosting local session {} with {} simulated guests",
+            session_id, config.guests
+        );
+
+        let (host, _) = P2PLoopBuilder::new()
+            .heartbeat_interval(config.heartbeat_interval)
+            .build_session_host_with_connection(
+                network.connect(),
+                session_id.clone(),
+                config.lobby_name.clone(),
+                config.host_name.clone(),
+            )?;
+
+        let mut guests = BotSwarm::new(config.behavior, config.seed);
+        for i in 1..=config.guests {
+            let (session_loop, _) = P2PLoopBuilder::new()
+                .heartbeat_interval(config.heartbeat_interval)
+                .build_session_guest_with_connection(network.connect(), session_id.clone());
+            guests.add_bot(format!("Bot {i}"), session_loop);
+        }
+
+        Ok(Self {
+            session_id,
+            host,
+            guests,
+        })
+    }
+
+    /// Poll the host and every guest once
+    pub fn poll(&mut self) -> usize {
+        self.host.poll() + self.guests.poll()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guests_join_the_local_host() {
+        let config = LocalConfig::new()
+            .with_names("Offline", "Alice")
+            .with_guests(2);
+        let mut session = LocalSession::start(&config).unwrap();
+
+        for _ in 0..5 {
+            for _ in 0..5 {
+                session.poll();
+            }
+            session.guests.step();
+        }
+
+        let lobby = session.host.get_lobby().unwrap();
+        assert_eq!(lobby.name(), "Offline");
+        assert_eq!(lobby.participants().len(), 3);
+        assert_eq!(session.guests.joined(), 2);
+    }
+}
@@ -0,0 +1,173 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Size at which a log file is rotated
+pub const DEFAULT_LOG_FILE_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Rotated log files kept next to the current one
+pub const DEFAULT_LOG_FILE_KEEP: usize = 5;
+
+/// Log file that moves aside once it grows too large
+///
+/// `path` is always the current file; full files become `path.1`,
+/// `path.2`, … and the oldest beyond `keep` is deleted. Each write lands in
+/// one file, so log lines are never split across a rotation.
+#[derive(Debug, Clone)]
+pub struct RotatingFile {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    path: PathBuf,
+    file: File,
+    written: u64,
+    max_bytes: u64,
+    keep: usize,
+}
+
+impl RotatingFile {
+    /// Append to `path`, creating it (and missing directories) if needed
+    pub fn open(path: impl Into<PathBuf>, max_bytes: u64, keep: usize) -> io::Result<Self> {
+        let path = path.into();
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let written = file.metadata()?.len();
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(Inner {
+                path,
+                file,
+                written,
+                max_bytes: max_bytes.max(1),
+                keep,
+            })),
+        })
+    }
+}
+
+impl Inner {
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.keep == 0 {
+            self.file = File::create(&self.path)?;
+        } else {
+            for generation in (1..self.keep).rev() {
+                rename_if_exists(
+                    &rotated_path(&self.path, generation),
+                    &rotated_path(&self.path, generation + 1),
+                )?;
+            }
+            std::fs::rename(&self.path, rotated_path(&self.path, 1))?;
+            self.file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+        }
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if inner.written > 0 && inner.written + buf.len() as u64 > inner.max_bytes {
+            inner.rotate()?;
+        }
+        inner.file.write_all(buf)?;
+        inner.written += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .file
+            .flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingFile {
+    type Writer = RotatingFile;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+/// The last `lines` lines logged to `path`, oldest first
+///
+/// Reaches back into rotated files when the current one is shorter.
+pub fn tail_log(path: &Path, lines: usize) -> io::Result<Vec<String>> {
+    let mut tail = Vec::new();
+    let mut generation = 0;
+
+    while tail.len() < lines {
+        let file = if generation == 0 {
+            path.to_path_buf()
+        } else {
+            rotated_path(path, generation)
+        };
+        let content = match std::fs::read_to_string(&file) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound && generation > 0 => break,
+            Err(e) => return Err(e),
+        };
+
+        let missing = lines - tail.len();
+        let older: Vec<String> = content.lines().map(str::to_string).collect();
+        let skip = older.len().saturating_sub(missing);
+        tail.splice(0..0, older.into_iter().skip(skip));
+        generation += 1;
+    }
+
+    Ok(tail)
+}
+
+fn rotated_path(path: &Path, generation: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{generation}"));
+    PathBuf::from(name)
+}
+
+fn rename_if_exists(from: &Path, to: &Path) -> io::Result<()> {
+    match std::fs::rename(from, to) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_rotates_and_tails_across_files() {
+        let dir = std::env::temp_dir().join(format!("konnekt-log-{}", Uuid::new_v4()));
+        let path = dir.join("session.log");
+        let mut file = RotatingFile::open(&path, 16, 2).unwrap();
+
+        for i in 0..8 {
+            file.write_all(format!("line {i}\n").as_bytes()).unwrap();
+        }
+
+        // Two 7-byte lines fit into 16 bytes; the oldest pair is gone
+        assert!(rotated_path(&path, 2).exists());
+        assert!(!rotated_path(&path, 3).exists());
+        assert_eq!(
+            tail_log(&path, 3).unwrap(),
+            vec!["line 5", "line 6", "line 7"]
+        );
+        assert_eq!(tail_log(&path, 10).unwrap().first().unwrap(), "line 2");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
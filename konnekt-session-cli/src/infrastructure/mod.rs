@@ -1,7 +1,31 @@
+pub mod bench;
+pub mod bot_swarm;
+pub mod daemon;
 pub mod error;
+pub mod host_script;
+pub mod join_qr;
+pub mod json_driver;
+pub mod lesson_script;
+pub mod local_session;
+pub mod log_file;
 pub mod observability;
+pub mod results_export;
 pub mod session_runtime;
+pub mod wait_condition;
+pub mod webhook;
 
+pub use bench::{BenchConfig, BenchResult, run_benchmarks};
+pub use bot_swarm::{BotBehavior, BotSwarm, SwarmStats};
+pub use daemon::{ControlListener, ControlRequest, ControlResponse, DaemonStatus, run_daemon};
 pub use error::{CliError, Result};
+pub use host_script::{HostScript, ScriptFile};
+pub use join_qr::{JoinTarget, join_link, render_qr};
+pub use json_driver::run_json_driver;
+pub use lesson_script::{LessonScript, ScriptAction, ScriptRunner, ScriptStep};
+pub use local_session::{LocalConfig, LocalSession};
+pub use log_file::{RotatingFile, tail_log};
 pub use observability::LogConfig;
+pub use results_export::{LeaderboardEntry, ResultRow, ResultsExport};
 pub use session_runtime::{SessionRuntime, SessionSnapshot};
+pub use wait_condition::{Comparison, WaitCondition, WaitMetric};
+pub use webhook::{WebhookEvent, Webhooks};
@@ -1,3 +1,6 @@
+use crate::infrastructure::log_file::{
+    DEFAULT_LOG_FILE_KEEP, DEFAULT_LOG_FILE_MAX_BYTES, RotatingFile,
+};
 use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
 
 /// Logging configuration
@@ -5,12 +8,19 @@ use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberI
 pub struct LogConfig {
     pub default_level: tracing::Level,
     pub json_format: bool,
+    /// JSON lines log file, written alongside any terminal output
     pub file_output: Option<String>,
+    /// Size at which the log file is rotated
+    pub file_max_bytes: u64,
+    /// Rotated log files kept
+    pub file_keep: usize,
     pub chrome_trace: bool,
     pub show_spans: bool,
     pub show_thread_ids: bool,
     pub show_targets: bool,
     pub show_logs: bool, // 🆕 NEW: Whether to show logs to stdout/stderr
+    /// Write logs to stderr, keeping stdout for machine-readable output
+    pub log_to_stderr: bool,
 
     #[cfg(feature = "console")]
     pub enable_console: bool,
@@ -22,11 +32,14 @@ impl Default for LogConfig {
             default_level: tracing::Level::INFO,
             json_format: false,
             file_output: None,
+            file_max_bytes: DEFAULT_LOG_FILE_MAX_BYTES,
+            file_keep: DEFAULT_LOG_FILE_KEEP,
             chrome_trace: false,
             show_spans: false,
             show_thread_ids: false,
             show_targets: true,
             show_logs: true, // 🆕 Default: show logs
+            log_to_stderr: false,
             #[cfg(feature = "console")]
             enable_console: false,
         }
@@ -83,12 +96,25 @@ impl LogConfig {
         self
     }
 
+    /// Log to stderr (stdout carries JSON output)
+    pub fn with_stderr(mut self) -> Self {
+        self.log_to_stderr = true;
+        self
+    }
+
     /// Log to file
     pub fn with_file_output(mut self, path: String) -> Self {
         self.file_output = Some(path);
         self
     }
 
+    /// Rotate the log file at `max_bytes`, keeping `keep` old files
+    pub fn with_file_rotation(mut self, max_bytes: u64, keep: usize) -> Self {
+        self.file_max_bytes = max_bytes;
+        self.file_keep = keep;
+        self
+    }
+
     pub fn init(self) -> Result<(), String> {
         // Build env filter
         let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
@@ -102,6 +128,16 @@ impl LogConfig {
             .add_directive("konnekt_session_p2p=debug".parse().unwrap())
         });
 
+        // Structured file output goes next to every other layer
+        let file_layer = match &self.file_output {
+            Some(path) => {
+                let file = RotatingFile::open(path, self.file_max_bytes, self.file_keep)
+                    .map_err(|e| format!("Failed to open log file {}: {}", path, e))?;
+                Some(fmt::layer().json().with_ansi(false).with_writer(file))
+            }
+            None => None,
+        };
+
         // 🔧 Chrome tracing (highest priority)
         #[cfg(all(feature = "chrome-trace", not(target_arch = "wasm32")))]
         if self.chrome_trace {
@@ -122,6 +158,7 @@ impl LogConfig {
 
                 tracing_subscriber::registry()
                     .with(env_filter)
+                    .with(file_layer)
                     .with(chrome_layer)
                     .with(fmt_layer)
                     .try_init()
@@ -129,6 +166,7 @@ impl LogConfig {
             } else {
                 tracing_subscriber::registry()
                     .with(env_filter)
+                    .with(file_layer)
                     .with(chrome_layer)
                     .try_init()
                     .map_err(|e| format!("Failed to initialize tracing: {}", e))?;
@@ -156,6 +194,7 @@ impl LogConfig {
 
             tracing_subscriber::registry()
                 .with(env_filter)
+                .with(file_layer)
                 .with(console_layer)
                 .try_init()
                 .map_err(|e| format!("Failed to initialize tracing: {}", e))?;
@@ -168,13 +207,26 @@ impl LogConfig {
         }
 
         // Default: fmt layer (only if show_logs is true)
-        if self.show_logs {
+        if self.show_logs && self.log_to_stderr {
+            let fmt_layer = fmt::layer()
+                .with_target(self.show_targets)
+                .with_thread_ids(self.show_thread_ids)
+                .with_writer(std::io::stderr);
+
+            tracing_subscriber::registry()
+                .with(env_filter)
+                .with(file_layer)
+                .with(fmt_layer)
+                .try_init()
+                .map_err(|e| format!("Failed to initialize tracing: {}", e))
+        } else if self.show_logs {
             let fmt_layer = fmt::layer()
                 .with_target(self.show_targets)
                 .with_thread_ids(self.show_thread_ids);
 
             tracing_subscriber::registry()
                 .with(env_filter)
+                .with(file_layer)
                 .with(fmt_layer)
                 .try_init()
                 .map_err(|e| format!("Failed to initialize tracing: {}", e))
@@ -182,6 +234,7 @@ impl LogConfig {
             // Silent mode: no fmt layer, just filter
             tracing_subscriber::registry()
                 .with(env_filter)
+                .with(file_layer)
                 .try_init()
                 .map_err(|e| format!("Failed to initialize tracing: {}", e))
         }
@@ -237,9 +290,20 @@ mod tests {
         assert!(!config.show_logs);
     }
 
+    #[test]
+    fn test_with_stderr() {
+        let config = LogConfig::default().with_stderr();
+        assert!(config.log_to_stderr);
+        assert!(config.show_logs);
+    }
+
     #[test]
     fn test_with_file_output() {
         let config = LogConfig::default().with_file_output("app.log".to_string());
         assert_eq!(config.file_output, Some("app.log".to_string()));
+        assert_eq!(config.file_max_bytes, DEFAULT_LOG_FILE_MAX_BYTES);
+
+        let config = config.with_file_rotation(1024, 2);
+        assert_eq!((config.file_max_bytes, config.file_keep), (1024, 2));
     }
 }
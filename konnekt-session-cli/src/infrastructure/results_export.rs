@@ -0,0 +1,296 @@
+use konnekt_session_core::analytics::{UNKNOWN_PARTICIPANT, result_score};
+use konnekt_session_core::domain::{ActivityResult, ActivityRun, RunStatus};
+use konnekt_session_core::projections::Leaderboard;
+pub use konnekt_session_core::projections::LeaderboardEntry;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// One participant's result in one completed activity
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResultRow {
+    pub run_id: Uuid,
+    pub activity_id: Uuid,
+    pub activity_name: String,
+    pub participant_id: Uuid,
+    pub participant_name: String,
+    pub response: Option<String>,
+    pub score: Option<u32>,
+    pub time_ms: Option<u64>,
+}
+
+/// Results of a session, ready to be written out for a gradebook
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ResultsExport {
+    /// Per activity (oldest first), then per participant (by name)
+    pub results: Vec<ResultRow>,
+    pub leaderboard: Vec<LeaderboardEntry>,
+}
+
+impl ResultsExport {
+    /// Collect the results of completed runs; cancelled and running ones are
+    /// skipped. `names` maps participant ids to display names, including
+    /// participants that have left since.
+    pub fn collect<'a>(
+        runs: impl IntoIterator<Item = &'a ActivityRun>,
+        names: &HashMap<Uuid, String>,
+    ) -> Self {
+        let name_of = |id: &Uuid| {
+            names
+                .get(id)
+                .cloned()
+                .unwrap_or_else(|| UNKNOWN_PARTICIPANT.to_string())
+        };
+
+        let runs: Vec<&ActivityRun> = runs.into_iter().collect();
+        let mut results = Vec::new();
+        for run in &runs {
+            if run.status() != RunStatus::Completed {
+                continue;
+            }
+            let config = run.config();
+            let mut rows: Vec<ResultRow> = run
+                .results()
+                .values()
+                .map(|result| ResultRow {
+                    run_id: run.id(),
+                    activity_id: config.id,
+                    activity_name: config.name.clone(),
+                    participant_id: result.participant_id,
+                    participant_name: name_of(&result.participant_id),
+                    response: response_of(result),
+                    score: result_score(run, result),
+                    time_ms: result.time_taken_ms,
+                })
+                .collect();
+            rows.sort_by(|a, b| {
+                a.participant_name
+                    .cmp(&b.participant_name)
+                    .then(a.participant_id.cmp(&b.participant_id))
+            });
+            results.extend(rows);
+        }
+
+        Self {
+            results,
+            leaderboard: Leaderboard::from_runs(runs, names).entries().to_vec(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// One line per participant and activity
+    pub fn results_csv(&self) -> String {
+        let mut csv = csv_line([
+            "activity_id",
+            "run_id",
+            "activity_name",
+            "participant_id",
+            "participant_name",
+            "response",
+            "score",
+            "time_ms",
+        ]);
+        for row in &self.results {
+            csv.push_str(&csv_line([
+                row.activity_id.to_string().as_str(),
+                row.run_id.to_string().as_str(),
+                &row.activity_name,
+                row.participant_id.to_string().as_str(),
+                &row.participant_name,
+                row.response.as_deref().unwrap_or(""),
+                &optional(row.score),
+                &optional(row.time_ms),
+            ]));
+        }
+        csv
+    }
+
+    pub fn leaderboard_csv(&self) -> String {
+        let mut csv = csv_line([
+            "rank",
+            "participant_id",
+            "participant_name",
+            "total_score",
+            "activities",
+            "total_time_ms",
+        ]);
+        for entry in &self.leaderboard {
+            csv.push_str(&csv_line([
+                entry.rank.to_string().as_str(),
+                entry.participant_id.to_string().as_str(),
+                &entry.participant_name,
+                entry.total_score.to_string().as_str(),
+                entry.activities.to_string().as_str(),
+                entry.total_time_ms.to_string().as_str(),
+            ]));
+        }
+        csv
+    }
+
+    /// Write `<base>.json` (everything), `<base>-results.csv` and
+    /// `<base>-leaderboard.csv`, where `<base>` is `path` without its
+    /// extension. Returns the written files.
+    pub fn write(&self, path: &Path) -> std::io::Result<Vec<PathBuf>> {
+        let base = path.with_extension("");
+        let sibling = |suffix: &str| {
+            let mut name = base.as_os_str().to_owned();
+            name.push(suffix);
+            PathBuf::from(name)
+        };
+
+        let files = [
+            (sibling(".json"), self.to_json()?),
+            (sibling("-results.csv"), self.results_csv()),
+            (sibling("-leaderboard.csv"), self.leaderboard_csv()),
+        ];
+        for (file, contents) in &files {
+            std::fs::write(file, contents)?;
+        }
+        Ok(files.into_iter().map(|(file, _)| file).collect())
+    }
+}
+
+/// The text response of a result (`{"response": "..."}`), if any
+fn response_of(result: &ActivityResult) -> Option<String> {
+    result
+        .data
+        .get("response")
+        .and_then(|r| r.as_str())
+        .map(str::to_string)
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// A CSV record (RFC 4180 quoting), including the line break
+fn csv_line<'a>(fields: impl IntoIterator<Item = &'a str>) -> String {
+    let fields: Vec<String> = fields
+        .into_iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect();
+    format!("{}\r\n", fields.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_core::EchoChallenge;
+    use konnekt_session_core::domain::ActivityConfig;
+    use std::collections::HashSet;
+
+    fn echo_run(prompt: &str, answers: &[(Uuid, &str, u64)]) -> ActivityRun {
+        let config = ActivityConfig::new(
+            EchoChallenge::activity_type().to_string(),
+            format!("Echo {prompt}"),
+            EchoChallenge::new(prompt.to_string()).to_config(),
+        );
+        let submitters: HashSet<Uuid> = answers.iter().map(|(id, _, _)| *id).collect();
+        let mut run = ActivityRun::new(Uuid::new_v4(), Uuid::new_v4(), config, submitters);
+        for (participant_id, response, time_ms) in answers {
+            run.submit_result(
+                ActivityResult::new(run.id(), *participant_id)
+                    .with_data(serde_json::json!({ "response": response }))
+                    .with_time(*time_ms),
+            )
+            .unwrap();
+        }
+        run
+    }
+
+    #[test]
+    fn test_collect_scores_and_ranks() {
+        let (alice, bob, carol) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let names = HashMap::from([(alice, "Alice".to_string()), (bob, "Bob, Jr.".to_string())]);
+
+        let first = echo_run("Hallo", &[(alice, "Hallo", 900), (bob, "hallo", 500)]);
+        let second = echo_run("Tschüss", &[(alice, "Tschüss", 800), (bob, "Tschüss", 700)]);
+        let mut cancelled = echo_run("Danke", &[]);
+        cancelled.cancel().unwrap();
+        let third = echo_run("Bitte", &[(carol, "Bitte", 100)]);
+
+        let export = ResultsExport::collect([&first, &second, &cancelled, &third], &names);
+
+        assert_eq!(export.results.len(), 5);
+        assert_eq!(export.results[0].participant_name, "Alice");
+        assert_eq!(export.results[0].score, Some(100));
+        assert_eq!(export.results[1].score, Some(0));
+        assert_eq!(export.results[4].participant_name, UNKNOWN_PARTICIPANT);
+
+        let ranking: Vec<_> = export
+            .leaderboard
+            .iter()
+            .map(|e| (e.rank, e.participant_id, e.total_score, e.activities))
+            .collect();
+        assert_eq!(
+            ranking,
+            vec![(1, alice, 200, 2), (2, carol, 100, 1), (3, bob, 100, 2)]
+        );
+    }
+
+    #[test]
+    fn test_equal_totals_share_a_rank() {
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let run = echo_run("Hallo", &[(alice, "Hallo", 500), (bob, "Hallo", 500)]);
+
+        let export = ResultsExport::collect([&run], &HashMap::new());
+
+        let ranks: Vec<_> = export.leaderboard.iter().map(|e| e.rank).collect();
+        assert_eq!(ranks, vec![1, 1]);
+    }
+
+    #[test]
+    fn test_write_csv_and_json() {
+        let alice = Uuid::new_v4();
+        let names = HashMap::from([(alice, "Alice \"Ace\", Jr.".to_string())]);
+        let run = echo_run("Hallo", &[(alice, "Hallo", 900)]);
+        let export = ResultsExport::collect([&run], &names);
+
+        let dir = std::env::temp_dir().join(format!("konnekt-export-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let files = export.write(&dir.join("scores.json")).unwrap();
+
+        let names: Vec<_> = files
+            .iter()
+            .map(|f| f.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "scores.json",
+                "scores-results.csv",
+                "scores-leaderboard.csv"
+            ]
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&files[0]).unwrap()).unwrap();
+        assert_eq!(json["leaderboard"][0]["total_score"], 100);
+
+        let results = std::fs::read_to_string(&files[1]).unwrap();
+        let mut lines = results.lines();
+        assert!(lines.next().unwrap().starts_with("activity_id,run_id"));
+        assert!(
+            lines
+                .next()
+                .unwrap()
+                .ends_with(",\"Alice \"\"Ace\"\", Jr.\",Hallo,100,900")
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
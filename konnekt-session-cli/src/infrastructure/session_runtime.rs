@@ -1,11 +1,19 @@
+use crate::infrastructure::host_script::{HostScript, ScriptFile};
+use crate::infrastructure::lesson_script::ScriptRunner;
 use bevy_ecs::prelude::{Resource, World};
 use bevy_ecs::schedule::Schedule;
 use bevy_ecs::system::ResMut;
+use futures::StreamExt;
 use konnekt_session_core::{DomainCommand, Lobby};
-use konnekt_session_p2p::{SessionId, SessionLoop};
+use konnekt_session_p2p::{AsyncSessionLoop, SessionEvent, SessionId, SessionLoop};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, watch};
 use uuid::Uuid;
 
+/// How often a lesson script checks for due actions
+const SCRIPT_TICK: Duration = Duration::from_millis(100);
+
 /// Snapshot of session state (read-only, cheap to clone)
 #[derive(Debug, Clone)]
 pub struct SessionSnapshot {
@@ -14,6 +22,8 @@ pub struct SessionSnapshot {
     pub peer_count: usize,
     pub is_host: bool,
     pub lobby_id: Uuid,
+    /// The host script closed the session
+    pub closed: bool,
 }
 
 impl Default for SessionSnapshot {
@@ -24,6 +34,7 @@ impl Default for SessionSnapshot {
             peer_count: 0,
             is_host: false,
             lobby_id: Uuid::nil(),
+            closed: false,
         }
     }
 }
@@ -43,39 +54,84 @@ pub struct SessionRuntime {
 impl SessionRuntime {
     /// Spawn a new runtime with existing SessionLoop
     pub fn spawn(session_loop: SessionLoop, session_id: SessionId) -> Self {
+        Self::spawn_inner(session_loop, session_id, None, None)
+    }
+
+    /// Spawn a runtime that also runs a host script
+    pub fn spawn_with_script(
+        session_loop: SessionLoop,
+        session_id: SessionId,
+        script: ScriptFile,
+    ) -> Self {
+        match script {
+            ScriptFile::Rhai(script) => {
+                Self::spawn_inner(session_loop, session_id, None, Some(*script))
+            }
+            ScriptFile::Lesson(script) => Self::spawn_inner(
+                session_loop,
+                session_id,
+                Some(ScriptRunner::new(script)),
+                None,
+            ),
+        }
+    }
+
+    fn spawn_inner(
+        session_loop: SessionLoop,
+        session_id: SessionId,
+        script: Option<ScriptRunner>,
+        host_script: Option<HostScript>,
+    ) -> Self {
         let (cmd_tx, mut cmd_rx) = mpsc::channel::<DomainCommand>(100);
         let (state_tx, state_rx) = watch::channel(SessionSnapshot::default());
 
         let lobby_id = session_loop.lobby_id();
         let is_host = session_loop.is_host();
+        let has_script = script.is_some();
 
         let mut world = World::new();
         world.insert_resource(RuntimeState {
-            session_loop,
+            session: AsyncSessionLoop::new(session_loop),
             state_tx,
             lobby_id,
             is_host,
+            script,
+            host_script,
+            started: Instant::now(),
+            names: HashMap::new(),
+            closed: false,
         });
         world.insert_resource(PendingCommands::default());
+        world.insert_resource(SessionEvents::default());
 
         let mut schedule = Schedule::default();
         schedule.add_systems(drive_session_runtime);
 
         let task_handle = tokio::spawn(async move {
-            let mut interval = tokio::time::interval(std::time::Duration::from_millis(100));
-            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            let mut script_tick = tokio::time::interval(SCRIPT_TICK);
+            script_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
             tracing::info!("SessionRuntime started for session {}", session_id);
 
-            loop {
-                interval.tick().await;
+            // Publish the initial snapshot
+            schedule.run(&mut world);
 
-                // Queue incoming user commands into the Bevy message bus.
-                while let Ok(cmd) = cmd_rx.try_recv() {
-                    world.resource_mut::<PendingCommands>().0.push(cmd);
+            // Tick whenever the session has events, a command arrives or the
+            // lesson script may have due actions
+            loop {
+                tokio::select! {
+                    event = next_event(&mut world) => {
+                        let Some(event) = event else { break };
+                        world.resource_mut::<SessionEvents>().0.push(event);
+                    }
+                    cmd = cmd_rx.recv() => {
+                        let Some(cmd) = cmd else { break };
+                        world.resource_mut::<PendingCommands>().0.push(cmd);
+                    }
+                    _ = script_tick.tick(), if has_script => {}
                 }
 
-                // Run one Bevy ECS tick (command handling + SessionLoop poll + snapshot publish).
+                // Run one Bevy ECS tick (command handling + script reactions + snapshot publish).
                 schedule.run(&mut world);
             }
         });
@@ -112,38 +168,95 @@ impl SessionRuntime {
     }
 }
 
+/// Wait for the next event of the runtime's session
+async fn next_event(world: &mut World) -> Option<SessionEvent> {
+    world.resource_mut::<RuntimeState>().session.next().await
+}
+
 #[derive(Resource)]
 struct RuntimeState {
-    session_loop: SessionLoop,
+    session: AsyncSessionLoop,
     state_tx: watch::Sender<SessionSnapshot>,
     lobby_id: Uuid,
     is_host: bool,
+    /// Timed host actions still to run
+    script: Option<ScriptRunner>,
+    /// Reacts to the domain events of the session
+    host_script: Option<HostScript>,
+    started: Instant,
+    /// Everyone seen in the lobby, so exported results keep their names
+    names: HashMap<Uuid, String>,
+    closed: bool,
+}
+
+impl RuntimeState {
+    fn run_script(&mut self) {
+        let Some(script) = self.script.as_mut() else {
+            return;
+        };
+
+        if let Some(lobby) = self.session.session().get_lobby() {
+            for participant in lobby.participants().values() {
+                self.names
+                    .insert(participant.id(), participant.name().to_string());
+            }
+        }
+
+        let elapsed = self.started.elapsed();
+        if script.run_due(elapsed, self.session.session_mut(), &self.names) {
+            self.closed = true;
+        }
+    }
+
+    fn run_host_script(&mut self, events: Vec<SessionEvent>) {
+        let Self {
+            host_script: Some(script),
+            session,
+            ..
+        } = self
+        else {
+            return;
+        };
+        for event in events {
+            script.react(&event, session.session_mut());
+        }
+    }
 }
 
 #[derive(Resource, Default)]
 struct PendingCommands(Vec<DomainCommand>);
 
+/// Session events since the last tick
+#[derive(Resource, Default)]
+struct SessionEvents(Vec<SessionEvent>);
+
 fn drive_session_runtime(
     mut state: ResMut<RuntimeState>,
     mut pending_commands: ResMut<PendingCommands>,
+    mut session_events: ResMut<SessionEvents>,
 ) {
     for cmd in pending_commands.0.drain(..) {
-        if let Err(e) = state.session_loop.submit_command(cmd) {
+        if let Err(e) = state.session.submit_command(cmd) {
             tracing::error!("Failed to submit command: {:?}", e);
         }
     }
 
-    let processed = state.session_loop.poll();
-    if processed > 0 {
-        tracing::debug!("SessionRuntime processed {} events", processed);
+    state.run_script();
+
+    let events = std::mem::take(&mut session_events.0);
+    if !events.is_empty() {
+        tracing::debug!("SessionRuntime processed {} events", events.len());
     }
+    state.run_host_script(events);
 
+    let session = state.session.session();
     let snapshot = SessionSnapshot {
-        lobby: state.session_loop.get_lobby().cloned(),
-        local_peer_id: state.session_loop.local_peer_id().map(|p| p.to_string()),
-        peer_count: state.session_loop.connected_peers().len(),
+        lobby: session.get_lobby().cloned(),
+        local_peer_id: session.local_peer_id().map(|p| p.to_string()),
+        peer_count: session.connected_peers().len(),
         is_host: state.is_host,
         lobby_id: state.lobby_id,
+        closed: state.closed,
     };
     let _ = state.state_tx.send(snapshot);
 }
@@ -0,0 +1,208 @@
+use konnekt_session_core::{Lobby, ParticipationMode};
+use std::fmt;
+
+use crate::infrastructure::error::{CliError, Result};
+
+/// What `wait` counts in the session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitMetric {
+    /// Everyone in the lobby, host included
+    Participants,
+    Guests,
+    /// Participants answering activities (not spectating)
+    Active,
+    Spectators,
+    /// Peers we are connected to
+    Peers,
+}
+
+impl WaitMetric {
+    const ALL: [(&'static str, WaitMetric); 5] = [
+        ("participants", WaitMetric::Participants),
+        ("guests", WaitMetric::Guests),
+        ("active", WaitMetric::Active),
+        ("spectators", WaitMetric::Spectators),
+        ("peers", WaitMetric::Peers),
+    ];
+
+    pub fn name(&self) -> &'static str {
+        Self::ALL
+            .iter()
+            .find(|(_, metric)| metric == self)
+            .map_or("", |(name, _)| name)
+    }
+
+    /// Current value in `lobby`, with `peers` connected peers
+    pub fn value(&self, lobby: &Lobby, peers: usize) -> usize {
+        let participants = lobby.participants().values();
+        match self {
+            WaitMetric::Participants => lobby.participants().len(),
+            WaitMetric::Guests => participants.filter(|p| !p.is_host()).count(),
+            WaitMetric::Active => participants
+                .filter(|p| p.participation_mode() == ParticipationMode::Active)
+                .count(),
+            WaitMetric::Spectators => participants
+                .filter(|p| p.participation_mode() == ParticipationMode::Spectating)
+                .count(),
+            WaitMetric::Peers => peers,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    AtLeast,
+    AtMost,
+    MoreThan,
+    LessThan,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    /// Two-character operators first, so `>=` is not read as `>`
+    const OPERATORS: [(&'static str, Comparison); 7] = [
+        (">=", Comparison::AtLeast),
+        ("<=", Comparison::AtMost),
+        ("==", Comparison::Equal),
+        ("!=", Comparison::NotEqual),
+        (">", Comparison::MoreThan),
+        ("<", Comparison::LessThan),
+        ("=", Comparison::Equal),
+    ];
+
+    fn symbol(&self) -> &'static str {
+        Self::OPERATORS
+            .iter()
+            .find(|(_, comparison)| comparison == self)
+            .map_or("", |(symbol, _)| symbol)
+    }
+
+    fn holds(&self, value: usize, target: usize) -> bool {
+        match self {
+            Comparison::AtLeast => value >= target,
+            Comparison::AtMost => value <= target,
+            Comparison::MoreThan => value > target,
+            Comparison::LessThan => value < target,
+            Comparison::Equal => value == target,
+            Comparison::NotEqual => value != target,
+        }
+    }
+}
+
+/// A condition on the session state, e.g. `participants>=5`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitCondition {
+    pub metric: WaitMetric,
+    pub comparison: Comparison,
+    pub target: usize,
+}
+
+impl WaitCondition {
+    /// Parse `<metric><op><number>`; spaces are ignored, metrics are
+    /// case-insensitive and the operators are `>= <= > < == = !=`
+    pub fn parse(input: &str) -> Result<Self> {
+        let compact: String = input.split_whitespace().collect();
+        let invalid = |reason: &str| {
+            CliError::InvalidInput(format!("Invalid wait condition '{}': {}", input, reason))
+        };
+
+        let (position, symbol, comparison) = Comparison::OPERATORS
+            .iter()
+            .filter_map(|(symbol, comparison)| {
+                compact
+                    .find(symbol)
+                    .map(|position| (position, *symbol, *comparison))
+            })
+            .min_by_key(|(position, symbol, _)| (*position, std::cmp::Reverse(symbol.len())))
+            .ok_or_else(|| invalid("expected an operator like >="))?;
+
+        let name = compact[..position].to_lowercase();
+        let metric = WaitMetric::ALL
+            .iter()
+            .find(|(metric, _)| *metric == name)
+            .map(|(_, metric)| *metric)
+            .ok_or_else(|| {
+                let names: Vec<_> = WaitMetric::ALL.iter().map(|(name, _)| *name).collect();
+                invalid(&format!("expected one of {}", names.join(", ")))
+            })?;
+        let target = compact[position + symbol.len()..]
+            .parse()
+            .map_err(|_| invalid("expected a number after the operator"))?;
+
+        Ok(Self {
+            metric,
+            comparison,
+            target,
+        })
+    }
+
+    pub fn holds(&self, lobby: &Lobby, peers: usize) -> bool {
+        self.comparison
+            .holds(self.metric.value(lobby, peers), self.target)
+    }
+}
+
+impl fmt::Display for WaitCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            self.metric.name(),
+            self.comparison.symbol(),
+            self.target
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_core::Participant;
+
+    #[test]
+    fn test_parse_conditions() {
+        let condition = WaitCondition::parse("participants>=5").unwrap();
+        assert_eq!(
+            condition,
+            WaitCondition {
+                metric: WaitMetric::Participants,
+                comparison: Comparison::AtLeast,
+                target: 5,
+            }
+        );
+        assert_eq!(condition.to_string(), "participants>=5");
+
+        let condition = WaitCondition::parse(" Guests = 0 ").unwrap();
+        assert_eq!(condition.comparison, Comparison::Equal);
+        assert_eq!(condition.to_string(), "guests==0");
+        assert_eq!(
+            WaitCondition::parse("active<3").unwrap().comparison,
+            Comparison::LessThan
+        );
+
+        for invalid in ["participants", "people>=5", "peers>=many", ">=5"] {
+            assert!(
+                matches!(WaitCondition::parse(invalid), Err(CliError::InvalidInput(_))),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_conditions_on_lobby() {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let mut lobby = Lobby::new("Class".to_string(), host).unwrap();
+        let mut guest = Participant::new_guest("Bob".to_string()).unwrap();
+        guest.toggle_participation_mode(false).unwrap();
+        lobby.add_guest(guest).unwrap();
+
+        let holds = |condition: &str| WaitCondition::parse(condition).unwrap().holds(&lobby, 1);
+        assert!(holds("participants>=2"));
+        assert!(!holds("participants>2"));
+        assert!(holds("guests==1"));
+        assert!(holds("spectators=1"));
+        assert!(holds("active<=1"));
+        assert!(holds("peers!=0"));
+    }
+}
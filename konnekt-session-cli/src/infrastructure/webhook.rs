@@ -0,0 +1,353 @@
+use konnekt_session_core::domain::{ActivityRun, RunStatus};
+use konnekt_session_core::{DomainEvent, Lobby};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::infrastructure::error::{CliError, Result};
+use crate::infrastructure::results_export::{ResultRow, ResultsExport};
+
+/// Events waiting for delivery before new ones are dropped
+const WEBHOOK_QUEUE: usize = 64;
+
+/// How long one delivery may take, including connecting
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A lobby lifecycle event, as posted to webhooks
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WebhookEvent {
+    LobbyCreated {
+        lobby_id: Uuid,
+        name: String,
+        host_id: Uuid,
+        host_name: String,
+    },
+    /// The hosting process shut down; carries the session's results for
+    /// archiving
+    LobbyClosed {
+        lobby_id: Uuid,
+        export: ResultsExport,
+    },
+    ParticipantJoined {
+        lobby_id: Uuid,
+        participant_id: Uuid,
+        name: String,
+    },
+    ParticipantLeft {
+        lobby_id: Uuid,
+        participant_id: Uuid,
+        name: Option<String>,
+    },
+    ParticipantKicked {
+        lobby_id: Uuid,
+        participant_id: Uuid,
+        name: Option<String>,
+        banned: bool,
+    },
+    /// One row per participant, as in the results export
+    ActivityCompleted {
+        lobby_id: Uuid,
+        run_id: Uuid,
+        results: Vec<ResultRow>,
+    },
+}
+
+impl WebhookEvent {
+    pub fn lobby_created(lobby: &Lobby) -> Self {
+        let host_name = lobby
+            .participants()
+            .get(&lobby.host_id())
+            .map(|host| host.name().to_string())
+            .unwrap_or_default();
+        Self::LobbyCreated {
+            lobby_id: lobby.id(),
+            name: lobby.name().to_string(),
+            host_id: lobby.host_id(),
+            host_name,
+        }
+    }
+
+    /// The webhook event for a domain event, if it is one deployments care
+    /// about
+    ///
+    /// `names` resolves participants who already left the lobby; `runs`
+    /// holds the finished run a completion refers to.
+    pub fn from_domain<'a>(
+        event: &DomainEvent,
+        names: &HashMap<Uuid, String>,
+        runs: impl IntoIterator<Item = &'a ActivityRun>,
+    ) -> Option<Self> {
+        match event {
+            DomainEvent::GuestJoined {
+                lobby_id,
+                participant,
+            } => Some(Self::ParticipantJoined {
+                lobby_id: *lobby_id,
+                participant_id: participant.id(),
+                name: participant.name().to_string(),
+            }),
+            DomainEvent::GuestLeft {
+                lobby_id,
+                participant_id,
+            } => Some(Self::ParticipantLeft {
+                lobby_id: *lobby_id,
+                participant_id: *participant_id,
+                name: names.get(participant_id).cloned(),
+            }),
+            DomainEvent::GuestKicked {
+                lobby_id,
+                participant_id,
+                banned,
+                ..
+            } => Some(Self::ParticipantKicked {
+                lobby_id: *lobby_id,
+                participant_id: *participant_id,
+                name: names.get(participant_id).cloned(),
+                banned: *banned,
+            }),
+            DomainEvent::RunEnded {
+                lobby_id,
+                run_id,
+                status: RunStatus::Completed,
+                ..
+            } => {
+                let run = runs.into_iter().find(|run| run.id() == *run_id)?;
+                Some(Self::ActivityCompleted {
+                    lobby_id: *lobby_id,
+                    run_id: *run_id,
+                    results: ResultsExport::collect([run], names).results,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The JSON body of one webhook call
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    session_id: &'a str,
+    /// Milliseconds since the Unix epoch
+    timestamp: u64,
+    #[serde(flatten)]
+    event: &'a WebhookEvent,
+}
+
+/// Posts lifecycle events to the configured URLs in the background
+///
+/// Delivery is best effort: events are posted in order, a failing URL is
+/// logged and skipped, and events are dropped while the queue is full so a
+/// slow endpoint never holds up the session. Without URLs it does nothing.
+#[derive(Debug, Default)]
+pub struct Webhooks {
+    session_id: String,
+    queue: Option<mpsc::Sender<String>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl Webhooks {
+    /// Start delivering to `urls` (`http://` or `https://`)
+    pub fn start(session_id: impl Into<String>, urls: Vec<String>) -> Result<Self> {
+        if let Some(url) = urls
+            .iter()
+            .find(|url| !url.starts_with("http://") && !url.starts_with("https://"))
+        {
+            return Err(CliError::InvalidInput(format!(
+                "Webhook URL must start with http:// or https://: {url}"
+            )));
+        }
+        if urls.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let (queue, events) = mpsc::channel(WEBHOOK_QUEUE);
+        let worker = tokio::spawn(deliver(urls, events));
+        Ok(Self {
+            session_id: session_id.into(),
+            queue: Some(queue),
+            worker: Some(worker),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.queue.is_some()
+    }
+
+    pub fn send(&self, event: WebhookEvent) {
+        let Some(queue) = &self.queue else { return };
+        let payload = WebhookPayload {
+            session_id: &self.session_id,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_millis() as u64),
+            event: &event,
+        };
+        let body = match serde_json::to_string(&payload) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!("Failed to encode webhook event {:?}: {}", event, e);
+                return;
+            }
+        };
+        if queue.try_send(body).is_err() {
+            tracing::warn!("🪝 Webhook queue full, dropping {:?}", event);
+        }
+    }
+
+    /// Deliver what is still queued, then stop
+    pub async fn finish(mut self) {
+        self.queue = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.await;
+        }
+    }
+}
+
+/// Post every queued body to every URL until the sender is gone
+async fn deliver(urls: Vec<String>, mut events: mpsc::Receiver<String>) {
+    let agent = ureq::Agent::config_builder()
+        .timeout_global(Some(DELIVERY_TIMEOUT))
+        .build()
+        .new_agent();
+
+    while let Some(body) = events.recv().await {
+        for url in &urls {
+            let agent = agent.clone();
+            let url = url.clone();
+            let body = body.clone();
+            let posted = tokio::task::spawn_blocking(move || {
+                agent
+                    .post(&url)
+                    .header("Content-Type", "application/json")
+                    .send(body)
+                    .map(|_| ())
+                    .map_err(|e| (url, e))
+            })
+            .await;
+            match posted {
+                Ok(Ok(())) => {}
+                Ok(Err((url, e))) => tracing::warn!("🪝 Webhook {} failed: {}", url, e),
+                Err(e) => tracing::warn!("🪝 Webhook delivery task failed: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_p2p::{LoopbackNetwork, P2PLoopBuilder, SessionId};
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    #[test]
+    fn test_domain_events_map_to_lifecycle_events() {
+        let network = LoopbackNetwork::new();
+        let (host, _) = P2PLoopBuilder::new()
+            .build_session_host_with_connection(
+                network.connect(),
+                SessionId::new(),
+                "Class 3b".to_string(),
+                "Teacher".to_string(),
+            )
+            .unwrap();
+        let lobby = host.get_lobby().unwrap();
+        let lobby_id = lobby.id();
+        let guest_id = Uuid::new_v4();
+        let names = HashMap::from([(guest_id, "Ada".to_string())]);
+
+        assert_eq!(
+            WebhookEvent::lobby_created(lobby),
+            WebhookEvent::LobbyCreated {
+                lobby_id,
+                name: "Class 3b".to_string(),
+                host_id: lobby.host_id(),
+                host_name: "Teacher".to_string(),
+            }
+        );
+        assert_eq!(
+            WebhookEvent::from_domain(
+                &DomainEvent::GuestKicked {
+                    lobby_id,
+                    participant_id: guest_id,
+                    kicked_by: lobby.host_id(),
+                    banned: true,
+                },
+                &names,
+                [],
+            ),
+            Some(WebhookEvent::ParticipantKicked {
+                lobby_id,
+                participant_id: guest_id,
+                name: Some("Ada".to_string()),
+                banned: true,
+            })
+        );
+        let failed = DomainEvent::command_failed(
+            konnekt_session_core::DomainCommand::SendChatMessage {
+                lobby_id,
+                author_id: guest_id,
+                text: String::new(),
+            },
+            konnekt_session_core::domain::ChatError::EmptyMessage,
+        );
+        assert_eq!(WebhookEvent::from_domain(&failed, &names, []), None);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_posts_events_as_json() {
+        let server = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", server.local_addr().unwrap());
+        let received = std::thread::spawn(move || {
+            let (stream, _) = server.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        });
+
+        let webhooks = Webhooks::start("session-1", vec![url]).unwrap();
+        let lobby_id = Uuid::new_v4();
+        webhooks.send(WebhookEvent::LobbyClosed {
+            lobby_id,
+            export: ResultsExport::default(),
+        });
+        webhooks.finish().await;
+
+        let body = received.join().unwrap();
+        assert_eq!(body["event"], "lobby_closed");
+        assert_eq!(body["session_id"], "session-1");
+        assert_eq!(body["lobby_id"], lobby_id.to_string());
+        assert_eq!(body["export"]["leaderboard"], serde_json::json!([]));
+        // Unix epoch milliseconds, not process uptime
+        assert!(body["timestamp"].as_u64().unwrap() > 1_600_000_000_000);
+    }
+
+    #[test]
+    fn test_rejects_non_http_urls() {
+        assert!(Webhooks::start("session-1", vec!["ftp://example.com".to_string()]).is_err());
+        assert!(
+            !Webhooks::start("session-1", Vec::new())
+                .unwrap()
+                .is_enabled()
+        );
+    }
+}
@@ -1,6 +1,12 @@
 pub mod infrastructure;
 
-pub use infrastructure::{CliError, LogConfig, Result, SessionRuntime, SessionSnapshot};
+pub use infrastructure::{
+    BenchConfig, BenchResult, BotBehavior, BotSwarm, CliError, ControlListener, ControlRequest,
+    ControlResponse, DaemonStatus, HostScript, LeaderboardEntry, LessonScript, LocalConfig,
+    LocalSession, LogConfig, Result, ResultRow, ResultsExport, ScriptFile, SessionRuntime,
+    SessionSnapshot, SwarmStats, WaitCondition, WebhookEvent, Webhooks, join_link, render_qr,
+    run_benchmarks, run_daemon, run_json_driver,
+};
 
 #[cfg(feature = "tui")]
 pub mod presentation;
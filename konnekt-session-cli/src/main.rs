@@ -1,11 +1,29 @@
 use clap::{Parser, Subcommand};
-use konnekt_session_cli::{LogConfig, Result, SessionRuntime}; // 🆕 Import LogConfig
-use konnekt_session_core::DomainCommand;
-use konnekt_session_p2p::{IceServer, P2PLoopBuilder, SessionId, SessionLoop};
+use konnekt_session_cli::{
+    BenchConfig, BenchResult, BotBehavior, BotSwarm, ControlListener, LocalConfig, LocalSession,
+    LogConfig, Result, ScriptFile, SessionRuntime, WaitCondition, Webhooks, join_link, render_qr,
+    run_benchmarks, run_daemon, run_json_driver,
+}; // 🆕 Import LogConfig
+use konnekt_session_core::domain::ActivityConfig;
+use konnekt_session_core::{DomainCommand, EchoChallenge};
+use konnekt_session_p2p::{
+    AsyncSessionLoop, CheckStatus, DEFAULT_IDLE_INTERVAL, IceServer, NetworkConditions,
+    NetworkConnection, P2PLoopBuilder, SessionId, SessionLoop, Simulation, SimulationConfig,
+    probe_clock, probe_signalling, run_diagnostics,
+};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::task::Poll;
 use std::time::Duration;
 use tracing::{debug, info};
 use uuid::Uuid;
 
+/// Where `daemon` serves its control API unless given `--listen`
+const DEFAULT_CONTROL_SOCKET: &str = "konnekt-session.sock";
+
+/// NTP server `doctor` compares the local clock with
+const DEFAULT_NTP_SERVER: &str = "pool.ntp.org";
+
 #[derive(Parser)]
 #[command(name = "konnekt-cli")]
 #[command(
@@ -15,6 +33,55 @@ use uuid::Uuid;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Also write structured JSON logs to this file (rotated at 10 MiB, 5 kept)
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+}
+
+/// How a running session reports to the terminal
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+enum OutputFormat {
+    /// Human-readable logs
+    #[default]
+    Text,
+    /// Events as JSON lines on stdout, commands as JSON lines on stdin
+    Json,
+}
+
+/// How `create-host` reports to the terminal
+struct HostOutput {
+    format: OutputFormat,
+    /// Print the join link as a QR code
+    qr: bool,
+    /// Web app the QR code links to (just the session ID without one)
+    join_url: Option<String>,
+    /// Automates the host (Rhai or timed lesson actions)
+    script: Option<ScriptFile>,
+}
+
+/// Where `daemon` takes requests and reports lobby events
+struct DaemonOutput {
+    listener: ControlListener,
+    /// URLs that get a JSON POST for every lobby lifecycle event
+    webhooks: Vec<String>,
+    /// Automates the host (Rhai or timed lesson actions)
+    script: Option<ScriptFile>,
+}
+
+impl Cli {
+    fn json_output(&self) -> bool {
+        matches!(
+            self.command,
+            Commands::CreateHost {
+                output: OutputFormat::Json,
+                ..
+            } | Commands::Join {
+                output: OutputFormat::Json,
+                ..
+            }
+        )
+    }
 }
 
 #[derive(Subcommand)]
@@ -37,6 +104,27 @@ enum Commands {
         #[arg(long)]
         seed: Option<String>,
 
+        /// Snapshot the session to this file and resume it from there after a crash
+        #[arg(long)]
+        resume: Option<PathBuf>,
+
+        /// Output format (json: scriptable, logs go to stderr)
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+
+        /// Print the join link as a QR code for participants on phones
+        #[arg(long)]
+        qr: bool,
+
+        /// Web app to join through; the QR code links here with the session ID
+        #[arg(long, value_name = "URL")]
+        join_url: Option<String>,
+
+        /// Host script: Rhai (`.rhai`) reacting to every domain event, or
+        /// YAML/JSON timed actions, e.g. start an activity at 60s
+        #[arg(long, value_name = "PATH")]
+        script: Option<PathBuf>,
+
         /// TURN server URL (optional, format: turn:host:port)
         #[arg(long)]
         turn_server: Option<String>,
@@ -48,6 +136,70 @@ enum Commands {
         /// TURN credential (required if turn-server is set)
         #[arg(long)]
         turn_credential: Option<String>,
+
+        /// TURN REST shared secret (coturn static-auth-secret), replaces username/credential
+        #[arg(long)]
+        turn_secret: Option<String>,
+    },
+
+    /// Host a session headlessly, driven through a local control API
+    ///
+    /// Clients send one JSON request per line (`{"request": "status"}`,
+    /// `{"request": "command", "command": {...}}`, `{"request": "export"}`)
+    /// and get one JSON response per line.
+    Daemon {
+        /// Matchbox signalling server URL
+        #[arg(short = 's', long, default_value = "wss://match.konnektoren.help")]
+        server: String,
+
+        /// Lobby name
+        #[arg(short = 'l', long, default_value = "CLI Lobby")]
+        lobby_name: String,
+
+        /// Host display name
+        #[arg(short = 'n', long, default_value = "Host")]
+        name: String,
+
+        /// Deterministic seed for session/lobby ID generation
+        #[arg(long)]
+        seed: Option<String>,
+
+        /// Snapshot the session to this file and resume it from there after a crash
+        #[arg(long)]
+        resume: Option<PathBuf>,
+
+        /// Unix socket to serve the control API on
+        #[arg(long, value_name = "PATH", default_value = DEFAULT_CONTROL_SOCKET)]
+        socket: PathBuf,
+
+        /// Serve the control API on this localhost address instead (e.g. 127.0.0.1:7878)
+        #[arg(long, value_name = "ADDR")]
+        listen: Option<SocketAddr>,
+
+        /// POST lobby lifecycle events (joins, kicks, completed activities, final results) as JSON to this URL (repeatable)
+        #[arg(long = "webhook", value_name = "URL")]
+        webhooks: Vec<String>,
+
+        /// Host script: Rhai (`.rhai`) reacting to every domain event, or
+        /// YAML/JSON timed actions, e.g. start an activity at 60s
+        #[arg(long, value_name = "PATH")]
+        script: Option<PathBuf>,
+
+        /// TURN server URL (optional, format: turn:host:port)
+        #[arg(long)]
+        turn_server: Option<String>,
+
+        /// TURN username (required if turn-server is set)
+        #[arg(long)]
+        turn_username: Option<String>,
+
+        /// TURN credential (required if turn-server is set)
+        #[arg(long)]
+        turn_credential: Option<String>,
+
+        /// TURN REST shared secret (coturn static-auth-secret), replaces username/credential
+        #[arg(long)]
+        turn_secret: Option<String>,
     },
 
     /// Join an existing session as guest
@@ -64,6 +216,10 @@ enum Commands {
         #[arg(short = 'n', long, default_value = "Guest")]
         name: String,
 
+        /// Output format (json: scriptable, logs go to stderr)
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        output: OutputFormat,
+
         /// TURN server URL (optional, format: turn:host:port)
         #[arg(long)]
         turn_server: Option<String>,
@@ -75,11 +231,204 @@ enum Commands {
         /// TURN credential (required if turn-server is set)
         #[arg(long)]
         turn_credential: Option<String>,
+
+        /// TURN REST shared secret (coturn static-auth-secret), replaces username/credential
+        #[arg(long)]
+        turn_secret: Option<String>,
+    },
+
+    /// Check signalling, STUN/TURN, NAT, candidates and clock, with advice
+    Doctor {
+        /// Signalling server to check
+        #[arg(short = 's', long, default_value = "wss://match.konnektoren.help")]
+        server: String,
+
+        /// NTP server to compare the local clock with (host or host:port)
+        #[arg(long, default_value = DEFAULT_NTP_SERVER)]
+        ntp_server: String,
+
+        /// TURN server URL (optional, format: turn:host:port)
+        #[arg(long)]
+        turn_server: Option<String>,
+
+        /// TURN username (required if turn-server is set)
+        #[arg(long)]
+        turn_username: Option<String>,
+
+        /// TURN credential (required if turn-server is set)
+        #[arg(long)]
+        turn_credential: Option<String>,
+
+        /// TURN REST shared secret (coturn static-auth-secret), replaces username/credential
+        #[arg(long)]
+        turn_secret: Option<String>,
+
+        /// Per-server probe timeout in milliseconds
+        #[arg(long, default_value_t = 2000)]
+        timeout_ms: u64,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Wait until the session state meets every condition: exits 0 once it
+    /// does, non-zero on timeout (for shell scripts and CI jobs)
+    Wait {
+        /// Matchbox signalling server URL
+        #[arg(short = 's', long, default_value = "wss://match.konnektoren.help")]
+        server: String,
+
+        /// Session ID to watch (watching does not join the lobby)
+        #[arg(short = 'i', long, visible_alias = "session")]
+        session_id: String,
+
+        /// Condition like participants>=5 (metrics: participants, guests,
+        /// active, spectators, peers; repeat for several)
+        #[arg(short = 'u', long, required = true)]
+        until: Vec<String>,
+
+        /// Give up after this many seconds
+        #[arg(short = 't', long, default_value_t = 60)]
+        timeout: u64,
+
+        /// TURN server URL (optional, format: turn:host:port)
+        #[arg(long)]
+        turn_server: Option<String>,
+
+        /// TURN username (required if turn-server is set)
+        #[arg(long)]
+        turn_username: Option<String>,
+
+        /// TURN credential (required if turn-server is set)
+        #[arg(long)]
+        turn_credential: Option<String>,
+
+        /// TURN REST shared secret (coturn static-auth-secret), replaces username/credential
+        #[arg(long)]
+        turn_secret: Option<String>,
+    },
+
+    /// Run a deterministic, seed-driven session simulation (no network needed),
+    /// or load-test a session with headless guest bots (--behavior)
+    Simulate {
+        /// Simulation seed (a failing seed replays the same run)
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Number of consecutive seeds to run, starting at --seed
+        #[arg(long, default_value_t = 1)]
+        runs: u64,
+
+        /// Number of guests
+        #[arg(short = 'g', long, default_value_t = 3)]
+        guests: usize,
+
+        /// Scripted ticks (workload + faults) before the session must settle
+        #[arg(long, default_value_t = 500)]
+        ticks: u64,
+
+        /// Message latency in ticks
+        #[arg(long, default_value_t = 0)]
+        latency: u32,
+
+        /// Extra random latency of up to this many ticks
+        #[arg(long, default_value_t = 0)]
+        jitter: u32,
+
+        /// Packet loss in percent
+        #[arg(long, default_value_t = 0.0)]
+        loss: f64,
+
+        /// Reordered messages in percent
+        #[arg(long, default_value_t = 0.0)]
+        reorder: f64,
+
+        /// Random guest partitions to inject (each healed later)
+        #[arg(long, default_value_t = 0)]
+        partitions: usize,
+
+        /// Print the reports as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Spawn headless guest bots acting like this instead (load testing)
+        #[arg(long, value_enum)]
+        behavior: Option<BotBehavior>,
+
+        /// Session the bots join (default: a local in-process host)
+        #[arg(short = 'i', long)]
+        session_id: Option<String>,
+
+        /// Matchbox signalling server URL (bots joining --session-id)
+        #[arg(short = 's', long, default_value = "wss://match.konnektoren.help")]
+        server: String,
+
+        /// Milliseconds between bot actions
+        #[arg(long, default_value_t = 1000)]
+        action_interval_ms: u64,
+
+        /// Stop the bots after this many seconds (default: run until Ctrl+C)
+        #[arg(long)]
+        duration_secs: Option<u64>,
+    },
+
+    /// Host a session with simulated guests entirely in-process (no signalling
+    /// server), running echo activities for them to play
+    Local {
+        /// Lobby name
+        #[arg(short = 'l', long, default_value = "Local Lobby")]
+        lobby_name: String,
+
+        /// Host display name
+        #[arg(short = 'n', long, default_value = "Host")]
+        name: String,
+
+        /// Number of simulated guests
+        #[arg(short = 'g', long, default_value_t = 3)]
+        guests: usize,
+
+        /// What the simulated guests do
+        #[arg(long, value_enum, default_value_t = BotBehavior::Submit)]
+        behavior: BotBehavior,
+
+        /// Seed of the guests' decisions
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Milliseconds between guest actions
+        #[arg(long, default_value_t = 1000)]
+        action_interval_ms: u64,
+
+        /// Stop after this many seconds (default: run until Ctrl+C)
+        #[arg(long)]
+        duration_secs: Option<u64>,
+
+        /// Print the final stats as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Measure local throughput of command processing, codecs and snapshots
+    Bench {
+        /// Lobby sizes (guests) to measure, comma separated
+        #[arg(long, value_delimiter = ',', default_values_t = [10, 100, 1000])]
+        sizes: Vec<usize>,
+
+        /// Repetitions per measurement
+        #[arg(long, default_value_t = 1000)]
+        iterations: usize,
+
+        /// Print the results as JSON
+        #[arg(long)]
+        json: bool,
     },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
     // 🆕 Initialize logging
     #[cfg(feature = "console")]
     let log_config = if std::env::var("TOKIO_CONSOLE").is_ok() {
@@ -97,57 +446,536 @@ async fn main() -> Result<()> {
         LogConfig::default()
     };
 
+    // Keep stdout clean for JSON lines
+    let log_config = if cli.json_output() {
+        log_config.with_stderr()
+    } else {
+        log_config
+    };
+    let log_config = match &cli.log_file {
+        Some(path) => log_config.with_file_output(path.display().to_string()),
+        None => log_config,
+    };
+
     log_config
         .init()
         .map_err(konnekt_session_cli::CliError::InvalidInput)?;
 
-    let cli = Cli::parse();
-
     match cli.command {
         Commands::CreateHost {
             server,
             lobby_name,
             name,
             seed,
+            resume,
+            output,
+            qr,
+            join_url,
+            script,
+            turn_server,
+            turn_username,
+            turn_credential,
+            turn_secret,
+        } => {
+            let ice_servers =
+                build_ice_servers(turn_server, turn_username, turn_credential, turn_secret)?;
+            let script = script.as_deref().map(ScriptFile::load).transpose()?;
+            if script.is_some() && output == OutputFormat::Json {
+                return Err(konnekt_session_cli::CliError::InvalidConfig(
+                    "--script needs --output text".to_string(),
+                ));
+            }
+            let output = HostOutput {
+                format: output,
+                qr,
+                join_url,
+                script,
+            };
+            create_host(
+                &server,
+                &lobby_name,
+                &name,
+                seed,
+                resume,
+                ice_servers,
+                output,
+            )
+            .await?;
+        }
+        Commands::Daemon {
+            server,
+            lobby_name,
+            name,
+            seed,
+            resume,
+            socket,
+            listen,
+            webhooks,
+            script,
             turn_server,
             turn_username,
             turn_credential,
+            turn_secret,
         } => {
-            let ice_servers = build_ice_servers(turn_server, turn_username, turn_credential)?;
-            create_host(&server, &lobby_name, &name, seed, ice_servers).await?;
+            let ice_servers =
+                build_ice_servers(turn_server, turn_username, turn_credential, turn_secret)?;
+            let output = DaemonOutput {
+                listener: bind_control_api(socket, listen).await?,
+                webhooks,
+                script: script.as_deref().map(ScriptFile::load).transpose()?,
+            };
+            run_host_daemon(
+                &server,
+                &lobby_name,
+                &name,
+                seed,
+                resume,
+                ice_servers,
+                output,
+            )
+            .await?;
         }
         Commands::Join {
             server,
             session_id,
             name,
+            output,
+            turn_server,
+            turn_username,
+            turn_credential,
+            turn_secret,
+        } => {
+            let ice_servers =
+                build_ice_servers(turn_server, turn_username, turn_credential, turn_secret)?;
+            join_session(&server, &session_id, &name, ice_servers, output).await?;
+        }
+        Commands::Doctor {
+            server,
+            ntp_server,
+            turn_server,
+            turn_username,
+            turn_credential,
+            turn_secret,
+            timeout_ms,
+            json,
+        } => {
+            let ice_servers =
+                build_ice_servers(turn_server, turn_username, turn_credential, turn_secret)?;
+            run_doctor(
+                server,
+                ntp_server,
+                ice_servers,
+                Duration::from_millis(timeout_ms),
+                json,
+            )
+            .await?;
+        }
+        Commands::Wait {
+            server,
+            session_id,
+            until,
+            timeout,
             turn_server,
             turn_username,
             turn_credential,
+            turn_secret,
+        } => {
+            let conditions = until
+                .iter()
+                .map(|condition| WaitCondition::parse(condition))
+                .collect::<Result<Vec<_>>>()?;
+            let ice_servers =
+                build_ice_servers(turn_server, turn_username, turn_credential, turn_secret)?;
+            run_wait(
+                &server,
+                &session_id,
+                &conditions,
+                Duration::from_secs(timeout),
+                ice_servers,
+            )
+            .await?;
+        }
+        Commands::Simulate {
+            seed,
+            guests,
+            json,
+            behavior: Some(behavior),
+            session_id,
+            server,
+            action_interval_ms,
+            duration_secs,
+            ..
         } => {
-            let ice_servers = build_ice_servers(turn_server, turn_username, turn_credential)?;
-            join_session(&server, &session_id, &name, ice_servers).await?;
+            let interval = Duration::from_millis(action_interval_ms.max(1));
+            let duration = duration_secs.map(Duration::from_secs);
+
+            match session_id {
+                Some(session_id) => {
+                    let swarm = BotSwarm::new(behavior, seed);
+                    join_bot_swarm(
+                        swarm,
+                        &server,
+                        &session_id,
+                        guests,
+                        interval,
+                        duration,
+                        json,
+                    )
+                    .await?
+                }
+                None => {
+                    let config = LocalConfig::new()
+                        .with_names("Bot Lobby", "Host")
+                        .with_guests(guests)
+                        .with_behavior(behavior, seed);
+                    run_local_session(&config, interval, duration, json).await?
+                }
+            }
+        }
+        Commands::Simulate {
+            seed,
+            runs,
+            guests,
+            ticks,
+            latency,
+            jitter,
+            loss,
+            reorder,
+            partitions,
+            json,
+            behavior: None,
+            ..
+        } => {
+            let conditions = NetworkConditions::perfect()
+                .with_latency(latency)
+                .with_jitter(jitter)
+                .with_loss(loss / 100.0)
+                .with_reorder(reorder / 100.0);
+            let configs = (seed..seed.saturating_add(runs)).map(|seed| {
+                SimulationConfig::new(seed)
+                    .with_guests(guests)
+                    .with_ticks(ticks)
+                    .with_conditions(conditions)
+                    .with_random_faults(partitions)
+            });
+            run_simulations(configs, json)?;
+        }
+        Commands::Local {
+            lobby_name,
+            name,
+            guests,
+            behavior,
+            seed,
+            action_interval_ms,
+            duration_secs,
+            json,
+        } => {
+            let config = LocalConfig::new()
+                .with_names(&lobby_name, &name)
+                .with_guests(guests)
+                .with_behavior(behavior, seed);
+            let interval = Duration::from_millis(action_interval_ms.max(1));
+            let duration = duration_secs.map(Duration::from_secs);
+            run_local_session(&config, interval, duration, json).await?;
+        }
+        Commands::Bench {
+            sizes,
+            iterations,
+            json,
+        } => {
+            let config = BenchConfig::new()
+                .with_sizes(sizes)
+                .with_iterations(iterations);
+            run_bench(&config, json)?;
         }
     }
 
     Ok(())
 }
 
+/// Run simulations in order, stopping at the first seed that diverges
+fn run_simulations(configs: impl Iterator<Item = SimulationConfig>, json: bool) -> Result<()> {
+    for config in configs {
+        let report = Simulation::new(config)?.run();
+
+        if json {
+            println!("{}", serde_json::to_string(&report)?);
+        } else {
+            let status = if report.converged() { "✅" } else { "❌" };
+            println!(
+                "{} seed {:<6} ticks {:<5} participants {:<3} actions {:<4} dropped {:<5} converged at {}",
+                status,
+                report.seed,
+                report.ticks,
+                report.participants,
+                report.actions,
+                report.messages_dropped,
+                report
+                    .converged_at
+                    .map(|tick| tick.to_string())
+                    .unwrap_or_else(|| "-".to_string())
+            );
+        }
+
+        if !report.converged() {
+            return Err(konnekt_session_cli::CliError::SimulationDiverged { seed: report.seed });
+        }
+    }
+
+    Ok(())
+}
+
+/// Run the benchmarks and print them as a table (or JSON)
+fn run_bench(config: &BenchConfig, json: bool) -> Result<()> {
+    if cfg!(debug_assertions) {
+        info!("⚠️  Debug build: use --release for meaningful numbers");
+    }
+
+    let results = run_benchmarks(config);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
+
+    println!(
+        "{:<18} {:>6} {:>8} {:>14} {:>10} {:>10}",
+        "benchmark", "size", "ops", "ops/s", "µs/op", "bytes"
+    );
+    for result in &results {
+        print_bench_result(result);
+    }
+
+    Ok(())
+}
+
+fn print_bench_result(result: &BenchResult) {
+    println!(
+        "{:<18} {:>6} {:>8} {:>14.0} {:>10.2} {:>10}",
+        result.name,
+        result
+            .lobby_size
+            .map(|size| size.to_string())
+            .unwrap_or_else(|| "-".to_string()),
+        result.ops,
+        result.ops_per_sec(),
+        result.us_per_op(),
+        result
+            .bytes
+            .map(|bytes| bytes.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    );
+}
+
+/// Spawn `guests` bots into a remote session and drive them
+async fn join_bot_swarm(
+    mut swarm: BotSwarm,
+    server: &str,
+    session_id: &str,
+    guests: usize,
+    interval: Duration,
+    duration: Option<Duration>,
+    json: bool,
+) -> Result<()> {
+    let session_id = SessionId::parse(session_id)?;
+    info!("🤖 Spawning {} bots into session {}", guests, session_id);
+
+    for i in 1..=guests {
+        let (session_loop, _) = P2PLoopBuilder::new()
+            .build_session_guest(
+                server,
+                session_id.clone(),
+                IceServer::default_stun_servers(),
+            )
+            .await?;
+        swarm.add_bot(format!("Bot {i}"), session_loop);
+    }
+
+    drive_bot_swarm(swarm, None, interval, duration, json).await
+}
+
+/// Drive simulated guests against an in-process host that keeps echo runs going
+async fn run_local_session(
+    config: &LocalConfig,
+    interval: Duration,
+    duration: Option<Duration>,
+    json: bool,
+) -> Result<()> {
+    let LocalSession {
+        mut host, guests, ..
+    } = LocalSession::start(config)?;
+
+    // The host only runs activities, the bots play them
+    let lobby_id = host.lobby_id();
+    let host_id = host.get_lobby().map(|lobby| lobby.host_id());
+    if let Some(host_id) = host_id {
+        host.submit_command(DomainCommand::ToggleParticipationMode {
+            lobby_id,
+            participant_id: host_id,
+            requester_id: host_id,
+        })?;
+    }
+
+    drive_bot_swarm(guests, Some(host), interval, duration, json).await
+}
+
+/// Poll the bots (and the local host), let them act every `interval` and
+/// report their stats until Ctrl+C or `duration` ran out
+async fn drive_bot_swarm<C: NetworkConnection>(
+    mut swarm: BotSwarm<C>,
+    mut host: Option<SessionLoop<C>>,
+    interval: Duration,
+    duration: Option<Duration>,
+    json: bool,
+) -> Result<()> {
+    let started = tokio::time::Instant::now();
+    let mut last_step = started;
+    let mut last_report = started;
+
+    loop {
+        // Wake on input, for the next step, or to let timeouts and heartbeats run
+        let deadline =
+            (tokio::time::Instant::now() + DEFAULT_IDLE_INTERVAL).min(last_step + interval);
+        tokio::select! {
+            _ = wait_for_input(&mut swarm, host.as_mut(), deadline) => {}
+            _ = tokio::signal::ctrl_c() => {
+                info!("Received Ctrl+C, stopping bots...");
+                break;
+            }
+        }
+
+        swarm.poll();
+        if let Some(host) = host.as_mut() {
+            host.poll();
+            keep_echo_run_going(host)?;
+        }
+
+        if last_step.elapsed() >= interval {
+            swarm.step();
+            last_step = tokio::time::Instant::now();
+        }
+
+        if !json && last_report.elapsed() >= Duration::from_secs(5) {
+            print_swarm_stats(&swarm);
+            last_report = tokio::time::Instant::now();
+        }
+
+        if duration.is_some_and(|duration| started.elapsed() >= duration) {
+            break;
+        }
+    }
+
+    // Leave gracefully and give the leaves a moment to go out
+    swarm.leave_all();
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(500);
+    while tokio::time::Instant::now() < deadline {
+        swarm.poll();
+        if let Some(host) = host.as_mut() {
+            host.poll();
+        }
+        wait_for_input(&mut swarm, host.as_mut(), deadline).await;
+    }
+
+    if json {
+        let stats = swarm.stats();
+        println!(
+            "{}",
+            serde_json::json!({
+                "bots": swarm.len(),
+                "joins": stats.joins,
+                "leaves": stats.leaves,
+                "submissions": stats.submissions,
+                "toggles": stats.toggles,
+                "errors": stats.errors,
+                "elapsed_ms": started.elapsed().as_millis() as u64,
+            })
+        );
+    } else {
+        print_swarm_stats(&swarm);
+    }
+
+    Ok(())
+}
+
+/// Wait until a bot's or the host's connection has events, or `deadline` passed
+async fn wait_for_input<C: NetworkConnection>(
+    swarm: &mut BotSwarm<C>,
+    mut host: Option<&mut SessionLoop<C>>,
+    deadline: tokio::time::Instant,
+) {
+    let input = futures::future::poll_fn(|cx| {
+        let swarm_ready = swarm.poll_ready(cx).is_ready();
+        let host_ready = host
+            .as_deref_mut()
+            .is_some_and(|host| host.poll_ready(cx).is_ready());
+        if swarm_ready || host_ready {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    });
+    let _ = tokio::time::timeout_at(deadline, input).await;
+}
+
+/// Start another echo run on the local host whenever none is in progress
+fn keep_echo_run_going<C: NetworkConnection>(host: &mut SessionLoop<C>) -> Result<()> {
+    let lobby_id = host.lobby_id();
+    let Some(lobby) = host.get_lobby() else {
+        return Ok(());
+    };
+    if lobby.has_active_run() || lobby.active_participant_ids().is_empty() {
+        return Ok(());
+    }
+
+    let prompt = format!("Echo {}", Uuid::new_v4().simple());
+    host.submit_command(DomainCommand::QueueActivity {
+        lobby_id,
+        config: ActivityConfig::new(
+            EchoChallenge::activity_type().to_string(),
+            "Echo".to_string(),
+            EchoChallenge::new(prompt).to_config(),
+        ),
+    })?;
+    host.submit_command(DomainCommand::StartNextRun { lobby_id })?;
+    Ok(())
+}
+
+fn print_swarm_stats<C: NetworkConnection>(swarm: &BotSwarm<C>) {
+    let stats = swarm.stats();
+    println!(
+        "🤖 joined {}/{} | joins {} leaves {} submissions {} toggles {} errors {}",
+        swarm.joined(),
+        swarm.len(),
+        stats.joins,
+        stats.leaves,
+        stats.submissions,
+        stats.toggles,
+        stats.errors
+    );
+}
+
 fn build_ice_servers(
     turn_server: Option<String>,
     turn_username: Option<String>,
     turn_credential: Option<String>,
+    turn_secret: Option<String>,
 ) -> Result<Vec<IceServer>> {
     let mut ice_servers = IceServer::default_stun_servers();
 
     if let Some(turn_url) = turn_server {
-        match (turn_username, turn_credential) {
-            (Some(username), Some(credential)) => {
+        match (turn_username, turn_credential, turn_secret) {
+            (_, _, Some(secret)) => {
+                info!("Using TURN server with REST credentials: {}", turn_url);
+                ice_servers.push(IceServer::turn_from_rest_endpoint(turn_url, secret));
+            }
+            (Some(username), Some(credential), None) => {
                 info!("Using TURN server: {}", turn_url);
                 ice_servers.push(IceServer::turn(turn_url, username, credential));
             }
             _ => {
                 return Err(konnekt_session_cli::CliError::InvalidInput(
-                    "TURN server requires both username and credential".to_string(),
+                    "TURN server requires either --turn-secret or both username and credential"
+                        .to_string(),
                 ));
             }
         }
@@ -156,17 +984,28 @@ fn build_ice_servers(
     Ok(ice_servers)
 }
 
-async fn create_host(
+/// Create (or resume) a hosted session
+async fn build_host(
     server: &str,
     lobby_name: &str,
     host_name: &str,
     seed: Option<String>,
+    resume: Option<PathBuf>,
     ice_servers: Vec<IceServer>,
-) -> Result<()> {
+) -> Result<(SessionLoop, SessionId)> {
     info!("Creating new session as host '{}'", host_name);
 
-    let builder = P2PLoopBuilder::new();
-    let (mut session_loop, session_id) = if let Some(seed) = seed {
+    let mut builder = P2PLoopBuilder::new();
+    let resuming = resume.as_ref().is_some_and(|path| path.exists());
+    if let Some(path) = resume {
+        if resuming {
+            info!("♻️  Resuming session from {}", path.display());
+        }
+        builder = builder.host_snapshot(path);
+    }
+
+    // A resumed session keeps the ID stored in its snapshot
+    let host = if let Some(seed) = seed.filter(|_| !resuming) {
         let deterministic_id = session_id_from_seed(&seed);
         info!(
             "Using deterministic session id derived from seed '{}' -> {}",
@@ -191,7 +1030,20 @@ async fn create_host(
             )
             .await?
     };
+    Ok(host)
+}
 
+async fn create_host(
+    server: &str,
+    lobby_name: &str,
+    host_name: &str,
+    seed: Option<String>,
+    resume: Option<PathBuf>,
+    ice_servers: Vec<IceServer>,
+    output: HostOutput,
+) -> Result<()> {
+    let (mut session_loop, session_id) =
+        build_host(server, lobby_name, host_name, seed, resume, ice_servers).await?;
     let lobby_id = session_loop.lobby_id();
 
     info!("✅ Session created successfully!");
@@ -203,6 +1055,9 @@ async fn create_host(
         "  konnekt-cli join --server {} --session-id {}",
         server, session_id
     );
+    if output.qr {
+        print_join_qr(&session_id, output.join_url.as_deref(), output.format)?;
+    }
     info!("");
     info!("=== Session Active ===");
     info!("  Press Ctrl+C to quit");
@@ -211,7 +1066,90 @@ async fn create_host(
     // Wait for peer ID to be assigned
     wait_for_peer_id(&mut session_loop).await?;
 
-    run_event_loop(session_loop, true, session_id).await
+    match output.format {
+        OutputFormat::Text => run_event_loop(session_loop, true, session_id, output.script).await,
+        OutputFormat::Json => run_json_loop(session_loop, session_id).await,
+    }
+}
+
+/// Listen on `listen` if given, else on the Unix socket
+async fn bind_control_api(socket: PathBuf, listen: Option<SocketAddr>) -> Result<ControlListener> {
+    if let Some(addr) = listen {
+        return ControlListener::bind_tcp(addr).await;
+    }
+
+    #[cfg(unix)]
+    {
+        ControlListener::bind_unix(socket)
+    }
+    #[cfg(not(unix))]
+    {
+        Err(konnekt_session_cli::CliError::InvalidInput(format!(
+            "Unix sockets are not available here, use --listen instead of {}",
+            socket.display()
+        )))
+    }
+}
+
+/// Host a session without a terminal UI until Ctrl+C
+async fn run_host_daemon(
+    server: &str,
+    lobby_name: &str,
+    host_name: &str,
+    seed: Option<String>,
+    resume: Option<PathBuf>,
+    ice_servers: Vec<IceServer>,
+    output: DaemonOutput,
+) -> Result<()> {
+    let (mut session_loop, session_id) =
+        build_host(server, lobby_name, host_name, seed, resume, ice_servers).await?;
+    let webhooks = Webhooks::start(session_id.to_string(), output.webhooks)?;
+
+    info!("✅ Session created successfully!");
+    info!("📋 Session ID: {}", session_id);
+    info!("📋 Lobby ID: {}", session_loop.lobby_id());
+    info!(
+        "  konnekt-cli join --server {} --session-id {}",
+        server, session_id
+    );
+
+    wait_for_peer_id(&mut session_loop).await?;
+
+    let shutdown = async {
+        let _ = tokio::signal::ctrl_c().await;
+        info!("Received Ctrl+C, shutting down...");
+    };
+    run_daemon(
+        AsyncSessionLoop::new(session_loop),
+        session_id,
+        output.listener,
+        webhooks,
+        output.script,
+        shutdown,
+    )
+    .await;
+
+    info!("✅ Shutdown complete");
+    Ok(())
+}
+
+/// Print the join link as a QR code (to stderr in JSON mode, which owns stdout)
+fn print_join_qr(
+    session_id: &SessionId,
+    join_url: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    let link = join_link(session_id, join_url);
+    let mut text = render_qr(&link)?;
+    text.push(format!("Scan to join: {}", link));
+
+    for line in text {
+        match format {
+            OutputFormat::Text => println!("{}", line),
+            OutputFormat::Json => eprintln!("{}", line),
+        }
+    }
+    Ok(())
 }
 
 fn session_id_from_seed(seed: &str) -> SessionId {
@@ -224,6 +1162,7 @@ async fn join_session(
     session_id_str: &str,
     guest_name: &str,
     ice_servers: Vec<IceServer>,
+    output: OutputFormat,
 ) -> Result<()> {
     info!("Joining session as guest '{}'", guest_name);
 
@@ -257,7 +1196,10 @@ async fn join_session(
     info!("  Press Ctrl+C to quit");
     info!("");
 
-    run_event_loop(session_loop, false, session_id).await
+    match output {
+        OutputFormat::Text => run_event_loop(session_loop, false, session_id, None).await,
+        OutputFormat::Json => run_json_loop(session_loop, session_id).await,
+    }
 }
 
 /// Wait for peer ID to be assigned by Matchbox
@@ -329,8 +1271,12 @@ async fn run_event_loop(
     session_loop: SessionLoop,
     is_host: bool,
     session_id: SessionId,
+    script: Option<ScriptFile>,
 ) -> Result<()> {
-    let runtime = SessionRuntime::spawn(session_loop, session_id);
+    let runtime = match script {
+        Some(script) => SessionRuntime::spawn_with_script(session_loop, session_id, script),
+        None => SessionRuntime::spawn(session_loop, session_id),
+    };
     let mut interval = tokio::time::interval(Duration::from_millis(100));
     let mut last_participant_count = 0;
 
@@ -344,6 +1290,11 @@ async fn run_event_loop(
 
                 // PRESENTATION: Display peer connections
                 debug!("Connected peers: {}", snapshot.peer_count);
+
+                if snapshot.closed {
+                    info!("📜 Host script closed the session");
+                    break;
+                }
             }
 
             _ = tokio::signal::ctrl_c() => {
@@ -365,6 +1316,187 @@ async fn run_event_loop(
     Ok(())
 }
 
+/// Scriptable mode: announce the session, then stream events to stdout and
+/// read commands from stdin (one JSON object per line) until Ctrl+C
+async fn run_json_loop(session_loop: SessionLoop, session_id: SessionId) -> Result<()> {
+    println!(
+        "{}",
+        serde_json::json!({
+            "session": {
+                "session_id": session_id.to_string(),
+                "lobby_id": session_loop.lobby_id(),
+                "is_host": session_loop.is_host(),
+            }
+        })
+    );
+
+    let stdin = tokio::io::BufReader::new(tokio::io::stdin());
+    let shutdown = async {
+        let _ = tokio::signal::ctrl_c().await;
+        info!("Received Ctrl+C, shutting down...");
+    };
+    run_json_driver(
+        AsyncSessionLoop::new(session_loop),
+        stdin,
+        std::io::stdout(),
+        shutdown,
+    )
+    .await?;
+
+    info!("✅ Shutdown complete");
+    Ok(())
+}
+
+/// Run connectivity diagnostics and print the report with pass/fail checks
+///
+/// Fails when any check fails, so scripts can gate on the exit code.
+async fn run_doctor(
+    server: String,
+    ntp_server: String,
+    ice_servers: Vec<IceServer>,
+    timeout: Duration,
+    json: bool,
+) -> Result<()> {
+    info!(
+        "🩺 Probing {} and {} ICE servers...",
+        server,
+        ice_servers.len()
+    );
+
+    let report = tokio::task::spawn_blocking(move || {
+        let mut report = run_diagnostics(&ice_servers, timeout);
+        report.signalling = Some(probe_signalling(&server, timeout));
+        report.clock = Some(probe_clock(&ntp_server, timeout));
+        report
+    })
+    .await
+    .map_err(|e| konnekt_session_cli::CliError::InvalidInput(format!("Diagnostics failed: {e}")))?;
+
+    let checks = report.checks();
+    let failed = checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Fail)
+        .count();
+
+    if json {
+        let mut output = serde_json::to_value(&report)?;
+        output["checks"] = serde_json::to_value(&checks)?;
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        print_doctor_report(&report, &checks);
+    }
+
+    if failed > 0 {
+        return Err(konnekt_session_cli::CliError::DiagnosticsFailed { failed });
+    }
+    Ok(())
+}
+
+fn print_doctor_report(
+    report: &konnekt_session_p2p::ConnectivityReport,
+    checks: &[konnekt_session_p2p::DiagnosticCheck],
+) {
+    println!("Connectivity report");
+    println!("  NAT type:       {}", report.nat_type);
+    println!("  Gathering time: {}ms", report.gathering_time_ms);
+    println!(
+        "  Public address: {}",
+        match report.public_addresses().as_slice() {
+            [] => "-".to_string(),
+            addresses => addresses.join(", "),
+        }
+    );
+
+    println!("Servers");
+    for probe in &report.servers {
+        let status = if probe.reachable { "✅" } else { "❌" };
+        let rtt = probe
+            .rtt_ms
+            .map(|ms| format!("{ms}ms"))
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "  {} {:<40} {:>6}  {}",
+            status,
+            probe.url,
+            rtt,
+            probe.detail.as_deref().unwrap_or("")
+        );
+    }
+
+    println!("Candidates");
+    for candidate in &report.candidates {
+        println!(
+            "  {:?} {} {}",
+            candidate.kind, candidate.protocol, candidate.address
+        );
+    }
+
+    println!();
+    println!("Checks");
+    for check in checks {
+        let status = match check.status {
+            CheckStatus::Pass => "✅",
+            CheckStatus::Warn => "⚠️ ",
+            CheckStatus::Fail => "❌",
+        };
+        println!("  {} {:<12} {}", status, check.name, check.detail);
+        if let Some(advice) = &check.advice {
+            println!("     → {advice}");
+        }
+    }
+}
+
+/// Watch a session without joining it until every condition holds
+async fn run_wait(
+    server: &str,
+    session_id_str: &str,
+    conditions: &[WaitCondition],
+    timeout: Duration,
+    ice_servers: Vec<IceServer>,
+) -> Result<()> {
+    let session_id = SessionId::parse(session_id_str)?;
+    let deadline = std::time::Instant::now() + timeout;
+    let wanted = conditions
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" and ");
+    info!("⏳ Waiting up to {}s until {}", timeout.as_secs(), wanted);
+
+    let (mut session_loop, _) = P2PLoopBuilder::new()
+        .build_session_guest(server, session_id, ice_servers)
+        .await?;
+
+    loop {
+        session_loop.poll();
+
+        let peers = session_loop.connected_peers().len();
+        let observed = session_loop.get_lobby().map(|lobby| {
+            let values = conditions
+                .iter()
+                .map(|c| format!("{} = {}", c.metric.name(), c.metric.value(lobby, peers)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            (conditions.iter().all(|c| c.holds(lobby, peers)), values)
+        });
+
+        match observed {
+            Some((true, values)) => {
+                println!("✅ {} ({})", wanted, values);
+                return Ok(());
+            }
+            _ if std::time::Instant::now() >= deadline => {
+                return Err(konnekt_session_cli::CliError::WaitTimedOut {
+                    secs: timeout.as_secs(),
+                    condition: wanted,
+                    observed: observed.map_or("lobby never synced".to_string(), |(_, v)| v),
+                });
+            }
+            _ => tokio::time::sleep(Duration::from_millis(50)).await,
+        }
+    }
+}
+
 /// Display lobby changes (presentation only)
 fn display_lobby_changes(lobby: Option<&konnekt_session_core::Lobby>, last_count: &mut usize) {
     if let Some(lobby) = lobby {
@@ -470,10 +1602,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_json_output_parsing() {
+        let cli = Cli::parse_from([
+            "konnekt-cli",
+            "join",
+            "--session-id",
+            "550e8400-e29b-41d4-a716-446655440000",
+            "--output",
+            "json",
+        ]);
+        assert!(cli.json_output());
+
+        let cli = Cli::parse_from(["konnekt-cli", "create-host"]);
+        assert!(!cli.json_output());
+        match cli.command {
+            Commands::CreateHost { output, .. } => assert_eq!(output, OutputFormat::Text),
+            _ => panic!("Expected CreateHost command"),
+        }
+    }
+
     #[test]
     fn test_turn_server_validation() {
         // TURN server without credentials should fail
-        let result = build_ice_servers(Some("turn:turn.example.com:3478".to_string()), None, None);
+        let result = build_ice_servers(
+            Some("turn:turn.example.com:3478".to_string()),
+            None,
+            None,
+            None,
+        );
 
         assert!(result.is_err());
 
@@ -482,9 +1639,214 @@ mod tests {
             Some("turn:turn.example.com:3478".to_string()),
             Some("user".to_string()),
             Some("pass".to_string()),
+            None,
         );
 
         assert!(result.is_ok());
+
+        // TURN server with a REST shared secret should derive credentials
+        let servers = build_ice_servers(
+            Some("turn:turn.example.com:3478".to_string()),
+            None,
+            None,
+            Some("secret".to_string()),
+        )
+        .unwrap();
+
+        let turn = servers.last().unwrap();
+        assert!(turn.rest_auth.is_some());
+        assert!(turn.username.is_some());
+        assert!(turn.credential.is_some());
+    }
+
+    #[test]
+    fn test_doctor_parsing() {
+        let cli = Cli::parse_from(["konnekt-cli", "doctor", "--json", "--timeout-ms", "500"]);
+
+        match cli.command {
+            Commands::Doctor {
+                server,
+                ntp_server,
+                json,
+                timeout_ms,
+                ..
+            } => {
+                assert!(json);
+                assert_eq!(timeout_ms, 500);
+                assert_eq!(server, "wss://match.konnektoren.help");
+                assert_eq!(ntp_server, DEFAULT_NTP_SERVER);
+            }
+            _ => panic!("Expected Doctor command"),
+        }
+    }
+
+    #[test]
+    fn test_wait_parsing() {
+        let cli = Cli::parse_from([
+            "konnekt-cli",
+            "wait",
+            "--session",
+            "550e8400-e29b-41d4-a716-446655440000",
+            "--until",
+            "participants>=5",
+            "--until",
+            "active>=4",
+            "--timeout",
+            "120",
+        ]);
+
+        match cli.command {
+            Commands::Wait {
+                session_id,
+                until,
+                timeout,
+                ..
+            } => {
+                assert_eq!(session_id, "550e8400-e29b-41d4-a716-446655440000");
+                assert_eq!(until, ["participants>=5", "active>=4"]);
+                assert_eq!(timeout, 120);
+            }
+            _ => panic!("Expected Wait command"),
+        }
+
+        // A condition is required
+        assert!(Cli::try_parse_from(["konnekt-cli", "wait", "--session-id", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_simulate_parsing() {
+        let cli = Cli::parse_from([
+            "konnekt-cli",
+            "simulate",
+            "--seed",
+            "42",
+            "--guests",
+            "5",
+            "--loss",
+            "20",
+            "--partitions",
+            "2",
+        ]);
+
+        match cli.command {
+            Commands::Simulate {
+                seed,
+                runs,
+                guests,
+                loss,
+                partitions,
+                ..
+            } => {
+                assert_eq!(seed, 42);
+                assert_eq!(runs, 1);
+                assert_eq!(guests, 5);
+                assert_eq!(loss, 20.0);
+                assert_eq!(partitions, 2);
+            }
+            _ => panic!("Expected Simulate command"),
+        }
+    }
+
+    #[test]
+    fn test_simulate_bots_parsing() {
+        let cli = Cli::parse_from([
+            "konnekt-cli",
+            "simulate",
+            "--guests",
+            "25",
+            "--behavior",
+            "random",
+        ]);
+
+        match cli.command {
+            Commands::Simulate {
+                guests,
+                behavior,
+                session_id,
+                action_interval_ms,
+                duration_secs,
+                ..
+            } => {
+                assert_eq!(guests, 25);
+                assert_eq!(behavior, Some(BotBehavior::Random));
+                assert_eq!(session_id, None);
+                assert_eq!(action_interval_ms, 1000);
+                assert_eq!(duration_secs, None);
+            }
+            _ => panic!("Expected Simulate command"),
+        }
+    }
+
+    #[test]
+    fn test_bench_parsing() {
+        let cli = Cli::parse_from([
+            "konnekt-cli",
+            "bench",
+            "--sizes",
+            "5,50",
+            "--iterations",
+            "20",
+            "--json",
+        ]);
+
+        match cli.command {
+            Commands::Bench {
+                sizes,
+                iterations,
+                json,
+            } => {
+                assert_eq!(sizes, vec![5, 50]);
+                assert_eq!(iterations, 20);
+                assert!(json);
+            }
+            _ => panic!("Expected Bench command"),
+        }
+    }
+
+    #[test]
+    fn test_create_host_script_parsing() {
+        let cli = Cli::parse_from(["konnekt-cli", "create-host", "--script", "lesson.yaml"]);
+
+        match cli.command {
+            Commands::CreateHost { script, .. } => {
+                assert_eq!(script, Some(PathBuf::from("lesson.yaml")))
+            }
+            _ => panic!("Expected CreateHost command"),
+        }
+    }
+
+    #[test]
+    fn test_log_file_parsing() {
+        let cli = Cli::parse_from(["konnekt-cli", "bench", "--log-file", "logs/cli.jsonl"]);
+        assert_eq!(cli.log_file, Some(PathBuf::from("logs/cli.jsonl")));
+    }
+
+    #[test]
+    fn test_local_parsing() {
+        let cli = Cli::parse_from(["konnekt-cli", "local", "-g", "5", "--duration-secs", "10"]);
+
+        match cli.command {
+            Commands::Local {
+                guests,
+                behavior,
+                duration_secs,
+                json,
+                ..
+            } => {
+                assert_eq!(guests, 5);
+                assert_eq!(behavior, BotBehavior::Submit);
+                assert_eq!(duration_secs, Some(10));
+                assert!(!json);
+            }
+            _ => panic!("Expected Local command"),
+        }
+    }
+
+    #[test]
+    fn test_simulation_run_converges() {
+        let config = SimulationConfig::new(3).with_guests(2).with_ticks(100);
+
+        assert!(run_simulations(std::iter::once(config), true).is_ok());
     }
 
     #[test]
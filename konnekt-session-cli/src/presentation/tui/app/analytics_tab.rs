@@ -0,0 +1,118 @@
+use crossterm::event::KeyCode;
+use konnekt_session_core::{DomainEvent, Lobby, Projection, SessionMetrics};
+
+/// Analytics tab state (presentation only)
+pub struct AnalyticsTab {
+    /// Kept up to date from the domain events we see
+    metrics: SessionMetrics,
+
+    /// Whether the participants already in the lobby were counted
+    seeded: bool,
+
+    /// Selected run index
+    selected_run: usize,
+}
+
+impl Default for AnalyticsTab {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnalyticsTab {
+    pub fn new() -> Self {
+        Self {
+            metrics: SessionMetrics::new(),
+            seeded: false,
+            selected_run: 0,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('j') | KeyCode::Down => {
+                let max = self.metrics.runs().len().saturating_sub(1);
+                self.selected_run = (self.selected_run + 1).min(max);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected_run = self.selected_run.saturating_sub(1);
+            }
+            _ => {}
+        }
+    }
+
+    /// Count the participants of the first lobby snapshot, who joined
+    /// before we saw any event
+    pub fn seed(&mut self, lobby: &Lobby) {
+        if !self.seeded {
+            self.seeded = true;
+            self.metrics.apply(&DomainEvent::LobbyCreated {
+                lobby: lobby.clone(),
+            });
+        }
+    }
+
+    pub fn record(&mut self, event: &DomainEvent) {
+        if let DomainEvent::LobbyCreated { .. } = event {
+            self.seeded = true;
+        }
+        self.metrics.apply(event);
+    }
+
+    // Getters for rendering
+    pub fn metrics(&self) -> &SessionMetrics {
+        &self.metrics
+    }
+
+    pub fn selected_run(&self) -> usize {
+        self.selected_run
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_core::Participant;
+    use konnekt_session_core::domain::{ActivityConfig, ActivityResult, RunStatus};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_counts_players_of_the_first_snapshot() {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let host_id = host.id();
+        let mut lobby = Lobby::new("Class".to_string(), host).unwrap();
+        lobby
+            .add_guest(Participant::new_guest("Bob".to_string()).unwrap())
+            .unwrap();
+        let lobby_id = lobby.id();
+
+        let mut tab = AnalyticsTab::new();
+        tab.seed(&lobby);
+        // Later snapshots don't count anyone twice
+        tab.seed(&lobby);
+        let run_id = Uuid::new_v4();
+        tab.record(&DomainEvent::RunStarted {
+            lobby_id,
+            run_id,
+            config: ActivityConfig::new(
+                "quiz".to_string(),
+                "Quiz".to_string(),
+                serde_json::json!({}),
+            ),
+            started_at_ms: None,
+        });
+        tab.record(&DomainEvent::ResultSubmitted {
+            lobby_id,
+            run_id,
+            result: ActivityResult::new(run_id, host_id).with_time(1_500),
+        });
+
+        let run = &tab.metrics().runs()[0];
+        assert_eq!(run.status, RunStatus::InProgress);
+        assert_eq!((run.eligible, run.submitted), (2, 1));
+        assert_eq!(tab.metrics().average_answer_ms(), Some(1_500));
+
+        tab.handle_key(KeyCode::Down);
+        assert_eq!(tab.selected_run(), 0);
+    }
+}
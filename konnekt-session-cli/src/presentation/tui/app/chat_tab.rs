@@ -0,0 +1,208 @@
+use crossterm::event::KeyCode;
+use konnekt_session_core::ChatMessage;
+use uuid::Uuid;
+
+use crate::presentation::tui::app::UserAction;
+
+/// Chat tab state (presentation only)
+pub struct ChatTab {
+    messages: Vec<ChatMessage>,
+    last_seen: Option<Uuid>,
+    unread: usize,
+
+    // Input line
+    input: String,
+    cursor_position: usize,
+
+    /// Messages scrolled back from the newest one
+    scroll_offset: usize,
+}
+
+impl Default for ChatTab {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChatTab {
+    pub fn new() -> Self {
+        Self {
+            messages: Vec::new(),
+            last_seen: None,
+            unread: 0,
+            input: String::new(),
+            cursor_position: 0,
+            scroll_offset: 0,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) -> Option<UserAction> {
+        match key {
+            KeyCode::Char(c) => {
+                self.input.insert(self.byte_index(), c);
+                self.cursor_position += 1;
+                None
+            }
+
+            KeyCode::Backspace => {
+                if self.cursor_position > 0 {
+                    self.cursor_position -= 1;
+                    self.input.remove(self.byte_index());
+                }
+                None
+            }
+
+            KeyCode::Left => {
+                self.cursor_position = self.cursor_position.saturating_sub(1);
+                None
+            }
+
+            KeyCode::Right => {
+                self.cursor_position = (self.cursor_position + 1).min(self.input.chars().count());
+                None
+            }
+
+            KeyCode::Up | KeyCode::PageUp => {
+                let max = self.messages.len().saturating_sub(1);
+                self.scroll_offset = (self.scroll_offset + 1).min(max);
+                None
+            }
+
+            KeyCode::Down | KeyCode::PageDown => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(1);
+                None
+            }
+
+            KeyCode::End => {
+                self.scroll_offset = 0;
+                None
+            }
+
+            KeyCode::Enter => {
+                if self.input.trim().is_empty() {
+                    return None;
+                }
+                let text = std::mem::take(&mut self.input);
+                self.cursor_position = 0;
+                self.scroll_offset = 0;
+                Some(UserAction::SendChat(text))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Take the lobby's chat history; messages from others arriving while
+    /// the tab is hidden count as unread
+    pub fn update_messages(
+        &mut self,
+        messages: &[ChatMessage],
+        local_id: Option<Uuid>,
+        visible: bool,
+    ) {
+        let first_new = self
+            .last_seen
+            .and_then(|id| messages.iter().position(|m| m.id() == id))
+            .map_or(0, |pos| pos + 1);
+
+        if visible {
+            self.unread = 0;
+        } else {
+            self.unread += messages[first_new..]
+                .iter()
+                .filter(|m| Some(m.author_id()) != local_id)
+                .count();
+        }
+
+        // Keep the scrolled-back view in place while new messages arrive
+        if self.scroll_offset > 0 {
+            self.scroll_offset = (self.scroll_offset + messages.len() - first_new)
+                .min(messages.len().saturating_sub(1));
+        }
+
+        self.last_seen = messages.last().map(|m| m.id());
+        self.messages = messages.to_vec();
+    }
+
+    pub fn mark_read(&mut self) {
+        self.unread = 0;
+    }
+
+    fn byte_index(&self) -> usize {
+        self.input
+            .char_indices()
+            .nth(self.cursor_position)
+            .map_or(self.input.len(), |(i, _)| i)
+    }
+
+    // Getters for rendering
+    pub fn messages(&self) -> &[ChatMessage] {
+        &self.messages
+    }
+
+    pub fn unread(&self) -> usize {
+        self.unread
+    }
+
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn cursor_position(&self) -> usize {
+        self.cursor_position
+    }
+
+    pub fn scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(author_id: Uuid, text: &str) -> ChatMessage {
+        ChatMessage::new(author_id, text.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_enter_sends_and_clears_input() {
+        let mut tab = ChatTab::new();
+
+        for c in "hi q".chars() {
+            assert!(tab.handle_key(KeyCode::Char(c)).is_none());
+        }
+        tab.handle_key(KeyCode::Left);
+        tab.handle_key(KeyCode::Backspace);
+        assert_eq!(tab.input(), "hiq");
+
+        match tab.handle_key(KeyCode::Enter) {
+            Some(UserAction::SendChat(text)) => assert_eq!(text, "hiq"),
+            other => panic!("Expected SendChat, got: {:?}", other),
+        }
+        assert_eq!(tab.input(), "");
+        assert!(tab.handle_key(KeyCode::Enter).is_none());
+    }
+
+    #[test]
+    fn test_unread_counts_messages_from_others_while_hidden() {
+        let mut tab = ChatTab::new();
+        let me = Uuid::new_v4();
+        let other = Uuid::new_v4();
+
+        let mut history = vec![message(other, "hello"), message(me, "hey")];
+        tab.update_messages(&history, Some(me), false);
+        assert_eq!(tab.unread(), 1);
+
+        // Same history again adds nothing
+        tab.update_messages(&history, Some(me), false);
+        assert_eq!(tab.unread(), 1);
+
+        history.push(message(other, "how are you?"));
+        tab.update_messages(&history, Some(me), false);
+        assert_eq!(tab.unread(), 2);
+
+        tab.update_messages(&history, Some(me), true);
+        assert_eq!(tab.unread(), 0);
+    }
+}
@@ -0,0 +1,98 @@
+use crossterm::event::KeyCode;
+
+use crate::presentation::tui::app::UserAction;
+
+/// Keys answering a confirmation dialog (case-insensitive)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmKeys {
+    pub yes: char,
+    pub no: char,
+}
+
+impl Default for ConfirmKeys {
+    fn default() -> Self {
+        Self { yes: 'y', no: 'n' }
+    }
+}
+
+impl ConfirmKeys {
+    pub fn new(yes: char, no: char) -> Self {
+        Self { yes, no }
+    }
+
+    fn matches(key: char, expected: char) -> bool {
+        key.to_lowercase().eq(expected.to_lowercase())
+    }
+
+    /// `Some(true)` for the yes key, `Some(false)` for the no key or Esc,
+    /// `None` for anything else
+    pub fn answer(&self, key: KeyCode) -> Option<bool> {
+        match key {
+            KeyCode::Char(c) if Self::matches(c, self.yes) => Some(true),
+            KeyCode::Char(c) if Self::matches(c, self.no) => Some(false),
+            KeyCode::Esc => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// Modal question guarding a destructive action (presentation only)
+#[derive(Debug, Clone)]
+pub struct ConfirmDialog {
+    title: String,
+    message: String,
+    action: UserAction,
+}
+
+impl ConfirmDialog {
+    pub fn new(title: impl Into<String>, message: impl Into<String>, action: UserAction) -> Self {
+        Self {
+            title: title.into(),
+            message: message.into(),
+            action,
+        }
+    }
+
+    /// `Some(true)` for the yes key, `Some(false)` for the no key or Esc,
+    /// `None` for anything else (the dialog stays open)
+    pub fn answer(&self, key: KeyCode, keys: ConfirmKeys) -> Option<bool> {
+        keys.answer(key)
+    }
+
+    pub fn into_action(self) -> UserAction {
+        self.action
+    }
+
+    // Getters for rendering
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn action(&self) -> &UserAction {
+        &self.action
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_with_custom_keys() {
+        let dialog = ConfirmDialog::new("Quit?", "Really?", UserAction::Quit);
+        let keys = ConfirmKeys::new('j', 'x');
+
+        assert_eq!(dialog.answer(KeyCode::Char('j'), keys), Some(true));
+        assert_eq!(dialog.answer(KeyCode::Char('J'), keys), Some(true));
+        assert_eq!(dialog.answer(KeyCode::Char('x'), keys), Some(false));
+        assert_eq!(dialog.answer(KeyCode::Esc, keys), Some(false));
+
+        // The default keys mean nothing here
+        assert_eq!(dialog.answer(KeyCode::Char('y'), keys), None);
+        assert_eq!(dialog.answer(KeyCode::Enter, keys), None);
+    }
+}
@@ -1,11 +1,406 @@
 use crossterm::event::KeyCode;
-use std::collections::VecDeque;
+use konnekt_session_core::DomainEvent;
+use konnekt_session_core::domain::{ParticipantAvatar, RunStatus};
+use konnekt_session_p2p::{ConnectionEvent, SessionEvent};
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+
+/// How much attention an event deserves (ordered, least first)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn parse(level: &str) -> Option<Self> {
+        match level {
+            "info" => Some(Severity::Info),
+            "warn" | "warning" => Some(Severity::Warning),
+            "error" | "err" => Some(Severity::Error),
+            _ => None,
+        }
+    }
+}
+
+/// One line of the event log
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    /// Event type, e.g. `GuestJoined`
+    pub kind: &'static str,
+    pub severity: Severity,
+    pub text: String,
+    /// Names of the participants involved
+    pub participants: Vec<String>,
+}
+
+impl LogEntry {
+    pub fn new(kind: &'static str, severity: Severity, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            severity,
+            text: text.into(),
+            participants: Vec::new(),
+        }
+    }
+
+    fn with_participants(mut self, participants: Vec<String>) -> Self {
+        self.participants = participants;
+        self
+    }
+
+    /// Describe a session event; `None` for internal traffic not worth a line
+    pub fn from_session_event(event: &SessionEvent, names: &HashMap<Uuid, String>) -> Option<Self> {
+        let name = |id: &Uuid| {
+            names
+                .get(id)
+                .cloned()
+                .unwrap_or_else(|| "(unknown)".to_string())
+        };
+
+        let entry = match event {
+            SessionEvent::Domain(event) => match event {
+                DomainEvent::LobbyCreated { lobby } => LogEntry::new(
+                    "LobbyCreated",
+                    Severity::Info,
+                    format!("Lobby '{}' created", lobby.name()),
+                ),
+                DomainEvent::GuestJoined { participant, .. } => LogEntry::new(
+                    "GuestJoined",
+                    Severity::Info,
+                    format!("{} joined", participant.name()),
+                )
+                .with_participants(vec![participant.name().to_string()]),
+                DomainEvent::GuestLeft { participant_id, .. } => LogEntry::new(
+                    "GuestLeft",
+                    Severity::Warning,
+                    format!("{} left", name(participant_id)),
+                )
+                .with_participants(vec![name(participant_id)]),
+                DomainEvent::GuestKicked {
+                    participant_id,
+                    kicked_by,
+                    banned,
+                    ..
+                } => LogEntry::new(
+                    "GuestKicked",
+                    Severity::Warning,
+                    format!(
+                        "{} was {} by {}",
+                        name(participant_id),
+                        if *banned { "banned" } else { "kicked" },
+                        name(kicked_by)
+                    ),
+                )
+                .with_participants(vec![name(participant_id), name(kicked_by)]),
+                DomainEvent::ParticipationModeChanged {
+                    participant_id,
+                    new_mode,
+                    ..
+                } => LogEntry::new(
+                    "ParticipationModeChanged",
+                    Severity::Info,
+                    format!("{} is now {}", name(participant_id), new_mode),
+                )
+                .with_participants(vec![name(participant_id)]),
+                DomainEvent::HostDelegated { from, to, .. } => LogEntry::new(
+                    "HostDelegated",
+                    Severity::Warning,
+                    format!("{} handed hosting to {}", name(from), name(to)),
+                )
+                .with_participants(vec![name(from), name(to)]),
+                DomainEvent::CoHostChanged {
+                    participant_id,
+                    co_host,
+                    ..
+                } => LogEntry::new(
+                    "CoHostChanged",
+                    Severity::Info,
+                    format!(
+                        "{} is {} a co-host",
+                        name(participant_id),
+                        if *co_host { "now" } else { "no longer" }
+                    ),
+                )
+                .with_participants(vec![name(participant_id)]),
+                DomainEvent::AvatarChanged {
+                    participant_id,
+                    avatar,
+                    ..
+                } => LogEntry::new(
+                    "AvatarChanged",
+                    Severity::Info,
+                    match avatar {
+                        Some(ParticipantAvatar::Emoji(emoji)) => {
+                            format!("{} picked the avatar {}", name(participant_id), emoji)
+                        }
+                        Some(ParticipantAvatar::Image(_)) => {
+                            format!("{} picked an avatar image", name(participant_id))
+                        }
+                        None => format!("{} uses the generated avatar", name(participant_id)),
+                    },
+                )
+                .with_participants(vec![name(participant_id)]),
+                DomainEvent::LobbySettingsChanged { settings, .. } => LogEntry::new(
+                    "LobbySettingsChanged",
+                    Severity::Info,
+                    format!(
+                        "Settings: {}, max {}, auto-delegation {}",
+                        if settings.locked { "locked" } else { "open" },
+                        settings
+                            .max_participants
+                            .map_or("unlimited".to_string(), |max| max.to_string()),
+                        settings.auto_delegation
+                    ),
+                ),
+                DomainEvent::ActivityQueued { config, .. } => LogEntry::new(
+                    "ActivityQueued",
+                    Severity::Info,
+                    format!("'{}' planned", config.name),
+                ),
+                DomainEvent::QueuedActivityMoved {
+                    to_index, moved_by, ..
+                } => LogEntry::new(
+                    "QueuedActivityMoved",
+                    Severity::Info,
+                    format!(
+                        "{} moved a planned activity to position {}",
+                        name(moved_by),
+                        to_index + 1
+                    ),
+                )
+                .with_participants(vec![name(moved_by)]),
+                DomainEvent::RunStarted { config, .. } => LogEntry::new(
+                    "RunStarted",
+                    Severity::Info,
+                    format!("'{}' started", config.name),
+                ),
+                DomainEvent::ResultSubmitted { result, .. } => LogEntry::new(
+                    "ResultSubmitted",
+                    Severity::Info,
+                    format!("{} submitted a result", name(&result.participant_id)),
+                )
+                .with_participants(vec![name(&result.participant_id)]),
+                DomainEvent::SubmitterRemoved { participant_id, .. } => LogEntry::new(
+                    "SubmitterRemoved",
+                    Severity::Warning,
+                    format!("{} no longer has to submit", name(participant_id)),
+                )
+                .with_participants(vec![name(participant_id)]),
+                DomainEvent::RunEnded {
+                    status, results, ..
+                } => match status {
+                    RunStatus::Cancelled => {
+                        LogEntry::new("RunEnded", Severity::Warning, "Activity cancelled")
+                    }
+                    _ => LogEntry::new(
+                        "RunEnded",
+                        Severity::Info,
+                        format!("Activity finished with {} results", results.len()),
+                    ),
+                },
+                DomainEvent::ReadyChanged {
+                    participant_id,
+                    ready,
+                    ..
+                } => LogEntry::new(
+                    "ReadyChanged",
+                    Severity::Info,
+                    format!(
+                        "{} is {}ready",
+                        name(participant_id),
+                        if *ready { "" } else { "not " }
+                    ),
+                )
+                .with_participants(vec![name(participant_id)]),
+                DomainEvent::CountdownStarted { started_by, .. } => LogEntry::new(
+                    "CountdownStarted",
+                    Severity::Info,
+                    format!("{} started the countdown", name(started_by)),
+                ),
+                DomainEvent::ChatMessageSent { message, .. } => LogEntry::new(
+                    "ChatMessageSent",
+                    Severity::Info,
+                    format!("{}: {}", name(&message.author_id()), message.text()),
+                )
+                .with_participants(vec![name(&message.author_id())]),
+                DomainEvent::CommandFailed { command, error } => LogEntry::new(
+                    "CommandFailed",
+                    Severity::Error,
+                    format!("{} failed: {}", command.name(), error),
+                ),
+            },
+
+            SessionEvent::Connection(event) => match event {
+                ConnectionEvent::PeerConnected(peer_id) => LogEntry::new(
+                    "PeerConnected",
+                    Severity::Info,
+                    format!("Peer {} connected", peer_id),
+                ),
+                ConnectionEvent::PeerDisconnected(peer_id) => LogEntry::new(
+                    "PeerDisconnected",
+                    Severity::Warning,
+                    format!("Peer {} disconnected", peer_id),
+                ),
+                ConnectionEvent::PeerTimedOut {
+                    peer_id,
+                    participant_id,
+                    was_host,
+                } => {
+                    let who = participant_id.as_ref().map(name);
+                    LogEntry::new(
+                        "PeerTimedOut",
+                        Severity::Error,
+                        format!(
+                            "{} timed out{}",
+                            who.clone().unwrap_or_else(|| format!("Peer {}", peer_id)),
+                            if *was_host { " (host)" } else { "" }
+                        ),
+                    )
+                    .with_participants(who.into_iter().collect())
+                }
+                ConnectionEvent::JoinAccepted { participant_id, .. } => LogEntry::new(
+                    "JoinAccepted",
+                    Severity::Info,
+                    format!("Joined as {}", name(participant_id)),
+                )
+                .with_participants(vec![name(participant_id)]),
+                ConnectionEvent::ResumeRejected => LogEntry::new(
+                    "ResumeRejected",
+                    Severity::Warning,
+                    "Host did not recognise our resume token",
+                ),
+                ConnectionEvent::Reconnecting { attempt } => LogEntry::new(
+                    "Reconnecting",
+                    Severity::Warning,
+                    format!("Signalling lost, reconnecting (attempt {})", attempt),
+                ),
+                ConnectionEvent::Reconnected { peer_id } => LogEntry::new(
+                    "Reconnected",
+                    Severity::Info,
+                    format!("Reconnected as peer {}", peer_id),
+                ),
+                ConnectionEvent::PeerRateLimited {
+                    peer_id,
+                    participant_id,
+                    violations,
+                    auto_kick,
+                } => {
+                    let who = participant_id.as_ref().map(name);
+                    LogEntry::new(
+                        "PeerRateLimited",
+                        if *auto_kick {
+                            Severity::Error
+                        } else {
+                            Severity::Warning
+                        },
+                        format!(
+                            "{} is rate limited ({} violations)",
+                            who.clone().unwrap_or_else(|| format!("Peer {}", peer_id)),
+                            violations
+                        ),
+                    )
+                    .with_participants(who.into_iter().collect())
+                }
+                ConnectionEvent::HostSuperseded { new_host, epoch } => LogEntry::new(
+                    "HostSuperseded",
+                    Severity::Warning,
+                    format!("Peer {} took over hosting (epoch {})", new_host, epoch),
+                ),
+                ConnectionEvent::TakeoverDeclined { participant_id } => LogEntry::new(
+                    "TakeoverDeclined",
+                    Severity::Warning,
+                    format!("{} declined to take over as host", name(participant_id)),
+                )
+                .with_participants(vec![name(participant_id)]),
+                ConnectionEvent::TimeoutsChanged { .. } => LogEntry::new(
+                    "TimeoutsChanged",
+                    Severity::Info,
+                    "Host changed the timeout settings",
+                ),
+                ConnectionEvent::MessageReceived { .. }
+                | ConnectionEvent::SyncNeeded { .. }
+                | ConnectionEvent::StateChecksum { .. }
+                | ConnectionEvent::CrdtUpdated
+                | ConnectionEvent::TopicOpened { .. }
+                | ConnectionEvent::TopicSyncNeeded { .. }
+                | ConnectionEvent::SnapshotProgress { .. } => return None,
+            },
+        };
+        Some(entry)
+    }
+}
+
+/// Which entries to show, parsed from e.g. `type:run who:alice level:warn late`
+///
+/// Each `type:` and `who:` term matches a part of the event type or of a
+/// participant name (any of them may match); `level:` sets the minimum
+/// severity; every other word has to appear in the text. Case-insensitive.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventFilter {
+    kinds: Vec<String>,
+    participants: Vec<String>,
+    min_severity: Option<Severity>,
+    words: Vec<String>,
+}
+
+impl EventFilter {
+    pub fn parse(input: &str) -> Self {
+        let mut filter = Self::default();
+        for term in input.split_whitespace() {
+            let term = term.to_lowercase();
+            match term.split_once(':') {
+                Some(("type", kind)) if !kind.is_empty() => filter.kinds.push(kind.to_string()),
+                Some(("who", name)) if !name.is_empty() => {
+                    filter.participants.push(name.to_string())
+                }
+                Some(("level", level)) if Severity::parse(level).is_some() => {
+                    filter.min_severity = Severity::parse(level)
+                }
+                _ => filter.words.push(term),
+            }
+        }
+        filter
+    }
+
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        let kind = entry.kind.to_lowercase();
+        let text = entry.text.to_lowercase();
+
+        (self.kinds.is_empty() || self.kinds.iter().any(|k| kind.contains(k.as_str())))
+            && (self.participants.is_empty()
+                || self.participants.iter().any(|p| {
+                    entry
+                        .participants
+                        .iter()
+                        .any(|name| name.to_lowercase().contains(p.as_str()))
+                }))
+            && self.min_severity.is_none_or(|min| entry.severity >= min)
+            && self.words.iter().all(|w| text.contains(w.as_str()))
+    }
+}
+
+/// Which input line is being edited
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventsInput {
+    Filter,
+    Search,
+}
 
 /// Events tab state (presentation only)
 pub struct EventsTab {
-    event_log: VecDeque<String>,
+    /// Newest first
+    event_log: VecDeque<LogEntry>,
+    /// First shown entry, counted in the filtered list
     scroll_offset: usize,
     max_events: usize,
+
+    filter_input: String,
+    filter: EventFilter,
+    /// Highlighted in the shown entries, `n`/`N` jump between matches
+    search: String,
+    editing: Option<EventsInput>,
 }
 
 impl EventsTab {
@@ -13,7 +408,11 @@ impl EventsTab {
         Self {
             event_log: VecDeque::new(),
             scroll_offset: 0,
-            max_events: 100,
+            max_events: 1000,
+            filter_input: String::new(),
+            filter: EventFilter::default(),
+            search: String::new(),
+            editing: None,
         }
     }
 
@@ -21,31 +420,244 @@ impl EventsTab {
         &mut self,
         key: KeyCode,
     ) -> Option<crate::presentation::tui::app::UserAction> {
+        if let Some(input) = self.editing {
+            self.handle_input_key(input, key);
+            return None;
+        }
+
         match key {
             KeyCode::Char('j') | KeyCode::Down => {
-                self.scroll_offset = self.scroll_offset.saturating_add(1);
+                let max = self.visible_count().saturating_sub(1);
+                self.scroll_offset = (self.scroll_offset + 1).min(max);
                 None
             }
             KeyCode::Char('k') | KeyCode::Up => {
                 self.scroll_offset = self.scroll_offset.saturating_sub(1);
                 None
             }
+            KeyCode::Char('f') => {
+                self.editing = Some(EventsInput::Filter);
+                None
+            }
+            KeyCode::Char('/') => {
+                self.editing = Some(EventsInput::Search);
+                None
+            }
+            KeyCode::Char('n') => {
+                self.jump_to_match(true);
+                None
+            }
+            KeyCode::Char('N') => {
+                self.jump_to_match(false);
+                None
+            }
             _ => None,
         }
     }
 
-    pub fn add_event(&mut self, event: String) {
-        self.event_log.push_front(event);
+    /// Typing updates the filter or search right away; Enter keeps it,
+    /// Esc clears it
+    fn handle_input_key(&mut self, input: EventsInput, key: KeyCode) {
+        let text = match input {
+            EventsInput::Filter => &mut self.filter_input,
+            EventsInput::Search => &mut self.search,
+        };
+        match key {
+            KeyCode::Char(c) => text.push(c),
+            KeyCode::Backspace => {
+                text.pop();
+            }
+            KeyCode::Esc => {
+                text.clear();
+                self.editing = None;
+            }
+            KeyCode::Enter => self.editing = None,
+            _ => {}
+        }
+
+        if input == EventsInput::Filter {
+            self.filter = EventFilter::parse(&self.filter_input);
+            self.scroll_offset = 0;
+        } else if key != KeyCode::Enter {
+            self.jump_to_first_match();
+        }
+    }
+
+    pub fn add_event(&mut self, entry: LogEntry) {
+        // Keep the view still while new events arrive above it
+        if self.scroll_offset > 0 && self.filter.matches(&entry) {
+            self.scroll_offset += 1;
+        }
+        self.event_log.push_front(entry);
         if self.event_log.len() > self.max_events {
             self.event_log.pop_back();
         }
     }
 
-    pub fn event_log(&self) -> &VecDeque<String> {
+    /// Entries passing the filter, newest first
+    pub fn visible(&self) -> impl Iterator<Item = &LogEntry> {
+        self.event_log.iter().filter(|e| self.filter.matches(e))
+    }
+
+    fn visible_count(&self) -> usize {
+        self.visible().count()
+    }
+
+    /// Whether `entry` contains the search text
+    pub fn is_match(&self, entry: &LogEntry) -> bool {
+        !self.search.is_empty()
+            && entry
+                .text
+                .to_lowercase()
+                .contains(&self.search.to_lowercase())
+    }
+
+    fn jump_to_match(&mut self, forward: bool) {
+        let matches: Vec<usize> = self
+            .visible()
+            .enumerate()
+            .filter(|(_, e)| self.is_match(e))
+            .map(|(i, _)| i)
+            .collect();
+        let next = if forward {
+            matches.iter().find(|&&i| i > self.scroll_offset)
+        } else {
+            matches.iter().rev().find(|&&i| i < self.scroll_offset)
+        };
+        if let Some(&index) = next {
+            self.scroll_offset = index;
+        }
+    }
+
+    fn jump_to_first_match(&mut self) {
+        let first = self.visible().position(|e| self.is_match(e));
+        if let Some(index) = first {
+            self.scroll_offset = index;
+        }
+    }
+
+    // Getters for rendering
+    pub fn event_log(&self) -> &VecDeque<LogEntry> {
         &self.event_log
     }
 
     pub fn scroll_offset(&self) -> usize {
         self.scroll_offset
     }
+
+    pub fn filter_input(&self) -> &str {
+        &self.filter_input
+    }
+
+    pub fn search(&self) -> &str {
+        &self.search
+    }
+
+    pub fn editing(&self) -> Option<EventsInput> {
+        self.editing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kind: &'static str, severity: Severity, text: &str, who: &[&str]) -> LogEntry {
+        LogEntry::new(kind, severity, text)
+            .with_participants(who.iter().map(|w| w.to_string()).collect())
+    }
+
+    fn type_text(tab: &mut EventsTab, text: &str) {
+        for c in text.chars() {
+            tab.handle_key(KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_filter_by_type_participant_level_and_text() {
+        let joined = entry("GuestJoined", Severity::Info, "Alice joined", &["Alice"]);
+        let kicked = entry(
+            "GuestKicked",
+            Severity::Warning,
+            "Bob was kicked by Alice",
+            &["Bob", "Alice"],
+        );
+        let failed = entry("CommandFailed", Severity::Error, "StartNextRun failed", &[]);
+
+        let filter = EventFilter::parse("type:guest who:bob");
+        assert!(!filter.matches(&joined));
+        assert!(filter.matches(&kicked));
+
+        let filter = EventFilter::parse("level:warn");
+        assert!(!filter.matches(&joined));
+        assert!(filter.matches(&kicked));
+        assert!(filter.matches(&failed));
+
+        let filter = EventFilter::parse("START failed");
+        assert!(filter.matches(&failed));
+        assert!(!filter.matches(&kicked));
+
+        // An unknown level is just a word
+        let filter = EventFilter::parse("level:loud");
+        assert!(!filter.matches(&failed));
+    }
+
+    #[test]
+    fn test_filter_input_and_search_jump() {
+        let mut tab = EventsTab::new();
+        for i in 0..5 {
+            tab.add_event(entry(
+                "GuestJoined",
+                Severity::Info,
+                &format!("Guest {i} joined"),
+                &[],
+            ));
+        }
+        tab.add_event(entry("CommandFailed", Severity::Error, "Kick failed", &[]));
+
+        // Newest first: "Kick failed", then Guest 4 .. Guest 0
+        tab.handle_key(KeyCode::Char('/'));
+        type_text(&mut tab, "guest 2");
+        tab.handle_key(KeyCode::Enter);
+        assert_eq!(tab.scroll_offset(), 3);
+        assert!(tab.editing().is_none());
+
+        // 'n' while not editing finds nothing further, 'f' edits the filter
+        tab.handle_key(KeyCode::Char('n'));
+        assert_eq!(tab.scroll_offset(), 3);
+
+        tab.handle_key(KeyCode::Char('f'));
+        type_text(&mut tab, "level:error");
+        assert_eq!(tab.editing(), Some(EventsInput::Filter));
+        assert_eq!(tab.scroll_offset(), 0);
+        let shown: Vec<_> = tab.visible().map(|e| e.kind).collect();
+        assert_eq!(shown, vec!["CommandFailed"]);
+
+        // Esc drops the filter again
+        tab.handle_key(KeyCode::Esc);
+        assert_eq!(tab.visible().count(), 6);
+        assert_eq!(tab.filter_input(), "");
+    }
+
+    #[test]
+    fn test_describe_session_events() {
+        let alice = Uuid::new_v4();
+        let names = HashMap::from([(alice, "Alice".to_string())]);
+
+        let left = SessionEvent::Domain(DomainEvent::GuestLeft {
+            lobby_id: Uuid::new_v4(),
+            participant_id: alice,
+        });
+        let entry = LogEntry::from_session_event(&left, &names).unwrap();
+        assert_eq!(entry.kind, "GuestLeft");
+        assert_eq!(entry.severity, Severity::Warning);
+        assert_eq!(entry.text, "Alice left");
+        assert_eq!(entry.participants, vec!["Alice".to_string()]);
+
+        let checksum = SessionEvent::Connection(ConnectionEvent::StateChecksum {
+            sequence: 1,
+            checksum: 2,
+        });
+        assert!(LogEntry::from_session_event(&checksum, &names).is_none());
+    }
 }
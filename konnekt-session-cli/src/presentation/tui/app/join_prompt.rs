@@ -0,0 +1,71 @@
+use crossterm::event::KeyCode;
+
+use crate::infrastructure::JoinTarget;
+
+/// What a key did in the join prompt
+#[derive(Debug, Clone)]
+pub enum PromptKey {
+    /// The prompt stays open
+    Editing,
+    Close,
+    Join(JoinTarget),
+}
+
+/// Prompt for another session to join: a session ID, a join link or a join
+/// command, typed or pasted (presentation only)
+#[derive(Debug, Clone, Default)]
+pub struct JoinPrompt {
+    input: String,
+    error: Option<String>,
+}
+
+impl JoinPrompt {
+    /// Open the prompt, filled in with e.g. the clipboard contents
+    pub fn new(input: Option<String>) -> Self {
+        let mut prompt = Self::default();
+        if let Some(input) = input {
+            prompt.paste(&input);
+        }
+        prompt
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) -> PromptKey {
+        match key {
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                self.error = None;
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                self.error = None;
+            }
+            KeyCode::Esc => return PromptKey::Close,
+            KeyCode::Enter => match JoinTarget::parse(&self.input) {
+                Ok(target) => return PromptKey::Join(target),
+                Err(e) => self.error = Some(e.to_string()),
+            },
+            _ => {}
+        }
+        PromptKey::Editing
+    }
+
+    /// Append pasted text; line breaks of a copied command become spaces
+    pub fn paste(&mut self, text: &str) {
+        self.input
+            .push_str(&text.split_whitespace().collect::<Vec<_>>().join(" "));
+        self.error = None;
+    }
+
+    pub fn set_error(&mut self, error: impl Into<String>) {
+        self.error = Some(error.into());
+    }
+
+    // Getters for rendering
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
@@ -1,10 +1,11 @@
 use crossterm::event::KeyCode;
-use konnekt_session_core::Lobby;
+use konnekt_session_core::{Lobby, LobbySettings};
 
 /// Lobby tab state (presentation only)
 pub struct LobbyTab {
     lobby_name: Option<String>,
     participant_count: usize,
+    settings: LobbySettings,
 }
 
 impl LobbyTab {
@@ -12,6 +13,7 @@ impl LobbyTab {
         Self {
             lobby_name: None,
             participant_count: 0,
+            settings: LobbySettings::default(),
         }
     }
 
@@ -25,6 +27,7 @@ impl LobbyTab {
     pub fn update_lobby(&mut self, lobby: &Lobby) {
         self.lobby_name = Some(lobby.name().to_string());
         self.participant_count = lobby.participants().len();
+        self.settings = *lobby.settings();
     }
 
     pub fn lobby_name(&self) -> Option<&str> {
@@ -34,4 +37,8 @@ impl LobbyTab {
     pub fn participant_count(&self) -> usize {
         self.participant_count
     }
+
+    pub fn settings(&self) -> &LobbySettings {
+        &self.settings
+    }
 }
@@ -1,22 +1,45 @@
 use crossterm::event::KeyCode;
-use konnekt_session_core::{Lobby, domain::ActivityConfig};
+use konnekt_session_core::{
+    Lobby, LobbySettings, ResultsAnalytics,
+    domain::{ActivityConfig, ActivityRun},
+};
+use konnekt_session_p2p::{ConnectionEvent, HostTakeover, PeerStats, Presence, SessionEvent};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+use crate::infrastructure::{JoinTarget, ResultsExport};
+
 mod activities_tab;
+mod analytics_tab;
+mod chat_tab;
+mod confirm_dialog;
 mod events_tab;
 mod help_tab;
+mod join_prompt;
 mod lobby_tab;
+mod notifications;
+mod participant_detail;
 mod participants_tab;
 mod results_tab;
 mod session_tab;
+mod settings_dialog;
 
 pub use activities_tab::ActivitiesTab;
-pub use events_tab::EventsTab;
+pub use analytics_tab::AnalyticsTab;
+pub use chat_tab::ChatTab;
+pub use confirm_dialog::{ConfirmDialog, ConfirmKeys};
+pub use events_tab::{EventFilter, EventsInput, EventsTab, LogEntry, Severity};
 pub use help_tab::HelpTab;
+pub use join_prompt::{JoinPrompt, PromptKey};
 pub use lobby_tab::LobbyTab;
+pub use notifications::{Notification, Notifications};
+pub use participant_detail::{
+    DetailAction, DetailKey, ParticipantDetail, PresenceEntry, PresenceHistory,
+};
 pub use participants_tab::ParticipantsTab;
-pub use results_tab::ResultsTab;
+pub use results_tab::{ResultsTab, ResultsView};
 pub use session_tab::SessionTab;
+pub use settings_dialog::{SettingsDialog, SettingsField};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Tab {
@@ -24,7 +47,9 @@ pub enum Tab {
     Lobby,
     Activities,
     Participants,
+    Chat,
     Results, // 🆕 NEW
+    Analytics,
     Events,
     Help,
 }
@@ -35,8 +60,10 @@ impl Tab {
             Tab::Session => Tab::Lobby,
             Tab::Lobby => Tab::Activities,
             Tab::Activities => Tab::Participants,
-            Tab::Participants => Tab::Results, // 🆕
-            Tab::Results => Tab::Events,       // 🆕
+            Tab::Participants => Tab::Chat,
+            Tab::Chat => Tab::Results,
+            Tab::Results => Tab::Analytics,
+            Tab::Analytics => Tab::Events,
             Tab::Events => Tab::Help,
             Tab::Help => Tab::Session,
         }
@@ -48,8 +75,10 @@ impl Tab {
             Tab::Lobby => Tab::Session,
             Tab::Activities => Tab::Lobby,
             Tab::Participants => Tab::Activities,
-            Tab::Results => Tab::Participants, // 🆕
-            Tab::Events => Tab::Results,       // 🆕
+            Tab::Chat => Tab::Participants,
+            Tab::Results => Tab::Chat, // 🆕
+            Tab::Analytics => Tab::Results,
+            Tab::Events => Tab::Analytics,
             Tab::Help => Tab::Events,
         }
     }
@@ -60,7 +89,9 @@ impl Tab {
             Tab::Lobby => "Lobby",
             Tab::Activities => "Activities",
             Tab::Participants => "Participants",
+            Tab::Chat => "Chat",
             Tab::Results => "Results", // 🆕
+            Tab::Analytics => "Analytics",
             Tab::Events => "Events",
             Tab::Help => "Help",
         }
@@ -73,16 +104,44 @@ pub enum UserAction {
     // Session actions
     CopySessionId,
     CopyJoinCommand,
+    /// Leave this session for another one
+    JoinSession(JoinTarget),
 
     // Participant actions
     ToggleParticipationMode,
     KickParticipant(Uuid),
+    /// Switch an active guest to spectating (host and co-hosts)
+    ForceSpectate(Uuid),
+
+    // Host controls
+    DelegateHost(Uuid),
+    SetCoHost {
+        participant_id: Uuid,
+        co_host: bool,
+    },
+    UpdateLobbySettings(LobbySettings),
+
+    // Host takeover, after being elected to succeed a missing host
+    AcceptTakeover,
+    DeclineTakeover,
 
     // Activity actions (🆕)
     PlanActivity(ActivityConfig),
     StartActivity(Uuid),
     CancelActivity(Uuid),
-    SubmitActivityResult { activity_id: Uuid, response: String },
+    SubmitActivityResult {
+        activity_id: Uuid,
+        response: String,
+    },
+
+    // Chat actions
+    SendChat(String),
+
+    // Results actions
+    ExportResults,
+
+    /// Write the last log lines to a file for a bug report
+    DumpLogs,
 
     // General
     Quit,
@@ -99,12 +158,36 @@ pub struct App {
     pub lobby_tab: LobbyTab,
     pub activities_tab: ActivitiesTab,
     pub results_tab: ResultsTab,
+    pub analytics_tab: AnalyticsTab,
     pub participants_tab: ParticipantsTab,
+    pub chat_tab: ChatTab,
     pub events_tab: EventsTab,
     pub help_tab: HelpTab,
 
+    /// Toasts and their history, shown over every tab
+    pub notifications: Notifications,
+
+    /// Open confirmation dialog; it takes every key until answered
+    pub confirm_dialog: Option<ConfirmDialog>,
+    pub confirm_keys: ConfirmKeys,
+
+    /// Open lobby settings popup (host only); it takes every key until closed
+    pub settings_dialog: Option<SettingsDialog>,
+
+    /// Open participant detail popup; it takes every key until closed
+    pub participant_detail: Option<ParticipantDetail>,
+
+    /// Open prompt for another session to join; it takes every key until closed
+    pub join_prompt: Option<JoinPrompt>,
+
+    /// Where we stand while the host is missing; an offer to take over
+    /// takes every key until answered
+    pub host_takeover: Option<HostTakeover>,
+
     // Flags
     pub should_quit: bool,
+    /// Session to join after quitting this one (picked in the join prompt)
+    pub next_session: Option<JoinTarget>,
 
     // Cached state from SessionLoop (read-only snapshots)
     pub lobby_snapshot: Option<Lobby>,
@@ -112,6 +195,12 @@ pub struct App {
     pub local_participant_id: Option<Uuid>,
     pub peer_count: usize,
     pub is_host: bool,
+    pub presence: Vec<(Uuid, Presence)>,
+    pub presence_history: PresenceHistory,
+    /// Finished runs, oldest first
+    pub finished_runs: Vec<ActivityRun>,
+    /// Everyone seen in the lobby, so results keep the names of those who left
+    pub participant_names: HashMap<Uuid, String>,
 }
 
 impl App {
@@ -124,36 +213,178 @@ impl App {
             lobby_tab: LobbyTab::new(),
             activities_tab: ActivitiesTab::new(),
             results_tab: ResultsTab::new(),
+            analytics_tab: AnalyticsTab::new(),
             participants_tab: ParticipantsTab::new(),
+            chat_tab: ChatTab::new(),
             events_tab: EventsTab::new(),
             help_tab: HelpTab::new(),
 
+            notifications: Notifications::new(),
+
+            confirm_dialog: None,
+            confirm_keys: ConfirmKeys::default(),
+
+            settings_dialog: None,
+
+            participant_detail: None,
+
+            join_prompt: None,
+
+            host_takeover: None,
+
             should_quit: false,
+            next_session: None,
 
             lobby_snapshot: None,
             local_peer_id: None,
             local_participant_id: None,
             peer_count: 0,
             is_host: false,
+            presence: Vec::new(),
+            presence_history: PresenceHistory::new(),
+            finished_runs: Vec::new(),
+            participant_names: HashMap::new(),
         }
     }
 
+    pub fn with_confirm_keys(mut self, keys: ConfirmKeys) -> Self {
+        self.confirm_keys = keys;
+        self
+    }
+
+    /// Signalling server, named in the join command the Session tab copies
+    pub fn with_server(mut self, server: impl Into<String>) -> Self {
+        self.session_tab.set_server(server.into());
+        self
+    }
+
+    /// Link the Session tab's QR code to `join_url`; `show_qr` opens it
+    pub fn with_join_qr(mut self, join_url: Option<String>, show_qr: bool) -> Self {
+        self.session_tab.set_join_qr(join_url, show_qr);
+        self
+    }
+
     /// Handle keyboard input → returns UserAction if applicable
     pub fn handle_key(&mut self, key: KeyCode) -> Option<UserAction> {
+        if matches!(self.host_takeover, Some(HostTakeover::Offered { .. })) {
+            return match self.confirm_keys.answer(key)? {
+                true => Some(UserAction::AcceptTakeover),
+                false => Some(UserAction::DeclineTakeover),
+            };
+        }
+
+        if let Some(dialog) = self.confirm_dialog.take() {
+            return match dialog.answer(key, self.confirm_keys) {
+                Some(true) => {
+                    let action = dialog.into_action();
+                    self.should_quit = matches!(action, UserAction::Quit);
+                    Some(action)
+                }
+                Some(false) => None,
+                None => {
+                    self.confirm_dialog = Some(dialog);
+                    None
+                }
+            };
+        }
+
+        if let Some(dialog) = &mut self.settings_dialog {
+            return match dialog.answer(key) {
+                Some(true) => self
+                    .settings_dialog
+                    .take()
+                    .map(|dialog| UserAction::UpdateLobbySettings(dialog.settings())),
+                Some(false) => {
+                    self.settings_dialog = None;
+                    None
+                }
+                None => None,
+            };
+        }
+
+        if let Some(detail) = self.participant_detail {
+            let answer = match &self.lobby_snapshot {
+                Some(lobby) => detail.answer(key, lobby, self.is_host, self.can_moderate()),
+                None => DetailKey::Close,
+            };
+            return match answer {
+                DetailKey::Ignored => None,
+                DetailKey::Close => {
+                    self.participant_detail = None;
+                    None
+                }
+                DetailKey::Act(action) => {
+                    self.participant_detail = None;
+                    self.confirm_destructive(Some(action))
+                }
+            };
+        }
+
+        if let Some(prompt) = &mut self.join_prompt {
+            return match prompt.handle_key(key) {
+                PromptKey::Editing => None,
+                PromptKey::Close => {
+                    self.join_prompt = None;
+                    None
+                }
+                PromptKey::Join(target) if target.session_id.to_string() == self.session_id => {
+                    prompt.set_error("You are already in this session");
+                    None
+                }
+                PromptKey::Join(target) => {
+                    self.join_prompt = None;
+                    self.confirm_destructive(Some(UserAction::JoinSession(target)))
+                }
+            };
+        }
+
+        // The history panel sits on top of the tabs
+        if self.notifications.is_panel_open() {
+            self.notifications.handle_key(key);
+            return None;
+        }
+        if key == KeyCode::F(2) {
+            self.notifications.toggle_panel();
+            return None;
+        }
+        if key == KeyCode::F(3) {
+            return Some(UserAction::DumpLogs);
+        }
+
+        // The filter and search inputs take every key until Enter or Esc
+        if self.current_tab == Tab::Events && self.events_tab.editing().is_some() {
+            return self.events_tab.handle_key(key);
+        }
+
+        // The chat input takes every character and the arrow keys
+        if self.current_tab == Tab::Chat
+            && matches!(key, KeyCode::Char(_) | KeyCode::Left | KeyCode::Right)
+        {
+            return self.chat_tab.handle_key(key);
+        }
+
         // Global keys
         match key {
             KeyCode::Char('q') | KeyCode::Esc => {
+                if self.is_host {
+                    self.confirm_dialog = Some(ConfirmDialog::new(
+                        "End session?",
+                        "You are hosting: quitting ends the session for everyone.",
+                        UserAction::Quit,
+                    ));
+                    return None;
+                }
                 self.should_quit = true;
                 return Some(UserAction::Quit);
             }
 
             KeyCode::Tab | KeyCode::Right => {
-                self.current_tab = self.current_tab.next();
+                self.switch_tab(self.current_tab.next());
                 return None;
             }
 
             KeyCode::BackTab | KeyCode::Left => {
-                self.current_tab = self.current_tab.previous();
+                self.switch_tab(self.current_tab.previous());
                 return None;
             }
 
@@ -161,17 +392,129 @@ impl App {
         }
 
         // Tab-specific keys
-        match self.current_tab {
+        let action = match self.current_tab {
+            Tab::Session if key == KeyCode::Char('j') => {
+                let clipboard = self
+                    .session_tab
+                    .clipboard_text()
+                    .filter(|text| JoinTarget::parse(text).is_ok());
+                self.join_prompt = Some(JoinPrompt::new(clipboard));
+                None
+            }
             Tab::Session => self.session_tab.handle_key(key),
             Tab::Lobby => self.lobby_tab.handle_key(key),
             Tab::Activities => self.activities_tab.handle_key(key, self.is_host),
-            Tab::Participants => {
-                self.participants_tab
-                    .handle_key(key, self.is_host, &self.lobby_snapshot)
+            Tab::Participants if key == KeyCode::Char('s') && self.is_host => {
+                self.open_settings();
+                None
             }
+            Tab::Participants if key == KeyCode::Enter => {
+                self.participant_detail = self
+                    .lobby_snapshot
+                    .as_ref()
+                    .and_then(|lobby| self.participants_tab.selected(lobby))
+                    .map(|participant| ParticipantDetail::new(participant.id()));
+                None
+            }
+            Tab::Participants => self.participants_tab.handle_key(
+                key,
+                self.is_host,
+                self.can_moderate(),
+                &self.lobby_snapshot,
+            ),
+            Tab::Chat => self.chat_tab.handle_key(key),
             Tab::Results => self.results_tab.handle_key(key), // 🆕 NEW
+            Tab::Analytics => {
+                self.analytics_tab.handle_key(key);
+                None
+            }
             Tab::Events => self.events_tab.handle_key(key),
             Tab::Help => None,
+        };
+        self.confirm_destructive(action)
+    }
+
+    /// Open the lobby settings popup with the current settings
+    fn open_settings(&mut self) {
+        if let Some(lobby) = &self.lobby_snapshot {
+            self.settings_dialog = Some(SettingsDialog::new(
+                *lobby.settings(),
+                lobby.participants().len(),
+            ));
+        }
+    }
+
+    /// Whether we may kick guests and change their mode (host or co-host)
+    pub fn can_moderate(&self) -> bool {
+        self.is_host
+            || self
+                .local_participant_id
+                .zip(self.lobby_snapshot.as_ref())
+                .is_some_and(|(id, lobby)| lobby.is_co_host(id))
+    }
+
+    /// Hold back kicks, handovers and cancellations behind a confirmation dialog
+    fn confirm_destructive(&mut self, action: Option<UserAction>) -> Option<UserAction> {
+        let (title, message) = match &action {
+            Some(UserAction::KickParticipant(guest_id)) => {
+                let name = self
+                    .lobby_snapshot
+                    .as_ref()
+                    .and_then(|lobby| lobby.participants().get(guest_id))
+                    .map_or("this participant", |p| p.name());
+                (
+                    "Kick participant?",
+                    format!("{} will be removed from the lobby.", name),
+                )
+            }
+            Some(UserAction::DelegateHost(new_host_id)) => {
+                let name = self
+                    .lobby_snapshot
+                    .as_ref()
+                    .and_then(|lobby| lobby.participants().get(new_host_id))
+                    .map_or("this participant", |p| p.name());
+                (
+                    "Hand over hosting?",
+                    format!("{} becomes the host; you stay on as a guest.", name),
+                )
+            }
+            Some(UserAction::CancelActivity(_)) => {
+                let name = self
+                    .activities_tab
+                    .current_activity()
+                    .map_or("the running activity", |a| a.name.as_str());
+                (
+                    "Cancel activity?",
+                    format!("{} ends now for everyone.", name),
+                )
+            }
+            Some(UserAction::JoinSession(_)) if self.is_host => (
+                "Join another session?",
+                "You are hosting: leaving ends this session for everyone.".to_string(),
+            ),
+            _ => return action,
+        };
+        self.confirm_dialog = action.map(|action| ConfirmDialog::new(title, message, action));
+        None
+    }
+
+    fn switch_tab(&mut self, tab: Tab) {
+        self.current_tab = tab;
+        if tab == Tab::Chat {
+            self.chat_tab.mark_read();
+        }
+    }
+
+    /// Handle pasted text: into the join prompt, which a paste on the
+    /// Session tab opens
+    pub fn handle_paste(&mut self, text: &str) {
+        if let Some(prompt) = &mut self.join_prompt {
+            prompt.paste(text);
+        } else if self.current_tab == Tab::Session
+            && self.confirm_dialog.is_none()
+            && self.settings_dialog.is_none()
+        {
+            self.join_prompt = Some(JoinPrompt::new(Some(text.to_string())));
         }
     }
 
@@ -194,10 +537,41 @@ impl App {
         self.lobby_tab.update_lobby(&lobby);
         self.activities_tab.update_lobby(&lobby);
         self.participants_tab.update_lobby(&lobby);
-        self.results_tab.update_lobby(&lobby);
+        self.analytics_tab.seed(&lobby);
+        for participant in lobby.participants().values() {
+            self.participant_names
+                .insert(participant.id(), participant.name().to_string());
+            if self.presence_history.first_seen(participant.id()).is_none() {
+                self.presence_history
+                    .record(participant.id(), "Already in the lobby");
+            }
+        }
+        // Close the detail popup of someone who left
+        if let Some(detail) = self.participant_detail
+            && !lobby.participants().contains_key(&detail.participant_id())
+        {
+            self.participant_detail = None;
+        }
+        self.chat_tab.update_messages(
+            lobby.chat_messages(),
+            self.local_participant_id,
+            self.current_tab == Tab::Chat,
+        );
         self.lobby_snapshot = Some(lobby);
     }
 
+    /// Update the host takeover state from SessionLoop
+    pub fn update_takeover(&mut self, takeover: Option<HostTakeover>) {
+        let offered = |t: &Option<HostTakeover>| matches!(t, Some(HostTakeover::Offered { .. }));
+        if offered(&takeover) && !offered(&self.host_takeover) {
+            self.notifications.push(
+                Severity::Warning,
+                "The host is gone and you were elected to take over".to_string(),
+            );
+        }
+        self.host_takeover = takeover;
+    }
+
     /// Update peer info from SessionLoop
     pub fn update_peer_info(&mut self, peer_id: String, peer_count: usize, is_host: bool) {
         self.local_peer_id = Some(peer_id.clone());
@@ -208,19 +582,177 @@ impl App {
         self.activities_tab.update_is_host(is_host);
     }
 
+    /// Update per-peer connection quality (Session tab)
+    pub fn update_peer_stats(&mut self, stats: Vec<PeerStats>) {
+        self.session_tab.update_peer_stats(stats);
+    }
+
+    /// Update the finished runs from SessionLoop
+    pub fn update_runs(&mut self, runs: Vec<ActivityRun>) {
+        self.finished_runs = runs;
+        self.results_tab.update_results(&self.results_export());
+        self.results_tab
+            .update_analytics(ResultsAnalytics::from_runs(
+                &self.finished_runs,
+                &self.participant_names,
+            ));
+    }
+
+    /// Results of the finished runs, for the Results tab and exporting
+    pub fn results_export(&self) -> ResultsExport {
+        ResultsExport::collect(&self.finished_runs, &self.participant_names)
+    }
+
+    /// Update presence signals of other participants from SessionLoop
+    pub fn update_presence(&mut self, presence: Vec<(Uuid, Presence)>) {
+        for (participant_id, signal) in &presence {
+            if self.presence_of(*participant_id) != Some(*signal) {
+                let text = match signal {
+                    Presence::Typing => "Started typing",
+                    Presence::Answering { .. } => "Started answering",
+                };
+                self.presence_history.record(*participant_id, text);
+            }
+        }
+        self.presence = presence;
+    }
+
+    /// Presence signal of another participant, if any
+    pub fn presence_of(&self, participant_id: Uuid) -> Option<Presence> {
+        self.presence
+            .iter()
+            .find(|(id, _)| *id == participant_id)
+            .map(|(_, presence)| *presence)
+    }
+
+    /// What we are doing right now (typing a chat message or a response)
+    pub fn local_presence(&self) -> Option<Presence> {
+        if self.current_tab == Tab::Chat {
+            return (!self.chat_tab.input().is_empty()).then_some(Presence::Typing);
+        }
+
+        let activity = self.activities_tab.current_activity()?;
+        (self.current_tab == Tab::Activities && !self.activities_tab.activity_input().is_empty())
+            .then_some(Presence::Answering {
+                run_id: activity.id,
+            })
+    }
+
     /// Get local participant ID
     pub fn get_local_participant_id(&self) -> Option<Uuid> {
         self.local_participant_id
     }
 
     /// Add event to log (for display only)
-    pub fn add_event(&mut self, event: String) {
-        self.events_tab.add_event(event);
+    pub fn add_event(&mut self, entry: LogEntry) {
+        self.events_tab.add_event(entry);
+    }
+
+    /// Log an entry and show it as a toast
+    pub fn notify(&mut self, entry: LogEntry) {
+        self.notifications.push(entry.severity, entry.text.clone());
+        self.events_tab.add_event(entry);
+    }
+
+    /// Log an event seen by the SessionLoop, toasting the ones worth interrupting for
+    pub fn record_event(&mut self, event: &SessionEvent) {
+        // Joins can arrive ahead of the lobby snapshot naming the guest
+        if let SessionEvent::Domain(konnekt_session_core::DomainEvent::GuestJoined {
+            participant,
+            ..
+        }) = event
+        {
+            self.participant_names
+                .insert(participant.id(), participant.name().to_string());
+        }
+        self.record_presence_change(event);
+        if let SessionEvent::Domain(event) = event {
+            self.analytics_tab.record(event);
+        }
+        // The host names our participant; guessing by role picks any guest
+        if let SessionEvent::Connection(ConnectionEvent::JoinAccepted { participant_id, .. }) =
+            event
+        {
+            self.local_participant_id = Some(*participant_id);
+        }
+        let Some(entry) = LogEntry::from_session_event(event, &self.participant_names) else {
+            return;
+        };
+
+        if let SessionEvent::Domain(konnekt_session_core::DomainEvent::GuestKicked {
+            participant_id,
+            banned,
+            ..
+        }) = event
+            && Some(*participant_id) == self.local_participant_id
+        {
+            let what = if *banned { "banned" } else { "kicked" };
+            self.notifications
+                .push(Severity::Error, format!("You were {} from the lobby", what));
+            self.events_tab.add_event(entry);
+            return;
+        }
+
+        if entry.severity > Severity::Info
+            || matches!(entry.kind, "GuestJoined" | "RunStarted" | "RunEnded")
+        {
+            self.notify(entry);
+        } else {
+            self.events_tab.add_event(entry);
+        }
+    }
+
+    /// Add joins, leaves and role or mode changes to the presence history
+    fn record_presence_change(&mut self, event: &SessionEvent) {
+        use konnekt_session_core::DomainEvent;
+
+        let (participant_id, text) = match event {
+            SessionEvent::Domain(DomainEvent::GuestJoined { participant, .. }) => {
+                (participant.id(), "Joined".to_string())
+            }
+            SessionEvent::Domain(DomainEvent::GuestLeft { participant_id, .. }) => {
+                (*participant_id, "Left".to_string())
+            }
+            SessionEvent::Domain(DomainEvent::GuestKicked { participant_id, .. }) => {
+                (*participant_id, "Kicked".to_string())
+            }
+            SessionEvent::Domain(DomainEvent::ParticipationModeChanged {
+                participant_id,
+                new_mode,
+                ..
+            }) => (*participant_id, format!("Now {}", new_mode)),
+            SessionEvent::Domain(DomainEvent::HostDelegated { from, to, .. }) => {
+                self.presence_history.record(*from, "Handed over hosting");
+                (*to, "Became host".to_string())
+            }
+            SessionEvent::Domain(DomainEvent::CoHostChanged {
+                participant_id,
+                co_host,
+                ..
+            }) => {
+                let text = if *co_host {
+                    "Became co-host"
+                } else {
+                    "No longer co-host"
+                };
+                (*participant_id, text.to_string())
+            }
+            SessionEvent::Connection(ConnectionEvent::PeerTimedOut {
+                participant_id: Some(participant_id),
+                ..
+            }) => (*participant_id, "Timed out".to_string()),
+            SessionEvent::Connection(ConnectionEvent::TakeoverDeclined { participant_id }) => {
+                (*participant_id, "Declined to take over as host".to_string())
+            }
+            _ => return,
+        };
+        self.presence_history.record(participant_id, text);
     }
 
     /// Tick for UI animations
     pub fn tick(&mut self) {
         self.session_tab.tick();
+        self.notifications.tick();
     }
 
     /// Copy session ID to clipboard (presentation concern)
@@ -233,3 +765,309 @@ impl App {
         self.session_tab.copy_join_command()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_core::Participant;
+
+    fn host_app() -> (App, Uuid) {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let guest = Participant::new_guest("Bob".to_string()).unwrap();
+        let guest_id = guest.id();
+        let mut lobby = Lobby::new("Class".to_string(), host).unwrap();
+        lobby.add_guest(guest).unwrap();
+
+        let mut app = App::new("session".to_string()).with_confirm_keys(ConfirmKeys::new('j', 'n'));
+        app.update_peer_info("peer".to_string(), 1, true);
+        app.update_lobby(lobby);
+        (app, guest_id)
+    }
+
+    #[test]
+    fn test_host_quit_needs_confirmation() {
+        let (mut app, _) = host_app();
+
+        assert!(app.handle_key(KeyCode::Char('q')).is_none());
+        assert!(!app.should_quit);
+        assert!(app.confirm_dialog.is_some());
+
+        // Esc backs out instead of quitting
+        assert!(app.handle_key(KeyCode::Esc).is_none());
+        assert!(app.confirm_dialog.is_none());
+        assert!(!app.should_quit);
+
+        app.handle_key(KeyCode::Char('q'));
+        assert!(matches!(
+            app.handle_key(KeyCode::Char('j')),
+            Some(UserAction::Quit)
+        ));
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_guest_quits_right_away() {
+        let mut app = App::new("session".to_string());
+
+        assert!(matches!(
+            app.handle_key(KeyCode::Char('q')),
+            Some(UserAction::Quit)
+        ));
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_kick_waits_for_the_yes_key() {
+        let (mut app, guest_id) = host_app();
+        app.current_tab = Tab::Participants;
+        let position = app
+            .lobby_snapshot
+            .as_ref()
+            .unwrap()
+            .participants()
+            .values()
+            .position(|p| p.id() == guest_id)
+            .unwrap();
+        for _ in 0..position {
+            app.handle_key(KeyCode::Down);
+        }
+
+        assert!(app.handle_key(KeyCode::Char('x')).is_none());
+        let dialog = app.confirm_dialog.as_ref().unwrap();
+        assert!(dialog.message().contains("Bob"));
+
+        // Other keys leave the dialog open, 'n' dismisses it
+        assert!(app.handle_key(KeyCode::Char('y')).is_none());
+        assert!(app.confirm_dialog.is_some());
+        assert!(app.handle_key(KeyCode::Char('n')).is_none());
+        assert!(app.confirm_dialog.is_none());
+
+        app.handle_key(KeyCode::Char('x'));
+        match app.handle_key(KeyCode::Char('j')) {
+            Some(UserAction::KickParticipant(id)) => assert_eq!(id, guest_id),
+            other => panic!("Expected KickParticipant, got: {:?}", other),
+        }
+        assert!(!app.should_quit);
+    }
+
+    #[test]
+    fn test_being_kicked_raises_an_error_toast() {
+        let (mut app, guest_id) = host_app();
+        let host_id = app.local_participant_id.unwrap();
+        let lobby_id = app.lobby_snapshot.as_ref().unwrap().id();
+        let kicked = |participant_id| {
+            SessionEvent::Domain(konnekt_session_core::DomainEvent::GuestKicked {
+                lobby_id,
+                participant_id,
+                kicked_by: host_id,
+                banned: false,
+            })
+        };
+
+        app.record_event(&kicked(guest_id));
+        // Seen from the guest's side
+        app.local_participant_id = Some(guest_id);
+        app.record_event(&kicked(guest_id));
+
+        let toasts: Vec<_> = app.notifications.toasts().collect();
+        assert_eq!(toasts.len(), 2);
+        assert_eq!(toasts[0].severity, Severity::Warning);
+        assert_eq!(toasts[1].severity, Severity::Error);
+        assert_eq!(toasts[1].message, "You were kicked from the lobby");
+        assert_eq!(app.events_tab.event_log().len(), 2);
+
+        // F2 opens the history on any tab and holds the keys until closed
+        app.current_tab = Tab::Chat;
+        assert!(app.handle_key(KeyCode::F(2)).is_none());
+        assert!(app.notifications.is_panel_open());
+        assert!(app.handle_key(KeyCode::Char('q')).is_none());
+        assert!(app.confirm_dialog.is_none());
+        app.handle_key(KeyCode::F(2));
+        assert!(!app.notifications.is_panel_open());
+
+        // F3 dumps the log from any tab, even while typing a message
+        assert!(matches!(
+            app.handle_key(KeyCode::F(3)),
+            Some(UserAction::DumpLogs)
+        ));
+    }
+
+    #[test]
+    fn test_host_controls_on_participants_tab() {
+        let (mut app, guest_id) = host_app();
+        app.current_tab = Tab::Participants;
+        let position = app
+            .lobby_snapshot
+            .as_ref()
+            .unwrap()
+            .participants()
+            .values()
+            .position(|p| p.id() == guest_id)
+            .unwrap();
+        for _ in 0..position {
+            app.handle_key(KeyCode::Down);
+        }
+
+        match app.handle_key(KeyCode::Char('o')) {
+            Some(UserAction::SetCoHost {
+                participant_id,
+                co_host: true,
+            }) => assert_eq!(participant_id, guest_id),
+            other => panic!("Expected SetCoHost, got: {:?}", other),
+        }
+
+        // Handing over hosting is confirmed first
+        assert!(app.handle_key(KeyCode::Char('d')).is_none());
+        match app.handle_key(KeyCode::Char('j')) {
+            Some(UserAction::DelegateHost(id)) => assert_eq!(id, guest_id),
+            other => panic!("Expected DelegateHost, got: {:?}", other),
+        }
+
+        // The settings popup takes every key until saved
+        assert!(app.handle_key(KeyCode::Char('s')).is_none());
+        assert!(app.settings_dialog.is_some());
+        app.handle_key(KeyCode::Down);
+        assert!(app.handle_key(KeyCode::Char('q')).is_none());
+        app.handle_key(KeyCode::Char(' '));
+        match app.handle_key(KeyCode::Enter) {
+            Some(UserAction::UpdateLobbySettings(settings)) => assert!(settings.locked),
+            other => panic!("Expected UpdateLobbySettings, got: {:?}", other),
+        }
+        assert!(app.settings_dialog.is_none());
+        assert!(!app.should_quit);
+
+        // Guests get none of it
+        app.update_peer_info("peer".to_string(), 1, false);
+        assert!(app.handle_key(KeyCode::Char('s')).is_none());
+        assert!(app.settings_dialog.is_none());
+        assert!(app.handle_key(KeyCode::Char('d')).is_none());
+        assert!(app.confirm_dialog.is_none());
+    }
+
+    #[test]
+    fn test_participant_detail_popup() {
+        let (mut app, guest_id) = host_app();
+        app.current_tab = Tab::Participants;
+        let position = app
+            .lobby_snapshot
+            .as_ref()
+            .unwrap()
+            .participants()
+            .values()
+            .position(|p| p.id() == guest_id)
+            .unwrap();
+        for _ in 0..position {
+            app.handle_key(KeyCode::Down);
+        }
+
+        app.update_presence(vec![(guest_id, Presence::Typing)]);
+        assert_eq!(
+            app.presence_history
+                .of(guest_id)
+                .map(|entry| entry.text.as_str())
+                .collect::<Vec<_>>(),
+            ["Already in the lobby", "Started typing"]
+        );
+
+        // Enter opens the popup, which holds the keys until closed
+        assert!(app.handle_key(KeyCode::Enter).is_none());
+        assert_eq!(
+            app.participant_detail.map(|detail| detail.participant_id()),
+            Some(guest_id)
+        );
+        assert!(app.handle_key(KeyCode::Tab).is_none());
+        assert_eq!(app.current_tab, Tab::Participants);
+
+        match app.handle_key(KeyCode::Char('f')) {
+            Some(UserAction::ForceSpectate(id)) => assert_eq!(id, guest_id),
+            other => panic!("Expected ForceSpectate, got: {:?}", other),
+        }
+        assert!(app.participant_detail.is_none());
+
+        // Kicking from the popup is still confirmed
+        app.handle_key(KeyCode::Enter);
+        assert!(app.handle_key(KeyCode::Char('x')).is_none());
+        assert!(app.confirm_dialog.is_some());
+        assert!(matches!(
+            app.handle_key(KeyCode::Char('j')),
+            Some(UserAction::KickParticipant(id)) if id == guest_id
+        ));
+
+        // Closed when the participant leaves
+        app.handle_key(KeyCode::Enter);
+        let mut lobby = app.lobby_snapshot.clone().unwrap();
+        lobby.remove_participant(guest_id).unwrap();
+        app.update_lobby(lobby);
+        assert!(app.participant_detail.is_none());
+    }
+
+    #[test]
+    fn test_takeover_offer_holds_the_keys() {
+        let (mut app, _) = host_app();
+        app.update_peer_info("peer".to_string(), 1, false);
+
+        // Waiting for the host leaves the keys alone
+        app.update_takeover(Some(HostTakeover::GracePeriod {
+            remaining: std::time::Duration::from_secs(5),
+        }));
+        app.handle_key(KeyCode::Tab);
+        assert_eq!(app.current_tab, Tab::Lobby);
+
+        app.update_takeover(Some(HostTakeover::Offered {
+            remaining: std::time::Duration::from_secs(30),
+        }));
+        assert_eq!(app.notifications.history().len(), 1);
+        assert!(app.handle_key(KeyCode::Tab).is_none());
+        assert!(app.handle_key(KeyCode::Char('q')).is_none());
+        assert_eq!(app.current_tab, Tab::Lobby);
+        assert!(!app.should_quit);
+
+        assert!(matches!(
+            app.handle_key(KeyCode::Char('n')),
+            Some(UserAction::DeclineTakeover)
+        ));
+        assert!(matches!(
+            app.handle_key(KeyCode::Char('j')),
+            Some(UserAction::AcceptTakeover)
+        ));
+
+        app.update_takeover(None);
+        app.handle_key(KeyCode::Tab);
+        assert_eq!(app.current_tab, Tab::Activities);
+    }
+
+    #[test]
+    fn test_pasted_join_link_switches_session() {
+        let (mut app, _) = host_app();
+        let other = konnekt_session_p2p::SessionId::new();
+
+        // Pasting on the Session tab opens the prompt
+        app.handle_paste(&format!(
+            "konnekt-tui join --server wss://match.example.org\n --session-id {}",
+            other
+        ));
+        assert!(app.join_prompt.is_some());
+        assert!(app.handle_key(KeyCode::Char('q')).is_none());
+        assert!(!app.should_quit);
+        app.handle_key(KeyCode::Backspace);
+
+        // Hosts are asked first: leaving ends the session
+        assert!(app.handle_key(KeyCode::Enter).is_none());
+        assert!(app.join_prompt.is_none());
+        match app.handle_key(KeyCode::Char('j')) {
+            Some(UserAction::JoinSession(target)) => {
+                assert_eq!(target.session_id, other);
+                assert_eq!(target.server.as_deref(), Some("wss://match.example.org"));
+            }
+            other => panic!("Expected JoinSession, got: {:?}", other),
+        }
+
+        // Anything without a session ID keeps the prompt open
+        app.handle_key(KeyCode::Char('j'));
+        app.handle_paste("session");
+        app.handle_key(KeyCode::Enter);
+        assert!(app.join_prompt.as_ref().unwrap().error().is_some());
+        app.handle_key(KeyCode::Esc);
+        assert!(app.join_prompt.is_none());
+    }
+}
@@ -0,0 +1,193 @@
+use crossterm::event::KeyCode;
+use std::collections::VecDeque;
+
+use crate::presentation::tui::app::Severity;
+
+/// Toasts shown at once; older ones make room for new ones
+const MAX_TOASTS: usize = 3;
+
+/// Something worth interrupting the user for
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub severity: Severity,
+    pub message: String,
+}
+
+struct Toast {
+    notification: Notification,
+    ticks_left: usize,
+}
+
+/// Transient toasts plus the history of everything shown (presentation only)
+pub struct Notifications {
+    /// Oldest first
+    toasts: VecDeque<Toast>,
+    /// Newest first
+    history: VecDeque<Notification>,
+    max_history: usize,
+    /// Notifications since the history panel was last opened
+    unread: usize,
+    panel_open: bool,
+    scroll_offset: usize,
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Notifications {
+    pub fn new() -> Self {
+        Self {
+            toasts: VecDeque::new(),
+            history: VecDeque::new(),
+            max_history: 200,
+            unread: 0,
+            panel_open: false,
+            scroll_offset: 0,
+        }
+    }
+
+    pub fn push(&mut self, severity: Severity, message: String) {
+        let notification = Notification { severity, message };
+
+        // Problems stay up longer (at 100ms ticks: 3s, 5s, 8s)
+        let ticks_left = match severity {
+            Severity::Info => 30,
+            Severity::Warning => 50,
+            Severity::Error => 80,
+        };
+        self.toasts.push_back(Toast {
+            notification: notification.clone(),
+            ticks_left,
+        });
+        if self.toasts.len() > MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+
+        self.history.push_front(notification);
+        if self.history.len() > self.max_history {
+            self.history.pop_back();
+        }
+        if self.panel_open {
+            self.scroll_offset = (self.scroll_offset + 1).min(self.history.len() - 1);
+        } else {
+            self.unread += 1;
+        }
+    }
+
+    /// Count down the toasts, dropping expired ones
+    pub fn tick(&mut self) {
+        for toast in &mut self.toasts {
+            toast.ticks_left = toast.ticks_left.saturating_sub(1);
+        }
+        self.toasts.retain(|toast| toast.ticks_left > 0);
+    }
+
+    /// Open or close the history panel; opening marks everything read
+    pub fn toggle_panel(&mut self) {
+        self.panel_open = !self.panel_open;
+        self.scroll_offset = 0;
+        if self.panel_open {
+            self.unread = 0;
+            self.toasts.clear();
+        }
+    }
+
+    /// Keys while the history panel is open
+    pub fn handle_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('j') | KeyCode::Down => {
+                let max = self.history.len().saturating_sub(1);
+                self.scroll_offset = (self.scroll_offset + 1).min(max);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(1);
+            }
+            KeyCode::Esc | KeyCode::F(2) => self.toggle_panel(),
+            _ => {}
+        }
+    }
+
+    // Getters for rendering
+    pub fn toasts(&self) -> impl Iterator<Item = &Notification> {
+        self.toasts.iter().map(|toast| &toast.notification)
+    }
+
+    pub fn history(&self) -> &VecDeque<Notification> {
+        &self.history
+    }
+
+    pub fn unread(&self) -> usize {
+        self.unread
+    }
+
+    pub fn is_panel_open(&self) -> bool {
+        self.panel_open
+    }
+
+    pub fn scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(notifications: &Notifications) -> Vec<&str> {
+        notifications.toasts().map(|n| n.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_toasts_expire_but_stay_in_history() {
+        let mut notifications = Notifications::new();
+        notifications.push(Severity::Info, "Bob joined".to_string());
+        notifications.push(Severity::Error, "Start failed".to_string());
+
+        for _ in 0..30 {
+            notifications.tick();
+        }
+        assert_eq!(messages(&notifications), vec!["Start failed"]);
+
+        for _ in 0..50 {
+            notifications.tick();
+        }
+        assert!(messages(&notifications).is_empty());
+        assert_eq!(notifications.history().len(), 2);
+        assert_eq!(notifications.history()[0].message, "Start failed");
+        assert_eq!(notifications.unread(), 2);
+    }
+
+    #[test]
+    fn test_only_the_newest_toasts_show() {
+        let mut notifications = Notifications::new();
+        for i in 0..5 {
+            notifications.push(Severity::Info, format!("Guest {i} joined"));
+        }
+
+        assert_eq!(
+            messages(&notifications),
+            vec!["Guest 2 joined", "Guest 3 joined", "Guest 4 joined"]
+        );
+    }
+
+    #[test]
+    fn test_opening_the_panel_marks_read() {
+        let mut notifications = Notifications::new();
+        notifications.push(Severity::Warning, "Bob left".to_string());
+
+        notifications.toggle_panel();
+        assert!(notifications.is_panel_open());
+        assert_eq!(notifications.unread(), 0);
+        assert_eq!(notifications.toasts().count(), 0);
+
+        // Arriving while the panel is open counts as read
+        notifications.push(Severity::Info, "Carol joined".to_string());
+        assert_eq!(notifications.unread(), 0);
+
+        notifications.handle_key(KeyCode::Esc);
+        assert!(!notifications.is_panel_open());
+    }
+}
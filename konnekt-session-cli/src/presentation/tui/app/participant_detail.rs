@@ -0,0 +1,231 @@
+use crossterm::event::KeyCode;
+use konnekt_session_core::{Lobby, ParticipationMode, Timestamp};
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+
+use crate::presentation::tui::app::UserAction;
+
+/// Entries kept per participant in the presence history
+const HISTORY_LEN: usize = 20;
+
+/// Contextual actions of the participant detail popup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetailAction {
+    Kick,
+    Delegate,
+    ForceSpectate,
+}
+
+impl DetailAction {
+    pub fn key(&self) -> char {
+        match self {
+            DetailAction::Kick => 'x',
+            DetailAction::Delegate => 'd',
+            DetailAction::ForceSpectate => 'f',
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            DetailAction::Kick => "kick",
+            DetailAction::Delegate => "make host",
+            DetailAction::ForceSpectate => "force spectate",
+        }
+    }
+
+    fn into_action(self, participant_id: Uuid) -> UserAction {
+        match self {
+            DetailAction::Kick => UserAction::KickParticipant(participant_id),
+            DetailAction::Delegate => UserAction::DelegateHost(participant_id),
+            DetailAction::ForceSpectate => UserAction::ForceSpectate(participant_id),
+        }
+    }
+}
+
+/// What a key did in the detail popup
+#[derive(Debug, Clone)]
+pub enum DetailKey {
+    /// The popup stays open
+    Ignored,
+    Close,
+    /// Close the popup and run this action
+    Act(UserAction),
+}
+
+/// Popup with everything about one participant (presentation only)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParticipantDetail {
+    participant_id: Uuid,
+}
+
+impl ParticipantDetail {
+    pub fn new(participant_id: Uuid) -> Self {
+        Self { participant_id }
+    }
+
+    pub fn participant_id(&self) -> Uuid {
+        self.participant_id
+    }
+
+    /// Actions we may take on this participant; nobody acts on the host
+    pub fn actions(&self, lobby: &Lobby, is_host: bool, can_moderate: bool) -> Vec<DetailAction> {
+        let Some(participant) = lobby.participants().get(&self.participant_id) else {
+            return Vec::new();
+        };
+        if participant.is_host() {
+            return Vec::new();
+        }
+
+        let mut actions = Vec::new();
+        if can_moderate {
+            actions.push(DetailAction::Kick);
+        }
+        if is_host {
+            actions.push(DetailAction::Delegate);
+        }
+        if can_moderate && participant.participation_mode() == ParticipationMode::Active {
+            actions.push(DetailAction::ForceSpectate);
+        }
+        actions
+    }
+
+    pub fn answer(
+        &self,
+        key: KeyCode,
+        lobby: &Lobby,
+        is_host: bool,
+        can_moderate: bool,
+    ) -> DetailKey {
+        match key {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => DetailKey::Close,
+            KeyCode::Char(c) => self
+                .actions(lobby, is_host, can_moderate)
+                .into_iter()
+                .find(|action| action.key() == c)
+                .map_or(DetailKey::Ignored, |action| {
+                    DetailKey::Act(action.into_action(self.participant_id))
+                }),
+            _ => DetailKey::Ignored,
+        }
+    }
+}
+
+/// One line of a participant's presence history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresenceEntry {
+    pub at: Timestamp,
+    pub text: String,
+}
+
+/// Joins, leaves, mode and role changes and typing, per participant
+#[derive(Debug, Default)]
+pub struct PresenceHistory {
+    entries: HashMap<Uuid, VecDeque<PresenceEntry>>,
+    /// First entry per participant, kept when the history rolls over
+    first_seen: HashMap<Uuid, PresenceEntry>,
+}
+
+impl PresenceHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an entry, skipping repeats of the latest one
+    pub fn record(&mut self, participant_id: Uuid, text: impl Into<String>) {
+        let text = text.into();
+        let entries = self.entries.entry(participant_id).or_default();
+        if entries.back().is_some_and(|last| last.text == text) {
+            return;
+        }
+        if entries.len() == HISTORY_LEN {
+            entries.pop_front();
+        }
+        let entry = PresenceEntry {
+            at: Timestamp::now(),
+            text,
+        };
+        self.first_seen
+            .entry(participant_id)
+            .or_insert_with(|| entry.clone());
+        entries.push_back(entry);
+    }
+
+    /// When we first saw the participant (joining, or already in the lobby)
+    pub fn first_seen(&self, participant_id: Uuid) -> Option<&PresenceEntry> {
+        self.first_seen.get(&participant_id)
+    }
+
+    /// Oldest first
+    pub fn of(&self, participant_id: Uuid) -> impl DoubleEndedIterator<Item = &PresenceEntry> {
+        self.entries.get(&participant_id).into_iter().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_core::Participant;
+
+    #[test]
+    fn test_actions_depend_on_role() {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let host_id = host.id();
+        let guest = Participant::new_guest("Bob".to_string()).unwrap();
+        let guest_id = guest.id();
+        let mut lobby = Lobby::new("Class".to_string(), host).unwrap();
+        lobby.add_guest(guest).unwrap();
+
+        let detail = ParticipantDetail::new(guest_id);
+        assert_eq!(
+            detail.actions(&lobby, true, true),
+            vec![
+                DetailAction::Kick,
+                DetailAction::Delegate,
+                DetailAction::ForceSpectate
+            ]
+        );
+        // Co-hosts moderate but do not hand over hosting
+        assert_eq!(
+            detail.actions(&lobby, false, true),
+            vec![DetailAction::Kick, DetailAction::ForceSpectate]
+        );
+        assert!(detail.actions(&lobby, false, false).is_empty());
+        assert!(
+            ParticipantDetail::new(host_id)
+                .actions(&lobby, true, true)
+                .is_empty()
+        );
+
+        assert!(matches!(
+            detail.answer(KeyCode::Char('f'), &lobby, true, true),
+            DetailKey::Act(UserAction::ForceSpectate(id)) if id == guest_id
+        ));
+        assert!(matches!(
+            detail.answer(KeyCode::Char('d'), &lobby, false, true),
+            DetailKey::Ignored
+        ));
+        assert!(matches!(
+            detail.answer(KeyCode::Esc, &lobby, true, true),
+            DetailKey::Close
+        ));
+    }
+
+    #[test]
+    fn test_presence_history_is_bounded() {
+        let id = Uuid::new_v4();
+        let mut history = PresenceHistory::new();
+
+        history.record(id, "Joined");
+        history.record(id, "Started typing");
+        history.record(id, "Started typing");
+        assert_eq!(history.of(id).count(), 2);
+
+        for i in 0..HISTORY_LEN {
+            history.record(id, format!("Entry {}", i));
+        }
+        assert_eq!(history.of(id).count(), HISTORY_LEN);
+        assert_eq!(history.of(id).next().unwrap().text, "Entry 0");
+        assert_eq!(history.first_seen(id).unwrap().text, "Joined");
+        assert_eq!(history.of(Uuid::new_v4()).count(), 0);
+    }
+}
@@ -1,6 +1,5 @@
 use crossterm::event::KeyCode;
-use konnekt_session_core::Lobby;
-use uuid::Uuid;
+use konnekt_session_core::{Lobby, Participant};
 
 use crate::presentation::tui::app::UserAction;
 
@@ -16,10 +15,12 @@ impl ParticipantsTab {
         }
     }
 
+    /// `can_moderate`: host or co-host (kicking); host controls need `is_host`
     pub fn handle_key(
         &mut self,
         key: KeyCode,
         is_host: bool,
+        can_moderate: bool,
         lobby: &Option<Lobby>,
     ) -> Option<UserAction> {
         match key {
@@ -38,23 +39,38 @@ impl ParticipantsTab {
 
             KeyCode::Char('t') => Some(UserAction::ToggleParticipationMode),
 
-            KeyCode::Char('x') if is_host => {
-                if let Some(lobby) = lobby {
-                    let participants: Vec<_> = lobby.participants().values().collect();
-                    if self.selected_participant < participants.len() {
-                        let selected = participants[self.selected_participant];
-                        if !selected.is_host() {
-                            return Some(UserAction::KickParticipant(selected.id()));
-                        }
-                    }
-                }
-                None
+            KeyCode::Char('x') if can_moderate => {
+                let guest = self.selected_guest(lobby.as_ref()?)?;
+                Some(UserAction::KickParticipant(guest.id()))
+            }
+
+            KeyCode::Char('d') if is_host => {
+                let guest = self.selected_guest(lobby.as_ref()?)?;
+                Some(UserAction::DelegateHost(guest.id()))
+            }
+
+            KeyCode::Char('o') if is_host => {
+                let lobby = lobby.as_ref()?;
+                let guest = self.selected_guest(lobby)?;
+                Some(UserAction::SetCoHost {
+                    participant_id: guest.id(),
+                    co_host: !lobby.is_co_host(guest.id()),
+                })
             }
 
             _ => None,
         }
     }
 
+    pub fn selected<'a>(&self, lobby: &'a Lobby) -> Option<&'a Participant> {
+        lobby.participants().values().nth(self.selected_participant)
+    }
+
+    /// The selected participant, unless it is the host
+    fn selected_guest<'a>(&self, lobby: &'a Lobby) -> Option<&'a Participant> {
+        self.selected(lobby).filter(|p| !p.is_host())
+    }
+
     pub fn update_lobby(&mut self, lobby: &Lobby) {
         // Reset selection if out of bounds
         let max = lobby.participants().len().saturating_sub(1);
@@ -1,7 +1,10 @@
 use crossterm::event::KeyCode;
-use konnekt_session_core::{Lobby, domain::ActivityId};
+use konnekt_session_core::ResultsAnalytics;
+use konnekt_session_core::domain::ActivityId;
 use uuid::Uuid;
 
+use crate::infrastructure::ResultsExport;
+
 /// Activity result with participant name (for display)
 #[derive(Debug, Clone)]
 pub struct DisplayResult {
@@ -20,6 +23,32 @@ pub struct ActivityResults {
     pub results: Vec<DisplayResult>,
 }
 
+/// What the score chart compares
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResultsView {
+    /// Each participant's score in the selected activity
+    #[default]
+    Activity,
+    /// Each participant's total over all completed activities
+    Session,
+}
+
+impl ResultsView {
+    pub fn toggle(self) -> Self {
+        match self {
+            ResultsView::Activity => ResultsView::Session,
+            ResultsView::Session => ResultsView::Activity,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ResultsView::Activity => "Scores in activity",
+            ResultsView::Session => "Session totals",
+        }
+    }
+}
+
 /// Results tab state (presentation only)
 pub struct ResultsTab {
     /// All completed activities with results
@@ -30,6 +59,11 @@ pub struct ResultsTab {
 
     /// Selected result index (for detail view)
     selected_result: usize,
+
+    /// What the score chart compares
+    view: ResultsView,
+    /// Score distribution and progression for the charts
+    analytics: ResultsAnalytics,
 }
 
 impl ResultsTab {
@@ -38,6 +72,8 @@ impl ResultsTab {
             completed_activities: Vec::new(),
             selected_activity: 0,
             selected_result: 0,
+            view: ResultsView::default(),
+            analytics: ResultsAnalytics::default(),
         }
     }
 
@@ -59,15 +95,38 @@ impl ResultsTab {
                 self.selected_result = 0; // Reset result selection
                 None
             }
+            KeyCode::Char('v') => {
+                self.view = self.view.toggle();
+                None
+            }
+            KeyCode::Char('e') => Some(crate::presentation::tui::app::UserAction::ExportResults),
             _ => None,
         }
     }
 
-    pub fn update_lobby(&mut self, lobby: &Lobby) {
-        // Current lobby snapshot does not include completed run history/results.
-        // Keep this tab empty until a history resource is introduced.
-        let _ = lobby;
+    /// Show the completed activities of an export
+    pub fn update_results(&mut self, export: &ResultsExport) {
         self.completed_activities.clear();
+        for row in &export.results {
+            let result = DisplayResult {
+                participant_name: row.participant_name.clone(),
+                participant_id: row.participant_id,
+                score: row.score,
+                response: row.response.clone(),
+                time_ms: row.time_ms,
+            };
+            // Rows of one activity are adjacent
+            match self.completed_activities.last_mut() {
+                Some(activity) if activity.activity_id == row.activity_id => {
+                    activity.results.push(result)
+                }
+                _ => self.completed_activities.push(ActivityResults {
+                    activity_id: row.activity_id,
+                    activity_name: row.activity_name.clone(),
+                    results: vec![result],
+                }),
+            }
+        }
 
         // Clamp selections
         if !self.completed_activities.is_empty() {
@@ -76,6 +135,11 @@ impl ResultsTab {
         }
     }
 
+    /// Show the charts of the finished runs
+    pub fn update_analytics(&mut self, analytics: ResultsAnalytics) {
+        self.analytics = analytics;
+    }
+
     // Getters for rendering
     pub fn completed_activities(&self) -> &[ActivityResults] {
         &self.completed_activities
@@ -88,6 +152,60 @@ impl ResultsTab {
     pub fn selected_result(&self) -> usize {
         self.selected_result
     }
+
+    pub fn view(&self) -> ResultsView {
+        self.view
+    }
+
+    /// Bars for the current view, highest score first
+    pub fn score_bars(&self) -> Vec<(String, u64)> {
+        let mut bars: Vec<(Uuid, String, u64)> = Vec::new();
+        let activities = match self.view {
+            ResultsView::Activity => self
+                .completed_activities
+                .get(self.selected_activity)
+                .map(std::slice::from_ref)
+                .unwrap_or_default(),
+            ResultsView::Session => &self.completed_activities[..],
+        };
+        for result in activities.iter().flat_map(|a| &a.results) {
+            let score = u64::from(result.score.unwrap_or(0));
+            match bars
+                .iter_mut()
+                .find(|(id, ..)| *id == result.participant_id)
+            {
+                Some((_, _, total)) => *total += score,
+                None => bars.push((
+                    result.participant_id,
+                    result.participant_name.clone(),
+                    score,
+                )),
+            }
+        }
+        bars.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.1.cmp(&b.1)));
+        bars.into_iter()
+            .map(|(_, name, score)| (name, score))
+            .collect()
+    }
+
+    /// Average score of each completed activity, in order
+    pub fn score_trend(&self) -> Vec<u64> {
+        self.completed_activities
+            .iter()
+            .map(|activity| {
+                let total: u64 = activity
+                    .results
+                    .iter()
+                    .map(|r| u64::from(r.score.unwrap_or(0)))
+                    .sum();
+                total / activity.results.len().max(1) as u64
+            })
+            .collect()
+    }
+
+    pub fn analytics(&self) -> &ResultsAnalytics {
+        &self.analytics
+    }
 }
 
 #[cfg(test)]
@@ -123,4 +241,75 @@ mod tests {
         tab.handle_key(KeyCode::Up);
         assert_eq!(tab.selected_activity, 0);
     }
+
+    #[test]
+    fn test_update_results_groups_rows_by_activity() {
+        let mut tab = ResultsTab::new();
+        let row = |activity_id: Uuid, name: &str| crate::infrastructure::ResultRow {
+            run_id: Uuid::new_v4(),
+            activity_id,
+            activity_name: "Echo".to_string(),
+            participant_id: Uuid::new_v4(),
+            participant_name: name.to_string(),
+            response: Some("Hallo".to_string()),
+            score: Some(100),
+            time_ms: Some(900),
+        };
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        let export = ResultsExport {
+            results: vec![row(first, "Alice"), row(first, "Bob"), row(second, "Alice")],
+            leaderboard: vec![],
+        };
+
+        tab.update_results(&export);
+
+        let sizes: Vec<_> = tab
+            .completed_activities()
+            .iter()
+            .map(|a| (a.activity_id, a.results.len()))
+            .collect();
+        assert_eq!(sizes, vec![(first, 2), (second, 1)]);
+        assert!(matches!(
+            tab.handle_key(KeyCode::Char('e')),
+            Some(crate::presentation::tui::app::UserAction::ExportResults)
+        ));
+    }
+
+    #[test]
+    fn test_score_bars_per_activity_and_session() {
+        let mut tab = ResultsTab::new();
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let result = |participant_id: Uuid, name: &str, score: u32| DisplayResult {
+            participant_name: name.to_string(),
+            participant_id,
+            score: Some(score),
+            response: None,
+            time_ms: None,
+        };
+        tab.completed_activities = vec![
+            ActivityResults {
+                activity_id: Uuid::new_v4(),
+                activity_name: "Echo".to_string(),
+                results: vec![result(alice, "Alice", 40), result(bob, "Bob", 100)],
+            },
+            ActivityResults {
+                activity_id: Uuid::new_v4(),
+                activity_name: "Quiz".to_string(),
+                results: vec![result(alice, "Alice", 100), result(bob, "Bob", 20)],
+            },
+        ];
+
+        assert_eq!(
+            tab.score_bars(),
+            vec![("Bob".to_string(), 100), ("Alice".to_string(), 40)]
+        );
+        assert_eq!(tab.score_trend(), vec![70, 60]);
+
+        tab.handle_key(KeyCode::Char('v'));
+        assert_eq!(tab.view(), ResultsView::Session);
+        assert_eq!(
+            tab.score_bars(),
+            vec![("Alice".to_string(), 140), ("Bob".to_string(), 120)]
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use crossterm::event::KeyCode;
+use konnekt_session_p2p::PeerStats;
 
 use crate::presentation::tui::app::UserAction;
 
@@ -9,6 +10,13 @@ pub struct SessionTab {
     clipboard_message_timer: usize,
     local_peer_id: Option<String>,
     peer_count: usize,
+    /// Other peers, struggling ones first
+    peer_stats: Vec<PeerStats>,
+    /// Web app the QR code links to (just the session ID without one)
+    join_url: Option<String>,
+    show_qr: bool,
+    /// Signalling server, named in the join command when known
+    server: Option<String>,
 }
 
 impl SessionTab {
@@ -19,6 +27,10 @@ impl SessionTab {
             clipboard_message_timer: 0,
             local_peer_id: None,
             peer_count: 0,
+            peer_stats: Vec::new(),
+            join_url: None,
+            show_qr: false,
+            server: None,
         }
     }
 
@@ -26,15 +38,34 @@ impl SessionTab {
         match key {
             KeyCode::Char('y') => Some(UserAction::CopySessionId),
             KeyCode::Char('c') => Some(UserAction::CopyJoinCommand),
+            KeyCode::Char('r') => {
+                self.show_qr = !self.show_qr;
+                None
+            }
             _ => None,
         }
     }
 
+    pub fn set_join_qr(&mut self, join_url: Option<String>, show_qr: bool) {
+        self.join_url = join_url;
+        self.show_qr = show_qr;
+    }
+
+    pub fn set_server(&mut self, server: String) {
+        self.server = Some(server);
+    }
+
     pub fn update_peer_info(&mut self, peer_id: String, peer_count: usize) {
         self.local_peer_id = Some(peer_id);
         self.peer_count = peer_count;
     }
 
+    pub fn update_peer_stats(&mut self, mut stats: Vec<PeerStats>) {
+        // Stable: equally good peers keep their connection order
+        stats.sort_by_key(|peer| std::cmp::Reverse(peer.quality()));
+        self.peer_stats = stats;
+    }
+
     pub fn tick(&mut self) {
         if self.clipboard_message_timer > 0 {
             self.clipboard_message_timer -= 1;
@@ -82,7 +113,7 @@ impl SessionTab {
         #[cfg(feature = "tui")]
         {
             use arboard::Clipboard;
-            let command = format!("konnekt-tui join --session-id {}", self.session_id);
+            let command = self.join_command();
             match Clipboard::new() {
                 Ok(mut clipboard) => match clipboard.set_text(&command) {
                     Ok(_) => {
@@ -108,6 +139,20 @@ impl SessionTab {
         }
     }
 
+    /// Text on the clipboard, to fill in the join prompt
+    pub fn clipboard_text(&self) -> Option<String> {
+        #[cfg(feature = "tui")]
+        {
+            arboard::Clipboard::new()
+                .and_then(|mut clipboard| clipboard.get_text())
+                .ok()
+        }
+        #[cfg(not(feature = "tui"))]
+        {
+            None
+        }
+    }
+
     // Getters for rendering
     pub fn session_id(&self) -> &str {
         &self.session_id
@@ -124,4 +169,85 @@ impl SessionTab {
     pub fn peer_count(&self) -> usize {
         self.peer_count
     }
+
+    pub fn peer_stats(&self) -> &[PeerStats] {
+        &self.peer_stats
+    }
+
+    /// What another terminal runs to join, with the server when known
+    pub fn join_command(&self) -> String {
+        match &self.server {
+            Some(server) => format!(
+                "konnekt-tui join --server {} --session-id {}",
+                server, self.session_id
+            ),
+            None => format!("konnekt-tui join --session-id {}", self.session_id),
+        }
+    }
+
+    pub fn join_url(&self) -> Option<&str> {
+        self.join_url.as_deref()
+    }
+
+    pub fn show_qr(&self) -> bool {
+        self.show_qr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_p2p::domain::MatchboxPeerId;
+    use konnekt_session_p2p::{ConnectionQuality, ConnectionStatus, PeerId};
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    fn stats(rtt_ms: Option<u64>) -> PeerStats {
+        PeerStats {
+            peer_id: PeerId::new(MatchboxPeerId(Uuid::new_v4())),
+            participant_id: None,
+            status: ConnectionStatus::Connected,
+            rtt: rtt_ms.map(Duration::from_millis),
+            last_seen: Duration::ZERO,
+            messages_sent: 0,
+            messages_received: 0,
+        }
+    }
+
+    #[test]
+    fn test_struggling_peers_come_first() {
+        let mut tab = SessionTab::new("session".to_string());
+        let fast = stats(Some(20));
+        let unknown = stats(None);
+        let slow = stats(Some(800));
+
+        tab.update_peer_stats(vec![fast.clone(), unknown.clone(), slow.clone()]);
+
+        let order: Vec<_> = tab.peer_stats().iter().map(|p| p.peer_id).collect();
+        assert_eq!(order, vec![slow.peer_id, unknown.peer_id, fast.peer_id]);
+        assert_eq!(tab.peer_stats()[0].quality(), ConnectionQuality::Poor);
+    }
+
+    #[test]
+    fn test_r_toggles_the_qr_code() {
+        let mut tab = SessionTab::new("session".to_string());
+        tab.set_join_qr(Some("https://example.org".to_string()), true);
+        assert!(tab.show_qr());
+
+        assert!(tab.handle_key(KeyCode::Char('r')).is_none());
+        assert!(!tab.show_qr());
+        assert_eq!(tab.join_url(), Some("https://example.org"));
+    }
+
+    #[test]
+    fn test_join_command_names_the_server() {
+        let mut tab = SessionTab::new("session".to_string());
+        assert_eq!(tab.join_command(), "konnekt-tui join --session-id session");
+
+        tab.set_server("wss://match.example.org".to_string());
+        assert_eq!(
+            tab.join_command(),
+            "konnekt-tui join --server wss://match.example.org --session-id session"
+        );
+    }
 }
@@ -0,0 +1,128 @@
+use crossterm::event::KeyCode;
+use konnekt_session_core::LobbySettings;
+
+/// Smallest capacity the popup offers; one below is "unlimited"
+const MIN_CAPACITY: usize = 2;
+
+/// Rows of the settings popup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsField {
+    Capacity,
+    Locked,
+    AutoDelegation,
+}
+
+impl SettingsField {
+    pub const ALL: [SettingsField; 3] = [
+        SettingsField::Capacity,
+        SettingsField::Locked,
+        SettingsField::AutoDelegation,
+    ];
+
+    pub fn label(&self) -> &str {
+        match self {
+            SettingsField::Capacity => "Capacity",
+            SettingsField::Locked => "Locked",
+            SettingsField::AutoDelegation => "Auto-delegation",
+        }
+    }
+}
+
+/// Popup editing the lobby settings (host only, presentation only)
+#[derive(Debug, Clone)]
+pub struct SettingsDialog {
+    settings: LobbySettings,
+    selected: usize,
+    /// Participants right now, where a new capacity starts
+    participant_count: usize,
+}
+
+impl SettingsDialog {
+    pub fn new(settings: LobbySettings, participant_count: usize) -> Self {
+        Self {
+            settings,
+            selected: 0,
+            participant_count,
+        }
+    }
+
+    /// `Some(true)` on Enter (save), `Some(false)` on Esc (discard),
+    /// `None` while editing
+    pub fn answer(&mut self, key: KeyCode) -> Option<bool> {
+        match key {
+            KeyCode::Enter => return Some(true),
+            KeyCode::Esc => return Some(false),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1).min(SettingsField::ALL.len() - 1);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Char('+') | KeyCode::Char('l') | KeyCode::Right => self.adjust(true),
+            KeyCode::Char('-') | KeyCode::Char('h') | KeyCode::Left => self.adjust(false),
+            KeyCode::Char(' ') => self.adjust(true),
+            _ => {}
+        }
+        None
+    }
+
+    fn adjust(&mut self, up: bool) {
+        match self.selected_field() {
+            SettingsField::Capacity => {
+                self.settings.max_participants = match (self.settings.max_participants, up) {
+                    (None, true) => Some(self.participant_count.max(MIN_CAPACITY)),
+                    (None, false) => None,
+                    (Some(max), true) => Some(max + 1),
+                    (Some(max), false) if max <= MIN_CAPACITY => None,
+                    (Some(max), false) => Some(max - 1),
+                };
+            }
+            SettingsField::Locked => self.settings.locked = !self.settings.locked,
+            SettingsField::AutoDelegation => {
+                self.settings.auto_delegation = self.settings.auto_delegation.next();
+            }
+        }
+    }
+
+    pub fn selected_field(&self) -> SettingsField {
+        SettingsField::ALL[self.selected]
+    }
+
+    pub fn settings(&self) -> LobbySettings {
+        self.settings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_core::AutoDelegation;
+
+    #[test]
+    fn test_edit_settings() {
+        let mut dialog = SettingsDialog::new(LobbySettings::default(), 5);
+
+        // Capacity starts at the current head count and goes back to unlimited
+        assert_eq!(dialog.answer(KeyCode::Char('+')), None);
+        assert_eq!(dialog.settings().max_participants, Some(5));
+        for _ in 0..3 {
+            dialog.answer(KeyCode::Char('-'));
+        }
+        assert_eq!(dialog.settings().max_participants, Some(2));
+        dialog.answer(KeyCode::Char('-'));
+        assert_eq!(dialog.settings().max_participants, None);
+
+        dialog.answer(KeyCode::Down);
+        dialog.answer(KeyCode::Char(' '));
+        assert!(dialog.settings().locked);
+
+        dialog.answer(KeyCode::Down);
+        dialog.answer(KeyCode::Right);
+        assert_eq!(
+            dialog.settings().auto_delegation,
+            AutoDelegation::CoHostsFirst
+        );
+
+        assert_eq!(dialog.answer(KeyCode::Enter), Some(true));
+    }
+}
@@ -3,6 +3,8 @@ use std::time::Duration;
 
 pub enum AppEvent {
     Key(KeyCode),
+    /// Text pasted into the terminal (bracketed paste)
+    Paste(String),
     Tick,
 }
 
@@ -11,6 +13,7 @@ pub async fn read_events() -> std::io::Result<AppEvent> {
     if event::poll(Duration::from_millis(100))? {
         match event::read()? {
             Event::Key(KeyEvent { code, .. }) => Ok(AppEvent::Key(code)),
+            Event::Paste(text) => Ok(AppEvent::Paste(text)),
             _ => Ok(AppEvent::Tick),
         }
     } else {
@@ -1,12 +1,15 @@
 pub mod app;
 pub mod event;
+pub mod theme;
 pub mod ui;
 
 pub use app::{App, UserAction};
 pub use event::AppEvent;
+pub use theme::Theme;
 
 use crate::infrastructure::Result;
 use crossterm::{
+    event::{DisableBracketedPaste, EnableBracketedPaste},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
@@ -18,7 +21,7 @@ pub type TuiTerminal = Terminal<CrosstermBackend<io::Stdout>>;
 pub fn setup_terminal() -> Result<TuiTerminal> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend)?;
     Ok(terminal)
@@ -27,7 +30,11 @@ pub fn setup_terminal() -> Result<TuiTerminal> {
 /// Restore terminal to normal mode
 pub fn restore_terminal(mut terminal: TuiTerminal) -> Result<()> {
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableBracketedPaste,
+        LeaveAlternateScreen
+    )?;
     terminal.show_cursor()?;
     Ok(())
 }
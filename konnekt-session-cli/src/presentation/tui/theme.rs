@@ -0,0 +1,228 @@
+use crate::infrastructure::{CliError, Result};
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Colours every tab renderer draws with, by role rather than by hue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Labels, headings, names and info messages
+    pub accent: Color,
+    /// The selected tab or row, key hints and values to look at
+    pub highlight: Color,
+    /// Your own input, good scores, confirmations
+    pub success: Color,
+    pub warning: Color,
+    /// Errors and destructive actions
+    pub error: Color,
+    /// Unread badges, prompts and presence hints
+    pub special: Color,
+    pub text: Color,
+    /// Footer and secondary labels
+    pub muted: Color,
+    /// Placeholders for empty lists
+    pub faint: Color,
+    /// Background of the selected row
+    pub selection: Color,
+    pub search_fg: Color,
+    pub search_bg: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// Names accepted by `by_name`
+    pub const BUILT_IN: [&'static str; 4] = ["dark", "light", "high-contrast", "colorblind"];
+
+    /// The classic palette, for dark terminals
+    pub fn dark() -> Self {
+        Self {
+            accent: Color::Cyan,
+            highlight: Color::Yellow,
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+            special: Color::Magenta,
+            text: Color::White,
+            muted: Color::Gray,
+            faint: Color::DarkGray,
+            selection: Color::DarkGray,
+            search_fg: Color::Black,
+            search_bg: Color::Yellow,
+        }
+    }
+
+    /// For light terminals, where yellow and white text vanish
+    pub fn light() -> Self {
+        Self {
+            accent: Color::Blue,
+            highlight: Color::Magenta,
+            success: Color::Green,
+            warning: Color::Rgb(175, 95, 0),
+            error: Color::Red,
+            special: Color::Rgb(135, 0, 175),
+            text: Color::Black,
+            muted: Color::DarkGray,
+            faint: Color::Gray,
+            selection: Color::Gray,
+            search_fg: Color::Black,
+            search_bg: Color::LightYellow,
+        }
+    }
+
+    /// Bright colours only, for projectors and low vision
+    pub fn high_contrast() -> Self {
+        Self {
+            accent: Color::LightCyan,
+            highlight: Color::LightYellow,
+            success: Color::LightGreen,
+            warning: Color::LightYellow,
+            error: Color::LightRed,
+            special: Color::LightMagenta,
+            text: Color::White,
+            muted: Color::White,
+            faint: Color::Gray,
+            selection: Color::Blue,
+            search_fg: Color::Black,
+            search_bg: Color::White,
+        }
+    }
+
+    /// The Okabe-Ito palette: success and error differ in more than red
+    /// against green
+    pub fn colorblind() -> Self {
+        Self {
+            accent: Color::Rgb(86, 180, 233),
+            highlight: Color::Rgb(240, 228, 66),
+            success: Color::Rgb(0, 114, 178),
+            warning: Color::Rgb(230, 159, 0),
+            error: Color::Rgb(213, 94, 0),
+            special: Color::Rgb(204, 121, 167),
+            text: Color::White,
+            muted: Color::Gray,
+            faint: Color::DarkGray,
+            selection: Color::DarkGray,
+            search_fg: Color::Black,
+            search_bg: Color::Rgb(240, 228, 66),
+        }
+    }
+
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "colorblind" => Some(Self::colorblind()),
+            _ => None,
+        }
+    }
+
+    /// A built-in theme by name, or a theme file at that path
+    pub fn load(spec: &str) -> Result<Self> {
+        if let Some(theme) = Self::by_name(spec) {
+            return Ok(theme);
+        }
+
+        let path = Path::new(spec);
+        if !path.is_file() {
+            return Err(CliError::InvalidConfig(format!(
+                "Unknown theme '{}' (expected {} or a theme file)",
+                spec,
+                Self::BUILT_IN.join(", ")
+            )));
+        }
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parse a theme file: a built-in `base` (default: dark) with some of
+    /// its colours replaced, e.g. `{"base": "light", "accent": "#005faf"}`.
+    /// Colours are names (`red`, `light-blue`), `#rrggbb` or 0–255.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let file: ThemeFile = serde_json::from_str(json)
+            .map_err(|e| CliError::InvalidConfig(format!("Invalid theme file: {}", e)))?;
+
+        let mut theme = match file.base.as_deref() {
+            Some(base) => Self::by_name(base)
+                .ok_or_else(|| CliError::InvalidConfig(format!("Unknown base theme '{}'", base)))?,
+            None => Self::dark(),
+        };
+
+        let overrides = [
+            (&mut theme.accent, file.accent),
+            (&mut theme.highlight, file.highlight),
+            (&mut theme.success, file.success),
+            (&mut theme.warning, file.warning),
+            (&mut theme.error, file.error),
+            (&mut theme.special, file.special),
+            (&mut theme.text, file.text),
+            (&mut theme.muted, file.muted),
+            (&mut theme.faint, file.faint),
+            (&mut theme.selection, file.selection),
+            (&mut theme.search_fg, file.search_fg),
+            (&mut theme.search_bg, file.search_bg),
+        ];
+        for (slot, value) in overrides {
+            if let Some(value) = value {
+                *slot = Color::from_str(&value).map_err(|_| {
+                    CliError::InvalidConfig(format!("Invalid colour '{}' in theme", value))
+                })?;
+            }
+        }
+        Ok(theme)
+    }
+}
+
+/// On-disk form of a theme: every colour is optional
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    base: Option<String>,
+    accent: Option<String>,
+    highlight: Option<String>,
+    success: Option<String>,
+    warning: Option<String>,
+    error: Option<String>,
+    special: Option<String>,
+    text: Option<String>,
+    muted: Option<String>,
+    faint: Option<String>,
+    selection: Option<String>,
+    search_fg: Option<String>,
+    search_bg: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_built_in_themes() {
+        for name in Theme::BUILT_IN {
+            assert!(Theme::load(name).is_ok(), "{name}");
+        }
+        assert_eq!(Theme::default(), Theme::dark());
+        assert!(matches!(
+            Theme::load("no-such-theme"),
+            Err(CliError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_theme_file_overrides_base() {
+        let theme =
+            Theme::from_json(r##"{"base": "light", "accent": "#005faf", "error": "light-red"}"##)
+                .unwrap();
+
+        assert_eq!(theme.accent, Color::Rgb(0, 95, 175));
+        assert_eq!(theme.error, Color::LightRed);
+        assert_eq!(theme.text, Theme::light().text);
+
+        assert!(Theme::from_json(r#"{"accent": "not a colour"}"#).is_err());
+        assert!(Theme::from_json(r#"{"acent": "red"}"#).is_err());
+    }
+}
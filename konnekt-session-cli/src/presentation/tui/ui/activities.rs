@@ -1,24 +1,30 @@
+use crate::presentation::tui::Theme;
 use crate::presentation::tui::app::{ActivitiesTab, App};
-use konnekt_session_core::EchoChallenge;
+use konnekt_session_core::PluginRegistry;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
 };
 
-pub fn render_activities(f: &mut Frame, area: Rect, app: &App) {
+pub fn render_activities(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
     let activities_tab = &app.activities_tab;
 
     if activities_tab.is_host() {
-        render_activities_host(f, area, activities_tab);
+        render_activities_host(f, area, activities_tab, theme);
     } else {
-        render_activities_guest(f, area, activities_tab);
+        render_activities_guest(f, area, activities_tab, theme);
     }
 }
 
-fn render_activities_host(f: &mut Frame, area: Rect, activities_tab: &ActivitiesTab) {
+fn render_activities_host(
+    f: &mut Frame,
+    area: Rect,
+    activities_tab: &ActivitiesTab,
+    theme: &Theme,
+) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -41,11 +47,11 @@ fn render_activities_host(f: &mut Frame, area: Rect, activities_tab: &Activities
 
             let mut item = ListItem::new(Line::from(vec![
                 Span::raw(prefix),
-                Span::styled(&template.name, Style::default().fg(Color::Cyan)),
+                Span::styled(&template.name, Style::default().fg(theme.accent)),
             ]));
 
             if idx == activities_tab.selected_template() {
-                item = item.style(Style::default().bg(Color::DarkGray));
+                item = item.style(Style::default().bg(theme.selection));
             }
 
             item
@@ -67,25 +73,25 @@ fn render_activities_host(f: &mut Frame, area: Rect, activities_tab: &Activities
         activity_text.push(Line::from(vec![Span::styled(
             "🎮 Current Activity:",
             Style::default()
-                .fg(Color::Green)
+                .fg(theme.success)
                 .add_modifier(Modifier::BOLD),
         )]));
         activity_text.push(Line::from(""));
         activity_text.push(Line::from(vec![Span::styled(
             &current.name,
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme.highlight),
         )]));
         activity_text.push(Line::from(""));
         activity_text.push(Line::from(vec![
             Span::raw("Press "),
-            Span::styled("x", Style::default().fg(Color::Red)),
+            Span::styled("x", Style::default().fg(theme.error)),
             Span::raw(" to cancel"),
         ]));
     } else if !activities_tab.planned_activities().is_empty() {
         activity_text.push(Line::from(vec![Span::styled(
             "📋 Planned Activities:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )]));
         activity_text.push(Line::from(""));
@@ -93,14 +99,14 @@ fn render_activities_host(f: &mut Frame, area: Rect, activities_tab: &Activities
         for activity in activities_tab.planned_activities() {
             activity_text.push(Line::from(vec![
                 Span::raw("  • "),
-                Span::styled(&activity.name, Style::default().fg(Color::White)),
+                Span::styled(&activity.name, Style::default().fg(theme.text)),
             ]));
         }
 
         activity_text.push(Line::from(""));
         activity_text.push(Line::from(vec![
             Span::raw("Press "),
-            Span::styled("s", Style::default().fg(Color::Green)),
+            Span::styled("s", Style::default().fg(theme.success)),
             Span::raw(" to start first activity"),
         ]));
     } else {
@@ -108,7 +114,7 @@ fn render_activities_host(f: &mut Frame, area: Rect, activities_tab: &Activities
         activity_text.push(Line::from(""));
         activity_text.push(Line::from(vec![
             Span::raw("Press "),
-            Span::styled("p", Style::default().fg(Color::Green)),
+            Span::styled("p", Style::default().fg(theme.success)),
             Span::raw(" to plan selected activity"),
         ]));
     }
@@ -122,33 +128,42 @@ fn render_activities_host(f: &mut Frame, area: Rect, activities_tab: &Activities
     f.render_widget(activities_para, chunks[1]);
 }
 
-fn render_activities_guest(f: &mut Frame, area: Rect, activities_tab: &ActivitiesTab) {
+fn render_activities_guest(
+    f: &mut Frame,
+    area: Rect,
+    activities_tab: &ActivitiesTab,
+    theme: &Theme,
+) {
     let mut text = vec![];
 
     if let Some(current) = activities_tab.current_activity() {
         text.push(Line::from(vec![Span::styled(
             "🎮 Current Activity:",
             Style::default()
-                .fg(Color::Green)
+                .fg(theme.success)
                 .add_modifier(Modifier::BOLD),
         )]));
         text.push(Line::from(""));
         text.push(Line::from(vec![Span::styled(
             &current.name,
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.highlight)
                 .add_modifier(Modifier::BOLD),
         )]));
         text.push(Line::from(""));
 
-        // Parse activity config to show prompt
-        if let Ok(challenge) = EchoChallenge::from_config(current.config.clone()) {
+        // Ask the activity's plugin for the prompt
+        if let Some(prompt) = PluginRegistry::global()
+            .render_hints(&current.activity_type, &current.config)
+            .ok()
+            .and_then(|hints| hints.prompt)
+        {
             text.push(Line::from(vec![
-                Span::styled("Prompt: ", Style::default().fg(Color::Cyan)),
+                Span::styled("Prompt: ", Style::default().fg(theme.accent)),
                 Span::styled(
-                    challenge.prompt.clone(),
+                    prompt,
                     Style::default()
-                        .fg(Color::Magenta)
+                        .fg(theme.special)
                         .add_modifier(Modifier::BOLD),
                 ),
             ]));
@@ -158,23 +173,23 @@ fn render_activities_guest(f: &mut Frame, area: Rect, activities_tab: &Activitie
         text.push(Line::from("─".repeat(50)));
         text.push(Line::from(""));
         text.push(Line::from(vec![
-            Span::styled("Your Response: ", Style::default().fg(Color::Cyan)),
+            Span::styled("Your Response: ", Style::default().fg(theme.accent)),
             Span::styled(
                 activities_tab.activity_input(),
-                Style::default().fg(Color::Green),
+                Style::default().fg(theme.success),
             ),
         ]));
         text.push(Line::from(""));
         text.push(Line::from(vec![
             Span::raw("Press "),
-            Span::styled("Enter", Style::default().fg(Color::Green)),
+            Span::styled("Enter", Style::default().fg(theme.success)),
             Span::raw(" to submit"),
         ]));
     } else if !activities_tab.planned_activities().is_empty() {
         text.push(Line::from(vec![Span::styled(
             "📋 Upcoming Activities:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )]));
         text.push(Line::from(""));
@@ -182,7 +197,7 @@ fn render_activities_guest(f: &mut Frame, area: Rect, activities_tab: &Activitie
         for activity in activities_tab.planned_activities() {
             text.push(Line::from(vec![
                 Span::raw("  • "),
-                Span::styled(&activity.name, Style::default().fg(Color::White)),
+                Span::styled(&activity.name, Style::default().fg(theme.text)),
             ]));
         }
 
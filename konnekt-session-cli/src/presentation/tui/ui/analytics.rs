@@ -0,0 +1,200 @@
+use crate::presentation::tui::Theme;
+use crate::presentation::tui::app::App;
+use konnekt_session_core::SessionMetrics;
+use konnekt_session_core::analytics::DEFAULT_SCORE_BUCKETS;
+use konnekt_session_core::domain::RunStatus;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, List, ListItem, Paragraph},
+};
+
+/// Height of the summary row on top
+const SUMMARY_HEIGHT: u16 = 5;
+
+/// Height of the drop-off list at the bottom
+const DROP_OFFS_HEIGHT: u16 = 8;
+
+pub fn render_analytics(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let metrics = app.analytics_tab.metrics();
+
+    if metrics.is_empty() {
+        let text = vec![
+            Line::from("No activities played yet"),
+            Line::from(""),
+            Line::from(
+                "Answer times, participation and drop-offs show up here once an activity starts",
+            ),
+        ];
+
+        let paragraph =
+            Paragraph::new(text).block(Block::default().borders(Borders::ALL).title("Analytics"));
+
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(SUMMARY_HEIGHT),
+            Constraint::Min(6),
+            Constraint::Length(DROP_OFFS_HEIGHT),
+        ])
+        .split(area);
+    let middle = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(rows[1]);
+
+    render_summary(f, rows[0], metrics, theme);
+    render_runs(f, middle[0], app, theme);
+    render_distribution(f, middle[1], metrics, theme);
+    render_drop_offs(f, rows[2], metrics, theme);
+}
+
+fn render_summary(f: &mut Frame, area: Rect, metrics: &SessionMetrics, theme: &Theme) {
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(theme.muted));
+    let value = |text: String| {
+        Span::styled(
+            text,
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+
+    let text = vec![
+        Line::from(vec![
+            label("Average answer time: "),
+            value(seconds(metrics.average_answer_ms())),
+        ]),
+        Line::from(vec![
+            label("Participation:       "),
+            value(percent(metrics.participation_rate())),
+        ]),
+        Line::from(vec![
+            label("Left the session:    "),
+            value(metrics.drop_offs().len().to_string()),
+        ]),
+    ];
+
+    let paragraph =
+        Paragraph::new(text).block(Block::default().borders(Borders::ALL).title("Session"));
+    f.render_widget(paragraph, area);
+}
+
+fn render_runs(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let tab = &app.analytics_tab;
+    let items: Vec<ListItem> = tab
+        .metrics()
+        .runs()
+        .iter()
+        .enumerate()
+        .map(|(idx, run)| {
+            let prefix = if idx == tab.selected_run() {
+                "> "
+            } else {
+                "  "
+            };
+            let status = match run.status {
+                RunStatus::InProgress => Span::styled(" ▶", Style::default().fg(theme.success)),
+                RunStatus::Completed => Span::raw("  "),
+                RunStatus::Cancelled => Span::styled(" ✗", Style::default().fg(theme.error)),
+            };
+
+            let mut spans = vec![
+                Span::raw(prefix),
+                Span::styled(&run.name, Style::default().fg(theme.accent)),
+                status,
+                Span::raw(format!(
+                    "  {}/{} answered ({})",
+                    run.submitted,
+                    run.eligible,
+                    percent(run.participation_rate())
+                )),
+                Span::styled(
+                    format!("  ⏱ {}", seconds(run.average_answer_ms())),
+                    Style::default().fg(theme.muted),
+                ),
+            ];
+            if run.dropped > 0 {
+                spans.push(Span::styled(
+                    format!("  {} dropped", run.dropped),
+                    Style::default().fg(theme.warning),
+                ));
+            }
+
+            let mut item = ListItem::new(Line::from(spans));
+            if idx == tab.selected_run() {
+                item = item.style(Style::default().bg(theme.selection));
+            }
+            item
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Activities (j/k: select)"),
+    );
+    f.render_widget(list, area);
+}
+
+fn render_distribution(f: &mut Frame, area: Rect, metrics: &SessionMetrics, theme: &Theme) {
+    let distribution = metrics.score_distribution(DEFAULT_SCORE_BUCKETS);
+    let bars: Vec<Bar> = distribution
+        .buckets
+        .iter()
+        .map(|bucket| {
+            Bar::default()
+                .value(bucket.count as u64)
+                .label(Line::from(format!("{}-{}", bucket.min, bucket.max)))
+                .style(Style::default().fg(theme.accent))
+                .value_style(Style::default().fg(theme.text).bg(theme.accent))
+        })
+        .collect();
+
+    let chart = BarChart::default()
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "Score distribution ({} results)",
+            distribution.total()
+        )))
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(7)
+        .bar_gap(1);
+
+    f.render_widget(chart, area);
+}
+
+fn render_drop_offs(f: &mut Frame, area: Rect, metrics: &SessionMetrics, theme: &Theme) {
+    let items: Vec<ListItem> = metrics
+        .drop_offs()
+        .iter()
+        .map(|drop_off| {
+            let when = match &drop_off.during {
+                Some(activity) => format!("during {}", activity),
+                None => format!("after {} activities", drop_off.completed_runs),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(&drop_off.name, Style::default().fg(theme.accent)),
+                Span::styled(format!("  left {}", when), Style::default().fg(theme.muted)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title("Drop-offs"));
+    f.render_widget(list, area);
+}
+
+fn seconds(ms: Option<u64>) -> String {
+    ms.map_or("–".to_string(), |ms| {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    })
+}
+
+fn percent(rate: Option<f64>) -> String {
+    rate.map_or("–".to_string(), |rate| format!("{:.0}%", rate * 100.0))
+}
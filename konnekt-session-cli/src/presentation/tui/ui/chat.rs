@@ -0,0 +1,97 @@
+use crate::presentation::tui::Theme;
+use crate::presentation::tui::app::App;
+use konnekt_session_p2p::Presence;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+};
+
+pub fn render_chat(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let chat_tab = &app.chat_tab;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Scrollback
+            Constraint::Length(3), // Input line
+        ])
+        .split(area);
+
+    // Newest message at the bottom, minus whatever is scrolled back
+    let messages = chat_tab.messages();
+    let end = messages.len().saturating_sub(chat_tab.scroll_offset());
+    let rows = chunks[0].height.saturating_sub(2) as usize;
+    let start = end.saturating_sub(rows);
+
+    let items: Vec<ListItem> = messages[start..end]
+        .iter()
+        .map(|message| {
+            let author = app
+                .lobby_snapshot
+                .as_ref()
+                .and_then(|lobby| lobby.participants().get(&message.author_id()))
+                .map(|p| p.name().to_string())
+                .unwrap_or_else(|| "(left)".to_string());
+            let author_style = if Some(message.author_id()) == app.local_participant_id {
+                Style::default()
+                    .fg(theme.success)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(author, author_style),
+                Span::raw(": "),
+                Span::raw(message.text()),
+            ]))
+        })
+        .collect();
+
+    let title = if chat_tab.scroll_offset() > 0 {
+        format!(
+            "Chat ({}) - {} newer below",
+            messages.len(),
+            chat_tab.scroll_offset()
+        )
+    } else {
+        format!("Chat ({})", messages.len())
+    };
+    let history = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .style(Style::default().fg(theme.text));
+    f.render_widget(history, chunks[0]);
+
+    // Who else is typing
+    let typing: Vec<String> = app
+        .presence
+        .iter()
+        .filter(|(_, presence)| *presence == Presence::Typing)
+        .filter_map(|(id, _)| {
+            app.lobby_snapshot
+                .as_ref()
+                .and_then(|lobby| lobby.participants().get(id))
+                .map(|p| p.name().to_string())
+        })
+        .collect();
+    let input_title = if typing.is_empty() {
+        "Message".to_string()
+    } else {
+        format!("Message - ✍️ {} typing…", typing.join(", "))
+    };
+
+    let input = Paragraph::new(chat_tab.input())
+        .style(Style::default().fg(theme.success))
+        .block(Block::default().borders(Borders::ALL).title(input_title));
+    f.render_widget(input, chunks[1]);
+
+    f.set_cursor_position((
+        chunks[1].x + 1 + chat_tab.cursor_position() as u16,
+        chunks[1].y + 1,
+    ));
+}
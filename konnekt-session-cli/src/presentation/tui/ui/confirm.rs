@@ -0,0 +1,67 @@
+use crate::presentation::tui::Theme;
+use crate::presentation::tui::app::App;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Draw the open confirmation dialog (if any) on top of everything else
+pub fn render_confirm(f: &mut Frame, app: &App, theme: &Theme) {
+    let Some(dialog) = &app.confirm_dialog else {
+        return;
+    };
+    let keys = app.confirm_keys;
+
+    let area = centered(f.area(), 50, 7);
+    let text = vec![
+        Line::from(""),
+        Line::from(dialog.message()),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                format!("[{}]", keys.yes),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" Yes    "),
+            Span::styled(
+                format!("[{}/Esc]", keys.no),
+                Style::default()
+                    .fg(theme.success)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" No"),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.error))
+                .title(dialog.title()),
+        );
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// A `width_percent` wide, `height` rows high box in the middle of `area`
+pub(super) fn centered(area: Rect, width_percent: u16, height: u16) -> Rect {
+    let width = ((area.width as u32 * width_percent as u32 / 100) as u16)
+        .max(30)
+        .min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
@@ -1,28 +1,143 @@
-use crate::presentation::tui::app::App;
+use crate::presentation::tui::Theme;
+use crate::presentation::tui::app::{App, EventsInput, LogEntry, Severity};
 use ratatui::{
     Frame,
-    layout::Rect,
-    style::{Color, Style},
-    widgets::{Block, Borders, List, ListItem},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
 };
 
-pub fn render_events(f: &mut Frame, area: Rect, app: &App) {
+pub fn render_events(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
     let events_tab = &app.events_tab;
 
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Log
+            Constraint::Length(3), // Filter / search
+        ])
+        .split(area);
+
+    let shown = events_tab.visible().count();
     let events: Vec<ListItem> = events_tab
-        .event_log()
-        .iter()
+        .visible()
         .skip(events_tab.scroll_offset())
-        .map(|e| ListItem::new(e.as_str()))
+        .take(chunks[0].height.saturating_sub(2) as usize)
+        .map(|entry| {
+            let search = events_tab.is_match(entry).then_some(events_tab.search());
+            ListItem::new(render_entry(entry, search, theme))
+        })
         .collect();
 
+    let title = if shown == events_tab.event_log().len() {
+        format!("Event Log ({})", shown)
+    } else {
+        format!("Event Log ({} of {})", shown, events_tab.event_log().len())
+    };
     let list = List::new(events)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(format!("Event Log ({})", events_tab.event_log().len())),
-        )
-        .style(Style::default().fg(Color::White));
-
-    f.render_widget(list, area);
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .style(Style::default().fg(theme.text));
+    f.render_widget(list, chunks[0]);
+
+    // Filter and search inputs
+    let field = |label: &'static str, value: &str, input: EventsInput| {
+        let style = if events_tab.editing() == Some(input) {
+            Style::default().fg(theme.success)
+        } else {
+            Style::default().fg(theme.muted)
+        };
+        vec![
+            Span::styled(label, Style::default().fg(theme.highlight)),
+            Span::styled(value.to_string(), style),
+        ]
+    };
+    let mut spans = field("Filter: ", events_tab.filter_input(), EventsInput::Filter);
+    spans.push(Span::raw("   "));
+    spans.extend(field("Search: ", events_tab.search(), EventsInput::Search));
+
+    let input = Paragraph::new(Line::from(spans)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("f: filter (type: who: level: text) | /: search"),
+    );
+    f.render_widget(input, chunks[1]);
+
+    // Cursor at the end of the edited input
+    if let Some(editing) = events_tab.editing() {
+        let offset = match editing {
+            EventsInput::Filter => "Filter: ".len() + events_tab.filter_input().chars().count(),
+            EventsInput::Search => {
+                "Filter: ".len()
+                    + events_tab.filter_input().chars().count()
+                    + "   Search: ".len()
+                    + events_tab.search().chars().count()
+            }
+        };
+        f.set_cursor_position((chunks[1].x + 1 + offset as u16, chunks[1].y + 1));
+    }
+}
+
+pub(super) fn severity_style(severity: Severity, theme: &Theme) -> Style {
+    match severity {
+        Severity::Info => Style::default().fg(theme.accent),
+        Severity::Warning => Style::default().fg(theme.warning),
+        Severity::Error => Style::default()
+            .fg(theme.error)
+            .add_modifier(Modifier::BOLD),
+    }
+}
+
+/// `[Kind] text`, with every occurrence of `search` highlighted
+fn render_entry<'a>(entry: &'a LogEntry, search: Option<&str>, theme: &Theme) -> Line<'a> {
+    let style = severity_style(entry.severity, theme);
+    let mut spans = vec![Span::styled(format!("[{}] ", entry.kind), style)];
+
+    let text = entry.text.as_str();
+    let text_style = if entry.severity == Severity::Info {
+        Style::default()
+    } else {
+        style
+    };
+    let highlight = Style::default()
+        .fg(theme.search_fg)
+        .bg(theme.search_bg)
+        .add_modifier(Modifier::BOLD);
+
+    let mut rest = 0;
+    if let Some(search) = search.filter(|s| !s.is_empty()) {
+        for (start, end) in match_ranges(text, search) {
+            spans.push(Span::styled(&text[rest..start], text_style));
+            spans.push(Span::styled(&text[start..end], highlight));
+            rest = end;
+        }
+    }
+    spans.push(Span::styled(&text[rest..], text_style));
+
+    Line::from(spans)
+}
+
+/// Byte ranges of case-insensitive, non-overlapping matches of `needle`
+fn match_ranges(haystack: &str, needle: &str) -> Vec<(usize, usize)> {
+    let needle: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+    let chars: Vec<(usize, char)> = haystack.char_indices().collect();
+    let mut ranges = Vec::new();
+
+    let mut i = 0;
+    while i + needle.len() <= chars.len() {
+        let matched = chars[i..i + needle.len()]
+            .iter()
+            .zip(&needle)
+            .all(|((_, c), n)| c.to_lowercase().eq(std::iter::once(*n)));
+        if matched {
+            let end = chars
+                .get(i + needle.len())
+                .map_or(haystack.len(), |(index, _)| *index);
+            ranges.push((chars[i].0, end));
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    ranges
 }
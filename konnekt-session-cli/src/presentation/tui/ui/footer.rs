@@ -1,15 +1,28 @@
+use crate::presentation::tui::Theme;
 use crate::presentation::tui::app::{App, Tab};
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Style},
+    style::Style,
     text::Line,
     widgets::{Block, Borders, Paragraph},
 };
 
-pub fn render_footer(f: &mut Frame, area: Rect, app: &App) {
+pub fn render_footer(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let confirm_hint;
     let shortcuts = match app.current_tab {
-        Tab::Session => "y: copy ID | c: copy cmd | Tab: switch | q: quit",
+        _ if app.confirm_dialog.is_some() => {
+            let keys = app.confirm_keys;
+            confirm_hint = format!("{}: confirm | {}/Esc: back", keys.yes, keys.no);
+            confirm_hint.as_str()
+        }
+        _ if app.notifications.is_panel_open() => "j/k: scroll | F2/Esc: close notifications",
+        _ if app.settings_dialog.is_some() => {
+            "j/k: select | ←/→: change | Enter: save | Esc: cancel"
+        }
+        Tab::Session => {
+            "y: copy ID | c: copy cmd | r: QR code | j: join other | Tab: switch | q: quit"
+        }
         Tab::Activities if app.is_host && app.activities_tab.current_activity().is_none() => {
             // Host in planning mode (no activity running)
             "j/k: select | p: plan | s: start | Tab: switch | q: quit"
@@ -23,10 +36,23 @@ pub fn render_footer(f: &mut Frame, area: Rect, app: &App) {
             "Type answer | Enter: submit | Tab: switch | q: quit"
         }
         Tab::Participants if app.is_host => {
-            "j/k: select | t: toggle mode | x: kick | Tab: switch | q: quit"
+            "j/k: select | Enter: details | t: mode | x: kick | d: make host | o: co-host | s: settings"
+        }
+        Tab::Participants if app.can_moderate() => {
+            "j/k: select | Enter: details | t: toggle mode | x: kick | Tab: switch | q: quit"
+        }
+        Tab::Participants => {
+            "j/k: select | Enter: details | t: toggle mode | Tab: switch | q: quit"
+        }
+        Tab::Chat => {
+            "Type message | Enter: send | ↑/↓: scroll | End: newest | Tab: switch | Esc: quit"
+        }
+        Tab::Results => "j/k: navigate | e: export | Tab: switch | q: quit",
+        Tab::Analytics => "j/k: select activity | Tab: switch | q: quit",
+        Tab::Events if app.events_tab.editing().is_some() => {
+            "Type to narrow down | Enter: keep | Esc: clear"
         }
-        Tab::Participants => "t: toggle mode | Tab: switch | q: quit",
-        Tab::Results => "j/k: navigate | Tab: switch | q: quit",
+        Tab::Events => "j/k: scroll | f: filter | /: search | n/N: next/prev match | q: quit",
         _ => "Tab: switch | q: quit",
     };
 
@@ -34,7 +60,7 @@ pub fn render_footer(f: &mut Frame, area: Rect, app: &App) {
 
     let paragraph = Paragraph::new(text)
         .block(Block::default().borders(Borders::ALL))
-        .style(Style::default().fg(Color::Gray));
+        .style(Style::default().fg(theme.muted));
 
     f.render_widget(paragraph, area);
 }
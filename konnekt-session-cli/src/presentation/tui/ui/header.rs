@@ -1,29 +1,63 @@
+use crate::presentation::tui::Theme;
 use crate::presentation::tui::app::{App, Tab};
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Tabs},
 };
 
-pub fn render_header(f: &mut Frame, area: Rect, app: &App) {
+pub fn render_header(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let unread = app.chat_tab.unread();
+    let chat_title = if unread > 0 {
+        Line::from(vec![
+            Span::raw(Tab::Chat.title()),
+            Span::styled(
+                format!(" ({})", unread),
+                Style::default()
+                    .fg(theme.special)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ])
+    } else {
+        Line::from(Tab::Chat.title())
+    };
+
     let titles = vec![
-        Tab::Session.title(),
-        Tab::Lobby.title(),
-        Tab::Activities.title(),
-        Tab::Participants.title(),
-        Tab::Results.title(),
-        Tab::Events.title(),
-        Tab::Help.title(),
+        Line::from(Tab::Session.title()),
+        Line::from(Tab::Lobby.title()),
+        Line::from(Tab::Activities.title()),
+        Line::from(Tab::Participants.title()),
+        chat_title,
+        Line::from(Tab::Results.title()),
+        Line::from(Tab::Analytics.title()),
+        Line::from(Tab::Events.title()),
+        Line::from(Tab::Help.title()),
     ];
 
+    let unseen = app.notifications.unread();
+    let title = if unseen > 0 {
+        Line::from(vec![
+            Span::raw("Konnekt TUI "),
+            Span::styled(
+                format!("🔔 {} (F2)", unseen),
+                Style::default()
+                    .fg(theme.special)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ])
+    } else {
+        Line::from("Konnekt TUI")
+    };
+
     let tabs = Tabs::new(titles)
-        .block(Block::default().borders(Borders::ALL).title("Konnekt TUI"))
+        .block(Block::default().borders(Borders::ALL).title(title))
         .select(app.current_tab as usize)
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(theme.text))
         .highlight_style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.highlight)
                 .add_modifier(Modifier::BOLD),
         );
 
@@ -1,116 +1,220 @@
+use crate::presentation::tui::Theme;
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
 
-pub fn render_help(f: &mut Frame, area: Rect) {
+pub fn render_help(f: &mut Frame, area: Rect, theme: &Theme) {
     let text = vec![
         Line::from(""),
         Line::from(vec![Span::styled(
             "Session Tab:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  y", Style::default().fg(Color::Yellow)),
+            Span::styled("  y", Style::default().fg(theme.highlight)),
             Span::raw("  Copy Session ID to clipboard"),
         ]),
         Line::from(vec![
-            Span::styled("  c", Style::default().fg(Color::Yellow)),
+            Span::styled("  c", Style::default().fg(theme.highlight)),
             Span::raw("  Copy join command to clipboard"),
         ]),
+        Line::from(vec![
+            Span::styled("  r", Style::default().fg(theme.highlight)),
+            Span::raw("  Show or hide the join QR code"),
+        ]),
+        Line::from(vec![
+            Span::styled("  j", Style::default().fg(theme.highlight)),
+            Span::raw("  Join another session (paste an ID, link or command)"),
+        ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Activities Tab (Host):",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  j/k", Style::default().fg(Color::Yellow)),
+            Span::styled("  j/k", Style::default().fg(theme.highlight)),
             Span::raw("  Select activity template"),
         ]),
         Line::from(vec![
-            Span::styled("  p", Style::default().fg(Color::Yellow)),
+            Span::styled("  p", Style::default().fg(theme.highlight)),
             Span::raw("  Plan selected activity"),
         ]),
         Line::from(vec![
-            Span::styled("  s", Style::default().fg(Color::Yellow)),
+            Span::styled("  s", Style::default().fg(theme.highlight)),
             Span::raw("  Start first planned activity"),
         ]),
         Line::from(vec![
-            Span::styled("  x", Style::default().fg(Color::Yellow)),
+            Span::styled("  x", Style::default().fg(theme.highlight)),
             Span::raw("  Cancel current activity"),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Activities Tab (Guest):",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  Type", Style::default().fg(Color::Yellow)),
+            Span::styled("  Type", Style::default().fg(theme.highlight)),
             Span::raw("  Enter your response"),
         ]),
         Line::from(vec![
-            Span::styled("  Enter", Style::default().fg(Color::Yellow)),
+            Span::styled("  Enter", Style::default().fg(theme.highlight)),
             Span::raw("  Submit response"),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Participants Tab:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  t", Style::default().fg(Color::Yellow)),
+            Span::styled("  t", Style::default().fg(theme.highlight)),
             Span::raw("  Toggle Active ↔ Spectating mode"),
         ]),
         Line::from(vec![
-            Span::styled("  j/k", Style::default().fg(Color::Yellow)),
-            Span::raw("  Navigate participants (host only)"),
+            Span::styled("  j/k", Style::default().fg(theme.highlight)),
+            Span::raw("  Navigate participants"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Enter", Style::default().fg(theme.highlight)),
+            Span::raw("  Details: join time, results, presence history and actions"),
+        ]),
+        Line::from(vec![
+            Span::styled("  x", Style::default().fg(theme.highlight)),
+            Span::raw("  Kick selected guest (host and co-hosts)"),
         ]),
         Line::from(vec![
-            Span::styled("  x", Style::default().fg(Color::Yellow)),
-            Span::raw("  Kick selected guest (host only)"),
+            Span::styled("  f", Style::default().fg(theme.highlight)),
+            Span::raw("  Force an active guest to spectate (in details, host and co-hosts)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  d", Style::default().fg(theme.highlight)),
+            Span::raw("  Hand hosting to the selected guest (host only)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  o", Style::default().fg(theme.highlight)),
+            Span::raw("  Make the selected guest a co-host ⭐, or not (host only)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  s", Style::default().fg(theme.highlight)),
+            Span::raw("  Lobby settings: capacity, lock, auto-delegation (host only)"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Chat Tab:",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("  Type", Style::default().fg(theme.highlight)),
+            Span::raw("  Write a message (←/→ move the cursor)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Enter", Style::default().fg(theme.highlight)),
+            Span::raw("  Send message"),
+        ]),
+        Line::from(vec![
+            Span::styled("  ↑/↓", Style::default().fg(theme.highlight)),
+            Span::raw("  Scroll history (End: jump to newest)"),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Results Tab:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  j/k", Style::default().fg(Color::Yellow)),
+            Span::styled("  j/k", Style::default().fg(theme.highlight)),
             Span::raw("  Navigate completed activities"),
         ]),
+        Line::from(vec![
+            Span::styled("  v", Style::default().fg(theme.highlight)),
+            Span::raw("  Chart the selected activity or the session totals"),
+        ]),
+        Line::from(vec![
+            Span::styled("  e", Style::default().fg(theme.highlight)),
+            Span::raw("  Export results and leaderboard (CSV + JSON)"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Analytics Tab:",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("  j/k", Style::default().fg(theme.highlight)),
+            Span::raw("  Select an activity (answer times, participation, drop-outs)"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Events Tab:",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("  f", Style::default().fg(theme.highlight)),
+            Span::raw("  Filter, e.g. type:run who:alice level:warn late"),
+        ]),
+        Line::from(vec![
+            Span::styled("  /", Style::default().fg(theme.highlight)),
+            Span::raw("  Search and highlight (n/N: next/previous match)"),
+        ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Navigation:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  Tab / →", Style::default().fg(Color::Yellow)),
+            Span::styled("  Tab / →", Style::default().fg(theme.highlight)),
             Span::raw("  Next tab"),
         ]),
         Line::from(vec![
-            Span::styled("  Shift+Tab / ←", Style::default().fg(Color::Yellow)),
+            Span::styled("  Shift+Tab / ←", Style::default().fg(theme.highlight)),
             Span::raw("  Previous tab"),
         ]),
+        Line::from(vec![
+            Span::styled("  F2", Style::default().fg(theme.highlight)),
+            Span::raw("  Notification history"),
+        ]),
+        Line::from(vec![
+            Span::styled("  F3", Style::default().fg(theme.highlight)),
+            Span::raw("  Dump the last log lines for a bug report (needs --log-file)"),
+        ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("  q / Esc", Style::default().fg(Color::Yellow)),
+            Span::styled("  q / Esc", Style::default().fg(theme.highlight)),
             Span::raw("  Quit"),
         ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Confirmations:",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("  y / n", Style::default().fg(theme.highlight)),
+            Span::raw("  Confirm or back out of a kick, handover, cancel or quitting as host"),
+        ]),
+        Line::from(vec![Span::raw(
+            "        (change with --confirm-yes-key / --confirm-no-key)",
+        )]),
     ];
 
     let paragraph = Paragraph::new(text).block(
@@ -0,0 +1,53 @@
+use super::confirm::centered;
+use crate::presentation::tui::Theme;
+use crate::presentation::tui::app::App;
+use ratatui::{
+    Frame,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Draw the open join prompt (if any) over the tabs
+pub fn render_join_prompt(f: &mut Frame, app: &App, theme: &Theme) {
+    let Some(prompt) = &app.join_prompt else {
+        return;
+    };
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "Paste or type a session ID, join link or join command:",
+            Style::default().fg(theme.muted),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme.accent)),
+            Span::styled(prompt.input(), Style::default().fg(theme.success)),
+            Span::styled("█", Style::default().fg(theme.highlight)),
+        ]),
+    ];
+    if let Some(error) = prompt.error() {
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            format!("❌ {}", error),
+            Style::default().fg(theme.error),
+        )));
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        "Enter: join | Esc: cancel",
+        Style::default().fg(theme.muted),
+    )));
+
+    let area = centered(f.area(), 70, text.len() as u16 + 4);
+    let paragraph = Paragraph::new(text).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title("Join another session"),
+    );
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
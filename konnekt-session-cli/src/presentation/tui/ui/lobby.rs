@@ -1,25 +1,42 @@
+use crate::presentation::tui::Theme;
 use crate::presentation::tui::app::App;
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Style},
+    style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
 
-pub fn render_lobby(f: &mut Frame, area: Rect, app: &App) {
+pub fn render_lobby(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
     let lobby_tab = &app.lobby_tab;
 
     let text = if let Some(lobby_name) = lobby_tab.lobby_name() {
+        let settings = lobby_tab.settings();
+        let capacity = settings
+            .max_participants
+            .map_or(String::new(), |max| format!(" / {}", max));
         vec![
             Line::from(vec![
-                Span::styled("Lobby: ", Style::default().fg(Color::Cyan)),
+                Span::styled("Lobby: ", Style::default().fg(theme.accent)),
                 Span::raw(lobby_name),
             ]),
             Line::from(""),
             Line::from(vec![
-                Span::styled("Participants: ", Style::default().fg(Color::Cyan)),
-                Span::raw(lobby_tab.participant_count().to_string()),
+                Span::styled("Participants: ", Style::default().fg(theme.accent)),
+                Span::raw(format!("{}{}", lobby_tab.participant_count(), capacity)),
+            ]),
+            Line::from(vec![
+                Span::styled("Joining: ", Style::default().fg(theme.accent)),
+                if settings.locked {
+                    Span::styled("🔒 locked", Style::default().fg(theme.error))
+                } else {
+                    Span::raw("open")
+                },
+            ]),
+            Line::from(vec![
+                Span::styled("Auto-delegation: ", Style::default().fg(theme.accent)),
+                Span::raw(settings.auto_delegation.to_string()),
             ]),
         ]
     } else {
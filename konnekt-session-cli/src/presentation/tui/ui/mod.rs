@@ -1,16 +1,25 @@
+use super::Theme;
 use super::app::App;
 use ratatui::Frame;
 use ratatui::layout::Rect;
 
 mod activities;
+mod analytics;
+mod chat;
+mod confirm;
 mod events;
 mod footer;
 mod header;
 mod help;
+mod join_prompt;
 mod lobby;
+mod notifications;
+mod participant_detail;
 mod participants;
 mod results;
 mod session;
+mod settings;
+mod takeover;
 
 use activities::render_activities;
 use events::render_events;
@@ -26,7 +35,7 @@ use super::app::Tab;
 use ratatui::layout::{Constraint, Direction, Layout};
 
 /// Main render function - orchestrates all tabs
-pub fn render(f: &mut Frame, app: &App) {
+pub fn render(f: &mut Frame, app: &App, theme: &Theme) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -36,20 +45,29 @@ pub fn render(f: &mut Frame, app: &App) {
         ])
         .split(f.area());
 
-    header::render_header(f, chunks[0], app);
-    render_content(f, chunks[1], app);
-    footer::render_footer(f, chunks[2], app);
+    header::render_header(f, chunks[0], app, theme);
+    render_content(f, chunks[1], app, theme);
+    footer::render_footer(f, chunks[2], app, theme);
+    notifications::render_toasts(f, chunks[1], app, theme);
+    notifications::render_history(f, chunks[1], app, theme);
+    settings::render_settings(f, app, theme);
+    participant_detail::render_participant_detail(f, app, theme);
+    join_prompt::render_join_prompt(f, app, theme);
+    confirm::render_confirm(f, app, theme);
+    takeover::render_takeover(f, app, theme);
 }
 
 /// Route to appropriate tab renderer
-fn render_content(f: &mut Frame, area: Rect, app: &App) {
+fn render_content(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
     match app.current_tab {
-        Tab::Session => session::render_session(f, area, app),
-        Tab::Lobby => lobby::render_lobby(f, area, app),
-        Tab::Activities => activities::render_activities(f, area, app),
-        Tab::Participants => participants::render_participants(f, area, app),
-        Tab::Results => results::render_results(f, area, app),
-        Tab::Events => events::render_events(f, area, app),
-        Tab::Help => help::render_help(f, area),
+        Tab::Session => session::render_session(f, area, app, theme),
+        Tab::Lobby => lobby::render_lobby(f, area, app, theme),
+        Tab::Activities => activities::render_activities(f, area, app, theme),
+        Tab::Participants => participants::render_participants(f, area, app, theme),
+        Tab::Chat => chat::render_chat(f, area, app, theme),
+        Tab::Results => results::render_results(f, area, app, theme),
+        Tab::Analytics => analytics::render_analytics(f, area, app, theme),
+        Tab::Events => events::render_events(f, area, app, theme),
+        Tab::Help => help::render_help(f, area, theme),
     }
 }
@@ -0,0 +1,99 @@
+use super::events::severity_style;
+use crate::presentation::tui::Theme;
+use crate::presentation::tui::app::{App, Severity};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+};
+
+const TOAST_WIDTH: u16 = 44;
+const TOAST_HEIGHT: u16 = 3;
+
+/// Stack the live toasts in the top-right corner of `area`, newest at the bottom
+pub fn render_toasts(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let width = TOAST_WIDTH.min(area.width);
+    let x = area.x + area.width - width;
+
+    for (i, toast) in app.notifications.toasts().enumerate() {
+        let y = area.y + i as u16 * TOAST_HEIGHT;
+        if y + TOAST_HEIGHT > area.y + area.height {
+            break;
+        }
+        let toast_area = Rect::new(x, y, width, TOAST_HEIGHT);
+
+        let style = severity_style(toast.severity, theme);
+        let paragraph = Paragraph::new(toast.message.as_str())
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(style)
+                    .title(Span::styled(title(toast.severity), style)),
+            );
+
+        f.render_widget(Clear, toast_area);
+        f.render_widget(paragraph, toast_area);
+    }
+}
+
+/// The notification history panel, when open, over the content area
+pub fn render_history(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let notifications = &app.notifications;
+    if !notifications.is_panel_open() {
+        return;
+    }
+
+    let width = (area.width * 3 / 4).max(40).min(area.width);
+    let height = (area.height * 3 / 4).max(8).min(area.height);
+    let panel = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let items: Vec<ListItem> = if notifications.history().is_empty() {
+        vec![ListItem::new(Span::styled(
+            "No notifications yet",
+            Style::default().fg(theme.faint),
+        ))]
+    } else {
+        notifications
+            .history()
+            .iter()
+            .skip(notifications.scroll_offset())
+            .take(height.saturating_sub(2) as usize)
+            .map(|n| {
+                let style = severity_style(n.severity, theme);
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:<8}", title(n.severity)), style),
+                    Span::raw(n.message.as_str()),
+                ]))
+            })
+            .collect()
+    };
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(format!(
+                "Notifications ({}) | j/k: scroll | F2/Esc: close",
+                notifications.history().len()
+            )),
+    );
+
+    f.render_widget(Clear, panel);
+    f.render_widget(list, panel);
+}
+
+fn title(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "Info",
+        Severity::Warning => "Warning",
+        Severity::Error => "Error",
+    }
+}
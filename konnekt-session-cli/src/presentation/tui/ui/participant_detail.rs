@@ -0,0 +1,146 @@
+use super::confirm::centered;
+use crate::presentation::tui::Theme;
+use crate::presentation::tui::app::App;
+use konnekt_session_core::{ParticipationMode, Timestamp};
+use konnekt_session_p2p::Presence;
+use ratatui::{
+    Frame,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// History lines shown, newest first
+const HISTORY_LINES: usize = 6;
+
+/// Draw the open participant detail popup (if any) over the tabs
+pub fn render_participant_detail(f: &mut Frame, app: &App, theme: &Theme) {
+    let Some(detail) = app.participant_detail else {
+        return;
+    };
+    let Some(lobby) = &app.lobby_snapshot else {
+        return;
+    };
+    let Some(participant) = lobby.participants().get(&detail.participant_id()) else {
+        return;
+    };
+    let id = participant.id();
+
+    let label =
+        |text: &str| Span::styled(format!("{:<10}", text), Style::default().fg(theme.muted));
+    let heading = |text: &str| {
+        Line::from(Span::styled(
+            text.to_string(),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+
+    let role = if participant.is_host() {
+        "👑 Host"
+    } else if lobby.is_co_host(id) {
+        "⭐ Co-host"
+    } else {
+        "👤 Guest"
+    };
+    let (mode, mode_style) = match participant.participation_mode() {
+        ParticipationMode::Active => ("🎮 Active", Style::default().fg(theme.success)),
+        ParticipationMode::Spectating => ("👁️  Spectating", Style::default().fg(theme.warning)),
+    };
+    let joined = match app.presence_history.first_seen(id) {
+        Some(entry) if entry.text == "Joined" => format!("{} ago", ago(entry.at)),
+        Some(entry) => format!("before you (seen {} ago)", ago(entry.at)),
+        None => "-".to_string(),
+    };
+    let presence = match app.presence_of(id) {
+        Some(Presence::Typing) => "✍️ typing…",
+        Some(Presence::Answering { .. }) => "✍️ answering…",
+        None => "idle",
+    };
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![label("Role"), Span::raw(role)]),
+        Line::from(vec![label("Mode"), Span::styled(mode, mode_style)]),
+        Line::from(vec![label("Joined"), Span::raw(joined)]),
+        Line::from(vec![
+            label("Now"),
+            Span::styled(presence, Style::default().fg(theme.special)),
+        ]),
+        Line::from(""),
+        heading("Results"),
+    ];
+
+    let export = app.results_export();
+    let results: Vec<_> = export
+        .results
+        .iter()
+        .filter(|row| row.participant_id == id)
+        .collect();
+    if results.is_empty() {
+        text.push(Line::from(Span::styled(
+            "  No results yet",
+            Style::default().fg(theme.faint),
+        )));
+    }
+    for row in &results {
+        let score = row
+            .score
+            .map_or("-".to_string(), |score| format!("{} pts", score));
+        let time = row
+            .time_ms
+            .map_or(String::new(), |ms| format!("  {:.1}s", ms as f64 / 1000.0));
+        text.push(Line::from(vec![
+            Span::raw(format!("  {:<24}", row.activity_name)),
+            Span::styled(score, Style::default().fg(theme.highlight)),
+            Span::raw(time),
+        ]));
+    }
+
+    text.push(Line::from(""));
+    text.push(heading("Presence history"));
+    for entry in app.presence_history.of(id).rev().take(HISTORY_LINES) {
+        text.push(Line::from(vec![
+            Span::styled(
+                format!("  {:>9} ago  ", ago(entry.at)),
+                Style::default().fg(theme.muted),
+            ),
+            Span::raw(entry.text.as_str()),
+        ]));
+    }
+
+    let mut hints: Vec<String> = detail
+        .actions(lobby, app.is_host, app.can_moderate())
+        .iter()
+        .map(|action| format!("{}: {}", action.key(), action.label()))
+        .collect();
+    hints.push("Esc: close".to_string());
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        hints.join(" | "),
+        Style::default().fg(theme.muted),
+    )));
+
+    let height = text.len() as u16 + 2;
+    let area = centered(f.area(), 60, height);
+    let paragraph = Paragraph::new(text).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(participant.name().to_string()),
+    );
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// Time since `at`, e.g. `42s` or `3m 05s`
+fn ago(at: Timestamp) -> String {
+    let secs = Timestamp::now().as_millis().saturating_sub(at.as_millis()) / 1000;
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m {:02}s", s / 60, s % 60),
+        s => format!("{}h {:02}m", s / 3600, (s % 3600) / 60),
+    }
+}
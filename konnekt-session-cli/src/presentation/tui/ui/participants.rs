@@ -1,13 +1,15 @@
+use crate::presentation::tui::Theme;
 use crate::presentation::tui::app::{App, Tab};
+use konnekt_session_p2p::Presence;
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem},
 };
 
-pub fn render_participants(f: &mut Frame, area: Rect, app: &App) {
+pub fn render_participants(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
     let participants_tab = &app.participants_tab;
 
     let items: Vec<ListItem> = if let Some(lobby) = &app.lobby_snapshot {
@@ -16,44 +18,61 @@ pub fn render_participants(f: &mut Frame, area: Rect, app: &App) {
             .values()
             .enumerate()
             .map(|(idx, p)| {
-                let role_icon = if p.is_host() { "👑" } else { "👤" };
+                let role_icon = if p.is_host() {
+                    "👑"
+                } else if lobby.is_co_host(p.id()) {
+                    "⭐"
+                } else {
+                    "👤"
+                };
 
                 let (mode_text, mode_style) = match p.participation_mode() {
                     konnekt_session_core::ParticipationMode::Active => {
-                        ("🎮 Active", Style::default().fg(Color::Green))
+                        ("🎮 Active", Style::default().fg(theme.success))
                     }
                     konnekt_session_core::ParticipationMode::Spectating => {
-                        ("👁️  Spectating", Style::default().fg(Color::Yellow))
+                        ("👁️  Spectating", Style::default().fg(theme.warning))
                     }
                 };
 
-                let selected = app.is_host
-                    && app.current_tab == Tab::Participants
+                let selected = app.current_tab == Tab::Participants
                     && idx == participants_tab.selected_participant();
 
                 let prefix = if selected { "> " } else { "  " };
 
-                let text = vec![
+                let mut text = vec![
                     Span::raw(prefix),
                     Span::raw(format!("{} ", role_icon)),
                     Span::styled(
                         p.name(),
                         if p.is_host() {
                             Style::default()
-                                .fg(Color::Cyan)
+                                .fg(theme.accent)
                                 .add_modifier(Modifier::BOLD)
                         } else {
-                            Style::default().fg(Color::White)
+                            Style::default().fg(theme.text)
                         },
                     ),
                     Span::raw(" - "),
                     Span::styled(mode_text, mode_style),
                 ];
+                if let Some(presence) = app.presence_of(p.id()) {
+                    let label = match presence {
+                        Presence::Typing => "  ✍️ typing…",
+                        Presence::Answering { .. } => "  ✍️ answering…",
+                    };
+                    text.push(Span::styled(
+                        label,
+                        Style::default()
+                            .fg(theme.special)
+                            .add_modifier(Modifier::ITALIC),
+                    ));
+                }
 
                 let mut item = ListItem::new(Line::from(text));
 
                 if selected {
-                    item = item.style(Style::default().bg(Color::DarkGray));
+                    item = item.style(Style::default().bg(theme.selection));
                 }
 
                 item
@@ -64,9 +83,11 @@ pub fn render_participants(f: &mut Frame, area: Rect, app: &App) {
     };
 
     let title = if app.is_host {
-        "Participants (j/k: select, t: toggle mode, x: kick)"
+        "Participants (j/k: select, Enter: details, x: kick, d: make host, o: co-host, s: settings)"
+    } else if app.can_moderate() {
+        "Participants (⭐ co-host — j/k: select, Enter: details, t: toggle mode, x: kick)"
     } else {
-        "Participants (t: toggle your mode)"
+        "Participants (j/k: select, Enter: details, t: toggle your mode)"
     };
 
     let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title));
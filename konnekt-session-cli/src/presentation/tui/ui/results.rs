@@ -1,13 +1,22 @@
-use crate::presentation::tui::app::App;
+use crate::presentation::tui::Theme;
+use crate::presentation::tui::app::{App, ResultsView};
+use konnekt_session_core::analytics::{RoundProgression, ScoreDistribution};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
+    symbols::Marker,
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{
+        Axis, Bar, BarChart, BarGroup, Block, Borders, Chart, Dataset, GraphType, List, ListItem,
+        Paragraph, Sparkline,
+    },
 };
 
-pub fn render_results(f: &mut Frame, area: Rect, app: &App) {
+/// Height of the charts row below the results
+const CHARTS_HEIGHT: u16 = 12;
+
+pub fn render_results(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
     let results_tab = &app.results_tab;
 
     if results_tab.completed_activities().is_empty() {
@@ -27,11 +36,18 @@ pub fn render_results(f: &mut Frame, area: Rect, app: &App) {
         return;
     }
 
+    // Results on top, charts below
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(8), Constraint::Length(CHARTS_HEIGHT)])
+        .split(area);
+    render_charts(f, rows[1], app, theme);
+
     // Split area: activity list on left, details on right
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
-        .split(area);
+        .split(rows[0]);
 
     // Render activity list
     let activity_items: Vec<ListItem> = results_tab
@@ -47,12 +63,12 @@ pub fn render_results(f: &mut Frame, area: Rect, app: &App) {
 
             let mut item = ListItem::new(Line::from(vec![
                 Span::raw(prefix),
-                Span::styled(&activity.activity_name, Style::default().fg(Color::Cyan)),
+                Span::styled(&activity.activity_name, Style::default().fg(theme.accent)),
                 Span::raw(format!(" ({} results)", activity.results.len())),
             ]));
 
             if idx == results_tab.selected_activity() {
-                item = item.style(Style::default().bg(Color::DarkGray));
+                item = item.style(Style::default().bg(theme.selection));
             }
 
             item
@@ -67,91 +83,186 @@ pub fn render_results(f: &mut Frame, area: Rect, app: &App) {
 
     f.render_widget(activities_list, chunks[0]);
 
-    // Render selected activity details
-    if let Some(selected) = results_tab
+    // Chart of the current view, the session trend and the responses below it
+    let view = results_tab.view();
+    let chart_rows = (results_tab.score_bars().len() as u16).max(1) * 2 + 2;
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(chart_rows),
+            Constraint::Length(4),
+            Constraint::Min(0),
+        ])
+        .split(chunks[1]);
+
+    let bars: Vec<Bar> = results_tab
+        .score_bars()
+        .into_iter()
+        .map(|(name, score)| {
+            let color = match view {
+                ResultsView::Activity if score >= 100 => theme.success,
+                ResultsView::Activity => theme.warning,
+                ResultsView::Session => theme.accent,
+            };
+            Bar::with_label(name, score).style(Style::default().fg(color))
+        })
+        .collect();
+    let title = match results_tab
         .completed_activities()
         .get(results_tab.selected_activity())
     {
-        let mut text = vec![
-            Line::from(vec![Span::styled(
-                &selected.activity_name,
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            )]),
-            Line::from(""),
-            Line::from("─".repeat(50)),
-            Line::from(""),
-        ];
+        Some(selected) if view == ResultsView::Activity => {
+            format!("{}: {} (v: toggle)", view.title(), selected.activity_name)
+        }
+        _ => format!("{} (v: toggle)", view.title()),
+    };
+    let chart = BarChart::horizontal(bars)
+        .bar_width(1)
+        .bar_gap(1)
+        .value_style(Style::default().fg(theme.highlight))
+        .block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(chart, right[0]);
 
-        // Sort results by score (descending)
-        let mut sorted_results = selected.results.clone();
-        sorted_results.sort_by(|a, b| {
-            b.score
-                .unwrap_or(0)
-                .cmp(&a.score.unwrap_or(0))
-                .then_with(|| {
-                    a.time_ms
-                        .unwrap_or(u64::MAX)
-                        .cmp(&b.time_ms.unwrap_or(u64::MAX))
-                })
-        });
-
-        for (idx, result) in sorted_results.iter().enumerate() {
-            let rank_icon = match idx {
-                0 => "🥇",
-                1 => "🥈",
-                2 => "🥉",
-                _ => "  ",
-            };
+    let trend = results_tab.score_trend();
+    let sparkline = Sparkline::default()
+        .data(&trend)
+        .max(100)
+        .style(Style::default().fg(theme.accent))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Average score per activity"),
+        );
+    f.render_widget(sparkline, right[1]);
 
-            text.push(Line::from(vec![
-                Span::raw(format!("{} ", rank_icon)),
-                Span::styled(
+    if let Some(selected) = results_tab
+        .completed_activities()
+        .get(results_tab.selected_activity())
+    {
+        let text: Vec<Line> = selected
+            .results
+            .iter()
+            .map(|result| {
+                let mut spans = vec![Span::styled(
                     &result.participant_name,
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
-                ),
-            ]));
+                )];
+                if let Some(response) = &result.response {
+                    spans.push(Span::raw(": "));
+                    spans.push(Span::styled(response, Style::default().fg(theme.success)));
+                }
+                if let Some(time_ms) = result.time_ms {
+                    spans.push(Span::styled(
+                        format!(" ({}ms)", time_ms),
+                        Style::default().fg(theme.muted),
+                    ));
+                }
+                Line::from(spans)
+            })
+            .collect();
 
-            if let Some(response) = &result.response {
-                text.push(Line::from(vec![
-                    Span::styled("   Response: ", Style::default().fg(Color::Gray)),
-                    Span::styled(response, Style::default().fg(Color::Green)),
-                ]));
-            }
+        let responses =
+            Paragraph::new(text).block(Block::default().borders(Borders::ALL).title("Responses"));
 
-            if let Some(score) = result.score {
-                text.push(Line::from(vec![
-                    Span::styled("   Score: ", Style::default().fg(Color::Gray)),
-                    Span::styled(
-                        format!("{}", score),
-                        if score == 100 {
-                            Style::default().fg(Color::Green)
-                        } else {
-                            Style::default().fg(Color::Yellow)
-                        },
-                    ),
-                ]));
-            }
+        f.render_widget(responses, right[2]);
+    }
+}
 
-            if let Some(time_ms) = result.time_ms {
-                text.push(Line::from(vec![
-                    Span::styled("   Time: ", Style::default().fg(Color::Gray)),
-                    Span::raw(format!("{}ms", time_ms)),
-                ]));
-            }
+fn render_charts(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let analytics = app.results_tab.analytics();
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area);
 
-            text.push(Line::from(""));
-        }
+    render_distribution(f, chunks[0], &analytics.distribution, theme);
+    render_progression(f, chunks[1], &analytics.progression, theme);
+}
+
+fn render_distribution(f: &mut Frame, area: Rect, distribution: &ScoreDistribution, theme: &Theme) {
+    let bars: Vec<Bar> = distribution
+        .buckets
+        .iter()
+        .map(|bucket| {
+            Bar::default()
+                .value(bucket.count as u64)
+                .label(Line::from(format!("{}-{}", bucket.min, bucket.max)))
+                .style(Style::default().fg(theme.accent))
+                .value_style(Style::default().fg(theme.text).bg(theme.accent))
+        })
+        .collect();
 
-        let details = Paragraph::new(text).block(
+    let chart = BarChart::default()
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "Score distribution ({} results)",
+            distribution.total()
+        )))
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(7)
+        .bar_gap(1);
+
+    f.render_widget(chart, area);
+}
+
+fn render_progression(f: &mut Frame, area: Rect, progression: &RoundProgression, theme: &Theme) {
+    let colors = [
+        theme.accent,
+        theme.highlight,
+        theme.success,
+        theme.special,
+        theme.warning,
+        theme.error,
+    ];
+    let points: Vec<Vec<(f64, f64)>> = progression
+        .series
+        .iter()
+        .map(|series| {
+            // Everyone starts at zero before the first round
+            std::iter::once(0)
+                .chain(series.cumulative())
+                .enumerate()
+                .map(|(round, total)| (round as f64, total as f64))
+                .collect()
+        })
+        .collect();
+    let datasets: Vec<Dataset> = progression
+        .series
+        .iter()
+        .zip(&points)
+        .enumerate()
+        .map(|(index, (series, points))| {
+            Dataset::default()
+                .name(series.participant_name.clone())
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(colors[index % colors.len()]))
+                .data(points)
+        })
+        .collect();
+
+    let rounds = progression.rounds.len().max(1) as f64;
+    let max_total = progression.max_total().max(1) as f64;
+    let chart = Chart::new(datasets)
+        .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("Results (sorted by score)"),
+                .title("Total score per round"),
+        )
+        .x_axis(
+            Axis::default()
+                .title("Round")
+                .style(Style::default().fg(theme.muted))
+                .bounds([0.0, rounds])
+                .labels(["0".to_string(), format!("{}", rounds as usize)]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted))
+                .bounds([0.0, max_total])
+                .labels(["0".to_string(), format!("{}", max_total as u32)]),
         );
 
-        f.render_widget(details, chunks[1]);
-    }
+    f.render_widget(chart, area);
 }
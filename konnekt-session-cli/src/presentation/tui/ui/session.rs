@@ -1,28 +1,55 @@
+use crate::infrastructure::{join_link, render_qr};
+use crate::presentation::tui::Theme;
 use crate::presentation::tui::app::App;
+use konnekt_session_p2p::{ConnectionQuality, ConnectionStatus, PeerStats};
 use ratatui::{
     Frame,
-    layout::{Alignment, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
 };
 
-pub fn render_session(f: &mut Frame, area: Rect, app: &App) {
+/// Peer rows shown before the list scrolls off
+const MAX_PEER_ROWS: u16 = 8;
+
+pub fn render_session(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
     let session_tab = &app.session_tab;
 
+    let peers = session_tab.peer_stats();
+    let area = if peers.is_empty() {
+        area
+    } else {
+        let rows = (peers.len() as u16).min(MAX_PEER_ROWS);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),           // Session information
+                Constraint::Length(rows + 2), // Peers
+            ])
+            .split(area);
+        render_peers(f, chunks[1], app, peers, theme);
+        chunks[0]
+    };
+
+    if session_tab.show_qr() {
+        render_qr_code(f, area, app, theme);
+        return;
+    }
+
     let mut text = vec![
         Line::from(""),
         Line::from(vec![Span::styled(
             "Session ID:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             session_tab.session_id(),
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.highlight)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
@@ -31,7 +58,7 @@ pub fn render_session(f: &mut Frame, area: Rect, app: &App) {
             Span::styled(
                 "y",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" to copy Session ID to clipboard"),
@@ -42,14 +69,14 @@ pub fn render_session(f: &mut Frame, area: Rect, app: &App) {
         Line::from(vec![Span::styled(
             "Share Command:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
-            format!("konnekt-tui join --session-id {}", session_tab.session_id()),
+            session_tab.join_command(),
             Style::default()
-                .fg(Color::Green)
+                .fg(theme.success)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
@@ -58,11 +85,22 @@ pub fn render_session(f: &mut Frame, area: Rect, app: &App) {
             Span::styled(
                 "c",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" to copy join command to clipboard"),
         ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("Press "),
+            Span::styled(
+                "j",
+                Style::default()
+                    .fg(theme.success)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" or paste a link to join another session"),
+        ]),
     ];
 
     // Show clipboard message if active
@@ -72,7 +110,7 @@ pub fn render_session(f: &mut Frame, area: Rect, app: &App) {
         text.push(Line::from(vec![Span::styled(
             msg,
             Style::default()
-                .fg(Color::Green)
+                .fg(theme.success)
                 .add_modifier(Modifier::BOLD),
         )]));
     }
@@ -83,16 +121,16 @@ pub fn render_session(f: &mut Frame, area: Rect, app: &App) {
     // Connection status
     if let Some(peer_id) = session_tab.local_peer_id() {
         text.push(Line::from(vec![
-            Span::styled("Local Peer ID: ", Style::default().fg(Color::Cyan)),
+            Span::styled("Local Peer ID: ", Style::default().fg(theme.accent)),
             Span::raw(peer_id),
         ]));
         text.push(Line::from(vec![
-            Span::styled("Connected Peers: ", Style::default().fg(Color::Cyan)),
+            Span::styled("Connected Peers: ", Style::default().fg(theme.accent)),
             Span::raw(session_tab.peer_count().to_string()),
         ]));
     } else {
         text.push(Line::from(vec![
-            Span::styled("Status: ", Style::default().fg(Color::Cyan)),
+            Span::styled("Status: ", Style::default().fg(theme.accent)),
             Span::raw("Connecting..."),
         ]));
     }
@@ -107,3 +145,86 @@ pub fn render_session(f: &mut Frame, area: Rect, app: &App) {
 
     f.render_widget(paragraph, area);
 }
+
+/// The join link as a QR code, for participants on phones
+fn render_qr_code(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let session_tab = &app.session_tab;
+    let link = join_link(session_tab.session_id(), session_tab.join_url());
+
+    let mut text: Vec<Line> = match render_qr(&link) {
+        Ok(lines) => lines.into_iter().map(Line::from).collect(),
+        Err(e) => vec![Line::from(Span::styled(
+            e.to_string(),
+            Style::default().fg(theme.error),
+        ))],
+    };
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        link,
+        Style::default()
+            .fg(theme.success)
+            .add_modifier(Modifier::BOLD),
+    )));
+
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Scan to join (r: back)"),
+        )
+        .alignment(Alignment::Center);
+
+    f.render_widget(paragraph, area);
+}
+
+/// One row per peer: quality dot, name, round trip, last seen and traffic
+fn render_peers(f: &mut Frame, area: Rect, app: &App, peers: &[PeerStats], theme: &Theme) {
+    let items: Vec<ListItem> = peers
+        .iter()
+        .map(|peer| {
+            let quality_style = Style::default().fg(match peer.quality() {
+                ConnectionQuality::Good => theme.success,
+                ConnectionQuality::Fair => theme.warning,
+                ConnectionQuality::Poor => theme.error,
+            });
+
+            let name = peer
+                .participant_id
+                .and_then(|id| app.participant_names.get(&id).cloned())
+                .unwrap_or_else(|| peer.peer_id.to_string().chars().take(8).collect());
+            let rtt = peer
+                .rtt
+                .map_or("–".to_string(), |rtt| format!("{} ms", rtt.as_millis()));
+
+            let mut spans = vec![
+                Span::styled("● ", quality_style),
+                Span::raw(format!("{:<16}", name)),
+                Span::styled("RTT ", Style::default().fg(theme.accent)),
+                Span::styled(format!("{:>7}", rtt), quality_style),
+                Span::styled("   seen ", Style::default().fg(theme.accent)),
+                Span::raw(format!("{:>5.1}s ago", peer.last_seen.as_secs_f32())),
+                Span::styled("   ↑ ", Style::default().fg(theme.accent)),
+                Span::raw(peer.messages_sent.to_string()),
+                Span::styled("  ↓ ", Style::default().fg(theme.accent)),
+                Span::raw(peer.messages_received.to_string()),
+            ];
+            match peer.status {
+                ConnectionStatus::Connected => {}
+                ConnectionStatus::Disconnected { .. } => {
+                    spans.push(Span::styled("   (silent)", quality_style));
+                }
+                ConnectionStatus::TimedOut => {
+                    spans.push(Span::styled("   (timed out)", quality_style));
+                }
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Peers ({})", peers.len())),
+    );
+    f.render_widget(list, area);
+}
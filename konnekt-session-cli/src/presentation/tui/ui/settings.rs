@@ -0,0 +1,60 @@
+use super::confirm::centered;
+use crate::presentation::tui::Theme;
+use crate::presentation::tui::app::{App, SettingsField};
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// Draw the open lobby settings popup (if any) over the tabs
+pub fn render_settings(f: &mut Frame, app: &App, theme: &Theme) {
+    let Some(dialog) = &app.settings_dialog else {
+        return;
+    };
+    let settings = dialog.settings();
+
+    let mut text = vec![Line::from("")];
+    for field in SettingsField::ALL {
+        let value = match field {
+            SettingsField::Capacity => settings
+                .max_participants
+                .map_or("unlimited".to_string(), |max| {
+                    format!("{} participants", max)
+                }),
+            SettingsField::Locked => if settings.locked { "🔒 yes" } else { "no" }.to_string(),
+            SettingsField::AutoDelegation => settings.auto_delegation.to_string(),
+        };
+        let selected = field == dialog.selected_field();
+        let style = if selected {
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        text.push(Line::from(vec![
+            Span::raw(if selected { "> " } else { "  " }),
+            Span::styled(format!("{:<16}", field.label()), style),
+            Span::styled(format!("‹ {} ›", value), style),
+        ]));
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        "j/k: select | ←/→: change | Enter: save | Esc: cancel",
+        Style::default().fg(theme.muted),
+    )));
+
+    let area = centered(f.area(), 50, 8);
+    let paragraph = Paragraph::new(text).alignment(Alignment::Left).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title("Lobby settings"),
+    );
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,103 @@
+use super::confirm::centered;
+use crate::presentation::tui::Theme;
+use crate::presentation::tui::app::App;
+use konnekt_session_p2p::HostTakeover;
+use ratatui::{
+    Frame,
+    layout::Alignment,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Draw the host takeover modal while the host is missing (guests only)
+pub fn render_takeover(f: &mut Frame, app: &App, theme: &Theme) {
+    let Some(takeover) = app.host_takeover else {
+        return;
+    };
+    let bold = |color| Style::default().fg(color).add_modifier(Modifier::BOLD);
+
+    let (title, color, text) = match takeover {
+        HostTakeover::GracePeriod { remaining } => (
+            "Host disconnected",
+            theme.warning,
+            vec![
+                Line::from(""),
+                Line::from("The host lost its connection."),
+                Line::from(vec![
+                    Span::raw("Waiting "),
+                    Span::styled(format!("{}s", remaining.as_secs()), bold(theme.highlight)),
+                    Span::raw(" for it to come back…"),
+                ]),
+            ],
+        ),
+        HostTakeover::Electing {
+            successor: Some(successor),
+        } => {
+            let name = app
+                .participant_names
+                .get(&successor)
+                .cloned()
+                .unwrap_or_else(|| "the next guest".to_string());
+            (
+                "Host gone",
+                theme.warning,
+                vec![
+                    Line::from(""),
+                    Line::from("The host did not come back."),
+                    Line::from(vec![
+                        Span::raw("Waiting for "),
+                        Span::styled(name, bold(theme.accent)),
+                        Span::raw(" to take over…"),
+                    ]),
+                ],
+            )
+        }
+        HostTakeover::Electing { successor: None } => (
+            "Host gone",
+            theme.error,
+            vec![
+                Line::from(""),
+                Line::from("The host did not come back and nobody can take over."),
+                Line::from("Waiting for the host…"),
+            ],
+        ),
+        HostTakeover::Offered { remaining } => {
+            let keys = app.confirm_keys;
+            (
+                "Take over as host?",
+                theme.special,
+                vec![
+                    Line::from(""),
+                    Line::from("The host is gone and you were elected to take over."),
+                    Line::from(vec![
+                        Span::raw("Taking over by itself in "),
+                        Span::styled(format!("{}s", remaining.as_secs()), bold(theme.highlight)),
+                        Span::raw("."),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled(format!("[{}]", keys.yes), bold(theme.success)),
+                        Span::raw(" Take over    "),
+                        Span::styled(format!("[{}/Esc]", keys.no), bold(theme.warning)),
+                        Span::raw(" Pass it on"),
+                    ]),
+                ],
+            )
+        }
+    };
+
+    let area = centered(f.area(), 50, text.len() as u16 + 3);
+    let paragraph = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color))
+                .title(title),
+        );
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
@@ -1,3 +1,4 @@
+use super::{ActivityMetadata, ActivityPlugin, PluginError, RenderHints};
 use serde::{Deserialize, Serialize};
 
 /// Echo Challenge - Simplest possible activity for testing
@@ -82,6 +83,46 @@ impl EchoResult {
     }
 }
 
+/// The echo challenge for the [`PluginRegistry`](super::PluginRegistry)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EchoPlugin;
+
+impl ActivityPlugin for EchoPlugin {
+    fn metadata(&self) -> ActivityMetadata {
+        ActivityMetadata {
+            activity_type: EchoChallenge::activity_type().to_string(),
+            name: "Echo Challenge".to_string(),
+            description: "Type the prompt back exactly".to_string(),
+        }
+    }
+
+    fn validate(&self, config: &serde_json::Value) -> Result<(), PluginError> {
+        let challenge = EchoChallenge::from_config(config.clone())
+            .map_err(|e| PluginError::InvalidConfig(e.to_string()))?;
+        if challenge.prompt.trim().is_empty() {
+            return Err(PluginError::InvalidConfig("empty prompt".to_string()));
+        }
+        Ok(())
+    }
+
+    fn score(&self, config: &serde_json::Value, data: &serde_json::Value) -> Option<u32> {
+        let response = data.get("response")?.as_str()?;
+        EchoChallenge::from_config(config.clone())
+            .ok()
+            .map(|challenge| challenge.calculate_score(response))
+    }
+
+    fn render_hints(&self, config: &serde_json::Value) -> Result<RenderHints, PluginError> {
+        let challenge = EchoChallenge::from_config(config.clone())
+            .map_err(|e| PluginError::InvalidConfig(e.to_string()))?;
+        Ok(RenderHints {
+            prompt: Some(challenge.prompt),
+            placeholder: Some("Type the prompt exactly...".to_string()),
+            time_limit_ms: challenge.time_limit_ms,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,5 @@
 pub mod echo;
+pub mod plugin;
 
-pub use echo::{EchoChallenge, EchoResult};
+pub use echo::{EchoChallenge, EchoPlugin, EchoResult};
+pub use plugin::{ActivityMetadata, ActivityPlugin, PluginError, PluginRegistry, RenderHints};
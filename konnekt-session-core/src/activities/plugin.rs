@@ -0,0 +1,272 @@
+use crate::domain::ActivityConfig;
+use crate::error::{CodedError, ErrorCode};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, OnceLock, RwLock, RwLockReadGuard};
+
+/// What a planner shows about an activity type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivityMetadata {
+    /// The [`ActivityConfig::activity_type`] the plugin plays
+    pub activity_type: String,
+    pub name: String,
+    pub description: String,
+}
+
+/// What a frontend needs to show a running activity it knows nothing about
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenderHints {
+    /// Shown to participants, e.g. the phrase to echo
+    pub prompt: Option<String>,
+    /// Greyed-out text in the empty response field
+    pub placeholder: Option<String>,
+    /// Answers are due this long after the start
+    pub time_limit_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PluginError {
+    #[error("Unknown activity type: {0}")]
+    UnknownActivityType(String),
+
+    #[error("Activity type already registered: {0}")]
+    AlreadyRegistered(String),
+
+    #[error("Invalid activity config: {0}")]
+    InvalidConfig(String),
+}
+
+impl CodedError for PluginError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            PluginError::UnknownActivityType(_) => ErrorCode::UnknownActivityType,
+            PluginError::AlreadyRegistered(_) => ErrorCode::DuplicateActivityType,
+            PluginError::InvalidConfig(_) => ErrorCode::InvalidActivityConfig,
+        }
+    }
+}
+
+/// A learning game the session can play
+///
+/// Plugins are stateless: everything about one activity lives in its
+/// config (`ActivityConfig::config`) and everything about one answer in the
+/// result data (`ActivityResult::data`).
+pub trait ActivityPlugin: Send + Sync {
+    fn metadata(&self) -> ActivityMetadata;
+
+    /// Check a config before it is queued
+    fn validate(&self, config: &serde_json::Value) -> Result<(), PluginError>;
+
+    /// Grade an answer (`None` if it can't be graded)
+    fn score(&self, config: &serde_json::Value, data: &serde_json::Value) -> Option<u32>;
+
+    fn render_hints(&self, config: &serde_json::Value) -> Result<RenderHints, PluginError>;
+}
+
+/// The activity types a process knows, by `activity_type`
+///
+/// Frontends and the host look plugins up in [`PluginRegistry::global`];
+/// a binary registers the games of other crates there at startup:
+///
+/// ```
+/// # use konnekt_session_core::activities::{ActivityPlugin, PluginRegistry};
+/// # fn register(plugin: impl ActivityPlugin + 'static) {
+/// PluginRegistry::global()
+///     .register(plugin)
+///     .expect("activity type taken");
+/// # }
+/// ```
+#[derive(Default)]
+pub struct PluginRegistry {
+    plugins: RwLock<BTreeMap<String, Arc<dyn ActivityPlugin>>>,
+}
+
+impl PluginRegistry {
+    /// A registry without any plugin
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry with the activities that ship with core
+    pub fn with_builtin() -> Self {
+        let registry = Self::new();
+        registry
+            .register(super::EchoPlugin)
+            .expect("empty registry");
+        registry
+    }
+
+    /// The registry shared by everything in this process, starting out
+    /// [`with_builtin`](Self::with_builtin) plugins
+    pub fn global() -> &'static PluginRegistry {
+        static GLOBAL: OnceLock<PluginRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(Self::with_builtin)
+    }
+
+    /// Add a plugin; its activity type must not be taken
+    pub fn register(&self, plugin: impl ActivityPlugin + 'static) -> Result<(), PluginError> {
+        let activity_type = plugin.metadata().activity_type;
+        let mut plugins = self.plugins.write().unwrap_or_else(|e| e.into_inner());
+        if plugins.contains_key(&activity_type) {
+            return Err(PluginError::AlreadyRegistered(activity_type));
+        }
+        plugins.insert(activity_type, Arc::new(plugin));
+        Ok(())
+    }
+
+    pub fn get(&self, activity_type: &str) -> Option<Arc<dyn ActivityPlugin>> {
+        self.read().get(activity_type).cloned()
+    }
+
+    pub fn contains(&self, activity_type: &str) -> bool {
+        self.read().contains_key(activity_type)
+    }
+
+    /// Every registered activity type, sorted by type
+    pub fn metadata(&self) -> Vec<ActivityMetadata> {
+        self.read()
+            .values()
+            .map(|plugin| plugin.metadata())
+            .collect()
+    }
+
+    /// Whether `config` can be played
+    pub fn validate(&self, config: &ActivityConfig) -> Result<(), PluginError> {
+        self.plugin(&config.activity_type)?.validate(&config.config)
+    }
+
+    /// Grade an answer to an activity of `activity_type` (`None` for unknown
+    /// types and answers the plugin can't grade)
+    pub fn score(
+        &self,
+        activity_type: &str,
+        config: &serde_json::Value,
+        data: &serde_json::Value,
+    ) -> Option<u32> {
+        self.get(activity_type)?.score(config, data)
+    }
+
+    pub fn render_hints(
+        &self,
+        activity_type: &str,
+        config: &serde_json::Value,
+    ) -> Result<RenderHints, PluginError> {
+        self.plugin(activity_type)?.render_hints(config)
+    }
+
+    fn plugin(&self, activity_type: &str) -> Result<Arc<dyn ActivityPlugin>, PluginError> {
+        self.get(activity_type)
+            .ok_or_else(|| PluginError::UnknownActivityType(activity_type.to_string()))
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, BTreeMap<String, Arc<dyn ActivityPlugin>>> {
+        self.plugins.read().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl std::fmt::Debug for PluginRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PluginRegistry")
+            .field("activity_types", &self.read().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EchoChallenge;
+    use serde_json::json;
+
+    /// Pick the right article for a German noun
+    struct ArticleQuiz;
+
+    impl ActivityPlugin for ArticleQuiz {
+        fn metadata(&self) -> ActivityMetadata {
+            ActivityMetadata {
+                activity_type: "article-quiz-v1".to_string(),
+                name: "Der, die, das".to_string(),
+                description: "Pick the article".to_string(),
+            }
+        }
+
+        fn validate(&self, config: &serde_json::Value) -> Result<(), PluginError> {
+            match config.get("article").and_then(|a| a.as_str()) {
+                Some("der" | "die" | "das") => Ok(()),
+                _ => Err(PluginError::InvalidConfig("no article".to_string())),
+            }
+        }
+
+        fn score(&self, config: &serde_json::Value, data: &serde_json::Value) -> Option<u32> {
+            let correct = config.get("article")? == data.get("response")?;
+            Some(if correct { 10 } else { 0 })
+        }
+
+        fn render_hints(&self, config: &serde_json::Value) -> Result<RenderHints, PluginError> {
+            self.validate(config)?;
+            Ok(RenderHints {
+                prompt: config
+                    .get("noun")
+                    .and_then(|n| n.as_str())
+                    .map(String::from),
+                placeholder: Some("der / die / das".to_string()),
+                time_limit_ms: None,
+            })
+        }
+    }
+
+    #[test]
+    fn test_registered_plugins_play_their_activities() {
+        let registry = PluginRegistry::with_builtin();
+        registry.register(ArticleQuiz).unwrap();
+        assert_eq!(
+            registry.register(ArticleQuiz),
+            Err(PluginError::AlreadyRegistered(
+                "article-quiz-v1".to_string()
+            ))
+        );
+        let types: Vec<_> = registry
+            .metadata()
+            .into_iter()
+            .map(|m| m.activity_type)
+            .collect();
+        assert_eq!(types, vec!["article-quiz-v1", "echo-challenge-v1"]);
+
+        let config = json!({ "noun": "Haus", "article": "das" });
+        let quiz = ActivityConfig::new("article-quiz-v1".to_string(), "Q".to_string(), config);
+        registry.validate(&quiz).unwrap();
+        assert_eq!(
+            registry.score(
+                &quiz.activity_type,
+                &quiz.config,
+                &json!({ "response": "das" })
+            ),
+            Some(10)
+        );
+        let hints = registry
+            .render_hints(&quiz.activity_type, &quiz.config)
+            .unwrap();
+        assert_eq!(hints.prompt.as_deref(), Some("Haus"));
+
+        let broken = ActivityConfig::new("article-quiz-v1".to_string(), "Q".to_string(), json!({}));
+        assert!(matches!(
+            registry.validate(&broken),
+            Err(PluginError::InvalidConfig(_))
+        ));
+        let unknown = ActivityConfig::new("memory-v1".to_string(), "M".to_string(), json!({}));
+        assert_eq!(
+            registry.validate(&unknown).unwrap_err().code(),
+            ErrorCode::UnknownActivityType
+        );
+    }
+
+    #[test]
+    fn test_global_registry_knows_the_echo_challenge() {
+        let config = EchoChallenge::new("Hallo".to_string()).with_time_limit(5_000);
+        let hints = PluginRegistry::global()
+            .render_hints(EchoChallenge::activity_type(), &config.to_config())
+            .unwrap();
+        assert_eq!(hints.prompt.as_deref(), Some("Hallo"));
+        assert_eq!(hints.time_limit_ms, Some(5_000));
+    }
+}
@@ -0,0 +1,289 @@
+//! Score analytics over completed runs, shared by the frontends' charts
+
+use crate::activities::PluginRegistry;
+use crate::domain::{ActivityConfig, ActivityResult, ActivityRun, ActivityRunId, RunStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Top of the score scale (echo challenges score 0..=100)
+pub const MAX_SCORE: u32 = 100;
+
+/// Name shown for participants we never saw in the lobby
+pub const UNKNOWN_PARTICIPANT: &str = "(unknown)";
+
+/// Buckets of [`ScoreDistribution`] unless asked otherwise
+pub const DEFAULT_SCORE_BUCKETS: usize = 5;
+
+/// The score of `result`, grading unscored responses of activities we know
+pub fn result_score(run: &ActivityRun, result: &ActivityResult) -> Option<u32> {
+    graded_score(run.config(), result)
+}
+
+/// [`result_score`] for a result of an activity played with `config`,
+/// graded by its plugin in the global [`PluginRegistry`]
+pub fn graded_score(config: &ActivityConfig, result: &ActivityResult) -> Option<u32> {
+    result.score.or_else(|| {
+        PluginRegistry::global().score(&config.activity_type, &config.config, &result.data)
+    })
+}
+
+/// Scores from `min` to `max` (inclusive) and how many results got one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreBucket {
+    pub min: u32,
+    pub max: u32,
+    pub count: usize,
+}
+
+/// How the scores of all results spread over the score scale
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreDistribution {
+    /// Lowest scores first
+    pub buckets: Vec<ScoreBucket>,
+}
+
+impl ScoreDistribution {
+    /// Split `0..=MAX_SCORE` (or the top score, if higher) into `buckets`
+    /// equal buckets, the last one taking the remainder
+    pub fn from_scores(scores: impl IntoIterator<Item = u32>, buckets: usize) -> Self {
+        let scores: Vec<u32> = scores.into_iter().collect();
+        let top = scores.iter().copied().max().unwrap_or(0).max(MAX_SCORE);
+        let buckets = buckets.clamp(1, top as usize + 1) as u32;
+        let width = (top + 1) / buckets;
+
+        let mut distribution: Vec<ScoreBucket> = (0..buckets)
+            .map(|index| ScoreBucket {
+                min: index * width,
+                max: if index + 1 == buckets {
+                    top
+                } else {
+                    (index + 1) * width - 1
+                },
+                count: 0,
+            })
+            .collect();
+        for score in scores {
+            let index = (score / width).min(buckets - 1) as usize;
+            distribution[index].count += 1;
+        }
+        Self {
+            buckets: distribution,
+        }
+    }
+
+    /// Results in the fullest bucket
+    pub fn max_count(&self) -> usize {
+        self.buckets.iter().map(|b| b.count).max().unwrap_or(0)
+    }
+
+    /// Results counted
+    pub fn total(&self) -> usize {
+        self.buckets.iter().map(|b| b.count).sum()
+    }
+}
+
+/// One completed run, a step of [`RoundProgression`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Round {
+    pub run_id: ActivityRunId,
+    pub name: String,
+}
+
+/// A participant's scores round by round
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgressionSeries {
+    pub participant_id: Uuid,
+    pub participant_name: String,
+    /// One per round; `None` where the participant has no scored result
+    pub scores: Vec<Option<u32>>,
+}
+
+impl ProgressionSeries {
+    /// Running total after each round
+    pub fn cumulative(&self) -> Vec<u32> {
+        self.scores
+            .iter()
+            .scan(0, |total, score| {
+                *total += score.unwrap_or(0);
+                Some(*total)
+            })
+            .collect()
+    }
+}
+
+/// How every participant's score developed over the rounds
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundProgression {
+    /// Oldest first
+    pub rounds: Vec<Round>,
+    /// By participant name
+    pub series: Vec<ProgressionSeries>,
+}
+
+impl RoundProgression {
+    /// Highest running total anyone reached
+    pub fn max_total(&self) -> u32 {
+        self.series
+            .iter()
+            .filter_map(|series| series.cumulative().last().copied())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// What the results charts show
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultsAnalytics {
+    pub distribution: ScoreDistribution,
+    pub progression: RoundProgression,
+}
+
+impl ResultsAnalytics {
+    /// Analyse the completed runs in start order; cancelled and running ones
+    /// are skipped. `names` maps participant ids to display names.
+    pub fn from_runs<'a>(
+        runs: impl IntoIterator<Item = &'a ActivityRun>,
+        names: &HashMap<Uuid, String>,
+    ) -> Self {
+        let runs: Vec<&ActivityRun> = runs
+            .into_iter()
+            .filter(|run| run.status() == RunStatus::Completed)
+            .collect();
+
+        let mut series: HashMap<Uuid, ProgressionSeries> = HashMap::new();
+        let mut scores = Vec::new();
+        for (round, run) in runs.iter().enumerate() {
+            for result in run.results().values() {
+                let score = result_score(run, result);
+                scores.extend(score);
+                let entry =
+                    series
+                        .entry(result.participant_id)
+                        .or_insert_with(|| ProgressionSeries {
+                            participant_id: result.participant_id,
+                            participant_name: names
+                                .get(&result.participant_id)
+                                .cloned()
+                                .unwrap_or_else(|| UNKNOWN_PARTICIPANT.to_string()),
+                            scores: vec![None; runs.len()],
+                        });
+                entry.scores[round] = score;
+            }
+        }
+
+        let mut series: Vec<ProgressionSeries> = series.into_values().collect();
+        series.sort_by(|a, b| {
+            a.participant_name
+                .cmp(&b.participant_name)
+                .then(a.participant_id.cmp(&b.participant_id))
+        });
+
+        Self {
+            distribution: ScoreDistribution::from_scores(scores, DEFAULT_SCORE_BUCKETS),
+            progression: RoundProgression {
+                rounds: runs
+                    .iter()
+                    .map(|run| Round {
+                        run_id: run.id(),
+                        name: run.config().name.clone(),
+                    })
+                    .collect(),
+                series,
+            },
+        }
+    }
+
+    /// No completed run yet
+    pub fn is_empty(&self) -> bool {
+        self.progression.rounds.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EchoChallenge;
+    use crate::domain::ActivityConfig;
+    use std::collections::HashSet;
+
+    fn echo_run(prompt: &str, answers: &[(Uuid, &str)]) -> ActivityRun {
+        let config = ActivityConfig::new(
+            EchoChallenge::activity_type().to_string(),
+            format!("Echo {prompt}"),
+            EchoChallenge::new(prompt.to_string()).to_config(),
+        );
+        let submitters: HashSet<Uuid> = answers.iter().map(|(id, _)| *id).collect();
+        let mut run = ActivityRun::new(Uuid::new_v4(), Uuid::new_v4(), config, submitters);
+        for (participant_id, response) in answers {
+            run.submit_result(
+                ActivityResult::new(run.id(), *participant_id)
+                    .with_data(serde_json::json!({ "response": response })),
+            )
+            .unwrap();
+        }
+        run
+    }
+
+    #[test]
+    fn test_score_distribution_buckets() {
+        let distribution = ScoreDistribution::from_scores([0, 19, 20, 85, 100, 100], 5);
+
+        let buckets: Vec<_> = distribution
+            .buckets
+            .iter()
+            .map(|b| (b.min, b.max, b.count))
+            .collect();
+        assert_eq!(
+            buckets,
+            vec![
+                (0, 19, 2),
+                (20, 39, 1),
+                (40, 59, 0),
+                (60, 79, 0),
+                (80, 100, 3)
+            ]
+        );
+        assert_eq!(distribution.max_count(), 3);
+        assert_eq!(distribution.total(), 6);
+
+        // Scores above the usual scale stretch it
+        let stretched = ScoreDistribution::from_scores([150], 3);
+        assert_eq!(stretched.buckets.last().unwrap().max, 150);
+        assert_eq!(stretched.buckets.last().unwrap().count, 1);
+    }
+
+    #[test]
+    fn test_results_analytics_from_runs() {
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let names = HashMap::from([(alice, "Alice".to_string()), (bob, "Bob".to_string())]);
+
+        let first = echo_run("Hallo", &[(alice, "Hallo"), (bob, "hallo")]);
+        let mut cancelled = echo_run("Danke", &[]);
+        cancelled.cancel().unwrap();
+        let second = echo_run("Bitte", &[(alice, "Bitte")]);
+
+        let analytics = ResultsAnalytics::from_runs([&first, &cancelled, &second], &names);
+
+        let rounds: Vec<_> = analytics
+            .progression
+            .rounds
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(rounds, vec!["Echo Hallo", "Echo Bitte"]);
+
+        let series = &analytics.progression.series;
+        assert_eq!(series[0].participant_name, "Alice");
+        assert_eq!(series[0].scores, vec![Some(100), Some(100)]);
+        assert_eq!(series[0].cumulative(), vec![100, 200]);
+        assert_eq!(series[1].scores, vec![Some(0), None]);
+        assert_eq!(analytics.progression.max_total(), 200);
+
+        assert_eq!(analytics.distribution.total(), 3);
+        assert_eq!(analytics.distribution.buckets[0].count, 1);
+        assert_eq!(analytics.distribution.buckets[4].count, 2);
+        assert!(!analytics.is_empty());
+        assert!(ResultsAnalytics::from_runs([&cancelled], &names).is_empty());
+    }
+}
@@ -30,6 +30,9 @@ pub enum DomainCommand {
         lobby_id: Uuid,
         host_id: Uuid,
         guest_id: Uuid,
+        /// Also ban the guest's peer from the session
+        #[serde(default)]
+        ban: bool,
     },
 
     /// `activity_in_progress` no longer needed — Lobby tracks this via `active_run_id`.
@@ -45,6 +48,28 @@ pub enum DomainCommand {
         new_host_id: Uuid,
     },
 
+    /// Make a guest a co-host (`co_host: true`) or demote them again.
+    SetCoHost {
+        lobby_id: Uuid,
+        host_id: Uuid,
+        participant_id: Uuid,
+        co_host: bool,
+    },
+
+    /// Pick an avatar (`None` goes back to the generated one).
+    SetAvatar {
+        lobby_id: Uuid,
+        participant_id: Uuid,
+        requester_id: Uuid,
+        avatar: Option<crate::domain::ParticipantAvatar>,
+    },
+
+    UpdateLobbySettings {
+        lobby_id: Uuid,
+        host_id: Uuid,
+        settings: crate::domain::LobbySettings,
+    },
+
     /// Add a participant directly (P2P sync).
     AddParticipant {
         lobby_id: Uuid,
@@ -63,6 +88,29 @@ pub enum DomainCommand {
         config: crate::domain::ActivityConfig,
     },
 
+    /// Move a planned activity to another place in the queue (host or co-host).
+    MoveQueuedActivity {
+        lobby_id: Uuid,
+        activity_id: crate::domain::ActivityId,
+        to_index: usize,
+        requester_id: Uuid,
+    },
+
+    /// Say the participant is (not) ready for the next activity.
+    SetReady {
+        lobby_id: Uuid,
+        participant_id: Uuid,
+        ready: bool,
+    },
+
+    /// Count down to the next activity (host or co-host).
+    /// `ends_at` is wall clock time in ms since the Unix epoch.
+    StartCountdown {
+        lobby_id: Uuid,
+        requester_id: Uuid,
+        ends_at: u64,
+    },
+
     // ── Run commands ──────────────────────────────────────────────────────────
     /// Dequeue the next activity and start a run.
     StartNextRun {
@@ -87,15 +135,156 @@ pub enum DomainCommand {
         participant_id: Uuid,
     },
 
+    /// Complete a run whose deadline passed on the host's clock, with the
+    /// results that came in on time. The host sends this itself on poll.
+    ExpireRun {
+        lobby_id: Uuid,
+        run_id: crate::domain::ActivityRunId,
+    },
+
     /// P2P sync: guest applies a run that the host already started.
     SyncRunStarted {
         lobby_id: Uuid,
         run_id: crate::domain::ActivityRunId,
         config: crate::domain::ActivityConfig,
         required_submitters: Vec<Uuid>,
+        /// When the host started the run, on its clock (Unix ms)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        started_at_ms: Option<u64>,
+    },
+
+    /// P2P sync: guest applies the outcome of a run that the host ended.
+    SyncRunEnded {
+        lobby_id: Uuid,
+        run_id: crate::domain::ActivityRunId,
+        status: crate::domain::RunStatus,
+        results: Vec<crate::domain::ActivityResult>,
+    },
+
+    // ── Chat commands ─────────────────────────────────────────────────────────
+    SendChatMessage {
+        lobby_id: Uuid,
+        author_id: Uuid,
+        text: String,
+    },
+
+    /// Append a chat message another peer already posted (P2P sync).
+    AddChatMessage {
+        lobby_id: Uuid,
+        message: crate::domain::ChatMessage,
     },
 }
 
+impl DomainCommand {
+    /// Variant name, as used in logs and failure reports
+    pub fn name(&self) -> &'static str {
+        match self {
+            DomainCommand::CreateLobby { .. } => "CreateLobby",
+            DomainCommand::CreateLobbyWithHost { .. } => "CreateLobbyWithHost",
+            DomainCommand::JoinLobby { .. } => "JoinLobby",
+            DomainCommand::LeaveLobby { .. } => "LeaveLobby",
+            DomainCommand::KickGuest { .. } => "KickGuest",
+            DomainCommand::ToggleParticipationMode { .. } => "ToggleParticipationMode",
+            DomainCommand::DelegateHost { .. } => "DelegateHost",
+            DomainCommand::SetCoHost { .. } => "SetCoHost",
+            DomainCommand::SetAvatar { .. } => "SetAvatar",
+            DomainCommand::UpdateLobbySettings { .. } => "UpdateLobbySettings",
+            DomainCommand::AddParticipant { .. } => "AddParticipant",
+            DomainCommand::UpdateParticipantMode { .. } => "UpdateParticipantMode",
+            DomainCommand::QueueActivity { .. } => "QueueActivity",
+            DomainCommand::MoveQueuedActivity { .. } => "MoveQueuedActivity",
+            DomainCommand::SetReady { .. } => "SetReady",
+            DomainCommand::StartCountdown { .. } => "StartCountdown",
+            DomainCommand::StartNextRun { .. } => "StartNextRun",
+            DomainCommand::SubmitResult { .. } => "SubmitResult",
+            DomainCommand::CancelRun { .. } => "CancelRun",
+            DomainCommand::RemoveSubmitter { .. } => "RemoveSubmitter",
+            DomainCommand::ExpireRun { .. } => "ExpireRun",
+            DomainCommand::SyncRunStarted { .. } => "SyncRunStarted",
+            DomainCommand::SyncRunEnded { .. } => "SyncRunEnded",
+            DomainCommand::SendChatMessage { .. } => "SendChatMessage",
+            DomainCommand::AddChatMessage { .. } => "AddChatMessage",
+        }
+    }
+
+    /// Lobby the command targets (`None` for `CreateLobby` without an ID)
+    pub fn lobby_id(&self) -> Option<Uuid> {
+        match self {
+            DomainCommand::CreateLobby { lobby_id, .. } => *lobby_id,
+            DomainCommand::CreateLobbyWithHost { lobby_id, .. }
+            | DomainCommand::JoinLobby { lobby_id, .. }
+            | DomainCommand::LeaveLobby { lobby_id, .. }
+            | DomainCommand::KickGuest { lobby_id, .. }
+            | DomainCommand::ToggleParticipationMode { lobby_id, .. }
+            | DomainCommand::DelegateHost { lobby_id, .. }
+            | DomainCommand::SetCoHost { lobby_id, .. }
+            | DomainCommand::SetAvatar { lobby_id, .. }
+            | DomainCommand::UpdateLobbySettings { lobby_id, .. }
+            | DomainCommand::AddParticipant { lobby_id, .. }
+            | DomainCommand::UpdateParticipantMode { lobby_id, .. }
+            | DomainCommand::QueueActivity { lobby_id, .. }
+            | DomainCommand::MoveQueuedActivity { lobby_id, .. }
+            | DomainCommand::SetReady { lobby_id, .. }
+            | DomainCommand::StartCountdown { lobby_id, .. }
+            | DomainCommand::StartNextRun { lobby_id }
+            | DomainCommand::SubmitResult { lobby_id, .. }
+            | DomainCommand::CancelRun { lobby_id, .. }
+            | DomainCommand::RemoveSubmitter { lobby_id, .. }
+            | DomainCommand::ExpireRun { lobby_id, .. }
+            | DomainCommand::SyncRunStarted { lobby_id, .. }
+            | DomainCommand::SyncRunEnded { lobby_id, .. }
+            | DomainCommand::SendChatMessage { lobby_id, .. }
+            | DomainCommand::AddChatMessage { lobby_id, .. } => Some(*lobby_id),
+        }
+    }
+
+    /// Participant the command acts for (`None` if it names nobody)
+    pub fn requester(&self) -> Option<Uuid> {
+        match self {
+            DomainCommand::LeaveLobby { participant_id, .. }
+            | DomainCommand::SetReady { participant_id, .. } => Some(*participant_id),
+            DomainCommand::KickGuest { host_id, .. }
+            | DomainCommand::SetCoHost { host_id, .. }
+            | DomainCommand::UpdateLobbySettings { host_id, .. } => Some(*host_id),
+            DomainCommand::ToggleParticipationMode { requester_id, .. }
+            | DomainCommand::SetAvatar { requester_id, .. }
+            | DomainCommand::MoveQueuedActivity { requester_id, .. }
+            | DomainCommand::StartCountdown { requester_id, .. } => Some(*requester_id),
+            DomainCommand::DelegateHost {
+                current_host_id, ..
+            } => Some(*current_host_id),
+            DomainCommand::SubmitResult { result, .. } => Some(result.participant_id),
+            DomainCommand::SendChatMessage { author_id, .. } => Some(*author_id),
+            DomainCommand::CreateLobby { .. }
+            | DomainCommand::CreateLobbyWithHost { .. }
+            | DomainCommand::JoinLobby { .. }
+            | DomainCommand::AddParticipant { .. }
+            | DomainCommand::UpdateParticipantMode { .. }
+            | DomainCommand::QueueActivity { .. }
+            | DomainCommand::StartNextRun { .. }
+            | DomainCommand::CancelRun { .. }
+            | DomainCommand::RemoveSubmitter { .. }
+            | DomainCommand::ExpireRun { .. }
+            | DomainCommand::SyncRunStarted { .. }
+            | DomainCommand::SyncRunEnded { .. }
+            | DomainCommand::AddChatMessage { .. } => None,
+        }
+    }
+
+    /// Replays what the host already decided (P2P sync)
+    pub fn is_sync(&self) -> bool {
+        matches!(
+            self,
+            DomainCommand::CreateLobbyWithHost { .. }
+                | DomainCommand::AddParticipant { .. }
+                | DomainCommand::UpdateParticipantMode { .. }
+                | DomainCommand::SyncRunStarted { .. }
+                | DomainCommand::SyncRunEnded { .. }
+                | DomainCommand::AddChatMessage { .. }
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,4 +299,18 @@ mod tests {
         let cloned = cmd.clone();
         assert_eq!(cmd, cloned);
     }
+
+    #[test]
+    fn test_command_lobby_id() {
+        let lobby_id = Uuid::new_v4();
+        let cmd = DomainCommand::StartNextRun { lobby_id };
+        assert_eq!(cmd.lobby_id(), Some(lobby_id));
+
+        let cmd = DomainCommand::CreateLobby {
+            lobby_name: "Test".to_string(),
+            host_name: "Alice".to_string(),
+            lobby_id: None,
+        };
+        assert_eq!(cmd.lobby_id(), None);
+    }
 }
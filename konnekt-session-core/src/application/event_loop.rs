@@ -1,12 +1,26 @@
-use crate::application::{DomainCommand, DomainEvent};
-use crate::domain::{ActivityRun, ActivityRunId, Lobby, Participant, ParticipationMode};
+use crate::application::subscription::Subscribers;
+use crate::application::{DomainCommand, DomainEvent, EventFilter, EventSubscription};
+use crate::domain::{
+    ActivityId, ActivityRun, ActivityRunError, ActivityRunId, ChatMessage, DefaultPolicy, Lobby,
+    LobbyAction, LobbyError, LobbyRole, LobbySettings, Participant, ParticipantAvatar,
+    ParticipationMode, PermissionPolicy, SharedClock, SystemClock, Timestamp,
+};
+use crate::error::CommandError;
 use std::collections::HashMap;
+use std::sync::Arc;
 use uuid::Uuid;
 
 #[derive(Debug, Clone)]
 pub struct DomainEventLoop {
     lobbies: HashMap<Uuid, Lobby>,
     runs: HashMap<ActivityRunId, ActivityRun>,
+    /// Run ids in the order the runs started
+    run_order: Vec<ActivityRunId>,
+    subscribers: Subscribers,
+    /// Installed on every lobby of this loop
+    policy: Arc<dyn PermissionPolicy>,
+    /// Stamps the runs this loop starts and the guests who join
+    clock: SharedClock,
 }
 
 impl DomainEventLoop {
@@ -14,10 +28,110 @@ impl DomainEventLoop {
         Self {
             lobbies: HashMap::new(),
             runs: HashMap::new(),
+            run_order: Vec::new(),
+            subscribers: Subscribers::default(),
+            policy: Arc::new(DefaultPolicy),
+            clock: SystemClock::shared(),
         }
     }
 
+    /// Stamp started runs with `clock` instead of real time (the host's
+    /// session clock, or a `TestClock`)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    /// Decide who may do what with `policy` instead of the [`DefaultPolicy`]
+    pub fn with_permission_policy(mut self, policy: impl PermissionPolicy + 'static) -> Self {
+        self.policy = Arc::new(policy);
+        for lobby in self.lobbies.values_mut() {
+            lobby.set_permission_policy(self.policy.clone());
+        }
+        self
+    }
+
+    /// Receive every event matching `filter` from now on, until the
+    /// subscription is dropped
+    pub fn subscribe(&mut self, filter: EventFilter) -> EventSubscription {
+        self.subscribers.subscribe(filter)
+    }
+
+    /// Subscriptions that are still alive
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.len()
+    }
+
     pub fn handle_command(&mut self, command: DomainCommand) -> DomainEvent {
+        // Handlers take the command apart, keep it to report a failure
+        let submitted = command.clone();
+        let event = self
+            .execute(command)
+            .unwrap_or_else(|error| DomainEvent::command_failed(submitted, error));
+        self.subscribers.publish(&event);
+        event
+    }
+
+    /// Run a command a guest sent to the host, if the guest may send it
+    ///
+    /// `sender` is the participant the guest's peer joined as (`None`
+    /// before it joined). A rejected request fails like any other command.
+    pub fn handle_request(&mut self, command: DomainCommand, sender: Option<Uuid>) -> DomainEvent {
+        match self.authorize(&command, sender) {
+            Ok(()) => self.handle_command(command),
+            Err(error) => {
+                let event = DomainEvent::command_failed(command, error);
+                self.subscribers.publish(&event);
+                event
+            }
+        }
+    }
+
+    /// May a guest joined as `sender` ask the host to run `command`?
+    ///
+    /// Guests can join, and otherwise only act as themselves; commands that
+    /// name nobody (running the queue) need the lobby's permission policy.
+    /// Sync commands replay the host's decisions and never come from guests.
+    pub fn authorize(
+        &self,
+        command: &DomainCommand,
+        sender: Option<Uuid>,
+    ) -> Result<(), CommandError> {
+        if matches!(command, DomainCommand::JoinLobby { .. }) {
+            return Ok(());
+        }
+        if command.is_sync() || matches!(command, DomainCommand::CreateLobby { .. }) {
+            return Err(LobbyError::PermissionDenied.into());
+        }
+        let sender = sender.ok_or(LobbyError::PermissionDenied)?;
+        match command.requester() {
+            Some(requester) if requester == sender => Ok(()),
+            Some(_) => Err(LobbyError::PermissionDenied.into()),
+            None => {
+                let lobby_id = command.lobby_id().ok_or(LobbyError::PermissionDenied)?;
+                let lobby = self
+                    .lobbies
+                    .get(&lobby_id)
+                    .ok_or(CommandError::LobbyNotFound(lobby_id))?;
+                let action = match command {
+                    DomainCommand::QueueActivity { .. } => LobbyAction::PlanQueue,
+                    DomainCommand::StartNextRun { .. } => LobbyAction::StartRun,
+                    _ => LobbyAction::ManageRun,
+                };
+                if lobby.allows(sender, action) {
+                    Ok(())
+                } else {
+                    Err(LobbyError::PermissionDenied.into())
+                }
+            }
+        }
+    }
+
+    fn execute(&mut self, command: DomainCommand) -> Result<DomainEvent, CommandError> {
         match command {
             DomainCommand::CreateLobby {
                 lobby_id,
@@ -45,7 +159,8 @@ impl DomainEventLoop {
                 lobby_id,
                 host_id,
                 guest_id,
-            } => self.handle_kick_guest(lobby_id, host_id, guest_id),
+                ban,
+            } => self.handle_kick_guest(lobby_id, host_id, guest_id, ban),
 
             DomainCommand::ToggleParticipationMode {
                 lobby_id,
@@ -59,6 +174,26 @@ impl DomainEventLoop {
                 new_host_id,
             } => self.handle_delegate_host(lobby_id, current_host_id, new_host_id),
 
+            DomainCommand::SetCoHost {
+                lobby_id,
+                host_id,
+                participant_id,
+                co_host,
+            } => self.handle_set_co_host(lobby_id, host_id, participant_id, co_host),
+
+            DomainCommand::SetAvatar {
+                lobby_id,
+                participant_id,
+                requester_id,
+                avatar,
+            } => self.handle_set_avatar(lobby_id, participant_id, requester_id, avatar),
+
+            DomainCommand::UpdateLobbySettings {
+                lobby_id,
+                host_id,
+                settings,
+            } => self.handle_update_lobby_settings(lobby_id, host_id, settings),
+
             DomainCommand::AddParticipant {
                 lobby_id,
                 participant,
@@ -74,6 +209,25 @@ impl DomainEventLoop {
                 self.handle_queue_activity(lobby_id, config)
             }
 
+            DomainCommand::MoveQueuedActivity {
+                lobby_id,
+                activity_id,
+                to_index,
+                requester_id,
+            } => self.handle_move_queued_activity(lobby_id, activity_id, to_index, requester_id),
+
+            DomainCommand::SetReady {
+                lobby_id,
+                participant_id,
+                ready,
+            } => self.handle_set_ready(lobby_id, participant_id, ready),
+
+            DomainCommand::StartCountdown {
+                lobby_id,
+                requester_id,
+                ends_at,
+            } => self.handle_start_countdown(lobby_id, requester_id, ends_at),
+
             DomainCommand::StartNextRun { lobby_id } => self.handle_start_next_run(lobby_id),
 
             DomainCommand::SubmitResult {
@@ -92,15 +246,71 @@ impl DomainEventLoop {
                 participant_id,
             } => self.handle_remove_submitter(lobby_id, run_id, participant_id),
 
+            DomainCommand::ExpireRun { lobby_id, run_id } => {
+                self.handle_expire_run(lobby_id, run_id)
+            }
+
             DomainCommand::SyncRunStarted {
                 lobby_id,
                 run_id,
                 config,
                 required_submitters,
-            } => self.handle_sync_run_started(lobby_id, run_id, config, required_submitters),
+                started_at_ms,
+            } => self.handle_sync_run_started(
+                lobby_id,
+                run_id,
+                config,
+                required_submitters,
+                started_at_ms,
+            ),
+
+            DomainCommand::SyncRunEnded {
+                lobby_id,
+                run_id,
+                status,
+                results,
+            } => self.handle_sync_run_ended(lobby_id, run_id, status, results),
+
+            DomainCommand::SendChatMessage {
+                lobby_id,
+                author_id,
+                text,
+            } => {
+                let sent_at = Timestamp::now_on(self.clock.as_ref());
+                let message = ChatMessage::new_at(author_id, text, sent_at)?;
+                self.handle_post_chat_message(lobby_id, message)
+            }
+
+            DomainCommand::AddChatMessage { lobby_id, message } => {
+                self.handle_post_chat_message(lobby_id, message)
+            }
         }
     }
 
+    fn lobby_mut(&mut self, lobby_id: Uuid) -> Result<&mut Lobby, CommandError> {
+        self.lobbies
+            .get_mut(&lobby_id)
+            .ok_or(CommandError::LobbyNotFound(lobby_id))
+    }
+
+    /// The run, if it belongs to `lobby_id`
+    fn run_mut(
+        &mut self,
+        lobby_id: Uuid,
+        run_id: ActivityRunId,
+    ) -> Result<&mut ActivityRun, CommandError> {
+        self.runs
+            .get_mut(&run_id)
+            .filter(|run| run.lobby_id() == lobby_id)
+            .ok_or(CommandError::RunNotFound(run_id))
+    }
+
+    fn insert_lobby(&mut self, mut lobby: Lobby) -> Lobby {
+        lobby.set_permission_policy(self.policy.clone());
+        self.lobbies.insert(lobby.id(), lobby.clone());
+        lobby
+    }
+
     // ── Lobby handlers ────────────────────────────────────────────────────────
 
     fn handle_create_lobby(
@@ -108,31 +318,16 @@ impl DomainEventLoop {
         lobby_id: Option<Uuid>,
         lobby_name: String,
         host_name: String,
-    ) -> DomainEvent {
-        match Participant::new_host(host_name) {
-            Ok(host) => {
-                let result = if let Some(id) = lobby_id {
-                    Lobby::with_id(id, lobby_name, host)
-                } else {
-                    Lobby::new(lobby_name, host)
-                };
-                match result {
-                    Ok(lobby) => {
-                        let id = lobby.id();
-                        self.lobbies.insert(id, lobby.clone());
-                        DomainEvent::LobbyCreated { lobby }
-                    }
-                    Err(e) => DomainEvent::CommandFailed {
-                        command: "CreateLobby".to_string(),
-                        reason: e.to_string(),
-                    },
-                }
-            }
-            Err(e) => DomainEvent::CommandFailed {
-                command: "CreateLobby".to_string(),
-                reason: e.to_string(),
-            },
-        }
+    ) -> Result<DomainEvent, CommandError> {
+        let host = Participant::new_host_at(host_name, Timestamp::now_on(self.clock.as_ref()))?;
+        let lobby = if let Some(id) = lobby_id {
+            Lobby::with_id(id, lobby_name, host)?
+        } else {
+            Lobby::new(lobby_name, host)?
+        };
+        Ok(DomainEvent::LobbyCreated {
+            lobby: self.insert_lobby(lobby),
+        })
     }
 
     fn handle_create_lobby_with_host(
@@ -140,90 +335,56 @@ impl DomainEventLoop {
         lobby_id: Uuid,
         lobby_name: String,
         host: Participant,
-    ) -> DomainEvent {
-        match Lobby::with_id(lobby_id, lobby_name, host) {
-            Ok(lobby) => {
-                self.lobbies.insert(lobby.id(), lobby.clone());
-                DomainEvent::LobbyCreated { lobby }
-            }
-            Err(e) => DomainEvent::CommandFailed {
-                command: "CreateLobbyWithHost".to_string(),
-                reason: e.to_string(),
-            },
-        }
+    ) -> Result<DomainEvent, CommandError> {
+        let lobby = Lobby::with_id(lobby_id, lobby_name, host)?;
+        Ok(DomainEvent::LobbyCreated {
+            lobby: self.insert_lobby(lobby),
+        })
     }
 
-    fn handle_join_lobby(&mut self, lobby_id: Uuid, guest_name: String) -> DomainEvent {
-        let lobby = match self.lobbies.get_mut(&lobby_id) {
-            Some(l) => l,
-            None => {
-                return DomainEvent::CommandFailed {
-                    command: "JoinLobby".to_string(),
-                    reason: format!("Lobby {} not found", lobby_id),
-                };
-            }
-        };
-        match Participant::new_guest(guest_name) {
-            Ok(guest) => match lobby.add_guest(guest.clone()) {
-                Ok(_) => DomainEvent::GuestJoined {
-                    lobby_id,
-                    participant: guest,
-                },
-                Err(e) => DomainEvent::CommandFailed {
-                    command: "JoinLobby".to_string(),
-                    reason: e.to_string(),
-                },
-            },
-            Err(e) => DomainEvent::CommandFailed {
-                command: "JoinLobby".to_string(),
-                reason: e.to_string(),
-            },
-        }
+    fn handle_join_lobby(
+        &mut self,
+        lobby_id: Uuid,
+        guest_name: String,
+    ) -> Result<DomainEvent, CommandError> {
+        let joined_at = Timestamp::now_on(self.clock.as_ref());
+        let lobby = self.lobby_mut(lobby_id)?;
+        lobby.check_can_join()?;
+        let guest = Participant::with_timestamp(guest_name, LobbyRole::Guest, joined_at)?;
+        lobby.add_guest(guest.clone())?;
+        Ok(DomainEvent::GuestJoined {
+            lobby_id,
+            participant: guest,
+        })
     }
 
-    fn handle_leave_lobby(&mut self, lobby_id: Uuid, participant_id: Uuid) -> DomainEvent {
-        let lobby = match self.lobbies.get_mut(&lobby_id) {
-            Some(l) => l,
-            None => {
-                return DomainEvent::CommandFailed {
-                    command: "LeaveLobby".to_string(),
-                    reason: format!("Lobby {} not found", lobby_id),
-                };
-            }
-        };
-        match lobby.remove_participant(participant_id) {
-            Ok(_) => DomainEvent::GuestLeft {
-                lobby_id,
-                participant_id,
-            },
-            Err(e) => DomainEvent::CommandFailed {
-                command: "LeaveLobby".to_string(),
-                reason: e.to_string(),
-            },
-        }
+    fn handle_leave_lobby(
+        &mut self,
+        lobby_id: Uuid,
+        participant_id: Uuid,
+    ) -> Result<DomainEvent, CommandError> {
+        self.lobby_mut(lobby_id)?
+            .remove_participant(participant_id)?;
+        Ok(DomainEvent::GuestLeft {
+            lobby_id,
+            participant_id,
+        })
     }
 
-    fn handle_kick_guest(&mut self, lobby_id: Uuid, host_id: Uuid, guest_id: Uuid) -> DomainEvent {
-        let lobby = match self.lobbies.get_mut(&lobby_id) {
-            Some(l) => l,
-            None => {
-                return DomainEvent::CommandFailed {
-                    command: "KickGuest".to_string(),
-                    reason: format!("Lobby {} not found", lobby_id),
-                };
-            }
-        };
-        match lobby.kick_guest(guest_id, host_id) {
-            Ok(_) => DomainEvent::GuestKicked {
-                lobby_id,
-                participant_id: guest_id,
-                kicked_by: host_id,
-            },
-            Err(e) => DomainEvent::CommandFailed {
-                command: "KickGuest".to_string(),
-                reason: e.to_string(),
-            },
-        }
+    fn handle_kick_guest(
+        &mut self,
+        lobby_id: Uuid,
+        host_id: Uuid,
+        guest_id: Uuid,
+        ban: bool,
+    ) -> Result<DomainEvent, CommandError> {
+        self.lobby_mut(lobby_id)?.kick_guest(guest_id, host_id)?;
+        Ok(DomainEvent::GuestKicked {
+            lobby_id,
+            participant_id: guest_id,
+            kicked_by: host_id,
+            banned: ban,
+        })
     }
 
     fn handle_toggle_participation_mode(
@@ -231,78 +392,124 @@ impl DomainEventLoop {
         lobby_id: Uuid,
         participant_id: Uuid,
         requester_id: Uuid,
-    ) -> DomainEvent {
-        let lobby = match self.lobbies.get_mut(&lobby_id) {
-            Some(l) => l,
-            None => {
-                return DomainEvent::CommandFailed {
-                    command: "ToggleParticipationMode".to_string(),
-                    reason: format!("Lobby {} not found", lobby_id),
-                };
-            }
-        };
-        match lobby.toggle_participation_mode(participant_id, requester_id) {
-            Ok(new_mode) => DomainEvent::ParticipationModeChanged {
-                lobby_id,
-                participant_id,
-                new_mode,
-            },
-            Err(e) => DomainEvent::CommandFailed {
-                command: "ToggleParticipationMode".to_string(),
-                reason: e.to_string(),
-            },
-        }
+    ) -> Result<DomainEvent, CommandError> {
+        let new_mode = self
+            .lobby_mut(lobby_id)?
+            .toggle_participation_mode(participant_id, requester_id)?;
+        Ok(DomainEvent::ParticipationModeChanged {
+            lobby_id,
+            participant_id,
+            new_mode,
+        })
     }
 
     fn handle_delegate_host(
         &mut self,
         lobby_id: Uuid,
-        _current_host_id: Uuid,
+        current_host_id: Uuid,
         new_host_id: Uuid,
-    ) -> DomainEvent {
-        let lobby = match self.lobbies.get_mut(&lobby_id) {
-            Some(l) => l,
-            None => {
-                return DomainEvent::CommandFailed {
-                    command: "DelegateHost".to_string(),
-                    reason: format!("Lobby {} not found", lobby_id),
-                };
-            }
-        };
+    ) -> Result<DomainEvent, CommandError> {
+        let lobby = self.lobby_mut(lobby_id)?;
         let old_host_id = lobby.host_id();
-        match lobby.delegate_host(new_host_id) {
-            Ok(_) => DomainEvent::HostDelegated {
-                lobby_id,
-                from: old_host_id,
-                to: new_host_id,
-            },
-            Err(e) => DomainEvent::CommandFailed {
-                command: "DelegateHost".to_string(),
-                reason: e.to_string(),
-            },
+        if !lobby.allows(current_host_id, LobbyAction::DelegateHost) {
+            return Err(LobbyError::PermissionDenied.into());
         }
+        lobby.delegate_host(new_host_id)?;
+        Ok(DomainEvent::HostDelegated {
+            lobby_id,
+            from: old_host_id,
+            to: new_host_id,
+        })
     }
 
-    fn handle_add_participant(&mut self, lobby_id: Uuid, participant: Participant) -> DomainEvent {
-        let lobby = match self.lobbies.get_mut(&lobby_id) {
-            Some(l) => l,
-            None => {
-                return DomainEvent::CommandFailed {
-                    command: "AddParticipant".to_string(),
-                    reason: format!("Lobby {} not found", lobby_id),
-                };
-            }
-        };
-        match lobby.add_guest(participant.clone()) {
-            Ok(_) => DomainEvent::GuestJoined {
-                lobby_id,
-                participant,
-            },
-            Err(e) => DomainEvent::CommandFailed {
-                command: "AddParticipant".to_string(),
-                reason: e.to_string(),
-            },
-        }
+    fn handle_set_co_host(
+        &mut self,
+        lobby_id: Uuid,
+        host_id: Uuid,
+        participant_id: Uuid,
+        co_host: bool,
+    ) -> Result<DomainEvent, CommandError> {
+        self.lobby_mut(lobby_id)?
+            .set_co_host(participant_id, host_id, co_host)?;
+        Ok(DomainEvent::CoHostChanged {
+            lobby_id,
+            participant_id,
+            co_host,
+            changed_by: host_id,
+        })
+    }
+
+    fn handle_set_avatar(
+        &mut self,
+        lobby_id: Uuid,
+        participant_id: Uuid,
+        requester_id: Uuid,
+        avatar: Option<ParticipantAvatar>,
+    ) -> Result<DomainEvent, CommandError> {
+        self.lobby_mut(lobby_id)?
+            .set_avatar(participant_id, requester_id, avatar.clone())?;
+        Ok(DomainEvent::AvatarChanged {
+            lobby_id,
+            participant_id,
+            avatar,
+            changed_by: requester_id,
+        })
+    }
+
+    fn handle_set_ready(
+        &mut self,
+        lobby_id: Uuid,
+        participant_id: Uuid,
+        ready: bool,
+    ) -> Result<DomainEvent, CommandError> {
+        self.lobby_mut(lobby_id)?.set_ready(participant_id, ready)?;
+        Ok(DomainEvent::ReadyChanged {
+            lobby_id,
+            participant_id,
+            ready,
+        })
+    }
+
+    fn handle_start_countdown(
+        &mut self,
+        lobby_id: Uuid,
+        requester_id: Uuid,
+        ends_at: u64,
+    ) -> Result<DomainEvent, CommandError> {
+        self.lobby_mut(lobby_id)?
+            .start_countdown(requester_id, ends_at)?;
+        Ok(DomainEvent::CountdownStarted {
+            lobby_id,
+            ends_at,
+            started_by: requester_id,
+        })
+    }
+
+    fn handle_update_lobby_settings(
+        &mut self,
+        lobby_id: Uuid,
+        host_id: Uuid,
+        settings: LobbySettings,
+    ) -> Result<DomainEvent, CommandError> {
+        self.lobby_mut(lobby_id)?
+            .update_settings(settings, host_id)?;
+        Ok(DomainEvent::LobbySettingsChanged {
+            lobby_id,
+            settings,
+            changed_by: host_id,
+        })
+    }
+
+    fn handle_add_participant(
+        &mut self,
+        lobby_id: Uuid,
+        participant: Participant,
+    ) -> Result<DomainEvent, CommandError> {
+        self.lobby_mut(lobby_id)?.add_guest(participant.clone())?;
+        Ok(DomainEvent::GuestJoined {
+            lobby_id,
+            participant,
+        })
     }
 
     fn handle_update_participant_mode(
@@ -310,97 +517,68 @@ impl DomainEventLoop {
         lobby_id: Uuid,
         participant_id: Uuid,
         new_mode: ParticipationMode,
-    ) -> DomainEvent {
-        let lobby = match self.lobbies.get_mut(&lobby_id) {
-            Some(l) => l,
-            None => {
-                return DomainEvent::CommandFailed {
-                    command: "UpdateParticipantMode".to_string(),
-                    reason: format!("Lobby {} not found", lobby_id),
-                };
-            }
-        };
-        match lobby.participants_mut().get_mut(&participant_id) {
-            Some(p) => {
-                p.force_participation_mode(new_mode);
-                DomainEvent::ParticipationModeChanged {
-                    lobby_id,
-                    participant_id,
-                    new_mode,
-                }
-            }
-            None => DomainEvent::CommandFailed {
-                command: "UpdateParticipantMode".to_string(),
-                reason: format!("Participant {} not found", participant_id),
-            },
-        }
+    ) -> Result<DomainEvent, CommandError> {
+        self.lobby_mut(lobby_id)?
+            .participants_mut()
+            .get_mut(&participant_id)
+            .ok_or(LobbyError::ParticipantNotFound(participant_id))?
+            .force_participation_mode(new_mode);
+        Ok(DomainEvent::ParticipationModeChanged {
+            lobby_id,
+            participant_id,
+            new_mode,
+        })
     }
 
     fn handle_queue_activity(
         &mut self,
         lobby_id: Uuid,
         config: crate::domain::ActivityConfig,
-    ) -> DomainEvent {
-        let lobby = match self.lobbies.get_mut(&lobby_id) {
-            Some(l) => l,
-            None => {
-                return DomainEvent::CommandFailed {
-                    command: "QueueActivity".to_string(),
-                    reason: format!("Lobby {} not found", lobby_id),
-                };
-            }
-        };
-        match lobby.queue_activity(config.clone()) {
-            Ok(_) => DomainEvent::ActivityQueued { lobby_id, config },
-            Err(e) => DomainEvent::CommandFailed {
-                command: "QueueActivity".to_string(),
-                reason: e.to_string(),
-            },
-        }
+    ) -> Result<DomainEvent, CommandError> {
+        self.lobby_mut(lobby_id)?.queue_activity(config.clone())?;
+        Ok(DomainEvent::ActivityQueued { lobby_id, config })
+    }
+
+    fn handle_move_queued_activity(
+        &mut self,
+        lobby_id: Uuid,
+        activity_id: ActivityId,
+        to_index: usize,
+        requester_id: Uuid,
+    ) -> Result<DomainEvent, CommandError> {
+        let to_index =
+            self.lobby_mut(lobby_id)?
+                .move_queued_activity(activity_id, to_index, requester_id)?;
+        Ok(DomainEvent::QueuedActivityMoved {
+            lobby_id,
+            activity_id,
+            to_index,
+            moved_by: requester_id,
+        })
     }
 
     // ── Run handlers ──────────────────────────────────────────────────────────
 
-    fn handle_start_next_run(&mut self, lobby_id: Uuid) -> DomainEvent {
-        let lobby = match self.lobbies.get_mut(&lobby_id) {
-            Some(l) => l,
-            None => {
-                return DomainEvent::CommandFailed {
-                    command: "StartNextRun".to_string(),
-                    reason: format!("Lobby {} not found", lobby_id),
-                };
-            }
-        };
+    fn handle_start_next_run(&mut self, lobby_id: Uuid) -> Result<DomainEvent, CommandError> {
+        let started_at_ms = Some(self.clock.unix_millis());
+        let lobby = self.lobby_mut(lobby_id)?;
 
         // Snapshot active participants before dequeuing
         let snapshot = lobby.active_participant_ids();
-
-        let config = match lobby.dequeue_next_activity() {
-            Ok(c) => c,
-            Err(e) => {
-                return DomainEvent::CommandFailed {
-                    command: "StartNextRun".to_string(),
-                    reason: e.to_string(),
-                };
-            }
-        };
+        let config = lobby.dequeue_next_activity()?;
 
         let run_id = Uuid::new_v4();
-        let run = ActivityRun::new(run_id, lobby_id, config.clone(), snapshot);
+        let run = ActivityRun::new(run_id, lobby_id, config.clone(), snapshot)
+            .with_started_at(started_at_ms);
+        lobby.set_active_run(run_id)?;
 
-        if let Err(e) = lobby.set_active_run(run_id) {
-            return DomainEvent::CommandFailed {
-                command: "StartNextRun".to_string(),
-                reason: e.to_string(),
-            };
-        }
-
-        self.runs.insert(run_id, run);
-        DomainEvent::RunStarted {
+        self.insert_run(run);
+        Ok(DomainEvent::RunStarted {
             lobby_id,
             run_id,
             config,
-        }
+            started_at_ms,
+        })
     }
 
     fn handle_submit_result(
@@ -408,75 +586,80 @@ impl DomainEventLoop {
         lobby_id: Uuid,
         run_id: ActivityRunId,
         result: crate::domain::ActivityResult,
-    ) -> DomainEvent {
-        let run = match self.runs.get_mut(&run_id) {
-            Some(r) => r,
-            None => {
-                return DomainEvent::CommandFailed {
-                    command: "SubmitResult".to_string(),
-                    reason: format!("Run {} not found", run_id),
-                };
-            }
-        };
+    ) -> Result<DomainEvent, CommandError> {
+        let participant_id = result.participant_id;
+        let spectating = self
+            .lobbies
+            .get(&lobby_id)
+            .and_then(|lobby| lobby.participants().get(&participant_id))
+            .is_some_and(|p| p.participation_mode() == ParticipationMode::Spectating);
+        if spectating {
+            return Err(ActivityRunError::SpectatorSubmission(participant_id).into());
+        }
 
-        match run.submit_result(result.clone()) {
-            Ok(completed) => {
-                if completed {
-                    let results: Vec<_> = run.results().values().cloned().collect();
-                    let status = run.status();
-                    if let Some(lobby) = self.lobbies.get_mut(&lobby_id) {
-                        lobby.clear_active_run();
-                    }
-                    DomainEvent::RunEnded {
-                        lobby_id,
-                        run_id,
-                        status,
-                        results,
-                    }
-                } else {
-                    DomainEvent::ResultSubmitted {
-                        lobby_id,
-                        run_id,
-                        result,
-                    }
-                }
-            }
-            Err(e) => DomainEvent::CommandFailed {
-                command: "SubmitResult".to_string(),
-                reason: e.to_string(),
-            },
+        let run = self.run_mut(lobby_id, run_id)?;
+
+        if !run.submit_result(result.clone())? {
+            return Ok(DomainEvent::ResultSubmitted {
+                lobby_id,
+                run_id,
+                result,
+            });
         }
+        let results: Vec<_> = run.results().values().cloned().collect();
+        let status = run.status();
+        if let Some(lobby) = self.lobbies.get_mut(&lobby_id) {
+            lobby.clear_active_run();
+        }
+        Ok(DomainEvent::RunEnded {
+            lobby_id,
+            run_id,
+            status,
+            results,
+        })
     }
 
-    fn handle_cancel_run(&mut self, lobby_id: Uuid, run_id: ActivityRunId) -> DomainEvent {
-        let run = match self.runs.get_mut(&run_id) {
-            Some(r) => r,
-            None => {
-                return DomainEvent::CommandFailed {
-                    command: "CancelRun".to_string(),
-                    reason: format!("Run {} not found", run_id),
-                };
-            }
-        };
-        match run.cancel() {
-            Ok(_) => {
-                let results: Vec<_> = run.results().values().cloned().collect();
-                let status = run.status();
-                if let Some(lobby) = self.lobbies.get_mut(&lobby_id) {
-                    lobby.clear_active_run();
-                }
-                DomainEvent::RunEnded {
-                    lobby_id,
-                    run_id,
-                    status,
-                    results,
-                }
-            }
-            Err(e) => DomainEvent::CommandFailed {
-                command: "CancelRun".to_string(),
-                reason: e.to_string(),
-            },
+    fn handle_cancel_run(
+        &mut self,
+        lobby_id: Uuid,
+        run_id: ActivityRunId,
+    ) -> Result<DomainEvent, CommandError> {
+        let run = self.run_mut(lobby_id, run_id)?;
+        run.cancel()?;
+
+        let results: Vec<_> = run.results().values().cloned().collect();
+        let status = run.status();
+        if let Some(lobby) = self.lobbies.get_mut(&lobby_id) {
+            lobby.clear_active_run();
         }
+        Ok(DomainEvent::RunEnded {
+            lobby_id,
+            run_id,
+            status,
+            results,
+        })
+    }
+
+    fn handle_expire_run(
+        &mut self,
+        lobby_id: Uuid,
+        run_id: ActivityRunId,
+    ) -> Result<DomainEvent, CommandError> {
+        let now_ms = self.clock.unix_millis();
+        let run = self.run_mut(lobby_id, run_id)?;
+        run.expire(now_ms)?;
+
+        let results: Vec<_> = run.results().values().cloned().collect();
+        let status = run.status();
+        if let Some(lobby) = self.lobbies.get_mut(&lobby_id) {
+            lobby.clear_active_run();
+        }
+        Ok(DomainEvent::RunEnded {
+            lobby_id,
+            run_id,
+            status,
+            results,
+        })
     }
 
     fn handle_remove_submitter(
@@ -484,43 +667,27 @@ impl DomainEventLoop {
         lobby_id: Uuid,
         run_id: ActivityRunId,
         participant_id: Uuid,
-    ) -> DomainEvent {
-        let run = match self.runs.get_mut(&run_id) {
-            Some(r) => r,
-            None => {
-                return DomainEvent::CommandFailed {
-                    command: "RemoveSubmitter".to_string(),
-                    reason: format!("Run {} not found", run_id),
-                };
-            }
-        };
-        match run.remove_submitter(participant_id) {
-            Ok(ended) => {
-                if ended {
-                    let results: Vec<_> = run.results().values().cloned().collect();
-                    let status = run.status();
-                    if let Some(lobby) = self.lobbies.get_mut(&lobby_id) {
-                        lobby.clear_active_run();
-                    }
-                    DomainEvent::RunEnded {
-                        lobby_id,
-                        run_id,
-                        status,
-                        results,
-                    }
-                } else {
-                    DomainEvent::SubmitterRemoved {
-                        lobby_id,
-                        run_id,
-                        participant_id,
-                    }
-                }
-            }
-            Err(e) => DomainEvent::CommandFailed {
-                command: "RemoveSubmitter".to_string(),
-                reason: e.to_string(),
-            },
+    ) -> Result<DomainEvent, CommandError> {
+        let run = self.run_mut(lobby_id, run_id)?;
+
+        if !run.remove_submitter(participant_id)? {
+            return Ok(DomainEvent::SubmitterRemoved {
+                lobby_id,
+                run_id,
+                participant_id,
+            });
+        }
+        let results: Vec<_> = run.results().values().cloned().collect();
+        let status = run.status();
+        if let Some(lobby) = self.lobbies.get_mut(&lobby_id) {
+            lobby.clear_active_run();
         }
+        Ok(DomainEvent::RunEnded {
+            lobby_id,
+            run_id,
+            status,
+            results,
+        })
     }
 
     fn handle_sync_run_started(
@@ -529,36 +696,73 @@ impl DomainEventLoop {
         run_id: crate::domain::ActivityRunId,
         config: crate::domain::ActivityConfig,
         required_submitters: Vec<Uuid>,
-    ) -> DomainEvent {
-        let lobby = match self.lobbies.get_mut(&lobby_id) {
-            Some(l) => l,
-            None => {
-                return DomainEvent::CommandFailed {
-                    command: "SyncRunStarted".to_string(),
-                    reason: format!("Lobby {} not found", lobby_id),
-                };
-            }
-        };
+        started_at_ms: Option<u64>,
+    ) -> Result<DomainEvent, CommandError> {
+        let lobby = self.lobby_mut(lobby_id)?;
         let snapshot: std::collections::HashSet<Uuid> = required_submitters.into_iter().collect();
-        let run = ActivityRun::new(run_id, lobby_id, config.clone(), snapshot);
-        if let Err(e) = lobby.set_active_run(run_id) {
-            return DomainEvent::CommandFailed {
-                command: "SyncRunStarted".to_string(),
-                reason: e.to_string(),
-            };
-        }
-        self.runs.insert(run_id, run);
-        DomainEvent::RunStarted {
+        // Keep the host's start time, not the time we heard of it
+        let run = ActivityRun::new(run_id, lobby_id, config.clone(), snapshot)
+            .with_started_at(started_at_ms);
+        lobby.set_active_run(run_id)?;
+        // The host dequeued it when starting the run
+        let _ = lobby.remove_queued_activity(config.id);
+        self.insert_run(run);
+        Ok(DomainEvent::RunStarted {
             lobby_id,
             run_id,
             config,
+            started_at_ms,
+        })
+    }
+
+    fn handle_sync_run_ended(
+        &mut self,
+        lobby_id: Uuid,
+        run_id: ActivityRunId,
+        status: crate::domain::RunStatus,
+        results: Vec<crate::domain::ActivityResult>,
+    ) -> Result<DomainEvent, CommandError> {
+        let run = self.run_mut(lobby_id, run_id)?;
+        run.apply_outcome(status, results);
+
+        let results: Vec<_> = run.results().values().cloned().collect();
+        if let Some(lobby) = self.lobbies.get_mut(&lobby_id)
+            && lobby.active_run_id() == Some(run_id)
+        {
+            lobby.clear_active_run();
+        }
+        Ok(DomainEvent::RunEnded {
+            lobby_id,
+            run_id,
+            status,
+            results,
+        })
+    }
+
+    fn insert_run(&mut self, run: ActivityRun) {
+        let run_id = run.id();
+        if self.runs.insert(run_id, run).is_none() {
+            self.run_order.push(run_id);
         }
     }
 
+    // ── Chat handlers ─────────────────────────────────────────────────────────
+
+    fn handle_post_chat_message(
+        &mut self,
+        lobby_id: Uuid,
+        message: ChatMessage,
+    ) -> Result<DomainEvent, CommandError> {
+        self.lobby_mut(lobby_id)?
+            .post_chat_message(message.clone())?;
+        Ok(DomainEvent::ChatMessageSent { lobby_id, message })
+    }
+
     // ── Inspection ────────────────────────────────────────────────────────────
 
+    /// Store `lobby` under this loop's permission policy
     pub fn add_lobby(&mut self, lobby: Lobby) {
-        self.lobbies.insert(lobby.id(), lobby);
+        self.insert_lobby(lobby);
     }
 
     pub fn get_lobby(&self, lobby_id: &Uuid) -> Option<&Lobby> {
@@ -569,6 +773,23 @@ impl DomainEventLoop {
         self.runs.get(run_id)
     }
 
+    /// Every run of a lobby (finished or not), oldest first
+    pub fn runs_for_lobby(&self, lobby_id: Uuid) -> impl Iterator<Item = &ActivityRun> {
+        self.run_order
+            .iter()
+            .filter_map(|run_id| self.runs.get(run_id))
+            .filter(move |run| run.lobby_id() == lobby_id)
+    }
+
+    /// The lobby's run in progress, if its deadline passed at `now_ms`
+    pub fn overdue_run(&self, lobby_id: Uuid, now_ms: u64) -> Option<ActivityRunId> {
+        let run_id = self.lobbies.get(&lobby_id)?.active_run_id()?;
+        self.runs
+            .get(&run_id)
+            .filter(|run| run.is_overdue(now_ms))
+            .map(ActivityRun::id)
+    }
+
     pub fn lobby_count(&self) -> usize {
         self.lobbies.len()
     }
@@ -584,7 +805,8 @@ impl Default for DomainEventLoop {
 mod tests {
     use super::*;
     use crate::application::DomainCommand;
-    use crate::domain::{ActivityConfig, ActivityResult, RunStatus};
+    use crate::domain::{ActivityConfig, ActivityResult, Clock, RunStatus, TestClock};
+    use crate::error::ErrorKind;
 
     fn create_lobby(el: &mut DomainEventLoop, name: &str, host: &str) -> (Uuid, Uuid) {
         match el.handle_command(DomainCommand::CreateLobby {
@@ -680,6 +902,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_spectators_cannot_submit_results() {
+        let mut el = DomainEventLoop::new();
+        let (lobby_id, _) = create_lobby(&mut el, "Test", "Alice");
+        let guest_id = join_lobby(&mut el, lobby_id, "Bob");
+        el.handle_command(DomainCommand::ToggleParticipationMode {
+            lobby_id,
+            participant_id: guest_id,
+            requester_id: guest_id,
+        });
+
+        let config =
+            ActivityConfig::new("quiz".to_string(), "Q1".to_string(), serde_json::json!({}));
+        el.handle_command(DomainCommand::QueueActivity { lobby_id, config });
+        let run_id = match el.handle_command(DomainCommand::StartNextRun { lobby_id }) {
+            DomainEvent::RunStarted { run_id, .. } => run_id,
+            e => panic!("Expected RunStarted, got {:?}", e),
+        };
+
+        let event = el.handle_command(DomainCommand::SubmitResult {
+            lobby_id,
+            run_id,
+            result: ActivityResult::new(run_id, guest_id),
+        });
+        match event {
+            DomainEvent::CommandFailed { error, .. } => {
+                assert_eq!(error.to_string(), "Spectators cannot submit results");
+                assert_eq!(error.kind(), ErrorKind::PermissionDenied);
+            }
+            e => panic!("Expected CommandFailed, got {:?}", e),
+        }
+    }
+
     #[test]
     fn test_toggle_participation_mode_blocked_during_run() {
         let mut el = DomainEventLoop::new();
@@ -703,6 +958,128 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_failure_carries_command_and_error() {
+        let mut el = DomainEventLoop::new();
+        let (lobby_id, _) = create_lobby(&mut el, "Test", "Alice");
+        let guest_id = join_lobby(&mut el, lobby_id, "Bob");
+
+        let kick = DomainCommand::KickGuest {
+            lobby_id,
+            host_id: guest_id,
+            guest_id,
+            ban: false,
+        };
+        assert_eq!(
+            el.handle_command(kick.clone()),
+            DomainEvent::command_failed(kick, LobbyError::PermissionDenied)
+        );
+
+        let missing = Uuid::new_v4();
+        match el.handle_command(DomainCommand::StartNextRun { lobby_id: missing }) {
+            DomainEvent::CommandFailed { command, error } => {
+                assert_eq!(command.name(), "StartNextRun");
+                assert_eq!(error, CommandError::LobbyNotFound(missing));
+            }
+            e => panic!("Expected CommandFailed, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_guest_requests_act_only_for_the_sender() {
+        let mut el = DomainEventLoop::new();
+        let (lobby_id, host_id) = create_lobby(&mut el, "Test", "Alice");
+        let guest_id = join_lobby(&mut el, lobby_id, "Bob");
+        let denied = |event: DomainEvent| matches!(event, DomainEvent::CommandFailed { error, .. } if error.kind() == ErrorKind::PermissionDenied);
+
+        // Bob posing as the host, replaying a sync command, running the queue
+        let kick = DomainCommand::KickGuest {
+            lobby_id,
+            host_id,
+            guest_id,
+            ban: false,
+        };
+        assert!(denied(el.handle_request(kick, Some(guest_id))));
+        let forged = DomainCommand::UpdateParticipantMode {
+            lobby_id,
+            participant_id: host_id,
+            new_mode: crate::domain::ParticipationMode::Spectating,
+        };
+        assert!(denied(el.handle_request(forged, Some(guest_id))));
+        assert!(denied(el.handle_request(
+            DomainCommand::StartNextRun { lobby_id },
+            Some(guest_id)
+        )));
+
+        // Before joining only JoinLobby goes through
+        let ready = DomainCommand::SetReady {
+            lobby_id,
+            participant_id: guest_id,
+            ready: true,
+        };
+        assert!(denied(el.handle_request(ready.clone(), None)));
+        assert!(matches!(
+            el.handle_request(ready, Some(guest_id)),
+            DomainEvent::ReadyChanged { ready: true, .. }
+        ));
+        assert!(matches!(
+            el.handle_request(
+                DomainCommand::JoinLobby {
+                    lobby_id,
+                    guest_name: "Carol".to_string(),
+                },
+                None
+            ),
+            DomainEvent::GuestJoined { .. }
+        ));
+    }
+
+    #[test]
+    fn test_permission_policy_governs_requests() {
+        /// Anyone may run the queue; only the host may chat
+        #[derive(Debug)]
+        struct OpenQueue;
+
+        impl PermissionPolicy for OpenQueue {
+            fn allows(&self, lobby: &Lobby, actor: Uuid, action: LobbyAction) -> bool {
+                match action {
+                    LobbyAction::PlanQueue | LobbyAction::StartRun => true,
+                    LobbyAction::Chat => actor == lobby.host_id(),
+                    action => DefaultPolicy.allows(lobby, actor, action),
+                }
+            }
+        }
+
+        let mut el = DomainEventLoop::new().with_permission_policy(OpenQueue);
+        let (lobby_id, _) = create_lobby(&mut el, "Test", "Alice");
+        let guest_id = join_lobby(&mut el, lobby_id, "Bob");
+
+        let config =
+            ActivityConfig::new("quiz".to_string(), "Q1".to_string(), serde_json::json!({}));
+        assert!(matches!(
+            el.handle_request(
+                DomainCommand::QueueActivity { lobby_id, config },
+                Some(guest_id)
+            ),
+            DomainEvent::ActivityQueued { .. }
+        ));
+        assert!(matches!(
+            el.handle_request(DomainCommand::StartNextRun { lobby_id }, Some(guest_id)),
+            DomainEvent::RunStarted { .. }
+        ));
+        assert!(matches!(
+            el.handle_request(
+                DomainCommand::SendChatMessage {
+                    lobby_id,
+                    author_id: guest_id,
+                    text: "Hallo".to_string(),
+                },
+                Some(guest_id)
+            ),
+            DomainEvent::CommandFailed { .. }
+        ));
+    }
+
     #[test]
     fn test_cancel_run() {
         let mut el = DomainEventLoop::new();
@@ -724,4 +1101,285 @@ mod tests {
         }
         assert!(!el.get_lobby(&lobby_id).unwrap().has_active_run());
     }
+
+    #[test]
+    fn test_run_commands_are_scoped_to_their_lobby() {
+        let mut el = DomainEventLoop::new();
+        let (lobby_a, host_a) = create_lobby(&mut el, "A", "Alice");
+        let (lobby_b, _) = create_lobby(&mut el, "B", "Bob");
+
+        for lobby_id in [lobby_a, lobby_b] {
+            let config =
+                ActivityConfig::new("quiz".to_string(), "Q1".to_string(), serde_json::json!({}));
+            el.handle_command(DomainCommand::QueueActivity { lobby_id, config });
+        }
+        el.handle_command(DomainCommand::StartNextRun { lobby_id: lobby_a });
+        let run_b = match el.handle_command(DomainCommand::StartNextRun { lobby_id: lobby_b }) {
+            DomainEvent::RunStarted { run_id, .. } => run_id,
+            e => panic!("Expected RunStarted, got {:?}", e),
+        };
+
+        let commands = [
+            DomainCommand::CancelRun {
+                lobby_id: lobby_a,
+                run_id: run_b,
+            },
+            DomainCommand::ExpireRun {
+                lobby_id: lobby_a,
+                run_id: run_b,
+            },
+            DomainCommand::SubmitResult {
+                lobby_id: lobby_a,
+                run_id: run_b,
+                result: ActivityResult::new(run_b, host_a),
+            },
+            DomainCommand::RemoveSubmitter {
+                lobby_id: lobby_a,
+                run_id: run_b,
+                participant_id: host_a,
+            },
+        ];
+        for command in commands {
+            match el.handle_command(command) {
+                DomainEvent::CommandFailed { error, .. } => {
+                    assert_eq!(error, CommandError::RunNotFound(run_b))
+                }
+                e => panic!("Expected CommandFailed, got {:?}", e),
+            }
+        }
+
+        assert!(el.get_lobby(&lobby_a).unwrap().has_active_run());
+        assert_eq!(el.get_run(&run_b).unwrap().status(), RunStatus::InProgress);
+    }
+
+    #[test]
+    fn test_expire_overdue_run() {
+        let clock = TestClock::starting_at(1_000);
+        let mut el = DomainEventLoop::new().with_clock(clock.shared());
+        let (lobby_id, host_id) = create_lobby(&mut el, "Test", "Alice");
+        join_lobby(&mut el, lobby_id, "Bob");
+
+        let config = ActivityConfig::new(
+            "quiz".to_string(),
+            "Q1".to_string(),
+            serde_json::json!({ "time_limit_ms": 5_000 }),
+        );
+        el.handle_command(DomainCommand::QueueActivity { lobby_id, config });
+        let run_id = match el.handle_command(DomainCommand::StartNextRun { lobby_id }) {
+            DomainEvent::RunStarted { run_id, .. } => run_id,
+            e => panic!("Expected RunStarted, got {:?}", e),
+        };
+        el.handle_command(DomainCommand::SubmitResult {
+            lobby_id,
+            run_id,
+            result: ActivityResult::new(run_id, host_id).with_submitted_at(3_000),
+        });
+
+        // Still on time: nothing to expire
+        assert_eq!(el.overdue_run(lobby_id, 6_000), None);
+        let event = el.handle_command(DomainCommand::ExpireRun { lobby_id, run_id });
+        assert!(matches!(event, DomainEvent::CommandFailed { .. }));
+
+        clock.advance(std::time::Duration::from_millis(5_001));
+        assert_eq!(el.overdue_run(lobby_id, clock.unix_millis()), Some(run_id));
+        match el.handle_command(DomainCommand::ExpireRun { lobby_id, run_id }) {
+            DomainEvent::RunEnded {
+                status, results, ..
+            } => {
+                assert_eq!(status, RunStatus::Completed);
+                assert_eq!(results.len(), 1);
+            }
+            e => panic!("Expected RunEnded, got {:?}", e),
+        }
+        assert!(!el.get_lobby(&lobby_id).unwrap().has_active_run());
+        assert_eq!(el.overdue_run(lobby_id, clock.unix_millis()), None);
+    }
+
+    #[test]
+    fn test_sync_run_mirrors_the_host() {
+        let mut el = DomainEventLoop::new().with_clock(TestClock::starting_at(1_000).shared());
+        let (lobby_id, host_id) = create_lobby(&mut el, "Test", "Alice");
+        let host = el.get_lobby(&lobby_id).unwrap().host().cloned().unwrap();
+
+        let config =
+            ActivityConfig::new("quiz".to_string(), "Q1".to_string(), serde_json::json!({}));
+        let mut replica = DomainEventLoop::new();
+        replica.add_lobby(Lobby::with_id(lobby_id, "Test".to_string(), host).unwrap());
+        for el in [&mut el, &mut replica] {
+            el.handle_command(DomainCommand::QueueActivity {
+                lobby_id,
+                config: config.clone(),
+            });
+        }
+
+        let (run_id, started_at_ms) =
+            match el.handle_command(DomainCommand::StartNextRun { lobby_id }) {
+                DomainEvent::RunStarted {
+                    run_id,
+                    started_at_ms,
+                    ..
+                } => (run_id, started_at_ms),
+                e => panic!("Expected RunStarted, got {:?}", e),
+            };
+        assert_eq!(started_at_ms, Some(1_000));
+        replica.handle_command(DomainCommand::SyncRunStarted {
+            lobby_id,
+            run_id,
+            config,
+            required_submitters: vec![host_id],
+            started_at_ms,
+        });
+        let lobby = replica.get_lobby(&lobby_id).unwrap();
+        assert_eq!(lobby.active_run_id(), Some(run_id));
+        assert_eq!(
+            replica.get_run(&run_id).unwrap().started_at_ms(),
+            Some(1_000)
+        );
+        assert!(lobby.activity_queue().is_empty());
+
+        let results = match el.handle_command(DomainCommand::SubmitResult {
+            lobby_id,
+            run_id,
+            result: ActivityResult::new(run_id, host_id),
+        }) {
+            DomainEvent::RunEnded { results, .. } => results,
+            e => panic!("Expected RunEnded, got {:?}", e),
+        };
+        let event = replica.handle_command(DomainCommand::SyncRunEnded {
+            lobby_id,
+            run_id,
+            status: RunStatus::Completed,
+            results,
+        });
+
+        assert!(matches!(event, DomainEvent::RunEnded { .. }));
+        assert!(!replica.get_lobby(&lobby_id).unwrap().has_active_run());
+        assert_eq!(replica.get_run(&run_id).unwrap().results().len(), 1);
+    }
+
+    #[test]
+    fn test_runs_for_lobby_in_start_order() {
+        let mut el = DomainEventLoop::new();
+        let (lobby_id, host_id) = create_lobby(&mut el, "Test", "Alice");
+        let (other_lobby, _) = create_lobby(&mut el, "Other", "Carol");
+
+        let mut run_ids = Vec::new();
+        for name in ["Q1", "Q2", "Q3"] {
+            let config =
+                ActivityConfig::new("quiz".to_string(), name.to_string(), serde_json::json!({}));
+            el.handle_command(DomainCommand::QueueActivity { lobby_id, config });
+            let run_id = match el.handle_command(DomainCommand::StartNextRun { lobby_id }) {
+                DomainEvent::RunStarted { run_id, .. } => run_id,
+                e => panic!("Expected RunStarted, got {:?}", e),
+            };
+            el.handle_command(DomainCommand::SubmitResult {
+                lobby_id,
+                run_id,
+                result: ActivityResult::new(run_id, host_id),
+            });
+            run_ids.push(run_id);
+        }
+
+        let listed: Vec<_> = el.runs_for_lobby(lobby_id).map(|run| run.id()).collect();
+        assert_eq!(listed, run_ids);
+        assert_eq!(el.runs_for_lobby(other_lobby).count(), 0);
+    }
+
+    #[test]
+    fn test_chat_message_is_replicated() {
+        let mut el = DomainEventLoop::new();
+        let (lobby_id, host_id) = create_lobby(&mut el, "Test", "Alice");
+
+        let message = match el.handle_command(DomainCommand::SendChatMessage {
+            lobby_id,
+            author_id: host_id,
+            text: "Hello!".to_string(),
+        }) {
+            DomainEvent::ChatMessageSent { message, .. } => message,
+            e => panic!("Expected ChatMessageSent, got {:?}", e),
+        };
+
+        // A peer applying the same message twice keeps one copy
+        let mut replica = DomainEventLoop::new();
+        replica.add_lobby(
+            Lobby::with_id(
+                lobby_id,
+                "Test".to_string(),
+                el.get_lobby(&lobby_id).unwrap().host().cloned().unwrap(),
+            )
+            .unwrap(),
+        );
+        for _ in 0..2 {
+            replica.handle_command(DomainCommand::AddChatMessage {
+                lobby_id,
+                message: message.clone(),
+            });
+        }
+        assert_eq!(
+            replica.get_lobby(&lobby_id).unwrap().chat_messages(),
+            &[message]
+        );
+
+        let event = el.handle_command(DomainCommand::SendChatMessage {
+            lobby_id,
+            author_id: Uuid::new_v4(),
+            text: "Hi".to_string(),
+        });
+        assert!(matches!(event, DomainEvent::CommandFailed { .. }));
+    }
+
+    #[test]
+    fn test_host_controls() {
+        let mut el = DomainEventLoop::new();
+        let (lobby_id, host_id) = create_lobby(&mut el, "Test", "Alice");
+        let bob_id = match el.handle_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Bob".to_string(),
+        }) {
+            DomainEvent::GuestJoined { participant, .. } => participant.id(),
+            e => panic!("Expected GuestJoined, got {:?}", e),
+        };
+
+        let event = el.handle_command(DomainCommand::SetCoHost {
+            lobby_id,
+            host_id,
+            participant_id: bob_id,
+            co_host: true,
+        });
+        assert!(matches!(
+            event,
+            DomainEvent::CoHostChanged { co_host: true, .. }
+        ));
+
+        let settings = LobbySettings {
+            locked: true,
+            ..LobbySettings::default()
+        };
+        let event = el.handle_command(DomainCommand::UpdateLobbySettings {
+            lobby_id,
+            host_id,
+            settings,
+        });
+        assert!(matches!(event, DomainEvent::LobbySettingsChanged { .. }));
+        let event = el.handle_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Carol".to_string(),
+        });
+        assert!(matches!(event, DomainEvent::CommandFailed { .. }));
+
+        // Only the current host can hand the role on
+        let event = el.handle_command(DomainCommand::DelegateHost {
+            lobby_id,
+            current_host_id: bob_id,
+            new_host_id: bob_id,
+        });
+        assert!(matches!(event, DomainEvent::CommandFailed { .. }));
+        let event = el.handle_command(DomainCommand::DelegateHost {
+            lobby_id,
+            current_host_id: host_id,
+            new_host_id: bob_id,
+        });
+        assert!(matches!(event, DomainEvent::HostDelegated { .. }));
+        assert_eq!(el.get_lobby(&lobby_id).unwrap().host_id(), bob_id);
+    }
 }
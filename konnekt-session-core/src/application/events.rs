@@ -1,8 +1,14 @@
-use crate::domain::{ActivityConfig, ActivityResult, ActivityRunId, Lobby, Participant, RunStatus};
+use crate::application::DomainCommand;
+use crate::domain::{
+    ActivityConfig, ActivityId, ActivityResult, ActivityRunId, ChatMessage, Lobby, LobbySettings,
+    Participant, ParticipantAvatar, RunStatus,
+};
+use crate::error::CommandError;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// Events emitted by the domain after successful command execution
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DomainEvent {
     // ── Lobby events ─────────────────────────────────────────────────────────
     LobbyCreated {
@@ -23,6 +29,9 @@ pub enum DomainEvent {
         lobby_id: Uuid,
         participant_id: Uuid,
         kicked_by: Uuid,
+        /// The guest's peer is banned from the session
+        #[serde(default)]
+        banned: bool,
     },
 
     ParticipationModeChanged {
@@ -37,16 +46,59 @@ pub enum DomainEvent {
         to: Uuid,
     },
 
+    CoHostChanged {
+        lobby_id: Uuid,
+        participant_id: Uuid,
+        co_host: bool,
+        changed_by: Uuid,
+    },
+
+    AvatarChanged {
+        lobby_id: Uuid,
+        participant_id: Uuid,
+        avatar: Option<ParticipantAvatar>,
+        changed_by: Uuid,
+    },
+
+    LobbySettingsChanged {
+        lobby_id: Uuid,
+        settings: LobbySettings,
+        changed_by: Uuid,
+    },
+
     ActivityQueued {
         lobby_id: Uuid,
         config: ActivityConfig,
     },
 
+    QueuedActivityMoved {
+        lobby_id: Uuid,
+        activity_id: ActivityId,
+        to_index: usize,
+        moved_by: Uuid,
+    },
+
+    ReadyChanged {
+        lobby_id: Uuid,
+        participant_id: Uuid,
+        ready: bool,
+    },
+
+    CountdownStarted {
+        lobby_id: Uuid,
+        /// Wall clock time in ms since the Unix epoch
+        ends_at: u64,
+        started_by: Uuid,
+    },
+
     // ── Run events ────────────────────────────────────────────────────────────
     RunStarted {
         lobby_id: Uuid,
         run_id: ActivityRunId,
         config: ActivityConfig,
+        /// When the host started the run, on its clock (Unix ms)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        started_at_ms: Option<u64>,
     },
 
     ResultSubmitted {
@@ -68,13 +120,103 @@ pub enum DomainEvent {
         results: Vec<ActivityResult>,
     },
 
+    // ── Chat events ───────────────────────────────────────────────────────────
+    ChatMessageSent {
+        lobby_id: Uuid,
+        message: ChatMessage,
+    },
+
     // ── Errors ────────────────────────────────────────────────────────────────
     CommandFailed {
-        command: String,
-        reason: String,
+        /// The rejected command, as it was submitted
+        command: Box<DomainCommand>,
+        error: CommandError,
     },
 }
 
+impl DomainEvent {
+    /// `command` was rejected with `error`
+    pub fn command_failed(command: DomainCommand, error: impl Into<CommandError>) -> Self {
+        DomainEvent::CommandFailed {
+            command: Box::new(command),
+            error: error.into(),
+        }
+    }
+
+    /// Lobby the event belongs to (`None` for `CommandFailed`)
+    pub fn lobby_id(&self) -> Option<Uuid> {
+        match self {
+            DomainEvent::LobbyCreated { lobby } => Some(lobby.id()),
+            DomainEvent::GuestJoined { lobby_id, .. }
+            | DomainEvent::GuestLeft { lobby_id, .. }
+            | DomainEvent::GuestKicked { lobby_id, .. }
+            | DomainEvent::ParticipationModeChanged { lobby_id, .. }
+            | DomainEvent::HostDelegated { lobby_id, .. }
+            | DomainEvent::CoHostChanged { lobby_id, .. }
+            | DomainEvent::AvatarChanged { lobby_id, .. }
+            | DomainEvent::LobbySettingsChanged { lobby_id, .. }
+            | DomainEvent::ActivityQueued { lobby_id, .. }
+            | DomainEvent::QueuedActivityMoved { lobby_id, .. }
+            | DomainEvent::ReadyChanged { lobby_id, .. }
+            | DomainEvent::CountdownStarted { lobby_id, .. }
+            | DomainEvent::RunStarted { lobby_id, .. }
+            | DomainEvent::ResultSubmitted { lobby_id, .. }
+            | DomainEvent::SubmitterRemoved { lobby_id, .. }
+            | DomainEvent::RunEnded { lobby_id, .. }
+            | DomainEvent::ChatMessageSent { lobby_id, .. } => Some(*lobby_id),
+            DomainEvent::CommandFailed { .. } => None,
+        }
+    }
+
+    pub fn kind(&self) -> EventKind {
+        match self {
+            DomainEvent::LobbyCreated { .. } => EventKind::LobbyCreated,
+            DomainEvent::GuestJoined { .. } => EventKind::GuestJoined,
+            DomainEvent::GuestLeft { .. } => EventKind::GuestLeft,
+            DomainEvent::GuestKicked { .. } => EventKind::GuestKicked,
+            DomainEvent::ParticipationModeChanged { .. } => EventKind::ParticipationModeChanged,
+            DomainEvent::HostDelegated { .. } => EventKind::HostDelegated,
+            DomainEvent::CoHostChanged { .. } => EventKind::CoHostChanged,
+            DomainEvent::AvatarChanged { .. } => EventKind::AvatarChanged,
+            DomainEvent::LobbySettingsChanged { .. } => EventKind::LobbySettingsChanged,
+            DomainEvent::ActivityQueued { .. } => EventKind::ActivityQueued,
+            DomainEvent::QueuedActivityMoved { .. } => EventKind::QueuedActivityMoved,
+            DomainEvent::ReadyChanged { .. } => EventKind::ReadyChanged,
+            DomainEvent::CountdownStarted { .. } => EventKind::CountdownStarted,
+            DomainEvent::RunStarted { .. } => EventKind::RunStarted,
+            DomainEvent::ResultSubmitted { .. } => EventKind::ResultSubmitted,
+            DomainEvent::SubmitterRemoved { .. } => EventKind::SubmitterRemoved,
+            DomainEvent::RunEnded { .. } => EventKind::RunEnded,
+            DomainEvent::ChatMessageSent { .. } => EventKind::ChatMessageSent,
+            DomainEvent::CommandFailed { .. } => EventKind::CommandFailed,
+        }
+    }
+}
+
+/// Which variant a [`DomainEvent`] is, without its payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    LobbyCreated,
+    GuestJoined,
+    GuestLeft,
+    GuestKicked,
+    ParticipationModeChanged,
+    HostDelegated,
+    CoHostChanged,
+    AvatarChanged,
+    LobbySettingsChanged,
+    ActivityQueued,
+    QueuedActivityMoved,
+    ReadyChanged,
+    CountdownStarted,
+    RunStarted,
+    ResultSubmitted,
+    SubmitterRemoved,
+    RunEnded,
+    ChatMessageSent,
+    CommandFailed,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,4 +242,20 @@ mod tests {
         let debug = format!("{:?}", event);
         assert!(debug.contains("ParticipationModeChanged"));
     }
+
+    #[test]
+    fn test_guest_kicked_without_banned_deserializes() {
+        let json = serde_json::json!({
+            "GuestKicked": {
+                "lobby_id": Uuid::new_v4(),
+                "participant_id": Uuid::new_v4(),
+                "kicked_by": Uuid::new_v4(),
+            }
+        });
+        let event: DomainEvent = serde_json::from_value(json).unwrap();
+        assert!(matches!(
+            event,
+            DomainEvent::GuestKicked { banned: false, .. }
+        ));
+    }
 }
@@ -2,8 +2,10 @@ mod commands;
 mod event_loop;
 mod events;
 pub mod runtime;
+mod subscription;
 
 pub use commands::DomainCommand;
 pub use event_loop::DomainEventLoop;
-pub use events::DomainEvent;
+pub use events::{DomainEvent, EventKind};
 pub use runtime::{CommandQueue, DomainLoop, QueueError};
+pub use subscription::{EventFilter, EventSubscription, SUBSCRIPTION_CAPACITY};
@@ -1,10 +1,19 @@
 use crate::application::DomainCommand;
 use std::collections::VecDeque;
+use uuid::Uuid;
+
+/// Who queued a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CommandOrigin {
+    Local,
+    /// A guest's request, with the participant its peer joined as
+    Guest(Option<Uuid>),
+}
 
 /// Synchronous command queue (no async, works in any runtime)
 #[derive(Debug)]
 pub struct CommandQueue {
-    queue: VecDeque<DomainCommand>,
+    queue: VecDeque<(DomainCommand, CommandOrigin)>,
     max_size: usize,
 }
 
@@ -18,21 +27,33 @@ impl CommandQueue {
 
     /// Push a command (returns error if full)
     pub fn push(&mut self, cmd: DomainCommand) -> Result<(), QueueError> {
-        if self.queue.len() >= self.max_size {
+        self.push_from(cmd, CommandOrigin::Local)
+    }
+
+    pub(crate) fn push_from(
+        &mut self,
+        cmd: DomainCommand,
+        origin: CommandOrigin,
+    ) -> Result<(), QueueError> {
+        if self.is_full() {
             return Err(QueueError::Full { max: self.max_size });
         }
-        self.queue.push_back(cmd);
+        self.queue.push_back((cmd, origin));
         Ok(())
     }
 
     /// Pop next command
     pub fn pop(&mut self) -> Option<DomainCommand> {
+        self.pop_with_origin().map(|(cmd, _)| cmd)
+    }
+
+    pub(crate) fn pop_with_origin(&mut self) -> Option<(DomainCommand, CommandOrigin)> {
         self.queue.pop_front()
     }
 
     /// Drain all commands (for batch processing)
     pub fn drain(&mut self) -> Vec<DomainCommand> {
-        self.queue.drain(..).collect()
+        self.queue.drain(..).map(|(cmd, _)| cmd).collect()
     }
 
     pub fn len(&self) -> usize {
@@ -46,6 +67,10 @@ impl CommandQueue {
     pub fn capacity(&self) -> usize {
         self.max_size
     }
+
+    pub fn is_full(&self) -> bool {
+        self.queue.len() >= self.max_size
+    }
 }
 
 #[derive(Debug, thiserror::Error, PartialEq)]
@@ -1,5 +1,9 @@
-use crate::application::runtime::CommandQueue;
-use crate::application::{DomainCommand, DomainEvent, DomainEventLoop};
+use crate::application::runtime::{CommandOrigin, CommandQueue};
+use crate::application::{
+    DomainCommand, DomainEvent, DomainEventLoop, EventFilter, EventSubscription,
+};
+use crate::domain::{PermissionPolicy, SharedClock};
+use uuid::Uuid;
 
 /// Domain event loop - processes commands in batches
 pub struct DomainLoop {
@@ -31,6 +35,17 @@ impl DomainLoop {
         }
     }
 
+    /// Decide who may do what with `policy` (see [`DomainEventLoop::with_permission_policy`])
+    pub fn with_permission_policy(mut self, policy: impl PermissionPolicy + 'static) -> Self {
+        self.event_loop = self.event_loop.with_permission_policy(policy);
+        self
+    }
+
+    /// Stamp started runs with `clock` (see [`DomainEventLoop::with_clock`])
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.event_loop.set_clock(clock);
+    }
+
     /// Submit a command (non-blocking)
     ///
     /// Returns error if queue is full (backpressure)
@@ -41,6 +56,18 @@ impl DomainLoop {
         self.inbound.push(cmd)
     }
 
+    /// Submit a command a guest sent to the host (HOST ONLY)
+    ///
+    /// It runs in turn like any other, once `DomainEventLoop::authorize`
+    /// lets `sender` through; otherwise it comes out as `CommandFailed`.
+    pub fn submit_request(
+        &mut self,
+        cmd: DomainCommand,
+        sender: Option<Uuid>,
+    ) -> Result<(), crate::application::runtime::QueueError> {
+        self.inbound.push_from(cmd, CommandOrigin::Guest(sender))
+    }
+
     /// Process up to `batch_size` commands
     ///
     /// Returns number of commands processed
@@ -48,9 +75,12 @@ impl DomainLoop {
         let mut processed = 0;
 
         while processed < self.batch_size {
-            match self.inbound.pop() {
-                Some(cmd) => {
-                    let event = self.event_loop.handle_command(cmd);
+            match self.inbound.pop_with_origin() {
+                Some((cmd, origin)) => {
+                    let event = match origin {
+                        CommandOrigin::Local => self.event_loop.handle_command(cmd),
+                        CommandOrigin::Guest(sender) => self.event_loop.handle_request(cmd, sender),
+                    };
                     self.outbound.push(event);
                     processed += 1;
                 }
@@ -66,6 +96,11 @@ impl DomainLoop {
         std::mem::take(&mut self.outbound)
     }
 
+    /// Listen to the events alongside whoever drains them
+    pub fn subscribe(&mut self, filter: EventFilter) -> EventSubscription {
+        self.event_loop.subscribe(filter)
+    }
+
     /// Get reference to event loop (for queries)
     pub fn event_loop(&self) -> &DomainEventLoop {
         &self.event_loop
@@ -81,6 +116,11 @@ impl DomainLoop {
         self.inbound.len()
     }
 
+    /// Is the command queue saturated? (`submit` would fail)
+    pub fn is_full(&self) -> bool {
+        self.inbound.is_full()
+    }
+
     /// Get pending event count
     pub fn pending_events(&self) -> usize {
         self.outbound.len()
@@ -96,6 +136,7 @@ impl Default for DomainLoop {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::{CodedError, ErrorCode};
 
     #[test]
     fn test_submit_and_poll() {
@@ -131,6 +172,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_requests_run_in_turn() {
+        let mut loop_ = DomainLoop::new(10, 100);
+        loop_
+            .submit(DomainCommand::CreateLobby {
+                lobby_name: "Test Lobby".to_string(),
+                host_name: "Alice".to_string(),
+                lobby_id: None,
+            })
+            .unwrap();
+        loop_.poll();
+        let Some(DomainEvent::LobbyCreated { lobby }) = loop_.drain_events().pop() else {
+            panic!("Expected LobbyCreated");
+        };
+
+        // A guest that never joined can't start the run; the host can
+        let start = DomainCommand::StartNextRun {
+            lobby_id: lobby.id(),
+        };
+        loop_.submit_request(start.clone(), None).unwrap();
+        loop_.submit_request(start, Some(lobby.host_id())).unwrap();
+        assert_eq!(loop_.poll(), 2);
+
+        let errors: Vec<_> = loop_
+            .drain_events()
+            .into_iter()
+            .map(|event| match event {
+                DomainEvent::CommandFailed { error, .. } => error.code(),
+                e => panic!("Expected CommandFailed, got {:?}", e),
+            })
+            .collect();
+        assert_eq!(
+            errors,
+            vec![ErrorCode::PermissionDenied, ErrorCode::EmptyQueue]
+        );
+    }
+
     #[test]
     fn test_batch_processing() {
         let mut loop_ = DomainLoop::new(3, 100); // batch_size = 3
@@ -227,6 +305,10 @@ mod tests {
         });
 
         assert!(result.is_err());
+        assert!(loop_.is_full());
+
+        loop_.poll();
+        assert!(!loop_.is_full());
     }
 
     #[test]
@@ -1,5 +1,6 @@
 mod command_queue;
 mod domain_loop;
 
+pub(crate) use command_queue::CommandOrigin;
 pub use command_queue::{CommandQueue, QueueError};
 pub use domain_loop::DomainLoop;
@@ -0,0 +1,246 @@
+use crate::application::{DomainEvent, EventKind};
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use uuid::Uuid;
+
+/// Events a subscription keeps before dropping the oldest
+pub const SUBSCRIPTION_CAPACITY: usize = 1024;
+
+/// Which events a subscription receives
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventFilter {
+    /// `None` for every kind
+    kinds: Option<HashSet<EventKind>>,
+    lobby_id: Option<Uuid>,
+}
+
+impl EventFilter {
+    /// Every event of every lobby
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Only events of the given kinds
+    pub fn kinds(kinds: impl IntoIterator<Item = EventKind>) -> Self {
+        Self {
+            kinds: Some(kinds.into_iter().collect()),
+            lobby_id: None,
+        }
+    }
+
+    /// Only events of one lobby, including its failed commands
+    pub fn with_lobby(mut self, lobby_id: Uuid) -> Self {
+        self.lobby_id = Some(lobby_id);
+        self
+    }
+
+    pub fn matches(&self, event: &DomainEvent) -> bool {
+        if let Some(kinds) = &self.kinds
+            && !kinds.contains(&event.kind())
+        {
+            return false;
+        }
+        let Some(lobby_id) = self.lobby_id else {
+            return true;
+        };
+        let event_lobby = match event {
+            DomainEvent::CommandFailed { command, .. } => command.lobby_id(),
+            event => event.lobby_id(),
+        };
+        event_lobby == Some(lobby_id)
+    }
+}
+
+#[derive(Debug)]
+struct Inbox {
+    filter: EventFilter,
+    events: VecDeque<DomainEvent>,
+    missed: usize,
+}
+
+/// One listener's view of the events a `DomainEventLoop` emits
+///
+/// Events queue up until taken; dropping the subscription unsubscribes.
+#[derive(Debug)]
+pub struct EventSubscription {
+    inbox: Arc<Mutex<Inbox>>,
+}
+
+impl EventSubscription {
+    /// Take every event received since the last call
+    pub fn drain(&self) -> Vec<DomainEvent> {
+        self.lock().events.drain(..).collect()
+    }
+
+    /// Take the oldest event received
+    pub fn next_event(&self) -> Option<DomainEvent> {
+        self.lock().events.pop_front()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().events.is_empty()
+    }
+
+    /// Events dropped because the subscription wasn't drained in time
+    pub fn missed(&self) -> usize {
+        self.lock().missed
+    }
+
+    pub fn filter(&self) -> EventFilter {
+        self.lock().filter.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inbox> {
+        self.inbox.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The subscriptions of one event loop
+///
+/// A cloned event loop starts without subscribers, so events of a copy
+/// (e.g. a dry run) never reach the original's listeners.
+#[derive(Debug, Default)]
+pub(crate) struct Subscribers {
+    inboxes: Vec<Weak<Mutex<Inbox>>>,
+}
+
+impl Clone for Subscribers {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl Subscribers {
+    pub(crate) fn subscribe(&mut self, filter: EventFilter) -> EventSubscription {
+        let inbox = Arc::new(Mutex::new(Inbox {
+            filter,
+            events: VecDeque::new(),
+            missed: 0,
+        }));
+        self.inboxes.push(Arc::downgrade(&inbox));
+        EventSubscription { inbox }
+    }
+
+    /// Hand `event` to every matching subscription, forgetting dropped ones
+    pub(crate) fn publish(&mut self, event: &DomainEvent) {
+        self.inboxes.retain(|inbox| {
+            let Some(inbox) = inbox.upgrade() else {
+                return false;
+            };
+            let mut inbox = inbox.lock().unwrap_or_else(|e| e.into_inner());
+            if inbox.filter.matches(event) {
+                if inbox.events.len() >= SUBSCRIPTION_CAPACITY {
+                    inbox.events.pop_front();
+                    inbox.missed += 1;
+                }
+                inbox.events.push_back(event.clone());
+            }
+            true
+        });
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.inboxes
+            .iter()
+            .filter(|inbox| inbox.strong_count() > 0)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{DomainCommand, DomainEventLoop};
+    use crate::domain::ActivityConfig;
+
+    fn create_lobby(el: &mut DomainEventLoop) -> Uuid {
+        match el.handle_command(DomainCommand::CreateLobby {
+            lobby_id: None,
+            lobby_name: "Test Lobby".to_string(),
+            host_name: "Alice".to_string(),
+        }) {
+            DomainEvent::LobbyCreated { lobby } => lobby.id(),
+            e => panic!("Expected LobbyCreated, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_subscribers_see_their_own_view() {
+        let mut el = DomainEventLoop::new();
+        let everything = el.subscribe(EventFilter::all());
+        let runs = el.subscribe(EventFilter::kinds([
+            EventKind::RunStarted,
+            EventKind::RunEnded,
+        ]));
+
+        let lobby_id = create_lobby(&mut el);
+        el.handle_command(DomainCommand::QueueActivity {
+            lobby_id,
+            config: ActivityConfig::new(
+                "quiz".to_string(),
+                "Trivia Quiz".to_string(),
+                serde_json::json!({}),
+            ),
+        });
+        el.handle_command(DomainCommand::StartNextRun { lobby_id });
+
+        let kinds: Vec<_> = everything.drain().iter().map(DomainEvent::kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                EventKind::LobbyCreated,
+                EventKind::ActivityQueued,
+                EventKind::RunStarted
+            ]
+        );
+        assert!(everything.is_empty());
+
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs.next_event().unwrap().kind(), EventKind::RunStarted);
+    }
+
+    #[test]
+    fn test_lobby_filter_includes_failed_commands() {
+        let mut el = DomainEventLoop::new();
+        let lobby_id = create_lobby(&mut el);
+        let other_lobby = create_lobby(&mut el);
+        let failures =
+            el.subscribe(EventFilter::kinds([EventKind::CommandFailed]).with_lobby(lobby_id));
+
+        el.handle_command(DomainCommand::StartNextRun { lobby_id });
+        el.handle_command(DomainCommand::StartNextRun {
+            lobby_id: other_lobby,
+        });
+
+        let failed = failures.drain();
+        assert_eq!(failed.len(), 1);
+        assert!(matches!(
+            &failed[0],
+            DomainEvent::CommandFailed { command, .. } if command.lobby_id() == Some(lobby_id)
+        ));
+    }
+
+    #[test]
+    fn test_dropped_and_slow_subscriptions() {
+        let mut el = DomainEventLoop::new();
+        let slow = el.subscribe(EventFilter::all());
+        drop(el.subscribe(EventFilter::all()));
+        assert_eq!(el.subscriber_count(), 1);
+
+        for _ in 0..SUBSCRIPTION_CAPACITY + 2 {
+            create_lobby(&mut el);
+        }
+        assert_eq!(slow.len(), SUBSCRIPTION_CAPACITY);
+        assert_eq!(slow.missed(), 2);
+
+        // A copy of the loop doesn't publish to the original's listeners
+        let mut copy = el.clone();
+        slow.drain();
+        create_lobby(&mut copy);
+        assert!(slow.is_empty());
+    }
+}
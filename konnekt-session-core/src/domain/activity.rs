@@ -38,6 +38,11 @@ impl ActivityConfig {
             config,
         }
     }
+
+    /// How long answers may take, from the game config's `time_limit_ms`
+    pub fn time_limit_ms(&self) -> Option<u64> {
+        self.config.get("time_limit_ms")?.as_u64()
+    }
 }
 
 /// Result submitted by a participant for a run.
@@ -50,6 +55,9 @@ pub struct ActivityResult {
     pub data: serde_json::Value,
     pub score: Option<u32>,
     pub time_taken_ms: Option<u64>,
+    /// When it was submitted, on the host's clock (Unix ms)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submitted_at_ms: Option<u64>,
 }
 
 impl ActivityResult {
@@ -60,6 +68,7 @@ impl ActivityResult {
             data: serde_json::Value::Null,
             score: None,
             time_taken_ms: None,
+            submitted_at_ms: None,
         }
     }
 
@@ -77,6 +86,11 @@ impl ActivityResult {
         self.time_taken_ms = Some(time_ms);
         self
     }
+
+    pub fn with_submitted_at(mut self, at_ms: u64) -> Self {
+        self.submitted_at_ms = Some(at_ms);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -12,22 +12,32 @@ pub enum RunStatus {
     Cancelled,
 }
 
-#[derive(Debug, thiserror::Error, PartialEq)]
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Serialize, Deserialize)]
 pub enum ActivityRunError {
     #[error("Participant not in required submitters: {0}")]
     NotARequiredSubmitter(Uuid),
 
+    #[error("Spectators cannot submit results")]
+    SpectatorSubmission(Uuid),
+
     #[error("Participant already submitted: {0}")]
     DuplicateSubmission(Uuid),
 
     #[error("Run is not in progress")]
     NotInProgress,
+
+    #[error("Deadline passed")]
+    DeadlinePassed,
+
+    #[error("Deadline not reached")]
+    DeadlineNotReached,
 }
 
 /// Aggregate root for one game in progress.
 ///
 /// `required_submitters` is snapshotted at creation — never grows.
-/// Completes when all required submitters have submitted or been removed.
+/// Completes when all required submitters have submitted or been removed,
+/// or when the host expires it past its deadline.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActivityRun {
     id: ActivityRunId,
@@ -36,6 +46,9 @@ pub struct ActivityRun {
     required_submitters: HashSet<Uuid>,
     results: HashMap<Uuid, ActivityResult>,
     status: RunStatus,
+    /// When the host started the run, on its clock (Unix ms)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    started_at_ms: Option<u64>,
 }
 
 impl ActivityRun {
@@ -52,9 +65,15 @@ impl ActivityRun {
             required_submitters: active_participants,
             results: HashMap::new(),
             status: RunStatus::InProgress,
+            started_at_ms: None,
         }
     }
 
+    pub fn with_started_at(mut self, at_ms: Option<u64>) -> Self {
+        self.started_at_ms = at_ms;
+        self
+    }
+
     pub fn id(&self) -> ActivityRunId {
         self.id
     }
@@ -75,6 +94,23 @@ impl ActivityRun {
         &self.results
     }
 
+    /// When the host started the run, on its clock (Unix ms); `None` for
+    /// runs recorded before runs were stamped
+    pub fn started_at_ms(&self) -> Option<u64> {
+        self.started_at_ms
+    }
+
+    /// When answers are due (Unix ms): the start plus the config's
+    /// `time_limit_ms`; `None` for untimed or unstamped runs
+    pub fn deadline_ms(&self) -> Option<u64> {
+        Some(self.started_at_ms? + self.config.time_limit_ms()?)
+    }
+
+    /// Is the run still open although its deadline passed at `now_ms`?
+    pub fn is_overdue(&self, now_ms: u64) -> bool {
+        self.status == RunStatus::InProgress && self.deadline_ms().is_some_and(|due| now_ms > due)
+    }
+
     pub fn required_submitters(&self) -> &HashSet<Uuid> {
         &self.required_submitters
     }
@@ -84,11 +120,19 @@ impl ActivityRun {
     }
 
     /// Submit a result. Returns true if this submission completed the run.
+    ///
+    /// Results the host stamped after the deadline are rejected.
     pub fn submit_result(&mut self, result: ActivityResult) -> Result<bool, ActivityRunError> {
         if self.status != RunStatus::InProgress {
             return Err(ActivityRunError::NotInProgress);
         }
 
+        if let (Some(deadline), Some(submitted_at)) = (self.deadline_ms(), result.submitted_at_ms)
+            && submitted_at > deadline
+        {
+            return Err(ActivityRunError::DeadlinePassed);
+        }
+
         let participant_id = result.participant_id;
 
         if !self.required_submitters.contains(&participant_id) {
@@ -139,6 +183,26 @@ impl ActivityRun {
         Ok(())
     }
 
+    /// Complete an overdue run with the results that came in on time.
+    pub fn expire(&mut self, now_ms: u64) -> Result<(), ActivityRunError> {
+        if self.status != RunStatus::InProgress {
+            return Err(ActivityRunError::NotInProgress);
+        }
+        if !self.is_overdue(now_ms) {
+            return Err(ActivityRunError::DeadlineNotReached);
+        }
+        self.status = RunStatus::Completed;
+        Ok(())
+    }
+
+    /// Adopt the outcome of a run that ended elsewhere (P2P sync).
+    pub fn apply_outcome(&mut self, status: RunStatus, results: Vec<ActivityResult>) {
+        for result in results {
+            self.results.insert(result.participant_id, result);
+        }
+        self.status = status;
+    }
+
     fn all_submitted(&self) -> bool {
         self.required_submitters
             .iter()
@@ -209,6 +273,23 @@ mod tests {
         assert_eq!(run.status(), RunStatus::Cancelled);
     }
 
+    #[test]
+    fn test_apply_outcome_adopts_missing_results() {
+        let p1 = Uuid::new_v4();
+        let p2 = Uuid::new_v4();
+        let mut run = make_run(vec![p1, p2]);
+
+        let first = ActivityResult::new(Uuid::new_v4(), p1);
+        run.submit_result(first.clone()).unwrap();
+
+        run.apply_outcome(
+            RunStatus::Completed,
+            vec![first, ActivityResult::new(Uuid::new_v4(), p2)],
+        );
+        assert_eq!(run.status(), RunStatus::Completed);
+        assert_eq!(run.results().len(), 2);
+    }
+
     #[test]
     fn test_duplicate_submission_rejected() {
         let p1 = Uuid::new_v4();
@@ -225,6 +306,55 @@ mod tests {
         assert_eq!(err, ActivityRunError::DuplicateSubmission(p1));
     }
 
+    #[test]
+    fn test_results_after_the_deadline_rejected() {
+        let p1 = Uuid::new_v4();
+        let p2 = Uuid::new_v4();
+        let config = ActivityConfig::new(
+            "quiz".to_string(),
+            "Timed Quiz".to_string(),
+            serde_json::json!({ "time_limit_ms": 5_000 }),
+        );
+        let mut run = ActivityRun::new(Uuid::new_v4(), Uuid::new_v4(), config, [p1, p2].into())
+            .with_started_at(Some(1_000));
+        assert_eq!(run.deadline_ms(), Some(6_000));
+
+        run.submit_result(ActivityResult::new(run.id(), p1).with_submitted_at(6_000))
+            .unwrap();
+        let err = run
+            .submit_result(ActivityResult::new(run.id(), p2).with_submitted_at(6_001))
+            .unwrap_err();
+        assert_eq!(err, ActivityRunError::DeadlinePassed);
+        assert_eq!(make_run(vec![p1]).deadline_ms(), None);
+    }
+
+    #[test]
+    fn test_expire_completes_an_overdue_run() {
+        let p1 = Uuid::new_v4();
+        let p2 = Uuid::new_v4();
+        let config = ActivityConfig::new(
+            "quiz".to_string(),
+            "Timed Quiz".to_string(),
+            serde_json::json!({ "time_limit_ms": 5_000 }),
+        );
+        let mut run = ActivityRun::new(Uuid::new_v4(), Uuid::new_v4(), config, [p1, p2].into())
+            .with_started_at(Some(1_000));
+        run.submit_result(ActivityResult::new(run.id(), p1))
+            .unwrap();
+
+        assert!(!run.is_overdue(6_000));
+        assert_eq!(run.expire(6_000), Err(ActivityRunError::DeadlineNotReached));
+
+        assert!(run.is_overdue(6_001));
+        run.expire(6_001).unwrap();
+        assert_eq!(run.status(), RunStatus::Completed);
+        assert_eq!(run.results().len(), 1);
+        assert!(!run.is_overdue(7_000));
+
+        // Untimed runs wait as long as it takes
+        assert!(!make_run(vec![p1]).is_overdue(u64::MAX));
+    }
+
     #[test]
     fn test_non_submitter_rejected() {
         let p1 = Uuid::new_v4();
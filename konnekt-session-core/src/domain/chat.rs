@@ -0,0 +1,87 @@
+use crate::domain::Timestamp;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Longest chat message a participant may post (in characters)
+pub const MAX_CHAT_MESSAGE_LEN: usize = 500;
+
+/// How many chat messages a lobby keeps (older ones are dropped)
+pub const CHAT_HISTORY_LIMIT: usize = 200;
+
+/// Domain value: A chat message posted to a lobby
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    id: Uuid,
+    author_id: Uuid,
+    text: String,
+    sent_at: Timestamp,
+}
+
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Serialize, Deserialize)]
+pub enum ChatError {
+    #[error("Chat message cannot be empty")]
+    EmptyMessage,
+
+    #[error("Chat message must be at most {MAX_CHAT_MESSAGE_LEN} characters")]
+    MessageTooLong,
+}
+
+impl ChatMessage {
+    pub fn new(author_id: Uuid, text: String) -> Result<Self, ChatError> {
+        Self::new_at(author_id, text, Timestamp::now())
+    }
+
+    /// Like [`ChatMessage::new`], sent at a session-clock time
+    pub fn new_at(author_id: Uuid, text: String, sent_at: Timestamp) -> Result<Self, ChatError> {
+        let text = text.trim().to_string();
+        if text.is_empty() {
+            return Err(ChatError::EmptyMessage);
+        }
+        if text.chars().count() > MAX_CHAT_MESSAGE_LEN {
+            return Err(ChatError::MessageTooLong);
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            author_id,
+            text,
+            sent_at,
+        })
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+    pub fn author_id(&self) -> Uuid {
+        self.author_id
+    }
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+    pub fn sent_at(&self) -> Timestamp {
+        self.sent_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_is_trimmed_and_validated() {
+        let author = Uuid::new_v4();
+
+        let message = ChatMessage::new(author, "  hello  ".to_string()).unwrap();
+        assert_eq!(message.text(), "hello");
+        assert_eq!(message.author_id(), author);
+
+        assert_eq!(
+            ChatMessage::new(author, "   ".to_string()),
+            Err(ChatError::EmptyMessage)
+        );
+        assert_eq!(
+            ChatMessage::new(author, "x".repeat(MAX_CHAT_MESSAGE_LEN + 1)),
+            Err(ChatError::MessageTooLong)
+        );
+    }
+}
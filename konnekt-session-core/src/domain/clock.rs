@@ -0,0 +1,168 @@
+use instant::{Duration, Instant, SystemTime};
+use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Source of time for timestamps, timeouts, heartbeats, checksums and rate
+/// limits
+///
+/// Components take a [`SharedClock`] so tests and simulations can drive them
+/// with a [`TestClock`] instead of real time.
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// Monotonic time, for measuring durations
+    fn now(&self) -> Instant;
+
+    /// Wall clock time in ms since the Unix epoch, for times other peers read
+    /// (countdowns, deadlines, result timestamps)
+    fn unix_millis(&self) -> u64;
+}
+
+/// A clock shared by the components of one peer
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Process-wide origin of [`Timestamp`](crate::domain::Timestamp)s
+pub(crate) fn anchor() -> Instant {
+    static ANCHOR: OnceLock<Instant> = OnceLock::new();
+    *ANCHOR.get_or_init(Instant::now)
+}
+
+/// Real time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl SystemClock {
+    pub fn shared() -> SharedClock {
+        Arc::new(Self)
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn unix_millis(&self) -> u64 {
+        // Read every time, so the wall clock stays right after a suspend
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|since| since.as_millis() as u64)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug)]
+struct TestTime {
+    now: Instant,
+    unix_millis: u64,
+}
+
+/// Time that stands still until `advance` is called (tests, deterministic
+/// simulations)
+///
+/// Clones share their time, so one clock can be injected into several peers
+/// and advanced once for all of them.
+#[derive(Debug, Clone)]
+pub struct TestClock {
+    time: Arc<Mutex<TestTime>>,
+}
+
+impl TestClock {
+    /// Starts at the current wall clock time
+    pub fn new() -> Self {
+        Self::starting_at(SystemClock.unix_millis())
+    }
+
+    /// Starts at `unix_millis` ms since the Unix epoch
+    pub fn starting_at(unix_millis: u64) -> Self {
+        Self {
+            time: Arc::new(Mutex::new(TestTime {
+                // Never before the timestamp origin, or timestamps would stick at 0
+                now: anchor().max(Instant::now()),
+                unix_millis,
+            })),
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        let mut time = self.time();
+        time.now += by;
+        time.unix_millis += by.as_millis() as u64;
+    }
+
+    /// This clock (and everything sharing its time) as a [`SharedClock`]
+    pub fn shared(&self) -> SharedClock {
+        Arc::new(self.clone())
+    }
+
+    fn time(&self) -> std::sync::MutexGuard<'_, TestTime> {
+        self.time.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for TestClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> Instant {
+        self.time().now
+    }
+
+    fn unix_millis(&self) -> u64 {
+        self.time().unix_millis
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Timestamp;
+
+    #[test]
+    fn test_test_clock_only_moves_on_advance() {
+        let clock = TestClock::starting_at(1_000);
+        let start = clock.now();
+
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(clock.now(), start);
+        assert_eq!(clock.unix_millis(), 1_000);
+
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(clock.now().duration_since(start), Duration::from_secs(30));
+        assert_eq!(clock.unix_millis(), 31_000);
+    }
+
+    #[test]
+    fn test_shared_test_clocks_advance_together() {
+        let clock = TestClock::starting_at(0);
+        let shared = clock.shared();
+
+        clock.advance(Duration::from_millis(250));
+        assert_eq!(shared.unix_millis(), 250);
+        assert_eq!(shared.now(), clock.now());
+    }
+
+    #[test]
+    fn test_timestamps_follow_the_test_clock() {
+        let clock = TestClock::new();
+        let start = Timestamp::now_on(&clock);
+
+        clock.advance(Duration::from_millis(1500));
+        assert_eq!(
+            Timestamp::now_on(&clock).as_millis() - start.as_millis(),
+            1500
+        );
+    }
+
+    #[test]
+    fn test_system_clock_reads_the_wall_clock() {
+        let clock = SystemClock;
+        let before = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        assert!(clock.unix_millis() >= before);
+    }
+}
@@ -1,8 +1,11 @@
 use crate::domain::{
-    ActivityConfig, ActivityId, ActivityRunId, Participant, ParticipantError, ParticipationMode,
+    ActivityConfig, ActivityId, ActivityRunId, AutoDelegation, CHAT_HISTORY_LIMIT, ChatMessage,
+    LobbyAction, LobbySettings, Participant, ParticipantAvatar, ParticipantError,
+    ParticipationMode, PermissionPolicy, permissions::LobbyPolicy,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -14,9 +17,26 @@ pub struct Lobby {
     activity_queue: Vec<ActivityConfig>,
     /// Some while a run is InProgress, None when idle.
     active_run_id: Option<ActivityRunId>,
+    /// Most recent chat messages, oldest first.
+    #[serde(default)]
+    chat: Vec<ChatMessage>,
+    #[serde(default)]
+    settings: LobbySettings,
+    /// Guests who may moderate and are preferred as the next host
+    #[serde(default)]
+    co_hosts: HashSet<Uuid>,
+    /// Participants who said they are ready for the next activity
+    #[serde(default)]
+    ready: HashSet<Uuid>,
+    /// Wall clock time (ms since the Unix epoch) the countdown to the next
+    /// activity ends at
+    #[serde(default)]
+    countdown_ends_at: Option<u64>,
+    #[serde(skip)]
+    policy: LobbyPolicy,
 }
 
-#[derive(Debug, thiserror::Error, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Serialize, Deserialize)]
 pub enum LobbyError {
     #[error("Lobby must have exactly one host")]
     NoHost,
@@ -56,6 +76,12 @@ pub enum LobbyError {
 
     #[error("Activity queue is empty")]
     EmptyQueue,
+
+    #[error("Lobby is full ({0} participants)")]
+    LobbyFull(usize),
+
+    #[error("Lobby is locked")]
+    LobbyLocked,
 }
 
 impl Lobby {
@@ -78,9 +104,42 @@ impl Lobby {
             host_id,
             activity_queue: Vec::new(),
             active_run_id: None,
+            chat: Vec::new(),
+            settings: LobbySettings::default(),
+            co_hosts: HashSet::new(),
+            ready: HashSet::new(),
+            countdown_ends_at: None,
+            policy: LobbyPolicy::default(),
         })
     }
 
+    /// Use `policy` instead of the [`DefaultPolicy`](crate::domain::DefaultPolicy)
+    pub fn with_permission_policy(mut self, policy: Arc<dyn PermissionPolicy>) -> Self {
+        self.set_permission_policy(policy);
+        self
+    }
+
+    pub fn set_permission_policy(&mut self, policy: Arc<dyn PermissionPolicy>) {
+        self.policy = LobbyPolicy::new(policy);
+    }
+
+    pub fn permission_policy(&self) -> &dyn PermissionPolicy {
+        self.policy.get()
+    }
+
+    /// Whether the policy lets `actor` do `action`
+    pub fn allows(&self, actor: Uuid, action: LobbyAction) -> bool {
+        self.policy.get().allows(self, actor, action)
+    }
+
+    fn check(&self, actor: Uuid, action: LobbyAction) -> Result<(), LobbyError> {
+        if self.allows(actor, action) {
+            Ok(())
+        } else {
+            Err(LobbyError::PermissionDenied)
+        }
+    }
+
     // ===== Getters =====
 
     pub fn id(&self) -> Uuid {
@@ -110,6 +169,19 @@ impl Lobby {
     pub fn has_active_run(&self) -> bool {
         self.active_run_id.is_some()
     }
+    pub fn settings(&self) -> &LobbySettings {
+        &self.settings
+    }
+    pub fn co_hosts(&self) -> &HashSet<Uuid> {
+        &self.co_hosts
+    }
+    pub fn is_co_host(&self, participant_id: Uuid) -> bool {
+        self.co_hosts.contains(&participant_id)
+    }
+    /// The host or a co-host
+    pub fn can_moderate(&self, participant_id: Uuid) -> bool {
+        participant_id == self.host_id || self.is_co_host(participant_id)
+    }
 
     // ===== Participant Management =====
 
@@ -126,6 +198,17 @@ impl Lobby {
         Ok(())
     }
 
+    /// Whether the settings let one more guest join
+    pub fn check_can_join(&self) -> Result<(), LobbyError> {
+        if self.settings.locked {
+            return Err(LobbyError::LobbyLocked);
+        }
+        match self.settings.max_participants {
+            Some(max) if self.participants.len() >= max => Err(LobbyError::LobbyFull(max)),
+            _ => Ok(()),
+        }
+    }
+
     pub fn remove_participant(&mut self, participant_id: Uuid) -> Result<bool, LobbyError> {
         if participant_id == self.host_id {
             return Err(LobbyError::CannotRemoveHost);
@@ -138,17 +221,18 @@ impl Lobby {
         self.participants
             .remove(&participant_id)
             .ok_or(LobbyError::ParticipantNotFound(participant_id))?;
+        self.co_hosts.remove(&participant_id);
+        self.ready.remove(&participant_id);
         Ok(was_host)
     }
 
+    /// Remove a guest. By default the host may kick anyone and co-hosts may
+    /// kick plain guests.
     pub fn kick_guest(&mut self, guest_id: Uuid, host_id: Uuid) -> Result<Participant, LobbyError> {
-        let requester = self
-            .participants
-            .get(&host_id)
-            .ok_or(LobbyError::ParticipantNotFound(host_id))?;
-        if !requester.is_host() {
-            return Err(LobbyError::PermissionDenied);
+        if !self.participants.contains_key(&host_id) {
+            return Err(LobbyError::ParticipantNotFound(host_id));
         }
+        self.check(host_id, LobbyAction::Kick { target: guest_id })?;
         if guest_id == host_id {
             return Err(LobbyError::CannotKickHost);
         }
@@ -160,6 +244,8 @@ impl Lobby {
             self.participants.insert(guest_id, kicked.clone());
             return Err(LobbyError::CannotKickHost);
         }
+        self.co_hosts.remove(&guest_id);
+        self.ready.remove(&guest_id);
         Ok(kicked)
     }
 
@@ -184,18 +270,13 @@ impl Lobby {
             old_host.demote_to_guest();
         }
         self.host_id = new_host_id;
+        self.co_hosts.remove(&new_host_id);
         Ok(())
     }
 
     pub fn auto_delegate_host(&mut self) -> Result<Uuid, LobbyError> {
-        let oldest_guest = self
-            .participants
-            .values()
-            .filter(|p| !p.is_host() && p.id() != self.host_id)
-            .min_by_key(|p| p.joined_at());
-        match oldest_guest {
-            Some(guest) => {
-                let new_host_id = guest.id();
+        match self.next_host_candidate() {
+            Some(new_host_id) => {
                 self.delegate_host(new_host_id)?;
                 Ok(new_host_id)
             }
@@ -203,6 +284,67 @@ impl Lobby {
         }
     }
 
+    /// The guest that would become host on automatic delegation
+    /// (per [`AutoDelegation`]; `None` when it is off)
+    pub fn next_host_candidate(&self) -> Option<Uuid> {
+        self.next_host_candidate_excluding(&HashSet::new())
+    }
+
+    /// Like [`Self::next_host_candidate`], passing over `excluded` guests
+    /// (e.g. those who declined to take over)
+    pub fn next_host_candidate_excluding(&self, excluded: &HashSet<Uuid>) -> Option<Uuid> {
+        let oldest = |co_hosts_only: bool| {
+            self.participants
+                .values()
+                .filter(|p| !p.is_host() && p.id() != self.host_id)
+                .filter(|p| !excluded.contains(&p.id()))
+                .filter(|p| !co_hosts_only || self.is_co_host(p.id()))
+                // Ties broken by id, so every peer elects the same guest
+                .min_by_key(|p| (p.joined_at(), p.id()))
+                .map(|p| p.id())
+        };
+        match self.settings.auto_delegation {
+            AutoDelegation::OldestGuest => oldest(false),
+            AutoDelegation::CoHostsFirst => oldest(true).or_else(|| oldest(false)),
+            AutoDelegation::Off => None,
+        }
+    }
+
+    // ===== Host Controls =====
+
+    /// Make a guest a co-host or demote them again (HOST ONLY by default).
+    /// Returns whether anything changed.
+    pub fn set_co_host(
+        &mut self,
+        participant_id: Uuid,
+        host_id: Uuid,
+        co_host: bool,
+    ) -> Result<bool, LobbyError> {
+        self.check(host_id, LobbyAction::SetCoHost)?;
+        if !self.participants.contains_key(&participant_id) {
+            return Err(LobbyError::ParticipantNotFound(participant_id));
+        }
+        if participant_id == self.host_id {
+            return Err(LobbyError::CannotDelegateToNonGuest);
+        }
+        Ok(if co_host {
+            self.co_hosts.insert(participant_id)
+        } else {
+            self.co_hosts.remove(&participant_id)
+        })
+    }
+
+    /// Replace the lobby settings (HOST ONLY by default)
+    pub fn update_settings(
+        &mut self,
+        settings: LobbySettings,
+        host_id: Uuid,
+    ) -> Result<(), LobbyError> {
+        self.check(host_id, LobbyAction::ChangeSettings)?;
+        self.settings = settings;
+        Ok(())
+    }
+
     // ===== Participation Mode =====
 
     pub fn toggle_participation_mode(
@@ -210,15 +352,15 @@ impl Lobby {
         participant_id: Uuid,
         requester_id: Uuid,
     ) -> Result<ParticipationMode, LobbyError> {
-        let requester = self
-            .participants
-            .get(&requester_id)
-            .ok_or(LobbyError::ParticipantNotFound(requester_id))?;
-        let is_self = participant_id == requester_id;
-        let is_host = requester.is_host();
-        if !is_self && !is_host {
-            return Err(LobbyError::PermissionDenied);
+        if !self.participants.contains_key(&requester_id) {
+            return Err(LobbyError::ParticipantNotFound(requester_id));
         }
+        self.check(
+            requester_id,
+            LobbyAction::ToggleMode {
+                target: participant_id,
+            },
+        )?;
         let activity_in_progress = self.active_run_id.is_some();
         let participant = self
             .participants
@@ -235,13 +377,15 @@ impl Lobby {
         host_id: Uuid,
         mode: ParticipationMode,
     ) -> Result<(), LobbyError> {
-        let requester = self
-            .participants
-            .get(&host_id)
-            .ok_or(LobbyError::ParticipantNotFound(host_id))?;
-        if !requester.is_host() {
-            return Err(LobbyError::PermissionDenied);
+        if !self.participants.contains_key(&host_id) {
+            return Err(LobbyError::ParticipantNotFound(host_id));
         }
+        self.check(
+            host_id,
+            LobbyAction::ForceMode {
+                target: participant_id,
+            },
+        )?;
         let participant = self
             .participants
             .get_mut(&participant_id)
@@ -286,6 +430,26 @@ impl Lobby {
         Ok(())
     }
 
+    /// Move a queued activity to `to_index` (host or co-host by default), clamped to the
+    /// end of the queue. Returns where it ended up.
+    pub fn move_queued_activity(
+        &mut self,
+        activity_id: ActivityId,
+        to_index: usize,
+        requester_id: Uuid,
+    ) -> Result<usize, LobbyError> {
+        self.check(requester_id, LobbyAction::PlanQueue)?;
+        let from = self
+            .activity_queue
+            .iter()
+            .position(|a| a.id == activity_id)
+            .ok_or(LobbyError::ActivityNotFound(activity_id))?;
+        let config = self.activity_queue.remove(from);
+        let to_index = to_index.min(self.activity_queue.len());
+        self.activity_queue.insert(to_index, config);
+        Ok(to_index)
+    }
+
     /// Dequeue the next activity config. Returns it so caller can create an ActivityRun.
     pub fn dequeue_next_activity(&mut self) -> Result<ActivityConfig, LobbyError> {
         if self.active_run_id.is_some() {
@@ -302,12 +466,106 @@ impl Lobby {
             return Err(LobbyError::RunAlreadyInProgress);
         }
         self.active_run_id = Some(run_id);
+        self.ready.clear();
+        self.countdown_ends_at = None;
         Ok(())
     }
 
     pub fn clear_active_run(&mut self) {
         self.active_run_id = None;
     }
+
+    /// Change a participant's avatar. By default the host and co-hosts may
+    /// also reset (but not pick) the avatar of others.
+    pub fn set_avatar(
+        &mut self,
+        participant_id: Uuid,
+        requester_id: Uuid,
+        avatar: Option<ParticipantAvatar>,
+    ) -> Result<(), LobbyError> {
+        self.check(
+            requester_id,
+            LobbyAction::SetAvatar {
+                target: participant_id,
+                reset: avatar.is_none(),
+            },
+        )?;
+        self.participants
+            .get_mut(&participant_id)
+            .ok_or(LobbyError::ParticipantNotFound(participant_id))?
+            .set_avatar(avatar)
+            .map_err(LobbyError::from)
+    }
+
+    // ===== Ready Check =====
+
+    pub fn ready(&self) -> &HashSet<Uuid> {
+        &self.ready
+    }
+
+    pub fn is_ready(&self, participant_id: Uuid) -> bool {
+        self.ready.contains(&participant_id)
+    }
+
+    /// Whether every active participant is ready (and there is one)
+    pub fn all_ready(&self) -> bool {
+        let active = self.active_participant_ids();
+        !active.is_empty() && active.iter().all(|id| self.ready.contains(id))
+    }
+
+    /// Mark a participant (not) ready. Returns whether anything changed.
+    pub fn set_ready(&mut self, participant_id: Uuid, ready: bool) -> Result<bool, LobbyError> {
+        if !self.participants.contains_key(&participant_id) {
+            return Err(LobbyError::ParticipantNotFound(participant_id));
+        }
+        Ok(if ready {
+            self.ready.insert(participant_id)
+        } else {
+            self.ready.remove(&participant_id)
+        })
+    }
+
+    pub fn countdown_ends_at(&self) -> Option<u64> {
+        self.countdown_ends_at
+    }
+
+    /// Count down to the next queued activity (HOST or CO-HOST by default).
+    /// The countdown is cleared when a run starts.
+    pub fn start_countdown(&mut self, requester_id: Uuid, ends_at: u64) -> Result<(), LobbyError> {
+        self.check(requester_id, LobbyAction::StartRun)?;
+        if self.active_run_id.is_some() {
+            return Err(LobbyError::RunAlreadyInProgress);
+        }
+        if self.activity_queue.is_empty() {
+            return Err(LobbyError::EmptyQueue);
+        }
+        self.countdown_ends_at = Some(ends_at);
+        Ok(())
+    }
+
+    // ===== Chat =====
+
+    pub fn chat_messages(&self) -> &[ChatMessage] {
+        &self.chat
+    }
+
+    /// Append a chat message, dropping the oldest beyond [`CHAT_HISTORY_LIMIT`].
+    /// Messages already in the history are ignored.
+    pub fn post_chat_message(&mut self, message: ChatMessage) -> Result<(), LobbyError> {
+        if !self.participants.contains_key(&message.author_id()) {
+            return Err(LobbyError::ParticipantNotFound(message.author_id()));
+        }
+        self.check(message.author_id(), LobbyAction::Chat)?;
+        if self.chat.iter().any(|m| m.id() == message.id()) {
+            return Ok(());
+        }
+        self.chat.push(message);
+        if self.chat.len() > CHAT_HISTORY_LIMIT {
+            let excess = self.chat.len() - CHAT_HISTORY_LIMIT;
+            self.chat.drain(..excess);
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -386,14 +644,133 @@ mod tests {
             Timestamp::from_millis(200),
         )
         .unwrap();
+        let carol_id = carol.id();
 
         lobby.add_guest(bob).unwrap();
         lobby.add_guest(carol).unwrap();
 
+        assert_eq!(lobby.next_host_candidate(), Some(bob_id));
+        assert_eq!(
+            lobby.next_host_candidate_excluding(&HashSet::from([bob_id])),
+            Some(carol_id)
+        );
+
         let new_host_id = lobby.auto_delegate_host().unwrap();
         assert_eq!(new_host_id, bob_id);
     }
 
+    #[test]
+    fn test_next_host_candidate_empty_lobby() {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let lobby = Lobby::new("Test".to_string(), host).unwrap();
+
+        assert_eq!(lobby.next_host_candidate(), None);
+    }
+
+    #[test]
+    fn test_auto_delegation_policy() {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let host_id = host.id();
+        let mut lobby = Lobby::new("Test".to_string(), host).unwrap();
+
+        let bob = Participant::with_timestamp(
+            "Bob".to_string(),
+            LobbyRole::Guest,
+            Timestamp::from_millis(100),
+        )
+        .unwrap();
+        let bob_id = bob.id();
+        let carol = Participant::with_timestamp(
+            "Carol".to_string(),
+            LobbyRole::Guest,
+            Timestamp::from_millis(200),
+        )
+        .unwrap();
+        let carol_id = carol.id();
+        lobby.add_guest(bob).unwrap();
+        lobby.add_guest(carol).unwrap();
+        assert!(lobby.set_co_host(carol_id, host_id, true).unwrap());
+
+        let mut settings = *lobby.settings();
+        settings.auto_delegation = AutoDelegation::CoHostsFirst;
+        lobby.update_settings(settings, host_id).unwrap();
+        assert_eq!(lobby.next_host_candidate(), Some(carol_id));
+
+        settings.auto_delegation = AutoDelegation::Off;
+        lobby.update_settings(settings, host_id).unwrap();
+        assert_eq!(lobby.next_host_candidate(), None);
+
+        // Only the host changes settings
+        assert_eq!(
+            lobby.update_settings(LobbySettings::default(), bob_id),
+            Err(LobbyError::PermissionDenied)
+        );
+    }
+
+    #[test]
+    fn test_co_hosts_moderate_plain_guests() {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let host_id = host.id();
+        let mut lobby = Lobby::new("Test".to_string(), host).unwrap();
+        let bob = Participant::new_guest("Bob".to_string()).unwrap();
+        let bob_id = bob.id();
+        let carol = Participant::new_guest("Carol".to_string()).unwrap();
+        let carol_id = carol.id();
+        let dave = Participant::new_guest("Dave".to_string()).unwrap();
+        let dave_id = dave.id();
+        lobby.add_guest(bob).unwrap();
+        lobby.add_guest(carol).unwrap();
+        lobby.add_guest(dave).unwrap();
+
+        assert_eq!(
+            lobby.set_co_host(carol_id, bob_id, true),
+            Err(LobbyError::PermissionDenied)
+        );
+        lobby.set_co_host(bob_id, host_id, true).unwrap();
+        lobby.set_co_host(carol_id, host_id, true).unwrap();
+
+        lobby.toggle_participation_mode(dave_id, bob_id).unwrap();
+        assert_eq!(
+            lobby.kick_guest(carol_id, bob_id),
+            Err(LobbyError::PermissionDenied)
+        );
+        lobby.kick_guest(dave_id, bob_id).unwrap();
+        assert_eq!(
+            lobby.kick_guest(host_id, bob_id),
+            Err(LobbyError::CannotKickHost)
+        );
+
+        // Promoting a co-host to host drops the co-host flag
+        lobby.delegate_host(bob_id).unwrap();
+        assert!(!lobby.is_co_host(bob_id));
+        assert!(lobby.is_co_host(carol_id));
+    }
+
+    #[test]
+    fn test_lock_and_capacity_turn_guests_away() {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let host_id = host.id();
+        let mut lobby = Lobby::new("Test".to_string(), host).unwrap();
+        assert_eq!(lobby.check_can_join(), Ok(()));
+
+        let settings = LobbySettings {
+            max_participants: Some(2),
+            ..LobbySettings::default()
+        };
+        lobby.update_settings(settings, host_id).unwrap();
+        lobby
+            .add_guest(Participant::new_guest("Bob".to_string()).unwrap())
+            .unwrap();
+        assert_eq!(lobby.check_can_join(), Err(LobbyError::LobbyFull(2)));
+
+        let settings = LobbySettings {
+            locked: true,
+            ..LobbySettings::default()
+        };
+        lobby.update_settings(settings, host_id).unwrap();
+        assert_eq!(lobby.check_can_join(), Err(LobbyError::LobbyLocked));
+    }
+
     #[test]
     fn test_active_participant_ids_snapshot() {
         let host = Participant::new_host("Alice".to_string()).unwrap();
@@ -439,6 +816,48 @@ mod tests {
         assert!(lobby.activity_queue().is_empty());
     }
 
+    #[test]
+    fn test_move_queued_activity() {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let host_id = host.id();
+        let mut lobby = Lobby::new("Test".to_string(), host).unwrap();
+        let guest = Participant::new_guest("Bob".to_string()).unwrap();
+        let guest_id = guest.id();
+        lobby.add_guest(guest).unwrap();
+        let ids: Vec<_> = ["Q1", "Q2", "Q3"]
+            .into_iter()
+            .map(|name| {
+                let config = ActivityConfig::new(
+                    "quiz".to_string(),
+                    name.to_string(),
+                    serde_json::json!({}),
+                );
+                let id = config.id;
+                lobby.queue_activity(config).unwrap();
+                id
+            })
+            .collect();
+        let order =
+            |lobby: &Lobby| -> Vec<_> { lobby.activity_queue().iter().map(|a| a.id).collect() };
+
+        assert_eq!(lobby.move_queued_activity(ids[2], 0, host_id), Ok(0));
+        assert_eq!(order(&lobby), vec![ids[2], ids[0], ids[1]]);
+
+        // Past the end means last
+        assert_eq!(lobby.move_queued_activity(ids[2], 10, host_id), Ok(2));
+        assert_eq!(order(&lobby), ids);
+
+        assert_eq!(
+            lobby.move_queued_activity(ids[0], 1, guest_id),
+            Err(LobbyError::PermissionDenied)
+        );
+        let unknown = Uuid::new_v4();
+        assert_eq!(
+            lobby.move_queued_activity(unknown, 0, host_id),
+            Err(LobbyError::ActivityNotFound(unknown))
+        );
+    }
+
     #[test]
     fn test_cannot_dequeue_during_active_run() {
         let host = Participant::new_host("Alice".to_string()).unwrap();
@@ -463,4 +882,99 @@ mod tests {
         lobby.clear_active_run();
         assert!(!lobby.has_active_run());
     }
+
+    #[test]
+    fn test_chat_history_is_capped() {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let host_id = host.id();
+        let mut lobby = Lobby::new("Test".to_string(), host).unwrap();
+
+        for i in 0..CHAT_HISTORY_LIMIT + 5 {
+            let message = ChatMessage::new(host_id, format!("Message {i}")).unwrap();
+            lobby.post_chat_message(message).unwrap();
+        }
+
+        assert_eq!(lobby.chat_messages().len(), CHAT_HISTORY_LIMIT);
+        assert_eq!(lobby.chat_messages()[0].text(), "Message 5");
+
+        let stranger = ChatMessage::new(Uuid::new_v4(), "Hi".to_string()).unwrap();
+        assert!(matches!(
+            lobby.post_chat_message(stranger),
+            Err(LobbyError::ParticipantNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_ready_check_and_countdown() {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let host_id = host.id();
+        let mut lobby = Lobby::new("Test".to_string(), host).unwrap();
+        let bob = Participant::new_guest("Bob".to_string()).unwrap();
+        let bob_id = bob.id();
+        lobby.add_guest(bob).unwrap();
+
+        assert!(lobby.set_ready(host_id, true).unwrap());
+        assert!(!lobby.set_ready(host_id, true).unwrap());
+        assert!(!lobby.all_ready());
+        lobby.set_ready(bob_id, true).unwrap();
+        assert!(lobby.all_ready());
+
+        assert_eq!(
+            lobby.start_countdown(bob_id, 3_000),
+            Err(LobbyError::PermissionDenied)
+        );
+        assert_eq!(
+            lobby.start_countdown(host_id, 3_000),
+            Err(LobbyError::EmptyQueue)
+        );
+        lobby
+            .queue_activity(ActivityConfig::new(
+                "quiz".to_string(),
+                "Quiz".to_string(),
+                serde_json::Value::Null,
+            ))
+            .unwrap();
+        lobby.start_countdown(host_id, 3_000).unwrap();
+        assert_eq!(lobby.countdown_ends_at(), Some(3_000));
+
+        lobby.set_active_run(Uuid::new_v4()).unwrap();
+        assert!(lobby.ready().is_empty());
+        assert_eq!(lobby.countdown_ends_at(), None);
+    }
+
+    #[test]
+    fn test_set_avatar() {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let host_id = host.id();
+        let mut lobby = Lobby::new("Test Lobby".to_string(), host).unwrap();
+        let guest = Participant::new_guest("Bob".to_string()).unwrap();
+        let guest_id = guest.id();
+        lobby.add_guest(guest).unwrap();
+
+        let fox = ParticipantAvatar::Emoji("🦊".to_string());
+        lobby
+            .set_avatar(guest_id, guest_id, Some(fox.clone()))
+            .unwrap();
+        assert_eq!(lobby.participants()[&guest_id].avatar(), Some(&fox));
+
+        assert_eq!(
+            lobby.set_avatar(guest_id, host_id, Some(fox)),
+            Err(LobbyError::PermissionDenied)
+        );
+        assert_eq!(
+            lobby.set_avatar(
+                guest_id,
+                guest_id,
+                Some(ParticipantAvatar::Image(
+                    "http://example.com/a.png".to_string()
+                ))
+            ),
+            Err(LobbyError::ParticipantError(
+                ParticipantError::InvalidAvatar
+            ))
+        );
+
+        lobby.set_avatar(guest_id, host_id, None).unwrap();
+        assert_eq!(lobby.participants()[&guest_id].avatar(), None);
+    }
 }
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Who takes over when the host leaves or times out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoDelegation {
+    /// The guest who joined first
+    #[default]
+    OldestGuest,
+    /// The co-host who joined first, then the oldest guest
+    CoHostsFirst,
+    /// Nobody: the lobby waits for the host to come back
+    Off,
+}
+
+impl AutoDelegation {
+    /// The next policy, for cycling through them in a UI
+    pub fn next(self) -> Self {
+        match self {
+            AutoDelegation::OldestGuest => AutoDelegation::CoHostsFirst,
+            AutoDelegation::CoHostsFirst => AutoDelegation::Off,
+            AutoDelegation::Off => AutoDelegation::OldestGuest,
+        }
+    }
+}
+
+impl fmt::Display for AutoDelegation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AutoDelegation::OldestGuest => write!(f, "Oldest guest"),
+            AutoDelegation::CoHostsFirst => write!(f, "Co-hosts first"),
+            AutoDelegation::Off => write!(f, "Off"),
+        }
+    }
+}
+
+/// Lobby rules the host can change at any time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LobbySettings {
+    /// Most participants allowed, host included (`None` = unlimited)
+    #[serde(default)]
+    pub max_participants: Option<usize>,
+    /// New guests are turned away
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default)]
+    pub auto_delegation: AutoDelegation,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_deserialize_with_defaults() {
+        let settings: LobbySettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings, LobbySettings::default());
+
+        let settings: LobbySettings =
+            serde_json::from_str(r#"{"locked":true,"auto_delegation":"co_hosts_first"}"#).unwrap();
+        assert!(settings.locked);
+        assert_eq!(settings.auto_delegation, AutoDelegation::CoHostsFirst);
+        assert_eq!(settings.max_participants, None);
+    }
+}
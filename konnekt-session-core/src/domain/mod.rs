@@ -1,11 +1,22 @@
 pub mod activity;
 pub mod activity_run;
+pub mod chat;
+pub mod clock;
 pub mod events;
 pub mod lobby;
+pub mod lobby_settings;
 pub mod participant;
+pub mod permissions;
 
 pub use activity::{ActivityConfig, ActivityId, ActivityResult};
 pub use activity_run::{ActivityRun, ActivityRunError, ActivityRunId, RunStatus};
+pub use chat::{CHAT_HISTORY_LIMIT, ChatError, ChatMessage, MAX_CHAT_MESSAGE_LEN};
+pub use clock::{Clock, SharedClock, SystemClock, TestClock};
 pub use events::DomainEvent;
 pub use lobby::{Lobby, LobbyError};
-pub use participant::{LobbyRole, Participant, ParticipantError, ParticipationMode, Timestamp};
+pub use lobby_settings::{AutoDelegation, LobbySettings};
+pub use participant::{
+    LobbyRole, MAX_AVATAR_EMOJI_LEN, MAX_AVATAR_URL_LEN, Participant, ParticipantAvatar,
+    ParticipantError, ParticipationMode, Timestamp,
+};
+pub use permissions::{DefaultPolicy, LobbyAction, PermissionPolicy};
@@ -1,4 +1,4 @@
-use instant::Instant;
+use crate::domain::clock::{self, Clock, SystemClock};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use uuid::Uuid;
@@ -39,9 +39,12 @@ pub struct Timestamp(u64);
 
 impl Timestamp {
     pub fn now() -> Self {
-        static ANCHOR: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
-        let anchor = ANCHOR.get_or_init(Instant::now);
-        let elapsed = Instant::now().duration_since(*anchor);
+        Self::now_on(&SystemClock)
+    }
+
+    /// Milliseconds on `clock`, so a `TestClock` moves it too
+    pub fn now_on(clock: &dyn Clock) -> Self {
+        let elapsed = clock.now().saturating_duration_since(clock::anchor());
         Timestamp(elapsed.as_millis() as u64)
     }
 
@@ -60,6 +63,44 @@ impl fmt::Display for Timestamp {
     }
 }
 
+/// Longest emoji sequence accepted as an avatar (in characters)
+pub const MAX_AVATAR_EMOJI_LEN: usize = 8;
+
+/// Longest image URL accepted as an avatar (in bytes)
+pub const MAX_AVATAR_URL_LEN: usize = 512;
+
+/// Avatar a participant picked instead of the one generated from their ID
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParticipantAvatar {
+    Emoji(String),
+    /// `https://` URL of an image
+    Image(String),
+}
+
+impl ParticipantAvatar {
+    pub fn validate(&self) -> Result<(), ParticipantError> {
+        let valid = match self {
+            ParticipantAvatar::Emoji(emoji) => {
+                let len = emoji.chars().count();
+                len > 0
+                    && len <= MAX_AVATAR_EMOJI_LEN
+                    && !emoji
+                        .chars()
+                        .any(|c| c.is_whitespace() || c.is_ascii_alphanumeric())
+            }
+            ParticipantAvatar::Image(url) => {
+                url.starts_with("https://") && url.len() <= MAX_AVATAR_URL_LEN
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(ParticipantError::InvalidAvatar)
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Participant {
     id: Uuid,
@@ -67,9 +108,11 @@ pub struct Participant {
     lobby_role: LobbyRole,
     participation_mode: ParticipationMode,
     joined_at: Timestamp,
+    #[serde(default)]
+    avatar: Option<ParticipantAvatar>,
 }
 
-#[derive(Debug, thiserror::Error, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Serialize, Deserialize)]
 pub enum ParticipantError {
     #[error("Name cannot be empty")]
     EmptyName,
@@ -79,28 +122,43 @@ pub enum ParticipantError {
 
     #[error("Cannot change participation mode during active activity")]
     CannotToggleDuringActivity,
+
+    #[error("Avatar must be a short emoji or an https:// image URL")]
+    InvalidAvatar,
 }
 
 impl Participant {
     pub fn new_host(name: String) -> Result<Self, ParticipantError> {
+        Self::new_host_at(name, Timestamp::now())
+    }
+
+    /// Like [`Participant::new_host`], joined at a session-clock time
+    pub fn new_host_at(name: String, joined_at: Timestamp) -> Result<Self, ParticipantError> {
         Self::validate_name(&name)?;
         Ok(Participant {
             id: Uuid::new_v4(),
             name,
             lobby_role: LobbyRole::Host,
             participation_mode: ParticipationMode::Active,
-            joined_at: Timestamp::now(),
+            joined_at,
+            avatar: None,
         })
     }
 
     pub fn new_guest(name: String) -> Result<Self, ParticipantError> {
+        Self::new_guest_at(name, Timestamp::now())
+    }
+
+    /// Like [`Participant::new_guest`], joined at a session-clock time
+    pub fn new_guest_at(name: String, joined_at: Timestamp) -> Result<Self, ParticipantError> {
         Self::validate_name(&name)?;
         Ok(Participant {
             id: Uuid::new_v4(),
             name,
             lobby_role: LobbyRole::Guest,
             participation_mode: ParticipationMode::default(),
-            joined_at: Timestamp::now(),
+            joined_at,
+            avatar: None,
         })
     }
 
@@ -118,26 +176,45 @@ impl Participant {
             lobby_role,
             participation_mode,
             joined_at,
+            avatar: None,
         })
     }
 
     pub fn host_with_id(id: Uuid, name: String) -> Result<Self, ParticipantError> {
+        Self::host_with_id_at(id, name, Timestamp::now())
+    }
+
+    /// Like [`Participant::host_with_id`], joined at a session-clock time
+    pub fn host_with_id_at(
+        id: Uuid,
+        name: String,
+        joined_at: Timestamp,
+    ) -> Result<Self, ParticipantError> {
         Self::with_id(
             id,
             name,
             LobbyRole::Host,
             ParticipationMode::Active,
-            Timestamp::now(),
+            joined_at,
         )
     }
 
     pub fn guest_with_id(id: Uuid, name: String) -> Result<Self, ParticipantError> {
+        Self::guest_with_id_at(id, name, Timestamp::now())
+    }
+
+    /// Like [`Participant::guest_with_id`], joined at a session-clock time
+    pub fn guest_with_id_at(
+        id: Uuid,
+        name: String,
+        joined_at: Timestamp,
+    ) -> Result<Self, ParticipantError> {
         Self::with_id(
             id,
             name,
             LobbyRole::Guest,
             ParticipationMode::default(),
-            Timestamp::now(),
+            joined_at,
         )
     }
 
@@ -153,6 +230,7 @@ impl Participant {
             lobby_role,
             participation_mode: ParticipationMode::default(),
             joined_at,
+            avatar: None,
         })
     }
 
@@ -186,6 +264,22 @@ impl Participant {
         self.joined_at
     }
 
+    pub fn avatar(&self) -> Option<&ParticipantAvatar> {
+        self.avatar.as_ref()
+    }
+
+    /// Pick an avatar, or go back to the generated one with `None`
+    pub fn set_avatar(
+        &mut self,
+        avatar: Option<ParticipantAvatar>,
+    ) -> Result<(), ParticipantError> {
+        if let Some(avatar) = &avatar {
+            avatar.validate()?;
+        }
+        self.avatar = avatar;
+        Ok(())
+    }
+
     pub fn is_host(&self) -> bool {
         matches!(self.lobby_role, LobbyRole::Host)
     }
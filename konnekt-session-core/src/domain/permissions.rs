@@ -0,0 +1,162 @@
+use crate::domain::Lobby;
+use std::fmt;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Something a participant asks the lobby to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LobbyAction {
+    /// Remove `target` from the lobby
+    Kick { target: Uuid },
+    /// Make a guest a co-host or demote them
+    SetCoHost,
+    /// Hand the host role to someone else
+    DelegateHost,
+    /// Change the lobby settings
+    ChangeSettings,
+    /// Switch `target` between playing and spectating
+    ToggleMode { target: Uuid },
+    /// Put `target` in a mode regardless of what they chose
+    ForceMode { target: Uuid },
+    /// Pick an avatar for `target`, or remove theirs (`reset`)
+    SetAvatar { target: Uuid, reset: bool },
+    /// Add, remove or reorder queued activities
+    PlanQueue,
+    /// Count down to or start the next activity
+    StartRun,
+    /// Cancel the run in progress or drop someone from it
+    ManageRun,
+    /// Post in the lobby chat
+    Chat,
+}
+
+/// Who may do what in a lobby
+///
+/// The lobby asks its policy before every action that needs permission;
+/// checks that keep the lobby consistent (e.g. the host can't be kicked)
+/// apply whatever the policy says. Replicas re-check the commands the host
+/// broadcasts, so every peer of a session needs the same policy.
+pub trait PermissionPolicy: fmt::Debug + Send + Sync {
+    /// May `actor`, a participant of `lobby`, do `action`?
+    fn allows(&self, lobby: &Lobby, actor: Uuid, action: LobbyAction) -> bool;
+}
+
+/// The host runs the lobby, co-hosts help moderate it
+///
+/// - Only the host may appoint co-hosts, delegate and change settings
+/// - Co-hosts may kick plain guests; the host may kick anyone
+/// - Host and co-hosts plan and run activities and reset avatars
+/// - Everyone may chat and change their own mode and avatar
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultPolicy;
+
+impl PermissionPolicy for DefaultPolicy {
+    fn allows(&self, lobby: &Lobby, actor: Uuid, action: LobbyAction) -> bool {
+        let is_host = actor == lobby.host_id();
+        match action {
+            LobbyAction::Kick { target } => {
+                is_host || (lobby.is_co_host(actor) && !lobby.is_co_host(target))
+            }
+            LobbyAction::SetCoHost
+            | LobbyAction::DelegateHost
+            | LobbyAction::ChangeSettings
+            | LobbyAction::ForceMode { .. } => is_host,
+            LobbyAction::ToggleMode { target } => actor == target || lobby.can_moderate(actor),
+            LobbyAction::SetAvatar { target, reset } => {
+                actor == target || (reset && lobby.can_moderate(actor))
+            }
+            LobbyAction::PlanQueue | LobbyAction::StartRun | LobbyAction::ManageRun => {
+                lobby.can_moderate(actor)
+            }
+            LobbyAction::Chat => true,
+        }
+    }
+}
+
+/// The policy a lobby consults
+///
+/// Not part of the lobby's state: it is neither serialized nor compared,
+/// and a deserialized lobby starts with the [`DefaultPolicy`].
+#[derive(Debug, Clone)]
+pub(crate) struct LobbyPolicy(Arc<dyn PermissionPolicy>);
+
+impl LobbyPolicy {
+    pub(crate) fn new(policy: Arc<dyn PermissionPolicy>) -> Self {
+        Self(policy)
+    }
+
+    pub(crate) fn get(&self) -> &dyn PermissionPolicy {
+        self.0.as_ref()
+    }
+}
+
+impl Default for LobbyPolicy {
+    fn default() -> Self {
+        Self(Arc::new(DefaultPolicy))
+    }
+}
+
+impl PartialEq for LobbyPolicy {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Lobby, LobbyError, Participant};
+
+    /// Students run the session: anyone may plan and start activities,
+    /// nobody but the teacher (host) may kick
+    #[derive(Debug)]
+    struct StudentLed;
+
+    impl PermissionPolicy for StudentLed {
+        fn allows(&self, lobby: &Lobby, actor: Uuid, action: LobbyAction) -> bool {
+            match action {
+                LobbyAction::PlanQueue | LobbyAction::StartRun => true,
+                LobbyAction::Kick { .. } => actor == lobby.host_id(),
+                action => DefaultPolicy.allows(lobby, actor, action),
+            }
+        }
+    }
+
+    #[test]
+    fn test_lobby_consults_its_policy() {
+        let host = Participant::new_host("Teacher".to_string()).unwrap();
+        let mut lobby = Lobby::new("Class".to_string(), host.clone()).unwrap();
+        let alice = Participant::new_guest("Alice".to_string()).unwrap();
+        let bob = Participant::new_guest("Bob".to_string()).unwrap();
+        lobby.add_guest(alice.clone()).unwrap();
+        lobby.add_guest(bob.clone()).unwrap();
+        lobby.set_co_host(alice.id(), host.id(), true).unwrap();
+        lobby
+            .queue_activity(crate::domain::ActivityConfig::new(
+                "quiz".to_string(),
+                "Quiz".to_string(),
+                serde_json::json!({}),
+            ))
+            .unwrap();
+
+        assert!(!lobby.allows(bob.id(), LobbyAction::StartRun));
+        assert_eq!(
+            lobby.start_countdown(bob.id(), 1_000),
+            Err(LobbyError::PermissionDenied)
+        );
+
+        lobby.set_permission_policy(Arc::new(StudentLed));
+        assert!(lobby.allows(bob.id(), LobbyAction::StartRun));
+        lobby.start_countdown(bob.id(), 1_000).unwrap();
+        // The co-host lost the right to kick
+        assert_eq!(
+            lobby.kick_guest(bob.id(), alice.id()),
+            Err(LobbyError::PermissionDenied)
+        );
+
+        // The policy doesn't travel with the lobby
+        let json = serde_json::to_string(&lobby).unwrap();
+        let copy: Lobby = serde_json::from_str(&json).unwrap();
+        assert!(!copy.allows(bob.id(), LobbyAction::StartRun));
+    }
+}
@@ -0,0 +1,334 @@
+use crate::application::runtime::QueueError;
+use crate::domain::{ActivityRunError, ActivityRunId, ChatError, LobbyError, ParticipantError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+/// Stable, machine-readable code for everything that can go wrong in a
+/// session
+///
+/// Frontends map codes to messages instead of matching on error text. The
+/// serialized names never change; new codes may be added.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    // ── Lobby ────────────────────────────────────────────────────────────────
+    LobbyNotFound,
+    LobbyFull,
+    LobbyLocked,
+    NoHost,
+    PermissionDenied,
+    ParticipantNotFound,
+    /// Delegating the host role to someone who can't take it
+    InvalidDelegation,
+    CannotRemoveHost,
+    CannotKickHost,
+
+    // ── Participants ─────────────────────────────────────────────────────────
+    InvalidName,
+    InvalidAvatar,
+    /// Participation mode can't change during an activity
+    ModeLocked,
+
+    // ── Activities and runs ──────────────────────────────────────────────────
+    ActivityNotFound,
+    ActivityAlreadyExists,
+    EmptyQueue,
+    RunNotFound,
+    RunAlreadyInProgress,
+    RunNotInProgress,
+    NotASubmitter,
+    DuplicateSubmission,
+    /// The result reached the host after the run's time limit
+    DeadlinePassed,
+    /// Expiring a run that still has time left
+    DeadlineNotReached,
+    /// No plugin plays this activity type
+    UnknownActivityType,
+    DuplicateActivityType,
+    InvalidActivityConfig,
+
+    // ── Chat ─────────────────────────────────────────────────────────────────
+    InvalidChatMessage,
+
+    // ── Transport ────────────────────────────────────────────────────────────
+    QueueFull,
+    ConnectionFailed,
+    InvalidSessionId,
+    InvalidResumeToken,
+    PeerNotFound,
+    SendFailed,
+    ReceiveFailed,
+    ChannelClosed,
+    Serialization,
+    Storage,
+
+    // ── Tools ────────────────────────────────────────────────────────────────
+    InvalidInput,
+    Io,
+    Timeout,
+    CheckFailed,
+
+    /// From a peer that predates error codes, or not classified yet
+    #[default]
+    Unknown,
+}
+
+impl ErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::LobbyNotFound => "lobby_not_found",
+            ErrorCode::LobbyFull => "lobby_full",
+            ErrorCode::LobbyLocked => "lobby_locked",
+            ErrorCode::NoHost => "no_host",
+            ErrorCode::PermissionDenied => "permission_denied",
+            ErrorCode::ParticipantNotFound => "participant_not_found",
+            ErrorCode::InvalidDelegation => "invalid_delegation",
+            ErrorCode::CannotRemoveHost => "cannot_remove_host",
+            ErrorCode::CannotKickHost => "cannot_kick_host",
+            ErrorCode::InvalidName => "invalid_name",
+            ErrorCode::InvalidAvatar => "invalid_avatar",
+            ErrorCode::ModeLocked => "mode_locked",
+            ErrorCode::ActivityNotFound => "activity_not_found",
+            ErrorCode::ActivityAlreadyExists => "activity_already_exists",
+            ErrorCode::EmptyQueue => "empty_queue",
+            ErrorCode::RunNotFound => "run_not_found",
+            ErrorCode::RunAlreadyInProgress => "run_already_in_progress",
+            ErrorCode::RunNotInProgress => "run_not_in_progress",
+            ErrorCode::NotASubmitter => "not_a_submitter",
+            ErrorCode::DuplicateSubmission => "duplicate_submission",
+            ErrorCode::DeadlinePassed => "deadline_passed",
+            ErrorCode::DeadlineNotReached => "deadline_not_reached",
+            ErrorCode::UnknownActivityType => "unknown_activity_type",
+            ErrorCode::DuplicateActivityType => "duplicate_activity_type",
+            ErrorCode::InvalidActivityConfig => "invalid_activity_config",
+            ErrorCode::InvalidChatMessage => "invalid_chat_message",
+            ErrorCode::QueueFull => "queue_full",
+            ErrorCode::ConnectionFailed => "connection_failed",
+            ErrorCode::InvalidSessionId => "invalid_session_id",
+            ErrorCode::InvalidResumeToken => "invalid_resume_token",
+            ErrorCode::PeerNotFound => "peer_not_found",
+            ErrorCode::SendFailed => "send_failed",
+            ErrorCode::ReceiveFailed => "receive_failed",
+            ErrorCode::ChannelClosed => "channel_closed",
+            ErrorCode::Serialization => "serialization",
+            ErrorCode::Storage => "storage",
+            ErrorCode::InvalidInput => "invalid_input",
+            ErrorCode::Io => "io",
+            ErrorCode::Timeout => "timeout",
+            ErrorCode::CheckFailed => "check_failed",
+            ErrorCode::Unknown => "unknown",
+        }
+    }
+
+    /// The same request may succeed later without anyone changing it
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            ErrorCode::QueueFull
+                | ErrorCode::ConnectionFailed
+                | ErrorCode::SendFailed
+                | ErrorCode::ReceiveFailed
+                | ErrorCode::Timeout
+        )
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An error that belongs to the shared taxonomy
+pub trait CodedError: std::error::Error {
+    fn code(&self) -> ErrorCode;
+
+    fn is_retryable(&self) -> bool {
+        self.code().is_retryable()
+    }
+}
+
+/// How a caller should treat a rejected command, coarser than [`ErrorCode`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// The requester isn't allowed to do this
+    PermissionDenied,
+    /// The lobby, run, participant or activity doesn't exist (anymore)
+    NotFound,
+    /// The command itself is malformed, retrying it unchanged won't help
+    Invalid,
+    /// Valid, but not in the current state of the lobby
+    Conflict,
+}
+
+/// Why the domain rejected a command
+#[derive(Debug, Clone, PartialEq, thiserror::Error, Serialize, Deserialize)]
+pub enum CommandError {
+    #[error("Lobby {0} not found")]
+    LobbyNotFound(Uuid),
+
+    #[error("Run {0} not found")]
+    RunNotFound(ActivityRunId),
+
+    #[error(transparent)]
+    Lobby(#[from] LobbyError),
+
+    #[error(transparent)]
+    Participant(#[from] ParticipantError),
+
+    #[error(transparent)]
+    Run(#[from] ActivityRunError),
+
+    #[error(transparent)]
+    Chat(#[from] ChatError),
+}
+
+impl CommandError {
+    pub fn kind(&self) -> ErrorKind {
+        match self.code() {
+            ErrorCode::PermissionDenied | ErrorCode::NotASubmitter => ErrorKind::PermissionDenied,
+            ErrorCode::LobbyNotFound
+            | ErrorCode::RunNotFound
+            | ErrorCode::ParticipantNotFound
+            | ErrorCode::ActivityNotFound => ErrorKind::NotFound,
+            ErrorCode::InvalidName
+            | ErrorCode::InvalidAvatar
+            | ErrorCode::InvalidChatMessage
+            | ErrorCode::InvalidDelegation => ErrorKind::Invalid,
+            _ => ErrorKind::Conflict,
+        }
+    }
+}
+
+impl CodedError for CommandError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            CommandError::LobbyNotFound(_) => ErrorCode::LobbyNotFound,
+            CommandError::RunNotFound(_) => ErrorCode::RunNotFound,
+            CommandError::Lobby(e) => e.code(),
+            CommandError::Participant(e) => e.code(),
+            CommandError::Run(e) => e.code(),
+            CommandError::Chat(e) => e.code(),
+        }
+    }
+}
+
+impl CodedError for LobbyError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            LobbyError::NoHost => ErrorCode::NoHost,
+            LobbyError::ParticipantNotFound(_) => ErrorCode::ParticipantNotFound,
+            LobbyError::CannotDelegateToNonGuest | LobbyError::EmptyLobby => {
+                ErrorCode::InvalidDelegation
+            }
+            LobbyError::CannotRemoveHost => ErrorCode::CannotRemoveHost,
+            LobbyError::CannotKickHost => ErrorCode::CannotKickHost,
+            LobbyError::PermissionDenied => ErrorCode::PermissionDenied,
+            LobbyError::ParticipantError(e) => e.code(),
+            LobbyError::ActivityNotFound(_) => ErrorCode::ActivityNotFound,
+            LobbyError::ActivityAlreadyExists(_) => ErrorCode::ActivityAlreadyExists,
+            LobbyError::RunAlreadyInProgress => ErrorCode::RunAlreadyInProgress,
+            LobbyError::NoRunInProgress => ErrorCode::RunNotInProgress,
+            LobbyError::EmptyQueue => ErrorCode::EmptyQueue,
+            LobbyError::LobbyFull(_) => ErrorCode::LobbyFull,
+            LobbyError::LobbyLocked => ErrorCode::LobbyLocked,
+        }
+    }
+}
+
+impl CodedError for ParticipantError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            ParticipantError::EmptyName | ParticipantError::InvalidNameLength => {
+                ErrorCode::InvalidName
+            }
+            ParticipantError::CannotToggleDuringActivity => ErrorCode::ModeLocked,
+            ParticipantError::InvalidAvatar => ErrorCode::InvalidAvatar,
+        }
+    }
+}
+
+impl CodedError for ActivityRunError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            ActivityRunError::NotARequiredSubmitter(_)
+            | ActivityRunError::SpectatorSubmission(_) => ErrorCode::NotASubmitter,
+            ActivityRunError::DuplicateSubmission(_) => ErrorCode::DuplicateSubmission,
+            ActivityRunError::NotInProgress => ErrorCode::RunNotInProgress,
+            ActivityRunError::DeadlinePassed => ErrorCode::DeadlinePassed,
+            ActivityRunError::DeadlineNotReached => ErrorCode::DeadlineNotReached,
+        }
+    }
+}
+
+impl CodedError for ChatError {
+    fn code(&self) -> ErrorCode {
+        ErrorCode::InvalidChatMessage
+    }
+}
+
+impl CodedError for QueueError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            QueueError::Full { .. } => ErrorCode::QueueFull,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_serialize_as_their_names() {
+        for code in [
+            ErrorCode::LobbyNotFound,
+            ErrorCode::NotASubmitter,
+            ErrorCode::QueueFull,
+            ErrorCode::Unknown,
+        ] {
+            assert_eq!(
+                serde_json::to_value(code).unwrap(),
+                serde_json::json!(code.as_str())
+            );
+        }
+        assert_eq!(
+            ErrorCode::InvalidChatMessage.to_string(),
+            "invalid_chat_message"
+        );
+    }
+
+    #[test]
+    fn test_domain_errors_are_classified() {
+        let nested = LobbyError::ParticipantError(ParticipantError::CannotToggleDuringActivity);
+        assert_eq!(nested.code(), ErrorCode::ModeLocked);
+        assert_eq!(LobbyError::LobbyFull(30).code(), ErrorCode::LobbyFull);
+        assert!(!LobbyError::PermissionDenied.is_retryable());
+
+        // A full queue drains, the same command can go in again
+        let full = QueueError::Full { max: 100 };
+        assert_eq!(full.code(), ErrorCode::QueueFull);
+        assert!(full.is_retryable());
+    }
+
+    #[test]
+    fn test_command_errors_have_a_kind() {
+        let denied = CommandError::from(LobbyError::PermissionDenied);
+        assert_eq!(denied.kind(), ErrorKind::PermissionDenied);
+        assert_eq!(denied.to_string(), "Permission denied");
+
+        let missing = CommandError::RunNotFound(Uuid::nil());
+        assert_eq!(missing.kind(), ErrorKind::NotFound);
+        assert_eq!(missing.code(), ErrorCode::RunNotFound);
+
+        assert_eq!(
+            CommandError::from(ChatError::MessageTooLong).kind(),
+            ErrorKind::Invalid
+        );
+        assert_eq!(
+            CommandError::from(LobbyError::RunAlreadyInProgress).kind(),
+            ErrorKind::Conflict
+        );
+    }
+}
@@ -1,13 +1,29 @@
 pub mod activities;
+pub mod analytics;
 pub mod application;
 pub mod domain;
+pub mod error;
+pub mod projections;
 
-pub use activities::{EchoChallenge, EchoResult};
+pub use activities::{
+    ActivityMetadata, ActivityPlugin, EchoChallenge, EchoPlugin, EchoResult, PluginError,
+    PluginRegistry, RenderHints,
+};
+
+pub use analytics::{ResultsAnalytics, RoundProgression, ScoreDistribution};
 
 pub use domain::{
-    ActivityConfig, ActivityRun, ActivityRunId, Lobby, LobbyError, LobbyRole, Participant,
-    ParticipantError, ParticipationMode, RunStatus, Timestamp,
+    ActivityConfig, ActivityRun, ActivityRunId, AutoDelegation, ChatMessage, Clock, DefaultPolicy,
+    Lobby, LobbyAction, LobbyError, LobbyRole, LobbySettings, Participant, ParticipantAvatar,
+    ParticipantError, ParticipationMode, PermissionPolicy, RunStatus, SharedClock, SystemClock,
+    TestClock, Timestamp,
 };
 
 pub use application::runtime::{CommandQueue, DomainLoop, QueueError};
-pub use application::{DomainCommand, DomainEvent, DomainEventLoop};
+pub use application::{
+    DomainCommand, DomainEvent, DomainEventLoop, EventFilter, EventKind, EventSubscription,
+};
+pub use error::{CodedError, CommandError, ErrorCode, ErrorKind};
+pub use projections::{
+    ActivityTimeline, Leaderboard, ParticipantSummaries, Projection, SessionMetrics,
+};
@@ -0,0 +1,263 @@
+use super::Projection;
+use crate::analytics::{UNKNOWN_PARTICIPANT, graded_score};
+use crate::application::DomainEvent;
+use crate::domain::{ActivityConfig, ActivityResult, ActivityRun, ActivityRunId, RunStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A participant's totals over all completed activities
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    /// 1-based; participants with equal score and time share a rank
+    pub rank: usize,
+    pub participant_id: Uuid,
+    pub participant_name: String,
+    pub total_score: u32,
+    pub activities: usize,
+    pub total_time_ms: u64,
+}
+
+/// Totals per participant, best first (faster wins a tie)
+///
+/// Only completed runs count; the ranking changes once per finished run.
+#[derive(Debug, Clone, Default)]
+pub struct Leaderboard {
+    /// Everyone who was ever in the lobby, by ID
+    names: HashMap<Uuid, String>,
+    /// Activities of the runs in progress, to grade their results
+    running: HashMap<ActivityRunId, ActivityConfig>,
+    entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The leaderboard of runs that already happened; `names` maps
+    /// participant ids to display names, including participants that have
+    /// left since
+    pub fn from_runs<'a>(
+        runs: impl IntoIterator<Item = &'a ActivityRun>,
+        names: &HashMap<Uuid, String>,
+    ) -> Self {
+        let mut leaderboard = Self {
+            names: names.clone(),
+            ..Self::default()
+        };
+        for run in runs {
+            if run.status() == RunStatus::Completed {
+                leaderboard.record(Some(run.config()), run.results().values());
+            }
+        }
+        leaderboard.rank();
+        leaderboard
+    }
+
+    pub fn entries(&self) -> &[LeaderboardEntry] {
+        &self.entries
+    }
+
+    pub fn entry(&self, participant_id: Uuid) -> Option<&LeaderboardEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.participant_id == participant_id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn record<'a>(
+        &mut self,
+        config: Option<&ActivityConfig>,
+        results: impl IntoIterator<Item = &'a ActivityResult>,
+    ) {
+        for result in results {
+            let score = match config {
+                Some(config) => graded_score(config, result),
+                None => result.score,
+            };
+            let index = match self
+                .entries
+                .iter()
+                .position(|entry| entry.participant_id == result.participant_id)
+            {
+                Some(index) => index,
+                None => {
+                    self.entries.push(LeaderboardEntry {
+                        rank: 0,
+                        participant_id: result.participant_id,
+                        participant_name: self
+                            .names
+                            .get(&result.participant_id)
+                            .cloned()
+                            .unwrap_or_else(|| UNKNOWN_PARTICIPANT.to_string()),
+                        total_score: 0,
+                        activities: 0,
+                        total_time_ms: 0,
+                    });
+                    self.entries.len() - 1
+                }
+            };
+            let entry = &mut self.entries[index];
+            entry.total_score += score.unwrap_or(0);
+            entry.activities += 1;
+            entry.total_time_ms += result.time_taken_ms.unwrap_or(0);
+        }
+    }
+
+    fn rank(&mut self) {
+        self.entries.sort_by(|a, b| {
+            b.total_score
+                .cmp(&a.total_score)
+                .then(a.total_time_ms.cmp(&b.total_time_ms))
+                .then(a.participant_name.cmp(&b.participant_name))
+                .then(a.participant_id.cmp(&b.participant_id))
+        });
+        for index in 0..self.entries.len() {
+            self.entries[index].rank = match index.checked_sub(1).map(|prev| &self.entries[prev]) {
+                Some(prev)
+                    if prev.total_score == self.entries[index].total_score
+                        && prev.total_time_ms == self.entries[index].total_time_ms =>
+                {
+                    prev.rank
+                }
+                _ => index + 1,
+            };
+        }
+    }
+}
+
+impl Projection for Leaderboard {
+    fn apply(&mut self, event: &DomainEvent) {
+        match event {
+            DomainEvent::LobbyCreated { lobby } => {
+                for participant in lobby.participants().values() {
+                    self.names
+                        .insert(participant.id(), participant.name().to_string());
+                }
+            }
+            DomainEvent::GuestJoined { participant, .. } => {
+                self.names
+                    .insert(participant.id(), participant.name().to_string());
+            }
+            DomainEvent::RunStarted { run_id, config, .. } => {
+                self.running.insert(*run_id, config.clone());
+            }
+            DomainEvent::RunEnded {
+                run_id,
+                status,
+                results,
+                ..
+            } => {
+                let config = self.running.remove(run_id);
+                if *status == RunStatus::Completed {
+                    self.record(config.as_ref(), results);
+                    self.rank();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EchoChallenge;
+    use crate::application::{DomainCommand, DomainEventLoop, EventFilter};
+
+    #[test]
+    fn test_leaderboard_follows_completed_runs() {
+        let mut el = DomainEventLoop::new();
+        let mut leaderboard = Leaderboard::new();
+        let events = el.subscribe(EventFilter::all());
+
+        let DomainEvent::LobbyCreated { lobby } = el.handle_command(DomainCommand::CreateLobby {
+            lobby_id: None,
+            lobby_name: "Test Lobby".to_string(),
+            host_name: "Alice".to_string(),
+        }) else {
+            panic!("lobby not created");
+        };
+        let (lobby_id, alice) = (lobby.id(), lobby.host_id());
+        let DomainEvent::GuestJoined { participant, .. } =
+            el.handle_command(DomainCommand::JoinLobby {
+                lobby_id,
+                guest_name: "Bob".to_string(),
+            })
+        else {
+            panic!("guest not joined");
+        };
+        let bob = participant.id();
+
+        // Bob echoes perfectly but slowly, Alice is graded 0
+        for (prompt, cancel) in [("Hallo", false), ("Danke", true)] {
+            let config = ActivityConfig::new(
+                EchoChallenge::activity_type().to_string(),
+                format!("Echo {prompt}"),
+                EchoChallenge::new(prompt.to_string()).to_config(),
+            );
+            el.handle_command(DomainCommand::QueueActivity { lobby_id, config });
+            let DomainEvent::RunStarted { run_id, .. } =
+                el.handle_command(DomainCommand::StartNextRun { lobby_id })
+            else {
+                panic!("run not started");
+            };
+            if cancel {
+                el.handle_command(DomainCommand::CancelRun { lobby_id, run_id });
+                continue;
+            }
+            for (participant_id, response, time) in [(alice, "Tschüss", 500), (bob, prompt, 900)] {
+                let result = ActivityResult::new(run_id, participant_id)
+                    .with_data(serde_json::json!({ "response": response }))
+                    .with_time(time);
+                el.handle_command(DomainCommand::SubmitResult {
+                    lobby_id,
+                    run_id,
+                    result,
+                });
+            }
+        }
+        leaderboard.apply_all(&events.drain());
+
+        let ranking: Vec<_> = leaderboard
+            .entries()
+            .iter()
+            .map(|e| (e.rank, e.participant_name.as_str(), e.total_score))
+            .collect();
+        assert_eq!(ranking, vec![(1, "Bob", 100), (2, "Alice", 0)]);
+        assert_eq!(leaderboard.entry(bob).unwrap().total_time_ms, 900);
+        assert_eq!(leaderboard.entry(alice).unwrap().activities, 1);
+
+        // Rebuilding from the runs gives the same board
+        let names = HashMap::from([(alice, "Alice".to_string()), (bob, "Bob".to_string())]);
+        let rebuilt = Leaderboard::from_runs(el.runs_for_lobby(lobby_id), &names);
+        assert_eq!(rebuilt.entries(), leaderboard.entries());
+    }
+
+    #[test]
+    fn test_equal_totals_share_a_rank() {
+        let run_id = Uuid::new_v4();
+        let (carol, dave, erin) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let results = [(carol, 5), (dave, 5), (erin, 3)]
+            .map(|(id, score)| ActivityResult::new(run_id, id).with_score(score));
+
+        let mut leaderboard = Leaderboard::new();
+        leaderboard.apply(&DomainEvent::RunEnded {
+            lobby_id: Uuid::new_v4(),
+            run_id,
+            status: RunStatus::Completed,
+            results: results.to_vec(),
+        });
+
+        let ranks: Vec<_> = leaderboard.entries().iter().map(|e| e.rank).collect();
+        assert_eq!(ranks, vec![1, 1, 3]);
+        assert_eq!(
+            leaderboard.entries()[0].participant_name,
+            UNKNOWN_PARTICIPANT
+        );
+    }
+}
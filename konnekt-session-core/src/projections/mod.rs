@@ -0,0 +1,44 @@
+//! Read models kept up to date from domain events
+//!
+//! Views subscribe to the event loop and apply each event to their
+//! projections instead of walking the lobby and its runs on every render:
+//!
+//! ```
+//! use konnekt_session_core::projections::{Leaderboard, Projection};
+//! use konnekt_session_core::{DomainEventLoop, EventFilter};
+//!
+//! let mut event_loop = DomainEventLoop::new();
+//! let events = event_loop.subscribe(EventFilter::all());
+//! let mut leaderboard = Leaderboard::new();
+//! // ... commands ...
+//! leaderboard.apply_all(&events.drain());
+//! ```
+
+mod leaderboard;
+mod participants;
+mod session_metrics;
+mod timeline;
+
+pub use leaderboard::{Leaderboard, LeaderboardEntry};
+pub use participants::{ParticipantSummaries, ParticipantSummary};
+pub use session_metrics::{DropOff, RunMetrics, SessionMetrics};
+pub use timeline::{ActivityTimeline, TimelineEntry, TimelineStatus};
+
+use crate::application::DomainEvent;
+
+/// A read model updated one event at a time
+///
+/// Feed it every event from the lobby's creation on; a projection that
+/// missed events only knows what happened since.
+pub trait Projection {
+    fn apply(&mut self, event: &DomainEvent);
+
+    fn apply_all<'a>(&mut self, events: impl IntoIterator<Item = &'a DomainEvent>)
+    where
+        Self: Sized,
+    {
+        for event in events {
+            self.apply(event);
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use super::Projection;
+use crate::application::DomainEvent;
+use crate::domain::{LobbyRole, Participant, ParticipantAvatar, ParticipationMode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// What a roster shows about one participant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParticipantSummary {
+    pub participant_id: Uuid,
+    pub name: String,
+    pub role: LobbyRole,
+    pub co_host: bool,
+    pub mode: ParticipationMode,
+    pub avatar: Option<ParticipantAvatar>,
+    pub ready: bool,
+    /// Still in the lobby; left and kicked participants stay listed
+    pub present: bool,
+    /// Submitted a result to the run in progress
+    pub submitted: bool,
+    /// Finished runs with a result of theirs
+    pub activities: usize,
+}
+
+impl ParticipantSummary {
+    fn new(participant: &Participant) -> Self {
+        Self {
+            participant_id: participant.id(),
+            name: participant.name().to_string(),
+            role: participant.lobby_role(),
+            co_host: false,
+            mode: participant.participation_mode(),
+            avatar: participant.avatar().cloned(),
+            ready: false,
+            present: true,
+            submitted: false,
+            activities: 0,
+        }
+    }
+}
+
+/// Everyone who was in the lobby, in join order
+#[derive(Debug, Clone, Default)]
+pub struct ParticipantSummaries {
+    summaries: Vec<ParticipantSummary>,
+    /// Position in `summaries` by participant ID
+    index: HashMap<Uuid, usize>,
+}
+
+impl ParticipantSummaries {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, participant_id: Uuid) -> Option<&ParticipantSummary> {
+        self.index
+            .get(&participant_id)
+            .map(|&index| &self.summaries[index])
+    }
+
+    /// Everyone, including those who left
+    pub fn all(&self) -> &[ParticipantSummary] {
+        &self.summaries
+    }
+
+    /// Who is in the lobby now
+    pub fn present(&self) -> impl Iterator<Item = &ParticipantSummary> {
+        self.summaries.iter().filter(|summary| summary.present)
+    }
+
+    fn get_mut(&mut self, participant_id: Uuid) -> Option<&mut ParticipantSummary> {
+        self.index
+            .get(&participant_id)
+            .map(|&index| &mut self.summaries[index])
+    }
+
+    fn insert(&mut self, participant: &Participant) {
+        match self.get_mut(participant.id()) {
+            // Rejoined
+            Some(summary) => {
+                let activities = summary.activities;
+                *summary = ParticipantSummary::new(participant);
+                summary.activities = activities;
+            }
+            None => {
+                self.index.insert(participant.id(), self.summaries.len());
+                self.summaries.push(ParticipantSummary::new(participant));
+            }
+        }
+    }
+
+    fn update(&mut self, participant_id: Uuid, change: impl FnOnce(&mut ParticipantSummary)) {
+        if let Some(summary) = self.get_mut(participant_id) {
+            change(summary);
+        }
+    }
+}
+
+impl Projection for ParticipantSummaries {
+    fn apply(&mut self, event: &DomainEvent) {
+        match event {
+            DomainEvent::LobbyCreated { lobby } => {
+                let mut participants: Vec<_> = lobby.participants().values().collect();
+                participants.sort_by_key(|p| (p.joined_at(), p.id()));
+                for participant in participants {
+                    self.insert(participant);
+                    let id = participant.id();
+                    self.update(id, |summary| {
+                        summary.co_host = lobby.is_co_host(id);
+                        summary.ready = lobby.is_ready(id);
+                    });
+                }
+            }
+            DomainEvent::GuestJoined { participant, .. } => self.insert(participant),
+            DomainEvent::GuestLeft { participant_id, .. }
+            | DomainEvent::GuestKicked { participant_id, .. } => {
+                self.update(*participant_id, |summary| {
+                    summary.present = false;
+                    summary.ready = false;
+                });
+            }
+            DomainEvent::ParticipationModeChanged {
+                participant_id,
+                new_mode,
+                ..
+            } => self.update(*participant_id, |summary| summary.mode = *new_mode),
+            DomainEvent::HostDelegated { from, to, .. } => {
+                self.update(*from, |summary| summary.role = LobbyRole::Guest);
+                self.update(*to, |summary| {
+                    summary.role = LobbyRole::Host;
+                    summary.co_host = false;
+                });
+            }
+            DomainEvent::CoHostChanged {
+                participant_id,
+                co_host,
+                ..
+            } => self.update(*participant_id, |summary| summary.co_host = *co_host),
+            DomainEvent::AvatarChanged {
+                participant_id,
+                avatar,
+                ..
+            } => self.update(*participant_id, |summary| summary.avatar = avatar.clone()),
+            DomainEvent::ReadyChanged {
+                participant_id,
+                ready,
+                ..
+            } => self.update(*participant_id, |summary| summary.ready = *ready),
+            DomainEvent::RunStarted { .. } => {
+                for summary in &mut self.summaries {
+                    summary.ready = false;
+                    summary.submitted = false;
+                }
+            }
+            DomainEvent::ResultSubmitted { result, .. } => {
+                self.update(result.participant_id, |summary| summary.submitted = true);
+            }
+            DomainEvent::RunEnded { results, .. } => {
+                for result in results {
+                    self.update(result.participant_id, |summary| summary.activities += 1);
+                }
+                for summary in &mut self.summaries {
+                    summary.submitted = false;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{DomainCommand, DomainEventLoop, EventFilter};
+    use crate::domain::{ActivityConfig, ActivityResult};
+
+    #[test]
+    fn test_roster_follows_the_lobby() {
+        let mut el = DomainEventLoop::new();
+        let mut roster = ParticipantSummaries::new();
+        let events = el.subscribe(EventFilter::all());
+
+        let DomainEvent::LobbyCreated { lobby } = el.handle_command(DomainCommand::CreateLobby {
+            lobby_id: None,
+            lobby_name: "Test Lobby".to_string(),
+            host_name: "Alice".to_string(),
+        }) else {
+            panic!("lobby not created");
+        };
+        let (lobby_id, alice) = (lobby.id(), lobby.host_id());
+        let mut guests = Vec::new();
+        for name in ["Bob", "Carol"] {
+            let DomainEvent::GuestJoined { participant, .. } =
+                el.handle_command(DomainCommand::JoinLobby {
+                    lobby_id,
+                    guest_name: name.to_string(),
+                })
+            else {
+                panic!("guest not joined");
+            };
+            guests.push(participant.id());
+        }
+        let (bob, carol) = (guests[0], guests[1]);
+
+        el.handle_command(DomainCommand::QueueActivity {
+            lobby_id,
+            config: ActivityConfig::new(
+                "quiz".to_string(),
+                "Trivia Quiz".to_string(),
+                serde_json::json!({}),
+            ),
+        });
+        let DomainEvent::RunStarted { run_id, .. } =
+            el.handle_command(DomainCommand::StartNextRun { lobby_id })
+        else {
+            panic!("run not started");
+        };
+        el.handle_command(DomainCommand::SubmitResult {
+            lobby_id,
+            run_id,
+            result: ActivityResult::new(run_id, bob).with_score(3),
+        });
+        roster.apply_all(&events.drain());
+        assert!(roster.get(bob).unwrap().submitted);
+        assert!(!roster.get(carol).unwrap().submitted);
+
+        el.handle_command(DomainCommand::LeaveLobby {
+            lobby_id,
+            participant_id: carol,
+        });
+        el.handle_command(DomainCommand::DelegateHost {
+            lobby_id,
+            current_host_id: alice,
+            new_host_id: bob,
+        });
+
+        roster.apply_all(&events.drain());
+        let names: Vec<_> = roster.all().iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Alice", "Bob", "Carol"]);
+        let present: Vec<_> = roster.present().map(|s| s.participant_id).collect();
+        assert_eq!(present, vec![alice, bob]);
+        assert_eq!(roster.get(bob).unwrap().role, LobbyRole::Host);
+        assert_eq!(roster.get(alice).unwrap().role, LobbyRole::Guest);
+    }
+}
@@ -0,0 +1,318 @@
+use super::Projection;
+use crate::analytics::{ScoreDistribution, graded_score};
+use crate::application::DomainEvent;
+use crate::domain::{ActivityConfig, ActivityRunId, ParticipationMode, RunStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// How one activity went
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunMetrics {
+    pub run_id: ActivityRunId,
+    pub name: String,
+    pub status: RunStatus,
+    /// Players expected to answer
+    pub eligible: usize,
+    pub submitted: usize,
+    /// Players dropped from the run without answering (e.g. disconnected)
+    pub dropped: usize,
+    /// Answer times of the results that reported one
+    pub answer_times_ms: Vec<u64>,
+}
+
+impl RunMetrics {
+    /// Share of the eligible players who answered (`None` without players)
+    pub fn participation_rate(&self) -> Option<f64> {
+        (self.eligible > 0).then(|| self.submitted as f64 / self.eligible as f64)
+    }
+
+    pub fn average_answer_ms(&self) -> Option<u64> {
+        average(&self.answer_times_ms)
+    }
+}
+
+/// A participant who left the lobby
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DropOff {
+    pub participant_id: Uuid,
+    pub name: String,
+    /// Activities completed before they left
+    pub completed_runs: usize,
+    /// The activity in progress when they left
+    pub during: Option<String>,
+}
+
+/// Engagement over a session: answer times, participation, who left when
+/// and how the scores spread
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionMetrics {
+    /// In start order
+    runs: Vec<RunMetrics>,
+    drop_offs: Vec<DropOff>,
+    /// Scores of the completed runs' results
+    scores: Vec<u32>,
+    /// Mode of everyone in the lobby
+    modes: HashMap<Uuid, ParticipationMode>,
+    names: HashMap<Uuid, String>,
+    /// What each run plays, to grade unscored results
+    configs: HashMap<ActivityRunId, ActivityConfig>,
+}
+
+impl SessionMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn runs(&self) -> &[RunMetrics] {
+        &self.runs
+    }
+
+    /// In the order they left
+    pub fn drop_offs(&self) -> &[DropOff] {
+        &self.drop_offs
+    }
+
+    /// Mean answer time over every activity
+    pub fn average_answer_ms(&self) -> Option<u64> {
+        let times: Vec<u64> = self
+            .runs
+            .iter()
+            .flat_map(|run| run.answer_times_ms.iter().copied())
+            .collect();
+        average(&times)
+    }
+
+    /// Share of the eligible players who answered, over the completed runs
+    pub fn participation_rate(&self) -> Option<f64> {
+        let (submitted, eligible) = self.completed().fold((0, 0), |(submitted, eligible), run| {
+            (submitted + run.submitted, eligible + run.eligible)
+        });
+        (eligible > 0).then(|| submitted as f64 / eligible as f64)
+    }
+
+    pub fn score_distribution(&self, buckets: usize) -> ScoreDistribution {
+        ScoreDistribution::from_scores(self.scores.iter().copied(), buckets)
+    }
+
+    /// Nothing happened yet worth showing
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty() && self.drop_offs.is_empty()
+    }
+
+    fn completed(&self) -> impl Iterator<Item = &RunMetrics> {
+        self.runs
+            .iter()
+            .filter(|run| run.status == RunStatus::Completed)
+    }
+
+    fn run_mut(&mut self, run_id: ActivityRunId) -> Option<&mut RunMetrics> {
+        self.runs.iter_mut().rev().find(|run| run.run_id == run_id)
+    }
+}
+
+impl Projection for SessionMetrics {
+    fn apply(&mut self, event: &DomainEvent) {
+        match event {
+            DomainEvent::LobbyCreated { lobby } => {
+                for participant in lobby.participants().values() {
+                    self.modes
+                        .insert(participant.id(), participant.participation_mode());
+                    self.names
+                        .insert(participant.id(), participant.name().to_string());
+                }
+            }
+            DomainEvent::GuestJoined { participant, .. } => {
+                self.modes
+                    .insert(participant.id(), participant.participation_mode());
+                self.names
+                    .insert(participant.id(), participant.name().to_string());
+            }
+            DomainEvent::GuestLeft { participant_id, .. } => {
+                self.modes.remove(participant_id);
+                self.drop_offs.push(DropOff {
+                    participant_id: *participant_id,
+                    name: self.names.get(participant_id).cloned().unwrap_or_default(),
+                    completed_runs: self.completed().count(),
+                    during: self
+                        .runs
+                        .iter()
+                        .rev()
+                        .find(|run| run.status == RunStatus::InProgress)
+                        .map(|run| run.name.clone()),
+                });
+            }
+            DomainEvent::GuestKicked { participant_id, .. } => {
+                self.modes.remove(participant_id);
+            }
+            DomainEvent::ParticipationModeChanged {
+                participant_id,
+                new_mode,
+                ..
+            } => {
+                self.modes.insert(*participant_id, *new_mode);
+            }
+            DomainEvent::RunStarted { run_id, config, .. } => {
+                self.configs.insert(*run_id, config.clone());
+                self.runs.push(RunMetrics {
+                    run_id: *run_id,
+                    name: config.name.clone(),
+                    status: RunStatus::InProgress,
+                    eligible: self
+                        .modes
+                        .values()
+                        .filter(|mode| **mode == ParticipationMode::Active)
+                        .count(),
+                    submitted: 0,
+                    dropped: 0,
+                    answer_times_ms: Vec::new(),
+                });
+            }
+            DomainEvent::ResultSubmitted { run_id, result, .. } => {
+                if let Some(run) = self.run_mut(*run_id) {
+                    run.submitted += 1;
+                    run.answer_times_ms.extend(result.time_taken_ms);
+                }
+            }
+            DomainEvent::SubmitterRemoved { run_id, .. } => {
+                if let Some(run) = self.run_mut(*run_id) {
+                    run.dropped += 1;
+                }
+            }
+            DomainEvent::RunEnded {
+                run_id,
+                status,
+                results,
+                ..
+            } => {
+                let config = self.configs.get(run_id).cloned();
+                let Some(run) = self.run_mut(*run_id) else {
+                    return;
+                };
+                // The results are authoritative, whatever we missed
+                run.status = *status;
+                run.submitted = results.len();
+                run.answer_times_ms = results.iter().filter_map(|r| r.time_taken_ms).collect();
+                if *status == RunStatus::Completed {
+                    // Everyone required either answered or was dropped
+                    run.eligible = run.submitted + run.dropped;
+                    if let Some(config) = config {
+                        self.scores
+                            .extend(results.iter().filter_map(|r| graded_score(&config, r)));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn average(values: &[u64]) -> Option<u64> {
+    (!values.is_empty()).then(|| values.iter().sum::<u64>() / values.len() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{DomainCommand, DomainEventLoop, EventFilter};
+    use crate::domain::ActivityResult;
+
+    #[test]
+    fn test_metrics_follow_the_session() {
+        let mut el = DomainEventLoop::new();
+        let mut metrics = SessionMetrics::new();
+        let events = el.subscribe(EventFilter::all());
+
+        let DomainEvent::LobbyCreated { lobby } = el.handle_command(DomainCommand::CreateLobby {
+            lobby_id: None,
+            lobby_name: "Test Lobby".to_string(),
+            host_name: "Alice".to_string(),
+        }) else {
+            panic!("lobby not created");
+        };
+        let (lobby_id, alice) = (lobby.id(), lobby.host_id());
+        let mut guests = Vec::new();
+        for name in ["Bob", "Carol", "Dave"] {
+            let DomainEvent::GuestJoined { participant, .. } =
+                el.handle_command(DomainCommand::JoinLobby {
+                    lobby_id,
+                    guest_name: name.to_string(),
+                })
+            else {
+                panic!("guest not joined");
+            };
+            guests.push(participant.id());
+        }
+        let (bob, carol, dave) = (guests[0], guests[1], guests[2]);
+        for name in ["First", "Second"] {
+            el.handle_command(DomainCommand::QueueActivity {
+                lobby_id,
+                config: ActivityConfig::new(
+                    "quiz".to_string(),
+                    name.to_string(),
+                    serde_json::json!({}),
+                ),
+            });
+        }
+
+        let answer = |run_id, participant_id, score, time_ms| DomainCommand::SubmitResult {
+            lobby_id,
+            run_id,
+            result: ActivityResult::new(run_id, participant_id)
+                .with_score(score)
+                .with_time(time_ms),
+        };
+        let DomainEvent::RunStarted { run_id, .. } =
+            el.handle_command(DomainCommand::StartNextRun { lobby_id })
+        else {
+            panic!("run not started");
+        };
+        el.handle_command(answer(run_id, alice, 100, 1_000));
+        el.handle_command(answer(run_id, bob, 40, 3_000));
+        el.handle_command(DomainCommand::LeaveLobby {
+            lobby_id,
+            participant_id: carol,
+        });
+        el.handle_command(DomainCommand::RemoveSubmitter {
+            lobby_id,
+            run_id,
+            participant_id: carol,
+        });
+        el.handle_command(answer(run_id, dave, 90, 2_000));
+        metrics.apply_all(&events.drain());
+
+        let first = &metrics.runs()[0];
+        assert_eq!(first.status, RunStatus::Completed);
+        assert_eq!((first.eligible, first.submitted, first.dropped), (4, 3, 1));
+        assert_eq!(first.participation_rate(), Some(0.75));
+        assert_eq!(first.average_answer_ms(), Some(2_000));
+        assert_eq!(
+            metrics.drop_offs(),
+            &[DropOff {
+                participant_id: carol,
+                name: "Carol".to_string(),
+                completed_runs: 0,
+                during: Some("First".to_string()),
+            }]
+        );
+
+        let DomainEvent::RunStarted { run_id, .. } =
+            el.handle_command(DomainCommand::StartNextRun { lobby_id })
+        else {
+            panic!("run not started");
+        };
+        el.handle_command(answer(run_id, alice, 70, 5_000));
+        metrics.apply_all(&events.drain());
+
+        let second = &metrics.runs()[1];
+        assert_eq!(second.status, RunStatus::InProgress);
+        assert_eq!((second.eligible, second.submitted), (3, 1));
+        assert_eq!(metrics.average_answer_ms(), Some(2_750));
+        // Only the completed run counts
+        assert_eq!(metrics.participation_rate(), Some(0.75));
+        let distribution = metrics.score_distribution(5);
+        assert_eq!(distribution.total(), 3);
+        assert_eq!(distribution.buckets[2].count, 1);
+        assert_eq!(distribution.buckets[4].count, 2);
+    }
+}
@@ -0,0 +1,216 @@
+use super::Projection;
+use crate::application::DomainEvent;
+use crate::domain::{ActivityConfig, ActivityId, ActivityRunId, RunStatus};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimelineStatus {
+    Queued,
+    Running,
+    Completed,
+    Cancelled,
+}
+
+/// One activity of the session, queued or played
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub activity_id: ActivityId,
+    pub name: String,
+    pub activity_type: String,
+    /// Once started
+    pub run_id: Option<ActivityRunId>,
+    pub status: TimelineStatus,
+    /// Results submitted so far
+    pub submissions: usize,
+}
+
+impl TimelineEntry {
+    fn queued(config: &ActivityConfig) -> Self {
+        Self {
+            activity_id: config.id,
+            name: config.name.clone(),
+            activity_type: config.activity_type.clone(),
+            run_id: None,
+            status: TimelineStatus::Queued,
+            submissions: 0,
+        }
+    }
+}
+
+/// Played activities in start order, then the queue in queue order
+#[derive(Debug, Clone, Default)]
+pub struct ActivityTimeline {
+    entries: Vec<TimelineEntry>,
+}
+
+impl ActivityTimeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entries(&self) -> &[TimelineEntry] {
+        &self.entries
+    }
+
+    pub fn current(&self) -> Option<&TimelineEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.status == TimelineStatus::Running)
+    }
+
+    pub fn queued(&self) -> &[TimelineEntry] {
+        &self.entries[self.started()..]
+    }
+
+    /// Entries before the queue
+    fn started(&self) -> usize {
+        self.entries
+            .iter()
+            .take_while(|entry| entry.status != TimelineStatus::Queued)
+            .count()
+    }
+
+    fn queue_position(&self, activity_id: ActivityId) -> Option<usize> {
+        let start = self.started();
+        self.entries[start..]
+            .iter()
+            .position(|entry| entry.activity_id == activity_id)
+            .map(|index| start + index)
+    }
+
+    fn run_mut(&mut self, run_id: ActivityRunId) -> Option<&mut TimelineEntry> {
+        self.entries
+            .iter_mut()
+            .rev()
+            .find(|entry| entry.run_id == Some(run_id))
+    }
+}
+
+impl Projection for ActivityTimeline {
+    fn apply(&mut self, event: &DomainEvent) {
+        match event {
+            DomainEvent::LobbyCreated { lobby } => {
+                self.entries
+                    .extend(lobby.activity_queue().iter().map(TimelineEntry::queued));
+            }
+            DomainEvent::ActivityQueued { config, .. } => {
+                self.entries.push(TimelineEntry::queued(config));
+            }
+            DomainEvent::QueuedActivityMoved {
+                activity_id,
+                to_index,
+                ..
+            } => {
+                if let Some(from) = self.queue_position(*activity_id) {
+                    let entry = self.entries.remove(from);
+                    let start = self.started();
+                    let to = (start + to_index).min(self.entries.len());
+                    self.entries.insert(to, entry);
+                }
+            }
+            DomainEvent::RunStarted { run_id, config, .. } => {
+                let mut entry = match self.queue_position(config.id) {
+                    Some(index) => self.entries.remove(index),
+                    None => TimelineEntry::queued(config),
+                };
+                entry.run_id = Some(*run_id);
+                entry.status = TimelineStatus::Running;
+                let start = self.started();
+                self.entries.insert(start, entry);
+            }
+            DomainEvent::ResultSubmitted { run_id, .. } => {
+                if let Some(entry) = self.run_mut(*run_id) {
+                    entry.submissions += 1;
+                }
+            }
+            DomainEvent::RunEnded {
+                run_id,
+                status,
+                results,
+                ..
+            } => {
+                if let Some(entry) = self.run_mut(*run_id) {
+                    entry.submissions = results.len();
+                    entry.status = match status {
+                        RunStatus::InProgress => TimelineStatus::Running,
+                        RunStatus::Completed => TimelineStatus::Completed,
+                        RunStatus::Cancelled => TimelineStatus::Cancelled,
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{DomainCommand, DomainEventLoop, EventFilter};
+    use crate::domain::ActivityResult;
+
+    #[test]
+    fn test_timeline_orders_played_before_queued() {
+        let mut el = DomainEventLoop::new();
+        let mut timeline = ActivityTimeline::new();
+        let events = el.subscribe(EventFilter::all());
+
+        let DomainEvent::LobbyCreated { lobby } = el.handle_command(DomainCommand::CreateLobby {
+            lobby_id: None,
+            lobby_name: "Test Lobby".to_string(),
+            host_name: "Alice".to_string(),
+        }) else {
+            panic!("lobby not created");
+        };
+        let (lobby_id, host_id) = (lobby.id(), lobby.host_id());
+
+        let configs: Vec<_> = ["First", "Second", "Third"]
+            .into_iter()
+            .map(|name| {
+                ActivityConfig::new("quiz".to_string(), name.to_string(), serde_json::json!({}))
+            })
+            .collect();
+        for config in &configs {
+            el.handle_command(DomainCommand::QueueActivity {
+                lobby_id,
+                config: config.clone(),
+            });
+        }
+        let DomainEvent::RunStarted { run_id, .. } =
+            el.handle_command(DomainCommand::StartNextRun { lobby_id })
+        else {
+            panic!("run not started");
+        };
+        el.handle_command(DomainCommand::MoveQueuedActivity {
+            lobby_id,
+            activity_id: configs[2].id,
+            to_index: 0,
+            requester_id: host_id,
+        });
+        timeline.apply_all(&events.drain());
+        assert_eq!(timeline.current().unwrap().name, "First");
+
+        el.handle_command(DomainCommand::SubmitResult {
+            lobby_id,
+            run_id,
+            result: ActivityResult::new(run_id, host_id).with_score(1),
+        });
+        el.handle_command(DomainCommand::StartNextRun { lobby_id });
+        timeline.apply_all(&events.drain());
+
+        let entries: Vec<_> = timeline
+            .entries()
+            .iter()
+            .map(|e| (e.name.as_str(), e.status, e.submissions))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("First", TimelineStatus::Completed, 1),
+                ("Third", TimelineStatus::Running, 0),
+                ("Second", TimelineStatus::Queued, 0),
+            ]
+        );
+        assert_eq!(timeline.queued().len(), 1);
+    }
+}
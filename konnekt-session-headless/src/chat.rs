@@ -0,0 +1,86 @@
+/// Part of a chat message: plain text or an `@name` mention
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment<'a> {
+    Text(&'a str),
+    Mention(&'a str),
+}
+
+/// Split `text` at mentions of `names` (`@Bob`, case-insensitive).
+///
+/// The longest matching name wins, and an `@` inside a word (an e-mail
+/// address) is not a mention.
+pub fn mention_segments<'a>(text: &'a str, names: &[&str]) -> Vec<Segment<'a>> {
+    let mut segments = Vec::new();
+    let mut plain_from = 0;
+    let mut search_from = 0;
+
+    while let Some(offset) = text[search_from..].find('@') {
+        let at = search_from + offset;
+        search_from = at + 1;
+        if text[..at].ends_with(char::is_alphanumeric) {
+            continue;
+        }
+
+        let after = &text[at + 1..];
+        let Some(len) = names
+            .iter()
+            .filter(|name| !name.is_empty())
+            .filter(|name| {
+                after
+                    .get(..name.len())
+                    .is_some_and(|prefix| prefix.eq_ignore_ascii_case(name))
+                    && !after[name.len()..].starts_with(char::is_alphanumeric)
+            })
+            .map(|name| name.len())
+            .max()
+        else {
+            continue;
+        };
+
+        if at > plain_from {
+            segments.push(Segment::Text(&text[plain_from..at]));
+        }
+        let end = at + 1 + len;
+        segments.push(Segment::Mention(&text[at..end]));
+        plain_from = end;
+        search_from = end;
+    }
+
+    if plain_from < text.len() {
+        segments.push(Segment::Text(&text[plain_from..]));
+    }
+    segments
+}
+
+/// Whether `mention` (`@name`) is of the participant called `name`
+pub fn is_mention_of(mention: &str, name: Option<&str>) -> bool {
+    name.is_some_and(|name| mention[1..].eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mentions_are_split_out() {
+        let names = ["Bob", "Bob Marley", "Alice"];
+
+        assert_eq!(
+            mention_segments("Hi @bob marley and @Alice!", &names),
+            vec![
+                Segment::Text("Hi "),
+                Segment::Mention("@bob marley"),
+                Segment::Text(" and "),
+                Segment::Mention("@Alice"),
+                Segment::Text("!"),
+            ]
+        );
+        // Not a participant, part of a longer word, or an e-mail address
+        assert_eq!(
+            mention_segments("@Carol @Bobby bob@example.org", &names),
+            vec![Segment::Text("@Carol @Bobby bob@example.org")]
+        );
+        assert!(is_mention_of("@ALICE", Some("Alice")));
+        assert!(!is_mention_of("@Alice", None));
+    }
+}
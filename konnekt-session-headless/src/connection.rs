@@ -0,0 +1,95 @@
+use konnekt_session_p2p::{ConnectionQuality, ConnectionStatus, PeerStats};
+
+/// How the session connection is doing overall
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionHealth {
+    /// Guest still looking for the host
+    Connecting,
+    Good,
+    /// Connected, but some peer is slow or missing
+    Degraded,
+    /// Lost signalling, trying to get back
+    Reconnecting,
+    /// Guest lost the host after having joined
+    Offline,
+}
+
+/// A host alone is fine; a guest needs someone to talk to
+pub fn connection_health(
+    is_host: bool,
+    reconnecting: bool,
+    synced: bool,
+    peers: &[PeerStats],
+) -> ConnectionHealth {
+    if reconnecting {
+        return ConnectionHealth::Reconnecting;
+    }
+    let connected = peers
+        .iter()
+        .any(|p| p.status == ConnectionStatus::Connected);
+    if !is_host && !connected {
+        return if synced {
+            ConnectionHealth::Offline
+        } else {
+            ConnectionHealth::Connecting
+        };
+    }
+    if peers.iter().any(|p| p.quality() == ConnectionQuality::Poor) {
+        ConnectionHealth::Degraded
+    } else {
+        ConnectionHealth::Good
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_p2p::PeerId;
+    use konnekt_session_p2p::domain::MatchboxPeerId;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    fn peer(status: ConnectionStatus, rtt_ms: u64) -> PeerStats {
+        PeerStats {
+            peer_id: PeerId::new(MatchboxPeerId(Uuid::new_v4())),
+            participant_id: None,
+            status,
+            rtt: Some(Duration::from_millis(rtt_ms)),
+            last_seen: Duration::ZERO,
+            messages_sent: 0,
+            messages_received: 0,
+        }
+    }
+
+    #[test]
+    fn test_connection_health() {
+        let fast = peer(ConnectionStatus::Connected, 40);
+        let slow = peer(ConnectionStatus::Connected, 900);
+        let gone = peer(ConnectionStatus::TimedOut, 40);
+
+        assert_eq!(
+            connection_health(true, false, true, &[]),
+            ConnectionHealth::Good
+        );
+        assert_eq!(
+            connection_health(false, false, false, &[]),
+            ConnectionHealth::Connecting
+        );
+        assert_eq!(
+            connection_health(false, false, true, &[gone]),
+            ConnectionHealth::Offline
+        );
+        assert_eq!(
+            connection_health(false, false, true, std::slice::from_ref(&fast)),
+            ConnectionHealth::Good
+        );
+        assert_eq!(
+            connection_health(true, false, true, &[fast.clone(), slow]),
+            ConnectionHealth::Degraded
+        );
+        assert_eq!(
+            connection_health(false, true, true, &[fast]),
+            ConnectionHealth::Reconnecting
+        );
+    }
+}
@@ -0,0 +1,153 @@
+/// Why a session can't go on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionError {
+    /// Connecting to the signalling server or creating the lobby failed
+    ConnectionFailed(String),
+    /// Connected, but the lobby never synced from the host
+    SyncTimeout,
+    /// The host sent a lobby this version can't read
+    IncompatibleProtocol(String),
+    /// The host or a co-host removed us from the lobby
+    Kicked,
+    /// The host has been gone for too long
+    LobbyClosed,
+}
+
+impl SessionError {
+    /// Stable name, e.g. for i18n keys and CSS modifiers
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SessionError::ConnectionFailed(_) => "connection_failed",
+            SessionError::SyncTimeout => "sync_timeout",
+            SessionError::IncompatibleProtocol(_) => "incompatible_protocol",
+            SessionError::Kicked => "kicked",
+            SessionError::LobbyClosed => "lobby_closed",
+        }
+    }
+
+    /// Technical details, if there are any
+    pub fn details(&self) -> Option<&str> {
+        match self {
+            SessionError::ConnectionFailed(details)
+            | SessionError::IncompatibleProtocol(details) => Some(details),
+            _ => None,
+        }
+    }
+
+    /// Trying the same session again may help
+    pub fn can_retry(&self) -> bool {
+        matches!(
+            self,
+            SessionError::ConnectionFailed(_) | SessionError::SyncTimeout
+        )
+    }
+
+    /// Joining the lobby again as a new guest may help
+    pub fn can_rejoin(&self) -> bool {
+        matches!(self, SessionError::Kicked)
+    }
+}
+
+/// How long to wait for the lobby and the host before giving up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionErrorOptions {
+    /// Without a synced lobby after this long the sync timed out
+    pub sync_timeout_ms: u32,
+    /// Without the host for this long the lobby counts as closed
+    pub lobby_closed_after_ms: u32,
+}
+
+impl Default for SessionErrorOptions {
+    fn default() -> Self {
+        Self {
+            sync_timeout_ms: 20_000,
+            lobby_closed_after_ms: 60_000,
+        }
+    }
+}
+
+/// What the session looks like to [`session_error`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionHealth {
+    pub runtime_error: Option<String>,
+    pub protocol_error: Option<String>,
+    pub synced: bool,
+    pub sync_timed_out: bool,
+    pub kicked: bool,
+    pub host_gone: bool,
+}
+
+/// The most important thing stopping the session, if anything
+pub fn session_error(health: &SessionHealth) -> Option<SessionError> {
+    if let Some(error) = &health.protocol_error {
+        return Some(SessionError::IncompatibleProtocol(error.clone()));
+    }
+    if health.kicked {
+        return Some(SessionError::Kicked);
+    }
+    if health.synced {
+        return health.host_gone.then_some(SessionError::LobbyClosed);
+    }
+    if let Some(error) = &health.runtime_error {
+        return Some(SessionError::ConnectionFailed(error.clone()));
+    }
+    health.sync_timed_out.then_some(SessionError::SyncTimeout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_error() {
+        let connecting = SessionHealth::default();
+        assert_eq!(session_error(&connecting), None);
+
+        let timed_out = SessionHealth {
+            sync_timed_out: true,
+            ..Default::default()
+        };
+        assert_eq!(session_error(&timed_out), Some(SessionError::SyncTimeout));
+
+        let failed = SessionHealth {
+            runtime_error: Some("no signalling".to_string()),
+            sync_timed_out: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            session_error(&failed),
+            Some(SessionError::ConnectionFailed("no signalling".to_string()))
+        );
+
+        // A synced lobby wins over an error from a failed restart
+        let synced = SessionHealth {
+            runtime_error: Some("no signalling".to_string()),
+            synced: true,
+            ..Default::default()
+        };
+        assert_eq!(session_error(&synced), None);
+
+        let closed = SessionHealth {
+            synced: true,
+            host_gone: true,
+            ..Default::default()
+        };
+        assert_eq!(session_error(&closed), Some(SessionError::LobbyClosed));
+
+        let kicked = SessionHealth {
+            kicked: true,
+            ..closed
+        };
+        assert_eq!(session_error(&kicked), Some(SessionError::Kicked));
+        assert!(SessionError::Kicked.can_rejoin());
+        assert!(!SessionError::Kicked.can_retry());
+
+        let incompatible = SessionHealth {
+            protocol_error: Some("missing field `lobby_id`".to_string()),
+            ..Default::default()
+        };
+        let error = session_error(&incompatible).unwrap();
+        assert_eq!(error.kind(), "incompatible_protocol");
+        assert_eq!(error.details(), Some("missing field `lobby_id`"));
+    }
+}
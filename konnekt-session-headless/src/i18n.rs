@@ -0,0 +1,469 @@
+//! Component texts in English and German, shared by the frontends
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::Arc;
+
+const ENGLISH: &[(&str, &str)] = &[
+    ("lobby.title", "Lobby"),
+    ("lobby.syncing", "Syncing lobby..."),
+    ("participants.title", "Participants ({count})"),
+    ("participants.host", "Host"),
+    ("participants.you", "you"),
+    ("participants.typing", "typing…"),
+    ("participants.answering", "answering…"),
+    ("participants.active", "Active"),
+    ("participants.spectating", "Spectating"),
+    ("participants.tooltip", "ID: {id}\nJoined: {joined}"),
+    ("participants.score", "{score} pts"),
+    ("participants.search", "Search participants…"),
+    ("participants.sort", "Sort by"),
+    ("participants.group", "Group by"),
+    ("participants.no_match", "Nobody matches \"{query}\""),
+    ("participants.sort_joined", "Join time"),
+    ("participants.sort_name", "Name"),
+    ("participants.sort_score", "Score"),
+    ("participants.group_none", "No groups"),
+    ("participants.group_mode", "By mode"),
+    ("participants.group_team", "By team"),
+    ("participants.no_team", "No team"),
+    ("pending.saving", "Waiting for the host…"),
+    ("pending.rolled_back", "The host did not confirm this"),
+    ("toast.joined", "{name} joined"),
+    ("toast.left", "{name} left"),
+    ("toast.removed", "You were removed from the lobby"),
+    ("toast.run_started", "{name} started"),
+    ("toast.run_ended", "{name} finished"),
+    ("toast.reconnecting", "Connection lost. Reconnecting…"),
+    ("toast.reconnected", "Reconnected"),
+    ("toast.error", "Connection error: {error}"),
+    ("toast.dismiss", "Dismiss"),
+    ("ready.title", "Ready? ({ready}/{count})"),
+    ("ready.ready", "I'm ready"),
+    ("ready.not_ready", "Not ready yet"),
+    ("ready.is_ready", "ready"),
+    ("ready.is_waiting", "not ready yet"),
+    ("ready.start", "Start countdown"),
+    ("ready.start_anyway", "Start anyway ({count} not ready)"),
+    ("countdown.title", "Get ready…"),
+    ("countdown.go", "Go!"),
+    ("avatar.title", "Avatar"),
+    ("avatar.image_url", "Image URL (https://…)"),
+    ("avatar.use_image", "Use image"),
+    ("avatar.reset", "Use generated avatar"),
+    ("participation.join", "Join activities"),
+    ("participation.spectate", "Spectate instead"),
+    ("spectator.title", "👀 You are spectating"),
+    (
+        "spectator.join_after_run",
+        "You can join once this activity ends",
+    ),
+    ("spectator.prompt", "Prompt"),
+    ("spectator.progress", "{submitted} of {count} answered"),
+    ("spectator.progress_label", "Answers so far"),
+    ("session_error.connection_failed.title", "Couldn't connect"),
+    (
+        "session_error.connection_failed.message",
+        "The session could not be started. Check your connection and try again.",
+    ),
+    ("session_error.sync_timeout.title", "The lobby didn't load"),
+    (
+        "session_error.sync_timeout.message",
+        "We're connected, but the host never sent the lobby.",
+    ),
+    (
+        "session_error.incompatible_protocol.title",
+        "Incompatible version",
+    ),
+    (
+        "session_error.incompatible_protocol.message",
+        "The host runs a version of the app this one can't talk to. Reload the page to update.",
+    ),
+    ("session_error.kicked.title", "You were removed"),
+    (
+        "session_error.kicked.message",
+        "The host removed you from the lobby.",
+    ),
+    ("session_error.lobby_closed.title", "The lobby has closed"),
+    (
+        "session_error.lobby_closed.message",
+        "The host has been gone for a while.",
+    ),
+    ("session_error.details", "Details"),
+    ("session_error.retry", "Try again"),
+    ("session_error.rejoin", "Rejoin"),
+    ("session_error.home", "Back to start"),
+    (
+        "session.creating",
+        "Creating lobby and waiting for peers...",
+    ),
+    ("session.connecting", "Connecting to host..."),
+    ("session.syncing", "Syncing lobby from host..."),
+    ("status.connecting", "Connecting"),
+    ("status.syncing", "Syncing"),
+    ("status.ready", "Ready"),
+    ("status.degraded", "Connection unstable"),
+    ("results_chart.title", "Results so far"),
+    ("results_chart.distribution", "Score distribution"),
+    ("results_chart.results", "{count} results"),
+    ("results_chart.progression", "Total score per round"),
+    ("results_chart.round", "Round {round}"),
+    ("analytics.title", "Session analytics"),
+    ("analytics.empty", "No activities played yet."),
+    ("analytics.answer_time", "Average answer time"),
+    ("analytics.participation", "Participation"),
+    ("analytics.left", "Left the session"),
+    ("analytics.answered", "{submitted}/{eligible} answered"),
+    ("analytics.dropped", "{count} dropped"),
+    ("analytics.left_during", "left during {activity}"),
+    ("analytics.left_after", "left after {count} activities"),
+    ("queue_editor.title", "Reorder plan"),
+    (
+        "queue_editor.hint",
+        "Drag activities or use the arrows to change their order.",
+    ),
+    ("queue_editor.move_up", "Move {name} up"),
+    ("queue_editor.move_down", "Move {name} down"),
+    ("error.connection_failed", "Connection failed."),
+    (
+        "error.invalid_session",
+        "Invalid session reference '{reference}'. Expected UUID or room URL ending with UUID.",
+    ),
+    (
+        "error.join_failed",
+        "Failed to join session {session}: {error}",
+    ),
+    (
+        "error.host_failed",
+        "Failed to create host session: {error}",
+    ),
+    ("error.lobby_failed", "Failed to create lobby: {error}"),
+];
+
+const GERMAN: &[(&str, &str)] = &[
+    ("lobby.title", "Lobby"),
+    ("lobby.syncing", "Lobby wird synchronisiert..."),
+    ("participants.title", "Teilnehmende ({count})"),
+    ("participants.host", "Host"),
+    ("participants.you", "du"),
+    ("participants.typing", "schreibt…"),
+    ("participants.answering", "antwortet…"),
+    ("participants.active", "Aktiv"),
+    ("participants.spectating", "Schaut zu"),
+    ("participants.tooltip", "ID: {id}\nBeigetreten: {joined}"),
+    ("participants.score", "{score} Pkt."),
+    ("participants.search", "Teilnehmende suchen…"),
+    ("participants.sort", "Sortieren nach"),
+    ("participants.group", "Gruppieren nach"),
+    ("participants.no_match", "Niemand passt zu \"{query}\""),
+    ("participants.sort_joined", "Beitrittszeit"),
+    ("participants.sort_name", "Name"),
+    ("participants.sort_score", "Punkte"),
+    ("participants.group_none", "Keine Gruppen"),
+    ("participants.group_mode", "Nach Modus"),
+    ("participants.group_team", "Nach Team"),
+    ("participants.no_team", "Kein Team"),
+    ("pending.saving", "Warte auf den Host…"),
+    ("pending.rolled_back", "Der Host hat das nicht bestätigt"),
+    ("toast.joined", "{name} ist beigetreten"),
+    ("toast.left", "{name} hat die Lobby verlassen"),
+    ("toast.removed", "Du wurdest aus der Lobby entfernt"),
+    ("toast.run_started", "{name} hat begonnen"),
+    ("toast.run_ended", "{name} ist beendet"),
+    (
+        "toast.reconnecting",
+        "Verbindung verloren. Verbinde erneut…",
+    ),
+    ("toast.reconnected", "Wieder verbunden"),
+    ("toast.error", "Verbindungsfehler: {error}"),
+    ("toast.dismiss", "Schließen"),
+    ("ready.title", "Bereit? ({ready}/{count})"),
+    ("ready.ready", "Ich bin bereit"),
+    ("ready.not_ready", "Noch nicht bereit"),
+    ("ready.is_ready", "bereit"),
+    ("ready.is_waiting", "noch nicht bereit"),
+    ("ready.start", "Countdown starten"),
+    (
+        "ready.start_anyway",
+        "Trotzdem starten ({count} nicht bereit)",
+    ),
+    ("countdown.title", "Macht euch bereit…"),
+    ("countdown.go", "Los!"),
+    ("avatar.title", "Avatar"),
+    ("avatar.image_url", "Bild-URL (https://…)"),
+    ("avatar.use_image", "Bild verwenden"),
+    ("avatar.reset", "Generierten Avatar verwenden"),
+    ("participation.join", "Bei Aktivitäten mitmachen"),
+    ("participation.spectate", "Lieber zuschauen"),
+    ("spectator.title", "👀 Du schaust zu"),
+    (
+        "spectator.join_after_run",
+        "Du kannst mitmachen, sobald diese Aktivität endet",
+    ),
+    ("spectator.prompt", "Aufgabe"),
+    (
+        "spectator.progress",
+        "{submitted} von {count} haben geantwortet",
+    ),
+    ("spectator.progress_label", "Bisherige Antworten"),
+    (
+        "session_error.connection_failed.title",
+        "Verbindung fehlgeschlagen",
+    ),
+    (
+        "session_error.connection_failed.message",
+        "Die Sitzung konnte nicht gestartet werden. Prüfe deine Verbindung und versuche es erneut.",
+    ),
+    (
+        "session_error.sync_timeout.title",
+        "Die Lobby wurde nicht geladen",
+    ),
+    (
+        "session_error.sync_timeout.message",
+        "Die Verbindung steht, aber der Host hat die Lobby nie geschickt.",
+    ),
+    (
+        "session_error.incompatible_protocol.title",
+        "Inkompatible Version",
+    ),
+    (
+        "session_error.incompatible_protocol.message",
+        "Der Host nutzt eine Version der App, mit der diese nicht sprechen kann. Lade die Seite neu, um sie zu aktualisieren.",
+    ),
+    ("session_error.kicked.title", "Du wurdest entfernt"),
+    (
+        "session_error.kicked.message",
+        "Der Host hat dich aus der Lobby entfernt.",
+    ),
+    (
+        "session_error.lobby_closed.title",
+        "Die Lobby wurde geschlossen",
+    ),
+    (
+        "session_error.lobby_closed.message",
+        "Der Host ist seit einer Weile weg.",
+    ),
+    ("session_error.details", "Details"),
+    ("session_error.retry", "Erneut versuchen"),
+    ("session_error.rejoin", "Wieder beitreten"),
+    ("session_error.home", "Zurück zum Start"),
+    (
+        "session.creating",
+        "Lobby wird erstellt, warte auf Teilnehmende...",
+    ),
+    ("session.connecting", "Verbinde mit dem Host..."),
+    ("session.syncing", "Lobby wird vom Host synchronisiert..."),
+    ("status.connecting", "Verbinden"),
+    ("status.syncing", "Synchronisieren"),
+    ("status.ready", "Bereit"),
+    ("status.degraded", "Verbindung instabil"),
+    ("results_chart.title", "Bisherige Ergebnisse"),
+    ("results_chart.distribution", "Punkteverteilung"),
+    ("results_chart.results", "{count} Ergebnisse"),
+    ("results_chart.progression", "Gesamtpunkte pro Runde"),
+    ("results_chart.round", "Runde {round}"),
+    ("analytics.title", "Sitzungsanalyse"),
+    ("analytics.empty", "Noch keine Aktivitäten gespielt."),
+    ("analytics.answer_time", "Durchschnittliche Antwortzeit"),
+    ("analytics.participation", "Beteiligung"),
+    ("analytics.left", "Haben die Sitzung verlassen"),
+    ("analytics.answered", "{submitted}/{eligible} geantwortet"),
+    ("analytics.dropped", "{count} ausgestiegen"),
+    ("analytics.left_during", "während {activity} gegangen"),
+    ("analytics.left_after", "nach {count} Aktivitäten gegangen"),
+    ("queue_editor.title", "Plan umsortieren"),
+    (
+        "queue_editor.hint",
+        "Ziehe Aktivitäten oder nutze die Pfeile, um die Reihenfolge zu ändern.",
+    ),
+    ("queue_editor.move_up", "{name} nach oben"),
+    ("queue_editor.move_down", "{name} nach unten"),
+    ("error.connection_failed", "Verbindung fehlgeschlagen."),
+    (
+        "error.invalid_session",
+        "Ungültige Sitzungsangabe '{reference}'. Erwartet wird eine UUID oder eine Raum-URL, die mit einer UUID endet.",
+    ),
+    (
+        "error.join_failed",
+        "Beitritt zur Sitzung {session} fehlgeschlagen: {error}",
+    ),
+    (
+        "error.host_failed",
+        "Host-Sitzung konnte nicht erstellt werden: {error}",
+    ),
+    (
+        "error.lobby_failed",
+        "Lobby konnte nicht erstellt werden: {error}",
+    ),
+];
+
+/// Translations of the component texts for one locale
+///
+/// A catalog for `"de"` applies to every German locale (`"de-AT"`, ...),
+/// one for `"de-AT"` only to that locale and on top of `"de"`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Catalog {
+    locale: String,
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    pub fn new(locale: impl Into<String>) -> Self {
+        Self {
+            locale: locale.into(),
+            messages: HashMap::new(),
+        }
+    }
+
+    pub fn english() -> Self {
+        Self::from_pairs("en", ENGLISH)
+    }
+
+    pub fn german() -> Self {
+        Self::from_pairs("de", GERMAN)
+    }
+
+    /// Add or replace the text of `key`
+    pub fn with_message(mut self, key: impl Into<String>, text: impl Into<String>) -> Self {
+        self.messages.insert(key.into(), text.into());
+        self
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+
+    fn from_pairs(locale: &'static str, pairs: &[(&str, &str)]) -> Self {
+        pairs
+            .iter()
+            .fold(Self::new(locale), |catalog, (key, text)| {
+                catalog.with_message(*key, *text)
+            })
+    }
+
+    fn covers_language(&self, locale: &str) -> bool {
+        self.locale.eq_ignore_ascii_case(language(locale))
+    }
+
+    fn covers_exactly(&self, locale: &str) -> bool {
+        self.locale.eq_ignore_ascii_case(locale) && !self.covers_language(locale)
+    }
+}
+
+/// `"de"` for `"de-AT"` or `"de_AT"`
+fn language(locale: &str) -> &str {
+    locale.split(['-', '_']).next().unwrap_or(locale)
+}
+
+/// Texts resolved for one locale
+///
+/// Keys missing in the locale fall back to English and then to the key
+/// itself, so a half-translated catalog still renders.
+#[derive(Debug, Clone, PartialEq)]
+pub struct I18n {
+    locale: String,
+    messages: Arc<HashMap<String, String>>,
+}
+
+impl I18n {
+    /// Resolve `locale` against the built-in catalogs and `catalogs`, where
+    /// later catalogs override earlier ones
+    pub fn new(locale: impl Into<String>, catalogs: &[Catalog]) -> Self {
+        let locale = locale.into().replace('_', "-");
+        let builtin = [Catalog::english(), Catalog::german()];
+        let all: Vec<&Catalog> = builtin.iter().chain(catalogs).collect();
+
+        let mut messages = HashMap::new();
+        let mut merge = |covers: &dyn Fn(&Catalog) -> bool| {
+            for catalog in all.iter().filter(|catalog| covers(catalog)) {
+                messages.extend(catalog.messages.clone());
+            }
+        };
+        merge(&|catalog| catalog.covers_language("en"));
+        merge(&|catalog| catalog.covers_language(&locale));
+        merge(&|catalog| catalog.covers_exactly(&locale));
+
+        Self {
+            locale,
+            messages: Arc::new(messages),
+        }
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Text of `key`
+    pub fn t(&self, key: &str) -> String {
+        self.messages
+            .get(key)
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    /// Text of `key` with each `{name}` placeholder replaced by its argument
+    pub fn t_with(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        args.iter().fold(self.t(key), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), &value.to_string())
+        })
+    }
+}
+
+impl Default for I18n {
+    fn default() -> Self {
+        Self::new("en", &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolves_locale_with_fallbacks() {
+        let catalogs = [
+            Catalog::new("de-AT").with_message("participants.you", "du (AT)"),
+            Catalog::new("en").with_message("lobby.rules", "Rules"),
+        ];
+
+        let austrian = I18n::new("de-AT", &catalogs);
+        assert_eq!(austrian.t("participants.you"), "du (AT)");
+        assert_eq!(austrian.t("participants.active"), "Aktiv");
+        assert_eq!(austrian.t("lobby.rules"), "Rules");
+        assert_eq!(austrian.t("no.such.key"), "no.such.key");
+
+        let german = I18n::new("de_DE", &catalogs);
+        assert_eq!(german.t("participants.you"), "du");
+
+        let unknown = I18n::new("fr", &[]);
+        assert_eq!(unknown.t("participants.active"), "Active");
+
+        assert_eq!(
+            I18n::default().t_with("participants.title", &[("count", &3)]),
+            "Participants (3)"
+        );
+    }
+
+    #[test]
+    fn test_german_catalog_is_complete() {
+        let german = Catalog::german();
+        for (key, english) in ENGLISH {
+            let text = german
+                .get(key)
+                .unwrap_or_else(|| panic!("missing German text for {}", key));
+            for placeholder in english.split('{').skip(1) {
+                let name = placeholder.split('}').next().unwrap();
+                assert!(
+                    text.contains(&format!("{{{}}}", name)),
+                    "{}: {{{}}}",
+                    key,
+                    name
+                );
+            }
+        }
+    }
+}
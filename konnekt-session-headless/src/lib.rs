@@ -0,0 +1,33 @@
+//! # Konnekt Session Headless
+//!
+//! The UI-framework-agnostic half of the session components: the runtime
+//! that drives a session loop from a UI timer, the optimistic command
+//! tracking, the participant, status and connection views, chat mentions,
+//! the generated avatars and the text catalogs.
+//!
+//! `konnekt-session-yew` and `konnekt-session-leptos` are thin bindings of
+//! this crate to their framework's state and components.
+
+mod chat;
+mod connection;
+mod error;
+mod i18n;
+mod participants;
+mod pending;
+mod runtime;
+mod status;
+
+pub use chat::{Segment, is_mention_of, mention_segments};
+pub use connection::{ConnectionHealth, connection_health};
+pub use error::{SessionError, SessionErrorOptions, SessionHealth, session_error};
+pub use i18n::{Catalog, I18n};
+pub use participants::{ParticipantView, avatar_hue, initials, participant_views};
+pub use pending::{
+    CONFIRM_TIMEOUT_MS, PendingCommand, PendingStatus, ROLLED_BACK_VISIBLE_MS, apply_pending,
+    reconcile,
+};
+pub use runtime::{
+    ActiveRunSnapshot, LoggedEvent, RuntimeError, RuntimeSnapshot, SessionRuntime,
+    parse_session_reference,
+};
+pub use status::{LobbyPhase, SessionStatus, StatusInputs, lobby_phase, session_status};
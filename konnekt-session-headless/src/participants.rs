@@ -0,0 +1,112 @@
+use konnekt_session_core::{Lobby, ParticipantAvatar, Timestamp};
+use konnekt_session_p2p::Presence;
+use uuid::Uuid;
+
+/// A participant as shown in a participant list
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParticipantView {
+    pub id: Uuid,
+    pub name: String,
+    pub is_host: bool,
+    pub is_co_host: bool,
+    /// The local participant
+    pub is_me: bool,
+    /// Takes part in activities (not spectating)
+    pub active: bool,
+    /// What the participant is doing right now (typing, answering)
+    pub presence: Option<Presence>,
+    pub joined_at: Timestamp,
+    /// The avatar the participant picked (`None`: generated from the ID)
+    pub avatar: Option<ParticipantAvatar>,
+}
+
+/// Participants of `lobby`, host first, then in the order they joined
+pub fn participant_views(
+    lobby: &Lobby,
+    local_id: Option<Uuid>,
+    presence: &[(Uuid, Presence)],
+) -> Vec<ParticipantView> {
+    let mut views: Vec<ParticipantView> = lobby
+        .participants()
+        .values()
+        .map(|participant| ParticipantView {
+            id: participant.id(),
+            name: participant.name().to_string(),
+            is_host: participant.is_host(),
+            is_co_host: lobby.is_co_host(participant.id()),
+            is_me: Some(participant.id()) == local_id,
+            active: participant.can_submit_results(),
+            presence: presence
+                .iter()
+                .find(|(id, _)| *id == participant.id())
+                .map(|(_, presence)| *presence),
+            joined_at: participant.joined_at(),
+            avatar: participant.avatar().cloned(),
+        })
+        .collect();
+    views.sort_by_key(|view| (!view.is_host, view.joined_at, view.id));
+    views
+}
+
+/// Hue (0..360) of the generated avatar, the same for an ID everywhere
+pub fn avatar_hue(participant_id: Uuid) -> u16 {
+    let id = participant_id.as_u128();
+    ((id ^ (id >> 64)) % 360) as u16
+}
+
+/// Up to two initials of `name`
+pub fn initials(name: &str) -> String {
+    let mut words = name.split_whitespace();
+    let first = words.next().and_then(|w| w.chars().next());
+    let last = words.last().and_then(|w| w.chars().next());
+    first
+        .into_iter()
+        .chain(last)
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_core::{LobbyRole, Participant};
+
+    #[test]
+    fn test_participant_views() {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let mut lobby = Lobby::new("Test Lobby".to_string(), host).unwrap();
+        let guest = |name: &str, joined_at| {
+            Participant::with_timestamp(
+                name.to_string(),
+                LobbyRole::Guest,
+                Timestamp::from_millis(joined_at),
+            )
+            .unwrap()
+        };
+        let bob = guest("Bob", 1_000);
+        let bob_id = bob.id();
+        lobby.add_guest(guest("Charlie", 2_000)).unwrap();
+        lobby.add_guest(bob).unwrap();
+
+        let views = participant_views(&lobby, Some(bob_id), &[(bob_id, Presence::Typing)]);
+
+        let names: Vec<_> = views.iter().map(|view| view.name.as_str()).collect();
+        assert_eq!(names, ["Alice", "Bob", "Charlie"]);
+        assert!(views[0].is_host);
+        assert!(views[1].is_me);
+        assert_eq!(views[1].presence, Some(Presence::Typing));
+        assert_eq!(views[2].presence, None);
+    }
+
+    #[test]
+    fn test_generated_avatar() {
+        assert_eq!(initials("Ada Lovelace"), "AL");
+        assert_eq!(initials("bob"), "B");
+        assert_eq!(initials("Jean Paul Sartre"), "JS");
+        assert_eq!(initials("  "), "");
+
+        let id = Uuid::new_v4();
+        assert_eq!(avatar_hue(id), avatar_hue(id));
+        assert!(avatar_hue(id) < 360);
+    }
+}
@@ -0,0 +1,492 @@
+use konnekt_session_core::domain::{ActivityConfig, ActivityId, ActivityResult};
+use konnekt_session_core::{ChatMessage, DomainCommand, Lobby, ParticipationMode, Timestamp};
+use konnekt_session_p2p::FailedCommand;
+use uuid::Uuid;
+
+use crate::ActiveRunSnapshot;
+
+/// Commands the host has not confirmed after this long are rolled back
+pub const CONFIRM_TIMEOUT_MS: u64 = 5_000;
+
+/// Rolled back commands stay listed this long so the UI can say so
+pub const ROLLED_BACK_VISIBLE_MS: u64 = 4_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PendingStatus {
+    /// Shown as done, waiting for the host
+    Pending,
+    /// Undone again: the host rejected it (with its reason) or never
+    /// confirmed it (`None`)
+    RolledBack { reason: Option<String> },
+}
+
+/// A command whose effect is shown before the host confirmed it
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingCommand {
+    pub id: Uuid,
+    pub command: DomainCommand,
+    pub status: PendingStatus,
+    /// When it was sent, or rolled back (`Timestamp::now` in ms)
+    since: u64,
+    change: Change,
+}
+
+/// What the command changes, and what the authoritative lobby shows once
+/// it went through
+#[derive(Debug, Clone, PartialEq)]
+enum Change {
+    Mode {
+        participant_id: Uuid,
+        mode: ParticipationMode,
+    },
+    Queued(ActivityConfig),
+    Moved {
+        activity_id: ActivityId,
+        to_index: usize,
+        requester_id: Uuid,
+    },
+    /// The message and how often its author had posted that text by then
+    Chat {
+        message: ChatMessage,
+        count: usize,
+    },
+    Submitted(ActivityResult),
+}
+
+impl PendingCommand {
+    /// Track `command` against the lobby as the user sees it, if its effect
+    /// can be shown right away and looks valid
+    pub fn track(
+        command: DomainCommand,
+        lobby: &Lobby,
+        active_run: Option<&ActiveRunSnapshot>,
+        now: u64,
+    ) -> Option<Self> {
+        let change = match &command {
+            DomainCommand::ToggleParticipationMode {
+                participant_id,
+                requester_id,
+                ..
+            } => {
+                let mode = lobby
+                    .clone()
+                    .toggle_participation_mode(*participant_id, *requester_id)
+                    .ok()?;
+                Change::Mode {
+                    participant_id: *participant_id,
+                    mode,
+                }
+            }
+            DomainCommand::QueueActivity { config, .. } => Change::Queued(config.clone()),
+            DomainCommand::MoveQueuedActivity {
+                activity_id,
+                to_index,
+                requester_id,
+                ..
+            } => Change::Moved {
+                activity_id: *activity_id,
+                to_index: *to_index,
+                requester_id: *requester_id,
+            },
+            DomainCommand::SendChatMessage {
+                author_id, text, ..
+            } => {
+                let sent_at = Timestamp::from_millis(now);
+                let message = ChatMessage::new_at(*author_id, text.clone(), sent_at).ok()?;
+                Change::Chat {
+                    count: chat_count(lobby, &message) + 1,
+                    message,
+                }
+            }
+            DomainCommand::SubmitResult { run_id, result, .. } => {
+                let run = active_run.filter(|run| run.run_id == *run_id)?;
+                if run
+                    .results
+                    .iter()
+                    .any(|r| r.participant_id == result.participant_id)
+                {
+                    return None;
+                }
+                Change::Submitted(result.clone())
+            }
+            _ => return None,
+        };
+
+        let mut view = lobby.clone();
+        let mut run = active_run.cloned();
+        change.apply(&mut view, &mut run).then(|| Self {
+            id: Uuid::new_v4(),
+            command,
+            status: PendingStatus::Pending,
+            since: now,
+            change,
+        })
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.status == PendingStatus::Pending
+    }
+
+    pub fn is_rolled_back(&self) -> bool {
+        matches!(self.status, PendingStatus::RolledBack { .. })
+    }
+
+    /// Participant whose participation mode is toggled
+    pub fn toggled_participant(&self) -> Option<Uuid> {
+        match &self.change {
+            Change::Mode { participant_id, .. } => Some(*participant_id),
+            _ => None,
+        }
+    }
+
+    /// Activity being queued
+    pub fn queued_activity(&self) -> Option<&ActivityConfig> {
+        match &self.change {
+            Change::Queued(config) => Some(config),
+            _ => None,
+        }
+    }
+
+    /// Queued activity being moved
+    pub fn moved_activity(&self) -> Option<ActivityId> {
+        match &self.change {
+            Change::Moved { activity_id, .. } => Some(*activity_id),
+            _ => None,
+        }
+    }
+
+    /// Chat message being posted, with the ID it has until the host's copy
+    /// replaces it
+    pub fn chat_message(&self) -> Option<&ChatMessage> {
+        match &self.change {
+            Change::Chat { message, .. } => Some(message),
+            _ => None,
+        }
+    }
+
+    /// Result being submitted
+    pub fn submitted_result(&self) -> Option<&ActivityResult> {
+        match &self.change {
+            Change::Submitted(result) => Some(result),
+            _ => None,
+        }
+    }
+
+    /// Name the domain reports failures of this command under
+    fn command_name(&self) -> &'static str {
+        match &self.change {
+            Change::Mode { .. } => "ToggleParticipationMode",
+            Change::Queued(_) => "QueueActivity",
+            Change::Moved { .. } => "MoveQueuedActivity",
+            Change::Chat { .. } => "SendChatMessage",
+            Change::Submitted(_) => "SubmitResult",
+        }
+    }
+
+    fn is_confirmed(&self, lobby: &Lobby, active_run: Option<&ActiveRunSnapshot>) -> bool {
+        match &self.change {
+            Change::Mode {
+                participant_id,
+                mode,
+            } => lobby
+                .participants()
+                .get(participant_id)
+                .is_none_or(|p| p.participation_mode() == *mode),
+            Change::Queued(config) => {
+                lobby.activity_queue().iter().any(|a| a.id == config.id)
+                    || active_run.is_some_and(|run| run.name == config.name)
+            }
+            // Gone from the queue (started or removed) counts as settled
+            Change::Moved {
+                activity_id,
+                to_index,
+                ..
+            } => {
+                let queue = lobby.activity_queue();
+                queue
+                    .iter()
+                    .position(|a| a.id == *activity_id)
+                    .is_none_or(|index| index == (*to_index).min(queue.len() - 1))
+            }
+            Change::Chat { message, count } => chat_count(lobby, message) >= *count,
+            // Once the run is over the result no longer shows either way
+            Change::Submitted(result) => active_run
+                .filter(|run| run.run_id == result.run_id)
+                .is_none_or(|run| {
+                    run.results
+                        .iter()
+                        .any(|r| r.participant_id == result.participant_id)
+                }),
+        }
+    }
+}
+
+impl Change {
+    /// Show the change in `lobby` and `active_run`; `false` if it does not
+    /// apply (anymore)
+    fn apply(&self, lobby: &mut Lobby, active_run: &mut Option<ActiveRunSnapshot>) -> bool {
+        match self {
+            Change::Mode {
+                participant_id,
+                mode,
+            } => match lobby.participants_mut().get_mut(participant_id) {
+                Some(participant) => {
+                    participant.force_participation_mode(*mode);
+                    true
+                }
+                None => false,
+            },
+            Change::Queued(config) => {
+                lobby.activity_queue().iter().any(|a| a.id == config.id)
+                    || lobby.queue_activity(config.clone()).is_ok()
+            }
+            Change::Moved {
+                activity_id,
+                to_index,
+                requester_id,
+            } => lobby
+                .move_queued_activity(*activity_id, *to_index, *requester_id)
+                .is_ok(),
+            Change::Chat { message, .. } => lobby.post_chat_message(message.clone()).is_ok(),
+            Change::Submitted(result) => match active_run {
+                Some(run) if run.run_id == result.run_id => {
+                    if !run
+                        .results
+                        .iter()
+                        .any(|r| r.participant_id == result.participant_id)
+                    {
+                        run.results.push(result.clone());
+                    }
+                    true
+                }
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Messages of `message`'s author with its text
+fn chat_count(lobby: &Lobby, message: &ChatMessage) -> usize {
+    lobby
+        .chat_messages()
+        .iter()
+        .filter(|m| m.author_id() == message.author_id() && m.text() == message.text())
+        .count()
+}
+
+/// `lobby` and `active_run` with the effects of the still pending commands
+pub fn apply_pending(
+    pending: &[PendingCommand],
+    mut lobby: Option<Lobby>,
+    mut active_run: Option<ActiveRunSnapshot>,
+) -> (Option<Lobby>, Option<ActiveRunSnapshot>) {
+    if let Some(lobby) = lobby.as_mut() {
+        for command in pending.iter().filter(|c| c.is_pending()) {
+            command.change.apply(lobby, &mut active_run);
+        }
+    }
+    (lobby, active_run)
+}
+
+/// Settle `pending` against the authoritative state: drop confirmed
+/// commands, roll back failed and overdue ones, and forget rolled back ones
+/// after a while
+///
+/// `failed` are the commands the host reported as failed.
+/// Returns whether anything changed.
+pub fn reconcile(
+    pending: &mut Vec<PendingCommand>,
+    lobby: Option<&Lobby>,
+    active_run: Option<&ActiveRunSnapshot>,
+    failed: &[FailedCommand],
+    now: u64,
+) -> bool {
+    let before = pending.len();
+    let mut changed = false;
+
+    for failure in failed {
+        if let Some(command) = pending
+            .iter_mut()
+            .find(|c| c.is_pending() && c.command_name() == failure.command.name())
+        {
+            tracing::warn!(
+                "↩️ Rolling back {}: {}",
+                failure.command.name(),
+                failure.error
+            );
+            command.status = PendingStatus::RolledBack {
+                reason: Some(failure.error.to_string()),
+            };
+            command.since = now;
+            changed = true;
+        }
+    }
+
+    pending.retain_mut(|command| match command.status {
+        PendingStatus::Pending => {
+            if lobby.is_some_and(|lobby| command.is_confirmed(lobby, active_run)) {
+                return false;
+            }
+            if now.saturating_sub(command.since) >= CONFIRM_TIMEOUT_MS {
+                tracing::warn!("↩️ Rolling back {}: not confirmed", command.command_name());
+                command.status = PendingStatus::RolledBack { reason: None };
+                command.since = now;
+                changed = true;
+            }
+            true
+        }
+        PendingStatus::RolledBack { .. } => {
+            now.saturating_sub(command.since) < ROLLED_BACK_VISIBLE_MS
+        }
+    });
+
+    changed || pending.len() != before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_core::Participant;
+    use konnekt_session_core::domain::ChatError;
+
+    #[test]
+    fn test_pending_commands_confirm_or_roll_back() {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let host_id = host.id();
+        let lobby = Lobby::new("Test Lobby".to_string(), host).unwrap();
+        let lobby_id = lobby.id();
+
+        let toggle = DomainCommand::ToggleParticipationMode {
+            lobby_id,
+            participant_id: host_id,
+            requester_id: host_id,
+        };
+        let chat = DomainCommand::SendChatMessage {
+            lobby_id,
+            author_id: host_id,
+            text: "Hi".to_string(),
+        };
+        let mut pending: Vec<_> = [toggle, chat.clone()]
+            .into_iter()
+            .map(|command| PendingCommand::track(command, &lobby, None, 0).unwrap())
+            .collect();
+
+        let (view, _) = apply_pending(&pending, Some(lobby.clone()), None);
+        let view = view.unwrap();
+        assert_eq!(
+            view.participants()[&host_id].participation_mode(),
+            ParticipationMode::Spectating
+        );
+        assert_eq!(view.chat_messages().len(), 1);
+
+        // The host applied the toggle but rejected the message
+        let mut confirmed = lobby.clone();
+        confirmed
+            .toggle_participation_mode(host_id, host_id)
+            .unwrap();
+        let failed = [FailedCommand {
+            command: chat.clone(),
+            error: ChatError::MessageTooLong.into(),
+        }];
+        assert!(reconcile(
+            &mut pending,
+            Some(&confirmed),
+            None,
+            &failed,
+            100
+        ));
+        assert_eq!(pending.len(), 1);
+        assert_eq!(
+            pending[0].status,
+            PendingStatus::RolledBack {
+                reason: Some(ChatError::MessageTooLong.to_string())
+            }
+        );
+        let (view, _) = apply_pending(&pending, Some(confirmed.clone()), None);
+        assert!(view.unwrap().chat_messages().is_empty());
+
+        assert!(!reconcile(&mut pending, Some(&confirmed), None, &[], 200));
+        assert!(reconcile(
+            &mut pending,
+            Some(&confirmed),
+            None,
+            &[],
+            100 + ROLLED_BACK_VISIBLE_MS
+        ));
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn test_unconfirmed_command_times_out() {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let host_id = host.id();
+        let lobby = Lobby::new("Test Lobby".to_string(), host).unwrap();
+        let config =
+            ActivityConfig::new("quiz".to_string(), "Quiz".to_string(), Default::default());
+        let queue = DomainCommand::QueueActivity {
+            lobby_id: lobby.id(),
+            config,
+        };
+        let mut pending = vec![PendingCommand::track(queue, &lobby, None, 0).unwrap()];
+
+        assert!(!reconcile(&mut pending, Some(&lobby), None, &[], 1_000));
+        assert!(pending[0].is_pending());
+        assert!(reconcile(
+            &mut pending,
+            Some(&lobby),
+            None,
+            &[],
+            CONFIRM_TIMEOUT_MS
+        ));
+        assert_eq!(
+            pending[0].status,
+            PendingStatus::RolledBack { reason: None }
+        );
+
+        // A stranger cannot be toggled, so nothing is shown for it
+        let stranger = Uuid::new_v4();
+        let toggle = DomainCommand::ToggleParticipationMode {
+            lobby_id: lobby.id(),
+            participant_id: stranger,
+            requester_id: host_id,
+        };
+        assert!(PendingCommand::track(toggle, &lobby, None, 0).is_none());
+    }
+
+    #[test]
+    fn test_moved_activity_shows_until_confirmed() {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let host_id = host.id();
+        let mut lobby = Lobby::new("Test Lobby".to_string(), host).unwrap();
+        for name in ["First", "Second"] {
+            lobby
+                .queue_activity(ActivityConfig::new(
+                    "quiz".to_string(),
+                    name.to_string(),
+                    Default::default(),
+                ))
+                .unwrap();
+        }
+        let second = lobby.activity_queue()[1].id;
+        let move_second = |requester_id| DomainCommand::MoveQueuedActivity {
+            lobby_id: lobby.id(),
+            activity_id: second,
+            to_index: 0,
+            requester_id,
+        };
+
+        // Only the host and co-hosts may reorder
+        assert!(PendingCommand::track(move_second(Uuid::new_v4()), &lobby, None, 0).is_none());
+
+        let mut pending =
+            vec![PendingCommand::track(move_second(host_id), &lobby, None, 0).unwrap()];
+        let (view, _) = apply_pending(&pending, Some(lobby.clone()), None);
+        assert_eq!(view.unwrap().activity_queue()[0].id, second);
+
+        assert!(!reconcile(&mut pending, Some(&lobby), None, &[], 100));
+        let mut confirmed = lobby.clone();
+        confirmed.move_queued_activity(second, 0, host_id).unwrap();
+        assert!(reconcile(&mut pending, Some(&confirmed), None, &[], 200));
+        assert!(pending.is_empty());
+    }
+}
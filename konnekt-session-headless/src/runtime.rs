@@ -0,0 +1,492 @@
+use konnekt_session_core::domain::ActivityResult;
+use konnekt_session_core::{
+    ActivityRun, DomainCommand, DomainEvent, DomainLoop, Lobby, Projection, ResultsAnalytics,
+    RunStatus, SessionMetrics,
+};
+use konnekt_session_p2p::{
+    FailedCommand, NetworkConnection, P2PTransport, PeerStats, Presence, QueueDepths, SessionId,
+    SessionLoopV2, SharedClock,
+};
+use std::collections::HashMap;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Peer stats are refreshed every this many ticks, so their ever-changing
+/// last seen times do not re-render the page on every tick
+const STATS_EVERY_TICKS: u16 = 10;
+
+/// A guest without a participant resends `JoinLobby` every this many ticks
+const JOIN_RETRY_TICKS: u16 = 10;
+
+#[derive(Debug, Error)]
+pub enum RuntimeError {
+    #[error("{0}")]
+    LobbyNotCreated(String),
+}
+
+/// The activity in progress as the frontends show it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveRunSnapshot {
+    pub run_id: Uuid,
+    pub status: RunStatus,
+    pub activity_type: String,
+    pub name: String,
+    pub config: serde_json::Value,
+    pub required_submitters: Vec<Uuid>,
+    pub results: Vec<ActivityResult>,
+    /// When the host started the run, on its clock (Unix ms)
+    pub started_at_ms: Option<u64>,
+    /// When answers are due, on the host's clock (Unix ms); `None` for
+    /// untimed or unstamped runs
+    pub deadline_ms: Option<u64>,
+}
+
+impl From<&ActivityRun> for ActiveRunSnapshot {
+    fn from(run: &ActivityRun) -> Self {
+        Self {
+            run_id: run.id(),
+            status: run.status(),
+            activity_type: run.config().activity_type.clone(),
+            name: run.config().name.clone(),
+            config: run.config().config.clone(),
+            required_submitters: run.required_submitters().iter().copied().collect(),
+            results: run.results().values().cloned().collect(),
+            started_at_ms: run.started_at_ms(),
+            deadline_ms: run.deadline_ms(),
+        }
+    }
+}
+
+/// A domain event the session runtime applied, and when (Unix ms)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoggedEvent {
+    pub at_ms: u64,
+    pub event: DomainEvent,
+}
+
+/// The session as of the latest [`SessionRuntime::tick`]
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeSnapshot {
+    pub lobby: Option<Lobby>,
+    pub active_run: Option<ActiveRunSnapshot>,
+    pub peer_count: usize,
+    pub peer_stats: Vec<PeerStats>,
+    pub reconnecting: bool,
+    /// How far the host's clock is ahead of ours, in ms; add it to the local
+    /// time for countdowns and deadlines
+    pub clock_offset_ms: i64,
+    pub local_participant_id: Option<Uuid>,
+    pub local_peer_id: Option<String>,
+    pub presence: Vec<(Uuid, Presence)>,
+    /// Failures the host reported during this tick
+    pub failed_commands: Vec<FailedCommand>,
+    /// Domain events applied during this tick
+    pub events: Vec<DomainEvent>,
+    pub queue_depths: QueueDepths,
+    pub protocol_error: Option<String>,
+    pub kicked: bool,
+    /// Set when a run completed during this tick
+    pub results: Option<ResultsAnalytics>,
+    /// Set when domain events were applied during this tick
+    pub metrics: Option<SessionMetrics>,
+}
+
+/// Drives a session loop for a UI: feeds it the user's commands, joins the
+/// lobby as a guest and publishes a [`RuntimeSnapshot`] per tick
+///
+/// Framework-agnostic; the Yew and Leptos providers tick it on a timer.
+pub struct SessionRuntime<C: NetworkConnection> {
+    session_loop: SessionLoopV2<C>,
+    is_host: bool,
+    local_name: String,
+    commands: Vec<DomainCommand>,
+    presence: Option<Option<Presence>>,
+    join_retry_ticks: u16,
+    join_in_flight: bool,
+    stats_ticks: u16,
+    /// Removed by the host or a co-host, so no more join attempts
+    kicked: bool,
+    /// Completed runs the published results cover
+    completed_runs: usize,
+    /// Fed every domain event we apply
+    metrics: SessionMetrics,
+    /// Participant we were before a reload (guest only)
+    resume_participant_id: Option<Uuid>,
+    snapshot: RuntimeSnapshot,
+}
+
+impl<C: NetworkConnection> SessionRuntime<C> {
+    /// Create the lobby `lobby_id` and host it over `connection`
+    pub fn host(
+        connection: C,
+        lobby_id: Uuid,
+        lobby_name: String,
+        host_name: String,
+    ) -> Result<Self, RuntimeError> {
+        let mut domain = DomainLoop::new(10, 100);
+        domain
+            .submit(DomainCommand::CreateLobby {
+                lobby_id: Some(lobby_id),
+                lobby_name,
+                host_name: host_name.clone(),
+            })
+            .map_err(|e| RuntimeError::LobbyNotCreated(format!("{:?}", e)))?;
+        domain.poll();
+        if !domain
+            .drain_events()
+            .iter()
+            .any(|e| matches!(e, DomainEvent::LobbyCreated { .. }))
+        {
+            return Err(RuntimeError::LobbyNotCreated(
+                "no LobbyCreated event".to_string(),
+            ));
+        }
+
+        let transport = P2PTransport::new_host(connection, 100);
+        Ok(Self::new(
+            SessionLoopV2::new(domain, transport, true, lobby_id),
+            host_name,
+        ))
+    }
+
+    /// Join the lobby `lobby_id` as `name` over `connection`
+    pub fn guest(connection: C, lobby_id: Uuid, name: String) -> Self {
+        let transport = P2PTransport::new_guest(connection, 100);
+        Self::new(
+            SessionLoopV2::new(DomainLoop::new(10, 100), transport, false, lobby_id),
+            name,
+        )
+    }
+
+    fn new(session_loop: SessionLoopV2<C>, local_name: String) -> Self {
+        Self {
+            is_host: session_loop.is_host(),
+            session_loop,
+            local_name,
+            commands: Vec::new(),
+            presence: None,
+            // Join on the first tick with a peer
+            join_retry_ticks: JOIN_RETRY_TICKS - 1,
+            join_in_flight: false,
+            stats_ticks: 0,
+            kicked: false,
+            completed_runs: 0,
+            metrics: SessionMetrics::new(),
+            resume_participant_id: None,
+            snapshot: RuntimeSnapshot::default(),
+        }
+    }
+
+    /// Be the participant we were before a reload, while the lobby still
+    /// has it (guest only)
+    pub fn with_resume_participant(mut self, participant_id: Option<Uuid>) -> Self {
+        self.resume_participant_id = participant_id.filter(|_| !self.is_host);
+        self
+    }
+
+    pub fn is_host(&self) -> bool {
+        self.is_host
+    }
+
+    pub fn lobby_id(&self) -> Uuid {
+        self.session_loop.lobby_id()
+    }
+
+    /// Send `command` on the next tick
+    pub fn submit(&mut self, command: DomainCommand) {
+        self.commands.push(command);
+    }
+
+    /// Tell the others what we are doing (`None` clears it) once we joined
+    pub fn set_presence(&mut self, presence: Option<Presence>) {
+        self.presence = Some(presence);
+    }
+
+    /// The session as of the latest tick
+    pub fn snapshot(&self) -> &RuntimeSnapshot {
+        &self.snapshot
+    }
+
+    /// Answer times, participation and drop-offs since we joined
+    pub fn metrics(&self) -> &SessionMetrics {
+        &self.metrics
+    }
+
+    /// Now on the host's clock (Unix ms)
+    pub fn session_time_ms(&self) -> u64 {
+        self.session_loop.session_time_ms()
+    }
+
+    /// The local clock the session runs on; add `clock_offset_ms` to read
+    /// the host's time from it
+    pub fn clock(&self) -> SharedClock {
+        self.session_loop.clock().clone()
+    }
+
+    /// Our participant in `lobby`: the resumed one while it is still there,
+    /// otherwise the one with our name
+    fn local_participant_id(&self, lobby: &Lobby) -> Option<Uuid> {
+        if self.is_host {
+            return lobby
+                .participants()
+                .values()
+                .find(|p| p.is_host())
+                .map(|p| p.id());
+        }
+
+        self.resume_participant_id
+            .filter(|id| lobby.participants().contains_key(id))
+            .or_else(|| {
+                lobby
+                    .participants()
+                    .values()
+                    .find(|p| p.name() == self.local_name && !p.is_host())
+                    .map(|p| p.id())
+            })
+    }
+
+    /// Completed runs of our lobby, in start order
+    fn completed(&self) -> impl Iterator<Item = &ActivityRun> {
+        self.session_loop
+            .runs()
+            .filter(|run| run.status() == RunStatus::Completed)
+    }
+
+    /// Send the queued commands, poll the session loop and take a new
+    /// snapshot
+    pub fn tick(&mut self) -> &RuntimeSnapshot {
+        for cmd in std::mem::take(&mut self.commands) {
+            if let Err(e) = self.session_loop.submit_command(cmd) {
+                tracing::error!("❌ Command failed: {:?}", e);
+            }
+        }
+
+        // Presence needs our participant, so it waits until we have joined
+        if let Some(participant_id) = self.snapshot.local_participant_id
+            && let Some(presence) = self.presence.take()
+            && let Err(e) = self.session_loop.set_presence(participant_id, presence)
+        {
+            tracing::warn!("⚠️ Presence update failed: {:?}", e);
+        }
+
+        let processed = self.session_loop.poll();
+        if processed > 0 {
+            tracing::debug!("SessionRuntime processed {} events", processed);
+        }
+
+        if !self.is_host {
+            self.join_if_needed();
+        }
+
+        let peer_stats = if self.stats_ticks == 0 {
+            self.session_loop.peer_stats()
+        } else {
+            std::mem::take(&mut self.snapshot.peer_stats)
+        };
+        self.stats_ticks = (self.stats_ticks + 1) % STATS_EVERY_TICKS;
+
+        let lobby = self.session_loop.get_lobby().cloned();
+        let queue_depths = self.session_loop.queue_depths();
+        let events = self.session_loop.take_events();
+        if let Some(me) = self.snapshot.local_participant_id
+            && events.iter().any(|event| {
+                matches!(event, DomainEvent::GuestKicked { participant_id, .. } if *participant_id == me)
+            })
+        {
+            tracing::warn!("🚫 Removed from the lobby");
+            self.kicked = true;
+        }
+        // Results only change when a run completes
+        let completed_runs = self.completed().count();
+        let results = (completed_runs != self.completed_runs).then(|| {
+            let names: HashMap<Uuid, String> = lobby
+                .iter()
+                .flat_map(|lobby| lobby.participants().values())
+                .map(|p| (p.id(), p.name().to_string()))
+                .collect();
+            ResultsAnalytics::from_runs(self.completed(), &names)
+        });
+        self.completed_runs = completed_runs;
+
+        // Count those already in the lobby when we first see it
+        if self.snapshot.lobby.is_none()
+            && let Some(lobby) = &lobby
+        {
+            self.metrics.apply(&DomainEvent::LobbyCreated {
+                lobby: lobby.clone(),
+            });
+        }
+        self.metrics.apply_all(&events);
+        let metrics = (!events.is_empty()).then(|| self.metrics.clone());
+
+        self.snapshot = RuntimeSnapshot {
+            local_participant_id: lobby
+                .as_ref()
+                .and_then(|lobby| self.local_participant_id(lobby)),
+            lobby,
+            active_run: self
+                .session_loop
+                .get_active_run()
+                .map(ActiveRunSnapshot::from),
+            peer_count: self.session_loop.connected_peers().len(),
+            peer_stats,
+            reconnecting: self.session_loop.is_reconnecting(),
+            clock_offset_ms: self.session_loop.clock_offset_ms(),
+            local_peer_id: self
+                .session_loop
+                .local_peer_id()
+                .map(|peer_id| peer_id.to_string()),
+            presence: self.session_loop.presence(),
+            failed_commands: self.session_loop.take_failed_commands(),
+            events,
+            queue_depths,
+            protocol_error: self.session_loop.protocol_error().map(str::to_string),
+            kicked: self.kicked,
+            results,
+            metrics,
+        };
+        &self.snapshot
+    }
+
+    /// Keep asking the host to let us in until our participant shows up
+    fn join_if_needed(&mut self) {
+        let has_connected_peers = !self.session_loop.connected_peers().is_empty();
+        let joined = self
+            .session_loop
+            .get_lobby()
+            .map(|lobby| {
+                if let Some(id) = self.snapshot.local_participant_id {
+                    lobby.participants().contains_key(&id)
+                } else {
+                    self.local_participant_id(lobby).is_some()
+                }
+            })
+            .unwrap_or(false);
+
+        if joined {
+            self.join_in_flight = false;
+        }
+
+        if has_connected_peers && !joined && !self.join_in_flight && !self.kicked {
+            self.join_retry_ticks = self.join_retry_ticks.saturating_add(1);
+            if self.join_retry_ticks >= JOIN_RETRY_TICKS {
+                self.join_retry_ticks = 0;
+                let lobby_id = self.session_loop.lobby_id();
+                let guest_name = self.local_name.clone();
+                if let Err(e) = self.session_loop.submit_command(DomainCommand::JoinLobby {
+                    lobby_id,
+                    guest_name: guest_name.clone(),
+                }) {
+                    tracing::warn!("⚠️ JoinLobby failed: {:?}", e);
+                } else {
+                    tracing::info!("🔁 Sent JoinLobby for '{}' (in-flight)", guest_name);
+                    self.join_in_flight = true;
+                }
+            }
+        } else if !has_connected_peers {
+            self.join_retry_ticks = 0;
+        }
+    }
+}
+
+/// The session ID in `raw`: a bare ID or a room or join URL ending with one
+pub fn parse_session_reference(raw: &str) -> Option<SessionId> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    if let Ok(id) = SessionId::parse(trimmed) {
+        return Some(id);
+    }
+
+    let without_query = trimmed.split('?').next().unwrap_or(trimmed);
+    let candidate = without_query.trim_end_matches('/');
+    let tail = candidate.rsplit('/').next().unwrap_or(candidate);
+    SessionId::parse(tail).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_p2p::{LoopbackConnection, LoopbackNetwork};
+
+    fn tick(runtimes: &mut [&mut SessionRuntime<LoopbackConnection>], rounds: usize) {
+        for _ in 0..rounds {
+            for runtime in runtimes.iter_mut() {
+                runtime.tick();
+            }
+        }
+    }
+
+    #[test]
+    fn test_guest_joins_and_follows_the_host() {
+        let network = LoopbackNetwork::new();
+        let lobby_id = Uuid::new_v4();
+        let mut host = SessionRuntime::host(
+            network.connect(),
+            lobby_id,
+            "Test Lobby".to_string(),
+            "Alice".to_string(),
+        )
+        .unwrap();
+        let mut guest = SessionRuntime::guest(network.connect(), lobby_id, "Bob".to_string());
+
+        tick(&mut [&mut host, &mut guest], 20);
+
+        let snapshot = guest.snapshot();
+        let lobby = snapshot.lobby.as_ref().expect("guest synced the lobby");
+        assert_eq!(lobby.participants().len(), 2);
+        let me = snapshot.local_participant_id.unwrap();
+        assert_eq!(lobby.participants()[&me].name(), "Bob");
+        assert!(host.snapshot().local_participant_id.is_some());
+        assert_eq!(snapshot.peer_count, 1);
+
+        // Commands go out on the next tick
+        guest.submit(DomainCommand::SendChatMessage {
+            lobby_id,
+            author_id: me,
+            text: "Hi".to_string(),
+        });
+        tick(&mut [&mut host, &mut guest], 10);
+        let chat = host.snapshot().lobby.as_ref().unwrap().chat_messages();
+        assert_eq!(chat.len(), 1);
+        assert_eq!(chat[0].text(), "Hi");
+
+        host.submit(DomainCommand::QueueActivity {
+            lobby_id,
+            config: konnekt_session_core::domain::ActivityConfig::new(
+                "quiz".to_string(),
+                "Quiz".to_string(),
+                serde_json::json!({}),
+            ),
+        });
+        host.submit(DomainCommand::StartNextRun { lobby_id });
+        tick(&mut [&mut host, &mut guest], 10);
+        let run_id = guest.snapshot().active_run.as_ref().unwrap().run_id;
+        guest.submit(DomainCommand::SubmitResult {
+            lobby_id,
+            run_id,
+            result: ActivityResult::new(run_id, me).with_time(2_000),
+        });
+        tick(&mut [&mut host, &mut guest], 10);
+        // Both count the players that were there before they saw any event
+        for runtime in [&host, &guest] {
+            let run = &runtime.metrics().runs()[0];
+            assert_eq!((run.eligible, run.submitted), (2, 1));
+            assert_eq!(run.average_answer_ms(), Some(2_000));
+        }
+    }
+
+    #[test]
+    fn test_parse_session_reference() {
+        let id = SessionId::new();
+        assert_eq!(parse_session_reference(&id.to_string()), Some(id.clone()));
+        assert_eq!(
+            parse_session_reference(&format!("https://example.com/join/{}/?ref=qr", id)),
+            Some(id)
+        );
+        assert_eq!(parse_session_reference("  "), None);
+        assert_eq!(parse_session_reference("https://example.com/join/"), None);
+    }
+}
@@ -0,0 +1,145 @@
+use konnekt_session_core::RunStatus;
+
+/// How far along the session is, for loading and progress UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionStatus {
+    /// Waiting for signalling to assign our peer ID, or for a peer to connect
+    Connecting,
+    /// Connected, waiting for the host's lobby
+    Syncing,
+    /// Lobby synced and the connection is healthy
+    Ready,
+    /// Lobby synced, but reconnecting or cut off from the host
+    Degraded,
+}
+
+impl SessionStatus {
+    /// Stable name, e.g. for i18n keys and CSS modifiers
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionStatus::Connecting => "connecting",
+            SessionStatus::Syncing => "syncing",
+            SessionStatus::Ready => "ready",
+            SessionStatus::Degraded => "degraded",
+        }
+    }
+
+    /// The lobby is there to render
+    pub fn is_synced(&self) -> bool {
+        matches!(self, SessionStatus::Ready | SessionStatus::Degraded)
+    }
+}
+
+/// What the session looks like to [`session_status`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatusInputs {
+    pub is_host: bool,
+    pub has_peer_id: bool,
+    pub peer_count: usize,
+    pub synced: bool,
+    pub reconnecting: bool,
+    pub failed: bool,
+}
+
+pub fn session_status(inputs: StatusInputs) -> SessionStatus {
+    if inputs.synced {
+        // A host alone in the lobby is fine, a guest without peers lost the host
+        let cut_off = !inputs.is_host && inputs.peer_count == 0;
+        if inputs.reconnecting || inputs.failed || cut_off {
+            SessionStatus::Degraded
+        } else {
+            SessionStatus::Ready
+        }
+    } else if inputs.has_peer_id && inputs.peer_count > 0 {
+        SessionStatus::Syncing
+    } else {
+        SessionStatus::Connecting
+    }
+}
+
+/// Which screen the session is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LobbyPhase {
+    /// Connecting or waiting for the lobby to sync
+    Connecting,
+    /// The session could not be started (see the runtime error)
+    Failed,
+    /// In the lobby between activities
+    Lobby,
+    /// An activity is in progress
+    Activity,
+}
+
+/// A synced lobby wins over an error from a failed (re)start
+pub fn lobby_phase(synced: bool, failed: bool, run_status: Option<RunStatus>) -> LobbyPhase {
+    match (synced, failed, run_status) {
+        (true, _, Some(RunStatus::InProgress)) => LobbyPhase::Activity,
+        (true, _, _) => LobbyPhase::Lobby,
+        (false, true, _) => LobbyPhase::Failed,
+        (false, false, _) => LobbyPhase::Connecting,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_status() {
+        let guest = StatusInputs::default();
+        assert_eq!(session_status(guest), SessionStatus::Connecting);
+
+        // A peer ID alone is not enough, the host has to be there too
+        let assigned = StatusInputs {
+            has_peer_id: true,
+            ..guest
+        };
+        assert_eq!(session_status(assigned), SessionStatus::Connecting);
+
+        let connected = StatusInputs {
+            peer_count: 1,
+            ..assigned
+        };
+        assert_eq!(session_status(connected), SessionStatus::Syncing);
+
+        let synced = StatusInputs {
+            synced: true,
+            ..connected
+        };
+        assert_eq!(session_status(synced), SessionStatus::Ready);
+        assert!(session_status(synced).is_synced());
+
+        let reconnecting = StatusInputs {
+            reconnecting: true,
+            ..synced
+        };
+        assert_eq!(session_status(reconnecting), SessionStatus::Degraded);
+
+        let cut_off = StatusInputs {
+            peer_count: 0,
+            ..synced
+        };
+        assert_eq!(session_status(cut_off), SessionStatus::Degraded);
+
+        let lonely_host = StatusInputs {
+            is_host: true,
+            ..cut_off
+        };
+        assert_eq!(session_status(lonely_host), SessionStatus::Ready);
+    }
+
+    #[test]
+    fn test_lobby_phase() {
+        assert_eq!(lobby_phase(false, false, None), LobbyPhase::Connecting);
+        assert_eq!(lobby_phase(false, true, None), LobbyPhase::Failed);
+        assert_eq!(lobby_phase(true, true, None), LobbyPhase::Lobby);
+        assert_eq!(
+            lobby_phase(true, false, Some(RunStatus::InProgress)),
+            LobbyPhase::Activity
+        );
+        assert_eq!(
+            lobby_phase(true, false, Some(RunStatus::Completed)),
+            LobbyPhase::Lobby
+        );
+    }
+}
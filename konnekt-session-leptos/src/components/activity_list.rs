@@ -0,0 +1,112 @@
+use konnekt_session_core::ActivityConfig;
+use konnekt_session_headless::{ActiveRunSnapshot, PendingCommand};
+use leptos::prelude::*;
+
+/// Displays queued activities and the currently running activity (if any).
+#[component]
+pub fn ActivityList(
+    /// Usually the lobby's `activity_queue()`
+    #[prop(into)]
+    queue: Signal<Vec<ActivityConfig>>,
+    #[prop(optional, into)] active_run: Signal<Option<ActiveRunSnapshot>>,
+    /// Marks activities the host has not queued or moved yet, or refused to
+    /// queue
+    #[prop(optional, into)]
+    pending_commands: Signal<Vec<PendingCommand>>,
+) -> impl IntoView {
+    let rolled_back = move || {
+        pending_commands.with(|pending| {
+            pending
+                .iter()
+                .filter(|c| c.is_rolled_back())
+                .filter_map(|c| c.queued_activity().cloned())
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let items = move || {
+        let queue = queue.get();
+        let rolled_back = rolled_back();
+        if queue.is_empty() && rolled_back.is_empty() {
+            return view! { <p class="konnekt-activity-list__empty">"No queued activities"</p> }
+                .into_any();
+        }
+
+        let (queuing, moving) = pending_commands.with(|pending| {
+            let pending: Vec<_> = pending.iter().filter(|c| c.is_pending()).collect();
+            (
+                pending
+                    .iter()
+                    .filter_map(|c| c.queued_activity().map(|a| a.id))
+                    .collect::<Vec<_>>(),
+                pending
+                    .iter()
+                    .filter_map(|c| c.moved_activity())
+                    .collect::<Vec<_>>(),
+            )
+        });
+        view! {
+            <ul class="konnekt-activity-list__items" role="list" aria-label="Queued activities">
+                {queue
+                    .into_iter()
+                    .map(|activity| {
+                        let is_queuing = queuing.contains(&activity.id);
+                        let is_moving = moving.contains(&activity.id);
+                        let pending = is_queuing || is_moving;
+                        let status = if is_queuing {
+                            "⏳ Queuing"
+                        } else if is_moving {
+                            "⏳ Moving"
+                        } else {
+                            "Queued"
+                        };
+                        view! {
+                            <li
+                                class="konnekt-activity-list__item planned"
+                                class:pending=pending
+                                aria-busy=pending.to_string()
+                            >
+                                <span class="konnekt-activity-list__icon" aria-hidden="true">"📋"</span>
+                                <span class="konnekt-activity-list__name">{activity.name}</span>
+                                <span class="konnekt-activity-list__status">{status}</span>
+                            </li>
+                        }
+                    })
+                    .collect_view()}
+                {rolled_back
+                    .into_iter()
+                    .map(|activity| {
+                        view! {
+                            <li class="konnekt-activity-list__item rolled-back">
+                                <span class="konnekt-activity-list__icon" aria-hidden="true">"↩️"</span>
+                                <span class="konnekt-activity-list__name">{activity.name}</span>
+                                <span class="konnekt-activity-list__status">"Not queued"</span>
+                            </li>
+                        }
+                    })
+                    .collect_view()}
+            </ul>
+        }
+        .into_any()
+    };
+
+    view! {
+        <div class="konnekt-activity-list" role="region" aria-label="Activities">
+            <h3 class="konnekt-activity-list__title">"Activities"</h3>
+            {move || {
+                active_run
+                    .get()
+                    .map(|run| {
+                        view! {
+                            <div class="konnekt-activity-list__item in-progress" aria-current="true">
+                                <span class="konnekt-activity-list__icon" aria-hidden="true">"▶️"</span>
+                                <span class="konnekt-activity-list__name">{run.name}</span>
+                                <span class="konnekt-activity-list__status">"InProgress"</span>
+                            </div>
+                        }
+                    })
+            }}
+            {items}
+        </div>
+    }
+}
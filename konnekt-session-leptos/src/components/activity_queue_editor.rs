@@ -0,0 +1,124 @@
+use konnekt_session_core::domain::{ActivityConfig, ActivityId};
+use leptos::ev::{DragEvent, MouseEvent};
+use leptos::prelude::*;
+
+use crate::hooks::use_i18n;
+
+/// Where the dragged item would land relative to the hovered one, as in the
+/// Yew editor
+fn drop_indicator(from: usize, over: usize) -> Option<&'static str> {
+    match from.cmp(&over) {
+        std::cmp::Ordering::Greater => Some("drop-before"),
+        std::cmp::Ordering::Less => Some("drop-after"),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+/// Lets hosts and co-hosts reorder the planned activities by dragging them,
+/// or with the arrow buttons from the keyboard
+#[component]
+pub fn ActivityQueueEditor(
+    /// Usually the lobby's `activity_queue()`
+    #[prop(into)]
+    queue: Signal<Vec<ActivityConfig>>,
+    /// Move an activity to a new position, e.g.
+    /// `use_host_actions().move_activity`
+    on_move: Callback<(ActivityId, usize)>,
+    #[prop(optional, into)] disabled: Signal<bool>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let dragging = RwSignal::new(None::<usize>);
+    let over = RwSignal::new(None::<usize>);
+    let reset = move || {
+        dragging.set(None);
+        over.set(None);
+    };
+
+    let title = i18n.t("queue_editor.title");
+    let hint = i18n.t("queue_editor.hint");
+    let items = move || {
+        let queue = queue.get();
+        let len = queue.len();
+        let disabled = disabled.get();
+        queue
+            .iter()
+            .enumerate()
+            .map(|(index, activity)| {
+                let id = activity.id;
+                let move_to = move |to: usize| move |_: MouseEvent| on_move.run((id, to));
+                let indicator = move || {
+                    dragging
+                        .get()
+                        .zip(over.get())
+                        .filter(|(_, over)| *over == index)
+                        .and_then(|(from, over)| drop_indicator(from, over))
+                        .unwrap_or_default()
+                };
+                let from_queue = queue.clone();
+
+                view! {
+                    <li
+                        class=move || format!("konnekt-queue-editor__item {}", indicator())
+                        class:dragging=move || dragging.get() == Some(index)
+                        draggable=(!disabled).to_string()
+                        on:dragstart=move |e: DragEvent| {
+                            if let Some(data) = e.data_transfer() {
+                                data.set_effect_allowed("move");
+                                // Firefox only starts dragging with some data set
+                                let _ = data.set_data("text/plain", &id.to_string());
+                            }
+                            dragging.set(Some(index));
+                        }
+                        on:dragover=move |e: DragEvent| {
+                            // Allows dropping here
+                            e.prevent_default();
+                            if over.get_untracked() != Some(index) {
+                                over.set(Some(index));
+                            }
+                        }
+                        on:drop=move |e: DragEvent| {
+                            e.prevent_default();
+                            if let Some(activity) = dragging
+                                .get_untracked()
+                                .filter(|from| *from != index)
+                                .and_then(|from| from_queue.get(from))
+                            {
+                                on_move.run((activity.id, index));
+                            }
+                            reset();
+                        }
+                        on:dragend=move |_: DragEvent| reset()
+                    >
+                        <span class="konnekt-queue-editor__handle" aria-hidden="true">"⠿"</span>
+                        <span class="konnekt-queue-editor__position">{index + 1}</span>
+                        <span class="konnekt-queue-editor__name">{activity.name.clone()}</span>
+                        <button
+                            class="konnekt-btn konnekt-btn--secondary konnekt-queue-editor__move"
+                            disabled=disabled || index == 0
+                            aria-label=i18n.t_with("queue_editor.move_up", &[("name", &activity.name)])
+                            on:click=move_to(index.saturating_sub(1))
+                        >
+                            "↑"
+                        </button>
+                        <button
+                            class="konnekt-btn konnekt-btn--secondary konnekt-queue-editor__move"
+                            disabled=disabled || index + 1 == len
+                            aria-label=i18n.t_with("queue_editor.move_down", &[("name", &activity.name)])
+                            on:click=move_to(index + 1)
+                        >
+                            "↓"
+                        </button>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="konnekt-queue-editor" class:disabled=move || disabled.get()>
+            <h4 class="konnekt-queue-editor__title">{title}</h4>
+            <p class="konnekt-queue-editor__hint">{hint}</p>
+            <ol class="konnekt-queue-editor__items">{items}</ol>
+        </div>
+    }
+}
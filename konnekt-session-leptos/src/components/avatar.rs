@@ -0,0 +1,53 @@
+use konnekt_session_core::ParticipantAvatar;
+use konnekt_session_headless::{avatar_hue, initials};
+use leptos::prelude::*;
+use uuid::Uuid;
+
+/// Round picture of a participant: the emoji or image they picked, otherwise
+/// their initials on a color generated from their ID
+#[component]
+pub fn Avatar(
+    participant_id: Uuid,
+    /// Initials of the generated avatar and the accessible label
+    #[prop(into)]
+    name: String,
+    /// The avatar the participant picked, if any
+    #[prop(optional_no_strip)]
+    avatar: Option<ParticipantAvatar>,
+    /// Width and height in pixels
+    #[prop(default = 32)]
+    size: u32,
+) -> impl IntoView {
+    let size_style = format!(
+        "width: {0}px; height: {0}px; font-size: {1}px;",
+        size,
+        size * 45 / 100
+    );
+    let (modifier, content, background) = match avatar {
+        Some(ParticipantAvatar::Emoji(emoji)) => ("emoji", emoji.into_any(), String::new()),
+        Some(ParticipantAvatar::Image(url)) => (
+            "image",
+            view! { <img class="konnekt-avatar__image" src=url alt="" /> }.into_any(),
+            String::new(),
+        ),
+        None => (
+            "generated",
+            initials(&name).into_any(),
+            format!(
+                " background: hsl({}, 55%, 45%);",
+                avatar_hue(participant_id)
+            ),
+        ),
+    };
+
+    view! {
+        <span
+            class=format!("konnekt-avatar konnekt-avatar--{}", modifier)
+            style=format!("{}{}", size_style, background)
+            role="img"
+            aria-label=name
+        >
+            {content}
+        </span>
+    }
+}
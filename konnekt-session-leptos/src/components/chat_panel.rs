@@ -0,0 +1,139 @@
+use konnekt_session_core::ChatMessage;
+use konnekt_session_core::domain::MAX_CHAT_MESSAGE_LEN;
+use konnekt_session_headless::{ParticipantView, Segment, is_mention_of, mention_segments};
+use leptos::ev::KeyboardEvent;
+use leptos::prelude::*;
+use uuid::Uuid;
+
+use super::Avatar;
+
+/// Chat of the lobby: message list with mentions highlighted and an input
+#[component]
+pub fn ChatPanel(
+    /// Usually the lobby's `chat_messages()`
+    #[prop(into)]
+    messages: Signal<Vec<ChatMessage>>,
+    /// Authors of the messages, usually `use_participants().participants`
+    #[prop(into)]
+    participants: Signal<Vec<ParticipantView>>,
+    /// Called with the trimmed message when it is sent
+    on_send: Callback<String>,
+    /// Our messages the host has not confirmed yet
+    #[prop(optional, into)]
+    pending: Signal<Vec<Uuid>>,
+) -> impl IntoView {
+    let text = RwSignal::new(String::new());
+    let send = move || {
+        let message = text.get_untracked().trim().to_string();
+        if !message.is_empty() && message.chars().count() <= MAX_CHAT_MESSAGE_LEN {
+            on_send.run(message);
+            text.set(String::new());
+        }
+    };
+    let too_long = move || text.with(|text| text.chars().count() > MAX_CHAT_MESSAGE_LEN);
+    let me = move || participants.with(|all| all.iter().find(|p| p.is_me).map(|p| p.id));
+
+    let list = move || {
+        let participants = participants.get();
+        let names: Vec<&str> = participants.iter().map(|p| p.name.as_str()).collect();
+        let local = participants.iter().find(|p| p.is_me);
+        let local_name = local.map(|p| p.name.as_str());
+        let messages = messages.get();
+        if messages.is_empty() {
+            return view! { <li class="konnekt-chat-panel__empty">"No messages yet. Say hi!"</li> }
+                .into_any();
+        }
+
+        messages
+            .iter()
+            .map(|message| {
+                let author = participants.iter().find(|p| p.id == message.author_id());
+                let is_mine = local.is_some_and(|me| me.id == message.author_id());
+                let is_pending = pending.with(|pending| pending.contains(&message.id()));
+                let segments = mention_segments(message.text(), &names);
+                let mentions_me = segments
+                    .iter()
+                    .any(|s| matches!(s, Segment::Mention(m) if is_mention_of(m, local_name)));
+                let text = segments
+                    .iter()
+                    .map(|segment| match segment {
+                        Segment::Text(text) => text.to_string().into_any(),
+                        Segment::Mention(mention) => view! {
+                            <span
+                                class="konnekt-chat-panel__mention"
+                                class:me=is_mention_of(mention, local_name)
+                            >
+                                {mention.to_string()}
+                            </span>
+                        }
+                        .into_any(),
+                    })
+                    .collect_view();
+
+                view! {
+                    <li
+                        class="konnekt-chat-panel__message"
+                        class:mine=is_mine
+                        class:mentioned=mentions_me && !is_mine
+                        class:pending=is_pending
+                        title=is_pending.then_some("Sending…")
+                    >
+                        {author
+                            .map(|author| {
+                                view! {
+                                    <span class="konnekt-chat-panel__avatar">
+                                        <Avatar
+                                            participant_id=author.id
+                                            name=author.name.clone()
+                                            avatar=author.avatar.clone()
+                                            size=20
+                                        />
+                                    </span>
+                                }
+                            })}
+                        <span class="konnekt-chat-panel__author">
+                            {if author.is_some_and(|p| p.is_host) { "👑 " } else { "" }}
+                            {author.map_or("Someone who left".to_string(), |p| p.name.clone())}
+                        </span>
+                        <span class="konnekt-chat-panel__text">{text}</span>
+                    </li>
+                }
+                .into_any()
+            })
+            .collect_view()
+            .into_any()
+    };
+
+    view! {
+        <div class="konnekt-chat-panel">
+            <h3 class="konnekt-chat-panel__title">"💬 Chat"</h3>
+            <ul class="konnekt-chat-panel__messages">{list}</ul>
+            <div class="konnekt-chat-input">
+                <input
+                    class="konnekt-chat-input__field"
+                    class=("too-long", too_long)
+                    type="text"
+                    placeholder="Write a message… (@name to mention)"
+                    disabled=move || me().is_none()
+                    prop:value=move || text.get()
+                    on:input=move |e| text.set(event_target_value(&e))
+                    on:keydown=move |e: KeyboardEvent| {
+                        if e.key() == "Enter" && !e.shift_key() {
+                            e.prevent_default();
+                            send();
+                        }
+                    }
+                />
+                <button
+                    class="konnekt-btn konnekt-chat-input__send"
+                    on:click=move |_| send()
+                    disabled=move || {
+                        me().is_none() || text.with(|text| text.trim().is_empty()) || too_long()
+                    }
+                >
+                    "Send"
+                </button>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,59 @@
+use konnekt_session_headless::{ConnectionHealth, connection_health};
+use leptos::prelude::*;
+
+use crate::hooks::use_session;
+
+/// Banner shown while the session connection is reconnecting, offline or
+/// degraded; renders nothing while it is fine
+#[component]
+pub fn ConnectionBanner(
+    /// Also warn about slow or missing peers, not only lost connections
+    #[prop(default = true)]
+    show_degraded: bool,
+) -> impl IntoView {
+    let session = use_session();
+
+    move || {
+        session.peer_stats.with(|peer_stats| {
+            let health = connection_health(
+                session.is_host.get(),
+                session.reconnecting.get(),
+                session.lobby.with(Option::is_some),
+                peer_stats,
+            );
+            let worst_rtt_ms = peer_stats
+                .iter()
+                .filter_map(|stats| stats.rtt)
+                .map(|rtt| rtt.as_millis() as u64)
+                .max();
+            let (modifier, text) = match health {
+                ConnectionHealth::Reconnecting => (
+                    "reconnecting",
+                    "🔄 Connection lost. Reconnecting…".to_string(),
+                ),
+                ConnectionHealth::Offline => (
+                    "offline",
+                    "📴 Lost the connection to the host. Waiting for it to come back…"
+                        .to_string(),
+                ),
+                ConnectionHealth::Degraded if show_degraded => (
+                    "degraded",
+                    match worst_rtt_ms {
+                        Some(rtt) => format!("🐢 Slow connection (up to {} ms round trip)", rtt),
+                        None => "🐢 Some participants are having connection trouble".to_string(),
+                    },
+                ),
+                _ => return None,
+            };
+
+            Some(view! {
+                <div
+                    class=format!("konnekt-connection-banner konnekt-connection-banner--{}", modifier)
+                    role="status"
+                >
+                    {text}
+                </div>
+            })
+        })
+    }
+}
@@ -0,0 +1,19 @@
+//! Components mirroring the Yew ones, with the same markup and classes
+
+mod activity_list;
+mod activity_queue_editor;
+mod avatar;
+mod chat_panel;
+mod connection_banner;
+mod participant_list;
+mod session_info;
+mod session_loading;
+
+pub use activity_list::ActivityList;
+pub use activity_queue_editor::ActivityQueueEditor;
+pub use avatar::Avatar;
+pub use chat_panel::ChatPanel;
+pub use connection_banner::ConnectionBanner;
+pub use participant_list::ParticipantList;
+pub use session_info::SessionInfo;
+pub use session_loading::SessionLoading;
@@ -0,0 +1,141 @@
+use konnekt_session_headless::{I18n, ParticipantView, PendingCommand, PendingStatus};
+use konnekt_session_p2p::Presence;
+use leptos::prelude::*;
+
+use super::Avatar;
+use crate::hooks::use_i18n;
+
+/// Lists the participants with their role, mode and what they are doing
+#[component]
+pub fn ParticipantList(
+    /// Usually `use_participants().participants`
+    #[prop(into)]
+    participants: Signal<Vec<ParticipantView>>,
+    /// Marks participation changes the host has not confirmed yet, or
+    /// refused
+    #[prop(optional, into)]
+    pending_commands: Signal<Vec<PendingCommand>>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let title = {
+        let i18n = i18n.clone();
+        move || {
+            i18n.t_with(
+                "participants.title",
+                &[("count", &participants.with(Vec::len))],
+            )
+        }
+    };
+
+    view! {
+        <div class="konnekt-participant-list">
+            <h3 class="konnekt-participant-list__title">{title}</h3>
+            <ul class="konnekt-participant-list__items" role="list">
+                {move || {
+                    let pending = pending_commands.get();
+                    participants
+                        .get()
+                        .into_iter()
+                        .map(|participant| {
+                            let sync = pending
+                                .iter()
+                                .rev()
+                                .find(|c| c.toggled_participant() == Some(participant.id))
+                                .map(|c| match &c.status {
+                                    PendingStatus::Pending => {
+                                        ("pending", "⏳", i18n.t("pending.saving"))
+                                    }
+                                    PendingStatus::RolledBack { reason } => (
+                                        "rolled-back",
+                                        "↩️",
+                                        reason
+                                            .clone()
+                                            .unwrap_or_else(|| i18n.t("pending.rolled_back")),
+                                    ),
+                                });
+                            render_participant(participant, sync, &i18n)
+                        })
+                        .collect_view()
+                }}
+            </ul>
+        </div>
+    }
+}
+
+fn render_participant(
+    participant: ParticipantView,
+    sync: Option<(&'static str, &'static str, String)>,
+    i18n: &I18n,
+) -> impl IntoView + use<> {
+    let mode_class = if participant.active {
+        "active"
+    } else {
+        "spectating"
+    };
+    let class = match &sync {
+        Some((sync_class, _, _)) => {
+            format!(
+                "konnekt-participant-list__item {} {}",
+                mode_class, sync_class
+            )
+        }
+        None => format!("konnekt-participant-list__item {}", mode_class),
+    };
+    let presence = participant.presence.map(|presence| match presence {
+        Presence::Typing => i18n.t("participants.typing"),
+        Presence::Answering { .. } => i18n.t("participants.answering"),
+    });
+    let mode = if participant.active {
+        format!("🎮 {}", i18n.t("participants.active"))
+    } else {
+        format!("👁️  {}", i18n.t("participants.spectating"))
+    };
+    let tooltip = i18n.t_with(
+        "participants.tooltip",
+        &[("id", &participant.id), ("joined", &participant.joined_at)],
+    );
+    let you = participant
+        .is_me
+        .then(|| format!(" ({})", i18n.t("participants.you")));
+    let role = participant
+        .is_host
+        .then(|| format!(" ({})", i18n.t("participants.host")));
+
+    view! {
+        <li class=class title=tooltip>
+            <span class="konnekt-participant-list__icon" aria-hidden="true">
+                <Avatar
+                    participant_id=participant.id
+                    name=participant.name.clone()
+                    avatar=participant.avatar.clone()
+                />
+                {participant
+                    .is_host
+                    .then(|| view! { <span class="konnekt-participant-list__crown">"👑"</span> })}
+            </span>
+            <span class="konnekt-participant-list__name">
+                {participant.name.clone()}
+                <span class="konnekt-participant-list__role">{role}</span>
+                {you.map(|you| view! { <span class="konnekt-participant-list__you">{you}</span> })}
+                {presence
+                    .map(|text| {
+                        view! { <span class="konnekt-participant-list__presence">" ✍️ "{text}</span> }
+                    })}
+            </span>
+            <span class="konnekt-participant-list__mode">
+                {mode}
+                {sync
+                    .map(|(_, icon, text)| {
+                        view! {
+                            <span class="konnekt-participant-list__sync" title=text>
+                                {format!(" {}", icon)}
+                            </span>
+                        }
+                    })}
+            </span>
+            <span class="konnekt-participant-list__id">
+                {format!("#{}", &participant.id.to_string()[..8])}
+            </span>
+        </li>
+    }
+}
@@ -0,0 +1,28 @@
+use leptos::prelude::*;
+
+/// Displays session metadata: the ID to share, connected peers and our role
+#[component]
+pub fn SessionInfo(
+    #[prop(into)] session_id: Signal<String>,
+    #[prop(optional, into)] peer_count: Signal<usize>,
+    #[prop(optional, into)] is_host: Signal<bool>,
+) -> impl IntoView {
+    view! {
+        <div class="konnekt-session-info">
+            <div class="konnekt-session-info__row">
+                <span class="konnekt-session-info__label">"Session ID:"</span>
+                <code class="konnekt-session-info__value">{move || session_id.get()}</code>
+            </div>
+            <div class="konnekt-session-info__row">
+                <span class="konnekt-session-info__label">"Connected Peers:"</span>
+                <span class="konnekt-session-info__value">{move || peer_count.get()}</span>
+            </div>
+            <div class="konnekt-session-info__row">
+                <span class="konnekt-session-info__label">"Role:"</span>
+                <span class="konnekt-session-info__value">
+                    {move || if is_host.get() { "👑 Host" } else { "👤 Guest" }}
+                </span>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,106 @@
+use konnekt_session_headless::SessionStatus;
+use leptos::prelude::*;
+
+use crate::hooks::use_i18n;
+
+/// The steps [`SessionLoading`] walks through
+const STEPS: [SessionStatus; 3] = [
+    SessionStatus::Connecting,
+    SessionStatus::Syncing,
+    SessionStatus::Ready,
+];
+
+/// Whether `step` is done, current or still to come while at `status`
+fn step_state(step: SessionStatus, status: SessionStatus) -> &'static str {
+    let index = |status| {
+        STEPS
+            .iter()
+            .position(|s| *s == status)
+            .unwrap_or(STEPS.len() - 1)
+    };
+    match index(step).cmp(&index(status)) {
+        std::cmp::Ordering::Less => "done",
+        std::cmp::Ordering::Equal => "current",
+        std::cmp::Ordering::Greater => "upcoming",
+    }
+}
+
+/// Progress through connecting and syncing, over a skeleton of the lobby
+#[component]
+pub fn SessionLoading(
+    /// Usually `Signal::derive(move || use_session().status())`
+    #[prop(into)]
+    status: Signal<SessionStatus>,
+    /// The host creates the lobby instead of syncing it
+    #[prop(optional, into)]
+    is_host: Signal<bool>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+
+    move || {
+        let status = status.get();
+        let message = match status {
+            _ if is_host.get() => i18n.t("session.creating"),
+            SessionStatus::Connecting => i18n.t("session.connecting"),
+            _ => i18n.t("session.syncing"),
+        };
+
+        view! {
+            <div
+                class=format!("konnekt-session-loading konnekt-session-loading--{}", status.as_str())
+                role="status"
+                aria-busy="true"
+            >
+                <ol class="konnekt-session-loading__steps">
+                    {STEPS
+                        .iter()
+                        .map(|step| {
+                            view! {
+                                <li class=format!(
+                                    "konnekt-session-loading__step {}",
+                                    step_state(*step, status),
+                                )>{i18n.t(&format!("status.{}", step.as_str()))}</li>
+                            }
+                        })
+                        .collect_view()}
+                </ol>
+                <p class="konnekt-session-loading__message">{message}</p>
+
+                <div class="konnekt-session-loading__lobby">
+                    <div class="konnekt-session-loading__participants">
+                        {skeleton("text", Some("width: 60%;"))}
+                        {(0..3)
+                            .map(|_| {
+                                view! {
+                                    <div class="konnekt-session-loading__participant">
+                                        {skeleton("circle", Some("width: 32px; height: 32px;"))}
+                                        {skeleton("text", None)}
+                                    </div>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                    <div class="konnekt-session-loading__activities">
+                        {skeleton("text", Some("width: 40%;"))}
+                        {skeleton("block", Some("height: 4rem;"))}
+                        {skeleton("block", Some("height: 4rem;"))}
+                        {skeleton("text", None)}
+                    </div>
+                </div>
+            </div>
+        }
+    }
+}
+
+/// A `konnekt-skeleton` placeholder of one bone
+fn skeleton(shape: &'static str, style: Option<&'static str>) -> impl IntoView {
+    view! {
+        <span
+            class=format!("konnekt-skeleton konnekt-skeleton--{}", shape)
+            style=style
+            aria-hidden="true"
+        >
+            <span class="konnekt-skeleton__bone"></span>
+        </span>
+    }
+}
@@ -0,0 +1,11 @@
+mod use_host_actions;
+mod use_i18n;
+mod use_lobby_state;
+mod use_participants;
+mod use_session;
+
+pub use use_host_actions::{HostActions, use_host_actions};
+pub use use_i18n::use_i18n;
+pub use use_lobby_state::{LobbyState, use_lobby_state};
+pub use use_participants::{ParticipantsState, use_participants};
+pub use use_session::use_session;
@@ -0,0 +1,153 @@
+use konnekt_session_core::domain::ActivityId;
+use konnekt_session_core::{ActivityConfig, DomainCommand};
+use leptos::prelude::*;
+use uuid::Uuid;
+
+use super::use_session;
+
+/// What the host (and partly co-hosts) can do with the lobby
+///
+/// The callbacks do nothing for participants lacking the rights; the host
+/// also rejects such commands.
+#[derive(Clone, Copy)]
+pub struct HostActions {
+    pub is_host: Signal<bool>,
+    /// The host or a co-host
+    pub can_moderate: Signal<bool>,
+    /// Add an activity to the end of the queue
+    pub queue_activity: Callback<ActivityConfig>,
+    /// Move a planned activity to a new position in the queue
+    pub move_activity: Callback<(ActivityId, usize)>,
+    /// Activities are planned and none is running
+    pub can_start: Signal<bool>,
+    /// Start the next planned activity
+    pub start_next: Callback<()>,
+    /// Cancel the activity in progress
+    pub cancel_run: Callback<()>,
+    /// Remove a guest from the lobby
+    pub kick: Callback<Uuid>,
+    /// Remove a guest and keep their peer out of the session
+    pub ban: Callback<Uuid>,
+    /// Hand the host role to another participant
+    pub make_host: Callback<Uuid>,
+    /// Make a guest co-host (`true`) or demote them (`false`)
+    pub set_co_host: Callback<(Uuid, bool)>,
+}
+
+/// Headless hook to the host's lobby and activity controls
+pub fn use_host_actions() -> HostActions {
+    let session = use_session();
+    let is_host: Signal<bool> = session.is_host.into();
+    let can_moderate = Signal::derive(move || {
+        let local_id = session.local_participant_id.get();
+        session.lobby.with(|lobby| {
+            lobby
+                .as_ref()
+                .zip(local_id)
+                .is_some_and(|(lobby, id)| lobby.can_moderate(id))
+        })
+    });
+    let can_start = Signal::derive(move || {
+        session.lobby.with(|lobby| {
+            lobby
+                .as_ref()
+                .is_some_and(|lobby| !lobby.activity_queue().is_empty() && !lobby.has_active_run())
+        })
+    });
+
+    // Read when an action runs, not when the hook is called
+    let lobby_id = move || {
+        session
+            .lobby
+            .with_untracked(|lobby| lobby.as_ref().map(|lobby| lobby.id()))
+    };
+    let local_id = move || session.local_participant_id.get_untracked();
+    let send = move |allowed: bool, command: Option<DomainCommand>| match command {
+        Some(command) if allowed => session.send_command(command),
+        _ => tracing::warn!("⚠️ Ignoring host action: not allowed or not in a lobby"),
+    };
+    let kick_guest = move |ban: bool| {
+        Callback::new(move |guest_id: Uuid| {
+            send(
+                can_moderate.get_untracked(),
+                lobby_id()
+                    .zip(local_id())
+                    .map(|(lobby_id, host_id)| DomainCommand::KickGuest {
+                        lobby_id,
+                        host_id,
+                        guest_id,
+                        ban,
+                    }),
+            );
+        })
+    };
+
+    HostActions {
+        is_host,
+        can_moderate,
+        queue_activity: Callback::new(move |config: ActivityConfig| {
+            send(
+                is_host.get_untracked(),
+                lobby_id().map(|lobby_id| DomainCommand::QueueActivity { lobby_id, config }),
+            );
+        }),
+        move_activity: Callback::new(move |(activity_id, to_index): (ActivityId, usize)| {
+            send(
+                can_moderate.get_untracked(),
+                lobby_id().zip(local_id()).map(|(lobby_id, requester_id)| {
+                    DomainCommand::MoveQueuedActivity {
+                        lobby_id,
+                        activity_id,
+                        to_index,
+                        requester_id,
+                    }
+                }),
+            );
+        }),
+        can_start,
+        start_next: Callback::new(move |_| {
+            send(
+                is_host.get_untracked() && can_start.get_untracked(),
+                lobby_id().map(|lobby_id| DomainCommand::StartNextRun { lobby_id }),
+            );
+        }),
+        cancel_run: Callback::new(move |_| {
+            let run_id = session
+                .active_run
+                .with_untracked(|run| run.as_ref().map(|run| run.run_id));
+            send(
+                is_host.get_untracked(),
+                lobby_id()
+                    .zip(run_id)
+                    .map(|(lobby_id, run_id)| DomainCommand::CancelRun { lobby_id, run_id }),
+            );
+        }),
+        kick: kick_guest(false),
+        ban: kick_guest(true),
+        make_host: Callback::new(move |new_host_id: Uuid| {
+            send(
+                is_host.get_untracked(),
+                lobby_id()
+                    .zip(local_id())
+                    .map(|(lobby_id, current_host_id)| DomainCommand::DelegateHost {
+                        lobby_id,
+                        current_host_id,
+                        new_host_id,
+                    }),
+            );
+        }),
+        set_co_host: Callback::new(move |(participant_id, co_host): (Uuid, bool)| {
+            send(
+                is_host.get_untracked(),
+                lobby_id()
+                    .zip(local_id())
+                    .map(|(lobby_id, host_id)| DomainCommand::SetCoHost {
+                        lobby_id,
+                        host_id,
+                        participant_id,
+                        co_host,
+                    }),
+            );
+        }),
+    }
+}
@@ -0,0 +1,7 @@
+use konnekt_session_headless::I18n;
+use leptos::prelude::*;
+
+/// Hook to the texts of the surrounding `I18nProvider` (English without one)
+pub fn use_i18n() -> I18n {
+    use_context::<I18n>().unwrap_or_default()
+}
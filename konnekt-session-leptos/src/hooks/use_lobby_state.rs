@@ -0,0 +1,43 @@
+use konnekt_session_headless::{LobbyPhase, lobby_phase};
+use konnekt_session_p2p::SessionId;
+use leptos::prelude::*;
+
+use super::use_session;
+
+/// Lobby as a whole, without participants or activities in detail
+#[derive(Clone, Copy)]
+pub struct LobbyState {
+    pub phase: Signal<LobbyPhase>,
+    pub session_id: Signal<SessionId>,
+    /// `None` until the lobby synced
+    pub name: Signal<Option<String>>,
+    pub is_host: Signal<bool>,
+    pub peer_count: Signal<usize>,
+    pub error: Signal<Option<String>>,
+}
+
+/// Headless hook to the lobby's phase and metadata
+pub fn use_lobby_state() -> LobbyState {
+    let session = use_session();
+
+    LobbyState {
+        phase: Signal::derive(move || {
+            lobby_phase(
+                session.lobby.with(Option::is_some),
+                session.runtime_error.with(Option::is_some),
+                session
+                    .active_run
+                    .with(|run| run.as_ref().map(|run| run.status)),
+            )
+        }),
+        session_id: session.session_id.into(),
+        name: Signal::derive(move || {
+            session
+                .lobby
+                .with(|lobby| lobby.as_ref().map(|lobby| lobby.name().to_string()))
+        }),
+        is_host: session.is_host.into(),
+        peer_count: session.peer_count.into(),
+        error: session.runtime_error.into(),
+    }
+}
@@ -0,0 +1,65 @@
+use konnekt_session_core::{DomainCommand, ParticipantAvatar};
+use konnekt_session_headless::{ParticipantView, participant_views};
+use leptos::prelude::*;
+
+use super::use_session;
+
+/// Participants of the lobby plus what the local participant can do
+#[derive(Clone, Copy)]
+pub struct ParticipantsState {
+    /// Host first, then in the order they joined
+    pub participants: Signal<Vec<ParticipantView>>,
+    /// The local participant, once known
+    pub me: Signal<Option<ParticipantView>>,
+    /// Switch the local participant between active and spectating
+    pub toggle_participation: Callback<()>,
+    /// Pick the local participant's avatar (`None`: the generated one)
+    pub set_avatar: Callback<Option<ParticipantAvatar>>,
+}
+
+/// Headless hook to the lobby's participants
+pub fn use_participants() -> ParticipantsState {
+    let session = use_session();
+    let participants = Memo::new(move |_| {
+        let local_id = session.local_participant_id.get();
+        session.presence.with(|presence| {
+            session.lobby.with(|lobby| {
+                lobby
+                    .as_ref()
+                    .map(|lobby| participant_views(lobby, local_id, presence))
+                    .unwrap_or_default()
+            })
+        })
+    });
+    // Both commands are about ourselves, so we request them too
+    let own = move || {
+        session
+            .lobby
+            .with_untracked(|lobby| lobby.as_ref().map(|lobby| lobby.id()))
+            .zip(session.local_participant_id.get_untracked())
+    };
+
+    ParticipantsState {
+        participants: participants.into(),
+        me: Signal::derive(move || participants.with(|all| all.iter().find(|p| p.is_me).cloned())),
+        toggle_participation: Callback::new(move |_| {
+            if let Some((lobby_id, participant_id)) = own() {
+                session.send_command(DomainCommand::ToggleParticipationMode {
+                    lobby_id,
+                    participant_id,
+                    requester_id: participant_id,
+                });
+            }
+        }),
+        set_avatar: Callback::new(move |avatar: Option<ParticipantAvatar>| {
+            if let Some((lobby_id, participant_id)) = own() {
+                session.send_command(DomainCommand::SetAvatar {
+                    lobby_id,
+                    participant_id,
+                    requester_id: participant_id,
+                    avatar,
+                });
+            }
+        }),
+    }
+}
@@ -0,0 +1,10 @@
+use leptos::prelude::*;
+
+use crate::providers::SessionContext;
+
+/// Hook to the session of the surrounding `SessionProvider`
+///
+/// Panics outside a `SessionProvider`.
+pub fn use_session() -> SessionContext {
+    expect_context::<SessionContext>()
+}
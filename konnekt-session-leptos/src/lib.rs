@@ -0,0 +1,29 @@
+//! # Konnekt Session Leptos Components
+//!
+//! Leptos counterparts of the `konnekt-session-yew` components, built on the
+//! same headless core (`konnekt-session-headless`).
+//!
+//! `SessionProvider` runs the session and provides a [`SessionContext`] of
+//! signals; the hooks (`use_participants`, `use_lobby_state`,
+//! `use_host_actions`) derive views and callbacks from it. The components
+//! render the same markup and `konnekt-*` classes as the Yew ones, so the
+//! Yew crate's `styles.css` styles them too.
+
+pub mod components;
+pub mod hooks;
+pub mod providers;
+
+// Re-exports for convenience
+pub use components::{
+    ActivityList, ActivityQueueEditor, ChatPanel, ConnectionBanner, ParticipantList, SessionInfo,
+    SessionLoading,
+};
+pub use hooks::{
+    HostActions, LobbyState, ParticipantsState, use_host_actions, use_i18n, use_lobby_state,
+    use_participants, use_session,
+};
+pub use konnekt_session_headless::{
+    ActiveRunSnapshot, Catalog, I18n, LobbyPhase, ParticipantView, PendingCommand, PendingStatus,
+    SessionStatus,
+};
+pub use providers::{I18nProvider, SessionContext, SessionProvider};
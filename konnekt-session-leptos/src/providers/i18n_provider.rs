@@ -0,0 +1,36 @@
+use konnekt_session_headless::{Catalog, I18n};
+use leptos::prelude::*;
+
+/// Translates the texts of all components inside it (see `use_i18n`)
+///
+/// The locale is resolved once; remount the provider to switch it.
+///
+/// ```rust,ignore
+/// let catalogs = vec![Catalog::new("fr").with_message("lobby.title", "Salon")];
+///
+/// view! {
+///     <I18nProvider locale="fr" catalogs>
+///         <ParticipantList participants />
+///     </I18nProvider>
+/// }
+/// ```
+#[component]
+pub fn I18nProvider(
+    /// Language tag such as `"de"` or `"en-GB"`; the browser language if unset
+    #[prop(optional, into)]
+    locale: Option<String>,
+    /// Catalogs adding locales or overriding built-in texts
+    #[prop(optional)]
+    catalogs: Vec<Catalog>,
+    children: Children,
+) -> impl IntoView {
+    let locale = locale
+        .or_else(browser_locale)
+        .unwrap_or_else(|| "en".to_string());
+    provide_context(I18n::new(locale, &catalogs));
+    children()
+}
+
+fn browser_locale() -> Option<String> {
+    web_sys::window()?.navigator().language()
+}
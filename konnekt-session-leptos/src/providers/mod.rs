@@ -0,0 +1,7 @@
+//! Context providers for session state
+
+mod i18n_provider;
+mod session_provider;
+
+pub use i18n_provider::I18nProvider;
+pub use session_provider::{SessionContext, SessionProvider};
@@ -0,0 +1,276 @@
+use crate::components::ConnectionBanner;
+use crate::hooks::use_i18n;
+use gloo_timers::future::TimeoutFuture;
+use konnekt_session_core::{DomainCommand, Lobby, ResultsAnalytics, Timestamp};
+use konnekt_session_headless::{
+    ActiveRunSnapshot, I18n, PendingCommand, SessionRuntime, SessionStatus, StatusInputs,
+    apply_pending, parse_session_reference, reconcile, session_status,
+};
+use konnekt_session_p2p::infrastructure::connection::MatchboxConnection;
+use konnekt_session_p2p::{IceServer, PeerStats, Presence, SessionId};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use uuid::Uuid;
+
+/// Milliseconds between two session runtime ticks
+const TICK_MS: u32 = 100;
+
+/// What the UI asked the runtime to do since the last tick
+#[derive(Default)]
+struct Outbox {
+    commands: Vec<DomainCommand>,
+    presence: Option<Option<Presence>>,
+}
+
+/// Session state as signals, provided by [`SessionProvider`]
+#[derive(Clone, Copy)]
+pub struct SessionContext {
+    pub session_id: RwSignal<SessionId>,
+    pub is_host: RwSignal<bool>,
+    /// The host's lobby plus our unconfirmed commands; `None` until synced
+    pub lobby: Signal<Option<Lobby>>,
+    /// The activity in progress, including our unconfirmed results
+    pub active_run: Signal<Option<ActiveRunSnapshot>>,
+    pub peer_count: RwSignal<usize>,
+    /// Round trip and status of every other peer (refreshed about once a second)
+    pub peer_stats: RwSignal<Vec<PeerStats>>,
+    /// Lost signalling and trying to get back
+    pub reconnecting: RwSignal<bool>,
+    pub local_participant_id: RwSignal<Option<Uuid>>,
+    pub local_peer_id: RwSignal<Option<String>>,
+    /// What other participants are doing right now (typing, answering)
+    pub presence: RwSignal<Vec<(Uuid, Presence)>>,
+    /// Commands shown before the host confirmed them, and recently rolled
+    /// back ones
+    pub pending_commands: RwSignal<Vec<PendingCommand>>,
+    pub runtime_error: RwSignal<Option<String>>,
+    /// The host's lobby could not be read
+    pub protocol_error: RwSignal<Option<String>>,
+    /// The host or a co-host removed us; we don't rejoin on our own then
+    pub kicked: RwSignal<bool>,
+    /// Score distribution and progression over the completed runs
+    pub results: RwSignal<ResultsAnalytics>,
+    confirmed_lobby: RwSignal<Option<Lobby>>,
+    confirmed_run: RwSignal<Option<ActiveRunSnapshot>>,
+    outbox: StoredValue<Outbox>,
+}
+
+impl SessionContext {
+    fn new(is_host: bool) -> Self {
+        let confirmed_lobby = RwSignal::new(None);
+        let confirmed_run = RwSignal::new(None);
+        let pending_commands = RwSignal::new(Vec::<PendingCommand>::new());
+        let view = Memo::new(move |_| {
+            pending_commands
+                .with(|pending| apply_pending(pending, confirmed_lobby.get(), confirmed_run.get()))
+        });
+
+        Self {
+            session_id: RwSignal::new(SessionId::new()),
+            is_host: RwSignal::new(is_host),
+            lobby: Signal::derive(move || view.with(|(lobby, _)| lobby.clone())),
+            active_run: Signal::derive(move || view.with(|(_, run)| run.clone())),
+            peer_count: RwSignal::new(0),
+            peer_stats: RwSignal::new(Vec::new()),
+            reconnecting: RwSignal::new(false),
+            local_participant_id: RwSignal::new(None),
+            local_peer_id: RwSignal::new(None),
+            presence: RwSignal::new(Vec::new()),
+            pending_commands,
+            runtime_error: RwSignal::new(None),
+            protocol_error: RwSignal::new(None),
+            kicked: RwSignal::new(false),
+            results: RwSignal::new(ResultsAnalytics::default()),
+            confirmed_lobby,
+            confirmed_run,
+            outbox: StoredValue::new(Outbox::default()),
+        }
+    }
+
+    /// Send a command to the session runtime
+    ///
+    /// Like with the Yew provider, commands whose effect can be shown up
+    /// front show up in `lobby` and `active_run` right away and are rolled
+    /// back if the host does not confirm them.
+    pub fn send_command(&self, cmd: DomainCommand) {
+        let tracked = self.pending_commands.with_untracked(|pending| {
+            let (view, active_run) = apply_pending(
+                pending,
+                self.confirmed_lobby.get_untracked(),
+                self.confirmed_run.get_untracked(),
+            );
+            view.and_then(|view| {
+                PendingCommand::track(
+                    cmd.clone(),
+                    &view,
+                    active_run.as_ref(),
+                    Timestamp::now().as_millis(),
+                )
+            })
+        });
+        if let Some(pending) = tracked {
+            self.pending_commands
+                .update(|commands| commands.push(pending));
+        }
+        self.outbox.update_value(|outbox| outbox.commands.push(cmd));
+    }
+
+    /// Tell the others what we are doing (`None` clears it)
+    pub fn set_presence(&self, presence: Option<Presence>) {
+        self.outbox
+            .update_value(|outbox| outbox.presence = Some(presence));
+    }
+
+    /// Where the session is between connecting and a synced lobby
+    pub fn status(&self) -> SessionStatus {
+        session_status(StatusInputs {
+            is_host: self.is_host.get(),
+            has_peer_id: self.local_peer_id.with(Option::is_some),
+            peer_count: self.peer_count.get(),
+            synced: self.confirmed_lobby.with(Option::is_some),
+            reconnecting: self.reconnecting.get(),
+            failed: self.runtime_error.with(Option::is_some),
+        })
+    }
+}
+
+/// Set `signal` only when `value` differs, so unchanged ticks do not
+/// re-render
+fn set_if_changed<T: PartialEq + Send + Sync + 'static>(signal: RwSignal<T>, value: T) {
+    if signal.with_untracked(|current| *current != value) {
+        signal.set(value);
+    }
+}
+
+/// Runs a session in the browser and provides its [`SessionContext`]
+///
+/// Without a `session_id` it creates a new lobby and hosts it; with one (or
+/// a join URL ending in one) it joins that session.
+#[component]
+pub fn SessionProvider(
+    #[prop(into)] signalling_server: String,
+    #[prop(optional, into)] lobby_name: Option<String>,
+    #[prop(optional, into)] session_id: Option<String>,
+    #[prop(optional, into)] name: Option<String>,
+    /// Show a banner while reconnecting, offline or on a slow connection
+    #[prop(default = true)]
+    show_connection_banner: bool,
+    children: Children,
+) -> impl IntoView {
+    let session = SessionContext::new(session_id.is_none());
+    provide_context(session);
+    let i18n = use_i18n();
+
+    spawn_local(async move {
+        let name = name.unwrap_or_else(|| "Guest".to_string());
+        let lobby_name = lobby_name.unwrap_or_else(|| "Leptos Lobby".to_string());
+        let runtime = start_runtime(&signalling_server, session_id, lobby_name, name, &i18n).await;
+        let mut runtime = match runtime {
+            Ok((runtime, sid)) => {
+                session.session_id.set(sid);
+                session.is_host.set(runtime.is_host());
+                runtime
+            }
+            Err(msg) => {
+                tracing::error!("❌ {}", msg);
+                session.runtime_error.set(Some(msg));
+                return;
+            }
+        };
+
+        tracing::info!("🔄 Starting main polling loop");
+
+        loop {
+            TimeoutFuture::new(TICK_MS).await;
+
+            // The provider is gone once its outbox is
+            let handed_over = session.outbox.try_update_value(|outbox| {
+                for cmd in outbox.commands.drain(..) {
+                    runtime.submit(cmd);
+                }
+                if let Some(presence) = outbox.presence.take() {
+                    runtime.set_presence(presence);
+                }
+            });
+            if handed_over.is_none() {
+                break;
+            }
+
+            let snapshot = runtime.tick().clone();
+
+            // Give the WebRTC tasks a few event-loop turns for ICE and DTLS
+            TimeoutFuture::new(5).await;
+
+            set_if_changed(session.confirmed_lobby, snapshot.lobby.clone());
+            set_if_changed(session.confirmed_run, snapshot.active_run.clone());
+            set_if_changed(session.peer_count, snapshot.peer_count);
+            set_if_changed(session.peer_stats, snapshot.peer_stats);
+            set_if_changed(session.reconnecting, snapshot.reconnecting);
+            set_if_changed(session.local_participant_id, snapshot.local_participant_id);
+            set_if_changed(session.local_peer_id, snapshot.local_peer_id);
+            set_if_changed(session.presence, snapshot.presence);
+            set_if_changed(session.protocol_error, snapshot.protocol_error);
+            set_if_changed(session.kicked, snapshot.kicked);
+            if let Some(results) = snapshot.results {
+                session.results.set(results);
+            }
+
+            // Settle optimistic commands against what the host applied
+            let mut pending = session.pending_commands.get_untracked();
+            if reconcile(
+                &mut pending,
+                snapshot.lobby.as_ref(),
+                snapshot.active_run.as_ref(),
+                &snapshot.failed_commands,
+                Timestamp::now().as_millis(),
+            ) {
+                session.pending_commands.set(pending);
+            }
+        }
+
+        tracing::warn!("🛑 Polling loop ended");
+    });
+
+    view! {
+        {show_connection_banner.then(|| view! { <ConnectionBanner /> })}
+        {children()}
+    }
+}
+
+/// Connect to the signalling server and host a new lobby or join
+/// `session_id`; the error is the translated message to show
+async fn start_runtime(
+    signalling_server: &str,
+    session_id: Option<String>,
+    lobby_name: String,
+    name: String,
+    i18n: &I18n,
+) -> Result<(SessionRuntime<MatchboxConnection>, SessionId), String> {
+    let ice_servers = IceServer::default_stun_servers();
+
+    let Some(reference) = session_id else {
+        tracing::info!("👑 Creating host session as '{}'", name);
+        let sid = SessionId::new();
+        let room_url = format!("{}/{}", signalling_server, sid.as_str());
+        let connection = MatchboxConnection::connect(&room_url, ice_servers)
+            .await
+            .map_err(|e| i18n.t_with("error.host_failed", &[("error", &format!("{:?}", e))]))?;
+        let runtime = SessionRuntime::host(connection, sid.inner(), lobby_name, name)
+            .map_err(|e| i18n.t_with("error.lobby_failed", &[("error", &e)]))?;
+        return Ok((runtime, sid));
+    };
+
+    let sid = parse_session_reference(&reference)
+        .ok_or_else(|| i18n.t_with("error.invalid_session", &[("reference", &reference)]))?;
+    tracing::info!("🔗 Joining session: {}", sid);
+    let room_url = format!("{}/{}", signalling_server, sid.as_str());
+    let connection = MatchboxConnection::connect(&room_url, ice_servers)
+        .await
+        .map_err(|e| {
+            i18n.t_with(
+                "error.join_failed",
+                &[("session", &sid), ("error", &format!("{:?}", e))],
+            )
+        })?;
+    Ok((SessionRuntime::guest(connection, sid.inner(), name), sid))
+}
@@ -0,0 +1,36 @@
+#![no_main]
+
+use konnekt_session_p2p::domain::MatchboxPeerId;
+use konnekt_session_p2p::{DomainEvent, EventSyncManager, PeerId, SyncMessage};
+use libfuzzer_sys::fuzz_target;
+use uuid::Uuid;
+
+// A stream of messages from one peer (one JSON message per line), fed to a
+// host and a guest of the same lobby. Neither may panic, and both must still
+// work afterwards.
+fuzz_target!(|data: &[u8]| {
+    let lobby_id = Uuid::from_u128(1);
+    let peer = PeerId::new(MatchboxPeerId(Uuid::from_u128(2)));
+    let mut host = EventSyncManager::new_host(lobby_id);
+    let mut guest = EventSyncManager::new_guest(lobby_id);
+
+    for line in data.split(|byte| *byte == b'\n') {
+        let Ok(message) = serde_json::from_slice::<SyncMessage>(line) else {
+            continue;
+        };
+        let _ = host.handle_message(peer, message.clone());
+        let _ = guest.handle_message(peer, message);
+    }
+
+    let event = || DomainEvent::LobbyCreated {
+        lobby_id,
+        host_id: Uuid::from_u128(3),
+        name: "Fuzz".to_string(),
+    };
+    host.create_event(event())
+        .expect("host should still sequence events");
+    guest.promote_to_host();
+    guest
+        .create_event(event())
+        .expect("guest should be able to take over");
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use konnekt_session_p2p::SyncMessage;
+use libfuzzer_sys::fuzz_target;
+
+// Bytes from a peer, decoded the way the P2P loop does. Anything that
+// decodes must survive a round trip.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(message) = serde_json::from_slice::<SyncMessage>(data) {
+        let encoded = serde_json::to_vec(&message).expect("decoded message should encode");
+        serde_json::from_slice::<SyncMessage>(&encoded).expect("encoded message should decode");
+    }
+});
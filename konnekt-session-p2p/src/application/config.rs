@@ -1,4 +1,4 @@
-use crate::domain::IceServer;
+use crate::domain::{IceServer, TimeoutConfig};
 
 /// Configuration for P2P session
 #[derive(Debug, Clone)]
@@ -11,6 +11,9 @@ pub struct SessionConfig {
 
     /// ICE servers for WebRTC connection
     pub ice_servers: Vec<IceServer>,
+
+    /// Grace period, heartbeat interval and timeout policy
+    pub timeouts: TimeoutConfig,
 }
 
 impl Default for SessionConfig {
@@ -19,6 +22,7 @@ impl Default for SessionConfig {
             signalling_server: "wss://match.konnektoren.help".to_string(),
             poll_interval_ms: 100,
             ice_servers: IceServer::default_stun_servers(),
+            timeouts: TimeoutConfig::default(),
         }
     }
 }
@@ -36,6 +40,11 @@ impl SessionConfig {
         self
     }
 
+    pub fn with_timeouts(mut self, timeouts: TimeoutConfig) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
     /// Add a STUN server
     pub fn with_stun_server(mut self, url: String) -> Self {
         self.ice_servers.push(IceServer::stun(url));
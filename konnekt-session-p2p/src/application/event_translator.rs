@@ -25,10 +25,12 @@ impl EventTranslator {
             P2PDomainEvent::GuestKicked {
                 participant_id,
                 kicked_by,
+                banned,
             } => Some(DomainCommand::KickGuest {
                 lobby_id: self.lobby_id,
                 host_id: *kicked_by,
                 guest_id: *participant_id,
+                ban: *banned,
             }),
 
             P2PDomainEvent::HostDelegated { from, to, .. } => Some(DomainCommand::DelegateHost {
@@ -37,6 +39,26 @@ impl EventTranslator {
                 new_host_id: *to,
             }),
 
+            P2PDomainEvent::CoHostChanged {
+                participant_id,
+                co_host,
+                changed_by,
+            } => Some(DomainCommand::SetCoHost {
+                lobby_id: self.lobby_id,
+                host_id: *changed_by,
+                participant_id: *participant_id,
+                co_host: *co_host,
+            }),
+
+            P2PDomainEvent::LobbySettingsChanged {
+                settings,
+                changed_by,
+            } => Some(DomainCommand::UpdateLobbySettings {
+                lobby_id: self.lobby_id,
+                host_id: *changed_by,
+                settings: *settings,
+            }),
+
             P2PDomainEvent::ParticipationModeChanged {
                 participant_id,
                 new_mode,
@@ -74,10 +96,38 @@ impl EventTranslator {
                 })
             }
 
-            // State snapshots — applied via snapshot sync, not commands
+            P2PDomainEvent::ChatMessageSent { message } => Some(DomainCommand::AddChatMessage {
+                lobby_id: self.lobby_id,
+                message: message.clone(),
+            }),
+
+            // Runs are mirrored as the host started and ended them
+            P2PDomainEvent::RunStarted {
+                run_id,
+                config,
+                required_submitters,
+                started_at_ms,
+            } => Some(DomainCommand::SyncRunStarted {
+                lobby_id: self.lobby_id,
+                run_id: *run_id,
+                config: config.clone(),
+                required_submitters: required_submitters.clone(),
+                started_at_ms: *started_at_ms,
+            }),
+
+            P2PDomainEvent::RunEnded {
+                run_id,
+                status,
+                results,
+            } => Some(DomainCommand::SyncRunEnded {
+                lobby_id: self.lobby_id,
+                run_id: *run_id,
+                status: *status,
+                results: results.clone(),
+            }),
+
+            // State snapshot — applied via snapshot sync, not a command
             P2PDomainEvent::LobbyCreated { .. } => None,
-            P2PDomainEvent::RunStarted { .. } => None,
-            P2PDomainEvent::RunEnded { .. } => None,
         }
     }
 
@@ -102,10 +152,12 @@ impl EventTranslator {
             CoreDomainEvent::GuestKicked {
                 participant_id,
                 kicked_by,
+                banned,
                 ..
             } => Some(P2PDomainEvent::GuestKicked {
                 participant_id,
                 kicked_by,
+                banned,
             }),
 
             CoreDomainEvent::HostDelegated { from, to, .. } => {
@@ -116,6 +168,26 @@ impl EventTranslator {
                 })
             }
 
+            CoreDomainEvent::CoHostChanged {
+                participant_id,
+                co_host,
+                changed_by,
+                ..
+            } => Some(P2PDomainEvent::CoHostChanged {
+                participant_id,
+                co_host,
+                changed_by,
+            }),
+
+            CoreDomainEvent::LobbySettingsChanged {
+                settings,
+                changed_by,
+                ..
+            } => Some(P2PDomainEvent::LobbySettingsChanged {
+                settings,
+                changed_by,
+            }),
+
             CoreDomainEvent::ParticipationModeChanged {
                 participant_id,
                 new_mode,
@@ -129,13 +201,19 @@ impl EventTranslator {
                 Some(P2PDomainEvent::ActivityQueued { config })
             }
 
-            CoreDomainEvent::RunStarted { run_id, config, .. } => {
-                // required_submitters comes from the ActivityRun — caller must enrich this.
-                // For now we broadcast without submitters; snapshot sync covers guests.
+            CoreDomainEvent::RunStarted {
+                run_id,
+                config,
+                started_at_ms,
+                ..
+            } => {
+                // required_submitters comes from the ActivityRun — the caller enriches
+                // this (see `P2PLoop::broadcast_correlated_p2p_event`).
                 Some(P2PDomainEvent::RunStarted {
                     run_id,
                     config,
                     required_submitters: vec![],
+                    started_at_ms,
                 })
             }
 
@@ -145,6 +223,12 @@ impl EventTranslator {
 
             CoreDomainEvent::SubmitterRemoved { .. } => None,
 
+            // Only synced by `SessionLoopV2`
+            CoreDomainEvent::ReadyChanged { .. }
+            | CoreDomainEvent::CountdownStarted { .. }
+            | CoreDomainEvent::AvatarChanged { .. }
+            | CoreDomainEvent::QueuedActivityMoved { .. } => None,
+
             CoreDomainEvent::RunEnded {
                 run_id,
                 status,
@@ -156,6 +240,10 @@ impl EventTranslator {
                 results,
             }),
 
+            CoreDomainEvent::ChatMessageSent { message, .. } => {
+                Some(P2PDomainEvent::ChatMessageSent { message })
+            }
+
             CoreDomainEvent::CommandFailed { .. } => None,
         }
     }
@@ -170,7 +258,7 @@ mod tests {
     use super::*;
     use konnekt_session_core::{
         Participant,
-        domain::{ActivityConfig, ActivityResult},
+        domain::{ActivityConfig, ActivityResult, LobbyError},
     };
 
     #[test]
@@ -200,10 +288,12 @@ mod tests {
     #[test]
     fn test_command_failed_not_translated() {
         let translator = EventTranslator::new(Uuid::new_v4());
-        let p2p_event = translator.to_p2p_event(CoreDomainEvent::CommandFailed {
-            command: "Test".to_string(),
-            reason: "Error".to_string(),
-        });
+        let p2p_event = translator.to_p2p_event(CoreDomainEvent::command_failed(
+            DomainCommand::StartNextRun {
+                lobby_id: Uuid::new_v4(),
+            },
+            LobbyError::EmptyQueue,
+        ));
         assert!(p2p_event.is_none());
     }
 
@@ -281,13 +371,71 @@ mod tests {
     }
 
     #[test]
-    fn test_run_ended_not_a_command() {
+    fn test_host_controls_roundtrip() {
+        let lobby_id = Uuid::new_v4();
+        let translator = EventTranslator::new(lobby_id);
+        let host_id = Uuid::new_v4();
+        let settings = konnekt_session_core::LobbySettings {
+            locked: true,
+            ..Default::default()
+        };
+
+        let p2p_event = translator
+            .to_p2p_event(CoreDomainEvent::LobbySettingsChanged {
+                lobby_id,
+                settings,
+                changed_by: host_id,
+            })
+            .expect("Should translate");
+        assert_eq!(
+            translator.to_domain_command(&p2p_event),
+            Some(DomainCommand::UpdateLobbySettings {
+                lobby_id,
+                host_id,
+                settings,
+            })
+        );
+
+        let participant_id = Uuid::new_v4();
+        let p2p_event = translator
+            .to_p2p_event(CoreDomainEvent::CoHostChanged {
+                lobby_id,
+                participant_id,
+                co_host: true,
+                changed_by: host_id,
+            })
+            .expect("Should translate");
+        assert_eq!(
+            translator.to_domain_command(&p2p_event),
+            Some(DomainCommand::SetCoHost {
+                lobby_id,
+                host_id,
+                participant_id,
+                co_host: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_run_ended_syncs_the_outcome() {
         let translator = EventTranslator::new(Uuid::new_v4());
+        let run_id = Uuid::new_v4();
         let p2p_event = P2PDomainEvent::RunEnded {
-            run_id: Uuid::new_v4(),
+            run_id,
             status: konnekt_session_core::domain::RunStatus::Completed,
             results: vec![],
         };
-        assert!(translator.to_domain_command(&p2p_event).is_none());
+
+        match translator.to_domain_command(&p2p_event) {
+            Some(DomainCommand::SyncRunEnded {
+                run_id: synced,
+                status,
+                ..
+            }) => {
+                assert_eq!(synced, run_id);
+                assert_eq!(status, konnekt_session_core::domain::RunStatus::Completed);
+            }
+            other => panic!("Expected SyncRunEnded, got {:?}", other),
+        }
     }
 }
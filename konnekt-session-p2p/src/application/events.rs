@@ -1,8 +1,9 @@
-use crate::domain::PeerId;
+use crate::domain::{PeerId, ResumeToken, TimeoutConfig};
+use serde::Serialize;
 use uuid::Uuid;
 
 /// Events emitted by the P2P connection
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum ConnectionEvent {
     /// A new peer has connected
     PeerConnected(PeerId),
@@ -25,4 +26,57 @@ pub enum ConnectionEvent {
         for_peer: PeerId,
         since_sequence: u64,
     },
+
+    /// The host bound us to a participant (after join or resume).
+    /// Persist the token to resume the session after a reconnect.
+    JoinAccepted {
+        participant_id: Uuid,
+        resume_token: ResumeToken,
+    },
+
+    /// The host did not recognise our resume token; join as a new guest instead
+    ResumeRejected,
+
+    /// The signalling connection was lost; reconnect attempt `attempt` is pending
+    Reconnecting { attempt: u32 },
+
+    /// Reconnected to signalling with a new local peer ID
+    Reconnected { peer_id: PeerId },
+
+    /// A peer exceeded the inbound rate limit; its messages are being dropped.
+    /// `auto_kick` is set once the configured violation threshold is reached.
+    PeerRateLimited {
+        peer_id: PeerId,
+        participant_id: Option<Uuid>,
+        violations: u32,
+        auto_kick: bool,
+    },
+
+    /// The host announced the hash of its state as of event `sequence`
+    StateChecksum { sequence: u64, checksum: u64 },
+
+    /// The lobby CRDT changed through a peer (`SyncMode::Crdt`)
+    CrdtUpdated,
+
+    /// Another peer won the host fence (higher epoch or tie-break); we were
+    /// demoted to guest and requested a full sync from `new_host`
+    HostSuperseded { new_host: PeerId, epoch: u64 },
+
+    /// The host started using a new lobby topic; we opened it and requested
+    /// its state
+    TopicOpened { topic: Uuid },
+
+    /// A peer asked for the full state of a topic lobby (host only)
+    TopicSyncNeeded { topic: Uuid, for_peer: PeerId },
+
+    /// The host changed the timeout settings; we adopted them (guest only)
+    TimeoutsChanged { config: TimeoutConfig },
+
+    /// Part `applied` of `total` of a streamed full sync went to the domain
+    /// (guest only; render progress while a large lobby loads)
+    SnapshotProgress { applied: u32, total: u32 },
+
+    /// The guest elected to replace the timed-out host declined; the
+    /// election moves on to the next candidate
+    TakeoverDeclined { participant_id: Uuid },
 }
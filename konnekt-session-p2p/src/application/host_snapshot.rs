@@ -0,0 +1,162 @@
+use crate::application::SnapshotPart;
+use crate::domain::{LobbyEvent, SessionId};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::infrastructure::error::{P2PError, Result};
+use instant::Duration;
+use konnekt_session_core::domain::ActivityRun;
+use konnekt_session_core::{DomainCommand, Lobby};
+use serde::{Deserialize, Serialize};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+use uuid::Uuid;
+
+/// How often a host writes its snapshot file by default
+pub const DEFAULT_HOST_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Everything a host needs to come back as the same session after a crash
+///
+/// The lobby (host, participants, activity queue, the active run and its
+/// results) is kept as snapshot parts; completed runs live on in the event
+/// log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostSnapshot {
+    pub lobby_id: Uuid,
+    pub as_of_sequence: u64,
+    pub parts: Vec<SnapshotPart>,
+    pub events: Vec<LobbyEvent>,
+}
+
+impl HostSnapshot {
+    /// Capture `lobby`, its active `run` and the host's event log
+    ///
+    /// `None` if the lobby has no host participant.
+    pub fn capture(
+        lobby: &Lobby,
+        run: Option<&ActivityRun>,
+        events: Vec<LobbyEvent>,
+        as_of_sequence: u64,
+    ) -> Option<Self> {
+        Some(Self {
+            lobby_id: lobby.id(),
+            as_of_sequence,
+            parts: SnapshotPart::split(lobby, run, as_of_sequence, usize::MAX)?,
+            events,
+        })
+    }
+
+    /// The session this snapshot belongs to (1:1 with the lobby)
+    pub fn session_id(&self) -> SessionId {
+        SessionId::from_uuid(self.lobby_id)
+    }
+
+    /// Domain commands that rebuild the lobby and its active run
+    pub fn to_commands(&self) -> Vec<DomainCommand> {
+        self.parts
+            .iter()
+            .flat_map(|part| part.to_commands(self.lobby_id))
+            .collect()
+    }
+
+    /// Write the snapshot to `path`
+    ///
+    /// Goes through a temporary file, so a crash mid-write keeps the
+    /// previous snapshot intact.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+
+        std::fs::write(&tmp, serde_json::to_vec(self)?)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| P2PError::Storage(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Read the snapshot at `path` (`None` if there is none yet)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        match std::fs::read(path) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(P2PError::Storage(format!("{}: {}", path.display(), e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_core::domain::{ActivityConfig, ActivityResult};
+    use konnekt_session_core::{DomainEvent, DomainEventLoop, Participant};
+
+    fn lobby_with_active_run() -> (DomainEventLoop, Uuid, Uuid) {
+        let mut event_loop = DomainEventLoop::new();
+        let host = Participant::new_host("Host".to_string()).unwrap();
+        let lobby = Lobby::new("Persistent".to_string(), host).unwrap();
+        let lobby_id = lobby.id();
+        let guest = Participant::new_guest("Guest".to_string()).unwrap();
+        let guest_id = guest.id();
+        event_loop.add_lobby(lobby);
+        event_loop.handle_command(DomainCommand::AddParticipant {
+            lobby_id,
+            participant: guest,
+        });
+        event_loop.handle_command(DomainCommand::QueueActivity {
+            lobby_id,
+            config: ActivityConfig::new(
+                "echo-challenge-v1".to_string(),
+                "Echo".to_string(),
+                serde_json::json!({}),
+            ),
+        });
+        let DomainEvent::RunStarted { run_id, .. } =
+            event_loop.handle_command(DomainCommand::StartNextRun { lobby_id })
+        else {
+            panic!("Expected RunStarted");
+        };
+        event_loop.handle_command(DomainCommand::SubmitResult {
+            lobby_id,
+            run_id,
+            result: ActivityResult::new(run_id, guest_id).with_score(7),
+        });
+        (event_loop, lobby_id, run_id)
+    }
+
+    #[test]
+    fn test_snapshot_rebuilds_lobby_and_active_run() {
+        let (event_loop, lobby_id, run_id) = lobby_with_active_run();
+        let lobby = event_loop.get_lobby(&lobby_id).unwrap();
+        let snapshot =
+            HostSnapshot::capture(lobby, event_loop.get_run(&run_id), Vec::new(), 4).unwrap();
+
+        let mut restored = DomainEventLoop::new();
+        for command in snapshot.to_commands() {
+            restored.handle_command(command);
+        }
+
+        let rebuilt = restored.get_lobby(&lobby_id).unwrap();
+        assert_eq!(rebuilt.host_id(), lobby.host_id());
+        assert_eq!(rebuilt.participants().len(), 2);
+        assert_eq!(rebuilt.active_run_id(), Some(run_id));
+        assert_eq!(restored.get_run(&run_id).unwrap().results().len(), 1);
+        assert_eq!(snapshot.session_id().inner(), lobby_id);
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let (event_loop, lobby_id, run_id) = lobby_with_active_run();
+        let lobby = event_loop.get_lobby(&lobby_id).unwrap();
+        let snapshot =
+            HostSnapshot::capture(lobby, event_loop.get_run(&run_id), Vec::new(), 4).unwrap();
+        let path = std::env::temp_dir().join(format!("konnekt-snapshot-{}.json", lobby_id));
+
+        assert!(HostSnapshot::load(&path).unwrap().is_none());
+        snapshot.save(&path).unwrap();
+        let loaded = HostSnapshot::load(&path).unwrap().unwrap();
+
+        assert_eq!(loaded.lobby_id, lobby_id);
+        assert_eq!(loaded.as_of_sequence, 4);
+        assert_eq!(loaded.to_commands().len(), snapshot.to_commands().len());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,11 +1,17 @@
 mod config;
 mod event_translator;
 mod events;
+mod host_snapshot;
 pub mod runtime;
+mod snapshot_stream;
 mod sync_manager;
 
 pub use config::SessionConfig;
 pub use event_translator::EventTranslator;
 pub use events::ConnectionEvent;
+pub use host_snapshot::{DEFAULT_HOST_SNAPSHOT_INTERVAL, HostSnapshot};
 pub use runtime::{MessageQueue, P2PLoop, P2PLoopBuilder, QueueError, SessionLoop};
-pub use sync_manager::{EventSyncManager, LobbySnapshot, SyncError, SyncMessage, SyncResponse};
+pub use snapshot_stream::{DEFAULT_SNAPSHOT_PAGE_SIZE, SnapshotPart};
+pub use sync_manager::{
+    EventSyncManager, LobbySnapshot, RosterEntry, SyncError, SyncMessage, SyncResponse,
+};
@@ -0,0 +1,164 @@
+use crate::application::runtime::{SessionEvent, SessionLoop};
+use crate::infrastructure::connection::MatchboxConnection;
+use crate::infrastructure::error::{Result, TrySubmitError};
+use crate::infrastructure::transport::NetworkConnection;
+use futures::Stream;
+use instant::Duration;
+use konnekt_session_core::DomainCommand;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+/// Longest the loop stays idle when the transport is quiet
+///
+/// Bounds timeouts, heartbeats and checksums, and the latency of transports
+/// that cannot signal readiness (see `NetworkConnection::poll_ready`).
+pub const DEFAULT_IDLE_INTERVAL: Duration = Duration::from_millis(100);
+
+#[cfg(target_arch = "wasm32")]
+type Timer = gloo_timers::future::TimeoutFuture;
+
+#[cfg(not(target_arch = "wasm32"))]
+type Timer = Pin<Box<tokio::time::Sleep>>;
+
+#[cfg(target_arch = "wasm32")]
+fn timer(after: Duration) -> Timer {
+    gloo_timers::future::TimeoutFuture::new(after.as_millis() as u32)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn timer(after: Duration) -> Timer {
+    Box::pin(tokio::time::sleep(after))
+}
+
+/// Event-driven wrapper around `SessionLoop`
+///
+/// A `Stream` of session events: the session is polled when the transport
+/// has input, a command was submitted, or the idle interval elapsed — not
+/// on a fixed busy-poll cadence.
+pub struct AsyncSessionLoop<C: NetworkConnection = MatchboxConnection> {
+    session: SessionLoop<C>,
+    idle_interval: Duration,
+    pending: VecDeque<SessionEvent>,
+    timer: Option<Timer>,
+    /// Task driving the stream (woken on `submit_command`)
+    waker: Option<Waker>,
+}
+
+impl<C: NetworkConnection> AsyncSessionLoop<C> {
+    pub fn new(mut session: SessionLoop<C>) -> Self {
+        session.record_events();
+
+        Self {
+            session,
+            idle_interval: DEFAULT_IDLE_INTERVAL,
+            pending: VecDeque::new(),
+            timer: None,
+            waker: None,
+        }
+    }
+
+    /// Set the longest time between polls while the transport is quiet
+    pub fn with_idle_interval(mut self, interval: Duration) -> Self {
+        self.idle_interval = interval;
+        self
+    }
+
+    /// Submit a domain command; it is processed on the next poll
+    pub fn submit_command(&mut self, cmd: DomainCommand) -> Result<()> {
+        self.session.submit_command(cmd)?;
+        self.wake();
+        Ok(())
+    }
+
+    /// Submit a domain command, waiting for room if the queue is saturated
+    ///
+    /// Drains the queue itself while waiting (the stream may not be polled
+    /// meanwhile), so bursts of user actions are delayed rather than dropped.
+    pub async fn submit(&mut self, mut cmd: DomainCommand) -> Result<()> {
+        loop {
+            match self.session.try_submit_command(cmd) {
+                Ok(()) => {
+                    self.wake();
+                    return Ok(());
+                }
+                Err(TrySubmitError::Full(returned)) => cmd = *returned,
+                Err(TrySubmitError::Failed(e)) => return Err(e),
+            }
+
+            self.session.poll();
+            self.pending.extend(self.session.take_events());
+            yield_now().await;
+        }
+    }
+
+    pub fn session(&self) -> &SessionLoop<C> {
+        &self.session
+    }
+
+    /// Mutable access to the session (wakes the stream, as state may change)
+    pub fn session_mut(&mut self) -> &mut SessionLoop<C> {
+        self.wake();
+        &mut self.session
+    }
+
+    pub fn into_inner(self) -> SessionLoop<C> {
+        self.session
+    }
+
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Let other tasks run before the next attempt
+async fn yield_now() {
+    let mut yielded = false;
+    futures::future::poll_fn(|cx| {
+        if yielded {
+            Poll::Ready(())
+        } else {
+            yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+impl<C: NetworkConnection + Unpin> Stream for AsyncSessionLoop<C> {
+    type Item = SessionEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<SessionEvent>> {
+        let this = self.get_mut();
+        this.waker = Some(cx.waker().clone());
+
+        loop {
+            if let Some(event) = this.pending.pop_front() {
+                return Poll::Ready(Some(event));
+            }
+
+            this.session.poll();
+            this.pending.extend(this.session.take_events());
+            if !this.pending.is_empty() {
+                continue;
+            }
+
+            if this.session.poll_ready(cx).is_ready() {
+                continue;
+            }
+
+            let idle_interval = this.idle_interval;
+            let timer = this.timer.get_or_insert_with(|| timer(idle_interval));
+            if Pin::new(timer).poll(cx).is_ready() {
+                this.timer = None;
+                continue;
+            }
+
+            return Poll::Pending;
+        }
+    }
+}
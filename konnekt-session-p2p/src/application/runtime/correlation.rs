@@ -0,0 +1,71 @@
+//! Tracing one user action across peers
+//!
+//! A command gets a [`CorrelationId`] when it is submitted. The ID travels
+//! with the command to the host and on the resulting event to every guest.
+//! Each stage opens a span with a `correlation_id` field and links to the
+//! span that handed it the action (`follows_from`):
+//!
+//! `command_submitted` → `command_received` (host) → `event_broadcast` (host)
+//! → `event_received` → `event_applied` (guests)
+//!
+//! With an OpenTelemetry layer (`tracing-opentelemetry`) the links become
+//! span links, and searching Jaeger for a `correlation_id` shows the action
+//! on every machine.
+
+use crate::domain::{CorrelationId, PeerId};
+use konnekt_session_core::DomainCommand;
+use tracing::{Span, info_span};
+
+/// A correlation ID plus the local span that last handled the action
+#[derive(Debug, Clone)]
+pub struct Correlation {
+    id: CorrelationId,
+    span: Span,
+}
+
+impl Correlation {
+    /// Start tracing a command submitted on this peer
+    pub fn submitted(command: &DomainCommand) -> Self {
+        let id = CorrelationId::new();
+        let span = info_span!(
+            "command_submitted",
+            correlation_id = %id,
+            command_type = ?std::mem::discriminant(command)
+        );
+        Self { id, span }
+    }
+
+    /// A guest's command arrived at the host
+    pub fn command_received(id: CorrelationId, from: PeerId) -> Self {
+        let span = info_span!("command_received", correlation_id = %id, peer_id = %from);
+        Self { id, span }
+    }
+
+    /// An event sequenced by the host arrived at a guest
+    pub fn event_received(id: CorrelationId, sequence: u64) -> Self {
+        let span = info_span!("event_received", correlation_id = %id, sequence = sequence);
+        Self { id, span }
+    }
+
+    pub fn id(&self) -> CorrelationId {
+        self.id
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Span for broadcasting the resulting event (host)
+    pub fn broadcast_span(&self) -> Span {
+        let span = info_span!("event_broadcast", correlation_id = %self.id);
+        span.follows_from(&self.span);
+        span
+    }
+
+    /// Span for applying the resulting event to our domain
+    pub fn applied_span(&self) -> Span {
+        let span = info_span!("event_applied", correlation_id = %self.id);
+        span.follows_from(&self.span);
+        span
+    }
+}
@@ -1,46 +1,117 @@
 use crate::domain::LobbyEvent;
 use std::collections::VecDeque;
 
-/// Synchronous message queue for P2P events
+/// Outbound priority classes, highest first
+///
+/// Control traffic (snapshots, join/resume, roster, host delegation) must
+/// never wait behind bulk traffic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MessagePriority {
+    Control,
+    LobbyEvent,
+    Chat,
+    Ephemeral,
+}
+
+impl MessagePriority {
+    /// All classes, highest priority first
+    pub const ALL: [MessagePriority; 4] = [
+        MessagePriority::Control,
+        MessagePriority::LobbyEvent,
+        MessagePriority::Chat,
+        MessagePriority::Ephemeral,
+    ];
+
+    /// Bulk classes may be delayed or dropped under load
+    pub fn is_bulk(&self) -> bool {
+        matches!(self, MessagePriority::Chat | MessagePriority::Ephemeral)
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// Synchronous message queue for P2P events, split into priority classes
+///
+/// Messages leave the queue highest class first, FIFO within a class.
 #[derive(Debug)]
-pub struct MessageQueue {
-    queue: VecDeque<LobbyEvent>,
+pub struct MessageQueue<T = LobbyEvent> {
+    classes: [VecDeque<T>; 4],
     max_size: usize,
 }
 
-impl MessageQueue {
+impl<T> MessageQueue<T> {
     pub fn new(max_size: usize) -> Self {
         Self {
-            queue: VecDeque::with_capacity(max_size),
+            classes: Default::default(),
             max_size,
         }
     }
 
-    /// Push a message (returns error if full)
-    pub fn push(&mut self, msg: LobbyEvent) -> Result<(), QueueError> {
-        if self.queue.len() >= self.max_size {
-            return Err(QueueError::Full { max: self.max_size });
+    /// Push a message in the `LobbyEvent` class (returns error if full)
+    pub fn push(&mut self, msg: T) -> Result<(), QueueError> {
+        self.push_with_priority(msg, MessagePriority::LobbyEvent)
+    }
+
+    /// Push a message in `priority`'s class. When the queue is full, the
+    /// newest message of a lower class is evicted to make room.
+    pub fn push_with_priority(
+        &mut self,
+        msg: T,
+        priority: MessagePriority,
+    ) -> Result<(), QueueError> {
+        if self.len() >= self.max_size {
+            let evicted = MessagePriority::ALL
+                .iter()
+                .rev()
+                .take_while(|class| **class > priority)
+                .any(|class| self.classes[class.index()].pop_back().is_some());
+
+            if !evicted {
+                return Err(QueueError::Full { max: self.max_size });
+            }
         }
-        self.queue.push_back(msg);
+
+        self.classes[priority.index()].push_back(msg);
         Ok(())
     }
 
-    /// Pop next message
-    pub fn pop(&mut self) -> Option<LobbyEvent> {
-        self.queue.pop_front()
+    /// Pop next message (highest priority first)
+    pub fn pop(&mut self) -> Option<T> {
+        self.classes.iter_mut().find_map(VecDeque::pop_front)
     }
 
-    /// Drain all messages (for batch processing)
-    pub fn drain(&mut self) -> Vec<LobbyEvent> {
-        self.queue.drain(..).collect()
+    /// Priority of the message `pop` would return
+    pub fn peek_priority(&self) -> Option<MessagePriority> {
+        MessagePriority::ALL
+            .into_iter()
+            .find(|class| !self.classes[class.index()].is_empty())
+    }
+
+    /// Drain all messages in priority order (for batch processing)
+    pub fn drain(&mut self) -> Vec<T> {
+        self.classes
+            .iter_mut()
+            .flat_map(|class| class.drain(..))
+            .collect()
     }
 
     pub fn len(&self) -> usize {
-        self.queue.len()
+        self.classes.iter().map(VecDeque::len).sum()
+    }
+
+    /// Number of queued messages in one class
+    pub fn len_of(&self, priority: MessagePriority) -> usize {
+        self.classes[priority.index()].len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.queue.is_empty()
+        self.classes.iter().all(VecDeque::is_empty)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() >= self.max_size
     }
 
     pub fn capacity(&self) -> usize {
@@ -139,6 +210,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_priority_order() {
+        let mut queue = MessageQueue::new(10);
+
+        queue
+            .push_with_priority("typing", MessagePriority::Ephemeral)
+            .unwrap();
+        queue
+            .push_with_priority("event", MessagePriority::LobbyEvent)
+            .unwrap();
+        queue
+            .push_with_priority("chat", MessagePriority::Chat)
+            .unwrap();
+        queue
+            .push_with_priority("snapshot", MessagePriority::Control)
+            .unwrap();
+
+        assert_eq!(queue.peek_priority(), Some(MessagePriority::Control));
+        assert_eq!(queue.drain(), vec!["snapshot", "event", "chat", "typing"]);
+        assert_eq!(queue.peek_priority(), None);
+    }
+
+    #[test]
+    fn test_full_queue_evicts_lower_priority() {
+        let mut queue = MessageQueue::new(2);
+
+        queue
+            .push_with_priority("typing", MessagePriority::Ephemeral)
+            .unwrap();
+        queue
+            .push_with_priority("event", MessagePriority::LobbyEvent)
+            .unwrap();
+
+        // Control evicts the ephemeral message
+        queue
+            .push_with_priority("snapshot", MessagePriority::Control)
+            .unwrap();
+        assert_eq!(queue.len_of(MessagePriority::Ephemeral), 0);
+
+        // Nothing lower than Chat left to evict
+        assert_eq!(
+            queue.push_with_priority("chat", MessagePriority::Chat),
+            Err(QueueError::Full { max: 2 })
+        );
+        assert_eq!(queue.pop(), Some("snapshot"));
+        assert_eq!(queue.pop(), Some("event"));
+    }
+
     #[test]
     fn test_default() {
         let queue = MessageQueue::default();
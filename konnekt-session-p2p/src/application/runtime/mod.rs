@@ -1,13 +1,25 @@
+#[cfg(any(target_arch = "wasm32", feature = "native"))]
+mod async_session_loop;
+mod correlation;
 mod message_queue;
 mod p2p_loop;
 mod runtime_builder;
 mod session_loop;
 mod session_loop_v2;
 mod session_loop_v2_builder;
+mod simulation;
+mod takeover;
 
-pub use message_queue::{MessageQueue, QueueError};
+#[cfg(any(target_arch = "wasm32", feature = "native"))]
+pub use async_session_loop::{AsyncSessionLoop, DEFAULT_IDLE_INTERVAL};
+pub use correlation::Correlation;
+pub use message_queue::{MessagePriority, MessageQueue, QueueError};
 pub use p2p_loop::P2PLoop;
 pub use runtime_builder::P2PLoopBuilder;
-pub use session_loop::SessionLoop;
-pub use session_loop_v2::{MatchboxSessionLoop, SessionLoopV2};
+pub use session_loop::{DEFAULT_CHECKSUM_INTERVAL, SessionEvent, SessionLoop};
+pub use session_loop_v2::{
+    FailedCommand, MatchboxSessionLoop, QueueDepths, RECENT_EVENTS_LIMIT, SessionLoopV2,
+};
 pub use session_loop_v2_builder::SessionLoopV2Builder;
+pub use simulation::{Fault, Simulation, SimulationConfig, SimulationReport};
+pub use takeover::HostTakeover;
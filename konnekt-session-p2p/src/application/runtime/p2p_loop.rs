@@ -1,21 +1,55 @@
-use crate::application::runtime::MessageQueue;
-use crate::application::sync_manager::{EventSyncManager, SyncMessage, SyncResponse};
-use crate::application::{ConnectionEvent, EventTranslator, LobbySnapshot};
-use crate::domain::{LobbyEvent, PeerId, PeerRegistry};
+use crate::application::runtime::{Correlation, MessagePriority, MessageQueue};
+use crate::application::sync_manager::{EventSyncManager, RosterEntry, SyncMessage, SyncResponse};
+use crate::application::{ConnectionEvent, EventTranslator, LobbySnapshot, SnapshotPart};
+use crate::domain::{
+    Capabilities, ClockSync, ConnectionStatus, CorrelationId, CrdtOp, DomainEvent, HostFence,
+    LobbyCrdt, LobbyCrdtState, LobbyEvent, PeerId, PeerParticipantMap, PeerRateLimiter,
+    PeerRegistry, PeerStats, Presence, PresenceMap, RateDecision, RateLimit, ResumeToken,
+    SharedClock, SystemClock, TimeoutConfig, Topology,
+};
 use crate::infrastructure::connection::MatchboxConnection;
-use crate::infrastructure::error::Result;
-use instant::Duration;
-use konnekt_session_core::{DomainCommand, DomainEvent as CoreDomainEvent};
-use std::collections::VecDeque;
+use crate::infrastructure::error::{P2PError, Result};
+use crate::infrastructure::event_store::EventLogStore;
+use crate::infrastructure::message::{MessageKind, MessageRoute, P2PMessage};
+use crate::infrastructure::metrics;
+use crate::infrastructure::transport::NetworkConnection;
+use instant::{Duration, Instant};
+use konnekt_session_core::{DomainCommand, DomainEvent as CoreDomainEvent, LobbySettings};
+use std::collections::{HashMap, HashSet, VecDeque};
 use uuid::Uuid;
 
 // 🆕 Add tracing
 use tracing::{debug, info, instrument, trace, warn};
 
+/// Heartbeats remembered for round trips (older acks are ignored)
+const HEARTBEATS_IN_FLIGHT: usize = 8;
+
+/// Serialized message waiting in the outbound queue
+#[derive(Debug)]
+struct OutboundMessage {
+    /// `None` = broadcast
+    target: Option<PeerId>,
+    data: Vec<u8>,
+}
+
+/// Event sync for a secondary lobby carried over this session (breakout room)
+struct TopicSync {
+    sync: EventSyncManager,
+    translator: EventTranslator,
+    /// Peers whose JoinLobby awaits its GuestJoined (HOST ONLY)
+    pending_joins: VecDeque<PeerId>,
+    /// Peer ↔ participant bindings of this lobby (HOST ONLY)
+    peer_participants: PeerParticipantMap,
+    /// Our own participant in this lobby, once the host accepted the join
+    local_participant: Option<Uuid>,
+}
+
 /// P2P event loop - handles network communication and event ordering
-pub struct P2PLoop {
-    /// WebRTC connection (Matchbox adapter)
-    connection: MatchboxConnection,
+///
+/// Generic over the network connection; defaults to Matchbox WebRTC.
+pub struct P2PLoop<C: NetworkConnection = MatchboxConnection> {
+    /// Network connection (Matchbox WebRTC by default)
+    connection: C,
 
     /// Peer registry (tracks connection state)
     peer_registry: PeerRegistry,
@@ -26,8 +60,11 @@ pub struct P2PLoop {
     /// Event translator (P2P ↔ Core domain)
     translator: EventTranslator,
 
-    /// Outbound message queue
-    outbound: MessageQueue,
+    /// Outbound message queue (flushed by priority class on every poll)
+    outbound: MessageQueue<OutboundMessage>,
+
+    /// Bulk (chat/ephemeral) messages sent per flush
+    batch_size: usize,
 
     /// Inbound connection events
     inbound_events: Vec<ConnectionEvent>,
@@ -35,64 +72,228 @@ pub struct P2PLoop {
     /// Inbound lobby events
     inbound_lobby_events: Vec<LobbyEvent>,
 
-    /// Domain commands to be processed by SessionLoop
-    pending_domain_commands: VecDeque<DomainCommand>,
+    /// Domain commands to be processed by SessionLoop (with their trace)
+    pending_domain_commands: VecDeque<(DomainCommand, Option<Correlation>)>,
+
+    /// Commands guests asked us to run, with the participant each sender
+    /// joined as (host only)
+    pending_requests: VecDeque<(DomainCommand, Option<Uuid>, Option<Correlation>)>,
+
+    /// Current topology for guest-originated traffic
+    topology: Topology,
+
+    /// Learned host peer (guest only). Used as relay in star topology.
+    host_peer: Option<PeerId>,
+
+    /// Peers connected to the host, as last announced by the host (guest only)
+    peer_roster: Vec<RosterEntry>,
+
+    /// Peer ↔ participant bindings and resume tokens (host only)
+    peer_participants: PeerParticipantMap,
+
+    /// Peers whose JoinLobby is being processed, in submission order (host only)
+    pending_joins: VecDeque<PeerId>,
+
+    /// Token to resume our participant after a reconnect (guest only)
+    resume_token: Option<ResumeToken>,
+
+    /// Durable event log (host only, optional)
+    event_store: Option<Box<dyn EventLogStore + Send + Sync>>,
+
+    /// Events broadcast while no peer was connected, delivered once one
+    /// joins (host only, optional)
+    outbox: Option<Box<dyn EventLogStore + Send + Sync>>,
+
+    /// Signalling connection lost, reconnect in progress
+    reconnecting: bool,
+
+    /// Inbound per-peer rate limits (optional)
+    rate_limiter: Option<PeerRateLimiter>,
+
+    /// Lobby CRDT replica (`SyncMode::Crdt` only)
+    crdt: Option<LobbyCrdt>,
+
+    /// CRDT states received before we knew whether their sender is the host
+    /// (GUEST ONLY)
+    held_crdt_states: HashMap<PeerId, LobbyCrdtState>,
+
+    /// Secondary lobbies multiplexed over this session, keyed by lobby ID
+    topics: HashMap<Uuid, TopicSync>,
+
+    /// Grace period, heartbeats and timeout policy
+    timeouts: TimeoutConfig,
+
+    /// When we last sent a heartbeat
+    last_heartbeat_at: Option<Instant>,
+
+    /// Sequence number of the last heartbeat sent
+    heartbeat_seq: u64,
+
+    /// Recent heartbeats awaiting acks, oldest first (round-trip times)
+    heartbeats_in_flight: VecDeque<(u64, Instant)>,
+
+    /// Offset of the host's clock, from the host's heartbeat acks (guest
+    /// only)
+    clock_sync: ClockSync,
+
+    /// Participants kept in the lobby after their peer timed out
+    /// (`TimeoutPolicy::MarkAway`, host only)
+    away: HashSet<Uuid>,
+
+    /// When we last asked the host for a full sync (guest only)
+    sync_requested_at: Option<Instant>,
+
+    /// Received parts of a streamed full sync, waiting for the domain (guest only)
+    snapshot_pages: VecDeque<SnapshotPage>,
+
+    /// Lobby and `as_of_sequence` of the snapshot being streamed (guest only)
+    snapshot_stream: Option<(Uuid, u64)>,
+
+    /// Optional protocol features we advertise
+    capabilities: Capabilities,
+
+    /// Features negotiated with each peer that sent a `Hello`
+    peer_capabilities: HashMap<PeerId, Capabilities>,
+
+    /// What other participants are doing right now (typing, answering)
+    presence: PresenceMap,
+
+    /// Time for heartbeats, timeouts, rate limits and clock sync
+    clock: SharedClock,
+}
+
+/// Domain commands of one streamed snapshot part
+struct SnapshotPage {
+    commands: Vec<DomainCommand>,
+    applied: u32,
+    total: u32,
 }
 
-impl P2PLoop {
+impl<C: NetworkConnection> P2PLoop<C> {
     /// Create a new P2P loop as HOST
     #[instrument(skip(connection), fields(lobby_id = %lobby_id))]
     pub fn new_host(
-        connection: MatchboxConnection,
+        connection: C,
         lobby_id: Uuid,
-        _batch_size: usize,
+        batch_size: usize,
         max_queue_size: usize,
     ) -> Self {
         info!("P2PLoop initialized as HOST");
         Self {
             connection,
-            peer_registry: PeerRegistry::with_grace_period(Duration::from_secs(30)),
+            peer_registry: PeerRegistry::with_grace_period(TimeoutConfig::default().grace_period),
             event_sync: EventSyncManager::new_host(lobby_id),
             translator: EventTranslator::new(lobby_id),
             outbound: MessageQueue::new(max_queue_size),
+            batch_size,
             inbound_events: Vec::new(),
             inbound_lobby_events: Vec::new(),
             pending_domain_commands: VecDeque::new(),
+            pending_requests: VecDeque::new(),
+            topology: Topology::Mesh,
+            host_peer: None,
+            peer_roster: Vec::new(),
+            peer_participants: PeerParticipantMap::new(),
+            pending_joins: VecDeque::new(),
+            resume_token: None,
+            event_store: None,
+            outbox: None,
+            reconnecting: false,
+            rate_limiter: None,
+            crdt: None,
+            held_crdt_states: HashMap::new(),
+            topics: HashMap::new(),
+            timeouts: TimeoutConfig::default(),
+            last_heartbeat_at: None,
+            heartbeat_seq: 0,
+            heartbeats_in_flight: VecDeque::new(),
+            clock_sync: ClockSync::new(),
+            away: HashSet::new(),
+            sync_requested_at: None,
+            snapshot_pages: VecDeque::new(),
+            snapshot_stream: None,
+            capabilities: Capabilities::empty(),
+            peer_capabilities: HashMap::new(),
+            presence: PresenceMap::new(),
+            clock: SystemClock::shared(),
         }
     }
 
     /// Create a new P2P loop as GUEST
     #[instrument(skip(connection), fields(lobby_id = %lobby_id))]
     pub fn new_guest(
-        connection: MatchboxConnection,
+        connection: C,
         lobby_id: Uuid,
-        _batch_size: usize,
+        batch_size: usize,
         max_queue_size: usize,
     ) -> Self {
         info!("P2PLoop initialized as GUEST");
         Self {
             connection,
-            peer_registry: PeerRegistry::with_grace_period(Duration::from_secs(30)),
+            peer_registry: PeerRegistry::with_grace_period(TimeoutConfig::default().grace_period),
             event_sync: EventSyncManager::new_guest(lobby_id),
             translator: EventTranslator::new(lobby_id),
             outbound: MessageQueue::new(max_queue_size),
+            batch_size,
             inbound_events: Vec::new(),
             inbound_lobby_events: Vec::new(),
             pending_domain_commands: VecDeque::new(),
+            pending_requests: VecDeque::new(),
+            topology: Topology::Mesh,
+            host_peer: None,
+            peer_roster: Vec::new(),
+            peer_participants: PeerParticipantMap::new(),
+            pending_joins: VecDeque::new(),
+            resume_token: None,
+            event_store: None,
+            outbox: None,
+            reconnecting: false,
+            rate_limiter: None,
+            crdt: None,
+            held_crdt_states: HashMap::new(),
+            topics: HashMap::new(),
+            timeouts: TimeoutConfig::default(),
+            last_heartbeat_at: None,
+            heartbeat_seq: 0,
+            heartbeats_in_flight: VecDeque::new(),
+            clock_sync: ClockSync::new(),
+            away: HashSet::new(),
+            sync_requested_at: None,
+            snapshot_pages: VecDeque::new(),
+            snapshot_stream: None,
+            capabilities: Capabilities::empty(),
+            peer_capabilities: HashMap::new(),
+            presence: PresenceMap::new(),
+            clock: SystemClock::shared(),
         }
     }
 
     /// Send a domain command to host (GUEST ONLY)
-    #[instrument(skip(self), fields(command_type = ?std::mem::discriminant(&command)))]
     pub fn send_command_to_host(&mut self, command: DomainCommand) -> Result<()> {
+        self.send_correlated_command(command, None)
+    }
+
+    /// Send a domain command to host, tagged with its user action (GUEST ONLY)
+    #[instrument(skip(self, command), fields(
+        command_type = ?std::mem::discriminant(&command),
+        correlation_id = ?correlation_id
+    ))]
+    pub fn send_correlated_command(
+        &mut self,
+        command: DomainCommand,
+        correlation_id: Option<CorrelationId>,
+    ) -> Result<()> {
         debug!("GUEST: Sending command to host");
 
-        let msg = SyncMessage::CommandRequest { command };
+        let msg = SyncMessage::CommandRequest {
+            command,
+            correlation_id,
+        };
         let data = serde_json::to_vec(&msg)
             .map_err(crate::infrastructure::error::P2PError::Serialization)?;
 
-        self.connection.broadcast(data)?;
-        trace!("Command broadcast complete");
+        self.enqueue(None, data, msg.priority())?;
+        trace!("Command queued for broadcast");
         Ok(())
     }
 
@@ -107,12 +308,204 @@ impl P2PLoop {
         let data = serde_json::to_vec(&sync_msg)
             .map_err(crate::infrastructure::error::P2PError::Serialization)?;
 
-        self.connection.broadcast(data)?;
+        self.enqueue(None, data, sync_msg.priority())?;
+        self.sync_requested_at = Some(self.clock.now());
+
+        info!("Queued full sync request to host");
+        Ok(())
+    }
+
+    pub fn timeouts(&self) -> TimeoutConfig {
+        self.timeouts
+    }
+
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
+    /// Measure time on `clock` instead of real time (deterministic tests)
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.peer_registry.set_clock(clock.clone());
+        self.event_sync.set_clock(clock.clone());
+        self.presence.set_clock(clock.clone());
+        self.clock = clock;
+    }
+
+    /// Apply new timeout settings locally
+    pub fn set_timeouts(&mut self, config: TimeoutConfig) {
+        self.peer_registry.set_grace_period(config.grace_period);
+        self.timeouts = config;
+    }
+
+    /// Tell every guest to use our timeout settings (HOST ONLY)
+    pub fn broadcast_timeouts(&mut self) -> Result<()> {
+        let sync_msg = self
+            .event_sync
+            .create_timeout_config(self.timeouts)
+            .map_err(|e| P2PError::SendFailed(e.to_string()))?;
+
+        let data = serde_json::to_vec(&sync_msg).map_err(P2PError::Serialization)?;
+        self.enqueue(None, data, sync_msg.priority())
+    }
+
+    /// Advertise our capabilities to a newly connected peer
+    fn send_hello(&mut self, peer: PeerId) {
+        let msg = SyncMessage::Hello {
+            capabilities: self.capabilities,
+        };
+        let result = serde_json::to_vec(&msg)
+            .map_err(P2PError::Serialization)
+            .and_then(|data| self.enqueue(Some(peer), data, msg.priority()));
+        if let Err(e) = result {
+            warn!(peer_id = %peer, error = %e, "Failed to send hello");
+        }
+    }
+
+    /// Send non-default timeout settings to a newly connected guest (host only)
+    fn send_timeouts_to(&mut self, peer: PeerId) {
+        if self.timeouts == TimeoutConfig::default() {
+            return;
+        }
+        let Ok(sync_msg) = self.event_sync.create_timeout_config(self.timeouts) else {
+            return;
+        };
+        if let Err(e) = self.send_to_peer(peer, &sync_msg) {
+            warn!(peer_id = %peer, error = %e, "Failed to send timeout settings");
+        }
+    }
+
+    fn maybe_send_heartbeat(&mut self) {
+        let Some(interval) = self.timeouts.heartbeat_interval else {
+            return;
+        };
+        if self
+            .last_heartbeat_at
+            .is_some_and(|sent| self.clock.now().saturating_duration_since(sent) < interval)
+        {
+            return;
+        }
+        self.last_heartbeat_at = Some(self.clock.now());
+
+        self.heartbeat_seq += 1;
+        self.heartbeats_in_flight
+            .push_back((self.heartbeat_seq, self.clock.now()));
+        if self.heartbeats_in_flight.len() > HEARTBEATS_IN_FLIGHT {
+            self.heartbeats_in_flight.pop_front();
+        }
+
+        let msg = SyncMessage::Heartbeat {
+            seq: Some(self.heartbeat_seq),
+        };
+        match serde_json::to_vec(&msg) {
+            Ok(data) => {
+                if let Err(e) = self.enqueue(None, data, msg.priority()) {
+                    warn!(error = %e, "Failed to queue heartbeat");
+                }
+            }
+            Err(e) => warn!(error = %e, "Failed to encode heartbeat"),
+        }
+    }
+
+    /// Keep a timed-out participant in the lobby as away (HOST ONLY)
+    pub fn mark_away(&mut self, participant_id: Uuid) {
+        self.away.insert(participant_id);
+    }
+
+    pub fn is_away(&self, participant_id: &Uuid) -> bool {
+        self.away.contains(participant_id)
+    }
+
+    pub fn away_participants(&self) -> Vec<Uuid> {
+        let mut away: Vec<Uuid> = self.away.iter().copied().collect();
+        away.sort();
+        away
+    }
+
+    /// Broadcast the hash of our lobby state so guests can detect drift (HOST ONLY)
+    pub fn broadcast_state_checksum(&mut self, checksum: u64) -> Result<()> {
+        let sync_msg = self
+            .event_sync
+            .create_state_checksum(checksum)
+            .map_err(|e| crate::infrastructure::error::P2PError::SendFailed(e.to_string()))?;
+
+        let data = serde_json::to_vec(&sync_msg)
+            .map_err(crate::infrastructure::error::P2PError::Serialization)?;
+
+        self.enqueue(None, data, sync_msg.priority())?;
 
-        info!("Sent full sync request to host");
+        trace!(checksum, "Queued state checksum");
         Ok(())
     }
 
+    /// Turn a part of a streamed snapshot into domain commands for
+    /// `apply_next_snapshot_page`
+    fn queue_snapshot_part(&mut self, part: SnapshotPart, index: u32, total: u32) {
+        if let SnapshotPart::Header {
+            lobby_id,
+            as_of_sequence,
+            ..
+        } = &part
+        {
+            self.snapshot_pages.clear();
+            self.snapshot_stream = Some((*lobby_id, *as_of_sequence));
+        }
+        let Some((lobby_id, as_of_sequence)) = self.snapshot_stream else {
+            return;
+        };
+
+        // Like `apply_snapshot_to_domain`: only replay events after the snapshot
+        let commands = match &part {
+            SnapshotPart::Events { events } => {
+                let translator = EventTranslator::new(lobby_id);
+                events
+                    .iter()
+                    .filter(|e| e.sequence > as_of_sequence)
+                    .filter_map(|e| translator.to_domain_command(&e.event))
+                    .collect()
+            }
+            _ => part.to_commands(lobby_id),
+        };
+        debug!(index = %index, total = %total, commands = %commands.len(), "Snapshot part received");
+
+        self.snapshot_pages.push_back(SnapshotPage {
+            commands,
+            applied: index + 1,
+            total,
+        });
+
+        if index + 1 == total {
+            self.snapshot_stream = None;
+            if let Some(requested_at) = self.sync_requested_at.take() {
+                metrics::sync_latency(self.clock.now().saturating_duration_since(requested_at));
+            }
+        }
+    }
+
+    /// Hand the next part of a streamed snapshot to the domain
+    ///
+    /// `SessionLoop` calls this once its command queue has drained, so large
+    /// lobbies load page by page without overflowing the queue.
+    /// Returns false when no part is waiting.
+    pub fn apply_next_snapshot_page(&mut self) -> bool {
+        let Some(page) = self.snapshot_pages.pop_front() else {
+            return false;
+        };
+
+        info!(applied = %page.applied, total = %page.total, "Applying snapshot part");
+        self.pending_domain_commands
+            .extend(page.commands.into_iter().map(|command| (command, None)));
+        self.inbound_events.push(ConnectionEvent::SnapshotProgress {
+            applied: page.applied,
+            total: page.total,
+        });
+        true
+    }
+
+    /// Is a streamed snapshot still arriving or waiting to be applied?
+    pub fn is_applying_snapshot(&self) -> bool {
+        self.snapshot_stream.is_some() || !self.snapshot_pages.is_empty()
+    }
+
     /// Apply snapshot to domain layer (converts snapshot to domain commands)
     #[instrument(skip(self, snapshot, events), fields(
         snapshot.lobby_id = %snapshot.lobby_id,
@@ -132,6 +525,18 @@ impl P2PLoop {
             .cloned()
             .expect("Snapshot must have a host participant");
 
+        // Bind the host's peer, so its timeout starts the takeover
+        if let Some(state) = self
+            .host_peer
+            .and_then(|host| self.peer_registry.get_peer_mut(&host))
+        {
+            state.set_participant_info(
+                host_participant.id(),
+                host_participant.name().to_string(),
+                true,
+            );
+        }
+
         tracing::info!(
             "📥 GUEST: Creating lobby with host {} (id: {})",
             host_participant.name(),
@@ -144,7 +549,8 @@ impl P2PLoop {
             lobby_name: snapshot.name.clone(),
             host: host_participant,
         };
-        self.pending_domain_commands.push_back(create_lobby_cmd);
+        self.pending_domain_commands
+            .push_back((create_lobby_cmd, None));
 
         // Add non-host participants directly from the snapshot. These are already
         // the authoritative final state; we must NOT also replay the historical
@@ -157,27 +563,95 @@ impl P2PLoop {
                     participant.name(),
                     participant.id()
                 );
-                self.pending_domain_commands
-                    .push_back(DomainCommand::AddParticipant {
+                self.pending_domain_commands.push_back((
+                    DomainCommand::AddParticipant {
                         lobby_id: snapshot.lobby_id,
                         participant: participant.clone(),
-                    });
+                    },
+                    None,
+                ));
             }
         }
 
+        if snapshot.settings != LobbySettings::default() {
+            self.pending_domain_commands.push_back((
+                DomainCommand::UpdateLobbySettings {
+                    lobby_id: snapshot.lobby_id,
+                    host_id: snapshot.host_id,
+                    settings: snapshot.settings,
+                },
+                None,
+            ));
+        }
+        for participant_id in &snapshot.co_hosts {
+            self.pending_domain_commands.push_back((
+                DomainCommand::SetCoHost {
+                    lobby_id: snapshot.lobby_id,
+                    host_id: snapshot.host_id,
+                    participant_id: *participant_id,
+                    co_host: true,
+                },
+                None,
+            ));
+        }
+
+        for config in &snapshot.activity_queue {
+            self.pending_domain_commands.push_back((
+                DomainCommand::QueueActivity {
+                    lobby_id: snapshot.lobby_id,
+                    config: config.clone(),
+                },
+                None,
+            ));
+        }
+
+        for message in &snapshot.chat {
+            self.pending_domain_commands.push_back((
+                DomainCommand::AddChatMessage {
+                    lobby_id: snapshot.lobby_id,
+                    message: message.clone(),
+                },
+                None,
+            ));
+        }
+
+        // After the queue: a countdown needs an activity to count down to
+        for participant_id in &snapshot.ready {
+            self.pending_domain_commands.push_back((
+                DomainCommand::SetReady {
+                    lobby_id: snapshot.lobby_id,
+                    participant_id: *participant_id,
+                    ready: true,
+                },
+                None,
+            ));
+        }
+        if let Some(ends_at) = snapshot.countdown_ends_at {
+            self.pending_domain_commands.push_back((
+                DomainCommand::StartCountdown {
+                    lobby_id: snapshot.lobby_id,
+                    requester_id: snapshot.host_id,
+                    ends_at,
+                },
+                None,
+            ));
+        }
+
         // Only translate events whose sequence is AFTER the snapshot's as_of_sequence.
         // Events at or before that sequence are already represented by the snapshot
         // participants above — replaying them would produce duplicate GuestJoined etc.
+        // (The snapshot may be for a topic lobby, so translate for its lobby ID.)
+        let translator = EventTranslator::new(snapshot.lobby_id);
         let delta_events = events
             .into_iter()
             .filter(|e| e.sequence > snapshot.as_of_sequence);
         for event in delta_events {
-            if let Some(cmd) = self.translator.to_domain_command(&event.event) {
+            if let Some(cmd) = translator.to_domain_command(&event.event) {
                 tracing::debug!(
                     sequence = %event.sequence,
                     "📥 GUEST: Applying post-snapshot delta event"
                 );
-                self.pending_domain_commands.push_back(cmd);
+                self.pending_domain_commands.push_back((cmd, None));
             }
         }
 
@@ -185,8 +659,20 @@ impl P2PLoop {
     }
 
     /// Broadcast a domain event (HOST ONLY)
-    #[instrument(skip(self, event), fields(event_type = ?std::mem::discriminant(&event)))]
     pub fn broadcast_domain_event(&mut self, event: CoreDomainEvent) -> Result<()> {
+        self.broadcast_correlated_event(event, None)
+    }
+
+    /// Broadcast a domain event, tagged with the user action that caused it (HOST ONLY)
+    #[instrument(skip(self, event), fields(
+        event_type = ?std::mem::discriminant(&event),
+        correlation_id = ?correlation_id
+    ))]
+    pub fn broadcast_correlated_event(
+        &mut self,
+        event: CoreDomainEvent,
+        correlation_id: Option<CorrelationId>,
+    ) -> Result<()> {
         // Translate core event to P2P event
         let p2p_event = self.translator.to_p2p_event(event).ok_or_else(|| {
             crate::infrastructure::error::P2PError::SendFailed(
@@ -194,19 +680,45 @@ impl P2PLoop {
             )
         })?;
 
+        self.broadcast_correlated_p2p_event(p2p_event, correlation_id)
+    }
+
+    /// Broadcast an already translated event, e.g. one the caller enriched (HOST ONLY)
+    pub fn broadcast_correlated_p2p_event(
+        &mut self,
+        p2p_event: DomainEvent,
+        correlation_id: Option<CorrelationId>,
+    ) -> Result<()> {
         // Create sequenced lobby event
         let sync_msg = self
             .event_sync
-            .create_event(p2p_event)
+            .create_correlated_event(p2p_event, correlation_id)
             .map_err(|e| crate::infrastructure::error::P2PError::SendFailed(e.to_string()))?;
 
-        // Serialize and broadcast
+        // Persist before anyone sees it
+        if let (Some(store), SyncMessage::EventBroadcast { event }) =
+            (self.event_store.as_mut(), &sync_msg)
+            && let Err(e) = store.append(event)
+        {
+            warn!(sequence = %event.sequence, "Failed to persist event: {}", e);
+        }
+
+        // Nobody to send it to: keep it for the first guest
+        if let (Some(outbox), SyncMessage::EventBroadcast { event }) =
+            (self.outbox.as_mut(), &sync_msg)
+            && self.connection.connected_peers().is_empty()
+            && let Err(e) = outbox.append(event)
+        {
+            warn!(sequence = %event.sequence, "Failed to buffer event in outbox: {}", e);
+        }
+
+        // Serialize and queue for broadcast
         let data = serde_json::to_vec(&sync_msg)
             .map_err(crate::infrastructure::error::P2PError::Serialization)?;
 
-        self.connection.broadcast(data)?;
+        self.enqueue(None, data, sync_msg.priority())?;
 
-        trace!("Domain event broadcast complete");
+        trace!("Domain event queued for broadcast");
         Ok(())
     }
 
@@ -214,89 +726,136 @@ impl P2PLoop {
     #[instrument(skip(self), fields(peer_count = %self.connection.connected_peers().len()))]
     pub fn poll(&mut self) -> usize {
         let mut processed = 0;
+        let mut peers_changed = false;
+
+        // 0. Send anything queued since the last poll
+        self.flush_outbound();
 
         // 1. Poll connection for network events
         let connection_events = self.connection.poll_events();
 
         for event in connection_events {
+            if let ConnectionEvent::PeerConnected(peer)
+            | ConnectionEvent::MessageReceived { from: peer, .. } = &event
+                && self.peer_registry.is_banned(peer)
+            {
+                trace!(peer_id = %peer, "Dropping event from banned peer");
+                continue;
+            }
+
             processed += 1;
 
             match &event {
                 ConnectionEvent::PeerConnected(peer_id) => {
                     self.peer_registry.add_peer(*peer_id);
+                    peers_changed = true;
                     debug!(peer_id = %peer_id, "Added peer to registry");
+                    self.send_hello(*peer_id);
+                    self.send_crdt_state(*peer_id);
+                    self.send_timeouts_to(*peer_id);
                 }
                 ConnectionEvent::MessageReceived { from, data } => {
-                    self.peer_registry.update_last_seen(from);
+                    if self.is_rate_limited(*from, data.len()) {
+                        continue;
+                    }
+
+                    self.peer_registry.record_received(from);
                     trace!(peer_id = %from, bytes = %data.len(), "Received message");
 
                     if let Ok(sync_msg) = serde_json::from_slice::<SyncMessage>(data) {
                         debug!(peer_id = %from, "Received sync message");
 
-                        match self.event_sync.handle_message(*from, sync_msg) {
-                            Ok(SyncResponse::ProcessCommand { command }) => {
-                                info!(peer_id = %from, "HOST: Processing command from peer");
-                                self.pending_domain_commands.push_back(command);
-                            }
-                            Ok(SyncResponse::ApplyEvents { events }) => {
-                                info!(events = %events.len(), "Applying events from sync");
-                                self.inbound_lobby_events.extend(events);
-                            }
-                            Ok(SyncResponse::SendMessage { to, message }) => {
-                                if let Ok(data) = serde_json::to_vec(&message) {
-                                    if let Some(peer) = to {
-                                        debug!(peer_id = %peer, "Sending sync response");
-                                        let _ = self.connection.send_to(PeerId(peer.inner()), data);
-                                    } else {
-                                        debug!("Broadcasting sync response");
-                                        let _ = self.connection.broadcast(data);
-                                    }
-                                }
-                            }
-                            Ok(SyncResponse::ApplySnapshot { snapshot, events }) => {
-                                info!(events = %events.len(), "Applying snapshot");
-                                self.apply_snapshot_to_domain(snapshot, events);
-                            }
-                            Ok(SyncResponse::NeedSnapshot {
-                                for_peer,
-                                since_sequence,
-                            }) => {
-                                info!(
-                                    peer_id = %for_peer,
-                                    since_sequence = %since_sequence,
-                                    "Peer needs snapshot - bubbling up to SessionLoop"
-                                );
-                                self.inbound_events.push(ConnectionEvent::SyncNeeded {
-                                    for_peer,
-                                    since_sequence,
-                                });
-                            }
-                            Ok(SyncResponse::None) => {
-                                trace!("Sync message processed (no action)");
-                            }
-                            Err(e) => {
-                                warn!(error = ?e, "Failed to handle sync message");
-                            }
+                        if !self.admit_host_message(*from, &sync_msg) {
+                            continue;
+                        }
+
+                        if matches!(
+                            sync_msg,
+                            SyncMessage::EventBroadcast { .. }
+                                | SyncMessage::FullSyncResponse { .. }
+                                | SyncMessage::SnapshotChunk { .. }
+                                | SyncMessage::PeerRoster { .. }
+                                | SyncMessage::JoinAccepted { .. }
+                        ) && !self.event_sync.is_host()
+                        {
+                            self.adopt_host_peer(*from);
+                        }
+
+                        self.handle_sync_message(*from, sync_msg);
+                    } else if let Ok(envelope) = serde_json::from_slice::<P2PMessage>(data) {
+                        match envelope {
+                            P2PMessage {
+                                route: Some(route),
+                                kind: MessageKind::Relay { payload },
+                                ..
+                            } => self.handle_relay(*from, route, payload),
+                            P2PMessage {
+                                topic: Some(topic),
+                                kind: MessageKind::Application { payload },
+                                ..
+                            } => self.handle_topic_message(*from, topic, payload),
+                            _ => trace!(peer_id = %from, "Ignoring unroutable envelope"),
                         }
                     }
                 }
                 ConnectionEvent::PeerDisconnected(peer_id) => {
                     self.peer_registry.mark_peer_disconnected(peer_id);
+                    peers_changed = true;
                     debug!(peer_id = %peer_id, "Marked peer as disconnected");
                 }
                 ConnectionEvent::PeerTimedOut { peer_id, .. } => {
                     self.peer_registry.remove_peer(peer_id);
+                    self.peer_capabilities.remove(peer_id);
+                    if let Some(limiter) = &mut self.rate_limiter {
+                        limiter.remove(peer_id);
+                    }
+                    peers_changed = true;
                     debug!(peer_id = %peer_id, "Removed peer after timeout");
                 }
-                // SyncNeeded is synthesized internally inside MessageReceived above and
-                // pushed directly to inbound_events — it never arrives from poll_events().
-                ConnectionEvent::SyncNeeded { .. } => {}
+                ConnectionEvent::Reconnecting { attempt } => {
+                    self.reconnecting = true;
+                    warn!(attempt = %attempt, "Connection lost, reconnecting");
+                }
+                ConnectionEvent::Reconnected { peer_id } => {
+                    self.reconnecting = false;
+                    info!(peer_id = %peer_id, "Reconnected with new peer ID");
+
+                    if self.event_sync.is_host() {
+                        self.mark_local_as_host();
+                    }
+                }
+                // SyncNeeded / JoinAccepted / ResumeRejected / PeerRateLimited /
+                // StateChecksum / CrdtUpdated are synthesized internally inside
+                // MessageReceived above and pushed directly to inbound_events —
+                // they never arrive from poll_events().
+                ConnectionEvent::SyncNeeded { .. }
+                | ConnectionEvent::JoinAccepted { .. }
+                | ConnectionEvent::ResumeRejected
+                | ConnectionEvent::PeerRateLimited { .. }
+                | ConnectionEvent::StateChecksum { .. }
+                | ConnectionEvent::CrdtUpdated
+                | ConnectionEvent::HostSuperseded { .. }
+                | ConnectionEvent::TopicOpened { .. }
+                | ConnectionEvent::TopicSyncNeeded { .. }
+                | ConnectionEvent::TimeoutsChanged { .. }
+                | ConnectionEvent::SnapshotProgress { .. }
+                | ConnectionEvent::TakeoverDeclined { .. } => {}
             }
 
             self.inbound_events.push(event);
         }
 
-        // 2. Check for grace period timeouts
+        // 2. Heartbeats: announce ourselves, start the grace period of silent peers
+        self.maybe_send_heartbeat();
+        self.presence.prune();
+        if let Some(silence) = self.timeouts.silence_timeout() {
+            let local = self.connection.local_peer_id();
+            for peer_id in self.peer_registry.mark_silent_peers(silence, local) {
+                debug!(peer_id = %peer_id, "Peer went silent, starting grace period");
+            }
+        }
+
+        // 2.5 Check for grace period timeouts
         let timed_out_peers = self.peer_registry.check_grace_periods();
         for peer_id in timed_out_peers {
             if let Some(peer_state) = self.peer_registry.get_peer(&peer_id) {
@@ -315,94 +874,1532 @@ impl P2PLoop {
             }
 
             self.peer_registry.remove_peer(&peer_id);
+            self.peer_capabilities.remove(&peer_id);
+            if let Some(limiter) = &mut self.rate_limiter {
+                limiter.remove(&peer_id);
+            }
+            peers_changed = true;
+        }
+
+        // 3. Keep the relay topology in sync with peer membership
+        if peers_changed {
+            if self.event_sync.is_host() {
+                self.broadcast_peer_roster();
+                self.flush_outbox();
+            } else {
+                self.refresh_topology();
+            }
         }
 
-        // 3. Translate incoming lobby events to domain commands
-        let lobby_events = std::mem::take(&mut self.inbound_lobby_events);
+        // 4. Translate incoming lobby events to domain commands
+        //    (held back until a streamed snapshot is fully applied)
+        let lobby_events = if self.is_applying_snapshot() {
+            Vec::new()
+        } else {
+            std::mem::take(&mut self.inbound_lobby_events)
+        };
         for lobby_event in lobby_events {
             if let Some(cmd) = self.translator.to_domain_command(&lobby_event.event) {
                 trace!(sequence = %lobby_event.sequence, "Translated P2P event → Domain command");
-                self.pending_domain_commands.push_back(cmd);
+                let correlation = lobby_event
+                    .correlation_id
+                    .map(|id| Correlation::event_received(id, lobby_event.sequence));
+                self.pending_domain_commands.push_back((cmd, correlation));
             }
         }
 
+        // 5. Send responses produced while handling messages
+        self.flush_outbound();
+
         if processed > 0 {
             debug!(processed = %processed, "Poll cycle complete");
         }
+        metrics::events_processed("network", processed);
 
         processed
     }
 
-    /// Send full sync to a specific peer (HOST ONLY)
-    #[instrument(skip(self, snapshot), fields(
-        peer_id = %peer_id,
-        snapshot.lobby_id = %snapshot.lobby_id,
-        participants = %snapshot.participants.len()
-    ))]
-    pub fn send_full_sync_to_peer(
+    /// Queue serialized data for `target` (`None` = broadcast)
+    fn enqueue(
         &mut self,
-        peer_id: PeerId,
-        snapshot: LobbySnapshot,
+        target: Option<PeerId>,
+        data: Vec<u8>,
+        priority: MessagePriority,
     ) -> Result<()> {
-        info!("Sending full sync to peer");
+        // Never drop control or lobby traffic: make room by sending now
+        if self.outbound.is_full() && !priority.is_bulk() {
+            self.flush_outbound();
+        }
 
-        let sync_msg = self
-            .event_sync
-            .create_full_sync_response(0, snapshot)
-            .map_err(|e| crate::infrastructure::error::P2PError::SendFailed(e.to_string()))?;
+        self.outbound
+            .push_with_priority(OutboundMessage { target, data }, priority)
+            .map_err(|e| P2PError::SendFailed(e.to_string()))
+    }
 
-        let data = serde_json::to_vec(&sync_msg)
-            .map_err(crate::infrastructure::error::P2PError::Serialization)?;
+    /// Send queued messages, highest priority class first.
+    ///
+    /// Control and lobby traffic is always sent in full; at most `batch_size`
+    /// bulk (chat/ephemeral) messages go out per call.
+    /// Returns the number of messages sent.
+    pub fn flush_outbound(&mut self) -> usize {
+        let mut sent = 0;
+        let mut bulk_budget = self.batch_size;
 
-        self.connection.send_to(PeerId(peer_id.inner()), data)?;
+        while let Some(priority) = self.outbound.peek_priority() {
+            if priority.is_bulk() {
+                if bulk_budget == 0 {
+                    trace!(remaining = %self.outbound.len(), "Bulk budget exhausted");
+                    break;
+                }
+                bulk_budget -= 1;
+            }
 
-        debug!("Full sync sent successfully");
-        Ok(())
-    }
+            let Some(message) = self.outbound.pop() else {
+                break;
+            };
 
-    // ... rest of methods unchanged ...
+            let result = match message.target {
+                Some(peer) if self.peer_registry.is_banned(&peer) => Ok(()),
+                Some(peer) => self.connection.send_to(peer, message.data),
+                None if self.peer_registry.has_bans() => self.broadcast_unbanned(message.data),
+                None => self.connection.broadcast(message.data),
+            };
 
-    pub fn drain_events(&mut self) -> Vec<ConnectionEvent> {
-        std::mem::take(&mut self.inbound_events)
-    }
+            match result {
+                Ok(()) => {
+                    sent += 1;
+                    match message.target {
+                        Some(peer) => self.peer_registry.record_sent(&peer),
+                        None => {
+                            for peer in self.connection.connected_peers() {
+                                self.peer_registry.record_sent(&peer);
+                            }
+                        }
+                    }
+                }
+                Err(e) => warn!(priority = ?priority, "Failed to send queued message: {}", e),
+            }
+        }
 
-    pub fn drain_domain_commands(&mut self) -> Vec<DomainCommand> {
-        self.pending_domain_commands.drain(..).collect()
+        metrics::messages_sent(sent);
+        metrics::outbound_queue_depth(self.outbound.len());
+        sent
     }
 
-    pub fn peer_registry(&self) -> &PeerRegistry {
-        &self.peer_registry
+    /// Deliver events buffered while no peer was connected (HOST ONLY)
+    ///
+    /// Guests skip the ones they already got through their full sync.
+    fn flush_outbox(&mut self) {
+        if self.connection.connected_peers().is_empty() {
+            return;
+        }
+        let Some(outbox) = self.outbox.as_mut() else {
+            return;
+        };
+
+        let events = match outbox.load() {
+            Ok(events) if events.is_empty() => return,
+            Ok(events) => events,
+            Err(e) => {
+                warn!("Failed to load outbox: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = outbox.clear() {
+            warn!("Failed to clear outbox: {}", e);
+        }
+
+        info!(count = %events.len(), "Delivering events buffered while offline");
+        for event in events {
+            let msg = SyncMessage::EventBroadcast { event };
+            match serde_json::to_vec(&msg) {
+                Ok(data) => {
+                    if let Err(e) = self.enqueue(None, data, msg.priority()) {
+                        warn!("Failed to queue buffered event: {}", e);
+                    }
+                }
+                Err(e) => warn!("Failed to serialize buffered event: {}", e),
+            }
+        }
     }
 
-    pub fn peer_registry_mut(&mut self) -> &mut PeerRegistry {
-        &mut self.peer_registry
+    /// Broadcast to every connected peer except banned ones
+    fn broadcast_unbanned(&mut self, data: Vec<u8>) -> Result<()> {
+        let peers: Vec<PeerId> = self
+            .connection
+            .connected_peers()
+            .into_iter()
+            .filter(|peer| !self.peer_registry.is_banned(peer))
+            .collect();
+
+        for peer in peers {
+            self.connection.send_to(peer, data.clone())?;
+        }
+        Ok(())
     }
 
-    pub fn local_peer_id(&self) -> Option<PeerId> {
-        self.connection.local_peer_id()
+    /// Ban the peer of a kicked participant
+    ///
+    /// Its messages and reconnects are dropped from now on, and it receives no
+    /// further broadcasts. Returns the banned peer, if it was known.
+    pub fn ban_participant(&mut self, participant_id: Uuid) -> Option<PeerId> {
+        let peer = self
+            .peer_participants
+            .get_peer(&participant_id)
+            .or_else(|| self.peer_registry.find_by_participant_id(participant_id))?;
+
+        self.peer_registry.ban_peer(peer);
+        self.peer_participants.forget_participant(&participant_id);
+        self.pending_joins.retain(|pending| *pending != peer);
+        for topic in self.topics.values_mut() {
+            topic.pending_joins.retain(|pending| *pending != peer);
+        }
+
+        info!(peer_id = %peer, participant_id = %participant_id, "Banned peer");
+        Some(peer)
     }
 
-    pub fn connected_peers(&self) -> Vec<PeerId> {
-        // Use the peer registry as the source of truth — it is authoritatively
-        // updated during poll() via PeerConnected / PeerDisconnected events.
-        // The raw socket's connected_peers() only reflects what update_peers()
-        // last saw and will return stale (empty) results when called outside
-        // of poll_events().
-        self.peer_registry
-            .all_peers()
-            .filter(|(_, state)| !state.is_timed_out() && !state.is_disconnected())
-            .map(|(peer_id, _)| *peer_id)
-            .collect()
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.peer_registry.is_banned(peer_id)
     }
 
-    pub fn current_sequence(&self) -> u64 {
-        self.event_sync.current_sequence()
+    /// Apply the inbound rate limit to a message from `from`.
+    /// Returns true if the message must be dropped.
+    fn is_rate_limited(&mut self, from: PeerId, bytes: usize) -> bool {
+        // Guests trust the host they follow (snapshots are large)
+        if self.host_peer == Some(from) {
+            return false;
+        }
+        let Some(limiter) = &mut self.rate_limiter else {
+            return false;
+        };
+
+        match limiter.check(from, bytes, self.clock.now()) {
+            RateDecision::Allow => false,
+            RateDecision::Throttle { violations, report } => {
+                if report {
+                    let auto_kick = limiter.should_kick(violations);
+                    let participant_id = self.peer_participants.get_participant(&from);
+                    warn!(
+                        peer_id = %from,
+                        violations = %violations,
+                        auto_kick = %auto_kick,
+                        "Peer exceeded inbound rate limit, dropping messages"
+                    );
+
+                    self.inbound_events.push(ConnectionEvent::PeerRateLimited {
+                        peer_id: from,
+                        participant_id,
+                        violations,
+                        auto_kick,
+                    });
+                }
+                true
+            }
+        }
     }
 
-    #[instrument(skip(self))]
-    pub fn promote_to_host(&mut self) {
-        info!("Promoting to HOST in P2P layer");
-        self.event_sync.promote_to_host();
+    /// Dispatch a sync message received (directly or via relay) from `from`
+    fn handle_sync_message(&mut self, from: PeerId, sync_msg: SyncMessage) {
+        match self.event_sync.handle_message(from, sync_msg) {
+            Ok(SyncResponse::ProcessCommand {
+                command,
+                correlation_id,
+            }) => {
+                let correlation = correlation_id.map(|id| Correlation::command_received(id, from));
+                let span = correlation
+                    .as_ref()
+                    .map_or_else(tracing::Span::none, |c| c.span().clone());
+                let _entered = span.enter();
+                info!(peer_id = %from, "HOST: Processing command from peer");
+                if matches!(command, DomainCommand::JoinLobby { .. }) {
+                    self.pending_joins.push_back(from);
+                }
+                let sender = self.peer_participants.get_participant(&from);
+                self.pending_requests
+                    .push_back((command, sender, correlation));
+            }
+            Ok(SyncResponse::ApplyEvents { events }) => {
+                info!(events = %events.len(), "Applying events from sync");
+                self.inbound_lobby_events.extend(events);
+            }
+            Ok(SyncResponse::SendMessage { to, message }) => {
+                if let Some(peer) = to {
+                    debug!(peer_id = %peer, "Sending sync response");
+                    let _ = self.send_to_peer(peer, &message);
+                } else if let Ok(data) = serde_json::to_vec(&message) {
+                    debug!("Broadcasting sync response");
+                    let _ = self.enqueue(None, data, message.priority());
+                }
+            }
+            Ok(SyncResponse::ApplySnapshot { snapshot, events }) => {
+                info!(events = %events.len(), "Applying snapshot");
+                if let Some(requested_at) = self.sync_requested_at.take() {
+                    metrics::sync_latency(self.clock.now().saturating_duration_since(requested_at));
+                }
+                self.snapshot_pages.clear();
+                self.snapshot_stream = None;
+                self.apply_snapshot_to_domain(snapshot, events);
+            }
+            Ok(SyncResponse::ApplySnapshotPart { part, index, total }) => {
+                self.queue_snapshot_part(part, index, total);
+            }
+            Ok(SyncResponse::NeedSnapshot {
+                for_peer,
+                since_sequence,
+            }) => {
+                info!(
+                    peer_id = %for_peer,
+                    since_sequence = %since_sequence,
+                    "Peer needs snapshot - bubbling up to SessionLoop"
+                );
+                self.inbound_events.push(ConnectionEvent::SyncNeeded {
+                    for_peer,
+                    since_sequence,
+                });
+            }
+            Ok(SyncResponse::UpdateRoster { peers }) => {
+                self.apply_peer_roster(from, &peers);
+                self.peer_roster = peers;
+                self.refresh_topology();
+            }
+            Ok(SyncResponse::Joined {
+                participant_id,
+                resume_token,
+            }) => {
+                info!(participant_id = %participant_id, "GUEST: Bound to participant");
+                self.resume_token = Some(resume_token);
+                self.bind_local_participant(participant_id);
+                self.inbound_events.push(ConnectionEvent::JoinAccepted {
+                    participant_id,
+                    resume_token,
+                });
+            }
+            Ok(SyncResponse::ResumeRequested { peer, resume_token }) => {
+                self.handle_resume_request(peer, resume_token);
+            }
+            Ok(SyncResponse::ResumeRejected) => {
+                self.resume_token = None;
+                self.inbound_events.push(ConnectionEvent::ResumeRejected);
+            }
+            Ok(SyncResponse::UpdatePresence {
+                from,
+                participant_id,
+                presence,
+            }) => {
+                // Guests may only speak for themselves
+                if self.event_sync.is_host()
+                    && self
+                        .peer_participants
+                        .get_participant(&from)
+                        .is_some_and(|bound| bound != participant_id)
+                {
+                    warn!(peer_id = %from, participant_id = %participant_id, "Dropping spoofed presence");
+                    return;
+                }
+                if self.presence.update(participant_id, presence) {
+                    trace!(participant_id = %participant_id, ?presence, "Presence changed");
+                }
+            }
+            Ok(SyncResponse::NegotiateCapabilities { from, capabilities }) => {
+                let negotiated = self.capabilities.negotiate(capabilities);
+                debug!(peer_id = %from, %negotiated, "Negotiated capabilities");
+                self.peer_capabilities.insert(from, negotiated);
+            }
+            Ok(SyncResponse::TakeoverDeclined {
+                from,
+                participant_id,
+            }) => {
+                info!(peer_id = %from, participant_id = %participant_id, "Guest declined to take over as host");
+                self.inbound_events
+                    .push(ConnectionEvent::TakeoverDeclined { participant_id });
+            }
+            Ok(SyncResponse::UpdateTimeouts { config }) => {
+                info!(?config, "Adopting host timeout settings");
+                self.set_timeouts(config);
+                self.inbound_events
+                    .push(ConnectionEvent::TimeoutsChanged { config });
+            }
+            Ok(SyncResponse::VerifyChecksum { sequence, checksum }) => {
+                self.inbound_events
+                    .push(ConnectionEvent::StateChecksum { sequence, checksum });
+            }
+            Ok(SyncResponse::ApplyCrdtOp { from, op }) => self.apply_crdt_op(from, op),
+            Ok(SyncResponse::MergeCrdtState { from, state }) => {
+                let Some(changed) = self
+                    .crdt
+                    .as_ref()
+                    .map(|crdt| crdt.state().changed_by(&state))
+                else {
+                    warn!(peer_id = %from, "Dropping CRDT state (CRDT sync disabled)");
+                    return;
+                };
+                // Only the host may hand us changes to other participants
+                let bound = self.crdt_participant(from);
+                if !self.is_host_peer(from) && changed.iter().any(|id| Some(*id) != bound) {
+                    if !self.event_sync.is_host() && self.host_peer.is_none() {
+                        debug!(peer_id = %from, "Holding CRDT state until the host is known");
+                        self.held_crdt_states.insert(from, state);
+                    } else {
+                        warn!(peer_id = %from, "Dropping CRDT state changing other participants");
+                    }
+                    return;
+                }
+                self.merge_crdt_state(from, &state);
+            }
+            Ok(SyncResponse::HeartbeatAcked { from, seq, at }) => {
+                if let Some((_, sent_at)) = self
+                    .heartbeats_in_flight
+                    .iter()
+                    .find(|(in_flight, _)| *in_flight == seq)
+                {
+                    let rtt = self.clock.now().saturating_duration_since(*sent_at);
+                    trace!(peer_id = %from, rtt_ms = %rtt.as_millis(), "Heartbeat acked");
+                    self.peer_registry.record_rtt(&from, rtt);
+
+                    if let Some(host_ms) = at
+                        && !self.event_sync.is_host()
+                        && self.host_peer == Some(from)
+                    {
+                        let received = self.clock.unix_millis();
+                        let sent = received.saturating_sub(rtt.as_millis() as u64);
+                        self.clock_sync.record(sent, host_ms, received);
+                    }
+                }
+            }
+            Ok(SyncResponse::None) => {
+                trace!("Sync message processed (no action)");
+            }
+            Err(e) => {
+                warn!(error = ?e, "Failed to handle sync message");
+            }
+        }
+    }
+
+    /// Fence host-originated messages by epoch (split-brain prevention)
+    ///
+    /// - A host that sees a superseding claim steps down and resyncs from the
+    ///   winner.
+    /// - A guest rejects claims that lose against the host it follows (while
+    ///   that host is still connected). When it switches hosts it drops the
+    ///   message and asks the new host for a full sync, since the two event
+    ///   logs may have diverged.
+    fn admit_host_message(&mut self, from: PeerId, message: &SyncMessage) -> bool {
+        let Some(epoch) = message.host_epoch() else {
+            return true;
+        };
+        let claim = HostFence::new(epoch, from);
+
+        if self.event_sync.is_host() {
+            let Some(local) = self.local_peer_id() else {
+                return false;
+            };
+            if claim.supersedes(&HostFence::new(self.epoch(), local)) {
+                self.step_down(from, epoch);
+            } else {
+                warn!(peer_id = %from, epoch, "Ignoring message from a stale host");
+            }
+            return false;
+        }
+
+        let Some(host) = self.host_peer else {
+            return true;
+        };
+        let current = HostFence::new(self.epoch(), host);
+
+        if from == host {
+            if epoch < current.epoch {
+                warn!(peer_id = %from, epoch, "Dropping message from an older epoch");
+                return false;
+            }
+            return true;
+        }
+
+        let incumbent_alive = self.connected_peers().contains(&host);
+        if epoch < current.epoch || (incumbent_alive && !claim.supersedes(&current)) {
+            warn!(
+                peer_id = %from,
+                epoch,
+                current_epoch = current.epoch,
+                "Rejecting message from a fenced-off host"
+            );
+            return false;
+        }
+
+        if matches!(
+            message,
+            SyncMessage::FullSyncResponse { .. } | SyncMessage::SnapshotChunk { .. }
+        ) {
+            return true;
+        }
+
+        info!(old = %host, new = %from, epoch, "Following new host, requesting full sync");
+        self.adopt_host_peer(from);
+        self.request_full_sync_from(from);
+        false
+    }
+
+    /// Give up the host role to `winner` (HOST ONLY)
+    fn step_down(&mut self, winner: PeerId, epoch: u64) {
+        warn!(winner = %winner, epoch, "Another host supersedes us, stepping down");
+
+        self.event_sync.demote_to_guest(epoch);
+        for topic in self.topics.values_mut() {
+            topic.sync.demote_to_guest(epoch);
+            topic.pending_joins.clear();
+        }
+        if let Some(state) = self
+            .local_peer_id()
+            .and_then(|local| self.peer_registry.get_peer_mut(&local))
+        {
+            state.is_host = false;
+        }
+        self.pending_joins.clear();
+        self.pending_requests.clear();
+        self.adopt_host_peer(winner);
+        self.request_full_sync_from(winner);
+
+        self.inbound_events.push(ConnectionEvent::HostSuperseded {
+            new_host: winner,
+            epoch,
+        });
+    }
+
+    /// Ask a specific host for a full sync
+    fn request_full_sync_from(&mut self, host: PeerId) {
+        match self.event_sync.request_full_sync() {
+            Ok(msg) => {
+                if let Err(e) = self.send_to_peer(host, &msg) {
+                    warn!(peer_id = %host, error = %e, "Failed to request full sync");
+                } else {
+                    self.sync_requested_at = Some(self.clock.now());
+                }
+            }
+            Err(e) => warn!(error = ?e, "Cannot request full sync"),
+        }
+    }
+
+    /// Carry another lobby over this session (e.g. a breakout room)
+    ///
+    /// Its events are sequenced independently and sent tagged with the lobby
+    /// ID as topic. Guests open topics automatically when the host uses them.
+    pub fn open_topic(&mut self, lobby_id: Uuid) -> bool {
+        if self.topics.contains_key(&lobby_id) {
+            return false;
+        }
+
+        let sync = if self.event_sync.is_host() {
+            EventSyncManager::new_host(lobby_id)
+        } else {
+            EventSyncManager::new_guest(lobby_id)
+        };
+        self.topics.insert(
+            lobby_id,
+            TopicSync {
+                sync,
+                translator: EventTranslator::new(lobby_id),
+                pending_joins: VecDeque::new(),
+                peer_participants: PeerParticipantMap::new(),
+                local_participant: None,
+            },
+        );
+
+        info!(topic = %lobby_id, "Opened topic");
+        true
+    }
+
+    pub fn close_topic(&mut self, lobby_id: &Uuid) -> bool {
+        self.topics.remove(lobby_id).is_some()
+    }
+
+    pub fn has_topic(&self, lobby_id: &Uuid) -> bool {
+        self.topics.contains_key(lobby_id)
+    }
+
+    /// Lobby IDs of all open topics
+    pub fn topics(&self) -> Vec<Uuid> {
+        let mut topics: Vec<Uuid> = self.topics.keys().copied().collect();
+        topics.sort();
+        topics
+    }
+
+    /// Broadcast a domain event of a topic lobby (HOST ONLY)
+    pub fn broadcast_topic_event(&mut self, topic: Uuid, event: CoreDomainEvent) -> Result<()> {
+        let entry = self.topic_mut(topic)?;
+        let p2p_event = entry
+            .translator
+            .to_p2p_event(event)
+            .ok_or_else(|| P2PError::SendFailed("Event not translatable to P2P".to_string()))?;
+        let sync_msg = entry
+            .sync
+            .create_event(p2p_event)
+            .map_err(|e| P2PError::SendFailed(e.to_string()))?;
+
+        self.send_on_topic(None, topic, &sync_msg)
+    }
+
+    /// Send a command for a topic lobby to the host (GUEST ONLY)
+    pub fn send_topic_command(&mut self, topic: Uuid, command: DomainCommand) -> Result<()> {
+        self.topic_mut(topic)?;
+        let msg = SyncMessage::CommandRequest {
+            command,
+            correlation_id: None,
+        };
+        self.send_on_topic(None, topic, &msg)
+    }
+
+    /// Ask the host for the full state of a topic lobby (GUEST ONLY)
+    pub fn request_topic_sync(&mut self, topic: Uuid) -> Result<()> {
+        let sync_msg = self
+            .topic_mut(topic)?
+            .sync
+            .request_full_sync()
+            .map_err(|e| P2PError::SendFailed(e.to_string()))?;
+
+        self.send_on_topic(None, topic, &sync_msg)
+    }
+
+    /// Send the full state of a topic lobby to `peer` (HOST ONLY)
+    pub fn send_topic_snapshot(
+        &mut self,
+        peer: PeerId,
+        lobby: &konnekt_session_core::Lobby,
+    ) -> Result<()> {
+        let topic = lobby.id();
+        let entry = self.topic_mut(topic)?;
+        let snapshot = LobbySnapshot::from_lobby(lobby, entry.sync.current_sequence());
+        let sync_msg = entry
+            .sync
+            .create_full_sync_response(0, snapshot)
+            .map_err(|e| P2PError::SendFailed(e.to_string()))?;
+
+        self.send_on_topic(Some(peer), topic, &sync_msg)
+    }
+
+    /// Bind the peer of the oldest pending topic join to its new participant
+    /// and tell that peer its participant ID (HOST ONLY)
+    ///
+    /// Returns `false` if no remote join is pending for the topic.
+    #[instrument(skip(self), fields(topic = %topic, participant_id = %participant_id))]
+    pub fn accept_topic_join(&mut self, topic: Uuid, participant_id: Uuid) -> bool {
+        let Some(entry) = self.topics.get_mut(&topic) else {
+            return false;
+        };
+        let Some(peer) = entry.pending_joins.pop_front() else {
+            return false;
+        };
+
+        entry.peer_participants.register(peer, participant_id);
+        let resume_token = entry.peer_participants.issue_token(participant_id);
+        info!(peer_id = %peer, "HOST: Bound joining peer to topic participant");
+
+        let msg = SyncMessage::JoinAccepted {
+            participant_id,
+            resume_token,
+        };
+        if let Err(e) = self.send_on_topic(Some(peer), topic, &msg) {
+            warn!(peer_id = %peer, error = %e, "Failed to send topic JoinAccepted");
+        }
+        true
+    }
+
+    /// Drop the oldest pending join of a topic (its JoinLobby failed) (HOST ONLY)
+    pub fn discard_pending_topic_join(&mut self, topic: &Uuid) {
+        if let Some(peer) = self
+            .topics
+            .get_mut(topic)
+            .and_then(|entry| entry.pending_joins.pop_front())
+        {
+            debug!(peer_id = %peer, topic = %topic, "Discarded pending topic join");
+        }
+    }
+
+    /// Forget a participant that left a topic lobby (HOST ONLY)
+    pub fn release_topic_participant(&mut self, topic: &Uuid, participant_id: Uuid) {
+        if let Some(entry) = self.topics.get_mut(topic) {
+            entry.peer_participants.forget_participant(&participant_id);
+        }
+    }
+
+    /// Our own participant in a topic lobby, once the host accepted our join
+    pub fn topic_participant_id(&self, topic: &Uuid) -> Option<Uuid> {
+        self.topics.get(topic)?.local_participant
+    }
+
+    fn topic_mut(&mut self, topic: Uuid) -> Result<&mut TopicSync> {
+        self.topics
+            .get_mut(&topic)
+            .ok_or_else(|| P2PError::SendFailed(format!("Unknown topic {}", topic)))
+    }
+
+    /// Queue a sync message tagged with `topic`
+    fn send_on_topic(
+        &mut self,
+        target: Option<PeerId>,
+        topic: Uuid,
+        message: &SyncMessage,
+    ) -> Result<()> {
+        let payload = serde_json::to_value(message).map_err(P2PError::Serialization)?;
+        let envelope = P2PMessage::application(payload).with_topic(topic);
+        let data = serde_json::to_vec(&envelope).map_err(P2PError::Serialization)?;
+
+        self.enqueue(target, data, message.priority())
+    }
+
+    /// Route a topic-tagged sync message to that lobby's sync state
+    #[instrument(skip(self, payload), fields(from = %from, topic = %topic))]
+    fn handle_topic_message(&mut self, from: PeerId, topic: Uuid, payload: serde_json::Value) {
+        let message = match serde_json::from_value::<SyncMessage>(payload) {
+            Ok(message) => message,
+            Err(e) => {
+                warn!(error = %e, "Failed to decode topic payload");
+                return;
+            }
+        };
+
+        if !self.topics.contains_key(&topic) {
+            // Guests follow the topics their host opens
+            let from_host = !self.event_sync.is_host() && Some(from) == self.host_peer;
+            if !from_host || message.host_epoch().is_none() {
+                debug!("Dropping message for unknown topic");
+                return;
+            }
+
+            self.open_topic(topic);
+            self.inbound_events
+                .push(ConnectionEvent::TopicOpened { topic });
+            if !matches!(message, SyncMessage::FullSyncResponse { .. })
+                && let Err(e) = self.request_topic_sync(topic)
+            {
+                warn!(error = %e, "Failed to request topic sync");
+            }
+        }
+
+        let Some(entry) = self.topics.get_mut(&topic) else {
+            return;
+        };
+
+        match entry.sync.handle_message(from, message) {
+            Ok(SyncResponse::ProcessCommand { command, .. }) => {
+                if command.lobby_id() != Some(topic) {
+                    warn!("Dropping topic command for another lobby");
+                    return;
+                }
+                // Guests act in a topic as the participant they joined it as
+                if matches!(command, DomainCommand::JoinLobby { .. }) {
+                    entry.pending_joins.push_back(from);
+                }
+                let sender = entry.peer_participants.get_participant(&from);
+                self.pending_requests.push_back((command, sender, None));
+            }
+            Ok(SyncResponse::Joined { participant_id, .. }) => {
+                info!(participant_id = %participant_id, "GUEST: Bound to topic participant");
+                entry.local_participant = Some(participant_id);
+            }
+            Ok(SyncResponse::ApplyEvents { events }) => {
+                for event in events {
+                    if let Some(cmd) = entry.translator.to_domain_command(&event.event) {
+                        self.pending_domain_commands.push_back((cmd, None));
+                    }
+                }
+            }
+            Ok(SyncResponse::ApplySnapshot { snapshot, events }) => {
+                self.apply_snapshot_to_domain(snapshot, events);
+            }
+            Ok(SyncResponse::NeedSnapshot { for_peer, .. }) => {
+                self.inbound_events
+                    .push(ConnectionEvent::TopicSyncNeeded { topic, for_peer });
+            }
+            Ok(_) => trace!("Topic message processed (no action)"),
+            Err(e) => warn!(error = ?e, "Failed to handle topic message"),
+        }
+    }
+
+    /// Handle a message relayed through the host (star topology)
+    #[instrument(skip(self, payload), fields(
+        from = %from,
+        origin = %route.origin,
+        hops = %route.hops
+    ))]
+    fn handle_relay(&mut self, from: PeerId, route: MessageRoute, payload: serde_json::Value) {
+        let local = self.local_peer_id();
+        let decoded = serde_json::from_value::<SyncMessage>(payload.clone());
+
+        if self.event_sync.is_host() {
+            if route.hops == 0 && route.origin != from {
+                warn!("Dropping relay with spoofed origin");
+                return;
+            }
+
+            if let Some(next) = route.next_hop() {
+                let forward = P2PMessage {
+                    sequence: 0,
+                    route: Some(next),
+                    topic: None,
+                    kind: MessageKind::Relay {
+                        payload: payload.clone(),
+                    },
+                };
+
+                let priority = decoded
+                    .as_ref()
+                    .map(SyncMessage::priority)
+                    .unwrap_or(MessagePriority::LobbyEvent);
+
+                if let Ok(data) = serde_json::to_vec(&forward) {
+                    let targets: Vec<PeerId> = self
+                        .connected_peers()
+                        .into_iter()
+                        .filter(|peer| next.is_for(*peer) && Some(*peer) != local)
+                        .collect();
+
+                    for peer in targets {
+                        trace!(peer_id = %peer, "HOST: Forwarding relayed message");
+                        let _ = self.enqueue(Some(peer), data.clone(), priority);
+                    }
+                }
+            }
+        } else if Some(from) != self.host_peer {
+            warn!("Dropping relay that did not come from the host");
+            return;
+        }
+
+        let for_us = match (route.destination, local) {
+            (None, _) => true,
+            (Some(dest), Some(local)) => dest == local,
+            (Some(_), None) => false,
+        };
+        if !for_us {
+            return;
+        }
+
+        match decoded {
+            // Host-originated messages are always sent directly, never relayed.
+            Ok(
+                SyncMessage::EventBroadcast { .. }
+                | SyncMessage::FullSyncResponse { .. }
+                | SyncMessage::SnapshotChunk { .. }
+                | SyncMessage::PeerRoster { .. }
+                | SyncMessage::JoinAccepted { .. }
+                | SyncMessage::ResumeRejected
+                | SyncMessage::StateChecksum { .. },
+            ) => {
+                warn!("Dropping relayed host-only message");
+            }
+            Ok(sync_msg) => {
+                debug!("Delivering relayed sync message");
+                self.handle_sync_message(route.origin, sync_msg);
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to decode relayed payload");
+            }
+        }
+    }
+
+    /// Send a sync message to a specific peer.
+    ///
+    /// Uses the direct link when available; otherwise (guest only) the message
+    /// is relayed through the host.
+    #[instrument(skip(self, message), fields(peer_id = %peer))]
+    pub fn send_to_peer(&mut self, peer: PeerId, message: &SyncMessage) -> Result<()> {
+        if self.connected_peers().contains(&peer) {
+            let data = serde_json::to_vec(message).map_err(P2PError::Serialization)?;
+            return self.enqueue(Some(peer), data, message.priority());
+        }
+
+        self.send_via_host(Some(peer), message)
+    }
+
+    /// Send a sync message to every other peer.
+    ///
+    /// In star topology guests hand the message to the host, which fans it out.
+    #[instrument(skip(self, message), fields(topology = %self.topology))]
+    pub fn broadcast_to_peers(&mut self, message: &SyncMessage) -> Result<()> {
+        if self.topology.is_star() && !self.event_sync.is_host() {
+            return self.send_via_host(None, message);
+        }
+
+        let data = serde_json::to_vec(message).map_err(P2PError::Serialization)?;
+        self.enqueue(None, data, message.priority())
+    }
+
+    /// Wrap a sync message in a relay envelope addressed to the host
+    fn send_via_host(&mut self, destination: Option<PeerId>, message: &SyncMessage) -> Result<()> {
+        let (Some(host), Some(local)) = (self.host_peer, self.local_peer_id()) else {
+            return Err(P2PError::PeerNotFound(
+                destination.map(|p| p.to_string()).unwrap_or_default(),
+            ));
+        };
+
+        let payload = serde_json::to_value(message).map_err(P2PError::Serialization)?;
+        let relay = P2PMessage::relay(local, destination, payload);
+        let data = serde_json::to_vec(&relay).map_err(P2PError::Serialization)?;
+
+        debug!(host = %host, "Relaying message through host");
+        self.enqueue(Some(host), data, message.priority())
+    }
+
+    /// Announce the host's peer roster so guests can detect partial meshes
+    /// and know who to elect on host timeout (HOST ONLY)
+    pub fn broadcast_peer_roster(&mut self) {
+        let peers = self
+            .connected_peers()
+            .into_iter()
+            .map(|peer_id| RosterEntry {
+                peer_id,
+                participant_id: self
+                    .peer_registry
+                    .get_peer(&peer_id)
+                    .and_then(|state| state.participant_id),
+            })
+            .collect();
+
+        let msg = SyncMessage::PeerRoster {
+            peers,
+            epoch: self.epoch(),
+        };
+
+        if let Ok(data) = serde_json::to_vec(&msg) {
+            let _ = self.enqueue(None, data, msg.priority());
+        }
+    }
+
+    /// Bind the peer whose JoinLobby produced `participant_id` and hand it a
+    /// resume token (HOST ONLY)
+    ///
+    /// Returns `false` if no remote join is pending (e.g. the join was local).
+    #[instrument(skip(self, name), fields(participant_id = %participant_id))]
+    pub fn accept_join(&mut self, participant_id: Uuid, name: &str) -> bool {
+        let Some(peer) = self.pending_joins.pop_front() else {
+            return false;
+        };
+
+        self.peer_participants.register(peer, participant_id);
+        let resume_token = self.peer_participants.issue_token(participant_id);
+
+        if let Some(state) = self.peer_registry.get_peer_mut(&peer) {
+            state.set_participant_info(participant_id, name.to_string(), false);
+        }
+
+        info!(peer_id = %peer, "HOST: Bound joining peer to participant");
+
+        let msg = SyncMessage::JoinAccepted {
+            participant_id,
+            resume_token,
+        };
+        if let Err(e) = self.send_to_peer(peer, &msg) {
+            warn!(peer_id = %peer, error = %e, "Failed to send JoinAccepted");
+        }
+
+        self.broadcast_peer_roster();
+        true
+    }
+
+    /// Drop the oldest pending join (its JoinLobby failed) (HOST ONLY)
+    pub fn discard_pending_join(&mut self) {
+        if let Some(peer) = self.pending_joins.pop_front() {
+            debug!(peer_id = %peer, "Discarded pending join");
+        }
+    }
+
+    /// Forget a participant that left the lobby, revoking its resume token (HOST ONLY)
+    pub fn release_participant(&mut self, participant_id: Uuid) {
+        self.away.remove(&participant_id);
+        if self
+            .peer_participants
+            .forget_participant(&participant_id)
+            .is_some()
+        {
+            debug!(participant_id = %participant_id, "Released participant binding");
+        }
+    }
+
+    /// Re-bind a reconnecting peer to its participant (HOST ONLY)
+    #[instrument(skip(self, resume_token), fields(peer_id = %peer))]
+    fn handle_resume_request(&mut self, peer: PeerId, resume_token: ResumeToken) {
+        let old_peer = self
+            .peer_participants
+            .participant_for_token(&resume_token)
+            .and_then(|id| self.peer_participants.get_peer(&id));
+
+        let Some(participant_id) = self.peer_participants.resume(peer, &resume_token) else {
+            warn!("Unknown resume token, rejecting");
+            let _ = self.send_to_peer(peer, &SyncMessage::ResumeRejected);
+            return;
+        };
+
+        // The old peer is gone for good: drop it without starting a timeout,
+        // so the participant is not removed from the lobby.
+        let mut name = String::new();
+        if let Some(old) = old_peer.filter(|old| *old != peer) {
+            if let Some(state) = self.peer_registry.get_peer(&old) {
+                name = state.name.clone().unwrap_or_default();
+            }
+            self.peer_registry.remove_peer(&old);
+        }
+
+        if self.peer_registry.get_peer(&peer).is_none() {
+            self.peer_registry.add_peer(peer);
+        }
+        if let Some(state) = self.peer_registry.get_peer_mut(&peer) {
+            state.set_participant_info(participant_id, name, false);
+        }
+
+        self.away.remove(&participant_id);
+        info!(participant_id = %participant_id, "HOST: Resumed participant on new peer");
+
+        let msg = SyncMessage::JoinAccepted {
+            participant_id,
+            resume_token,
+        };
+        let _ = self.send_to_peer(peer, &msg);
+        self.broadcast_peer_roster();
+    }
+
+    /// Ask the host to re-bind us to our participant (GUEST ONLY)
+    #[instrument(skip(self))]
+    pub fn request_resume(&mut self) -> Result<()> {
+        let Some(token) = self.resume_token else {
+            return Err(P2PError::InvalidResumeToken(
+                "No resume token available".to_string(),
+            ));
+        };
+
+        let msg = self
+            .event_sync
+            .request_resume(token)
+            .map_err(|e| P2PError::SendFailed(e.to_string()))?;
+        let data = serde_json::to_vec(&msg).map_err(P2PError::Serialization)?;
+
+        self.enqueue(None, data, msg.priority())?;
+
+        info!("Queued resume request to host");
+        Ok(())
+    }
+
+    /// Switch lobby state replication to CRDTs (`SyncMode::Crdt`)
+    pub fn enable_crdt(&mut self) {
+        if self.crdt.is_none() {
+            info!("CRDT lobby sync enabled");
+            self.crdt = Some(LobbyCrdt::new(Uuid::new_v4()));
+        }
+    }
+
+    /// Tell every peer what `participant_id` is doing (`None` clears it)
+    pub fn broadcast_presence(
+        &mut self,
+        participant_id: Uuid,
+        presence: Option<Presence>,
+    ) -> Result<()> {
+        self.broadcast_to_peers(&SyncMessage::Presence {
+            participant_id,
+            presence,
+        })
+    }
+
+    /// Tell every peer we won't take over from the timed-out host
+    pub fn broadcast_takeover_declined(&mut self, participant_id: Uuid) -> Result<()> {
+        self.broadcast_to_peers(&SyncMessage::TakeoverDeclined { participant_id })
+    }
+
+    /// Time left before the silent host is given up on (GUEST ONLY;
+    /// `None` while the host is responsive or once it timed out)
+    pub fn host_grace_remaining(&self) -> Option<Duration> {
+        let host = self.host_peer?;
+        match self.peer_registry.get_peer(&host)?.status {
+            ConnectionStatus::Disconnected { since } => Some(
+                self.peer_registry
+                    .grace_period()
+                    .saturating_sub(self.clock.now().saturating_duration_since(since)),
+            ),
+            _ => None,
+        }
+    }
+
+    /// What other participants are doing right now
+    pub fn presence(&self) -> &PresenceMap {
+        &self.presence
+    }
+
+    pub fn crdt(&self) -> Option<&LobbyCrdt> {
+        self.crdt.as_ref()
+    }
+
+    pub fn crdt_mut(&mut self) -> Option<&mut LobbyCrdt> {
+        self.crdt.as_mut()
+    }
+
+    /// Send a CRDT op produced by our replica to every other peer
+    pub fn broadcast_crdt_op(&mut self, op: CrdtOp) -> Result<()> {
+        if self.crdt.is_none() {
+            return Err(P2PError::SendFailed("CRDT sync is not enabled".to_string()));
+        }
+        self.broadcast_to_peers(&SyncMessage::CrdtOp { op })
+    }
+
+    /// Join the lobby as a new participant without going through the host
+    /// (`SyncMode::Crdt`)
+    pub fn crdt_join(&mut self, name: String) -> Result<Uuid> {
+        let Some(crdt) = &mut self.crdt else {
+            return Err(P2PError::SendFailed("CRDT sync is not enabled".to_string()));
+        };
+
+        let participant_id = Uuid::new_v4();
+        let op = crdt.join(participant_id, name);
+        if !self.event_sync.is_host() {
+            self.bind_local_participant(participant_id);
+        }
+        self.broadcast_crdt_op(op)?;
+
+        info!(participant_id = %participant_id, "Joined lobby via CRDT");
+        Ok(participant_id)
+    }
+
+    fn merge_crdt_state(&mut self, from: PeerId, state: &LobbyCrdtState) {
+        if let Some(crdt) = &mut self.crdt
+            && crdt.merge(state)
+        {
+            debug!(peer_id = %from, "Merged CRDT state");
+            self.inbound_events.push(ConnectionEvent::CrdtUpdated);
+        }
+    }
+
+    /// Whether `from` is the host we follow (GUEST ONLY)
+    fn is_host_peer(&self, from: PeerId) -> bool {
+        !self.event_sync.is_host() && self.host_peer == Some(from)
+    }
+
+    /// The participant `peer` joined as, if known
+    fn crdt_participant(&self, peer: PeerId) -> Option<Uuid> {
+        self.peer_registry
+            .get_peer(&peer)
+            .and_then(|state| state.participant_id)
+    }
+
+    /// Whether `from` may make `op`: guests only change their own participant
+    ///
+    /// A peer that has not joined yet may only join as a participant nobody
+    /// used before (kicked and departed IDs stay known).
+    fn crdt_op_allowed(&self, from: PeerId, op: &CrdtOp) -> bool {
+        let target = match op {
+            CrdtOp::Join { participant_id, .. }
+            | CrdtOp::Leave { participant_id, .. }
+            | CrdtOp::SetMode { participant_id, .. } => *participant_id,
+            CrdtOp::Chat(_) => return true,
+        };
+        if self.is_host_peer(from) {
+            return true;
+        }
+
+        match self.crdt_participant(from) {
+            Some(bound) => bound == target,
+            None => {
+                matches!(op, CrdtOp::Join { .. })
+                    && self
+                        .crdt
+                        .as_ref()
+                        .is_some_and(|crdt| !crdt.state().knows(&target))
+            }
+        }
+    }
+
+    /// Apply a CRDT op from `from`
+    fn apply_crdt_op(&mut self, from: PeerId, op: CrdtOp) {
+        if self.crdt.is_some() && !self.crdt_op_allowed(from, &op) {
+            warn!(peer_id = %from, "Dropping CRDT op for another participant");
+            return;
+        }
+        let Some(crdt) = &mut self.crdt else {
+            warn!(peer_id = %from, "Dropping CRDT op (CRDT sync disabled)");
+            return;
+        };
+        if !crdt.apply(&op) {
+            return;
+        }
+
+        // The host tracks who joined from which peer (timeouts, roster)
+        if let CrdtOp::Join {
+            participant_id,
+            name,
+            ..
+        } = &op
+            && self.event_sync.is_host()
+            && let Some(state) = self.peer_registry.get_peer_mut(&from)
+            && !state.has_participant_info()
+        {
+            state.set_participant_info(*participant_id, name.clone(), false);
+            // Commands the peer sends the host act as this participant
+            self.peer_participants.register(from, *participant_id);
+            self.broadcast_peer_roster();
+        }
+
+        self.inbound_events.push(ConnectionEvent::CrdtUpdated);
+    }
+
+    /// Send our CRDT state to a newly connected peer (anti-entropy)
+    fn send_crdt_state(&mut self, peer: PeerId) {
+        let Some(crdt) = &self.crdt else {
+            return;
+        };
+
+        let msg = SyncMessage::CrdtState {
+            state: crdt.state().clone(),
+        };
+        if let Err(e) = self.send_to_peer(peer, &msg) {
+            warn!(peer_id = %peer, error = %e, "Failed to send CRDT state");
+        }
+    }
+
+    /// Register our own participant in the peer registry (GUEST ONLY)
+    fn bind_local_participant(&mut self, participant_id: Uuid) {
+        let Some(local) = self.local_peer_id() else {
+            return;
+        };
+
+        if self.peer_registry.get_peer(&local).is_none() {
+            self.peer_registry.add_peer(local);
+        }
+        if let Some(state) = self.peer_registry.get_peer_mut(&local) {
+            let name = state.name.clone().unwrap_or_default();
+            state.set_participant_info(participant_id, name, false);
+        }
+    }
+
+    /// Record the participant ↔ peer bindings announced by the host (GUEST ONLY)
+    fn apply_peer_roster(&mut self, host: PeerId, roster: &[RosterEntry]) {
+        let local = self.local_peer_id();
+
+        for entry in roster {
+            let Some(participant_id) = entry.participant_id else {
+                continue;
+            };
+
+            if Some(entry.peer_id) == local && self.peer_registry.get_peer(&entry.peer_id).is_none()
+            {
+                self.peer_registry.add_peer(entry.peer_id);
+            }
+
+            if let Some(state) = self.peer_registry.get_peer_mut(&entry.peer_id) {
+                let name = state.name.clone().unwrap_or_default();
+                state.set_participant_info(participant_id, name, entry.peer_id == host);
+            }
+        }
+    }
+
+    /// Re-evaluate mesh vs star based on the host roster and our direct links
+    fn refresh_topology(&mut self) {
+        if self.event_sync.is_host() {
+            return;
+        }
+
+        let roster: Vec<PeerId> = self.peer_roster.iter().map(|e| e.peer_id).collect();
+        let topology = Topology::detect(&roster, &self.connected_peers(), self.local_peer_id());
+
+        if topology != self.topology {
+            info!(from = %self.topology, to = %topology, "Topology changed");
+            self.topology = topology;
+        }
+    }
+
+    /// Send full sync to a specific peer (HOST ONLY)
+    #[instrument(skip(self, snapshot), fields(
+        peer_id = %peer_id,
+        snapshot.lobby_id = %snapshot.lobby_id,
+        participants = %snapshot.participants.len()
+    ))]
+    pub fn send_full_sync_to_peer(
+        &mut self,
+        peer_id: PeerId,
+        snapshot: LobbySnapshot,
+    ) -> Result<()> {
+        info!("Sending full sync to peer");
+
+        let sync_msg = self
+            .event_sync
+            .create_full_sync_response(0, snapshot)
+            .map_err(|e| crate::infrastructure::error::P2PError::SendFailed(e.to_string()))?;
+
+        let data = serde_json::to_vec(&sync_msg)
+            .map_err(crate::infrastructure::error::P2PError::Serialization)?;
+
+        metrics::snapshot_size(data.len());
+        self.enqueue(Some(peer_id), data, sync_msg.priority())?;
+
+        debug!("Full sync queued");
+        Ok(())
+    }
+
+    /// Stream a full sync to a specific peer in pages of `page_size` (HOST ONLY)
+    ///
+    /// `parts` come from `SnapshotPart::split`; the event log is appended.
+    #[instrument(skip(self, parts), fields(peer_id = %peer_id, parts = %parts.len()))]
+    pub fn stream_snapshot_to_peer(
+        &mut self,
+        peer_id: PeerId,
+        parts: Vec<SnapshotPart>,
+        page_size: usize,
+    ) -> Result<()> {
+        let chunks = self
+            .event_sync
+            .create_snapshot_stream(parts, page_size)
+            .map_err(|e| P2PError::SendFailed(e.to_string()))?;
+        info!(chunks = %chunks.len(), "Streaming full sync to peer");
+
+        let mut bytes = 0;
+        for chunk in chunks {
+            let data = serde_json::to_vec(&chunk).map_err(P2PError::Serialization)?;
+            bytes += data.len();
+            self.enqueue(Some(peer_id), data, chunk.priority())?;
+        }
+        metrics::snapshot_size(bytes);
+
+        Ok(())
+    }
+
+    // ... rest of methods unchanged ...
+
+    /// Wake `cx` once the connection has new events (see `NetworkConnection::poll_ready`)
+    pub fn poll_ready(&mut self, cx: &mut std::task::Context<'_>) -> std::task::Poll<()> {
+        self.connection.poll_ready(cx)
+    }
+
+    pub fn drain_events(&mut self) -> Vec<ConnectionEvent> {
+        std::mem::take(&mut self.inbound_events)
+    }
+
+    pub fn drain_domain_commands(&mut self) -> Vec<DomainCommand> {
+        self.pending_domain_commands
+            .drain(..)
+            .map(|(command, _)| command)
+            .collect()
+    }
+
+    /// Like `drain_domain_commands`, with the trace of each command
+    pub fn drain_correlated_commands(&mut self) -> Vec<(DomainCommand, Option<Correlation>)> {
+        self.pending_domain_commands.drain(..).collect()
+    }
+
+    /// Commands guests sent us, with the participant the sending peer is
+    /// bound to (`None` until it joined) and their trace (host only)
+    ///
+    /// Run them with `DomainLoop::submit_request`, which checks that the
+    /// guest may ask for them.
+    pub fn drain_command_requests(
+        &mut self,
+    ) -> Vec<(DomainCommand, Option<Uuid>, Option<Correlation>)> {
+        self.pending_requests.drain(..).collect()
+    }
+
+    /// Underlying network connection
+    pub fn connection(&self) -> &C {
+        &self.connection
+    }
+
+    pub fn connection_mut(&mut self) -> &mut C {
+        &mut self.connection
+    }
+
+    pub fn peer_registry(&self) -> &PeerRegistry {
+        &self.peer_registry
+    }
+
+    pub fn peer_registry_mut(&mut self) -> &mut PeerRegistry {
+        &mut self.peer_registry
+    }
+
+    pub fn local_peer_id(&self) -> Option<PeerId> {
+        self.connection.local_peer_id()
+    }
+
+    /// Round trip, last seen and message counts of every other peer
+    pub fn peer_stats(&self) -> Vec<PeerStats> {
+        let mut stats = self.peer_registry.stats(self.local_peer_id());
+        for peer in &mut stats {
+            if peer.participant_id.is_none() {
+                peer.participant_id = self.peer_participants.get_participant(&peer.peer_id);
+            }
+        }
+        stats
+    }
+
+    pub fn connected_peers(&self) -> Vec<PeerId> {
+        // Use the peer registry as the source of truth — it is authoritatively
+        // updated during poll() via PeerConnected / PeerDisconnected events.
+        // The raw socket's connected_peers() only reflects what update_peers()
+        // last saw and will return stale (empty) results when called outside
+        // of poll_events().
+        self.peer_registry
+            .all_peers()
+            .filter(|(_, state)| !state.is_timed_out() && !state.is_disconnected())
+            .map(|(peer_id, _)| *peer_id)
+            .collect()
+    }
+
+    pub fn current_sequence(&self) -> u64 {
+        self.event_sync.current_sequence()
+    }
+
+    /// Persist every event this host broadcasts to `store`
+    pub fn set_event_store(&mut self, store: Box<dyn EventLogStore + Send + Sync>) {
+        self.event_store = Some(store);
+    }
+
+    pub fn has_event_store(&self) -> bool {
+        self.event_store.is_some()
+    }
+
+    /// Buffer events broadcast while no peer is connected in `outbox` and
+    /// deliver them when the first guest joins
+    pub fn set_outbox(&mut self, outbox: Box<dyn EventLogStore + Send + Sync>) {
+        self.outbox = Some(outbox);
+    }
+
+    pub fn has_outbox(&self) -> bool {
+        self.outbox.is_some()
+    }
+
+    /// All events in the log (e.g. for a host snapshot)
+    pub fn all_events(&self) -> Vec<LobbyEvent> {
+        self.event_sync.all_events()
+    }
+
+    /// Seed the event log from persisted history (HOST ONLY, before any broadcast)
+    pub fn restore_events(&mut self, events: Vec<LobbyEvent>) {
+        self.event_sync.restore_events(events);
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    #[instrument(skip(self))]
+    pub fn promote_to_host(&mut self) {
+        info!("Promoting to HOST in P2P layer");
+        self.event_sync.promote_to_host();
+        for topic in self.topics.values_mut() {
+            topic.sync.promote_to_host();
+        }
+        self.host_peer = None;
+        self.clock_sync.reset();
+        // Keep telling the guests apart by the roster of the previous host
+        let local = self.local_peer_id();
+        for entry in self.peer_roster.drain(..) {
+            if let Some(participant_id) = entry.participant_id
+                && Some(entry.peer_id) != local
+            {
+                self.peer_participants
+                    .register(entry.peer_id, participant_id);
+            }
+        }
+        self.topology = Topology::Mesh;
+
+        self.mark_local_as_host();
+        self.broadcast_peer_roster();
+    }
+
+    fn mark_local_as_host(&mut self) {
+        if let Some(local) = self.local_peer_id() {
+            if self.peer_registry.get_peer(&local).is_none() {
+                self.peer_registry.add_peer(local);
+            }
+            if let Some(state) = self.peer_registry.get_peer_mut(&local) {
+                state.is_host = true;
+            }
+        }
+    }
+
+    /// Remember `peer` as the host (GUEST ONLY)
+    ///
+    /// If the host reconnected under a new peer ID, the stale entry is dropped
+    /// so its grace period doesn't trigger a host takeover.
+    fn adopt_host_peer(&mut self, peer: PeerId) {
+        let previous = self.host_peer.replace(peer);
+        if let Some(state) = self.held_crdt_states.remove(&peer) {
+            self.merge_crdt_state(peer, &state);
+        }
+        self.held_crdt_states.clear();
+        if previous != Some(peer) {
+            // Another host, another clock
+            self.clock_sync.reset();
+        }
+        if let Some(previous) = previous
+            && previous != peer
+            && self
+                .peer_registry
+                .get_peer(&previous)
+                .is_some_and(|state| state.is_disconnected())
+        {
+            info!(old = %previous, new = %peer, "Host reconnected under a new peer ID");
+            self.peer_registry.remove_peer(&previous);
+        }
+    }
+
+    /// How far the host's clock is ahead of ours, in ms (0 on the host, and
+    /// until the host acked a heartbeat; needs a heartbeat interval)
+    pub fn clock_offset_ms(&self) -> i64 {
+        self.clock_sync.offset_ms().unwrap_or(0)
+    }
+
+    /// Now on the host's clock (Unix ms), so countdowns and deadlines agree
+    /// across peers
+    pub fn session_time_ms(&self) -> u64 {
+        self.clock_sync.now_ms(self.clock.unix_millis())
+    }
+
+    /// Is the connection currently reconnecting to signalling?
+    pub fn is_reconnecting(&self) -> bool {
+        self.reconnecting
+    }
+
+    /// Optional protocol features to advertise to peers (default: none)
+    ///
+    /// Takes effect for peers that connect afterwards.
+    pub fn set_capabilities(&mut self, capabilities: Capabilities) {
+        self.capabilities = capabilities;
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        self.capabilities
+    }
+
+    /// Features both we and `peer` support (empty until its `Hello` arrives,
+    /// and for peers that predate the handshake)
+    pub fn peer_capabilities(&self, peer: &PeerId) -> Capabilities {
+        self.peer_capabilities
+            .get(peer)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Limit inbound traffic per peer (`None` disables limiting)
+    pub fn set_rate_limit(&mut self, limit: Option<RateLimit>) {
+        self.rate_limiter = limit.map(PeerRateLimiter::new);
+    }
+
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.rate_limiter.as_ref().map(PeerRateLimiter::limit)
+    }
+
+    /// Token to resume our participant after a reconnect (GUEST ONLY)
+    pub fn resume_token(&self) -> Option<ResumeToken> {
+        self.resume_token
+    }
+
+    /// Provide a previously issued resume token (e.g. restored from storage)
+    pub fn set_resume_token(&mut self, token: ResumeToken) {
+        self.resume_token = Some(token);
+    }
+
+    /// Current host epoch (incremented on every host takeover)
+    pub fn epoch(&self) -> u64 {
+        self.event_sync.epoch()
+    }
+
+    /// Our own participant ID, as bound by the host roster
+    pub fn local_participant_id(&self) -> Option<Uuid> {
+        self.local_peer_id()
+            .and_then(|peer| self.peer_registry.get_peer(&peer))
+            .and_then(|state| state.participant_id)
     }
 
     pub fn pending_messages(&self) -> usize {
@@ -410,6 +2407,6 @@ impl P2PLoop {
     }
 
     pub fn pending_domain_commands(&self) -> usize {
-        self.pending_domain_commands.len()
+        self.pending_domain_commands.len() + self.pending_requests.len()
     }
 }
@@ -1,13 +1,49 @@
-use crate::application::runtime::{P2PLoop, SessionLoop};
-use crate::domain::{IceServer, SessionId};
-use crate::infrastructure::{connection::MatchboxConnection, error::Result};
-use konnekt_session_core::DomainLoop;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::application::DEFAULT_HOST_SNAPSHOT_INTERVAL;
+use crate::application::runtime::{DEFAULT_CHECKSUM_INTERVAL, P2PLoop, SessionLoop};
+use crate::application::{DEFAULT_SNAPSHOT_PAGE_SIZE, EventTranslator, HostSnapshot};
+use crate::domain::{
+    Capabilities, DomainEvent, IceServer, LobbyEvent, RateLimit, ResumeToken, SessionId,
+    SharedClock, SyncMode, SystemClock, TimeoutConfig, TimeoutPolicy,
+};
+use crate::infrastructure::event_store::EventLogStore;
+use crate::infrastructure::transport::NetworkConnection;
+#[cfg(feature = "webtransport")]
+use crate::infrastructure::webtransport::WebTransportConnection;
+use crate::infrastructure::{
+    connection::MatchboxConnection,
+    error::{P2PError, Result},
+};
+use instant::Duration;
+use konnekt_session_core::{DomainCommand, DomainLoop, Participant, Timestamp};
 use uuid::Uuid;
 
 /// Builder for creating P2P components with automatic sync
+///
+/// The `build_*` methods connect over Matchbox WebRTC, the
+/// `build_session_*_webtransport` methods (feature `webtransport`) over a
+/// QUIC relay, and the `build_*_with_connection` variants accept any
+/// `NetworkConnection`.
 pub struct P2PLoopBuilder {
     batch_size: usize,
     queue_size: usize,
+    resume_token: Option<ResumeToken>,
+    rate_limit: Option<RateLimit>,
+    checksum_interval: Option<Duration>,
+    snapshot_page_size: usize,
+    takeover_answer_timeout: Option<Duration>,
+    sync_mode: SyncMode,
+    timeouts: TimeoutConfig,
+    capabilities: Capabilities,
+    clock: SharedClock,
+    #[cfg(not(target_arch = "wasm32"))]
+    resume_from: Option<std::path::PathBuf>,
+    #[cfg(not(target_arch = "wasm32"))]
+    outbox: Option<std::path::PathBuf>,
+    #[cfg(not(target_arch = "wasm32"))]
+    host_snapshot: Option<std::path::PathBuf>,
+    #[cfg(not(target_arch = "wasm32"))]
+    host_snapshot_interval: Duration,
 }
 
 impl P2PLoopBuilder {
@@ -15,6 +51,23 @@ impl P2PLoopBuilder {
         Self {
             batch_size: 10,
             queue_size: 100,
+            resume_token: None,
+            rate_limit: None,
+            checksum_interval: Some(DEFAULT_CHECKSUM_INTERVAL),
+            snapshot_page_size: DEFAULT_SNAPSHOT_PAGE_SIZE,
+            takeover_answer_timeout: None,
+            sync_mode: SyncMode::default(),
+            timeouts: TimeoutConfig::default(),
+            capabilities: Capabilities::empty(),
+            clock: SystemClock::shared(),
+            #[cfg(not(target_arch = "wasm32"))]
+            resume_from: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            outbox: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            host_snapshot: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            host_snapshot_interval: DEFAULT_HOST_SNAPSHOT_INTERVAL,
         }
     }
 
@@ -28,14 +81,126 @@ impl P2PLoopBuilder {
         self
     }
 
-    /// Build P2P loop for host (creates new session)
+    /// Resume a previous participant when joining as guest
+    pub fn resume_token(mut self, token: ResumeToken) -> Self {
+        self.resume_token = Some(token);
+        self
+    }
+
+    /// Limit inbound messages/bytes per peer (off by default)
+    pub fn rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limit = Some(limit);
+        self
+    }
+
+    /// How often a session host broadcasts a state checksum so guests can
+    /// detect drift and resync (`None` disables it; default 10s)
+    pub fn checksum_interval(mut self, interval: Option<Duration>) -> Self {
+        self.checksum_interval = interval;
+        self
+    }
+
+    /// Stream full syncs of lobbies larger than `page_size` (participants,
+    /// queued activities or results) in pages of that size (default 50)
+    pub fn snapshot_page_size(mut self, page_size: usize) -> Self {
+        self.snapshot_page_size = page_size;
+        self
+    }
+
+    /// Ask the guest elected to replace a timed-out host before it takes
+    /// over: it has `answer_within` to accept or decline, then accepts
+    /// (default `None`: take over right away)
+    pub fn confirm_takeover(mut self, answer_within: Option<Duration>) -> Self {
+        self.takeover_answer_timeout = answer_within;
+        self
+    }
+
+    /// How sessions replicate participants, modes and chat (default: host event log)
+    pub fn sync_mode(mut self, mode: SyncMode) -> Self {
+        self.sync_mode = mode;
+        self
+    }
+
+    /// How long a disconnected peer may take to come back (default 30s)
+    pub fn grace_period(mut self, grace_period: Duration) -> Self {
+        self.timeouts.grace_period = grace_period;
+        self
+    }
+
+    /// Send heartbeats and treat silent peers as disconnected (off by default)
+    pub fn heartbeat_interval(mut self, interval: Option<Duration>) -> Self {
+        self.timeouts.heartbeat_interval = interval;
+        self
+    }
+
+    /// What a host does with participants whose peer timed out (default: remove)
+    pub fn timeout_policy(mut self, policy: TimeoutPolicy) -> Self {
+        self.timeouts.policy = policy;
+        self
+    }
+
+    /// All timeout settings at once (e.g. from `SessionConfig::timeouts`)
+    pub fn timeouts(mut self, config: TimeoutConfig) -> Self {
+        self.timeouts = config;
+        self
+    }
+
+    /// Optional protocol features to advertise; each peer uses the ones both
+    /// sides support (default: none)
+    pub fn capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Measure time on `clock` instead of real time (e.g. a `TestClock`
+    /// shared by all peers of a simulation)
+    pub fn clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Persist the host's event log at `path` and restore the lobby from it
+    /// if it already exists (`.db` / `.sqlite` use SQLite with the `sqlite`
+    /// feature, anything else a JSON-lines file)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn resume_from(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.resume_from = Some(path.into());
+        self
+    }
+
+    /// Buffer host broadcasts made while no guest is connected at `path` and
+    /// deliver them when the first guest joins. A host restarted before
+    /// anyone connected rebuilds its lobby from the buffered events.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn outbox(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.outbox = Some(path.into());
+        self
+    }
+
+    /// Periodically save the host's lobby, active run and event log to
+    /// `path` and restore the exact session from it if it already exists
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn host_snapshot(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.host_snapshot = Some(path.into());
+        self
+    }
+
+    /// How often the `host_snapshot` file is written (default 5s)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn host_snapshot_interval(mut self, interval: Duration) -> Self {
+        self.host_snapshot_interval = interval;
+        self
+    }
+
+    /// Build P2P loop for host (creates new session, or reopens the session
+    /// recorded in the `host_snapshot` or `resume_from` log)
     /// Returns (p2p_loop, session_id, lobby_id)
     pub async fn build_host(
         self,
         signalling_server: &str,
         ice_servers: Vec<IceServer>,
     ) -> Result<(P2PLoop, SessionId, Uuid)> {
-        let session_id = SessionId::new();
+        let session_id = self.stored_session_id()?.unwrap_or_default();
         self.build_host_with_session_id(signalling_server, session_id, ice_servers)
             .await
     }
@@ -48,18 +213,167 @@ impl P2PLoopBuilder {
         session_id: SessionId,
         ice_servers: Vec<IceServer>,
     ) -> Result<(P2PLoop, SessionId, Uuid)> {
-        let lobby_id = session_id.inner(); // 1:1 mapping
+        let connection =
+            Self::connect_matchbox(signalling_server, &session_id, ice_servers).await?;
+        self.build_host_with_connection(connection, session_id)
+    }
 
+    /// Build P2P loop for host over a caller-supplied connection.
+    /// Returns (p2p_loop, session_id, lobby_id)
+    pub fn build_host_with_connection<C: NetworkConnection>(
+        self,
+        connection: C,
+        session_id: SessionId,
+    ) -> Result<(P2PLoop<C>, SessionId, Uuid)> {
+        let snapshot = self.load_host_snapshot()?;
+        let (p2p_loop, session_id, lobby_id, _) =
+            self.attach_host(connection, session_id, snapshot.as_ref())?;
+        Ok((p2p_loop, session_id, lobby_id))
+    }
+
+    async fn connect_matchbox(
+        signalling_server: &str,
+        session_id: &SessionId,
+        ice_servers: Vec<IceServer>,
+    ) -> Result<MatchboxConnection> {
         let room_url = format!("{}/{}", signalling_server, session_id.as_str());
+        MatchboxConnection::connect(&room_url, ice_servers).await
+    }
+
+    /// Create the host loop and restore any persisted history.
+    /// Returns (p2p_loop, session_id, lobby_id, history)
+    #[allow(clippy::type_complexity)]
+    fn attach_host<C: NetworkConnection>(
+        self,
+        connection: C,
+        session_id: SessionId,
+        snapshot: Option<&HostSnapshot>,
+    ) -> Result<(P2PLoop<C>, SessionId, Uuid, Vec<LobbyEvent>)> {
+        let lobby_id = session_id.inner(); // 1:1 mapping
+
+        if let Some(snapshot) = snapshot.filter(|s| s.lobby_id != lobby_id) {
+            return Err(P2PError::Storage(format!(
+                "Host snapshot belongs to lobby {}, not {}",
+                snapshot.lobby_id, lobby_id
+            )));
+        }
+
+        let stored = self.open_event_store()?;
+        let outbox = self.open_outbox()?;
+
+        let mut history: Vec<LobbyEvent> = stored
+            .iter()
+            .chain(outbox.iter())
+            .flat_map(|(_, events)| events.iter().cloned())
+            .chain(snapshot.iter().flat_map(|s| s.events.iter().cloned()))
+            .collect();
+        history.sort_by_key(|e| e.sequence);
+        history.dedup_by_key(|e| e.sequence);
+
+        if let Some(foreign) = history.iter().find(|e| e.lobby_id != lobby_id) {
+            return Err(P2PError::Storage(format!(
+                "Event log belongs to lobby {}, not {}",
+                foreign.lobby_id, lobby_id
+            )));
+        }
 
         tracing::info!("🎯 Creating HOST session {}", session_id);
         tracing::info!("📋 Lobby ID: {}", lobby_id);
 
-        let connection = MatchboxConnection::connect(&room_url, ice_servers).await?;
+        let mut p2p_loop =
+            P2PLoop::new_host(connection, lobby_id, self.batch_size, self.queue_size);
+        p2p_loop.set_rate_limit(self.rate_limit);
+        p2p_loop.set_timeouts(self.timeouts);
+        p2p_loop.set_capabilities(self.capabilities);
+        p2p_loop.set_clock(self.clock);
 
-        let p2p_loop = P2PLoop::new_host(connection, lobby_id, self.batch_size, self.queue_size);
+        if !history.is_empty() {
+            tracing::info!("💾 Restoring {} persisted events", history.len());
+            p2p_loop.restore_events(history.clone());
+        }
+        if let Some((store, _)) = stored {
+            p2p_loop.set_event_store(store);
+        }
+        if let Some((outbox, _)) = outbox {
+            p2p_loop.set_outbox(outbox);
+        }
 
-        Ok((p2p_loop, session_id, lobby_id))
+        Ok((p2p_loop, session_id, lobby_id, history))
+    }
+
+    /// Open the `resume_from` store and load its history
+    #[cfg(not(target_arch = "wasm32"))]
+    #[allow(clippy::type_complexity)]
+    fn open_event_store(
+        &self,
+    ) -> Result<Option<(Box<dyn EventLogStore + Send + Sync>, Vec<LobbyEvent>)>> {
+        Self::open_log(self.resume_from.as_deref())
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[allow(clippy::type_complexity)]
+    fn open_event_store(
+        &self,
+    ) -> Result<Option<(Box<dyn EventLogStore + Send + Sync>, Vec<LobbyEvent>)>> {
+        Ok(None)
+    }
+
+    /// Open the `outbox` and load the events still waiting for a guest
+    #[cfg(not(target_arch = "wasm32"))]
+    #[allow(clippy::type_complexity)]
+    fn open_outbox(
+        &self,
+    ) -> Result<Option<(Box<dyn EventLogStore + Send + Sync>, Vec<LobbyEvent>)>> {
+        Self::open_log(self.outbox.as_deref())
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[allow(clippy::type_complexity)]
+    fn open_outbox(
+        &self,
+    ) -> Result<Option<(Box<dyn EventLogStore + Send + Sync>, Vec<LobbyEvent>)>> {
+        Ok(None)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[allow(clippy::type_complexity)]
+    fn open_log(
+        path: Option<&std::path::Path>,
+    ) -> Result<Option<(Box<dyn EventLogStore + Send + Sync>, Vec<LobbyEvent>)>> {
+        let Some(path) = path else {
+            return Ok(None);
+        };
+
+        let store = crate::infrastructure::event_store::open_event_store(path)?;
+        let history = store.load()?;
+        Ok(Some((store, history)))
+    }
+
+    /// Read the `host_snapshot` file, if there is one
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_host_snapshot(&self) -> Result<Option<HostSnapshot>> {
+        match self.host_snapshot.as_deref() {
+            Some(path) => HostSnapshot::load(path),
+            None => Ok(None),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn load_host_snapshot(&self) -> Result<Option<HostSnapshot>> {
+        Ok(None)
+    }
+
+    /// Session recorded in the `host_snapshot`, the `resume_from` log or the
+    /// `outbox`, if any
+    fn stored_session_id(&self) -> Result<Option<SessionId>> {
+        if let Some(snapshot) = self.load_host_snapshot()? {
+            return Ok(Some(snapshot.session_id()));
+        }
+
+        let first = |log: Option<(_, Vec<LobbyEvent>)>| {
+            log.and_then(|(_, history)| history.first().map(|e| SessionId::from_uuid(e.lobby_id)))
+        };
+        Ok(first(self.open_event_store()?).or(first(self.open_outbox()?)))
     }
 
     /// Build P2P loop for guest (joins existing session)
@@ -70,18 +384,36 @@ impl P2PLoopBuilder {
         session_id: SessionId,
         ice_servers: Vec<IceServer>,
     ) -> Result<(P2PLoop, Uuid)> {
-        let lobby_id = session_id.inner(); // 1:1 mapping
+        let connection =
+            Self::connect_matchbox(signalling_server, &session_id, ice_servers).await?;
+        Ok(self.build_guest_with_connection(connection, session_id))
+    }
 
-        let room_url = format!("{}/{}", signalling_server, session_id.as_str());
+    /// Build P2P loop for guest over a caller-supplied connection.
+    /// Returns (p2p_loop, lobby_id)
+    pub fn build_guest_with_connection<C: NetworkConnection>(
+        self,
+        connection: C,
+        session_id: SessionId,
+    ) -> (P2PLoop<C>, Uuid) {
+        let lobby_id = session_id.inner(); // 1:1 mapping
 
         tracing::info!("🎯 Joining GUEST session {}", session_id);
         tracing::info!("📋 Lobby ID: {}", lobby_id);
 
-        let connection = MatchboxConnection::connect(&room_url, ice_servers).await?;
+        let mut p2p_loop =
+            P2PLoop::new_guest(connection, lobby_id, self.batch_size, self.queue_size);
+        p2p_loop.set_rate_limit(self.rate_limit);
+        p2p_loop.set_timeouts(self.timeouts);
+        p2p_loop.set_capabilities(self.capabilities);
+        p2p_loop.set_clock(self.clock);
 
-        let p2p_loop = P2PLoop::new_guest(connection, lobby_id, self.batch_size, self.queue_size);
+        if let Some(token) = self.resume_token {
+            tracing::info!("🔑 Resuming with existing participant token");
+            p2p_loop.set_resume_token(token);
+        }
 
-        Ok((p2p_loop, lobby_id))
+        (p2p_loop, lobby_id)
     }
 
     /// Build complete SessionLoop for HOST (P2P + Core integrated)
@@ -100,48 +432,15 @@ impl P2PLoopBuilder {
         lobby_name: String,
         host_name: String,
     ) -> Result<(SessionLoop, SessionId)> {
-        // 🔧 FIX: Extract values BEFORE consuming self
-        let batch_size = self.batch_size;
-        let queue_size = self.queue_size;
-
-        // Create P2P layer (consumes self)
-        let (p2p_loop, session_id, lobby_id) =
-            self.build_host(signalling_server, ice_servers).await?;
-
-        // Create domain layer (using extracted values)
-        let mut domain_loop = DomainLoop::new(batch_size, queue_size);
-
-        // Create lobby in domain
-        let create_cmd = konnekt_session_core::DomainCommand::CreateLobby {
-            lobby_id: Some(lobby_id), // Use same ID as session
+        let session_id = self.stored_session_id()?.unwrap_or_default();
+        self.build_session_host_with_session_id(
+            signalling_server,
+            session_id,
+            ice_servers,
             lobby_name,
             host_name,
-        };
-
-        domain_loop
-            .submit(create_cmd)
-            .map_err(|e| crate::infrastructure::error::P2PError::SendFailed(e.to_string()))?;
-
-        // Process command to create lobby
-        domain_loop.poll();
-
-        // Verify lobby was created
-        let events = domain_loop.drain_events();
-        if !events
-            .iter()
-            .any(|e| matches!(e, konnekt_session_core::DomainEvent::LobbyCreated { .. }))
-        {
-            return Err(crate::infrastructure::error::P2PError::ConnectionFailed(
-                "Failed to create lobby".to_string(),
-            ));
-        }
-
-        // Create unified session loop
-        let session_loop = SessionLoop::new_host(p2p_loop, domain_loop, lobby_id);
-
-        tracing::info!("✅ SessionLoop created for HOST");
-
-        Ok((session_loop, session_id))
+        )
+        .await
     }
 
     /// Build complete SessionLoop for HOST using a deterministic/preselected session ID.
@@ -155,42 +454,148 @@ impl P2PLoopBuilder {
         lobby_name: String,
         host_name: String,
     ) -> Result<(SessionLoop, SessionId)> {
+        let connection =
+            Self::connect_matchbox(signalling_server, &session_id, ice_servers).await?;
+        self.build_session_host_with_connection(connection, session_id, lobby_name, host_name)
+    }
+
+    /// Build complete SessionLoop for HOST over a caller-supplied connection.
+    ///
+    /// With a `host_snapshot`, the exact lobby is restored (host,
+    /// participants, queue, the active run and its results). With only
+    /// `resume_from` or an `outbox`, the lobby (host identity, participants,
+    /// queue) is rebuilt from the persisted events; activity runs in progress
+    /// are not restored.
+    ///
+    /// Returns (session_loop, session_id)
+    pub fn build_session_host_with_connection<C: NetworkConnection>(
+        self,
+        connection: C,
+        session_id: SessionId,
+        lobby_name: String,
+        host_name: String,
+    ) -> Result<(SessionLoop<C>, SessionId)> {
+        // 🔧 FIX: Extract values BEFORE consuming self
         let batch_size = self.batch_size;
         let queue_size = self.queue_size;
-
-        let (p2p_loop, session_id, lobby_id) = self
-            .build_host_with_session_id(signalling_server, session_id, ice_servers)
-            .await?;
+        let checksum_interval = self.checksum_interval;
+        let snapshot_page_size = self.snapshot_page_size;
+        let takeover_answer_timeout = self.takeover_answer_timeout;
+        let sync_mode = self.sync_mode;
+        let clock = self.clock.clone();
+        #[cfg(not(target_arch = "wasm32"))]
+        let snapshot_file = self
+            .host_snapshot
+            .clone()
+            .map(|path| (path, self.host_snapshot_interval));
+
+        let snapshot = self.load_host_snapshot()?;
+        let (mut p2p_loop, session_id, lobby_id, history) =
+            self.attach_host(connection, session_id, snapshot.as_ref())?;
 
         let mut domain_loop = DomainLoop::new(batch_size, queue_size);
+        // Runs are stamped on the session clock
+        domain_loop.set_clock(clock.clone());
+
+        if let Some(snapshot) = &snapshot {
+            restore_snapshot(&mut domain_loop, snapshot)?;
+            // Events the `resume_from` log recorded after the snapshot
+            let newer: Vec<LobbyEvent> = history
+                .into_iter()
+                .filter(|e| e.sequence > snapshot.as_of_sequence)
+                .collect();
+            let replayed = replay_history(&mut domain_loop, lobby_id, &newer);
+            tracing::info!(
+                "💾 Restored host snapshot as of event {} (+{} newer events)",
+                snapshot.as_of_sequence,
+                replayed
+            );
+        } else {
+            let restored_lobby = history.iter().find_map(|e| match &e.event {
+                DomainEvent::LobbyCreated { host_id, name, .. } => Some((*host_id, name.clone())),
+                _ => None,
+            });
+
+            let create_cmd = match &restored_lobby {
+                Some((host_id, name)) => DomainCommand::CreateLobbyWithHost {
+                    lobby_id,
+                    lobby_name: name.clone(),
+                    host: Participant::host_with_id_at(
+                        *host_id,
+                        host_name,
+                        Timestamp::now_on(clock.as_ref()),
+                    )?,
+                },
+                None => DomainCommand::CreateLobby {
+                    lobby_id: Some(lobby_id), // Use same ID as session
+                    lobby_name,
+                    host_name,
+                },
+            };
+
+            domain_loop
+                .submit(create_cmd)
+                .map_err(|e| P2PError::SendFailed(e.to_string()))?;
+
+            // Process command to create lobby
+            domain_loop.poll();
+
+            // Verify lobby was created
+            let Some(created) = domain_loop
+                .drain_events()
+                .into_iter()
+                .find(|e| matches!(e, konnekt_session_core::DomainEvent::LobbyCreated { .. }))
+            else {
+                return Err(P2PError::ConnectionFailed(
+                    "Failed to create lobby".to_string(),
+                ));
+            };
+
+            if !history.is_empty() {
+                let replayed = replay_history(&mut domain_loop, lobby_id, &history);
+                tracing::info!("💾 Replayed {} persisted events into lobby", replayed);
+            }
+            if restored_lobby.is_none() && (p2p_loop.has_event_store() || p2p_loop.has_outbox()) {
+                // Record the lobby itself so a restart keeps the host identity
+                p2p_loop.broadcast_domain_event(created)?;
+            }
+        }
 
-        let create_cmd = konnekt_session_core::DomainCommand::CreateLobby {
-            lobby_id: Some(lobby_id),
-            lobby_name,
-            host_name,
-        };
-
-        domain_loop
-            .submit(create_cmd)
-            .map_err(|e| crate::infrastructure::error::P2PError::SendFailed(e.to_string()))?;
-
-        domain_loop.poll();
-
-        let events = domain_loop.drain_events();
-        if !events
-            .iter()
-            .any(|e| matches!(e, konnekt_session_core::DomainEvent::LobbyCreated { .. }))
-        {
-            return Err(crate::infrastructure::error::P2PError::ConnectionFailed(
-                "Failed to create lobby".to_string(),
-            ));
+        // Create unified session loop
+        let mut session_loop = SessionLoop::new_host(p2p_loop, domain_loop, lobby_id);
+        session_loop.set_checksum_interval(checksum_interval);
+        session_loop.set_snapshot_page_size(snapshot_page_size);
+        session_loop.set_confirm_takeover(takeover_answer_timeout);
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some((path, interval)) = snapshot_file {
+            session_loop.set_snapshot_file(Some(path), interval);
+        }
+        if sync_mode.is_crdt() {
+            session_loop.enable_crdt_sync();
         }
 
-        let session_loop = SessionLoop::new_host(p2p_loop, domain_loop, lobby_id);
         tracing::info!("✅ SessionLoop created for HOST");
+
         Ok((session_loop, session_id))
     }
 
+    /// Build complete SessionLoop for HOST over a WebTransport / QUIC relay
+    /// instead of WebRTC (for networks that block WebRTC)
+    ///
+    /// Returns (session_loop, session_id)
+    #[cfg(feature = "webtransport")]
+    pub async fn build_session_host_webtransport(
+        self,
+        relay_url: &str,
+        lobby_name: String,
+        host_name: String,
+    ) -> Result<(SessionLoop<WebTransportConnection>, SessionId)> {
+        let session_id = self.stored_session_id()?.unwrap_or_default();
+        let room_url = format!("{}/{}", relay_url, session_id.as_str());
+        let connection = WebTransportConnection::connect(&room_url).await?;
+        self.build_session_host_with_connection(connection, session_id, lobby_name, host_name)
+    }
+
     /// Build complete SessionLoop for GUEST (P2P + Core integrated)
     ///
     /// This creates:
@@ -207,25 +612,117 @@ impl P2PLoopBuilder {
         session_id: SessionId,
         ice_servers: Vec<IceServer>,
     ) -> Result<(SessionLoop, Uuid)> {
+        let connection =
+            Self::connect_matchbox(signalling_server, &session_id, ice_servers).await?;
+        Ok(self.build_session_guest_with_connection(connection, session_id))
+    }
+
+    /// Build complete SessionLoop for GUEST over a WebTransport / QUIC relay
+    ///
+    /// Returns (session_loop, lobby_id)
+    #[cfg(feature = "webtransport")]
+    pub async fn build_session_guest_webtransport(
+        self,
+        relay_url: &str,
+        session_id: SessionId,
+    ) -> Result<(SessionLoop<WebTransportConnection>, Uuid)> {
+        let room_url = format!("{}/{}", relay_url, session_id.as_str());
+        let connection = WebTransportConnection::connect(&room_url).await?;
+        Ok(self.build_session_guest_with_connection(connection, session_id))
+    }
+
+    /// Build complete SessionLoop for GUEST over a caller-supplied connection.
+    ///
+    /// Returns (session_loop, lobby_id)
+    pub fn build_session_guest_with_connection<C: NetworkConnection>(
+        self,
+        connection: C,
+        session_id: SessionId,
+    ) -> (SessionLoop<C>, Uuid) {
         // 🔧 FIX: Extract values BEFORE consuming self
         let batch_size = self.batch_size;
         let queue_size = self.queue_size;
+        let checksum_interval = self.checksum_interval;
+        let snapshot_page_size = self.snapshot_page_size;
+        let takeover_answer_timeout = self.takeover_answer_timeout;
+        let sync_mode = self.sync_mode;
+        let clock = self.clock.clone();
 
         // Create P2P layer (consumes self)
-        let (p2p_loop, lobby_id) = self
-            .build_guest(signalling_server, session_id, ice_servers)
-            .await?;
+        let (p2p_loop, lobby_id) = self.build_guest_with_connection(connection, session_id);
 
-        // Create domain layer (using extracted values)
-        let domain_loop = DomainLoop::new(batch_size, queue_size);
+        // Create domain layer (using extracted values); stamps runs should we
+        // take over as host
+        let mut domain_loop = DomainLoop::new(batch_size, queue_size);
+        domain_loop.set_clock(clock);
 
         // Create unified session loop
-        let session_loop = SessionLoop::new_guest(p2p_loop, domain_loop, lobby_id);
+        let mut session_loop = SessionLoop::new_guest(p2p_loop, domain_loop, lobby_id);
+        session_loop.set_checksum_interval(checksum_interval);
+        session_loop.set_snapshot_page_size(snapshot_page_size);
+        session_loop.set_confirm_takeover(takeover_answer_timeout);
+        if sync_mode.is_crdt() {
+            session_loop.enable_crdt_sync();
+        }
 
         tracing::info!("✅ SessionLoop created for GUEST");
 
-        Ok((session_loop, lobby_id))
+        (session_loop, lobby_id)
+    }
+}
+
+/// Rebuild the lobby and its active run from a host snapshot.
+fn restore_snapshot(domain_loop: &mut DomainLoop, snapshot: &HostSnapshot) -> Result<()> {
+    let mut commands = snapshot.to_commands().into_iter();
+
+    let created = commands
+        .next()
+        .map(|cmd| domain_loop.event_loop_mut().handle_command(cmd));
+    if !matches!(
+        created,
+        Some(konnekt_session_core::DomainEvent::LobbyCreated { .. })
+    ) {
+        return Err(P2PError::Storage(
+            "Host snapshot does not start with a lobby".to_string(),
+        ));
+    }
+
+    for cmd in commands {
+        if let konnekt_session_core::DomainEvent::CommandFailed { command, error } =
+            domain_loop.event_loop_mut().handle_command(cmd)
+        {
+            tracing::warn!("Skipping snapshot part ({}): {}", command.name(), error);
+        }
     }
+
+    Ok(())
+}
+
+/// Re-apply persisted events to a freshly created lobby.
+/// Returns the number of events applied.
+fn replay_history(domain_loop: &mut DomainLoop, lobby_id: Uuid, history: &[LobbyEvent]) -> usize {
+    let translator = EventTranslator::new(lobby_id);
+    let mut replayed = 0;
+
+    for event in history {
+        let Some(cmd) = translator.to_domain_command(&event.event) else {
+            continue;
+        };
+
+        match domain_loop.event_loop_mut().handle_command(cmd) {
+            konnekt_session_core::DomainEvent::CommandFailed { command, error } => {
+                tracing::warn!(
+                    "Skipping persisted event {} ({}): {}",
+                    event.sequence,
+                    command.name(),
+                    error
+                );
+            }
+            _ => replayed += 1,
+        }
+    }
+
+    replayed
 }
 
 impl Default for P2PLoopBuilder {
@@ -243,6 +740,10 @@ mod tests {
         let builder = P2PLoopBuilder::new();
         assert_eq!(builder.batch_size, 10);
         assert_eq!(builder.queue_size, 100);
+        assert!(builder.resume_token.is_none());
+        assert_eq!(builder.checksum_interval, Some(DEFAULT_CHECKSUM_INTERVAL));
+        assert_eq!(builder.sync_mode, SyncMode::EventLog);
+        assert_eq!(builder.timeouts, TimeoutConfig::default());
     }
 
     #[test]
@@ -252,5 +753,90 @@ mod tests {
         assert_eq!(builder.queue_size, 200);
     }
 
+    #[test]
+    fn test_builder_resume_token() {
+        let token = ResumeToken::new();
+        let builder = P2PLoopBuilder::new().resume_token(token);
+        assert_eq!(builder.resume_token, Some(token));
+    }
+
+    #[test]
+    fn test_builder_resume_from() {
+        let builder = P2PLoopBuilder::new().resume_from("lobby.jsonl");
+        assert_eq!(
+            builder.resume_from.as_deref(),
+            Some(std::path::Path::new("lobby.jsonl"))
+        );
+    }
+
+    #[test]
+    fn test_stored_session_id_from_log() {
+        use crate::infrastructure::event_store::FileEventLogStore;
+
+        let path = std::env::temp_dir().join(format!("konnekt-builder-{}.jsonl", Uuid::new_v4()));
+        let lobby_id = Uuid::new_v4();
+
+        let builder = P2PLoopBuilder::new().resume_from(&path);
+        assert!(builder.stored_session_id().unwrap().is_none());
+
+        FileEventLogStore::open(&path)
+            .unwrap()
+            .append(&LobbyEvent::new(
+                1,
+                lobby_id,
+                DomainEvent::LobbyCreated {
+                    lobby_id,
+                    host_id: Uuid::new_v4(),
+                    name: "Persisted".to_string(),
+                },
+            ))
+            .unwrap();
+
+        let session_id = builder.stored_session_id().unwrap().unwrap();
+        assert_eq!(session_id.inner(), lobby_id);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_replay_history_restores_participants() {
+        let lobby_id = Uuid::new_v4();
+        let host = Participant::host_with_id(Uuid::new_v4(), "Host".to_string()).unwrap();
+        let guest = Participant::new_guest("Alice".to_string()).unwrap();
+
+        let mut domain_loop = DomainLoop::new(10, 100);
+        domain_loop
+            .event_loop_mut()
+            .handle_command(DomainCommand::CreateLobbyWithHost {
+                lobby_id,
+                lobby_name: "Persisted".to_string(),
+                host: host.clone(),
+            });
+
+        let history = vec![
+            LobbyEvent::new(
+                1,
+                lobby_id,
+                DomainEvent::LobbyCreated {
+                    lobby_id,
+                    host_id: host.id(),
+                    name: "Persisted".to_string(),
+                },
+            ),
+            LobbyEvent::new(
+                2,
+                lobby_id,
+                DomainEvent::GuestJoined {
+                    participant: guest.clone(),
+                },
+            ),
+        ];
+
+        assert_eq!(replay_history(&mut domain_loop, lobby_id, &history), 1);
+
+        let lobby = domain_loop.event_loop().get_lobby(&lobby_id).unwrap();
+        assert!(lobby.participants().contains_key(&guest.id()));
+    }
+
     // Integration tests with real connections would go in tests/ directory
 }
@@ -1,16 +1,46 @@
-use crate::application::LobbySnapshot;
-use crate::application::runtime::P2PLoop;
-use crate::domain::PeerId;
-use crate::infrastructure::error::Result;
-use konnekt_session_core::{DomainCommand, DomainEvent as CoreDomainEvent, DomainLoop, Lobby};
+use crate::application::runtime::takeover::Succession;
+use crate::application::runtime::{Correlation, HostTakeover, P2PLoop};
+use crate::application::{
+    ConnectionEvent, DEFAULT_SNAPSHOT_PAGE_SIZE, HostSnapshot, LobbySnapshot, SnapshotPart,
+};
+use crate::domain::{
+    ChatMessage, DomainEvent as P2PDomainEvent, PRESENCE_TTL, PeerId, PeerStats, Presence,
+    TimeoutConfig, TimeoutPolicy, stamp_submission,
+};
+use crate::infrastructure::connection::MatchboxConnection;
+use crate::infrastructure::error::{Result, TrySubmitError};
+use crate::infrastructure::metrics;
+use crate::infrastructure::transport::NetworkConnection;
+use instant::{Duration, Instant};
+use konnekt_session_core::{
+    DomainCommand, DomainEvent as CoreDomainEvent, DomainLoop, Lobby, LobbyAction, Participant,
+    QueueError, Timestamp,
+};
+use std::collections::VecDeque;
+use std::task::{Context, Poll, Waker};
 use uuid::Uuid;
 
+/// How often the host broadcasts a checksum of its lobby state
+pub const DEFAULT_CHECKSUM_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Something that happened in the session
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)] // domain events carry whole lobbies
+pub enum SessionEvent {
+    /// Event emitted by the domain (ours or replicated from the host)
+    Domain(CoreDomainEvent),
+
+    /// Connection-level event (raw `MessageReceived` data is not included)
+    Connection(ConnectionEvent),
+}
+
 /// Unified session loop that coordinates P2P ↔ Core
 ///
 /// This is the single integration point between networking and business logic.
-pub struct SessionLoop {
+pub struct SessionLoop<C: NetworkConnection = MatchboxConnection> {
     /// P2P networking layer
-    p2p: P2PLoop,
+    p2p: P2PLoop<C>,
 
     /// Core domain layer
     domain: DomainLoop,
@@ -20,11 +50,63 @@ pub struct SessionLoop {
 
     /// Are we the host?
     is_host: bool,
+
+    /// Peers that asked for a full sync while we were a guest. Answered if we
+    /// take over as host.
+    pending_sync_requests: Vec<PeerId>,
+
+    /// Send a full sync to every peer at the end of the current poll
+    /// (set after a host takeover)
+    resync_pending: bool,
+
+    /// Anti-entropy: how often the host broadcasts a state checksum
+    /// (`None` disables it)
+    checksum_interval: Option<Duration>,
+
+    /// When the host last broadcast a state checksum
+    last_checksum_at: Option<Instant>,
+
+    /// Host checksum `(sequence, checksum)` the guest has not verified yet
+    pending_checksum: Option<(u64, u64)>,
+
+    /// The lobby CRDT changed; mirror it into the domain (`SyncMode::Crdt`)
+    crdt_dirty: bool,
+
+    /// Events kept for `AsyncSessionLoop` (`None` = not recording)
+    observed: Option<VecDeque<SessionEvent>>,
+
+    /// Task waiting for room in the command queue
+    submit_waker: Option<Waker>,
+
+    /// Trace of each command in the domain queue, in queue order
+    /// (the domain emits one event per command)
+    correlations: VecDeque<Option<Correlation>>,
+
+    /// Full syncs of lobbies with more items than this are streamed in pages
+    snapshot_page_size: usize,
+
+    /// Our last presence signal and when it was sent
+    presence_sent: Option<(Option<Presence>, Instant)>,
+
+    /// Where and how often the host writes its snapshot (crash recovery)
+    #[cfg(not(target_arch = "wasm32"))]
+    snapshot_file: Option<(std::path::PathBuf, Duration)>,
+
+    /// When the host last wrote its snapshot
+    #[cfg(not(target_arch = "wasm32"))]
+    last_snapshot_at: Option<Instant>,
+
+    /// How long a guest elected to replace the host may take to accept
+    /// (`None` takes over right away)
+    takeover_answer_timeout: Option<Duration>,
+
+    /// Host election while the host is missing (GUEST ONLY)
+    succession: Succession,
 }
 
-impl SessionLoop {
+impl<C: NetworkConnection> SessionLoop<C> {
     /// Create a new session loop for HOST
-    pub fn new_host(p2p: P2PLoop, domain: DomainLoop, lobby_id: Uuid) -> Self {
+    pub fn new_host(p2p: P2PLoop<C>, domain: DomainLoop, lobby_id: Uuid) -> Self {
         tracing::info!("🎯 SessionLoop created as HOST for lobby {}", lobby_id);
 
         Self {
@@ -32,11 +114,28 @@ impl SessionLoop {
             domain,
             lobby_id,
             is_host: true,
+            pending_sync_requests: Vec::new(),
+            resync_pending: false,
+            checksum_interval: Some(DEFAULT_CHECKSUM_INTERVAL),
+            last_checksum_at: None,
+            pending_checksum: None,
+            crdt_dirty: false,
+            observed: None,
+            submit_waker: None,
+            correlations: VecDeque::new(),
+            snapshot_page_size: DEFAULT_SNAPSHOT_PAGE_SIZE,
+            presence_sent: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            snapshot_file: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            last_snapshot_at: None,
+            takeover_answer_timeout: None,
+            succession: Succession::default(),
         }
     }
 
     /// Create a new session loop for GUEST
-    pub fn new_guest(p2p: P2PLoop, domain: DomainLoop, lobby_id: Uuid) -> Self {
+    pub fn new_guest(p2p: P2PLoop<C>, domain: DomainLoop, lobby_id: Uuid) -> Self {
         tracing::info!("🎯 SessionLoop created as GUEST for lobby {}", lobby_id);
 
         // ✅ FIX: Don't request sync here - wait for PeerConnected event in poll()
@@ -47,31 +146,596 @@ impl SessionLoop {
             domain,
             lobby_id,
             is_host: false,
+            pending_sync_requests: Vec::new(),
+            resync_pending: false,
+            checksum_interval: Some(DEFAULT_CHECKSUM_INTERVAL),
+            last_checksum_at: None,
+            pending_checksum: None,
+            crdt_dirty: false,
+            observed: None,
+            submit_waker: None,
+            correlations: VecDeque::new(),
+            snapshot_page_size: DEFAULT_SNAPSHOT_PAGE_SIZE,
+            presence_sent: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            snapshot_file: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            last_snapshot_at: None,
+            takeover_answer_timeout: None,
+            succession: Succession::default(),
+        }
+    }
+
+    /// Write a [`HostSnapshot`] to `path` every `interval` while hosting
+    /// (`None` stops it)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_snapshot_file(&mut self, path: Option<std::path::PathBuf>, interval: Duration) {
+        self.snapshot_file = path.map(|path| (path, interval));
+        self.last_snapshot_at = None;
+    }
+
+    /// Set how often the host broadcasts a state checksum (`None` disables it)
+    pub fn set_checksum_interval(&mut self, interval: Option<Duration>) {
+        self.checksum_interval = interval;
+    }
+
+    pub fn checksum_interval(&self) -> Option<Duration> {
+        self.checksum_interval
+    }
+
+    /// Stream full syncs of lobbies with more participants, queued activities
+    /// or results than `page_size`, in pages of that size. Keep it below the
+    /// domain queue size, since guests apply one page at a time.
+    pub fn set_snapshot_page_size(&mut self, page_size: usize) {
+        self.snapshot_page_size = page_size.max(1);
+    }
+
+    pub fn snapshot_page_size(&self) -> usize {
+        self.snapshot_page_size
+    }
+
+    pub fn timeouts(&self) -> TimeoutConfig {
+        self.p2p.timeouts()
+    }
+
+    /// Change grace period, heartbeats and timeout policy at runtime (HOST ONLY)
+    ///
+    /// Guests adopt the new settings when the broadcast reaches them.
+    pub fn set_timeouts(&mut self, config: TimeoutConfig) -> Result<()> {
+        if !self.is_host {
+            return Err(crate::infrastructure::error::P2PError::SendFailed(
+                "Only host can change timeouts".to_string(),
+            ));
         }
+
+        tracing::info!("⏱️  HOST: Updating timeouts: {:?}", config);
+        self.p2p.set_timeouts(config);
+        self.p2p.broadcast_timeouts()
+    }
+
+    /// Participants kept in the lobby after their peer timed out
+    /// (`TimeoutPolicy::MarkAway`)
+    pub fn away_participants(&self) -> Vec<Uuid> {
+        self.p2p.away_participants()
+    }
+
+    /// Replicate participants, participation modes and chat with CRDTs
+    /// instead of the host's event log (`SyncMode::Crdt`)
+    pub fn enable_crdt_sync(&mut self) {
+        self.p2p.enable_crdt();
+
+        // Seed the replica with the lobby we created (guests get it on connect)
+        let seed: Vec<(Uuid, String)> = match (self.is_host, self.get_lobby()) {
+            (true, Some(lobby)) => lobby
+                .participants()
+                .values()
+                .map(|p| (p.id(), p.name().to_string()))
+                .collect(),
+            _ => Vec::new(),
+        };
+        if let Some(crdt) = self.p2p.crdt_mut() {
+            for (id, name) in seed {
+                crdt.join(id, name);
+            }
+        }
+    }
+
+    pub fn is_crdt(&self) -> bool {
+        self.p2p.crdt().is_some()
     }
 
     /// Submit a domain command
     ///
     /// - Host: Processes locally
-    /// - Guest: Sends to host via P2P
-    pub fn submit_command(&mut self, cmd: DomainCommand) -> Result<()> {
+    /// - Guest: Sends to host via P2P (tagged with the topic for topic lobbies)
+    /// - CRDT sync: membership and mode changes are applied locally and
+    ///   broadcast to every peer
+    pub fn submit_command(&mut self, mut cmd: DomainCommand) -> Result<()> {
+        if self.is_host {
+            stamp_submission(&mut cmd, self.p2p.session_time_ms());
+        }
+        let correlation = Correlation::submitted(&cmd);
+        let span = correlation.span().clone();
+        let _entered = span.enter();
         tracing::debug!("📝 Submitting domain command: {:?}", cmd);
 
         if self.is_host {
             // Host: Process locally
-            self.domain
-                .submit(cmd)
-                .map_err(|e| crate::infrastructure::error::P2PError::SendFailed(e.to_string()))
+            self.submit_local(cmd, Some(correlation))
+        } else if let Some(topic) = self.topic_of_command(&cmd) {
+            self.p2p.send_topic_command(topic, cmd)
+        } else if self.is_crdt() && self.is_own_crdt_change(&cmd) {
+            self.apply_crdt_command(cmd)
         } else {
             // Guest: Send to host via P2P
-            self.p2p.send_command_to_host(cmd)
+            self.p2p
+                .send_correlated_command(cmd, Some(correlation.id()))
+        }
+    }
+
+    /// Submit a domain command without dropping it under load
+    ///
+    /// Returns `TrySubmitError::Full` with the command when the host's command
+    /// queue is saturated; wait with `poll_submit_ready` and retry.
+    pub fn try_submit_command(
+        &mut self,
+        cmd: DomainCommand,
+    ) -> std::result::Result<(), TrySubmitError> {
+        if self.is_host && self.domain.is_full() {
+            tracing::debug!("⏳ Command queue full, handing command back");
+            return Err(TrySubmitError::Full(Box::new(cmd)));
+        }
+
+        Ok(self.submit_command(cmd)?)
+    }
+
+    /// `Ready` once `try_submit_command` has room; otherwise wakes `cx` when
+    /// `poll` drains the command queue
+    pub fn poll_submit_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if !self.is_host || !self.domain.is_full() {
+            return Poll::Ready(());
+        }
+
+        self.submit_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Submit to our own domain, routing CRDT-managed commands through the CRDT
+    fn submit_local(&mut self, cmd: DomainCommand, correlation: Option<Correlation>) -> Result<()> {
+        if self.is_crdt() && Self::is_crdt_managed(&cmd) && cmd.lobby_id() == Some(self.lobby_id) {
+            return self.apply_crdt_command(cmd);
+        }
+
+        self.submit_to_domain(cmd, correlation)
+            .map_err(|e| crate::infrastructure::error::P2PError::SendFailed(e.to_string()))
+    }
+
+    /// Expire the lobby's run once its deadline passed on the session clock
+    ///
+    /// Waits for queued commands first, they may still end the run.
+    fn expire_overdue_run(&mut self) {
+        if self.domain.pending_commands() > 0 {
+            return;
+        }
+        let now_ms = self.p2p.session_time_ms();
+        let Some(run_id) = self.domain.event_loop().overdue_run(self.lobby_id, now_ms) else {
+            return;
+        };
+        tracing::info!("⏰ HOST: Run {} is past its deadline", run_id);
+        let cmd = DomainCommand::ExpireRun {
+            lobby_id: self.lobby_id,
+            run_id,
+        };
+        if let Err(e) = self.submit_to_domain(cmd, None) {
+            tracing::warn!("Failed to expire run {}: {:?}", run_id, e);
+        }
+    }
+
+    /// Queue a command in the domain, remembering its trace for the resulting event
+    fn submit_to_domain(
+        &mut self,
+        cmd: DomainCommand,
+        correlation: Option<Correlation>,
+    ) -> std::result::Result<(), QueueError> {
+        self.domain.submit(cmd)?;
+        self.correlations.push_back(correlation);
+        Ok(())
+    }
+
+    /// Queue a guest's command; the domain runs it only if `sender` may ask
+    /// for it (HOST ONLY)
+    fn submit_request(
+        &mut self,
+        mut cmd: DomainCommand,
+        sender: Option<Uuid>,
+        correlation: Option<Correlation>,
+    ) -> Result<()> {
+        stamp_submission(&mut cmd, self.p2p.session_time_ms());
+        if self.is_crdt() && Self::is_crdt_managed(&cmd) && cmd.lobby_id() == Some(self.lobby_id) {
+            if let Err(e) = self.domain.event_loop().authorize(&cmd, sender) {
+                tracing::warn!("🚫 HOST: Refusing {} from a guest: {}", cmd.name(), e);
+                return Ok(());
+            }
+            return self.apply_crdt_command(cmd);
+        }
+
+        self.domain
+            .submit_request(cmd, sender)
+            .map_err(|e| crate::infrastructure::error::P2PError::SendFailed(e.to_string()))?;
+        self.correlations.push_back(correlation);
+        Ok(())
+    }
+
+    fn log_received(cmd: &DomainCommand) {
+        match cmd {
+            DomainCommand::CreateLobby { lobby_name, .. } => {
+                tracing::info!("📥 Received lobby creation: {}", lobby_name);
+            }
+            DomainCommand::JoinLobby { guest_name, .. } => {
+                tracing::info!("📥 Guest '{}' wants to join", guest_name);
+            }
+            DomainCommand::LeaveLobby { participant_id, .. } => {
+                tracing::info!("📥 Participant {} leaving", participant_id);
+            }
+            DomainCommand::SubmitResult { result, run_id, .. } => {
+                tracing::info!(
+                    "📥 HOST: Received result from participant {} for run {}",
+                    result.participant_id,
+                    run_id
+                );
+            }
+            _ => {
+                tracing::debug!("📥 Received command: {:?}", cmd);
+            }
+        }
+    }
+
+    /// Commands replicated by the lobby CRDT (guests may not kick)
+    fn is_crdt_managed(cmd: &DomainCommand) -> bool {
+        matches!(
+            cmd,
+            DomainCommand::JoinLobby { .. }
+                | DomainCommand::LeaveLobby { .. }
+                | DomainCommand::KickGuest { .. }
+                | DomainCommand::ToggleParticipationMode { .. }
+        )
+    }
+
+    /// Whether a guest may apply `cmd` to the CRDT itself
+    ///
+    /// Peers only take a guest's word for its own participant; kicks and
+    /// changes to others go through the host.
+    fn is_own_crdt_change(&self, cmd: &DomainCommand) -> bool {
+        match cmd {
+            DomainCommand::JoinLobby { .. } => true,
+            DomainCommand::LeaveLobby { participant_id, .. }
+            | DomainCommand::ToggleParticipationMode { participant_id, .. } => {
+                self.p2p.local_participant_id() == Some(*participant_id)
+            }
+            _ => false,
+        }
+    }
+
+    /// Domain events that the CRDT replicates, so the host must not broadcast them
+    fn is_crdt_event(event: &CoreDomainEvent) -> bool {
+        matches!(
+            event,
+            CoreDomainEvent::GuestJoined { .. }
+                | CoreDomainEvent::GuestLeft { .. }
+                | CoreDomainEvent::GuestKicked { .. }
+                | CoreDomainEvent::ParticipationModeChanged { .. }
+        )
+    }
+
+    /// Turn a membership/mode command into a CRDT op and broadcast it
+    fn apply_crdt_command(&mut self, cmd: DomainCommand) -> Result<()> {
+        let fail = |reason: &str| crate::infrastructure::error::P2PError::SendFailed(reason.into());
+        if let DomainCommand::JoinLobby { guest_name, .. } = cmd {
+            self.p2p.crdt_join(guest_name)?;
+            self.crdt_dirty = true;
+            return Ok(());
+        }
+
+        // The lobby rules apply as if the domain ran the command
+        let lobby = self.get_lobby().ok_or_else(|| fail("No lobby found"))?;
+        let toggled = match &cmd {
+            DomainCommand::KickGuest {
+                host_id: kicker,
+                guest_id,
+                ..
+            } => {
+                if !lobby.allows(*kicker, LobbyAction::Kick { target: *guest_id }) {
+                    return Err(fail("Not allowed to kick this participant"));
+                }
+                if *guest_id == lobby.host_id() {
+                    return Err(fail("The host cannot be kicked"));
+                }
+                None
+            }
+            DomainCommand::ToggleParticipationMode {
+                participant_id,
+                requester_id,
+                ..
+            } => {
+                let action = LobbyAction::ToggleMode {
+                    target: *participant_id,
+                };
+                if !lobby.allows(*requester_id, action) {
+                    return Err(fail("Not allowed to change this participant's mode"));
+                }
+                let mut participant = lobby
+                    .participants()
+                    .get(participant_id)
+                    .cloned()
+                    .ok_or_else(|| fail("Unknown participant"))?;
+                let mode = participant
+                    .toggle_participation_mode(lobby.has_active_run())
+                    .map_err(|e| fail(&e.to_string()))?;
+                Some(mode)
+            }
+            _ => None,
+        };
+
+        let crdt = self
+            .p2p
+            .crdt_mut()
+            .ok_or_else(|| fail("CRDT sync is not enabled"))?;
+
+        let op = match cmd {
+            DomainCommand::LeaveLobby { participant_id, .. } => crdt.leave(participant_id),
+            DomainCommand::KickGuest { guest_id, ban, .. } => {
+                let op = crdt.leave(guest_id);
+                if ban {
+                    self.p2p.ban_participant(guest_id);
+                }
+                op
+            }
+            DomainCommand::ToggleParticipationMode { participant_id, .. } => {
+                let mode = toggled.ok_or_else(|| fail("Unknown participant"))?;
+                crdt.set_mode(participant_id, mode)
+            }
+            other => {
+                return self
+                    .submit_to_domain(other, None)
+                    .map_err(|e| fail(&e.to_string()));
+            }
+        };
+
+        self.crdt_dirty = true;
+        self.p2p.broadcast_crdt_op(op)
+    }
+
+    /// Mirror the lobby CRDT into the domain (adds, leaves, mode changes)
+    fn reconcile_crdt(&mut self) {
+        let (Some(crdt), Some(lobby)) = (self.p2p.crdt(), self.get_lobby()) else {
+            return;
+        };
+        let state = crdt.state();
+        let mut commands = Vec::new();
+        // The host turns away CRDT joins the lobby settings do not allow
+        let mut refused = Vec::new();
+        let mut seats = match lobby.check_can_join() {
+            Ok(()) => lobby
+                .settings()
+                .max_participants
+                .map_or(usize::MAX, |max| max - lobby.participants().len()),
+            Err(_) => 0,
+        };
+
+        for id in state.participants() {
+            match lobby.participants().get(&id) {
+                None if self.is_host && seats == 0 => refused.push(id),
+                None => {
+                    seats = seats.saturating_sub(1);
+                    let name = state.name(&id).unwrap_or_default().to_string();
+                    let joined_at = Timestamp::now_on(self.p2p.clock().as_ref());
+                    match Participant::guest_with_id_at(id, name, joined_at) {
+                        Ok(participant) => commands.push(DomainCommand::AddParticipant {
+                            lobby_id: self.lobby_id,
+                            participant,
+                        }),
+                        Err(e) => tracing::warn!("⚠️  Invalid CRDT participant {}: {}", id, e),
+                    }
+                }
+                Some(participant) => {
+                    if let Some(mode) = state.mode(&id)
+                        && mode != participant.participation_mode()
+                    {
+                        commands.push(DomainCommand::UpdateParticipantMode {
+                            lobby_id: self.lobby_id,
+                            participant_id: id,
+                            new_mode: mode,
+                        });
+                    }
+                }
+            }
+        }
+
+        for id in lobby.participants().keys() {
+            if *id != lobby.host_id() && state.knows(id) && !state.contains(id) {
+                commands.push(DomainCommand::LeaveLobby {
+                    lobby_id: self.lobby_id,
+                    participant_id: *id,
+                });
+            }
+        }
+
+        self.crdt_dirty = false;
+        for id in refused {
+            tracing::warn!("🚫 HOST: Turning away CRDT join of {}", id);
+            let Some(op) = self.p2p.crdt_mut().map(|crdt| crdt.leave(id)) else {
+                break;
+            };
+            if let Err(e) = self.p2p.broadcast_crdt_op(op) {
+                tracing::warn!("Failed to broadcast refused join: {:?}", e);
+            }
+        }
+        for cmd in commands {
+            tracing::debug!("🧬 Applying CRDT change: {:?}", cmd);
+            if let Err(e) = self.submit_to_domain(cmd, None) {
+                tracing::warn!("Failed to submit CRDT change to domain: {:?}", e);
+            }
+        }
+    }
+
+    /// Open a secondary lobby (breakout room) over this session (HOST ONLY)
+    ///
+    /// The lobby is hosted by our own participant; guests pick it up on the
+    /// first event and join it with `JoinLobby` for the returned lobby ID.
+    pub fn open_topic(&mut self, lobby_name: String) -> Result<Uuid> {
+        let fail = |reason: &str| crate::infrastructure::error::P2PError::SendFailed(reason.into());
+
+        if !self.is_host {
+            return Err(fail("Only host can open topics"));
+        }
+
+        let host = self
+            .get_lobby()
+            .and_then(|lobby| lobby.participants().get(&lobby.host_id()))
+            .cloned()
+            .ok_or_else(|| fail("No lobby found"))?;
+
+        let topic = Uuid::new_v4();
+        self.p2p.open_topic(topic);
+        self.domain
+            .submit(DomainCommand::CreateLobbyWithHost {
+                lobby_id: topic,
+                lobby_name,
+                host,
+            })
+            .map_err(|e| fail(&e.to_string()))?;
+
+        tracing::info!("🚪 HOST: Opened topic lobby {}", topic);
+        Ok(topic)
+    }
+
+    /// Stop carrying a topic lobby (its local state is kept)
+    pub fn close_topic(&mut self, topic: &Uuid) -> bool {
+        self.p2p.close_topic(topic)
+    }
+
+    /// Lobby IDs of all open topics
+    pub fn topics(&self) -> Vec<Uuid> {
+        self.p2p.topics()
+    }
+
+    /// Get the state of a topic lobby
+    pub fn topic_lobby(&self, topic: &Uuid) -> Option<&Lobby> {
+        self.domain.event_loop().get_lobby(topic)
+    }
+
+    /// Our own participant in a topic lobby, once the host accepted our join
+    pub fn topic_participant_id(&self, topic: &Uuid) -> Option<Uuid> {
+        self.p2p.topic_participant_id(topic)
+    }
+
+    /// Keep the topic's peer ↔ participant bindings in step with its
+    /// membership events (HOST ONLY)
+    fn track_topic_membership(&mut self, topic: Uuid, event: &CoreDomainEvent) {
+        match event {
+            CoreDomainEvent::GuestJoined { participant, .. } => {
+                self.p2p.accept_topic_join(topic, participant.id());
+            }
+            CoreDomainEvent::GuestLeft { participant_id, .. }
+            | CoreDomainEvent::GuestKicked { participant_id, .. } => {
+                self.p2p.release_topic_participant(&topic, *participant_id);
+            }
+            _ => {}
+        }
+    }
+
+    /// The open topic a command targets (`None` for the main lobby)
+    fn topic_of_command(&self, cmd: &DomainCommand) -> Option<Uuid> {
+        cmd.lobby_id()
+            .filter(|id| *id != self.lobby_id && self.p2p.has_topic(id))
+    }
+
+    /// Our participant, once joined
+    fn joined_participant_id(&self) -> Result<Uuid> {
+        self.p2p
+            .local_participant_id()
+            .or_else(|| {
+                self.is_host
+                    .then(|| self.get_lobby().map(|lobby| lobby.host_id()))
+                    .flatten()
+            })
+            .ok_or_else(|| {
+                crate::infrastructure::error::P2PError::SendFailed("Not joined yet".to_string())
+            })
+    }
+
+    /// Post a chat message
+    ///
+    /// With `SyncMode::Crdt` the message goes into the lobby CRDT; otherwise
+    /// it becomes part of the lobby (see `Lobby::chat_messages`).
+    pub fn send_chat(&mut self, text: String) -> Result<()> {
+        let author = self.joined_participant_id()?;
+
+        if !self.is_crdt() {
+            return self.submit_command(DomainCommand::SendChatMessage {
+                lobby_id: self.lobby_id,
+                author_id: author,
+                text,
+            });
+        }
+
+        let op = self
+            .p2p
+            .crdt_mut()
+            .ok_or_else(|| {
+                crate::infrastructure::error::P2PError::SendFailed(
+                    "CRDT sync is not enabled".to_string(),
+                )
+            })?
+            .chat(author, text);
+        self.p2p.broadcast_crdt_op(op)
+    }
+
+    /// Tell the others what we are doing right now (`None` clears it)
+    ///
+    /// Cheap to call on every keystroke: an unchanged signal is only resent
+    /// to keep it from expiring.
+    pub fn set_presence(&mut self, presence: Option<Presence>) -> Result<()> {
+        let now = self.p2p.clock().now();
+        if let Some((sent, at)) = self.presence_sent
+            && sent == presence
+            && (presence.is_none() || now.saturating_duration_since(at) < PRESENCE_TTL / 2)
+        {
+            return Ok(());
         }
+
+        let participant_id = self.joined_participant_id()?;
+        self.p2p.broadcast_presence(participant_id, presence)?;
+        self.presence_sent = Some((presence, now));
+        Ok(())
+    }
+
+    /// What other participants of the lobby are doing right now
+    pub fn presence(&self) -> Vec<(Uuid, Presence)> {
+        let Some(lobby) = self.get_lobby() else {
+            return Vec::new();
+        };
+        self.p2p
+            .presence()
+            .active()
+            .into_iter()
+            .filter(|(id, _)| lobby.participants().contains_key(id))
+            .collect()
+    }
+
+    /// Chat history in causal order (`SyncMode::Crdt`)
+    pub fn chat_messages(&self) -> Vec<ChatMessage> {
+        self.p2p
+            .crdt()
+            .map(|crdt| crdt.state().chat().to_vec())
+            .unwrap_or_default()
     }
 
     /// Register participant with peer (for tracking disconnections)
     fn register_participant_for_peer(&mut self, participant_id: Uuid) {
         if let Some(peer_id) = self.local_peer_id()
             && let Some(state) = self.p2p.peer_registry_mut().get_peer_mut(&peer_id)
+            && !state.has_participant_info()
         {
             state.set_participant_info(participant_id, String::new(), self.is_host);
 
@@ -83,9 +747,15 @@ impl SessionLoop {
         }
     }
 
-    /// Map the most recent unregistered peer to a participant
+    /// Map the joining peer to a participant
     /// Call this after GuestJoined event
     fn map_newest_guest_to_participant(&mut self, participant_id: Uuid, participant_name: &str) {
+        // Preferred: bind the peer that actually sent the JoinLobby
+        if self.p2p.accept_join(participant_id, participant_name) {
+            return;
+        }
+
+        // Fallback: the most recent unregistered peer
         // Find connected peers without participant IDs
         let unregistered_peers: Vec<PeerId> = self
             .p2p
@@ -111,6 +781,9 @@ impl SessionLoop {
             if let Some(state) = self.p2p.peer_registry_mut().get_peer_mut(peer_id) {
                 state.set_participant_info(participant_id, participant_name.to_string(), false);
             }
+
+            // Let guests know who is who (needed for host election)
+            self.p2p.broadcast_peer_roster();
         } else {
             tracing::warn!(
                 "⚠️  No unregistered peer found for participant {}",
@@ -119,13 +792,25 @@ impl SessionLoop {
         }
     }
 
+    /// Ask before taking over from a timed-out host: the elected guest gets
+    /// `answer_within` to [`Self::accept_takeover`] or
+    /// [`Self::decline_takeover`], then accepts (`None`: take over right away)
+    pub fn set_confirm_takeover(&mut self, answer_within: Option<Duration>) {
+        self.takeover_answer_timeout = answer_within;
+    }
+
     /// Main event loop - call this regularly (e.g., every 100ms)
     ///
+    /// Async callers can use `AsyncSessionLoop` instead, which polls only when
+    /// there is work.
+    ///
     /// This AUTOMATICALLY:
     /// 1. Polls P2P for network events
     /// 2. Gets domain commands (from P2P or translated events)
     /// 3. Processes commands in domain
     /// 4. Broadcasts resulting events (HOST ONLY)
+    /// 5. Broadcasts (HOST) or verifies (GUEST) a state checksum
+    /// 6. Flushes the outbound queue, control messages first
     pub fn poll(&mut self) -> usize {
         let mut processed = 0;
 
@@ -140,6 +825,28 @@ impl SessionLoop {
         // ===== Step 1.5: Handle connection events =====
         let connection_events = self.p2p.drain_events();
 
+        if let Some(observed) = &mut self.observed {
+            observed.extend(
+                connection_events
+                    .iter()
+                    .filter(|e| {
+                        !matches!(
+                            e,
+                            crate::application::ConnectionEvent::MessageReceived { .. }
+                        )
+                    })
+                    .cloned()
+                    .map(SessionEvent::Connection),
+            );
+        }
+
+        if connection_events
+            .iter()
+            .any(|e| matches!(e, crate::application::ConnectionEvent::CrdtUpdated))
+        {
+            self.crdt_dirty = true;
+        }
+
         if self.is_host {
             // HOST: Handle peer connections
             for event in &connection_events {
@@ -150,16 +857,8 @@ impl SessionLoop {
                             peer_id
                         );
 
-                        if let Some(lobby) = self.get_lobby() {
-                            let snapshot = LobbySnapshot {
-                                lobby_id: lobby.id(),
-                                name: lobby.name().to_string(),
-                                host_id: lobby.host_id(),
-                                participants: lobby.participants().values().cloned().collect(),
-                                as_of_sequence: self.p2p.current_sequence(),
-                            };
-
-                            if let Err(e) = self.p2p.send_full_sync_to_peer(*peer_id, snapshot) {
+                        if self.get_lobby().is_some() {
+                            if let Err(e) = self.send_full_sync_to_peer(*peer_id) {
                                 tracing::error!(
                                     "❌ Failed to send full sync to {}: {}",
                                     peer_id,
@@ -185,7 +884,15 @@ impl SessionLoop {
                             was_host
                         );
 
-                        if let Some(participant_id) = participant_id {
+                        if let Some(participant_id) = participant_id
+                            && self.p2p.timeouts().policy == TimeoutPolicy::MarkAway
+                        {
+                            tracing::info!(
+                                "💤 HOST: Keeping participant {} as away (peer timed out)",
+                                participant_id
+                            );
+                            self.p2p.mark_away(*participant_id);
+                        } else if let Some(participant_id) = participant_id {
                             tracing::info!(
                                 "🔴 HOST: Auto-removing participant {} (peer timed out)",
                                 participant_id
@@ -196,7 +903,7 @@ impl SessionLoop {
                                 participant_id: *participant_id,
                             };
 
-                            if let Err(e) = self.domain.submit(leave_cmd) {
+                            if let Err(e) = self.submit_local(leave_cmd, None) {
                                 tracing::error!(
                                     "Failed to submit LeaveLobby for timed-out peer: {:?}",
                                     e
@@ -219,16 +926,8 @@ impl SessionLoop {
                             since_sequence
                         );
 
-                        if let Some(lobby) = self.get_lobby() {
-                            let snapshot = LobbySnapshot {
-                                lobby_id: lobby.id(),
-                                name: lobby.name().to_string(),
-                                host_id: lobby.host_id(),
-                                participants: lobby.participants().values().cloned().collect(),
-                                as_of_sequence: self.p2p.current_sequence(),
-                            };
-
-                            if let Err(e) = self.p2p.send_full_sync_to_peer(*for_peer, snapshot) {
+                        if self.get_lobby().is_some() {
+                            if let Err(e) = self.send_full_sync_to_peer(*for_peer) {
                                 tracing::error!(
                                     "❌ HOST: Failed to send on-demand full sync to {}: {}",
                                     for_peer,
@@ -245,59 +944,210 @@ impl SessionLoop {
                         }
                     }
 
+                    crate::application::ConnectionEvent::TopicSyncNeeded { topic, for_peer } => {
+                        tracing::info!(
+                            "📤 HOST: Guest {} requested full sync of topic {}",
+                            for_peer,
+                            topic
+                        );
+
+                        if let Some(lobby) = self.topic_lobby(topic).cloned()
+                            && let Err(e) = self.p2p.send_topic_snapshot(*for_peer, &lobby)
+                        {
+                            tracing::error!(
+                                "❌ HOST: Failed to send topic sync to {}: {}",
+                                for_peer,
+                                e
+                            );
+                        }
+                    }
+
+                    crate::application::ConnectionEvent::HostSuperseded { new_host, epoch } => {
+                        tracing::warn!(
+                            "⚔️  HOST: {} holds a newer host claim (epoch {}) - stepping down",
+                            new_host,
+                            epoch
+                        );
+
+                        self.is_host = false;
+                        self.resync_pending = false;
+                        self.last_checksum_at = None;
+                    }
+
+                    crate::application::ConnectionEvent::PeerRateLimited {
+                        peer_id,
+                        participant_id,
+                        violations,
+                        auto_kick,
+                    } => {
+                        tracing::warn!(
+                            "🚦 HOST: Throttling peer {} (violation {})",
+                            peer_id,
+                            violations
+                        );
+
+                        if let (true, Some(guest_id), Some(host_id)) = (
+                            *auto_kick,
+                            participant_id,
+                            self.get_lobby().map(|lobby| lobby.host_id()),
+                        ) {
+                            tracing::warn!(
+                                "🥾 HOST: Auto-kicking participant {} for flooding",
+                                guest_id
+                            );
+
+                            let kick_cmd = DomainCommand::KickGuest {
+                                lobby_id: self.lobby_id,
+                                host_id,
+                                guest_id: *guest_id,
+                                ban: false,
+                            };
+
+                            if let Err(e) = self.submit_local(kick_cmd, None) {
+                                tracing::error!(
+                                    "Failed to submit KickGuest for flooding peer: {:?}",
+                                    e
+                                );
+                            }
+                        }
+                    }
+
                     _ => {}
                 }
             }
         } else {
             // ✅ GUEST: Handle peer connections
             for event in &connection_events {
-                if let crate::application::ConnectionEvent::PeerConnected(peer_id) = event {
-                    tracing::info!("🟢 GUEST: Connected to host peer {}", peer_id);
-                    tracing::info!("📤 GUEST: Requesting full sync from host");
+                match event {
+                    crate::application::ConnectionEvent::PeerConnected(peer_id) => {
+                        tracing::info!("🟢 GUEST: Connected to host peer {}", peer_id);
+
+                        if self.p2p.resume_token().is_some() {
+                            tracing::info!("📤 GUEST: Resuming previous participant");
+                            if let Err(e) = self.p2p.request_resume() {
+                                tracing::error!("❌ GUEST: Failed to request resume: {:?}", e);
+                            }
+                        }
+
+                        tracing::info!("📤 GUEST: Requesting full sync from host");
+
+                        // ✅ Request sync now that we have a connection
+                        if let Err(e) = self.p2p.request_full_sync() {
+                            tracing::error!("❌ GUEST: Failed to request full sync: {:?}", e);
+                        }
+                    }
+
+                    crate::application::ConnectionEvent::PeerTimedOut {
+                        participant_id: Some(old_host_id),
+                        was_host: true,
+                        ..
+                    } => {
+                        tracing::warn!("⏰ GUEST: Host {} timed out", old_host_id);
+                        self.handle_host_timeout(*old_host_id);
+                    }
+
+                    crate::application::ConnectionEvent::SyncNeeded { for_peer, .. }
+                        if !self.pending_sync_requests.contains(for_peer) =>
+                    {
+                        self.pending_sync_requests.push(*for_peer);
+                    }
+
+                    crate::application::ConnectionEvent::TakeoverDeclined { participant_id } => {
+                        tracing::info!("🙅 GUEST: {} declined to take over", participant_id);
+                        self.succession.declined.insert(*participant_id);
+                        if self.succession.successor == Some(*participant_id) {
+                            self.elect_successor();
+                        }
+                    }
+
+                    crate::application::ConnectionEvent::Reconnecting { attempt } => {
+                        tracing::warn!(
+                            "🔌 GUEST: Connection lost, reconnecting (attempt {})",
+                            attempt
+                        );
+                    }
 
-                    // ✅ Request sync now that we have a connection
-                    if let Err(e) = self.p2p.request_full_sync() {
-                        tracing::error!("❌ GUEST: Failed to request full sync: {:?}", e);
+                    crate::application::ConnectionEvent::Reconnected { peer_id } => {
+                        tracing::info!(
+                            "🔌 GUEST: Reconnected as {} - will resume once the host is back",
+                            peer_id
+                        );
                     }
+
+                    crate::application::ConnectionEvent::StateChecksum { sequence, checksum } => {
+                        // Still short of the previous checksum a whole interval
+                        // later: an event was lost, the gap will not fill itself
+                        if let Some((previous, _)) = self.pending_checksum
+                            && self.p2p.current_sequence() < previous
+                        {
+                            tracing::warn!(
+                                "🧮 GUEST: Stuck before sequence {} - requesting full sync",
+                                previous
+                            );
+                            if let Err(e) = self.p2p.request_full_sync() {
+                                tracing::error!("❌ GUEST: Failed to request full sync: {:?}", e);
+                            }
+                        }
+
+                        // Verified after the domain caught up (step 5)
+                        self.pending_checksum = Some((*sequence, *checksum));
+                    }
+
+                    crate::application::ConnectionEvent::TopicOpened { topic } => {
+                        tracing::info!("🚪 GUEST: Host opened topic lobby {}", topic);
+                    }
+
+                    _ => {}
                 }
             }
         }
 
         // ===== Step 2: Get domain commands from P2P =====
-        let commands = self.p2p.drain_domain_commands();
+        // (a streamed snapshot is applied one page per drained command queue)
+        if self.domain.pending_commands() == 0 {
+            self.p2p.apply_next_snapshot_page();
+        }
+        let commands = self.p2p.drain_correlated_commands();
+        let requests = self.p2p.drain_command_requests();
 
-        if !commands.is_empty() {
-            tracing::info!("📥 Received {} domain commands from P2P", commands.len());
+        if !commands.is_empty() || !requests.is_empty() {
+            tracing::info!(
+                "📥 Received {} domain commands from P2P",
+                commands.len() + requests.len()
+            );
         }
 
-        for cmd in commands {
-            match &cmd {
-                DomainCommand::CreateLobby { lobby_name, .. } => {
-                    tracing::info!("📥 Received lobby creation: {}", lobby_name);
-                }
-                DomainCommand::JoinLobby { guest_name, .. } => {
-                    tracing::info!("📥 Guest '{}' wants to join", guest_name);
-                }
-                DomainCommand::LeaveLobby { participant_id, .. } => {
-                    tracing::info!("📥 Participant {} leaving", participant_id);
-                }
-                DomainCommand::SubmitResult { result, run_id, .. } => {
-                    tracing::info!(
-                        "📥 HOST: Received result from participant {} for run {}",
-                        result.participant_id,
-                        run_id
-                    );
-                }
-                _ => {
-                    tracing::debug!("📥 Received command: {:?}", cmd);
-                }
+        for (cmd, sender, correlation) in requests {
+            Self::log_received(&cmd);
+            if let Err(e) = self.submit_request(cmd, sender, correlation) {
+                tracing::warn!("Failed to submit request to domain: {:?}", e);
             }
+        }
 
-            if let Err(e) = self.domain.submit(cmd) {
+        for (cmd, correlation) in commands {
+            Self::log_received(&cmd);
+
+            let submitted = if self.is_host {
+                self.submit_local(cmd, correlation)
+            } else {
+                self.submit_to_domain(cmd, correlation)
+                    .map_err(|e| crate::infrastructure::error::P2PError::SendFailed(e.to_string()))
+            };
+            if let Err(e) = submitted {
                 tracing::warn!("Failed to submit command to domain: {:?}", e);
             }
         }
 
+        // ===== Step 2.5: Mirror CRDT changes into the domain =====
+        if self.crdt_dirty {
+            self.reconcile_crdt();
+        }
+
+        // ===== Step 2.6: Close a run past its deadline (HOST ONLY) =====
+        if self.is_host {
+            self.expire_overdue_run();
+        }
+
         // ===== Step 3: Process domain commands =====
         let domain_processed = self.domain.poll();
         processed += domain_processed;
@@ -305,6 +1155,14 @@ impl SessionLoop {
         if domain_processed > 0 {
             tracing::debug!("🔧 Domain processed {} commands", domain_processed);
         }
+        metrics::events_processed("domain", domain_processed);
+        metrics::command_queue_depth(self.domain.pending_commands());
+
+        if !self.domain.is_full()
+            && let Some(waker) = self.submit_waker.take()
+        {
+            waker.wake();
+        }
 
         // ===== Step 4: Broadcast domain events =====
         let events = self.domain.drain_events();
@@ -314,6 +1172,29 @@ impl SessionLoop {
         }
 
         for event in events {
+            let correlation = self.correlations.pop_front().flatten();
+            let span = match &correlation {
+                Some(correlation) if self.is_host => correlation.broadcast_span(),
+                Some(correlation) => correlation.applied_span(),
+                None => tracing::Span::none(),
+            };
+            let _entered = span.enter();
+
+            if let Some(observed) = &mut self.observed {
+                observed.push_back(SessionEvent::Domain(event.clone()));
+            }
+
+            // Topic lobbies are sequenced separately and skip main-lobby bookkeeping
+            if let Some(topic) = event.lobby_id().filter(|id| *id != self.lobby_id) {
+                if self.is_host && self.p2p.has_topic(&topic) {
+                    self.track_topic_membership(topic, &event);
+                    if let Err(e) = self.p2p.broadcast_topic_event(topic, event) {
+                        tracing::error!("❌ Failed to broadcast topic event: {:?}", e);
+                    }
+                }
+                continue;
+            }
+
             // Log BEFORE processing
             tracing::info!(
                 "📤 Processing domain event: {:?}",
@@ -367,13 +1248,14 @@ impl SessionLoop {
                     );
 
                     // HOST: Register peer → participant mapping
-                    if self.is_host {
+                    // (with CRDT sync the peer is bound when its Join op arrives)
+                    if self.is_host && !self.is_crdt() {
                         self.map_newest_guest_to_participant(participant.id(), participant.name());
                         tracing::info!("📡 HOST: About to broadcast GuestJoined to all peers");
                     }
 
                     // GUEST: Register own participant ID
-                    if !self.is_host {
+                    if !self.is_host && !self.is_crdt() {
                         self.register_participant_for_peer(participant.id());
                         tracing::info!(
                             "📝 GUEST: Registered own participant ID: {}",
@@ -383,6 +1265,37 @@ impl SessionLoop {
                 }
                 CoreDomainEvent::GuestLeft { participant_id, .. } => {
                     tracing::info!("📤 Domain event: GuestLeft - {}", participant_id);
+
+                    if self.is_host {
+                        self.p2p.release_participant(*participant_id);
+                    }
+                }
+                CoreDomainEvent::GuestKicked {
+                    participant_id,
+                    banned,
+                    ..
+                } => {
+                    tracing::info!("📤 Domain event: GuestKicked - {}", participant_id);
+
+                    // Every peer enforces bans, so mesh traffic is dropped too
+                    if *banned && let Some(peer) = self.p2p.ban_participant(*participant_id) {
+                        tracing::warn!("🚫 Banned peer {} ({})", peer, participant_id);
+                    }
+                    if self.is_host {
+                        self.p2p.release_participant(*participant_id);
+                    }
+                }
+                CoreDomainEvent::HostDelegated { from, to, .. } => {
+                    tracing::info!("📤 Domain event: HostDelegated - {} → {}", from, to);
+
+                    // GUEST: the host handed the role to us. Our full syncs
+                    // carry the new epoch, which makes the old host step down.
+                    if !self.is_host && self.p2p.local_participant_id() == Some(*to) {
+                        tracing::info!("👑 GUEST: {} delegated the host role to us", from);
+                        self.promote_to_host();
+                        self.resync_pending = true;
+                        continue;
+                    }
                 }
                 CoreDomainEvent::ParticipationModeChanged {
                     participant_id,
@@ -404,8 +1317,15 @@ impl SessionLoop {
                         results.len()
                     );
                 }
-                CoreDomainEvent::CommandFailed { command, reason } => {
-                    tracing::warn!("⚠️  Command failed: {} - {}", command, reason);
+                CoreDomainEvent::CommandFailed { command, error } => {
+                    tracing::warn!("⚠️  Command failed: {} - {}", command.name(), error);
+
+                    if self.is_host && matches!(**command, DomainCommand::JoinLobby { .. }) {
+                        match self.topic_of_command(command) {
+                            Some(topic) => self.p2p.discard_pending_topic_join(&topic),
+                            None => self.p2p.discard_pending_join(),
+                        }
+                    }
                 }
                 _ => {
                     tracing::debug!("📤 Domain event: {:?}", event);
@@ -419,12 +1339,47 @@ impl SessionLoop {
                     continue;
                 }
 
+                if self.is_crdt() && Self::is_crdt_event(&event) {
+                    tracing::debug!("🧬 HOST: Not broadcasting CRDT-replicated event");
+                    continue;
+                }
+
                 tracing::info!(
                     "📡 HOST: Broadcasting event type: {:?}",
                     std::mem::discriminant(&event)
                 );
 
-                if let Err(e) = self.p2p.broadcast_domain_event(event.clone()) {
+                let correlation_id = correlation.as_ref().map(Correlation::id);
+                let broadcast = match &event {
+                    // Guests need the submitters snapshot, which only the run has
+                    CoreDomainEvent::RunStarted {
+                        run_id,
+                        config,
+                        started_at_ms,
+                        ..
+                    } => {
+                        let required_submitters = self
+                            .domain
+                            .event_loop()
+                            .get_run(run_id)
+                            .map(|run| run.required_submitters().iter().copied().collect())
+                            .unwrap_or_default();
+                        self.p2p.broadcast_correlated_p2p_event(
+                            P2PDomainEvent::RunStarted {
+                                run_id: *run_id,
+                                config: config.clone(),
+                                required_submitters,
+                                started_at_ms: *started_at_ms,
+                            },
+                            correlation_id,
+                        )
+                    }
+                    _ => self
+                        .p2p
+                        .broadcast_correlated_event(event.clone(), correlation_id),
+                };
+
+                if let Err(e) = broadcast {
                     tracing::error!("❌ Failed to broadcast event: {:?}", e);
                 } else {
                     tracing::info!(
@@ -437,9 +1392,346 @@ impl SessionLoop {
             }
         }
 
+        // ===== Step 5: Re-sync guests after a host takeover =====
+        if self.resync_pending && self.get_lobby().is_some() {
+            self.resync_pending = false;
+            self.resync_all_peers();
+        }
+
+        // ===== Step 5.1: Host succession (GUEST) =====
+        if !self.is_host {
+            self.update_succession();
+        }
+
+        // ===== Step 5.5: Anti-entropy state checksum =====
+        // (skipped with CRDT sync: replicas converge on their own)
+        if !self.is_crdt() {
+            if self.is_host {
+                self.maybe_broadcast_checksum();
+            } else {
+                self.verify_pending_checksum();
+            }
+        }
+
+        // ===== Step 5.6: Crash-recovery snapshot =====
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.is_host {
+            self.maybe_save_snapshot();
+        }
+
+        // ===== Step 6: Send everything queued this poll (by priority) =====
+        let sent = self.p2p.flush_outbound();
+        if sent > 0 {
+            tracing::trace!("📤 Flushed {} outbound messages", sent);
+        }
+
         processed
     }
 
+    /// Wake `cx` once the transport has new events
+    ///
+    /// `Ready` means the next `poll` has network input to process.
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.p2p.poll_ready(cx)
+    }
+
+    /// Start keeping connection and domain events for `take_events`
+    pub fn record_events(&mut self) {
+        self.observed.get_or_insert_with(VecDeque::new);
+    }
+
+    /// Events seen by `poll` since the last call (when recording)
+    pub fn take_events(&mut self) -> VecDeque<SessionEvent> {
+        self.observed
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Checksum of our local lobby state
+    pub fn state_checksum(&self) -> Option<u64> {
+        self.get_lobby()
+            .map(|lobby| LobbySnapshot::from_lobby(lobby, self.p2p.current_sequence()).checksum())
+    }
+
+    /// Lobby, active run and event log as they are now (HOST ONLY)
+    pub fn host_snapshot(&self) -> Option<HostSnapshot> {
+        if !self.is_host {
+            return None;
+        }
+
+        let lobby = self.get_lobby()?;
+        let run = lobby
+            .active_run_id()
+            .and_then(|run_id| self.domain.event_loop().get_run(&run_id));
+        HostSnapshot::capture(
+            lobby,
+            run,
+            self.p2p.all_events(),
+            self.p2p.current_sequence(),
+        )
+    }
+
+    /// Write the snapshot file once the interval elapsed (HOST ONLY)
+    #[cfg(not(target_arch = "wasm32"))]
+    fn maybe_save_snapshot(&mut self) {
+        let Some((path, interval)) = &self.snapshot_file else {
+            return;
+        };
+
+        let now = self.p2p.clock().now();
+        if self
+            .last_snapshot_at
+            .is_some_and(|at| now.saturating_duration_since(at) < *interval)
+        {
+            return;
+        }
+
+        let Some(snapshot) = self.host_snapshot() else {
+            return;
+        };
+
+        self.last_snapshot_at = Some(now);
+        if let Err(e) = snapshot.save(path) {
+            tracing::warn!("⚠️  HOST: Failed to save snapshot: {}", e);
+        }
+    }
+
+    /// Broadcast our state checksum once the interval elapsed (HOST ONLY)
+    fn maybe_broadcast_checksum(&mut self) {
+        let Some(interval) = self.checksum_interval else {
+            return;
+        };
+
+        let now = self.p2p.clock().now();
+        if self
+            .last_checksum_at
+            .is_some_and(|at| now.saturating_duration_since(at) < interval)
+        {
+            return;
+        }
+
+        let local = self.local_peer_id();
+        if !self.connected_peers().iter().any(|p| Some(*p) != local) {
+            return;
+        }
+
+        let Some(checksum) = self.state_checksum() else {
+            return;
+        };
+
+        self.last_checksum_at = Some(now);
+        if let Err(e) = self.p2p.broadcast_state_checksum(checksum) {
+            tracing::warn!("⚠️  HOST: Failed to broadcast state checksum: {}", e);
+        }
+    }
+
+    /// Compare the host's checksum against ours once we reached its sequence
+    /// and request a full sync on mismatch (GUEST ONLY)
+    fn verify_pending_checksum(&mut self) {
+        let Some((sequence, expected)) = self.pending_checksum else {
+            return;
+        };
+
+        let current = self.p2p.current_sequence();
+        if current < sequence {
+            // Still waiting for events the checksum covers
+            return;
+        }
+        self.pending_checksum = None;
+
+        if current > sequence {
+            // Stale: we already applied newer events
+            return;
+        }
+
+        let actual = self.state_checksum();
+        if actual == Some(expected) {
+            tracing::trace!("🧮 GUEST: State checksum matches at sequence {}", sequence);
+            return;
+        }
+
+        tracing::warn!(
+            "🧮 GUEST: State diverged from host at sequence {} (expected {:x}, got {:?}) - requesting full sync",
+            sequence,
+            expected,
+            actual
+        );
+
+        if let Err(e) = self.p2p.request_full_sync() {
+            tracing::error!("❌ GUEST: Failed to request full sync: {:?}", e);
+        }
+    }
+
+    /// Elect a successor for a timed-out host (oldest guest) and take over if
+    /// that is us. Every guest runs the same election on the same lobby state.
+    fn handle_host_timeout(&mut self, old_host_id: Uuid) {
+        self.succession.vacant = Some(old_host_id);
+        self.elect_successor();
+    }
+
+    /// (Re-)run the election, skipping guests that declined
+    fn elect_successor(&mut self) {
+        let Some(old_host_id) = self.succession.vacant else {
+            return;
+        };
+        let successor = self
+            .get_lobby()
+            .and_then(|l| l.next_host_candidate_excluding(&self.succession.declined));
+        self.succession.successor = successor;
+
+        let Some(successor) = successor else {
+            tracing::warn!("⚠️  GUEST: No successor available for host {}", old_host_id);
+            return;
+        };
+
+        if self.p2p.local_participant_id() != Some(successor) {
+            tracing::info!(
+                "⏳ GUEST: Waiting for {} to take over as host (epoch {})",
+                successor,
+                self.p2p.epoch() + 1
+            );
+            return;
+        }
+
+        match self.takeover_answer_timeout {
+            Some(timeout) => {
+                if self.succession.offer_expires.is_none() {
+                    tracing::info!(
+                        "🗳️  GUEST: Elected to take over as host - waiting for an answer"
+                    );
+                    self.succession.offer_expires = Some(self.p2p.clock().now() + timeout);
+                }
+            }
+            None => self.take_over_host(old_host_id, successor),
+        }
+    }
+
+    /// Forget the election once someone else hosts; accept an offer nobody
+    /// answered in time
+    fn update_succession(&mut self) {
+        if let Some(old_host_id) = self.succession.vacant
+            && let Some(new_host_id) = self
+                .get_lobby()
+                .map(|l| l.host_id())
+                .filter(|id| *id != old_host_id)
+        {
+            tracing::info!("👑 GUEST: {} took over as host", new_host_id);
+            self.succession.reset();
+            return;
+        }
+
+        if self
+            .succession
+            .offer_remaining(self.p2p.clock().now())
+            .is_some_and(|remaining| remaining.is_zero())
+        {
+            tracing::info!("⌛ GUEST: No answer to the takeover - taking over");
+            self.accept_takeover();
+        }
+    }
+
+    /// Where we stand while the host is missing (GUEST ONLY; `None` while
+    /// the host is fine)
+    pub fn host_takeover(&self) -> Option<HostTakeover> {
+        if self.is_host {
+            return None;
+        }
+        if let Some(remaining) = self.succession.offer_remaining(self.p2p.clock().now()) {
+            return Some(HostTakeover::Offered { remaining });
+        }
+        if self.succession.vacant.is_some() {
+            return Some(HostTakeover::Electing {
+                successor: self.succession.successor,
+            });
+        }
+        self.p2p
+            .host_grace_remaining()
+            .map(|remaining| HostTakeover::GracePeriod { remaining })
+    }
+
+    /// Take over as host after being elected (see `set_confirm_takeover`).
+    /// Returns whether there was an offer to accept.
+    pub fn accept_takeover(&mut self) -> bool {
+        let (Some(old_host_id), Some(local)) =
+            (self.succession.vacant, self.p2p.local_participant_id())
+        else {
+            return false;
+        };
+        if self.succession.offer_expires.take().is_none() {
+            return false;
+        }
+        self.take_over_host(old_host_id, local);
+        true
+    }
+
+    /// Pass the takeover on to the next candidate and tell every peer.
+    /// Returns whether there was an offer to decline.
+    pub fn decline_takeover(&mut self) -> Result<bool> {
+        let Some(local) = self.p2p.local_participant_id() else {
+            return Ok(false);
+        };
+        if self.succession.offer_expires.take().is_none() {
+            return Ok(false);
+        }
+
+        tracing::info!("🙅 GUEST: Declining to take over as host");
+        self.succession.declined.insert(local);
+        self.p2p.broadcast_takeover_declined(local)?;
+        self.elect_successor();
+        Ok(true)
+    }
+
+    /// Assume the host role after the previous host timed out
+    fn take_over_host(&mut self, old_host_id: Uuid, new_host_id: Uuid) {
+        tracing::info!(
+            "👑 Taking over as HOST from {} (epoch {})",
+            old_host_id,
+            self.p2p.epoch() + 1
+        );
+
+        self.promote_to_host();
+
+        let commands = [
+            DomainCommand::DelegateHost {
+                lobby_id: self.lobby_id,
+                current_host_id: old_host_id,
+                new_host_id,
+            },
+            DomainCommand::LeaveLobby {
+                lobby_id: self.lobby_id,
+                participant_id: old_host_id,
+            },
+        ];
+
+        for cmd in commands {
+            if let Err(e) = self.submit_local(cmd, None) {
+                tracing::error!("Failed to submit takeover command: {:?}", e);
+            }
+        }
+
+        self.succession.reset();
+        self.resync_pending = true;
+    }
+
+    /// Send a full sync to every connected peer and anyone who asked while we
+    /// were a guest (HOST ONLY)
+    fn resync_all_peers(&mut self) {
+        let local = self.local_peer_id();
+        let mut peers = self.p2p.connected_peers();
+        for peer in self.pending_sync_requests.drain(..) {
+            if !peers.contains(&peer) {
+                peers.push(peer);
+            }
+        }
+
+        for peer_id in peers.into_iter().filter(|p| Some(*p) != local) {
+            if let Err(e) = self.send_full_sync_to_peer(peer_id) {
+                tracing::error!("❌ Failed to re-sync {} after takeover: {}", peer_id, e);
+            }
+        }
+    }
+
     /// Get the current lobby state (for rendering UI)
     pub fn get_lobby(&self) -> Option<&Lobby> {
         self.domain.event_loop().get_lobby(&self.lobby_id)
@@ -457,6 +1749,27 @@ impl SessionLoop {
         self.p2p.connected_peers()
     }
 
+    /// Round trip, last seen and message counts of every other peer
+    pub fn peer_stats(&self) -> Vec<PeerStats> {
+        self.p2p.peer_stats()
+    }
+
+    /// Is the connection currently reconnecting to signalling?
+    pub fn is_reconnecting(&self) -> bool {
+        self.p2p.is_reconnecting()
+    }
+
+    /// How far the host's clock is ahead of ours, in ms (see
+    /// [`P2PLoop::clock_offset_ms`])
+    pub fn clock_offset_ms(&self) -> i64 {
+        self.p2p.clock_offset_ms()
+    }
+
+    /// Now on the host's clock (Unix ms); use it for countdowns and deadlines
+    pub fn session_time_ms(&self) -> u64 {
+        self.p2p.session_time_ms()
+    }
+
     pub fn is_host(&self) -> bool {
         self.is_host
     }
@@ -476,29 +1789,37 @@ impl SessionLoop {
 
         tracing::info!("📤 Sending full sync to peer {}", peer_id);
 
-        let lobby = self
-            .get_lobby()
-            .ok_or_else(|| {
-                crate::infrastructure::error::P2PError::SendFailed("No lobby found".to_string())
-            })?
-            .clone();
-
-        let snapshot = LobbySnapshot {
-            lobby_id: lobby.id(),
-            name: lobby.name().to_string(),
-            host_id: lobby.host_id(),
-            participants: lobby.participants().values().cloned().collect(),
-            as_of_sequence: self.p2p.current_sequence(),
-        };
+        let lobby = self.get_lobby().ok_or_else(|| {
+            crate::infrastructure::error::P2PError::SendFailed("No lobby found".to_string())
+        })?;
+        let as_of_sequence = self.p2p.current_sequence();
+        let run = lobby
+            .active_run_id()
+            .and_then(|run_id| self.domain.event_loop().get_run(&run_id));
+
+        // Large lobbies are streamed page by page
+        if SnapshotPart::should_stream(lobby, run, self.snapshot_page_size) {
+            match SnapshotPart::split(lobby, run, as_of_sequence, self.snapshot_page_size) {
+                Some(parts) => {
+                    return self.p2p.stream_snapshot_to_peer(
+                        peer_id,
+                        parts,
+                        self.snapshot_page_size,
+                    );
+                }
+                None => tracing::warn!("⚠️ Lobby has no host, sending a whole snapshot instead"),
+            }
+        }
 
+        let snapshot = LobbySnapshot::from_lobby(lobby, as_of_sequence);
         self.p2p.send_full_sync_to_peer(peer_id, snapshot)
     }
 
-    pub fn p2p(&self) -> &P2PLoop {
+    pub fn p2p(&self) -> &P2PLoop<C> {
         &self.p2p
     }
 
-    pub fn p2p_mut(&mut self) -> &mut P2PLoop {
+    pub fn p2p_mut(&mut self) -> &mut P2PLoop<C> {
         &mut self.p2p
     }
 
@@ -1,9 +1,43 @@
+use crate::domain::{
+    PRESENCE_TTL, PeerId, PeerParticipantMap, PeerStats, Presence, PresenceMap, SharedClock,
+    stamp_submission,
+};
 use crate::infrastructure::error::Result;
 use crate::infrastructure::transport::{NetworkConnection, P2PTransport, TransportEvent};
-use konnekt_session_core::{DomainCommand, DomainEvent as CoreDomainEvent, DomainLoop, Lobby};
-use std::collections::HashSet;
+use instant::{Duration, Instant};
+use konnekt_session_core::{
+    CodedError, CommandError, DomainCommand, DomainEvent as CoreDomainEvent, DomainLoop,
+    EventFilter, EventSubscription, Lobby,
+};
+use std::collections::VecDeque;
 use uuid::Uuid;
 
+/// How often round trips to the peers are measured
+const PING_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Domain events kept for `take_events` before the oldest are dropped
+pub const RECENT_EVENTS_LIMIT: usize = 256;
+
+/// How much work is waiting inside a session loop
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueDepths {
+    /// Commands submitted to the domain but not executed yet
+    pub domain_commands: usize,
+    /// Events the domain emitted that were not handled yet
+    pub domain_events: usize,
+    /// Failures not taken with `take_failed_commands` yet
+    pub failed_commands: usize,
+    /// Events not taken with `take_events` yet
+    pub recent_events: usize,
+}
+
+/// A command the host failed to execute
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FailedCommand {
+    pub command: DomainCommand,
+    pub error: CommandError,
+}
+
 /// Unified session loop (translation layer between domain and transport)
 /// Generic over connection type to allow mocking in tests
 pub struct SessionLoopV2<C: NetworkConnection> {
@@ -18,6 +52,32 @@ pub struct SessionLoopV2<C: NetworkConnection> {
 
     /// Lobby ID
     lobby_id: Uuid,
+
+    /// What other participants are doing right now
+    presence: PresenceMap,
+
+    /// Our participant and last presence signal, when it was sent
+    presence_sent: Option<(Uuid, Option<Presence>, Instant)>,
+
+    /// When round trips were last measured
+    last_ping: Option<Instant>,
+
+    /// Commands the host failed to execute, not yet taken (a guest only
+    /// learns about its own)
+    failed_commands: Vec<FailedCommand>,
+
+    /// Participant each guest's peer joined as (host only)
+    peer_participants: PeerParticipantMap,
+
+    /// Guest whose request each queued domain command is, in the order the
+    /// domain answers them; `None` for our own commands (host only)
+    origins: VecDeque<Option<PeerId>>,
+
+    /// Domain events applied locally, not yet taken
+    recent_events: VecDeque<CoreDomainEvent>,
+
+    /// Why the last snapshot from the host could not be read
+    protocol_error: Option<String>,
 }
 
 impl<C: NetworkConnection> SessionLoopV2<C> {
@@ -28,23 +88,36 @@ impl<C: NetworkConnection> SessionLoopV2<C> {
         is_host: bool,
         lobby_id: Uuid,
     ) -> Self {
+        let origins =
+            std::iter::repeat_n(None, domain.pending_commands() + domain.pending_events())
+                .collect();
         Self {
             domain,
-            transport,
             is_host,
             lobby_id,
+            presence: PresenceMap::new().with_clock(transport.clock().clone()),
+            presence_sent: None,
+            last_ping: None,
+            failed_commands: Vec::new(),
+            peer_participants: PeerParticipantMap::new(),
+            origins,
+            recent_events: VecDeque::new(),
+            protocol_error: None,
+            transport,
         }
     }
 
     /// Submit a domain command
-    pub fn submit_command(&mut self, cmd: DomainCommand) -> Result<()> {
+    pub fn submit_command(&mut self, mut cmd: DomainCommand) -> Result<()> {
         if self.is_host {
+            stamp_submission(&mut cmd, self.session_time_ms());
             // Host: execute locally
             self.domain
                 .submit(cmd)
                 .map_err(|e| crate::infrastructure::error::P2PError::SendFailed(e.to_string()))?;
+            self.origins.push_back(None);
         } else {
-            // Guest: send to host
+            // Guest: ask the host, which broadcasts the outcome if it agrees
             let payload = serde_json::to_value(&cmd)
                 .map_err(crate::infrastructure::error::P2PError::Serialization)?;
             self.transport.send_to_host(payload)?;
@@ -55,16 +128,31 @@ impl<C: NetworkConnection> SessionLoopV2<C> {
     /// Main event loop
     pub fn poll(&mut self) -> usize {
         let mut processed = 0;
-        let mut host_prebroadcast_submissions: HashSet<(Uuid, Uuid)> = HashSet::new();
+
+        let now = self.transport.clock().now();
+        if self
+            .last_ping
+            .is_none_or(|at| now.saturating_duration_since(at) >= PING_INTERVAL)
+        {
+            self.transport.ping();
+            self.last_ping = Some(now);
+        }
+
+        // Read the network first, so a snapshot lands before the messages
+        // that follow it
+        let messages = self.transport.poll();
 
         // 1. Handle transport events
+        let mut snapshot_requested = false;
         for event in self.transport.drain_events() {
             match event {
                 TransportEvent::PeerConnected(peer_id) => {
                     if self.is_host {
                         tracing::info!("🟢 HOST: Peer {} connected - sending snapshot", peer_id);
                         self.send_snapshot_to_peer(peer_id);
-                    } else {
+                    } else if !snapshot_requested {
+                        // One request covers every peer that connected meanwhile
+                        snapshot_requested = true;
                         tracing::info!("🟢 GUEST: Connected to host - requesting snapshot");
                         let _ = self.transport.request_snapshot();
                     }
@@ -86,18 +174,53 @@ impl<C: NetworkConnection> SessionLoopV2<C> {
             processed += 1;
         }
 
-        // 2. Poll transport for messages
-        let messages = self.transport.poll();
-
+        // 2. Apply messages
         if !messages.is_empty() {
             tracing::debug!("📥 Received {} messages from transport", messages.len());
         }
 
-        for payload in messages {
+        for (from, payload) in messages {
             processed += 1;
 
-            if let Ok(cmd) = serde_json::from_value::<DomainCommand>(payload.clone()) {
-                tracing::debug!("📥 Processing command: {:?}", std::mem::discriminant(&cmd));
+            if let Some(update) = payload
+                .get("presence")
+                .and_then(|value| serde_json::from_value::<PresenceUpdate>(value.clone()).ok())
+            {
+                // Guests may only speak for themselves
+                if self.is_host
+                    && self
+                        .peer_participants
+                        .get_participant(&from)
+                        .is_some_and(|bound| bound != update.participant_id)
+                {
+                    tracing::warn!("🚫 HOST: Dropping spoofed presence from {}", from);
+                    continue;
+                }
+                self.presence.update(update.participant_id, update.presence);
+                if self.is_host {
+                    // Fan out to the other guests
+                    let _ = self.transport.send(payload);
+                }
+                continue;
+            }
+
+            if let Some(failure) = payload
+                .get("command_failed")
+                .and_then(|value| serde_json::from_value::<FailedCommand>(value.clone()).ok())
+            {
+                if !self.is_host {
+                    tracing::warn!(
+                        "↩️ GUEST: Host refused {}: {}",
+                        failure.command.name(),
+                        failure.error
+                    );
+                    self.failed_commands.push(failure);
+                }
+                continue;
+            }
+
+            if let Ok(mut cmd) = serde_json::from_value::<DomainCommand>(payload) {
+                tracing::debug!("📥 Processing command: {}", cmd.name());
 
                 // Log details for important commands
                 match &cmd {
@@ -114,41 +237,28 @@ impl<C: NetworkConnection> SessionLoopV2<C> {
                     _ => {}
                 }
 
-                // ✅ FIX: Execute in domain FIRST
-                if let Err(e) = self.domain.submit(cmd.clone()) {
-                    tracing::warn!("❌ Failed to submit command to domain: {:?}", e);
-                    continue; // Skip broadcast if command failed
-                }
-
-                // ✅ FIX: If host, ALWAYS broadcast to all guests (even if we executed it)
                 if self.is_host {
-                    if matches!(cmd, DomainCommand::JoinLobby { .. }) {
-                        tracing::debug!(
-                            "📡 HOST: Skipping direct JoinLobby rebroadcast; GuestJoined event will sync authoritative participant"
-                        );
+                    // A guest's request: broadcast are only the events the
+                    // domain emits once it accepted it (step 4)
+                    let sender = self.peer_participants.get_participant(&from);
+                    stamp_submission(&mut cmd, self.session_time_ms());
+                    if let Err(e) = self.domain.submit_request(cmd, sender) {
+                        tracing::warn!("❌ Failed to submit request to domain: {:?}", e);
                         continue;
                     }
-
-                    tracing::debug!(
-                        "📡 HOST: Broadcasting command to all peers: {:?}",
-                        std::mem::discriminant(&cmd)
-                    );
-
-                    if let DomainCommand::SubmitResult { run_id, result, .. } = &cmd {
-                        host_prebroadcast_submissions.insert((*run_id, result.participant_id));
-                    }
-
-                    if let Ok(payload) = serde_json::to_value(&cmd) {
-                        if let Err(e) = self.transport.send(payload) {
-                            tracing::warn!("❌ Failed to broadcast: {:?}", e);
-                        } else {
-                            tracing::debug!("✅ Broadcast successful");
-                        }
-                    }
+                    self.origins.push_back(Some(from));
+                } else if let Err(e) = self.domain.submit(cmd) {
+                    // The host's decision, replayed
+                    tracing::warn!("❌ Failed to submit command to domain: {:?}", e);
                 }
             }
         }
 
+        // 2.5. Close a run past its deadline
+        if self.is_host {
+            self.expire_overdue_run();
+        }
+
         // 3. Process domain commands
         let domain_processed = self.domain.poll();
         processed += domain_processed;
@@ -157,59 +267,128 @@ impl<C: NetworkConnection> SessionLoopV2<C> {
             tracing::debug!("🔧 Domain processed {} commands", domain_processed);
         }
 
-        // 4. Broadcast HOST-INITIATED events (not guest commands)
+        // 4. Broadcast what the domain accepted, ours and the guests'
         if self.is_host {
             for event in self.domain.drain_events() {
-                tracing::debug!(
-                    "📤 HOST: Processing domain event: {:?}",
-                    std::mem::discriminant(&event)
-                );
+                let origin = self.origins.pop_front().flatten();
+                self.record_event(&event);
+                tracing::debug!("📤 HOST: Processing domain event: {:?}", event.kind());
 
                 match &event {
-                    // ✅ Skip events that came from guest commands (already broadcast in step 2)
-                    CoreDomainEvent::ResultSubmitted { .. } => {
-                        if let CoreDomainEvent::ResultSubmitted { run_id, result, .. } = &event {
-                            if host_prebroadcast_submissions
-                                .contains(&(*run_id, result.participant_id))
-                            {
-                                tracing::debug!(
-                                    "   ↳ Skipping ResultSubmitted (already broadcast guest command)"
-                                );
-                                continue;
-                            }
+                    CoreDomainEvent::GuestJoined { participant, .. } => {
+                        if let Some(peer) = origin {
+                            self.peer_participants.register(peer, participant.id());
                         }
                     }
-                    CoreDomainEvent::GuestLeft { .. } => {
-                        tracing::debug!("   ↳ Skipping GuestLeft (already broadcast)");
-                        continue;
-                    }
-                    CoreDomainEvent::RunEnded { .. } => {
-                        tracing::debug!("   ↳ Skipping RunEnded (auto-completes on guests)");
+                    CoreDomainEvent::CommandFailed { command, error } => {
+                        tracing::warn!(
+                            "⚠️ HOST: {} failed ({}): {}",
+                            command.name(),
+                            error.code(),
+                            error
+                        );
+                        let failure = FailedCommand {
+                            command: (**command).clone(),
+                            error: error.clone(),
+                        };
+                        // Only the guest that asked hears about it
+                        if let Some(peer) = origin
+                            && let Ok(failure) = serde_json::to_value(&failure)
+                        {
+                            let payload = serde_json::json!({ "command_failed": failure });
+                            if let Err(e) = self.transport.send_to_peer(peer, payload) {
+                                tracing::warn!("❌ Failed to report failure to {}: {:?}", peer, e);
+                            }
+                        }
+                        self.failed_commands.push(failure);
                         continue;
                     }
                     _ => {}
                 }
 
-                // Translate HOST-initiated events → commands for guests
+                // Translate events → commands for guests
                 if let Some(cmd) = self.event_to_command(event) {
-                    tracing::debug!(
-                        "   ↳ Broadcasting host-initiated event as command: {:?}",
-                        std::mem::discriminant(&cmd)
-                    );
+                    tracing::debug!("   ↳ Broadcasting event as command: {}", cmd.name());
 
-                    if let Ok(payload) = serde_json::to_value(&cmd) {
-                        let _ = self.transport.send(payload);
+                    if let Ok(payload) = serde_json::to_value(&cmd)
+                        && let Err(e) = self.transport.send(payload)
+                    {
+                        tracing::warn!("❌ Failed to broadcast: {:?}", e);
                     }
                 }
             }
         } else {
             // Guests drain events (but don't broadcast)
-            self.domain.drain_events();
+            for event in self.domain.drain_events() {
+                self.record_event(&event);
+            }
         }
 
         processed
     }
 
+    /// Tell the others what `participant_id` (us) is doing (`None` clears it)
+    ///
+    /// An unchanged signal is only resent to keep it from expiring. There is
+    /// no ephemeral class here: signals travel with the ordered messages.
+    /// Expire the lobby's run once its deadline passed on the session clock
+    /// (HOST ONLY); waits for queued commands, they may still end the run
+    fn expire_overdue_run(&mut self) {
+        if self.domain.pending_commands() > 0 {
+            return;
+        }
+        let now_ms = self.session_time_ms();
+        let Some(run_id) = self.domain.event_loop().overdue_run(self.lobby_id, now_ms) else {
+            return;
+        };
+        let cmd = DomainCommand::ExpireRun {
+            lobby_id: self.lobby_id,
+            run_id,
+        };
+        if let Err(e) = self.domain.submit(cmd) {
+            tracing::warn!("❌ Failed to expire run {}: {:?}", run_id, e);
+            return;
+        }
+        self.origins.push_back(None);
+    }
+
+    pub fn set_presence(&mut self, participant_id: Uuid, presence: Option<Presence>) -> Result<()> {
+        let now = self.transport.clock().now();
+        if let Some((id, sent, at)) = self.presence_sent
+            && id == participant_id
+            && sent == presence
+            && (presence.is_none() || now.saturating_duration_since(at) < PRESENCE_TTL / 2)
+        {
+            return Ok(());
+        }
+
+        let update = PresenceUpdate {
+            participant_id,
+            presence,
+        };
+        let payload = serde_json::json!({ "presence": update });
+        if self.is_host {
+            self.transport.send(payload)?;
+        } else {
+            self.transport.send_to_host(payload)?;
+        }
+        self.presence_sent = Some((participant_id, presence, now));
+        Ok(())
+    }
+
+    /// What other participants of the lobby are doing right now
+    pub fn presence(&self) -> Vec<(Uuid, Presence)> {
+        let Some(lobby) = self.get_lobby() else {
+            return Vec::new();
+        };
+        let local = self.presence_sent.map(|(id, _, _)| id);
+        self.presence
+            .active()
+            .into_iter()
+            .filter(|(id, _)| Some(*id) != local && lobby.participants().contains_key(id))
+            .collect()
+    }
+
     /// Send snapshot to a specific peer (HOST ONLY)
     fn send_snapshot_to_peer(&mut self, peer_id: crate::domain::PeerId) {
         if let Some(lobby) = self.get_lobby() {
@@ -227,59 +406,148 @@ impl<C: NetworkConnection> SessionLoopV2<C> {
             name: lobby.name().to_string(),
             host_id: lobby.host_id(),
             participants: lobby.participants().values().cloned().collect(),
+            activity_queue: lobby.activity_queue().to_vec(),
+            ready: lobby.ready().iter().copied().collect(),
+            countdown_ends_at: lobby.countdown_ends_at(),
         }
     }
 
     /// Apply received snapshot (GUEST ONLY)
     fn apply_snapshot(&mut self, snapshot_json: serde_json::Value) {
-        if let Ok(snapshot) = serde_json::from_value::<LobbySnapshot>(snapshot_json) {
-            tracing::info!("📥 GUEST: Applying snapshot for lobby '{}'", snapshot.name);
-
-            // Find host participant
-            let host_participant = snapshot
-                .participants
-                .iter()
-                .find(|p| p.is_host())
-                .cloned()
-                .expect("Snapshot must have a host");
-
-            // Create lobby with host
-            let create_cmd = DomainCommand::CreateLobbyWithHost {
-                lobby_id: snapshot.lobby_id,
-                lobby_name: snapshot.name,
-                host: host_participant,
-            };
-
-            let _ = self.domain.submit(create_cmd);
-            self.domain.poll();
-
-            // Add other participants
-            for participant in snapshot.participants.iter() {
-                if !participant.is_host() {
-                    let add_cmd = DomainCommand::AddParticipant {
-                        lobby_id: snapshot.lobby_id,
-                        participant: participant.clone(),
-                    };
-                    let _ = self.domain.submit(add_cmd);
-                }
+        let snapshot = match serde_json::from_value::<LobbySnapshot>(snapshot_json) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                tracing::error!("❌ GUEST: Can't read the host's snapshot: {}", e);
+                self.protocol_error = Some(e.to_string());
+                return;
             }
+        };
+
+        // Find host participant
+        let Some(host_participant) = snapshot.participants.iter().find(|p| p.is_host()).cloned()
+        else {
+            tracing::error!(
+                "❌ GUEST: Snapshot of lobby '{}' has no host",
+                snapshot.name
+            );
+            self.protocol_error =
+                Some(format!("Snapshot of lobby '{}' has no host", snapshot.name));
+            return;
+        };
+        self.protocol_error = None;
+        tracing::info!("📥 GUEST: Applying snapshot for lobby '{}'", snapshot.name);
+
+        // Create lobby with host
+        let create_cmd = DomainCommand::CreateLobbyWithHost {
+            lobby_id: snapshot.lobby_id,
+            lobby_name: snapshot.name,
+            host: host_participant,
+        };
+
+        // Add other participants
+        let add_cmds = snapshot
+            .participants
+            .into_iter()
+            .filter(|participant| !participant.is_host())
+            .map(|participant| DomainCommand::AddParticipant {
+                lobby_id: snapshot.lobby_id,
+                participant,
+            });
+        let queue_cmds =
+            snapshot
+                .activity_queue
+                .into_iter()
+                .map(|config| DomainCommand::QueueActivity {
+                    lobby_id: snapshot.lobby_id,
+                    config,
+                });
 
+        // After the queue: a countdown needs an activity to count down to
+        let ready_cmds = snapshot
+            .ready
+            .into_iter()
+            .map(|participant_id| DomainCommand::SetReady {
+                lobby_id: snapshot.lobby_id,
+                participant_id,
+                ready: true,
+            });
+        let countdown_cmd =
+            snapshot
+                .countdown_ends_at
+                .map(|ends_at| DomainCommand::StartCountdown {
+                    lobby_id: snapshot.lobby_id,
+                    requester_id: snapshot.host_id,
+                    ends_at,
+                });
+
+        // Poll after each command: a large lobby holds more participants
+        // than the domain queue
+        let commands = std::iter::once(create_cmd)
+            .chain(add_cmds)
+            .chain(queue_cmds)
+            .chain(ready_cmds)
+            .chain(countdown_cmd);
+        for cmd in commands {
+            let _ = self.domain.submit(cmd);
             self.domain.poll();
-
-            tracing::info!("✅ GUEST: Snapshot applied successfully");
         }
+
+        tracing::info!("✅ GUEST: Snapshot applied successfully");
     }
 
     /// Translate domain event to command for guests
     fn event_to_command(&self, event: CoreDomainEvent) -> Option<DomainCommand> {
         match event {
+            CoreDomainEvent::GuestLeft { participant_id, .. } => Some(DomainCommand::LeaveLobby {
+                lobby_id: self.lobby_id,
+                participant_id,
+            }),
+            CoreDomainEvent::ParticipationModeChanged {
+                participant_id,
+                new_mode,
+                ..
+            } => Some(DomainCommand::UpdateParticipantMode {
+                lobby_id: self.lobby_id,
+                participant_id,
+                new_mode,
+            }),
+            CoreDomainEvent::HostDelegated { from, to, .. } => Some(DomainCommand::DelegateHost {
+                lobby_id: self.lobby_id,
+                current_host_id: from,
+                new_host_id: to,
+            }),
+            CoreDomainEvent::CoHostChanged {
+                participant_id,
+                co_host,
+                changed_by,
+                ..
+            } => Some(DomainCommand::SetCoHost {
+                lobby_id: self.lobby_id,
+                host_id: changed_by,
+                participant_id,
+                co_host,
+            }),
+            CoreDomainEvent::LobbySettingsChanged {
+                settings,
+                changed_by,
+                ..
+            } => Some(DomainCommand::UpdateLobbySettings {
+                lobby_id: self.lobby_id,
+                host_id: changed_by,
+                settings,
+            }),
             CoreDomainEvent::GuestJoined { participant, .. } => {
                 Some(DomainCommand::AddParticipant {
                     lobby_id: self.lobby_id,
                     participant,
                 })
             }
-            CoreDomainEvent::RunStarted { run_id, config, .. } => {
+            CoreDomainEvent::RunStarted {
+                run_id,
+                config,
+                started_at_ms,
+                ..
+            } => {
                 let required_submitters = self
                     .domain
                     .event_loop()
@@ -291,12 +559,64 @@ impl<C: NetworkConnection> SessionLoopV2<C> {
                     run_id,
                     config,
                     required_submitters,
+                    started_at_ms,
                 })
             }
             CoreDomainEvent::ActivityQueued { config, .. } => Some(DomainCommand::QueueActivity {
                 lobby_id: self.lobby_id,
                 config,
             }),
+            CoreDomainEvent::QueuedActivityMoved {
+                activity_id,
+                to_index,
+                moved_by,
+                ..
+            } => Some(DomainCommand::MoveQueuedActivity {
+                lobby_id: self.lobby_id,
+                activity_id,
+                to_index,
+                requester_id: moved_by,
+            }),
+            CoreDomainEvent::ReadyChanged {
+                participant_id,
+                ready,
+                ..
+            } => Some(DomainCommand::SetReady {
+                lobby_id: self.lobby_id,
+                participant_id,
+                ready,
+            }),
+            CoreDomainEvent::CountdownStarted {
+                ends_at,
+                started_by,
+                ..
+            } => Some(DomainCommand::StartCountdown {
+                lobby_id: self.lobby_id,
+                requester_id: started_by,
+                ends_at,
+            }),
+            CoreDomainEvent::GuestKicked {
+                participant_id,
+                kicked_by,
+                banned,
+                ..
+            } => Some(DomainCommand::KickGuest {
+                lobby_id: self.lobby_id,
+                host_id: kicked_by,
+                guest_id: participant_id,
+                ban: banned,
+            }),
+            CoreDomainEvent::AvatarChanged {
+                participant_id,
+                avatar,
+                changed_by,
+                ..
+            } => Some(DomainCommand::SetAvatar {
+                lobby_id: self.lobby_id,
+                participant_id,
+                requester_id: changed_by,
+                avatar,
+            }),
             CoreDomainEvent::ResultSubmitted { run_id, result, .. } => {
                 Some(DomainCommand::SubmitResult {
                     lobby_id: self.lobby_id,
@@ -304,18 +624,80 @@ impl<C: NetworkConnection> SessionLoopV2<C> {
                     result,
                 })
             }
+            CoreDomainEvent::ChatMessageSent { message, .. } => {
+                Some(DomainCommand::AddChatMessage {
+                    lobby_id: self.lobby_id,
+                    message,
+                })
+            }
+            CoreDomainEvent::SubmitterRemoved {
+                run_id,
+                participant_id,
+                ..
+            } => Some(DomainCommand::RemoveSubmitter {
+                lobby_id: self.lobby_id,
+                run_id,
+                participant_id,
+            }),
+            // Carries the last result, which has no ResultSubmitted of its own
             CoreDomainEvent::RunEnded {
-                run_id: _,
-                results: _,
+                run_id,
+                status,
+                results,
                 ..
-            } => {
-                // Guests auto-complete when they process all SubmitResult commands
-                None // Guest will auto-complete when they receive all results
-            }
+            } => Some(DomainCommand::SyncRunEnded {
+                lobby_id: self.lobby_id,
+                run_id,
+                status,
+                results,
+            }),
             _ => None,
         }
     }
 
+    /// Commands the host failed to execute since the last call
+    ///
+    /// The host sees every failure, a guest the commands of its own that the
+    /// host refused.
+    pub fn take_failed_commands(&mut self) -> Vec<FailedCommand> {
+        std::mem::take(&mut self.failed_commands)
+    }
+
+    fn record_event(&mut self, event: &CoreDomainEvent) {
+        if self.recent_events.len() == RECENT_EVENTS_LIMIT {
+            self.recent_events.pop_front();
+        }
+        self.recent_events.push_back(event.clone());
+    }
+
+    /// Domain events applied since the last call, oldest first (at most
+    /// [`RECENT_EVENTS_LIMIT`])
+    pub fn take_events(&mut self) -> Vec<CoreDomainEvent> {
+        self.recent_events.drain(..).collect()
+    }
+
+    /// Receive the domain's events matching `filter` independently of
+    /// `take_events`, e.g. one subscription per view
+    pub fn subscribe(&mut self, filter: EventFilter) -> EventSubscription {
+        self.domain.subscribe(filter)
+    }
+
+    /// Work waiting in the domain and in the loop itself
+    pub fn queue_depths(&self) -> QueueDepths {
+        QueueDepths {
+            domain_commands: self.domain.pending_commands(),
+            domain_events: self.domain.pending_events(),
+            failed_commands: self.failed_commands.len(),
+            recent_events: self.recent_events.len(),
+        }
+    }
+
+    /// Why the host's last snapshot could not be read, most likely because
+    /// it runs an incompatible version
+    pub fn protocol_error(&self) -> Option<&str> {
+        self.protocol_error.as_deref()
+    }
+
     /// Get current lobby
     pub fn get_lobby(&self) -> Option<&Lobby> {
         self.domain.event_loop().get_lobby(&self.lobby_id)
@@ -329,10 +711,41 @@ impl<C: NetworkConnection> SessionLoopV2<C> {
         self.is_host
     }
 
+    /// Our peer ID, once signalling assigned one
+    pub fn local_peer_id(&self) -> Option<crate::domain::PeerId> {
+        self.transport.local_peer_id()
+    }
+
     pub fn connected_peers(&self) -> Vec<crate::domain::PeerId> {
         self.transport.connected_peers()
     }
 
+    /// Round trip, last seen and message counts of every other peer
+    pub fn peer_stats(&self) -> Vec<PeerStats> {
+        self.transport.peer_stats()
+    }
+
+    /// Is the connection currently reconnecting to signalling?
+    pub fn is_reconnecting(&self) -> bool {
+        self.transport.is_reconnecting()
+    }
+
+    /// How far the host's clock is ahead of ours, in ms (see
+    /// [`P2PTransport::clock_offset_ms`])
+    pub fn clock_offset_ms(&self) -> i64 {
+        self.transport.clock_offset_ms()
+    }
+
+    /// Now on the host's clock (Unix ms); use it for countdowns and deadlines
+    pub fn session_time_ms(&self) -> u64 {
+        self.transport.session_time_ms()
+    }
+
+    /// The local clock the session runs on (see [`P2PTransport::clock`])
+    pub fn clock(&self) -> &SharedClock {
+        self.transport.clock()
+    }
+
     pub fn get_active_run(&self) -> Option<&konnekt_session_core::ActivityRun> {
         let run_id = self.get_lobby()?.active_run_id()?;
         self.domain.event_loop().get_run(&run_id)
@@ -341,11 +754,23 @@ impl<C: NetworkConnection> SessionLoopV2<C> {
     pub fn get_run(&self, run_id: &Uuid) -> Option<&konnekt_session_core::ActivityRun> {
         self.domain.event_loop().get_run(run_id)
     }
+
+    /// Every run of the lobby, in the order they started
+    pub fn runs(&self) -> impl Iterator<Item = &konnekt_session_core::ActivityRun> {
+        self.domain.event_loop().runs_for_lobby(self.lobby_id)
+    }
 }
 
 // Type alias for production use
 pub type MatchboxSessionLoop = SessionLoopV2<crate::infrastructure::connection::MatchboxConnection>;
 
+/// Presence signal of one participant
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct PresenceUpdate {
+    participant_id: Uuid,
+    presence: Option<Presence>,
+}
+
 /// Snapshot of lobby state (for sync)
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct LobbySnapshot {
@@ -353,4 +778,41 @@ struct LobbySnapshot {
     name: String,
     host_id: Uuid,
     participants: Vec<konnekt_session_core::Participant>,
+    #[serde(default)]
+    activity_queue: Vec<konnekt_session_core::domain::ActivityConfig>,
+    /// Participants who said they are ready
+    #[serde(default)]
+    ready: Vec<Uuid>,
+    /// Wall clock time (Unix ms) the countdown to the next activity ends at
+    #[serde(default)]
+    countdown_ends_at: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::loopback::LoopbackNetwork;
+    use konnekt_session_core::Participant;
+
+    #[test]
+    fn test_snapshot_without_host_is_a_protocol_error() {
+        let network = LoopbackNetwork::new();
+        let transport = P2PTransport::new_guest(network.connect(), 100);
+        let mut guest =
+            SessionLoopV2::new(DomainLoop::new(10, 100), transport, false, Uuid::new_v4());
+        let snapshot = LobbySnapshot {
+            lobby_id: Uuid::new_v4(),
+            name: "Hostless".to_string(),
+            host_id: Uuid::new_v4(),
+            participants: vec![Participant::new_guest("Bob".to_string()).unwrap()],
+            activity_queue: Vec::new(),
+            ready: Vec::new(),
+            countdown_ends_at: None,
+        };
+
+        guest.apply_snapshot(serde_json::to_value(snapshot).unwrap());
+
+        assert!(guest.protocol_error().unwrap().contains("no host"));
+        assert!(guest.get_lobby().is_none());
+    }
 }
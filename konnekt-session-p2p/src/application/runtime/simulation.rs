@@ -0,0 +1,441 @@
+use crate::application::runtime::{DEFAULT_CHECKSUM_INTERVAL, P2PLoopBuilder, SessionLoop};
+use crate::domain::{PeerId, SessionId, TestClock};
+use crate::infrastructure::error::Result;
+use crate::infrastructure::loopback::{LoopbackConnection, LoopbackNetwork};
+use crate::infrastructure::network_sim::{NetworkConditions, NetworkSimulator, SimRng};
+use instant::Duration;
+use konnekt_session_core::DomainCommand;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// A scripted disturbance, applied at the start of a tick
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fault {
+    /// Change link quality
+    Conditions(NetworkConditions),
+    /// Cut guest `n` (0-based) off from everyone else
+    PartitionGuest(usize),
+    /// Remove all partitions
+    Heal,
+}
+
+/// Parameters of a simulated session
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub seed: u64,
+    pub guests: usize,
+    /// Length of the scripted phase (workload + faults)
+    pub ticks: u64,
+    /// Ticks allowed afterwards (faults cleared) for the session to converge
+    pub settle_ticks: u64,
+    /// Virtual time per tick
+    pub tick_duration: Duration,
+    /// Probability per tick that a random participant toggles its mode
+    pub action_rate: f64,
+    /// Link quality at the start
+    pub conditions: NetworkConditions,
+    pub checksum_interval: Duration,
+    faults: Vec<(u64, Fault)>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            guests: 3,
+            ticks: 500,
+            settle_ticks: 1000,
+            tick_duration: Duration::from_millis(50),
+            action_rate: 0.05,
+            conditions: NetworkConditions::perfect(),
+            checksum_interval: DEFAULT_CHECKSUM_INTERVAL,
+            faults: Vec::new(),
+        }
+    }
+}
+
+impl SimulationConfig {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_guests(mut self, guests: usize) -> Self {
+        self.guests = guests;
+        self
+    }
+
+    pub fn with_ticks(mut self, ticks: u64) -> Self {
+        self.ticks = ticks;
+        self
+    }
+
+    pub fn with_settle_ticks(mut self, ticks: u64) -> Self {
+        self.settle_ticks = ticks;
+        self
+    }
+
+    pub fn with_tick_duration(mut self, duration: Duration) -> Self {
+        self.tick_duration = duration;
+        self
+    }
+
+    pub fn with_action_rate(mut self, rate: f64) -> Self {
+        self.action_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_conditions(mut self, conditions: NetworkConditions) -> Self {
+        self.conditions = conditions;
+        self
+    }
+
+    pub fn with_checksum_interval(mut self, interval: Duration) -> Self {
+        self.checksum_interval = interval;
+        self
+    }
+
+    /// Inject `fault` at the start of tick `at`
+    pub fn with_fault(mut self, at: u64, fault: Fault) -> Self {
+        self.faults.push((at, fault));
+        self
+    }
+
+    /// Add `count` seed-derived partitions, each healed after a while
+    pub fn with_random_faults(mut self, count: usize) -> Self {
+        if self.guests == 0 || self.ticks < 2 {
+            return self;
+        }
+
+        let mut rng = SimRng::new(self.seed.rotate_left(17));
+        let span = self.ticks as u32;
+        for _ in 0..count {
+            let start = rng.below(span - 1) as u64;
+            let end = start + 1 + rng.below(span / 4 + 1) as u64;
+            let guest = rng.below(self.guests as u32) as usize;
+
+            self.faults.push((start, Fault::PartitionGuest(guest)));
+            self.faults.push((end.min(self.ticks), Fault::Heal));
+        }
+        self
+    }
+
+    pub fn faults(&self) -> &[(u64, Fault)] {
+        &self.faults
+    }
+}
+
+/// Outcome of a simulation run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SimulationReport {
+    pub seed: u64,
+    /// Ticks run (scripted phase + settling)
+    pub ticks: u64,
+    /// First settling tick at which every guest matched the host
+    pub converged_at: Option<u64>,
+    /// Guests (0-based) whose state differs from the host's at the end
+    pub diverged_guests: Vec<usize>,
+    /// Participants in the host's lobby at the end
+    pub participants: usize,
+    pub messages_dropped: u64,
+    pub actions: u64,
+}
+
+impl SimulationReport {
+    pub fn converged(&self) -> bool {
+        self.converged_at.is_some()
+    }
+}
+
+/// Deterministic, seed-driven run of a host and N guests
+///
+/// All peers share one thread, a `LoopbackNetwork` driven by a
+/// `NetworkSimulator`, and a `TestClock` advanced by `tick_duration` per
+/// tick — so timeouts and checksums fire without waiting. Peer IDs, the
+/// workload (joins, mode toggles), the fault script and the network
+/// schedule all derive from the seed; a failing seed can be replayed.
+///
+/// After the scripted phase all faults are cleared and the run continues
+/// until every guest holds the host's lobby state (see `SimulationReport`).
+pub struct Simulation {
+    config: SimulationConfig,
+    network: LoopbackNetwork,
+    host: SessionLoop<LoopbackConnection>,
+    guests: Vec<SessionLoop<LoopbackConnection>>,
+    /// Guests that submitted `JoinLobby`
+    joined: Vec<bool>,
+    rng: SimRng,
+    clock: TestClock,
+    tick: u64,
+    actions: u64,
+}
+
+impl Simulation {
+    pub fn new(config: SimulationConfig) -> Result<Self> {
+        let clock = TestClock::new();
+        let seed = config.seed;
+        let peer_id =
+            |index: u64| PeerId::new(matchbox_socket::PeerId(Uuid::from_u64_pair(seed, index)));
+
+        let network = LoopbackNetwork::with_simulator(
+            NetworkSimulator::new(seed).with_conditions(config.conditions),
+        );
+        let session_id = SessionId::from_uuid(Uuid::from_u64_pair(seed, u64::MAX));
+
+        let (host, _) = P2PLoopBuilder::new()
+            .clock(clock.shared())
+            .checksum_interval(Some(config.checksum_interval))
+            .build_session_host_with_connection(
+                network.connect_as(peer_id(0)),
+                session_id.clone(),
+                "Simulation".to_string(),
+                "Host".to_string(),
+            )?;
+
+        let guests = (1..=config.guests as u64)
+            .map(|index| {
+                P2PLoopBuilder::new()
+                    .clock(clock.shared())
+                    .build_session_guest_with_connection(
+                        network.connect_as(peer_id(index)),
+                        session_id.clone(),
+                    )
+                    .0
+            })
+            .collect();
+
+        tracing::info!(seed, guests = config.guests, "🎲 Simulation started");
+
+        Ok(Self {
+            joined: vec![false; config.guests],
+            rng: SimRng::new(seed.rotate_left(32)),
+            config,
+            network,
+            host,
+            guests,
+            clock,
+            tick: 0,
+            actions: 0,
+        })
+    }
+
+    pub fn host(&self) -> &SessionLoop<LoopbackConnection> {
+        &self.host
+    }
+
+    pub fn guests(&self) -> &[SessionLoop<LoopbackConnection>] {
+        &self.guests
+    }
+
+    pub fn network(&self) -> &LoopbackNetwork {
+        &self.network
+    }
+
+    /// Current tick
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Guests (0-based) whose lobby state differs from the host's
+    pub fn diverged_guests(&self) -> Vec<usize> {
+        let expected = self.host.state_checksum();
+
+        self.guests
+            .iter()
+            .enumerate()
+            .filter(|(_, guest)| expected.is_none() || guest.state_checksum() != expected)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Run one tick of the scripted phase: faults, workload, polls, network
+    pub fn step(&mut self) {
+        let faults: Vec<_> = self
+            .config
+            .faults
+            .iter()
+            .filter(|(at, _)| *at == self.tick)
+            .map(|(_, fault)| *fault)
+            .collect();
+        for fault in faults {
+            self.apply(fault);
+        }
+
+        self.join_ready_guests();
+        if self.rng.chance(self.config.action_rate) {
+            self.random_action();
+        }
+
+        self.advance();
+    }
+
+    /// Run the script, clear all faults, then settle until converged
+    pub fn run(mut self) -> SimulationReport {
+        while self.tick < self.config.ticks {
+            self.step();
+        }
+
+        self.apply(Fault::Heal);
+        self.apply(Fault::Conditions(NetworkConditions::perfect()));
+
+        let mut converged_at = None;
+        for _ in 0..self.config.settle_ticks {
+            self.join_ready_guests();
+            self.advance();
+
+            if self.diverged_guests().is_empty() {
+                converged_at = Some(self.tick);
+                break;
+            }
+        }
+
+        let report = SimulationReport {
+            seed: self.config.seed,
+            ticks: self.tick,
+            converged_at,
+            diverged_guests: self.diverged_guests(),
+            participants: self
+                .host
+                .get_lobby()
+                .map_or(0, |lobby| lobby.participants().len()),
+            messages_dropped: self.network.dropped(),
+            actions: self.actions,
+        };
+
+        if report.converged() {
+            tracing::info!(?report, "🎲 Simulation converged");
+        } else {
+            tracing::warn!(?report, "🎲 Simulation diverged");
+        }
+        report
+    }
+
+    fn apply(&mut self, fault: Fault) {
+        tracing::debug!(tick = self.tick, ?fault, "🎲 Injecting fault");
+
+        match fault {
+            Fault::Conditions(conditions) => self.network.set_conditions(conditions),
+            Fault::PartitionGuest(index) => {
+                let Some(guest) = self.guests.get(index).and_then(|g| g.local_peer_id()) else {
+                    return;
+                };
+                let others: Vec<_> = std::iter::once(&self.host)
+                    .chain(self.guests.iter())
+                    .filter_map(|session| session.local_peer_id())
+                    .filter(|peer| *peer != guest)
+                    .collect();
+                self.network.partition(&[guest], &others);
+            }
+            Fault::Heal => self.network.heal(),
+        }
+    }
+
+    /// Guests join once they received the lobby
+    fn join_ready_guests(&mut self) {
+        let lobby_id = self.host.lobby_id();
+
+        for (index, guest) in self.guests.iter_mut().enumerate() {
+            if self.joined[index] || guest.get_lobby().is_none() {
+                continue;
+            }
+
+            let cmd = DomainCommand::JoinLobby {
+                lobby_id,
+                guest_name: format!("Guest{}", index + 1),
+            };
+            if guest.submit_command(cmd).is_ok() {
+                self.joined[index] = true;
+            }
+        }
+    }
+
+    /// A random participant toggles its own participation mode
+    fn random_action(&mut self) {
+        let lobby_id = self.host.lobby_id();
+        let pick = self.rng.below(self.guests.len() as u32 + 1) as usize;
+
+        let (session, participant_id) = if pick == 0 {
+            let host_id = self.host.get_lobby().map(|lobby| lobby.host_id());
+            (&mut self.host, host_id)
+        } else {
+            let guest = &mut self.guests[pick - 1];
+            let participant_id = guest.p2p().local_participant_id();
+            (guest, participant_id)
+        };
+        let Some(participant_id) = participant_id else {
+            return;
+        };
+
+        let cmd = DomainCommand::ToggleParticipationMode {
+            lobby_id,
+            participant_id,
+            requester_id: participant_id,
+        };
+        if session.submit_command(cmd).is_ok() {
+            self.actions += 1;
+        }
+    }
+
+    /// Poll every peer, deliver due messages and advance virtual time
+    fn advance(&mut self) {
+        self.host.poll();
+        for guest in self.guests.iter_mut() {
+            guest.poll();
+        }
+
+        self.network.tick();
+        self.clock.advance(self.config.tick_duration);
+        self.tick += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_perfect_network_converges() {
+        let report = Simulation::new(SimulationConfig::new(1).with_ticks(200))
+            .unwrap()
+            .run();
+
+        assert!(report.converged(), "{report:?}");
+        assert_eq!(report.participants, 4);
+        assert_eq!(report.messages_dropped, 0);
+    }
+
+    #[test]
+    fn test_random_faults_are_seeded() {
+        let a = SimulationConfig::new(9).with_random_faults(3);
+        let b = SimulationConfig::new(9).with_random_faults(3);
+        let c = SimulationConfig::new(10).with_random_faults(3);
+
+        assert_eq!(a.faults(), b.faults());
+        assert_ne!(a.faults(), c.faults());
+        assert_eq!(a.faults().len(), 6);
+    }
+
+    #[test]
+    fn test_converges_after_loss_and_partitions() {
+        for seed in 0..5 {
+            let config = SimulationConfig::new(seed)
+                .with_ticks(400)
+                .with_action_rate(0.1)
+                .with_conditions(
+                    NetworkConditions::perfect()
+                        .with_latency(2)
+                        .with_jitter(2)
+                        .with_loss(0.1)
+                        .with_reorder(0.2),
+                )
+                .with_random_faults(2);
+
+            let report = Simulation::new(config).unwrap().run();
+
+            assert!(report.converged(), "seed {seed} diverged: {report:?}");
+            assert!(report.messages_dropped > 0);
+        }
+    }
+}
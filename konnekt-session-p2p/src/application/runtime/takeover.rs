@@ -0,0 +1,40 @@
+use instant::{Duration, Instant};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Where a guest stands while the host is missing (render a countdown or
+/// the accept/decline prompt from it)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostTakeover {
+    /// The host went quiet; it is given up on when `remaining` runs out
+    GracePeriod { remaining: Duration },
+    /// The host timed out and `successor` was elected (`None`: nobody is
+    /// left to take over, the lobby waits for the host)
+    Electing { successor: Option<Uuid> },
+    /// We were elected: accept or decline within `remaining`, or we accept
+    Offered { remaining: Duration },
+}
+
+/// Election after the host timed out (GUEST ONLY)
+#[derive(Debug, Default)]
+pub(crate) struct Succession {
+    /// The host that timed out
+    pub vacant: Option<Uuid>,
+    pub successor: Option<Uuid>,
+    /// Guests that declined to take over (skipped by the election)
+    pub declined: HashSet<Uuid>,
+    /// Deadline of our pending offer
+    pub offer_expires: Option<Instant>,
+}
+
+impl Succession {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The offer is still open; `None` when there is none
+    pub fn offer_remaining(&self, now: Instant) -> Option<Duration> {
+        self.offer_expires
+            .map(|expires| expires.saturating_duration_since(now))
+    }
+}
@@ -0,0 +1,367 @@
+use crate::domain::LobbyEvent;
+use konnekt_session_core::domain::{ActivityConfig, ActivityResult, ActivityRun, ActivityRunId};
+use konnekt_session_core::{ChatMessage, DomainCommand, Lobby, LobbySettings, Participant};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Items per streamed snapshot message; larger lobbies are streamed
+pub const DEFAULT_SNAPSHOT_PAGE_SIZE: usize = 50;
+
+/// One message of a streamed full sync (large lobbies)
+///
+/// A lobby is sent as a `Header`, its participants page by page, its settings
+/// (unless default), the activity queue, the ready check (if any), the chat
+/// history, the active run and its results, and finally the host's event log.
+/// Guests apply each part as it arrives, so the UI fills in progressively.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "part", rename_all = "snake_case")]
+pub enum SnapshotPart {
+    /// Lobby identity and host (always first)
+    Header {
+        lobby_id: Uuid,
+        name: String,
+        host: Participant,
+        as_of_sequence: u64,
+    },
+    /// A page of guests
+    Participants { participants: Vec<Participant> },
+    /// Lobby settings and co-hosts, as changed by `host_id`
+    Settings {
+        host_id: Uuid,
+        settings: LobbySettings,
+        co_hosts: Vec<Uuid>,
+    },
+    /// A page of the activity queue
+    Activities { activities: Vec<ActivityConfig> },
+    /// Who is ready, and the countdown `host_id` started (Unix ms)
+    ReadyCheck {
+        host_id: Uuid,
+        ready: Vec<Uuid>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        countdown_ends_at: Option<u64>,
+    },
+    /// A page of the chat history (oldest first)
+    Chat { messages: Vec<ChatMessage> },
+    /// The run in progress
+    ActiveRun {
+        run_id: ActivityRunId,
+        config: ActivityConfig,
+        required_submitters: Vec<Uuid>,
+        /// When the host started the run, on its clock (Unix ms)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        started_at_ms: Option<u64>,
+    },
+    /// A page of results submitted to the active run
+    Results {
+        run_id: ActivityRunId,
+        results: Vec<ActivityResult>,
+    },
+    /// A page of the host's event log (always last)
+    Events { events: Vec<LobbyEvent> },
+}
+
+impl SnapshotPart {
+    /// Split `lobby` and its active `run` into parts of at most `page_size` items
+    ///
+    /// The event log pages are added by the sync manager, which owns the log.
+    /// `None` if the lobby has no host participant (e.g. mid-takeover).
+    pub fn split(
+        lobby: &Lobby,
+        run: Option<&ActivityRun>,
+        as_of_sequence: u64,
+        page_size: usize,
+    ) -> Option<Vec<Self>> {
+        let page_size = page_size.max(1);
+        let host = lobby.host().cloned()?;
+        let guests: Vec<Participant> = lobby
+            .participants()
+            .values()
+            .filter(|p| !p.is_host())
+            .cloned()
+            .collect();
+
+        let mut parts = vec![SnapshotPart::Header {
+            lobby_id: lobby.id(),
+            name: lobby.name().to_string(),
+            host,
+            as_of_sequence,
+        }];
+        parts.extend(
+            guests
+                .chunks(page_size)
+                .map(|page| SnapshotPart::Participants {
+                    participants: page.to_vec(),
+                }),
+        );
+        if *lobby.settings() != LobbySettings::default() || !lobby.co_hosts().is_empty() {
+            let mut co_hosts: Vec<Uuid> = lobby.co_hosts().iter().copied().collect();
+            co_hosts.sort();
+            parts.push(SnapshotPart::Settings {
+                host_id: lobby.host_id(),
+                settings: *lobby.settings(),
+                co_hosts,
+            });
+        }
+        parts.extend(lobby.activity_queue().chunks(page_size).map(|page| {
+            SnapshotPart::Activities {
+                activities: page.to_vec(),
+            }
+        }));
+        if !lobby.ready().is_empty() || lobby.countdown_ends_at().is_some() {
+            let mut ready: Vec<Uuid> = lobby.ready().iter().copied().collect();
+            ready.sort();
+            parts.push(SnapshotPart::ReadyCheck {
+                host_id: lobby.host_id(),
+                ready,
+                countdown_ends_at: lobby.countdown_ends_at(),
+            });
+        }
+
+        parts.extend(
+            lobby
+                .chat_messages()
+                .chunks(page_size)
+                .map(|page| SnapshotPart::Chat {
+                    messages: page.to_vec(),
+                }),
+        );
+
+        if let Some(run) = run {
+            parts.push(SnapshotPart::ActiveRun {
+                run_id: run.id(),
+                config: run.config().clone(),
+                required_submitters: run.required_submitters().iter().copied().collect(),
+                started_at_ms: run.started_at_ms(),
+            });
+            let results: Vec<ActivityResult> = run.results().values().cloned().collect();
+            parts.extend(results.chunks(page_size).map(|page| SnapshotPart::Results {
+                run_id: run.id(),
+                results: page.to_vec(),
+            }));
+        }
+
+        Some(parts)
+    }
+
+    /// Whether a full sync of `lobby` should be streamed rather than sent whole
+    ///
+    /// Always true while a run is active: whole snapshots don't carry runs.
+    pub fn should_stream(lobby: &Lobby, run: Option<&ActivityRun>, page_size: usize) -> bool {
+        run.is_some()
+            || lobby.participants().len() > page_size
+            || lobby.activity_queue().len() > page_size
+    }
+
+    /// Domain commands that apply this part to `lobby_id` on a guest
+    ///
+    /// `Events` yields nothing: replaying the log is up to the caller, which
+    /// knows the snapshot's `as_of_sequence`.
+    pub fn to_commands(&self, lobby_id: Uuid) -> Vec<DomainCommand> {
+        match self {
+            SnapshotPart::Header { name, host, .. } => vec![DomainCommand::CreateLobbyWithHost {
+                lobby_id,
+                lobby_name: name.clone(),
+                host: host.clone(),
+            }],
+            SnapshotPart::Participants { participants } => participants
+                .iter()
+                .map(|participant| DomainCommand::AddParticipant {
+                    lobby_id,
+                    participant: participant.clone(),
+                })
+                .collect(),
+            SnapshotPart::Settings {
+                host_id,
+                settings,
+                co_hosts,
+            } => std::iter::once(DomainCommand::UpdateLobbySettings {
+                lobby_id,
+                host_id: *host_id,
+                settings: *settings,
+            })
+            .chain(
+                co_hosts
+                    .iter()
+                    .map(|participant_id| DomainCommand::SetCoHost {
+                        lobby_id,
+                        host_id: *host_id,
+                        participant_id: *participant_id,
+                        co_host: true,
+                    }),
+            )
+            .collect(),
+            SnapshotPart::Activities { activities } => activities
+                .iter()
+                .map(|config| DomainCommand::QueueActivity {
+                    lobby_id,
+                    config: config.clone(),
+                })
+                .collect(),
+            SnapshotPart::ReadyCheck {
+                host_id,
+                ready,
+                countdown_ends_at,
+            } => ready
+                .iter()
+                .map(|participant_id| DomainCommand::SetReady {
+                    lobby_id,
+                    participant_id: *participant_id,
+                    ready: true,
+                })
+                .chain(
+                    countdown_ends_at.map(|ends_at| DomainCommand::StartCountdown {
+                        lobby_id,
+                        requester_id: *host_id,
+                        ends_at,
+                    }),
+                )
+                .collect(),
+            SnapshotPart::Chat { messages } => messages
+                .iter()
+                .map(|message| DomainCommand::AddChatMessage {
+                    lobby_id,
+                    message: message.clone(),
+                })
+                .collect(),
+            SnapshotPart::ActiveRun {
+                run_id,
+                config,
+                required_submitters,
+                started_at_ms,
+            } => vec![DomainCommand::SyncRunStarted {
+                lobby_id,
+                run_id: *run_id,
+                config: config.clone(),
+                required_submitters: required_submitters.clone(),
+                started_at_ms: *started_at_ms,
+            }],
+            SnapshotPart::Results { run_id, results } => results
+                .iter()
+                .map(|result| DomainCommand::SubmitResult {
+                    lobby_id,
+                    run_id: *run_id,
+                    result: result.clone(),
+                })
+                .collect(),
+            SnapshotPart::Events { .. } => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_core::DomainEventLoop;
+
+    fn lobby_with_guests(count: usize) -> Lobby {
+        let host = Participant::new_host("Host".to_string()).unwrap();
+        let mut lobby = Lobby::new("Big Lobby".to_string(), host).unwrap();
+        for i in 0..count {
+            lobby
+                .add_guest(Participant::new_guest(format!("Guest{i}")).unwrap())
+                .unwrap();
+        }
+        lobby
+    }
+
+    #[test]
+    fn test_split_pages_participants() {
+        let lobby = lobby_with_guests(120);
+
+        let parts = SnapshotPart::split(&lobby, None, 7, 50).unwrap();
+
+        assert!(matches!(
+            &parts[0],
+            SnapshotPart::Header {
+                as_of_sequence: 7,
+                ..
+            }
+        ));
+        let pages: Vec<usize> = parts[1..]
+            .iter()
+            .map(|part| match part {
+                SnapshotPart::Participants { participants } => participants.len(),
+                other => panic!("Unexpected part {other:?}"),
+            })
+            .collect();
+        assert_eq!(pages, vec![50, 50, 20]);
+        assert!(SnapshotPart::should_stream(&lobby, None, 50));
+        assert!(!SnapshotPart::should_stream(&lobby, None, 200));
+    }
+
+    #[test]
+    fn test_parts_rebuild_the_lobby() {
+        let lobby = lobby_with_guests(30);
+        let mut replica = DomainEventLoop::new();
+
+        for part in SnapshotPart::split(&lobby, None, 0, 8).unwrap() {
+            for command in part.to_commands(lobby.id()) {
+                replica.handle_command(command);
+            }
+        }
+
+        let rebuilt = replica.get_lobby(&lobby.id()).unwrap();
+        assert_eq!(rebuilt.host_id(), lobby.host_id());
+        assert_eq!(rebuilt.participants().len(), 31);
+    }
+
+    #[test]
+    fn test_parts_carry_settings_and_co_hosts() {
+        let mut lobby = lobby_with_guests(3);
+        let host_id = lobby.host_id();
+        let guest_id = lobby.next_host_candidate().unwrap();
+        let settings = LobbySettings {
+            max_participants: Some(10),
+            ..LobbySettings::default()
+        };
+        lobby.update_settings(settings, host_id).unwrap();
+        lobby.set_co_host(guest_id, host_id, true).unwrap();
+
+        let mut replica = DomainEventLoop::new();
+        for part in SnapshotPart::split(&lobby, None, 0, 2).unwrap() {
+            for command in part.to_commands(lobby.id()) {
+                replica.handle_command(command);
+            }
+        }
+
+        let rebuilt = replica.get_lobby(&lobby.id()).unwrap();
+        assert_eq!(rebuilt.settings(), &settings);
+        assert!(rebuilt.is_co_host(guest_id));
+    }
+
+    #[test]
+    fn test_parts_carry_ready_check_and_countdown() {
+        let mut lobby = lobby_with_guests(3);
+        let host_id = lobby.host_id();
+        let guest_id = lobby.next_host_candidate().unwrap();
+        lobby
+            .queue_activity(ActivityConfig::new(
+                "echo".to_string(),
+                "Echo".to_string(),
+                serde_json::json!({}),
+            ))
+            .unwrap();
+        lobby.set_ready(guest_id, true).unwrap();
+        lobby.start_countdown(host_id, 1_000).unwrap();
+
+        let mut replica = DomainEventLoop::new();
+        for part in SnapshotPart::split(&lobby, None, 0, 2).unwrap() {
+            for command in part.to_commands(lobby.id()) {
+                replica.handle_command(command);
+            }
+        }
+
+        let rebuilt = replica.get_lobby(&lobby.id()).unwrap();
+        assert!(rebuilt.is_ready(guest_id));
+        assert_eq!(rebuilt.countdown_ends_at(), Some(1_000));
+    }
+
+    #[test]
+    fn test_split_without_host_is_none() {
+        let mut lobby = lobby_with_guests(3);
+        let host_id = lobby.host_id();
+        lobby.participants_mut().remove(&host_id);
+
+        assert!(SnapshotPart::split(&lobby, None, 0, 2).is_none());
+    }
+}
@@ -1,15 +1,28 @@
-use crate::domain::{DomainEvent, EventLog, LobbyEvent, PeerId};
-use konnekt_session_core::DomainCommand;
+use crate::application::SnapshotPart;
+use crate::application::runtime::MessagePriority;
+use crate::domain::{
+    Capabilities, CorrelationId, CrdtOp, DomainEvent, EventLog, LobbyCrdtState, LobbyEvent, PeerId,
+    Presence, ResumeToken, SharedClock, SystemClock, TimeoutConfig,
+};
+use konnekt_session_core::{DomainCommand, ParticipantAvatar, Timestamp};
 use std::collections::HashMap;
 use tracing::{debug, info, instrument, warn};
 use uuid::Uuid;
 
+/// Out-of-order events a guest buffers before dropping more (the state
+/// checksum notices the gap and triggers a full sync)
+const MAX_PENDING_EVENTS: usize = 1024;
+
 /// Messages sent over the P2P network for event synchronization
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum SyncMessage {
     /// Guest → Host: Execute this domain command
-    CommandRequest { command: DomainCommand },
+    CommandRequest {
+        command: DomainCommand,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        correlation_id: Option<CorrelationId>,
+    },
 
     /// Host → All: Domain event happened (with sequence number)
     EventBroadcast { event: LobbyEvent },
@@ -21,7 +34,148 @@ pub enum SyncMessage {
     FullSyncResponse {
         snapshot: LobbySnapshot,
         events: Vec<LobbyEvent>,
+        #[serde(default)]
+        epoch: u64,
+    },
+
+    /// Host → Guest: Part `index` of `total` of a streamed full sync
+    /// (large lobbies; replaces `FullSyncResponse`)
+    SnapshotChunk {
+        index: u32,
+        total: u32,
+        part: SnapshotPart,
+        #[serde(default)]
+        epoch: u64,
+    },
+
+    /// Host → All: Peers currently connected to the host (topology detection,
+    /// host election)
+    PeerRoster {
+        peers: Vec<RosterEntry>,
+        #[serde(default)]
+        epoch: u64,
+    },
+
+    /// Host → Guest: You are this participant; keep the token to resume later
+    JoinAccepted {
+        participant_id: Uuid,
+        resume_token: ResumeToken,
+    },
+
+    /// Guest → Host: I reconnected, re-bind me to my participant
+    ResumeSession {
+        lobby_id: Uuid,
+        resume_token: ResumeToken,
+    },
+
+    /// Host → Guest: Unknown resume token
+    ResumeRejected,
+
+    /// Host → All: Hash of the lobby state after event `sequence`
+    /// (anti-entropy; guests resync on mismatch)
+    StateChecksum {
+        sequence: u64,
+        checksum: u64,
+        #[serde(default)]
+        epoch: u64,
+    },
+
+    /// Any → All: Change to the lobby CRDT (`SyncMode::Crdt`)
+    CrdtOp { op: CrdtOp },
+
+    /// Any → Peer: Full CRDT state, exchanged on connect to heal partitions
+    CrdtState { state: LobbyCrdtState },
+
+    /// Any → All: Still here (liveness when there is no other traffic).
+    /// With a `seq`, peers answer with a `HeartbeatAck` to measure round trips.
+    Heartbeat {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        seq: Option<u64>,
     },
+
+    /// Any → Peer: Answer to heartbeat `seq`, stamped with the answering
+    /// peer's wall clock (Unix ms) for clock sync
+    HeartbeatAck {
+        seq: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        at: Option<u64>,
+    },
+
+    /// Any → All: What this participant is doing right now (`None` clears
+    /// it). Sent in the ephemeral class: dropped first under load, never
+    /// resent.
+    Presence {
+        participant_id: Uuid,
+        presence: Option<Presence>,
+    },
+
+    /// Any → Peer: Optional features I support (sent on connect; peers that
+    /// predate it never send one and get none)
+    Hello { capabilities: Capabilities },
+
+    /// Host → All: Grace period, heartbeat interval and timeout policy
+    TimeoutConfig {
+        config: TimeoutConfig,
+        #[serde(default)]
+        epoch: u64,
+    },
+
+    /// Guest → All: I was elected to replace the timed-out host and won't;
+    /// elect the next candidate
+    TakeoverDeclined { participant_id: Uuid },
+}
+
+impl SyncMessage {
+    /// Epoch of the sending host, for host-originated broadcasts (fencing)
+    pub fn host_epoch(&self) -> Option<u64> {
+        match self {
+            SyncMessage::EventBroadcast { event } => Some(event.epoch),
+            SyncMessage::FullSyncResponse { epoch, .. }
+            | SyncMessage::SnapshotChunk { epoch, .. }
+            | SyncMessage::PeerRoster { epoch, .. }
+            | SyncMessage::StateChecksum { epoch, .. }
+            | SyncMessage::TimeoutConfig { epoch, .. } => Some(*epoch),
+            _ => None,
+        }
+    }
+
+    /// Outbound priority class
+    pub fn priority(&self) -> MessagePriority {
+        match self {
+            // Checksums must stay ordered behind the events they cover
+            SyncMessage::CommandRequest { .. } | SyncMessage::StateChecksum { .. } => {
+                MessagePriority::LobbyEvent
+            }
+            SyncMessage::CrdtOp { op } if op.is_chat() => MessagePriority::Chat,
+            SyncMessage::CrdtOp { .. } => MessagePriority::LobbyEvent,
+            SyncMessage::Presence { .. } => MessagePriority::Ephemeral,
+            SyncMessage::EventBroadcast { event } => match event.event {
+                DomainEvent::HostDelegated { .. } => MessagePriority::Control,
+                DomainEvent::ChatMessageSent { .. } => MessagePriority::Chat,
+                _ => MessagePriority::LobbyEvent,
+            },
+            SyncMessage::RequestFullSync { .. }
+            | SyncMessage::FullSyncResponse { .. }
+            | SyncMessage::SnapshotChunk { .. }
+            | SyncMessage::PeerRoster { .. }
+            | SyncMessage::JoinAccepted { .. }
+            | SyncMessage::ResumeSession { .. }
+            | SyncMessage::ResumeRejected
+            | SyncMessage::CrdtState { .. }
+            | SyncMessage::Heartbeat { .. }
+            | SyncMessage::HeartbeatAck { .. }
+            | SyncMessage::Hello { .. }
+            | SyncMessage::TimeoutConfig { .. }
+            | SyncMessage::TakeoverDeclined { .. } => MessagePriority::Control,
+        }
+    }
+}
+
+/// A peer known to the host, with its participant (if already joined)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RosterEntry {
+    pub peer_id: PeerId,
+    pub participant_id: Option<Uuid>,
 }
 
 /// Snapshot of lobby state (for late joiners)
@@ -31,9 +185,185 @@ pub struct LobbySnapshot {
     pub name: String,
     pub host_id: Uuid,
     pub participants: Vec<konnekt_session_core::Participant>,
+    /// Queued activities (absent in snapshots from older hosts)
+    #[serde(default)]
+    pub activity_queue: Vec<konnekt_session_core::domain::ActivityConfig>,
+    /// Recent chat messages (absent in snapshots from older hosts)
+    #[serde(default)]
+    pub chat: Vec<konnekt_session_core::ChatMessage>,
+    #[serde(default)]
+    pub settings: konnekt_session_core::LobbySettings,
+    #[serde(default)]
+    pub co_hosts: Vec<Uuid>,
+    /// Participants who said they are ready (absent in snapshots from older hosts)
+    #[serde(default)]
+    pub ready: Vec<Uuid>,
+    /// Wall clock time (Unix ms) the countdown to the next activity ends at
+    #[serde(default)]
+    pub countdown_ends_at: Option<u64>,
     pub as_of_sequence: u64,
 }
 
+impl LobbySnapshot {
+    /// Build a snapshot of `lobby` as of event `as_of_sequence`
+    pub fn from_lobby(lobby: &konnekt_session_core::Lobby, as_of_sequence: u64) -> Self {
+        Self {
+            lobby_id: lobby.id(),
+            name: lobby.name().to_string(),
+            host_id: lobby.host_id(),
+            participants: lobby.participants().values().cloned().collect(),
+            activity_queue: lobby.activity_queue().to_vec(),
+            chat: lobby.chat_messages().to_vec(),
+            settings: *lobby.settings(),
+            co_hosts: lobby.co_hosts().iter().copied().collect(),
+            ready: lobby.ready().iter().copied().collect(),
+            countdown_ends_at: lobby.countdown_ends_at(),
+            as_of_sequence,
+        }
+    }
+
+    /// Stable hash of everything a snapshot replicates.
+    ///
+    /// FNV-1a over a canonical encoding, so native and WASM peers agree:
+    /// participants, co-hosts and ready flags are sorted by ID, while the
+    /// activity queue and chat keep their order (it is part of the state).
+    pub fn checksum(&self) -> u64 {
+        let mut participants: Vec<_> = self.participants.iter().collect();
+        participants.sort_by_key(|p| p.id());
+        let mut co_hosts = self.co_hosts.clone();
+        co_hosts.sort();
+        let mut ready = self.ready.clone();
+        ready.sort();
+
+        let mut hash = Fnv1a::new();
+        hash.write(self.lobby_id.as_bytes());
+        hash.write(self.name.as_bytes());
+        hash.write(self.host_id.as_bytes());
+
+        hash.write_len(participants.len());
+        for participant in participants {
+            // `joined_at` is stamped locally when guests replay events, so skip it
+            hash.write(participant.id().as_bytes());
+            hash.write(participant.name().as_bytes());
+            hash.write(participant.lobby_role().to_string().as_bytes());
+            hash.write(participant.participation_mode().to_string().as_bytes());
+            match participant.avatar() {
+                None => hash.write(b"none"),
+                Some(ParticipantAvatar::Emoji(emoji)) => {
+                    hash.write(b"emoji");
+                    hash.write(emoji.as_bytes());
+                }
+                Some(ParticipantAvatar::Image(url)) => {
+                    hash.write(b"image");
+                    hash.write(url.as_bytes());
+                }
+            }
+        }
+
+        hash.write_len(self.activity_queue.len());
+        for activity in &self.activity_queue {
+            hash.write(activity.id.as_bytes());
+            hash.write(activity.activity_type.as_bytes());
+            hash.write(activity.name.as_bytes());
+            hash.write_json(&activity.config);
+        }
+
+        hash.write_len(self.chat.len());
+        for message in &self.chat {
+            hash.write(message.id().as_bytes());
+            hash.write(message.author_id().as_bytes());
+            hash.write(message.text().as_bytes());
+            hash.write(&message.sent_at().as_millis().to_le_bytes());
+        }
+
+        match self.settings.max_participants {
+            None => hash.write(b"unlimited"),
+            Some(max) => hash.write_len(max),
+        }
+        hash.write(&[self.settings.locked as u8]);
+        hash.write(self.settings.auto_delegation.to_string().as_bytes());
+
+        hash.write_len(co_hosts.len());
+        for co_host in co_hosts {
+            hash.write(co_host.as_bytes());
+        }
+
+        hash.write_len(ready.len());
+        for participant_id in ready {
+            hash.write(participant_id.as_bytes());
+        }
+        match self.countdown_ends_at {
+            None => hash.write(b"no countdown"),
+            Some(ends_at) => hash.write(&ends_at.to_le_bytes()),
+        }
+        hash.finish()
+    }
+}
+
+/// 64-bit FNV-1a
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        // Field separator so ("ab", "c") != ("a", "bc")
+        self.0 ^= 0xff;
+        self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+
+    /// Lengths and counts as fixed-width integers, whatever the platform
+    fn write_len(&mut self, len: usize) {
+        self.write(&(len as u64).to_le_bytes());
+    }
+
+    /// JSON with object keys sorted, since key order depends on how the
+    /// value was built
+    fn write_json(&mut self, value: &serde_json::Value) {
+        use serde_json::Value;
+
+        match value {
+            Value::Null => self.write(b"null"),
+            Value::Bool(b) => self.write(if *b { b"true" } else { b"false" }),
+            Value::Number(n) => {
+                self.write(b"#");
+                self.write(n.to_string().as_bytes());
+            }
+            Value::String(s) => {
+                self.write(b"\"");
+                self.write(s.as_bytes());
+            }
+            Value::Array(items) => {
+                self.write(b"[");
+                self.write_len(items.len());
+                for item in items {
+                    self.write_json(item);
+                }
+            }
+            Value::Object(map) => {
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_by_key(|(key, _)| *key);
+                self.write(b"{");
+                self.write_len(entries.len());
+                for (key, item) in entries {
+                    self.write(key.as_bytes());
+                    self.write_json(item);
+                }
+            }
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
 /// Manages event synchronization for a lobby
 pub struct EventSyncManager {
     /// Our lobby ID
@@ -47,6 +377,15 @@ pub struct EventSyncManager {
 
     /// Out-of-order events waiting for gaps to be filled
     pending_events: HashMap<u64, LobbyEvent>,
+
+    /// Host epoch (incremented on every host takeover)
+    epoch: u64,
+
+    /// Index of the next chunk of a streamed full sync in progress
+    next_chunk: Option<u32>,
+
+    /// Stamps events, and heartbeat acks for clock sync
+    clock: SharedClock,
 }
 
 impl EventSyncManager {
@@ -59,6 +398,9 @@ impl EventSyncManager {
             is_host: true,
             event_log: EventLog::new(),
             pending_events: HashMap::new(),
+            epoch: 0,
+            next_chunk: None,
+            clock: SystemClock::shared(),
         }
     }
 
@@ -71,14 +413,67 @@ impl EventSyncManager {
             is_host: false,
             event_log: EventLog::new(),
             pending_events: HashMap::new(),
+            epoch: 0,
+            next_chunk: None,
+            clock: SystemClock::shared(),
         }
     }
 
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
     /// Promote to host (after delegation)
-    #[instrument(skip(self))]
+    ///
+    /// Adopts the event log received from the previous host, so sequencing
+    /// continues where it left off, and bumps the epoch.
+    #[instrument(skip(self), fields(epoch = %self.epoch))]
     pub fn promote_to_host(&mut self) {
         info!("Promoting EventSyncManager to HOST");
         self.is_host = true;
+        self.event_log.resume_sequencing();
+        self.pending_events.clear();
+        self.epoch += 1;
+        info!(new_epoch = %self.epoch, "Took over event log");
+    }
+
+    /// Step down after losing a host fence to another peer (split brain)
+    ///
+    /// Keeps the winner's epoch; the event log is replaced by its full sync.
+    #[instrument(skip(self), fields(epoch = %self.epoch))]
+    pub fn demote_to_guest(&mut self, epoch: u64) {
+        info!(new_epoch = %epoch, "Demoting EventSyncManager to GUEST");
+        self.is_host = false;
+        self.pending_events.clear();
+        self.epoch = self.epoch.max(epoch);
+    }
+
+    /// Seed the event log from persisted history (host restart)
+    ///
+    /// New events continue after the highest restored sequence, in the
+    /// highest restored epoch.
+    #[instrument(skip(self, events), fields(count = %events.len()))]
+    pub fn restore_events(&mut self, events: Vec<LobbyEvent>) {
+        for event in events {
+            self.epoch = self.epoch.max(event.epoch);
+            self.event_log.add_event(event);
+        }
+        self.event_log.resume_sequencing();
+        info!(
+            next_sequence = %self.event_log.next_sequence(),
+            epoch = %self.epoch,
+            "Restored event log"
+        );
+    }
+
+    /// Current host epoch
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Are we the host?
+    pub fn is_host(&self) -> bool {
+        self.is_host
     }
 
     /// Get current sequence number
@@ -96,12 +491,24 @@ impl EventSyncManager {
         is_host = %self.is_host
     ))]
     pub fn create_event(&mut self, event: DomainEvent) -> Result<SyncMessage, SyncError> {
+        self.create_correlated_event(event, None)
+    }
+
+    /// Like `create_event`, tagged with the user action that caused it
+    pub fn create_correlated_event(
+        &mut self,
+        event: DomainEvent,
+        correlation_id: Option<CorrelationId>,
+    ) -> Result<SyncMessage, SyncError> {
         if !self.is_host {
             warn!("Attempted to create event as guest");
             return Err(SyncError::NotHost);
         }
 
-        let lobby_event = LobbyEvent::without_sequence(self.lobby_id, event);
+        let timestamp = Timestamp::now_on(self.clock.as_ref());
+        let lobby_event = LobbyEvent::without_sequence_at(self.lobby_id, event, timestamp)
+            .with_epoch(self.epoch)
+            .with_correlation(correlation_id);
         let sequence = self.event_log.append(lobby_event.clone());
 
         debug!(sequence = %sequence, "Host created new event");
@@ -122,14 +529,20 @@ impl EventSyncManager {
         message: SyncMessage,
     ) -> Result<SyncResponse, SyncError> {
         match message {
-            SyncMessage::CommandRequest { command } => {
+            SyncMessage::CommandRequest {
+                command,
+                correlation_id,
+            } => {
                 if !self.is_host {
                     warn!("Guest received CommandRequest, ignoring");
                     return Ok(SyncResponse::None);
                 }
 
                 info!("HOST: Received command request from peer");
-                Ok(SyncResponse::ProcessCommand { command })
+                Ok(SyncResponse::ProcessCommand {
+                    command,
+                    correlation_id,
+                })
             }
 
             SyncMessage::EventBroadcast { event } => self.handle_event_broadcast(event),
@@ -147,8 +560,131 @@ impl EventSyncManager {
                 })
             }
 
-            SyncMessage::FullSyncResponse { snapshot, events } => {
-                self.handle_full_sync_response(snapshot, events)
+            SyncMessage::FullSyncResponse {
+                snapshot,
+                events,
+                epoch,
+            } => self.handle_full_sync_response(snapshot, events, epoch),
+
+            SyncMessage::SnapshotChunk {
+                index,
+                total,
+                part,
+                epoch,
+            } => self.handle_snapshot_chunk(index, total, part, epoch),
+
+            SyncMessage::PeerRoster { peers, .. } => {
+                if self.is_host {
+                    warn!("Host received PeerRoster, ignoring");
+                    return Ok(SyncResponse::None);
+                }
+
+                debug!(peers = %peers.len(), "Received peer roster from host");
+                Ok(SyncResponse::UpdateRoster { peers })
+            }
+
+            SyncMessage::JoinAccepted {
+                participant_id,
+                resume_token,
+            } => {
+                if self.is_host {
+                    warn!("Host received JoinAccepted, ignoring");
+                    return Ok(SyncResponse::None);
+                }
+
+                info!(participant_id = %participant_id, "Host accepted our join");
+                Ok(SyncResponse::Joined {
+                    participant_id,
+                    resume_token,
+                })
+            }
+
+            SyncMessage::ResumeSession {
+                lobby_id,
+                resume_token,
+            } => {
+                if !self.is_host {
+                    return Ok(SyncResponse::None);
+                }
+
+                if lobby_id != self.lobby_id {
+                    warn!(expected = %self.lobby_id, received = %lobby_id, "Wrong lobby ID");
+                    return Err(SyncError::WrongLobby);
+                }
+
+                info!("Peer requested to resume session");
+                Ok(SyncResponse::ResumeRequested {
+                    peer: from,
+                    resume_token,
+                })
+            }
+
+            SyncMessage::ResumeRejected => {
+                if self.is_host {
+                    return Ok(SyncResponse::None);
+                }
+
+                warn!("Host rejected our resume token");
+                Ok(SyncResponse::ResumeRejected)
+            }
+
+            SyncMessage::StateChecksum {
+                sequence, checksum, ..
+            } => {
+                if self.is_host {
+                    return Ok(SyncResponse::None);
+                }
+
+                debug!(sequence, checksum, "Received state checksum from host");
+                Ok(SyncResponse::VerifyChecksum { sequence, checksum })
+            }
+
+            // CRDT changes may come from any peer, host or not
+            SyncMessage::CrdtOp { op } => Ok(SyncResponse::ApplyCrdtOp { from, op }),
+
+            SyncMessage::CrdtState { state } => Ok(SyncResponse::MergeCrdtState { from, state }),
+
+            // Receiving it already refreshed the peer; echo it for round trips
+            SyncMessage::Heartbeat { seq: None } => Ok(SyncResponse::None),
+            SyncMessage::Heartbeat { seq: Some(seq) } => Ok(SyncResponse::SendMessage {
+                to: Some(from),
+                message: SyncMessage::HeartbeatAck {
+                    seq,
+                    at: Some(self.clock.unix_millis()),
+                },
+            }),
+
+            SyncMessage::HeartbeatAck { seq, at } => {
+                Ok(SyncResponse::HeartbeatAcked { from, seq, at })
+            }
+
+            SyncMessage::Presence {
+                participant_id,
+                presence,
+            } => Ok(SyncResponse::UpdatePresence {
+                from,
+                participant_id,
+                presence,
+            }),
+
+            SyncMessage::Hello { capabilities } => {
+                Ok(SyncResponse::NegotiateCapabilities { from, capabilities })
+            }
+
+            SyncMessage::TakeoverDeclined { participant_id } => {
+                Ok(SyncResponse::TakeoverDeclined {
+                    from,
+                    participant_id,
+                })
+            }
+
+            SyncMessage::TimeoutConfig { config, .. } => {
+                if self.is_host {
+                    return Ok(SyncResponse::None);
+                }
+
+                debug!(?config, "Received timeout config from host");
+                Ok(SyncResponse::UpdateTimeouts { config })
             }
         }
     }
@@ -167,7 +703,13 @@ impl EventSyncManager {
             return Err(SyncError::WrongLobby);
         }
 
-        let expected_sequence = self.event_log.highest_sequence() + 1;
+        if event.epoch > self.epoch {
+            info!(old_epoch = %self.epoch, new_epoch = %event.epoch, "Following new host epoch");
+            self.epoch = event.epoch;
+        }
+
+        // Saturating: a peer may claim any sequence, up to u64::MAX
+        let expected_sequence = self.event_log.highest_sequence().saturating_add(1);
 
         if event.sequence == expected_sequence {
             // Event is next in sequence - apply immediately
@@ -182,6 +724,17 @@ impl EventSyncManager {
 
             Ok(SyncResponse::ApplyEvents { events })
         } else if event.sequence > expected_sequence {
+            if self.pending_events.len() >= MAX_PENDING_EVENTS
+                && !self.pending_events.contains_key(&event.sequence)
+            {
+                warn!(
+                    received = %event.sequence,
+                    pending = %self.pending_events.len(),
+                    "Too many out-of-order events, dropping"
+                );
+                return Ok(SyncResponse::None);
+            }
+
             // Out of order - buffer it
             warn!(
                 expected = %expected_sequence,
@@ -210,7 +763,7 @@ impl EventSyncManager {
         let mut applied = Vec::new();
 
         loop {
-            let next_expected = self.event_log.highest_sequence() + 1;
+            let next_expected = self.event_log.highest_sequence().saturating_add(1);
 
             if let Some(event) = self.pending_events.remove(&next_expected) {
                 debug!(sequence = %event.sequence, "Applying pending event from buffer");
@@ -241,11 +794,15 @@ impl EventSyncManager {
         &mut self,
         snapshot: LobbySnapshot,
         events: Vec<LobbyEvent>,
+        epoch: u64,
     ) -> Result<SyncResponse, SyncError> {
         info!("Received full sync response");
 
-        // Clear our event log
+        // Clear our event log (and anything buffered from a previous host)
         self.event_log = EventLog::new();
+        self.pending_events.clear();
+        self.next_chunk = None;
+        self.epoch = epoch;
 
         // Add all events
         for event in &events {
@@ -264,7 +821,8 @@ impl EventSyncManager {
             name: snapshot.name.clone(),
         };
 
-        let lobby_event = LobbyEvent::new(0, snapshot.lobby_id, create_lobby_event);
+        let timestamp = Timestamp::now_on(self.clock.as_ref());
+        let lobby_event = LobbyEvent::new_at(0, snapshot.lobby_id, create_lobby_event, timestamp);
 
         let mut all_events = vec![lobby_event];
         all_events.extend(events.clone());
@@ -275,6 +833,76 @@ impl EventSyncManager {
         })
     }
 
+    fn handle_snapshot_chunk(
+        &mut self,
+        index: u32,
+        total: u32,
+        part: SnapshotPart,
+        epoch: u64,
+    ) -> Result<SyncResponse, SyncError> {
+        if self.is_host {
+            warn!("Host received SnapshotChunk, ignoring");
+            return Ok(SyncResponse::None);
+        }
+
+        match &part {
+            SnapshotPart::Header { .. } if index == 0 => {
+                info!(total = %total, "Receiving streamed full sync");
+                self.event_log = EventLog::new();
+                self.pending_events.clear();
+                self.epoch = epoch;
+            }
+            _ if self.next_chunk == Some(index) => {}
+            _ => {
+                warn!(index = %index, expected = ?self.next_chunk, "Dropping out-of-order snapshot chunk");
+                self.next_chunk = None;
+                return Ok(SyncResponse::None);
+            }
+        }
+
+        if let SnapshotPart::Events { events } = &part {
+            for event in events {
+                self.event_log.add_event(event.clone());
+            }
+        }
+        self.next_chunk = (index + 1 < total).then_some(index + 1);
+
+        Ok(SyncResponse::ApplySnapshotPart { part, index, total })
+    }
+
+    /// Create a streamed full sync: `parts` followed by the event log, in
+    /// pages of `page_size` (host only)
+    pub fn create_snapshot_stream(
+        &self,
+        mut parts: Vec<SnapshotPart>,
+        page_size: usize,
+    ) -> Result<Vec<SyncMessage>, SyncError> {
+        if !self.is_host {
+            return Err(SyncError::NotHost);
+        }
+
+        let events = self.event_log.all_events();
+        parts.extend(
+            events
+                .chunks(page_size.max(1))
+                .map(|page| SnapshotPart::Events {
+                    events: page.to_vec(),
+                }),
+        );
+
+        let total = parts.len() as u32;
+        Ok(parts
+            .into_iter()
+            .enumerate()
+            .map(|(index, part)| SyncMessage::SnapshotChunk {
+                index: index as u32,
+                total,
+                part,
+                epoch: self.epoch,
+            })
+            .collect())
+    }
+
     /// Create a full sync response (host only)
     pub fn create_full_sync_response(
         &self,
@@ -297,7 +925,48 @@ impl EventSyncManager {
             events.len()
         );
 
-        Ok(SyncMessage::FullSyncResponse { snapshot, events })
+        Ok(SyncMessage::FullSyncResponse {
+            snapshot,
+            events,
+            epoch: self.epoch,
+        })
+    }
+
+    /// Announce the hash of our state as of the latest event (host only)
+    pub fn create_state_checksum(&self, checksum: u64) -> Result<SyncMessage, SyncError> {
+        if !self.is_host {
+            return Err(SyncError::NotHost);
+        }
+
+        Ok(SyncMessage::StateChecksum {
+            sequence: self.current_sequence(),
+            checksum,
+            epoch: self.epoch,
+        })
+    }
+
+    /// Announce our timeout settings to guests (host only)
+    pub fn create_timeout_config(&self, config: TimeoutConfig) -> Result<SyncMessage, SyncError> {
+        if !self.is_host {
+            return Err(SyncError::NotHost);
+        }
+
+        Ok(SyncMessage::TimeoutConfig {
+            config,
+            epoch: self.epoch,
+        })
+    }
+
+    /// Ask the host to re-bind us to our participant (guest only)
+    pub fn request_resume(&self, resume_token: ResumeToken) -> Result<SyncMessage, SyncError> {
+        if self.is_host {
+            return Err(SyncError::AlreadyHost);
+        }
+
+        Ok(SyncMessage::ResumeSession {
+            lobby_id: self.lobby_id,
+            resume_token,
+        })
     }
 
     /// Request full sync from host (guest only)
@@ -311,8 +980,7 @@ impl EventSyncManager {
         })
     }
 
-    /// Get all events (for debugging)
-    #[cfg(test)]
+    /// Get all events in the log
     pub fn all_events(&self) -> Vec<LobbyEvent> {
         self.event_log.all_events()
     }
@@ -339,6 +1007,13 @@ pub enum SyncResponse {
         events: Vec<LobbyEvent>,
     },
 
+    /// Apply part `index` of `total` of a streamed full sync
+    ApplySnapshotPart {
+        part: SnapshotPart,
+        index: u32,
+        total: u32,
+    },
+
     /// Send this message to peer(s)
     SendMessage {
         to: Option<PeerId>,
@@ -352,7 +1027,63 @@ pub enum SyncResponse {
     },
 
     /// Host should process this command locally
-    ProcessCommand { command: DomainCommand },
+    ProcessCommand {
+        command: DomainCommand,
+        correlation_id: Option<CorrelationId>,
+    },
+
+    /// Host announced its peer roster (guest only)
+    UpdateRoster { peers: Vec<RosterEntry> },
+
+    /// Host bound us to a participant (guest only)
+    Joined {
+        participant_id: Uuid,
+        resume_token: ResumeToken,
+    },
+
+    /// A reconnecting peer wants to resume its participant (host only)
+    ResumeRequested {
+        peer: PeerId,
+        resume_token: ResumeToken,
+    },
+
+    /// Host did not recognise our resume token (guest only)
+    ResumeRejected,
+
+    /// Compare our state after `sequence` against the host's (guest only)
+    VerifyChecksum { sequence: u64, checksum: u64 },
+
+    /// Apply a peer's CRDT operation
+    ApplyCrdtOp { from: PeerId, op: CrdtOp },
+
+    /// Merge a peer's full CRDT state
+    MergeCrdtState { from: PeerId, state: LobbyCrdtState },
+
+    /// Adopt the host's timeout settings (guest only)
+    UpdateTimeouts { config: TimeoutConfig },
+
+    /// A participant's presence changed
+    UpdatePresence {
+        from: PeerId,
+        participant_id: Uuid,
+        presence: Option<Presence>,
+    },
+
+    /// A peer advertised its optional features
+    NegotiateCapabilities {
+        from: PeerId,
+        capabilities: Capabilities,
+    },
+
+    /// A peer answered our heartbeat `seq` (at `at` on its wall clock)
+    HeartbeatAcked {
+        from: PeerId,
+        seq: u64,
+        at: Option<u64>,
+    },
+
+    /// A guest passed on taking over from the timed-out host
+    TakeoverDeclined { from: PeerId, participant_id: Uuid },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -387,15 +1118,21 @@ mod tests {
         let mut sync = EventSyncManager::new_host(lobby_id);
         let peer = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
 
+        let correlation = CorrelationId::new();
         let msg = SyncMessage::CommandRequest {
             command: create_test_command(),
+            correlation_id: Some(correlation),
         };
 
         let response = sync.handle_message(peer, msg).unwrap();
 
         match response {
-            SyncResponse::ProcessCommand { command } => {
+            SyncResponse::ProcessCommand {
+                command,
+                correlation_id,
+            } => {
                 assert!(matches!(command, DomainCommand::JoinLobby { .. }));
+                assert_eq!(correlation_id, Some(correlation));
             }
             _ => panic!("Expected ProcessCommand"),
         }
@@ -409,6 +1146,7 @@ mod tests {
 
         let msg = SyncMessage::CommandRequest {
             command: create_test_command(),
+            correlation_id: None,
         };
 
         let response = sync.handle_message(peer, msg).unwrap();
@@ -469,4 +1207,404 @@ mod tests {
 
         assert_eq!(sync.current_sequence(), 3);
     }
+
+    #[test]
+    fn test_guest_bounds_out_of_order_events() {
+        let lobby_id = Uuid::new_v4();
+        let mut sync = EventSyncManager::new_guest(lobby_id);
+        let peer = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+
+        for seq in 2..MAX_PENDING_EVENTS as u64 + 100 {
+            let event = LobbyEvent::new(
+                seq,
+                lobby_id,
+                DomainEvent::GuestLeft {
+                    participant_id: Uuid::new_v4(),
+                },
+            );
+            sync.handle_message(peer, SyncMessage::EventBroadcast { event })
+                .unwrap();
+        }
+
+        assert_eq!(sync.pending_count(), MAX_PENDING_EVENTS);
+    }
+
+    #[test]
+    fn test_hostile_sequence_does_not_overflow() {
+        let lobby_id = Uuid::from_u128(1);
+        let mut sync = EventSyncManager::new_guest(lobby_id);
+        let peer = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+        let event = LobbyEvent::new(
+            u64::MAX,
+            lobby_id,
+            DomainEvent::GuestLeft {
+                participant_id: Uuid::new_v4(),
+            },
+        );
+        let host =
+            konnekt_session_core::Participant::host_with_id(Uuid::new_v4(), "Host".to_string())
+                .unwrap();
+
+        // A full sync can seed the log at any sequence
+        sync.handle_message(
+            peer,
+            SyncMessage::FullSyncResponse {
+                snapshot: snapshot_with(vec![host]),
+                events: vec![event.clone()],
+                epoch: 0,
+            },
+        )
+        .unwrap();
+        sync.handle_message(peer, SyncMessage::EventBroadcast { event })
+            .unwrap();
+
+        sync.promote_to_host();
+        assert!(
+            sync.create_event(DomainEvent::GuestLeft {
+                participant_id: Uuid::new_v4(),
+            })
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_guest_receives_peer_roster() {
+        let lobby_id = Uuid::new_v4();
+        let mut sync = EventSyncManager::new_guest(lobby_id);
+        let host = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+        let other = RosterEntry {
+            peer_id: PeerId::new(matchbox_socket::PeerId(Uuid::new_v4())),
+            participant_id: Some(Uuid::new_v4()),
+        };
+
+        let msg = SyncMessage::PeerRoster {
+            peers: vec![other],
+            epoch: 0,
+        };
+
+        match sync.handle_message(host, msg).unwrap() {
+            SyncResponse::UpdateRoster { peers } => assert_eq!(peers, vec![other]),
+            other => panic!("Expected UpdateRoster, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_promoted_guest_continues_sequence_with_new_epoch() {
+        let lobby_id = Uuid::new_v4();
+        let mut sync = EventSyncManager::new_guest(lobby_id);
+        let old_host = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+
+        for seq in 1..=3 {
+            let event = LobbyEvent::new(
+                seq,
+                lobby_id,
+                DomainEvent::GuestLeft {
+                    participant_id: Uuid::new_v4(),
+                },
+            );
+            sync.handle_message(old_host, SyncMessage::EventBroadcast { event })
+                .unwrap();
+        }
+
+        sync.promote_to_host();
+        assert_eq!(sync.epoch(), 1);
+        assert_eq!(sync.current_sequence(), 3);
+
+        match sync
+            .create_event(DomainEvent::GuestLeft {
+                participant_id: Uuid::new_v4(),
+            })
+            .unwrap()
+        {
+            SyncMessage::EventBroadcast { event } => {
+                assert_eq!(event.sequence, 4);
+                assert_eq!(event.epoch, 1);
+            }
+            other => panic!("Expected EventBroadcast, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_guest_follows_new_epoch() {
+        let lobby_id = Uuid::new_v4();
+        let mut sync = EventSyncManager::new_guest(lobby_id);
+        let new_host = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+
+        let event = LobbyEvent::new(
+            1,
+            lobby_id,
+            DomainEvent::GuestLeft {
+                participant_id: Uuid::new_v4(),
+            },
+        )
+        .with_epoch(2);
+
+        sync.handle_message(new_host, SyncMessage::EventBroadcast { event })
+            .unwrap();
+        assert_eq!(sync.epoch(), 2);
+    }
+
+    #[test]
+    fn test_host_receives_resume_request() {
+        let lobby_id = Uuid::new_v4();
+        let mut sync = EventSyncManager::new_host(lobby_id);
+        let peer = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+        let token = ResumeToken::new();
+
+        let guest = EventSyncManager::new_guest(lobby_id);
+        let msg = guest.request_resume(token).unwrap();
+
+        match sync.handle_message(peer, msg).unwrap() {
+            SyncResponse::ResumeRequested {
+                peer: from,
+                resume_token,
+            } => {
+                assert_eq!(from, peer);
+                assert_eq!(resume_token, token);
+            }
+            other => panic!("Expected ResumeRequested, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_resume_request_wrong_lobby() {
+        let mut sync = EventSyncManager::new_host(Uuid::new_v4());
+        let peer = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+
+        let msg = SyncMessage::ResumeSession {
+            lobby_id: Uuid::new_v4(),
+            resume_token: ResumeToken::new(),
+        };
+
+        assert!(matches!(
+            sync.handle_message(peer, msg),
+            Err(SyncError::WrongLobby)
+        ));
+    }
+
+    fn snapshot_with(participants: Vec<konnekt_session_core::Participant>) -> LobbySnapshot {
+        LobbySnapshot {
+            lobby_id: Uuid::from_u128(1),
+            name: "Lobby".to_string(),
+            host_id: participants[0].id(),
+            participants,
+            activity_queue: Vec::new(),
+            chat: Vec::new(),
+            settings: Default::default(),
+            co_hosts: Vec::new(),
+            ready: Vec::new(),
+            countdown_ends_at: None,
+            as_of_sequence: 0,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_checksum_ignores_order_and_sequence() {
+        use konnekt_session_core::Participant;
+
+        let host = Participant::host_with_id(Uuid::from_u128(2), "Host".to_string()).unwrap();
+        let guest = Participant::guest_with_id(Uuid::from_u128(3), "Alice".to_string()).unwrap();
+
+        let a = snapshot_with(vec![host.clone(), guest.clone()]);
+        let mut b = snapshot_with(vec![host.clone(), guest]);
+        b.participants.reverse();
+        b.as_of_sequence = 42;
+        assert_eq!(a.checksum(), b.checksum());
+
+        let c = snapshot_with(vec![host]);
+        assert_ne!(a.checksum(), c.checksum());
+    }
+
+    #[test]
+    fn test_snapshot_checksum_covers_every_synced_field() {
+        use konnekt_session_core::domain::ActivityConfig;
+        use konnekt_session_core::{ChatMessage, Participant};
+
+        let host = Participant::host_with_id(Uuid::from_u128(2), "Host".to_string()).unwrap();
+        let guest = Participant::guest_with_id(Uuid::from_u128(3), "Alice".to_string()).unwrap();
+        let base = snapshot_with(vec![host.clone(), guest.clone()]);
+
+        let mut avatar = guest.clone();
+        avatar
+            .set_avatar(Some(ParticipantAvatar::Emoji("🦊".to_string())))
+            .unwrap();
+        let mut queued = base.clone();
+        queued.activity_queue.push(ActivityConfig::new(
+            "echo".to_string(),
+            "Echo".to_string(),
+            serde_json::json!({ "prompt": "Hallo" }),
+        ));
+        let mut chatted = base.clone();
+        chatted.chat.push(
+            ChatMessage::new_at(host.id(), "Hi".to_string(), Timestamp::from_millis(5)).unwrap(),
+        );
+        let mut locked = base.clone();
+        locked.settings.locked = true;
+        let mut promoted = base.clone();
+        promoted.co_hosts.push(guest.id());
+        let mut ready = base.clone();
+        ready.ready.push(guest.id());
+        let mut counting_down = base.clone();
+        counting_down.countdown_ends_at = Some(1_000);
+
+        let changed = [
+            snapshot_with(vec![host, avatar]),
+            queued,
+            chatted,
+            locked,
+            promoted,
+            ready,
+            counting_down,
+        ];
+        for snapshot in &changed {
+            assert_ne!(base.checksum(), snapshot.checksum());
+        }
+    }
+
+    #[test]
+    fn test_snapshot_checksum_ignores_config_key_order() {
+        use konnekt_session_core::Participant;
+        use konnekt_session_core::domain::ActivityConfig;
+
+        let host = Participant::host_with_id(Uuid::from_u128(2), "Host".to_string()).unwrap();
+        let config = |value: serde_json::Value| {
+            let mut config = ActivityConfig::new("echo".to_string(), "Echo".to_string(), value);
+            config.id = Uuid::from_u128(9);
+            config
+        };
+
+        let mut a = snapshot_with(vec![host.clone()]);
+        a.activity_queue
+            .push(config(serde_json::json!({ "a": 1, "b": "x" })));
+        let mut b = snapshot_with(vec![host]);
+        let mut map = serde_json::Map::new();
+        map.insert("b".to_string(), serde_json::json!("x"));
+        map.insert("a".to_string(), serde_json::json!(1));
+        b.activity_queue
+            .push(config(serde_json::Value::Object(map)));
+        assert_eq!(a.checksum(), b.checksum());
+    }
+
+    #[test]
+    fn test_guest_verifies_state_checksum() {
+        let lobby_id = Uuid::new_v4();
+        let host = EventSyncManager::new_host(lobby_id);
+        let mut guest = EventSyncManager::new_guest(lobby_id);
+        let peer = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+
+        let msg = host.create_state_checksum(7).unwrap();
+        assert_eq!(msg.priority(), MessagePriority::LobbyEvent);
+        assert!(matches!(
+            guest.handle_message(peer, msg).unwrap(),
+            SyncResponse::VerifyChecksum {
+                sequence: 0,
+                checksum: 7
+            }
+        ));
+
+        assert!(matches!(
+            guest.create_state_checksum(7),
+            Err(SyncError::NotHost)
+        ));
+    }
+
+    #[test]
+    fn test_guest_follows_snapshot_stream_in_order() {
+        use konnekt_session_core::{Lobby, Participant};
+
+        let host = Participant::new_host("Host".to_string()).unwrap();
+        let mut lobby = Lobby::new("Big".to_string(), host).unwrap();
+        for i in 0..5 {
+            lobby
+                .add_guest(Participant::new_guest(format!("Guest{i}")).unwrap())
+                .unwrap();
+        }
+
+        let mut host_sync = EventSyncManager::new_host(lobby.id());
+        host_sync
+            .create_event(DomainEvent::GuestLeft {
+                participant_id: Uuid::new_v4(),
+            })
+            .unwrap();
+        let mut guest = EventSyncManager::new_guest(lobby.id());
+        let peer = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+
+        let chunks = host_sync
+            .create_snapshot_stream(SnapshotPart::split(&lobby, None, 1, 2).unwrap(), 2)
+            .unwrap();
+        // Header, three pages of guests, one page of events
+        assert_eq!(chunks.len(), 5);
+        assert!(
+            chunks
+                .iter()
+                .all(|c| c.priority() == MessagePriority::Control)
+        );
+
+        let mut chunks = chunks.into_iter();
+        let header = chunks.next().unwrap();
+        let skipped = chunks.next().unwrap();
+        assert!(matches!(
+            guest.handle_message(peer, header.clone()).unwrap(),
+            SyncResponse::ApplySnapshotPart {
+                index: 0,
+                total: 5,
+                ..
+            }
+        ));
+        // A missing chunk aborts the stream
+        let third = chunks.next().unwrap();
+        assert!(matches!(
+            guest.handle_message(peer, third.clone()).unwrap(),
+            SyncResponse::None
+        ));
+
+        for msg in [header, skipped, third].into_iter().chain(chunks) {
+            assert!(matches!(
+                guest.handle_message(peer, msg).unwrap(),
+                SyncResponse::ApplySnapshotPart { .. }
+            ));
+        }
+        assert_eq!(guest.current_sequence(), 1);
+    }
+
+    #[test]
+    fn test_heartbeat_is_acked_and_stays_wire_compatible() {
+        let mut sync = EventSyncManager::new_guest(Uuid::new_v4());
+        let peer = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+
+        match sync
+            .handle_message(peer, SyncMessage::Heartbeat { seq: Some(7) })
+            .unwrap()
+        {
+            SyncResponse::SendMessage {
+                to: Some(to),
+                message:
+                    SyncMessage::HeartbeatAck {
+                        seq: 7,
+                        at: Some(_),
+                    },
+            } => assert_eq!(to, peer),
+            other => panic!("Expected HeartbeatAck, got: {:?}", other),
+        }
+        assert!(matches!(
+            sync.handle_message(peer, SyncMessage::HeartbeatAck { seq: 7, at: None })
+                .unwrap(),
+            SyncResponse::HeartbeatAcked { seq: 7, .. }
+        ));
+
+        // Acks from peers that predate clock sync carry no time
+        let old: SyncMessage = serde_json::from_str(r#"{"type":"heartbeat_ack","seq":7}"#).unwrap();
+        assert!(matches!(
+            old,
+            SyncMessage::HeartbeatAck { seq: 7, at: None }
+        ));
+
+        // Heartbeats from peers that predate acks carry no seq
+        let old: SyncMessage = serde_json::from_str(r#"{"type":"heartbeat"}"#).unwrap();
+        assert!(matches!(old, SyncMessage::Heartbeat { seq: None }));
+        assert!(matches!(
+            sync.handle_message(peer, old).unwrap(),
+            SyncResponse::None
+        ));
+    }
 }
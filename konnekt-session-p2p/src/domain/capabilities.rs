@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Domain value: Optional protocol features a peer supports
+///
+/// Peers advertise their set when they connect and use the intersection with
+/// each remote peer. Bits this build doesn't know survive (de)serialization
+/// but never end up in a negotiated set, so newer clients can add features
+/// without breaking older ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Capabilities(u32);
+
+impl Capabilities {
+    /// Compressed message payloads
+    pub const COMPRESSION: Self = Self(1 << 0);
+    /// End-to-end encrypted payloads
+    pub const ENCRYPTION: Self = Self(1 << 1);
+    /// Binary instead of JSON message encoding
+    pub const BINARY_CODEC: Self = Self(1 << 2);
+    /// Unreliable/unordered channel for ephemeral traffic
+    pub const UNRELIABLE_CHANNEL: Self = Self(1 << 3);
+
+    const KNOWN: [(Self, &'static str); 4] = [
+        (Self::COMPRESSION, "compression"),
+        (Self::ENCRYPTION, "encryption"),
+        (Self::BINARY_CODEC, "binary_codec"),
+        (Self::UNRELIABLE_CHANNEL, "unreliable_channel"),
+    ];
+
+    /// No optional features (what peers without a handshake get)
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Every feature this build knows about
+    pub const fn all() -> Self {
+        Self(
+            Self::COMPRESSION.0
+                | Self::ENCRYPTION.0
+                | Self::BINARY_CODEC.0
+                | Self::UNRELIABLE_CHANNEL.0,
+        )
+    }
+
+    pub const fn bits(&self) -> u32 {
+        self.0
+    }
+
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+
+    /// Features both sides support and this build knows
+    pub const fn negotiate(&self, remote: Self) -> Self {
+        Self(self.0 & remote.0 & Self::all().0)
+    }
+}
+
+impl std::ops::BitOr for Capabilities {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = Self::KNOWN
+            .iter()
+            .filter(|(flag, _)| self.contains(*flag))
+            .map(|(_, name)| *name)
+            .collect();
+
+        if names.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", names.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate_keeps_common_known_features() {
+        let local = Capabilities::COMPRESSION | Capabilities::BINARY_CODEC;
+        // A newer peer with a feature we don't know yet
+        let remote = Capabilities::from_bits(Capabilities::COMPRESSION.bits() | 1 << 31);
+
+        let negotiated = local.negotiate(remote);
+
+        assert_eq!(negotiated, Capabilities::COMPRESSION);
+        assert_eq!(negotiated.to_string(), "compression");
+        assert!(local.negotiate(Capabilities::empty()).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_bits_survive_serialization() {
+        let remote = Capabilities::from_bits(Capabilities::ENCRYPTION.bits() | 1 << 20);
+
+        let json = serde_json::to_string(&remote).unwrap();
+        let decoded: Capabilities = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            json,
+            (Capabilities::ENCRYPTION.bits() | 1 << 20).to_string()
+        );
+        assert_eq!(decoded, remote);
+    }
+}
@@ -0,0 +1,135 @@
+use konnekt_session_core::DomainCommand;
+use std::collections::VecDeque;
+
+/// Round trips remembered for the offset estimate
+pub const CLOCK_SAMPLES: usize = 8;
+
+/// One round trip to the host
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ClockSample {
+    /// How far the host's clock was ahead of ours
+    offset_ms: i64,
+    round_trip_ms: u64,
+}
+
+/// Estimates how far the host's clock is ahead of ours, NTP style
+///
+/// The host stamps its answers to our probes with its wall clock. Assuming
+/// the stamp was taken halfway through the round trip gives an offset that
+/// is off by at most half the round trip, so the sample with the shortest
+/// round trip of the last [`CLOCK_SAMPLES`] wins.
+#[derive(Debug, Clone, Default)]
+pub struct ClockSync {
+    samples: VecDeque<ClockSample>,
+}
+
+impl ClockSync {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A probe sent at `sent_ms` was answered with the host's `host_ms` and
+    /// arrived at `received_ms` (both on our wall clock)
+    pub fn record(&mut self, sent_ms: u64, host_ms: u64, received_ms: u64) {
+        let round_trip_ms = received_ms.saturating_sub(sent_ms);
+        let midpoint = sent_ms + round_trip_ms / 2;
+        self.samples.push_back(ClockSample {
+            offset_ms: host_ms as i64 - midpoint as i64,
+            round_trip_ms,
+        });
+        if self.samples.len() > CLOCK_SAMPLES {
+            self.samples.pop_front();
+        }
+    }
+
+    fn best(&self) -> Option<&ClockSample> {
+        self.samples
+            .iter()
+            .min_by_key(|sample| sample.round_trip_ms)
+    }
+
+    /// How far the host's clock is ahead of ours (`None` before the first
+    /// answer)
+    pub fn offset_ms(&self) -> Option<i64> {
+        self.best().map(|sample| sample.offset_ms)
+    }
+
+    /// Worst-case error of [`offset_ms`](Self::offset_ms)
+    pub fn accuracy_ms(&self) -> Option<u64> {
+        self.best().map(|sample| sample.round_trip_ms / 2)
+    }
+
+    /// Our wall clock time `local_ms` (Unix ms) on the host's clock; our own
+    /// clock until the first answer
+    pub fn now_ms(&self, local_ms: u64) -> u64 {
+        local_ms.saturating_add_signed(self.offset_ms().unwrap_or(0))
+    }
+
+    /// Forget the samples, e.g. when another peer becomes the host
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+}
+
+/// Stamp a result with the host's time it arrived at (HOST ONLY)
+///
+/// Replaces whatever the submitter put there, so nobody can backdate an
+/// answer.
+pub(crate) fn stamp_submission(command: &mut DomainCommand, host_time_ms: u64) {
+    if let DomainCommand::SubmitResult { result, .. } = command {
+        result.submitted_at_ms = Some(host_time_ms);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_core::domain::ActivityResult;
+
+    #[test]
+    fn test_offset_comes_from_the_fastest_round_trip() {
+        let mut sync = ClockSync::new();
+        assert_eq!(sync.offset_ms(), None);
+
+        // Host is 5s ahead; a 40ms round trip, stamped halfway
+        sync.record(1_000, 6_020, 1_040);
+        assert_eq!(sync.offset_ms(), Some(5_000));
+        assert_eq!(sync.accuracy_ms(), Some(20));
+
+        // Queued on the way back: slower and skewed, so ignored
+        sync.record(2_000, 7_010, 2_300);
+        assert_eq!(sync.offset_ms(), Some(5_000));
+
+        // Host behind us works too
+        let mut behind = ClockSync::new();
+        behind.record(10_000, 7_005, 10_010);
+        assert_eq!(behind.offset_ms(), Some(-3_000));
+
+        for sent in 0..CLOCK_SAMPLES as u64 {
+            sync.record(sent * 1_000, sent * 1_000 + 5_030, sent * 1_000 + 60);
+        }
+        // The 40ms sample was pushed out
+        assert_eq!(sync.offset_ms(), Some(5_000));
+        assert_eq!(sync.accuracy_ms(), Some(30));
+
+        sync.reset();
+        assert_eq!(sync.offset_ms(), None);
+    }
+
+    #[test]
+    fn test_host_stamp_replaces_the_submitted_time() {
+        let run_id = uuid::Uuid::new_v4();
+        let mut command = DomainCommand::SubmitResult {
+            lobby_id: uuid::Uuid::new_v4(),
+            run_id,
+            result: ActivityResult::new(run_id, uuid::Uuid::new_v4()).with_submitted_at(1),
+        };
+
+        stamp_submission(&mut command, 5_000);
+
+        let DomainCommand::SubmitResult { result, .. } = command else {
+            unreachable!()
+        };
+        assert_eq!(result.submitted_at_ms, Some(5_000));
+    }
+}
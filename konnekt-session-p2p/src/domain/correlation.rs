@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+/// Domain value: Identifies one user action across peers
+///
+/// Minted when a command is submitted and carried with it to the host and
+/// on the resulting event to every guest, so all spans of the action share it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CorrelationId(Uuid);
+
+impl CorrelationId {
+    /// Create a new random correlation ID
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    pub fn inner(&self) -> Uuid {
+        self.0
+    }
+}
+
+impl Default for CorrelationId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
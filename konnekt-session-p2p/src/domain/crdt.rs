@@ -0,0 +1,555 @@
+use konnekt_session_core::ParticipationMode;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use uuid::Uuid;
+
+/// How lobby state is replicated between peers
+///
+/// - `EventLog`: every change goes through the host, which sequences it
+/// - `Crdt`: participant set, participation modes and chat are CRDTs that any
+///   peer can change and that converge without the host (e.g. while it is
+///   unreachable). Activities and runs still go through the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SyncMode {
+    #[default]
+    EventLog,
+    Crdt,
+}
+
+impl SyncMode {
+    pub fn is_crdt(&self) -> bool {
+        matches!(self, Self::Crdt)
+    }
+}
+
+impl std::fmt::Display for SyncMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EventLog => write!(f, "EventLog"),
+            Self::Crdt => write!(f, "Crdt"),
+        }
+    }
+}
+
+/// Lamport timestamp of one replica; orders LWW writes and tags OR-set adds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Dot {
+    pub counter: u64,
+    pub replica: Uuid,
+}
+
+/// Last-writer-wins register (ties broken by replica ID)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LwwRegister<T> {
+    value: T,
+    stamp: Dot,
+}
+
+impl<T: Clone + PartialEq> LwwRegister<T> {
+    pub fn new(value: T, stamp: Dot) -> Self {
+        Self { value, stamp }
+    }
+
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    pub fn stamp(&self) -> Dot {
+        self.stamp
+    }
+
+    /// Apply a write; returns whether it won
+    pub fn set(&mut self, value: T, stamp: Dot) -> bool {
+        if stamp <= self.stamp {
+            return false;
+        }
+        self.value = value;
+        self.stamp = stamp;
+        true
+    }
+
+    pub fn merge(&mut self, other: &Self) -> bool {
+        self.set(other.value.clone(), other.stamp)
+    }
+}
+
+/// Observed-remove set: a remove only cancels the adds it has seen, so a
+/// concurrent re-add survives
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrSet<T: Ord> {
+    entries: BTreeMap<T, BTreeSet<Dot>>,
+    tombstones: BTreeSet<Dot>,
+}
+
+impl<T: Ord> Default for OrSet<T> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            tombstones: BTreeSet::new(),
+        }
+    }
+}
+
+impl<T: Ord + Clone> OrSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `element` tagged with `dot`; returns whether the set changed
+    pub fn add(&mut self, element: T, dot: Dot) -> bool {
+        if self.tombstones.contains(&dot) {
+            return false;
+        }
+        self.entries.entry(element).or_default().insert(dot)
+    }
+
+    /// Remove `element` locally; returns the observed dots to replicate
+    pub fn remove(&mut self, element: &T) -> Vec<Dot> {
+        let dots: Vec<Dot> = self
+            .entries
+            .get(element)
+            .map(|dots| dots.iter().copied().collect())
+            .unwrap_or_default();
+        self.remove_dots(element, &dots);
+        dots
+    }
+
+    /// Apply a (possibly remote) remove of the given dots
+    pub fn remove_dots(&mut self, element: &T, dots: &[Dot]) -> bool {
+        self.tombstones.extend(dots.iter().copied());
+
+        let Some(live) = self.entries.get_mut(element) else {
+            return false;
+        };
+        let before = live.len();
+        live.retain(|dot| !dots.contains(dot));
+        let changed = live.len() != before;
+        if live.is_empty() {
+            self.entries.remove(element);
+        }
+        changed
+    }
+
+    pub fn contains(&self, element: &T) -> bool {
+        self.entries.contains_key(element)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.entries.keys()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn merge(&mut self, other: &Self) -> bool {
+        let mut changed = false;
+
+        for (element, dots) in &self.entries.clone() {
+            let removed: Vec<Dot> = dots
+                .iter()
+                .filter(|dot| other.tombstones.contains(dot))
+                .copied()
+                .collect();
+            changed |= self.remove_dots(element, &removed);
+        }
+        self.tombstones.extend(other.tombstones.iter().copied());
+
+        for (element, dots) in &other.entries {
+            for dot in dots {
+                changed |= self.add(element.clone(), *dot);
+            }
+        }
+        changed
+    }
+
+    fn max_counter(&self) -> u64 {
+        self.entries
+            .values()
+            .flatten()
+            .chain(&self.tombstones)
+            .map(|dot| dot.counter)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Chat message, ordered by its dot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub dot: Dot,
+    pub author: Uuid,
+    pub text: String,
+}
+
+/// Operation broadcast to every peer when a replica changes the lobby CRDT
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CrdtOp {
+    Join {
+        participant_id: Uuid,
+        name: String,
+        dot: Dot,
+    },
+    Leave {
+        participant_id: Uuid,
+        observed: Vec<Dot>,
+    },
+    SetMode {
+        participant_id: Uuid,
+        mode: ParticipationMode,
+        stamp: Dot,
+    },
+    Chat(ChatMessage),
+}
+
+impl CrdtOp {
+    pub fn is_chat(&self) -> bool {
+        matches!(self, Self::Chat(_))
+    }
+}
+
+/// Replicated lobby state (exchanged on connect for anti-entropy)
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct LobbyCrdtState {
+    participants: OrSet<Uuid>,
+    /// Names are fixed at join time; kept after leave so late merges agree
+    names: BTreeMap<Uuid, String>,
+    modes: BTreeMap<Uuid, LwwRegister<ParticipationMode>>,
+    chat: Vec<ChatMessage>,
+}
+
+impl LobbyCrdtState {
+    /// Apply a remote (or local) operation; idempotent and commutative
+    pub fn apply(&mut self, op: &CrdtOp) -> bool {
+        match op {
+            CrdtOp::Join {
+                participant_id,
+                name,
+                dot,
+            } => {
+                self.names
+                    .entry(*participant_id)
+                    .or_insert_with(|| name.clone());
+                self.participants.add(*participant_id, *dot)
+            }
+            CrdtOp::Leave {
+                participant_id,
+                observed,
+            } => self.participants.remove_dots(participant_id, observed),
+            CrdtOp::SetMode {
+                participant_id,
+                mode,
+                stamp,
+            } => match self.modes.get_mut(participant_id) {
+                Some(register) => register.set(*mode, *stamp),
+                None => {
+                    self.modes
+                        .insert(*participant_id, LwwRegister::new(*mode, *stamp));
+                    true
+                }
+            },
+            CrdtOp::Chat(message) => {
+                match self.chat.binary_search_by_key(&message.dot, |m| m.dot) {
+                    Ok(_) => false,
+                    Err(index) => {
+                        self.chat.insert(index, message.clone());
+                        true
+                    }
+                }
+            }
+        }
+    }
+
+    /// Merge another replica's full state
+    pub fn merge(&mut self, other: &Self) -> bool {
+        let mut changed = self.participants.merge(&other.participants);
+
+        for (id, name) in &other.names {
+            self.names.entry(*id).or_insert_with(|| name.clone());
+        }
+        for (id, register) in &other.modes {
+            changed |= self.apply(&CrdtOp::SetMode {
+                participant_id: *id,
+                mode: *register.get(),
+                stamp: register.stamp(),
+            });
+        }
+        for message in &other.chat {
+            changed |= self.apply(&CrdtOp::Chat(message.clone()));
+        }
+        changed
+    }
+
+    /// Participants whose membership or mode merging `other` would change
+    pub fn changed_by(&self, other: &Self) -> BTreeSet<Uuid> {
+        let mut merged = self.clone();
+        merged.merge(other);
+        merged
+            .names
+            .keys()
+            .chain(merged.modes.keys())
+            .filter(|id| {
+                !self.knows(id)
+                    || self.contains(id) != merged.contains(id)
+                    || self.mode(id) != merged.mode(id)
+            })
+            .copied()
+            .collect()
+    }
+
+    pub fn participants(&self) -> impl Iterator<Item = Uuid> + '_ {
+        self.participants.iter().copied()
+    }
+
+    pub fn contains(&self, participant_id: &Uuid) -> bool {
+        self.participants.contains(participant_id)
+    }
+
+    /// Whether the participant ever joined through the CRDT
+    pub fn knows(&self, participant_id: &Uuid) -> bool {
+        self.names.contains_key(participant_id)
+    }
+
+    pub fn name(&self, participant_id: &Uuid) -> Option<&str> {
+        self.names.get(participant_id).map(String::as_str)
+    }
+
+    pub fn mode(&self, participant_id: &Uuid) -> Option<ParticipationMode> {
+        self.modes.get(participant_id).map(|r| *r.get())
+    }
+
+    pub fn chat(&self) -> &[ChatMessage] {
+        &self.chat
+    }
+
+    fn max_counter(&self) -> u64 {
+        let modes = self.modes.values().map(|r| r.stamp().counter);
+        let chat = self.chat.iter().map(|m| m.dot.counter);
+        modes
+            .chain(chat)
+            .chain(std::iter::once(self.participants.max_counter()))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// One peer's replica of the lobby CRDT
+#[derive(Debug, Clone)]
+pub struct LobbyCrdt {
+    replica: Uuid,
+    clock: u64,
+    state: LobbyCrdtState,
+}
+
+impl LobbyCrdt {
+    pub fn new(replica: Uuid) -> Self {
+        Self {
+            replica,
+            clock: 0,
+            state: LobbyCrdtState::default(),
+        }
+    }
+
+    pub fn replica(&self) -> Uuid {
+        self.replica
+    }
+
+    pub fn state(&self) -> &LobbyCrdtState {
+        &self.state
+    }
+
+    fn next_dot(&mut self) -> Dot {
+        self.clock += 1;
+        Dot {
+            counter: self.clock,
+            replica: self.replica,
+        }
+    }
+
+    /// Add a participant; returns the op to broadcast
+    pub fn join(&mut self, participant_id: Uuid, name: String) -> CrdtOp {
+        let op = CrdtOp::Join {
+            participant_id,
+            name,
+            dot: self.next_dot(),
+        };
+        self.state.apply(&op);
+        op
+    }
+
+    /// Remove a participant (as far as we have observed it)
+    pub fn leave(&mut self, participant_id: Uuid) -> CrdtOp {
+        let observed = self.state.participants.remove(&participant_id);
+        CrdtOp::Leave {
+            participant_id,
+            observed,
+        }
+    }
+
+    pub fn set_mode(&mut self, participant_id: Uuid, mode: ParticipationMode) -> CrdtOp {
+        let op = CrdtOp::SetMode {
+            participant_id,
+            mode,
+            stamp: self.next_dot(),
+        };
+        self.state.apply(&op);
+        op
+    }
+
+    pub fn chat(&mut self, author: Uuid, text: String) -> CrdtOp {
+        let op = CrdtOp::Chat(ChatMessage {
+            dot: self.next_dot(),
+            author,
+            text,
+        });
+        self.state.apply(&op);
+        op
+    }
+
+    /// Apply an op from another replica
+    pub fn apply(&mut self, op: &CrdtOp) -> bool {
+        let counter = match op {
+            CrdtOp::Join { dot, .. } => dot.counter,
+            CrdtOp::Leave { observed, .. } => observed.iter().map(|d| d.counter).max().unwrap_or(0),
+            CrdtOp::SetMode { stamp, .. } => stamp.counter,
+            CrdtOp::Chat(message) => message.dot.counter,
+        };
+        self.clock = self.clock.max(counter);
+        self.state.apply(op)
+    }
+
+    /// Merge another replica's full state
+    pub fn merge(&mut self, other: &LobbyCrdtState) -> bool {
+        self.clock = self.clock.max(other.max_counter());
+        self.state.merge(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replica(n: u128) -> LobbyCrdt {
+        LobbyCrdt::new(Uuid::from_u128(n))
+    }
+
+    #[test]
+    fn test_lww_register_highest_stamp_wins() {
+        let a = Dot {
+            counter: 1,
+            replica: Uuid::from_u128(1),
+        };
+        let b = Dot {
+            counter: 1,
+            replica: Uuid::from_u128(2),
+        };
+
+        let mut register = LwwRegister::new("a", a);
+        assert!(register.set("b", b));
+        assert!(!register.set("a", a));
+        assert_eq!(*register.get(), "b");
+    }
+
+    #[test]
+    fn test_concurrent_add_wins_over_remove() {
+        let mut a = replica(1);
+        let mut b = replica(2);
+        let alice = Uuid::new_v4();
+
+        let join = a.join(alice, "Alice".to_string());
+        b.apply(&join);
+
+        // Concurrently: A removes Alice, B re-adds her
+        let leave = a.leave(alice);
+        let rejoin = b.join(alice, "Alice".to_string());
+        a.apply(&rejoin);
+        b.apply(&leave);
+
+        assert!(a.state().contains(&alice));
+        assert_eq!(a.state(), b.state());
+    }
+
+    #[test]
+    fn test_ops_commute_and_are_idempotent() {
+        let mut a = replica(1);
+        let alice = Uuid::new_v4();
+        let ops = vec![
+            a.join(alice, "Alice".to_string()),
+            a.set_mode(alice, ParticipationMode::Spectating),
+            a.chat(alice, "hi".to_string()),
+            a.set_mode(alice, ParticipationMode::Active),
+        ];
+
+        let mut forward = replica(2);
+        let mut backward = replica(3);
+        for op in &ops {
+            forward.apply(op);
+            forward.apply(op);
+        }
+        for op in ops.iter().rev() {
+            backward.apply(op);
+        }
+
+        assert_eq!(forward.state(), a.state());
+        assert_eq!(backward.state(), a.state());
+        assert_eq!(a.state().mode(&alice), Some(ParticipationMode::Active));
+        assert_eq!(a.state().chat().len(), 1);
+    }
+
+    #[test]
+    fn test_partitioned_replicas_converge_on_merge() {
+        let mut a = replica(1);
+        let mut b = replica(2);
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+
+        b.apply(&a.join(alice, "Alice".to_string()));
+
+        // Partition: both sides change things independently
+        a.join(bob, "Bob".to_string());
+        a.chat(alice, "from a".to_string());
+        b.set_mode(alice, ParticipationMode::Spectating);
+        b.leave(alice);
+        b.chat(alice, "from b".to_string());
+
+        let (state_a, state_b) = (a.state().clone(), b.state().clone());
+        assert!(a.merge(&state_b));
+        assert!(b.merge(&state_a));
+
+        assert_eq!(a.state(), b.state());
+        assert!(!a.state().contains(&alice));
+        assert!(a.state().knows(&alice));
+        assert!(a.state().contains(&bob));
+        assert_eq!(a.state().chat().len(), 2);
+
+        // Clocks moved past everything seen, so new writes win
+        let op = a.set_mode(alice, ParticipationMode::Active);
+        assert!(b.apply(&op));
+    }
+
+    #[test]
+    fn test_changed_by_lists_touched_participants() {
+        let mut a = replica(1);
+        let mut b = replica(2);
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+
+        b.apply(&a.join(alice, "Alice".to_string()));
+        b.apply(&a.join(bob, "Bob".to_string()));
+        assert!(a.state().changed_by(b.state()).is_empty());
+
+        b.leave(alice);
+        b.chat(bob, "hi".to_string());
+        assert_eq!(a.state().changed_by(b.state()), BTreeSet::from([alice]));
+
+        b.set_mode(bob, ParticipationMode::Spectating);
+        assert_eq!(
+            a.state().changed_by(b.state()),
+            BTreeSet::from([alice, bob])
+        );
+    }
+}
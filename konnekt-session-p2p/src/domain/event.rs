@@ -1,5 +1,6 @@
+use crate::domain::CorrelationId;
 use konnekt_session_core::{
-    Participant, Timestamp,
+    LobbySettings, Participant, Timestamp,
     domain::{ActivityConfig, ActivityResult, ActivityRunId, RunStatus},
 };
 use serde::{Deserialize, Serialize};
@@ -26,6 +27,8 @@ pub enum DomainEvent {
     GuestKicked {
         participant_id: Uuid,
         kicked_by: Uuid,
+        #[serde(default)]
+        banned: bool,
     },
 
     HostDelegated {
@@ -34,6 +37,17 @@ pub enum DomainEvent {
         reason: DelegationReason,
     },
 
+    CoHostChanged {
+        participant_id: Uuid,
+        co_host: bool,
+        changed_by: Uuid,
+    },
+
+    LobbySettingsChanged {
+        settings: LobbySettings,
+        changed_by: Uuid,
+    },
+
     ParticipationModeChanged {
         participant_id: Uuid,
         new_mode: String,
@@ -50,6 +64,9 @@ pub enum DomainEvent {
         run_id: ActivityRunId,
         config: ActivityConfig,
         required_submitters: Vec<Uuid>,
+        /// When the host started the run, on its clock (Unix ms)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        started_at_ms: Option<u64>,
     },
 
     ResultSubmitted {
@@ -62,6 +79,11 @@ pub enum DomainEvent {
         status: RunStatus,
         results: Vec<ActivityResult>,
     },
+
+    // ── Chat events ───────────────────────────────────────────────────────────
+    ChatMessageSent {
+        message: konnekt_session_core::ChatMessage,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -79,31 +101,61 @@ pub struct LobbyEvent {
     pub sequence: u64,
     pub lobby_id: Uuid,
     pub timestamp: Timestamp,
+    /// Host epoch this event was sequenced in (bumped on every host takeover)
+    #[serde(default)]
+    pub epoch: u64,
     pub event: DomainEvent,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub signature: Option<Vec<u8>>,
+    /// The user action that produced this event (tracing)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<CorrelationId>,
 }
 
 impl LobbyEvent {
     pub fn new(sequence: u64, lobby_id: Uuid, event: DomainEvent) -> Self {
+        Self::new_at(sequence, lobby_id, event, Timestamp::now())
+    }
+
+    /// Like [`LobbyEvent::new`], stamped with a session-clock time
+    pub fn new_at(sequence: u64, lobby_id: Uuid, event: DomainEvent, timestamp: Timestamp) -> Self {
         Self {
             sequence,
             lobby_id,
-            timestamp: Timestamp::now(),
+            timestamp,
+            epoch: 0,
             event,
             signature: None,
+            correlation_id: None,
         }
     }
 
     pub fn without_sequence(lobby_id: Uuid, event: DomainEvent) -> Self {
+        Self::without_sequence_at(lobby_id, event, Timestamp::now())
+    }
+
+    /// Like [`LobbyEvent::without_sequence`], stamped with a session-clock time
+    pub fn without_sequence_at(lobby_id: Uuid, event: DomainEvent, timestamp: Timestamp) -> Self {
         Self {
             sequence: 0,
             lobby_id,
-            timestamp: Timestamp::now(),
+            timestamp,
+            epoch: 0,
             event,
             signature: None,
+            correlation_id: None,
         }
     }
+
+    pub fn with_epoch(mut self, epoch: u64) -> Self {
+        self.epoch = epoch;
+        self
+    }
+
+    pub fn with_correlation(mut self, correlation_id: Option<CorrelationId>) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -161,6 +213,7 @@ mod tests {
             run_id,
             config,
             required_submitters: vec![p1, p2],
+            started_at_ms: Some(1_000),
         };
 
         let json = serde_json::to_string(&event).unwrap();
@@ -169,9 +222,11 @@ mod tests {
         match deserialized {
             DomainEvent::RunStarted {
                 required_submitters,
+                started_at_ms,
                 ..
             } => {
                 assert_eq!(required_submitters.len(), 2);
+                assert_eq!(started_at_ms, Some(1_000));
             }
             _ => panic!("Expected RunStarted"),
         }
@@ -51,7 +51,7 @@ impl EventLog {
     pub fn append(&mut self, mut event: LobbyEvent) -> u64 {
         event.sequence = self.next_sequence;
         let assigned_seq = self.next_sequence;
-        self.next_sequence += 1;
+        self.next_sequence = self.next_sequence.saturating_add(1);
 
         debug!(
             sequence = %assigned_seq,
@@ -163,6 +163,16 @@ impl EventLog {
         self.next_sequence
     }
 
+    /// Continue sequencing after the highest event seen so far
+    ///
+    /// Used when a guest takes over as host: new events must follow the
+    /// sequence numbers assigned by the previous host.
+    #[instrument(skip(self), fields(highest_seen = %self.highest_seen))]
+    pub fn resume_sequencing(&mut self) {
+        self.next_sequence = self.next_sequence.max(self.highest_seen.saturating_add(1));
+        debug!(next_sequence = %self.next_sequence, "Resumed sequencing from event log");
+    }
+
     /// Check if we're missing any events between oldest and highest
     #[instrument(skip(self), fields(
         event_count = %self.events.len(),
@@ -356,4 +366,25 @@ mod tests {
         assert!(log.get(7).is_none());
         assert!(log.get(10).is_some());
     }
+
+    #[test]
+    fn test_resume_sequencing_after_takeover() {
+        let mut log = EventLog::new();
+        let lobby_id = Uuid::new_v4();
+
+        for seq in 1..=4 {
+            log.add_event(create_test_event(lobby_id, seq));
+        }
+        assert_eq!(log.next_sequence(), 1);
+
+        log.resume_sequencing();
+
+        let seq = log.append(LobbyEvent::without_sequence(
+            lobby_id,
+            DomainEvent::GuestLeft {
+                participant_id: Uuid::new_v4(),
+            },
+        ));
+        assert_eq!(seq, 5);
+    }
 }
@@ -0,0 +1,58 @@
+use crate::domain::PeerId;
+
+/// A peer's claim to be host: its epoch plus its peer ID as tie-break
+///
+/// Used to fence off stale hosts after a network split, when both sides may
+/// have promoted a host of their own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostFence {
+    pub epoch: u64,
+    pub host: PeerId,
+}
+
+impl HostFence {
+    pub fn new(epoch: u64, host: PeerId) -> Self {
+        Self { epoch, host }
+    }
+
+    /// Whether this claim wins over `other`
+    ///
+    /// The higher epoch wins; on equal epochs the lower peer ID wins, so every
+    /// peer resolves a simultaneous self-promotion the same way.
+    pub fn supersedes(&self, other: &HostFence) -> bool {
+        match self.epoch.cmp(&other.epoch) {
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Equal => self.host.inner().0 < other.host.inner().0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn peer(n: u128) -> PeerId {
+        PeerId::new(matchbox_socket::PeerId(Uuid::from_u128(n)))
+    }
+
+    #[test]
+    fn test_higher_epoch_wins() {
+        let old = HostFence::new(1, peer(1));
+        let new = HostFence::new(2, peer(9));
+
+        assert!(new.supersedes(&old));
+        assert!(!old.supersedes(&new));
+    }
+
+    #[test]
+    fn test_equal_epoch_tie_break_is_deterministic() {
+        let a = HostFence::new(3, peer(1));
+        let b = HostFence::new(3, peer(2));
+
+        assert!(a.supersedes(&b));
+        assert!(!b.supersedes(&a));
+        assert!(!a.supersedes(&a));
+    }
+}
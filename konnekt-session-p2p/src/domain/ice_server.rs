@@ -1,5 +1,11 @@
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use instant::{Duration, SystemTime};
 use serde::{Deserialize, Serialize};
 
+/// Default lifetime of TURN REST credentials
+pub const DEFAULT_TURN_REST_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
 /// ICE server configuration for WebRTC
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct IceServer {
@@ -9,6 +15,64 @@ pub struct IceServer {
     pub username: Option<String>,
     /// Credential for authentication (optional, required for TURN)
     pub credential: Option<String>,
+    /// Shared secret for time-limited credentials (never serialized)
+    #[serde(skip)]
+    pub rest_auth: Option<TurnRestAuth>,
+}
+
+/// Shared-secret auth for coturn's TURN REST API (`use-auth-secret`)
+///
+/// Credentials are derived locally: username `"<expiry>:<user>"`, credential
+/// `base64(HMAC-SHA1(secret, username))`.
+#[derive(Clone, PartialEq)]
+pub struct TurnRestAuth {
+    secret: String,
+    user: String,
+    ttl: Duration,
+    /// Unix timestamp (seconds) the current credentials expire at
+    expires_at: u64,
+}
+
+impl TurnRestAuth {
+    /// Unix timestamp (seconds) the current credentials expire at
+    pub fn expires_at(&self) -> u64 {
+        self.expires_at
+    }
+
+    /// Credentials are refreshed once less than a tenth of the TTL remains
+    fn needs_refresh(&self, now: u64) -> bool {
+        now + self.ttl.as_secs() / 10 >= self.expires_at
+    }
+}
+
+impl std::fmt::Debug for TurnRestAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TurnRestAuth")
+            .field("secret", &"<redacted>")
+            .field("user", &self.user)
+            .field("ttl", &self.ttl)
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+/// Derive a TURN REST username/credential pair
+fn rest_credentials(secret: &str, user: &str, expires_at: u64) -> (String, String) {
+    let username = format!("{}:{}", expires_at, user);
+
+    let mut mac = Hmac::<sha1::Sha1>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(username.as_bytes());
+    let credential = base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes());
+
+    (username, credential)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
 }
 
 impl IceServer {
@@ -18,6 +82,7 @@ impl IceServer {
             urls: vec![url],
             username: None,
             credential: None,
+            rest_auth: None,
         }
     }
 
@@ -27,7 +92,72 @@ impl IceServer {
             urls: vec![url],
             username: Some(username),
             credential: Some(credential),
+            rest_auth: None,
+        }
+    }
+
+    /// Create a TURN server using coturn's REST API shared secret
+    /// (`static-auth-secret`) instead of static credentials.
+    ///
+    /// Credentials are valid for [`DEFAULT_TURN_REST_TTL`] and re-derived by
+    /// [`IceServer::refresh`] before they expire.
+    pub fn turn_from_rest_endpoint(url: String, secret: String) -> Self {
+        let mut server = Self::from_urls(vec![url]);
+        server.rest_auth = Some(TurnRestAuth {
+            secret,
+            user: "konnekt".to_string(),
+            ttl: DEFAULT_TURN_REST_TTL,
+            expires_at: 0,
+        });
+        server.refresh();
+        server
+    }
+
+    /// Set the user part of TURN REST usernames
+    pub fn with_rest_user(mut self, user: impl Into<String>) -> Self {
+        if let Some(auth) = &mut self.rest_auth {
+            auth.user = user.into();
+            auth.expires_at = 0;
+        }
+        self.refresh();
+        self
+    }
+
+    /// Set the lifetime of TURN REST credentials
+    pub fn with_rest_ttl(mut self, ttl: Duration) -> Self {
+        if let Some(auth) = &mut self.rest_auth {
+            auth.ttl = ttl;
+            auth.expires_at = 0;
+        }
+        self.refresh();
+        self
+    }
+
+    /// Re-derive TURN REST credentials if they are close to expiry.
+    /// Returns true if new credentials were issued.
+    pub fn refresh(&mut self) -> bool {
+        self.refresh_at(unix_now())
+    }
+
+    fn refresh_at(&mut self, now: u64) -> bool {
+        let Some(auth) = &mut self.rest_auth else {
+            return false;
+        };
+
+        if !auth.needs_refresh(now) {
+            return false;
         }
+
+        auth.expires_at = now + auth.ttl.as_secs();
+        let (username, credential) = rest_credentials(&auth.secret, &auth.user, auth.expires_at);
+        tracing::debug!(
+            "Issued TURN REST credentials valid until {}",
+            auth.expires_at
+        );
+
+        self.username = Some(username);
+        self.credential = Some(credential);
+        true
     }
 
     /// Create from multiple URLs (for failover)
@@ -36,6 +166,7 @@ impl IceServer {
             urls,
             username: None,
             credential: None,
+            rest_auth: None,
         }
     }
 
@@ -109,6 +240,67 @@ mod tests {
         assert_eq!(server.urls, urls);
     }
 
+    #[test]
+    fn test_rest_credentials_match_coturn() {
+        let (username, credential) = rest_credentials("s3cret", "konnekt", 1_700_000_000);
+        assert_eq!(username, "1700000000:konnekt");
+        assert_eq!(credential, "Gmxm4ur8px/oq4GsjkqZdL+TUio=");
+    }
+
+    #[test]
+    fn test_turn_from_rest_endpoint() {
+        let server = IceServer::turn_from_rest_endpoint(
+            "turn:turn.example.com:3478".to_string(),
+            "s3cret".to_string(),
+        )
+        .with_rest_user("alice");
+
+        let auth = server.rest_auth.as_ref().unwrap();
+        let username = server.username.as_ref().unwrap();
+        assert_eq!(username, &format!("{}:alice", auth.expires_at()));
+        assert!(auth.expires_at() > unix_now());
+        assert!(server.credential.is_some());
+    }
+
+    #[test]
+    fn test_rest_credentials_refresh_before_expiry() {
+        let mut server = IceServer::turn_from_rest_endpoint(
+            "turn:turn.example.com:3478".to_string(),
+            "s3cret".to_string(),
+        )
+        .with_rest_ttl(Duration::from_secs(1000));
+        let expires_at = server.rest_auth.as_ref().unwrap().expires_at();
+
+        assert!(!server.refresh_at(expires_at - 500));
+        assert!(server.refresh_at(expires_at - 50));
+        assert_eq!(
+            server.rest_auth.as_ref().unwrap().expires_at(),
+            expires_at - 50 + 1000
+        );
+    }
+
+    #[test]
+    fn test_static_servers_never_refresh() {
+        let mut server = IceServer::turn(
+            "turn:turn.example.com:3478".to_string(),
+            "user".to_string(),
+            "pass".to_string(),
+        );
+        assert!(!server.refresh());
+        assert_eq!(server.username, Some("user".to_string()));
+    }
+
+    #[test]
+    fn test_secret_not_serialized() {
+        let server = IceServer::turn_from_rest_endpoint(
+            "turn:turn.example.com:3478".to_string(),
+            "s3cret".to_string(),
+        );
+        let json = serde_json::to_string(&server).unwrap();
+        assert!(!json.contains("s3cret"));
+        assert!(!format!("{:?}", server).contains("s3cret"));
+    }
+
     #[test]
     fn test_serialization() {
         let server = IceServer::turn(
@@ -1,15 +1,39 @@
+mod capabilities;
+mod clock_sync;
+mod correlation;
+mod crdt;
 mod event;
 mod event_log;
+mod host_fence;
 mod ice_server;
 mod peer;
 mod peer_participant_map;
 mod peer_state;
+mod presence;
+mod rate_limiter;
+mod resume_token;
 mod session;
+mod timeout;
+mod topology;
 
+pub use capabilities::Capabilities;
+pub(crate) use clock_sync::stamp_submission;
+pub use clock_sync::{CLOCK_SAMPLES, ClockSync};
+pub use correlation::CorrelationId;
+pub use crdt::{ChatMessage, CrdtOp, Dot, LobbyCrdt, LobbyCrdtState, LwwRegister, OrSet, SyncMode};
 pub use event::{DelegationReason, DomainEvent, LobbyEvent};
 pub use event_log::EventLog;
-pub use ice_server::IceServer;
+pub use host_fence::HostFence;
+pub use ice_server::{DEFAULT_TURN_REST_TTL, IceServer, TurnRestAuth};
 pub use peer::{MatchboxPeerId, PeerId};
 pub use peer_participant_map::PeerParticipantMap;
-pub use peer_state::{PeerRegistry, PeerState};
+pub use peer_state::{ConnectionQuality, ConnectionStatus, PeerRegistry, PeerState, PeerStats};
+pub use presence::{PRESENCE_TTL, Presence, PresenceMap};
+pub use rate_limiter::{PeerRateLimiter, RateDecision, RateLimit};
+pub use resume_token::ResumeToken;
 pub use session::SessionId;
+pub use timeout::{DEFAULT_GRACE_PERIOD, SILENCE_HEARTBEATS, TimeoutConfig, TimeoutPolicy};
+pub use topology::Topology;
+
+// Shared with the domain core, so timestamps follow a test clock too
+pub use konnekt_session_core::domain::{Clock, SharedClock, SystemClock, TestClock, clock};
@@ -1,18 +1,23 @@
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use crate::domain::PeerId;
+use crate::domain::{PeerId, ResumeToken};
 
 /// Enforces 1:1 bidirectional mapping between peers and participants
 ///
 /// This is a core domain invariant: every peer corresponds to exactly one participant,
 /// and every participant corresponds to exactly one peer.
+///
+/// Resume tokens outlive the peer mapping: a participant whose peer dropped can be
+/// re-bound to a new peer by presenting its token.
 #[derive(Debug, Default, Clone)]
 pub struct PeerParticipantMap {
     /// Peer ID → Participant ID
     peer_to_participant: HashMap<PeerId, Uuid>,
     /// Participant ID → Peer ID
     participant_to_peer: HashMap<Uuid, PeerId>,
+    /// Resume token → Participant ID
+    tokens: HashMap<ResumeToken, Uuid>,
 }
 
 impl PeerParticipantMap {
@@ -61,6 +66,45 @@ impl PeerParticipantMap {
         }
     }
 
+    /// Issue a resume token for a participant (returns the existing one if already issued)
+    pub fn issue_token(&mut self, participant_id: Uuid) -> ResumeToken {
+        if let Some(token) = self.token_for(&participant_id) {
+            return token;
+        }
+
+        let token = ResumeToken::new();
+        self.tokens.insert(token, participant_id);
+        token
+    }
+
+    /// Get the resume token issued to a participant
+    pub fn token_for(&self, participant_id: &Uuid) -> Option<ResumeToken> {
+        self.tokens
+            .iter()
+            .find(|(_, id)| *id == participant_id)
+            .map(|(token, _)| *token)
+    }
+
+    /// Get the participant a resume token was issued to
+    pub fn participant_for_token(&self, token: &ResumeToken) -> Option<Uuid> {
+        self.tokens.get(token).copied()
+    }
+
+    /// Re-bind the participant owning `token` to a (new) peer
+    ///
+    /// Returns the participant ID, or `None` if the token is unknown.
+    pub fn resume(&mut self, peer_id: PeerId, token: &ResumeToken) -> Option<Uuid> {
+        let participant_id = self.participant_for_token(token)?;
+        self.register(peer_id, participant_id);
+        Some(participant_id)
+    }
+
+    /// Remove a participant entirely (mapping and resume token)
+    pub fn forget_participant(&mut self, participant_id: &Uuid) -> Option<PeerId> {
+        self.tokens.retain(|_, id| id != participant_id);
+        self.remove_by_participant(participant_id)
+    }
+
     /// Get participant ID for a peer
     pub fn get_participant(&self, peer_id: &PeerId) -> Option<Uuid> {
         self.peer_to_participant.get(peer_id).copied()
@@ -110,6 +154,7 @@ impl PeerParticipantMap {
     pub fn clear(&mut self) {
         self.peer_to_participant.clear();
         self.participant_to_peer.clear();
+        self.tokens.clear();
     }
 }
 
@@ -314,6 +359,59 @@ mod tests {
         assert!(map.is_empty());
     }
 
+    #[test]
+    fn test_issue_token_is_stable() {
+        let mut map = PeerParticipantMap::new();
+        let participant = Uuid::new_v4();
+
+        let token = map.issue_token(participant);
+
+        assert_eq!(map.issue_token(participant), token);
+        assert_eq!(map.token_for(&participant), Some(token));
+        assert_eq!(map.participant_for_token(&token), Some(participant));
+    }
+
+    #[test]
+    fn test_resume_rebinds_to_new_peer() {
+        let mut map = PeerParticipantMap::new();
+        let old_peer = create_peer();
+        let new_peer = create_peer();
+        let participant = Uuid::new_v4();
+
+        map.register(old_peer, participant);
+        let token = map.issue_token(participant);
+
+        // Peer drops, mapping removed but token survives
+        map.remove_by_peer(&old_peer);
+
+        assert_eq!(map.resume(new_peer, &token), Some(participant));
+        assert_eq!(map.get_peer(&participant), Some(new_peer));
+        assert_eq!(map.get_participant(&old_peer), None);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_resume_unknown_token() {
+        let mut map = PeerParticipantMap::new();
+
+        assert_eq!(map.resume(create_peer(), &ResumeToken::new()), None);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_forget_participant_revokes_token() {
+        let mut map = PeerParticipantMap::new();
+        let peer = create_peer();
+        let participant = Uuid::new_v4();
+
+        map.register(peer, participant);
+        let token = map.issue_token(participant);
+
+        assert_eq!(map.forget_participant(&participant), Some(peer));
+        assert_eq!(map.participant_for_token(&token), None);
+        assert_eq!(map.resume(create_peer(), &token), None);
+    }
+
     #[test]
     fn test_bidirectional_invariant() {
         let mut map = PeerParticipantMap::new();
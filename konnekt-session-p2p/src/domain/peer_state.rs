@@ -1,6 +1,6 @@
-use crate::domain::PeerId;
+use crate::domain::{DEFAULT_GRACE_PERIOD, PeerId, SharedClock, SystemClock};
 use instant::{Duration, Instant};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// Connection status of a peer
@@ -29,11 +29,17 @@ pub struct PeerState {
     pub name: Option<String>,
     /// Whether this peer is a host
     pub is_host: bool,
+    /// Latest heartbeat round trip (`None` until the peer answers one)
+    pub rtt: Option<Duration>,
+    /// Messages sent to this peer (directly or as part of a broadcast)
+    pub messages_sent: u64,
+    /// Messages received from this peer
+    pub messages_received: u64,
 }
 
 impl PeerState {
-    pub fn new() -> Self {
-        let now = Instant::now();
+    /// A peer that connected at `now`
+    pub fn new(now: Instant) -> Self {
         Self {
             connected_at: now,
             last_seen: now,
@@ -41,12 +47,15 @@ impl PeerState {
             participant_id: None,
             name: None,
             is_host: false,
+            rtt: None,
+            messages_sent: 0,
+            messages_received: 0,
         }
     }
 
     /// Update the last seen timestamp
-    pub fn update_last_seen(&mut self) {
-        self.last_seen = Instant::now();
+    pub fn update_last_seen(&mut self, now: Instant) {
+        self.last_seen = now;
     }
 
     /// Set participant information
@@ -62,16 +71,16 @@ impl PeerState {
     }
 
     /// Mark as disconnected
-    pub fn mark_disconnected(&mut self) {
-        self.status = ConnectionStatus::Disconnected {
-            since: Instant::now(),
-        };
+    pub fn mark_disconnected(&mut self, now: Instant) {
+        self.status = ConnectionStatus::Disconnected { since: now };
     }
 
     /// Check if grace period has expired
-    pub fn check_grace_period(&mut self, grace_period: Duration) -> bool {
+    pub fn check_grace_period(&mut self, grace_period: Duration, now: Instant) -> bool {
         match self.status {
-            ConnectionStatus::Disconnected { since } if since.elapsed() >= grace_period => {
+            ConnectionStatus::Disconnected { since }
+                if now.saturating_duration_since(since) >= grace_period =>
+            {
                 self.status = ConnectionStatus::TimedOut;
                 true
             }
@@ -93,43 +102,117 @@ impl PeerState {
     }
 }
 
-impl Default for PeerState {
-    fn default() -> Self {
-        Self::new()
+/// How well a peer is keeping up, for red/yellow/green indicators
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConnectionQuality {
+    Good,
+    Fair,
+    Poor,
+}
+
+impl ConnectionQuality {
+    /// Round trips up to this are good
+    pub const GOOD_RTT: Duration = Duration::from_millis(150);
+    /// Round trips up to this are fair; slower ones are poor
+    pub const FAIR_RTT: Duration = Duration::from_millis(400);
+}
+
+/// Snapshot of one peer's connection, for display
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerStats {
+    pub peer_id: PeerId,
+    /// Participant bound to the peer (if known)
+    pub participant_id: Option<Uuid>,
+    pub status: ConnectionStatus,
+    pub rtt: Option<Duration>,
+    /// Time since we last heard from the peer
+    pub last_seen: Duration,
+    pub messages_sent: u64,
+    pub messages_received: u64,
+}
+
+impl PeerStats {
+    /// Disconnected peers are poor; otherwise the round trip decides
+    /// (unknown counts as fair: the peer may predate heartbeat acks)
+    pub fn quality(&self) -> ConnectionQuality {
+        if self.status != ConnectionStatus::Connected {
+            return ConnectionQuality::Poor;
+        }
+        match self.rtt {
+            Some(rtt) if rtt <= ConnectionQuality::GOOD_RTT => ConnectionQuality::Good,
+            Some(rtt) if rtt > ConnectionQuality::FAIR_RTT => ConnectionQuality::Poor,
+            _ => ConnectionQuality::Fair,
+        }
     }
 }
 
 /// Manages state for all connected peers
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct PeerRegistry {
     peers: HashMap<PeerId, PeerState>,
     grace_period: Duration,
+    /// Peers banned from the session (never re-added)
+    banned: HashSet<PeerId>,
+    /// Time for last seen, silence and grace periods
+    clock: SharedClock,
+}
+
+impl Default for PeerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PeerRegistry {
     pub fn new() -> Self {
-        Self {
-            peers: HashMap::new(),
-            grace_period: Duration::from_secs(30),
-        }
+        Self::with_grace_period(DEFAULT_GRACE_PERIOD)
     }
 
     pub fn with_grace_period(grace_period: Duration) -> Self {
         Self {
             peers: HashMap::new(),
             grace_period,
+            banned: HashSet::new(),
+            clock: SystemClock::shared(),
         }
     }
 
-    /// Add a new peer
+    /// Measure time on `clock` instead of real time
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    /// Add a new peer (banned peers are ignored)
     pub fn add_peer(&mut self, peer_id: PeerId) {
-        self.peers.insert(peer_id, PeerState::new());
+        if self.banned.contains(&peer_id) {
+            return;
+        }
+        self.peers.insert(peer_id, PeerState::new(self.clock.now()));
+    }
+
+    /// Ban a peer: forget its state and refuse to track it again
+    pub fn ban_peer(&mut self, peer_id: PeerId) -> Option<PeerState> {
+        self.banned.insert(peer_id);
+        self.peers.remove(&peer_id)
+    }
+
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.banned.contains(peer_id)
+    }
+
+    pub fn has_bans(&self) -> bool {
+        !self.banned.is_empty()
     }
 
     /// Mark a peer as disconnected (starts grace period)
     pub fn mark_peer_disconnected(&mut self, peer_id: &PeerId) {
         if let Some(peer) = self.peers.get_mut(peer_id) {
-            peer.mark_disconnected();
+            peer.mark_disconnected(self.clock.now());
         }
     }
 
@@ -148,20 +231,101 @@ impl PeerRegistry {
         self.peers.get(peer_id)
     }
 
+    pub fn grace_period(&self) -> Duration {
+        self.grace_period
+    }
+
+    pub fn set_grace_period(&mut self, grace_period: Duration) {
+        self.grace_period = grace_period;
+    }
+
     /// Update last seen for a peer
+    ///
+    /// A message proves the peer is alive, so a peer marked disconnected
+    /// (but not yet timed out) counts as connected again.
     pub fn update_last_seen(&mut self, peer_id: &PeerId) {
         if let Some(peer) = self.peers.get_mut(peer_id) {
-            peer.update_last_seen();
+            peer.update_last_seen(self.clock.now());
+            if matches!(peer.status, ConnectionStatus::Disconnected { .. }) {
+                peer.status = ConnectionStatus::Connected;
+            }
+        }
+    }
+
+    /// Count a message from a peer (also refreshes its last seen)
+    pub fn record_received(&mut self, peer_id: &PeerId) {
+        self.update_last_seen(peer_id);
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.messages_received += 1;
+        }
+    }
+
+    /// Count a message sent to a peer
+    pub fn record_sent(&mut self, peer_id: &PeerId) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.messages_sent += 1;
+        }
+    }
+
+    /// Remember the latest heartbeat round trip to a peer
+    pub fn record_rtt(&mut self, peer_id: &PeerId, rtt: Duration) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.rtt = Some(rtt);
+        }
+    }
+
+    /// Stats for every tracked peer except `except` (our own), oldest
+    /// connection first
+    pub fn stats(&self, except: Option<PeerId>) -> Vec<PeerStats> {
+        let now = self.clock.now();
+        let mut peers: Vec<_> = self
+            .peers
+            .iter()
+            .filter(|(peer_id, _)| Some(**peer_id) != except)
+            .collect();
+        peers.sort_by_key(|(_, state)| state.connected_at);
+
+        peers
+            .into_iter()
+            .map(|(peer_id, state)| PeerStats {
+                peer_id: *peer_id,
+                participant_id: state.participant_id,
+                status: state.status,
+                rtt: state.rtt,
+                last_seen: now.saturating_duration_since(state.last_seen),
+                messages_sent: state.messages_sent,
+                messages_received: state.messages_received,
+            })
+            .collect()
+    }
+
+    /// Mark connected peers not heard from for `silence` as disconnected
+    /// (starts their grace period). `except` is our own peer.
+    pub fn mark_silent_peers(&mut self, silence: Duration, except: Option<PeerId>) -> Vec<PeerId> {
+        let now = self.clock.now();
+        let mut silent = Vec::new();
+
+        for (peer_id, peer_state) in self.peers.iter_mut() {
+            if Some(*peer_id) != except
+                && peer_state.status == ConnectionStatus::Connected
+                && now.saturating_duration_since(peer_state.last_seen) >= silence
+            {
+                peer_state.mark_disconnected(now);
+                silent.push(*peer_id);
+            }
         }
+
+        silent
     }
 
     /// Check all disconnected peers for grace period expiration
     /// Returns list of peers that have timed out
     pub fn check_grace_periods(&mut self) -> Vec<PeerId> {
+        let now = self.clock.now();
         let mut timed_out = Vec::new();
 
         for (peer_id, peer_state) in self.peers.iter_mut() {
-            if peer_state.check_grace_period(self.grace_period) {
+            if peer_state.check_grace_period(self.grace_period, now) {
                 timed_out.push(*peer_id);
             }
         }
@@ -210,52 +374,137 @@ impl PeerRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::TestClock;
 
     #[test]
     fn test_connection_status() {
-        let mut state = PeerState::new();
+        let now = Instant::now();
+        let mut state = PeerState::new(now);
         assert_eq!(state.status, ConnectionStatus::Connected);
 
-        state.mark_disconnected();
+        state.mark_disconnected(now);
         assert!(state.is_disconnected());
         assert!(!state.is_timed_out());
     }
 
     #[test]
     fn test_grace_period_expiry() {
-        let mut state = PeerState::new();
-        state.mark_disconnected();
+        let now = Instant::now();
+        let mut state = PeerState::new(now);
+        state.mark_disconnected(now);
 
         // Should not expire immediately
-        let expired = state.check_grace_period(Duration::from_secs(30));
+        let expired = state.check_grace_period(Duration::from_secs(30), now);
         assert!(!expired);
         assert!(!state.is_timed_out());
 
-        // Simulate time passage (use zero duration for testing)
-        let expired = state.check_grace_period(Duration::from_millis(0));
+        let expired =
+            state.check_grace_period(Duration::from_secs(30), now + Duration::from_secs(30));
         assert!(expired);
         assert!(state.is_timed_out());
     }
 
     #[test]
     fn test_peer_registry_disconnection() {
-        let mut registry = PeerRegistry::new();
+        let clock = TestClock::new();
+        let mut registry =
+            PeerRegistry::with_grace_period(Duration::from_secs(30)).with_clock(clock.shared());
         let peer_id = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
 
         registry.add_peer(peer_id);
         assert_eq!(registry.peer_count(), 1);
 
         registry.mark_peer_disconnected(&peer_id);
+        clock.advance(Duration::from_secs(29));
+        assert!(registry.check_grace_periods().is_empty());
         assert_eq!(registry.peer_count(), 1); // Still counted during grace period
 
-        // After timeout
-        registry
-            .get_peer_mut(&peer_id)
-            .unwrap()
-            .check_grace_period(Duration::from_millis(0));
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(registry.check_grace_periods(), vec![peer_id]);
         assert_eq!(registry.peer_count(), 0); // No longer counted
     }
 
+    #[test]
+    fn test_silent_peer_is_marked_disconnected_until_heard_from() {
+        let clock = TestClock::new();
+        let mut registry = PeerRegistry::new().with_clock(clock.shared());
+        let local = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+        let peer_id = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+        registry.add_peer(local);
+        registry.add_peer(peer_id);
+
+        clock.advance(Duration::from_secs(59));
+        assert!(
+            registry
+                .mark_silent_peers(Duration::from_secs(60), Some(local))
+                .is_empty()
+        );
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(
+            registry.mark_silent_peers(Duration::from_secs(60), Some(local)),
+            vec![peer_id]
+        );
+        assert!(registry.get_peer(&peer_id).unwrap().is_disconnected());
+        assert!(!registry.get_peer(&local).unwrap().is_disconnected());
+
+        registry.update_last_seen(&peer_id);
+        assert!(!registry.get_peer(&peer_id).unwrap().is_disconnected());
+    }
+
+    #[test]
+    fn test_stats_track_traffic_and_quality() {
+        let mut registry = PeerRegistry::new();
+        let local = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+        let peer_id = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+        registry.add_peer(local);
+        registry.add_peer(peer_id);
+
+        registry.record_received(&peer_id);
+        registry.record_received(&peer_id);
+        registry.record_sent(&peer_id);
+
+        let stats = registry.stats(Some(local));
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].peer_id, peer_id);
+        assert_eq!(stats[0].messages_received, 2);
+        assert_eq!(stats[0].messages_sent, 1);
+        assert_eq!(stats[0].rtt, None);
+        assert_eq!(stats[0].quality(), ConnectionQuality::Fair);
+
+        registry.record_rtt(&peer_id, Duration::from_millis(40));
+        assert_eq!(
+            registry.stats(Some(local))[0].quality(),
+            ConnectionQuality::Good
+        );
+
+        registry.record_rtt(&peer_id, Duration::from_millis(900));
+        assert_eq!(
+            registry.stats(Some(local))[0].quality(),
+            ConnectionQuality::Poor
+        );
+
+        registry.record_rtt(&peer_id, Duration::from_millis(40));
+        registry.mark_peer_disconnected(&peer_id);
+        assert_eq!(
+            registry.stats(Some(local))[0].quality(),
+            ConnectionQuality::Poor
+        );
+    }
+
+    #[test]
+    fn test_banned_peer_is_not_readded() {
+        let mut registry = PeerRegistry::new();
+        let peer_id = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+
+        registry.add_peer(peer_id);
+        assert!(registry.ban_peer(peer_id).is_some());
+        assert!(registry.is_banned(&peer_id));
+
+        registry.add_peer(peer_id);
+        assert!(registry.get_peer(&peer_id).is_none());
+        assert_eq!(registry.peer_count(), 0);
+    }
+
     #[test]
     fn test_find_host_excludes_timed_out() {
         let mut registry = PeerRegistry::new();
@@ -274,7 +523,7 @@ mod tests {
         registry
             .get_peer_mut(&host_peer)
             .unwrap()
-            .check_grace_period(Duration::from_millis(0));
+            .check_grace_period(Duration::ZERO, Instant::now());
 
         // Should not find timed-out host
         assert!(registry.find_host().is_none());
@@ -0,0 +1,126 @@
+use crate::domain::{SharedClock, SystemClock};
+use instant::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// How long a presence signal is shown unless it is refreshed
+pub const PRESENCE_TTL: Duration = Duration::from_secs(5);
+
+/// Domain value: What a participant is doing right now (ephemeral, never
+/// part of the lobby state)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Presence {
+    /// Typing a chat message
+    Typing,
+    /// Working on an answer for an activity run
+    Answering { run_id: Uuid },
+}
+
+/// Latest presence signal per participant
+///
+/// Signals expire after [`PRESENCE_TTL`], so a peer that goes quiet (or
+/// whose "stopped typing" message was dropped) disappears on its own.
+#[derive(Debug)]
+pub struct PresenceMap {
+    entries: HashMap<Uuid, (Presence, Instant)>,
+    clock: SharedClock,
+}
+
+impl Default for PresenceMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PresenceMap {
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            clock: SystemClock::shared(),
+        }
+    }
+
+    /// Expire signals on `clock` instead of real time
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    /// Record a signal (`None` clears it). Returns whether anything changed.
+    pub fn update(&mut self, participant_id: Uuid, presence: Option<Presence>) -> bool {
+        match presence {
+            Some(presence) => self
+                .entries
+                .insert(participant_id, (presence, self.clock.now()))
+                .is_none_or(|(previous, _)| previous != presence),
+            None => self.entries.remove(&participant_id).is_some(),
+        }
+    }
+
+    /// Current signal of a participant, if it hasn't expired
+    pub fn get(&self, participant_id: &Uuid) -> Option<Presence> {
+        self.entries
+            .get(participant_id)
+            .filter(|(_, at)| !self.is_expired(*at))
+            .map(|(presence, _)| *presence)
+    }
+
+    /// All signals that haven't expired, ordered by participant ID
+    pub fn active(&self) -> Vec<(Uuid, Presence)> {
+        let mut active: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|(_, (_, at))| !self.is_expired(*at))
+            .map(|(id, (presence, _))| (*id, *presence))
+            .collect();
+        active.sort_by_key(|(id, _)| *id);
+        active
+    }
+
+    /// Drop expired signals
+    pub fn prune(&mut self) {
+        let now = self.clock.now();
+        self.entries
+            .retain(|_, (_, at)| now.saturating_duration_since(*at) < PRESENCE_TTL);
+    }
+
+    fn is_expired(&self, at: Instant) -> bool {
+        self.clock.now().saturating_duration_since(at) >= PRESENCE_TTL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TestClock;
+
+    #[test]
+    fn test_presence_expires_unless_refreshed() {
+        let clock = TestClock::new();
+        let mut map = PresenceMap::new().with_clock(clock.shared());
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+
+        assert!(map.update(alice, Some(Presence::Typing)));
+        assert!(!map.update(alice, Some(Presence::Typing)));
+        map.update(bob, Some(Presence::Typing));
+
+        clock.advance(Duration::from_secs(3));
+        map.update(alice, Some(Presence::Typing));
+        clock.advance(Duration::from_secs(3));
+
+        assert_eq!(map.get(&alice), Some(Presence::Typing));
+        assert_eq!(map.get(&bob), None);
+        assert_eq!(map.active(), vec![(alice, Presence::Typing)]);
+
+        assert!(map.update(alice, None));
+        map.prune();
+        assert!(map.active().is_empty());
+    }
+}
@@ -0,0 +1,215 @@
+use crate::domain::PeerId;
+use instant::{Duration, Instant};
+use std::collections::HashMap;
+
+/// Inbound limits applied to every peer (except the host we follow)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Sustained messages per second (also the burst size)
+    pub messages_per_sec: u32,
+    /// Sustained bytes per second (also the burst size)
+    pub bytes_per_sec: u64,
+    /// Kick the peer's participant after this many violations (host only)
+    pub kick_after_violations: Option<u32>,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            messages_per_sec: 50,
+            bytes_per_sec: 256 * 1024,
+            kick_after_violations: None,
+        }
+    }
+}
+
+impl RateLimit {
+    pub fn new(messages_per_sec: u32, bytes_per_sec: u64) -> Self {
+        Self {
+            messages_per_sec,
+            bytes_per_sec,
+            kick_after_violations: None,
+        }
+    }
+
+    /// Automatically kick peers that keep exceeding the limit
+    pub fn with_auto_kick(mut self, violations: u32) -> Self {
+        self.kick_after_violations = Some(violations);
+        self
+    }
+}
+
+/// Outcome of checking one inbound message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateDecision {
+    Allow,
+    /// Drop the message. `violations` counts throttled seconds; `report` is
+    /// set at most once per second so floods don't flood the event queue too.
+    Throttle {
+        violations: u32,
+        report: bool,
+    },
+}
+
+/// Token bucket per peer
+#[derive(Debug, Clone)]
+struct Bucket {
+    messages: f64,
+    bytes: f64,
+    refilled_at: Instant,
+    violations: u32,
+    last_violation: Option<Instant>,
+}
+
+/// Per-peer token-bucket rate limiter
+#[derive(Debug, Clone)]
+pub struct PeerRateLimiter {
+    limit: RateLimit,
+    buckets: HashMap<PeerId, Bucket>,
+}
+
+impl PeerRateLimiter {
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            buckets: HashMap::new(),
+        }
+    }
+
+    pub fn limit(&self) -> RateLimit {
+        self.limit
+    }
+
+    /// Account for a message of `bytes` from `peer`, received at `now`
+    pub fn check(&mut self, peer: PeerId, bytes: usize, now: Instant) -> RateDecision {
+        let max_messages = self.limit.messages_per_sec as f64;
+        let max_bytes = self.limit.bytes_per_sec as f64;
+
+        let bucket = self.buckets.entry(peer).or_insert_with(|| Bucket {
+            messages: max_messages,
+            bytes: max_bytes,
+            refilled_at: now,
+            violations: 0,
+            last_violation: None,
+        });
+
+        let elapsed = now
+            .saturating_duration_since(bucket.refilled_at)
+            .as_secs_f64();
+        bucket.messages = (bucket.messages + elapsed * max_messages).min(max_messages);
+        bucket.bytes = (bucket.bytes + elapsed * max_bytes).min(max_bytes);
+        bucket.refilled_at = now;
+
+        if bucket.messages >= 1.0 && bucket.bytes >= bytes as f64 {
+            bucket.messages -= 1.0;
+            bucket.bytes -= bytes as f64;
+            return RateDecision::Allow;
+        }
+
+        let report = bucket
+            .last_violation
+            .is_none_or(|at| now.saturating_duration_since(at) >= Duration::from_secs(1));
+        if report {
+            bucket.violations += 1;
+            bucket.last_violation = Some(now);
+        }
+
+        RateDecision::Throttle {
+            violations: bucket.violations,
+            report,
+        }
+    }
+
+    /// Whether `violations` reached the auto-kick threshold
+    pub fn should_kick(&self, violations: u32) -> bool {
+        self.limit
+            .kick_after_violations
+            .is_some_and(|threshold| violations >= threshold)
+    }
+
+    /// Forget a peer (disconnected)
+    pub fn remove(&mut self, peer: &PeerId) {
+        self.buckets.remove(peer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn peer() -> PeerId {
+        PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_message_rate_limit() {
+        let mut limiter = PeerRateLimiter::new(RateLimit::new(3, 1024));
+        let peer = peer();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(limiter.check(peer, 10, now), RateDecision::Allow);
+        }
+        assert_eq!(
+            limiter.check(peer, 10, now),
+            RateDecision::Throttle {
+                violations: 1,
+                report: true
+            }
+        );
+        // Reported at most once per second
+        assert_eq!(
+            limiter.check(peer, 10, now),
+            RateDecision::Throttle {
+                violations: 1,
+                report: false
+            }
+        );
+
+        // Tokens refill over time
+        let later = now + Duration::from_millis(500);
+        assert_eq!(limiter.check(peer, 10, later), RateDecision::Allow);
+    }
+
+    #[test]
+    fn test_byte_rate_limit() {
+        let mut limiter = PeerRateLimiter::new(RateLimit::new(100, 100));
+        let peer = peer();
+        let now = Instant::now();
+
+        assert_eq!(limiter.check(peer, 80, now), RateDecision::Allow);
+        assert!(matches!(
+            limiter.check(peer, 80, now),
+            RateDecision::Throttle { .. }
+        ));
+        assert_eq!(
+            limiter.check(peer, 80, now + Duration::from_secs(1)),
+            RateDecision::Allow
+        );
+    }
+
+    #[test]
+    fn test_peers_are_limited_independently() {
+        let mut limiter = PeerRateLimiter::new(RateLimit::new(1, 1024));
+        let (a, b) = (peer(), peer());
+        let now = Instant::now();
+
+        assert_eq!(limiter.check(a, 1, now), RateDecision::Allow);
+        assert!(matches!(
+            limiter.check(a, 1, now),
+            RateDecision::Throttle { .. }
+        ));
+        assert_eq!(limiter.check(b, 1, now), RateDecision::Allow);
+    }
+
+    #[test]
+    fn test_auto_kick_threshold() {
+        let limiter = PeerRateLimiter::new(RateLimit::new(1, 1024).with_auto_kick(3));
+        assert!(!limiter.should_kick(2));
+        assert!(limiter.should_kick(3));
+
+        let limiter = PeerRateLimiter::new(RateLimit::default());
+        assert!(!limiter.should_kick(u32::MAX));
+    }
+}
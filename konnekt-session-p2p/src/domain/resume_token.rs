@@ -0,0 +1,63 @@
+use crate::infrastructure::error::{P2PError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+/// Domain value: Secret issued by the host on join so a reconnecting peer
+/// (with a new peer ID) can be re-bound to its existing participant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ResumeToken(Uuid);
+
+impl ResumeToken {
+    /// Create a new random resume token
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    /// Parse a resume token from a string
+    pub fn parse(s: &str) -> Result<Self> {
+        Uuid::parse_str(s)
+            .map(Self)
+            .map_err(|e| P2PError::InvalidResumeToken(e.to_string()))
+    }
+
+    /// Get the resume token as a string
+    pub fn as_str(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl Default for ResumeToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for ResumeToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_token_unique() {
+        assert_ne!(ResumeToken::new(), ResumeToken::new());
+    }
+
+    #[test]
+    fn test_resume_token_parse_roundtrip() {
+        let token = ResumeToken::new();
+        let parsed = ResumeToken::parse(&token.as_str()).unwrap();
+
+        assert_eq!(token, parsed);
+    }
+
+    #[test]
+    fn test_resume_token_parse_invalid() {
+        assert!(ResumeToken::parse("not-a-token").is_err());
+    }
+}
@@ -0,0 +1,97 @@
+use instant::Duration;
+use serde::{Deserialize, Serialize};
+
+/// How long a disconnected peer may take to come back (default)
+pub const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// Missed heartbeats after which a silent peer counts as disconnected
+pub const SILENCE_HEARTBEATS: u32 = 3;
+
+/// What the host does with a participant whose peer timed out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeoutPolicy {
+    /// Remove the participant from the lobby
+    #[default]
+    Remove,
+    /// Keep the participant (shown as away) so it can resume later
+    MarkAway,
+}
+
+/// Peer liveness settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeoutConfig {
+    /// How long a disconnected peer may take to come back
+    pub grace_period: Duration,
+    /// How often to send heartbeats (`None` disables them). Peers silent for
+    /// `SILENCE_HEARTBEATS` intervals count as disconnected.
+    pub heartbeat_interval: Option<Duration>,
+    pub policy: TimeoutPolicy,
+}
+
+impl Default for TimeoutConfig {
+    fn default() -> Self {
+        Self {
+            grace_period: DEFAULT_GRACE_PERIOD,
+            heartbeat_interval: None,
+            policy: TimeoutPolicy::default(),
+        }
+    }
+}
+
+impl TimeoutConfig {
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    pub fn with_heartbeat_interval(mut self, interval: Option<Duration>) -> Self {
+        self.heartbeat_interval = interval;
+        self
+    }
+
+    pub fn with_policy(mut self, policy: TimeoutPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Silence after which a connected peer counts as disconnected
+    pub fn silence_timeout(&self) -> Option<Duration> {
+        self.heartbeat_interval
+            .map(|interval| interval * SILENCE_HEARTBEATS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_matches_previous_behaviour() {
+        let config = TimeoutConfig::default();
+
+        assert_eq!(config.grace_period, Duration::from_secs(30));
+        assert_eq!(config.policy, TimeoutPolicy::Remove);
+        assert_eq!(config.silence_timeout(), None);
+    }
+
+    #[test]
+    fn test_silence_timeout_follows_heartbeats() {
+        let config = TimeoutConfig::default().with_heartbeat_interval(Some(Duration::from_secs(2)));
+
+        assert_eq!(config.silence_timeout(), Some(Duration::from_secs(6)));
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let config = TimeoutConfig::default()
+            .with_grace_period(Duration::from_secs(5))
+            .with_policy(TimeoutPolicy::MarkAway);
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            serde_json::from_str::<TimeoutConfig>(&json).unwrap(),
+            config
+        );
+    }
+}
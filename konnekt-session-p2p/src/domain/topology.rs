@@ -0,0 +1,79 @@
+use crate::domain::PeerId;
+use serde::{Deserialize, Serialize};
+
+/// Network topology used for guest-originated traffic
+///
+/// - `Mesh`: every peer has a direct WebRTC link to every other peer
+/// - `Star`: at least one guest pair could not connect directly, so guest
+///   traffic is routed through the host
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Topology {
+    #[default]
+    Mesh,
+    Star,
+}
+
+impl Topology {
+    /// Select the topology from the host's peer roster and our direct links.
+    ///
+    /// Falls back to `Star` as soon as any rostered peer (other than ourselves)
+    /// is missing from the set of directly connected peers.
+    pub fn detect(roster: &[PeerId], direct: &[PeerId], local: Option<PeerId>) -> Self {
+        let partial = roster
+            .iter()
+            .filter(|peer| Some(**peer) != local)
+            .any(|peer| !direct.contains(peer));
+
+        if partial { Self::Star } else { Self::Mesh }
+    }
+
+    pub fn is_star(&self) -> bool {
+        matches!(self, Self::Star)
+    }
+}
+
+impl std::fmt::Display for Topology {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mesh => write!(f, "Mesh"),
+            Self::Star => write!(f, "Star"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn create_peer() -> PeerId {
+        PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_full_mesh() {
+        let local = create_peer();
+        let a = create_peer();
+        let b = create_peer();
+
+        let topology = Topology::detect(&[local, a, b], &[a, b], Some(local));
+        assert_eq!(topology, Topology::Mesh);
+    }
+
+    #[test]
+    fn test_partial_mesh_selects_star() {
+        let local = create_peer();
+        let host = create_peer();
+        let unreachable = create_peer();
+
+        let topology = Topology::detect(&[local, host, unreachable], &[host], Some(local));
+        assert_eq!(topology, Topology::Star);
+        assert!(topology.is_star());
+    }
+
+    #[test]
+    fn test_empty_roster_is_mesh() {
+        let topology = Topology::detect(&[], &[], None);
+        assert_eq!(topology, Topology::Mesh);
+    }
+}
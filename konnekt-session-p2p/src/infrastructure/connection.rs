@@ -1,14 +1,73 @@
 use crate::application::ConnectionEvent;
 use crate::domain::{IceServer, PeerId};
 use crate::infrastructure::error::{P2PError, Result};
-use matchbox_socket::{RtcIceServerConfig, WebRtcSocket, WebRtcSocketBuilder};
+use futures::StreamExt;
+use futures::channel::mpsc;
+use instant::{Duration, Instant};
+use matchbox_socket::{Packet, RtcIceServerConfig, WebRtcSocket, WebRtcSocketBuilder};
+use std::collections::VecDeque;
 use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+/// First reconnect delay; doubles per failed attempt
+const RECONNECT_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound for the reconnect delay
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// How long a reconnect attempt may wait for a peer ID
+const RECONNECT_ATTEMPT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// A packet on the data channel, with the remote peer
+type ChannelMessage = (matchbox_socket::PeerId, Packet);
 
 /// Infrastructure adapter: Manages WebRTC connection via Matchbox signalling
+///
+/// If the signalling socket or its message loop dies, the connection
+/// reconnects with exponential backoff, which renegotiates ICE with every
+/// peer. Progress is reported via `ConnectionEvent::Reconnecting` /
+/// `ConnectionEvent::Reconnected`; the local peer ID changes on reconnect.
 pub struct MatchboxConnection {
     socket: Arc<Mutex<WebRtcSocket>>,
     local_peer_id: Option<PeerId>,
+
+    /// Data channel, taken out of the socket so `poll_ready` can wait on it
+    outgoing: mpsc::UnboundedSender<ChannelMessage>,
+    incoming: mpsc::UnboundedReceiver<ChannelMessage>,
+
+    /// Messages taken off `incoming` by `poll_ready`, not yet reported
+    received: VecDeque<ChannelMessage>,
+
+    /// Signalling room URL and ICE servers (for reconnecting)
+    signalling_url: String,
+    ice_servers: Vec<IceServer>,
+
+    /// Set when the socket's message loop has finished
+    loop_closed: Arc<AtomicBool>,
+
+    /// Peers reported as connected (so they can be disconnected on failure)
+    peers: Vec<PeerId>,
+
+    /// In-progress reconnect, if any
+    reconnect: Option<ReconnectState>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ReconnectState {
+    attempt: u32,
+    next_attempt_at: Instant,
+    /// Set while a fresh socket waits for its peer ID
+    started_at: Option<Instant>,
+}
+
+/// Delay before reconnect attempt `attempt` (1-based)
+fn reconnect_delay(attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1).min(16));
+    RECONNECT_BASE_DELAY
+        .saturating_mul(factor)
+        .min(RECONNECT_MAX_DELAY)
 }
 
 impl MatchboxConnection {
@@ -18,7 +77,7 @@ impl MatchboxConnection {
     }
 
     /// Connect to Matchbox signalling server with custom ICE servers
-    pub async fn connect(signalling_url: &str, ice_servers: Vec<IceServer>) -> Result<Self> {
+    pub async fn connect(signalling_url: &str, mut ice_servers: Vec<IceServer>) -> Result<Self> {
         tracing::info!("Connecting to signalling server: {}", signalling_url);
         tracing::info!("Configured with {} ICE servers", ice_servers.len());
 
@@ -34,33 +93,11 @@ impl MatchboxConnection {
             }
         }
 
-        let ice_server_config = build_ice_server_config(&ice_servers);
-
-        let (mut socket, loop_fut) = WebRtcSocketBuilder::new(signalling_url)
-            .ice_server(ice_server_config)
-            .add_channel(matchbox_socket::ChannelConfig::reliable())
-            .build();
-
-        // 🔧 Platform-agnostic async spawn
-        let matchbox_span = tracing::info_span!("matchbox::webrtc_loop");
-
-        #[cfg(target_arch = "wasm32")]
-        wasm_bindgen_futures::spawn_local(async move {
-            let _enter = matchbox_span.enter();
-            let _ = loop_fut.await;
+        ice_servers.iter_mut().for_each(|server| {
+            server.refresh();
         });
-
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            #[cfg(feature = "native")]
-            tokio::spawn(async move {
-                let _enter = matchbox_span.enter();
-                let _ = loop_fut.await;
-            });
-
-            #[cfg(not(feature = "native"))]
-            compile_error!("Non-WASM builds require the 'native' feature to be enabled");
-        }
+        let (mut socket, (outgoing, incoming), loop_closed) =
+            open_socket(signalling_url, &ice_servers);
 
         // Wait for peer ID to be assigned
         let peer_id = wait_for_peer_id(&mut socket).await?;
@@ -70,9 +107,22 @@ impl MatchboxConnection {
         Ok(MatchboxConnection {
             socket: Arc::new(Mutex::new(socket)),
             local_peer_id: Some(peer_id),
+            outgoing,
+            incoming,
+            received: VecDeque::new(),
+            signalling_url: signalling_url.to_string(),
+            ice_servers,
+            loop_closed,
+            peers: Vec::new(),
+            reconnect: None,
         })
     }
 
+    /// Are we currently reconnecting?
+    pub fn is_reconnecting(&self) -> bool {
+        self.reconnect.is_some()
+    }
+
     /// Get our local peer ID
     pub fn local_peer_id(&self) -> Option<PeerId> {
         self.local_peer_id
@@ -86,13 +136,19 @@ impl MatchboxConnection {
 
     /// Send data to a specific peer
     pub fn send_to(&mut self, peer: PeerId, data: Vec<u8>) -> Result<()> {
-        let mut socket = self.socket.lock().unwrap();
+        if self.reconnect.is_some() {
+            return Err(P2PError::SendFailed(format!(
+                "Reconnecting, cannot send to peer {}",
+                peer
+            )));
+        }
 
-        // 🔧 FIX: Get mutable reference to channel
-        let channel = socket.channel_mut(0);
-        channel.send(data.clone().into_boxed_slice(), peer.inner());
+        let len = data.len();
+        self.outgoing
+            .unbounded_send((peer.inner(), data.into_boxed_slice()))
+            .map_err(|_| P2PError::SendFailed(format!("Data channel to peer {} closed", peer)))?;
 
-        tracing::debug!("Sent {} bytes to peer {}", data.len(), peer);
+        tracing::debug!("Sent {} bytes to peer {}", len, peer);
         Ok(())
     }
 
@@ -111,39 +167,235 @@ impl MatchboxConnection {
 
     /// Poll for events (call this regularly in your event loop)
     pub fn poll_events(&mut self) -> Vec<ConnectionEvent> {
+        if self.reconnect.is_some() {
+            return self.poll_reconnect();
+        }
+
+        let socket = self.socket.clone();
+        let mut socket = socket.lock().unwrap();
+
+        let Ok(updates) = catch_unwind(AssertUnwindSafe(|| socket.update_peers())) else {
+            drop(socket);
+            return self.start_reconnect();
+        };
+
+        drop(socket);
+        let mut events = self.peer_events(updates);
+
+        // Check for messages
+        while let Ok(message) = self.incoming.try_recv() {
+            self.received.push_back(message);
+        }
+        for (peer_id, packet) in self.received.drain(..) {
+            let peer = PeerId::new(peer_id);
+            tracing::debug!("Received {} bytes from peer {}", packet.len(), peer);
+
+            events.push(ConnectionEvent::MessageReceived {
+                from: peer,
+                data: packet.to_vec(),
+            });
+        }
+
+        if self.loop_closed.load(Ordering::Acquire) {
+            events.extend(self.start_reconnect());
+        }
+
+        events
+    }
+
+    /// Wake `cx` once a message arrives on the data channel
+    ///
+    /// Peer updates and reconnects are not signalled and are picked up by
+    /// the caller's idle timer.
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.reconnect.is_some() {
+            return Poll::Pending;
+        }
+        if !self.received.is_empty() {
+            return Poll::Ready(());
+        }
+
+        match self.incoming.poll_next_unpin(cx) {
+            Poll::Ready(Some(message)) => {
+                self.received.push_back(message);
+                Poll::Ready(())
+            }
+            // A closed channel means the socket died; the reconnect runs on the idle timer
+            Poll::Ready(None) | Poll::Pending => Poll::Pending,
+        }
+    }
+
+    /// Translate Matchbox peer updates into connection events
+    fn peer_events(
+        &mut self,
+        updates: Vec<(matchbox_socket::PeerId, matchbox_socket::PeerState)>,
+    ) -> Vec<ConnectionEvent> {
         let mut events = Vec::new();
-        let mut socket = self.socket.lock().unwrap();
 
-        // Check for new peers
-        for (peer_id, state) in socket.update_peers() {
+        for (peer_id, state) in updates {
             let peer = PeerId::new(peer_id);
             match state {
                 matchbox_socket::PeerState::Connected => {
                     tracing::info!("Peer connected: {}", peer);
+                    if !self.peers.contains(&peer) {
+                        self.peers.push(peer);
+                    }
                     events.push(ConnectionEvent::PeerConnected(peer));
                 }
                 matchbox_socket::PeerState::Disconnected => {
                     tracing::info!("Peer disconnected: {}", peer);
+                    self.peers.retain(|p| *p != peer);
                     events.push(ConnectionEvent::PeerDisconnected(peer));
                 }
             }
         }
 
-        // Check for messages
-        // 🔧 FIX: Use channel_mut(0).receive() for mutable access
-        let channel = socket.channel_mut(0);
-        for (peer_id, packet) in channel.receive() {
-            let peer = PeerId::new(peer_id);
-            tracing::debug!("Received {} bytes from peer {}", packet.len(), peer);
+        events
+    }
 
-            events.push(ConnectionEvent::MessageReceived {
-                from: peer,
-                data: packet.to_vec(),
-            });
-        }
+    /// The socket died: disconnect every peer and schedule a reconnect
+    fn start_reconnect(&mut self) -> Vec<ConnectionEvent> {
+        tracing::warn!("Signalling connection lost, reconnecting");
+
+        let mut events: Vec<ConnectionEvent> = self
+            .peers
+            .drain(..)
+            .map(ConnectionEvent::PeerDisconnected)
+            .collect();
+
+        self.reconnect = Some(ReconnectState {
+            attempt: 1,
+            next_attempt_at: Instant::now(),
+            started_at: None,
+        });
+        events.push(ConnectionEvent::Reconnecting { attempt: 1 });
 
         events
     }
+
+    fn poll_reconnect(&mut self) -> Vec<ConnectionEvent> {
+        let Some(mut state) = self.reconnect else {
+            return Vec::new();
+        };
+
+        let Some(started_at) = state.started_at else {
+            if Instant::now() >= state.next_attempt_at {
+                tracing::info!(
+                    "Reconnect attempt {} to {}",
+                    state.attempt,
+                    self.signalling_url
+                );
+                // Time-limited TURN credentials may have expired meanwhile
+                self.ice_servers.iter_mut().for_each(|server| {
+                    server.refresh();
+                });
+                let (socket, (outgoing, incoming), loop_closed) =
+                    open_socket(&self.signalling_url, &self.ice_servers);
+                *self.socket.lock().unwrap() = socket;
+                self.outgoing = outgoing;
+                self.incoming = incoming;
+                self.received.clear();
+                self.loop_closed = loop_closed;
+                state.started_at = Some(Instant::now());
+                self.reconnect = Some(state);
+            }
+            return Vec::new();
+        };
+
+        let socket = self.socket.clone();
+        let mut socket = socket.lock().unwrap();
+        let updates = catch_unwind(AssertUnwindSafe(|| socket.update_peers()));
+
+        match (updates, socket.id()) {
+            (Ok(updates), Some(id)) if !self.loop_closed.load(Ordering::Acquire) => {
+                drop(socket);
+                let peer_id = PeerId::new(id);
+                tracing::info!("Reconnected with peer ID: {}", peer_id);
+
+                self.local_peer_id = Some(peer_id);
+                self.reconnect = None;
+
+                let mut events = vec![ConnectionEvent::Reconnected { peer_id }];
+                events.extend(self.peer_events(updates));
+                events
+            }
+            (Ok(_), None)
+                if !self.loop_closed.load(Ordering::Acquire)
+                    && started_at.elapsed() < RECONNECT_ATTEMPT_TIMEOUT =>
+            {
+                Vec::new()
+            }
+            _ => {
+                drop(socket);
+                state.attempt += 1;
+                state.next_attempt_at = Instant::now() + reconnect_delay(state.attempt);
+                state.started_at = None;
+                self.reconnect = Some(state);
+
+                tracing::warn!(
+                    "Reconnect failed, retrying in {:?} (attempt {})",
+                    reconnect_delay(state.attempt),
+                    state.attempt
+                );
+                vec![ConnectionEvent::Reconnecting {
+                    attempt: state.attempt,
+                }]
+            }
+        }
+    }
+}
+
+/// Build a Matchbox socket, take its data channel and spawn its message loop.
+/// The returned flag is set once the loop finishes.
+fn open_socket(
+    signalling_url: &str,
+    ice_servers: &[IceServer],
+) -> (
+    WebRtcSocket,
+    (
+        mpsc::UnboundedSender<ChannelMessage>,
+        mpsc::UnboundedReceiver<ChannelMessage>,
+    ),
+    Arc<AtomicBool>,
+) {
+    let ice_server_config = build_ice_server_config(ice_servers);
+
+    let (mut socket, loop_fut) = WebRtcSocketBuilder::new(signalling_url)
+        .ice_server(ice_server_config)
+        .add_channel(matchbox_socket::ChannelConfig::reliable())
+        .build();
+    let channel = socket
+        .take_channel(0)
+        .expect("data channel 0 is configured above")
+        .split();
+
+    let loop_closed = Arc::new(AtomicBool::new(false));
+    let closed = loop_closed.clone();
+
+    // 🔧 Platform-agnostic async spawn
+    let matchbox_span = tracing::info_span!("matchbox::webrtc_loop");
+
+    #[cfg(target_arch = "wasm32")]
+    wasm_bindgen_futures::spawn_local(async move {
+        let _enter = matchbox_span.enter();
+        let _ = loop_fut.await;
+        closed.store(true, Ordering::Release);
+    });
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        #[cfg(feature = "native")]
+        tokio::spawn(async move {
+            let _enter = matchbox_span.enter();
+            let _ = loop_fut.await;
+            closed.store(true, Ordering::Release);
+        });
+
+        #[cfg(not(feature = "native"))]
+        compile_error!("Non-WASM builds require the 'native' feature to be enabled");
+    }
+
+    (socket, channel, loop_closed)
 }
 
 /// Build ICE server configuration for Matchbox.
@@ -293,6 +545,14 @@ mod tests {
         assert!(!config.urls.is_empty());
     }
 
+    #[test]
+    fn test_reconnect_delay_backs_off_and_caps() {
+        assert_eq!(reconnect_delay(1), RECONNECT_BASE_DELAY);
+        assert_eq!(reconnect_delay(2), RECONNECT_BASE_DELAY * 2);
+        assert_eq!(reconnect_delay(3), RECONNECT_BASE_DELAY * 4);
+        assert_eq!(reconnect_delay(50), RECONNECT_MAX_DELAY);
+    }
+
     // 🆕 NEW: Test WASM compilation
     #[cfg(target_arch = "wasm32")]
     #[test]
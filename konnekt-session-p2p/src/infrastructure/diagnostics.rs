@@ -0,0 +1,1329 @@
+//! NAT and connectivity diagnostics
+//!
+//! Probes the configured STUN/TURN servers, classifies the local NAT and
+//! summarizes the result in a [`ConnectivityReport`]. Native builds probe
+//! over UDP directly ([`run_diagnostics`]) and can add the signalling server
+//! ([`probe_signalling`]) and the clock ([`probe_clock`]); browsers gather
+//! ICE candidates through `RTCPeerConnection` and build the report with
+//! [`ConnectivityReport::from_candidates`]. [`ConnectivityReport::checks`]
+//! turns a report into pass/fail findings with advice.
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::domain::IceServer;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+/// Default time to wait for a single server to answer
+pub const DEFAULT_PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Clock skew worth a warning
+const CLOCK_SKEW_WARN_MS: i64 = 2_000;
+/// Clock skew that breaks time-limited TURN credentials and timestamps
+const CLOCK_SKEW_FAIL_MS: i64 = 60_000;
+
+/// NAT behaviour as seen from the STUN servers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NatType {
+    /// Public address, no translation
+    Open,
+    /// Same public mapping for every destination (full/restricted cone)
+    EndpointIndependent,
+    /// Mapping changes per destination (symmetric) - direct connections
+    /// usually fail, a TURN relay is needed
+    EndpointDependent,
+    /// No STUN server answered (UDP blocked or servers down)
+    Blocked,
+    /// Not enough answers to tell (configure at least two STUN servers)
+    Unknown,
+}
+
+impl NatType {
+    /// Whether peers behind this NAT will likely need a TURN relay
+    pub fn needs_relay(&self) -> bool {
+        matches!(self, NatType::EndpointDependent | NatType::Blocked)
+    }
+}
+
+impl std::fmt::Display for NatType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            NatType::Open => "open (no NAT)",
+            NatType::EndpointIndependent => "endpoint-independent (cone)",
+            NatType::EndpointDependent => "endpoint-dependent (symmetric)",
+            NatType::Blocked => "blocked",
+            NatType::Unknown => "unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Classify the NAT from local (host) addresses and the public addresses
+/// reported by different STUN servers
+pub fn classify_nat(host: &[SocketAddr], mapped: &[SocketAddr]) -> NatType {
+    if mapped.is_empty() {
+        return NatType::Blocked;
+    }
+
+    if mapped.iter().any(|addr| host.contains(addr)) {
+        return NatType::Open;
+    }
+
+    let first = mapped[0];
+    if mapped.iter().any(|addr| *addr != first) {
+        NatType::EndpointDependent
+    } else if mapped.len() > 1 {
+        NatType::EndpointIndependent
+    } else {
+        NatType::Unknown
+    }
+}
+
+/// ICE server kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerKind {
+    Stun,
+    Turn,
+}
+
+/// Result of probing a single STUN/TURN URL
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerProbe {
+    pub url: String,
+    pub kind: ServerKind,
+    pub reachable: bool,
+    /// Public address reported by a STUN server
+    pub mapped_address: Option<SocketAddr>,
+    pub rtt_ms: Option<u64>,
+    /// Why the probe failed, or a note about the answer
+    pub detail: Option<String>,
+}
+
+/// Result of reaching the signalling (Matchbox) server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignallingProbe {
+    pub url: String,
+    /// A TCP connection was accepted (the WebSocket handshake is not checked)
+    pub reachable: bool,
+    pub address: Option<SocketAddr>,
+    pub rtt_ms: Option<u64>,
+    pub detail: Option<String>,
+}
+
+/// The local clock compared with an NTP server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClockProbe {
+    pub server: String,
+    /// How far the local clock is ahead (negative: behind)
+    pub skew_ms: Option<i64>,
+    pub detail: Option<String>,
+}
+
+/// Outcome of one diagnostic check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    /// Works, but some peers may not be able to connect
+    Warn,
+    Fail,
+}
+
+/// One finding of [`ConnectivityReport::checks`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagnosticCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to do about a warning or failure
+    pub advice: Option<String>,
+}
+
+impl DiagnosticCheck {
+    fn new(name: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+            advice: None,
+        }
+    }
+
+    fn with_advice(mut self, advice: &str) -> Self {
+        self.advice = Some(advice.to_string());
+        self
+    }
+}
+
+/// ICE candidate type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CandidateKind {
+    Host,
+    ServerReflexive,
+    PeerReflexive,
+    Relay,
+}
+
+/// A gathered ICE candidate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IceCandidate {
+    pub kind: CandidateKind,
+    pub protocol: String,
+    /// `ip:port`, or an mDNS name for obfuscated browser host candidates
+    pub address: String,
+}
+
+impl IceCandidate {
+    /// Parse an SDP candidate line
+    /// (`candidate:<foundation> <component> <proto> <priority> <addr> <port> typ <type> ...`)
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim().trim_start_matches("a=");
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 8 || !fields[0].starts_with("candidate:") || fields[6] != "typ" {
+            return None;
+        }
+
+        let kind = match fields[7] {
+            "host" => CandidateKind::Host,
+            "srflx" => CandidateKind::ServerReflexive,
+            "prflx" => CandidateKind::PeerReflexive,
+            "relay" => CandidateKind::Relay,
+            _ => return None,
+        };
+        let port: u16 = fields[5].parse().ok()?;
+        let address = match fields[4].parse::<std::net::IpAddr>() {
+            Ok(ip) => SocketAddr::new(ip, port).to_string(),
+            Err(_) => format!("{}:{}", fields[4], port),
+        };
+
+        Some(Self {
+            kind,
+            protocol: fields[2].to_lowercase(),
+            address,
+        })
+    }
+
+    pub fn socket_addr(&self) -> Option<SocketAddr> {
+        self.address.parse().ok()
+    }
+}
+
+/// Structured result of a connectivity check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConnectivityReport {
+    pub servers: Vec<ServerProbe>,
+    pub candidates: Vec<IceCandidate>,
+    pub nat_type: NatType,
+    /// Time taken to gather all candidates
+    pub gathering_time_ms: u64,
+    #[serde(default)]
+    pub signalling: Option<SignallingProbe>,
+    #[serde(default)]
+    pub clock: Option<ClockProbe>,
+}
+
+impl ConnectivityReport {
+    /// Build a report from candidates gathered by a browser `RTCPeerConnection`
+    pub fn from_candidates(candidates: Vec<IceCandidate>, gathering_time_ms: u64) -> Self {
+        let addresses_of = |kind: CandidateKind| -> Vec<SocketAddr> {
+            candidates
+                .iter()
+                .filter(|c| c.kind == kind && c.protocol == "udp")
+                .filter_map(IceCandidate::socket_addr)
+                .collect()
+        };
+        let nat_type = classify_nat(
+            &addresses_of(CandidateKind::Host),
+            &addresses_of(CandidateKind::ServerReflexive),
+        );
+
+        Self {
+            servers: Vec::new(),
+            candidates,
+            nat_type,
+            gathering_time_ms,
+            signalling: None,
+            clock: None,
+        }
+    }
+
+    /// Distinct public addresses seen by STUN servers
+    pub fn public_addresses(&self) -> Vec<String> {
+        let mut addresses: Vec<String> = self
+            .candidates
+            .iter()
+            .filter(|c| c.kind == CandidateKind::ServerReflexive)
+            .map(|c| c.address.clone())
+            .collect();
+        addresses.sort();
+        addresses.dedup();
+        addresses
+    }
+
+    pub fn stun_reachable(&self) -> bool {
+        self.candidates
+            .iter()
+            .any(|c| c.kind == CandidateKind::ServerReflexive)
+    }
+
+    pub fn turn_reachable(&self) -> bool {
+        self.candidates
+            .iter()
+            .any(|c| c.kind == CandidateKind::Relay)
+            || self
+                .servers
+                .iter()
+                .any(|s| s.kind == ServerKind::Turn && s.reachable)
+    }
+
+    /// Short advice for the user, if something looks wrong
+    pub fn recommendation(&self) -> Option<&'static str> {
+        match self.nat_type {
+            NatType::Blocked => Some(
+                "No STUN server answered. UDP may be blocked; configure a TURN server over TCP/TLS.",
+            ),
+            NatType::EndpointDependent if !self.turn_reachable() => Some(
+                "Symmetric NAT detected. Direct connections will likely fail; configure a TURN server.",
+            ),
+            NatType::Unknown => {
+                Some("Only one STUN server answered; add another one to classify the NAT.")
+            }
+            _ => None,
+        }
+    }
+
+    /// Pass/warn/fail findings, in the order a connection needs them
+    pub fn checks(&self) -> Vec<DiagnosticCheck> {
+        let mut checks = Vec::new();
+
+        if let Some(signalling) = &self.signalling {
+            checks.push(if signalling.reachable {
+                DiagnosticCheck::new(
+                    "Signalling",
+                    CheckStatus::Pass,
+                    format!(
+                        "{} reachable in {}ms",
+                        signalling.url,
+                        signalling.rtt_ms.unwrap_or(0)
+                    ),
+                )
+            } else {
+                DiagnosticCheck::new(
+                    "Signalling",
+                    CheckStatus::Fail,
+                    signalling.detail.clone().unwrap_or_default(),
+                )
+                .with_advice(
+                    "Check the --server URL and that outgoing WebSocket (HTTPS) traffic is allowed.",
+                )
+            });
+        }
+
+        let probed = |kind: ServerKind| {
+            let probes: Vec<_> = self.servers.iter().filter(|s| s.kind == kind).collect();
+            let reachable = probes.iter().filter(|s| s.reachable).count();
+            (probes.len(), reachable)
+        };
+
+        let (stun, stun_reachable) = probed(ServerKind::Stun);
+        if stun > 0 {
+            let detail = format!("{} of {} STUN servers answered", stun_reachable, stun);
+            checks.push(match stun_reachable {
+                0 => DiagnosticCheck::new("STUN", CheckStatus::Fail, detail).with_advice(
+                    "UDP looks blocked; configure a TURN server reachable over TCP/TLS.",
+                ),
+                n if n < stun => DiagnosticCheck::new("STUN", CheckStatus::Warn, detail)
+                    .with_advice("Remove or replace the STUN servers that did not answer."),
+                _ => DiagnosticCheck::new("STUN", CheckStatus::Pass, detail),
+            });
+        }
+
+        let (turn, turn_reachable) = probed(ServerKind::Turn);
+        if turn > 0 {
+            let detail = format!("{} of {} TURN servers answered", turn_reachable, turn);
+            checks.push(if turn_reachable == 0 {
+                DiagnosticCheck::new("TURN", CheckStatus::Fail, detail)
+                    .with_advice("Check the TURN URL, port and credentials, and the firewall.")
+            } else {
+                DiagnosticCheck::new("TURN", CheckStatus::Pass, detail)
+            });
+        }
+
+        let nat = DiagnosticCheck::new(
+            "NAT",
+            match self.nat_type {
+                NatType::Open | NatType::EndpointIndependent => CheckStatus::Pass,
+                NatType::EndpointDependent if self.turn_reachable() => CheckStatus::Pass,
+                NatType::EndpointDependent | NatType::Unknown => CheckStatus::Warn,
+                NatType::Blocked => CheckStatus::Fail,
+            },
+            self.nat_type.to_string(),
+        );
+        checks.push(match self.recommendation() {
+            Some(advice) => nat.with_advice(advice),
+            None => nat,
+        });
+
+        let count = |kind: CandidateKind| self.candidates.iter().filter(|c| c.kind == kind).count();
+        let (host, srflx, relay) = (
+            count(CandidateKind::Host),
+            count(CandidateKind::ServerReflexive),
+            count(CandidateKind::Relay),
+        );
+        let detail = format!(
+            "{} host, {} server-reflexive, {} relay in {}ms",
+            host, srflx, relay, self.gathering_time_ms
+        );
+        checks.push(if srflx + relay > 0 {
+            DiagnosticCheck::new("Candidates", CheckStatus::Pass, detail)
+        } else {
+            DiagnosticCheck::new("Candidates", CheckStatus::Fail, detail).with_advice(
+                "Only peers on this network can connect; make a STUN or TURN server reachable.",
+            )
+        });
+
+        if let Some(clock) = &self.clock {
+            checks.push(match clock.skew_ms {
+                None => DiagnosticCheck::new(
+                    "Clock",
+                    CheckStatus::Warn,
+                    clock.detail.clone().unwrap_or_default(),
+                )
+                .with_advice("Could not compare the clock; pass another --ntp-server."),
+                Some(skew) => {
+                    let detail = format!("{:+}ms against {}", skew, clock.server);
+                    match skew.abs() {
+                        s if s <= CLOCK_SKEW_WARN_MS => {
+                            DiagnosticCheck::new("Clock", CheckStatus::Pass, detail)
+                        }
+                        s if s <= CLOCK_SKEW_FAIL_MS => {
+                            DiagnosticCheck::new("Clock", CheckStatus::Warn, detail)
+                                .with_advice("Enable time synchronisation (NTP) on this machine.")
+                        }
+                        _ => DiagnosticCheck::new("Clock", CheckStatus::Fail, detail).with_advice(
+                            "Sync the system clock (NTP): time-limited TURN credentials are rejected otherwise.",
+                        ),
+                    }
+                }
+            });
+        }
+
+        checks
+    }
+}
+
+/// Minimal STUN (RFC 5389) message codec used by the probes
+pub mod stun {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+    pub const MAGIC_COOKIE: u32 = 0x2112_A442;
+
+    pub const BINDING_REQUEST: u16 = 0x0001;
+    pub const BINDING_SUCCESS: u16 = 0x0101;
+    pub const ALLOCATE_REQUEST: u16 = 0x0003;
+    pub const ALLOCATE_SUCCESS: u16 = 0x0103;
+    pub const ALLOCATE_ERROR: u16 = 0x0113;
+
+    const ATTR_MAPPED_ADDRESS: u16 = 0x0001;
+    const ATTR_ERROR_CODE: u16 = 0x0009;
+    const ATTR_XOR_RELAYED_ADDRESS: u16 = 0x0016;
+    const ATTR_REQUESTED_TRANSPORT: u16 = 0x0019;
+    const ATTR_XOR_MAPPED_ADDRESS: u16 = 0x0020;
+
+    const PROTOCOL_UDP: u8 = 17;
+
+    pub type TransactionId = [u8; 12];
+
+    /// Decoded STUN response
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Response {
+        pub message_type: u16,
+        pub mapped_address: Option<SocketAddr>,
+        pub relayed_address: Option<SocketAddr>,
+        pub error_code: Option<u16>,
+    }
+
+    pub fn transaction_id() -> TransactionId {
+        let mut id = [0u8; 12];
+        id.copy_from_slice(&uuid::Uuid::new_v4().as_bytes()[..12]);
+        id
+    }
+
+    pub fn binding_request(transaction_id: &TransactionId) -> Vec<u8> {
+        encode(BINDING_REQUEST, transaction_id, &[])
+    }
+
+    /// Unauthenticated Allocate; servers answer with `401` if they are alive
+    pub fn allocate_request(transaction_id: &TransactionId) -> Vec<u8> {
+        encode(
+            ALLOCATE_REQUEST,
+            transaction_id,
+            &[(ATTR_REQUESTED_TRANSPORT, vec![PROTOCOL_UDP, 0, 0, 0])],
+        )
+    }
+
+    fn encode(
+        message_type: u16,
+        transaction_id: &TransactionId,
+        attrs: &[(u16, Vec<u8>)],
+    ) -> Vec<u8> {
+        let mut body = Vec::new();
+        for (attr_type, value) in attrs {
+            body.extend_from_slice(&attr_type.to_be_bytes());
+            body.extend_from_slice(&(value.len() as u16).to_be_bytes());
+            body.extend_from_slice(value);
+            body.resize(body.len().next_multiple_of(4), 0);
+        }
+
+        let mut message = Vec::with_capacity(20 + body.len());
+        message.extend_from_slice(&message_type.to_be_bytes());
+        message.extend_from_slice(&(body.len() as u16).to_be_bytes());
+        message.extend_from_slice(&MAGIC_COOKIE.to_be_bytes());
+        message.extend_from_slice(transaction_id);
+        message.extend_from_slice(&body);
+        message
+    }
+
+    /// Decode a response to the request with `transaction_id`.
+    /// Returns `None` for anything else (other transactions, garbage).
+    pub fn decode(data: &[u8], transaction_id: &TransactionId) -> Option<Response> {
+        if data.len() < 20 {
+            return None;
+        }
+
+        let message_type = u16::from_be_bytes([data[0], data[1]]);
+        let length = u16::from_be_bytes([data[2], data[3]]) as usize;
+        if data[4..8] != MAGIC_COOKIE.to_be_bytes()
+            || data[8..20] != transaction_id[..]
+            || data.len() < 20 + length
+        {
+            return None;
+        }
+
+        let mut response = Response {
+            message_type,
+            mapped_address: None,
+            relayed_address: None,
+            error_code: None,
+        };
+
+        let mut attrs = &data[20..20 + length];
+        while attrs.len() >= 4 {
+            let attr_type = u16::from_be_bytes([attrs[0], attrs[1]]);
+            let attr_len = u16::from_be_bytes([attrs[2], attrs[3]]) as usize;
+            let value = attrs.get(4..4 + attr_len)?;
+
+            match attr_type {
+                ATTR_XOR_MAPPED_ADDRESS => {
+                    response.mapped_address = decode_address(value, Some(transaction_id));
+                }
+                ATTR_MAPPED_ADDRESS if response.mapped_address.is_none() => {
+                    response.mapped_address = decode_address(value, None);
+                }
+                ATTR_XOR_RELAYED_ADDRESS => {
+                    response.relayed_address = decode_address(value, Some(transaction_id));
+                }
+                ATTR_ERROR_CODE if value.len() >= 4 => {
+                    response.error_code = Some((value[2] & 0x07) as u16 * 100 + value[3] as u16);
+                }
+                _ => {}
+            }
+
+            let padded = (4 + attr_len).next_multiple_of(4);
+            attrs = attrs.get(padded..).unwrap_or_default();
+        }
+
+        Some(response)
+    }
+
+    /// Decode a (XOR-)MAPPED-ADDRESS value; `xor` carries the transaction id
+    fn decode_address(value: &[u8], xor: Option<&TransactionId>) -> Option<SocketAddr> {
+        if value.len() < 4 {
+            return None;
+        }
+
+        let cookie = MAGIC_COOKIE.to_be_bytes();
+        let mut port = u16::from_be_bytes([value[2], value[3]]);
+        if xor.is_some() {
+            port ^= (MAGIC_COOKIE >> 16) as u16;
+        }
+
+        let ip = match value[1] {
+            0x01 => {
+                let mut octets: [u8; 4] = value.get(4..8)?.try_into().ok()?;
+                if xor.is_some() {
+                    octets.iter_mut().zip(cookie).for_each(|(b, k)| *b ^= k);
+                }
+                IpAddr::V4(Ipv4Addr::from(octets))
+            }
+            0x02 => {
+                let mut octets: [u8; 16] = value.get(4..20)?.try_into().ok()?;
+                if let Some(transaction_id) = xor {
+                    let key = cookie.iter().chain(transaction_id.iter());
+                    octets.iter_mut().zip(key).for_each(|(b, k)| *b ^= k);
+                }
+                IpAddr::V6(Ipv6Addr::from(octets))
+            }
+            _ => return None,
+        };
+
+        Some(SocketAddr::new(ip, port))
+    }
+}
+
+/// Minimal SNTP (RFC 4330) client codec used by the clock probe
+pub mod sntp {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    pub const PORT: u16 = 123;
+
+    /// Seconds from 1900 (NTP era 0) to 1970
+    const UNIX_OFFSET_SECS: u64 = 2_208_988_800;
+
+    /// Client request (version 3, mode 3) carrying `sent` as transmit time
+    pub fn request(sent: SystemTime) -> [u8; 48] {
+        let mut packet = [0u8; 48];
+        packet[0] = 0x1B;
+        packet[40..48].copy_from_slice(&timestamp(sent));
+        packet
+    }
+
+    /// Server receive and transmit times of the answer to `request`.
+    /// Returns `None` for anything else (other requests, kiss-o'-death).
+    pub fn decode(data: &[u8], request: &[u8; 48]) -> Option<(SystemTime, SystemTime)> {
+        if data.len() < 48 || data[0] & 0x07 != 4 || data[1] == 0 || data[24..32] != request[40..48]
+        {
+            return None;
+        }
+        Some((
+            from_timestamp(data[32..40].try_into().ok()?),
+            from_timestamp(data[40..48].try_into().ok()?),
+        ))
+    }
+
+    /// How far the local clock is ahead of the server, in milliseconds
+    pub fn local_skew_ms(
+        sent: SystemTime,
+        server_received: SystemTime,
+        server_sent: SystemTime,
+        received: SystemTime,
+    ) -> i64 {
+        let offset = ((unix_ms(server_received) - unix_ms(sent))
+            + (unix_ms(server_sent) - unix_ms(received)))
+            / 2;
+        -offset
+    }
+
+    pub fn timestamp(time: SystemTime) -> [u8; 8] {
+        let since_unix = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let seconds = (since_unix.as_secs() + UNIX_OFFSET_SECS) as u32;
+        let fraction = ((since_unix.subsec_nanos() as u64) << 32) / 1_000_000_000;
+
+        let mut bytes = [0u8; 8];
+        bytes[..4].copy_from_slice(&seconds.to_be_bytes());
+        bytes[4..].copy_from_slice(&(fraction as u32).to_be_bytes());
+        bytes
+    }
+
+    fn from_timestamp(bytes: [u8; 8]) -> SystemTime {
+        let seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64;
+        let fraction = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as u64;
+        let nanos = (fraction * 1_000_000_000) >> 32;
+        UNIX_EPOCH
+            + Duration::from_secs(seconds.saturating_sub(UNIX_OFFSET_SECS))
+            + Duration::from_nanos(nanos)
+    }
+
+    fn unix_ms(time: SystemTime) -> i64 {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => since.as_millis() as i64,
+            Err(before) => -(before.duration().as_millis() as i64),
+        }
+    }
+}
+
+/// Split a signalling URL (`wss://host:port/path`) into host and port
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_signalling_url(url: &str) -> Option<(String, u16)> {
+    let (scheme, rest) = url.split_once("://")?;
+    let default_port = match scheme {
+        "ws" | "http" => 80,
+        "wss" | "https" => 443,
+        _ => return None,
+    };
+
+    let authority = rest.split(['/', '?']).next()?;
+    let (host, port) = if let Some(stripped) = authority.strip_prefix('[') {
+        let (host, tail) = stripped.split_once(']')?;
+        (host, tail.strip_prefix(':'))
+    } else {
+        match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+    if host.is_empty() {
+        return None;
+    }
+    let port = match port {
+        Some(port) => port.parse().ok()?,
+        None => default_port,
+    };
+
+    Some((host.to_string(), port))
+}
+
+/// Split an ICE URL (`stun:host:port`, `turn:host?transport=udp`) into
+/// kind, host and port. Returns `None` for URLs that can't be probed over UDP.
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_ice_url(url: &str) -> Option<(ServerKind, String, u16)> {
+    let (scheme, rest) = url.split_once(':')?;
+    let kind = match scheme {
+        "stun" => ServerKind::Stun,
+        "turn" => ServerKind::Turn,
+        _ => return None,
+    };
+
+    let (address, query) = rest.split_once('?').unwrap_or((rest, ""));
+    if query.split('&').any(|p| p == "transport=tcp") {
+        return None;
+    }
+
+    let (host, port) = if let Some(stripped) = address.strip_prefix('[') {
+        let (host, tail) = stripped.split_once(']')?;
+        (host, tail.strip_prefix(':'))
+    } else {
+        match address.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (address, None),
+        }
+    };
+    let port = match port {
+        Some(port) => port.parse().ok()?,
+        None => 3478,
+    };
+
+    Some((kind, host.to_string(), port))
+}
+
+/// Probe all UDP STUN/TURN URLs in `ice_servers` and classify the NAT
+///
+/// Blocking; run it on a blocking thread from async code.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn run_diagnostics(
+    ice_servers: &[IceServer],
+    probe_timeout: std::time::Duration,
+) -> ConnectivityReport {
+    use std::net::{ToSocketAddrs, UdpSocket};
+    use std::time::Instant;
+
+    let started = Instant::now();
+    let mut servers = Vec::new();
+
+    let socket = match UdpSocket::bind("0.0.0.0:0") {
+        Ok(socket) => socket,
+        Err(e) => {
+            tracing::warn!("Diagnostics: cannot open UDP socket: {}", e);
+            return ConnectivityReport {
+                servers,
+                candidates: Vec::new(),
+                nat_type: NatType::Blocked,
+                gathering_time_ms: 0,
+                signalling: None,
+                clock: None,
+            };
+        }
+    };
+
+    let mut host_address = None;
+    let mut mapped = Vec::new();
+    let mut relayed = Vec::new();
+
+    for url in ice_servers.iter().flat_map(|s| s.urls.iter()) {
+        let Some((kind, host, port)) = parse_ice_url(url) else {
+            tracing::debug!("Diagnostics: skipping non-UDP ICE URL {}", url);
+            continue;
+        };
+
+        let target = (host.as_str(), port)
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addrs| addrs.find(SocketAddr::is_ipv4));
+        let Some(target) = target else {
+            servers.push(ServerProbe {
+                url: url.clone(),
+                kind,
+                reachable: false,
+                mapped_address: None,
+                rtt_ms: None,
+                detail: Some(format!("cannot resolve {}", host)),
+            });
+            continue;
+        };
+
+        if host_address.is_none() {
+            host_address = local_address_towards(target, &socket);
+        }
+
+        let probe = probe_server(&socket, url, kind, target, probe_timeout);
+        if let Some(addr) = probe.mapped_address {
+            mapped.push(addr);
+        }
+        if let Some(addr) = probe.relayed {
+            relayed.push(addr);
+        }
+        servers.push(probe.result);
+    }
+
+    let host: Vec<SocketAddr> = host_address.into_iter().collect();
+    let nat_type = classify_nat(&host, &mapped);
+
+    let mut candidates: Vec<IceCandidate> = host
+        .iter()
+        .map(|addr| (CandidateKind::Host, *addr))
+        .chain(
+            mapped
+                .iter()
+                .map(|addr| (CandidateKind::ServerReflexive, *addr)),
+        )
+        .chain(relayed.iter().map(|addr| (CandidateKind::Relay, *addr)))
+        .map(|(kind, addr)| IceCandidate {
+            kind,
+            protocol: "udp".to_string(),
+            address: addr.to_string(),
+        })
+        .collect();
+    candidates.dedup();
+
+    let report = ConnectivityReport {
+        servers,
+        candidates,
+        nat_type,
+        gathering_time_ms: started.elapsed().as_millis() as u64,
+        signalling: None,
+        clock: None,
+    };
+
+    tracing::info!(
+        "Diagnostics: NAT {}, {} candidates in {}ms",
+        report.nat_type,
+        report.candidates.len(),
+        report.gathering_time_ms
+    );
+    report
+}
+
+/// Resolve the signalling server and open a TCP connection to it
+///
+/// Blocking; run it on a blocking thread from async code.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn probe_signalling(url: &str, probe_timeout: std::time::Duration) -> SignallingProbe {
+    use std::net::{TcpStream, ToSocketAddrs};
+    use std::time::Instant;
+
+    let mut probe = SignallingProbe {
+        url: url.to_string(),
+        reachable: false,
+        address: None,
+        rtt_ms: None,
+        detail: None,
+    };
+
+    let Some((host, port)) = parse_signalling_url(url) else {
+        probe.detail = Some("not a ws://, wss://, http:// or https:// URL".to_string());
+        return probe;
+    };
+    let Some(address) = (host.as_str(), port)
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+    else {
+        probe.detail = Some(format!("cannot resolve {}", host));
+        return probe;
+    };
+    probe.address = Some(address);
+
+    let started = Instant::now();
+    match TcpStream::connect_timeout(&address, probe_timeout) {
+        Ok(_) => {
+            probe.reachable = true;
+            probe.rtt_ms = Some(started.elapsed().as_millis() as u64);
+        }
+        Err(e) => probe.detail = Some(format!("cannot connect to {}: {}", address, e)),
+    }
+
+    tracing::info!(
+        "Diagnostics: signalling {} {}",
+        url,
+        if probe.reachable {
+            "reachable"
+        } else {
+            "unreachable"
+        }
+    );
+    probe
+}
+
+/// Compare the local clock with an NTP `server` (`host` or `host:port`)
+///
+/// Blocking; run it on a blocking thread from async code.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn probe_clock(server: &str, probe_timeout: std::time::Duration) -> ClockProbe {
+    use std::net::{ToSocketAddrs, UdpSocket};
+    use std::time::SystemTime;
+
+    let mut probe = ClockProbe {
+        server: server.to_string(),
+        skew_ms: None,
+        detail: None,
+    };
+
+    let target = server
+        .to_socket_addrs()
+        .or_else(|_| (server, sntp::PORT).to_socket_addrs())
+        .ok()
+        .and_then(|mut addrs| addrs.find(SocketAddr::is_ipv4));
+    let Some(target) = target else {
+        probe.detail = Some(format!("cannot resolve {}", server));
+        return probe;
+    };
+
+    let socket = match UdpSocket::bind("0.0.0.0:0") {
+        Ok(socket) => socket,
+        Err(e) => {
+            probe.detail = Some(format!("cannot open UDP socket: {}", e));
+            return probe;
+        }
+    };
+
+    let sent = SystemTime::now();
+    let request = sntp::request(sent);
+    if let Err(e) = socket.send_to(&request, target) {
+        probe.detail = Some(format!("send failed: {}", e));
+        return probe;
+    }
+
+    let _ = socket.set_read_timeout(Some(probe_timeout));
+    let mut buf = [0u8; 128];
+    match socket.recv_from(&mut buf) {
+        Ok((len, from)) if from == target => match sntp::decode(&buf[..len], &request) {
+            Some((server_received, server_sent)) => {
+                let skew =
+                    sntp::local_skew_ms(sent, server_received, server_sent, SystemTime::now());
+                probe.skew_ms = Some(skew);
+            }
+            None => probe.detail = Some("invalid NTP answer".to_string()),
+        },
+        Ok(_) => probe.detail = Some("answer from an unexpected address".to_string()),
+        Err(_) => probe.detail = Some("no answer (timeout)".to_string()),
+    }
+    probe
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct ProbeOutcome {
+    result: ServerProbe,
+    mapped_address: Option<SocketAddr>,
+    relayed: Option<SocketAddr>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn probe_server(
+    socket: &std::net::UdpSocket,
+    url: &str,
+    kind: ServerKind,
+    target: SocketAddr,
+    probe_timeout: std::time::Duration,
+) -> ProbeOutcome {
+    use std::time::Instant;
+
+    let transaction_id = stun::transaction_id();
+    let request = match kind {
+        ServerKind::Stun => stun::binding_request(&transaction_id),
+        ServerKind::Turn => stun::allocate_request(&transaction_id),
+    };
+
+    let mut outcome = ProbeOutcome {
+        result: ServerProbe {
+            url: url.to_string(),
+            kind,
+            reachable: false,
+            mapped_address: None,
+            rtt_ms: None,
+            detail: None,
+        },
+        mapped_address: None,
+        relayed: None,
+    };
+
+    let sent_at = Instant::now();
+    if let Err(e) = socket.send_to(&request, target) {
+        outcome.result.detail = Some(format!("send failed: {}", e));
+        return outcome;
+    }
+
+    let deadline = sent_at + probe_timeout;
+    let mut buf = [0u8; 1500];
+    let response = loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() || socket.set_read_timeout(Some(remaining)).is_err() {
+            break None;
+        }
+
+        match socket.recv_from(&mut buf) {
+            Ok((len, from)) if from == target => {
+                if let Some(response) = stun::decode(&buf[..len], &transaction_id) {
+                    break Some(response);
+                }
+            }
+            Ok(_) => continue,
+            Err(_) => break None,
+        }
+    };
+
+    let Some(response) = response else {
+        outcome.result.detail = Some("no answer (timeout)".to_string());
+        return outcome;
+    };
+
+    outcome.result.reachable = true;
+    outcome.result.rtt_ms = Some(sent_at.elapsed().as_millis() as u64);
+
+    match (kind, response.message_type) {
+        (ServerKind::Stun, stun::BINDING_SUCCESS) => {
+            outcome.result.mapped_address = response.mapped_address;
+            outcome.mapped_address = response.mapped_address;
+        }
+        (ServerKind::Turn, stun::ALLOCATE_SUCCESS) => {
+            outcome.relayed = response.relayed_address;
+            outcome.mapped_address = response.mapped_address;
+            outcome.result.mapped_address = response.mapped_address;
+        }
+        (ServerKind::Turn, stun::ALLOCATE_ERROR) if response.error_code == Some(401) => {
+            outcome.result.detail = Some("reachable, authentication required".to_string());
+        }
+        (_, message_type) => {
+            outcome.result.detail = Some(match response.error_code {
+                Some(code) => format!("error response {}", code),
+                None => format!("unexpected response 0x{:04x}", message_type),
+            });
+        }
+    }
+
+    outcome
+}
+
+/// Local interface address used to reach `target`, with the probe socket's port
+#[cfg(not(target_arch = "wasm32"))]
+fn local_address_towards(
+    target: SocketAddr,
+    probe_socket: &std::net::UdpSocket,
+) -> Option<SocketAddr> {
+    // Connecting a UDP socket sends nothing but picks the outgoing interface
+    let route = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
+    route.connect(target).ok()?;
+    let ip = route.local_addr().ok()?.ip();
+    let port = probe_socket.local_addr().ok()?.port();
+    Some(SocketAddr::new(ip, port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(s: &str) -> SocketAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_classify_nat() {
+        let host = [addr("192.168.1.10:5000")];
+
+        assert_eq!(classify_nat(&host, &[]), NatType::Blocked);
+        assert_eq!(
+            classify_nat(&[addr("203.0.113.5:5000")], &[addr("203.0.113.5:5000")]),
+            NatType::Open
+        );
+        assert_eq!(
+            classify_nat(&host, &[addr("203.0.113.5:6000")]),
+            NatType::Unknown
+        );
+        assert_eq!(
+            classify_nat(&host, &[addr("203.0.113.5:6000"), addr("203.0.113.5:6000")]),
+            NatType::EndpointIndependent
+        );
+        assert_eq!(
+            classify_nat(&host, &[addr("203.0.113.5:6000"), addr("203.0.113.5:6001")]),
+            NatType::EndpointDependent
+        );
+        assert!(NatType::EndpointDependent.needs_relay());
+        assert!(!NatType::EndpointIndependent.needs_relay());
+    }
+
+    #[test]
+    fn test_parse_candidate() {
+        let srflx = IceCandidate::parse(
+            "candidate:842163049 1 udp 1677729535 203.0.113.5 54321 typ srflx raddr 0.0.0.0 rport 0 generation 0",
+        )
+        .unwrap();
+        assert_eq!(srflx.kind, CandidateKind::ServerReflexive);
+        assert_eq!(srflx.protocol, "udp");
+        assert_eq!(srflx.socket_addr(), Some(addr("203.0.113.5:54321")));
+
+        let host = IceCandidate::parse("a=candidate:1 1 UDP 2122252543 f3a1.local 60000 typ host")
+            .unwrap();
+        assert_eq!(host.kind, CandidateKind::Host);
+        assert_eq!(host.address, "f3a1.local:60000");
+        assert_eq!(host.socket_addr(), None);
+
+        assert!(IceCandidate::parse("candidate:1 1 udp 1 1.2.3.4 1 typ bogus").is_none());
+        assert!(IceCandidate::parse("").is_none());
+    }
+
+    #[test]
+    fn test_report_from_browser_candidates() {
+        let candidates = [
+            "candidate:1 1 udp 2122252543 f3a1.local 60000 typ host",
+            "candidate:2 1 udp 1677729535 203.0.113.5 6000 typ srflx raddr 0.0.0.0 rport 0",
+            "candidate:3 1 udp 1677729535 203.0.113.5 6001 typ srflx raddr 0.0.0.0 rport 0",
+        ]
+        .iter()
+        .filter_map(|line| IceCandidate::parse(line))
+        .collect();
+
+        let report = ConnectivityReport::from_candidates(candidates, 120);
+        assert_eq!(report.nat_type, NatType::EndpointDependent);
+        assert!(report.stun_reachable());
+        assert!(!report.turn_reachable());
+        assert_eq!(report.public_addresses().len(), 2);
+        assert!(report.recommendation().is_some());
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains("\"endpoint_dependent\""));
+    }
+
+    #[test]
+    fn test_parse_ice_url() {
+        assert_eq!(
+            parse_ice_url("stun:stun.l.google.com:19302"),
+            Some((ServerKind::Stun, "stun.l.google.com".to_string(), 19302))
+        );
+        assert_eq!(
+            parse_ice_url("turn:turn.example.com?transport=udp"),
+            Some((ServerKind::Turn, "turn.example.com".to_string(), 3478))
+        );
+        assert_eq!(
+            parse_ice_url("stun:[2001:db8::1]:3478"),
+            Some((ServerKind::Stun, "2001:db8::1".to_string(), 3478))
+        );
+        assert_eq!(
+            parse_ice_url("turn:turn.example.com:443?transport=tcp"),
+            None
+        );
+        assert_eq!(parse_ice_url("turns:turn.example.com:5349"), None);
+    }
+
+    #[test]
+    fn test_stun_binding_roundtrip() {
+        let transaction_id = stun::transaction_id();
+        let request = stun::binding_request(&transaction_id);
+        assert_eq!(request.len(), 20);
+        assert_eq!(&request[0..2], &stun::BINDING_REQUEST.to_be_bytes());
+
+        // Binding success with XOR-MAPPED-ADDRESS 203.0.113.5:6000
+        let mapped = addr("203.0.113.5:6000");
+        let cookie = stun::MAGIC_COOKIE.to_be_bytes();
+        let mut value = vec![0, 0x01];
+        value.extend_from_slice(&(6000u16 ^ 0x2112).to_be_bytes());
+        value.extend([203u8, 0, 113, 5].iter().zip(cookie).map(|(b, k)| b ^ k));
+
+        let mut response = Vec::new();
+        response.extend_from_slice(&stun::BINDING_SUCCESS.to_be_bytes());
+        response.extend_from_slice(&12u16.to_be_bytes());
+        response.extend_from_slice(&cookie);
+        response.extend_from_slice(&transaction_id);
+        response.extend_from_slice(&0x0020u16.to_be_bytes());
+        response.extend_from_slice(&8u16.to_be_bytes());
+        response.extend_from_slice(&value);
+
+        let decoded = stun::decode(&response, &transaction_id).unwrap();
+        assert_eq!(decoded.message_type, stun::BINDING_SUCCESS);
+        assert_eq!(decoded.mapped_address, Some(mapped));
+
+        // Other transactions are ignored
+        assert!(stun::decode(&response, &stun::transaction_id()).is_none());
+    }
+
+    #[test]
+    fn test_stun_error_code() {
+        let transaction_id = stun::transaction_id();
+        let request = stun::allocate_request(&transaction_id);
+        assert_eq!(request.len(), 28);
+
+        let mut response = Vec::new();
+        response.extend_from_slice(&stun::ALLOCATE_ERROR.to_be_bytes());
+        response.extend_from_slice(&16u16.to_be_bytes());
+        response.extend_from_slice(&stun::MAGIC_COOKIE.to_be_bytes());
+        response.extend_from_slice(&transaction_id);
+        response.extend_from_slice(&0x0009u16.to_be_bytes());
+        response.extend_from_slice(&12u16.to_be_bytes());
+        response.extend_from_slice(&[0, 0, 4, 1]);
+        response.extend_from_slice(b"Unauthd\0");
+
+        let decoded = stun::decode(&response, &transaction_id).unwrap();
+        assert_eq!(decoded.error_code, Some(401));
+    }
+
+    #[test]
+    fn test_diagnostics_against_local_stun_responder() {
+        use std::net::UdpSocket;
+
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let server_addr = server.local_addr().unwrap();
+
+        let responder = std::thread::spawn(move || {
+            let mut buf = [0u8; 1500];
+            let (len, from) = server.recv_from(&mut buf).unwrap();
+            assert!(len >= 20);
+
+            // Echo back a MAPPED-ADDRESS with the sender's address
+            let std::net::IpAddr::V4(ip) = from.ip() else {
+                unreachable!()
+            };
+            let mut response = Vec::new();
+            response.extend_from_slice(&stun::BINDING_SUCCESS.to_be_bytes());
+            response.extend_from_slice(&12u16.to_be_bytes());
+            response.extend_from_slice(&buf[4..20]);
+            response.extend_from_slice(&0x0001u16.to_be_bytes());
+            response.extend_from_slice(&8u16.to_be_bytes());
+            response.extend_from_slice(&[0, 0x01]);
+            response.extend_from_slice(&from.port().to_be_bytes());
+            response.extend_from_slice(&ip.octets());
+            server.send_to(&response, from).unwrap();
+        });
+
+        let ice_servers = vec![IceServer::stun(format!("stun:{}", server_addr))];
+        let report = run_diagnostics(&ice_servers, std::time::Duration::from_secs(2));
+        responder.join().unwrap();
+
+        assert_eq!(report.servers.len(), 1);
+        assert!(report.servers[0].reachable);
+        assert!(report.stun_reachable());
+        // Loopback: mapped address is our own host address
+        assert_eq!(report.nat_type, NatType::Open);
+    }
+
+    #[test]
+    fn test_clock_probe_against_local_ntp_responder() {
+        use std::net::UdpSocket;
+        use std::time::{Duration, SystemTime};
+
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let server_addr = server.local_addr().unwrap();
+
+        // A server whose clock is 10s ahead of ours
+        let responder = std::thread::spawn(move || {
+            let mut buf = [0u8; 48];
+            let (_, from) = server.recv_from(&mut buf).unwrap();
+            let now = sntp::timestamp(SystemTime::now() + Duration::from_secs(10));
+
+            let mut response = [0u8; 48];
+            response[0] = 0x1C;
+            response[1] = 2;
+            response[24..32].copy_from_slice(&buf[40..48]);
+            response[32..40].copy_from_slice(&now);
+            response[40..48].copy_from_slice(&now);
+            server.send_to(&response, from).unwrap();
+        });
+
+        let probe = probe_clock(&server_addr.to_string(), Duration::from_secs(2));
+        responder.join().unwrap();
+
+        let skew = probe.skew_ms.expect("skew measured");
+        assert!((-10_500..=-9_500).contains(&skew), "{skew}");
+
+        // Answers to another request are ignored
+        let request = sntp::request(SystemTime::now());
+        let mut stray = [0u8; 48];
+        stray[0] = 0x1C;
+        stray[1] = 2;
+        assert!(sntp::decode(&stray, &request).is_none());
+    }
+
+    #[test]
+    fn test_signalling_probe() {
+        assert_eq!(
+            parse_signalling_url("wss://match.konnektoren.help/room?x=1"),
+            Some(("match.konnektoren.help".to_string(), 443))
+        );
+        assert_eq!(
+            parse_signalling_url("ws://[::1]:3536"),
+            Some(("::1".to_string(), 3536))
+        );
+        assert_eq!(parse_signalling_url("stun:example.com"), None);
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let probe = probe_signalling(&url, std::time::Duration::from_secs(2));
+        assert!(probe.reachable);
+        assert!(probe.rtt_ms.is_some());
+
+        let probe = probe_signalling("matchbox", std::time::Duration::from_secs(2));
+        assert!(!probe.reachable);
+        assert!(probe.detail.is_some());
+    }
+
+    #[test]
+    fn test_checks() {
+        let candidates = [
+            "candidate:1 1 udp 2122252543 f3a1.local 60000 typ host",
+            "candidate:2 1 udp 1677729535 203.0.113.5 6000 typ srflx raddr 0.0.0.0 rport 0",
+        ]
+        .iter()
+        .filter_map(|line| IceCandidate::parse(line))
+        .collect();
+        let mut report = ConnectivityReport::from_candidates(candidates, 80);
+        report.signalling = Some(SignallingProbe {
+            url: "wss://match.konnektoren.help".to_string(),
+            reachable: false,
+            address: None,
+            rtt_ms: None,
+            detail: Some("cannot resolve match.konnektoren.help".to_string()),
+        });
+        report.clock = Some(ClockProbe {
+            server: "pool.ntp.org".to_string(),
+            skew_ms: Some(-120_000),
+            detail: None,
+        });
+
+        let checks = report.checks();
+        let status = |name: &str| {
+            checks
+                .iter()
+                .find(|check| check.name == name)
+                .map(|check| check.status)
+        };
+        assert_eq!(status("Signalling"), Some(CheckStatus::Fail));
+        assert_eq!(status("Candidates"), Some(CheckStatus::Pass));
+        assert_eq!(status("Clock"), Some(CheckStatus::Fail));
+        assert_eq!(status("NAT"), Some(CheckStatus::Warn));
+        assert_eq!(status("TURN"), None);
+        assert!(
+            checks
+                .iter()
+                .filter(|check| check.status != CheckStatus::Pass)
+                .all(|check| check.advice.is_some())
+        );
+
+        let host_only = ConnectivityReport::from_candidates(
+            IceCandidate::parse("candidate:1 1 udp 2122252543 f3a1.local 60000 typ host")
+                .into_iter()
+                .collect(),
+            10,
+        );
+        let checks = host_only.checks();
+        assert_eq!(checks.last().unwrap().status, CheckStatus::Fail);
+    }
+}
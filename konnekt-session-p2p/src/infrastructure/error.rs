@@ -1,3 +1,6 @@
+use crate::application::runtime::QueueError;
+use konnekt_session_core::{CodedError, ErrorCode};
+
 /// Infrastructure layer errors
 #[derive(Debug, thiserror::Error)]
 pub enum P2PError {
@@ -7,6 +10,9 @@ pub enum P2PError {
     #[error("Invalid session ID: {0}")]
     InvalidSessionId(String),
 
+    #[error("Invalid resume token: {0}")]
+    InvalidResumeToken(String),
+
     #[error("Peer not found: {0}")]
     PeerNotFound(String),
 
@@ -19,6 +25,9 @@ pub enum P2PError {
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
+    #[error("Storage error: {0}")]
+    Storage(String),
+
     #[error("Channel closed")]
     ChannelClosed,
 
@@ -26,4 +35,67 @@ pub enum P2PError {
     ParticipantError(#[from] konnekt_session_core::ParticipantError),
 }
 
+/// Why a command could not be submitted right now
+#[derive(Debug, thiserror::Error)]
+pub enum TrySubmitError {
+    /// The command queue is saturated; the command is handed back to retry
+    #[error("Command queue is full")]
+    Full(Box<konnekt_session_core::DomainCommand>),
+
+    #[error(transparent)]
+    Failed(#[from] P2PError),
+}
+
+impl CodedError for P2PError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            P2PError::ConnectionFailed(_) => ErrorCode::ConnectionFailed,
+            P2PError::InvalidSessionId(_) => ErrorCode::InvalidSessionId,
+            P2PError::InvalidResumeToken(_) => ErrorCode::InvalidResumeToken,
+            P2PError::PeerNotFound(_) => ErrorCode::PeerNotFound,
+            P2PError::SendFailed(_) => ErrorCode::SendFailed,
+            P2PError::ReceiveFailed(_) => ErrorCode::ReceiveFailed,
+            P2PError::Serialization(_) => ErrorCode::Serialization,
+            P2PError::Storage(_) => ErrorCode::Storage,
+            P2PError::ChannelClosed => ErrorCode::ChannelClosed,
+            P2PError::ParticipantError(e) => e.code(),
+        }
+    }
+}
+
+impl CodedError for TrySubmitError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            TrySubmitError::Full(_) => ErrorCode::QueueFull,
+            TrySubmitError::Failed(e) => e.code(),
+        }
+    }
+}
+
+impl CodedError for QueueError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            QueueError::Full { .. } => ErrorCode::QueueFull,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, P2PError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transport_errors_are_classified() {
+        let lost = P2PError::SendFailed("peer gone".to_string());
+        assert_eq!(lost.code(), ErrorCode::SendFailed);
+        assert!(lost.is_retryable());
+
+        let bad_id = P2PError::InvalidSessionId("not a uuid".to_string());
+        assert!(!bad_id.is_retryable());
+
+        let nested = P2PError::from(konnekt_session_core::ParticipantError::EmptyName);
+        assert_eq!(nested.code(), ErrorCode::InvalidName);
+    }
+}
@@ -0,0 +1,340 @@
+use crate::domain::LobbyEvent;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::infrastructure::error::P2PError;
+use crate::infrastructure::error::Result;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+
+/// Durable storage for the host's event log (allows restoring a lobby after a
+/// crash or restart)
+pub trait EventLogStore {
+    /// Persist a sequenced event
+    fn append(&mut self, event: &LobbyEvent) -> Result<()>;
+
+    /// Load all persisted events, ordered by sequence
+    fn load(&self) -> Result<Vec<LobbyEvent>>;
+
+    /// Remove all persisted events
+    fn clear(&mut self) -> Result<()>;
+}
+
+/// Open a store for `path`.
+///
+/// With the `sqlite` feature, `.db` / `.sqlite` paths use SQLite; everything
+/// else is a JSON-lines file.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn open_event_store(path: &Path) -> Result<Box<dyn EventLogStore + Send + Sync>> {
+    #[cfg(feature = "sqlite")]
+    if matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("db" | "sqlite")
+    ) {
+        return Ok(Box::new(SqliteEventLogStore::open(path)?));
+    }
+
+    Ok(Box::new(FileEventLogStore::open(path)?))
+}
+
+/// In-memory store (tests, WASM)
+#[derive(Debug, Default, Clone)]
+pub struct InMemoryEventLogStore {
+    events: Vec<LobbyEvent>,
+}
+
+impl InMemoryEventLogStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl EventLogStore for InMemoryEventLogStore {
+    fn append(&mut self, event: &LobbyEvent) -> Result<()> {
+        self.events.push(event.clone());
+        Ok(())
+    }
+
+    fn load(&self) -> Result<Vec<LobbyEvent>> {
+        let mut events = self.events.clone();
+        events.sort_by_key(|e| e.sequence);
+        Ok(events)
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        self.events.clear();
+        Ok(())
+    }
+}
+
+/// Append-only JSON-lines file (one `LobbyEvent` per line)
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub struct FileEventLogStore {
+    path: std::path::PathBuf,
+    file: std::fs::File,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileEventLogStore {
+    /// Open (or create) the log file at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| P2PError::Storage(format!("{}: {}", path.display(), e)))?;
+
+        // A crash mid-write leaves a partial last line; end it so the next
+        // event starts on a line of its own
+        if !ends_with_newline(&mut file).map_err(|e| P2PError::Storage(e.to_string()))? {
+            use std::io::Write;
+            tracing::warn!("Ending truncated last line of {}", path.display());
+            file.write_all(b"\n")
+                .map_err(|e| P2PError::Storage(e.to_string()))?;
+        }
+
+        tracing::info!("Opened event log file {}", path.display());
+        Ok(Self { path, file })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Whether `file` is empty or its last byte is a newline
+#[cfg(not(target_arch = "wasm32"))]
+fn ends_with_newline(file: &mut std::fs::File) -> std::io::Result<bool> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    if file.metadata()?.len() == 0 {
+        return Ok(true);
+    }
+    let mut last = [0u8; 1];
+    file.seek(SeekFrom::End(-1))?;
+    file.read_exact(&mut last)?;
+    Ok(last[0] == b'\n')
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl EventLogStore for FileEventLogStore {
+    fn append(&mut self, event: &LobbyEvent) -> Result<()> {
+        use std::io::Write;
+
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+
+        self.file
+            .write_all(&line)
+            .and_then(|_| self.file.flush())
+            .map_err(|e| P2PError::Storage(e.to_string()))
+    }
+
+    fn load(&self) -> Result<Vec<LobbyEvent>> {
+        let content = std::fs::read_to_string(&self.path)
+            .map_err(|e| P2PError::Storage(format!("{}: {}", self.path.display(), e)))?;
+
+        let mut events = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match serde_json::from_str::<LobbyEvent>(line) {
+                Ok(event) => events.push(event),
+                Err(e) => {
+                    // A crash mid-write leaves a truncated last line; keep what we have.
+                    tracing::warn!(
+                        "Skipping unreadable event log line {} in {}: {}",
+                        index + 1,
+                        self.path.display(),
+                        e
+                    );
+                }
+            }
+        }
+
+        events.sort_by_key(|e| e.sequence);
+        events.dedup_by_key(|e| e.sequence);
+        Ok(events)
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        self.file
+            .set_len(0)
+            .map_err(|e| P2PError::Storage(e.to_string()))
+    }
+}
+
+/// SQLite-backed store (native builds with the `sqlite` feature)
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+pub struct SqliteEventLogStore {
+    // `Connection` is not `Sync`; the lock lets the store live in shared state
+    conn: std::sync::Mutex<rusqlite::Connection>,
+}
+
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+impl SqliteEventLogStore {
+    /// Open (or create) the database at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let conn = rusqlite::Connection::open(path.as_ref())
+            .map_err(|e| P2PError::Storage(e.to_string()))?;
+        Self::init(conn)
+    }
+
+    /// Open a private in-memory database
+    pub fn open_in_memory() -> Result<Self> {
+        let conn =
+            rusqlite::Connection::open_in_memory().map_err(|e| P2PError::Storage(e.to_string()))?;
+        Self::init(conn)
+    }
+
+    fn init(conn: rusqlite::Connection) -> Result<Self> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS lobby_events (
+                sequence INTEGER PRIMARY KEY,
+                lobby_id TEXT NOT NULL,
+                payload  TEXT NOT NULL
+            );",
+        )
+        .map_err(|e| P2PError::Storage(e.to_string()))?;
+
+        Ok(Self {
+            conn: std::sync::Mutex::new(conn),
+        })
+    }
+
+    fn conn(&self) -> std::sync::MutexGuard<'_, rusqlite::Connection> {
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+impl EventLogStore for SqliteEventLogStore {
+    fn append(&mut self, event: &LobbyEvent) -> Result<()> {
+        let payload = serde_json::to_string(event)?;
+
+        self.conn()
+            .execute(
+                "INSERT OR REPLACE INTO lobby_events (sequence, lobby_id, payload) VALUES (?1, ?2, ?3)",
+                rusqlite::params![event.sequence as i64, event.lobby_id.to_string(), payload],
+            )
+            .map_err(|e| P2PError::Storage(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn load(&self) -> Result<Vec<LobbyEvent>> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare("SELECT payload FROM lobby_events ORDER BY sequence")
+            .map_err(|e| P2PError::Storage(e.to_string()))?;
+
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| P2PError::Storage(e.to_string()))?;
+
+        let mut events = Vec::new();
+        for row in rows {
+            let payload = row.map_err(|e| P2PError::Storage(e.to_string()))?;
+            events.push(serde_json::from_str(&payload)?);
+        }
+
+        Ok(events)
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        self.conn()
+            .execute("DELETE FROM lobby_events", [])
+            .map_err(|e| P2PError::Storage(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::DomainEvent;
+    use uuid::Uuid;
+
+    fn create_test_event(lobby_id: Uuid, sequence: u64) -> LobbyEvent {
+        LobbyEvent::new(
+            sequence,
+            lobby_id,
+            DomainEvent::GuestLeft {
+                participant_id: Uuid::new_v4(),
+            },
+        )
+    }
+
+    fn exercise_store(store: &mut dyn EventLogStore) {
+        let lobby_id = Uuid::new_v4();
+
+        store.append(&create_test_event(lobby_id, 2)).unwrap();
+        store.append(&create_test_event(lobby_id, 1)).unwrap();
+
+        let events = store.load().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].sequence, 1);
+        assert_eq!(events[1].sequence, 2);
+
+        store.clear().unwrap();
+        assert!(store.load().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_in_memory_store() {
+        exercise_store(&mut InMemoryEventLogStore::new());
+    }
+
+    #[test]
+    fn test_file_store() {
+        let path = std::env::temp_dir().join(format!("konnekt-events-{}.jsonl", Uuid::new_v4()));
+        let mut store = FileEventLogStore::open(&path).unwrap();
+
+        exercise_store(&mut store);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_file_store_survives_reopen_and_truncated_line() {
+        let path = std::env::temp_dir().join(format!("konnekt-events-{}.jsonl", Uuid::new_v4()));
+        let lobby_id = Uuid::new_v4();
+
+        {
+            let mut store = FileEventLogStore::open(&path).unwrap();
+            store.append(&create_test_event(lobby_id, 1)).unwrap();
+        }
+
+        // Simulate a crash mid-write
+        {
+            use std::io::Write;
+            let mut file = std::fs::OpenOptions::new()
+                .append(true)
+                .open(&path)
+                .unwrap();
+            file.write_all(b"{\"sequence\":2,\"lob").unwrap();
+        }
+
+        let mut store = FileEventLogStore::open(&path).unwrap();
+        let events = store.load().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].lobby_id, lobby_id);
+
+        // Events written after the restart must not be glued onto the partial line
+        store.append(&create_test_event(lobby_id, 2)).unwrap();
+        let store = FileEventLogStore::open(&path).unwrap();
+        let sequences: Vec<u64> = store.load().unwrap().iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, vec![1, 2]);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_store() {
+        exercise_store(&mut SqliteEventLogStore::open_in_memory().unwrap());
+    }
+}
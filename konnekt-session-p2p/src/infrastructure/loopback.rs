@@ -0,0 +1,353 @@
+use crate::application::ConnectionEvent;
+use crate::domain::PeerId;
+use crate::infrastructure::error::{P2PError, Result};
+use crate::infrastructure::network_sim::{NetworkConditions, NetworkSimulator};
+use crate::infrastructure::transport::NetworkConnection;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use uuid::Uuid;
+
+/// In-process network shared by `LoopbackConnection`s
+///
+/// Every connection sees every other connection as a directly connected
+/// peer; messages are delivered on the receiver's next `poll_events`.
+///
+/// Link quality can be degraded through a `NetworkSimulator`; delayed
+/// messages then arrive as the network is advanced with `tick`.
+#[derive(Debug, Clone, Default)]
+pub struct LoopbackNetwork {
+    inboxes: Arc<Mutex<HashMap<PeerId, Inbox>>>,
+    simulator: Arc<Mutex<NetworkSimulator>>,
+}
+
+/// Handles are equal when they belong to the same network
+impl PartialEq for LoopbackNetwork {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inboxes, &other.inboxes)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inbox {
+    events: VecDeque<ConnectionEvent>,
+    /// Task waiting in `poll_ready`
+    waker: Option<Waker>,
+}
+
+impl Inbox {
+    fn push(&mut self, event: ConnectionEvent) {
+        self.events.push_back(event);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl LoopbackNetwork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Network whose links follow the given simulator
+    pub fn with_simulator(simulator: NetworkSimulator) -> Self {
+        Self {
+            inboxes: Arc::default(),
+            simulator: Arc::new(Mutex::new(simulator)),
+        }
+    }
+
+    /// Change link quality for messages sent from now on
+    pub fn set_conditions(&self, conditions: NetworkConditions) {
+        self.simulator.lock().unwrap().set_conditions(conditions);
+    }
+
+    /// Cut every link between the two groups of peers
+    pub fn partition(&self, side_a: &[PeerId], side_b: &[PeerId]) {
+        self.simulator.lock().unwrap().partition(side_a, side_b);
+    }
+
+    /// Remove all partitions
+    pub fn heal(&self) {
+        self.simulator.lock().unwrap().heal();
+    }
+
+    /// Advance network time by one tick, delivering messages that arrived
+    pub fn tick(&self) {
+        self.simulator.lock().unwrap().tick();
+        self.deliver_due();
+    }
+
+    /// Messages lost to packet loss or partitions so far
+    pub fn dropped(&self) -> u64 {
+        self.simulator.lock().unwrap().dropped()
+    }
+
+    /// Join the network as a new peer
+    pub fn connect(&self) -> LoopbackConnection {
+        self.connect_as(PeerId::new(matchbox_socket::PeerId(Uuid::new_v4())))
+    }
+
+    /// Join the network with a chosen peer ID (reproducible simulations)
+    pub fn connect_as(&self, local_id: PeerId) -> LoopbackConnection {
+        let mut inboxes = self.inboxes.lock().unwrap();
+
+        let mut own_inbox = Inbox::default();
+        for (peer, inbox) in inboxes.iter_mut() {
+            inbox.push(ConnectionEvent::PeerConnected(local_id));
+            own_inbox.push(ConnectionEvent::PeerConnected(*peer));
+        }
+        inboxes.insert(local_id, own_inbox);
+
+        tracing::debug!("Loopback peer {} connected", local_id);
+
+        LoopbackConnection {
+            local_id,
+            network: self.clone(),
+        }
+    }
+
+    /// Number of connected peers
+    pub fn peer_count(&self) -> usize {
+        self.inboxes.lock().unwrap().len()
+    }
+
+    fn transmit(&self, from: PeerId, to: PeerId, data: Vec<u8>) {
+        self.simulator.lock().unwrap().send(from, to, data);
+        self.deliver_due();
+    }
+
+    fn deliver_due(&self) {
+        let due = self.simulator.lock().unwrap().take_due();
+        let mut inboxes = self.inboxes.lock().unwrap();
+
+        for delivery in due {
+            if let Some(inbox) = inboxes.get_mut(&delivery.to) {
+                inbox.push(ConnectionEvent::MessageReceived {
+                    from: delivery.from,
+                    data: delivery.data,
+                });
+            }
+        }
+    }
+
+    fn disconnect(&self, local_id: PeerId) {
+        let mut inboxes = self.inboxes.lock().unwrap();
+        inboxes.remove(&local_id);
+
+        for inbox in inboxes.values_mut() {
+            inbox.push(ConnectionEvent::PeerDisconnected(local_id));
+        }
+
+        tracing::debug!("Loopback peer {} disconnected", local_id);
+    }
+}
+
+/// In-memory `NetworkConnection` (tests, single-process demos, custom relays)
+///
+/// Dropping the connection disconnects it from the network.
+#[derive(Debug)]
+pub struct LoopbackConnection {
+    local_id: PeerId,
+    network: LoopbackNetwork,
+}
+
+impl NetworkConnection for LoopbackConnection {
+    fn local_peer_id(&self) -> Option<PeerId> {
+        Some(self.local_id)
+    }
+
+    fn connected_peers(&self) -> Vec<PeerId> {
+        let mut peers: Vec<_> = self
+            .network
+            .inboxes
+            .lock()
+            .unwrap()
+            .keys()
+            .filter(|peer| **peer != self.local_id)
+            .copied()
+            .collect();
+        // Stable order keeps simulated runs reproducible
+        peers.sort_by_key(|peer| peer.inner().0);
+        peers
+    }
+
+    fn send_to(&mut self, peer: PeerId, data: Vec<u8>) -> Result<()> {
+        if !self.network.inboxes.lock().unwrap().contains_key(&peer) {
+            return Err(P2PError::PeerNotFound(peer.to_string()));
+        }
+
+        self.network.transmit(self.local_id, peer, data);
+        Ok(())
+    }
+
+    fn broadcast(&mut self, data: Vec<u8>) -> Result<()> {
+        for peer in self.connected_peers() {
+            self.network.transmit(self.local_id, peer, data.clone());
+        }
+        Ok(())
+    }
+
+    fn poll_events(&mut self) -> Vec<ConnectionEvent> {
+        self.network
+            .inboxes
+            .lock()
+            .unwrap()
+            .get_mut(&self.local_id)
+            .map(|inbox| inbox.events.drain(..).collect())
+            .unwrap_or_default()
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let mut inboxes = self.network.inboxes.lock().unwrap();
+        let Some(inbox) = inboxes.get_mut(&self.local_id) else {
+            return Poll::Pending;
+        };
+
+        if inbox.events.is_empty() {
+            inbox.waker = Some(cx.waker().clone());
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+}
+
+impl Drop for LoopbackConnection {
+    fn drop(&mut self) {
+        self.network.disconnect(self.local_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peers_see_each_other() {
+        let network = LoopbackNetwork::new();
+        let mut a = network.connect();
+        let mut b = network.connect();
+
+        assert_eq!(a.connected_peers(), vec![b.local_id]);
+        assert_eq!(b.connected_peers(), vec![a.local_id]);
+
+        assert!(matches!(
+            a.poll_events().as_slice(),
+            [ConnectionEvent::PeerConnected(peer)] if *peer == b.local_id
+        ));
+        assert!(matches!(
+            b.poll_events().as_slice(),
+            [ConnectionEvent::PeerConnected(peer)] if *peer == a.local_id
+        ));
+    }
+
+    #[test]
+    fn test_send_and_broadcast() {
+        let network = LoopbackNetwork::new();
+        let mut a = network.connect();
+        let mut b = network.connect();
+        let mut c = network.connect();
+        b.poll_events();
+        c.poll_events();
+
+        a.send_to(b.local_id, b"direct".to_vec()).unwrap();
+        a.broadcast(b"all".to_vec()).unwrap();
+
+        let received: Vec<_> = b
+            .poll_events()
+            .into_iter()
+            .filter_map(|e| match e {
+                ConnectionEvent::MessageReceived { data, .. } => Some(data),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(received, vec![b"direct".to_vec(), b"all".to_vec()]);
+        assert_eq!(c.poll_events().len(), 1);
+        assert!(
+            a.poll_events()
+                .iter()
+                .all(|e| !matches!(e, ConnectionEvent::MessageReceived { .. }))
+        );
+    }
+
+    #[test]
+    fn test_poll_ready_wakes_on_delivery() {
+        use futures::task::{ArcWake, waker};
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        struct Flag(AtomicBool);
+        impl ArcWake for Flag {
+            fn wake_by_ref(arc_self: &Arc<Self>) {
+                arc_self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let network = LoopbackNetwork::new();
+        let mut a = network.connect();
+        let mut b = network.connect();
+        b.poll_events();
+
+        let flag = Arc::new(Flag(AtomicBool::new(false)));
+        let waker = waker(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(b.poll_ready(&mut cx).is_pending());
+        a.send_to(b.local_id, vec![1]).unwrap();
+
+        assert!(flag.0.load(Ordering::SeqCst));
+        assert!(b.poll_ready(&mut cx).is_ready());
+    }
+
+    #[test]
+    fn test_simulated_latency_and_partition() {
+        let network = LoopbackNetwork::with_simulator(
+            NetworkSimulator::new(1).with_conditions(NetworkConditions::perfect().with_latency(2)),
+        );
+        let mut a = network.connect();
+        let mut b = network.connect();
+        b.poll_events();
+
+        a.send_to(b.local_id, vec![1]).unwrap();
+        network.tick();
+        assert!(b.poll_events().is_empty());
+        network.tick();
+        assert_eq!(b.poll_events().len(), 1);
+
+        network.partition(&[a.local_id], &[b.local_id]);
+        a.broadcast(vec![2]).unwrap();
+        network.tick();
+        network.tick();
+        assert!(b.poll_events().is_empty());
+        assert_eq!(network.dropped(), 1);
+    }
+
+    #[test]
+    fn test_send_to_unknown_peer_fails() {
+        let network = LoopbackNetwork::new();
+        let mut a = network.connect();
+        let unknown = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
+
+        assert!(matches!(
+            a.send_to(unknown, vec![1]),
+            Err(P2PError::PeerNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_drop_disconnects() {
+        let network = LoopbackNetwork::new();
+        let mut a = network.connect();
+        let b = network.connect();
+        let b_id = b.local_id;
+        a.poll_events();
+
+        drop(b);
+
+        assert_eq!(network.peer_count(), 1);
+        assert!(matches!(
+            a.poll_events().as_slice(),
+            [ConnectionEvent::PeerDisconnected(peer)] if *peer == b_id
+        ));
+    }
+}
@@ -1,4 +1,9 @@
+use crate::domain::PeerId;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Maximum number of relay hops (guest → host → guest)
+pub const MAX_RELAY_HOPS: u8 = 1;
 
 /// Generic P2P message envelope
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -6,11 +11,60 @@ pub struct P2PMessage {
     /// Sequence number for ordering
     pub sequence: u64,
 
+    /// Routing metadata (only set for messages relayed through the host)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub route: Option<MessageRoute>,
+
+    /// Logical lobby this message belongs to, when one session carries
+    /// several lobbies (breakout rooms). `None` = the session's main lobby.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<Uuid>,
+
     /// Message type discriminator
     #[serde(flatten)]
     pub kind: MessageKind,
 }
 
+/// Routing metadata for star-topology relaying
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageRoute {
+    /// Peer that originally sent the message
+    pub origin: PeerId,
+
+    /// Final recipient (`None` = every peer except the origin)
+    pub destination: Option<PeerId>,
+
+    /// Number of times this message has been forwarded
+    pub hops: u8,
+}
+
+impl MessageRoute {
+    pub fn new(origin: PeerId, destination: Option<PeerId>) -> Self {
+        Self {
+            origin,
+            destination,
+            hops: 0,
+        }
+    }
+
+    /// Route for the next hop, or `None` if the hop limit is reached
+    pub fn next_hop(&self) -> Option<Self> {
+        if self.hops >= MAX_RELAY_HOPS {
+            return None;
+        }
+
+        Some(Self {
+            hops: self.hops + 1,
+            ..*self
+        })
+    }
+
+    /// Whether `peer` should receive this message
+    pub fn is_for(&self, peer: PeerId) -> bool {
+        peer != self.origin && self.destination.is_none_or(|dest| dest == peer)
+    }
+}
+
 /// Message types (control + application)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data")]
@@ -39,6 +93,22 @@ pub enum MessageKind {
     /// Response with missing messages
     #[serde(rename = "resend_resp")]
     ResendResponse { messages: Vec<P2PMessage> },
+
+    /// Payload forwarded through the host (see `P2PMessage::route`)
+    #[serde(rename = "relay")]
+    Relay { payload: serde_json::Value },
+
+    /// Round-trip probe, answered with a `Pong` carrying the same nonce
+    #[serde(rename = "ping")]
+    Ping { nonce: u64 },
+
+    #[serde(rename = "pong")]
+    Pong {
+        nonce: u64,
+        /// The answering peer's wall clock (Unix ms), for clock sync
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        at: Option<u64>,
+    },
 }
 
 impl P2PMessage {
@@ -46,6 +116,8 @@ impl P2PMessage {
     pub fn application(payload: serde_json::Value) -> Self {
         Self {
             sequence: 0, // Will be assigned by transport
+            route: None,
+            topic: None,
             kind: MessageKind::Application { payload },
         }
     }
@@ -54,6 +126,8 @@ impl P2PMessage {
     pub fn snapshot_request() -> Self {
         Self {
             sequence: 0,
+            route: None,
+            topic: None,
             kind: MessageKind::SnapshotRequest,
         }
     }
@@ -62,6 +136,8 @@ impl P2PMessage {
     pub fn snapshot_response(snapshot: serde_json::Value, as_of_sequence: u64) -> Self {
         Self {
             sequence: 0,
+            route: None,
+            topic: None,
             kind: MessageKind::SnapshotResponse {
                 snapshot,
                 as_of_sequence,
@@ -73,9 +149,50 @@ impl P2PMessage {
     pub fn resend_request(from: u64, to: u64) -> Self {
         Self {
             sequence: 0,
+            route: None,
+            topic: None,
             kind: MessageKind::ResendRequest { from, to },
         }
     }
+
+    /// Create a relayed message (guest → host → destination)
+    pub fn relay(origin: PeerId, destination: Option<PeerId>, payload: serde_json::Value) -> Self {
+        Self {
+            sequence: 0,
+            route: Some(MessageRoute::new(origin, destination)),
+            topic: None,
+            kind: MessageKind::Relay { payload },
+        }
+    }
+
+    /// Create a round-trip probe
+    pub fn ping(nonce: u64) -> Self {
+        Self {
+            sequence: 0,
+            route: None,
+            topic: None,
+            kind: MessageKind::Ping { nonce },
+        }
+    }
+
+    /// Create the answer to a probe, stamped with our wall clock `at`
+    pub fn pong(nonce: u64, at: u64) -> Self {
+        Self {
+            sequence: 0,
+            route: None,
+            topic: None,
+            kind: MessageKind::Pong {
+                nonce,
+                at: Some(at),
+            },
+        }
+    }
+
+    /// Tag the message with a lobby topic
+    pub fn with_topic(mut self, topic: Uuid) -> Self {
+        self.topic = Some(topic);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -101,4 +218,84 @@ mod tests {
         let msg = P2PMessage::snapshot_request();
         assert!(matches!(msg.kind, MessageKind::SnapshotRequest));
     }
+
+    #[test]
+    fn test_unrouted_message_omits_route() {
+        let msg = P2PMessage::snapshot_request();
+        let json = serde_json::to_value(&msg).unwrap();
+        assert!(json.get("route").is_none());
+    }
+
+    #[test]
+    fn test_topic_roundtrip() {
+        let topic = Uuid::new_v4();
+        let msg = P2PMessage::application(serde_json::json!({})).with_topic(topic);
+
+        let json = serde_json::to_string(&msg).unwrap();
+        let deserialized: P2PMessage = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.topic, Some(topic));
+
+        let untagged = serde_json::to_value(P2PMessage::snapshot_request()).unwrap();
+        assert!(untagged.get("topic").is_none());
+    }
+
+    #[test]
+    fn test_relay_roundtrip() {
+        let origin = PeerId::new(matchbox_socket::PeerId(uuid::Uuid::new_v4()));
+        let dest = PeerId::new(matchbox_socket::PeerId(uuid::Uuid::new_v4()));
+
+        let msg = P2PMessage::relay(origin, Some(dest), serde_json::json!({"type": "ping"}));
+        let json = serde_json::to_string(&msg).unwrap();
+        let deserialized: P2PMessage = serde_json::from_str(&json).unwrap();
+
+        let route = deserialized
+            .route
+            .expect("route should survive serialization");
+        assert_eq!(route.origin, origin);
+        assert_eq!(route.destination, Some(dest));
+        assert_eq!(route.hops, 0);
+        assert!(matches!(deserialized.kind, MessageKind::Relay { .. }));
+    }
+
+    #[test]
+    fn test_pong_without_clock_stays_readable() {
+        let old: P2PMessage =
+            serde_json::from_str(r#"{"sequence":0,"type":"pong","data":{"nonce":3}}"#).unwrap();
+        assert!(matches!(old.kind, MessageKind::Pong { nonce: 3, at: None }));
+
+        let json = serde_json::to_string(&P2PMessage::pong(3, 1_000)).unwrap();
+        let new: P2PMessage = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            new.kind,
+            MessageKind::Pong {
+                nonce: 3,
+                at: Some(1_000)
+            }
+        ));
+    }
+
+    #[test]
+    fn test_route_hop_limit() {
+        let origin = PeerId::new(matchbox_socket::PeerId(uuid::Uuid::new_v4()));
+        let route = MessageRoute::new(origin, None);
+
+        let forwarded = route.next_hop().expect("first hop allowed");
+        assert_eq!(forwarded.hops, 1);
+        assert!(forwarded.next_hop().is_none());
+    }
+
+    #[test]
+    fn test_route_is_for() {
+        let origin = PeerId::new(matchbox_socket::PeerId(uuid::Uuid::new_v4()));
+        let a = PeerId::new(matchbox_socket::PeerId(uuid::Uuid::new_v4()));
+        let b = PeerId::new(matchbox_socket::PeerId(uuid::Uuid::new_v4()));
+
+        let broadcast = MessageRoute::new(origin, None);
+        assert!(broadcast.is_for(a));
+        assert!(!broadcast.is_for(origin));
+
+        let direct = MessageRoute::new(origin, Some(a));
+        assert!(direct.is_for(a));
+        assert!(!direct.is_for(b));
+    }
 }
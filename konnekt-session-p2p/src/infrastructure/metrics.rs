@@ -0,0 +1,167 @@
+//! Runtime metrics
+//!
+//! With the `metrics` feature, `P2PLoop` and `SessionLoop` record counters,
+//! gauges and histograms through the [`metrics`](https://docs.rs/metrics)
+//! facade. Native hosts install a recorder (e.g. `metrics-exporter-prometheus`)
+//! to scrape them. Without the feature every call compiles to nothing.
+
+/// Events handled per poll (label `source`: `network` or `domain`)
+pub const EVENTS_PROCESSED: &str = "konnekt_session_events_processed_total";
+/// Messages sent from the outbound queue
+pub const MESSAGES_SENT: &str = "konnekt_session_messages_sent_total";
+/// Messages waiting in the outbound queue
+pub const OUTBOUND_QUEUE_DEPTH: &str = "konnekt_session_outbound_queue_depth";
+/// Commands waiting in the domain command queue
+pub const COMMAND_QUEUE_DEPTH: &str = "konnekt_session_command_queue_depth";
+/// Seconds from a guest's full sync request until the snapshot is applied
+pub const SYNC_LATENCY: &str = "konnekt_session_sync_latency_seconds";
+/// Size of full sync messages sent by the host, in bytes
+pub const SNAPSHOT_SIZE: &str = "konnekt_session_snapshot_size_bytes";
+
+pub(crate) use recorder::*;
+
+/// Register descriptions and units with the installed recorder
+///
+/// Optional: metrics are recorded either way, this only adds help texts.
+pub fn describe() {
+    recorder::describe();
+}
+
+#[cfg(feature = "metrics")]
+mod recorder {
+    use super::*;
+    use ::metrics::{
+        Unit, counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram,
+    };
+    use instant::Duration;
+
+    pub(super) fn describe() {
+        describe_counter!(EVENTS_PROCESSED, "Events handled by the session loops");
+        describe_counter!(MESSAGES_SENT, "Messages sent from the outbound queue");
+        describe_gauge!(
+            OUTBOUND_QUEUE_DEPTH,
+            "Messages waiting in the outbound queue"
+        );
+        describe_gauge!(COMMAND_QUEUE_DEPTH, "Commands waiting in the domain queue");
+        describe_histogram!(
+            SYNC_LATENCY,
+            Unit::Seconds,
+            "Time from a full sync request until the snapshot is applied"
+        );
+        describe_histogram!(
+            SNAPSHOT_SIZE,
+            Unit::Bytes,
+            "Size of full sync messages sent by the host"
+        );
+    }
+
+    pub(crate) fn events_processed(source: &'static str, count: usize) {
+        if count > 0 {
+            counter!(EVENTS_PROCESSED, "source" => source).increment(count as u64);
+        }
+    }
+
+    pub(crate) fn messages_sent(count: usize) {
+        if count > 0 {
+            counter!(MESSAGES_SENT).increment(count as u64);
+        }
+    }
+
+    pub(crate) fn outbound_queue_depth(depth: usize) {
+        gauge!(OUTBOUND_QUEUE_DEPTH).set(depth as f64);
+    }
+
+    pub(crate) fn command_queue_depth(depth: usize) {
+        gauge!(COMMAND_QUEUE_DEPTH).set(depth as f64);
+    }
+
+    pub(crate) fn sync_latency(elapsed: Duration) {
+        histogram!(SYNC_LATENCY).record(elapsed.as_secs_f64());
+    }
+
+    pub(crate) fn snapshot_size(bytes: usize) {
+        histogram!(SNAPSHOT_SIZE).record(bytes as f64);
+    }
+}
+
+#[cfg(not(feature = "metrics"))]
+mod recorder {
+    use instant::Duration;
+
+    pub(super) fn describe() {}
+
+    pub(crate) fn events_processed(_source: &'static str, _count: usize) {}
+
+    pub(crate) fn messages_sent(_count: usize) {}
+
+    pub(crate) fn outbound_queue_depth(_depth: usize) {}
+
+    pub(crate) fn command_queue_depth(_depth: usize) {}
+
+    pub(crate) fn sync_latency(_elapsed: Duration) {}
+
+    pub(crate) fn snapshot_size(_bytes: usize) {}
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+    use super::*;
+    use crate::{LoopbackNetwork, P2PLoopBuilder, SessionId};
+    use konnekt_session_core::DomainCommand;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    #[test]
+    fn test_session_records_metrics() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        ::metrics::with_local_recorder(&recorder, || {
+            let network = LoopbackNetwork::new();
+            let session_id = SessionId::new();
+            let (mut host, _) = P2PLoopBuilder::new()
+                .build_session_host_with_connection(
+                    network.connect(),
+                    session_id.clone(),
+                    "Metrics".to_string(),
+                    "Host".to_string(),
+                )
+                .unwrap();
+            let (mut guest, _) = P2PLoopBuilder::new()
+                .build_session_guest_with_connection(network.connect(), session_id);
+
+            guest
+                .submit_command(DomainCommand::JoinLobby {
+                    lobby_id: host.lobby_id(),
+                    guest_name: "Guest".to_string(),
+                })
+                .unwrap();
+            for _ in 0..5 {
+                host.poll();
+                guest.poll();
+            }
+        });
+
+        let recorded: Vec<_> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| (key.key().name().to_string(), value))
+            .collect();
+        let value = |name: &str| {
+            recorded
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value)
+        };
+
+        assert!(matches!(value(EVENTS_PROCESSED), Some(DebugValue::Counter(n)) if *n > 0));
+        assert!(matches!(value(MESSAGES_SENT), Some(DebugValue::Counter(n)) if *n > 0));
+        assert!(matches!(
+            value(OUTBOUND_QUEUE_DEPTH),
+            Some(DebugValue::Gauge(_))
+        ));
+        assert!(
+            matches!(value(SNAPSHOT_SIZE), Some(DebugValue::Histogram(sizes)) if !sizes.is_empty())
+        );
+    }
+}
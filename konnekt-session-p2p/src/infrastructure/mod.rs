@@ -1,9 +1,29 @@
 pub mod connection;
+pub mod diagnostics;
 pub mod error;
+pub mod event_store;
+pub mod loopback;
 pub mod message;
+pub mod metrics;
+pub mod network_sim;
 pub mod transport;
 pub mod transport_builder;
+pub mod webtransport;
 
-pub use message::{MessageKind, P2PMessage};
+pub use diagnostics::{
+    CandidateKind, CheckStatus, ClockProbe, ConnectivityReport, DiagnosticCheck, IceCandidate,
+    NatType, ServerKind, ServerProbe, SignallingProbe,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use diagnostics::{probe_clock, probe_signalling, run_diagnostics};
+#[cfg(all(feature = "sqlite", not(target_arch = "wasm32")))]
+pub use event_store::SqliteEventLogStore;
+pub use event_store::{EventLogStore, InMemoryEventLogStore};
+#[cfg(not(target_arch = "wasm32"))]
+pub use event_store::{FileEventLogStore, open_event_store};
+pub use loopback::{LoopbackConnection, LoopbackNetwork};
+pub use message::{MAX_RELAY_HOPS, MessageKind, MessageRoute, P2PMessage};
+pub use network_sim::{NetworkConditions, NetworkSimulator, SimRng};
 pub use transport::{MatchboxP2PTransport, NetworkConnection, P2PTransport, TransportEvent};
 pub use transport_builder::P2PTransportBuilder;
+pub use webtransport::WebTransportConnection;
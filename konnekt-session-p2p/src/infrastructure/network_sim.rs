@@ -0,0 +1,338 @@
+use crate::domain::PeerId;
+use std::collections::HashSet;
+
+/// Link quality applied to every simulated message
+///
+/// Delays are counted in network ticks (see `NetworkSimulator::tick`), so a
+/// run with the same seed and conditions is fully reproducible.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct NetworkConditions {
+    /// Delivery delay in ticks
+    pub latency: u32,
+    /// Extra random delay of up to this many ticks
+    pub jitter: u32,
+    /// Probability (0.0–1.0) that a message is dropped
+    pub loss: f64,
+    /// Probability (0.0–1.0) that a message is held back so later ones overtake it
+    pub reorder: f64,
+}
+
+impl NetworkConditions {
+    /// Instant, lossless, in-order delivery
+    pub fn perfect() -> Self {
+        Self::default()
+    }
+
+    pub fn with_latency(mut self, ticks: u32) -> Self {
+        self.latency = ticks;
+        self
+    }
+
+    pub fn with_jitter(mut self, ticks: u32) -> Self {
+        self.jitter = ticks;
+        self
+    }
+
+    pub fn with_loss(mut self, probability: f64) -> Self {
+        self.loss = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_reorder(mut self, probability: f64) -> Self {
+        self.reorder = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn is_perfect(&self) -> bool {
+        *self == Self::perfect()
+    }
+}
+
+/// Small seeded PRNG (xorshift64*) for reproducible simulations
+#[derive(Debug, Clone)]
+pub struct SimRng(u64);
+
+impl SimRng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift must not start from zero
+        Self(seed ^ 0x9E37_79B9_7F4A_7C15)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Uniform value in `0..bound` (0 if `bound` is 0)
+    pub fn below(&mut self, bound: u32) -> u32 {
+        if bound == 0 {
+            0
+        } else {
+            (self.next_u64() % bound as u64) as u32
+        }
+    }
+
+    /// `true` with the given probability
+    pub fn chance(&mut self, probability: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < probability
+    }
+}
+
+#[derive(Debug)]
+struct InFlight {
+    deliver_at: u64,
+    /// Send order, keeps equal deadlines FIFO
+    order: u64,
+    from: PeerId,
+    to: PeerId,
+    data: Vec<u8>,
+}
+
+/// A message due for delivery
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delivery {
+    pub from: PeerId,
+    pub to: PeerId,
+    pub data: Vec<u8>,
+}
+
+/// Deterministic model of an unreliable network
+///
+/// Sits between in-memory connections and their inboxes: `send` applies
+/// loss, latency, jitter, reordering and partitions; `tick` advances time;
+/// `take_due` yields the messages that arrived. With perfect conditions
+/// every message is due immediately.
+#[derive(Debug)]
+pub struct NetworkSimulator {
+    conditions: NetworkConditions,
+    rng: SimRng,
+    now: u64,
+    next_order: u64,
+    in_flight: Vec<InFlight>,
+    /// Directed links that currently drop everything
+    partitions: HashSet<(PeerId, PeerId)>,
+    dropped: u64,
+}
+
+impl Default for NetworkSimulator {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl NetworkSimulator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            conditions: NetworkConditions::perfect(),
+            rng: SimRng::new(seed),
+            now: 0,
+            next_order: 0,
+            in_flight: Vec::new(),
+            partitions: HashSet::new(),
+            dropped: 0,
+        }
+    }
+
+    pub fn with_conditions(mut self, conditions: NetworkConditions) -> Self {
+        self.conditions = conditions;
+        self
+    }
+
+    pub fn conditions(&self) -> NetworkConditions {
+        self.conditions
+    }
+
+    /// Change link quality (applies to messages sent from now on)
+    pub fn set_conditions(&mut self, conditions: NetworkConditions) {
+        self.conditions = conditions;
+    }
+
+    /// Current network time in ticks
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Messages sent but not yet delivered
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Messages lost to packet loss or partitions so far
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Cut every link between the two groups (both directions)
+    pub fn partition(&mut self, side_a: &[PeerId], side_b: &[PeerId]) {
+        for a in side_a {
+            for b in side_b {
+                self.partitions.insert((*a, *b));
+                self.partitions.insert((*b, *a));
+            }
+        }
+    }
+
+    /// Remove all partitions
+    pub fn heal(&mut self) {
+        self.partitions.clear();
+    }
+
+    pub fn is_partitioned(&self, from: PeerId, to: PeerId) -> bool {
+        self.partitions.contains(&(from, to))
+    }
+
+    /// Put a message on the wire (it may be dropped)
+    pub fn send(&mut self, from: PeerId, to: PeerId, data: Vec<u8>) {
+        if self.is_partitioned(from, to) || self.rng.chance(self.conditions.loss) {
+            self.dropped += 1;
+            return;
+        }
+
+        let NetworkConditions {
+            latency, jitter, ..
+        } = self.conditions;
+        let mut delay = latency as u64 + self.rng.below(jitter + 1) as u64;
+        if self.rng.chance(self.conditions.reorder) {
+            delay += 1 + self.rng.below(latency + jitter + 1) as u64;
+        }
+
+        self.in_flight.push(InFlight {
+            deliver_at: self.now + delay,
+            order: self.next_order,
+            from,
+            to,
+            data,
+        });
+        self.next_order += 1;
+    }
+
+    /// Advance network time by one tick
+    pub fn tick(&mut self) {
+        self.now += 1;
+    }
+
+    /// Remove and return messages that have arrived, in arrival order
+    ///
+    /// Messages caught by a partition raised while they were in flight are
+    /// dropped.
+    pub fn take_due(&mut self) -> Vec<Delivery> {
+        let now = self.now;
+        let (mut due, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.in_flight)
+            .into_iter()
+            .partition(|m| m.deliver_at <= now);
+        self.in_flight = pending;
+
+        due.sort_by_key(|m| (m.deliver_at, m.order));
+        let before = due.len();
+        let delivered: Vec<_> = due
+            .into_iter()
+            .filter(|m| !self.partitions.contains(&(m.from, m.to)))
+            .map(|m| Delivery {
+                from: m.from,
+                to: m.to,
+                data: m.data,
+            })
+            .collect();
+        self.dropped += (before - delivered.len()) as u64;
+
+        delivered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn peer() -> PeerId {
+        PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_perfect_network_delivers_immediately() {
+        let (a, b) = (peer(), peer());
+        let mut sim = NetworkSimulator::new(1);
+
+        sim.send(a, b, vec![1]);
+        sim.send(a, b, vec![2]);
+
+        let data: Vec<_> = sim.take_due().into_iter().map(|d| d.data).collect();
+        assert_eq!(data, vec![vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn test_latency_delays_delivery() {
+        let (a, b) = (peer(), peer());
+        let mut sim =
+            NetworkSimulator::new(1).with_conditions(NetworkConditions::perfect().with_latency(3));
+
+        sim.send(a, b, vec![1]);
+        for _ in 0..2 {
+            sim.tick();
+            assert!(sim.take_due().is_empty());
+        }
+        sim.tick();
+        assert_eq!(sim.take_due().len(), 1);
+    }
+
+    #[test]
+    fn test_loss_is_seeded() {
+        let (a, b) = (peer(), peer());
+        let run = |seed| {
+            let mut sim = NetworkSimulator::new(seed)
+                .with_conditions(NetworkConditions::perfect().with_loss(0.2));
+            for i in 0..1000u32 {
+                sim.send(a, b, i.to_be_bytes().to_vec());
+            }
+            sim.take_due()
+        };
+
+        let delivered = run(7);
+        assert_eq!(delivered, run(7));
+        assert!((700..900).contains(&delivered.len()));
+    }
+
+    #[test]
+    fn test_reorder_lets_later_messages_overtake() {
+        let (a, b) = (peer(), peer());
+        let mut sim = NetworkSimulator::new(3).with_conditions(
+            NetworkConditions::perfect()
+                .with_latency(1)
+                .with_reorder(0.5),
+        );
+
+        for i in 0..50u8 {
+            sim.send(a, b, vec![i]);
+        }
+        let mut received = Vec::new();
+        for _ in 0..10 {
+            sim.tick();
+            received.extend(sim.take_due().into_iter().map(|d| d.data[0]));
+        }
+
+        assert_eq!(received.len(), 50);
+        assert!(received.windows(2).any(|w| w[0] > w[1]));
+    }
+
+    #[test]
+    fn test_partition_drops_until_healed() {
+        let (a, b) = (peer(), peer());
+        let mut sim =
+            NetworkSimulator::new(1).with_conditions(NetworkConditions::perfect().with_latency(1));
+
+        sim.send(a, b, vec![1]);
+        sim.partition(&[a], &[b]);
+        sim.send(b, a, vec![2]);
+        sim.tick();
+        assert!(sim.take_due().is_empty());
+        assert_eq!(sim.dropped(), 2);
+
+        sim.heal();
+        sim.send(a, b, vec![3]);
+        sim.tick();
+        assert_eq!(sim.take_due().len(), 1);
+    }
+}
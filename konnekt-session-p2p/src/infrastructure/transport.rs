@@ -1,8 +1,13 @@
 use crate::application::ConnectionEvent;
-use crate::domain::PeerId;
+use crate::domain::{ClockSync, PeerId, PeerRegistry, PeerStats, SharedClock, SystemClock};
 use crate::infrastructure::error::{P2PError, Result};
 use crate::infrastructure::message::{MessageKind, P2PMessage};
+use instant::Instant;
 use std::collections::{HashMap, VecDeque};
+use std::task::{Context, Poll};
+
+/// Pings remembered for round trips (older pongs are ignored)
+const PINGS_IN_FLIGHT: usize = 8;
 
 /// Events emitted by transport (for SessionLoop to handle)
 #[derive(Debug, Clone)]
@@ -27,6 +32,14 @@ pub trait NetworkConnection {
     fn send_to(&mut self, peer: PeerId, data: Vec<u8>) -> Result<()>;
     fn broadcast(&mut self, data: Vec<u8>) -> Result<()>;
     fn poll_events(&mut self) -> Vec<ConnectionEvent>;
+
+    /// Wake `cx` once `poll_events` has something new
+    ///
+    /// Returns `Ready` if events are already waiting. Transports that cannot
+    /// signal readiness keep this default and are polled on a timer instead.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<()> {
+        Poll::Pending
+    }
 }
 
 /// Implement NetworkConnection for MatchboxConnection
@@ -50,6 +63,37 @@ impl NetworkConnection for crate::infrastructure::connection::MatchboxConnection
     fn poll_events(&mut self) -> Vec<ConnectionEvent> {
         self.poll_events()
     }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.poll_ready(cx)
+    }
+}
+
+/// Boxed connections, to pick the transport at runtime
+impl<C: NetworkConnection + ?Sized> NetworkConnection for Box<C> {
+    fn local_peer_id(&self) -> Option<PeerId> {
+        (**self).local_peer_id()
+    }
+
+    fn connected_peers(&self) -> Vec<PeerId> {
+        (**self).connected_peers()
+    }
+
+    fn send_to(&mut self, peer: PeerId, data: Vec<u8>) -> Result<()> {
+        (**self).send_to(peer, data)
+    }
+
+    fn broadcast(&mut self, data: Vec<u8>) -> Result<()> {
+        (**self).broadcast(data)
+    }
+
+    fn poll_events(&mut self) -> Vec<ConnectionEvent> {
+        (**self).poll_events()
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        (**self).poll_ready(cx)
+    }
 }
 
 /// Reliable P2P transport (domain-agnostic, generic over connection)
@@ -63,8 +107,8 @@ pub struct P2PTransport<C: NetworkConnection> {
     /// Highest sequence received (all peers)
     highest_received: u64,
 
-    /// Out-of-order messages waiting for gaps
-    pending_messages: HashMap<u64, P2PMessage>,
+    /// Out-of-order messages waiting for gaps, with the peer they came from
+    pending_messages: HashMap<u64, (PeerId, serde_json::Value)>,
 
     /// Delivered messages (for resend requests)
     message_cache: VecDeque<P2PMessage>,
@@ -80,6 +124,23 @@ pub struct P2PTransport<C: NetworkConnection> {
 
     /// Transport events (for SessionLoop)
     pending_events: Vec<TransportEvent>,
+
+    /// Connection status, round trip and message counts per peer
+    peers: PeerRegistry,
+
+    /// Is the connection reconnecting to signalling?
+    reconnecting: bool,
+
+    /// Pings awaiting their pong, oldest first
+    pings_in_flight: VecDeque<(u64, Instant)>,
+
+    next_ping: u64,
+
+    /// Offset of the host's clock, from the pongs of the host (guest only)
+    clock_sync: ClockSync,
+
+    /// Time for round trips, grace periods and clock sync
+    clock: SharedClock,
 }
 
 impl<C: NetworkConnection> P2PTransport<C> {
@@ -95,6 +156,12 @@ impl<C: NetworkConnection> P2PTransport<C> {
             is_host: true,
             host_peer: None,
             pending_events: Vec::new(),
+            peers: PeerRegistry::new(),
+            reconnecting: false,
+            pings_in_flight: VecDeque::new(),
+            next_ping: 0,
+            clock_sync: ClockSync::new(),
+            clock: SystemClock::shared(),
         }
     }
 
@@ -110,9 +177,26 @@ impl<C: NetworkConnection> P2PTransport<C> {
             is_host: false,
             host_peer: None,
             pending_events: Vec::new(),
+            peers: PeerRegistry::new(),
+            reconnecting: false,
+            pings_in_flight: VecDeque::new(),
+            next_ping: 0,
+            clock_sync: ClockSync::new(),
+            clock: SystemClock::shared(),
         }
     }
 
+    /// Measure time on `clock` instead of real time (deterministic tests)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.peers.set_clock(clock.clone());
+        self.clock = clock;
+        self
+    }
+
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
     /// Send an application message (HOST ONLY - broadcasts to ALL peers)
     pub fn send(&mut self, payload: serde_json::Value) -> Result<u64> {
         if !self.is_host {
@@ -132,6 +216,7 @@ impl<C: NetworkConnection> P2PTransport<C> {
 
         // ✅ FIX: Broadcast to ALL connected peers (not including self)
         self.connection.broadcast(data)?;
+        self.record_broadcast();
 
         // Cache for resend
         self.message_cache.push_back(msg);
@@ -161,17 +246,41 @@ impl<C: NetworkConnection> P2PTransport<C> {
                 host_peer
             } else {
                 let fallback = peers[0];
-                self.host_peer = Some(fallback);
+                self.follow_host(fallback);
                 fallback
             }
         } else {
             let fallback = peers[0];
-            self.host_peer = Some(fallback);
+            self.follow_host(fallback);
             fallback
         };
 
         self.connection.send_to(target, data)?;
+        self.peers.record_sent(&target);
+
+        Ok(())
+    }
+
+    /// Remember `peer` as the host; another host's clock needs new samples
+    fn follow_host(&mut self, peer: PeerId) {
+        if self.host_peer.replace(peer) != Some(peer) {
+            self.clock_sync.reset();
+        }
+    }
 
+    /// Send an unsequenced message to one guest, e.g. a reply to its command
+    /// (host only)
+    pub fn send_to_peer(&mut self, peer: PeerId, payload: serde_json::Value) -> Result<()> {
+        if !self.is_host {
+            return Err(P2PError::SendFailed(
+                "Only host can message a single guest".to_string(),
+            ));
+        }
+
+        let data = serde_json::to_vec(&P2PMessage::application(payload))
+            .map_err(P2PError::Serialization)?;
+        self.connection.send_to(peer, data)?;
+        self.peers.record_sent(&peer);
         Ok(())
     }
 
@@ -187,6 +296,7 @@ impl<C: NetworkConnection> P2PTransport<C> {
         let data = serde_json::to_vec(&msg).map_err(P2PError::Serialization)?;
 
         self.connection.send_to(peer, data)?;
+        self.peers.record_sent(&peer);
         tracing::info!(
             "📤 Sent snapshot to peer {} (seq: {})",
             peer,
@@ -208,13 +318,18 @@ impl<C: NetworkConnection> P2PTransport<C> {
         let data = serde_json::to_vec(&msg).map_err(P2PError::Serialization)?;
 
         self.connection.broadcast(data)?;
+        self.record_broadcast();
         tracing::info!("📤 Requested snapshot from host");
 
         Ok(())
     }
 
-    /// Poll for application messages (handles ordering + gap detection)
-    pub fn poll(&mut self) -> Vec<serde_json::Value> {
+    /// Poll for application messages with the peer that sent each (handles
+    /// ordering + gap detection)
+    ///
+    /// The host receives its guests' messages; a guest only what the host
+    /// sent, messages from other peers are dropped.
+    pub fn poll(&mut self) -> Vec<(PeerId, serde_json::Value)> {
         let mut delivered = Vec::new();
 
         // Get raw network events
@@ -222,16 +337,26 @@ impl<C: NetworkConnection> P2PTransport<C> {
             match event {
                 ConnectionEvent::PeerConnected(peer_id) => {
                     tracing::info!("🟢 Peer connected: {}", peer_id);
+                    self.peers.add_peer(peer_id);
+                    self.ping_peer(peer_id);
                     self.pending_events
                         .push(TransportEvent::PeerConnected(peer_id));
                 }
+                ConnectionEvent::PeerDisconnected(peer_id) => {
+                    tracing::info!("🔴 Peer disconnected: {}", peer_id);
+                    self.peers.mark_peer_disconnected(&peer_id);
+                }
+                ConnectionEvent::Reconnecting { .. } => self.reconnecting = true,
+                ConnectionEvent::Reconnected { .. } => self.reconnecting = false,
                 ConnectionEvent::MessageReceived { from, data } => {
+                    self.peers.record_received(&from);
                     if let Ok(msg) = serde_json::from_slice::<P2PMessage>(&data) {
                         match msg.kind {
                             MessageKind::Application { payload } => {
                                 self.handle_application_message(
+                                    from,
                                     msg.sequence,
-                                    payload.clone(),
+                                    payload,
                                     &mut delivered,
                                 );
                             }
@@ -246,9 +371,17 @@ impl<C: NetworkConnection> P2PTransport<C> {
                             } => {
                                 if !self.is_host {
                                     // Snapshot responses are authoritative from host.
-                                    self.host_peer = Some(from);
+                                    self.follow_host(from);
+                                }
+                                if as_of_sequence < self.highest_received {
+                                    tracing::debug!(
+                                        "⏭️ Skipping stale snapshot (seq: {})",
+                                        as_of_sequence
+                                    );
+                                    continue;
                                 }
                                 tracing::info!("📥 Received snapshot (seq: {})", as_of_sequence);
+                                self.skip_to(as_of_sequence, &mut delivered);
                                 self.pending_events.push(TransportEvent::SnapshotReceived {
                                     snapshot,
                                     as_of_sequence,
@@ -258,8 +391,18 @@ impl<C: NetworkConnection> P2PTransport<C> {
                                 self.handle_resend_request(seq_from, to, from);
                             }
                             MessageKind::ResendResponse { messages } => {
-                                self.handle_resend_response(messages, &mut delivered);
+                                self.handle_resend_response(from, messages, &mut delivered);
+                            }
+                            MessageKind::Relay { .. } => {
+                                // Relaying is handled by P2PLoop; the transport only
+                                // speaks direct host ↔ guest.
+                                tracing::trace!("Ignoring relayed message from {}", from);
                             }
+                            MessageKind::Ping { nonce } => self.send_control(
+                                from,
+                                P2PMessage::pong(nonce, self.clock.unix_millis()),
+                            ),
+                            MessageKind::Pong { nonce, at } => self.handle_pong(from, nonce, at),
                         }
                     }
                 }
@@ -267,9 +410,88 @@ impl<C: NetworkConnection> P2PTransport<C> {
             }
         }
 
+        for peer_id in self.peers.check_grace_periods() {
+            self.peers.remove_peer(&peer_id);
+        }
+
         delivered
     }
 
+    /// Probe the round trip to every connected peer
+    pub fn ping(&mut self) {
+        for peer_id in self.connection.connected_peers() {
+            self.ping_peer(peer_id);
+        }
+    }
+
+    fn ping_peer(&mut self, peer_id: PeerId) {
+        let nonce = self.next_ping;
+        self.next_ping += 1;
+        self.pings_in_flight.push_back((nonce, self.clock.now()));
+        if self.pings_in_flight.len() > PINGS_IN_FLIGHT {
+            self.pings_in_flight.pop_front();
+        }
+        self.send_control(peer_id, P2PMessage::ping(nonce));
+    }
+
+    fn handle_pong(&mut self, from: PeerId, nonce: u64, at: Option<u64>) {
+        if let Some((_, sent_at)) = self
+            .pings_in_flight
+            .iter()
+            .find(|(in_flight, _)| *in_flight == nonce)
+        {
+            let rtt = self.clock.now().saturating_duration_since(*sent_at);
+            tracing::trace!("🏓 Round trip to {}: {}ms", from, rtt.as_millis());
+            self.peers.record_rtt(&from, rtt);
+
+            if let Some(host_ms) = at
+                && !self.is_host
+                && self.host_peer == Some(from)
+            {
+                let received = self.clock.unix_millis();
+                let sent = received.saturating_sub(rtt.as_millis() as u64);
+                self.clock_sync.record(sent, host_ms, received);
+            }
+        }
+    }
+
+    /// How far the host's clock is ahead of ours, in ms (0 on the host and
+    /// until the host answered a ping)
+    pub fn clock_offset_ms(&self) -> i64 {
+        self.clock_sync.offset_ms().unwrap_or(0)
+    }
+
+    /// Now on the host's clock (Unix ms), so countdowns and deadlines agree
+    /// across peers
+    pub fn session_time_ms(&self) -> u64 {
+        self.clock_sync.now_ms(self.clock.unix_millis())
+    }
+
+    /// Send an unsequenced transport message to one peer (best effort)
+    fn send_control(&mut self, peer: PeerId, msg: P2PMessage) {
+        if let Ok(data) = serde_json::to_vec(&msg)
+            && self.connection.send_to(peer, data).is_ok()
+        {
+            self.peers.record_sent(&peer);
+        }
+    }
+
+    fn record_broadcast(&mut self) {
+        for peer_id in self.connection.connected_peers() {
+            self.peers.record_sent(&peer_id);
+        }
+    }
+
+    /// Round trip, last seen and message counts of every peer
+    pub fn peer_stats(&self) -> Vec<PeerStats> {
+        self.peers.stats(self.local_peer_id())
+    }
+
+    /// Is the connection currently reconnecting to signalling?
+    pub fn is_reconnecting(&self) -> bool {
+        self.reconnecting
+    }
+
     /// Drain transport events (for SessionLoop)
     pub fn drain_events(&mut self) -> Vec<TransportEvent> {
         std::mem::take(&mut self.pending_events)
@@ -278,35 +500,46 @@ impl<C: NetworkConnection> P2PTransport<C> {
     /// Handle application message with ordering
     fn handle_application_message(
         &mut self,
+        from: PeerId,
         sequence: u64,
         payload: serde_json::Value,
-        delivered: &mut Vec<serde_json::Value>,
+        delivered: &mut Vec<(PeerId, serde_json::Value)>,
     ) {
+        if self.is_host {
+            // Guests never sequence, so only requests reach the host
+            if sequence == 0 {
+                delivered.push((from, payload));
+            } else {
+                tracing::warn!("🚫 HOST: Dropping sequenced message from guest {}", from);
+            }
+            return;
+        }
+
+        // Guests only listen to the host (other guests can't speak for it)
+        if self.host_peer.is_some_and(|host| host != from) {
+            tracing::warn!("🚫 GUEST: Dropping message from non-host peer {}", from);
+            return;
+        }
+
         if sequence == 0 {
-            // Unsequenced message (from guest) - deliver immediately
-            delivered.push(payload);
+            // Unsequenced reply from the host - deliver immediately
+            delivered.push((from, payload));
             return;
         }
 
         if sequence == self.highest_received + 1 {
             // In order - deliver immediately
-            delivered.push(payload);
+            delivered.push((from, payload));
             self.highest_received = sequence;
 
             // Check if we can deliver pending messages
             while let Some(pending) = self.pending_messages.remove(&(self.highest_received + 1)) {
-                if let MessageKind::Application { payload } = pending.kind {
-                    delivered.push(payload);
-                    self.highest_received = pending.sequence;
-                }
+                delivered.push(pending);
+                self.highest_received += 1;
             }
         } else if sequence > self.highest_received + 1 {
             // Out of order - buffer it
-            let msg = P2PMessage {
-                sequence,
-                kind: MessageKind::Application { payload },
-            };
-            self.pending_messages.insert(sequence, msg);
+            self.pending_messages.insert(sequence, (from, payload));
 
             // Request missing range
             self.request_resend(self.highest_received + 1, sequence - 1);
@@ -314,6 +547,19 @@ impl<C: NetworkConnection> P2PTransport<C> {
         // else: duplicate/old message, ignore
     }
 
+    /// Treat everything up to `sequence` as delivered (a snapshot covers it,
+    /// including what this poll delivered so far)
+    fn skip_to(&mut self, sequence: u64, delivered: &mut Vec<(PeerId, serde_json::Value)>) {
+        delivered.clear();
+        self.highest_received = self.highest_received.max(sequence);
+        self.pending_messages
+            .retain(|pending, _| *pending > sequence);
+        while let Some(pending) = self.pending_messages.remove(&(self.highest_received + 1)) {
+            delivered.push(pending);
+            self.highest_received += 1;
+        }
+    }
+
     /// Handle resend request (host only)
     fn handle_resend_request(&mut self, from: u64, to: u64, peer: PeerId) {
         if !self.is_host {
@@ -330,26 +576,27 @@ impl<C: NetworkConnection> P2PTransport<C> {
         if !messages.is_empty() {
             let response = P2PMessage {
                 sequence: 0,
+                route: None,
+                topic: None,
                 kind: MessageKind::ResendResponse { messages },
             };
 
-            if let Ok(data) = serde_json::to_vec(&response) {
-                let _ = self.connection.send_to(peer, data);
-            }
+            self.send_control(peer, response);
         }
     }
 
     /// Handle resend response (guest only)
     fn handle_resend_response(
         &mut self,
+        from: PeerId,
         messages: Vec<P2PMessage>,
-        delivered: &mut Vec<serde_json::Value>,
+        delivered: &mut Vec<(PeerId, serde_json::Value)>,
     ) {
         for msg in messages {
             // Extract sequence first, then handle payload
             let sequence = msg.sequence;
             if let MessageKind::Application { payload } = msg.kind {
-                self.handle_application_message(sequence, payload, delivered);
+                self.handle_application_message(from, sequence, payload, delivered);
             }
         }
     }
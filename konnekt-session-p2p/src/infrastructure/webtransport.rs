@@ -0,0 +1,704 @@
+//! WebTransport / QUIC transport (alternative to Matchbox WebRTC)
+//!
+//! Peers don't connect to each other directly; every peer holds one
+//! bidirectional stream to a relay that forwards frames within a room. Native
+//! builds speak raw QUIC via quinn (ALPN [`RELAY_ALPN`]); browsers use the
+//! WebTransport API (requires `--cfg=web_sys_unstable_apis`). The relay is
+//! expected to accept both on the same port.
+
+use crate::application::ConnectionEvent;
+use crate::domain::PeerId;
+use crate::infrastructure::error::{P2PError, Result};
+use crate::infrastructure::transport::NetworkConnection;
+use futures::StreamExt;
+use futures::channel::mpsc;
+use serde::{Deserialize, Serialize};
+use std::task::{Context, Poll};
+use uuid::Uuid;
+
+/// ALPN protocol id for native QUIC connections to the relay
+pub const RELAY_ALPN: &[u8] = b"konnekt-relay/1";
+
+/// Largest frame accepted from the relay
+pub const MAX_FRAME_SIZE: usize = 1024 * 1024;
+
+/// Frames exchanged with the relay (length-prefixed JSON)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RelayFrame {
+    /// Client → relay: join a room (first frame on the stream)
+    Join { room: String },
+
+    /// Relay → client: assigned peer ID and peers already in the room
+    Welcome { peer_id: Uuid, peers: Vec<Uuid> },
+
+    /// Relay → client: a peer entered the room
+    PeerJoined { peer_id: Uuid },
+
+    /// Relay → client: a peer left the room
+    PeerLeft { peer_id: Uuid },
+
+    /// Client → relay: deliver to one peer, or everyone else if `to` is `None`
+    Send { to: Option<Uuid>, data: Vec<u8> },
+
+    /// Relay → client: data from another peer
+    Deliver { from: Uuid, data: Vec<u8> },
+}
+
+impl RelayFrame {
+    /// Encode as a big-endian u32 length followed by the JSON body
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let body = serde_json::to_vec(self)?;
+        let mut frame = Vec::with_capacity(4 + body.len());
+        frame.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&body);
+        Ok(frame)
+    }
+}
+
+/// Reassembles `RelayFrame`s from a byte stream
+#[derive(Debug, Default)]
+pub struct FrameDecoder {
+    buffer: Vec<u8>,
+}
+
+impl FrameDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed received bytes
+    pub fn extend(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Next complete frame, if any
+    pub fn next_frame(&mut self) -> Result<Option<RelayFrame>> {
+        if self.buffer.len() < 4 {
+            return Ok(None);
+        }
+
+        let len = u32::from_be_bytes([
+            self.buffer[0],
+            self.buffer[1],
+            self.buffer[2],
+            self.buffer[3],
+        ]) as usize;
+
+        if len > MAX_FRAME_SIZE {
+            return Err(P2PError::ReceiveFailed(format!(
+                "Relay frame too large: {} bytes",
+                len
+            )));
+        }
+
+        if self.buffer.len() < 4 + len {
+            return Ok(None);
+        }
+
+        let frame = serde_json::from_slice(&self.buffer[4..4 + len])?;
+        self.buffer.drain(..4 + len);
+        Ok(Some(frame))
+    }
+}
+
+/// Split a relay URL (`https://host[:port]/path`) into host, port and room path
+pub fn parse_relay_url(url: &str) -> Result<(String, u16, String)> {
+    let rest = url
+        .strip_prefix("https://")
+        .ok_or_else(|| P2PError::ConnectionFailed(format!("Relay URL must be https: {}", url)))?;
+
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+
+    let invalid_port = || P2PError::ConnectionFailed(format!("Invalid relay port: {}", url));
+    let (host, port) = if let Some(bracketed) = authority.strip_prefix('[') {
+        // IPv6 literal: [addr] or [addr]:port
+        let (host, rest) = bracketed.split_once(']').ok_or_else(invalid_port)?;
+        let port = match rest.strip_prefix(':') {
+            Some(port) => port.parse().map_err(|_| invalid_port())?,
+            None => 443,
+        };
+        (host, port)
+    } else {
+        match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| invalid_port())?),
+            None => (authority, 443),
+        }
+    };
+
+    if host.is_empty() {
+        return Err(P2PError::ConnectionFailed(format!(
+            "Relay URL has no host: {}",
+            url
+        )));
+    }
+
+    Ok((host.to_string(), port, path.to_string()))
+}
+
+/// `NetworkConnection` over a WebTransport / QUIC relay
+pub struct WebTransportConnection {
+    local_peer_id: PeerId,
+    peers: Vec<PeerId>,
+    outgoing: mpsc::UnboundedSender<RelayFrame>,
+    incoming: mpsc::UnboundedReceiver<RelayFrame>,
+    pending_events: Vec<ConnectionEvent>,
+    closed: bool,
+}
+
+impl WebTransportConnection {
+    /// Connect to the relay at `url` (`https://relay.example.org:4433/<room>`)
+    #[cfg(feature = "webtransport")]
+    pub async fn connect(url: &str) -> Result<Self> {
+        tracing::info!("Connecting to WebTransport relay: {}", url);
+
+        let (_, _, room) = parse_relay_url(url)?;
+        let (outgoing, relay_rx) = mpsc::unbounded();
+        let (relay_tx, mut incoming) = mpsc::unbounded();
+
+        io::spawn(url, relay_rx, relay_tx).await?;
+
+        outgoing
+            .unbounded_send(RelayFrame::Join { room })
+            .map_err(|_| P2PError::ChannelClosed)?;
+
+        match incoming.next().await {
+            Some(RelayFrame::Welcome { peer_id, peers }) => {
+                tracing::info!("Connected with peer ID: {}", peer_id);
+                Ok(Self::from_channels(peer_id, peers, outgoing, incoming))
+            }
+            Some(other) => Err(P2PError::ConnectionFailed(format!(
+                "Unexpected first relay frame: {:?}",
+                other
+            ))),
+            None => Err(P2PError::ConnectionFailed(
+                "Relay closed the stream before welcoming us".to_string(),
+            )),
+        }
+    }
+
+    /// Wrap relay frame channels whose I/O is driven elsewhere, given the
+    /// contents of the relay's `Welcome` frame
+    pub fn from_channels(
+        peer_id: Uuid,
+        peers: Vec<Uuid>,
+        outgoing: mpsc::UnboundedSender<RelayFrame>,
+        incoming: mpsc::UnboundedReceiver<RelayFrame>,
+    ) -> Self {
+        let peers: Vec<PeerId> = peers.into_iter().map(to_peer_id).collect();
+        let pending_events = peers
+            .iter()
+            .copied()
+            .map(ConnectionEvent::PeerConnected)
+            .collect();
+
+        Self {
+            local_peer_id: to_peer_id(peer_id),
+            peers,
+            outgoing,
+            incoming,
+            pending_events,
+            closed: false,
+        }
+    }
+
+    fn send_frame(&self, frame: RelayFrame) -> Result<()> {
+        self.outgoing
+            .unbounded_send(frame)
+            .map_err(|_| P2PError::ChannelClosed)
+    }
+
+    fn handle_frame(&mut self, frame: RelayFrame, events: &mut Vec<ConnectionEvent>) {
+        match frame {
+            RelayFrame::PeerJoined { peer_id } => {
+                let peer = to_peer_id(peer_id);
+                if !self.peers.contains(&peer) {
+                    self.peers.push(peer);
+                    tracing::info!("Peer connected: {}", peer);
+                    events.push(ConnectionEvent::PeerConnected(peer));
+                }
+            }
+            RelayFrame::PeerLeft { peer_id } => {
+                let peer = to_peer_id(peer_id);
+                if let Some(index) = self.peers.iter().position(|p| *p == peer) {
+                    self.peers.remove(index);
+                    tracing::info!("Peer disconnected: {}", peer);
+                    events.push(ConnectionEvent::PeerDisconnected(peer));
+                }
+            }
+            RelayFrame::Deliver { from, data } => {
+                let from = to_peer_id(from);
+                tracing::debug!("Received {} bytes from peer {}", data.len(), from);
+                events.push(ConnectionEvent::MessageReceived { from, data });
+            }
+            other => {
+                tracing::warn!("Ignoring unexpected relay frame: {:?}", other);
+            }
+        }
+    }
+}
+
+impl NetworkConnection for WebTransportConnection {
+    fn local_peer_id(&self) -> Option<PeerId> {
+        Some(self.local_peer_id)
+    }
+
+    fn connected_peers(&self) -> Vec<PeerId> {
+        self.peers.clone()
+    }
+
+    fn send_to(&mut self, peer: PeerId, data: Vec<u8>) -> Result<()> {
+        tracing::debug!("Sent {} bytes to peer {}", data.len(), peer);
+        self.send_frame(RelayFrame::Send {
+            to: Some(peer.inner().0),
+            data,
+        })
+    }
+
+    fn broadcast(&mut self, data: Vec<u8>) -> Result<()> {
+        tracing::debug!(
+            "Broadcast {} bytes to {} peers",
+            data.len(),
+            self.peers.len()
+        );
+        self.send_frame(RelayFrame::Send { to: None, data })
+    }
+
+    fn poll_events(&mut self) -> Vec<ConnectionEvent> {
+        let mut events = std::mem::take(&mut self.pending_events);
+
+        while !self.closed {
+            match self.incoming.try_recv() {
+                Ok(frame) => self.handle_frame(frame, &mut events),
+                Err(mpsc::TryRecvError::Closed) => {
+                    tracing::warn!("WebTransport relay stream closed");
+                    self.closed = true;
+                    events.extend(self.peers.drain(..).map(ConnectionEvent::PeerDisconnected));
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+            }
+        }
+
+        events
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if !self.pending_events.is_empty() {
+            return Poll::Ready(());
+        }
+
+        while !self.closed {
+            match self.incoming.poll_next_unpin(cx) {
+                Poll::Ready(Some(frame)) => {
+                    let mut events = std::mem::take(&mut self.pending_events);
+                    self.handle_frame(frame, &mut events);
+                    self.pending_events = events;
+                    if !self.pending_events.is_empty() {
+                        return Poll::Ready(());
+                    }
+                }
+                // Let `poll_events` report the closed stream
+                Poll::Ready(None) => return Poll::Ready(()),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+fn to_peer_id(id: Uuid) -> PeerId {
+    PeerId::new(matchbox_socket::PeerId(id))
+}
+
+/// Native: raw QUIC via quinn
+#[cfg(all(feature = "webtransport", not(target_arch = "wasm32")))]
+mod io {
+    use super::{FrameDecoder, RELAY_ALPN, RelayFrame, parse_relay_url};
+    use crate::infrastructure::error::{P2PError, Result};
+    use futures::StreamExt;
+    use futures::channel::mpsc;
+    use std::sync::Arc;
+
+    fn connect_err(e: impl std::fmt::Display) -> P2PError {
+        P2PError::ConnectionFailed(e.to_string())
+    }
+
+    pub(super) async fn spawn(
+        url: &str,
+        mut outgoing: mpsc::UnboundedReceiver<RelayFrame>,
+        incoming: mpsc::UnboundedSender<RelayFrame>,
+    ) -> Result<()> {
+        let (host, port, _) = parse_relay_url(url)?;
+
+        let addr = tokio::net::lookup_host((host.as_str(), port))
+            .await
+            .map_err(connect_err)?
+            .next()
+            .ok_or_else(|| connect_err(format!("Could not resolve relay host {}", host)))?;
+
+        let mut roots = rustls::RootCertStore::empty();
+        roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+
+        let mut crypto = rustls::ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        crypto.alpn_protocols = vec![RELAY_ALPN.to_vec()];
+
+        let quic_crypto =
+            quinn::crypto::rustls::QuicClientConfig::try_from(crypto).map_err(connect_err)?;
+
+        let bind_addr = if addr.is_ipv6() {
+            "[::]:0".parse().unwrap()
+        } else {
+            "0.0.0.0:0".parse().unwrap()
+        };
+        let mut endpoint = quinn::Endpoint::client(bind_addr).map_err(connect_err)?;
+        endpoint.set_default_client_config(quinn::ClientConfig::new(Arc::new(quic_crypto)));
+
+        let connection = endpoint
+            .connect(addr, &host)
+            .map_err(connect_err)?
+            .await
+            .map_err(connect_err)?;
+        let (mut send, mut recv) = connection.open_bi().await.map_err(connect_err)?;
+
+        let span = tracing::info_span!("webtransport::quic_loop");
+
+        tokio::spawn(async move {
+            let _enter = span.enter();
+            while let Some(frame) = outgoing.next().await {
+                let Ok(bytes) = frame.encode() else {
+                    continue;
+                };
+                if let Err(e) = send.write_all(&bytes).await {
+                    tracing::warn!("Relay write failed: {}", e);
+                    break;
+                }
+            }
+            let _ = send.finish();
+        });
+
+        tokio::spawn(async move {
+            // Keep the endpoint and connection alive with the read loop
+            let _endpoint = endpoint;
+            let _connection = connection;
+            let mut decoder = FrameDecoder::new();
+            let mut buf = vec![0u8; 16 * 1024];
+
+            loop {
+                match recv.read(&mut buf).await {
+                    Ok(Some(n)) => decoder.extend(&buf[..n]),
+                    Ok(None) => break,
+                    Err(e) => {
+                        tracing::warn!("Relay read failed: {}", e);
+                        break;
+                    }
+                }
+
+                loop {
+                    match decoder.next_frame() {
+                        Ok(Some(frame)) => {
+                            if incoming.unbounded_send(frame).is_err() {
+                                return;
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            tracing::warn!("Dropping relay stream: {}", e);
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
+
+/// WASM: browser WebTransport API
+#[cfg(all(feature = "webtransport", target_arch = "wasm32"))]
+mod io {
+    use super::{FrameDecoder, RelayFrame};
+    use crate::infrastructure::error::{P2PError, Result};
+    use futures::StreamExt;
+    use futures::channel::mpsc;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+
+    fn connect_err(e: wasm_bindgen::JsValue) -> P2PError {
+        P2PError::ConnectionFailed(format!("{:?}", e))
+    }
+
+    pub(super) async fn spawn(
+        url: &str,
+        mut outgoing: mpsc::UnboundedReceiver<RelayFrame>,
+        incoming: mpsc::UnboundedSender<RelayFrame>,
+    ) -> Result<()> {
+        let transport = web_sys::WebTransport::new(url).map_err(connect_err)?;
+        JsFuture::from(transport.ready())
+            .await
+            .map_err(connect_err)?;
+
+        let stream: web_sys::WebTransportBidirectionalStream =
+            JsFuture::from(transport.create_bidirectional_stream())
+                .await
+                .map_err(connect_err)?
+                .unchecked_into();
+
+        let writer = stream.writable().get_writer().map_err(connect_err)?;
+        let reader: web_sys::ReadableStreamDefaultReader =
+            stream.readable().get_reader().unchecked_into();
+
+        let span = tracing::info_span!("webtransport::browser_loop");
+
+        let write_span = span.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let _enter = write_span.enter();
+            while let Some(frame) = outgoing.next().await {
+                let Ok(bytes) = frame.encode() else {
+                    continue;
+                };
+                let chunk = js_sys::Uint8Array::from(bytes.as_slice());
+                if let Err(e) = JsFuture::from(writer.write_with_chunk(&chunk)).await {
+                    tracing::warn!("Relay write failed: {:?}", e);
+                    break;
+                }
+            }
+            transport.close();
+        });
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let _enter = span.enter();
+            let mut decoder = FrameDecoder::new();
+
+            loop {
+                let result = match JsFuture::from(reader.read()).await {
+                    Ok(result) => result,
+                    Err(e) => {
+                        tracing::warn!("Relay read failed: {:?}", e);
+                        break;
+                    }
+                };
+
+                let done = js_sys::Reflect::get(&result, &"done".into())
+                    .ok()
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                if done {
+                    break;
+                }
+
+                if let Ok(value) = js_sys::Reflect::get(&result, &"value".into()) {
+                    decoder.extend(&js_sys::Uint8Array::new(&value).to_vec());
+                }
+
+                loop {
+                    match decoder.next_frame() {
+                        Ok(Some(frame)) => {
+                            if incoming.unbounded_send(frame).is_err() {
+                                return;
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            tracing::warn!("Dropping relay stream: {}", e);
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_connection(
+        peers: Vec<Uuid>,
+    ) -> (
+        WebTransportConnection,
+        mpsc::UnboundedReceiver<RelayFrame>,
+        mpsc::UnboundedSender<RelayFrame>,
+    ) {
+        let (outgoing, relay_rx) = mpsc::unbounded();
+        let (relay_tx, incoming) = mpsc::unbounded();
+        let connection =
+            WebTransportConnection::from_channels(Uuid::new_v4(), peers, outgoing, incoming);
+        (connection, relay_rx, relay_tx)
+    }
+
+    #[test]
+    fn test_frame_roundtrip_across_chunks() {
+        let frame = RelayFrame::Deliver {
+            from: Uuid::new_v4(),
+            data: vec![1, 2, 3],
+        };
+        let bytes = frame.encode().unwrap();
+
+        let mut decoder = FrameDecoder::new();
+        decoder.extend(&bytes[..3]);
+        assert!(decoder.next_frame().unwrap().is_none());
+
+        decoder.extend(&bytes[3..]);
+        assert_eq!(decoder.next_frame().unwrap(), Some(frame));
+        assert!(decoder.next_frame().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_oversized_frame_rejected() {
+        let mut decoder = FrameDecoder::new();
+        decoder.extend(&((MAX_FRAME_SIZE as u32) + 1).to_be_bytes());
+        assert!(decoder.next_frame().is_err());
+    }
+
+    #[test]
+    fn test_parse_relay_url() {
+        assert_eq!(
+            parse_relay_url("https://relay.example.org:4433/room/abc").unwrap(),
+            (
+                "relay.example.org".to_string(),
+                4433,
+                "/room/abc".to_string()
+            )
+        );
+        assert_eq!(
+            parse_relay_url("https://relay.example.org").unwrap(),
+            ("relay.example.org".to_string(), 443, "/".to_string())
+        );
+        assert_eq!(
+            parse_relay_url("https://[::1]:4433/x").unwrap(),
+            ("::1".to_string(), 4433, "/x".to_string())
+        );
+        assert_eq!(
+            parse_relay_url("https://[::1]").unwrap(),
+            ("::1".to_string(), 443, "/".to_string())
+        );
+        assert!(parse_relay_url("wss://relay.example.org").is_err());
+        assert!(parse_relay_url("https://relay.example.org:http/").is_err());
+    }
+
+    #[test]
+    fn test_welcome_peers_reported_as_connected() {
+        let existing = Uuid::new_v4();
+        let (mut connection, _relay_rx, _relay_tx) = create_connection(vec![existing]);
+
+        assert_eq!(connection.connected_peers(), vec![to_peer_id(existing)]);
+        assert!(matches!(
+            connection.poll_events().as_slice(),
+            [ConnectionEvent::PeerConnected(peer)] if *peer == to_peer_id(existing)
+        ));
+    }
+
+    #[test]
+    fn test_relay_frames_become_connection_events() {
+        let (mut connection, _relay_rx, relay_tx) = create_connection(vec![]);
+        let peer = Uuid::new_v4();
+
+        relay_tx
+            .unbounded_send(RelayFrame::PeerJoined { peer_id: peer })
+            .unwrap();
+        relay_tx
+            .unbounded_send(RelayFrame::Deliver {
+                from: peer,
+                data: b"hello".to_vec(),
+            })
+            .unwrap();
+        relay_tx
+            .unbounded_send(RelayFrame::PeerLeft { peer_id: peer })
+            .unwrap();
+
+        let events = connection.poll_events();
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], ConnectionEvent::PeerConnected(_)));
+        assert!(matches!(
+            &events[1],
+            ConnectionEvent::MessageReceived { data, .. } if data == b"hello"
+        ));
+        assert!(matches!(events[2], ConnectionEvent::PeerDisconnected(_)));
+        assert!(connection.connected_peers().is_empty());
+    }
+
+    #[test]
+    fn test_sends_go_through_relay() {
+        let peer = Uuid::new_v4();
+        let (mut connection, mut relay_rx, _relay_tx) = create_connection(vec![peer]);
+
+        connection.send_to(to_peer_id(peer), vec![1]).unwrap();
+        connection.broadcast(vec![2]).unwrap();
+
+        assert_eq!(
+            relay_rx.try_recv().unwrap(),
+            RelayFrame::Send {
+                to: Some(peer),
+                data: vec![1]
+            }
+        );
+        assert_eq!(
+            relay_rx.try_recv().unwrap(),
+            RelayFrame::Send {
+                to: None,
+                data: vec![2]
+            }
+        );
+    }
+
+    #[test]
+    fn test_closed_stream_disconnects_peers() {
+        let peer = Uuid::new_v4();
+        let (mut connection, _relay_rx, relay_tx) = create_connection(vec![peer]);
+        connection.poll_events();
+
+        drop(relay_tx);
+
+        assert!(matches!(
+            connection.poll_events().as_slice(),
+            [ConnectionEvent::PeerDisconnected(_)]
+        ));
+        assert!(connection.poll_events().is_empty());
+    }
+
+    #[test]
+    fn test_poll_ready_wakes_on_relay_frame() {
+        use futures::task::{ArcWake, waker};
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        struct Flag(AtomicBool);
+        impl ArcWake for Flag {
+            fn wake_by_ref(arc_self: &Arc<Self>) {
+                arc_self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let (mut connection, _relay_rx, relay_tx) = create_connection(vec![]);
+        let peer = Uuid::new_v4();
+
+        let flag = Arc::new(Flag(AtomicBool::new(false)));
+        let waker = waker(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(connection.poll_ready(&mut cx).is_pending());
+        relay_tx
+            .unbounded_send(RelayFrame::Deliver {
+                from: peer,
+                data: vec![1],
+            })
+            .unwrap();
+
+        assert!(flag.0.load(Ordering::SeqCst));
+        assert!(connection.poll_ready(&mut cx).is_ready());
+        assert!(matches!(
+            connection.poll_events().as_slice(),
+            [ConnectionEvent::MessageReceived { data, .. }] if data == &[1]
+        ));
+    }
+}
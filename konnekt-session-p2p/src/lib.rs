@@ -8,16 +8,30 @@ pub mod application;
 pub mod infrastructure;
 
 // Re-exports for convenience
+#[cfg(any(target_arch = "wasm32", feature = "native"))]
+pub use application::runtime::{AsyncSessionLoop, DEFAULT_IDLE_INTERVAL};
 pub use application::runtime::{
-    MatchboxSessionLoop, MessageQueue, P2PLoop, P2PLoopBuilder, QueueError, SessionLoop,
-    SessionLoopV2, SessionLoopV2Builder,
+    FailedCommand, Fault, HostTakeover, MatchboxSessionLoop, MessageQueue, P2PLoop, P2PLoopBuilder,
+    QueueDepths, QueueError, RECENT_EVENTS_LIMIT, SessionEvent, SessionLoop, SessionLoopV2,
+    SessionLoopV2Builder, Simulation, SimulationConfig, SimulationReport,
 };
 pub use application::{
-    ConnectionEvent, EventSyncManager, EventTranslator, LobbySnapshot, SessionConfig, SyncError,
-    SyncMessage, SyncResponse,
+    ConnectionEvent, DEFAULT_HOST_SNAPSHOT_INTERVAL, DEFAULT_SNAPSHOT_PAGE_SIZE, EventSyncManager,
+    EventTranslator, HostSnapshot, LobbySnapshot, RosterEntry, SessionConfig, SnapshotPart,
+    SyncError, SyncMessage, SyncResponse,
 };
 pub use domain::{
-    DelegationReason, DomainEvent, EventLog, IceServer, LobbyEvent, PeerId, SessionId,
+    Capabilities, ChatMessage, Clock, ClockSync, ConnectionQuality, ConnectionStatus,
+    CorrelationId, CrdtOp, DEFAULT_TURN_REST_TTL, DelegationReason, DomainEvent, EventLog,
+    IceServer, LobbyCrdt, LobbyCrdtState, LobbyEvent, PRESENCE_TTL, PeerId, PeerStats, Presence,
+    RateLimit, ResumeToken, SessionId, SharedClock, SyncMode, SystemClock, TestClock,
+    TimeoutConfig, TimeoutPolicy, Topology, TurnRestAuth,
 };
-pub use infrastructure::error::{P2PError, Result};
-pub use infrastructure::{NetworkConnection, P2PTransport, P2PTransportBuilder};
+pub use infrastructure::error::{P2PError, Result, TrySubmitError};
+pub use infrastructure::{
+    CheckStatus, ConnectivityReport, DiagnosticCheck, EventLogStore, IceCandidate,
+    InMemoryEventLogStore, LoopbackConnection, LoopbackNetwork, NatType, NetworkConditions,
+    NetworkConnection, NetworkSimulator, P2PTransport, P2PTransportBuilder, WebTransportConnection,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use infrastructure::{probe_clock, probe_signalling, run_diagnostics};
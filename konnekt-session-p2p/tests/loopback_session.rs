@@ -0,0 +1,1649 @@
+use futures::StreamExt;
+use konnekt_session_core::DomainEvent;
+use konnekt_session_core::domain::ActivityConfig;
+use konnekt_session_core::{DomainCommand, Participant, ParticipationMode, RunStatus, TestClock};
+use konnekt_session_p2p::{
+    AsyncSessionLoop, Capabilities, ConnectionEvent, ConnectionStatus, HostTakeover,
+    LoopbackConnection, LoopbackNetwork, NetworkConditions, NetworkConnection, NetworkSimulator,
+    P2PLoopBuilder, PeerId, Presence, RateLimit, Result, SessionEvent, SessionId, SessionLoop,
+    SyncMode, TimeoutConfig, TimeoutPolicy, TrySubmitError,
+};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::field::{Field, Visit};
+use tracing::span;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+fn tick(sessions: &mut [&mut SessionLoop<LoopbackConnection>], rounds: usize) {
+    for _ in 0..rounds {
+        for session in sessions.iter_mut() {
+            session.poll();
+        }
+    }
+}
+
+#[test]
+fn test_session_loop_over_loopback() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Loopback Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+
+    let (mut guest, lobby_id) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    tick(&mut [&mut host, &mut guest], 10);
+
+    let guest_lobby = guest.get_lobby().expect("Guest should have synced lobby");
+    assert_eq!(guest_lobby.name(), "Loopback Lobby");
+
+    guest
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Alice".to_string(),
+        })
+        .unwrap();
+
+    tick(&mut [&mut host, &mut guest], 10);
+
+    assert_eq!(host.get_lobby().unwrap().participants().len(), 2);
+    assert_eq!(guest.get_lobby().unwrap().participants().len(), 2);
+}
+
+/// Loopback connection that can inject reconnect events
+struct FlakyConnection {
+    inner: LoopbackConnection,
+    injected: Vec<ConnectionEvent>,
+}
+
+impl NetworkConnection for FlakyConnection {
+    fn local_peer_id(&self) -> Option<PeerId> {
+        self.inner.local_peer_id()
+    }
+
+    fn connected_peers(&self) -> Vec<PeerId> {
+        self.inner.connected_peers()
+    }
+
+    fn send_to(&mut self, peer: PeerId, data: Vec<u8>) -> Result<()> {
+        self.inner.send_to(peer, data)
+    }
+
+    fn broadcast(&mut self, data: Vec<u8>) -> Result<()> {
+        self.inner.broadcast(data)
+    }
+
+    fn poll_events(&mut self) -> Vec<ConnectionEvent> {
+        let mut events = std::mem::take(&mut self.injected);
+        events.extend(self.inner.poll_events());
+        events
+    }
+}
+
+#[test]
+fn test_session_reports_reconnecting() {
+    let network = LoopbackNetwork::new();
+    let connection = FlakyConnection {
+        inner: network.connect(),
+        injected: Vec::new(),
+    };
+    let peer_id = connection.local_peer_id().unwrap();
+
+    let (mut guest, _) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(connection, SessionId::new());
+    assert!(!guest.is_reconnecting());
+
+    guest
+        .p2p_mut()
+        .connection_mut()
+        .injected
+        .push(ConnectionEvent::Reconnecting { attempt: 1 });
+    guest.poll();
+    assert!(guest.is_reconnecting());
+
+    guest
+        .p2p_mut()
+        .connection_mut()
+        .injected
+        .push(ConnectionEvent::Reconnected { peer_id });
+    guest.poll();
+    assert!(!guest.is_reconnecting());
+}
+
+#[test]
+fn test_host_kicks_flooding_guest() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .rate_limit(RateLimit::new(5, 64 * 1024).with_auto_kick(1))
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Guarded Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+
+    let (mut guest, lobby_id) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    tick(&mut [&mut host, &mut guest], 10);
+    guest
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Mallory".to_string(),
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+    assert_eq!(host.get_lobby().unwrap().participants().len(), 2);
+
+    for _ in 0..50 {
+        guest.p2p_mut().request_full_sync().unwrap();
+    }
+    tick(&mut [&mut host, &mut guest], 10);
+
+    assert_eq!(host.get_lobby().unwrap().participants().len(), 1);
+}
+
+#[test]
+fn test_guest_resyncs_on_checksum_mismatch() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .checksum_interval(Some(Duration::ZERO))
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Checked Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+
+    let (mut guest, lobby_id) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    tick(&mut [&mut host, &mut guest], 10);
+    assert_eq!(guest.state_checksum(), host.state_checksum());
+
+    // Diverge the guest behind the host's back
+    guest
+        .domain_mut()
+        .event_loop_mut()
+        .handle_command(DomainCommand::AddParticipant {
+            lobby_id,
+            participant: Participant::new_guest("Ghost".to_string()).unwrap(),
+        });
+    assert_eq!(guest.get_lobby().unwrap().participants().len(), 2);
+    assert_ne!(guest.state_checksum(), host.state_checksum());
+
+    tick(&mut [&mut host, &mut guest], 10);
+
+    assert_eq!(guest.get_lobby().unwrap().participants().len(), 1);
+    assert_eq!(guest.state_checksum(), host.state_checksum());
+}
+
+#[test]
+fn test_crdt_guests_sync_without_host() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .sync_mode(SyncMode::Crdt)
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "CRDT Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+
+    let (mut alice, lobby_id) = P2PLoopBuilder::new()
+        .sync_mode(SyncMode::Crdt)
+        .build_session_guest_with_connection(network.connect(), session_id.clone());
+    let (mut bob, _) = P2PLoopBuilder::new()
+        .sync_mode(SyncMode::Crdt)
+        .build_session_guest_with_connection(network.connect(), session_id);
+
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+
+    for (guest, name) in [(&mut alice, "Alice"), (&mut bob, "Bob")] {
+        guest
+            .submit_command(DomainCommand::JoinLobby {
+                lobby_id,
+                guest_name: name.to_string(),
+            })
+            .unwrap();
+    }
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+
+    for session in [&host, &alice, &bob] {
+        assert_eq!(session.get_lobby().unwrap().participants().len(), 3);
+    }
+
+    // Host stops polling; guests keep changing state between themselves
+    let alice_id = alice.p2p().local_participant_id().unwrap();
+    alice
+        .submit_command(DomainCommand::ToggleParticipationMode {
+            lobby_id,
+            participant_id: alice_id,
+            requester_id: alice_id,
+        })
+        .unwrap();
+    alice.send_chat("host is away".to_string()).unwrap();
+    tick(&mut [&mut alice, &mut bob], 10);
+
+    let mode = |session: &SessionLoop<LoopbackConnection>| {
+        session.get_lobby().unwrap().participants()[&alice_id].participation_mode()
+    };
+    assert_eq!(mode(&bob), ParticipationMode::Spectating);
+    assert_eq!(bob.chat_messages().len(), 1);
+
+    // The host catches up once it is back
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+    assert_eq!(mode(&host), ParticipationMode::Spectating);
+    assert_eq!(host.chat_messages()[0].text, "host is away");
+}
+
+#[test]
+fn test_crdt_guests_only_change_their_own_participant() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .sync_mode(SyncMode::Crdt)
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "CRDT Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let (mut alice, lobby_id) = P2PLoopBuilder::new()
+        .sync_mode(SyncMode::Crdt)
+        .build_session_guest_with_connection(network.connect(), session_id.clone());
+    let (mut bob, _) = P2PLoopBuilder::new()
+        .sync_mode(SyncMode::Crdt)
+        .build_session_guest_with_connection(network.connect(), session_id);
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+
+    for (guest, name) in [(&mut alice, "Alice"), (&mut bob, "Bob")] {
+        guest
+            .submit_command(DomainCommand::JoinLobby {
+                lobby_id,
+                guest_name: name.to_string(),
+            })
+            .unwrap();
+    }
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+    let alice_id = alice.p2p().local_participant_id().unwrap();
+
+    // Bob forges ops for Alice and a join for a made-up participant
+    let crdt = bob.p2p_mut().crdt_mut().unwrap();
+    let forged = [
+        crdt.set_mode(alice_id, ParticipationMode::Spectating),
+        crdt.leave(alice_id),
+        crdt.join(uuid::Uuid::new_v4(), "Mallory".to_string()),
+    ];
+    for op in forged {
+        bob.p2p_mut().broadcast_crdt_op(op).unwrap();
+    }
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+
+    for session in [&host, &alice] {
+        let lobby = session.get_lobby().unwrap();
+        assert_eq!(lobby.participants().len(), 3);
+        assert_eq!(
+            lobby.participants()[&alice_id].participation_mode(),
+            ParticipationMode::Active
+        );
+    }
+}
+
+#[test]
+fn test_crdt_joins_respect_lobby_capacity() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .sync_mode(SyncMode::Crdt)
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Small Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let lobby_id = host.lobby_id();
+    let host_id = host.get_lobby().unwrap().host_id();
+    let mut settings = *host.get_lobby().unwrap().settings();
+    settings.max_participants = Some(2);
+    host.submit_command(DomainCommand::UpdateLobbySettings {
+        lobby_id,
+        host_id,
+        settings,
+    })
+    .unwrap();
+
+    let (mut alice, _) = P2PLoopBuilder::new()
+        .sync_mode(SyncMode::Crdt)
+        .build_session_guest_with_connection(network.connect(), session_id.clone());
+    let (mut bob, _) = P2PLoopBuilder::new()
+        .sync_mode(SyncMode::Crdt)
+        .build_session_guest_with_connection(network.connect(), session_id);
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+
+    // Alice takes the last seat, Bob is turned away
+    alice
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Alice".to_string(),
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+    bob.submit_command(DomainCommand::JoinLobby {
+        lobby_id,
+        guest_name: "Bob".to_string(),
+    })
+    .unwrap();
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+
+    for session in [&host, &alice, &bob] {
+        assert_eq!(session.get_lobby().unwrap().participants().len(), 2);
+    }
+}
+
+#[test]
+fn test_crdt_kicks_and_mode_changes_follow_lobby_rules() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .sync_mode(SyncMode::Crdt)
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "CRDT Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let lobby_id = host.lobby_id();
+    let host_id = host.get_lobby().unwrap().host_id();
+    let (mut alice, _) = P2PLoopBuilder::new()
+        .sync_mode(SyncMode::Crdt)
+        .build_session_guest_with_connection(network.connect(), session_id.clone());
+    let (mut bob, _) = P2PLoopBuilder::new()
+        .sync_mode(SyncMode::Crdt)
+        .build_session_guest_with_connection(network.connect(), session_id);
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+
+    for (guest, name) in [(&mut alice, "Alice"), (&mut bob, "Bob")] {
+        guest
+            .submit_command(DomainCommand::JoinLobby {
+                lobby_id,
+                guest_name: name.to_string(),
+            })
+            .unwrap();
+    }
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+    let alice_id = alice.p2p().local_participant_id().unwrap();
+    let bob_id = bob.p2p().local_participant_id().unwrap();
+
+    // No mode changes while an activity runs
+    host.submit_command(DomainCommand::QueueActivity {
+        lobby_id,
+        config: ActivityConfig::new("quiz".to_string(), "Q1".to_string(), serde_json::json!({})),
+    })
+    .unwrap();
+    host.submit_command(DomainCommand::StartNextRun { lobby_id })
+        .unwrap();
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+
+    let toggle = DomainCommand::ToggleParticipationMode {
+        lobby_id,
+        participant_id: alice_id,
+        requester_id: alice_id,
+    };
+    assert!(alice.submit_command(toggle.clone()).is_err());
+    assert!(host.submit_command(toggle).is_err());
+
+    // Co-hosts may kick guests, as the permission policy says
+    host.submit_command(DomainCommand::SetCoHost {
+        lobby_id,
+        host_id,
+        participant_id: alice_id,
+        co_host: true,
+    })
+    .unwrap();
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+    alice
+        .submit_command(DomainCommand::KickGuest {
+            lobby_id,
+            host_id: alice_id,
+            guest_id: bob_id,
+            ban: false,
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+
+    for session in [&host, &alice] {
+        let lobby = session.get_lobby().unwrap();
+        assert_eq!(lobby.participants().len(), 2);
+        assert_eq!(
+            lobby.participants()[&alice_id].participation_mode(),
+            ParticipationMode::Active
+        );
+    }
+}
+
+#[test]
+fn test_chat_history_reaches_late_joiners() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Chatty Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let (mut alice, lobby_id) = P2PLoopBuilder::new()
+        .build_session_guest_with_connection(network.connect(), session_id.clone());
+
+    tick(&mut [&mut host, &mut alice], 10);
+    alice
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Alice".to_string(),
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut alice], 10);
+
+    alice.send_chat("hi all".to_string()).unwrap();
+    tick(&mut [&mut host, &mut alice], 10);
+    host.send_chat("welcome!".to_string()).unwrap();
+    tick(&mut [&mut host, &mut alice], 10);
+
+    let texts = |session: &SessionLoop<LoopbackConnection>| -> Vec<String> {
+        let lobby = session.get_lobby().unwrap();
+        lobby
+            .chat_messages()
+            .iter()
+            .map(|message| message.text().to_string())
+            .collect()
+    };
+    assert_eq!(texts(&host), vec!["hi all", "welcome!"]);
+    assert_eq!(texts(&alice), texts(&host));
+
+    let (mut bob, _) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+
+    assert_eq!(texts(&bob), texts(&host));
+}
+
+#[test]
+fn test_guests_follow_runs_live() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Quiz Night".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let (mut alice, lobby_id) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    tick(&mut [&mut host, &mut alice], 10);
+    alice
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Alice".to_string(),
+        })
+        .unwrap();
+    host.submit_command(DomainCommand::QueueActivity {
+        lobby_id,
+        config: ActivityConfig::new("quiz".to_string(), "Q1".to_string(), serde_json::json!({})),
+    })
+    .unwrap();
+    tick(&mut [&mut host, &mut alice], 10);
+
+    host.submit_command(DomainCommand::StartNextRun { lobby_id })
+        .unwrap();
+    tick(&mut [&mut host, &mut alice], 10);
+
+    let run_id = host.get_lobby().unwrap().active_run_id().unwrap();
+    let alice_lobby = alice.get_lobby().unwrap();
+    assert_eq!(alice_lobby.active_run_id(), Some(run_id));
+    assert!(alice_lobby.activity_queue().is_empty());
+
+    // Cancelling on the host ends the run for Alice as well
+    host.submit_command(DomainCommand::CancelRun { lobby_id, run_id })
+        .unwrap();
+    tick(&mut [&mut host, &mut alice], 10);
+
+    assert!(!alice.get_lobby().unwrap().has_active_run());
+}
+
+#[test]
+fn test_host_expires_runs_past_their_deadline() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+    let clock = TestClock::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .clock(clock.shared())
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Speed Round".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let (mut alice, lobby_id) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    tick(&mut [&mut host, &mut alice], 10);
+    alice
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Alice".to_string(),
+        })
+        .unwrap();
+    host.submit_command(DomainCommand::QueueActivity {
+        lobby_id,
+        config: ActivityConfig::new(
+            "quiz".to_string(),
+            "Q1".to_string(),
+            serde_json::json!({ "time_limit_ms": 5_000 }),
+        ),
+    })
+    .unwrap();
+    tick(&mut [&mut host, &mut alice], 10);
+
+    host.submit_command(DomainCommand::StartNextRun { lobby_id })
+        .unwrap();
+    tick(&mut [&mut host, &mut alice], 10);
+    let run_id = host.get_lobby().unwrap().active_run_id().unwrap();
+
+    // Time left: polling keeps the run open
+    clock.advance(Duration::from_secs(5));
+    tick(&mut [&mut host, &mut alice], 10);
+    assert_eq!(host.get_lobby().unwrap().active_run_id(), Some(run_id));
+
+    clock.advance(Duration::from_millis(1));
+    tick(&mut [&mut host, &mut alice], 10);
+
+    let run = host.domain().event_loop().get_run(&run_id).unwrap();
+    assert_eq!(run.status(), RunStatus::Completed);
+    assert!(!host.get_lobby().unwrap().has_active_run());
+    assert!(!alice.get_lobby().unwrap().has_active_run());
+}
+
+#[test]
+fn test_stale_host_steps_down_after_split_brain() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Split Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+
+    let (mut alice, lobby_id) = P2PLoopBuilder::new()
+        .build_session_guest_with_connection(network.connect(), session_id.clone());
+    let (mut bob, _) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+    alice
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Alice".to_string(),
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+
+    // Alice wrongly believes the host is gone and promotes herself (epoch 1)
+    alice.promote_to_host();
+    assert_eq!(alice.p2p().epoch(), 1);
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+
+    assert!(alice.is_host());
+    assert!(!host.is_host());
+    assert_eq!(host.p2p().epoch(), 1);
+    assert_eq!(bob.p2p().epoch(), 1);
+    assert!(host.get_lobby().is_some());
+}
+
+#[test]
+fn test_breakout_topic_shares_session() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Main Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+
+    let (mut guest, lobby_id) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    tick(&mut [&mut host, &mut guest], 10);
+
+    let breakout = host.open_topic("Breakout".to_string()).unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+
+    assert_eq!(guest.topics(), vec![breakout]);
+    assert_eq!(guest.topic_lobby(&breakout).unwrap().name(), "Breakout");
+
+    guest
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id: breakout,
+            guest_name: "Alice".to_string(),
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+
+    assert_eq!(host.topic_lobby(&breakout).unwrap().participants().len(), 2);
+    assert_eq!(
+        guest.topic_lobby(&breakout).unwrap().participants().len(),
+        2
+    );
+    assert_eq!(host.get_lobby().unwrap().participants().len(), 1);
+    assert_eq!(guest.get_lobby().unwrap().participants().len(), 1);
+    assert_eq!(guest.lobby_id(), lobby_id);
+}
+
+#[test]
+fn test_guest_acts_in_topic_as_its_topic_participant() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Main Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+
+    let (mut guest, lobby_id) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+    tick(&mut [&mut host, &mut guest], 10);
+
+    guest
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Alice".to_string(),
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+    let main_id = guest.p2p().local_participant_id().unwrap();
+
+    let breakout = host.open_topic("Breakout".to_string()).unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+    guest
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id: breakout,
+            guest_name: "Alice".to_string(),
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+
+    // The topic gave the guest a participant of its own
+    let topic_id = guest.topic_participant_id(&breakout).unwrap();
+    assert_ne!(topic_id, main_id);
+    assert!(
+        host.topic_lobby(&breakout)
+            .unwrap()
+            .participants()
+            .contains_key(&topic_id)
+    );
+
+    guest
+        .submit_command(DomainCommand::ToggleParticipationMode {
+            lobby_id: breakout,
+            participant_id: topic_id,
+            requester_id: topic_id,
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+
+    let mode = |session: &SessionLoop<LoopbackConnection>| {
+        session.topic_lobby(&breakout).unwrap().participants()[&topic_id].participation_mode()
+    };
+    assert_eq!(mode(&host), ParticipationMode::Spectating);
+    assert_eq!(mode(&guest), ParticipationMode::Spectating);
+
+    guest
+        .submit_command(DomainCommand::LeaveLobby {
+            lobby_id: breakout,
+            participant_id: topic_id,
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+
+    assert_eq!(host.topic_lobby(&breakout).unwrap().participants().len(), 1);
+    assert_eq!(host.get_lobby().unwrap().participants().len(), 2);
+}
+
+#[tokio::test]
+async fn test_async_session_loop_is_event_driven() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (host, _) = P2PLoopBuilder::new()
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Async Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let (guest, lobby_id) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    // A long idle interval: progress must come from transport wake-ups
+    let idle = Duration::from_secs(60);
+    let mut host = AsyncSessionLoop::new(host).with_idle_interval(idle);
+    let mut guest = AsyncSessionLoop::new(guest).with_idle_interval(idle);
+
+    let mut join_sent = false;
+    let joined = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            tokio::select! {
+                Some(event) = host.next() => {
+                    if let SessionEvent::Domain(DomainEvent::GuestJoined { participant, .. }) = event {
+                        return participant.name().to_string();
+                    }
+                }
+                Some(event) = guest.next() => {
+                    if let SessionEvent::Domain(DomainEvent::LobbyCreated { .. }) = event
+                        && !join_sent
+                    {
+                        join_sent = true;
+                        guest
+                            .submit_command(DomainCommand::JoinLobby {
+                                lobby_id,
+                                guest_name: "Alice".to_string(),
+                            })
+                            .unwrap();
+                    }
+                }
+            }
+        }
+    })
+    .await
+    .expect("Guest should join without idle polling");
+
+    assert_eq!(joined, "Alice");
+    assert_eq!(host.session().get_lobby().unwrap().participants().len(), 2);
+}
+
+#[test]
+fn test_saturated_queue_hands_commands_back() {
+    use futures::task::noop_waker;
+    use std::task::Context;
+
+    let network = LoopbackNetwork::new();
+    let (mut host, _) = P2PLoopBuilder::new()
+        .batch_size(1)
+        .queue_size(2)
+        .build_session_host_with_connection(
+            network.connect(),
+            SessionId::new(),
+            "Busy Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    host.poll();
+
+    let lobby_id = host.lobby_id();
+    let join = |name: &str| DomainCommand::JoinLobby {
+        lobby_id,
+        guest_name: name.to_string(),
+    };
+    host.try_submit_command(join("A")).unwrap();
+    host.try_submit_command(join("B")).unwrap();
+
+    let Err(TrySubmitError::Full(returned)) = host.try_submit_command(join("C")) else {
+        panic!("Expected a full queue");
+    };
+    assert_eq!(*returned, join("C"));
+
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    assert!(host.poll_submit_ready(&mut cx).is_pending());
+
+    host.poll();
+    assert!(host.poll_submit_ready(&mut cx).is_ready());
+    host.try_submit_command(*returned).unwrap();
+
+    // The async variant waits for room instead of failing
+    let mut host = AsyncSessionLoop::new(host);
+    futures::executor::block_on(async {
+        for name in ["D", "E", "F", "G"] {
+            host.submit(join(name)).await.unwrap();
+        }
+    });
+}
+
+#[test]
+fn test_banned_guest_is_dropped_at_transport() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Strict Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+
+    let (mut guest, lobby_id) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+    let guest_peer = guest.local_peer_id().unwrap();
+
+    tick(&mut [&mut host, &mut guest], 10);
+    guest
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Mallory".to_string(),
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+
+    let lobby = host.get_lobby().unwrap();
+    let guest_id = lobby
+        .participants()
+        .values()
+        .find(|p| !p.is_host())
+        .unwrap()
+        .id();
+    host.submit_command(DomainCommand::KickGuest {
+        lobby_id,
+        host_id: lobby.host_id(),
+        guest_id,
+        ban: true,
+    })
+    .unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+
+    assert!(host.p2p().is_banned(&guest_peer));
+    assert_eq!(host.get_lobby().unwrap().participants().len(), 1);
+
+    // Rejoining from the banned peer is ignored
+    guest
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Mallory".to_string(),
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+
+    assert_eq!(host.get_lobby().unwrap().participants().len(), 1);
+    assert!(!host.connected_peers().contains(&guest_peer));
+}
+
+#[test]
+fn test_timeout_policy_marks_away_and_propagates() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .grace_period(Duration::ZERO)
+        .timeout_policy(TimeoutPolicy::MarkAway)
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Patient Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+
+    let (mut alice, lobby_id) = P2PLoopBuilder::new()
+        .build_session_guest_with_connection(network.connect(), session_id.clone());
+    let (mut bob, _) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+    assert_eq!(bob.timeouts(), host.timeouts());
+
+    alice
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Alice".to_string(),
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+    let alice_id = alice.p2p().local_participant_id().unwrap();
+
+    drop(alice);
+    tick(&mut [&mut host, &mut bob], 5);
+
+    assert_eq!(host.get_lobby().unwrap().participants().len(), 2);
+    assert_eq!(host.away_participants(), vec![alice_id]);
+
+    // The host changes the policy at runtime; guests follow
+    let strict = TimeoutConfig::default().with_grace_period(Duration::from_secs(5));
+    host.set_timeouts(strict).unwrap();
+    tick(&mut [&mut host, &mut bob], 5);
+
+    assert_eq!(bob.timeouts(), strict);
+    assert!(bob.set_timeouts(strict).is_err());
+}
+
+#[test]
+fn test_silent_peer_times_out_with_heartbeats() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .grace_period(Duration::ZERO)
+        .heartbeat_interval(Some(Duration::from_millis(10)))
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Heartbeat Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+
+    let (mut guest, lobby_id) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    tick(&mut [&mut host, &mut guest], 10);
+    guest
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Sleepy".to_string(),
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+    assert_eq!(host.get_lobby().unwrap().participants().len(), 2);
+
+    // The guest stays connected but stops polling (and heartbeating)
+    std::thread::sleep(Duration::from_millis(50));
+    tick(&mut [&mut host], 3);
+
+    assert_eq!(host.get_lobby().unwrap().participants().len(), 1);
+}
+
+#[test]
+fn test_heartbeat_acks_measure_round_trips() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .heartbeat_interval(Some(Duration::from_millis(1)))
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Stats Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let (mut guest, lobby_id) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    tick(&mut [&mut host, &mut guest], 10);
+    guest
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Pinged".to_string(),
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+    std::thread::sleep(Duration::from_millis(2));
+    tick(&mut [&mut host, &mut guest], 5);
+
+    let stats = host.peer_stats();
+    assert_eq!(stats.len(), 1);
+    let guest_stats = &stats[0];
+    assert!(guest_stats.rtt.is_some());
+    assert!(guest_stats.messages_sent > 0);
+    assert!(guest_stats.messages_received > 0);
+    assert_eq!(guest_stats.status, ConnectionStatus::Connected);
+
+    let guest_id = guest
+        .get_lobby()
+        .unwrap()
+        .participants()
+        .values()
+        .find(|p| !p.is_host())
+        .unwrap()
+        .id();
+    assert_eq!(guest_stats.participant_id, Some(guest_id));
+
+    // The host stamps its acks, so the guest follows the host's clock
+    assert!(guest.clock_offset_ms().abs() < 50);
+    assert!(guest.session_time_ms().abs_diff(host.session_time_ms()) < 50);
+}
+
+#[test]
+fn test_session_converges_over_degraded_loopback() {
+    let network = LoopbackNetwork::with_simulator(NetworkSimulator::new(11));
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .checksum_interval(Some(Duration::ZERO))
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Lossy Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let lobby_id = host.lobby_id();
+    let host_id = host.get_lobby().unwrap().host_id();
+    let (mut alice, _) = P2PLoopBuilder::new()
+        .build_session_guest_with_connection(network.connect(), session_id.clone());
+    let (mut bob, _) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    let toggle_host_mode = |host: &mut SessionLoop<LoopbackConnection>| {
+        host.submit_command(DomainCommand::ToggleParticipationMode {
+            lobby_id,
+            participant_id: host_id,
+            requester_id: host_id,
+        })
+        .unwrap();
+    };
+    let run = |sessions: &mut [&mut SessionLoop<LoopbackConnection>], ticks: usize| {
+        for _ in 0..ticks {
+            tick(sessions, 1);
+            network.tick();
+        }
+    };
+
+    // Slow, reordering links
+    network.set_conditions(
+        NetworkConditions::perfect()
+            .with_latency(2)
+            .with_jitter(2)
+            .with_reorder(0.3),
+    );
+    run(&mut [&mut host, &mut alice, &mut bob], 50);
+    for (guest, name) in [(&mut alice, "Alice"), (&mut bob, "Bob")] {
+        guest
+            .submit_command(DomainCommand::JoinLobby {
+                lobby_id,
+                guest_name: name.to_string(),
+            })
+            .unwrap();
+    }
+    run(&mut [&mut host, &mut alice, &mut bob], 100);
+    for session in [&host, &alice, &bob] {
+        assert_eq!(session.get_lobby().unwrap().participants().len(), 3);
+    }
+
+    // Lost host updates are recovered through checksum resync
+    network.set_conditions(NetworkConditions::perfect().with_latency(1).with_loss(0.2));
+    for _ in 0..5 {
+        toggle_host_mode(&mut host);
+        run(&mut [&mut host, &mut alice, &mut bob], 5);
+    }
+    run(&mut [&mut host, &mut alice, &mut bob], 200);
+    assert!(network.dropped() > 0);
+    for guest in [&alice, &bob] {
+        assert_eq!(guest.state_checksum(), host.state_checksum());
+    }
+
+    // Bob misses an update while cut off, then catches up after healing
+    network.set_conditions(NetworkConditions::perfect());
+    network.partition(
+        &[bob.local_peer_id().unwrap()],
+        &[
+            host.local_peer_id().unwrap(),
+            alice.local_peer_id().unwrap(),
+        ],
+    );
+    toggle_host_mode(&mut host);
+    run(&mut [&mut host, &mut alice, &mut bob], 10);
+    assert_eq!(alice.state_checksum(), host.state_checksum());
+    assert_ne!(bob.state_checksum(), host.state_checksum());
+
+    network.heal();
+    run(&mut [&mut host, &mut alice, &mut bob], 20);
+    assert_eq!(bob.state_checksum(), host.state_checksum());
+}
+
+/// Records `(span name, correlation_id)` for every span that has one
+#[derive(Clone, Default)]
+struct CorrelationSpans(Arc<Mutex<Vec<(&'static str, String)>>>);
+
+impl<S: tracing::Subscriber> Layer<S> for CorrelationSpans {
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, _: &span::Id, _: Context<'_, S>) {
+        struct Visitor(Option<String>);
+
+        impl Visit for Visitor {
+            fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+                if field.name() == "correlation_id" {
+                    self.0 = Some(format!("{:?}", value));
+                }
+            }
+        }
+
+        let mut visitor = Visitor(None);
+        attrs.record(&mut visitor);
+        if let Some(id) = visitor.0 {
+            self.0.lock().unwrap().push((attrs.metadata().name(), id));
+        }
+    }
+}
+
+#[test]
+fn test_command_is_traced_across_peers() {
+    let spans = CorrelationSpans::default();
+    let subscriber = tracing_subscriber::registry().with(spans.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        let network = LoopbackNetwork::new();
+        let session_id = SessionId::new();
+
+        let (mut host, _) = P2PLoopBuilder::new()
+            .build_session_host_with_connection(
+                network.connect(),
+                session_id.clone(),
+                "Traced Lobby".to_string(),
+                "Host".to_string(),
+            )
+            .unwrap();
+        let (mut guest, lobby_id) = P2PLoopBuilder::new()
+            .build_session_guest_with_connection(network.connect(), session_id);
+
+        tick(&mut [&mut host, &mut guest], 5);
+        guest
+            .submit_command(DomainCommand::JoinLobby {
+                lobby_id,
+                guest_name: "Alice".to_string(),
+            })
+            .unwrap();
+        tick(&mut [&mut host, &mut guest], 5);
+
+        assert_eq!(guest.get_lobby().unwrap().participants().len(), 2);
+    });
+
+    let spans = spans.0.lock().unwrap();
+    let (_, correlation_id) = spans
+        .iter()
+        .find(|(name, _)| *name == "command_submitted")
+        .expect("Submitting a command opens a span");
+    let stages: Vec<_> = spans
+        .iter()
+        .filter(|(_, id)| id == correlation_id)
+        .map(|(name, _)| *name)
+        .collect();
+
+    for stage in [
+        "command_received",
+        "event_broadcast",
+        "event_received",
+        "event_applied",
+    ] {
+        assert!(stages.contains(&stage), "missing {stage} in {stages:?}");
+    }
+}
+
+#[test]
+fn test_large_lobby_snapshot_is_streamed() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .snapshot_page_size(10)
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Big Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let lobby_id = host.lobby_id();
+    for i in 0..45 {
+        host.domain_mut()
+            .event_loop_mut()
+            .handle_command(DomainCommand::AddParticipant {
+                lobby_id,
+                participant: Participant::new_guest(format!("Guest{i}")).unwrap(),
+            });
+    }
+    assert_eq!(host.get_lobby().unwrap().participants().len(), 46);
+
+    let (mut guest, _) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    let mut seen = Vec::new();
+    for _ in 0..30 {
+        host.poll();
+        guest.poll();
+        if let Some(lobby) = guest.get_lobby() {
+            seen.push(lobby.participants().len());
+        }
+    }
+
+    // The guest fills in page by page instead of all at once
+    seen.dedup();
+    assert!(seen.len() > 2, "Expected progressive sync, saw {seen:?}");
+    assert_eq!(guest.get_lobby().unwrap().participants().len(), 46);
+    assert_eq!(guest.state_checksum(), host.state_checksum());
+}
+
+#[test]
+fn test_outbox_survives_restart_until_a_guest_joins() {
+    let session_id = SessionId::new();
+    let path = std::env::temp_dir().join(format!("konnekt-outbox-{}.jsonl", session_id.as_str()));
+
+    {
+        let (mut host, _) = P2PLoopBuilder::new()
+            .outbox(&path)
+            .build_session_host_with_connection(
+                LoopbackNetwork::new().connect(),
+                session_id.clone(),
+                "Prepared Lobby".to_string(),
+                "Host".to_string(),
+            )
+            .unwrap();
+        host.submit_command(DomainCommand::QueueActivity {
+            lobby_id: host.lobby_id(),
+            config: ActivityConfig::new(
+                "echo-challenge-v1".to_string(),
+                "Planned Offline".to_string(),
+                serde_json::json!({}),
+            ),
+        })
+        .unwrap();
+        tick(&mut [&mut host], 5);
+        assert_eq!(host.get_lobby().unwrap().activity_queue().len(), 1);
+    }
+
+    // Restarted before anyone connected
+    let network = LoopbackNetwork::new();
+    let (mut host, _) = P2PLoopBuilder::new()
+        .outbox(&path)
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Ignored".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let lobby = host.get_lobby().unwrap();
+    assert_eq!(lobby.name(), "Prepared Lobby");
+    assert_eq!(lobby.activity_queue().len(), 1);
+
+    let (mut guest, _) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+    tick(&mut [&mut host, &mut guest], 10);
+
+    assert_eq!(guest.get_lobby().unwrap().activity_queue().len(), 1);
+    assert_eq!(guest.state_checksum(), host.state_checksum());
+    assert!(std::fs::read_to_string(&path).unwrap().is_empty());
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_peers_negotiate_common_capabilities() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .capabilities(Capabilities::COMPRESSION | Capabilities::BINARY_CODEC)
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Capable Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let (mut guest, _) = P2PLoopBuilder::new()
+        .capabilities(Capabilities::COMPRESSION | Capabilities::UNRELIABLE_CHANNEL)
+        .build_session_guest_with_connection(network.connect(), session_id.clone());
+    // A client without any optional features
+    let (mut legacy, _) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    tick(&mut [&mut host, &mut guest, &mut legacy], 10);
+
+    let host_peer = host.local_peer_id().unwrap();
+    let guest_peer = guest.local_peer_id().unwrap();
+    let legacy_peer = legacy.local_peer_id().unwrap();
+
+    assert_eq!(
+        host.p2p().peer_capabilities(&guest_peer),
+        Capabilities::COMPRESSION
+    );
+    assert_eq!(
+        guest.p2p().peer_capabilities(&host_peer),
+        Capabilities::COMPRESSION
+    );
+    assert!(host.p2p().peer_capabilities(&legacy_peer).is_empty());
+    assert!(guest.get_lobby().is_some());
+    assert!(legacy.get_lobby().is_some());
+}
+
+#[test]
+fn test_presence_signals_reach_other_peers() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Busy Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let (mut guest, lobby_id) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    tick(&mut [&mut host, &mut guest], 10);
+    assert!(guest.set_presence(Some(Presence::Typing)).is_err());
+
+    guest
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Alice".to_string(),
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+    let alice = guest.p2p().local_participant_id().unwrap();
+    let host_id = host.get_lobby().unwrap().host_id();
+
+    guest.set_presence(Some(Presence::Typing)).unwrap();
+    let run_id = uuid::Uuid::new_v4();
+    host.set_presence(Some(Presence::Answering { run_id }))
+        .unwrap();
+    tick(&mut [&mut host, &mut guest], 5);
+
+    assert_eq!(host.presence(), vec![(alice, Presence::Typing)]);
+    assert_eq!(
+        guest.presence(),
+        vec![(host_id, Presence::Answering { run_id })]
+    );
+
+    guest.set_presence(None).unwrap();
+    tick(&mut [&mut host, &mut guest], 5);
+
+    assert!(host.presence().is_empty());
+}
+
+#[test]
+fn test_host_restores_snapshot_after_crash() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+    let path = std::env::temp_dir().join(format!(
+        "konnekt-host-snapshot-{}.json",
+        session_id.as_str()
+    ));
+    let builder = || {
+        P2PLoopBuilder::new()
+            .host_snapshot(&path)
+            .host_snapshot_interval(Duration::ZERO)
+    };
+
+    let (mut host, _) = builder()
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Crashy Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let (mut guest, lobby_id) = P2PLoopBuilder::new()
+        .build_session_guest_with_connection(network.connect(), session_id.clone());
+    tick(&mut [&mut host, &mut guest], 10);
+
+    guest
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Alice".to_string(),
+        })
+        .unwrap();
+    for name in ["First", "Second"] {
+        host.submit_command(DomainCommand::QueueActivity {
+            lobby_id,
+            config: ActivityConfig::new(
+                "echo-challenge-v1".to_string(),
+                name.to_string(),
+                serde_json::json!({}),
+            ),
+        })
+        .unwrap();
+    }
+    tick(&mut [&mut host, &mut guest], 10);
+    host.submit_command(DomainCommand::StartNextRun { lobby_id })
+        .unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+
+    let run_id = host.get_lobby().unwrap().active_run_id().unwrap();
+    let alice = guest
+        .get_lobby()
+        .unwrap()
+        .participants()
+        .values()
+        .find(|p| p.name() == "Alice")
+        .unwrap()
+        .id();
+    guest
+        .submit_command(DomainCommand::SubmitResult {
+            lobby_id,
+            run_id,
+            result: konnekt_session_core::domain::ActivityResult::new(run_id, alice).with_score(3),
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut guest], 10);
+    let host_id = host.get_lobby().unwrap().host_id();
+
+    // Crash: the host disappears without a goodbye
+    drop(host);
+    tick(&mut [&mut guest], 3);
+
+    let (mut host, restored_id) = builder()
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Ignored".to_string(),
+            "Ignored".to_string(),
+        )
+        .unwrap();
+    assert_eq!(restored_id, session_id);
+
+    let lobby = host.get_lobby().unwrap();
+    assert_eq!(lobby.name(), "Crashy Lobby");
+    assert_eq!(lobby.host_id(), host_id);
+    assert_eq!(lobby.participants().len(), 2);
+    assert_eq!(lobby.activity_queue().len(), 1);
+    assert_eq!(lobby.active_run_id(), Some(run_id));
+    let run = host.domain().event_loop().get_run(&run_id).unwrap();
+    assert_eq!(run.results()[&alice].score, Some(3));
+
+    // The restarted host re-announces itself to the waiting guest
+    tick(&mut [&mut host, &mut guest], 10);
+    assert_eq!(guest.state_checksum(), host.state_checksum());
+    assert_eq!(guest.get_lobby().unwrap().active_run_id(), Some(run_id));
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_host_delegates_to_a_guest() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Handover Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let (mut alice, lobby_id) = P2PLoopBuilder::new()
+        .build_session_guest_with_connection(network.connect(), session_id.clone());
+    let (mut bob, _) =
+        P2PLoopBuilder::new().build_session_guest_with_connection(network.connect(), session_id);
+
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+    for (guest, name) in [(&mut alice, "Alice"), (&mut bob, "Bob")] {
+        guest
+            .submit_command(DomainCommand::JoinLobby {
+                lobby_id,
+                guest_name: name.to_string(),
+            })
+            .unwrap();
+    }
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+
+    let host_id = host.get_lobby().unwrap().host_id();
+    let alice_id = alice.p2p().local_participant_id().unwrap();
+    host.submit_command(DomainCommand::SetCoHost {
+        lobby_id,
+        host_id,
+        participant_id: alice_id,
+        co_host: true,
+    })
+    .unwrap();
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+    assert!(bob.get_lobby().unwrap().is_co_host(alice_id));
+
+    host.submit_command(DomainCommand::DelegateHost {
+        lobby_id,
+        current_host_id: host_id,
+        new_host_id: alice_id,
+    })
+    .unwrap();
+    tick(&mut [&mut host, &mut alice, &mut bob], 20);
+
+    assert!(alice.is_host());
+    assert!(!host.is_host());
+    assert!(!bob.is_host());
+    for session in [&host, &alice, &bob] {
+        let lobby = session.get_lobby().unwrap();
+        assert_eq!(lobby.host_id(), alice_id);
+        assert!(!lobby.is_co_host(alice_id));
+        assert_eq!(lobby.participants().len(), 3);
+    }
+
+    // The new host's commands reach everyone, the old host's are fenced off
+    bob.submit_command(DomainCommand::ToggleParticipationMode {
+        lobby_id,
+        participant_id: bob.p2p().local_participant_id().unwrap(),
+        requester_id: bob.p2p().local_participant_id().unwrap(),
+    })
+    .unwrap();
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+    let bob_id = bob.p2p().local_participant_id().unwrap();
+    for session in [&host, &alice, &bob] {
+        let mode = session.get_lobby().unwrap().participants()[&bob_id].participation_mode();
+        assert_eq!(mode, ParticipationMode::Spectating);
+    }
+}
+
+#[test]
+fn test_elected_guest_declines_host_takeover() {
+    let network = LoopbackNetwork::new();
+    let session_id = SessionId::new();
+
+    let (mut host, _) = P2PLoopBuilder::new()
+        .grace_period(Duration::ZERO)
+        .build_session_host_with_connection(
+            network.connect(),
+            session_id.clone(),
+            "Orphaned Lobby".to_string(),
+            "Host".to_string(),
+        )
+        .unwrap();
+    let (mut alice, lobby_id) = P2PLoopBuilder::new()
+        .confirm_takeover(Some(Duration::from_secs(60)))
+        .build_session_guest_with_connection(network.connect(), session_id.clone());
+    let (mut bob, _) = P2PLoopBuilder::new()
+        .confirm_takeover(Some(Duration::from_secs(60)))
+        .build_session_guest_with_connection(network.connect(), session_id);
+
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+    // Alice joins first, so she is elected first
+    alice
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: "Alice".to_string(),
+        })
+        .unwrap();
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+    std::thread::sleep(Duration::from_millis(5));
+    bob.submit_command(DomainCommand::JoinLobby {
+        lobby_id,
+        guest_name: "Bob".to_string(),
+    })
+    .unwrap();
+    tick(&mut [&mut host, &mut alice, &mut bob], 10);
+    let alice_id = alice.p2p().local_participant_id().unwrap();
+    let bob_id = bob.p2p().local_participant_id().unwrap();
+    assert_eq!(alice.host_takeover(), None);
+
+    drop(host);
+    tick(&mut [&mut alice, &mut bob], 5);
+
+    // The oldest guest is asked, nobody takes over silently
+    assert!(matches!(
+        alice.host_takeover(),
+        Some(HostTakeover::Offered { .. })
+    ));
+    assert_eq!(
+        bob.host_takeover(),
+        Some(HostTakeover::Electing {
+            successor: Some(alice_id)
+        })
+    );
+    assert!(!alice.is_host() && !bob.is_host());
+
+    assert!(alice.decline_takeover().unwrap());
+    tick(&mut [&mut alice, &mut bob], 5);
+    assert_eq!(
+        alice.host_takeover(),
+        Some(HostTakeover::Electing {
+            successor: Some(bob_id)
+        })
+    );
+    assert!(matches!(
+        bob.host_takeover(),
+        Some(HostTakeover::Offered { .. })
+    ));
+
+    assert!(bob.accept_takeover());
+    tick(&mut [&mut alice, &mut bob], 10);
+
+    assert!(bob.is_host());
+    assert_eq!(alice.get_lobby().unwrap().host_id(), bob_id);
+    assert_eq!(alice.host_takeover(), None);
+    assert_eq!(bob.host_takeover(), None);
+}
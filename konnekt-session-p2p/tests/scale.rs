@@ -0,0 +1,138 @@
+mod support;
+
+use konnekt_session_p2p::Topology;
+use support::{SessionFixture, SyncMetrics};
+
+/// Guests in the large sessions below
+const GUESTS: usize = 100;
+
+/// Ticks a large session may take to settle
+const MAX_TICKS: usize = 2_000;
+
+/// Ticks for answers still on their way once everyone is in sync
+const SETTLE_TICKS: usize = 10;
+
+/// `guests` guests connect and receive the lobby; the metrics cover the
+/// handshake
+fn connect_session(guests: usize, topology: Topology) -> (SessionFixture, SyncMetrics) {
+    let mut fixture = SessionFixture::with_topology(guests, topology);
+    let metrics = fixture
+        .tick_until(MAX_TICKS, SessionFixture::is_synced)
+        .unwrap_or_else(|| panic!("{} guests never received the lobby", topology));
+    report(&format!("{} handshake", topology), &metrics);
+
+    fixture.tick(SETTLE_TICKS);
+    (fixture, metrics)
+}
+
+/// `guests` guests join a fresh session; the metrics cover the joins only
+fn join_session(guests: usize, topology: Topology) -> (SessionFixture, SyncMetrics) {
+    let (mut fixture, _) = connect_session(guests, topology);
+
+    fixture.join_all();
+    let metrics = fixture
+        .tick_until(MAX_TICKS, |fixture| {
+            fixture.participant_count() == guests + 1 && fixture.is_synced()
+        })
+        .unwrap_or_else(|| panic!("{} guests never settled after joining", topology));
+    report(&format!("{} joins", topology), &metrics);
+
+    (fixture, metrics)
+}
+
+fn report(label: &str, metrics: &SyncMetrics) {
+    println!(
+        "{}: {} peers settled in {} ticks, {} messages ({} bytes, {:.0}/s, {:.1} per peer) in {:?}",
+        label,
+        metrics.peers,
+        metrics.ticks,
+        metrics.messages,
+        metrics.bytes,
+        metrics.throughput(),
+        metrics.messages_per_peer(),
+        metrics.elapsed,
+    );
+}
+
+#[test]
+fn test_hundred_guests_join_a_mesh() {
+    let (fixture, metrics) = join_session(GUESTS, Topology::Mesh);
+
+    assert_eq!(fixture.participant_count(), GUESTS + 1);
+    assert_eq!(metrics.peers, GUESTS + 1);
+}
+
+#[test]
+fn test_hundred_guests_join_a_star() {
+    let (fixture, _) = join_session(GUESTS, Topology::Star);
+
+    assert_eq!(fixture.participant_count(), GUESTS + 1);
+}
+
+#[test]
+fn test_late_guest_receives_a_large_lobby() {
+    // More participants than a guest's domain queue holds
+    const CROWD: usize = 150;
+
+    let (mut fixture, _) = join_session(GUESTS, Topology::Star);
+    for _ in GUESTS..CROWD {
+        fixture.add_guest();
+    }
+    fixture.join(GUESTS..CROWD);
+    fixture
+        .tick_until(MAX_TICKS, |fixture| {
+            fixture.participant_count() == CROWD + 1 && fixture.is_synced()
+        })
+        .expect("second wave never settled");
+
+    let late = fixture.add_guest();
+    fixture
+        .tick_until(MAX_TICKS, SessionFixture::is_synced)
+        .expect("late guest never received the lobby");
+
+    let lobby = fixture.guests[late].get_lobby().unwrap();
+    assert_eq!(lobby.participants().len(), CROWD + 1);
+}
+
+#[test]
+fn test_star_handshake_avoids_quadratic_traffic() {
+    let (_, mesh) = connect_session(GUESTS, Topology::Mesh);
+    let (_, star) = connect_session(GUESTS, Topology::Star);
+
+    // Guests broadcast their snapshot request to every direct link
+    assert!(mesh.messages >= (GUESTS * GUESTS) as u64);
+    assert!(star.messages < (GUESTS * 10) as u64);
+}
+
+#[test]
+fn test_join_traffic_grows_linearly_per_join() {
+    // Guests only talk to the host, so both topologies cost the same: each
+    // join goes out once to every guest
+    for topology in [Topology::Mesh, Topology::Star] {
+        let (_, metrics) = join_session(GUESTS, topology);
+        assert_eq!(metrics.messages, (GUESTS * GUESTS) as u64, "{}", topology);
+    }
+}
+
+#[test]
+fn test_several_hundred_guests_join_in_waves() {
+    const LARGE: usize = 300;
+    // The host queues at most 100 commands, more joins at once are dropped
+    const WAVE: usize = 100;
+
+    let mut fixture = SessionFixture::with_topology(LARGE, Topology::Star);
+    fixture
+        .tick_until(MAX_TICKS, SessionFixture::is_synced)
+        .expect("guests never received the lobby");
+
+    for start in (0..LARGE).step_by(WAVE) {
+        fixture.join(start..start + WAVE);
+        let metrics = fixture
+            .tick_until(MAX_TICKS, |fixture| {
+                fixture.participant_count() == start + WAVE + 1 && fixture.is_synced()
+            })
+            .unwrap_or_else(|| panic!("wave from Guest{} never settled", start + 1));
+        println!("Guest{}+: {:?}", start + 1, metrics);
+    }
+    assert_eq!(fixture.participant_count(), LARGE + 1);
+}
@@ -1,6 +1,10 @@
 mod support;
 
-use konnekt_session_core::{DomainCommand, domain::ActivityConfig};
+use konnekt_session_core::{
+    CommandError, DomainCommand, DomainEvent, ErrorKind, EventFilter, EventKind, ParticipationMode,
+    domain::{ActivityConfig, LobbyError},
+};
+use konnekt_session_p2p::{ConnectionStatus, NetworkConditions, NetworkSimulator, QueueDepths};
 use support::SessionFixture;
 
 #[test]
@@ -220,4 +224,517 @@ fn test_activity_completion() {
     // Run should be completed on host
     // After completion, active_run is cleared from lobby
     assert!(!fixture.host.get_lobby().unwrap().has_active_run());
+
+    // Results carry when they were submitted, on the host's clock
+    let run = fixture.host.get_run(&run_id).unwrap();
+    assert!(run.results().values().all(|r| r.submitted_at_ms.is_some()));
+}
+
+fn queue_activity(fixture: &mut SessionFixture, name: &str) {
+    let config = ActivityConfig::new(
+        "echo-challenge-v1".to_string(),
+        name.to_string(),
+        serde_json::json!({}),
+    );
+
+    fixture
+        .host
+        .submit_command(DomainCommand::QueueActivity {
+            lobby_id: fixture.lobby_id,
+            config,
+        })
+        .unwrap();
+}
+
+#[test]
+fn test_sync_over_slow_reordering_network() {
+    let mut fixture = SessionFixture::with_network(
+        2,
+        NetworkSimulator::new(42).with_conditions(
+            NetworkConditions::perfect()
+                .with_latency(2)
+                .with_jitter(3)
+                .with_reorder(0.3),
+        ),
+    );
+
+    fixture.tick(50);
+
+    for (i, guest) in fixture.guests.iter_mut().enumerate() {
+        guest
+            .submit_command(DomainCommand::JoinLobby {
+                lobby_id: fixture.lobby_id,
+                guest_name: format!("Guest{}", i + 1),
+            })
+            .unwrap();
+    }
+    fixture.tick(100);
+
+    queue_activity(&mut fixture, "Echo 1");
+    queue_activity(&mut fixture, "Echo 2");
+    fixture.tick(100);
+
+    for guest in &fixture.guests {
+        let lobby = guest.get_lobby().expect("Guest should have lobby");
+        assert_eq!(lobby.participants().len(), 3);
+        assert_eq!(lobby.activity_queue().len(), 2);
+    }
+}
+
+#[test]
+fn test_sync_under_packet_loss() {
+    let mut fixture = SessionFixture::with_network(
+        2,
+        NetworkSimulator::new(7).with_conditions(NetworkConditions::perfect().with_loss(0.2)),
+    );
+
+    fixture.tick(200);
+
+    for (i, guest) in fixture.guests.iter_mut().enumerate() {
+        guest
+            .submit_command(DomainCommand::JoinLobby {
+                lobby_id: fixture.lobby_id,
+                guest_name: format!("Guest{}", i + 1),
+            })
+            .unwrap();
+    }
+    fixture.tick(200);
+    queue_activity(&mut fixture, "Echo 1");
+    fixture.tick(200);
+
+    assert!(fixture.dropped() > 0);
+    assert_eq!(fixture.host.get_lobby().unwrap().participants().len(), 3);
+    for guest in &fixture.guests {
+        let lobby = guest.get_lobby().expect("Guest should have lobby");
+        assert_eq!(lobby.participants().len(), 3);
+        assert_eq!(lobby.activity_queue().len(), 1);
+    }
+}
+
+#[test]
+fn test_partitioned_guest_catches_up_after_heal() {
+    let mut fixture = SessionFixture::new(2);
+
+    fixture.tick(10);
+
+    for (i, guest) in fixture.guests.iter_mut().enumerate() {
+        guest
+            .submit_command(DomainCommand::JoinLobby {
+                lobby_id: fixture.lobby_id,
+                guest_name: format!("Guest{}", i + 1),
+            })
+            .unwrap();
+    }
+    fixture.tick(10);
+
+    fixture.partition_guest(0);
+    queue_activity(&mut fixture, "Missed");
+    fixture.tick(10);
+
+    assert!(
+        fixture.guests[0]
+            .get_lobby()
+            .unwrap()
+            .activity_queue()
+            .is_empty()
+    );
+    assert_eq!(
+        fixture.guests[1]
+            .get_lobby()
+            .unwrap()
+            .activity_queue()
+            .len(),
+        1
+    );
+
+    fixture.heal();
+    queue_activity(&mut fixture, "After heal");
+    fixture.tick(20);
+
+    for guest in &fixture.guests {
+        assert_eq!(guest.get_lobby().unwrap().activity_queue().len(), 2);
+    }
+}
+
+#[test]
+fn test_peer_stats_measure_round_trips() {
+    let mut fixture = SessionFixture::new(2);
+
+    fixture.tick(10);
+
+    let stats = fixture.host.peer_stats();
+    assert_eq!(stats.len(), 2, "Host should track both guests");
+    for peer in &stats {
+        assert_eq!(peer.status, ConnectionStatus::Connected);
+        assert!(
+            peer.rtt.is_some(),
+            "Host should have pinged {}",
+            peer.peer_id
+        );
+        assert!(peer.messages_received > 0);
+    }
+    assert!(
+        fixture.guests[0]
+            .peer_stats()
+            .iter()
+            .all(|peer| peer.rtt.is_some())
+    );
+    assert!(!fixture.host.is_reconnecting());
+
+    // Everyone shares this process's clock
+    assert_eq!(fixture.host.clock_offset_ms(), 0);
+    assert!(fixture.guests[0].clock_offset_ms().abs() < 50);
+}
+
+#[test]
+fn test_host_reports_failed_commands() {
+    let mut fixture = SessionFixture::new(0);
+    let stranger = uuid::Uuid::new_v4();
+
+    fixture
+        .host
+        .submit_command(DomainCommand::ToggleParticipationMode {
+            lobby_id: fixture.lobby_id,
+            participant_id: stranger,
+            requester_id: stranger,
+        })
+        .unwrap();
+    fixture.tick(1);
+
+    let failed = fixture.host.take_failed_commands();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].command.name(), "ToggleParticipationMode");
+    assert_eq!(
+        failed[0].error,
+        CommandError::Lobby(LobbyError::ParticipantNotFound(stranger))
+    );
+    assert!(fixture.host.take_failed_commands().is_empty());
+}
+
+#[test]
+fn test_host_refuses_forged_guest_commands() {
+    let mut fixture = SessionFixture::new(2);
+    fixture.tick(10);
+    fixture.join_all();
+    fixture.tick(20);
+
+    let lobby = fixture.host.get_lobby().unwrap();
+    let host_id = lobby.host_id();
+    let victim = lobby
+        .participants()
+        .values()
+        .find(|p| p.name() == "Guest2")
+        .unwrap()
+        .id();
+
+    // Guest1 kicks in the host's name and replays a sync command
+    let forged = [
+        DomainCommand::KickGuest {
+            lobby_id: fixture.lobby_id,
+            host_id,
+            guest_id: victim,
+            ban: false,
+        },
+        DomainCommand::UpdateParticipantMode {
+            lobby_id: fixture.lobby_id,
+            participant_id: victim,
+            new_mode: ParticipationMode::Spectating,
+        },
+    ];
+    for command in forged {
+        fixture.guests[0].submit_command(command).unwrap();
+    }
+    fixture.tick(10);
+
+    for session in std::iter::once(&fixture.host).chain(&fixture.guests) {
+        let participants = session.get_lobby().unwrap().participants();
+        assert_eq!(
+            participants[&victim].participation_mode(),
+            ParticipationMode::Active
+        );
+    }
+    // The forger hears back, the victim doesn't
+    let refused = fixture.guests[0].take_failed_commands();
+    let kinds: Vec<_> = refused
+        .iter()
+        .map(|f| (f.command.name(), f.error.kind()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("KickGuest", ErrorKind::PermissionDenied),
+            ("UpdateParticipantMode", ErrorKind::PermissionDenied)
+        ]
+    );
+    assert!(fixture.guests[1].take_failed_commands().is_empty());
+    assert_eq!(fixture.host.take_failed_commands().len(), 2);
+}
+
+#[test]
+fn test_events_and_queue_depths() {
+    let mut fixture = SessionFixture::new(1);
+    fixture.tick(10);
+    fixture.host.take_events();
+    fixture.guests[0].take_events();
+
+    fixture.guests[0]
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id: fixture.lobby_id,
+            guest_name: "Guest1".to_string(),
+        })
+        .unwrap();
+    fixture.tick(10);
+
+    let joined = |events: Vec<DomainEvent>| {
+        events
+            .iter()
+            .any(|e| matches!(e, DomainEvent::GuestJoined { .. }))
+    };
+    assert_eq!(fixture.host.queue_depths().recent_events, 1);
+    assert!(joined(fixture.host.take_events()));
+    assert!(joined(fixture.guests[0].take_events()));
+    assert_eq!(fixture.host.queue_depths(), QueueDepths::default());
+}
+
+#[test]
+fn test_subscriptions_see_events_independently() {
+    let mut fixture = SessionFixture::new(1);
+    fixture.tick(10);
+    let roster = fixture.guests[0].subscribe(EventFilter::kinds([EventKind::GuestJoined]));
+    let everything = fixture.guests[0].subscribe(EventFilter::all());
+
+    fixture.guests[0]
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id: fixture.lobby_id,
+            guest_name: "Guest1".to_string(),
+        })
+        .unwrap();
+    fixture.tick(10);
+
+    // Taking the loop's events leaves the subscriptions alone
+    assert!(!fixture.guests[0].take_events().is_empty());
+    let joined = roster.drain();
+    assert_eq!(joined.len(), 1);
+    assert!(everything.drain().contains(&joined[0]));
+    assert!(roster.is_empty());
+}
+
+#[test]
+fn test_ready_check_and_countdown_sync() {
+    let mut fixture = SessionFixture::new(1);
+    fixture.tick(10);
+
+    fixture.guests[0]
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id: fixture.lobby_id,
+            guest_name: "Guest1".to_string(),
+        })
+        .unwrap();
+    fixture.tick(10);
+
+    let lobby = fixture.host.get_lobby().unwrap();
+    let host_id = lobby.host_id();
+    let guest_id = lobby
+        .participants()
+        .values()
+        .find(|p| p.name() == "Guest1")
+        .unwrap()
+        .id();
+
+    fixture.guests[0]
+        .submit_command(DomainCommand::SetReady {
+            lobby_id: fixture.lobby_id,
+            participant_id: guest_id,
+            ready: true,
+        })
+        .unwrap();
+    queue_activity(&mut fixture, "Quiz");
+    fixture.tick(10);
+
+    fixture
+        .host
+        .submit_command(DomainCommand::StartCountdown {
+            lobby_id: fixture.lobby_id,
+            requester_id: host_id,
+            ends_at: 1_000,
+        })
+        .unwrap();
+    fixture.tick(10);
+
+    for lobby in [
+        fixture.host.get_lobby().unwrap(),
+        fixture.guests[0].get_lobby().unwrap(),
+    ] {
+        assert!(lobby.is_ready(guest_id));
+        assert_eq!(lobby.countdown_ends_at(), Some(1_000));
+    }
+}
+
+#[test]
+fn test_late_guest_joins_mid_countdown() {
+    let mut fixture = SessionFixture::new(1);
+    fixture.join_all();
+    fixture.tick(10);
+
+    let lobby = fixture.host.get_lobby().unwrap();
+    let host_id = lobby.host_id();
+    let guest_id = lobby
+        .participants()
+        .values()
+        .find(|p| p.name() == "Guest1")
+        .unwrap()
+        .id();
+
+    fixture.guests[0]
+        .submit_command(DomainCommand::SetReady {
+            lobby_id: fixture.lobby_id,
+            participant_id: guest_id,
+            ready: true,
+        })
+        .unwrap();
+    queue_activity(&mut fixture, "Quiz");
+    fixture.tick(10);
+    fixture
+        .host
+        .submit_command(DomainCommand::StartCountdown {
+            lobby_id: fixture.lobby_id,
+            requester_id: host_id,
+            ends_at: 1_000,
+        })
+        .unwrap();
+    fixture.tick(10);
+
+    // Syncs from the host's snapshot, not from the events it missed
+    let late = fixture.add_guest();
+    fixture.tick(10);
+
+    let lobby = fixture.guests[late].get_lobby().unwrap();
+    assert_eq!(lobby.activity_queue().len(), 1);
+    assert!(lobby.is_ready(guest_id));
+    assert!(!lobby.is_ready(host_id));
+    assert_eq!(lobby.countdown_ends_at(), Some(1_000));
+}
+
+#[test]
+fn test_kicked_guest_learns_about_it() {
+    let mut fixture = SessionFixture::new(2);
+    fixture.tick(10);
+
+    for (i, guest) in fixture.guests.iter_mut().enumerate() {
+        guest
+            .submit_command(DomainCommand::JoinLobby {
+                lobby_id: fixture.lobby_id,
+                guest_name: format!("Guest{}", i + 1),
+            })
+            .unwrap();
+    }
+    fixture.tick(20);
+
+    let lobby = fixture.host.get_lobby().unwrap();
+    let host_id = lobby.host_id();
+    let guest_id = lobby
+        .participants()
+        .values()
+        .find(|p| p.name() == "Guest1")
+        .unwrap()
+        .id();
+    fixture.guests[0].take_events();
+
+    fixture
+        .host
+        .submit_command(DomainCommand::KickGuest {
+            lobby_id: fixture.lobby_id,
+            host_id,
+            guest_id,
+            ban: false,
+        })
+        .unwrap();
+    fixture.tick(10);
+
+    for guest in &fixture.guests {
+        assert!(
+            !guest
+                .get_lobby()
+                .unwrap()
+                .participants()
+                .contains_key(&guest_id)
+        );
+    }
+    assert!(fixture.guests[0].take_events().iter().any(|e| matches!(
+        e,
+        DomainEvent::GuestKicked { participant_id, .. } if *participant_id == guest_id
+    )));
+    assert!(fixture.guests[0].protocol_error().is_none());
+}
+
+#[test]
+fn test_queue_reorder_syncs() {
+    let mut fixture = SessionFixture::new(1);
+    fixture.tick(10);
+
+    fixture.guests[0]
+        .submit_command(DomainCommand::JoinLobby {
+            lobby_id: fixture.lobby_id,
+            guest_name: "Guest1".to_string(),
+        })
+        .unwrap();
+    for name in ["First", "Second", "Third"] {
+        queue_activity(&mut fixture, name);
+    }
+    fixture.tick(20);
+
+    let lobby = fixture.host.get_lobby().unwrap();
+    let host_id = lobby.host_id();
+    let third = lobby.activity_queue()[2].id;
+    let guest_id = lobby
+        .participants()
+        .values()
+        .find(|p| p.name() == "Guest1")
+        .unwrap()
+        .id();
+    let names = |lobby: &konnekt_session_core::Lobby| -> Vec<String> {
+        lobby
+            .activity_queue()
+            .iter()
+            .map(|a| a.name.clone())
+            .collect()
+    };
+
+    fixture
+        .host
+        .submit_command(DomainCommand::MoveQueuedActivity {
+            lobby_id: fixture.lobby_id,
+            activity_id: third,
+            to_index: 0,
+            requester_id: host_id,
+        })
+        .unwrap();
+    fixture.tick(10);
+
+    assert_eq!(
+        names(fixture.guests[0].get_lobby().unwrap()),
+        vec!["Third", "First", "Second"]
+    );
+
+    // Plain guests can't reorder; the host drops their command
+    fixture.guests[0]
+        .submit_command(DomainCommand::MoveQueuedActivity {
+            lobby_id: fixture.lobby_id,
+            activity_id: third,
+            to_index: 2,
+            requester_id: guest_id,
+        })
+        .unwrap();
+    fixture.tick(10);
+
+    let failed = fixture.host.take_failed_commands();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].command.name(), "MoveQueuedActivity");
+    assert_eq!(failed[0].error.kind(), ErrorKind::PermissionDenied);
+    for lobby in [
+        fixture.host.get_lobby().unwrap(),
+        fixture.guests[0].get_lobby().unwrap(),
+    ] {
+        assert_eq!(names(lobby), vec!["Third", "First", "Second"]);
+    }
 }
@@ -1,5 +1,6 @@
 use konnekt_session_p2p::application::ConnectionEvent;
 use konnekt_session_p2p::domain::PeerId;
+use konnekt_session_p2p::{NetworkConditions, NetworkSimulator};
 use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
@@ -27,6 +28,59 @@ pub struct MockNetwork {
 
     /// Connection events (peer connected/disconnected)
     pub events: VecDeque<(PeerId, ConnectionEvent)>,
+
+    /// Link model (latency, jitter, loss, reordering, partitions)
+    pub simulator: NetworkSimulator,
+
+    /// Peer every direct link goes through (star); `None` links all peers
+    /// (mesh)
+    pub hub: Option<PeerId>,
+
+    /// Messages handed to the network so far
+    pub sent: u64,
+
+    /// Bytes handed to the network so far
+    pub sent_bytes: u64,
+}
+
+impl MockNetwork {
+    /// Degrade (or restore) the links for messages sent from now on
+    pub fn set_conditions(&mut self, conditions: NetworkConditions) {
+        self.simulator.set_conditions(conditions);
+    }
+
+    /// Cut every link between the two groups of peers
+    pub fn partition(&mut self, side_a: &[PeerId], side_b: &[PeerId]) {
+        self.simulator.partition(side_a, side_b);
+    }
+
+    /// Remove all partitions
+    pub fn heal(&mut self) {
+        self.simulator.heal();
+    }
+
+    /// Whether `a` and `b` have a direct link
+    pub fn linked(&self, a: PeerId, b: PeerId) -> bool {
+        a != b && self.hub.is_none_or(|hub| a == hub || b == hub)
+    }
+
+    /// Advance network time by one tick, delivering messages that arrived
+    pub fn tick(&mut self) {
+        self.simulator.tick();
+        self.deliver_due();
+    }
+
+    /// Move arrived messages into the receivers' inboxes
+    fn deliver_due(&mut self) {
+        for delivery in self.simulator.take_due() {
+            if let Some(inbox) = self.peers.get(&delivery.to) {
+                inbox
+                    .lock()
+                    .unwrap()
+                    .push_back((delivery.from, delivery.data));
+            }
+        }
+    }
 }
 
 impl MockConnection {
@@ -35,7 +89,7 @@ impl MockConnection {
         let local_id = PeerId::new(matchbox_socket::PeerId(Uuid::new_v4()));
         let inbox = Arc::new(Mutex::new(VecDeque::new()));
 
-        println!("🔌 MockConnection: New peer {} created", local_id);
+        tracing::trace!("🔌 MockConnection: New peer {} created", local_id);
 
         // Register with network
         network
@@ -44,21 +98,25 @@ impl MockConnection {
             .peers
             .insert(local_id, inbox.clone());
 
-        // Notify all existing peers
-        let existing_peers: Vec<PeerId> = network
-            .lock()
-            .unwrap()
-            .peers
-            .keys()
-            .filter(|&&id| id != local_id)
-            .copied()
-            .collect();
+        // Notify all existing peers we have a link to
+        let existing_peers: Vec<PeerId> = {
+            let network = network.lock().unwrap();
+            network
+                .peers
+                .keys()
+                .filter(|&&id| network.linked(local_id, id))
+                .copied()
+                .collect()
+        };
 
-        println!("   ↳ Found {} existing peers", existing_peers.len());
+        tracing::trace!("   ↳ Found {} existing peers", existing_peers.len());
 
         for peer_id in existing_peers {
-            println!("   ↳ Notifying {} about {}", local_id, peer_id);
-            println!("   ↳ Notifying {} about {}", peer_id, local_id);
+            tracing::trace!(
+                "   ↳ Notifying {} and {} about each other",
+                local_id,
+                peer_id
+            );
 
             network
                 .lock()
@@ -87,17 +145,15 @@ impl MockConnection {
 
     /// Get connected peers
     pub fn connected_peers(&self) -> Vec<PeerId> {
-        let peers: Vec<_> = self
-            .network
-            .lock()
-            .unwrap()
+        let network = self.network.lock().unwrap();
+        let peers: Vec<_> = network
             .peers
             .keys()
-            .filter(|&&id| id != self.local_id)
+            .filter(|&&id| network.linked(self.local_id, id))
             .copied()
             .collect();
 
-        println!(
+        tracing::trace!(
             "🔍 Peer {} sees {} connected peers",
             self.local_id,
             peers.len()
@@ -105,7 +161,7 @@ impl MockConnection {
         peers
     }
 
-    /// Send to specific peer (✅ synchronous delivery)
+    /// Send to specific peer (synchronous unless the network is degraded)
     pub fn send_to(&mut self, peer: PeerId, data: Vec<u8>) -> Result<(), String> {
         tracing::trace!(
             "📤 Peer {} → Peer {} ({} bytes)",
@@ -114,10 +170,13 @@ impl MockConnection {
             data.len()
         );
 
-        let network = self.network.lock().unwrap();
+        let mut network = self.network.lock().unwrap();
 
-        if let Some(peer_inbox) = network.peers.get(&peer) {
-            peer_inbox.lock().unwrap().push_back((self.local_id, data));
+        if network.peers.contains_key(&peer) && network.linked(self.local_id, peer) {
+            network.sent += 1;
+            network.sent_bytes += data.len() as u64;
+            network.simulator.send(self.local_id, peer, data);
+            network.deliver_due();
             Ok(())
         } else {
             Err(format!("Peer {} not found", peer))
@@ -168,7 +227,7 @@ impl MockConnection {
         let message_count = inbox.len();
 
         while let Some((from, data)) = inbox.pop_front() {
-            println!(
+            tracing::trace!(
                 "📥 Peer {} ← Peer {} ({} bytes)",
                 self.local_id,
                 from,
@@ -178,7 +237,7 @@ impl MockConnection {
         }
 
         if !events.is_empty() || message_count > 0 {
-            println!(
+            tracing::trace!(
                 "📊 Peer {} polled: {} events ({} messages)",
                 self.local_id,
                 events.len(),
@@ -192,10 +251,19 @@ impl MockConnection {
 
 /// Create a mock network (shared between all peers)
 pub fn create_mock_network() -> Arc<Mutex<MockNetwork>> {
-    println!("🌐 Creating mock network");
+    create_simulated_network(NetworkSimulator::default())
+}
+
+/// Create a mock network whose links follow the given simulator
+pub fn create_simulated_network(simulator: NetworkSimulator) -> Arc<Mutex<MockNetwork>> {
+    tracing::trace!("🌐 Creating mock network");
     Arc::new(Mutex::new(MockNetwork {
         peers: HashMap::new(),
         events: VecDeque::new(),
+        simulator,
+        hub: None,
+        sent: 0,
+        sent_bytes: 0,
     }))
 }
 
@@ -256,4 +324,45 @@ mod tests {
         assert!(!events2.is_empty());
         assert!(!events3.is_empty());
     }
+
+    #[test]
+    fn test_latency_holds_messages_until_tick() {
+        let network = create_simulated_network(
+            NetworkSimulator::new(1).with_conditions(NetworkConditions::perfect().with_latency(1)),
+        );
+
+        let mut peer1 = MockConnection::new(network.clone());
+        let mut peer2 = MockConnection::new(network.clone());
+        peer2.poll_events();
+
+        peer1
+            .send_to(peer2.local_peer_id().unwrap(), b"late".to_vec())
+            .unwrap();
+        assert!(peer2.poll_events().is_empty());
+
+        network.lock().unwrap().tick();
+        assert_eq!(peer2.poll_events().len(), 1);
+    }
+
+    #[test]
+    fn test_star_links_guests_through_the_hub() {
+        let network = create_mock_network();
+
+        let hub = MockConnection::new(network.clone());
+        network.lock().unwrap().hub = hub.local_peer_id();
+        let mut guest1 = MockConnection::new(network.clone());
+        let guest2 = MockConnection::new(network.clone());
+
+        assert_eq!(hub.connected_peers().len(), 2);
+        assert_eq!(guest1.connected_peers(), vec![hub.local_peer_id().unwrap()]);
+        assert!(
+            guest1
+                .send_to(guest2.local_peer_id().unwrap(), b"direct".to_vec())
+                .is_err()
+        );
+
+        guest1.broadcast(b"Broadcast".to_vec()).unwrap();
+        let network = network.lock().unwrap();
+        assert_eq!((network.sent, network.sent_bytes), (1, 9));
+    }
 }
@@ -1,3 +1,5 @@
+#![allow(dead_code)] // Each test binary uses a different subset of the fixture
+
 pub mod mock_connection;
 
 use konnekt_session_core::DomainLoop;
@@ -6,8 +8,11 @@ use konnekt_session_p2p::application::ConnectionEvent;
 use konnekt_session_p2p::domain::PeerId;
 use konnekt_session_p2p::infrastructure::error::{P2PError, Result};
 use konnekt_session_p2p::infrastructure::transport::{NetworkConnection, P2PTransport};
-use mock_connection::{MockConnection, MockNetwork, create_mock_network};
+use konnekt_session_p2p::{NetworkConditions, NetworkSimulator, TestClock, Topology};
+use mock_connection::{MockConnection, MockNetwork, create_simulated_network};
+use std::ops::Range;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 // Implement NetworkConnection for MockConnection
@@ -33,44 +38,200 @@ impl NetworkConnection for MockConnection {
     }
 }
 
+/// What it took a session to settle
+#[derive(Debug, Clone, Copy)]
+pub struct SyncMetrics {
+    /// Host and guests
+    pub peers: usize,
+    pub ticks: usize,
+    /// Messages handed to the network meanwhile
+    pub messages: u64,
+    pub bytes: u64,
+    pub elapsed: Duration,
+}
+
+impl SyncMetrics {
+    /// Messages per second of wall-clock time
+    pub fn throughput(&self) -> f64 {
+        self.messages as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Messages sent per peer on average
+    pub fn messages_per_peer(&self) -> f64 {
+        self.messages as f64 / self.peers as f64
+    }
+}
+
 /// Test fixture for P2P session
 pub struct SessionFixture {
     pub host: SessionLoopV2<MockConnection>,
     pub guests: Vec<SessionLoopV2<MockConnection>>,
     pub lobby_id: Uuid,
-    _network: Arc<Mutex<MockNetwork>>,
+    network: Arc<Mutex<MockNetwork>>,
+    /// Peer IDs (host first, then guests in order)
+    peers: Vec<PeerId>,
+    /// Shared by all peers; stands still, so no pings fire while a slow run
+    /// settles
+    clock: TestClock,
 }
 
 impl SessionFixture {
     /// Create a new test session with host + N guests
     pub fn new(guest_count: usize) -> Self {
-        let network = create_mock_network();
+        Self::with_network(guest_count, NetworkSimulator::default())
+    }
+
+    /// Create a test session whose network follows the given simulator
+    pub fn with_network(guest_count: usize, simulator: NetworkSimulator) -> Self {
+        Self::build(guest_count, simulator, Topology::Mesh)
+    }
+
+    /// Create a test session where guests link to each other directly
+    /// (`Mesh`) or only to the host (`Star`)
+    pub fn with_topology(guest_count: usize, topology: Topology) -> Self {
+        Self::build(guest_count, NetworkSimulator::default(), topology)
+    }
+
+    fn build(guest_count: usize, simulator: NetworkSimulator, topology: Topology) -> Self {
+        let network = create_simulated_network(simulator);
         let lobby_id = Uuid::new_v4();
+        let clock = TestClock::new();
 
-        let host = Self::create_host(network.clone(), lobby_id, "Test Lobby", "Host");
+        let (host, host_peer) =
+            Self::create_host(network.clone(), &clock, lobby_id, "Test Lobby", "Host");
+        let mut peers = vec![host_peer];
+        if topology.is_star() {
+            network.lock().unwrap().hub = Some(host_peer);
+        }
 
         let mut guests = Vec::new();
         for i in 0..guest_count {
-            let guest = Self::create_guest(network.clone(), lobby_id, &format!("Guest{}", i + 1));
+            let (guest, peer) = Self::create_guest(
+                network.clone(),
+                &clock,
+                lobby_id,
+                &format!("Guest{}", i + 1),
+            );
             guests.push(guest);
+            peers.push(peer);
         }
 
         Self {
             host,
             guests,
             lobby_id,
-            _network: network,
+            network,
+            peers,
+            clock,
         }
     }
 
+    /// Degrade (or restore) the network for messages sent from now on
+    pub fn set_conditions(&mut self, conditions: NetworkConditions) {
+        self.network.lock().unwrap().set_conditions(conditions);
+    }
+
+    /// Cut guest `index` off from the host and all other guests
+    pub fn partition_guest(&mut self, index: usize) {
+        let guest = self.peers[index + 1];
+        let others: Vec<_> = self.peers.iter().copied().filter(|p| *p != guest).collect();
+        self.network.lock().unwrap().partition(&[guest], &others);
+    }
+
+    /// Remove all partitions
+    pub fn heal(&mut self) {
+        self.network.lock().unwrap().heal();
+    }
+
+    /// Messages lost to packet loss or partitions so far
+    pub fn dropped(&self) -> u64 {
+        self.network.lock().unwrap().simulator.dropped()
+    }
+
+    /// Connect one more guest, returning its index
+    pub fn add_guest(&mut self) -> usize {
+        let index = self.guests.len();
+        let (guest, peer) = Self::create_guest(
+            self.network.clone(),
+            &self.clock,
+            self.lobby_id,
+            &format!("Guest{}", index + 1),
+        );
+        self.guests.push(guest);
+        self.peers.push(peer);
+        index
+    }
+
+    /// Every guest asks to join as `Guest<n>`
+    pub fn join_all(&mut self) {
+        self.join(0..self.guests.len());
+    }
+
+    /// The guests at `indices` ask to join as `Guest<n>`
+    pub fn join(&mut self, indices: Range<usize>) {
+        for i in indices {
+            self.guests[i]
+                .submit_command(konnekt_session_core::DomainCommand::JoinLobby {
+                    lobby_id: self.lobby_id,
+                    guest_name: format!("Guest{}", i + 1),
+                })
+                .unwrap();
+        }
+    }
+
+    /// Participants in the host's lobby
+    pub fn participant_count(&self) -> usize {
+        self.host
+            .get_lobby()
+            .map_or(0, |lobby| lobby.participants().len())
+    }
+
+    /// Whether every guest holds the same lobby as the host
+    pub fn is_synced(&self) -> bool {
+        let lobby = self.host.get_lobby();
+        self.guests.iter().all(|guest| guest.get_lobby() == lobby)
+    }
+
+    /// Tick until `settled` holds, measuring what it took
+    ///
+    /// `None` if it still doesn't hold after `max_ticks`.
+    pub fn tick_until(
+        &mut self,
+        max_ticks: usize,
+        settled: impl Fn(&Self) -> bool,
+    ) -> Option<SyncMetrics> {
+        let (sent, sent_bytes) = {
+            let network = self.network.lock().unwrap();
+            (network.sent, network.sent_bytes)
+        };
+        let started = Instant::now();
+
+        for ticks in 0..=max_ticks {
+            if settled(self) {
+                let network = self.network.lock().unwrap();
+                return Some(SyncMetrics {
+                    peers: self.peers.len(),
+                    ticks,
+                    messages: network.sent - sent,
+                    bytes: network.sent_bytes - sent_bytes,
+                    elapsed: started.elapsed(),
+                });
+            }
+            self.tick(1);
+        }
+        None
+    }
+
     fn create_host(
         network: Arc<Mutex<MockNetwork>>,
+        clock: &TestClock,
         lobby_id: Uuid,
         lobby_name: &str,
         host_name: &str,
-    ) -> SessionLoopV2<MockConnection> {
+    ) -> (SessionLoopV2<MockConnection>, PeerId) {
         let mock_conn = MockConnection::new(network);
-        let transport = P2PTransport::new_host(mock_conn, 100);
+        let peer = mock_conn.local_peer_id().expect("mock peers have an ID");
+        let transport = P2PTransport::new_host(mock_conn, 100).with_clock(clock.shared());
 
         let mut domain = DomainLoop::new(10, 100);
 
@@ -84,19 +245,21 @@ impl SessionFixture {
         domain.poll();
         domain.drain_events();
 
-        SessionLoopV2::new(domain, transport, true, lobby_id)
+        (SessionLoopV2::new(domain, transport, true, lobby_id), peer)
     }
 
     fn create_guest(
         network: Arc<Mutex<MockNetwork>>,
+        clock: &TestClock,
         lobby_id: Uuid,
         _guest_name: &str,
-    ) -> SessionLoopV2<MockConnection> {
+    ) -> (SessionLoopV2<MockConnection>, PeerId) {
         let mock_conn = MockConnection::new(network);
-        let transport = P2PTransport::new_guest(mock_conn, 100);
+        let peer = mock_conn.local_peer_id().expect("mock peers have an ID");
+        let transport = P2PTransport::new_guest(mock_conn, 100).with_clock(clock.shared());
         let domain = DomainLoop::new(10, 100);
 
-        SessionLoopV2::new(domain, transport, false, lobby_id)
+        (SessionLoopV2::new(domain, transport, false, lobby_id), peer)
     }
 
     /// Poll all peers N times with proper ordering
@@ -111,6 +274,8 @@ impl SessionFixture {
                 guest.poll();
             }
 
+            self.network.lock().unwrap().tick();
+
             if i % 5 == 0 && i > 0 {
                 tracing::trace!("🔄 Tick {}/{}", i, count);
             }
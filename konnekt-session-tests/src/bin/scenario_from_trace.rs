@@ -0,0 +1,35 @@
+//! Print a scenario skeleton for a recorded session trace
+//!
+//! Usage: `scenario-from-trace <trace.jsonl | -> [scenario name]`
+
+use konnekt_session_tests::ScenarioRecorder;
+use std::io::{self, BufReader};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let Some(path) = args.next() else {
+        eprintln!("Usage: scenario-from-trace <trace.jsonl | -> [scenario name]");
+        return ExitCode::FAILURE;
+    };
+    let scenario = args
+        .next()
+        .unwrap_or_else(|| "Recorded session".to_string());
+
+    let recorded = if path == "-" {
+        ScenarioRecorder::from_trace(io::stdin().lock())
+    } else {
+        std::fs::File::open(&path)
+            .and_then(|file| ScenarioRecorder::from_trace(BufReader::new(file)))
+    };
+    match recorded {
+        Ok(recorder) => {
+            print!("{}", recorder.to_feature("Regression", &scenario));
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Failed to read {path}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
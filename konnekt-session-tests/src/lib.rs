@@ -1,17 +1,34 @@
 use bevy_app::App;
 use cucumber::World;
 use konnekt_session_bevy::{SessionCommand, SessionDomain, SessionEventLog, SessionPlugin};
+use konnekt_session_core::domain::{ActivityId, ActivityResult};
 use konnekt_session_core::{
-    DomainCommand, DomainEvent, DomainEventLoop, Lobby, LobbyRole, ParticipationMode,
+    ActivityConfig, ActivityRun, ActivityRunId, Clock, DomainCommand, DomainEvent, DomainEventLoop,
+    Lobby, LobbyRole, ParticipationMode, TestClock,
 };
+use konnekt_session_p2p::domain::DEFAULT_GRACE_PERIOD;
 use std::collections::HashMap;
+use std::time::Duration;
 use uuid::Uuid;
 
+pub mod network;
+pub mod recorder;
+
+pub use network::NetworkScenario;
+pub use recorder::ScenarioRecorder;
+
 #[derive(Debug, World, Default)]
+#[world(init = Self::new)]
 pub struct SessionWorld {
     /// Domain event loop (the system under test)
     pub event_loop: DomainEventLoop,
 
+    /// Time of the domain scenarios; only moves when a step says time passes
+    pub clock: TestClock,
+
+    /// Since when the host is offline (Unix ms on `clock`), if it is
+    pub host_offline_since_ms: Option<u64>,
+
     /// Last command executed (for debugging)
     pub last_command: Option<DomainCommand>,
 
@@ -24,6 +41,12 @@ pub struct SessionWorld {
     /// Track participant IDs by name
     pub participant_ids: HashMap<String, Uuid>,
 
+    /// Track queued activity IDs by name
+    pub activity_ids: HashMap<String, ActivityId>,
+
+    /// Runs started in this scenario, oldest first
+    pub run_ids: Vec<ActivityRunId>,
+
     /// Track errors (also used to temporarily store P2P events as JSON)
     pub last_error: Option<String>,
 
@@ -32,6 +55,9 @@ pub struct SessionWorld {
 
     /// Last who_am_i resolution (Yew identity function)
     pub last_who_am_i: Option<WhoAmIObservation>,
+
+    /// Host + guests on a simulated network
+    pub network: Option<NetworkScenario>,
 }
 
 #[derive(Debug, Clone)]
@@ -45,20 +71,66 @@ pub struct WhoAmIObservation {
 }
 
 impl SessionWorld {
+    pub fn new() -> Self {
+        let clock = TestClock::new();
+        Self {
+            event_loop: DomainEventLoop::new().with_clock(clock.shared()),
+            clock,
+            ..Default::default()
+        }
+    }
+
     /// Execute a command and store the result
-    pub fn execute(&mut self, command: DomainCommand) -> &DomainEvent {
+    ///
+    /// Results are stamped with the time they arrive, as the host does.
+    pub fn execute(&mut self, mut command: DomainCommand) -> &DomainEvent {
+        if let DomainCommand::SubmitResult { result, .. } = &mut command {
+            result.submitted_at_ms = Some(self.clock.unix_millis());
+        }
         self.last_command = Some(command.clone());
         let event = self.event_loop.handle_command(command);
 
         // Extract error if CommandFailed
-        if let DomainEvent::CommandFailed { reason, .. } = &event {
-            self.last_error = Some(reason.clone());
+        if let DomainEvent::CommandFailed { error, .. } = &event {
+            self.last_error = Some(error.to_string());
+        }
+        if let DomainEvent::RunStarted { run_id, .. } = &event {
+            self.run_ids.push(*run_id);
         }
 
         self.last_event = Some(event);
         self.last_event.as_ref().unwrap()
     }
 
+    /// Let time pass: on the simulated network if there is one, otherwise on
+    /// `clock`, handing the host role on once the host was offline too long
+    pub fn pass_time(&mut self, by: Duration) {
+        if let Some(network) = self.network.as_mut() {
+            network.run_for(by);
+            return;
+        }
+        self.clock.advance(by);
+
+        let Some(offline_since) = self.host_offline_since_ms else {
+            return;
+        };
+        if self.clock.unix_millis() - offline_since < DEFAULT_GRACE_PERIOD.as_millis() as u64 {
+            return;
+        }
+        self.host_offline_since_ms = None;
+        let Some(lobby) = self.get_lobby("Test Lobby") else {
+            return;
+        };
+        let (lobby_id, current_host_id) = (lobby.id(), lobby.host_id());
+        if let Some(new_host_id) = lobby.next_host_candidate() {
+            self.execute(DomainCommand::DelegateHost {
+                lobby_id,
+                current_host_id,
+                new_host_id,
+            });
+        }
+    }
+
     /// Get the last event (panics if none)
     pub fn last_event(&self) -> &DomainEvent {
         self.last_event.as_ref().expect("No event executed yet")
@@ -88,6 +160,67 @@ impl SessionWorld {
         self.last_error.as_deref()
     }
 
+    /// ID of the "Test Lobby" most scenarios run in
+    pub fn test_lobby_id(&self) -> Uuid {
+        *self.lobby_ids.get("Test Lobby").expect("No lobby")
+    }
+
+    /// Queue an activity named `name` in the test lobby
+    pub fn queue_activity(&mut self, name: &str) -> &DomainEvent {
+        self.queue_config(ActivityConfig::new(
+            "quiz".to_string(),
+            name.to_string(),
+            serde_json::Value::Null,
+        ))
+    }
+
+    /// Queue `config` in the test lobby, remembering it by name
+    pub fn queue_config(&mut self, config: ActivityConfig) -> &DomainEvent {
+        self.activity_ids.insert(config.name.clone(), config.id);
+        let lobby_id = self.test_lobby_id();
+        self.execute(DomainCommand::QueueActivity { lobby_id, config })
+    }
+
+    /// Get a queued activity ID by name
+    pub fn get_activity_id(&self, name: &str) -> ActivityId {
+        *self
+            .activity_ids
+            .get(name)
+            .unwrap_or_else(|| panic!("Activity '{}' not queued", name))
+    }
+
+    /// The run in progress in the test lobby, if any
+    pub fn current_run(&self) -> Option<&ActivityRun> {
+        let run_id = self.get_lobby("Test Lobby")?.active_run_id()?;
+        self.event_loop.get_run(&run_id)
+    }
+
+    /// The most recently started run (panics if none)
+    pub fn last_run(&self) -> &ActivityRun {
+        let run_id = self.run_ids.last().expect("No run started");
+        self.event_loop.get_run(run_id).expect("Run not found")
+    }
+
+    /// Submit a scored result for `participant` to the most recent run
+    pub fn submit_score(&mut self, participant: &str, score: u32) -> &DomainEvent {
+        let run_id = *self.run_ids.last().expect("No run started");
+        let participant_id = self.get_participant_id(participant);
+        let lobby_id = self.test_lobby_id();
+        self.execute(DomainCommand::SubmitResult {
+            lobby_id,
+            run_id,
+            result: ActivityResult::new(run_id, participant_id).with_score(score),
+        })
+    }
+
+    /// Display names of everyone who joined, for results analytics
+    pub fn participant_names(&self) -> HashMap<Uuid, String> {
+        self.participant_ids
+            .iter()
+            .map(|(name, id)| (*id, name.clone()))
+            .collect()
+    }
+
     // 🆕 Helper for P2P integration tests
     /// Get or create a lobby ID for P2P tests
     pub fn get_or_create_lobby_id(&mut self) -> Uuid {
@@ -136,6 +269,13 @@ impl SessionWorld {
         app.world().resource::<SessionEventLog>().0.clone()
     }
 
+    /// Networked session under test (panics if none)
+    pub fn network(&mut self) -> &mut NetworkScenario {
+        self.network
+            .as_mut()
+            .expect("Networked session not initialized for scenario")
+    }
+
     /// Read lobby participant count from Bevy-backed domain loop.
     pub fn bevy_lobby_participant_count(&self, lobby_id: Uuid) -> usize {
         let app = self
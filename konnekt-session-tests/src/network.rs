@@ -0,0 +1,197 @@
+use konnekt_session_core::DomainCommand;
+use konnekt_session_p2p::{
+    LoopbackConnection, LoopbackNetwork, NetworkConditions, NetworkSimulator, P2PLoopBuilder,
+    PeerId, SessionId, SessionLoop, TestClock,
+};
+use std::fmt;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Seed for scenario networks, so failures reproduce
+pub const NETWORK_SEED: u64 = 42;
+
+/// Virtual time that passes per tick
+pub const TICK: Duration = Duration::from_millis(50);
+
+/// Heartbeats let peers notice a host that went silent
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Host + guests on a simulated network (degraded-network and chaos
+/// scenarios)
+///
+/// All peers share a `TestClock`: time advances by [`TICK`] per tick, so
+/// timeouts of tens of seconds run instantly.
+pub struct NetworkScenario {
+    pub network: LoopbackNetwork,
+    pub host: SessionLoop<LoopbackConnection>,
+    pub guests: Vec<SessionLoop<LoopbackConnection>>,
+    conditions: NetworkConditions,
+    clock: TestClock,
+}
+
+impl NetworkScenario {
+    pub fn new(guest_count: usize) -> Self {
+        let clock = TestClock::new();
+        let network = LoopbackNetwork::with_simulator(NetworkSimulator::new(NETWORK_SEED));
+        let session_id = SessionId::new();
+
+        let (host, _) = P2PLoopBuilder::new()
+            .clock(clock.shared())
+            // Checksum every poll, so lost events are noticed within a few ticks
+            .checksum_interval(Some(Duration::ZERO))
+            .heartbeat_interval(Some(HEARTBEAT_INTERVAL))
+            .build_session_host_with_connection(
+                network.connect(),
+                session_id.clone(),
+                "Network Lobby".to_string(),
+                "Host".to_string(),
+            )
+            .expect("Failed to build host session");
+
+        let guests = (0..guest_count)
+            .map(|_| {
+                P2PLoopBuilder::new()
+                    .clock(clock.shared())
+                    .build_session_guest_with_connection(network.connect(), session_id.clone())
+                    .0
+            })
+            .collect();
+
+        Self {
+            network,
+            host,
+            guests,
+            conditions: NetworkConditions::perfect(),
+            clock,
+        }
+    }
+
+    pub fn lobby_id(&self) -> Uuid {
+        self.host.lobby_id()
+    }
+
+    /// Change link quality for messages sent from now on
+    pub fn degrade(&mut self, update: impl FnOnce(NetworkConditions) -> NetworkConditions) {
+        self.conditions = update(self.conditions);
+        self.network.set_conditions(self.conditions);
+    }
+
+    /// Cut guest `index` (0-based) off from everyone else
+    pub fn partition_guest(&mut self, index: usize) {
+        self.isolate(self.guest_peer(index));
+    }
+
+    /// Cut the original host off from every guest
+    pub fn isolate_host(&mut self) {
+        self.isolate(self.host_peer());
+    }
+
+    /// Drop everything sent between guest `index` (0-based) and the
+    /// original host; both still reach the other guests
+    pub fn cut_link_to_host(&mut self, index: usize) {
+        self.network
+            .partition(&[self.guest_peer(index)], &[self.host_peer()]);
+    }
+
+    pub fn heal(&self) {
+        self.network.heal();
+    }
+
+    /// Poll every peer once per tick, then advance the network and time
+    pub fn run(&mut self, ticks: usize) {
+        for _ in 0..ticks {
+            self.host.poll();
+            for guest in self.guests.iter_mut() {
+                guest.poll();
+            }
+            self.network.tick();
+            self.clock.advance(TICK);
+        }
+    }
+
+    /// Run as many ticks as fit into `duration` of virtual time
+    pub fn run_for(&mut self, duration: Duration) {
+        self.run(duration.as_millis().div_ceil(TICK.as_millis()) as usize);
+    }
+
+    /// Every guest submits `JoinLobby` (as "Guest1", "Guest2", ...)
+    pub fn join_all(&mut self) {
+        let lobby_id = self.lobby_id();
+
+        for (i, guest) in self.guests.iter_mut().enumerate() {
+            guest
+                .submit_command(DomainCommand::JoinLobby {
+                    lobby_id,
+                    guest_name: format!("Guest{}", i + 1),
+                })
+                .expect("Failed to submit JoinLobby");
+        }
+    }
+
+    /// Host switches its own participation mode (a replicated state change)
+    pub fn toggle_host_mode(&mut self) {
+        let lobby_id = self.lobby_id();
+        let host_id = self
+            .host
+            .get_lobby()
+            .expect("Host should have a lobby")
+            .host_id();
+
+        self.host
+            .submit_command(DomainCommand::ToggleParticipationMode {
+                lobby_id,
+                participant_id: host_id,
+                requester_id: host_id,
+            })
+            .expect("Failed to submit ToggleParticipationMode");
+    }
+
+    /// Does guest `index` (0-based) hold the same lobby state as the host?
+    pub fn guest_matches_host(&self, index: usize) -> bool {
+        let expected = self.host.state_checksum();
+        expected.is_some() && self.guests[index].state_checksum() == expected
+    }
+
+    /// Guests (0-based) that took over the host role
+    pub fn promoted_guests(&self) -> Vec<usize> {
+        self.guests
+            .iter()
+            .enumerate()
+            .filter(|(_, guest)| guest.is_host())
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn host_peer(&self) -> PeerId {
+        self.host
+            .local_peer_id()
+            .expect("Loopback peers have an ID")
+    }
+
+    fn guest_peer(&self, index: usize) -> PeerId {
+        self.guests[index]
+            .local_peer_id()
+            .expect("Loopback peers have an ID")
+    }
+
+    /// Partition `peer` from every other peer
+    fn isolate(&self, peer: PeerId) {
+        let others: Vec<_> = std::iter::once(&self.host)
+            .chain(self.guests.iter())
+            .filter_map(|session| session.local_peer_id())
+            .filter(|other| *other != peer)
+            .collect();
+
+        self.network.partition(&[peer], &others);
+    }
+}
+
+impl fmt::Debug for NetworkScenario {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NetworkScenario")
+            .field("guests", &self.guests.len())
+            .field("conditions", &self.conditions)
+            .field("dropped", &self.network.dropped())
+            .finish()
+    }
+}
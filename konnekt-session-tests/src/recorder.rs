@@ -0,0 +1,362 @@
+//! Turn a recorded session trace into a cucumber scenario skeleton
+//!
+//! A trace is what `konnekt-session create-host --output json` prints, one
+//! JSON object per line:
+//!
+//! ```text
+//! konnekt-session create-host --output json | tee session.jsonl
+//! cargo run -p konnekt-session-tests --bin scenario-from-trace -- session.jsonl
+//! ```
+//!
+//! Events become the steps of `tests/features`, anything without a step is
+//! left as a `# TODO` comment to fill in by hand.
+
+use konnekt_session_core::domain::{ActivityId, ActivityResult};
+use konnekt_session_core::{DomainCommand, DomainEvent, RunStatus};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::io::{self, BufRead};
+use uuid::Uuid;
+
+/// Name the steps use for the lobby's host
+const HOST: &str = "Host";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Keyword {
+    Given,
+    When,
+    Then,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Line {
+    Step(Keyword, String),
+    Todo(String),
+}
+
+/// Records a session's commands and events as Gherkin steps
+#[derive(Debug, Default)]
+pub struct ScenarioRecorder {
+    lines: Vec<Line>,
+    /// Guest names by participant ID
+    names: HashMap<Uuid, String>,
+    activities: HashMap<ActivityId, String>,
+    /// Who submitted to the current run so far
+    submitted: HashSet<Uuid>,
+}
+
+impl ScenarioRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record every line of a JSON-lines trace
+    pub fn from_trace(trace: impl BufRead) -> io::Result<Self> {
+        let mut recorder = Self::new();
+        for line in trace.lines() {
+            recorder.record_line(&line?)?;
+        }
+        Ok(recorder)
+    }
+
+    /// Record one driver output line; session and connection lines have no
+    /// steps
+    pub fn record_line(&mut self, line: &str) -> serde_json::Result<()> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(());
+        }
+        let value: serde_json::Value = serde_json::from_str(line)?;
+        if let Some(event) = value.get("domain") {
+            self.record_event(&DomainEvent::deserialize(event)?);
+        } else if let Some(message) = value.get("error") {
+            let message = message.as_str().unwrap_or_default();
+            self.todo(format!("rejected input: {message}"));
+        }
+        Ok(())
+    }
+
+    pub fn record_event(&mut self, event: &DomainEvent) {
+        match event {
+            DomainEvent::LobbyCreated { .. } => {}
+            DomainEvent::GuestJoined { participant, .. } => {
+                let name = participant.name().to_string();
+                self.names.insert(participant.id(), name.clone());
+                self.step(
+                    Keyword::Given,
+                    format!("guest \"{name}\" has joined that lobby"),
+                );
+            }
+            DomainEvent::ParticipationModeChanged { participant_id, .. } => {
+                let step = self.switches_mode(*participant_id);
+                self.step(Keyword::When, step);
+            }
+            DomainEvent::HostDelegated { to, .. } => {
+                let step = format!("the host delegates to \"{}\"", self.name(*to));
+                self.step(Keyword::When, step);
+            }
+            DomainEvent::ActivityQueued { config, .. } => {
+                self.activities.insert(config.id, config.name.clone());
+                self.step(Keyword::When, queues(&config.name));
+            }
+            DomainEvent::QueuedActivityMoved {
+                activity_id,
+                to_index,
+                moved_by,
+                ..
+            } => {
+                let step = self.moves(*moved_by, activity_id, *to_index);
+                self.step(Keyword::When, step);
+            }
+            DomainEvent::RunStarted { .. } => {
+                self.submitted.clear();
+                self.step(Keyword::When, "the host starts the next activity".into())
+            }
+            DomainEvent::ResultSubmitted { result, .. } => self.record_result(result),
+            DomainEvent::SubmitterRemoved { participant_id, .. } => {
+                let step = format!("\"{}\" is removed from the run", self.name(*participant_id));
+                self.step(Keyword::When, step);
+            }
+            DomainEvent::RunEnded {
+                status, results, ..
+            } => {
+                // The last submission ends the run instead of announcing itself
+                let mut last: Vec<_> = results
+                    .iter()
+                    .filter(|result| !self.submitted.contains(&result.participant_id))
+                    .collect();
+                last.sort_by_key(|result| self.name(result.participant_id));
+                for result in last {
+                    self.record_result(result);
+                }
+                if *status == RunStatus::Cancelled {
+                    self.step(Keyword::When, "the host cancels the run".into());
+                }
+                self.step(Keyword::Then, format!("the run should be {:?}", status));
+            }
+            DomainEvent::CommandFailed { command, error } => {
+                match self.command_step(command) {
+                    Some(step) => self.step(Keyword::When, step),
+                    None => self.todo(format!("{} failed", command.name())),
+                }
+                self.step(Keyword::Then, format!("the error should be \"{error}\""));
+            }
+            other => self.todo(format!("{:?}", other.kind())),
+        }
+    }
+
+    /// The recording as a feature file with one scenario
+    pub fn to_feature(&self, feature: &str, scenario: &str) -> String {
+        let mut out = format!("Feature: {feature}\n\n  Scenario: {scenario}\n");
+        let given = Line::Step(Keyword::Given, "a lobby exists with a host".into());
+        let mut previous = None;
+        for line in std::iter::once(&given).chain(&self.lines) {
+            match line {
+                Line::Step(keyword, text) => {
+                    let word = if previous == Some(*keyword) {
+                        "And".to_string()
+                    } else {
+                        format!("{:?}", keyword)
+                    };
+                    let _ = writeln!(out, "    {word} {text}");
+                    previous = Some(*keyword);
+                }
+                Line::Todo(note) => {
+                    let _ = writeln!(out, "    # TODO: {note}");
+                }
+            }
+        }
+        out
+    }
+
+    fn record_result(&mut self, result: &ActivityResult) {
+        self.submitted.insert(result.participant_id);
+        match self.submits(result) {
+            Some(step) => self.step(Keyword::When, step),
+            None => self.todo(format!(
+                "\"{}\" submitted a result without a score",
+                self.name(result.participant_id)
+            )),
+        }
+    }
+
+    fn step(&mut self, keyword: Keyword, text: String) {
+        self.lines.push(Line::Step(keyword, text));
+    }
+
+    fn todo(&mut self, note: String) {
+        self.lines.push(Line::Todo(note));
+    }
+
+    /// Guests by name; the host, and anyone who joined before the trace
+    /// started, as the host
+    fn name(&self, participant_id: Uuid) -> &str {
+        self.names.get(&participant_id).map_or(HOST, String::as_str)
+    }
+
+    /// The step a command takes when it fails
+    fn command_step(&self, command: &DomainCommand) -> Option<String> {
+        match command {
+            DomainCommand::QueueActivity { config, .. } => Some(queues(&config.name)),
+            DomainCommand::MoveQueuedActivity {
+                activity_id,
+                to_index,
+                requester_id,
+                ..
+            } => Some(self.moves(*requester_id, activity_id, *to_index)),
+            DomainCommand::ToggleParticipationMode { participant_id, .. } => {
+                Some(self.switches_mode(*participant_id))
+            }
+            DomainCommand::StartNextRun { .. } => Some("the host starts the next activity".into()),
+            DomainCommand::SubmitResult { result, .. } => self.submits(result),
+            DomainCommand::CancelRun { .. } => Some("the host cancels the run".into()),
+            _ => None,
+        }
+    }
+
+    fn switches_mode(&self, participant_id: Uuid) -> String {
+        format!(
+            "\"{}\" switches participation mode",
+            self.name(participant_id)
+        )
+    }
+
+    fn moves(&self, requester_id: Uuid, activity_id: &ActivityId, to_index: usize) -> String {
+        let activity = self
+            .activities
+            .get(activity_id)
+            .map_or("unknown activity", String::as_str);
+        match self.name(requester_id) {
+            HOST => format!("the host moves \"{activity}\" to position {to_index}"),
+            name => format!("\"{name}\" moves \"{activity}\" to position {to_index}"),
+        }
+    }
+
+    fn submits(&self, result: &ActivityResult) -> Option<String> {
+        let score = result.score?;
+        Some(format!(
+            "\"{}\" submits a score of {score}",
+            self.name(result.participant_id)
+        ))
+    }
+}
+
+fn queues(activity: &str) -> String {
+    format!("the host queues a \"{activity}\" activity")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_core::DomainEventLoop;
+    use konnekt_session_core::domain::ActivityConfig;
+
+    /// Runs commands against a real event loop and records the events
+    struct Session {
+        event_loop: DomainEventLoop,
+        recorder: ScenarioRecorder,
+    }
+
+    impl Session {
+        fn execute(&mut self, command: DomainCommand) -> DomainEvent {
+            let event = self.event_loop.handle_command(command);
+            self.recorder.record_event(&event);
+            event
+        }
+    }
+
+    #[test]
+    fn test_played_activity_becomes_a_scenario() {
+        let mut session = Session {
+            event_loop: DomainEventLoop::new(),
+            recorder: ScenarioRecorder::new(),
+        };
+        let DomainEvent::LobbyCreated { lobby } = session.execute(DomainCommand::CreateLobby {
+            lobby_id: None,
+            lobby_name: "Class 3b".to_string(),
+            host_name: "Teacher".to_string(),
+        }) else {
+            panic!("lobby not created");
+        };
+        let lobby_id = lobby.id();
+        let DomainEvent::GuestJoined { participant, .. } =
+            session.execute(DomainCommand::JoinLobby {
+                lobby_id,
+                guest_name: "Alice".to_string(),
+            })
+        else {
+            panic!("guest not joined");
+        };
+
+        session.execute(DomainCommand::StartNextRun { lobby_id });
+        session.execute(DomainCommand::QueueActivity {
+            lobby_id,
+            config: ActivityConfig::new(
+                "quiz".to_string(),
+                "Trivia Quiz".to_string(),
+                serde_json::Value::Null,
+            ),
+        });
+        let DomainEvent::RunStarted { run_id, .. } =
+            session.execute(DomainCommand::StartNextRun { lobby_id })
+        else {
+            panic!("run not started");
+        };
+        for participant_id in [lobby.host_id(), participant.id()] {
+            session.execute(DomainCommand::SubmitResult {
+                lobby_id,
+                run_id,
+                result: ActivityResult::new(run_id, participant_id).with_score(8),
+            });
+        }
+        session.execute(DomainCommand::SendChatMessage {
+            lobby_id,
+            author_id: participant.id(),
+            text: "gg".to_string(),
+        });
+
+        assert_eq!(
+            session
+                .recorder
+                .to_feature("Bug report", "Alice finishes a quiz"),
+            "Feature: Bug report
+
+  Scenario: Alice finishes a quiz
+    Given a lobby exists with a host
+    And guest \"Alice\" has joined that lobby
+    When the host starts the next activity
+    Then the error should be \"Activity queue is empty\"
+    When the host queues a \"Trivia Quiz\" activity
+    And the host starts the next activity
+    And \"Host\" submits a score of 8
+    And \"Alice\" submits a score of 8
+    Then the run should be Completed
+    # TODO: ChatMessageSent
+"
+        );
+    }
+
+    #[test]
+    fn test_reads_json_driver_output() {
+        let participant = konnekt_session_core::Participant::new_guest("Bob".to_string()).unwrap();
+        let joined = DomainEvent::GuestJoined {
+            lobby_id: Uuid::new_v4(),
+            participant,
+        };
+        let trace = format!(
+            "{}\n{}\n\n{}\n{}\n",
+            r#"{"session": {"session_id": "abc", "is_host": true}}"#,
+            serde_json::json!({ "domain": joined }),
+            r#"{"connection": {"PeerConnected": "peer-1"}}"#,
+            r#"{"error": "Invalid command: expected value", "code": "invalid_input"}"#,
+        );
+
+        let recorder = ScenarioRecorder::from_trace(trace.as_bytes()).unwrap();
+        let feature = recorder.to_feature("Trace", "Bob joins");
+
+        assert!(feature.contains("    And guest \"Bob\" has joined that lobby\n"));
+        assert!(feature.contains("    # TODO: rejected input: Invalid command"));
+        assert!(ScenarioRecorder::from_trace("not json\n".as_bytes()).is_err());
+    }
+}
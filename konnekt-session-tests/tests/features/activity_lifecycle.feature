@@ -1,89 +1,216 @@
 Feature: Activity Lifecycle
   As documented in docs/02-discover.adoc - Activity Management
 
-  Activity state machine: Planned → InProgress → Completed/Cancelled
+  Activities wait in the lobby's queue until the host starts the next one.
+  A run snapshots the active participants as its required submitters.
+  Run state machine: InProgress → Completed/Cancelled
   Only active participants count for completion.
 
   Background:
     Given a lobby exists with a host
-    And guest "Alice" in Active mode
-    And guest "Bob" in Active mode
-    And guest "Carol" in Spectating mode
-
-  Scenario: Plan activity
-    When the host plans a "Trivia Quiz" activity
-    Then the activity should be in Planned status
-    And an ActivityPlanned event should be broadcast
-
-  Scenario: Start activity
-    Given a "Trivia Quiz" activity is planned
-    When the host starts the activity
-    Then the activity status should be InProgress
-    And an ActivityStarted event should be broadcast
-    And the start time should be recorded
-
-  Scenario: Only one activity in progress
+    And guest "Alice" has joined that lobby
+    And guest "Bob" has joined that lobby
+    And guest "Carol" has joined that lobby
+    And "Carol" is spectating
+
+  # ── Queue ──────────────────────────────────────────────────────────────────
+
+  Scenario: Queue an activity
+    When the host queues a "Trivia Quiz" activity
+    Then an ActivityQueued event should be emitted
+    And the activity queue should be "Trivia Quiz"
+
+  Scenario: Reorder the queue
+    Given a "Trivia Quiz" activity is queued
+    And a "Spelling Bee" activity is queued
+    When the host moves "Spelling Bee" to position 0
+    Then the activity queue should be "Spelling Bee, Trivia Quiz"
+
+  Scenario: Guests cannot reorder the queue
+    Given a "Trivia Quiz" activity is queued
+    And a "Spelling Bee" activity is queued
+    When "Alice" moves "Spelling Bee" to position 0
+    Then the error should be "Permission denied"
+    And the activity queue should be "Trivia Quiz, Spelling Bee"
+
+  # ── Start ──────────────────────────────────────────────────────────────────
+
+  Scenario: Start the next activity
+    Given a "Trivia Quiz" activity is queued
+    When the host starts the next activity
+    Then a run of "Trivia Quiz" should be in progress
+    And the activity queue should be empty
+    And the run should wait for "Host, Alice, Bob"
+
+  Scenario: Starting with an empty queue is rejected
+    When the host starts the next activity
+    Then the command should fail
+    And the error should be "Activity queue is empty"
+
+  Scenario: Only one run at a time
     Given a "Trivia Quiz" activity is in progress
-    When the host tries to start another activity
-    Then the start should be rejected
-    And the error should be "Only one activity can be in progress"
+    And a "Spelling Bee" activity is queued
+    When the host starts the next activity
+    Then the error should be "A run is already in progress"
+    And the activity queue should be "Spelling Bee"
+
+  # ── Countdown ──────────────────────────────────────────────────────────────
+
+  Scenario: Count down to the next activity
+    Given a "Trivia Quiz" activity is queued
+    When the host starts a countdown ending in 10 seconds
+    Then a CountdownStarted event should be emitted
+    And the lobby should count down to that deadline
+    When the host starts the next activity
+    Then the countdown should be cleared
+
+  Scenario: A countdown needs a queued activity
+    When the host starts a countdown ending in 10 seconds
+    Then the error should be "Activity queue is empty"
+
+  Scenario: Guests cannot start a countdown
+    Given a "Trivia Quiz" activity is queued
+    When "Alice" starts a countdown ending in 10 seconds
+    Then the error should be "Permission denied"
+
+  # ── Results ────────────────────────────────────────────────────────────────
 
   Scenario: Collect results from active participants
     Given a "Trivia Quiz" activity is in progress
-    When "Alice" submits a result with score 8
-    And "Bob" submits a result with score 9
-    Then 2 results should be recorded
-    But the activity should still be in progress
+    When "Alice" submits a score of 8
+    And "Bob" submits a score of 9
+    Then the run should have 2 results
+    And the run should still be in progress
 
-  Scenario: Activity completion when all active participants submit
+  Scenario: The run completes when every active participant submitted
     Given a "Trivia Quiz" activity is in progress
-    And the host has submitted (score 7)
-    And "Alice" has submitted (score 8)
-    When "Bob" submits (score 9)
-    Then the activity should complete
-    And an ActivityCompleted event should be broadcast
-    And the completion time should be recorded
-    And "Carol" should not be counted
-
-  Scenario: Activity continues when spectator present
+    And "Host" submitted a score of 7
+    And "Alice" submitted a score of 8
+    When "Bob" submits a score of 9
+    Then the run should be Completed
+    And a RunEnded event should carry 3 results
+    And no run should be in progress
+
+  Scenario: Spectators are not asked for results
     Given a "Trivia Quiz" activity is in progress
-    And "Carol" is spectating
-    When all active participants submit
-    Then the activity should complete
+    When "Carol" submits a score of 10
+    Then the command should fail
+    And the error should be "Spectators cannot submit results"
 
-  Scenario: Cancel activity
+  Scenario: A result counts only once
     Given a "Trivia Quiz" activity is in progress
-    When the host cancels the activity
-    Then the activity status should be Cancelled
-    And an ActivityCancelled event should be broadcast
+    And "Alice" submitted a score of 8
+    When "Alice" submits a score of 10
+    Then the error should be "Participant already submitted"
+    And "Alice" should have scored 8 in the run
 
-  Scenario: Remove planned activity
-    Given a "Trivia Quiz" activity is planned
-    When the host removes the activity from queue
-    Then the activity should be removed
-    And an ActivityRemoved event should be broadcast
+  Scenario: Participation mode is locked during a run
+    Given a "Trivia Quiz" activity is in progress
+    When "Alice" switches participation mode
+    Then the error should be "Cannot change participation mode during active activity"
 
-  Scenario: Guest abandons activity mid-game
+  Scenario: A participant who drops out no longer holds up the run
     Given a "Trivia Quiz" activity is in progress
-    And "Alice" toggles to Spectating mid-activity
-    Then an ActivityAbandoned event is recorded for "Alice"
-    And the activity can complete without "Alice"
+    And "Host" submitted a score of 7
+    And "Bob" submitted a score of 9
+    When "Alice" is removed from the run
+    Then the run should be Completed
+
+  # ── Deadlines ──────────────────────────────────────────────────────────────
 
-  Scenario: Guest disconnects during activity
+  Scenario: A timed activity is due its time limit after the host started it
+    Given a "Speed Round" activity with a 30 second time limit is queued
+    When the host starts the next activity
+    Then the run should be due 30000 milliseconds after it started
+
+  Scenario: An untimed activity has no deadline
     Given a "Trivia Quiz" activity is in progress
-    And "Alice" disconnects
+    Then the run should have no deadline
+
+  Scenario: Results up to the deadline count
+    Given a "Speed Round" activity with a 30 second time limit is in progress
     When 30 seconds pass
-    Then "Alice" is removed from active participants
-    And the activity can complete without "Alice"
-
-  Scenario: Leaderboard update after completion
-    Given a "Trivia Quiz" activity has completed
-    When the system calculates scores
-    Then a LeaderboardUpdated event should be broadcast
-    And spectators should be excluded from rankings
-
-  Scenario: Activity timeout
-    Given a "Trivia Quiz" activity started 35 minutes ago
-    When the timeout policy runs
-    Then the activity should be auto-cancelled
-    And partial results should be recorded
+    And "Alice" submits a score of 8
+    Then the run should have 1 result
+
+  Scenario: Late results are rejected
+    Given a "Speed Round" activity with a 30 second time limit is in progress
+    And "Alice" submitted a score of 8
+    When 31 seconds pass
+    And "Bob" submits a score of 9
+    Then the command should fail
+    And the error should be "Deadline passed"
+    And the run should have 1 result
+    And the run should still be in progress
+
+  Scenario: The arrival time counts, not the time the guest claims
+    Given a "Speed Round" activity with a 30 second time limit is in progress
+    When 31 seconds pass
+    And "Bob" submits a score of 9 stamped at the start of the run
+    Then the error should be "Deadline passed"
+
+  Scenario: The host closes a run past its deadline
+    Given a "Speed Round" activity with a 30 second time limit is in progress
+    And "Host" submitted a score of 7
+    And "Alice" submitted a score of 8
+    When 31 seconds pass
+    And the host polls
+    Then the run should be Completed
+    And a RunEnded event should carry 2 results
+    And no run should be in progress
+
+  Scenario: The host keeps a run open until its deadline
+    Given a "Speed Round" activity with a 30 second time limit is in progress
+    When 30 seconds pass
+    And the host polls
+    Then the run should still be in progress
+
+  Scenario: Untimed activities wait as long as it takes
+    Given a "Trivia Quiz" activity is in progress
+    When 600 seconds pass
+    And "Alice" submits a score of 8
+    Then the run should have 1 result
+
+  # ── Cancel ─────────────────────────────────────────────────────────────────
+
+  Scenario: Cancel a run
+    Given a "Trivia Quiz" activity is in progress
+    And "Alice" submitted a score of 8
+    When the host cancels the run
+    Then the run should be Cancelled
+    And a RunEnded event should carry 1 result
+    And no run should be in progress
+
+  Scenario: A finished run takes no more results
+    Given a "Trivia Quiz" activity is in progress
+    When the host cancels the run
+    And "Alice" submits a score of 8
+    Then the error should be "Run is not in progress"
+
+  # ── Rounds ─────────────────────────────────────────────────────────────────
+
+  Scenario: Several rounds in a row
+    Given a "Round 1" activity is queued
+    And a "Round 2" activity is queued
+    When the host starts the next activity
+    And "Host" submits a score of 5
+    And "Alice" submits a score of 8
+    And "Bob" submits a score of 6
+    And the host starts the next activity
+    And "Host" submits a score of 7
+    And "Alice" submits a score of 9
+    And "Bob" submits a score of 4
+    Then the completed rounds should be "Round 1, Round 2"
+    And "Alice" should have a total of 17
+    And "Bob" should have a total of 10
+
+  Scenario: A cancelled round does not count
+    Given a "Round 1" activity is in progress
+    And a "Round 2" activity is queued
+    When the host cancels the run
+    And the host starts the next activity
+    And "Host" submits a score of 5
+    And "Alice" submits a score of 8
+    And "Bob" submits a score of 6
+    Then the completed rounds should be "Round 2"
+    And "Alice" should have a total of 8
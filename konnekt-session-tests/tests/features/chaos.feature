@@ -0,0 +1,60 @@
+Feature: Session recovery under injected faults
+  Host and guests run on a simulated network with virtual time. Faults cut
+  the host off, or drop one guest's link to it, for a given time. Once the
+  network is back every peer must converge on one lobby state - with the
+  original host if it returned within the grace period, with a guest that
+  took over otherwise.
+
+  Background:
+    Given a networked session with 2 guests
+    And every guest has joined the lobby
+
+  # ── Host outage ────────────────────────────────────────────────────────────
+
+  Scenario: The session rides out a short host outage
+    When the host loses connectivity for 10 seconds
+    And the network runs for 40 ticks
+    Then no guest should have taken over as host
+    And every guest should match the host's state
+    And every peer should see 3 participants
+
+  Scenario: Changes made while the host was offline reach every guest
+    When the host goes offline
+    And the host toggles its participation mode 1 time
+    And 5 seconds pass
+    Then some messages should have been dropped
+    And guest 1 should not match the host's state
+    When the network is restored
+    And the network runs for 40 ticks
+    Then every guest should match the host's state
+
+  Scenario: A guest takes over when the host stays away too long
+    When the host loses connectivity for 40 seconds
+    Then a guest should have taken over as host
+    When 5 seconds pass
+    Then the original host should have stepped down
+    And exactly one peer should act as host
+    And every peer should agree on the lobby state
+    And every peer should see 2 participants
+
+  # ── Lossy links ────────────────────────────────────────────────────────────
+
+  Scenario: A guest cut off from the host catches up
+    When messages between Guest1 and the host are dropped
+    And the host toggles its participation mode 1 time
+    Then guest 1 should not match the host's state
+    And guest 2 should match the host's state
+    When the network is restored
+    And the network runs for 40 ticks
+    Then every guest should match the host's state
+
+  # Guest1 also loses the host; whether it is elected to take over depends
+  # on the election order, but the session must end up with one host.
+  Scenario: Peers converge after a link stays down past the grace period
+    When messages between Guest1 and the host are dropped
+    And 40 seconds pass
+    And the network is restored
+    And 5 seconds pass
+    Then exactly one peer should act as host
+    And every peer should agree on the lobby state
+    And every peer should see 2 participants
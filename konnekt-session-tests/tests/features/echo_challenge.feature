@@ -68,7 +68,9 @@ Feature: Echo Challenge Activity
     Given an Echo Challenge with prompt "First" is completed
     When the host plans an Echo Challenge with prompt "Second"
     And the host starts the activity
+    And the host submits response "Second" (score 100)
     And "Alice" submits response "Second"
+    And "Bob" submits response "Second"
     Then the new activity should complete
     And results from "First" should be preserved
 
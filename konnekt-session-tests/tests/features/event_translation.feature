@@ -91,7 +91,7 @@ Feature: Event Translation Between P2P and Core Domains
     And the P2P event should contain mode "Spectating"
 
   Scenario: CommandFailed event does not produce a P2P event
-    Given a core event "CommandFailed" with reason "Invalid state"
+    Given a core event "CommandFailed" with reason "Activity queue is empty"
     When I translate the core event to a P2P event
     Then the translation should return None
   # Roundtrip Translation
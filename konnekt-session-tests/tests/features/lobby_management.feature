@@ -30,7 +30,7 @@ Feature: Lobby Management
     And 3 guests have already joined
     When another guest tries to join
     Then the join should be rejected
-    And the error should be "Lobby full"
+    And the error should be "Lobby is full"
 
   Scenario: Close lobby
     Given a lobby exists with 2 guests
@@ -0,0 +1,35 @@
+Feature: Session sync under degraded network conditions
+  Host and guests run on a simulated network with latency, jitter, packet
+  loss, reordering and partitions. Resync must keep every guest converged
+  on the host's lobby state.
+
+  Background:
+    Given a networked session with 2 guests
+
+  Scenario: Guests join over a slow, reordering network
+    Given 2 ticks of latency
+    And 2 ticks of jitter
+    And 30% reordering
+    When every guest joins the lobby
+    And the network runs for 100 ticks
+    Then every peer should see 3 participants
+
+  Scenario: Lost host updates are recovered
+    Given every guest has joined the lobby
+    And 1 tick of latency
+    And 20% packet loss
+    When the host toggles its participation mode 5 times
+    And the network runs for 200 ticks
+    Then some messages should have been dropped
+    And every guest should match the host's state
+
+  Scenario: Partitioned guest catches up after healing
+    Given every guest has joined the lobby
+    And guest 2 is partitioned from the session
+    When the host toggles its participation mode 1 time
+    And the network runs for 10 ticks
+    Then guest 1 should match the host's state
+    And guest 2 should not match the host's state
+    When the partition heals
+    And the network runs for 20 ticks
+    Then every guest should match the host's state
@@ -16,7 +16,7 @@ Feature: P2P and Core Domain Integration
   Scenario: P2P event is translated to domain command
     Given a GuestJoined event is received from P2P
     When the P2P loop polls
-    Then a JoinLobby command should be queued
+    Then an AddParticipant command should be queued
     And the command should have the correct lobby ID
 
   Scenario: Roundtrip translation preserves data
@@ -39,4 +39,4 @@ Feature: P2P and Core Domain Integration
     Given a core ParticipationModeChanged event
     When the event is broadcast via P2P
     Then peers should receive ParticipationModeChanged
-    And peers should translate to ToggleParticipationMode command
+    And peers should translate to UpdateParticipantMode command
@@ -0,0 +1,353 @@
+use cucumber::{given, then, when};
+use konnekt_session_core::domain::ActivityResult;
+use konnekt_session_core::{
+    ActivityConfig, Clock, DomainCommand, DomainEvent, ResultsAnalytics, RunStatus, Timestamp,
+};
+use konnekt_session_tests::SessionWorld;
+
+/// Split a comma separated list of names from a step
+fn names(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|name| name.trim().to_string())
+        .collect()
+}
+
+// ===== Given Steps =====
+
+#[given(expr = "{string} is spectating")]
+async fn participant_is_spectating(world: &mut SessionWorld, name: String) {
+    switch_participation_mode(world, name).await;
+    assert!(
+        matches!(
+            world.last_event(),
+            DomainEvent::ParticipationModeChanged { .. }
+        ),
+        "Expected ParticipationModeChanged, got {:?}",
+        world.last_event()
+    );
+}
+
+#[given(expr = "a {string} activity is queued")]
+async fn activity_is_queued(world: &mut SessionWorld, name: String) {
+    let event = world.queue_activity(&name);
+    assert!(matches!(event, DomainEvent::ActivityQueued { .. }));
+}
+
+#[given(expr = "a {string} activity is in progress")]
+async fn activity_in_progress(world: &mut SessionWorld, name: String) {
+    activity_is_queued(world, name).await;
+    start_next_activity(world).await;
+    assert!(matches!(world.last_event(), DomainEvent::RunStarted { .. }));
+}
+
+#[given(expr = "a {string} activity with a {int} second time limit is queued")]
+async fn timed_activity_is_queued(world: &mut SessionWorld, name: String, seconds: u64) {
+    let config = ActivityConfig::new(
+        "quiz".to_string(),
+        name,
+        serde_json::json!({ "time_limit_ms": seconds * 1000 }),
+    );
+    let event = world.queue_config(config);
+    assert!(matches!(event, DomainEvent::ActivityQueued { .. }));
+}
+
+#[given(expr = "a {string} activity with a {int} second time limit is in progress")]
+async fn timed_activity_in_progress(world: &mut SessionWorld, name: String, seconds: u64) {
+    timed_activity_is_queued(world, name, seconds).await;
+    start_next_activity(world).await;
+    assert!(matches!(world.last_event(), DomainEvent::RunStarted { .. }));
+}
+
+#[given(expr = "{string} submitted a score of {int}")]
+async fn participant_submitted_score(world: &mut SessionWorld, name: String, score: u32) {
+    let event = world.submit_score(&name, score);
+    assert!(
+        matches!(event, DomainEvent::ResultSubmitted { .. }),
+        "Expected ResultSubmitted, got {:?}",
+        event
+    );
+}
+
+// ===== When Steps =====
+
+#[when(expr = "the host queues a {string} activity")]
+async fn host_queues_activity(world: &mut SessionWorld, name: String) {
+    world.queue_activity(&name);
+}
+
+#[when(expr = "the host moves {string} to position {int}")]
+async fn host_moves_activity(world: &mut SessionWorld, activity: String, to_index: usize) {
+    participant_moves_activity(world, "Host".to_string(), activity, to_index).await;
+}
+
+#[when(expr = "{string} moves {string} to position {int}")]
+async fn participant_moves_activity(
+    world: &mut SessionWorld,
+    name: String,
+    activity: String,
+    to_index: usize,
+) {
+    let cmd = DomainCommand::MoveQueuedActivity {
+        lobby_id: world.test_lobby_id(),
+        activity_id: world.get_activity_id(&activity),
+        to_index,
+        requester_id: world.get_participant_id(&name),
+    };
+    world.execute(cmd);
+}
+
+#[when("the host starts the next activity")]
+async fn start_next_activity(world: &mut SessionWorld) {
+    let lobby_id = world.test_lobby_id();
+    world.execute(DomainCommand::StartNextRun { lobby_id });
+}
+
+#[when(expr = "the host starts a countdown ending in {int} seconds")]
+async fn host_starts_countdown(world: &mut SessionWorld, seconds: u64) {
+    participant_starts_countdown(world, "Host".to_string(), seconds).await;
+}
+
+#[when(expr = "{string} starts a countdown ending in {int} seconds")]
+async fn participant_starts_countdown(world: &mut SessionWorld, name: String, seconds: u64) {
+    let cmd = DomainCommand::StartCountdown {
+        lobby_id: world.test_lobby_id(),
+        requester_id: world.get_participant_id(&name),
+        ends_at: Timestamp::now().as_millis() + seconds * 1000,
+    };
+    world.execute(cmd);
+}
+
+#[when(expr = "{string} submits a score of {int}")]
+async fn participant_submits_score(world: &mut SessionWorld, name: String, score: u32) {
+    world.submit_score(&name, score);
+}
+
+#[when(expr = "{string} submits a score of {int} stamped at the start of the run")]
+async fn participant_submits_backdated_score(world: &mut SessionWorld, name: String, score: u32) {
+    let run = world.last_run();
+    let (run_id, started_at_ms) = (run.id(), run.started_at_ms().expect("Run not stamped"));
+    let cmd = DomainCommand::SubmitResult {
+        lobby_id: world.test_lobby_id(),
+        run_id,
+        result: ActivityResult::new(run_id, world.get_participant_id(&name))
+            .with_score(score)
+            .with_submitted_at(started_at_ms),
+    };
+    world.execute(cmd);
+}
+
+#[when(expr = "{string} switches participation mode")]
+async fn switch_participation_mode(world: &mut SessionWorld, name: String) {
+    let participant_id = world.get_participant_id(&name);
+    let cmd = DomainCommand::ToggleParticipationMode {
+        lobby_id: world.test_lobby_id(),
+        participant_id,
+        requester_id: participant_id,
+    };
+    world.execute(cmd);
+}
+
+#[when(expr = "{string} is removed from the run")]
+async fn participant_removed_from_run(world: &mut SessionWorld, name: String) {
+    let cmd = DomainCommand::RemoveSubmitter {
+        lobby_id: world.test_lobby_id(),
+        run_id: world.last_run().id(),
+        participant_id: world.get_participant_id(&name),
+    };
+    world.execute(cmd);
+}
+
+#[when("the host polls")]
+async fn host_polls(world: &mut SessionWorld) {
+    // The session loop expires a run past its deadline on every host poll
+    let lobby_id = world.test_lobby_id();
+    if let Some(run_id) = world
+        .event_loop
+        .overdue_run(lobby_id, world.clock.unix_millis())
+    {
+        world.execute(DomainCommand::ExpireRun { lobby_id, run_id });
+    }
+}
+
+#[when("the host cancels the run")]
+async fn host_cancels_run(world: &mut SessionWorld) {
+    let cmd = DomainCommand::CancelRun {
+        lobby_id: world.test_lobby_id(),
+        run_id: world.last_run().id(),
+    };
+    world.execute(cmd);
+}
+
+// ===== Then Steps =====
+
+#[then("the command should fail")]
+async fn command_should_fail(world: &mut SessionWorld) {
+    assert!(
+        world.last_command_failed(),
+        "Expected CommandFailed, got {:?}",
+        world.last_event()
+    );
+}
+
+#[then("an ActivityQueued event should be emitted")]
+async fn activity_queued_emitted(world: &mut SessionWorld) {
+    assert!(matches!(
+        world.last_event(),
+        DomainEvent::ActivityQueued { .. }
+    ));
+}
+
+#[then(expr = "the activity queue should be {string}")]
+async fn activity_queue_should_be(world: &mut SessionWorld, expected: String) {
+    let lobby = world.get_lobby("Test Lobby").expect("No lobby");
+    let queue: Vec<String> = lobby
+        .activity_queue()
+        .iter()
+        .map(|config| config.name.clone())
+        .collect();
+    assert_eq!(queue, names(&expected));
+}
+
+#[then("the activity queue should be empty")]
+async fn activity_queue_should_be_empty(world: &mut SessionWorld) {
+    let lobby = world.get_lobby("Test Lobby").expect("No lobby");
+    assert!(lobby.activity_queue().is_empty());
+}
+
+#[then(expr = "a run of {string} should be in progress")]
+async fn run_of_should_be_in_progress(world: &mut SessionWorld, name: String) {
+    let run = world.current_run().expect("No run in progress");
+    assert_eq!(run.config().name, name);
+    assert_eq!(run.config().id, world.get_activity_id(&name));
+    assert_eq!(run.status(), RunStatus::InProgress);
+}
+
+#[then(expr = "the run should wait for {string}")]
+async fn run_should_wait_for(world: &mut SessionWorld, expected: String) {
+    let mut expected: Vec<_> = names(&expected)
+        .iter()
+        .map(|name| world.get_participant_id(name))
+        .collect();
+    let mut required: Vec<_> = world
+        .last_run()
+        .required_submitters()
+        .iter()
+        .copied()
+        .collect();
+    expected.sort();
+    required.sort();
+    assert_eq!(required, expected);
+}
+
+#[then("a CountdownStarted event should be emitted")]
+async fn countdown_started_emitted(world: &mut SessionWorld) {
+    assert!(matches!(
+        world.last_event(),
+        DomainEvent::CountdownStarted { .. }
+    ));
+}
+
+#[then("the lobby should count down to that deadline")]
+async fn lobby_counts_down(world: &mut SessionWorld) {
+    let Some(DomainCommand::StartCountdown { ends_at, .. }) = &world.last_command else {
+        panic!("Expected a StartCountdown command");
+    };
+    let lobby = world.get_lobby("Test Lobby").expect("No lobby");
+    assert_eq!(lobby.countdown_ends_at(), Some(*ends_at));
+}
+
+#[then("the countdown should be cleared")]
+async fn countdown_cleared(world: &mut SessionWorld) {
+    let lobby = world.get_lobby("Test Lobby").expect("No lobby");
+    assert_eq!(lobby.countdown_ends_at(), None);
+}
+
+#[then(expr = "the run should have {int} result(s)")]
+async fn run_should_have_results(world: &mut SessionWorld, count: usize) {
+    assert_eq!(world.last_run().results().len(), count);
+}
+
+#[then(expr = "the run should be due {int} milliseconds after it started")]
+async fn run_due_after(world: &mut SessionWorld, time_limit_ms: u64) {
+    let run = world.last_run();
+    let started_at_ms = run.started_at_ms().expect("Run not stamped");
+    assert_eq!(run.deadline_ms(), Some(started_at_ms + time_limit_ms));
+}
+
+#[then("the run should have no deadline")]
+async fn run_has_no_deadline(world: &mut SessionWorld) {
+    assert_eq!(world.last_run().deadline_ms(), None);
+}
+
+#[then("the run should still be in progress")]
+async fn run_still_in_progress(world: &mut SessionWorld) {
+    assert_eq!(world.last_run().status(), RunStatus::InProgress);
+    assert!(world.current_run().is_some());
+}
+
+#[then(expr = "the run should be {word}")]
+async fn run_should_be(world: &mut SessionWorld, status: String) {
+    let expected = match status.as_str() {
+        "Completed" => RunStatus::Completed,
+        "Cancelled" => RunStatus::Cancelled,
+        "InProgress" => RunStatus::InProgress,
+        other => panic!("Unknown run status '{}'", other),
+    };
+    assert_eq!(world.last_run().status(), expected);
+}
+
+#[then(expr = "a RunEnded event should carry {int} result(s)")]
+async fn run_ended_with_results(world: &mut SessionWorld, count: usize) {
+    match world.last_event() {
+        DomainEvent::RunEnded { results, .. } => assert_eq!(results.len(), count),
+        other => panic!("Expected RunEnded, got {:?}", other),
+    }
+}
+
+#[then("no run should be in progress")]
+async fn no_run_in_progress(world: &mut SessionWorld) {
+    let lobby = world.get_lobby("Test Lobby").expect("No lobby");
+    assert!(!lobby.has_active_run());
+}
+
+#[then(expr = "{string} should have scored {int} in the run")]
+async fn participant_scored(world: &mut SessionWorld, name: String, score: u32) {
+    let participant_id = world.get_participant_id(&name);
+    let result = world
+        .last_run()
+        .results()
+        .get(&participant_id)
+        .unwrap_or_else(|| panic!("No result from '{}'", name));
+    assert_eq!(result.score, Some(score));
+}
+
+#[then(expr = "the completed rounds should be {string}")]
+async fn completed_rounds(world: &mut SessionWorld, expected: String) {
+    let analytics = analytics(world);
+    let rounds: Vec<String> = analytics
+        .progression
+        .rounds
+        .iter()
+        .map(|round| round.name.clone())
+        .collect();
+    assert_eq!(rounds, names(&expected));
+}
+
+#[then(expr = "{string} should have a total of {int}")]
+async fn participant_total(world: &mut SessionWorld, name: String, total: u32) {
+    let analytics = analytics(world);
+    let series = analytics
+        .progression
+        .series
+        .iter()
+        .find(|series| series.participant_name == name)
+        .unwrap_or_else(|| panic!("No scores for '{}'", name));
+    assert_eq!(series.cumulative().last().copied(), Some(total));
+}
+
+fn analytics(world: &SessionWorld) -> ResultsAnalytics {
+    ResultsAnalytics::from_runs(
+        world.event_loop.runs_for_lobby(world.test_lobby_id()),
+        &world.participant_names(),
+    )
+}
@@ -0,0 +1,74 @@
+use cucumber::{then, when};
+use konnekt_session_tests::SessionWorld;
+use std::time::Duration;
+
+// ===== When Steps =====
+
+#[when("the host goes offline")]
+async fn host_goes_offline(world: &mut SessionWorld) {
+    world.network().isolate_host();
+}
+
+#[when(expr = "the host loses connectivity for {int} seconds")]
+async fn host_loses_connectivity(world: &mut SessionWorld, seconds: u64) {
+    let network = world.network();
+    network.isolate_host();
+    network.run_for(Duration::from_secs(seconds));
+    network.heal();
+}
+
+#[when(expr = "messages between Guest{int} and the host are dropped")]
+async fn link_to_host_dropped(world: &mut SessionWorld, guest: usize) {
+    world.network().cut_link_to_host(guest - 1);
+}
+
+#[when("the network is restored")]
+async fn network_restored(world: &mut SessionWorld) {
+    world.network().heal();
+}
+
+// ===== Then Steps =====
+
+#[then("no guest should have taken over as host")]
+async fn no_takeover(world: &mut SessionWorld) {
+    let network = world.network();
+    assert!(network.host.is_host(), "The original host lost its role");
+    assert_eq!(network.promoted_guests(), Vec::<usize>::new());
+}
+
+#[then("a guest should have taken over as host")]
+async fn guest_took_over(world: &mut SessionWorld) {
+    let promoted = world.network().promoted_guests();
+    assert_eq!(promoted.len(), 1, "Guests hosting: {:?}", promoted);
+}
+
+#[then("the original host should have stepped down")]
+async fn host_stepped_down(world: &mut SessionWorld) {
+    assert!(
+        !world.network().host.is_host(),
+        "The original host still acts as host"
+    );
+}
+
+#[then("exactly one peer should act as host")]
+async fn single_host(world: &mut SessionWorld) {
+    let network = world.network();
+    let hosts = network.promoted_guests().len() + usize::from(network.host.is_host());
+    assert_eq!(hosts, 1, "{} peers act as host", hosts);
+}
+
+#[then("every peer should agree on the lobby state")]
+async fn every_peer_agrees(world: &mut SessionWorld) {
+    let network = world.network();
+    let expected = network.host.state_checksum();
+
+    assert!(expected.is_some(), "The original host has no lobby");
+    for (index, guest) in network.guests.iter().enumerate() {
+        assert_eq!(
+            guest.state_checksum(),
+            expected,
+            "Guest {} diverged from the original host",
+            index + 1
+        );
+    }
+}
@@ -1,93 +1,128 @@
 use cucumber::{given, then, when};
-use konnekt_session_core::domain::{ActivityMetadata, ActivityResult, ActivityStatus};
-use konnekt_session_core::{DomainCommand, DomainEvent, EchoChallenge, EchoResult};
+use konnekt_session_core::domain::ActivityResult;
+use konnekt_session_core::{
+    ActivityConfig, DomainCommand, DomainEvent, EchoChallenge, EchoResult, RunStatus,
+};
 use konnekt_session_tests::SessionWorld;
-use uuid::Uuid;
+use std::time::Duration;
 
-// ===== Given Steps =====
-
-#[given(expr = "an Echo Challenge with prompt {string} is planned")]
-async fn echo_challenge_planned(world: &mut SessionWorld, prompt: String) {
-    let lobby_id = *world.lobby_ids.get("Test Lobby").expect("No lobby");
-
-    let challenge = EchoChallenge::new(prompt.clone());
-    let metadata = ActivityMetadata::new(
+/// Plan `challenge` under the name "Echo: <prompt>"
+fn plan_challenge(world: &mut SessionWorld, challenge: EchoChallenge) -> &DomainEvent {
+    let config = ActivityConfig::new(
         EchoChallenge::activity_type().to_string(),
-        format!("Echo: {}", prompt),
+        format!("Echo: {}", challenge.prompt),
         challenge.to_config(),
     );
-    let activity_id = metadata.id;
+    world.queue_config(config)
+}
+
+/// Start the next planned challenge
+fn start_next(world: &mut SessionWorld) {
+    let lobby_id = world.test_lobby_id();
+    let event = world.execute(DomainCommand::StartNextRun { lobby_id });
+    assert!(
+        matches!(event, DomainEvent::RunStarted { .. }),
+        "Expected RunStarted, got {:?}",
+        event
+    );
+}
 
-    let cmd = DomainCommand::PlanActivity { lobby_id, metadata };
+/// Answer the most recent run, scored by the challenge
+fn submit(world: &mut SessionWorld, participant: &str, response: String, time_ms: u64) {
+    let run = world.last_run();
+    let run_id = run.id();
+    let challenge = EchoChallenge::from_config(run.config().config.clone()).unwrap();
+    let score = challenge.calculate_score(&response);
 
+    let cmd = DomainCommand::SubmitResult {
+        lobby_id: world.test_lobby_id(),
+        run_id,
+        result: ActivityResult::new(run_id, world.get_participant_id(participant))
+            .with_data(EchoResult::new(response, time_ms).to_json())
+            .with_score(score)
+            .with_time(time_ms),
+    };
     world.execute(cmd);
-
-    // Store activity ID for later reference
-    world
-        .lobby_ids
-        .insert(format!("Activity:{}", prompt), activity_id);
 }
 
-#[given(expr = "an Echo Challenge with prompt {string} is in progress")]
-async fn echo_challenge_in_progress(world: &mut SessionWorld, prompt: String) {
-    echo_challenge_planned(world, prompt.clone()).await;
+/// The config of the most recently planned activity
+fn planned_config(world: &SessionWorld) -> &ActivityConfig {
+    match world.last_event() {
+        DomainEvent::ActivityQueued { config, .. } => config,
+        other => panic!("Expected ActivityQueued, got {:?}", other),
+    }
+}
 
-    let activity_id = *world
-        .lobby_ids
-        .get(&format!("Activity:{}", prompt))
-        .expect("No activity");
-    let lobby_id = *world.lobby_ids.get("Test Lobby").expect("No lobby");
+// ===== Given Steps =====
 
-    let cmd = DomainCommand::StartActivity {
-        lobby_id,
-        activity_id,
+#[given(expr = "guest {string} in {word} mode")]
+async fn guest_in_mode(world: &mut SessionWorld, guest_name: String, mode: String) {
+    let lobby_id = world.test_lobby_id();
+    let event = world
+        .execute(DomainCommand::JoinLobby {
+            lobby_id,
+            guest_name: guest_name.clone(),
+        })
+        .clone();
+    let DomainEvent::GuestJoined { participant, .. } = event else {
+        panic!("Expected GuestJoined, got {:?}", event);
     };
+    let participant_id = participant.id();
+    world.participant_ids.insert(guest_name, participant_id);
+
+    match mode.as_str() {
+        "Active" => {}
+        "Spectating" => {
+            let event = world.execute(DomainCommand::ToggleParticipationMode {
+                lobby_id,
+                participant_id,
+                requester_id: participant_id,
+            });
+            assert!(
+                matches!(event, DomainEvent::ParticipationModeChanged { .. }),
+                "Expected ParticipationModeChanged, got {:?}",
+                event
+            );
+        }
+        other => panic!("Unknown participation mode '{}'", other),
+    }
+}
 
-    world.execute(cmd);
+#[given(expr = "an Echo Challenge with prompt {string} is planned")]
+async fn echo_challenge_planned(world: &mut SessionWorld, prompt: String) {
+    let event = plan_challenge(world, EchoChallenge::new(prompt));
+    assert!(matches!(event, DomainEvent::ActivityQueued { .. }));
+}
+
+#[given(expr = "an Echo Challenge with prompt {string} is in progress")]
+async fn echo_challenge_in_progress(world: &mut SessionWorld, prompt: String) {
+    echo_challenge_planned(world, prompt).await;
+    start_next(world);
 }
 
 #[given(expr = "an Echo Challenge with prompt {string} is completed")]
 async fn echo_challenge_completed(world: &mut SessionWorld, prompt: String) {
     echo_challenge_in_progress(world, prompt.clone()).await;
 
-    let activity_id = *world
-        .lobby_ids
-        .get(&format!("Activity:{}", prompt))
-        .expect("No activity");
-    let lobby_id = *world.lobby_ids.get("Test Lobby").expect("No lobby");
-    let host_id = *world.participant_ids.get("Host").expect("No host");
-
-    // Submit result to complete
-    let result = EchoResult::new(prompt, 1000);
-    let cmd = DomainCommand::SubmitResult {
-        lobby_id,
-        result: ActivityResult::new(activity_id, host_id)
-            .with_data(result.to_json())
-            .with_score(100)
-            .with_time(1000),
-    };
-
-    world.execute(cmd);
+    let submitters: Vec<String> = world
+        .participant_ids
+        .iter()
+        .filter(|(_, id)| world.last_run().required_submitters().contains(id))
+        .map(|(name, _)| name.clone())
+        .collect();
+    for name in submitters {
+        submit(world, &name, prompt.clone(), 1000);
+    }
+    activity_completes(world).await;
 }
 
 #[given(expr = "an Echo Challenge with prompt {string} and time limit {int}ms is planned")]
 async fn echo_challenge_with_time_limit(world: &mut SessionWorld, prompt: String, time_limit: u64) {
-    let lobby_id = *world.lobby_ids.get("Test Lobby").expect("No lobby");
-
-    let challenge = EchoChallenge::new(prompt.clone()).with_time_limit(time_limit);
-    let metadata = ActivityMetadata::new(
-        EchoChallenge::activity_type().to_string(),
-        format!("Echo: {}", prompt),
-        challenge.to_config(),
+    let event = plan_challenge(
+        world,
+        EchoChallenge::new(prompt).with_time_limit(time_limit),
     );
-    let activity_id = metadata.id;
-
-    let cmd = DomainCommand::PlanActivity { lobby_id, metadata };
-
-    world.execute(cmd);
-    world
-        .lobby_ids
-        .insert(format!("Activity:{}", prompt), activity_id);
+    assert!(matches!(event, DomainEvent::ActivityQueued { .. }));
 }
 
 #[given(expr = "an Echo Challenge with prompt {string}")]
@@ -101,37 +136,17 @@ async fn echo_challenge_exists(world: &mut SessionWorld, prompt: String) {
 
 #[when(expr = "the host plans an Echo Challenge with prompt {string}")]
 async fn plan_echo_challenge(world: &mut SessionWorld, prompt: String) {
-    echo_challenge_planned(world, prompt).await;
+    plan_challenge(world, EchoChallenge::new(prompt));
+}
+
+#[when("the host starts the activity")]
+async fn host_starts_activity(world: &mut SessionWorld) {
+    start_next(world);
 }
 
 #[when(expr = r#"{string} submits response {string}"#)]
 async fn submit_response(world: &mut SessionWorld, participant_name: String, response: String) {
-    let lobby_id = *world.lobby_ids.get("Test Lobby").expect("No lobby");
-    let participant_id = world.get_participant_id(&participant_name);
-
-    // Find the current in-progress activity
-    let lobby = world
-        .event_loop
-        .get_lobby(&lobby_id)
-        .expect("Lobby not found");
-    let activity = lobby.current_activity().expect("No activity in progress");
-    let activity_id = activity.id;
-
-    // Deserialize the challenge to calculate score
-    let challenge = EchoChallenge::from_config(activity.config.clone()).unwrap();
-    let score = challenge.calculate_score(&response);
-
-    let result = EchoResult::new(response, 1000);
-
-    let cmd = DomainCommand::SubmitResult {
-        lobby_id,
-        result: ActivityResult::new(activity_id, participant_id)
-            .with_data(result.to_json())
-            .with_score(score)
-            .with_time(1000),
-    };
-
-    world.execute(cmd);
+    submit(world, &participant_name, response, 1000);
 }
 
 #[when(expr = r#"{string} submits response {string} \(score {int}\)"#)]
@@ -139,9 +154,19 @@ async fn submit_response_with_score(
     world: &mut SessionWorld,
     participant_name: String,
     response: String,
-    _expected_score: u32,
+    expected_score: u32,
 ) {
-    submit_response(world, participant_name, response).await;
+    submit_response(world, participant_name.clone(), response).await;
+    participant_receives_score(world, participant_name, expected_score).await;
+}
+
+#[when(expr = r#"the host submits response {string} \(score {int}\)"#)]
+async fn host_submits_response_with_score(
+    world: &mut SessionWorld,
+    response: String,
+    expected_score: u32,
+) {
+    submit_response_with_score(world, "Host".to_string(), response, expected_score).await;
 }
 
 #[when(expr = r#"{string} submits response {string} after {int} milliseconds"#)]
@@ -151,30 +176,8 @@ async fn submit_response_with_time(
     response: String,
     time_ms: u64,
 ) {
-    let lobby_id = *world.lobby_ids.get("Test Lobby").expect("No lobby");
-    let participant_id = world.get_participant_id(&participant_name);
-
-    let lobby = world
-        .event_loop
-        .get_lobby(&lobby_id)
-        .expect("Lobby not found");
-    let activity = lobby.current_activity().expect("No activity in progress");
-    let activity_id = activity.id;
-
-    let challenge = EchoChallenge::from_config(activity.config.clone()).unwrap();
-    let score = challenge.calculate_score(&response);
-
-    let result = EchoResult::new(response, time_ms);
-
-    let cmd = DomainCommand::SubmitResult {
-        lobby_id,
-        result: ActivityResult::new(activity_id, participant_id)
-            .with_data(result.to_json())
-            .with_score(score)
-            .with_time(time_ms),
-    };
-
-    world.execute(cmd);
+    world.pass_time(Duration::from_millis(time_ms));
+    submit(world, &participant_name, response, time_ms);
 }
 
 #[when(expr = r#"{string} tries to submit response {string}"#)]
@@ -183,7 +186,7 @@ async fn try_submit_response(world: &mut SessionWorld, participant_name: String,
 }
 
 #[when("the activity config is serialized to JSON")]
-async fn serialize_config(world: &mut SessionWorld) {
+async fn serialize_config(_world: &mut SessionWorld) {
     // Challenge already stored in world.last_error as JSON
 }
 
@@ -197,24 +200,26 @@ async fn deserialize_config(world: &mut SessionWorld) {
 
 // ===== Then Steps =====
 
+#[then("the activity should be in Planned status")]
+#[then("the activity should be created")]
+async fn activity_planned(world: &mut SessionWorld) {
+    planned_config(world);
+}
+
+#[then("the activity status should be InProgress")]
+async fn activity_in_progress(world: &mut SessionWorld) {
+    let run = world.current_run().expect("No run in progress");
+    assert_eq!(run.status(), RunStatus::InProgress);
+}
+
 #[then(expr = "the activity type should be {string}")]
 async fn activity_type_is(world: &mut SessionWorld, expected_type: String) {
-    match world.last_event() {
-        DomainEvent::ActivityPlanned { metadata, .. } => {
-            assert_eq!(metadata.activity_type, expected_type);
-        }
-        _ => panic!("Expected ActivityPlanned event"),
-    }
+    assert_eq!(planned_config(world).activity_type, expected_type);
 }
 
 #[then(expr = "the activity name should be {string}")]
 async fn activity_name_is(world: &mut SessionWorld, expected_name: String) {
-    match world.last_event() {
-        DomainEvent::ActivityPlanned { metadata, .. } => {
-            assert_eq!(metadata.name, expected_name);
-        }
-        _ => panic!("Expected ActivityPlanned event"),
-    }
+    assert_eq!(planned_config(world).name, expected_name);
 }
 
 #[then(expr = r#"{string} should receive score {int}"#)]
@@ -223,30 +228,51 @@ async fn participant_receives_score(
     participant_name: String,
     expected_score: u32,
 ) {
-    match world.last_event() {
-        DomainEvent::ResultSubmitted { result, .. } => {
-            let participant_id = world.get_participant_id(&participant_name);
-            assert_eq!(result.participant_id, participant_id);
-            assert_eq!(result.score, Some(expected_score));
-        }
-        _ => panic!("Expected ResultSubmitted event"),
-    }
+    let participant_id = world.get_participant_id(&participant_name);
+    let result = world
+        .last_run()
+        .results()
+        .get(&participant_id)
+        .unwrap_or_else(|| panic!("No result from '{}'", participant_name));
+    assert_eq!(result.score, Some(expected_score));
 }
 
 #[then("the result should be recorded")]
-async fn result_recorded(world: &mut SessionWorld) {
-    assert!(matches!(
-        world.last_event(),
-        DomainEvent::ResultSubmitted { .. }
-    ));
-}
-
 #[then("the result should be accepted")]
 async fn result_accepted(world: &mut SessionWorld) {
-    assert!(matches!(
-        world.last_event(),
-        DomainEvent::ResultSubmitted { .. }
-    ));
+    assert!(
+        matches!(
+            world.last_event(),
+            DomainEvent::ResultSubmitted { .. } | DomainEvent::RunEnded { .. }
+        ),
+        "Expected the result to be accepted, got {:?}",
+        world.last_event()
+    );
+}
+
+#[then("the submission should be rejected")]
+async fn submission_rejected(world: &mut SessionWorld) {
+    assert!(
+        world.last_command_failed(),
+        "Expected the submission to be rejected, got {:?}",
+        world.last_event()
+    );
+}
+
+#[then("the activity should complete")]
+#[then("the new activity should complete")]
+async fn activity_completes(world: &mut SessionWorld) {
+    assert!(
+        matches!(world.last_event(), DomainEvent::RunEnded { .. }),
+        "Expected RunEnded, got {:?}",
+        world.last_event()
+    );
+    assert_eq!(world.last_run().status(), RunStatus::Completed);
+}
+
+#[then(expr = "{int} results should be recorded")]
+async fn results_recorded(world: &mut SessionWorld, count: usize) {
+    assert_eq!(world.last_run().results().len(), count);
 }
 
 #[then(expr = "the result should record time {int} milliseconds")]
@@ -255,50 +281,37 @@ async fn result_records_time(world: &mut SessionWorld, expected_time: u64) {
         DomainEvent::ResultSubmitted { result, .. } => {
             assert_eq!(result.time_taken_ms, Some(expected_time));
         }
-        _ => panic!("Expected ResultSubmitted event"),
+        other => panic!("Expected ResultSubmitted, got {:?}", other),
     }
 }
 
 #[then(expr = r#"results from {string} should be preserved"#)]
 async fn results_preserved(world: &mut SessionWorld, activity_prompt: String) {
-    let lobby_id = *world.lobby_ids.get("Test Lobby").expect("No lobby");
-    let lobby = world
+    let name = format!("Echo: {}", activity_prompt);
+    let run = world
         .event_loop
-        .get_lobby(&lobby_id)
-        .expect("Lobby not found");
+        .runs_for_lobby(world.test_lobby_id())
+        .find(|run| run.config().name == name)
+        .unwrap_or_else(|| panic!("No run of '{}'", name));
 
-    let activity_id = *world
-        .lobby_ids
-        .get(&format!("Activity:{}", activity_prompt))
-        .expect("No activity");
-
-    let results = lobby.get_results(activity_id);
     assert!(
-        !results.is_empty(),
+        !run.results().is_empty(),
         "Results from '{}' were not preserved",
         activity_prompt
     );
 }
 
-#[then("the new activity should complete")]
-async fn new_activity_completes(world: &mut SessionWorld) {
-    assert!(matches!(
-        world.last_event(),
-        DomainEvent::ActivityCompleted { .. }
-    ));
-}
-
 #[then(expr = r#"the prompt should be {string}"#)]
 async fn prompt_is(world: &mut SessionWorld, expected_prompt: String) {
-    let json = world.last_error.as_ref().expect("No challenge data");
-    let challenge: EchoChallenge = serde_json::from_str(json).unwrap();
+    // Either a challenge that was just planned, or one round-tripped through JSON
+    let challenge = match world.last_event.as_ref() {
+        Some(DomainEvent::ActivityQueued { config, .. }) => {
+            EchoChallenge::from_config(config.config.clone()).unwrap()
+        }
+        _ => {
+            let json = world.last_error.as_ref().expect("No challenge data");
+            serde_json::from_str(json).unwrap()
+        }
+    };
     assert_eq!(challenge.prompt, expected_prompt);
 }
-
-#[then("the activity should be created")]
-async fn activity_created(world: &mut SessionWorld) {
-    assert!(matches!(
-        world.last_event(),
-        DomainEvent::ActivityPlanned { .. }
-    ));
-}
@@ -1,6 +1,7 @@
 use cucumber::{given, then, when};
 use konnekt_session_core::{
     DomainCommand, DomainEvent as CoreDomainEvent, Lobby, Participant, ParticipationMode,
+    domain::LobbyError,
 };
 use konnekt_session_p2p::application::EventTranslator;
 use konnekt_session_p2p::domain::DomainEvent as P2PDomainEvent;
@@ -109,6 +110,7 @@ async fn p2p_event_guest_kicked(
     world.current_p2p_event = Some(P2PDomainEvent::GuestKicked {
         participant_id: guest_id,
         kicked_by: host_id,
+        banned: false,
     });
 }
 
@@ -203,6 +205,7 @@ async fn core_event_guest_kicked(
         lobby_id: world.lobby_id,
         participant_id: guest_id,
         kicked_by: host_id,
+        banned: false,
     });
 }
 
@@ -256,10 +259,15 @@ async fn core_event_command_failed(
 ) {
     assert_eq!(event_type, "CommandFailed");
 
-    world.current_core_event = Some(CoreDomainEvent::CommandFailed {
-        command: "Test".to_string(),
-        reason,
-    });
+    let error = LobbyError::EmptyQueue;
+    assert_eq!(error.to_string(), reason);
+
+    world.current_core_event = Some(CoreDomainEvent::command_failed(
+        DomainCommand::StartNextRun {
+            lobby_id: world.lobby_id,
+        },
+        error,
+    ));
 }
 
 // ===== When Steps =====
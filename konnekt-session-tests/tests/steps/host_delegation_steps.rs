@@ -1,6 +1,7 @@
 use cucumber::{given, then, when};
-use konnekt_session_core::{DomainCommand, DomainEvent, LobbyRole, Participant, Timestamp};
+use konnekt_session_core::{Clock, DomainCommand, DomainEvent};
 use konnekt_session_tests::SessionWorld;
+use std::time::Duration;
 
 // ===== Given Steps =====
 
@@ -35,7 +36,8 @@ async fn lobby_exists_with_named_host(world: &mut SessionWorld, host_name: Strin
 
 #[given(expr = "guest {string} joined {int} seconds ago")]
 async fn guest_joined_seconds_ago(world: &mut SessionWorld, name: String, _seconds_ago: u64) {
-    // For now, just join normally - timestamp ordering will be handled by creation order
+    // Guests are listed oldest first; a second apart keeps the oldest unambiguous
+    world.pass_time(Duration::from_secs(1));
     let lobby_id = *world.lobby_ids.get("Test Lobby").expect("No lobby");
 
     let cmd = DomainCommand::JoinLobby {
@@ -57,14 +59,20 @@ async fn guest_joined_at_time(world: &mut SessionWorld, name: String, _time: u64
 }
 
 #[given("an activity is in progress")]
-async fn activity_in_progress(_world: &mut SessionWorld) {
-    // TODO: Implement when activity management is ready
+async fn activity_in_progress(world: &mut SessionWorld) {
+    if world.get_lobby("Test Lobby").is_none() {
+        lobby_exists_with_default_host(world).await;
+    }
+    world.queue_activity("Quiz");
+    let lobby_id = world.test_lobby_id();
+    let event = world.execute(DomainCommand::StartNextRun { lobby_id });
+    assert!(matches!(event, DomainEvent::RunStarted { .. }));
 }
 
 #[given("the host disconnects at time T")]
 #[given("the host disconnects")]
-async fn host_disconnects(_world: &mut SessionWorld) {
-    // TODO: Implement when P2P layer is ready
+async fn host_disconnects(world: &mut SessionWorld) {
+    world.host_offline_since_ms = Some(world.clock.unix_millis());
 }
 
 #[given(expr = "a lobby with only the host and {int} guest(s)")]
@@ -112,23 +120,19 @@ async fn host_delegates_to(world: &mut SessionWorld, guest_name: String) {
 
 #[when(expr = "{int} seconds pass")]
 #[when(expr = "{int} second passes")]
-async fn seconds_pass(_world: &mut SessionWorld, _seconds: u64) {
-    // TODO: Implement time-based delegation when P2P layer is ready
-}
-
 #[when(expr = "{int} more seconds pass")]
-async fn more_seconds_pass(_world: &mut SessionWorld, _seconds: u64) {
-    // TODO: Implement time-based delegation when P2P layer is ready
+async fn seconds_pass(world: &mut SessionWorld, seconds: u64) {
+    world.pass_time(Duration::from_secs(seconds));
 }
 
 #[when("the host reconnects")]
-async fn host_reconnects(_world: &mut SessionWorld) {
-    // TODO: Implement reconnection logic when P2P layer is ready
+async fn host_reconnects(world: &mut SessionWorld) {
+    world.host_offline_since_ms = None;
 }
 
 #[when("the 30s timeout expires")]
-async fn timeout_expires(_world: &mut SessionWorld) {
-    // TODO: Implement auto-delegation when P2P layer handles timeouts
+async fn timeout_expires(world: &mut SessionWorld) {
+    seconds_pass(world, 30).await;
 }
 
 // ===== Then Steps =====
@@ -173,13 +177,19 @@ async fn host_marked_as(_world: &mut SessionWorld, _status: String) {
 }
 
 #[then("the host should retain their role")]
-async fn host_retains_role(_world: &mut SessionWorld) {
-    // TODO: Implement when reconnection logic is added
+async fn host_retains_role(world: &mut SessionWorld) {
+    let host_id = world.get_participant_id("Host");
+    let lobby = world.get_lobby("Test Lobby").expect("No lobby");
+    assert_eq!(lobby.host_id(), host_id);
 }
 
 #[then("no delegation should occur")]
-async fn no_delegation_occurs(_world: &mut SessionWorld) {
-    // TODO: Implement when reconnection logic is added
+async fn no_delegation_occurs(world: &mut SessionWorld) {
+    assert!(
+        !matches!(world.last_event, Some(DomainEvent::HostDelegated { .. })),
+        "Unexpected {:?}",
+        world.last_event
+    );
 }
 
 #[then(expr = "the original host should rejoin as a guest")]
@@ -237,8 +247,8 @@ async fn can_manage_activity(world: &mut SessionWorld, name: String) {
 }
 
 #[then("the activity should continue")]
-async fn activity_continues(_world: &mut SessionWorld) {
-    // TODO: Implement when activity management is ready
+async fn activity_continues(world: &mut SessionWorld) {
+    assert!(world.current_run().is_some(), "The run ended with the host");
 }
 
 #[then("the new host can manually delegate back")]
@@ -1,5 +1,5 @@
 use cucumber::{given, then, when};
-use konnekt_session_core::{DomainCommand, DomainEvent, ParticipationMode};
+use konnekt_session_core::{DomainCommand, DomainEvent, LobbySettings, ParticipationMode};
 use konnekt_session_tests::SessionWorld;
 
 // ===== Given Steps =====
@@ -37,10 +37,21 @@ async fn lobby_exists(world: &mut SessionWorld) {
 }
 
 #[given(expr = "a lobby exists with max {int} guests")]
-async fn lobby_exists_with_max_guests(world: &mut SessionWorld, _max_guests: usize) {
-    // TODO: Implement when lobby capacity is configurable
-    // For now, create a standard lobby
+async fn lobby_exists_with_max_guests(world: &mut SessionWorld, max_guests: usize) {
     lobby_exists(world).await;
+
+    let settings = LobbySettings {
+        // The host counts too
+        max_participants: Some(max_guests + 1),
+        ..LobbySettings::default()
+    };
+    let cmd = DomainCommand::UpdateLobbySettings {
+        lobby_id: world.test_lobby_id(),
+        host_id: world.get_participant_id("Host"),
+        settings,
+    };
+    let event = world.execute(cmd);
+    assert!(matches!(event, DomainEvent::LobbySettingsChanged { .. }));
 }
 
 #[given(expr = "{int} guests have already joined")]
@@ -1,8 +1,11 @@
+mod activity_lifecycle_steps;
 mod bevy_application_steps;
+mod chaos_steps;
 mod echo_challenge_steps;
 mod event_translation_steps;
 mod host_delegation_steps;
 mod lobby_management_steps;
+mod network_conditions_steps;
 mod p2p_integration_steps;
 mod peer_participant_mapping_steps;
 mod who_am_i_steps;
@@ -0,0 +1,131 @@
+use cucumber::{given, then, when};
+use konnekt_session_tests::{NetworkScenario, SessionWorld};
+
+// ===== Given Steps =====
+
+#[given(expr = "a networked session with {int} guest(s)")]
+async fn networked_session(world: &mut SessionWorld, guests: usize) {
+    let mut scenario = NetworkScenario::new(guests);
+    scenario.run(10);
+    world.network = Some(scenario);
+}
+
+#[given("every guest has joined the lobby")]
+async fn every_guest_joined(world: &mut SessionWorld) {
+    let network = world.network();
+    network.join_all();
+    network.run(20);
+}
+
+#[given(expr = "{int}% packet loss")]
+async fn packet_loss(world: &mut SessionWorld, percent: u32) {
+    world
+        .network()
+        .degrade(|c| c.with_loss(percent as f64 / 100.0));
+}
+
+#[given(expr = "{int} tick(s) of latency")]
+async fn latency(world: &mut SessionWorld, ticks: u32) {
+    world.network().degrade(|c| c.with_latency(ticks));
+}
+
+#[given(expr = "{int} tick(s) of jitter")]
+async fn jitter(world: &mut SessionWorld, ticks: u32) {
+    world.network().degrade(|c| c.with_jitter(ticks));
+}
+
+#[given(expr = "{int}% reordering")]
+async fn reordering(world: &mut SessionWorld, percent: u32) {
+    world
+        .network()
+        .degrade(|c| c.with_reorder(percent as f64 / 100.0));
+}
+
+#[given(expr = "guest {int} is partitioned from the session")]
+async fn guest_partitioned(world: &mut SessionWorld, guest: usize) {
+    world.network().partition_guest(guest - 1);
+}
+
+// ===== When Steps =====
+
+#[when("every guest joins the lobby")]
+async fn every_guest_joins(world: &mut SessionWorld) {
+    world.network().join_all();
+}
+
+#[when(expr = "the host toggles its participation mode {int} time(s)")]
+async fn host_toggles_mode(world: &mut SessionWorld, times: usize) {
+    let network = world.network();
+    for _ in 0..times {
+        network.toggle_host_mode();
+        network.run(5);
+    }
+}
+
+#[when(expr = "the network runs for {int} ticks")]
+async fn network_runs(world: &mut SessionWorld, ticks: usize) {
+    world.network().run(ticks);
+}
+
+#[when("the partition heals")]
+async fn partition_heals(world: &mut SessionWorld) {
+    world.network().heal();
+}
+
+// ===== Then Steps =====
+
+#[then(expr = "every peer should see {int} participants")]
+async fn every_peer_sees(world: &mut SessionWorld, expected: usize) {
+    let network = world.network();
+
+    for (name, session) in std::iter::once(("host".to_string(), &network.host)).chain(
+        network
+            .guests
+            .iter()
+            .enumerate()
+            .map(|(i, guest)| (format!("guest {}", i + 1), guest)),
+    ) {
+        let count = session
+            .get_lobby()
+            .unwrap_or_else(|| panic!("{} should have a lobby", name))
+            .participants()
+            .len();
+        assert_eq!(count, expected, "{} sees {} participants", name, count);
+    }
+}
+
+#[then("some messages should have been dropped")]
+async fn messages_dropped(world: &mut SessionWorld) {
+    assert!(world.network().network.dropped() > 0, "No message was lost");
+}
+
+#[then("every guest should match the host's state")]
+async fn every_guest_matches(world: &mut SessionWorld) {
+    let network = world.network();
+
+    for index in 0..network.guests.len() {
+        assert!(
+            network.guest_matches_host(index),
+            "Guest {} diverged from the host",
+            index + 1
+        );
+    }
+}
+
+#[then(expr = "guest {int} should match the host's state")]
+async fn guest_matches(world: &mut SessionWorld, guest: usize) {
+    assert!(
+        world.network().guest_matches_host(guest - 1),
+        "Guest {} diverged from the host",
+        guest
+    );
+}
+
+#[then(expr = "guest {int} should not match the host's state")]
+async fn guest_diverged(world: &mut SessionWorld, guest: usize) {
+    assert!(
+        !world.network().guest_matches_host(guest - 1),
+        "Guest {} unexpectedly matches the host",
+        guest
+    );
+}
@@ -1,5 +1,7 @@
 use cucumber::{given, then, when};
-use konnekt_session_core::{DomainCommand, DomainEvent as CoreDomainEvent, Participant};
+use konnekt_session_core::{
+    DomainCommand, DomainEvent as CoreDomainEvent, Participant, domain::LobbyError,
+};
 use konnekt_session_p2p::{DomainEvent as P2PDomainEvent, EventTranslator};
 use konnekt_session_tests::SessionWorld;
 use uuid::Uuid;
@@ -45,10 +47,11 @@ async fn core_guest_joined_for(world: &mut SessionWorld, name: String) {
 
 #[given("the core domain emits a CommandFailed event")]
 async fn core_emits_command_failed(world: &mut SessionWorld) {
-    world.last_event = Some(CoreDomainEvent::CommandFailed {
-        command: "TestCommand".to_string(),
-        reason: "Test error".to_string(),
-    });
+    let lobby_id = world.get_or_create_lobby_id();
+    world.last_event = Some(CoreDomainEvent::command_failed(
+        DomainCommand::StartNextRun { lobby_id },
+        LobbyError::EmptyQueue,
+    ));
 }
 
 #[given("a core HostDelegated event")]
@@ -124,8 +127,9 @@ async fn roundtrip_translation(world: &mut SessionWorld) {
 
 #[when("the event is broadcast via P2P")]
 async fn event_broadcast_via_p2p(world: &mut SessionWorld) {
-    // Same as "processes the event"
+    // Same as "processes the event", then what receiving peers apply
     p2p_processes_event(world).await;
+    p2p_loop_polls(world).await;
 }
 
 // ===== Then Steps =====
@@ -142,35 +146,32 @@ async fn event_has_sequence_number(_world: &mut SessionWorld) {
     // This is tested in P2P layer unit tests
 }
 
-#[then("a JoinLobby command should be queued")]
-async fn join_lobby_command_queued(world: &mut SessionWorld) {
+#[then("an AddParticipant command should be queued")]
+async fn add_participant_command_queued(world: &mut SessionWorld) {
     assert!(world.last_command.is_some(), "No command was translated");
 
+    // Peers add the participant the host already admitted
     match world.last_command.as_ref().unwrap() {
-        DomainCommand::JoinLobby { .. } => {}
-        other => panic!("Expected JoinLobby, got: {:?}", other),
+        DomainCommand::AddParticipant { .. } => {}
+        other => panic!("Expected AddParticipant, got: {:?}", other),
     }
 }
 
 #[then("the command should have the correct lobby ID")]
 async fn command_has_correct_lobby_id(world: &mut SessionWorld) {
     let expected_lobby_id = world.get_or_create_lobby_id();
+    let command = world.last_command.as_ref().expect("No command was translated");
 
-    match world.last_command.as_ref().unwrap() {
-        DomainCommand::JoinLobby { lobby_id, .. } => {
-            assert_eq!(*lobby_id, expected_lobby_id);
-        }
-        _ => panic!("Expected JoinLobby command"),
-    }
+    assert_eq!(command.lobby_id(), Some(expected_lobby_id));
 }
 
 #[then(expr = "the resulting command should contain {string}")]
 async fn command_contains_name(world: &mut SessionWorld, name: String) {
     match world.last_command.as_ref().unwrap() {
-        DomainCommand::JoinLobby { guest_name, .. } => {
-            assert_eq!(guest_name, &name);
+        DomainCommand::AddParticipant { participant, .. } => {
+            assert_eq!(participant.name(), name);
         }
-        _ => panic!("Expected JoinLobby command"),
+        other => panic!("Expected AddParticipant command, got: {:?}", other),
     }
 }
 
@@ -217,10 +218,11 @@ async fn peers_receive_participation_mode_changed(world: &mut SessionWorld) {
     }
 }
 
-#[then("peers should translate to ToggleParticipationMode command")]
-async fn peers_translate_to_toggle_participation(world: &mut SessionWorld) {
+#[then("peers should translate to UpdateParticipantMode command")]
+async fn peers_translate_to_update_participant_mode(world: &mut SessionWorld) {
+    // Peers set the mode the host decided instead of toggling it again
     match world.last_command.as_ref() {
-        Some(DomainCommand::ToggleParticipationMode { .. }) => {}
-        other => panic!("Expected ToggleParticipationMode command, got: {:?}", other),
+        Some(DomainCommand::UpdateParticipantMode { .. }) => {}
+        other => panic!("Expected UpdateParticipantMode command, got: {:?}", other),
     }
 }
@@ -1,9 +1,7 @@
 use cucumber::{given, then, when};
 use konnekt_session_core::{DomainCommand, DomainEvent, LobbyRole, ParticipationMode};
-use konnekt_session_p2p::SessionId;
 use konnekt_session_tests::{SessionWorld, WhoAmIObservation};
 use konnekt_session_yew::hooks::{P2PRole, SessionContext};
-use std::rc::Rc;
 
 #[given(expr = "a lobby named {string} with host {string}")]
 async fn lobby_named_with_host(world: &mut SessionWorld, lobby_name: String, host_name: String) {
@@ -68,15 +66,13 @@ async fn resolve_who_am_i_for(
     };
 
     let ctx = SessionContext {
-        session_id: SessionId::new(),
         lobby: Some(lobby),
         peer_count: 1,
         is_host,
-        active_run: None,
         local_participant_id: Some(participant_id),
         local_peer_id: Some(peer_id),
-        send_command: Rc::new(|_| {}),
         local_participant_name: None, // explicit: identity should not rely on name tracking
+        ..SessionContext::default()
     };
 
     let info = ctx.who_am_i_info();
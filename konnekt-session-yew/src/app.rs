@@ -1,5 +1,7 @@
+use crate::components::{SessionErrorBoundary, session_id_from_path};
+use crate::hooks::use_identity;
 use crate::pages::{LoginScreen, SessionScreen};
-use crate::providers::SessionProvider;
+use crate::providers::{I18nProvider, NotificationProvider, SessionProvider, ThemeProvider};
 use yew::prelude::*;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -17,7 +19,7 @@ enum AppState {
     },
 }
 
-/// Extract session_id from URL query parameters
+/// Extract session_id from the URL query parameters or a `/join/<id>` path
 fn get_session_id_from_url() -> Option<String> {
     if let Some(window) = web_sys::window() {
         if let Ok(url) = window.location().href() {
@@ -27,6 +29,10 @@ fn get_session_id_from_url() -> Option<String> {
                     tracing::info!("Found session_id in URL: {}", session_id);
                     return Some(session_id);
                 }
+                if let Some(session_id) = session_id_from_path(&parsed.pathname()) {
+                    tracing::info!("Found session_id in join link: {}", session_id);
+                    return Some(session_id.to_string());
+                }
             }
         }
     }
@@ -35,21 +41,39 @@ fn get_session_id_from_url() -> Option<String> {
 
 #[function_component(App)]
 pub fn app() -> Html {
-    let state = use_state(|| {
-        // ✅ Check URL for session_id parameter
-        let initial_session_id = get_session_id_from_url();
+    let identity = use_identity();
+
+    let state = {
+        let identity = identity.identity.clone();
+        use_state(move || {
+            // ✅ Check URL for session_id parameter
+            let initial_session_id = get_session_id_from_url();
+
+            // A reload inside a session we already joined rejoins right away
+            if let (Some(session_id), Some(guest_name)) = (&initial_session_id, &identity.name) {
+                if identity.resume_token(session_id).is_some() {
+                    tracing::info!("Rejoining session {} as {}", session_id, guest_name);
+                    return AppState::JoiningSession {
+                        session_id: session_id.clone(),
+                        guest_name: guest_name.clone(),
+                    };
+                }
+            }
 
-        if initial_session_id.is_some() {
-            tracing::info!("Auto-switching to Join tab");
-        }
+            if initial_session_id.is_some() {
+                tracing::info!("Auto-switching to Join tab");
+            }
 
-        AppState::Login { initial_session_id }
-    });
+            AppState::Login { initial_session_id }
+        })
+    };
 
     let on_create_lobby = {
         let state = state.clone();
+        let set_name = identity.set_name.clone();
         Callback::from(move |(lobby_name, host_name): (String, String)| {
             tracing::info!("Creating lobby: {} as {}", lobby_name, host_name);
+            set_name.emit(host_name.clone());
             state.set(AppState::CreatingSession {
                 lobby_name,
                 host_name,
@@ -59,8 +83,10 @@ pub fn app() -> Html {
 
     let on_join_lobby = {
         let state = state.clone();
+        let set_name = identity.set_name.clone();
         Callback::from(move |(session_id, guest_name): (String, String)| {
             tracing::info!("Joining session: {} as {}", session_id, guest_name);
+            set_name.emit(guest_name.clone());
             state.set(AppState::JoiningSession {
                 session_id,
                 guest_name,
@@ -78,44 +104,75 @@ pub fn app() -> Html {
         })
     };
 
-    html! {
-        <div class="app">
-            {match &*state {
-                AppState::Login { initial_session_id } => {
-                    html! {
-                        <LoginScreen
-                            on_create_lobby={on_create_lobby}
-                            on_join_lobby={on_join_lobby}
-                            initial_session_id={initial_session_id.clone()}
-                        />
-                    }
-                }
-
-                AppState::CreatingSession { lobby_name, host_name } => {
-                    html! {
-                        <SessionProvider
-                            signalling_server="wss://match.konnektoren.help"
-                            lobby_name={Some(AttrValue::from(lobby_name.clone()))}
-                            name={Some(AttrValue::from(host_name.clone()))}
-                        >
-                            <SessionScreen on_leave={on_leave.clone()} />
-                        </SessionProvider>
-                    }
-                }
+    // Bumped to remount the SessionProvider, which starts the session over
+    let attempt = use_state(|| 0u32);
+    let on_retry = {
+        let attempt = attempt.clone();
+        Callback::from(move |_: ()| {
+            tracing::info!("Starting the session over");
+            attempt.set(*attempt + 1);
+        })
+    };
 
-                AppState::JoiningSession { session_id, guest_name } => {
-                    html! {
-                        <SessionProvider
-                            signalling_server="wss://match.konnektoren.help"
-                            session_id={Some(AttrValue::from(session_id.clone()))}
-                            name={Some(AttrValue::from(guest_name.clone()))}
-                        >
-                            <SessionScreen on_leave={on_leave.clone()} />
-                        </SessionProvider>
-                    }
-                }
-            }}
-        </div>
+    html! {
+        <ThemeProvider>
+            <I18nProvider>
+                <NotificationProvider>
+                    <div class="app">
+                        {match &*state {
+                            AppState::Login { initial_session_id } => {
+                                html! {
+                                    <LoginScreen
+                                        on_create_lobby={on_create_lobby}
+                                        on_join_lobby={on_join_lobby}
+                                        initial_session_id={initial_session_id.clone()}
+                                        initial_name={identity.identity.name.clone()}
+                                    />
+                                }
+                            }
+
+                            AppState::CreatingSession { lobby_name, host_name } => {
+                                html! {
+                                    <SessionProvider
+                                        key={*attempt}
+                                        signalling_server="wss://match.konnektoren.help"
+                                        lobby_name={Some(AttrValue::from(lobby_name.clone()))}
+                                        name={Some(AttrValue::from(host_name.clone()))}
+                                    >
+                                        <SessionErrorBoundary
+                                            on_retry={on_retry.clone()}
+                                            on_home={on_leave.clone()}
+                                        >
+                                            <SessionScreen on_leave={on_leave.clone()} />
+                                        </SessionErrorBoundary>
+                                    </SessionProvider>
+                                }
+                            }
+
+                            AppState::JoiningSession { session_id, guest_name } => {
+                                html! {
+                                    <SessionProvider
+                                        key={*attempt}
+                                        signalling_server="wss://match.konnektoren.help"
+                                        session_id={Some(AttrValue::from(session_id.clone()))}
+                                        name={Some(AttrValue::from(guest_name.clone()))}
+                                        persist_identity=true
+                                    >
+                                        <SessionErrorBoundary
+                                            on_retry={on_retry.clone()}
+                                            on_rejoin={on_retry.clone()}
+                                            on_home={on_leave.clone()}
+                                        >
+                                            <SessionScreen on_leave={on_leave.clone()} />
+                                        </SessionErrorBoundary>
+                                    </SessionProvider>
+                                }
+                            }
+                        }}
+                    </div>
+                </NotificationProvider>
+            </I18nProvider>
+        </ThemeProvider>
     }
 }
 
@@ -1,4 +1,5 @@
-use crate::hooks::ActiveRunSnapshot;
+use crate::components::list_navigation::{item_tabindex, list_keydown};
+use crate::hooks::{ActiveRunSnapshot, PendingCommand};
 use konnekt_session_core::Lobby;
 use yew::prelude::*;
 
@@ -6,21 +7,52 @@ use yew::prelude::*;
 pub struct ActivityListProps {
     pub lobby: Lobby,
     pub active_run: Option<ActiveRunSnapshot>,
+    /// Marks activities the host has not queued or moved yet, or refused to
+    /// queue
+    #[prop_or_default]
+    pub pending_commands: Vec<PendingCommand>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
 }
 
 /// Displays queued activities and the currently running activity (if any).
 #[function_component(ActivityList)]
 pub fn activity_list(props: &ActivityListProps) -> Html {
     let queue = props.lobby.activity_queue();
+    let is_pending = |id| {
+        props
+            .pending_commands
+            .iter()
+            .any(|c| c.is_pending() && c.queued_activity().is_some_and(|a| a.id == id))
+    };
+    let is_moving = |id| {
+        props
+            .pending_commands
+            .iter()
+            .any(|c| c.is_pending() && c.moved_activity() == Some(id))
+    };
+    let rolled_back: Vec<_> = props
+        .pending_commands
+        .iter()
+        .filter(|c| c.is_rolled_back())
+        .filter_map(|c| c.queued_activity())
+        .collect();
 
     html! {
-        <div class="konnekt-activity-list">
+        <div
+            class={classes!("konnekt-activity-list", props.classes.clone())}
+            style={props.style.clone()}
+            role="region"
+            aria-label="Activities"
+        >
             <h3 class="konnekt-activity-list__title">{"Activities"}</h3>
 
             {if let Some(run) = &props.active_run {
                 html! {
-                    <div class="konnekt-activity-list__item in-progress">
-                        <span class="konnekt-activity-list__icon">{"▶️"}</span>
+                    <div class="konnekt-activity-list__item in-progress" aria-current="true">
+                        <span class="konnekt-activity-list__icon" aria-hidden="true">{"▶️"}</span>
                         <span class="konnekt-activity-list__name">{run.name.clone()}</span>
                         <span class="konnekt-activity-list__status">{"InProgress"}</span>
                     </div>
@@ -29,22 +61,49 @@ pub fn activity_list(props: &ActivityListProps) -> Html {
                 html! {}
             }}
 
-            {if queue.is_empty() {
+            {if queue.is_empty() && rolled_back.is_empty() {
                 html! {
                     <p class="konnekt-activity-list__empty">{"No queued activities"}</p>
                 }
             } else {
                 html! {
-                    <ul class="konnekt-activity-list__items">
-                        {for queue.iter().map(|activity| {
+                    <ul
+                        class="konnekt-activity-list__items"
+                        role="list"
+                        aria-label="Queued activities"
+                        onkeydown={list_keydown()}
+                    >
+                        {for queue.iter().enumerate().map(|(i, activity)| {
+                            let queuing = is_pending(activity.id);
+                            let moving = is_moving(activity.id);
+                            let pending = queuing || moving;
                             html! {
-                                <li class="konnekt-activity-list__item planned">
-                                    <span class="konnekt-activity-list__icon">{"📋"}</span>
+                                <li
+                                    class={classes!("konnekt-activity-list__item", "planned", pending.then_some("pending"))}
+                                    tabindex={item_tabindex(i)}
+                                    aria-busy={pending.to_string()}
+                                >
+                                    <span class="konnekt-activity-list__icon" aria-hidden="true">{"📋"}</span>
                                     <span class="konnekt-activity-list__name">{activity.name.clone()}</span>
-                                    <span class="konnekt-activity-list__status">{"Queued"}</span>
+                                    <span class="konnekt-activity-list__status">
+                                        {if queuing {
+                                            "⏳ Queuing"
+                                        } else if moving {
+                                            "⏳ Moving"
+                                        } else {
+                                            "Queued"
+                                        }}
+                                    </span>
                                 </li>
                             }
                         })}
+                        {for rolled_back.iter().enumerate().map(|(i, activity)| html! {
+                            <li class="konnekt-activity-list__item rolled-back" tabindex={item_tabindex(queue.len() + i)}>
+                                <span class="konnekt-activity-list__icon" aria-hidden="true">{"↩️"}</span>
+                                <span class="konnekt-activity-list__name">{activity.name.clone()}</span>
+                                <span class="konnekt-activity-list__status">{"Not queued"}</span>
+                            </li>
+                        })}
                     </ul>
                 }
             }}
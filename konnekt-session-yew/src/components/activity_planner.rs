@@ -1,5 +1,5 @@
-use crate::hooks::use_session;
-use konnekt_session_core::{ActivityConfig, DomainCommand, EchoChallenge};
+use crate::hooks::{use_activities, use_host_actions};
+use konnekt_session_core::{ActivityConfig, EchoChallenge};
 use uuid::Uuid;
 use yew::prelude::*;
 
@@ -14,11 +14,16 @@ const ACTIVITY_TEMPLATES: &[(&str, &str)] = &[
 #[derive(Properties, PartialEq)]
 pub struct ActivityPlannerProps {
     pub lobby_id: Uuid,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
 }
 
 #[function_component(ActivityPlanner)]
 pub fn activity_planner(props: &ActivityPlannerProps) -> Html {
-    let session = use_session();
+    let host = use_host_actions();
+    let activities = use_activities();
     let selected = use_state(|| 0usize);
 
     let on_select = {
@@ -30,46 +35,29 @@ pub fn activity_planner(props: &ActivityPlannerProps) -> Html {
 
     let on_plan = {
         let selected = *selected;
-        let send_command = session.send_command.clone();
-        let lobby_id = props.lobby_id;
+        let queue_activity = host.queue_activity.clone();
 
         Callback::from(move |_: MouseEvent| {
             if let Some((name, prompt)) = ACTIVITY_TEMPLATES.get(selected) {
                 let challenge = EchoChallenge::new((*prompt).to_string());
-                let config = ActivityConfig::new(
+                queue_activity.emit(ActivityConfig::new(
                     "echo-challenge-v1".to_string(),
                     (*name).to_string(),
                     challenge.to_config(),
-                );
-
-                send_command(DomainCommand::QueueActivity { lobby_id, config });
+                ));
             }
         })
     };
 
     let on_start = {
-        let send_command = session.send_command.clone();
-        let lobby = session.lobby.clone();
-
-        Callback::from(move |_: MouseEvent| {
-            if let Some(lobby) = &lobby {
-                if !lobby.activity_queue().is_empty() && !lobby.has_active_run() {
-                    send_command(DomainCommand::StartNextRun {
-                        lobby_id: lobby.id(),
-                    });
-                }
-            }
-        })
+        let start_next = host.start_next.clone();
+        Callback::from(move |_: MouseEvent| start_next.emit(()))
     };
 
-    let has_planned = session
-        .lobby
-        .as_ref()
-        .map(|l| !l.activity_queue().is_empty())
-        .unwrap_or(false);
+    let has_planned = activities.has_planned();
 
     html! {
-        <div class="konnekt-activity-planner">
+        <div class={classes!("konnekt-activity-planner", props.classes.clone())} style={props.style.clone()}>
             <h3>{"Plan Activity"}</h3>
             <ul class="konnekt-activity-templates">
                 {for ACTIVITY_TEMPLATES.iter().enumerate().map(|(idx, (name, _))| {
@@ -0,0 +1,208 @@
+use konnekt_session_core::domain::{ActivityConfig, ActivityId};
+use yew::prelude::*;
+
+use crate::hooks::use_i18n;
+
+#[cfg(feature = "preview")]
+use yew_preview::prelude::*;
+#[cfg(feature = "preview")]
+use yew_preview::test_utils::{exists, has_text};
+
+/// Where the dragged item would land relative to the hovered one: the
+/// dragged item takes the hovered item's place, pushing it down when coming
+/// from below and up when coming from above
+pub(crate) fn drop_indicator(from: usize, over: usize) -> Option<&'static str> {
+    match from.cmp(&over) {
+        std::cmp::Ordering::Greater => Some("drop-before"),
+        std::cmp::Ordering::Less => Some("drop-after"),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct ActivityQueueEditorProps {
+    /// Usually `lobby.activity_queue()`
+    pub queue: Vec<ActivityConfig>,
+    /// Move an activity to a new position, e.g.
+    /// `use_host_actions().move_activity`
+    pub on_move: Callback<(ActivityId, usize)>,
+    #[prop_or_default]
+    pub disabled: bool,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Lets hosts and co-hosts reorder the planned activities by dragging them,
+/// or with the arrow buttons from the keyboard
+#[function_component(ActivityQueueEditor)]
+pub fn activity_queue_editor(props: &ActivityQueueEditorProps) -> Html {
+    let i18n = use_i18n();
+    let dragging = use_state(|| None::<usize>);
+    let over = use_state(|| None::<usize>);
+    let len = props.queue.len();
+
+    let reset = {
+        let dragging = dragging.clone();
+        let over = over.clone();
+        move || {
+            dragging.set(None);
+            over.set(None);
+        }
+    };
+
+    html! {
+        <div
+            class={classes!(
+                "konnekt-queue-editor",
+                props.disabled.then_some("disabled"),
+                props.classes.clone(),
+            )}
+            style={props.style.clone()}
+        >
+            <h4 class="konnekt-queue-editor__title">{i18n.t("queue_editor.title")}</h4>
+            <p class="konnekt-queue-editor__hint">{i18n.t("queue_editor.hint")}</p>
+            <ol class="konnekt-queue-editor__items">
+                {for props.queue.iter().enumerate().map(|(index, activity)| {
+                    let id = activity.id;
+                    let indicator = (*dragging).zip(*over)
+                        .filter(|(_, over)| *over == index)
+                        .and_then(|(from, over)| drop_indicator(from, over));
+                    let move_to = move |to: usize| {
+                        let on_move = props.on_move.clone();
+                        move |_: MouseEvent| on_move.emit((id, to))
+                    };
+
+                    let ondragstart = {
+                        let dragging = dragging.clone();
+                        move |e: DragEvent| {
+                            if let Some(data) = e.data_transfer() {
+                                data.set_effect_allowed("move");
+                                // Firefox only starts dragging with some data set
+                                let _ = data.set_data("text/plain", &id.to_string());
+                            }
+                            dragging.set(Some(index));
+                        }
+                    };
+                    let ondragover = {
+                        let over = over.clone();
+                        move |e: DragEvent| {
+                            // Allows dropping here
+                            e.prevent_default();
+                            if *over != Some(index) {
+                                over.set(Some(index));
+                            }
+                        }
+                    };
+                    let ondrop = {
+                        let from = *dragging;
+                        let queue = props.queue.clone();
+                        let on_move = props.on_move.clone();
+                        let reset = reset.clone();
+                        move |e: DragEvent| {
+                            e.prevent_default();
+                            if let Some(activity) = from
+                                .filter(|from| *from != index)
+                                .and_then(|from| queue.get(from))
+                            {
+                                on_move.emit((activity.id, index));
+                            }
+                            reset();
+                        }
+                    };
+                    let ondragend = {
+                        let reset = reset.clone();
+                        move |_: DragEvent| reset()
+                    };
+
+                    html! {
+                        <li
+                            key={id.to_string()}
+                            class={classes!(
+                                "konnekt-queue-editor__item",
+                                (*dragging == Some(index)).then_some("dragging"),
+                                indicator,
+                            )}
+                            draggable={(!props.disabled).to_string()}
+                            {ondragstart}
+                            {ondragover}
+                            {ondrop}
+                            {ondragend}
+                        >
+                            <span class="konnekt-queue-editor__handle" aria-hidden="true">{"⠿"}</span>
+                            <span class="konnekt-queue-editor__position">{(index + 1).to_string()}</span>
+                            <span class="konnekt-queue-editor__name">{activity.name.clone()}</span>
+                            <button
+                                class="konnekt-btn konnekt-btn--secondary konnekt-queue-editor__move"
+                                disabled={props.disabled || index == 0}
+                                aria-label={i18n.t_with("queue_editor.move_up", &[("name", &activity.name)])}
+                                onclick={move_to(index.saturating_sub(1))}
+                            >
+                                {"↑"}
+                            </button>
+                            <button
+                                class="konnekt-btn konnekt-btn--secondary konnekt-queue-editor__move"
+                                disabled={props.disabled || index + 1 == len}
+                                aria-label={i18n.t_with("queue_editor.move_down", &[("name", &activity.name)])}
+                                onclick={move_to(index + 1)}
+                            >
+                                {"↓"}
+                            </button>
+                        </li>
+                    }
+                })}
+            </ol>
+        </div>
+    }
+}
+
+#[cfg(feature = "preview")]
+fn make_sample_queue() -> Vec<ActivityConfig> {
+    ["Echo: Hello Rust", "Echo: WebAssembly", "Echo: Konnekt"]
+        .into_iter()
+        .map(|name| {
+            ActivityConfig::new(
+                "echo-challenge-v1".to_string(),
+                name.to_string(),
+                Default::default(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(feature = "preview")]
+yew_preview::create_preview_with_tests!(
+    component: ActivityQueueEditor,
+    default_props: ActivityQueueEditorProps {
+        queue: make_sample_queue(),
+        on_move: Callback::noop(),
+    },
+    variants: [
+        (
+            "Disabled",
+            ActivityQueueEditorProps {
+                queue: make_sample_queue(),
+                on_move: Callback::noop(),
+                disabled: true,
+            }
+        ),
+    ],
+    tests: [
+        ("Has main container class", exists("konnekt-queue-editor")),
+        ("Has item class", exists("konnekt-queue-editor__item")),
+        ("Shows activities", has_text("Echo: WebAssembly")),
+    ]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drop_indicator() {
+        assert_eq!(drop_indicator(2, 0), Some("drop-before"));
+        assert_eq!(drop_indicator(0, 2), Some("drop-after"));
+        assert_eq!(drop_indicator(1, 1), None);
+    }
+}
@@ -0,0 +1,77 @@
+use konnekt_session_core::RunStatus;
+use yew::prelude::*;
+
+use crate::hooks::{CurrentActivity, use_activity};
+
+/// Properties every activity rendered by [`ActivityRunner`] receives
+#[derive(Properties, Clone, PartialEq)]
+pub struct ActivityProps {
+    pub activity: CurrentActivity,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ActivityRunnerProps {
+    /// Only run activities of this type; others show the waiting screen
+    #[prop_or_default]
+    pub activity_type: Option<AttrValue>,
+    /// Shown while no activity is running (a spinner by default)
+    #[prop_or_default]
+    pub waiting: Option<Html>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Renders the activity component `T` while an activity is in progress and
+/// a waiting screen otherwise
+///
+/// ```rust,ignore
+/// #[function_component(Quiz)]
+/// fn quiz(props: &ActivityProps) -> Html { /* ... */ }
+///
+/// html! { <ActivityRunner<Quiz> activity_type="quiz-v1" /> }
+/// ```
+#[function_component(ActivityRunner)]
+pub fn activity_runner<T>(props: &ActivityRunnerProps) -> Html
+where
+    T: BaseComponent<Properties = ActivityProps>,
+{
+    let activity = use_activity().filter(|activity| {
+        activity.status == RunStatus::InProgress
+            && props
+                .activity_type
+                .as_ref()
+                .is_none_or(|activity_type| activity.activity_type == activity_type.as_str())
+    });
+
+    match activity {
+        Some(activity) => {
+            // A new run remounts `T`, so its local state starts fresh
+            let key = activity.run_id.to_string();
+            html! {
+                <div
+                    class={classes!("konnekt-activity-runner", props.classes.clone())}
+                    style={props.style.clone()}
+                >
+                    <T {key} {activity} />
+                </div>
+            }
+        }
+        None => props.waiting.clone().unwrap_or_else(|| {
+            html! {
+                <div
+                    class={classes!(
+                        "konnekt-activity-runner",
+                        "konnekt-activity-runner--waiting",
+                        props.classes.clone(),
+                    )}
+                    style={props.style.clone()}
+                >
+                    <div class="konnekt-spinner"></div>
+                    <p>{"Waiting for the next activity..."}</p>
+                </div>
+            }
+        }),
+    }
+}
@@ -1,6 +1,8 @@
-use crate::hooks::ActiveRunSnapshot;
-use crate::hooks::use_session;
-use konnekt_session_core::{DomainCommand, EchoChallenge, EchoResult, Lobby};
+use crate::hooks::{
+    ActiveRunSnapshot, ActivityAnswer, use_activity, use_host_actions, use_session,
+};
+use konnekt_session_core::{EchoResult, Lobby, PluginRegistry};
+use konnekt_session_p2p::Presence;
 use uuid::Uuid;
 use yew::prelude::*;
 
@@ -12,75 +14,72 @@ pub struct ActivitySubmissionProps {
     pub active_run: Option<ActiveRunSnapshot>,
     pub is_host: bool,
     pub participant_id: Option<Uuid>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
 }
 
 #[function_component(ActivitySubmission)]
 pub fn activity_submission(props: &ActivitySubmissionProps) -> Html {
     let session = use_session();
+    let current = use_activity();
+    let host = use_host_actions();
     let response = use_state(String::new);
 
     let on_input = {
         let response = response.clone();
+        let set_presence = session.set_presence.clone();
+        let run_id = props.active_run.as_ref().map(|run| run.run_id);
         Callback::from(move |e: InputEvent| {
             let input: web_sys::HtmlInputElement = e.target_unchecked_into();
             response.set(input.value());
+            if let Some(run_id) = run_id {
+                set_presence(Some(Presence::Answering { run_id }));
+            }
         })
     };
 
     let on_submit_form = {
         let response = response.clone();
-        let lobby = props.lobby.clone();
-        let active_run = props.active_run.clone();
-        let send_command = session.send_command.clone();
-        let participant_id = props.participant_id;
+        let submit_result = current
+            .as_ref()
+            .map(|activity| activity.submit_result.clone());
+        let current = current.clone();
+        let activity = props
+            .active_run
+            .as_ref()
+            .map(|run| (run.activity_type.clone(), run.config.clone()));
 
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
 
-            if let (Some(lobby), Some(run), Some(pid)) = (&lobby, &active_run, participant_id) {
-                let response_text = (*response).clone();
-
-                if let Ok(challenge) = EchoChallenge::from_config(run.config.clone()) {
-                    let score = challenge.calculate_score(&response_text);
-                    let echo_result = EchoResult::new(response_text, 1000);
-
-                    let result = konnekt_session_core::domain::ActivityResult::new(run.run_id, pid)
-                        .with_data(echo_result.to_json())
-                        .with_score(score)
-                        .with_time(1000);
-
-                    send_command(DomainCommand::SubmitResult {
-                        lobby_id: lobby.id(),
-                        run_id: run.run_id,
-                        result,
-                    });
-
-                    response.set(String::new());
-                }
-            }
+            let (Some(submit_result), Some(current), Some((activity_type, config))) =
+                (&submit_result, &current, &activity)
+            else {
+                return;
+            };
+            let time_taken = (current.now_ms() - current.started_at_ms).max(0) as u64;
+            // Text answers share the echo result's shape
+            let data = EchoResult::new((*response).clone(), time_taken).to_json();
+            let score = PluginRegistry::global().score(activity_type, config, &data);
+
+            submit_result.emit(ActivityAnswer { data, score });
+            response.set(String::new());
         })
     };
 
     let on_cancel = {
-        let send_command = session.send_command.clone();
-        let lobby = props.lobby.clone();
-        let active_run = props.active_run.clone();
-
-        Callback::from(move |_: MouseEvent| {
-            if let (Some(lobby), Some(run)) = (&lobby, &active_run) {
-                send_command(DomainCommand::CancelRun {
-                    lobby_id: lobby.id(),
-                    run_id: run.run_id,
-                });
-            }
-        })
+        let cancel_run = host.cancel_run.clone();
+        Callback::from(move |_: MouseEvent| cancel_run.emit(()))
     };
 
     if let (Some(lobby), Some(run)) = (&props.lobby, &props.active_run) {
-        let (prompt, error) = match EchoChallenge::from_config(run.config.clone()) {
-            Ok(challenge) => (Some(challenge.prompt.clone()), None),
-            Err(e) => (None, Some(format!("Failed to load: {}", e))),
-        };
+        let (hints, error) =
+            match PluginRegistry::global().render_hints(&run.activity_type, &run.config) {
+                Ok(hints) => (Some(hints), None),
+                Err(e) => (None, Some(format!("Failed to load: {}", e))),
+            };
 
         let has_user_submitted = props
             .participant_id
@@ -88,7 +87,7 @@ pub fn activity_submission(props: &ActivitySubmissionProps) -> Html {
             .unwrap_or(false);
 
         return html! {
-            <div class="konnekt-activity-screen">
+            <div class={classes!("konnekt-activity-screen", props.classes.clone())} style={props.style.clone()}>
                 <div class="konnekt-activity-screen__header">
                     <h2 class="konnekt-activity-screen__title">
                         {"🎮 "}{run.name.clone()}
@@ -113,12 +112,13 @@ pub fn activity_submission(props: &ActivitySubmissionProps) -> Html {
                             {err}
                         </div>
                     }
-                } else if let Some(prompt_text) = prompt {
+                } else if let Some(hints) = hints {
                     html! {
                         <div class="konnekt-activity-screen__content">
                             <SubmissionStatus
                                 lobby={lobby.clone()}
                                 active_run={run.clone()}
+                                presence={session.presence.clone()}
                             />
 
                             {if has_user_submitted {
@@ -134,12 +134,18 @@ pub fn activity_submission(props: &ActivitySubmissionProps) -> Html {
                             } else {
                                 html! {
                                     <>
-                                        <div class="konnekt-activity-screen__prompt">
-                                            <h3>{"Echo this:"}</h3>
-                                            <div class="konnekt-activity-screen__prompt-text">
-                                                {prompt_text}
-                                            </div>
-                                        </div>
+                                        {if let Some(prompt_text) = hints.prompt.clone() {
+                                            html! {
+                                                <div class="konnekt-activity-screen__prompt">
+                                                    <h3>{"Prompt:"}</h3>
+                                                    <div class="konnekt-activity-screen__prompt-text">
+                                                        {prompt_text}
+                                                    </div>
+                                                </div>
+                                            }
+                                        } else {
+                                            html! {}
+                                        }}
 
                                         <form
                                             class="konnekt-activity-screen__form"
@@ -152,7 +158,10 @@ pub fn activity_submission(props: &ActivitySubmissionProps) -> Html {
                                                     type="text"
                                                     value={(*response).clone()}
                                                     oninput={on_input}
-                                                    placeholder="Type here..."
+                                                    placeholder={hints
+                                                        .placeholder
+                                                        .clone()
+                                                        .unwrap_or_else(|| "Type here...".to_string())}
                                                     autofocus={true}
                                                 />
                                             </label>
@@ -177,7 +186,7 @@ pub fn activity_submission(props: &ActivitySubmissionProps) -> Html {
     }
 
     html! {
-        <div class="konnekt-session-screen__error">
+        <div class={classes!("konnekt-session-screen__error", props.classes.clone())} style={props.style.clone()}>
             {"No activity in progress"}
         </div>
     }
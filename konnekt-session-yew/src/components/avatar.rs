@@ -0,0 +1,103 @@
+use konnekt_session_core::ParticipantAvatar;
+pub(crate) use konnekt_session_headless::avatar_hue;
+use konnekt_session_headless::initials;
+use uuid::Uuid;
+use yew::prelude::*;
+
+#[cfg(feature = "preview")]
+use yew_preview::prelude::*;
+#[cfg(feature = "preview")]
+use yew_preview::test_utils::{exists, has_text};
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct AvatarProps {
+    pub participant_id: Uuid,
+    /// Initials of the generated avatar and the accessible label
+    pub name: String,
+    /// The avatar the participant picked, if any
+    #[prop_or_default]
+    pub avatar: Option<ParticipantAvatar>,
+    /// Width and height in pixels
+    #[prop_or(32)]
+    pub size: u32,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Round picture of a participant: the emoji or image they picked, otherwise
+/// their initials on a color generated from their ID
+#[function_component(Avatar)]
+pub fn avatar(props: &AvatarProps) -> Html {
+    let size = format!(
+        "width: {0}px; height: {0}px; font-size: {1}px;",
+        props.size,
+        props.size * 45 / 100
+    );
+
+    let (modifier, content, background) = match &props.avatar {
+        Some(ParticipantAvatar::Emoji(emoji)) => {
+            ("emoji", html! { {emoji.clone()} }, String::new())
+        }
+        Some(ParticipantAvatar::Image(url)) => (
+            "image",
+            html! { <img class="konnekt-avatar__image" src={url.clone()} alt="" /> },
+            String::new(),
+        ),
+        None => (
+            "generated",
+            html! { {initials(&props.name)} },
+            format!(
+                " background: hsl({}, 55%, 45%);",
+                avatar_hue(props.participant_id)
+            ),
+        ),
+    };
+
+    html! {
+        <span
+            class={classes!(
+                "konnekt-avatar",
+                format!("konnekt-avatar--{}", modifier),
+                props.classes.clone(),
+            )}
+            style={format!(
+                "{}{} {}",
+                size,
+                background,
+                props.style.as_deref().unwrap_or_default()
+            )}
+            role="img"
+            aria-label={props.name.clone()}
+        >
+            {content}
+        </span>
+    }
+}
+
+#[cfg(feature = "preview")]
+yew_preview::create_preview_with_tests!(
+    component: Avatar,
+    default_props: AvatarProps {
+        participant_id: Uuid::from_u128(42),
+        name: "Ada Lovelace".to_string(),
+        size: 48,
+    },
+    variants: [
+        (
+            "Emoji",
+            AvatarProps {
+                participant_id: Uuid::from_u128(42),
+                name: "Ada Lovelace".to_string(),
+                avatar: Some(ParticipantAvatar::Emoji("🦊".to_string())),
+                size: 48,
+            }
+        ),
+    ],
+    tests: [
+        ("Has main container class", exists("konnekt-avatar")),
+        ("Has generated modifier", exists("konnekt-avatar--generated")),
+        ("Shows initials", has_text("AL")),
+    ]
+);
@@ -0,0 +1,123 @@
+use konnekt_session_core::ParticipantAvatar;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::hooks::use_i18n;
+
+#[cfg(feature = "preview")]
+use yew_preview::prelude::*;
+#[cfg(feature = "preview")]
+use yew_preview::test_utils::exists;
+
+/// Emojis offered by [`AvatarPicker`]
+pub const AVATAR_EMOJIS: &[&str] = &[
+    "🦊", "🐼", "🐸", "🦁", "🐙", "🦄", "🐝", "🐢", "🦉", "🐳", "🚀", "🌵", "🍕", "⚽", "🎸", "🌈",
+];
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct AvatarPickerProps {
+    /// The avatar picked so far (`None`: the generated one)
+    #[prop_or_default]
+    pub current: Option<ParticipantAvatar>,
+    /// Emits the new choice, `None` to go back to the generated avatar
+    pub on_change: Callback<Option<ParticipantAvatar>>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Lets the local participant pick an emoji or image avatar
+#[function_component(AvatarPicker)]
+pub fn avatar_picker(props: &AvatarPickerProps) -> Html {
+    let i18n = use_i18n();
+    let url = use_state(|| match &props.current {
+        Some(ParticipantAvatar::Image(url)) => url.clone(),
+        _ => String::new(),
+    });
+    let image = ParticipantAvatar::Image(url.trim().to_string());
+    let image_valid = image.validate().is_ok();
+
+    let on_url_input = {
+        let url = url.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            url.set(input.value());
+        })
+    };
+
+    let on_use_image = {
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            if image_valid {
+                on_change.emit(Some(image.clone()));
+            }
+        })
+    };
+
+    html! {
+        <div class={classes!("konnekt-avatar-picker", props.classes.clone())} style={props.style.clone()}>
+            <div class="konnekt-avatar-picker__emojis" role="radiogroup" aria-label={i18n.t("avatar.title")}>
+                {for AVATAR_EMOJIS.iter().map(|emoji| {
+                    let choice = ParticipantAvatar::Emoji(emoji.to_string());
+                    let selected = props.current.as_ref() == Some(&choice);
+                    let on_change = props.on_change.clone();
+                    html! {
+                        <button
+                            type="button"
+                            class={classes!("konnekt-avatar-picker__emoji", selected.then_some("selected"))}
+                            role="radio"
+                            aria-checked={selected.to_string()}
+                            onclick={move |_| on_change.emit(Some(choice.clone()))}
+                        >
+                            {*emoji}
+                        </button>
+                    }
+                })}
+            </div>
+            <form class="konnekt-avatar-picker__image" onsubmit={on_use_image}>
+                <input
+                    type="url"
+                    class="konnekt-avatar-picker__url"
+                    placeholder={i18n.t("avatar.image_url")}
+                    aria-label={i18n.t("avatar.image_url")}
+                    value={(*url).clone()}
+                    oninput={on_url_input}
+                />
+                <button type="submit" class="konnekt-btn konnekt-btn--secondary" disabled={!image_valid}>
+                    {i18n.t("avatar.use_image")}
+                </button>
+            </form>
+            {if props.current.is_some() {
+                let on_change = props.on_change.clone();
+                html! {
+                    <button
+                        type="button"
+                        class="konnekt-btn konnekt-btn--secondary konnekt-avatar-picker__reset"
+                        onclick={move |_| on_change.emit(None)}
+                    >
+                        {i18n.t("avatar.reset")}
+                    </button>
+                }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
+
+#[cfg(feature = "preview")]
+yew_preview::create_preview_with_tests!(
+    component: AvatarPicker,
+    default_props: AvatarPickerProps {
+        current: Some(ParticipantAvatar::Emoji("🦊".to_string())),
+        on_change: Callback::noop(),
+    },
+    variants: [],
+    tests: [
+        ("Has main container class", exists("konnekt-avatar-picker")),
+        ("Marks the current emoji", exists("selected")),
+        ("Has reset button class", exists("konnekt-avatar-picker__reset")),
+    ]
+);
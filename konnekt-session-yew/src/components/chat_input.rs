@@ -0,0 +1,88 @@
+use konnekt_session_core::domain::MAX_CHAT_MESSAGE_LEN;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct ChatInputProps {
+    /// Called with the trimmed message when it is sent
+    pub on_send: Callback<String>,
+    #[prop_or_default]
+    pub disabled: bool,
+    #[prop_or(AttrValue::from("Write a message… (@name to mention)"))]
+    pub placeholder: AttrValue,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Text field that sends a chat message on Enter or with the send button
+#[function_component(ChatInput)]
+pub fn chat_input(props: &ChatInputProps) -> Html {
+    let text = use_state(String::new);
+
+    let on_input = {
+        let text = text.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            text.set(input.value());
+        })
+    };
+
+    let send = {
+        let text = text.clone();
+        let on_send = props.on_send.clone();
+        move || {
+            let message = text.trim();
+            if !message.is_empty() && message.chars().count() <= MAX_CHAT_MESSAGE_LEN {
+                on_send.emit(message.to_string());
+                text.set(String::new());
+            }
+        }
+    };
+
+    let on_keydown = {
+        let send = send.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Enter" && !e.shift_key() {
+                e.prevent_default();
+                send();
+            }
+        })
+    };
+
+    let on_click = Callback::from(move |_: MouseEvent| send());
+
+    let length = text.chars().count();
+    let too_long = length > MAX_CHAT_MESSAGE_LEN;
+
+    html! {
+        <div class={classes!("konnekt-chat-input", props.classes.clone())} style={props.style.clone()}>
+            <input
+                class={classes!("konnekt-chat-input__field", too_long.then_some("too-long"))}
+                type="text"
+                value={(*text).clone()}
+                placeholder={props.placeholder.clone()}
+                disabled={props.disabled}
+                oninput={on_input}
+                onkeydown={on_keydown}
+            />
+            {if length > MAX_CHAT_MESSAGE_LEN * 4 / 5 {
+                html! {
+                    <span class="konnekt-chat-input__counter">
+                        {format!("{}/{}", length, MAX_CHAT_MESSAGE_LEN)}
+                    </span>
+                }
+            } else {
+                html! {}
+            }}
+            <button
+                class="konnekt-btn konnekt-chat-input__send"
+                onclick={on_click}
+                disabled={props.disabled || text.trim().is_empty() || too_long}
+            >
+                {"Send"}
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,238 @@
+use konnekt_session_core::{ChatMessage, Lobby};
+use konnekt_session_headless::{Segment, is_mention_of, mention_segments};
+use uuid::Uuid;
+use web_sys::Element;
+use yew::prelude::*;
+
+use super::{Avatar, ChatInput};
+
+#[cfg(feature = "preview")]
+use yew_preview::prelude::*;
+#[cfg(feature = "preview")]
+use yew_preview::test_utils::{exists, has_text};
+
+/// How close to the bottom (in pixels) still counts as following the chat
+const STICK_TO_BOTTOM_PX: i32 = 32;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct ChatPanelProps {
+    pub lobby: Lobby,
+    #[prop_or_default]
+    pub local_participant_id: Option<Uuid>,
+    /// Messages of others not read yet (see `use_chat`)
+    #[prop_or_default]
+    pub unread: usize,
+    pub on_send: Callback<String>,
+    /// Called when the newest message has been scrolled into view
+    #[prop_or_default]
+    pub on_read: Callback<()>,
+    /// Our messages the host has not confirmed yet
+    #[prop_or_default]
+    pub pending: Vec<Uuid>,
+    /// Our messages the host did not take
+    #[prop_or_default]
+    pub rolled_back: Vec<ChatMessage>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Chat of the lobby: message list with mentions highlighted and an input
+///
+/// The list follows new messages while it is scrolled to the bottom.
+#[function_component(ChatPanel)]
+pub fn chat_panel(props: &ChatPanelProps) -> Html {
+    let list_ref = use_node_ref();
+    let following = use_mut_ref(|| true);
+
+    let messages = props.lobby.chat_messages();
+    let participants = props.lobby.participants();
+    let names: Vec<&str> = participants.values().map(|p| p.name()).collect();
+    let local_name = props
+        .local_participant_id
+        .and_then(|id| participants.get(&id))
+        .map(|p| p.name());
+
+    {
+        let list_ref = list_ref.clone();
+        let following = following.clone();
+        let on_read = props.on_read.clone();
+
+        use_effect_with(messages.last().map(|m| m.id()), move |_| {
+            if *following.borrow()
+                && let Some(list) = list_ref.cast::<Element>()
+            {
+                list.set_scroll_top(list.scroll_height());
+                on_read.emit(());
+            }
+            || ()
+        });
+    }
+
+    let on_scroll = {
+        let list_ref = list_ref.clone();
+        let following = following.clone();
+        let on_read = props.on_read.clone();
+
+        Callback::from(move |_: Event| {
+            if let Some(list) = list_ref.cast::<Element>() {
+                let at_bottom = list.scroll_height() - list.scroll_top() - list.client_height()
+                    <= STICK_TO_BOTTOM_PX;
+                *following.borrow_mut() = at_bottom;
+                if at_bottom {
+                    on_read.emit(());
+                }
+            }
+        })
+    };
+
+    let on_jump = {
+        let list_ref = list_ref.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(list) = list_ref.cast::<Element>() {
+                list.set_scroll_top(list.scroll_height());
+            }
+        })
+    };
+
+    html! {
+        <div class={classes!("konnekt-chat-panel", props.classes.clone())} style={props.style.clone()}>
+            <h3 class="konnekt-chat-panel__title">
+                {"💬 Chat"}
+                {if props.unread > 0 {
+                    html! {
+                        <button class="konnekt-chat-panel__unread" onclick={on_jump}>
+                            {format!("{} new", props.unread)}
+                        </button>
+                    }
+                } else {
+                    html! {}
+                }}
+            </h3>
+            <ul class="konnekt-chat-panel__messages" ref={list_ref} onscroll={on_scroll}>
+                {if messages.is_empty() {
+                    html! { <li class="konnekt-chat-panel__empty">{"No messages yet. Say hi!"}</li> }
+                } else {
+                    html! {}
+                }}
+                {for messages.iter().map(|message| {
+                    let author = participants.get(&message.author_id());
+                    let is_mine = Some(message.author_id()) == props.local_participant_id;
+                    let is_pending = props.pending.contains(&message.id());
+                    let segments = mention_segments(message.text(), &names);
+                    let mentions_me = segments
+                        .iter()
+                        .any(|s| matches!(s, Segment::Mention(m) if is_mention_of(m, local_name)));
+
+                    html! {
+                        <li
+                            key={message.id().to_string()}
+                            class={classes!(
+                                "konnekt-chat-panel__message",
+                                is_mine.then_some("mine"),
+                                (mentions_me && !is_mine).then_some("mentioned"),
+                                is_pending.then_some("pending"),
+                            )}
+                            title={is_pending.then_some("Sending…")}
+                        >
+                            {match author {
+                                Some(author) => html! {
+                                    <Avatar
+                                        classes="konnekt-chat-panel__avatar"
+                                        participant_id={author.id()}
+                                        name={author.name().to_string()}
+                                        avatar={author.avatar().cloned()}
+                                        size={20}
+                                    />
+                                },
+                                None => html! {},
+                            }}
+                            <span class="konnekt-chat-panel__author">
+                                {if author.is_some_and(|p| p.is_host()) { "👑 " } else { "" }}
+                                {author.map_or("Someone who left", |p| p.name())}
+                            </span>
+                            <span class="konnekt-chat-panel__text">
+                                {for segments.iter().map(|segment| match segment {
+                                    Segment::Text(text) => html! { {*text} },
+                                    Segment::Mention(mention) => html! {
+                                        <span class={classes!(
+                                            "konnekt-chat-panel__mention",
+                                            is_mention_of(mention, local_name).then_some("me"),
+                                        )}>
+                                            {*mention}
+                                        </span>
+                                    },
+                                })}
+                            </span>
+                        </li>
+                    }
+                })}
+                {for props.rolled_back.iter().map(|message| html! {
+                    <li
+                        key={message.id().to_string()}
+                        class="konnekt-chat-panel__message mine rolled-back"
+                    >
+                        <span class="konnekt-chat-panel__text">{message.text()}</span>
+                        <span class="konnekt-chat-panel__status">{"↩️ Not sent"}</span>
+                    </li>
+                })}
+            </ul>
+            <ChatInput
+                on_send={props.on_send.clone()}
+                disabled={props.local_participant_id.is_none()}
+            />
+        </div>
+    }
+}
+
+#[cfg(feature = "preview")]
+mod preview_fixtures {
+    use konnekt_session_core::{ChatMessage, Lobby, Participant};
+
+    pub fn make_chatty_lobby() -> Lobby {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let host_id = host.id();
+        let mut lobby = Lobby::new("Preview Lobby".to_string(), host).unwrap();
+        let bob = Participant::new_guest("Bob".to_string()).unwrap();
+        let bob_id = bob.id();
+        lobby.add_guest(bob).unwrap();
+
+        for (author, text) in [
+            (host_id, "Welcome everyone!"),
+            (bob_id, "Hi @Alice, ready when you are"),
+            (host_id, "@bob great, starting in a minute"),
+        ] {
+            let message = ChatMessage::new(author, text.to_string()).unwrap();
+            lobby.post_chat_message(message).unwrap();
+        }
+        lobby
+    }
+
+    pub fn bob_id(lobby: &Lobby) -> Option<uuid::Uuid> {
+        lobby
+            .participants()
+            .values()
+            .find(|p| !p.is_host())
+            .map(|p| p.id())
+    }
+}
+
+#[cfg(feature = "preview")]
+yew_preview::create_preview_with_tests!(
+    component: ChatPanel,
+    default_props: ChatPanelProps {
+        lobby: preview_fixtures::make_chatty_lobby(),
+        local_participant_id: preview_fixtures::bob_id(&preview_fixtures::make_chatty_lobby()),
+        unread: 1,
+        on_send: Callback::noop(),
+    },
+    variants: [],
+    tests: [
+        ("Has main container class", exists("konnekt-chat-panel")),
+        ("Has message class", exists("konnekt-chat-panel__message")),
+        ("Highlights mentions", exists("konnekt-chat-panel__mention")),
+        ("Shows unread count", has_text("1 new")),
+        ("Contains the welcome", has_text("Welcome everyone!")),
+    ]
+);
@@ -0,0 +1,54 @@
+use yew::prelude::*;
+
+use crate::hooks::{ConnectionHealth, use_connection_quality};
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct ConnectionBannerProps {
+    /// Also warn about slow or missing peers, not only lost connections
+    #[prop_or(true)]
+    pub show_degraded: bool,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Banner shown while the session connection is reconnecting, offline or
+/// degraded; renders nothing while it is fine
+#[function_component(ConnectionBanner)]
+pub fn connection_banner(props: &ConnectionBannerProps) -> Html {
+    let quality = use_connection_quality();
+
+    let (modifier, text) = match quality.health {
+        ConnectionHealth::Reconnecting => (
+            "reconnecting",
+            "🔄 Connection lost. Reconnecting…".to_string(),
+        ),
+        ConnectionHealth::Offline => (
+            "offline",
+            "📴 Lost the connection to the host. Waiting for it to come back…".to_string(),
+        ),
+        ConnectionHealth::Degraded if props.show_degraded => (
+            "degraded",
+            match quality.worst_rtt_ms {
+                Some(rtt) => format!("🐢 Slow connection (up to {} ms round trip)", rtt),
+                None => "🐢 Some participants are having connection trouble".to_string(),
+            },
+        ),
+        _ => return html! {},
+    };
+
+    html! {
+        <div
+            class={classes!(
+                "konnekt-connection-banner",
+                format!("konnekt-connection-banner--{}", modifier),
+                props.classes.clone(),
+            )}
+            style={props.style.clone()}
+            role="status"
+        >
+            {text}
+        </div>
+    }
+}
@@ -0,0 +1,124 @@
+use chrono::Utc;
+use gloo_timers::callback::Interval;
+use yew::prelude::*;
+
+use crate::hooks::use_i18n;
+
+#[cfg(feature = "preview")]
+use yew_preview::prelude::*;
+#[cfg(feature = "preview")]
+use yew_preview::test_utils::exists;
+
+/// How long "Go!" stays up after the countdown ended
+const GO_VISIBLE_MS: i64 = 1_000;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct CountdownProps {
+    /// When the countdown reaches zero (Unix ms, on the host's clock)
+    pub ends_at_ms: i64,
+    /// How far the host's clock is ahead of ours; usually
+    /// `use_session().clock_offset_ms`
+    #[prop_or_default]
+    pub clock_offset_ms: i64,
+    /// Fired once when it reaches zero, also if it already had when mounted
+    #[prop_or_default]
+    pub on_finished: Callback<()>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// What to show `remaining_ms` before the end: the seconds left rounded up,
+/// `Some(0)` for "Go!" and `None` once that is over
+pub(crate) fn countdown_step(remaining_ms: i64) -> Option<u32> {
+    if remaining_ms > 0 {
+        Some(((remaining_ms + 999) / 1_000) as u32)
+    } else if remaining_ms > -GO_VISIBLE_MS {
+        Some(0)
+    } else {
+        None
+    }
+}
+
+/// "3…2…1…Go!" towards a wall clock time, so every browser shows the same
+/// number
+#[function_component(Countdown)]
+pub fn countdown(props: &CountdownProps) -> Html {
+    let i18n = use_i18n();
+    let now = use_state(|| Utc::now().timestamp_millis());
+    let finished = use_mut_ref(|| None::<i64>);
+
+    {
+        let now = now.setter();
+        use_effect_with(props.ends_at_ms, move |_| {
+            let interval = Interval::new(100, move || now.set(Utc::now().timestamp_millis()));
+            move || drop(interval)
+        });
+    }
+
+    let remaining_ms = props.ends_at_ms - (*now + props.clock_offset_ms);
+
+    {
+        let on_finished = props.on_finished.clone();
+        use_effect_with(
+            (props.ends_at_ms, remaining_ms <= 0),
+            move |(ends_at_ms, done)| {
+                if *done && finished.replace(Some(*ends_at_ms)) != Some(*ends_at_ms) {
+                    on_finished.emit(());
+                }
+                || ()
+            },
+        );
+    }
+
+    let (modifier, value) = match countdown_step(remaining_ms) {
+        Some(0) => ("go", i18n.t("countdown.go")),
+        Some(seconds) => ("counting", seconds.to_string()),
+        None => return html! {},
+    };
+
+    html! {
+        <div
+            class={classes!(
+                "konnekt-countdown",
+                format!("konnekt-countdown--{}", modifier),
+                props.classes.clone(),
+            )}
+            style={props.style.clone()}
+            role="timer"
+            aria-live="assertive"
+        >
+            <span class="konnekt-countdown__title">{i18n.t("countdown.title")}</span>
+            <span key={value.clone()} class="konnekt-countdown__value">{value}</span>
+        </div>
+    }
+}
+
+#[cfg(feature = "preview")]
+yew_preview::create_preview_with_tests!(
+    component: Countdown,
+    default_props: CountdownProps {
+        ends_at_ms: Utc::now().timestamp_millis() + 3_000,
+    },
+    variants: [],
+    tests: [
+        ("Has main container class", exists("konnekt-countdown")),
+        ("Has value class", exists("konnekt-countdown__value")),
+    ]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_countdown_step() {
+        assert_eq!(countdown_step(3_000), Some(3));
+        assert_eq!(countdown_step(2_001), Some(3));
+        assert_eq!(countdown_step(1), Some(1));
+        assert_eq!(countdown_step(0), Some(0));
+        assert_eq!(countdown_step(-999), Some(0));
+        assert_eq!(countdown_step(-1_000), None);
+    }
+}
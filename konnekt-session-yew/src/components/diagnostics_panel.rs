@@ -0,0 +1,203 @@
+use futures::StreamExt;
+use futures::channel::mpsc;
+use gloo_timers::future::TimeoutFuture;
+use konnekt_session_p2p::{ConnectivityReport, IceCandidate, IceServer};
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    RtcConfiguration, RtcIceServer, RtcPeerConnection, RtcPeerConnectionIceEvent, RtcSdpType,
+    RtcSessionDescriptionInit,
+};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct DiagnosticsPanelProps {
+    #[prop_or_else(IceServer::default_stun_servers)]
+    pub ice_servers: Vec<IceServer>,
+    /// Give up on candidate gathering after this long
+    #[prop_or(5_000)]
+    pub timeout_ms: u32,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Debug panel that gathers ICE candidates and shows a connectivity report
+#[function_component(DiagnosticsPanel)]
+pub fn diagnostics_panel(props: &DiagnosticsPanelProps) -> Html {
+    let report = use_state(|| None::<Result<ConnectivityReport, String>>);
+    let running = use_state(|| false);
+
+    let on_run = {
+        let report = report.clone();
+        let running = running.clone();
+        let ice_servers = props.ice_servers.clone();
+        let timeout_ms = props.timeout_ms;
+
+        Callback::from(move |_: MouseEvent| {
+            let report = report.clone();
+            let running = running.clone();
+            let ice_servers = ice_servers.clone();
+
+            running.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = gather_report(&ice_servers, timeout_ms).await;
+                if let Err(e) = &result {
+                    tracing::warn!("Connectivity diagnostics failed: {}", e);
+                }
+                report.set(Some(result));
+                running.set(false);
+            });
+        })
+    };
+
+    html! {
+        <details class={classes!("konnekt-diagnostics", props.classes.clone())} style={props.style.clone()}>
+            <summary class="konnekt-diagnostics__title">{"🩺 Connectivity diagnostics"}</summary>
+            <button
+                class="konnekt-diagnostics__run"
+                onclick={on_run}
+                disabled={*running}
+            >
+                {if *running { "Probing..." } else { "Run diagnostics" }}
+            </button>
+
+            {match &*report {
+                None => html! {},
+                Some(Err(e)) => html! {
+                    <div class="konnekt-diagnostics__error">{format!("✗ {}", e)}</div>
+                },
+                Some(Ok(report)) => render_report(report),
+            }}
+        </details>
+    }
+}
+
+fn render_report(report: &ConnectivityReport) -> Html {
+    let public = report.public_addresses();
+
+    html! {
+        <div class="konnekt-diagnostics__report">
+            <div class="konnekt-diagnostics__row">
+                <span class="konnekt-diagnostics__label">{"NAT type:"}</span>
+                <span class="konnekt-diagnostics__value">{report.nat_type.to_string()}</span>
+            </div>
+            <div class="konnekt-diagnostics__row">
+                <span class="konnekt-diagnostics__label">{"Gathering time:"}</span>
+                <span class="konnekt-diagnostics__value">
+                    {format!("{}ms", report.gathering_time_ms)}
+                </span>
+            </div>
+            <div class="konnekt-diagnostics__row">
+                <span class="konnekt-diagnostics__label">{"Public address:"}</span>
+                <span class="konnekt-diagnostics__value">
+                    {if public.is_empty() { "-".to_string() } else { public.join(", ") }}
+                </span>
+            </div>
+            <div class="konnekt-diagnostics__row">
+                <span class="konnekt-diagnostics__label">{"TURN relay:"}</span>
+                <span class="konnekt-diagnostics__value">
+                    {if report.turn_reachable() { "✓" } else { "✗" }}
+                </span>
+            </div>
+
+            <ul class="konnekt-diagnostics__candidates">
+                {for report.candidates.iter().map(|c| html! {
+                    <li>{format!("{:?} {} {}", c.kind, c.protocol, c.address)}</li>
+                })}
+            </ul>
+
+            {if let Some(advice) = report.recommendation() {
+                html! { <div class="konnekt-diagnostics__warning">{advice}</div> }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
+
+/// Gather ICE candidates with a throwaway `RTCPeerConnection`
+async fn gather_report(
+    ice_servers: &[IceServer],
+    timeout_ms: u32,
+) -> Result<ConnectivityReport, String> {
+    let js_err = |e: JsValue| format!("{:?}", e);
+
+    let servers = js_sys::Array::new();
+    for server in ice_servers {
+        let rtc_server = RtcIceServer::new();
+        let urls: js_sys::Array = server.urls.iter().map(|u| JsValue::from_str(u)).collect();
+        rtc_server.set_urls(&urls);
+        if let Some(username) = &server.username {
+            rtc_server.set_username(username);
+        }
+        if let Some(credential) = &server.credential {
+            rtc_server.set_credential(credential);
+        }
+        servers.push(&rtc_server);
+    }
+
+    let config = RtcConfiguration::new();
+    config.set_ice_servers(&servers);
+    let pc = RtcPeerConnection::new_with_configuration(&config).map_err(js_err)?;
+    // A data channel is needed for the offer to contain any media section
+    let _channel = pc.create_data_channel("diagnostics");
+
+    let (tx, mut rx) = mpsc::unbounded::<Option<String>>();
+    let on_candidate = Closure::<dyn FnMut(RtcPeerConnectionIceEvent)>::new(
+        move |event: RtcPeerConnectionIceEvent| {
+            let _ = tx.unbounded_send(event.candidate().map(|c| c.candidate()));
+        },
+    );
+    pc.set_onicecandidate(Some(on_candidate.as_ref().unchecked_ref()));
+
+    let started = js_sys::Date::now();
+
+    let offer = JsFuture::from(pc.create_offer()).await.map_err(js_err)?;
+    let sdp = js_sys::Reflect::get(&offer, &JsValue::from_str("sdp"))
+        .ok()
+        .and_then(|sdp| sdp.as_string())
+        .ok_or_else(|| "offer has no SDP".to_string())?;
+    let description = RtcSessionDescriptionInit::new(RtcSdpType::Offer);
+    description.set_sdp(&sdp);
+    JsFuture::from(pc.set_local_description(&description))
+        .await
+        .map_err(js_err)?;
+
+    let mut candidates = Vec::new();
+    let mut timeout = TimeoutFuture::new(timeout_ms);
+    loop {
+        match futures::future::select(rx.next(), &mut timeout).await {
+            // `None` candidate marks the end of gathering
+            futures::future::Either::Left((Some(Some(line)), _)) => {
+                candidates.extend(IceCandidate::parse(&line));
+            }
+            futures::future::Either::Left(_) => break,
+            futures::future::Either::Right(_) => {
+                tracing::debug!("Candidate gathering timed out after {}ms", timeout_ms);
+                break;
+            }
+        }
+    }
+
+    let gathering_time_ms = (js_sys::Date::now() - started) as u64;
+    pc.set_onicecandidate(None);
+    pc.close();
+
+    Ok(ConnectivityReport::from_candidates(candidates, gathering_time_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostics_panel_default_props() {
+        let props = yew::props!(DiagnosticsPanelProps {});
+
+        assert_eq!(props.timeout_ms, 5_000);
+        assert!(!props.ice_servers.is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+use yew::prelude::*;
+
+#[cfg(feature = "preview")]
+use yew_preview::prelude::*;
+#[cfg(feature = "preview")]
+use yew_preview::test_utils::{exists, has_text};
+
+/// Path prefix of join links, followed by the session ID
+pub const JOIN_PATH: &str = "/join/";
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct JoinLinkProps {
+    pub session_id: AttrValue,
+    /// Origin (and base path) of the app; the current page's origin if unset
+    #[prop_or_default]
+    pub base_url: Option<AttrValue>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Shareable `/join/<session id>` link with a button copying it to the
+/// clipboard
+#[function_component(JoinLink)]
+pub fn join_link(props: &JoinLinkProps) -> Html {
+    let copy_message = use_state(|| None::<&'static str>);
+
+    let base_url = props
+        .base_url
+        .as_ref()
+        .map(|base| base.to_string())
+        .or_else(|| web_sys::window()?.location().origin().ok())
+        .unwrap_or_default();
+    let url = join_url(&base_url, &props.session_id);
+
+    let on_copy = {
+        let url = url.clone();
+        let copy_message = copy_message.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(window) = web_sys::window() {
+                let clipboard = window.navigator().clipboard();
+                let url = url.clone();
+                let copy_message = copy_message.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    match wasm_bindgen_futures::JsFuture::from(clipboard.write_text(&url)).await {
+                        Ok(_) => copy_message.set(Some("✓ Join link copied!")),
+                        Err(_) => copy_message.set(Some("✗ Failed")),
+                    }
+                });
+            }
+        })
+    };
+
+    html! {
+        <div class={classes!("konnekt-join-link", props.classes.clone())} style={props.style.clone()}>
+            <a class="konnekt-join-link__url" href={url.clone()}>{url}</a>
+            <button class="konnekt-join-link__copy" onclick={on_copy} title="Copy Join Link">
+                {"🔗"}
+            </button>
+            {if let Some(message) = *copy_message {
+                html! { <span class="konnekt-join-link__message">{message}</span> }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
+
+/// Join link for `session_id` under `base_url`
+pub fn join_url(base_url: &str, session_id: &str) -> String {
+    format!(
+        "{}{}{}",
+        base_url.trim_end_matches('/'),
+        JOIN_PATH,
+        session_id
+    )
+}
+
+/// Session ID of a `/join/<session id>` path
+pub(crate) fn session_id_from_path(path: &str) -> Option<&str> {
+    let (_, rest) = path.split_once(JOIN_PATH)?;
+    let session_id = rest.trim_end_matches('/');
+    (!session_id.is_empty() && !session_id.contains('/')).then_some(session_id)
+}
+
+#[cfg(feature = "preview")]
+yew_preview::create_preview_with_tests!(
+    component: JoinLink,
+    default_props: JoinLinkProps {
+        session_id: AttrValue::from("a1b2-c3d4-e5f6"),
+        base_url: Some(AttrValue::from("https://konnektoren.help")),
+    },
+    variants: [],
+    tests: [
+        ("Has main container class", exists("konnekt-join-link")),
+        ("Has copy button class", exists("konnekt-join-link__copy")),
+        ("Shows join link", has_text("https://konnektoren.help/join/a1b2-c3d4-e5f6")),
+    ]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_url_round_trip() {
+        let url = join_url("https://konnektoren.help/app/", "a1b2");
+        assert_eq!(url, "https://konnektoren.help/app/join/a1b2");
+        assert_eq!(session_id_from_path("/app/join/a1b2"), Some("a1b2"));
+        assert_eq!(session_id_from_path("/join/a1b2/"), Some("a1b2"));
+        assert_eq!(session_id_from_path("/join/"), None);
+        assert_eq!(session_id_from_path("/"), None);
+    }
+}
@@ -0,0 +1,73 @@
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement, Node};
+use yew::prelude::*;
+
+/// Index of the item to focus after pressing `key` on item `current` of
+/// `len`, if the key navigates
+pub(crate) fn next_index(key: &str, current: Option<usize>, len: usize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    match (key, current) {
+        ("ArrowDown" | "ArrowRight", Some(i)) => Some((i + 1).min(len - 1)),
+        ("ArrowUp" | "ArrowLeft", Some(i)) => Some(i.saturating_sub(1)),
+        ("ArrowDown" | "ArrowRight", None) | ("Home", _) => Some(0),
+        ("ArrowUp" | "ArrowLeft", None) | ("End", _) => Some(len - 1),
+        _ => None,
+    }
+}
+
+/// `tabindex` of list item `index`: only the first is in the tab order, the
+/// others are reached with the arrow keys
+pub(crate) fn item_tabindex(index: usize) -> &'static str {
+    if index == 0 { "0" } else { "-1" }
+}
+
+/// `onkeydown` for a list moving focus between its children with the arrow,
+/// Home and End keys
+pub(crate) fn list_keydown() -> Callback<KeyboardEvent> {
+    Callback::from(|e: KeyboardEvent| {
+        let Some(list) = e.current_target().map(|t| t.unchecked_into::<Element>()) else {
+            return;
+        };
+        let items = list.children();
+        let len = items.length() as usize;
+        let focused: Option<Node> = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.active_element())
+            .map(Into::into);
+        let current = (0..len).find(|&i| {
+            items
+                .item(i as u32)
+                .zip(focused.as_ref())
+                .is_some_and(|(item, focused)| item.contains(Some(focused)))
+        });
+
+        if let Some(next) = next_index(&e.key(), current, len) {
+            e.prevent_default();
+            if let Some(item) = items
+                .item(next as u32)
+                .and_then(|item| item.dyn_into::<HtmlElement>().ok())
+            {
+                let _ = item.focus();
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_index() {
+        assert_eq!(next_index("ArrowDown", Some(0), 3), Some(1));
+        assert_eq!(next_index("ArrowDown", Some(2), 3), Some(2));
+        assert_eq!(next_index("ArrowUp", Some(0), 3), Some(0));
+        assert_eq!(next_index("ArrowUp", None, 3), Some(2));
+        assert_eq!(next_index("Home", Some(2), 3), Some(0));
+        assert_eq!(next_index("End", Some(0), 3), Some(2));
+        assert_eq!(next_index("Enter", Some(0), 3), None);
+        assert_eq!(next_index("ArrowDown", None, 0), None);
+    }
+}
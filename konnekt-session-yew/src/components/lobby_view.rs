@@ -1,41 +1,55 @@
 use crate::components::{ActivityList, ParticipantList, SessionInfo};
-use crate::hooks::use_session;
+use crate::hooks::{use_activities, use_i18n, use_lobby_state, use_session};
 use yew::prelude::*;
 
+#[derive(Properties, PartialEq, Clone)]
+pub struct LobbyViewProps {
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
 /// Main lobby view component
 ///
 /// Combines session info, participants, and activities into a complete UI.
 #[function_component(LobbyView)]
-pub fn lobby_view() -> Html {
+pub fn lobby_view(props: &LobbyViewProps) -> Html {
     let session = use_session();
+    let lobby_state = use_lobby_state();
+    let activities = use_activities();
+    let i18n = use_i18n();
 
     html! {
-        <div class="konnekt-lobby-view">
-            <h1 class="konnekt-lobby-view__title">{"Lobby"}</h1>
+        <div class={classes!("konnekt-lobby-view", props.classes.clone())} style={props.style.clone()}>
+            <h1 class="konnekt-lobby-view__title">{i18n.t("lobby.title")}</h1>
 
             <SessionInfo
-                session_id={session.session_id.to_string()}
-                peer_count={session.peer_count}
-                is_host={session.is_host}
+                session_id={lobby_state.session_id.to_string()}
+                peer_count={lobby_state.peer_count}
+                is_host={lobby_state.is_host}
             />
 
-            {if let Some(lobby) = session.lobby.as_ref() {
+            {if let Some(lobby) = lobby_state.lobby.as_ref() {
                 html! {
                     <div class="konnekt-lobby-view__content">
                         <div class="konnekt-lobby-view__section">
                             <ParticipantList
                                 lobby={lobby.clone()}
                                 local_participant_id={session.get_local_participant_id()}
+                                presence={session.presence.clone()}
                             />
                         </div>
                         <div class="konnekt-lobby-view__section">
-                            <ActivityList lobby={lobby.clone()} active_run={session.active_run.clone()} />
+                            <ActivityList lobby={lobby.clone()} active_run={activities.active_run.clone()} />
                         </div>
                     </div>
                 }
             } else {
                 html! {
-                    <p class="konnekt-lobby-view__loading">{"Syncing lobby..."}</p>
+                    <p class="konnekt-lobby-view__loading" role="status" aria-busy="true">
+                        {i18n.t("lobby.syncing")}
+                    </p>
                 }
             }}
         </div>
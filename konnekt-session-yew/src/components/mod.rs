@@ -1,18 +1,60 @@
 //! UI components for Konnekt Session
 
 mod activity_list;
+mod activity_queue_editor;
+mod activity_runner;
+mod avatar;
+mod avatar_picker;
+mod chat_input;
+mod chat_panel;
+mod connection_banner;
+mod countdown;
+mod diagnostics_panel;
+mod join_link;
+mod list_navigation;
 mod lobby_view;
 mod participant_list;
+mod ready_check;
+mod session_dev_tools;
+mod session_error_boundary;
 mod session_info;
+mod session_loading;
+mod skeleton;
+mod spectator_view;
+mod toast_stack;
 pub use activity_list::ActivityList;
-pub use lobby_view::LobbyView;
-pub use participant_list::ParticipantList;
+pub use activity_queue_editor::{ActivityQueueEditor, ActivityQueueEditorProps};
+pub use activity_runner::{ActivityProps, ActivityRunner, ActivityRunnerProps};
+pub use avatar::{Avatar, AvatarProps};
+pub use avatar_picker::{AVATAR_EMOJIS, AvatarPicker, AvatarPickerProps};
+pub use chat_input::{ChatInput, ChatInputProps};
+pub use chat_panel::{ChatPanel, ChatPanelProps};
+pub use connection_banner::{ConnectionBanner, ConnectionBannerProps};
+pub use countdown::{Countdown, CountdownProps};
+pub use diagnostics_panel::{DiagnosticsPanel, DiagnosticsPanelProps};
+pub(crate) use join_link::session_id_from_path;
+pub use join_link::{JOIN_PATH, JoinLink, JoinLinkProps, join_url};
+pub use lobby_view::{LobbyView, LobbyViewProps};
+pub use participant_list::{ParticipantGrouping, ParticipantList, ParticipantSort};
+pub use ready_check::{ReadyCheck, ReadyCheckProps};
+pub use session_dev_tools::{SessionDevTools, SessionDevToolsProps};
+pub use session_error_boundary::{
+    SessionErrorBoundary, SessionErrorBoundaryProps, SessionErrorView, SessionErrorViewProps,
+};
 pub use session_info::SessionInfo;
+pub use session_loading::{SessionLoading, SessionLoadingProps};
+pub use skeleton::{Skeleton, SkeletonProps, SkeletonShape};
+pub use spectator_view::{SpectatorView, SpectatorViewProps};
+pub use toast_stack::{ToastStack, ToastStackProps};
 mod activity_planner;
 mod activity_submission;
+mod results_chart;
 mod results_view;
+mod session_analytics;
 mod submission_status;
 pub use activity_planner::ActivityPlanner;
 pub use activity_submission::ActivitySubmission;
+pub use results_chart::{ResultsChart, ResultsChartProps};
 pub use results_view::ResultsView;
+pub use session_analytics::{SessionAnalytics, SessionAnalyticsProps};
 pub use submission_status::SubmissionStatus;
@@ -1,93 +1,493 @@
 use konnekt_session_core::Lobby;
+use konnekt_session_p2p::Presence;
 use uuid::Uuid;
+use web_sys::{Element, HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
+use crate::components::Avatar;
+use crate::components::list_navigation::{item_tabindex, list_keydown};
+use crate::hooks::{ParticipantView, PendingCommand, PendingStatus, participant_views, use_i18n};
+use crate::providers::I18n;
+
 #[cfg(feature = "preview")]
 use yew_preview::prelude::*;
 #[cfg(feature = "preview")]
 use yew_preview::test_utils::{exists, has_text};
 
+/// Rows rendered above and below the viewport of a windowed list
+const OVERSCAN_ROWS: usize = 5;
+
+/// Order of the participant list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParticipantSort {
+    /// Host first, then in the order they joined
+    #[default]
+    Joined,
+    Name,
+    /// Highest score first
+    Score,
+}
+
+/// Sections of the participant list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParticipantGrouping {
+    #[default]
+    None,
+    /// Active participants, then spectators
+    Mode,
+    /// By team name, participants without a team last
+    Team,
+}
+
 #[derive(Properties, PartialEq, Clone)]
 pub struct ParticipantListProps {
     pub lobby: Lobby,
     #[prop_or_default]
     pub local_participant_id: Option<Uuid>,
+    /// Transient presence signals of other participants
+    #[prop_or_default]
+    pub presence: Vec<(Uuid, Presence)>,
+    /// Marks participants whose mode change the host has not confirmed yet
+    #[prop_or_default]
+    pub pending_commands: Vec<PendingCommand>,
+    /// Scores to show and sort by, e.g. totals over the session's activities
+    #[prop_or_default]
+    pub scores: Vec<(Uuid, u32)>,
+    /// Team of each participant, for grouping by team
+    #[prop_or_default]
+    pub teams: Vec<(Uuid, String)>,
+    /// Initial order
+    #[prop_or_default]
+    pub sort: ParticipantSort,
+    /// Initial grouping
+    #[prop_or_default]
+    pub grouping: ParticipantGrouping,
+    /// Search, sort and grouping controls show beyond this many participants
+    #[prop_or(10)]
+    pub controls_after: usize,
+    /// Only the rows in view are rendered beyond this many participants
+    #[prop_or(100)]
+    pub virtualize_after: usize,
+    /// Height of a row in the windowed list
+    #[prop_or(48)]
+    pub row_height_px: u32,
+    /// Height of the scrolling viewport of the windowed list
+    #[prop_or(480)]
+    pub viewport_height_px: u32,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Participants matching `query`, sorted and split into groups
+pub(crate) fn participant_rows(
+    participants: Vec<ParticipantView>,
+    query: &str,
+    sort: ParticipantSort,
+    grouping: ParticipantGrouping,
+    scores: &[(Uuid, u32)],
+    teams: &[(Uuid, String)],
+    i18n: &I18n,
+) -> Vec<ParticipantRow> {
+    let score = |id: Uuid| {
+        scores
+            .iter()
+            .find(|(s, _)| *s == id)
+            .map(|(_, score)| *score)
+    };
+    let team = |id: Uuid| {
+        teams
+            .iter()
+            .find(|(t, _)| *t == id)
+            .map(|(_, team)| team.as_str())
+    };
+
+    let query = query.trim().to_lowercase();
+    let mut participants: Vec<_> = participants
+        .into_iter()
+        .filter(|p| query.is_empty() || p.name.to_lowercase().contains(&query))
+        .collect();
+    match sort {
+        ParticipantSort::Joined => {}
+        ParticipantSort::Name => {
+            participants.sort_by_cached_key(|p| (p.name.to_lowercase(), p.id));
+        }
+        ParticipantSort::Score => participants.sort_by_cached_key(|p| {
+            (
+                std::cmp::Reverse(score(p.id).unwrap_or(0)),
+                p.name.to_lowercase(),
+                p.id,
+            )
+        }),
+    }
+
+    let mut groups: Vec<(Option<String>, Vec<ParticipantView>)> = Vec::new();
+    for participant in participants {
+        let group = match grouping {
+            ParticipantGrouping::None => None,
+            ParticipantGrouping::Mode => Some(if participant.active {
+                i18n.t("participants.active")
+            } else {
+                i18n.t("participants.spectating")
+            }),
+            ParticipantGrouping::Team => Some(
+                team(participant.id)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| i18n.t("participants.no_team")),
+            ),
+        };
+        match groups.iter_mut().find(|(g, _)| *g == group) {
+            Some((_, members)) => members.push(participant),
+            None => groups.push((group, vec![participant])),
+        }
+    }
+    match grouping {
+        ParticipantGrouping::None => {}
+        ParticipantGrouping::Mode => {
+            groups.sort_by_key(|(_, members)| !members[0].active);
+        }
+        ParticipantGrouping::Team => groups.sort_by_cached_key(|(_, members)| {
+            let team = team(members[0].id);
+            (team.is_none(), team.map(str::to_lowercase))
+        }),
+    }
+
+    groups
+        .into_iter()
+        .flat_map(|(group, members)| {
+            let header = group.map(|label| ParticipantRow::Group {
+                label,
+                count: members.len(),
+            });
+            header
+                .into_iter()
+                .chain(members.into_iter().map(ParticipantRow::Participant))
+        })
+        .collect()
+}
+
+/// A row of the participant list
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ParticipantRow {
+    Group { label: String, count: usize },
+    Participant(ParticipantView),
+}
+
+/// Rows to render of `total` rows of `row_px` when scrolled to `scroll_top`
+pub(crate) fn visible_rows(
+    scroll_top: u32,
+    viewport_px: u32,
+    row_px: u32,
+    total: usize,
+) -> std::ops::Range<usize> {
+    let row_px = row_px.max(1) as usize;
+    let first = (scroll_top as usize / row_px).saturating_sub(OVERSCAN_ROWS);
+    let last = (scroll_top as usize + viewport_px as usize).div_ceil(row_px) + OVERSCAN_ROWS;
+    first.min(total)..last.min(total)
 }
 
 /// Displays list of participants in the lobby
+///
+/// Large lobbies get search, sorting and grouping controls, and only the
+/// rows in view are rendered.
 #[function_component(ParticipantList)]
 pub fn participant_list(props: &ParticipantListProps) -> Html {
-    let participants = props.lobby.participants();
+    let participants = participant_views(&props.lobby, props.local_participant_id, &props.presence);
+    let i18n = use_i18n();
+    let query = use_state(String::new);
+    let sort = use_state(|| props.sort);
+    let grouping = use_state(|| props.grouping);
+    let scroll_top = use_state(|| 0u32);
+
+    let total = participants.len();
+    let title = i18n.t_with("participants.title", &[("count", &total)]);
+    let show_controls = total > props.controls_after;
+    let rows = participant_rows(
+        participants,
+        &query,
+        *sort,
+        *grouping,
+        &props.scores,
+        &props.teams,
+        &i18n,
+    );
+    let virtualized = total > props.virtualize_after;
+    let row_px = props.row_height_px;
+    let window = if virtualized {
+        visible_rows(*scroll_top, props.viewport_height_px, row_px, rows.len())
+    } else {
+        0..rows.len()
+    };
+    let first = window.start;
+    let row_style = virtualized.then(|| format!("height: {}px; box-sizing: border-box;", row_px));
+
+    let on_search = {
+        let query = query.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            query.set(input.value());
+        })
+    };
+    let on_sort = {
+        let sort = sort.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            sort.set(match select.value().as_str() {
+                "name" => ParticipantSort::Name,
+                "score" => ParticipantSort::Score,
+                _ => ParticipantSort::Joined,
+            });
+        })
+    };
+    let on_group = {
+        let grouping = grouping.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            grouping.set(match select.value().as_str() {
+                "mode" => ParticipantGrouping::Mode,
+                "team" => ParticipantGrouping::Team,
+                _ => ParticipantGrouping::None,
+            });
+        })
+    };
+    let on_scroll = {
+        let scroll_top = scroll_top.clone();
+        Callback::from(move |e: Event| {
+            let viewport: Element = e.target_unchecked_into();
+            scroll_top.set(viewport.scroll_top().max(0) as u32);
+        })
+    };
+
+    let items_style = virtualized.then(|| {
+        format!(
+            "padding-top: {}px; padding-bottom: {}px;",
+            window.start as u32 * row_px,
+            (rows.len() - window.end) as u32 * row_px,
+        )
+    });
 
     html! {
-        <div class="konnekt-participant-list">
+        <div class={classes!("konnekt-participant-list", props.classes.clone())} style={props.style.clone()}>
             <h3 class="konnekt-participant-list__title">
-                {"Participants ("}
-                {participants.len()}
-                {")"}
+                {title.clone()}
             </h3>
-            <ul class="konnekt-participant-list__items">
-                {for participants.values().map(|participant| {
-                    let role_icon = if participant.is_host() {
-                        "👑"
-                    } else {
-                        "👤"
-                    };
-
-                    let role_text = if participant.is_host() {
-                        " (Host)"
-                    } else {
-                        ""
-                    };
-                    let is_me = Some(participant.id()) == props.local_participant_id;
-
-                    let mode_class = if participant.can_submit_results() {
-                        "active"
-                    } else {
-                        "spectating"
-                    };
-
-                    // ✅ Build tooltip with participant ID
-                    let tooltip = format!(
-                        "ID: {}\nJoined: {}",
-                        participant.id(),
-                        participant.joined_at()
-                    );
-
-                    html! {
-                        <li
-                            class={classes!("konnekt-participant-list__item", mode_class)}
-                            title={tooltip}
+            {if show_controls {
+                html! {
+                    <div class="konnekt-participant-list__controls">
+                        <input
+                            type="search"
+                            class="konnekt-participant-list__search"
+                            placeholder={i18n.t("participants.search")}
+                            aria-label={i18n.t("participants.search")}
+                            value={(*query).clone()}
+                            oninput={on_search}
+                        />
+                        <select
+                            class="konnekt-participant-list__sort"
+                            aria-label={i18n.t("participants.sort")}
+                            onchange={on_sort}
                         >
-                            <span class="konnekt-participant-list__icon">{role_icon}</span>
-                            <span class="konnekt-participant-list__name">
-                                {participant.name()}
-                                <span class="konnekt-participant-list__role">{role_text}</span>
-                                {if is_me {
-                                    html! { <span class="konnekt-participant-list__you">{" (you)"}</span> }
-                                } else {
-                                    html! {}
-                                }}
-                            </span>
-                            <span class="konnekt-participant-list__mode">
-                                {if participant.can_submit_results() {
-                                    "🎮 Active"
-                                } else {
-                                    "👁️  Spectating"
-                                }}
-                            </span>
-                            // ✅ Show short ID for debugging
-                            <span class="konnekt-participant-list__id">
-                                {format!("#{}", &participant.id().to_string()[..8])}
-                            </span>
-                        </li>
-                    }
-                })}
-            </ul>
+                            <option value="joined" selected={*sort == ParticipantSort::Joined}>
+                                {i18n.t("participants.sort_joined")}
+                            </option>
+                            <option value="name" selected={*sort == ParticipantSort::Name}>
+                                {i18n.t("participants.sort_name")}
+                            </option>
+                            <option value="score" selected={*sort == ParticipantSort::Score}>
+                                {i18n.t("participants.sort_score")}
+                            </option>
+                        </select>
+                        <select
+                            class="konnekt-participant-list__grouping"
+                            aria-label={i18n.t("participants.group")}
+                            onchange={on_group}
+                        >
+                            <option value="none" selected={*grouping == ParticipantGrouping::None}>
+                                {i18n.t("participants.group_none")}
+                            </option>
+                            <option value="mode" selected={*grouping == ParticipantGrouping::Mode}>
+                                {i18n.t("participants.group_mode")}
+                            </option>
+                            <option value="team" selected={*grouping == ParticipantGrouping::Team}>
+                                {i18n.t("participants.group_team")}
+                            </option>
+                        </select>
+                    </div>
+                }
+            } else {
+                html! {}
+            }}
+            {if rows.is_empty() && !query.is_empty() {
+                html! {
+                    <p class="konnekt-participant-list__empty">
+                        {i18n.t_with("participants.no_match", &[("query", &*query)])}
+                    </p>
+                }
+            } else {
+                html! {}
+            }}
+            <div
+                class={classes!("konnekt-participant-list__viewport", virtualized.then_some("virtualized"))}
+                style={virtualized.then(|| format!("height: {}px;", props.viewport_height_px))}
+                onscroll={on_scroll}
+            >
+                <ul
+                    class="konnekt-participant-list__items"
+                    style={items_style}
+                    role="list"
+                    aria-label={title.clone()}
+                    onkeydown={list_keydown()}
+                >
+                    {for rows[window].iter().enumerate().map(|(i, row)| match row {
+                        ParticipantRow::Group { label, count } => html! {
+                            <li
+                                key={format!("group-{}", label)}
+                                class="konnekt-participant-list__group"
+                                style={row_style.clone()}
+                                role="heading"
+                                aria-level="4"
+                                tabindex={item_tabindex(first + i)}
+                            >
+                                {format!("{} ({})", label, count)}
+                            </li>
+                        },
+                        ParticipantRow::Participant(participant) => {
+                            let score = props
+                                .scores
+                                .iter()
+                                .find(|(id, _)| *id == participant.id)
+                                .map(|(_, score)| *score);
+                            render_participant(
+                                participant,
+                                score,
+                                props,
+                                &i18n,
+                                row_style.clone(),
+                                item_tabindex(first + i),
+                            )
+                        }
+                    })}
+                </ul>
+            </div>
         </div>
     }
 }
 
+fn render_participant(
+    participant: &ParticipantView,
+    score: Option<u32>,
+    props: &ParticipantListProps,
+    i18n: &I18n,
+    row_style: Option<String>,
+    tabindex: &'static str,
+) -> Html {
+    let role_text = if participant.is_host {
+        format!(" ({})", i18n.t("participants.host"))
+    } else {
+        String::new()
+    };
+
+    let presence = participant.presence.map(|presence| match presence {
+        Presence::Typing => i18n.t("participants.typing"),
+        Presence::Answering { .. } => i18n.t("participants.answering"),
+    });
+
+    let mode_class = if participant.active {
+        "active"
+    } else {
+        "spectating"
+    };
+
+    let sync = props
+        .pending_commands
+        .iter()
+        .rev()
+        .find(|c| c.toggled_participant() == Some(participant.id))
+        .map(|c| match &c.status {
+            PendingStatus::Pending => ("pending", "⏳", i18n.t("pending.saving")),
+            PendingStatus::RolledBack { reason } => (
+                "rolled-back",
+                "↩️",
+                reason
+                    .clone()
+                    .unwrap_or_else(|| i18n.t("pending.rolled_back")),
+            ),
+        });
+
+    // ✅ Build tooltip with participant ID
+    let tooltip = i18n.t_with(
+        "participants.tooltip",
+        &[("id", &participant.id), ("joined", &participant.joined_at)],
+    );
+
+    html! {
+        <li
+            key={participant.id.to_string()}
+            class={classes!(
+                "konnekt-participant-list__item",
+                mode_class,
+                sync.as_ref().map(|(class, _, _)| *class),
+            )}
+            title={tooltip}
+            style={row_style}
+            {tabindex}
+        >
+            <span class="konnekt-participant-list__icon" aria-hidden="true">
+                <Avatar
+                    participant_id={participant.id}
+                    name={participant.name.clone()}
+                    avatar={participant.avatar.clone()}
+                />
+                {if participant.is_host {
+                    html! { <span class="konnekt-participant-list__crown">{"👑"}</span> }
+                } else {
+                    html! {}
+                }}
+            </span>
+            <span class="konnekt-participant-list__name">
+                {participant.name.clone()}
+                <span class="konnekt-participant-list__role">{role_text}</span>
+                {if participant.is_me {
+                    html! { <span class="konnekt-participant-list__you">{format!(" ({})", i18n.t("participants.you"))}</span> }
+                } else {
+                    html! {}
+                }}
+                {if let Some(text) = presence {
+                    html! { <span class="konnekt-participant-list__presence">{" ✍️ "}{text}</span> }
+                } else {
+                    html! {}
+                }}
+            </span>
+            <span class="konnekt-participant-list__mode">
+                {if participant.active {
+                    format!("🎮 {}", i18n.t("participants.active"))
+                } else {
+                    format!("👁️  {}", i18n.t("participants.spectating"))
+                }}
+                {if let Some((_, icon, text)) = sync {
+                    html! { <span class="konnekt-participant-list__sync" title={text}>{format!(" {}", icon)}</span> }
+                } else {
+                    html! {}
+                }}
+            </span>
+            {if let Some(score) = score {
+                html! {
+                    <span class="konnekt-participant-list__score">
+                        {i18n.t_with("participants.score", &[("score", &score)])}
+                    </span>
+                }
+            } else {
+                html! {}
+            }}
+            // ✅ Show short ID for debugging
+            <span class="konnekt-participant-list__id">
+                {format!("#{}", &participant.id.to_string()[..8])}
+            </span>
+        </li>
+    }
+}
+
 #[cfg(feature = "preview")]
 mod preview_fixtures {
     use super::*;
@@ -104,6 +504,28 @@ mod preview_fixtures {
             .unwrap();
         lobby
     }
+
+    fn student_id(i: u128) -> Uuid {
+        Uuid::from_u128(i)
+    }
+
+    /// A classroom of 150 students
+    pub fn make_large_lobby() -> Lobby {
+        let host = Participant::new_host("Teacher".to_string()).unwrap();
+        let mut lobby = Lobby::new("Classroom".to_string(), host).unwrap();
+        for i in 1..=150 {
+            let student =
+                Participant::guest_with_id(student_id(i), format!("Student {}", i)).unwrap();
+            lobby.add_guest(student).unwrap();
+        }
+        lobby
+    }
+
+    pub fn make_large_scores() -> Vec<(Uuid, u32)> {
+        (1..=150)
+            .map(|i| (student_id(i), (i * 37 % 100) as u32))
+            .collect()
+    }
 }
 
 #[cfg(feature = "preview")]
@@ -112,7 +534,16 @@ yew_preview::create_preview_with_tests!(
     default_props: ParticipantListProps {
         lobby: preview_fixtures::make_sample_lobby(),
     },
-    variants: [],
+    variants: [
+        (
+            "Large Class",
+            ParticipantListProps {
+                lobby: preview_fixtures::make_large_lobby(),
+                scores: preview_fixtures::make_large_scores(),
+                sort: ParticipantSort::Score,
+            }
+        ),
+    ],
     tests: [
         ("Has main container class", exists("konnekt-participant-list")),
         ("Has title tag", exists("h3")),
@@ -163,4 +594,85 @@ mod tests {
         let bob = participants.iter().find(|p| !p.is_host()).unwrap();
         assert_eq!(bob.name(), "Bob");
     }
+
+    fn labels(rows: &[ParticipantRow]) -> Vec<String> {
+        rows.iter()
+            .map(|row| match row {
+                ParticipantRow::Group { label, count } => format!("[{} ({})]", label, count),
+                ParticipantRow::Participant(p) => p.name.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_participant_rows() {
+        let i18n = I18n::default();
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let alice = host.id();
+        let mut lobby = Lobby::new("Test Lobby".to_string(), host).unwrap();
+        let bob = Participant::new_guest("Bob".to_string()).unwrap();
+        let charlie = Participant::new_guest("charlie".to_string()).unwrap();
+        let (bob, charlie) = {
+            let ids = (bob.id(), charlie.id());
+            lobby.add_guest(bob).unwrap();
+            lobby.add_guest(charlie).unwrap();
+            ids
+        };
+        let mut views = participant_views(&lobby, None, &[]);
+        views.iter_mut().find(|p| p.id == charlie).unwrap().active = false;
+        let scores = [(bob, 30), (charlie, 10)];
+        let teams = [(alice, "Red".to_string()), (charlie, "Blue".to_string())];
+        let rows = |query, sort, grouping| {
+            labels(&participant_rows(
+                views.clone(),
+                query,
+                sort,
+                grouping,
+                &scores,
+                &teams,
+                &i18n,
+            ))
+        };
+
+        assert_eq!(
+            rows("", ParticipantSort::Score, ParticipantGrouping::None),
+            ["Bob", "charlie", "Alice"]
+        );
+        assert_eq!(
+            rows("", ParticipantSort::Name, ParticipantGrouping::None),
+            ["Alice", "Bob", "charlie"]
+        );
+        assert_eq!(
+            rows("CH", ParticipantSort::Joined, ParticipantGrouping::None),
+            ["charlie"]
+        );
+        assert_eq!(
+            rows("", ParticipantSort::Name, ParticipantGrouping::Mode),
+            [
+                "[Active (2)]",
+                "Alice",
+                "Bob",
+                "[Spectating (1)]",
+                "charlie"
+            ]
+        );
+        assert_eq!(
+            rows("", ParticipantSort::Name, ParticipantGrouping::Team),
+            [
+                "[Blue (1)]",
+                "charlie",
+                "[Red (1)]",
+                "Alice",
+                "[No team (1)]",
+                "Bob"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_visible_rows() {
+        assert_eq!(visible_rows(0, 480, 48, 1_000), 0..15);
+        assert_eq!(visible_rows(4_800, 480, 48, 1_000), 95..115);
+        assert_eq!(visible_rows(0, 480, 48, 3), 0..3);
+    }
 }
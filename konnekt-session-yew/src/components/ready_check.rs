@@ -0,0 +1,154 @@
+use konnekt_session_core::Lobby;
+use uuid::Uuid;
+use yew::prelude::*;
+
+use crate::hooks::{participant_views, use_i18n};
+
+#[cfg(feature = "preview")]
+use yew_preview::prelude::*;
+#[cfg(feature = "preview")]
+use yew_preview::test_utils::{exists, has_text};
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct ReadyCheckProps {
+    pub lobby: Lobby,
+    #[prop_or_default]
+    pub local_participant_id: Option<Uuid>,
+    /// Show the button starting the countdown (host or co-host)
+    #[prop_or_default]
+    pub can_start: bool,
+    /// Length of the countdown in seconds
+    #[prop_or(3)]
+    pub countdown_secs: u32,
+    #[prop_or_default]
+    pub on_set_ready: Callback<bool>,
+    /// Emits `countdown_secs`
+    #[prop_or_default]
+    pub on_start_countdown: Callback<u32>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Shows which active participants are ready for the next activity, lets the
+/// local participant say so and the host start the countdown
+#[function_component(ReadyCheck)]
+pub fn ready_check(props: &ReadyCheckProps) -> Html {
+    let i18n = use_i18n();
+    let lobby = &props.lobby;
+    let active: Vec<_> = participant_views(lobby, props.local_participant_id, &[])
+        .into_iter()
+        .filter(|p| p.active)
+        .collect();
+    let ready_count = active.iter().filter(|p| lobby.is_ready(p.id)).count();
+    let me = active.iter().find(|p| p.is_me);
+
+    html! {
+        <div class={classes!("konnekt-ready-check", props.classes.clone())} style={props.style.clone()}>
+            <h3 class="konnekt-ready-check__title">
+                {i18n.t_with(
+                    "ready.title",
+                    &[("ready", &ready_count), ("count", &active.len())],
+                )}
+            </h3>
+            <ul class="konnekt-ready-check__items">
+                {for active.iter().map(|participant| {
+                    let ready = lobby.is_ready(participant.id);
+                    html! {
+                        <li
+                            key={participant.id.to_string()}
+                            class={classes!(
+                                "konnekt-ready-check__item",
+                                if ready { "ready" } else { "waiting" },
+                            )}
+                        >
+                            <span class="konnekt-ready-check__icon" aria-hidden="true">
+                                {if ready { "✅" } else { "⏳" }}
+                            </span>
+                            <span class="konnekt-ready-check__name">{participant.name.clone()}</span>
+                            <span class="konnekt-sr-only">
+                                {if ready { i18n.t("ready.is_ready") } else { i18n.t("ready.is_waiting") }}
+                            </span>
+                        </li>
+                    }
+                })}
+            </ul>
+            <div class="konnekt-ready-check__actions">
+                {match me {
+                    Some(me) => {
+                        let ready = lobby.is_ready(me.id);
+                        let on_set_ready = props.on_set_ready.clone();
+                        html! {
+                            <button
+                                class={classes!(
+                                    "konnekt-btn",
+                                    if ready { "konnekt-btn--secondary" } else { "konnekt-btn--primary" },
+                                )}
+                                aria-pressed={ready.to_string()}
+                                onclick={move |_| on_set_ready.emit(!ready)}
+                            >
+                                {if ready { i18n.t("ready.not_ready") } else { i18n.t("ready.ready") }}
+                            </button>
+                        }
+                    }
+                    None => html! {},
+                }}
+                {if props.can_start {
+                    let on_start_countdown = props.on_start_countdown.clone();
+                    let seconds = props.countdown_secs;
+                    html! {
+                        <button
+                            class="konnekt-btn konnekt-btn--primary konnekt-ready-check__start"
+                            onclick={move |_| on_start_countdown.emit(seconds)}
+                        >
+                            {if lobby.all_ready() {
+                                i18n.t("ready.start")
+                            } else {
+                                i18n.t_with(
+                                    "ready.start_anyway",
+                                    &[("count", &(active.len() - ready_count))],
+                                )
+                            }}
+                        </button>
+                    }
+                } else {
+                    html! {}
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[cfg(feature = "preview")]
+mod preview_fixtures {
+    use konnekt_session_core::{Lobby, Participant};
+
+    pub fn make_sample_lobby() -> Lobby {
+        let host = Participant::new_host("Alice".to_string()).unwrap();
+        let host_id = host.id();
+        let mut lobby = Lobby::new("Preview Lobby".to_string(), host).unwrap();
+        lobby
+            .add_guest(Participant::new_guest("Bob".to_string()).unwrap())
+            .unwrap();
+        lobby.set_ready(host_id, true).unwrap();
+        lobby
+    }
+}
+
+#[cfg(feature = "preview")]
+yew_preview::create_preview_with_tests!(
+    component: ReadyCheck,
+    default_props: ReadyCheckProps {
+        lobby: preview_fixtures::make_sample_lobby(),
+        can_start: true,
+    },
+    variants: [],
+    tests: [
+        ("Has main container class", exists("konnekt-ready-check")),
+        ("Has ready item", exists("ready")),
+        ("Has start button class", exists("konnekt-ready-check__start")),
+        ("Shows ready count", has_text("Ready? (1/2)")),
+        ("Contains Bob", has_text("Bob")),
+    ]
+);
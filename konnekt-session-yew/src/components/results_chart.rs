@@ -0,0 +1,314 @@
+use konnekt_session_core::ResultsAnalytics;
+use konnekt_session_core::analytics::{RoundProgression, ScoreDistribution};
+use yew::prelude::*;
+
+use super::avatar::avatar_hue;
+use crate::hooks::use_i18n;
+
+#[cfg(feature = "preview")]
+use yew_preview::prelude::*;
+#[cfg(feature = "preview")]
+use yew_preview::test_utils::{exists, has_text};
+
+/// Size of the SVG drawing area (it scales to the container)
+const WIDTH: f64 = 320.0;
+const HEIGHT: f64 = 160.0;
+/// Room for the axis labels on the left and at the bottom
+const LEFT: f64 = 28.0;
+const BOTTOM: f64 = 20.0;
+const TOP: f64 = 8.0;
+const RIGHT: f64 = 8.0;
+
+/// A bar of the distribution chart, in SVG units
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct BarRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// One bar per bucket, as high as its share of the fullest bucket
+pub(crate) fn bar_rects(distribution: &ScoreDistribution) -> Vec<BarRect> {
+    let count = distribution.buckets.len().max(1) as f64;
+    let slot = (WIDTH - LEFT - RIGHT) / count;
+    let max = distribution.max_count().max(1) as f64;
+    distribution
+        .buckets
+        .iter()
+        .enumerate()
+        .map(|(index, bucket)| {
+            let height = (HEIGHT - TOP - BOTTOM) * bucket.count as f64 / max;
+            BarRect {
+                x: LEFT + slot * index as f64 + slot * 0.1,
+                y: HEIGHT - BOTTOM - height,
+                width: slot * 0.8,
+                height,
+            }
+        })
+        .collect()
+}
+
+/// SVG `points` of a running total, starting at zero before round one
+pub(crate) fn line_points(cumulative: &[u32], rounds: usize, max_total: u32) -> String {
+    let step = (WIDTH - LEFT - RIGHT) / rounds.max(1) as f64;
+    let scale = (HEIGHT - TOP - BOTTOM) / max_total.max(1) as f64;
+    std::iter::once(0)
+        .chain(cumulative.iter().copied())
+        .enumerate()
+        .map(|(round, total)| {
+            format!(
+                "{:.1},{:.1}",
+                LEFT + step * round as f64,
+                HEIGHT - BOTTOM - scale * total as f64
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn axes() -> Html {
+    let bottom = (HEIGHT - BOTTOM).to_string();
+    html! {
+        <g class="konnekt-results-chart__axes">
+            <line x1={LEFT.to_string()} y1={TOP.to_string()} x2={LEFT.to_string()} y2={bottom.clone()} />
+            <line x1={LEFT.to_string()} y1={bottom.clone()} x2={(WIDTH - RIGHT).to_string()} y2={bottom} />
+        </g>
+    }
+}
+
+fn label(x: f64, y: f64, anchor: &'static str, text: String) -> Html {
+    html! {
+        <text class="konnekt-results-chart__label" x={x.to_string()} y={y.to_string()} text-anchor={anchor}>
+            {text}
+        </text>
+    }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct ResultsChartProps {
+    /// Usually `use_session().results`
+    pub results: ResultsAnalytics,
+    /// Bar chart of how the scores spread
+    #[prop_or(true)]
+    pub show_distribution: bool,
+    /// Line chart of everyone's running total per round
+    #[prop_or(true)]
+    pub show_progression: bool,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Score distribution and per-round progression of the completed runs, drawn
+/// as SVG from the same analytics as the TUI charts
+#[function_component(ResultsChart)]
+pub fn results_chart(props: &ResultsChartProps) -> Html {
+    let i18n = use_i18n();
+    let distribution = &props.results.distribution;
+    let progression = &props.results.progression;
+
+    let distribution_chart = if props.show_distribution {
+        let title = i18n.t("results_chart.distribution");
+        html! {
+            <figure class="konnekt-results-chart__figure">
+                <figcaption>
+                    {title.clone()}{" · "}
+                    {i18n.t_with("results_chart.results", &[("count", &distribution.total())])}
+                </figcaption>
+                <svg
+                    class="konnekt-results-chart__svg"
+                    viewBox={format!("0 0 {} {}", WIDTH, HEIGHT)}
+                    role="img"
+                    aria-label={title}
+                >
+                    {axes()}
+                    {label(LEFT - 4.0, TOP + 8.0, "end", distribution.max_count().to_string())}
+                    {label(LEFT - 4.0, HEIGHT - BOTTOM, "end", "0".to_string())}
+                    {for distribution.buckets.iter().zip(bar_rects(distribution)).map(|(bucket, bar)| {
+                        let range = format!("{}–{}", bucket.min, bucket.max);
+                        html! {
+                            <g class="konnekt-results-chart__bucket">
+                                <rect
+                                    class="konnekt-results-chart__bar"
+                                    x={bar.x.to_string()}
+                                    y={bar.y.to_string()}
+                                    width={bar.width.to_string()}
+                                    height={bar.height.to_string()}
+                                >
+                                    <title>{format!("{}: {}", range, bucket.count)}</title>
+                                </rect>
+                                {label(bar.x + bar.width / 2.0, HEIGHT - BOTTOM + 14.0, "middle", range)}
+                            </g>
+                        }
+                    })}
+                </svg>
+            </figure>
+        }
+    } else {
+        html! {}
+    };
+
+    let progression_chart = if props.show_progression {
+        let title = i18n.t("results_chart.progression");
+        let rounds = progression.rounds.len();
+        let max_total = progression.max_total();
+        html! {
+            <figure class="konnekt-results-chart__figure">
+                <figcaption>{title.clone()}</figcaption>
+                <svg
+                    class="konnekt-results-chart__svg"
+                    viewBox={format!("0 0 {} {}", WIDTH, HEIGHT)}
+                    role="img"
+                    aria-label={title}
+                >
+                    {axes()}
+                    {label(LEFT - 4.0, TOP + 8.0, "end", max_total.to_string())}
+                    {label(LEFT - 4.0, HEIGHT - BOTTOM, "end", "0".to_string())}
+                    {label(
+                        WIDTH - RIGHT,
+                        HEIGHT - BOTTOM + 14.0,
+                        "end",
+                        i18n.t_with("results_chart.round", &[("round", &rounds)]),
+                    )}
+                    {for progression.series.iter().map(|series| html! {
+                        <polyline
+                            class="konnekt-results-chart__line"
+                            points={line_points(&series.cumulative(), rounds, max_total)}
+                            stroke={format!("hsl({}, 65%, 45%)", avatar_hue(series.participant_id))}
+                        >
+                            <title>{series.participant_name.clone()}</title>
+                        </polyline>
+                    })}
+                </svg>
+                {legend(progression)}
+            </figure>
+        }
+    } else {
+        html! {}
+    };
+
+    html! {
+        <div class={classes!("konnekt-results-chart", props.classes.clone())} style={props.style.clone()}>
+            {distribution_chart}
+            {progression_chart}
+        </div>
+    }
+}
+
+fn legend(progression: &RoundProgression) -> Html {
+    html! {
+        <ul class="konnekt-results-chart__legend">
+            {for progression.series.iter().map(|series| html! {
+                <li key={series.participant_id.to_string()} class="konnekt-results-chart__legend-item">
+                    <span
+                        class="konnekt-results-chart__swatch"
+                        style={format!("background: hsl({}, 65%, 45%);", avatar_hue(series.participant_id))}
+                    ></span>
+                    {format!(
+                        "{} ({})",
+                        series.participant_name,
+                        series.cumulative().last().copied().unwrap_or(0)
+                    )}
+                </li>
+            })}
+        </ul>
+    }
+}
+
+#[cfg(feature = "preview")]
+mod preview_fixtures {
+    use konnekt_session_core::ResultsAnalytics;
+    use konnekt_session_core::analytics::{
+        ProgressionSeries, Round, RoundProgression, ScoreDistribution,
+    };
+    use uuid::Uuid;
+
+    pub fn make_sample_results() -> ResultsAnalytics {
+        let rounds = ["Echo Hallo", "Echo Danke", "Echo Bitte"];
+        let series = [
+            ("Alice", [Some(100), Some(80), Some(100)]),
+            ("Bob", [Some(40), None, Some(100)]),
+            ("Carol", [Some(0), Some(60), Some(20)]),
+        ];
+        ResultsAnalytics {
+            distribution: ScoreDistribution::from_scores(
+                series
+                    .iter()
+                    .flat_map(|(_, scores)| scores.iter().flatten().copied()),
+                5,
+            ),
+            progression: RoundProgression {
+                rounds: rounds
+                    .iter()
+                    .enumerate()
+                    .map(|(index, name)| Round {
+                        run_id: Uuid::from_u128(index as u128 + 1),
+                        name: name.to_string(),
+                    })
+                    .collect(),
+                series: series
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (name, scores))| ProgressionSeries {
+                        participant_id: Uuid::from_u128(index as u128 + 10),
+                        participant_name: name.to_string(),
+                        scores: scores.to_vec(),
+                    })
+                    .collect(),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "preview")]
+yew_preview::create_preview_with_tests!(
+    component: ResultsChart,
+    default_props: ResultsChartProps {
+        results: preview_fixtures::make_sample_results(),
+    },
+    variants: [
+        (
+            "Distribution only",
+            ResultsChartProps {
+                results: preview_fixtures::make_sample_results(),
+                show_progression: false,
+            }
+        ),
+    ],
+    tests: [
+        ("Has main container class", exists("konnekt-results-chart")),
+        ("Has bar class", exists("konnekt-results-chart__bar")),
+        ("Has line class", exists("konnekt-results-chart__line")),
+        ("Shows legend", has_text("Alice (280)")),
+    ]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_rects_scale_to_the_fullest_bucket() {
+        let distribution = ScoreDistribution::from_scores([10, 90, 95, 100], 5);
+        let bars = bar_rects(&distribution);
+
+        assert_eq!(bars.len(), 5);
+        let full = HEIGHT - TOP - BOTTOM;
+        assert_eq!(bars[4].height, full);
+        assert_eq!(bars[0].height, full / 3.0);
+        assert_eq!(bars[1].height, 0.0);
+        assert_eq!(bars[4].y, TOP);
+        assert!(bars[0].x < bars[1].x);
+    }
+
+    #[test]
+    fn test_line_points_start_at_zero() {
+        let points = line_points(&[100, 200], 2, 200);
+        assert_eq!(points, "28.0,140.0 170.0,74.0 312.0,8.0");
+
+        // Nobody scored yet
+        assert_eq!(line_points(&[0], 1, 0), "28.0,140.0 312.0,140.0");
+    }
+}
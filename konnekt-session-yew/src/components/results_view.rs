@@ -1,28 +1,41 @@
-use konnekt_session_core::Lobby;
+use konnekt_session_core::{Lobby, ResultsAnalytics};
 use yew::prelude::*;
 
+use super::results_chart::ResultsChart;
+
 #[derive(Properties, PartialEq, Clone)]
 pub struct ResultsViewProps {
     pub lobby: Option<Lobby>,
     pub is_host: bool,
+    /// Analytics of the completed runs (see `use_session().results`)
+    #[prop_or_default]
+    pub results: ResultsAnalytics,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
 }
 
 #[function_component(ResultsView)]
 pub fn results_view(props: &ResultsViewProps) -> Html {
     if props.lobby.is_none() {
         return html! {
-            <div class="konnekt-results-screen">
+            <div class={classes!("konnekt-results-screen", props.classes.clone())} style={props.style.clone()}>
                 <p>{"Loading..."}</p>
             </div>
         };
     }
 
     html! {
-        <div class="konnekt-results-screen">
+        <div class={classes!("konnekt-results-screen", props.classes.clone())} style={props.style.clone()}>
             <div class="konnekt-results-screen__header">
                 <h2>{"🏆 Results"}</h2>
             </div>
-            <p>{"Run history is not yet exposed in the current snapshot model."}</p>
+            {if props.results.is_empty() {
+                html! { <p>{"No completed activities yet."}</p> }
+            } else {
+                html! { <ResultsChart results={props.results.clone()} /> }
+            }}
             <div class="konnekt-results-screen__footer">
                 <p class="konnekt-results-screen__note">
                     {if props.is_host {
@@ -0,0 +1,249 @@
+use konnekt_session_core::analytics::DEFAULT_SCORE_BUCKETS;
+use konnekt_session_core::domain::RunStatus;
+use konnekt_session_core::{ResultsAnalytics, SessionMetrics};
+use yew::prelude::*;
+
+use super::results_chart::ResultsChart;
+use crate::hooks::use_i18n;
+
+#[cfg(feature = "preview")]
+use yew_preview::prelude::*;
+#[cfg(feature = "preview")]
+use yew_preview::test_utils::{exists, has_text};
+
+/// An answer time in seconds, "–" before anyone answered
+pub(crate) fn format_seconds(ms: Option<u64>) -> String {
+    ms.map_or("–".to_string(), |ms| {
+        format!("{:.1} s", ms as f64 / 1000.0)
+    })
+}
+
+/// A rate as a whole percentage, "–" without anyone eligible
+pub(crate) fn format_percent(rate: Option<f64>) -> String {
+    rate.map_or("–".to_string(), |rate| format!("{:.0} %", rate * 100.0))
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct SessionAnalyticsProps {
+    /// Usually `use_session().metrics`
+    pub metrics: SessionMetrics,
+    /// Bar chart of how the scores spread; turn it off next to a `ResultsChart`
+    #[prop_or(true)]
+    pub show_distribution: bool,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Average answer time, participation per activity, who left when and how
+/// the scores spread, from the same metrics as the TUI Analytics tab
+#[function_component(SessionAnalytics)]
+pub fn session_analytics(props: &SessionAnalyticsProps) -> Html {
+    let i18n = use_i18n();
+    let metrics = &props.metrics;
+
+    if metrics.is_empty() {
+        return html! {
+            <div class={classes!("konnekt-session-analytics", props.classes.clone())} style={props.style.clone()}>
+                <p class="konnekt-session-analytics__empty">{i18n.t("analytics.empty")}</p>
+            </div>
+        };
+    }
+
+    let summary = html! {
+        <dl class="konnekt-session-analytics__summary">
+            <div>
+                <dt>{i18n.t("analytics.answer_time")}</dt>
+                <dd>{format_seconds(metrics.average_answer_ms())}</dd>
+            </div>
+            <div>
+                <dt>{i18n.t("analytics.participation")}</dt>
+                <dd>{format_percent(metrics.participation_rate())}</dd>
+            </div>
+            <div>
+                <dt>{i18n.t("analytics.left")}</dt>
+                <dd>{metrics.drop_offs().len()}</dd>
+            </div>
+        </dl>
+    };
+
+    let runs = html! {
+        <ul class="konnekt-session-analytics__runs">
+            {for metrics.runs().iter().map(|run| {
+                let status = match run.status {
+                    RunStatus::InProgress => "konnekt-session-analytics__run--in-progress",
+                    RunStatus::Completed => "konnekt-session-analytics__run--completed",
+                    RunStatus::Cancelled => "konnekt-session-analytics__run--cancelled",
+                };
+                html! {
+                    <li key={run.run_id.to_string()} class={classes!("konnekt-session-analytics__run", status)}>
+                        <span class="konnekt-session-analytics__run-name">{run.name.clone()}</span>
+                        <span>
+                            {i18n.t_with(
+                                "analytics.answered",
+                                &[("submitted", &run.submitted), ("eligible", &run.eligible)],
+                            )}
+                            {format!(" ({})", format_percent(run.participation_rate()))}
+                        </span>
+                        <span class="konnekt-session-analytics__time">
+                            {format!("⏱ {}", format_seconds(run.average_answer_ms()))}
+                        </span>
+                        {if run.dropped > 0 {
+                            html! {
+                                <span class="konnekt-session-analytics__dropped">
+                                    {i18n.t_with("analytics.dropped", &[("count", &run.dropped)])}
+                                </span>
+                            }
+                        } else {
+                            html! {}
+                        }}
+                    </li>
+                }
+            })}
+        </ul>
+    };
+
+    let drop_offs = if metrics.drop_offs().is_empty() {
+        html! {}
+    } else {
+        html! {
+            <ul class="konnekt-session-analytics__drop-offs">
+                {for metrics.drop_offs().iter().map(|drop_off| {
+                    let when = match &drop_off.during {
+                        Some(activity) => i18n.t_with("analytics.left_during", &[("activity", activity)]),
+                        None => i18n.t_with("analytics.left_after", &[("count", &drop_off.completed_runs)]),
+                    };
+                    html! {
+                        <li key={drop_off.participant_id.to_string()} class="konnekt-session-analytics__drop-off">
+                            <strong>{drop_off.name.clone()}</strong>{" "}{when}
+                        </li>
+                    }
+                })}
+            </ul>
+        }
+    };
+
+    let distribution = if props.show_distribution {
+        let results = ResultsAnalytics {
+            distribution: metrics.score_distribution(DEFAULT_SCORE_BUCKETS),
+            ..Default::default()
+        };
+        html! { <ResultsChart {results} show_progression={false} /> }
+    } else {
+        html! {}
+    };
+
+    html! {
+        <div class={classes!("konnekt-session-analytics", props.classes.clone())} style={props.style.clone()}>
+            {summary}
+            {runs}
+            {drop_offs}
+            {distribution}
+        </div>
+    }
+}
+
+#[cfg(feature = "preview")]
+mod preview_fixtures {
+    use konnekt_session_core::application::{DomainCommand, DomainEventLoop, EventFilter};
+    use konnekt_session_core::domain::{ActivityConfig, ActivityResult};
+    use konnekt_session_core::{DomainEvent, Projection, SessionMetrics};
+
+    /// A quiz everyone answered and one Carol left during
+    pub fn make_sample_metrics() -> SessionMetrics {
+        let mut el = DomainEventLoop::new();
+        let events = el.subscribe(EventFilter::all());
+        let DomainEvent::LobbyCreated { lobby } = el.handle_command(DomainCommand::CreateLobby {
+            lobby_id: None,
+            lobby_name: "Preview Lobby".to_string(),
+            host_name: "Alice".to_string(),
+        }) else {
+            return SessionMetrics::new();
+        };
+        let (lobby_id, alice) = (lobby.id(), lobby.host_id());
+        let mut players = vec![alice];
+        for name in ["Bob", "Carol"] {
+            if let DomainEvent::GuestJoined { participant, .. } =
+                el.handle_command(DomainCommand::JoinLobby {
+                    lobby_id,
+                    guest_name: name.to_string(),
+                })
+            {
+                players.push(participant.id());
+            }
+        }
+        for name in ["Echo Hallo", "Echo Danke"] {
+            el.handle_command(DomainCommand::QueueActivity {
+                lobby_id,
+                config: ActivityConfig::new(
+                    "echo".to_string(),
+                    name.to_string(),
+                    serde_json::json!({}),
+                ),
+            });
+        }
+
+        for (round, scores) in [[100, 40, 80], [60, 0, 0]].iter().enumerate() {
+            let DomainEvent::RunStarted { run_id, .. } =
+                el.handle_command(DomainCommand::StartNextRun { lobby_id })
+            else {
+                break;
+            };
+            let answering = if round == 0 { 3 } else { 2 };
+            for (index, participant_id) in players.iter().take(answering).enumerate() {
+                el.handle_command(DomainCommand::SubmitResult {
+                    lobby_id,
+                    run_id,
+                    result: ActivityResult::new(run_id, *participant_id)
+                        .with_score(scores[index])
+                        .with_time(1_500 + 1_000 * index as u64),
+                });
+            }
+            if round == 1 {
+                el.handle_command(DomainCommand::LeaveLobby {
+                    lobby_id,
+                    participant_id: players[2],
+                });
+            }
+        }
+
+        let mut metrics = SessionMetrics::new();
+        metrics.apply_all(&events.drain());
+        metrics
+    }
+}
+
+#[cfg(feature = "preview")]
+yew_preview::create_preview_with_tests!(
+    component: SessionAnalytics,
+    default_props: SessionAnalyticsProps {
+        metrics: preview_fixtures::make_sample_metrics(),
+    },
+    variants: [
+        (
+            "Nothing played yet",
+            SessionAnalyticsProps {
+                metrics: konnekt_session_core::SessionMetrics::new(),
+            }
+        ),
+    ],
+    tests: [
+        ("Has main container class", exists("konnekt-session-analytics")),
+        ("Has run class", exists("konnekt-session-analytics__run")),
+        ("Shows drop-off", has_text("Carol")),
+    ]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_helpers_show_a_dash_without_data() {
+        assert_eq!(format_seconds(None), "–");
+        assert_eq!(format_seconds(Some(2_500)), "2.5 s");
+        assert_eq!(format_percent(None), "–");
+        assert_eq!(format_percent(Some(0.75)), "75 %");
+    }
+}
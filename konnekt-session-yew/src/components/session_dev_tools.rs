@@ -0,0 +1,263 @@
+use chrono::DateTime;
+use konnekt_session_core::{DomainCommand, DomainEvent};
+use uuid::Uuid;
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+use crate::hooks::use_session;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct SessionDevToolsProps {
+    /// Start expanded
+    #[prop_or_default]
+    pub open: bool,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Name of the variant of `event`, e.g. `GuestJoined`
+pub(crate) fn event_name(event: &DomainEvent) -> String {
+    match serde_json::to_value(event) {
+        Ok(serde_json::Value::Object(map)) => map.keys().next().cloned().unwrap_or_default(),
+        Ok(serde_json::Value::String(name)) => name,
+        _ => "?".to_string(),
+    }
+}
+
+/// A command typed into the dev tools, as JSON
+pub(crate) fn parse_command(json: &str) -> Result<DomainCommand, String> {
+    serde_json::from_str(json.trim()).map_err(|e| e.to_string())
+}
+
+fn format_time(at_ms: u64) -> String {
+    DateTime::from_timestamp_millis(at_ms as i64)
+        .map(|at| at.format("%H:%M:%S%.3f").to_string())
+        .unwrap_or_default()
+}
+
+/// Collapsible panel for developers building on the hooks: the latest domain
+/// events, queue depths and peers, and a form injecting raw commands
+///
+/// The event log and queue depths are only recorded when the surrounding
+/// `SessionProvider` has `dev_tools` set.
+#[function_component(SessionDevTools)]
+pub fn session_dev_tools(props: &SessionDevToolsProps) -> Html {
+    let session = use_session();
+    let command = use_state(String::new);
+    let command_error = use_state(|| None::<String>);
+    // Events logged up to this time are hidden
+    let cleared_at_ms = use_state(|| 0u64);
+
+    let on_command_input = {
+        let command = command.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            command.set(input.value());
+        })
+    };
+
+    let on_template = {
+        let command = command.clone();
+        let template = session
+            .lobby
+            .as_ref()
+            .zip(session.local_participant_id)
+            .map(
+                |(lobby, participant_id)| DomainCommand::ToggleParticipationMode {
+                    lobby_id: lobby.id(),
+                    participant_id,
+                    requester_id: participant_id,
+                },
+            )
+            .and_then(|cmd| serde_json::to_string_pretty(&cmd).ok());
+        Callback::from(move |_: MouseEvent| {
+            if let Some(template) = &template {
+                command.set(template.clone());
+            }
+        })
+    };
+
+    let on_send = {
+        let command = command.clone();
+        let command_error = command_error.clone();
+        let send_command = session.send_command.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            match parse_command(&command) {
+                Ok(cmd) => {
+                    tracing::info!("🛠️ DevTools: injecting {:?}", cmd);
+                    send_command(cmd);
+                    command_error.set(None);
+                }
+                Err(e) => command_error.set(Some(e)),
+            }
+        })
+    };
+
+    let on_clear = {
+        let cleared_at_ms = cleared_at_ms.clone();
+        let last = session.event_log.last().map(|logged| logged.at_ms);
+        Callback::from(move |_: MouseEvent| {
+            if let Some(last) = last {
+                cleared_at_ms.set(last);
+            }
+        })
+    };
+
+    let depths = session.queue_depths;
+    let queues = [
+        ("Optimistic commands", session.pending_commands.len()),
+        ("Domain commands", depths.domain_commands),
+        ("Domain events", depths.domain_events),
+        ("Untaken failures", depths.failed_commands),
+        ("Untaken events", depths.recent_events),
+    ];
+    let name_of = |participant_id: Uuid| {
+        session
+            .lobby
+            .as_ref()
+            .and_then(|lobby| lobby.participants().get(&participant_id))
+            .map(|p| p.name().to_string())
+    };
+
+    html! {
+        <details
+            class={classes!("konnekt-dev-tools", props.classes.clone())}
+            style={props.style.clone()}
+            open={props.open}
+        >
+            <summary class="konnekt-dev-tools__title">{"🛠️ Session DevTools"}</summary>
+
+            <section class="konnekt-dev-tools__section">
+                <h4>{"Queues"}</h4>
+                <table class="konnekt-dev-tools__table">
+                    <tbody>
+                        {for queues.iter().map(|(label, depth)| html! {
+                            <tr>
+                                <th scope="row">{*label}</th>
+                                <td>{depth}</td>
+                            </tr>
+                        })}
+                    </tbody>
+                </table>
+            </section>
+
+            <section class="konnekt-dev-tools__section">
+                <h4>{format!("Peers ({})", session.peer_stats.len())}</h4>
+                <table class="konnekt-dev-tools__table">
+                    <thead>
+                        <tr>
+                            <th>{"Peer"}</th>
+                            <th>{"Participant"}</th>
+                            <th>{"Status"}</th>
+                            <th>{"RTT"}</th>
+                            <th>{"Last seen"}</th>
+                            <th>{"Sent / received"}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {for session.peer_stats.iter().map(|peer| html! {
+                            <tr key={peer.peer_id.to_string()}>
+                                <td class="konnekt-dev-tools__mono">{peer.peer_id.to_string()}</td>
+                                <td>
+                                    {peer
+                                        .participant_id
+                                        .and_then(name_of)
+                                        .unwrap_or_else(|| "-".to_string())}
+                                </td>
+                                <td>{format!("{:?}", peer.status)}</td>
+                                <td>
+                                    {peer
+                                        .rtt
+                                        .map(|rtt| format!("{}ms", rtt.as_millis()))
+                                        .unwrap_or_else(|| "-".to_string())}
+                                </td>
+                                <td>{format!("{}s", peer.last_seen.as_secs())}</td>
+                                <td>{format!("{} / {}", peer.messages_sent, peer.messages_received)}</td>
+                            </tr>
+                        })}
+                    </tbody>
+                </table>
+            </section>
+
+            <section class="konnekt-dev-tools__section">
+                <div class="konnekt-dev-tools__header">
+                    <h4>{format!("Events ({})", session.event_log.len())}</h4>
+                    <button class="konnekt-dev-tools__button" onclick={on_clear}>{"Clear"}</button>
+                </div>
+                <ol class="konnekt-dev-tools__events" reversed={true}>
+                    {for session
+                        .event_log
+                        .iter()
+                        .rev()
+                        .filter(|logged| logged.at_ms > *cleared_at_ms)
+                        .map(|logged| html! {
+                            <li class="konnekt-dev-tools__event">
+                                <details>
+                                    <summary>
+                                        <span class="konnekt-dev-tools__mono">{format_time(logged.at_ms)}</span>
+                                        {" "}{event_name(&logged.event)}
+                                    </summary>
+                                    <pre class="konnekt-dev-tools__json">
+                                        {serde_json::to_string_pretty(&logged.event).unwrap_or_default()}
+                                    </pre>
+                                </details>
+                            </li>
+                        })}
+                </ol>
+            </section>
+
+            <section class="konnekt-dev-tools__section">
+                <div class="konnekt-dev-tools__header">
+                    <h4>{"Inject command"}</h4>
+                    <button class="konnekt-dev-tools__button" onclick={on_template}>{"Template"}</button>
+                </div>
+                <form class="konnekt-dev-tools__inject" onsubmit={on_send}>
+                    <textarea
+                        class="konnekt-dev-tools__command konnekt-dev-tools__mono"
+                        rows="6"
+                        placeholder="{\"SendChatMessage\": { ... }}"
+                        aria-label="Command as JSON"
+                        value={(*command).clone()}
+                        oninput={on_command_input}
+                    />
+                    <button type="submit" class="konnekt-dev-tools__button" disabled={command.trim().is_empty()}>
+                        {"Send"}
+                    </button>
+                </form>
+                {match &*command_error {
+                    Some(error) => html! {
+                        <div class="konnekt-dev-tools__error" role="alert">{format!("✗ {}", error)}</div>
+                    },
+                    None => html! {},
+                }}
+            </section>
+        </details>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_name_and_parse_command() {
+        let event = DomainEvent::ReadyChanged {
+            lobby_id: Uuid::new_v4(),
+            participant_id: Uuid::new_v4(),
+            ready: true,
+        };
+        assert_eq!(event_name(&event), "ReadyChanged");
+
+        let cmd = DomainCommand::SetReady {
+            lobby_id: Uuid::new_v4(),
+            participant_id: Uuid::new_v4(),
+            ready: true,
+        };
+        let json = serde_json::to_string(&cmd).unwrap();
+        assert_eq!(parse_command(&format!("  {}\n", json)), Ok(cmd));
+        assert!(parse_command("{\"NoSuchCommand\": {}}").is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use yew::prelude::*;
+
+use crate::hooks::{SessionError, SessionErrorOptions, use_i18n, use_session_error};
+
+#[cfg(feature = "preview")]
+use yew_preview::prelude::*;
+#[cfg(feature = "preview")]
+use yew_preview::test_utils::{exists, has_text};
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct SessionErrorViewProps {
+    pub error: SessionError,
+    /// Try the same session again (shown if the error allows it)
+    #[prop_or_default]
+    pub on_retry: Option<Callback<()>>,
+    /// Join the lobby again (shown if the error allows it)
+    #[prop_or_default]
+    pub on_rejoin: Option<Callback<()>>,
+    /// Leave the session
+    #[prop_or_default]
+    pub on_home: Option<Callback<()>>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// What went wrong with a session and the ways out of it
+#[function_component(SessionErrorView)]
+pub fn session_error_view(props: &SessionErrorViewProps) -> Html {
+    let i18n = use_i18n();
+    let error = &props.error;
+    let kind = error.kind();
+
+    let action = |label: &str, callback: Option<&Callback<()>>, primary: bool| match callback {
+        Some(callback) => {
+            let callback = callback.clone();
+            html! {
+                <button
+                    class={classes!(
+                        "konnekt-btn",
+                        if primary { "konnekt-btn--primary" } else { "konnekt-btn--secondary" },
+                    )}
+                    onclick={move |_| callback.emit(())}
+                >
+                    {i18n.t(label)}
+                </button>
+            }
+        }
+        None => html! {},
+    };
+
+    html! {
+        <div
+            class={classes!(
+                "konnekt-session-error",
+                format!("konnekt-session-error--{}", kind),
+                props.classes.clone(),
+            )}
+            style={props.style.clone()}
+            role="alert"
+        >
+            <h2 class="konnekt-session-error__title">{i18n.t(&format!("session_error.{}.title", kind))}</h2>
+            <p class="konnekt-session-error__message">{i18n.t(&format!("session_error.{}.message", kind))}</p>
+            {match error.details() {
+                Some(details) => html! {
+                    <details class="konnekt-session-error__details">
+                        <summary>{i18n.t("session_error.details")}</summary>
+                        <pre>{details}</pre>
+                    </details>
+                },
+                None => html! {},
+            }}
+            <div class="konnekt-session-error__actions">
+                {action(
+                    "session_error.retry",
+                    props.on_retry.as_ref().filter(|_| error.can_retry()),
+                    true,
+                )}
+                {action(
+                    "session_error.rejoin",
+                    props.on_rejoin.as_ref().filter(|_| error.can_rejoin()),
+                    true,
+                )}
+                {action("session_error.home", props.on_home.as_ref(), false)}
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct SessionErrorBoundaryProps {
+    /// Try the same session again, e.g. by remounting the `SessionProvider`
+    #[prop_or_default]
+    pub on_retry: Option<Callback<()>>,
+    /// Join the lobby again after being removed
+    #[prop_or_default]
+    pub on_rejoin: Option<Callback<()>>,
+    #[prop_or_default]
+    pub on_home: Option<Callback<()>>,
+    /// Told about every new error, for logging or custom handling
+    #[prop_or_default]
+    pub on_error: Callback<SessionError>,
+    #[prop_or_default]
+    pub options: SessionErrorOptions,
+    /// Rendered instead of the default recovery screen
+    #[prop_or_default]
+    pub fallback: Option<Callback<SessionError, Html>>,
+    pub children: Children,
+}
+
+/// Renders its children until the session fails (sync timeout, incompatible
+/// host, removed from the lobby, host gone), then a recovery screen
+///
+/// Must be inside a `SessionProvider`. Yew can't catch panics, so only the
+/// failures the session reports are handled.
+#[function_component(SessionErrorBoundary)]
+pub fn session_error_boundary(props: &SessionErrorBoundaryProps) -> Html {
+    let error = use_session_error(props.options);
+
+    {
+        let on_error = props.on_error.clone();
+        use_effect_with(error.clone(), move |error| {
+            if let Some(error) = error {
+                tracing::warn!("💥 Session failed: {:?}", error);
+                on_error.emit(error.clone());
+            }
+            || ()
+        });
+    }
+
+    match error {
+        None => html! { <>{props.children.clone()}</> },
+        Some(error) => match &props.fallback {
+            Some(fallback) => fallback.emit(error),
+            None => html! {
+                <SessionErrorView
+                    {error}
+                    on_retry={props.on_retry.clone()}
+                    on_rejoin={props.on_rejoin.clone()}
+                    on_home={props.on_home.clone()}
+                />
+            },
+        },
+    }
+}
+
+#[cfg(feature = "preview")]
+yew_preview::create_preview_with_tests!(
+    component: SessionErrorView,
+    default_props: SessionErrorViewProps {
+        error: SessionError::ConnectionFailed("WebSocket closed (1006)".to_string()),
+        on_retry: Some(Callback::noop()),
+        on_home: Some(Callback::noop()),
+    },
+    variants: [
+        (
+            "Kicked",
+            SessionErrorViewProps {
+                error: SessionError::Kicked,
+                on_rejoin: Some(Callback::noop()),
+                on_home: Some(Callback::noop()),
+            }
+        ),
+    ],
+    tests: [
+        ("Has main container class", exists("konnekt-session-error")),
+        ("Has kind modifier", exists("konnekt-session-error--connection_failed")),
+        ("Shows details", has_text("WebSocket closed (1006)")),
+        ("Offers retry", has_text("Try again")),
+    ]
+);
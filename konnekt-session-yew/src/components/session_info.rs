@@ -18,6 +18,10 @@ pub struct SessionInfoProps {
     pub host_unreachable: bool,
     #[prop_or_default]
     pub last_host_connection: Option<String>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
 }
 
 /// Displays session metadata with shareable URL
@@ -78,7 +82,7 @@ pub fn session_info(props: &SessionInfoProps) -> Html {
     };
 
     html! {
-        <div class="konnekt-session-info">
+        <div class={classes!("konnekt-session-info", props.classes.clone())} style={props.style.clone()}>
             <div class="konnekt-session-info__row">
                 <span class="konnekt-session-info__label">{"Session ID:"}</span>
                 <code class="konnekt-session-info__value">{&props.session_id}</code>
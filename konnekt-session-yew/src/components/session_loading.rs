@@ -0,0 +1,145 @@
+use yew::prelude::*;
+
+use super::skeleton::{Skeleton, SkeletonShape};
+use crate::hooks::{SessionStatus, use_i18n};
+
+#[cfg(feature = "preview")]
+use yew_preview::prelude::*;
+#[cfg(feature = "preview")]
+use yew_preview::test_utils::{exists, has_text};
+
+/// The steps [`SessionLoading`] walks through
+const STEPS: [SessionStatus; 3] = [
+    SessionStatus::Connecting,
+    SessionStatus::Syncing,
+    SessionStatus::Ready,
+];
+
+/// Whether `step` is done, current or still to come while at `status`
+pub(crate) fn step_state(step: SessionStatus, status: SessionStatus) -> &'static str {
+    let index = |status| {
+        STEPS
+            .iter()
+            .position(|s| *s == status)
+            .unwrap_or(STEPS.len() - 1)
+    };
+    match index(step).cmp(&index(status)) {
+        std::cmp::Ordering::Less => "done",
+        std::cmp::Ordering::Equal => "current",
+        std::cmp::Ordering::Greater => "upcoming",
+    }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct SessionLoadingProps {
+    /// Usually `use_session().status()`
+    pub status: SessionStatus,
+    /// The host creates the lobby instead of syncing it
+    #[prop_or_default]
+    pub is_host: bool,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Progress through connecting and syncing, over a skeleton of the lobby
+#[function_component(SessionLoading)]
+pub fn session_loading(props: &SessionLoadingProps) -> Html {
+    let i18n = use_i18n();
+    let message = match props.status {
+        _ if props.is_host => i18n.t("session.creating"),
+        SessionStatus::Connecting => i18n.t("session.connecting"),
+        _ => i18n.t("session.syncing"),
+    };
+
+    html! {
+        <div
+            class={classes!(
+                "konnekt-session-loading",
+                format!("konnekt-session-loading--{}", props.status.as_str()),
+                props.classes.clone(),
+            )}
+            style={props.style.clone()}
+            role="status"
+            aria-busy="true"
+        >
+            <ol class="konnekt-session-loading__steps">
+                {for STEPS.iter().map(|step| html! {
+                    <li class={classes!(
+                        "konnekt-session-loading__step",
+                        step_state(*step, props.status),
+                    )}>
+                        {i18n.t(&format!("status.{}", step.as_str()))}
+                    </li>
+                })}
+            </ol>
+            <p class="konnekt-session-loading__message">{message}</p>
+
+            <div class="konnekt-session-loading__lobby">
+                <div class="konnekt-session-loading__participants">
+                    <Skeleton width="60%" />
+                    {for (0..3).map(|_| html! {
+                        <div class="konnekt-session-loading__participant">
+                            <Skeleton shape={SkeletonShape::Circle} width="32px" height="32px" />
+                            <Skeleton />
+                        </div>
+                    })}
+                </div>
+                <div class="konnekt-session-loading__activities">
+                    <Skeleton width="40%" />
+                    <Skeleton shape={SkeletonShape::Block} height="4rem" />
+                    <Skeleton shape={SkeletonShape::Block} height="4rem" />
+                    <Skeleton lines={2} />
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[cfg(feature = "preview")]
+yew_preview::create_preview_with_tests!(
+    component: SessionLoading,
+    default_props: SessionLoadingProps {
+        status: SessionStatus::Syncing,
+    },
+    variants: [
+        (
+            "Connecting",
+            SessionLoadingProps {
+                status: SessionStatus::Connecting,
+            }
+        ),
+        (
+            "Host",
+            SessionLoadingProps {
+                status: SessionStatus::Connecting,
+                is_host: true,
+            }
+        ),
+    ],
+    tests: [
+        ("Has main container class", exists("konnekt-session-loading")),
+        ("Has status modifier", exists("konnekt-session-loading--syncing")),
+        ("Marks the current step", exists("current")),
+        ("Shows message", has_text("Syncing lobby from host...")),
+    ]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_state() {
+        let syncing = SessionStatus::Syncing;
+        assert_eq!(step_state(SessionStatus::Connecting, syncing), "done");
+        assert_eq!(step_state(SessionStatus::Syncing, syncing), "current");
+        assert_eq!(step_state(SessionStatus::Ready, syncing), "upcoming");
+
+        // Degraded is past syncing, like ready
+        let degraded = SessionStatus::Degraded;
+        assert_eq!(step_state(SessionStatus::Syncing, degraded), "done");
+        assert_eq!(step_state(SessionStatus::Ready, degraded), "current");
+    }
+}
@@ -0,0 +1,107 @@
+use yew::prelude::*;
+
+#[cfg(feature = "preview")]
+use yew_preview::prelude::*;
+#[cfg(feature = "preview")]
+use yew_preview::test_utils::exists;
+
+/// Shape of a [`Skeleton`] placeholder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SkeletonShape {
+    /// Lines of text, the last one shorter
+    #[default]
+    Text,
+    /// An avatar or icon
+    Circle,
+    /// A card, button or image
+    Block,
+}
+
+impl SkeletonShape {
+    fn modifier(&self) -> &'static str {
+        match self {
+            SkeletonShape::Text => "text",
+            SkeletonShape::Circle => "circle",
+            SkeletonShape::Block => "block",
+        }
+    }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct SkeletonProps {
+    #[prop_or_default]
+    pub shape: SkeletonShape,
+    /// Number of lines for [`SkeletonShape::Text`]
+    #[prop_or(1)]
+    pub lines: u32,
+    /// CSS width, e.g. `"8rem"` (default: fill the container)
+    #[prop_or_default]
+    pub width: Option<AttrValue>,
+    /// CSS height, e.g. `"3rem"`
+    #[prop_or_default]
+    pub height: Option<AttrValue>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Shimmering placeholder for content that is still loading
+#[function_component(Skeleton)]
+pub fn skeleton(props: &SkeletonProps) -> Html {
+    let size = [("width", &props.width), ("height", &props.height)]
+        .into_iter()
+        .filter_map(|(name, value)| value.as_ref().map(|value| format!("{}: {};", name, value)))
+        .chain(props.style.as_ref().map(|style| style.to_string()))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let lines = match props.shape {
+        SkeletonShape::Text => props.lines.max(1),
+        _ => 1,
+    };
+
+    html! {
+        <span
+            class={classes!(
+                "konnekt-skeleton",
+                format!("konnekt-skeleton--{}", props.shape.modifier()),
+                props.classes.clone(),
+            )}
+            style={(!size.is_empty()).then_some(size)}
+            aria-hidden="true"
+        >
+            {for (0..lines).map(|_| html! { <span class="konnekt-skeleton__bone"></span> })}
+        </span>
+    }
+}
+
+#[cfg(feature = "preview")]
+yew_preview::create_preview_with_tests!(
+    component: Skeleton,
+    default_props: SkeletonProps {
+        lines: 3,
+        width: Some(AttrValue::from("16rem")),
+    },
+    variants: [
+        (
+            "Circle",
+            SkeletonProps {
+                shape: SkeletonShape::Circle,
+                width: Some(AttrValue::from("40px")),
+                height: Some(AttrValue::from("40px")),
+            }
+        ),
+        (
+            "Block",
+            SkeletonProps {
+                shape: SkeletonShape::Block,
+                height: Some(AttrValue::from("4rem")),
+            }
+        ),
+    ],
+    tests: [
+        ("Has main container class", exists("konnekt-skeleton")),
+        ("Has shape modifier", exists("konnekt-skeleton--text")),
+        ("Has bone class", exists("konnekt-skeleton__bone")),
+    ]
+);
@@ -0,0 +1,202 @@
+use konnekt_session_core::{Lobby, PluginRegistry};
+use konnekt_session_p2p::Presence;
+use uuid::Uuid;
+use yew::prelude::*;
+
+use super::submission_status::SubmissionStatus;
+use crate::hooks::{ActiveRunSnapshot, use_i18n};
+
+#[cfg(feature = "preview")]
+use yew_preview::prelude::*;
+#[cfg(feature = "preview")]
+use yew_preview::test_utils::{exists, has_text};
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct SpectatorViewProps {
+    pub lobby: Lobby,
+    pub active_run: ActiveRunSnapshot,
+    /// Transient presence signals of the participants
+    #[prop_or_default]
+    pub presence: Vec<(Uuid, Presence)>,
+    /// Switch the local participant back to active
+    #[prop_or_default]
+    pub on_toggle_participation: Callback<()>,
+    /// Shows a cancel button for a spectating host
+    #[prop_or_default]
+    pub on_cancel: Option<Callback<()>>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// How many of the participants who have to submit did, and how many have to
+pub(crate) fn submission_progress(run: &ActiveRunSnapshot) -> (usize, usize) {
+    let submitted = run
+        .required_submitters
+        .iter()
+        .filter(|id| run.results.iter().any(|r| r.participant_id == **id))
+        .count();
+    (submitted, run.required_submitters.len())
+}
+
+/// The running activity as a spectator sees it: the prompt without an answer
+/// form and how far the active participants are
+#[function_component(SpectatorView)]
+pub fn spectator_view(props: &SpectatorViewProps) -> Html {
+    let i18n = use_i18n();
+    let run = &props.active_run;
+    let (submitted, required) = submission_progress(run);
+    let prompt = PluginRegistry::global()
+        .render_hints(&run.activity_type, &run.config)
+        .ok()
+        .and_then(|hints| hints.prompt);
+    // Participation can't change while an activity runs
+    let can_join = props.lobby.active_run_id().is_none();
+
+    html! {
+        <div class={classes!("konnekt-spectator-view", props.classes.clone())} style={props.style.clone()}>
+            <div class="konnekt-spectator-view__banner" role="status">
+                <span class="konnekt-spectator-view__badge">{i18n.t("spectator.title")}</span>
+                <button
+                    class="konnekt-btn konnekt-btn--primary konnekt-spectator-view__join"
+                    disabled={!can_join}
+                    onclick={let on_toggle = props.on_toggle_participation.clone(); move |_| on_toggle.emit(())}
+                >
+                    {i18n.t("participation.join")}
+                </button>
+                {if can_join {
+                    html! {}
+                } else {
+                    html! {
+                        <span class="konnekt-spectator-view__hint">{i18n.t("spectator.join_after_run")}</span>
+                    }
+                }}
+            </div>
+
+            <div class="konnekt-spectator-view__header">
+                <h2 class="konnekt-spectator-view__title">{"🎮 "}{run.name.clone()}</h2>
+                {match props.on_cancel.clone() {
+                    Some(on_cancel) => html! {
+                        <button class="konnekt-btn konnekt-btn--danger" onclick={move |_| on_cancel.emit(())}>
+                            {"Cancel Activity"}
+                        </button>
+                    },
+                    None => html! {},
+                }}
+            </div>
+
+            {match prompt {
+                Some(prompt) => html! {
+                    <div class="konnekt-spectator-view__prompt">
+                        <h3>{i18n.t("spectator.prompt")}</h3>
+                        <div class="konnekt-spectator-view__prompt-text">{prompt}</div>
+                    </div>
+                },
+                None => html! {},
+            }}
+
+            <div class="konnekt-spectator-view__progress">
+                <progress
+                    class="konnekt-spectator-view__bar"
+                    value={submitted.to_string()}
+                    max={required.max(1).to_string()}
+                    aria-label={i18n.t("spectator.progress_label")}
+                />
+                <span class="konnekt-spectator-view__count">
+                    {i18n.t_with(
+                        "spectator.progress",
+                        &[("submitted", &submitted), ("count", &required)],
+                    )}
+                </span>
+            </div>
+
+            <SubmissionStatus
+                lobby={props.lobby.clone()}
+                active_run={run.clone()}
+                presence={props.presence.clone()}
+            />
+        </div>
+    }
+}
+
+#[cfg(feature = "preview")]
+mod preview_fixtures {
+    use crate::hooks::ActiveRunSnapshot;
+    use konnekt_session_core::domain::{ActivityResult, RunStatus};
+    use konnekt_session_core::{EchoChallenge, Lobby, Participant, Timestamp};
+    use uuid::Uuid;
+
+    pub fn make_sample_lobby() -> Lobby {
+        let host = Participant::new_host("Alice".to_string(), Timestamp::now()).unwrap();
+        let mut lobby = Lobby::new("Preview Lobby".to_string(), host).unwrap();
+        lobby
+            .add_guest(Participant::new_guest("Bob".to_string(), Timestamp::now()).unwrap())
+            .unwrap();
+        lobby
+    }
+
+    pub fn make_sample_run(lobby: &Lobby) -> ActiveRunSnapshot {
+        let run_id = Uuid::from_u128(1);
+        let required_submitters: Vec<Uuid> = lobby.participants().keys().copied().collect();
+        ActiveRunSnapshot {
+            run_id,
+            status: RunStatus::InProgress,
+            activity_type: EchoChallenge::activity_type().to_string(),
+            name: "Echo".to_string(),
+            config: EchoChallenge::new("Hallo Welt".to_string()).to_config(),
+            results: vec![ActivityResult::new(run_id, required_submitters[0]).with_score(100)],
+            required_submitters,
+            started_at_ms: None,
+            deadline_ms: None,
+        }
+    }
+}
+
+#[cfg(feature = "preview")]
+yew_preview::create_preview_with_tests!(
+    component: SpectatorView,
+    default_props: SpectatorViewProps {
+        lobby: preview_fixtures::make_sample_lobby(),
+        active_run: preview_fixtures::make_sample_run(&preview_fixtures::make_sample_lobby()),
+    },
+    variants: [],
+    tests: [
+        ("Has main container class", exists("konnekt-spectator-view")),
+        ("Has join button class", exists("konnekt-spectator-view__join")),
+        ("Shows prompt", has_text("Hallo Welt")),
+        ("Shows progress", has_text("1 of 2 answered")),
+    ]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use konnekt_session_core::domain::{ActivityResult, RunStatus};
+
+    #[test]
+    fn test_submission_progress() {
+        let run_id = Uuid::new_v4();
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut run = ActiveRunSnapshot {
+            run_id,
+            status: RunStatus::InProgress,
+            activity_type: "echo-challenge-v1".to_string(),
+            name: "Echo".to_string(),
+            config: serde_json::Value::Null,
+            required_submitters: vec![alice, bob],
+            results: vec![],
+            started_at_ms: None,
+            deadline_ms: None,
+        };
+        assert_eq!(submission_progress(&run), (0, 2));
+
+        run.results.push(ActivityResult::new(run_id, alice));
+        assert_eq!(submission_progress(&run), (1, 2));
+
+        // Results of someone who doesn't have to submit don't count
+        run.results
+            .push(ActivityResult::new(run_id, Uuid::new_v4()));
+        assert_eq!(submission_progress(&run), (1, 2));
+    }
+}
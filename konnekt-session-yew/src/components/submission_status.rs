@@ -1,11 +1,20 @@
 use crate::hooks::ActiveRunSnapshot;
 use konnekt_session_core::Lobby;
+use konnekt_session_p2p::Presence;
+use uuid::Uuid;
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq, Clone)]
 pub struct SubmissionStatusProps {
     pub lobby: Lobby,
     pub active_run: ActiveRunSnapshot,
+    /// Transient presence signals of other participants
+    #[prop_or_default]
+    pub presence: Vec<(Uuid, Presence)>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
 }
 
 #[function_component(SubmissionStatus)]
@@ -19,7 +28,7 @@ pub fn submission_status(props: &SubmissionStatusProps) -> Html {
         .collect();
 
     html! {
-        <div class="konnekt-submission-status">
+        <div class={classes!("konnekt-submission-status", props.classes.clone())} style={props.style.clone()}>
             <h3>{"Submission Status"}</h3>
             <div class="konnekt-submission-status__stats">
                 <span class="konnekt-submission-status__count">
@@ -62,9 +71,20 @@ pub fn submission_status(props: &SubmissionStatusProps) -> Html {
                                     .get(participant_id)
                                     .map(|p| p.name())
                                     .unwrap_or("Unknown");
+                                let answering = props.presence.iter().any(|(id, presence)| {
+                                    id == *participant_id
+                                        && *presence == Presence::Answering {
+                                            run_id: props.active_run.run_id,
+                                        }
+                                });
                                 html! {
                                     <li class="konnekt-submission-status__pending">
                                         {"⏳ "}{participant_name}
+                                        {if answering {
+                                            html! { <span class="konnekt-submission-status__answering">{" ✍️ answering…"}</span> }
+                                        } else {
+                                            html! {}
+                                        }}
                                     </li>
                                 }
                             })}
@@ -0,0 +1,91 @@
+use yew::prelude::*;
+
+use crate::hooks::use_i18n;
+use crate::providers::Notification;
+
+#[cfg(feature = "preview")]
+use crate::providers::NotificationKind;
+#[cfg(feature = "preview")]
+use yew_preview::prelude::*;
+#[cfg(feature = "preview")]
+use yew_preview::test_utils::{exists, has_text};
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct ToastStackProps {
+    /// Oldest first; the newest ends up at the bottom
+    pub notifications: Vec<Notification>,
+    #[prop_or_default]
+    pub on_dismiss: Callback<u32>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
+}
+
+/// Stack of dismissible toasts; renders nothing while there are none
+#[function_component(ToastStack)]
+pub fn toast_stack(props: &ToastStackProps) -> Html {
+    let i18n = use_i18n();
+
+    if props.notifications.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div
+            class={classes!("konnekt-toast-stack", props.classes.clone())}
+            style={props.style.clone()}
+            role="status"
+            aria-live="polite"
+        >
+            {for props.notifications.iter().map(|notification| {
+                let id = notification.id;
+                let on_dismiss = props.on_dismiss.clone();
+                html! {
+                    <div
+                        key={id}
+                        class={classes!(
+                            "konnekt-toast",
+                            format!("konnekt-toast--{}", notification.kind.as_str()),
+                        )}
+                    >
+                        <span class="konnekt-toast__text">{notification.text.clone()}</span>
+                        <button
+                            class="konnekt-toast__dismiss"
+                            title={i18n.t("toast.dismiss")}
+                            onclick={move |_| on_dismiss.emit(id)}
+                        >
+                            {"✕"}
+                        </button>
+                    </div>
+                }
+            })}
+        </div>
+    }
+}
+
+#[cfg(feature = "preview")]
+yew_preview::create_preview_with_tests!(
+    component: ToastStack,
+    default_props: ToastStackProps {
+        notifications: vec![
+            Notification {
+                id: 1,
+                kind: NotificationKind::Info,
+                text: "Bob joined".to_string(),
+            },
+            Notification {
+                id: 2,
+                kind: NotificationKind::Error,
+                text: "Connection error: timeout".to_string(),
+            },
+        ],
+    },
+    variants: [],
+    tests: [
+        ("Has main container class", exists("konnekt-toast-stack")),
+        ("Has error toast class", exists("konnekt-toast--error")),
+        ("Has dismiss button class", exists("konnekt-toast__dismiss")),
+        ("Shows toast text", has_text("Bob joined")),
+    ]
+);
@@ -1,9 +1,46 @@
+mod use_activities;
+mod use_activity;
+mod use_chat;
+mod use_connection_quality;
+mod use_host_actions;
 mod use_host_connectivity;
+mod use_i18n;
+mod use_identity;
 mod use_lobby;
+mod use_lobby_state;
+mod use_notifications;
+mod use_participants;
+mod use_pending_commands;
+mod use_presence;
+mod use_ready_check;
 mod use_session;
+mod use_session_error;
+mod use_theme;
 
+pub use use_activities::{ActivitiesState, use_activities};
+pub use use_activity::{ActivityAnswer, CurrentActivity, use_activity};
+pub use use_chat::{ChatState, use_chat};
+pub use use_connection_quality::{
+    ConnectionHealth, ConnectionQualityState, PeerQuality, use_connection_quality,
+};
+pub use use_host_actions::{HostActions, use_host_actions};
 pub use use_host_connectivity::{
     HostConnectivityOptions, HostConnectivityState, use_host_connectivity,
 };
+pub use use_i18n::use_i18n;
+pub use use_identity::{Identity, IdentityHandle, IdentityStorage, use_identity, use_identity_in};
 pub use use_lobby::use_lobby;
-pub use use_session::{ActiveRunSnapshot, P2PRole, SessionContext, WhoAmI, use_session};
+pub use use_lobby_state::{LobbyPhase, LobbyState, use_lobby_state};
+pub use use_notifications::use_notifications;
+pub use use_participants::{
+    ParticipantView, ParticipantsState, participant_views, use_participants,
+};
+pub use use_pending_commands::{PendingCommand, PendingStatus, use_pending_commands};
+pub(crate) use use_pending_commands::{apply_pending, reconcile};
+pub use use_presence::use_presence;
+pub use use_ready_check::{ReadyCheckState, use_ready_check};
+pub use use_session::{
+    ActiveRunSnapshot, LoggedEvent, P2PRole, SessionContext, SessionStatus, WhoAmI, use_session,
+};
+pub use use_session_error::{SessionError, SessionErrorOptions, use_session_error};
+pub use use_theme::use_theme;
@@ -0,0 +1,41 @@
+use konnekt_session_core::ActivityConfig;
+use yew::prelude::*;
+
+use super::{ActiveRunSnapshot, CurrentActivity, use_activity, use_session};
+
+/// Planned activities and the current run
+#[derive(Clone, PartialEq)]
+pub struct ActivitiesState {
+    /// Planned activities, next first
+    pub queue: Vec<ActivityConfig>,
+    /// The run in progress, or the one that ended last
+    pub active_run: Option<ActiveRunSnapshot>,
+    /// The current run as the local participant takes part in it
+    pub current: Option<CurrentActivity>,
+}
+
+impl ActivitiesState {
+    pub fn has_planned(&self) -> bool {
+        !self.queue.is_empty()
+    }
+}
+
+/// Headless hook to the lobby's activities
+///
+/// Host-only actions like planning and starting activities are in
+/// `use_host_actions`.
+#[hook]
+pub fn use_activities() -> ActivitiesState {
+    let session = use_session();
+    let current = use_activity();
+
+    ActivitiesState {
+        queue: session
+            .lobby
+            .as_ref()
+            .map(|lobby| lobby.activity_queue().to_vec())
+            .unwrap_or_default(),
+        active_run: session.active_run,
+        current,
+    }
+}
@@ -0,0 +1,151 @@
+use konnekt_session_core::{DomainCommand, RunStatus, SharedClock, domain::ActivityResult};
+use std::sync::Arc;
+use uuid::Uuid;
+use yew::prelude::*;
+
+use super::use_session;
+
+/// What an activity submits for the local participant
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ActivityAnswer {
+    /// Activity-specific answer, opaque to the library
+    pub data: serde_json::Value,
+    pub score: Option<u32>,
+}
+
+/// The activity currently running in the lobby
+#[derive(Clone)]
+pub struct CurrentActivity {
+    pub run_id: Uuid,
+    pub activity_type: String,
+    pub name: String,
+    /// Activity-specific config, opaque to the library
+    pub config: serde_json::Value,
+    pub status: RunStatus,
+    /// When the host started the run (Unix ms, on the host's clock)
+    pub started_at_ms: i64,
+    /// When answers are due (Unix ms, on the host's clock), for configs with
+    /// a `time_limit_ms`
+    pub deadline_ms: Option<i64>,
+    /// How far the host's clock is ahead of ours
+    pub clock_offset_ms: i64,
+    /// The clock the session runtime runs on
+    pub clock: SharedClock,
+    /// Whether the local participant is expected to submit (not spectating)
+    pub can_submit: bool,
+    /// Whether the local participant already submitted
+    pub submitted: bool,
+    /// Submit the local participant's result; the time taken is measured from
+    /// `started_at_ms`
+    pub submit_result: Callback<ActivityAnswer>,
+}
+
+impl CurrentActivity {
+    /// Now on the host's clock (Unix ms), to measure against `started_at_ms`
+    pub fn now_ms(&self) -> i64 {
+        self.clock.unix_millis() as i64 + self.clock_offset_ms
+    }
+
+    /// Milliseconds left until the deadline (negative once it passed)
+    pub fn remaining_ms(&self) -> Option<i64> {
+        self.deadline_ms.map(|deadline| deadline - self.now_ms())
+    }
+}
+
+impl PartialEq for CurrentActivity {
+    fn eq(&self, other: &Self) -> bool {
+        self.run_id == other.run_id
+            && self.activity_type == other.activity_type
+            && self.name == other.name
+            && self.config == other.config
+            && self.status == other.status
+            && self.started_at_ms == other.started_at_ms
+            && self.deadline_ms == other.deadline_ms
+            && self.clock_offset_ms == other.clock_offset_ms
+            && Arc::ptr_eq(&self.clock, &other.clock)
+            && self.can_submit == other.can_submit
+            && self.submitted == other.submitted
+            && self.submit_result == other.submit_result
+    }
+}
+
+/// Hook to the activity currently running in the lobby
+///
+/// Returns `None` while no activity has been started.
+#[hook]
+pub fn use_activity() -> Option<CurrentActivity> {
+    let session = use_session();
+    let started = use_mut_ref(|| None::<(Uuid, i64)>);
+
+    let run = session.active_run.clone()?;
+    let lobby_id = session.lobby.as_ref()?.id();
+    let participant_id = session.get_local_participant_id();
+
+    // Runs restored from logs that predate start stamps fall back to when
+    // this client first saw them
+    let started_at_ms = match run.started_at_ms {
+        Some(at) => at as i64,
+        None => {
+            let mut started = started.borrow_mut();
+            match *started {
+                Some((run_id, at)) if run_id == run.run_id => at,
+                _ => {
+                    let now = session.now_ms();
+                    *started = Some((run.run_id, now));
+                    now
+                }
+            }
+        }
+    };
+
+    let submitted =
+        participant_id.is_some_and(|id| run.results.iter().any(|r| r.participant_id == id));
+    let can_submit = participant_id.is_some_and(|id| run.required_submitters.contains(&id));
+
+    let submit_result = {
+        let send_command = session.send_command.clone();
+        let set_presence = session.set_presence.clone();
+        let run_id = run.run_id;
+        let in_progress = run.status == RunStatus::InProgress;
+        let clock = session.clock.clone();
+        let clock_offset_ms = session.clock_offset_ms;
+
+        Callback::from(move |answer: ActivityAnswer| {
+            let Some(participant_id) = participant_id.filter(|_| in_progress && !submitted) else {
+                tracing::warn!("⚠️ Ignoring result: run is over or already answered");
+                return;
+            };
+
+            // The host stamps when the result arrived
+            let now = clock.unix_millis() as i64 + clock_offset_ms;
+            let mut result = ActivityResult::new(run_id, participant_id)
+                .with_data(answer.data)
+                .with_time((now - started_at_ms).max(0) as u64);
+            if let Some(score) = answer.score {
+                result = result.with_score(score);
+            }
+
+            send_command(DomainCommand::SubmitResult {
+                lobby_id,
+                run_id,
+                result,
+            });
+            set_presence(None);
+        })
+    };
+
+    Some(CurrentActivity {
+        run_id: run.run_id,
+        deadline_ms: run.deadline_ms.map(|at| at as i64),
+        activity_type: run.activity_type,
+        name: run.name,
+        config: run.config,
+        status: run.status,
+        started_at_ms,
+        clock_offset_ms: session.clock_offset_ms,
+        clock: session.clock.clone(),
+        can_submit,
+        submitted,
+        submit_result,
+    })
+}
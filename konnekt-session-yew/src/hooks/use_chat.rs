@@ -0,0 +1,129 @@
+use konnekt_session_core::{ChatMessage, DomainCommand};
+use uuid::Uuid;
+use yew::prelude::*;
+
+use super::use_session;
+
+/// Chat of the current lobby
+#[derive(Clone, PartialEq)]
+pub struct ChatState {
+    /// Messages, oldest first
+    pub messages: Vec<ChatMessage>,
+    /// Messages of others posted since the chat was last marked read
+    pub unread: usize,
+    /// Our messages in `messages` the host has not confirmed yet
+    pub pending: Vec<Uuid>,
+    /// Our messages the host did not take, shown for a few seconds
+    pub rolled_back: Vec<ChatMessage>,
+    /// Post a message as the local participant
+    pub send: Callback<String>,
+    /// Mark everything posted so far as read
+    pub mark_read: Callback<()>,
+}
+
+/// Hook to read and post chat messages
+///
+/// Messages already in the lobby when the hook mounts count as read.
+#[hook]
+pub fn use_chat() -> ChatState {
+    let session = use_session();
+    let messages = session
+        .lobby
+        .as_ref()
+        .map(|lobby| lobby.chat_messages().to_vec())
+        .unwrap_or_default();
+    let local_id = session.get_local_participant_id();
+
+    let pending: Vec<Uuid> = session
+        .pending_commands
+        .iter()
+        .filter(|c| c.is_pending())
+        .filter_map(|c| c.chat_message().map(|m| m.id()))
+        .collect();
+    let rolled_back: Vec<ChatMessage> = session
+        .pending_commands
+        .iter()
+        .filter(|c| c.is_rolled_back())
+        .filter_map(|c| c.chat_message().cloned())
+        .collect();
+    // Pending messages get replaced by the host's copy, so they can't mark
+    // where reading stopped
+    let latest = messages
+        .iter()
+        .rev()
+        .find(|m| !pending.contains(&m.id()))
+        .map(|m| m.id());
+
+    let last_read = use_state(move || latest);
+
+    let send = {
+        let send_command = session.send_command.clone();
+        let lobby_id = session.lobby.as_ref().map(|lobby| lobby.id());
+
+        Callback::from(move |text: String| {
+            let (Some(lobby_id), Some(author_id)) = (lobby_id, local_id) else {
+                tracing::warn!("⚠️ Cannot chat before joining the lobby");
+                return;
+            };
+            send_command(DomainCommand::SendChatMessage {
+                lobby_id,
+                author_id,
+                text,
+            });
+        })
+    };
+
+    let mark_read = {
+        let last_read = last_read.clone();
+        Callback::from(move |_| {
+            if *last_read != latest {
+                last_read.set(latest);
+            }
+        })
+    };
+
+    ChatState {
+        unread: unread_count(&messages, *last_read, local_id),
+        messages,
+        pending,
+        rolled_back,
+        send,
+        mark_read,
+    }
+}
+
+/// Messages of others after `last_read` (all of them if it is unknown)
+fn unread_count(
+    messages: &[ChatMessage],
+    last_read: Option<Uuid>,
+    local_id: Option<Uuid>,
+) -> usize {
+    let start = last_read
+        .and_then(|id| messages.iter().position(|m| m.id() == id))
+        .map_or(0, |position| position + 1);
+    messages[start..]
+        .iter()
+        .filter(|m| Some(m.author_id()) != local_id)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unread_count_skips_own_and_read_messages() {
+        let me = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let messages: Vec<_> = [other, me, other, other]
+            .into_iter()
+            .map(|author| ChatMessage::new(author, "Hi".to_string()).unwrap())
+            .collect();
+
+        assert_eq!(unread_count(&messages, None, Some(me)), 3);
+        assert_eq!(unread_count(&messages, Some(messages[1].id()), Some(me)), 2);
+        assert_eq!(unread_count(&messages, Some(messages[3].id()), Some(me)), 0);
+        // The last read message fell out of the history
+        assert_eq!(unread_count(&messages, Some(Uuid::new_v4()), None), 4);
+    }
+}
@@ -0,0 +1,58 @@
+pub use konnekt_session_headless::ConnectionHealth;
+use konnekt_session_headless::connection_health;
+use konnekt_session_p2p::ConnectionQuality;
+use uuid::Uuid;
+use yew::prelude::*;
+
+use super::use_session;
+
+/// Connection to one other peer
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerQuality {
+    pub peer_id: String,
+    pub participant_id: Option<Uuid>,
+    /// Participant name, once the peer has joined the lobby
+    pub name: Option<String>,
+    pub rtt_ms: Option<u64>,
+    pub quality: ConnectionQuality,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionQualityState {
+    pub health: ConnectionHealth,
+    pub peers: Vec<PeerQuality>,
+    /// Slowest round trip among the peers
+    pub worst_rtt_ms: Option<u64>,
+}
+
+/// Hook to per-peer round trips and the overall connection health
+#[hook]
+pub fn use_connection_quality() -> ConnectionQualityState {
+    let session = use_session();
+
+    let peers: Vec<PeerQuality> = session
+        .peer_stats
+        .iter()
+        .map(|stats| PeerQuality {
+            peer_id: stats.peer_id.to_string(),
+            participant_id: stats.participant_id,
+            name: stats
+                .participant_id
+                .and_then(|id| session.lobby.as_ref()?.participants().get(&id))
+                .map(|p| p.name().to_string()),
+            rtt_ms: stats.rtt.map(|rtt| rtt.as_millis() as u64),
+            quality: stats.quality(),
+        })
+        .collect();
+
+    ConnectionQualityState {
+        health: connection_health(
+            session.is_host,
+            session.reconnecting,
+            session.lobby.is_some(),
+            &session.peer_stats,
+        ),
+        worst_rtt_ms: peers.iter().filter_map(|p| p.rtt_ms).max(),
+        peers,
+    }
+}
@@ -0,0 +1,181 @@
+use konnekt_session_core::domain::ActivityId;
+use konnekt_session_core::{ActivityConfig, DomainCommand};
+use std::rc::Rc;
+use uuid::Uuid;
+use yew::prelude::*;
+
+use super::use_session;
+
+/// What the host (and partly co-hosts) can do with the lobby
+///
+/// The callbacks do nothing for participants lacking the rights; the host
+/// also rejects such commands.
+#[derive(Clone, PartialEq)]
+pub struct HostActions {
+    pub is_host: bool,
+    /// The host or a co-host
+    pub can_moderate: bool,
+    /// Add an activity to the end of the queue
+    pub queue_activity: Callback<ActivityConfig>,
+    /// Move a planned activity to a new position in the queue
+    pub move_activity: Callback<(ActivityId, usize)>,
+    /// Activities are planned and none is running
+    pub can_start: bool,
+    /// Start the next planned activity
+    pub start_next: Callback<()>,
+    /// Cancel the activity in progress
+    pub cancel_run: Callback<()>,
+    /// Remove a guest from the lobby
+    pub kick: Callback<Uuid>,
+    /// Remove a guest and keep their peer out of the session
+    pub ban: Callback<Uuid>,
+    /// Hand the host role to another participant
+    pub make_host: Callback<Uuid>,
+    /// Make a guest co-host (`true`) or demote them (`false`)
+    pub set_co_host: Callback<(Uuid, bool)>,
+}
+
+/// Headless hook to the host's lobby and activity controls
+#[hook]
+pub fn use_host_actions() -> HostActions {
+    let session = use_session();
+    let lobby_id = session.lobby.as_ref().map(|lobby| lobby.id());
+    let local_id = session.get_local_participant_id();
+    let can_moderate = session
+        .lobby
+        .as_ref()
+        .zip(local_id)
+        .is_some_and(|(lobby, id)| lobby.can_moderate(id));
+    let is_host = session.is_host;
+    let can_start = session
+        .lobby
+        .as_ref()
+        .is_some_and(|lobby| !lobby.activity_queue().is_empty() && !lobby.has_active_run());
+
+    let send = {
+        let send_command = session.send_command.clone();
+        Rc::new(
+            move |allowed: bool, command: Option<DomainCommand>| match command {
+                Some(command) if allowed => send_command(command),
+                _ => tracing::warn!("⚠️ Ignoring host action: not allowed or not in a lobby"),
+            },
+        )
+    };
+
+    let queue_activity = {
+        let send = send.clone();
+        Callback::from(move |config: ActivityConfig| {
+            send(
+                is_host,
+                lobby_id.map(|lobby_id| DomainCommand::QueueActivity { lobby_id, config }),
+            );
+        })
+    };
+
+    let move_activity = {
+        let send = send.clone();
+        Callback::from(move |(activity_id, to_index): (ActivityId, usize)| {
+            send(
+                can_moderate,
+                lobby_id.zip(local_id).map(|(lobby_id, requester_id)| {
+                    DomainCommand::MoveQueuedActivity {
+                        lobby_id,
+                        activity_id,
+                        to_index,
+                        requester_id,
+                    }
+                }),
+            );
+        })
+    };
+
+    let start_next = {
+        let send = send.clone();
+        Callback::from(move |_| {
+            send(
+                is_host && can_start,
+                lobby_id.map(|lobby_id| DomainCommand::StartNextRun { lobby_id }),
+            );
+        })
+    };
+
+    let cancel_run = {
+        let send = send.clone();
+        let run_id = session.active_run.as_ref().map(|run| run.run_id);
+        Callback::from(move |_| {
+            send(
+                is_host,
+                lobby_id
+                    .zip(run_id)
+                    .map(|(lobby_id, run_id)| DomainCommand::CancelRun { lobby_id, run_id }),
+            );
+        })
+    };
+
+    let kick_guest = {
+        let send = send.clone();
+        move |ban: bool| {
+            let send = send.clone();
+            Callback::from(move |guest_id: Uuid| {
+                send(
+                    can_moderate,
+                    lobby_id
+                        .zip(local_id)
+                        .map(|(lobby_id, host_id)| DomainCommand::KickGuest {
+                            lobby_id,
+                            host_id,
+                            guest_id,
+                            ban,
+                        }),
+                );
+            })
+        }
+    };
+
+    let make_host = {
+        let send = send.clone();
+        Callback::from(move |new_host_id: Uuid| {
+            send(
+                is_host,
+                lobby_id.zip(local_id).map(|(lobby_id, current_host_id)| {
+                    DomainCommand::DelegateHost {
+                        lobby_id,
+                        current_host_id,
+                        new_host_id,
+                    }
+                }),
+            );
+        })
+    };
+
+    let set_co_host = {
+        let send = send.clone();
+        Callback::from(move |(participant_id, co_host): (Uuid, bool)| {
+            send(
+                is_host,
+                lobby_id
+                    .zip(local_id)
+                    .map(|(lobby_id, host_id)| DomainCommand::SetCoHost {
+                        lobby_id,
+                        host_id,
+                        participant_id,
+                        co_host,
+                    }),
+            );
+        })
+    };
+
+    HostActions {
+        is_host,
+        can_moderate,
+        queue_activity,
+        move_activity,
+        can_start,
+        start_next,
+        cancel_run,
+        kick: kick_guest(false),
+        ban: kick_guest(true),
+        make_host,
+        set_co_host,
+    }
+}
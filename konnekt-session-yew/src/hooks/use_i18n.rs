@@ -0,0 +1,10 @@
+use yew::prelude::*;
+
+use crate::providers::I18n;
+
+/// Hook to the translations of the nearest `I18nProvider` (English outside
+/// of one)
+#[hook]
+pub fn use_i18n() -> I18n {
+    use_context::<I18n>().unwrap_or_default()
+}
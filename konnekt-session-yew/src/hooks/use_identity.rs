@@ -0,0 +1,178 @@
+use gloo::storage::{LocalStorage, SessionStorage, Storage};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use yew::prelude::*;
+
+const STORAGE_KEY: &str = "konnekt-session.identity";
+
+/// Resume tokens of older sessions are dropped beyond this many
+const MAX_RESUME_TOKENS: usize = 10;
+
+/// Where [`use_identity_in`] keeps the identity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdentityStorage {
+    /// Shared by all tabs and kept across browser restarts
+    #[default]
+    Local,
+    /// Per tab, so several tabs can be different participants
+    Session,
+}
+
+impl IdentityStorage {
+    fn load(self) -> Identity {
+        let stored = match self {
+            IdentityStorage::Local => LocalStorage::get(STORAGE_KEY),
+            IdentityStorage::Session => SessionStorage::get(STORAGE_KEY),
+        };
+        stored.unwrap_or_default()
+    }
+
+    fn save(self, identity: &Identity) {
+        let result = match self {
+            IdentityStorage::Local => LocalStorage::set(STORAGE_KEY, identity),
+            IdentityStorage::Session => SessionStorage::set(STORAGE_KEY, identity),
+        };
+        if let Err(e) = result {
+            tracing::warn!("⚠️ Could not store identity: {}", e);
+        }
+    }
+}
+
+/// Who the local user is, across page reloads
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Identity {
+    pub name: Option<String>,
+    /// Emoji or image URL chosen by the user
+    pub avatar: Option<String>,
+    /// Participant we were in each session, by session ID, oldest first
+    #[serde(default)]
+    resume_tokens: Vec<(String, Uuid)>,
+}
+
+impl Identity {
+    /// Participant to resume as in `session_id`
+    pub fn resume_token(&self, session_id: &str) -> Option<Uuid> {
+        self.resume_tokens
+            .iter()
+            .find(|(session, _)| session == session_id)
+            .map(|(_, participant_id)| *participant_id)
+    }
+
+    pub fn with_resume_token(mut self, session_id: &str, participant_id: Uuid) -> Self {
+        self.resume_tokens
+            .retain(|(session, _)| session != session_id);
+        self.resume_tokens
+            .push((session_id.to_string(), participant_id));
+        let excess = self.resume_tokens.len().saturating_sub(MAX_RESUME_TOKENS);
+        self.resume_tokens.drain(..excess);
+        self
+    }
+}
+
+/// Stored identity and callbacks to change it
+#[derive(Clone, PartialEq)]
+pub struct IdentityHandle {
+    pub identity: Identity,
+    pub set_name: Callback<String>,
+    pub set_avatar: Callback<Option<String>>,
+    /// Remember the participant we are in a session (session ID, participant)
+    pub remember: Callback<(String, Uuid)>,
+    /// Forget everything stored
+    pub clear: Callback<()>,
+}
+
+/// Hook to the identity kept in `localStorage`
+///
+/// `SessionProvider` with `persist_identity` uses it to rejoin a session as
+/// the same participant after a reload.
+#[hook]
+pub fn use_identity() -> IdentityHandle {
+    use_identity_in(IdentityStorage::Local)
+}
+
+/// Hook to the identity kept in `storage`
+#[hook]
+pub fn use_identity_in(storage: IdentityStorage) -> IdentityHandle {
+    let identity = use_state(|| storage.load());
+
+    let set_name = {
+        let identity = identity.clone();
+        Callback::from(move |name: String| {
+            update(storage, &identity, |current| Identity {
+                name: Some(name),
+                ..current
+            })
+        })
+    };
+
+    let set_avatar = {
+        let identity = identity.clone();
+        Callback::from(move |avatar: Option<String>| {
+            update(storage, &identity, |current| Identity { avatar, ..current })
+        })
+    };
+
+    let remember = {
+        let identity = identity.clone();
+        Callback::from(move |(session_id, participant_id): (String, Uuid)| {
+            update(storage, &identity, |current| {
+                current.with_resume_token(&session_id, participant_id)
+            })
+        })
+    };
+
+    let clear = {
+        let identity = identity.clone();
+        Callback::from(move |_| update(storage, &identity, |_| Identity::default()))
+    };
+
+    IdentityHandle {
+        identity: (*identity).clone(),
+        set_name,
+        set_avatar,
+        remember,
+        clear,
+    }
+}
+
+/// Apply `change` to the stored identity
+///
+/// Starts from storage rather than the state, so handles in several
+/// components don't overwrite each other's changes.
+fn update(
+    storage: IdentityStorage,
+    identity: &UseStateHandle<Identity>,
+    change: impl FnOnce(Identity) -> Identity,
+) {
+    let current = storage.load();
+    let next = change(current.clone());
+    if next != current {
+        storage.save(&next);
+    }
+    if next != **identity {
+        identity.set(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_tokens_keep_latest_sessions() {
+        let mut identity = Identity::default();
+        let first = Uuid::new_v4();
+        identity = identity.with_resume_token("session-0", first);
+        for i in 1..=MAX_RESUME_TOKENS {
+            identity = identity.with_resume_token(&format!("session-{}", i), Uuid::new_v4());
+        }
+        assert_eq!(identity.resume_token("session-0"), None);
+        assert!(identity.resume_token("session-1").is_some());
+
+        let rejoined = Uuid::new_v4();
+        identity = identity.with_resume_token("session-1", rejoined);
+        assert_eq!(identity.resume_token("session-1"), Some(rejoined));
+        assert_eq!(identity.resume_tokens.len(), MAX_RESUME_TOKENS);
+        assert_eq!(identity.resume_tokens.last().unwrap().0, "session-1");
+    }
+}
@@ -0,0 +1,40 @@
+use konnekt_session_core::Lobby;
+pub use konnekt_session_headless::LobbyPhase;
+use konnekt_session_headless::lobby_phase;
+use konnekt_session_p2p::SessionId;
+use yew::prelude::*;
+
+use super::use_session;
+
+/// Lobby as a whole, without participants or activities in detail
+#[derive(Clone, PartialEq)]
+pub struct LobbyState {
+    pub phase: LobbyPhase,
+    pub session_id: SessionId,
+    /// `None` until the lobby synced
+    pub lobby: Option<Lobby>,
+    pub name: Option<String>,
+    pub is_host: bool,
+    pub peer_count: usize,
+    pub error: Option<String>,
+}
+
+/// Headless hook to the lobby's phase and metadata
+#[hook]
+pub fn use_lobby_state() -> LobbyState {
+    let session = use_session();
+
+    LobbyState {
+        phase: lobby_phase(
+            session.lobby.is_some(),
+            session.runtime_error.is_some(),
+            session.active_run.as_ref().map(|run| run.status),
+        ),
+        session_id: session.session_id.clone(),
+        name: session.lobby.as_ref().map(|lobby| lobby.name().to_string()),
+        lobby: session.lobby,
+        is_host: session.is_host,
+        peer_count: session.peer_count,
+        error: session.runtime_error,
+    }
+}
@@ -0,0 +1,14 @@
+use yew::prelude::*;
+
+use crate::providers::Notifications;
+
+/// Hook to the toasts of the nearest `NotificationProvider`
+///
+/// ```rust,ignore
+/// let notifications = use_notifications();
+/// notifications.success("Saved");
+/// ```
+#[hook]
+pub fn use_notifications() -> Notifications {
+    use_context::<Notifications>().unwrap_or_default()
+}
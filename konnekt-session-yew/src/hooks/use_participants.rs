@@ -0,0 +1,69 @@
+use konnekt_session_core::{DomainCommand, ParticipantAvatar};
+pub use konnekt_session_headless::{ParticipantView, participant_views};
+use yew::prelude::*;
+
+use super::use_session;
+
+/// Participants of the lobby plus what the local participant can do
+#[derive(Clone, PartialEq)]
+pub struct ParticipantsState {
+    /// Host first, then in the order they joined
+    pub participants: Vec<ParticipantView>,
+    /// The local participant, once known
+    pub me: Option<ParticipantView>,
+    /// Switch the local participant between active and spectating
+    pub toggle_participation: Callback<()>,
+    /// Pick the local participant's avatar (`None`: the generated one)
+    pub set_avatar: Callback<Option<ParticipantAvatar>>,
+}
+
+/// Headless hook to the lobby's participants
+#[hook]
+pub fn use_participants() -> ParticipantsState {
+    let session = use_session();
+    let local_id = session.get_local_participant_id();
+
+    let participants = session
+        .lobby
+        .as_ref()
+        .map(|lobby| participant_views(lobby, local_id, &session.presence))
+        .unwrap_or_default();
+
+    let toggle_participation = {
+        let send_command = session.send_command.clone();
+        let lobby_id = session.lobby.as_ref().map(|lobby| lobby.id());
+
+        Callback::from(move |_| {
+            if let (Some(lobby_id), Some(participant_id)) = (lobby_id, local_id) {
+                send_command(DomainCommand::ToggleParticipationMode {
+                    lobby_id,
+                    participant_id,
+                    requester_id: participant_id,
+                });
+            }
+        })
+    };
+
+    let set_avatar = {
+        let send_command = session.send_command.clone();
+        let lobby_id = session.lobby.as_ref().map(|lobby| lobby.id());
+
+        Callback::from(move |avatar: Option<ParticipantAvatar>| {
+            if let (Some(lobby_id), Some(participant_id)) = (lobby_id, local_id) {
+                send_command(DomainCommand::SetAvatar {
+                    lobby_id,
+                    participant_id,
+                    requester_id: participant_id,
+                    avatar,
+                });
+            }
+        })
+    };
+
+    ParticipantsState {
+        me: participants.iter().find(|p| p.is_me).cloned(),
+        participants,
+        toggle_participation,
+        set_avatar,
+    }
+}
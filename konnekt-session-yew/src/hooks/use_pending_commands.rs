@@ -0,0 +1,15 @@
+pub use konnekt_session_headless::{PendingCommand, PendingStatus};
+pub(crate) use konnekt_session_headless::{apply_pending, reconcile};
+use yew::prelude::*;
+
+use super::use_session;
+
+/// Hook to the commands shown before the host confirmed them, and the ones
+/// rolled back recently
+///
+/// The session's lobby and active run already include the effects of the
+/// pending ones; use this to mark them as pending or rolled back.
+#[hook]
+pub fn use_pending_commands() -> Vec<PendingCommand> {
+    use_session().pending_commands
+}
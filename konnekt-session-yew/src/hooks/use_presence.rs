@@ -0,0 +1,15 @@
+use konnekt_session_p2p::Presence;
+use uuid::Uuid;
+use yew::prelude::*;
+
+use super::use_session;
+
+/// Hook to access presence signals of other participants (convenience wrapper)
+///
+/// Signals are transient: they disappear a few seconds after a participant
+/// stops typing or answering.
+#[hook]
+pub fn use_presence() -> Vec<(Uuid, Presence)> {
+    let session = use_session();
+    session.presence
+}
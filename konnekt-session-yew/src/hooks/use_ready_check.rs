@@ -0,0 +1,84 @@
+use konnekt_session_core::DomainCommand;
+use std::collections::HashSet;
+use uuid::Uuid;
+use yew::prelude::*;
+
+use super::use_session;
+
+/// Who is ready for the next activity and the countdown to it
+#[derive(Clone, PartialEq)]
+pub struct ReadyCheckState {
+    /// Participants who said they are ready
+    pub ready: HashSet<Uuid>,
+    pub me_ready: bool,
+    /// Every active participant is ready
+    pub all_ready: bool,
+    /// When the countdown to the next activity ends (Unix ms), the same in
+    /// every browser
+    pub countdown_ends_at_ms: Option<i64>,
+    /// The local participant may start the countdown (host or co-host, with
+    /// an activity planned and none running)
+    pub can_start_countdown: bool,
+    /// Say the local participant is (not) ready
+    pub set_ready: Callback<bool>,
+    /// Count down this many seconds, then the host starts the next activity
+    pub start_countdown: Callback<u32>,
+}
+
+/// Headless hook to the ready check before an activity
+#[hook]
+pub fn use_ready_check() -> ReadyCheckState {
+    let session = use_session();
+    let lobby = session.lobby.as_ref();
+    let lobby_id = lobby.map(|lobby| lobby.id());
+    let local_id = session.get_local_participant_id();
+
+    let ready = lobby.map(|lobby| lobby.ready().clone()).unwrap_or_default();
+    let me_ready = local_id.is_some_and(|id| ready.contains(&id));
+    let can_start_countdown = lobby.zip(local_id).is_some_and(|(lobby, id)| {
+        lobby.can_moderate(id) && !lobby.activity_queue().is_empty() && !lobby.has_active_run()
+    });
+
+    let set_ready = {
+        let send_command = session.send_command.clone();
+        Callback::from(move |ready: bool| match lobby_id.zip(local_id) {
+            Some((lobby_id, participant_id)) => send_command(DomainCommand::SetReady {
+                lobby_id,
+                participant_id,
+                ready,
+            }),
+            None => tracing::warn!("⚠️ Not in a lobby, ignoring ready change"),
+        })
+    };
+
+    let start_countdown = {
+        let send_command = session.send_command.clone();
+        let clock = session.clock.clone();
+        let clock_offset_ms = session.clock_offset_ms;
+        Callback::from(move |seconds: u32| match lobby_id.zip(local_id) {
+            Some((lobby_id, requester_id)) if can_start_countdown => {
+                // On the host's clock, like everyone reading it
+                let now_ms = clock.unix_millis() as i64 + clock_offset_ms;
+                let ends_at = now_ms as u64 + u64::from(seconds) * 1_000;
+                send_command(DomainCommand::StartCountdown {
+                    lobby_id,
+                    requester_id,
+                    ends_at,
+                });
+            }
+            _ => tracing::warn!("⚠️ Ignoring countdown: not allowed or not in a lobby"),
+        })
+    };
+
+    ReadyCheckState {
+        me_ready,
+        all_ready: lobby.is_some_and(|lobby| lobby.all_ready()),
+        countdown_ends_at_ms: lobby
+            .and_then(|lobby| lobby.countdown_ends_at())
+            .map(|ends_at| ends_at as i64),
+        can_start_countdown,
+        ready,
+        set_ready,
+        start_countdown,
+    }
+}
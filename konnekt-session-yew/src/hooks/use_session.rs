@@ -1,20 +1,16 @@
 use konnekt_session_core::{
-    DomainCommand, Lobby, LobbyRole, Participant, ParticipationMode, RunStatus,
+    DomainCommand, Lobby, LobbyRole, Participant, ParticipationMode, ResultsAnalytics,
+    SessionMetrics, SharedClock, SystemClock,
 };
-use konnekt_session_p2p::SessionId;
+pub use konnekt_session_headless::{ActiveRunSnapshot, LoggedEvent, SessionStatus};
+use konnekt_session_headless::{StatusInputs, session_status};
+use konnekt_session_p2p::{PeerStats, Presence, QueueDepths, SessionId};
 use std::rc::Rc;
+use std::sync::Arc;
 use uuid::Uuid;
 use yew::prelude::*;
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct ActiveRunSnapshot {
-    pub run_id: Uuid,
-    pub status: RunStatus,
-    pub name: String,
-    pub config: serde_json::Value,
-    pub required_submitters: Vec<Uuid>,
-    pub results: Vec<konnekt_session_core::domain::ActivityResult>,
-}
+use super::PendingCommand;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum P2PRole {
@@ -38,20 +34,74 @@ pub struct SessionContext {
     pub session_id: SessionId,
     pub lobby: Option<Lobby>,
     pub peer_count: usize,
+    /// Round trip and status of every other peer (refreshed about once a second)
+    pub peer_stats: Vec<PeerStats>,
+    /// Lost signalling and trying to get back
+    pub reconnecting: bool,
+    /// How far the host's clock is ahead of ours, in ms (see `now_ms`)
+    pub clock_offset_ms: i64,
+    /// The clock the session runtime runs on (see `now_ms`)
+    pub clock: SharedClock,
     pub is_host: bool,
     pub active_run: Option<ActiveRunSnapshot>,
     pub local_participant_id: Option<Uuid>,
     pub local_peer_id: Option<String>,
 
     /// Send commands to the session runtime
+    ///
+    /// Toggling participation, queueing activities, chatting and submitting
+    /// results show up in `lobby` and `active_run` right away and are rolled
+    /// back if the host does not confirm them (see `pending_commands`).
     pub send_command: Rc<dyn Fn(DomainCommand)>,
 
+    /// Commands shown before the host confirmed them, and recently rolled
+    /// back ones
+    pub pending_commands: Vec<PendingCommand>,
+
+    /// What other participants are doing right now (typing, answering)
+    pub presence: Vec<(Uuid, Presence)>,
+
+    /// Tell the others what we are doing (`None` clears it)
+    pub set_presence: Rc<dyn Fn(Option<Presence>)>,
+
     /// Our participant name (immutable)
     pub local_participant_name: Option<String>,
     pub runtime_error: Option<String>,
+    /// The host's lobby could not be read (see `use_session_error`)
+    pub protocol_error: Option<String>,
+    /// The host or a co-host removed us; we don't rejoin on our own then
+    pub kicked: bool,
+    /// Score distribution and progression over the completed runs
+    pub results: ResultsAnalytics,
+    /// Answer times, participation and drop-offs, including the run in progress
+    pub metrics: SessionMetrics,
+
+    /// Latest domain events, oldest first; only recorded with the provider's
+    /// `dev_tools`
+    pub event_log: Rc<Vec<LoggedEvent>>,
+    /// Work waiting in the session runtime; only updated with `dev_tools`
+    pub queue_depths: QueueDepths,
 }
 
 impl SessionContext {
+    /// Where the session is between connecting and a synced lobby
+    pub fn status(&self) -> SessionStatus {
+        session_status(StatusInputs {
+            is_host: self.is_host,
+            has_peer_id: self.local_peer_id.is_some(),
+            peer_count: self.peer_count,
+            synced: self.lobby.is_some(),
+            reconnecting: self.reconnecting,
+            failed: self.runtime_error.is_some(),
+        })
+    }
+
+    /// Now on the host's clock (Unix ms), which countdowns, deadlines and
+    /// result timestamps use
+    pub fn now_ms(&self) -> i64 {
+        self.clock.unix_millis() as i64 + self.clock_offset_ms
+    }
+
     /// Rich identity view combining P2P and lobby/domain identity.
     pub fn who_am_i_info(&self) -> WhoAmI {
         let participant = self.who_am_i();
@@ -103,17 +153,61 @@ impl SessionContext {
     }
 }
 
+/// A fresh session with no lobby yet and commands that go nowhere (tests,
+/// previews)
+impl Default for SessionContext {
+    fn default() -> Self {
+        Self {
+            session_id: SessionId::new(),
+            lobby: None,
+            peer_count: 0,
+            peer_stats: Vec::new(),
+            reconnecting: false,
+            clock_offset_ms: 0,
+            clock: SystemClock::shared(),
+            is_host: false,
+            active_run: None,
+            local_participant_id: None,
+            local_peer_id: None,
+            send_command: Rc::new(|_| {}),
+            pending_commands: Vec::new(),
+            presence: Vec::new(),
+            set_presence: Rc::new(|_| {}),
+            local_participant_name: None,
+            runtime_error: None,
+            protocol_error: None,
+            kicked: false,
+            results: ResultsAnalytics::default(),
+            metrics: SessionMetrics::default(),
+            event_log: Rc::new(Vec::new()),
+            queue_depths: QueueDepths::default(),
+        }
+    }
+}
+
 impl PartialEq for SessionContext {
     fn eq(&self, other: &Self) -> bool {
         self.session_id == other.session_id
             && self.lobby == other.lobby
             && self.peer_count == other.peer_count
+            && self.peer_stats == other.peer_stats
+            && self.reconnecting == other.reconnecting
+            && self.clock_offset_ms == other.clock_offset_ms
+            && Arc::ptr_eq(&self.clock, &other.clock)
             && self.is_host == other.is_host
             && self.active_run == other.active_run
             && self.local_participant_id == other.local_participant_id
             && self.local_peer_id == other.local_peer_id
+            && self.pending_commands == other.pending_commands
+            && self.presence == other.presence
             && self.local_participant_name == other.local_participant_name
             && self.runtime_error == other.runtime_error
+            && self.protocol_error == other.protocol_error
+            && self.kicked == other.kicked
+            && self.results == other.results
+            && self.metrics == other.metrics
+            && self.event_log == other.event_log
+            && self.queue_depths == other.queue_depths
     }
 }
 
@@ -132,10 +226,11 @@ impl PartialEq for SessionContext {
 ///     let session_id = &session.session_id;
 ///     let is_host = session.is_host;
 ///     let peer_count = session.peer_count;
+///     let status = session.status();
 ///
 ///     html! {
 ///         <div>
-///             <p>{format!("Peers: {}", peer_count)}</p>
+///             <p>{format!("Peers: {} ({:?})", peer_count, status)}</p>
 ///         </div>
 ///     }
 /// }
@@ -0,0 +1,44 @@
+use gloo_timers::callback::Timeout;
+pub use konnekt_session_headless::{SessionError, SessionErrorOptions};
+use konnekt_session_headless::{SessionHealth, session_error};
+use yew::prelude::*;
+
+use super::{HostConnectivityOptions, use_host_connectivity, use_session};
+
+/// Headless hook to what stops the session, if anything
+#[hook]
+pub fn use_session_error(options: SessionErrorOptions) -> Option<SessionError> {
+    let session = use_session();
+    let synced = session.lobby.is_some();
+    let sync_timed_out = use_state(|| false);
+    let host_connectivity = use_host_connectivity(
+        session.is_host,
+        session.peer_count,
+        HostConnectivityOptions {
+            enabled: synced,
+            unreachable_delay_ms: options.lobby_closed_after_ms,
+        },
+    );
+
+    {
+        let sync_timed_out = sync_timed_out.clone();
+        use_effect_with(
+            (synced, options.sync_timeout_ms),
+            move |(synced, sync_timeout_ms)| {
+                sync_timed_out.set(false);
+                let timeout = (!*synced)
+                    .then(|| Timeout::new(*sync_timeout_ms, move || sync_timed_out.set(true)));
+                move || drop(timeout)
+            },
+        );
+    }
+
+    session_error(&SessionHealth {
+        runtime_error: session.runtime_error,
+        protocol_error: session.protocol_error,
+        synced,
+        sync_timed_out: *sync_timed_out,
+        kicked: session.kicked,
+        host_gone: host_connectivity.host_unreachable,
+    })
+}
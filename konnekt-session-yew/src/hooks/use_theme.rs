@@ -0,0 +1,10 @@
+use yew::prelude::*;
+
+use crate::providers::Theme;
+
+/// Hook to the theme of the nearest `ThemeProvider` (the light theme
+/// outside of one)
+#[hook]
+pub fn use_theme() -> Theme {
+    use_context::<Theme>().unwrap_or_default()
+}
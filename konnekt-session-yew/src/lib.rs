@@ -1,6 +1,12 @@
 //! # Konnekt Session Yew Components
 //!
 //! Reusable Yew components for building P2P session UIs.
+//!
+//! The components are thin views over headless hooks (`use_lobby_state`,
+//! `use_participants`, `use_activities`, `use_host_actions`) that return
+//! data and callbacks only, so apps can also build a fully custom UI.
+//! Their actions show up at once and are rolled back if the host rejects
+//! them (`use_pending_commands`).
 
 pub mod app;
 pub mod components;
@@ -9,12 +15,30 @@ pub mod pages;
 #[cfg(feature = "preview")]
 pub mod preview;
 pub mod providers;
+pub mod router;
 
 // Re-exports for convenience
 pub use app::App;
-pub use components::{ActivityList, LobbyView, ParticipantList, SessionInfo};
+pub use components::{
+    ActivityList, ActivityProps, ActivityQueueEditor, ActivityRunner, Avatar, AvatarPicker,
+    ChatInput, ChatPanel, ConnectionBanner, Countdown, DiagnosticsPanel, JoinLink, LobbyView,
+    ParticipantGrouping, ParticipantList, ParticipantSort, ReadyCheck, ResultsChart,
+    SessionAnalytics, SessionDevTools, SessionErrorBoundary, SessionErrorView, SessionInfo,
+    SessionLoading, Skeleton, SkeletonShape, SpectatorView, ToastStack,
+};
 pub use hooks::{
-    HostConnectivityOptions, HostConnectivityState, use_host_connectivity, use_lobby, use_session,
+    ActivitiesState, ActivityAnswer, ChatState, ConnectionHealth, ConnectionQualityState,
+    CurrentActivity, HostActions, HostConnectivityOptions, HostConnectivityState, Identity,
+    IdentityHandle, IdentityStorage, LobbyPhase, LobbyState, LoggedEvent, ParticipantView,
+    ParticipantsState, PendingCommand, PendingStatus, ReadyCheckState, SessionError,
+    SessionErrorOptions, SessionStatus, use_activities, use_activity, use_chat,
+    use_connection_quality, use_host_actions, use_host_connectivity, use_i18n, use_identity,
+    use_identity_in, use_lobby, use_lobby_state, use_notifications, use_participants,
+    use_pending_commands, use_presence, use_ready_check, use_session, use_session_error, use_theme,
 };
 pub use pages::{LoginScreen, SessionScreen};
-pub use providers::{SessionProvider, SessionProviderProps};
+pub use providers::{
+    Catalog, I18n, I18nProvider, I18nProviderProps, Notification, NotificationKind,
+    NotificationProvider, NotificationProviderProps, Notifications, SessionProvider,
+    SessionProviderProps, Theme, ThemeProvider, ThemeProviderProps,
+};
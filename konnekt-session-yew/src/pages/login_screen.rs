@@ -8,6 +8,14 @@ pub struct LoginScreenProps {
     /// Optional pre-filled session ID (from URL params)
     #[prop_or_default]
     pub initial_session_id: Option<String>,
+
+    /// Optional pre-filled participant name (e.g. a remembered identity)
+    #[prop_or_default]
+    pub initial_name: Option<String>,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
 }
 
 #[function_component(LoginScreen)]
@@ -22,12 +30,22 @@ pub fn login_screen(props: &LoginScreenProps) -> Html {
     });
 
     let lobby_name = use_state(|| "My Lobby".to_string());
-    let host_name = use_state(|| "Host".to_string());
+    let host_name = use_state(|| {
+        props
+            .initial_name
+            .clone()
+            .unwrap_or_else(|| "Host".to_string())
+    });
 
     // ✅ FIX: Use initial_session_id if provided
     let session_id = use_state(|| props.initial_session_id.clone().unwrap_or_default());
 
-    let guest_name = use_state(|| "Guest".to_string());
+    let guest_name = use_state(|| {
+        props
+            .initial_name
+            .clone()
+            .unwrap_or_else(|| "Guest".to_string())
+    });
 
     let on_mode_change = {
         let mode = mode.clone();
@@ -55,7 +73,7 @@ pub fn login_screen(props: &LoginScreenProps) -> Html {
     };
 
     html! {
-        <div class="konnekt-login">
+        <div class={classes!("konnekt-login", props.classes.clone())} style={props.style.clone()}>
             <h1 class="konnekt-login__title">{"Konnekt Session"}</h1>
 
             <div class="konnekt-login__tabs">
@@ -1,9 +1,16 @@
 use crate::components::{
-    ActivityList, ActivityPlanner, ActivitySubmission, ParticipantList, SessionInfo,
+    ActivityList, ActivityPlanner, ActivityQueueEditor, ActivitySubmission, AvatarPicker,
+    ChatPanel, Countdown, DiagnosticsPanel, ParticipantList, ReadyCheck, ResultsChart,
+    SessionAnalytics, SessionDevTools, SessionInfo, SessionLoading, SpectatorView,
 };
-use crate::hooks::{HostConnectivityOptions, use_host_connectivity, use_session};
+use crate::hooks::{
+    HostActions, HostConnectivityOptions, LobbyPhase, ParticipantsState, ReadyCheckState,
+    SessionContext, use_chat, use_host_actions, use_host_connectivity, use_i18n, use_lobby_state,
+    use_participants, use_ready_check, use_session,
+};
+use crate::providers::I18n;
 use chrono::Utc;
-use konnekt_session_core::{DomainCommand, RunStatus};
+use web_sys::HtmlElement;
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
@@ -13,18 +20,39 @@ pub struct SessionScreenProps {
     pub show_host_connectivity_warning: bool,
     #[prop_or(5_000)]
     pub host_disconnect_grace_ms: u32,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum ViewMode {
-    Lobby,
-    ActivityInProgress,
+    /// Show the connectivity diagnostics debug panel
+    #[prop_or_default]
+    pub show_diagnostics: bool,
+    /// Show the session dev tools (set `dev_tools` on the `SessionProvider`
+    /// too for the event log)
+    #[prop_or_default]
+    pub show_dev_tools: bool,
+    /// Show the lobby chat
+    #[prop_or(true)]
+    pub show_chat: bool,
+    /// Ask participants whether they are ready before an activity and count
+    /// down to it
+    #[prop_or(true)]
+    pub show_ready_check: bool,
+    /// Length of that countdown in seconds
+    #[prop_or(3)]
+    pub countdown_secs: u32,
+    #[prop_or_default]
+    pub classes: Classes,
+    #[prop_or_default]
+    pub style: Option<AttrValue>,
 }
 
 #[function_component(SessionScreen)]
 pub fn session_screen(props: &SessionScreenProps) -> Html {
     let session = use_session();
-    let view_mode = use_state(|| ViewMode::Lobby);
+    let i18n = use_i18n();
+    let lobby_state = use_lobby_state();
+    let participants = use_participants();
+    let chat = use_chat();
+    let ready_check = use_ready_check();
+    let host_actions = use_host_actions();
+    let spectating = participants.me.as_ref().is_some_and(|me| !me.active);
     let host_connectivity = use_host_connectivity(
         session.is_host,
         session.peer_count,
@@ -34,51 +62,27 @@ pub fn session_screen(props: &SessionScreenProps) -> Html {
         },
     );
 
+    // Move focus to the new content when switching between lobby and
+    // activity, so keyboard and screen reader users aren't left behind
+    let main_ref = use_node_ref();
     {
-        let view_mode = view_mode.clone();
-        let active_run = session.active_run.clone();
-
-        use_effect_with(active_run, move |run| {
-            if let Some(run) = run {
-                if run.status == RunStatus::InProgress {
-                    view_mode.set(ViewMode::ActivityInProgress);
-                } else {
-                    view_mode.set(ViewMode::Lobby);
-                }
-            } else {
-                view_mode.set(ViewMode::Lobby);
+        let main_ref = main_ref.clone();
+        let first_render = use_mut_ref(|| true);
+        use_effect_with(lobby_state.phase, move |_| {
+            if !first_render.replace(false)
+                && let Some(main) = main_ref.cast::<HtmlElement>()
+            {
+                let _ = main.focus();
             }
             || ()
         });
     }
 
-    let on_toggle_participation = {
-        let send_command = session.send_command.clone();
-        let lobby = session.lobby.clone();
-        let session_clone = session.clone();
-
-        Callback::from(move |_: MouseEvent| {
-            if let (Some(lobby), Some(participant_id)) =
-                (&lobby, session_clone.get_local_participant_id())
-            {
-                send_command(DomainCommand::ToggleParticipationMode {
-                    lobby_id: lobby.id(),
-                    participant_id,
-                    requester_id: participant_id,
-                });
-            }
-        })
-    };
-
     html! {
-        <div class="konnekt-session-screen">
+        <div class={classes!("konnekt-session-screen", props.classes.clone())} style={props.style.clone()}>
             <header class="konnekt-session-screen__header">
                 <h1 class="konnekt-session-screen__title">
-                    {if let Some(lobby) = session.lobby.as_ref() {
-                        lobby.name().to_string()
-                    } else {
-                        "Loading...".to_string()
-                    }}
+                    {lobby_state.name.clone().unwrap_or_else(|| "Loading...".to_string())}
                 </h1>
                 <button
                     class="konnekt-session-screen__leave-btn"
@@ -110,39 +114,97 @@ pub fn session_screen(props: &SessionScreenProps) -> Html {
                     })}
             />
 
-            {match *view_mode {
-                ViewMode::Lobby => render_lobby_view(
-                    &session.lobby,
-                    &session.active_run,
-                    session.is_host,
-                    session.peer_count,
-                    session.runtime_error.clone(),
-                    session.get_local_participant_id(),
-                    on_toggle_participation,
-                ),
-                ViewMode::ActivityInProgress => html! {
-                    <ActivitySubmission
-                        lobby={session.lobby.clone()}
-                        active_run={session.active_run.clone()}
-                        is_host={session.is_host}
-                        participant_id={session.get_local_participant_id()}
+            <main class="konnekt-session-screen__main" ref={main_ref} tabindex="-1">
+                {match (lobby_state.phase, &session.lobby, &session.active_run) {
+                    (LobbyPhase::Activity, Some(lobby), Some(active_run)) if spectating => html! {
+                        <SpectatorView
+                            lobby={lobby.clone()}
+                            active_run={active_run.clone()}
+                            presence={session.presence.clone()}
+                            on_toggle_participation={participants.toggle_participation.clone()}
+                            on_cancel={session.is_host.then(|| host_actions.cancel_run.clone())}
+                        />
+                    },
+                    (LobbyPhase::Activity, _, _) => html! {
+                        <ActivitySubmission
+                            lobby={session.lobby.clone()}
+                            active_run={session.active_run.clone()}
+                            is_host={session.is_host}
+                            participant_id={session.get_local_participant_id()}
+                        />
+                    },
+                    _ => render_lobby_view(
+                        &session,
+                        &participants,
+                        &host_actions,
+                        props.show_ready_check.then_some(&ready_check),
+                        props.countdown_secs,
+                        &i18n,
+                    ),
+                }}
+            </main>
+
+            {match ready_check.countdown_ends_at_ms {
+                Some(ends_at_ms) if session.active_run.is_none() => {
+                    // Only the host may start the activity once it ends
+                    let on_finished = if session.is_host {
+                        host_actions.start_next.clone()
+                    } else {
+                        Callback::default()
+                    };
+                    let clock_offset_ms = session.clock_offset_ms;
+                    html! { <Countdown {ends_at_ms} {clock_offset_ms} {on_finished} /> }
+                }
+                _ => html! {},
+            }}
+
+            {match session.lobby.as_ref() {
+                Some(lobby) if props.show_chat => html! {
+                    <ChatPanel
+                        lobby={lobby.clone()}
+                        local_participant_id={session.get_local_participant_id()}
+                        unread={chat.unread}
+                        on_send={chat.send.clone()}
+                        on_read={chat.mark_read.clone()}
+                        pending={chat.pending.clone()}
+                        rolled_back={chat.rolled_back.clone()}
                     />
                 },
+                _ => html! {},
+            }}
+
+            {if props.show_diagnostics {
+                html! { <DiagnosticsPanel /> }
+            } else {
+                html! {}
+            }}
+
+            {if props.show_dev_tools {
+                html! { <SessionDevTools /> }
+            } else {
+                html! {}
             }}
         </div>
     }
 }
 
 fn render_lobby_view(
-    lobby: &Option<konnekt_session_core::Lobby>,
-    active_run: &Option<crate::hooks::ActiveRunSnapshot>,
-    is_host: bool,
-    peer_count: usize,
-    runtime_error: Option<String>,
-    local_participant_id: Option<uuid::Uuid>,
-    on_toggle_participation: Callback<MouseEvent>,
+    session: &SessionContext,
+    participants: &ParticipantsState,
+    host_actions: &HostActions,
+    ready_check: Option<&ReadyCheckState>,
+    countdown_secs: u32,
+    i18n: &I18n,
 ) -> Html {
-    if let Some(lobby) = lobby {
+    let is_host = session.is_host;
+    let active_run = &session.active_run;
+    let on_toggle_participation = {
+        let toggle_participation = participants.toggle_participation.clone();
+        Callback::from(move |_: MouseEvent| toggle_participation.emit(()))
+    };
+    let spectating = participants.me.as_ref().is_some_and(|me| !me.active);
+
+    if let Some(lobby) = &session.lobby {
         let has_planned_activities = !lobby.activity_queue().is_empty();
 
         html! {
@@ -150,16 +212,43 @@ fn render_lobby_view(
                 <div class="konnekt-session-screen__column">
                     <ParticipantList
                         lobby={lobby.clone()}
-                        local_participant_id={local_participant_id}
+                        local_participant_id={session.get_local_participant_id()}
+                        presence={session.presence.clone()}
+                        pending_commands={session.pending_commands.clone()}
                     />
 
                     <div class="konnekt-session-screen__participation">
-                        <button
-                            class="konnekt-btn konnekt-btn--secondary"
-                            onclick={on_toggle_participation}
-                        >
-                            {"Toggle Active/Spectating"}
-                        </button>
+                        {if spectating {
+                            html! {
+                                <>
+                                    <span class="konnekt-session-screen__spectating" role="status">
+                                        {i18n.t("spectator.title")}
+                                    </span>
+                                    <button
+                                        class="konnekt-btn konnekt-btn--primary konnekt-btn--large"
+                                        onclick={on_toggle_participation}
+                                    >
+                                        {i18n.t("participation.join")}
+                                    </button>
+                                </>
+                            }
+                        } else {
+                            html! {
+                                <button
+                                    class="konnekt-btn konnekt-btn--secondary"
+                                    onclick={on_toggle_participation}
+                                >
+                                    {i18n.t("participation.spectate")}
+                                </button>
+                            }
+                        }}
+                        <details class="konnekt-session-screen__avatar">
+                            <summary>{i18n.t("avatar.title")}</summary>
+                            <AvatarPicker
+                                current={participants.me.as_ref().and_then(|me| me.avatar.clone())}
+                                on_change={participants.set_avatar.clone()}
+                            />
+                        </details>
                     </div>
 
                     {if is_host {
@@ -172,7 +261,36 @@ fn render_lobby_view(
                 </div>
 
                 <div class="konnekt-session-screen__column">
-                    <ActivityList lobby={lobby.clone()} active_run={active_run.clone()} />
+                    <ActivityList
+                        lobby={lobby.clone()}
+                        active_run={active_run.clone()}
+                        pending_commands={session.pending_commands.clone()}
+                    />
+
+                    {if host_actions.can_moderate && lobby.activity_queue().len() > 1 && active_run.is_none() {
+                        html! {
+                            <ActivityQueueEditor
+                                queue={lobby.activity_queue().to_vec()}
+                                on_move={host_actions.move_activity.clone()}
+                            />
+                        }
+                    } else {
+                        html! {}
+                    }}
+
+                    {match ready_check {
+                        Some(ready_check) if has_planned_activities && active_run.is_none() => html! {
+                            <ReadyCheck
+                                lobby={lobby.clone()}
+                                local_participant_id={session.get_local_participant_id()}
+                                can_start={ready_check.can_start_countdown}
+                                {countdown_secs}
+                                on_set_ready={ready_check.set_ready.clone()}
+                                on_start_countdown={ready_check.start_countdown.clone()}
+                            />
+                        },
+                        _ => html! {},
+                    }}
 
                     {if !is_host && !has_planned_activities && active_run.is_none() {
                         html! {
@@ -183,32 +301,46 @@ fn render_lobby_view(
                     } else {
                         html! {}
                     }}
+
+                    {if session.results.is_empty() {
+                        html! {}
+                    } else {
+                        html! {
+                            <section class="konnekt-session-screen__results">
+                                <h3>{i18n.t("results_chart.title")}</h3>
+                                <ResultsChart results={session.results.clone()} />
+                            </section>
+                        }
+                    }}
+
+                    {if is_host && !session.metrics.is_empty() {
+                        html! {
+                            <section class="konnekt-session-screen__results">
+                                <h3>{i18n.t("analytics.title")}</h3>
+                                <SessionAnalytics
+                                    metrics={session.metrics.clone()}
+                                    show_distribution={false}
+                                />
+                            </section>
+                        }
+                    } else {
+                        html! {}
+                    }}
                 </div>
             </div>
         }
     } else {
-        if let Some(error) = runtime_error {
+        if let Some(error) = session.runtime_error.clone() {
             return html! {
-                <div class="konnekt-session-screen__loading">
-                    <p>{"Connection failed."}</p>
+                <div class="konnekt-session-screen__loading" role="alert">
+                    <p>{i18n.t("error.connection_failed")}</p>
                     <p>{error}</p>
                 </div>
             };
         }
 
         html! {
-            <div class="konnekt-session-screen__loading">
-                <p>
-                    {if is_host {
-                        "Creating lobby and waiting for peers..."
-                    } else if peer_count == 0 {
-                        "Connecting to host..."
-                    } else {
-                        "Syncing lobby from host..."
-                    }}
-                </p>
-                <div class="konnekt-spinner"></div>
-            </div>
+            <SessionLoading status={session.status()} {is_host} />
         }
     }
 }
@@ -7,7 +7,9 @@ use yew_preview::create_component_group;
 use yew_preview::prelude::*;
 
 use crate::components::{
-    ActivityList, ParticipantList, ResultsView, SessionInfo, SubmissionStatus,
+    ActivityList, ActivityQueueEditor, Avatar, AvatarPicker, ChatPanel, Countdown, JoinLink,
+    ParticipantList, ReadyCheck, ResultsChart, ResultsView, SessionAnalytics, SessionErrorView,
+    SessionInfo, SessionLoading, Skeleton, SpectatorView, SubmissionStatus, ToastStack,
 };
 
 // ── Fixture helpers ──────────────────────────────────────────────────────────
@@ -27,12 +29,33 @@ fn _make_lobby() -> Lobby {
 /// Collect all component previews into organized groups
 pub fn preview_groups() -> ComponentList {
     vec![
-        create_component_group!("Session", SessionInfo::preview()),
-        create_component_group!("Lobby", ParticipantList::preview(), ActivityList::preview(),),
+        create_component_group!(
+            "Session",
+            SessionInfo::preview(),
+            JoinLink::preview(),
+            ToastStack::preview(),
+            SessionErrorView::preview(),
+            SessionLoading::preview(),
+            Skeleton::preview(),
+        ),
+        create_component_group!(
+            "Lobby",
+            ParticipantList::preview(),
+            Avatar::preview(),
+            AvatarPicker::preview(),
+            ActivityList::preview(),
+            ActivityQueueEditor::preview(),
+            ChatPanel::preview(),
+            ReadyCheck::preview(),
+            Countdown::preview(),
+        ),
         create_component_group!(
             "Activity",
             ResultsView::preview(),
+            ResultsChart::preview(),
+            SessionAnalytics::preview(),
             SubmissionStatus::preview(),
+            SpectatorView::preview(),
         ),
     ]
 }
@@ -0,0 +1,52 @@
+use yew::prelude::*;
+
+pub use konnekt_session_headless::{Catalog, I18n};
+
+#[derive(Properties, PartialEq)]
+pub struct I18nProviderProps {
+    /// Language tag such as `"de"` or `"en-GB"`; the browser language if unset
+    #[prop_or_default]
+    pub locale: Option<AttrValue>,
+    /// Catalogs adding locales or overriding built-in texts
+    #[prop_or_default]
+    pub catalogs: Vec<Catalog>,
+    pub children: Children,
+}
+
+/// Translates the texts of all components inside it (see `use_i18n`)
+///
+/// ```rust,ignore
+/// let catalogs = vec![Catalog::new("fr").with_message("lobby.title", "Salon")];
+///
+/// html! {
+///     <I18nProvider locale="fr" {catalogs}>
+///         <LobbyView />
+///     </I18nProvider>
+/// }
+/// ```
+#[function_component(I18nProvider)]
+pub fn i18n_provider(props: &I18nProviderProps) -> Html {
+    let i18n = use_memo(
+        (props.locale.clone(), props.catalogs.clone()),
+        |(locale, catalogs)| {
+            let locale = locale
+                .clone()
+                .or_else(browser_locale)
+                .unwrap_or_else(|| "en".into());
+            I18n::new(locale.to_string(), catalogs)
+        },
+    );
+
+    html! {
+        <ContextProvider<I18n> context={(*i18n).clone()}>
+            {props.children.clone()}
+        </ContextProvider<I18n>>
+    }
+}
+
+fn browser_locale() -> Option<AttrValue> {
+    web_sys::window()?
+        .navigator()
+        .language()
+        .map(AttrValue::from)
+}
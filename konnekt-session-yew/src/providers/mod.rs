@@ -1,5 +1,14 @@
 //! Context providers for session state
 
+mod i18n_provider;
+mod notification_provider;
 mod session_provider;
+mod theme_provider;
 
+pub use i18n_provider::{Catalog, I18n, I18nProvider, I18nProviderProps};
+pub(crate) use notification_provider::SessionNotifications;
+pub use notification_provider::{
+    Notification, NotificationKind, NotificationProvider, NotificationProviderProps, Notifications,
+};
 pub use session_provider::{SessionProvider, SessionProviderProps};
+pub use theme_provider::{Theme, ThemeProvider, ThemeProviderProps};
@@ -0,0 +1,367 @@
+use std::rc::Rc;
+
+use gloo_timers::callback::Timeout;
+use uuid::Uuid;
+use yew::prelude::*;
+
+use crate::components::ToastStack;
+use crate::hooks::{SessionContext, use_i18n};
+use crate::providers::I18n;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl NotificationKind {
+    /// CSS modifier of the toast
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NotificationKind::Info => "info",
+            NotificationKind::Success => "success",
+            NotificationKind::Warning => "warning",
+            NotificationKind::Error => "error",
+        }
+    }
+}
+
+/// A toast
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub id: u32,
+    pub kind: NotificationKind,
+    pub text: String,
+}
+
+/// Toasts on screen and callbacks to add or remove them, provided by
+/// [`NotificationProvider`]
+///
+/// Outside of a provider nothing is shown and `notify` does nothing.
+#[derive(Clone, PartialEq, Default)]
+pub struct Notifications {
+    /// Oldest first
+    pub items: Vec<Notification>,
+    pub notify: Callback<(NotificationKind, String)>,
+    pub dismiss: Callback<u32>,
+    pub clear: Callback<()>,
+}
+
+impl Notifications {
+    pub fn info(&self, text: impl Into<String>) {
+        self.notify.emit((NotificationKind::Info, text.into()));
+    }
+
+    pub fn success(&self, text: impl Into<String>) {
+        self.notify.emit((NotificationKind::Success, text.into()));
+    }
+
+    pub fn warning(&self, text: impl Into<String>) {
+        self.notify.emit((NotificationKind::Warning, text.into()));
+    }
+
+    pub fn error(&self, text: impl Into<String>) {
+        self.notify.emit((NotificationKind::Error, text.into()));
+    }
+}
+
+enum NotificationAction {
+    /// Show a toast, dropping the oldest beyond the limit
+    Push(Notification, usize),
+    Dismiss(u32),
+    Clear,
+}
+
+#[derive(Default, PartialEq)]
+struct NotificationList(Vec<Notification>);
+
+impl Reducible for NotificationList {
+    type Action = NotificationAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut items = self.0.clone();
+        match action {
+            NotificationAction::Push(notification, max_visible) => {
+                items.push(notification);
+                let excess = items.len().saturating_sub(max_visible.max(1));
+                items.drain(..excess);
+            }
+            NotificationAction::Dismiss(id) => items.retain(|n| n.id != id),
+            NotificationAction::Clear => items.clear(),
+        }
+        Rc::new(Self(items))
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct NotificationProviderProps {
+    /// Toasts disappear after this long; `0` keeps them until dismissed
+    #[prop_or(5_000)]
+    pub timeout_ms: u32,
+    /// Older toasts are dropped beyond this many
+    #[prop_or(5)]
+    pub max_visible: usize,
+    pub children: Children,
+}
+
+/// Shows toasts stacked in a corner (see `use_notifications`)
+///
+/// A `SessionProvider` inside it adds toasts for joins, leaves, being
+/// removed, activities starting and ending and connection trouble.
+///
+/// ```rust,ignore
+/// html! {
+///     <NotificationProvider timeout_ms={3_000}>
+///         <SessionProvider signalling_server="wss://match.konnektoren.help">
+///             <SessionScreen {on_leave} />
+///         </SessionProvider>
+///     </NotificationProvider>
+/// }
+/// ```
+#[function_component(NotificationProvider)]
+pub fn notification_provider(props: &NotificationProviderProps) -> Html {
+    let list = use_reducer(NotificationList::default);
+    let next_id = use_mut_ref(|| 0u32);
+
+    let notify = {
+        let dispatcher = list.dispatcher();
+        let timeout_ms = props.timeout_ms;
+        let max_visible = props.max_visible;
+        Callback::from(move |(kind, text): (NotificationKind, String)| {
+            let id = {
+                let mut next_id = next_id.borrow_mut();
+                *next_id = next_id.wrapping_add(1);
+                *next_id
+            };
+            dispatcher.dispatch(NotificationAction::Push(
+                Notification { id, kind, text },
+                max_visible,
+            ));
+            if timeout_ms > 0 {
+                let dispatcher = dispatcher.clone();
+                Timeout::new(timeout_ms, move || {
+                    dispatcher.dispatch(NotificationAction::Dismiss(id))
+                })
+                .forget();
+            }
+        })
+    };
+
+    let dismiss = {
+        let dispatcher = list.dispatcher();
+        Callback::from(move |id| dispatcher.dispatch(NotificationAction::Dismiss(id)))
+    };
+
+    let clear = {
+        let dispatcher = list.dispatcher();
+        Callback::from(move |_| dispatcher.dispatch(NotificationAction::Clear))
+    };
+
+    let context = Notifications {
+        items: list.0.clone(),
+        notify,
+        dismiss: dismiss.clone(),
+        clear,
+    };
+
+    html! {
+        <ContextProvider<Notifications> context={context.clone()}>
+            {props.children.clone()}
+            <ToastStack notifications={context.items} on_dismiss={dismiss} />
+        </ContextProvider<Notifications>>
+    }
+}
+
+/// What the built-in toasts compare between renders
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct SessionDigest {
+    /// `None` until the lobby is synced, so the first sync isn't a flood of
+    /// joins
+    participants: Option<Vec<(Uuid, String)>>,
+    local_id: Option<Uuid>,
+    run: Option<(Uuid, String)>,
+    reconnecting: bool,
+    error: Option<String>,
+}
+
+impl SessionDigest {
+    pub(crate) fn of(session: &SessionContext) -> Self {
+        Self {
+            participants: session.lobby.as_ref().map(|lobby| {
+                lobby
+                    .participants()
+                    .values()
+                    .map(|p| (p.id(), p.name().to_string()))
+                    .collect()
+            }),
+            local_id: session.get_local_participant_id(),
+            run: session
+                .active_run
+                .as_ref()
+                .map(|run| (run.run_id, run.name.clone())),
+            reconnecting: session.reconnecting,
+            error: session.runtime_error.clone(),
+        }
+    }
+}
+
+/// Toasts for what changed from `before` to `after`
+pub(crate) fn session_toasts(
+    before: &SessionDigest,
+    after: &SessionDigest,
+    i18n: &I18n,
+) -> Vec<(NotificationKind, String)> {
+    let mut toasts = Vec::new();
+
+    if let (Some(old), Some(new)) = (&before.participants, &after.participants) {
+        for (id, name) in new {
+            if !old.iter().any(|(old_id, _)| old_id == id) && Some(*id) != after.local_id {
+                toasts.push((
+                    NotificationKind::Info,
+                    i18n.t_with("toast.joined", &[("name", name)]),
+                ));
+            }
+        }
+        for (id, name) in old {
+            if new.iter().any(|(new_id, _)| new_id == id) {
+                continue;
+            }
+            if Some(*id) == before.local_id {
+                toasts.push((NotificationKind::Error, i18n.t("toast.removed")));
+            } else {
+                toasts.push((
+                    NotificationKind::Info,
+                    i18n.t_with("toast.left", &[("name", name)]),
+                ));
+            }
+        }
+    }
+
+    if before.run.as_ref().map(|(id, _)| id) != after.run.as_ref().map(|(id, _)| id) {
+        if let Some((_, name)) = &before.run {
+            toasts.push((
+                NotificationKind::Success,
+                i18n.t_with("toast.run_ended", &[("name", name)]),
+            ));
+        }
+        if let Some((_, name)) = &after.run {
+            toasts.push((
+                NotificationKind::Info,
+                i18n.t_with("toast.run_started", &[("name", name)]),
+            ));
+        }
+    }
+
+    match (before.reconnecting, after.reconnecting) {
+        (false, true) => toasts.push((NotificationKind::Warning, i18n.t("toast.reconnecting"))),
+        (true, false) => toasts.push((NotificationKind::Success, i18n.t("toast.reconnected"))),
+        _ => {}
+    }
+
+    if let Some(error) = &after.error
+        && before.error.as_ref() != Some(error)
+    {
+        toasts.push((
+            NotificationKind::Error,
+            i18n.t_with("toast.error", &[("error", error)]),
+        ));
+    }
+
+    toasts
+}
+
+#[derive(Properties, PartialEq)]
+pub(crate) struct SessionNotificationsProps {
+    /// Toast through a surrounding `NotificationProvider`
+    pub toasts: bool,
+    /// Announce to screen readers whatever isn't toasted
+    pub announce: bool,
+}
+
+/// Fires the built-in toasts of the session around it, or announces them in
+/// a visually hidden live region
+#[function_component(SessionNotifications)]
+pub(crate) fn session_notifications(props: &SessionNotificationsProps) -> Html {
+    let session = use_context::<SessionContext>();
+    let notifications = use_context::<Notifications>().filter(|_| props.toasts);
+    let i18n = use_i18n();
+    let previous = use_mut_ref(SessionDigest::default);
+    let announcement = use_state(String::new);
+    // The toast stack is a live region of its own
+    let announce = props.announce && notifications.is_none();
+
+    let digest = session.as_ref().map(SessionDigest::of).unwrap_or_default();
+    {
+        let announcement = announcement.setter();
+        use_effect_with(digest, move |digest| {
+            let before = previous.replace(digest.clone());
+            let toasts = session_toasts(&before, digest, &i18n);
+            if let Some(notifications) = notifications {
+                for toast in toasts {
+                    notifications.notify.emit(toast);
+                }
+            } else if announce && !toasts.is_empty() {
+                let texts: Vec<_> = toasts.into_iter().map(|(_, text)| text).collect();
+                announcement.set(texts.join(". "));
+            }
+            || ()
+        });
+    }
+
+    if announce {
+        html! {
+            <div class="konnekt-sr-only" role="status" aria-live="polite">
+                {(*announcement).clone()}
+            </div>
+        }
+    } else {
+        html! {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest(participants: &[(Uuid, &str)], run: Option<(Uuid, &str)>) -> SessionDigest {
+        SessionDigest {
+            participants: Some(
+                participants
+                    .iter()
+                    .map(|(id, name)| (*id, name.to_string()))
+                    .collect(),
+            ),
+            local_id: participants.first().map(|(id, _)| *id),
+            run: run.map(|(id, name)| (id, name.to_string())),
+            ..SessionDigest::default()
+        }
+    }
+
+    #[test]
+    fn test_session_toasts() {
+        let i18n = I18n::default();
+        let (me, bob, carol) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let run = Uuid::new_v4();
+
+        // The first sync announces nobody
+        let synced = digest(&[(me, "Alice"), (bob, "Bob")], None);
+        assert!(session_toasts(&SessionDigest::default(), &synced, &i18n).is_empty());
+
+        let changed = digest(&[(me, "Alice"), (carol, "Carol")], Some((run, "Quiz")));
+        let texts: Vec<_> = session_toasts(&synced, &changed, &i18n)
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect();
+        assert_eq!(texts, ["Carol joined", "Bob left", "Quiz started"]);
+
+        let removed = SessionDigest {
+            local_id: None,
+            ..digest(&[(carol, "Carol")], None)
+        };
+        let toasts = session_toasts(&changed, &removed, &i18n);
+        assert_eq!(toasts[0].0, NotificationKind::Error);
+        assert_eq!(toasts[1].1, "Quiz finished");
+    }
+}
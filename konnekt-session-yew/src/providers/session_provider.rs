@@ -1,11 +1,21 @@
-use crate::hooks::{ActiveRunSnapshot, SessionContext};
+use crate::components::ConnectionBanner;
+use crate::hooks::{
+    ActiveRunSnapshot, LoggedEvent, PendingCommand, SessionContext, apply_pending, reconcile,
+    use_i18n, use_identity,
+};
+use crate::providers::SessionNotifications;
 use bevy_ecs::prelude::{Resource, World};
 use bevy_ecs::schedule::Schedule;
 use bevy_ecs::system::ResMut;
 use futures::StreamExt;
-use konnekt_session_core::{DomainCommand, DomainEvent, DomainLoop, Lobby};
+use konnekt_session_core::{
+    DomainCommand, Lobby, ResultsAnalytics, SessionMetrics, SystemClock, Timestamp,
+};
+use konnekt_session_headless::{SessionRuntime, parse_session_reference};
 use konnekt_session_p2p::infrastructure::connection::MatchboxConnection;
-use konnekt_session_p2p::{IceServer, MatchboxSessionLoop, P2PTransport, SessionId};
+use konnekt_session_p2p::{
+    IceServer, LoopbackNetwork, NetworkConnection, PeerStats, Presence, QueueDepths, SessionId,
+};
 use std::rc::Rc;
 use uuid::Uuid;
 use yew::prelude::*;
@@ -19,17 +29,48 @@ pub struct SessionProviderProps {
     pub session_id: Option<AttrValue>,
     #[prop_or_default]
     pub name: Option<AttrValue>,
+    /// Show a banner while reconnecting, offline or on a slow connection
+    #[prop_or(true)]
+    pub show_connection_banner: bool,
+    /// Toast joins, leaves, activities and connection trouble (needs a
+    /// surrounding `NotificationProvider`)
+    #[prop_or(true)]
+    pub show_notifications: bool,
+    /// Announce the same events to screen readers when they aren't toasted
+    #[prop_or(true)]
+    pub announce_events: bool,
+    /// Join the session of a `/join/:session_id` route and push the host's
+    /// join route (needs the `router` feature and a surrounding router)
+    #[prop_or_default]
+    pub sync_route: bool,
+    /// Rejoin as the participant stored by `use_identity` after a reload and
+    /// default the name to the stored one
+    #[prop_or_default]
+    pub persist_identity: bool,
+    /// Record the latest domain events and queue depths for
+    /// `SessionDevTools`
+    #[prop_or_default]
+    pub dev_tools: bool,
+    /// Connect through this in-memory network instead of the signalling
+    /// server; providers sharing it see each other (tests, offline demos)
+    #[prop_or_default]
+    pub network: Option<LoopbackNetwork>,
     pub children: Children,
 }
 
 struct SessionState {
     command_queue: Vec<DomainCommand>,
+    presence: Option<Option<Presence>>,
+    /// Commands shown before the host confirmed them
+    pending: Vec<PendingCommand>,
 }
 
 impl SessionState {
     fn new() -> Self {
         Self {
             command_queue: Vec::new(),
+            presence: None,
+            pending: Vec::new(),
         }
     }
 
@@ -37,158 +78,131 @@ impl SessionState {
         self.command_queue.push(cmd);
     }
 
+    /// Show `cmd` on top of the authoritative `lobby` until the host
+    /// confirms it; `false` if its effect can't be shown up front
+    fn track(
+        &mut self,
+        cmd: &DomainCommand,
+        lobby: Option<Lobby>,
+        active_run: Option<ActiveRunSnapshot>,
+    ) -> bool {
+        let (Some(view), active_run) = apply_pending(&self.pending, lobby, active_run) else {
+            return false;
+        };
+        let now = Timestamp::now().as_millis();
+        match PendingCommand::track(cmd.clone(), &view, active_run.as_ref(), now) {
+            Some(pending) => {
+                self.pending.push(pending);
+                true
+            }
+            None => false,
+        }
+    }
+
     fn drain_commands(&mut self) -> Vec<DomainCommand> {
         std::mem::take(&mut self.command_queue)
     }
-}
-
-#[derive(Resource)]
-struct RuntimeState {
-    session_loop: MatchboxSessionLoop,
-    is_host: bool,
-    lobby_id: Uuid,
-    local_name: String,
-    join_retry_ticks: u16,
-    join_in_flight: bool,
-}
-
-#[derive(Resource, Default)]
-struct PendingCommands(Vec<DomainCommand>);
-
-#[derive(Resource, Clone, Default)]
-struct RuntimeSnapshot {
-    lobby: Option<Lobby>,
-    active_run: Option<ActiveRunSnapshot>,
-    peer_count: usize,
-    local_participant_id: Option<Uuid>,
-}
 
-fn drive_session_runtime(
-    mut state: ResMut<RuntimeState>,
-    mut pending_commands: ResMut<PendingCommands>,
-    mut snapshot: ResMut<RuntimeSnapshot>,
-) {
-    for cmd in pending_commands.0.drain(..) {
-        if let Err(e) = state.session_loop.submit_command(cmd) {
-            tracing::error!("❌ Command failed: {:?}", e);
-        }
+    fn set_presence(&mut self, presence: Option<Presence>) {
+        self.presence = Some(presence);
     }
 
-    let processed = state.session_loop.poll();
-    if processed > 0 {
-        tracing::debug!("SessionRuntime processed {} events", processed);
+    fn take_presence(&mut self) -> Option<Option<Presence>> {
+        self.presence.take()
     }
+}
 
-    if !state.is_host {
-        let has_connected_peers = !state.session_loop.connected_peers().is_empty();
-        let joined = state
-            .session_loop
-            .get_lobby()
-            .map(|lobby| {
-                if let Some(id) = snapshot.local_participant_id {
-                    lobby.participants().contains_key(&id)
-                } else {
-                    lobby
-                        .participants()
-                        .values()
-                        .any(|p| p.name() == state.local_name && !p.is_host())
-                }
-            })
-            .unwrap_or(false);
-
-        if joined {
-            state.join_in_flight = false;
-        }
-
-        if has_connected_peers && !joined && !state.join_in_flight {
-            state.join_retry_ticks = state.join_retry_ticks.saturating_add(1);
-            if state.join_retry_ticks >= 10 {
-                state.join_retry_ticks = 0;
-                let lobby_id = state.lobby_id;
-                let guest_name = state.local_name.clone();
-                if let Err(e) = state.session_loop.submit_command(DomainCommand::JoinLobby {
-                    lobby_id,
-                    guest_name: guest_name.clone(),
-                }) {
-                    tracing::warn!("⚠️ JoinLobby failed: {:?}", e);
-                } else {
-                    tracing::info!("🔁 Sent JoinLobby for '{}' (in-flight)", guest_name);
-                    state.join_in_flight = true;
-                }
-            }
-        } else if !has_connected_peers {
-            state.join_retry_ticks = 0;
-        }
+/// Matchbox in the browser, or a loopback network
+type Connection = Box<dyn NetworkConnection + Send + Sync>;
+
+/// Open the connection for room `sid`
+async fn connect(
+    network: Option<LoopbackNetwork>,
+    signalling_server: &str,
+    sid: &SessionId,
+    ice_servers: Vec<IceServer>,
+) -> Result<Connection, String> {
+    if let Some(network) = network {
+        return Ok(Box::new(network.connect()));
     }
-
-    let lobby = state.session_loop.get_lobby().cloned();
-    *snapshot = RuntimeSnapshot {
-        lobby: lobby.clone(),
-        active_run: state
-            .session_loop
-            .get_active_run()
-            .map(|run| ActiveRunSnapshot {
-                run_id: run.id(),
-                status: run.status(),
-                name: run.config().name.clone(),
-                config: run.config().config.clone(),
-                required_submitters: run.required_submitters().iter().copied().collect(),
-                results: run.results().values().cloned().collect(),
-            }),
-        peer_count: state.session_loop.connected_peers().len(),
-        local_participant_id: lobby.as_ref().and_then(|l| {
-            if state.is_host {
-                l.participants()
-                    .values()
-                    .find(|p| p.is_host())
-                    .map(|p| p.id())
-            } else {
-                l.participants()
-                    .values()
-                    .find(|p| p.name() == state.local_name && !p.is_host())
-                    .map(|p| p.id())
-            }
-        }),
-    };
+    let room_url = format!("{}/{}", signalling_server, sid.as_str());
+    MatchboxConnection::connect(&room_url, ice_servers)
+        .await
+        .map(|connection| Box::new(connection) as Connection)
+        .map_err(|e| format!("{:?}", e))
 }
 
-fn parse_session_reference(raw: &str) -> Option<SessionId> {
-    let trimmed = raw.trim();
-    if trimmed.is_empty() {
-        return None;
-    }
+/// The session runtime as a Bevy resource
+#[derive(Resource)]
+struct Runtime(SessionRuntime<Connection>);
 
-    if let Ok(id) = SessionId::parse(trimmed) {
-        return Some(id);
-    }
+/// Domain events kept in the context with `dev_tools`
+const EVENT_LOG_LIMIT: usize = 200;
 
-    let without_query = trimmed.split('?').next().unwrap_or(trimmed);
-    let candidate = without_query.trim_end_matches('/');
-    let tail = candidate.rsplit('/').next().unwrap_or(candidate);
-    SessionId::parse(tail).ok()
+fn drive_session_runtime(mut runtime: ResMut<Runtime>) {
+    runtime.0.tick();
 }
 
 #[function_component(SessionProvider)]
 pub fn session_provider(props: &SessionProviderProps) -> Html {
-    let starts_as_host = props.session_id.is_none();
+    let route_session_id = crate::router::use_route_session_id().filter(|_| props.sync_route);
+    let session_id = props
+        .session_id
+        .clone()
+        .or_else(|| route_session_id.map(AttrValue::from));
+
+    let starts_as_host = session_id.is_none();
     let lobby = use_state(|| None::<Lobby>);
     let active_run = use_state(|| None::<ActiveRunSnapshot>);
     let peer_count = use_state(|| 0usize);
+    let peer_stats = use_state(Vec::<PeerStats>::new);
+    let reconnecting = use_state(|| false);
+    let clock_offset_ms = use_state(|| 0i64);
+    let clock = use_state(SystemClock::shared);
     let local_participant_id = use_state(|| None::<Uuid>);
+    let local_peer_id = use_state(|| None::<String>);
+    let presence = use_state(Vec::<(Uuid, Presence)>::new);
+    let pending_commands = use_state(Vec::<PendingCommand>::new);
     let is_host = use_state(move || starts_as_host);
     let actual_session_id = use_state(|| SessionId::new());
     let local_participant_name = use_state(|| None::<String>);
     let runtime_error = use_state(|| None::<String>);
+    let protocol_error = use_state(|| None::<String>);
+    let kicked = use_state(|| false);
+    let results = use_state(ResultsAnalytics::default);
+    let metrics = use_state(SessionMetrics::default);
+    let event_log = use_state(|| Rc::new(Vec::<LoggedEvent>::new()));
+    let queue_depths = use_state(QueueDepths::default);
+    let i18n = use_i18n();
+    let identity = use_identity();
+
+    crate::router::use_push_join_route(
+        (props.sync_route && *is_host && lobby.is_some()).then(|| actual_session_id.to_string()),
+    );
 
     let session_state = use_mut_ref(SessionState::new);
 
     let send_command = {
         let session_state = session_state.clone();
+        let pending_commands = pending_commands.clone();
+        let lobby = (*lobby).clone();
+        let active_run = (*active_run).clone();
         Rc::new(move |cmd: DomainCommand| {
-            session_state.borrow_mut().enqueue_command(cmd);
+            let mut state = session_state.borrow_mut();
+            if state.track(&cmd, lobby.clone(), active_run.clone()) {
+                pending_commands.set(state.pending.clone());
+            }
+            state.enqueue_command(cmd);
         }) as Rc<dyn Fn(DomainCommand)>
     };
 
+    let set_presence = {
+        let session_state = session_state.clone();
+        Rc::new(move |presence: Option<Presence>| {
+            session_state.borrow_mut().set_presence(presence);
+        }) as Rc<dyn Fn(Option<Presence>)>
+    };
+
     {
         let signalling_server = props.signalling_server.to_string();
         let lobby_name = props
@@ -196,17 +210,42 @@ pub fn session_provider(props: &SessionProviderProps) -> Html {
             .clone()
             .map(|v| v.to_string())
             .unwrap_or_else(|| "Yew Lobby".to_string());
-        let session_id_prop = props.session_id.clone();
-        let name = props.name.clone().unwrap_or_else(|| "Guest".into());
+        let session_id_prop = session_id.clone();
+        let stored_name = identity
+            .identity
+            .name
+            .clone()
+            .filter(|_| props.persist_identity);
+        let name = props
+            .name
+            .clone()
+            .or_else(|| stored_name.map(AttrValue::from))
+            .unwrap_or_else(|| "Guest".into());
+        let stored_identity = props.persist_identity.then(|| identity.identity.clone());
         let is_host_clone = is_host.clone();
         let actual_session_id_clone = actual_session_id.clone();
         let lobby_clone = lobby.clone();
         let active_run_clone = active_run.clone();
         let peer_count_clone = peer_count.clone();
+        let peer_stats_clone = peer_stats.clone();
+        let reconnecting_clone = reconnecting.clone();
+        let clock_offset_ms_clone = clock_offset_ms.clone();
+        let clock_clone = clock.clone();
         let local_participant_id_clone = local_participant_id.clone();
+        let local_peer_id_clone = local_peer_id.clone();
+        let presence_clone = presence.clone();
+        let pending_commands_clone = pending_commands.clone();
         let local_participant_name_clone = local_participant_name.clone();
         let runtime_error_clone = runtime_error.clone();
+        let protocol_error_clone = protocol_error.clone();
+        let kicked_clone = kicked.clone();
+        let results_clone = results.clone();
+        let metrics_clone = metrics.clone();
         let session_state_clone = session_state.clone();
+        let event_log_clone = event_log.clone();
+        let queue_depths_clone = queue_depths.clone();
+        let dev_tools = props.dev_tools;
+        let network = props.network.clone();
 
         use_effect_with((), move |_| {
             tracing::info!("🚀 SessionProvider starting");
@@ -215,14 +254,12 @@ pub fn session_provider(props: &SessionProviderProps) -> Html {
                 let ice_servers = IceServer::default_stun_servers();
                 let local_name = name.to_string();
 
-                let (session_loop, sid) = if let Some(sid_str) = session_id_prop {
+                let (runtime, sid) = if let Some(sid_str) = session_id_prop {
                     let sid = match parse_session_reference(&sid_str) {
                         Some(parsed) => parsed,
                         None => {
-                            let msg = format!(
-                                "Invalid session reference '{}'. Expected UUID or room URL ending with UUID.",
-                                sid_str
-                            );
+                            let msg =
+                                i18n.t_with("error.invalid_session", &[("reference", &sid_str)]);
                             tracing::error!("❌ {}", msg);
                             runtime_error_clone.set(Some(msg));
                             return;
@@ -230,108 +267,99 @@ pub fn session_provider(props: &SessionProviderProps) -> Html {
                     };
                     tracing::info!("🔗 Joining session: {}", sid);
 
-                    let room_url = format!("{}/{}", signalling_server, sid.as_str());
-                    let connection = match MatchboxConnection::connect(&room_url, ice_servers).await
+                    let connection = match connect(network, &signalling_server, &sid, ice_servers)
+                        .await
                     {
                         Ok(connection) => connection,
                         Err(e) => {
-                            let msg = format!("Failed to join session {}: {:?}", sid, e);
+                            let msg = i18n
+                                .t_with("error.join_failed", &[("session", &sid), ("error", &e)]);
                             tracing::error!("❌ {}", msg);
                             runtime_error_clone.set(Some(msg));
                             return;
                         }
                     };
 
-                    let transport = P2PTransport::new_guest(connection, 100);
-                    let domain = DomainLoop::new(10, 100);
-
                     local_participant_name_clone.set(Some(local_name.clone()));
                     is_host_clone.set(false);
 
                     (
-                        MatchboxSessionLoop::new(domain, transport, false, sid.inner()),
+                        SessionRuntime::guest(connection, sid.inner(), local_name),
                         sid,
                     )
                 } else {
                     tracing::info!("👑 Creating host session as '{}'", name);
 
                     let sid = SessionId::new();
-                    let room_url = format!("{}/{}", signalling_server, sid.as_str());
-                    let connection = match MatchboxConnection::connect(&room_url, ice_servers).await
-                    {
-                        Ok(connection) => connection,
+                    let connection =
+                        match connect(network, &signalling_server, &sid, ice_servers).await {
+                            Ok(connection) => connection,
+                            Err(e) => {
+                                let msg = i18n.t_with("error.host_failed", &[("error", &e)]);
+                                tracing::error!("❌ {}", msg);
+                                runtime_error_clone.set(Some(msg));
+                                return;
+                            }
+                        };
+
+                    let runtime = match SessionRuntime::host(
+                        connection,
+                        sid.inner(),
+                        lobby_name,
+                        local_name.clone(),
+                    ) {
+                        Ok(runtime) => runtime,
                         Err(e) => {
-                            let msg = format!("Failed to create host session: {:?}", e);
+                            let msg = i18n.t_with("error.lobby_failed", &[("error", &e)]);
                             tracing::error!("❌ {}", msg);
                             runtime_error_clone.set(Some(msg));
                             return;
                         }
                     };
 
-                    let transport = P2PTransport::new_host(connection, 100);
-                    let mut domain = DomainLoop::new(10, 100);
-                    let create_cmd = DomainCommand::CreateLobby {
-                        lobby_id: Some(sid.inner()),
-                        lobby_name,
-                        host_name: local_name.clone(),
-                    };
-
-                    if let Err(e) = domain.submit(create_cmd) {
-                        let msg = format!("Failed to submit CreateLobby: {:?}", e);
-                        tracing::error!("❌ {}", msg);
-                        runtime_error_clone.set(Some(msg));
-                        return;
-                    }
-                    domain.poll();
-                    let events = domain.drain_events();
-                    if !events
-                        .iter()
-                        .any(|e| matches!(e, DomainEvent::LobbyCreated { .. }))
-                    {
-                        let msg = "Failed to create lobby in domain loop".to_string();
-                        tracing::error!("❌ {}", msg);
-                        runtime_error_clone.set(Some(msg));
-                        return;
-                    }
-
                     local_participant_name_clone.set(Some(local_name.clone()));
                     is_host_clone.set(true);
 
-                    (
-                        MatchboxSessionLoop::new(domain, transport, true, sid.inner()),
-                        sid,
-                    )
+                    (runtime, sid)
                 };
 
+                let resume_participant_id = stored_identity
+                    .filter(|_| !runtime.is_host())
+                    .and_then(|identity| identity.resume_token(&sid.as_str()));
+                if let Some(participant_id) = resume_participant_id {
+                    tracing::info!("♻️ Resuming as participant {}", participant_id);
+                }
+
                 actual_session_id_clone.set(sid);
                 runtime_error_clone.set(None);
+                clock_clone.set(runtime.clock());
 
                 // Run the session through a Bevy ECS application tick.
-                let runtime_is_host = session_loop.is_host();
-                let runtime_lobby_id = session_loop.lobby_id();
                 let mut world = World::new();
-                world.insert_resource(RuntimeState {
-                    session_loop,
-                    is_host: runtime_is_host,
-                    lobby_id: runtime_lobby_id,
-                    local_name,
-                    join_retry_ticks: 9,
-                    join_in_flight: false,
-                });
-                world.insert_resource(PendingCommands::default());
-                world.insert_resource(RuntimeSnapshot::default());
+                world.insert_resource(Runtime(
+                    runtime.with_resume_participant(resume_participant_id),
+                ));
 
                 let mut schedule = Schedule::default();
                 schedule.add_systems(drive_session_runtime);
 
                 let mut interval = gloo_timers::future::IntervalStream::new(100);
+                // The state handle only sees the log as of this effect
+                let mut event_log = Vec::<LoggedEvent>::new();
 
                 tracing::info!("🔄 Starting main polling loop");
 
                 while interval.next().await.is_some() {
-                    // 1. Drain Yew command queue into Bevy resources
-                    let commands = session_state_clone.borrow_mut().drain_commands();
-                    world.resource_mut::<PendingCommands>().0.extend(commands);
+                    // 1. Hand the Yew command queue to the runtime
+                    {
+                        let mut runtime = world.resource_mut::<Runtime>();
+                        for cmd in session_state_clone.borrow_mut().drain_commands() {
+                            runtime.0.submit(cmd);
+                        }
+                        if let Some(update) = session_state_clone.borrow_mut().take_presence() {
+                            runtime.0.set_presence(update);
+                        }
+                    }
 
                     // 2. Run one Bevy ECS tick (synchronous — blocks JS event loop)
                     schedule.run(&mut world);
@@ -342,19 +370,79 @@ pub fn session_provider(props: &SessionProviderProps) -> Html {
                     gloo_timers::future::TimeoutFuture::new(5).await;
 
                     // 3. Publish snapshot to Yew state — only set when changed to avoid render spam
-                    let snapshot = world.resource::<RuntimeSnapshot>().clone();
+                    let snapshot = world.resource::<Runtime>().0.snapshot().clone();
                     if *lobby_clone != snapshot.lobby {
-                        lobby_clone.set(snapshot.lobby);
+                        lobby_clone.set(snapshot.lobby.clone());
                     }
                     if *active_run_clone != snapshot.active_run {
-                        active_run_clone.set(snapshot.active_run);
+                        active_run_clone.set(snapshot.active_run.clone());
                     }
                     if *peer_count_clone != snapshot.peer_count {
                         peer_count_clone.set(snapshot.peer_count);
                     }
+                    if *peer_stats_clone != snapshot.peer_stats {
+                        peer_stats_clone.set(snapshot.peer_stats);
+                    }
+                    if *reconnecting_clone != snapshot.reconnecting {
+                        reconnecting_clone.set(snapshot.reconnecting);
+                    }
+                    if *clock_offset_ms_clone != snapshot.clock_offset_ms {
+                        clock_offset_ms_clone.set(snapshot.clock_offset_ms);
+                    }
                     if *local_participant_id_clone != snapshot.local_participant_id {
                         local_participant_id_clone.set(snapshot.local_participant_id);
                     }
+                    if *local_peer_id_clone != snapshot.local_peer_id {
+                        local_peer_id_clone.set(snapshot.local_peer_id);
+                    }
+                    if *presence_clone != snapshot.presence {
+                        presence_clone.set(snapshot.presence);
+                    }
+                    if *protocol_error_clone != snapshot.protocol_error {
+                        protocol_error_clone.set(snapshot.protocol_error);
+                    }
+                    if *kicked_clone != snapshot.kicked {
+                        kicked_clone.set(snapshot.kicked);
+                    }
+                    if let Some(results) = snapshot.results {
+                        results_clone.set(results);
+                    }
+                    if let Some(metrics) = snapshot.metrics {
+                        metrics_clone.set(metrics);
+                    }
+                    if dev_tools {
+                        if !snapshot.events.is_empty() {
+                            let at_ms = Timestamp::now().as_millis();
+                            event_log.extend(
+                                snapshot
+                                    .events
+                                    .into_iter()
+                                    .map(|event| LoggedEvent { at_ms, event }),
+                            );
+                            let overflow = event_log.len().saturating_sub(EVENT_LOG_LIMIT);
+                            event_log.drain(..overflow);
+                            event_log_clone.set(Rc::new(event_log.clone()));
+                        }
+                        if *queue_depths_clone != snapshot.queue_depths {
+                            queue_depths_clone.set(snapshot.queue_depths);
+                        }
+                    }
+
+                    // 4. Settle optimistic commands against what the host applied
+                    let settled = {
+                        let mut state = session_state_clone.borrow_mut();
+                        reconcile(
+                            &mut state.pending,
+                            snapshot.lobby.as_ref(),
+                            snapshot.active_run.as_ref(),
+                            &snapshot.failed_commands,
+                            Timestamp::now().as_millis(),
+                        )
+                        .then(|| state.pending.clone())
+                    };
+                    if let Some(pending) = settled {
+                        pending_commands_clone.set(pending);
+                    }
                 }
 
                 tracing::warn!("🛑 Polling loop ended");
@@ -366,21 +454,73 @@ pub fn session_provider(props: &SessionProviderProps) -> Html {
         });
     }
 
+    {
+        let remember = identity.remember.clone();
+        let persist_identity = props.persist_identity;
+        use_effect_with(
+            (
+                (*actual_session_id).clone(),
+                *local_participant_id,
+                *is_host,
+            ),
+            move |(session_id, participant_id, is_host)| {
+                if let (true, false, Some(participant_id)) =
+                    (persist_identity, *is_host, *participant_id)
+                {
+                    remember.emit((session_id.to_string(), participant_id));
+                }
+                || ()
+            },
+        );
+    }
+
+    // What the user sees: the host's state plus our unconfirmed commands
+    let (lobby_view, active_run_view) =
+        apply_pending(&pending_commands, (*lobby).clone(), (*active_run).clone());
+
     let context = SessionContext {
         session_id: (*actual_session_id).clone(),
-        lobby: (*lobby).clone(),
+        lobby: lobby_view,
         peer_count: *peer_count,
+        peer_stats: (*peer_stats).clone(),
+        reconnecting: *reconnecting,
+        clock_offset_ms: *clock_offset_ms,
+        clock: (*clock).clone(),
         is_host: *is_host,
-        active_run: (*active_run).clone(),
+        active_run: active_run_view,
         local_participant_id: *local_participant_id,
-        local_peer_id: None,
+        local_peer_id: (*local_peer_id).clone(),
         send_command,
+        pending_commands: (*pending_commands).clone(),
+        presence: (*presence).clone(),
+        set_presence,
         local_participant_name: (*local_participant_name).clone(),
         runtime_error: (*runtime_error).clone(),
+        protocol_error: (*protocol_error).clone(),
+        kicked: *kicked,
+        results: (*results).clone(),
+        metrics: (*metrics).clone(),
+        event_log: (*event_log).clone(),
+        queue_depths: *queue_depths,
     };
 
     html! {
         <ContextProvider<SessionContext> {context}>
+            {if props.show_connection_banner {
+                html! { <ConnectionBanner /> }
+            } else {
+                html! {}
+            }}
+            {if props.show_notifications || props.announce_events {
+                html! {
+                    <SessionNotifications
+                        toasts={props.show_notifications}
+                        announce={props.announce_events}
+                    />
+                }
+            } else {
+                html! {}
+            }}
             {props.children.clone()}
         </ContextProvider<SessionContext>>
     }
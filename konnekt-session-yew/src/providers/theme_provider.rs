@@ -0,0 +1,173 @@
+use yew::prelude::*;
+
+/// Colors, spacing and corners of the components
+///
+/// Applied as the `--konnekt-*` CSS variables of `styles.css`; shades such as
+/// hover and background tints are derived from these in CSS.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub dark: bool,
+    /// Turn off animations and transitions even if the system doesn't ask
+    /// for it (`prefers-reduced-motion` is always honored)
+    pub reduced_motion: bool,
+    pub primary: AttrValue,
+    /// Text on primary (and other filled) buttons
+    pub on_primary: AttrValue,
+    pub success: AttrValue,
+    pub warning: AttrValue,
+    pub danger: AttrValue,
+    /// Page behind the cards
+    pub background: AttrValue,
+    /// Cards and panels
+    pub surface: AttrValue,
+    pub text: AttrValue,
+    pub text_muted: AttrValue,
+    pub border: AttrValue,
+    /// Base unit for paddings, margins and gaps
+    pub spacing: AttrValue,
+    pub radius: AttrValue,
+    pub shadow: AttrValue,
+}
+
+impl Theme {
+    pub fn light() -> Self {
+        Self {
+            dark: false,
+            reduced_motion: false,
+            primary: "#2196f3".into(),
+            on_primary: "white".into(),
+            success: "#4caf50".into(),
+            warning: "#ff9800".into(),
+            danger: "#f44336".into(),
+            background: "#f5f5f5".into(),
+            surface: "white".into(),
+            text: "#333".into(),
+            text_muted: "#666".into(),
+            border: "#ddd".into(),
+            spacing: "1rem".into(),
+            radius: "8px".into(),
+            shadow: "0 2px 4px rgba(0, 0, 0, 0.1)".into(),
+        }
+    }
+
+    pub fn dark() -> Self {
+        Self {
+            dark: true,
+            primary: "#64b5f6".into(),
+            on_primary: "#0b1620".into(),
+            success: "#81c784".into(),
+            warning: "#ffb74d".into(),
+            danger: "#e57373".into(),
+            background: "#121212".into(),
+            surface: "#1e1e1e".into(),
+            text: "#e6e6e6".into(),
+            text_muted: "#a0a0a0".into(),
+            border: "#3a3a3a".into(),
+            shadow: "0 2px 4px rgba(0, 0, 0, 0.5)".into(),
+            ..Self::light()
+        }
+    }
+
+    pub fn with_primary(mut self, color: impl Into<AttrValue>) -> Self {
+        self.primary = color.into();
+        self
+    }
+
+    pub fn with_spacing(mut self, spacing: impl Into<AttrValue>) -> Self {
+        self.spacing = spacing.into();
+        self
+    }
+
+    pub fn with_radius(mut self, radius: impl Into<AttrValue>) -> Self {
+        self.radius = radius.into();
+        self
+    }
+
+    pub fn with_reduced_motion(mut self, reduced_motion: bool) -> Self {
+        self.reduced_motion = reduced_motion;
+        self
+    }
+
+    /// Inline style declaring the theme's CSS variables
+    pub fn css_variables(&self) -> String {
+        [
+            ("color-primary", &self.primary),
+            ("color-on-primary", &self.on_primary),
+            ("color-success", &self.success),
+            ("color-warning", &self.warning),
+            ("color-danger", &self.danger),
+            ("color-background", &self.background),
+            ("color-surface", &self.surface),
+            ("color-text", &self.text),
+            ("color-text-muted", &self.text_muted),
+            ("color-border", &self.border),
+            ("spacing", &self.spacing),
+            ("radius", &self.radius),
+            ("shadow", &self.shadow),
+        ]
+        .iter()
+        .map(|(name, value)| format!("--konnekt-{}: {};", name, value))
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ThemeProviderProps {
+    #[prop_or_default]
+    pub theme: Theme,
+    #[prop_or_default]
+    pub classes: Classes,
+    pub children: Children,
+}
+
+/// Applies a [`Theme`] to all components inside it (see `use_theme`)
+///
+/// For changes a theme can't express, every component also takes `classes`
+/// and `style` props that end up on its root element.
+#[function_component(ThemeProvider)]
+pub fn theme_provider(props: &ThemeProviderProps) -> Html {
+    let theme = props.theme.clone();
+
+    html! {
+        <ContextProvider<Theme> context={theme.clone()}>
+            <div
+                class={classes!(
+                    "konnekt-theme",
+                    theme.dark.then_some("konnekt-theme--dark"),
+                    theme.reduced_motion.then_some("konnekt-theme--reduced-motion"),
+                    props.classes.clone(),
+                )}
+                style={theme.css_variables()}
+            >
+                {props.children.clone()}
+            </div>
+        </ContextProvider<Theme>>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_css_variables() {
+        let theme = Theme::light().with_primary("rebeccapurple");
+        let style = theme.css_variables();
+
+        assert!(style.contains("--konnekt-color-primary: rebeccapurple;"));
+        assert!(style.contains("--konnekt-spacing: 1rem;"));
+        assert_eq!(style.matches("--konnekt-").count(), 13);
+
+        let dark = Theme::dark();
+        assert!(dark.dark);
+        assert_eq!(dark.spacing, Theme::light().spacing);
+        assert_ne!(dark.surface, Theme::light().surface);
+    }
+}
@@ -0,0 +1,62 @@
+//! Optional `yew-router` integration (`router` feature)
+//!
+//! With `sync_route` set, `SessionProvider` joins the session of a
+//! `/join/:session_id` route and moves the host to its own join route once
+//! the lobby exists, so the address bar always holds a shareable link.
+//! Without the feature the hooks do nothing, so components can always call
+//! them (hooks may not sit behind a `#[cfg]` inside a component).
+
+use yew::prelude::*;
+#[cfg(feature = "router")]
+use yew_router::prelude::*;
+
+/// Routes the session components understand
+#[cfg(feature = "router")]
+#[derive(Debug, Clone, PartialEq, Routable)]
+pub enum SessionRoute {
+    #[at("/")]
+    Home,
+    #[at("/join/:session_id")]
+    Join { session_id: String },
+}
+
+/// Session ID of the current `/join/:session_id` route
+#[cfg(feature = "router")]
+#[hook]
+pub fn use_route_session_id() -> Option<String> {
+    match use_route::<SessionRoute>()? {
+        SessionRoute::Join { session_id } => Some(session_id),
+        SessionRoute::Home => None,
+    }
+}
+
+/// Session ID of the current route (always `None` without `router`)
+#[cfg(not(feature = "router"))]
+#[hook]
+pub fn use_route_session_id() -> Option<String> {
+    None
+}
+
+/// Push the join route of `session_id` unless it is already current
+#[cfg(feature = "router")]
+#[hook]
+pub fn use_push_join_route(session_id: Option<String>) {
+    let navigator = use_navigator();
+    let current = use_route_session_id();
+
+    use_effect_with(session_id, move |session_id| {
+        if let (Some(navigator), Some(session_id)) = (navigator, session_id) {
+            if current.as_ref() != Some(session_id) {
+                tracing::info!("🧭 Pushing join route for {}", session_id);
+                navigator.push(&SessionRoute::Join {
+                    session_id: session_id.clone(),
+                });
+            }
+        }
+    });
+}
+
+/// Push the join route of `session_id` (a no-op without `router`)
+#[cfg(not(feature = "router"))]
+#[hook]
+pub fn use_push_join_route(_session_id: Option<String>) {}
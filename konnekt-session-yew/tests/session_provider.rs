@@ -0,0 +1,238 @@
+//! Browser tests mounting `SessionProvider` on a loopback network
+//!
+//! Run with `just test` (`wasm-pack test --headless --firefox`).
+
+use gloo_timers::future::TimeoutFuture;
+use konnekt_session_core::ActivityConfig;
+use konnekt_session_p2p::LoopbackNetwork;
+use konnekt_session_yew::hooks::SessionContext;
+use konnekt_session_yew::{
+    ActivityAnswer, CurrentActivity, HostActions, LobbyView, SessionProvider, use_activity,
+    use_host_actions, use_session,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen_test::*;
+use web_sys::Element;
+use yew::prelude::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// How long a flow may take before the test fails
+const TIMEOUT_MS: u32 = 10_000;
+
+/// What a peer's hooks returned on its latest render
+#[derive(Clone)]
+struct Rendered {
+    session: SessionContext,
+    actions: HostActions,
+    activity: Option<CurrentActivity>,
+}
+
+#[derive(Properties, PartialEq)]
+struct ProbeProps {
+    on_render: Callback<Rendered>,
+}
+
+#[function_component(Probe)]
+fn probe(props: &ProbeProps) -> Html {
+    props.on_render.emit(Rendered {
+        session: use_session(),
+        actions: use_host_actions(),
+        activity: use_activity(),
+    });
+    html! {}
+}
+
+#[derive(Properties, PartialEq)]
+struct PeerProps {
+    network: LoopbackNetwork,
+    name: AttrValue,
+    #[prop_or_default]
+    session_id: Option<AttrValue>,
+    on_render: Callback<Rendered>,
+}
+
+#[function_component(Peer)]
+fn peer(props: &PeerProps) -> Html {
+    html! {
+        <SessionProvider
+            signalling_server="ws://unused.invalid"
+            name={props.name.clone()}
+            session_id={props.session_id.clone()}
+            network={Some(props.network.clone())}
+            show_notifications={false}
+            announce_events={false}
+        >
+            <LobbyView />
+            <Probe on_render={props.on_render.clone()} />
+        </SessionProvider>
+    }
+}
+
+/// A provider mounted into its own element of the test page
+struct MountedPeer {
+    root: Element,
+    rendered: Rc<RefCell<Option<Rendered>>>,
+    _app: yew::AppHandle<Peer>,
+}
+
+impl MountedPeer {
+    fn mount(network: &LoopbackNetwork, name: &str, session_id: Option<String>) -> Self {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let root = document.create_element("div").unwrap();
+        document.body().unwrap().append_child(&root).unwrap();
+
+        let rendered = Rc::new(RefCell::new(None));
+        let on_render = {
+            let rendered = rendered.clone();
+            Callback::from(move |latest| *rendered.borrow_mut() = Some(latest))
+        };
+        let props = PeerProps {
+            network: network.clone(),
+            name: AttrValue::from(name.to_string()),
+            session_id: session_id.map(AttrValue::from),
+            on_render,
+        };
+        let app = yew::Renderer::<Peer>::with_root_and_props(root.clone(), props).render();
+
+        Self {
+            root,
+            rendered,
+            _app: app,
+        }
+    }
+
+    fn latest(&self) -> Rendered {
+        self.rendered
+            .borrow()
+            .clone()
+            .expect("peer has not rendered yet")
+    }
+
+    fn participant_count(&self) -> usize {
+        self.rendered
+            .borrow()
+            .as_ref()
+            .and_then(|rendered| rendered.session.lobby.as_ref())
+            .map_or(0, |lobby| lobby.participants().len())
+    }
+
+    fn text(&self) -> String {
+        self.root.text_content().unwrap_or_default()
+    }
+}
+
+/// Yield to the browser until `condition` holds, failing after `TIMEOUT_MS`
+async fn wait_until(what: &str, condition: impl Fn() -> bool) {
+    for _ in 0..TIMEOUT_MS / 50 {
+        if condition() {
+            return;
+        }
+        TimeoutFuture::new(50).await;
+    }
+    panic!("timed out waiting until {}", what);
+}
+
+/// A host "Alice" and a guest "Bob" who joined her session
+async fn host_and_guest(network: &LoopbackNetwork) -> (MountedPeer, MountedPeer) {
+    let host = MountedPeer::mount(network, "Alice", None);
+    wait_until("the host created the lobby", || {
+        host.participant_count() == 1
+    })
+    .await;
+
+    let session_id = host.latest().session.session_id.to_string();
+    let guest = MountedPeer::mount(network, "Bob", Some(session_id));
+    wait_until("both peers see the guest", || {
+        host.participant_count() == 2 && guest.participant_count() == 2
+    })
+    .await;
+
+    (host, guest)
+}
+
+#[wasm_bindgen_test]
+async fn test_host_creates_lobby() {
+    let network = LoopbackNetwork::new();
+    let host = MountedPeer::mount(&network, "Alice", None);
+
+    wait_until("the host created the lobby", || {
+        host.participant_count() == 1
+    })
+    .await;
+
+    let session = host.latest().session;
+    assert!(session.is_host);
+    assert!(session.local_participant_id.is_some());
+    assert!(host.text().contains("Alice"));
+}
+
+#[wasm_bindgen_test]
+async fn test_guest_joins_host_session() {
+    let network = LoopbackNetwork::new();
+    let (host, guest) = host_and_guest(&network).await;
+
+    let guest_session = guest.latest().session;
+    assert!(!guest_session.is_host);
+    assert_eq!(guest_session.session_id, host.latest().session.session_id);
+    assert!(host.text().contains("Bob"));
+    assert!(guest.text().contains("Alice"));
+}
+
+#[wasm_bindgen_test]
+async fn test_guest_plays_activity_started_by_host() {
+    let network = LoopbackNetwork::new();
+    let (host, guest) = host_and_guest(&network).await;
+
+    host.latest()
+        .actions
+        .queue_activity
+        .emit(ActivityConfig::new(
+            "quiz".to_string(),
+            "Trivia Quiz".to_string(),
+            serde_json::json!({}),
+        ));
+    wait_until("the host can start the activity", || {
+        host.latest().actions.can_start
+    })
+    .await;
+    host.latest().actions.start_next.emit(());
+
+    wait_until("the guest sees the activity", || {
+        guest.latest().activity.is_some()
+    })
+    .await;
+    let activity = guest.latest().activity.unwrap();
+    assert_eq!(activity.name, "Trivia Quiz");
+    assert!(activity.can_submit);
+
+    activity.submit_result.emit(ActivityAnswer {
+        data: serde_json::json!({ "answer": 42 }),
+        score: Some(8),
+    });
+    wait_until("the host received the result", || {
+        host.latest()
+            .session
+            .active_run
+            .is_some_and(|run| run.results.iter().any(|result| result.score == Some(8)))
+    })
+    .await;
+    assert!(guest.latest().activity.unwrap().submitted);
+}
+
+#[wasm_bindgen_test]
+async fn test_guest_with_unknown_session_reference_sees_error() {
+    let network = LoopbackNetwork::new();
+    let guest = MountedPeer::mount(&network, "Bob", Some("not a session".to_string()));
+
+    wait_until("the guest reports the bad reference", || {
+        guest
+            .rendered
+            .borrow()
+            .as_ref()
+            .is_some_and(|rendered| rendered.session.runtime_error.is_some())
+    })
+    .await;
+    assert_eq!(network.peer_count(), 0);
+}